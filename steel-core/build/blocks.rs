@@ -31,59 +31,140 @@ fn generate_registrations<'a>(
 }
 
 pub fn build(blocks: &[BlockClass]) -> String {
+    let mut anvil_blocks = Vec::new();
     let mut barrel_blocks = Vec::new();
+    let mut brewing_stand_blocks = Vec::new();
+    let mut cartography_table_blocks = Vec::new();
+    let mut chest_blocks = Vec::new();
+    let mut concrete_powder_blocks = Vec::new();
     let mut crafting_table_blocks = Vec::new();
     let mut crop_blocks = Vec::new();
+    let mut detector_rail_blocks = Vec::new();
+    let mut enchanting_table_blocks = Vec::new();
     let mut end_portal_frame_blocks = Vec::new();
+    let mut falling_blocks = Vec::new();
     let mut farm_blocks = Vec::new();
     let mut fence_blocks = Vec::new();
+    let mut furnace_blocks = Vec::new();
+    let mut grindstone_blocks = Vec::new();
+    let mut hopper_blocks = Vec::new();
+    let mut loom_blocks = Vec::new();
     let mut rotated_pillar_blocks = Vec::new();
+    let mut sculk_sensor_blocks = Vec::new();
+    let mut calibrated_sculk_sensor_blocks = Vec::new();
+    let mut smithing_table_blocks = Vec::new();
     let mut standing_sign_blocks = Vec::new();
+    let mut stonecutter_blocks = Vec::new();
     let mut wall_sign_blocks = Vec::new();
     let mut ceiling_hanging_sign_blocks = Vec::new();
     let mut wall_hanging_sign_blocks = Vec::new();
+    let mut tnt_blocks = Vec::new();
 
     for block in blocks {
         let const_ident = to_const_ident(&block.name);
         match block.class.as_str() {
+            "AnvilBlock" => anvil_blocks.push(const_ident),
             "BarrelBlock" => barrel_blocks.push(const_ident),
+            "BrewingStandBlock" => brewing_stand_blocks.push(const_ident),
+            "CartographyTableBlock" => cartography_table_blocks.push(const_ident),
+            "ChestBlock" | "TrappedChestBlock" => chest_blocks.push(const_ident),
+            "ConcretePowderBlock" => concrete_powder_blocks.push(const_ident),
             "CraftingTableBlock" => crafting_table_blocks.push(const_ident),
             "CropBlock" => crop_blocks.push(const_ident),
+            "DetectorRailBlock" => detector_rail_blocks.push(const_ident),
+            "EnchantingTableBlock" => enchanting_table_blocks.push(const_ident),
             "EndPortalFrameBlock" => end_portal_frame_blocks.push(const_ident),
+            "SandBlock" | "ColoredFallingBlock" => falling_blocks.push(const_ident),
             "FarmBlock" => farm_blocks.push(const_ident),
             "FenceBlock" => fence_blocks.push(const_ident),
+            "FurnaceBlock" | "BlastFurnaceBlock" | "SmokerBlock" => {
+                furnace_blocks.push(const_ident);
+            }
+            "GrindstoneBlock" => grindstone_blocks.push(const_ident),
+            "HopperBlock" => hopper_blocks.push(const_ident),
+            "LoomBlock" => loom_blocks.push(const_ident),
             "RotatedPillarBlock" => rotated_pillar_blocks.push(const_ident),
+            "SculkSensorBlock" => sculk_sensor_blocks.push(const_ident),
+            "CalibratedSculkSensorBlock" => calibrated_sculk_sensor_blocks.push(const_ident),
+            "SmithingTableBlock" => smithing_table_blocks.push(const_ident),
             "StandingSignBlock" => standing_sign_blocks.push(const_ident),
+            "StonecutterBlock" => stonecutter_blocks.push(const_ident),
             "WallSignBlock" => wall_sign_blocks.push(const_ident),
             "CeilingHangingSignBlock" => ceiling_hanging_sign_blocks.push(const_ident),
             "WallHangingSignBlock" => wall_hanging_sign_blocks.push(const_ident),
+            "TntBlock" => tnt_blocks.push(const_ident),
             _ => {}
         }
     }
 
+    let anvil_type = Ident::new("AnvilBlock", Span::call_site());
     let barrel_type = Ident::new("BarrelBlock", Span::call_site());
+    let brewing_stand_type = Ident::new("BrewingStandBlock", Span::call_site());
+    let cartography_table_type = Ident::new("CartographyTableBlock", Span::call_site());
+    let chest_type = Ident::new("ChestBlock", Span::call_site());
+    let concrete_powder_type = Ident::new("ConcretePowderBlock", Span::call_site());
     let crafting_table_type = Ident::new("CraftingTableBlock", Span::call_site());
     let crop_type = Ident::new("CropBlock", Span::call_site());
+    let detector_rail_type = Ident::new("DetectorRailBlock", Span::call_site());
+    let enchanting_table_type = Ident::new("EnchantingTableBlock", Span::call_site());
     let end_portal_frame_type = Ident::new("EndPortalFrameBlock", Span::call_site());
+    let falling_type = Ident::new("FallingBlock", Span::call_site());
     let farmland_type = Ident::new("FarmlandBlock", Span::call_site());
     let fence_type = Ident::new("FenceBlock", Span::call_site());
+    let furnace_type = Ident::new("FurnaceBlock", Span::call_site());
+    let grindstone_type = Ident::new("GrindstoneBlock", Span::call_site());
+    let hopper_type = Ident::new("HopperBlock", Span::call_site());
+    let loom_type = Ident::new("LoomBlock", Span::call_site());
     let pillar_type = Ident::new("RotatedPillarBlock", Span::call_site());
+    let sculk_sensor_type = Ident::new("SculkSensorBlock", Span::call_site());
+    let calibrated_sculk_sensor_type = Ident::new("CalibratedSculkSensorBlock", Span::call_site());
+    let smithing_table_type = Ident::new("SmithingTableBlock", Span::call_site());
     let standing_sign_type = Ident::new("StandingSignBlock", Span::call_site());
+    let stonecutter_type = Ident::new("StonecutterBlock", Span::call_site());
     let wall_sign_type = Ident::new("WallSignBlock", Span::call_site());
     let ceiling_hanging_sign_type = Ident::new("CeilingHangingSignBlock", Span::call_site());
     let wall_hanging_sign_type = Ident::new("WallHangingSignBlock", Span::call_site());
+    let tnt_type = Ident::new("TntBlock", Span::call_site());
 
+    let anvil_registrations = generate_registrations(anvil_blocks.iter(), &anvil_type);
     let barrel_registrations = generate_registrations(barrel_blocks.iter(), &barrel_type);
+    let brewing_stand_registrations =
+        generate_registrations(brewing_stand_blocks.iter(), &brewing_stand_type);
+    let cartography_table_registrations =
+        generate_registrations(cartography_table_blocks.iter(), &cartography_table_type);
+    let chest_registrations = generate_registrations(chest_blocks.iter(), &chest_type);
+    let concrete_powder_registrations =
+        generate_registrations(concrete_powder_blocks.iter(), &concrete_powder_type);
     let crafting_table_registrations =
         generate_registrations(crafting_table_blocks.iter(), &crafting_table_type);
     let crop_registrations = generate_registrations(crop_blocks.iter(), &crop_type);
+    let detector_rail_registrations =
+        generate_registrations(detector_rail_blocks.iter(), &detector_rail_type);
+    let enchanting_table_registrations =
+        generate_registrations(enchanting_table_blocks.iter(), &enchanting_table_type);
     let end_portal_frame_registrations =
         generate_registrations(end_portal_frame_blocks.iter(), &end_portal_frame_type);
+    let falling_registrations = generate_registrations(falling_blocks.iter(), &falling_type);
     let farm_registrations = generate_registrations(farm_blocks.iter(), &farmland_type);
     let fence_registrations = generate_registrations(fence_blocks.iter(), &fence_type);
+    let furnace_registrations = generate_registrations(furnace_blocks.iter(), &furnace_type);
+    let grindstone_registrations =
+        generate_registrations(grindstone_blocks.iter(), &grindstone_type);
+    let hopper_registrations = generate_registrations(hopper_blocks.iter(), &hopper_type);
+    let loom_registrations = generate_registrations(loom_blocks.iter(), &loom_type);
     let pillar_registrations = generate_registrations(rotated_pillar_blocks.iter(), &pillar_type);
+    let sculk_sensor_registrations =
+        generate_registrations(sculk_sensor_blocks.iter(), &sculk_sensor_type);
+    let calibrated_sculk_sensor_registrations = generate_registrations(
+        calibrated_sculk_sensor_blocks.iter(),
+        &calibrated_sculk_sensor_type,
+    );
+    let smithing_table_registrations =
+        generate_registrations(smithing_table_blocks.iter(), &smithing_table_type);
     let standing_sign_registrations =
         generate_registrations(standing_sign_blocks.iter(), &standing_sign_type);
+    let stonecutter_registrations =
+        generate_registrations(stonecutter_blocks.iter(), &stonecutter_type);
     let wall_sign_registrations = generate_registrations(wall_sign_blocks.iter(), &wall_sign_type);
     let ceiling_hanging_sign_registrations = generate_registrations(
         ceiling_hanging_sign_blocks.iter(),
@@ -91,6 +172,7 @@ pub fn build(blocks: &[BlockClass]) -> String {
     );
     let wall_hanging_sign_registrations =
         generate_registrations(wall_hanging_sign_blocks.iter(), &wall_hanging_sign_type);
+    let tnt_registrations = generate_registrations(tnt_blocks.iter(), &tnt_type);
 
     let output = quote! {
         //! Generated block behavior assignments.
@@ -98,23 +180,41 @@ pub fn build(blocks: &[BlockClass]) -> String {
         use steel_registry::vanilla_blocks;
         use crate::behavior::BlockBehaviorRegistry;
         use crate::behavior::blocks::{
-            BarrelBlock, CraftingTableBlock, CropBlock, EndPortalFrameBlock, FarmlandBlock,
-            FenceBlock, RotatedPillarBlock, StandingSignBlock, WallSignBlock,
-            CeilingHangingSignBlock, WallHangingSignBlock,
+            AnvilBlock, BarrelBlock, BrewingStandBlock, CartographyTableBlock, ChestBlock, ConcretePowderBlock, CraftingTableBlock, CropBlock,
+            DetectorRailBlock, EnchantingTableBlock, EndPortalFrameBlock, FallingBlock, FarmlandBlock, FenceBlock, FurnaceBlock, GrindstoneBlock, HopperBlock, LoomBlock, RotatedPillarBlock, SculkSensorBlock,
+            CalibratedSculkSensorBlock, SmithingTableBlock, StandingSignBlock, StonecutterBlock, WallSignBlock,
+            CeilingHangingSignBlock, WallHangingSignBlock, TntBlock,
         };
 
         pub fn register_block_behaviors(registry: &mut BlockBehaviorRegistry) {
+            #anvil_registrations
             #barrel_registrations
+            #brewing_stand_registrations
+            #cartography_table_registrations
+            #chest_registrations
+            #concrete_powder_registrations
             #crafting_table_registrations
             #crop_registrations
+            #detector_rail_registrations
+            #enchanting_table_registrations
             #end_portal_frame_registrations
+            #falling_registrations
             #farm_registrations
             #fence_registrations
+            #furnace_registrations
+            #grindstone_registrations
+            #hopper_registrations
+            #loom_registrations
             #pillar_registrations
+            #sculk_sensor_registrations
+            #calibrated_sculk_sensor_registrations
+            #smithing_table_registrations
             #standing_sign_registrations
+            #stonecutter_registrations
             #wall_sign_registrations
             #ceiling_hanging_sign_registrations
             #wall_hanging_sign_registrations
+            #tnt_registrations
         }
     };
 
@@ -109,6 +109,7 @@ pub fn build(items: &[ItemClass]) -> String {
     let mut sign_items: Vec<(Ident, Ident, Ident)> = Vec::new();
     let mut hanging_sign_items: Vec<(Ident, Ident, Ident)> = Vec::new();
     let mut ender_eye_items: Vec<Ident> = Vec::new();
+    let mut flint_and_steel_items: Vec<Ident> = Vec::new();
 
     for item in items {
         let item_field = to_item_field(&item.name);
@@ -136,6 +137,7 @@ pub fn build(items: &[ItemClass]) -> String {
                 }
             }
             "EnderEyeItem" => ender_eye_items.push(item_field),
+            "FlintAndSteelItem" => flint_and_steel_items.push(item_field),
             _ => {}
         }
     }
@@ -149,18 +151,23 @@ pub fn build(items: &[ItemClass]) -> String {
     let ender_eye_registrations =
         generate_simple_registrations(ender_eye_items.iter(), &ender_eye_type);
 
+    let flint_and_steel_type = Ident::new("FlintAndSteelBehavior", Span::call_site());
+    let flint_and_steel_registrations =
+        generate_simple_registrations(flint_and_steel_items.iter(), &flint_and_steel_type);
+
     let output = quote! {
         //! Generated item behavior assignments.
 
         use steel_registry::{vanilla_blocks, vanilla_items};
         use crate::behavior::ItemBehaviorRegistry;
-        use crate::behavior::items::{BlockItemBehavior, EnderEyeBehavior, HangingSignItemBehavior, SignItemBehavior};
+        use crate::behavior::items::{BlockItemBehavior, EnderEyeBehavior, FlintAndSteelBehavior, HangingSignItemBehavior, SignItemBehavior};
 
         pub fn register_item_behaviors(registry: &mut ItemBehaviorRegistry) {
             #block_item_registrations
             #sign_item_registrations
             #hanging_sign_item_registrations
             #ender_eye_registrations
+            #flint_and_steel_registrations
         }
     };
 
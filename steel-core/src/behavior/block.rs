@@ -139,6 +139,39 @@ pub trait BlockBehaviour: Send + Sync {
         // Override for redstone components, doors, etc.
     }
 
+    /// Called after an explosion blew this block up and replaced it with air.
+    ///
+    /// Used by TNT to light itself instead of just disappearing.
+    ///
+    /// # Arguments
+    /// * `state` - The block state that was blown up
+    /// * `world` - The world
+    /// * `pos` - Position of the block
+    #[allow(unused_variables)]
+    fn was_exploded(&self, state: BlockStateId, world: &World, pos: BlockPos) {
+        // Default: no-op
+    }
+
+    /// Called after a falling block of this block landed and was placed.
+    ///
+    /// Used by concrete powder to harden when it lands in water.
+    ///
+    /// # Arguments
+    /// * `state` - The block state that landed
+    /// * `world` - The world
+    /// * `pos` - Position the block landed at
+    /// * `replaced_state` - The block state the falling block replaced
+    #[allow(unused_variables)]
+    fn on_land(
+        &self,
+        state: BlockStateId,
+        world: &World,
+        pos: BlockPos,
+        replaced_state: BlockStateId,
+    ) {
+        // Default: no-op
+    }
+
     /// Returns the item stack to give when a player picks this block (middle click).
     ///
     /// The default implementation looks up an item with the same key as the block.
@@ -184,6 +217,20 @@ pub trait BlockBehaviour: Send + Sync {
         // Default: no-op
     }
 
+    /// Called when a tick scheduled for this block with
+    /// [`World::schedule_block_tick`] comes due.
+    ///
+    /// Used by falling blocks to check whether they still have support.
+    ///
+    /// # Arguments
+    /// * `state` - The current block state
+    /// * `world` - The world the block is in
+    /// * `pos` - The position of the block
+    #[allow(unused_variables)]
+    fn tick(&self, state: BlockStateId, world: &World, pos: BlockPos) {
+        // Default: no-op
+    }
+
     // === Block Entity Methods ===
 
     /// Returns whether this block has an associated block entity.
@@ -0,0 +1,112 @@
+//! Anvil block behavior implementation.
+//!
+//! Opens the anvil menu when right-clicked. Anvils have no block entity; the
+//! repair and rename logic lives in the menu, and each use has a chance to
+//! wear the anvil down one stage.
+
+use std::ptr;
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::{REGISTRY, level_events, vanilla_blocks};
+use steel_utils::{BlockPos, BlockStateId, types::UpdateFlags};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::inventory::AnvilMenuProvider;
+use crate::player::Player;
+use crate::world::World;
+
+/// Chance for an anvil to be damaged each time a player takes a result.
+pub const DAMAGE_CHANCE: f32 = 0.12;
+
+/// Behavior for anvil, chipped anvil and damaged anvil blocks.
+pub struct AnvilBlock {
+    block: BlockRef,
+}
+
+impl AnvilBlock {
+    /// Creates a new anvil block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Returns true if the block is one of the three anvil stages.
+    #[must_use]
+    pub fn is_anvil(block: BlockRef) -> bool {
+        ptr::eq(block, vanilla_blocks::ANVIL)
+            || ptr::eq(block, vanilla_blocks::CHIPPED_ANVIL)
+            || ptr::eq(block, vanilla_blocks::DAMAGED_ANVIL)
+    }
+
+    /// Returns the next, more worn anvil state, or `None` if the anvil breaks.
+    ///
+    /// Based on Java's `AnvilBlock.damage`.
+    #[must_use]
+    pub fn damage(state: BlockStateId) -> Option<BlockStateId> {
+        let block = state.get_block();
+        let next = if ptr::eq(block, vanilla_blocks::ANVIL) {
+            vanilla_blocks::CHIPPED_ANVIL
+        } else if ptr::eq(block, vanilla_blocks::CHIPPED_ANVIL) {
+            vanilla_blocks::DAMAGED_ANVIL
+        } else {
+            return None;
+        };
+
+        let facing: Direction = state.get_value(&BlockStateProperties::HORIZONTAL_FACING);
+        Some(
+            next.default_state()
+                .set_value(&BlockStateProperties::HORIZONTAL_FACING, facing),
+        )
+    }
+
+    /// Plays the use sound and possibly wears down the anvil at `pos` after a
+    /// player took a result from it.
+    ///
+    /// Based on the block access callback in Java's `AnvilMenu.onTake`.
+    pub fn on_used(world: &World, pos: BlockPos, player: &Player) {
+        let state = world.get_block_state(&pos);
+        if !player.has_infinite_materials()
+            && Self::is_anvil(state.get_block())
+            && rand::random::<f32>() < DAMAGE_CHANCE
+        {
+            if let Some(damaged) = Self::damage(state) {
+                world.set_block(pos, damaged, UpdateFlags::UPDATE_CLIENTS);
+                world.level_event(level_events::SOUND_ANVIL_USED, pos, 0, None);
+            } else {
+                let air = REGISTRY.blocks.get_base_state_id(vanilla_blocks::AIR);
+                world.set_block(pos, air, UpdateFlags::UPDATE_ALL);
+                world.level_event(level_events::SOUND_ANVIL_BROKEN, pos, 0, None);
+            }
+        } else {
+            world.level_event(level_events::SOUND_ANVIL_USED, pos, 0, None);
+        }
+    }
+}
+
+impl BlockBehaviour for AnvilBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        // Anvils are placed sideways to the player
+        Some(self.block.default_state().set_value(
+            &BlockStateProperties::HORIZONTAL_FACING,
+            context.horizontal_direction.rotate_y_clockwise(),
+        ))
+    }
+
+    // TODO: Make anvils fall like sand once falling block entities exist.
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        _world: &World,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        player.open_menu(&AnvilMenuProvider::new(player.inventory.clone(), pos));
+        // TODO: Award stat INTERACT_WITH_ANVIL
+        InteractionResult::Success
+    }
+}
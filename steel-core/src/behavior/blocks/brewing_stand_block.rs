@@ -0,0 +1,91 @@
+//! Brewing stand block behavior implementation.
+//!
+//! Brewing stands brew potions with blaze powder. Right-clicking opens the
+//! brewing stand menu.
+
+use std::sync::Weak;
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::inventory::brewing_stand_menu::BrewingStandMenuProvider;
+use crate::inventory::container::calculate_redstone_signal_from_container;
+use crate::player::Player;
+use crate::player::stats::{CustomStat, StatKey};
+use crate::world::World;
+
+/// Behavior for brewing stands.
+///
+/// Based on Java's `BrewingStandBlock`.
+pub struct BrewingStandBlock {
+    block: BlockRef,
+}
+
+impl BrewingStandBlock {
+    /// Creates a new brewing stand block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehaviour for BrewingStandBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        world: &World,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        let Some(block_entity) = world.get_block_entity(&pos) else {
+            return InteractionResult::Pass;
+        };
+
+        player.open_menu(&BrewingStandMenuProvider::new(
+            player.inventory.clone(),
+            block_entity,
+            TextComponent::translated(translations::CONTAINER_BREWING.msg()),
+        ));
+        player.award_stat(StatKey::custom(CustomStat::InteractWithBrewingstand), 1);
+
+        InteractionResult::Success
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(vanilla_block_entity_types::BREWING_STAND, level, pos, state)
+    }
+
+    fn has_analog_output_signal(&self, _state: BlockStateId) -> bool {
+        true
+    }
+
+    fn get_analog_output_signal(&self, _state: BlockStateId, world: &World, pos: BlockPos) -> i32 {
+        world.get_block_entity(&pos).map_or(0, |be| {
+            let guard = be.lock();
+            if let Some(container) = guard.as_container() {
+                calculate_redstone_signal_from_container(container)
+            } else {
+                0
+            }
+        })
+    }
+}
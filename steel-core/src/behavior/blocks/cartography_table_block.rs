@@ -0,0 +1,50 @@
+//! Cartography table block behavior implementation.
+//!
+//! Opens the cartography table menu when right-clicked.
+
+use steel_registry::blocks::BlockRef;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::inventory::CartographyTableMenuProvider;
+use crate::player::Player;
+use crate::player::stats::{CustomStat, StatKey};
+use crate::world::World;
+
+/// Behavior for the cartography table block.
+///
+/// Based on Java's `CartographyTableBlock`.
+pub struct CartographyTableBlock {
+    block: BlockRef,
+}
+
+impl CartographyTableBlock {
+    /// Creates a new cartography table block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehaviour for CartographyTableBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        _world: &World,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        player.open_menu(&CartographyTableMenuProvider::new(
+            player.inventory.clone(),
+            pos,
+        ));
+        player.award_stat(StatKey::custom(CustomStat::InteractWithCartographyTable), 1);
+        InteractionResult::Success
+    }
+}
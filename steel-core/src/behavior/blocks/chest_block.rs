@@ -0,0 +1,237 @@
+//! Chest block behavior implementation.
+//!
+//! Chests placed next to a single chest facing the same way join it into a
+//! double chest. Right-clicking opens a 27-slot menu, or a 54-slot menu
+//! covering both halves of a double chest.
+
+use std::ptr;
+use std::sync::Weak;
+
+use steel_protocol::packets::game::SoundSource;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, ChestType, Direction};
+use steel_registry::{sound_events, vanilla_block_entity_types, vanilla_blocks};
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::inventory::chest_menu::ChestMenuProvider;
+use crate::inventory::container::calculate_redstone_signal_from_container;
+use crate::inventory::lock::ContainerRef;
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for chests and trapped chests.
+pub struct ChestBlock {
+    block: BlockRef,
+}
+
+impl ChestBlock {
+    /// Creates a new chest block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Returns the direction of the other half of a double chest.
+    ///
+    /// Based on Java's `ChestBlock.getConnectedDirection`.
+    fn connected_direction(state: BlockStateId) -> Direction {
+        let facing: Direction = state.get_value(&BlockStateProperties::HORIZONTAL_FACING);
+        if state.get_value(&BlockStateProperties::CHEST_TYPE) == ChestType::Left {
+            facing.rotate_y_clockwise()
+        } else {
+            facing.rotate_y_counter_clockwise()
+        }
+    }
+
+    /// Returns the facing of the chest at `pos` if it could pair with a new chest.
+    fn candidate_partner_facing(&self, world: &World, pos: BlockPos) -> Option<Direction> {
+        let state = world.get_block_state(&pos);
+        if ptr::eq(state.get_block(), self.block)
+            && state.get_value(&BlockStateProperties::CHEST_TYPE) == ChestType::Single
+        {
+            Some(state.get_value(&BlockStateProperties::HORIZONTAL_FACING))
+        } else {
+            None
+        }
+    }
+}
+
+impl BlockBehaviour for ChestBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        let facing = context.horizontal_direction.opposite();
+        let pos = context.relative_pos;
+
+        // TODO: Sneaking players should be able to pick the partner by clicking
+        // its side, and to avoid pairing altogether.
+        let chest_type = if self
+            .candidate_partner_facing(context.world, facing.rotate_y_clockwise().relative(&pos))
+            == Some(facing)
+        {
+            ChestType::Left
+        } else if self.candidate_partner_facing(
+            context.world,
+            facing.rotate_y_counter_clockwise().relative(&pos),
+        ) == Some(facing)
+        {
+            ChestType::Right
+        } else {
+            ChestType::Single
+        };
+
+        Some(
+            self.block
+                .default_state()
+                .set_value(&BlockStateProperties::HORIZONTAL_FACING, facing)
+                .set_value(&BlockStateProperties::CHEST_TYPE, chest_type),
+        )
+    }
+
+    /// Joins a newly placed neighbor into a double chest, or falls back to a
+    /// single chest when the other half goes away.
+    ///
+    /// Based on Java's `ChestBlock.updateShape`.
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        _world: &World,
+        _pos: BlockPos,
+        direction: Direction,
+        _neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        if ptr::eq(neighbor_state.get_block(), self.block) && direction.is_horizontal() {
+            let neighbor_type: ChestType =
+                neighbor_state.get_value(&BlockStateProperties::CHEST_TYPE);
+            let facing: Direction = state.get_value(&BlockStateProperties::HORIZONTAL_FACING);
+            let neighbor_facing: Direction =
+                neighbor_state.get_value(&BlockStateProperties::HORIZONTAL_FACING);
+
+            if state.get_value(&BlockStateProperties::CHEST_TYPE) == ChestType::Single
+                && neighbor_type != ChestType::Single
+                && facing == neighbor_facing
+                && Self::connected_direction(neighbor_state) == direction.opposite()
+            {
+                let chest_type = if neighbor_type == ChestType::Left {
+                    ChestType::Right
+                } else {
+                    ChestType::Left
+                };
+                return state.set_value(&BlockStateProperties::CHEST_TYPE, chest_type);
+            }
+        } else if state.get_value(&BlockStateProperties::CHEST_TYPE) != ChestType::Single
+            && Self::connected_direction(state) == direction
+        {
+            return state.set_value(&BlockStateProperties::CHEST_TYPE, ChestType::Single);
+        }
+
+        state
+    }
+
+    fn use_without_item(
+        &self,
+        state: BlockStateId,
+        world: &World,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        let Some(container_ref) = world
+            .get_block_entity(&pos)
+            .and_then(ContainerRef::from_block_entity)
+        else {
+            return InteractionResult::Pass;
+        };
+
+        let chest_type: ChestType = state.get_value(&BlockStateProperties::CHEST_TYPE);
+        let partner = if chest_type == ChestType::Single {
+            None
+        } else {
+            let partner_pos = Self::connected_direction(state).relative(&pos);
+            let partner_state = world.get_block_state(&partner_pos);
+            if ptr::eq(partner_state.get_block(), self.block) {
+                world
+                    .get_block_entity(&partner_pos)
+                    .and_then(ContainerRef::from_block_entity)
+            } else {
+                None
+            }
+        };
+
+        // TODO: Don't open when a solid block or a sitting cat is on top.
+        let provider = match partner {
+            // The right half holds the top three rows, like Java's DoubleBlockCombiner.
+            Some(partner) if chest_type == ChestType::Right => ChestMenuProvider::double(
+                player.inventory.clone(),
+                container_ref,
+                partner,
+                TextComponent::translated(translations::CONTAINER_CHEST_DOUBLE.msg()),
+            ),
+            Some(partner) => ChestMenuProvider::double(
+                player.inventory.clone(),
+                partner,
+                container_ref,
+                TextComponent::translated(translations::CONTAINER_CHEST_DOUBLE.msg()),
+            ),
+            None => ChestMenuProvider::three_rows(
+                player.inventory.clone(),
+                container_ref,
+                TextComponent::translated(translations::CONTAINER_CHEST.msg()),
+            ),
+        };
+        player.open_menu(&provider);
+
+        // Vanilla: ChestBlockEntity.playSound
+        world.play_sound(
+            sound_events::BLOCK_CHEST_OPEN,
+            SoundSource::Blocks,
+            pos,
+            0.5,
+            rand::random::<f32>() * 0.1 + 0.9,
+            None,
+        );
+
+        // TODO: Award stat OPEN_CHEST / TRIGGER_TRAPPED_CHEST
+        // TODO: Only play the open sound for the first viewer, and play the close
+        // sound and the lid animation (ContainerOpenersCounter)
+
+        InteractionResult::Success
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        let block_entity_type = if ptr::eq(self.block, vanilla_blocks::TRAPPED_CHEST) {
+            vanilla_block_entity_types::TRAPPED_CHEST
+        } else {
+            vanilla_block_entity_types::CHEST
+        };
+        BLOCK_ENTITIES.create(block_entity_type, level, pos, state)
+    }
+
+    fn has_analog_output_signal(&self, _state: BlockStateId) -> bool {
+        true
+    }
+
+    fn get_analog_output_signal(&self, _state: BlockStateId, world: &World, pos: BlockPos) -> i32 {
+        world.get_block_entity(&pos).map_or(0, |be| {
+            let guard = be.lock();
+            if let Some(container) = guard.as_container() {
+                calculate_redstone_signal_from_container(container)
+            } else {
+                0
+            }
+        })
+    }
+}
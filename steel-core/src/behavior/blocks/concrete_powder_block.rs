@@ -0,0 +1,132 @@
+//! Concrete powder block behavior implementation.
+//!
+//! Concrete powder falls like sand and hardens into concrete in water.
+
+use std::ptr;
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::{REGISTRY, vanilla_blocks};
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::blocks::falling_block::{self, FALL_DELAY};
+use crate::behavior::context::BlockPlaceContext;
+use crate::world::World;
+
+/// Behavior for the concrete powder blocks.
+///
+/// Concrete powder falls like sand. Placed next to water or landing in it,
+/// it turns into the concrete of its color instead.
+pub struct ConcretePowderBlock {
+    block: BlockRef,
+}
+
+impl ConcretePowderBlock {
+    /// Creates a new concrete powder block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Returns the state of the concrete this powder hardens into.
+    fn concrete(&self) -> BlockStateId {
+        REGISTRY
+            .blocks
+            .by_key(&concrete_key(&self.block.key))
+            .map_or_else(|| self.block.default_state(), |block| block.default_state())
+    }
+}
+
+impl BlockBehaviour for ConcretePowderBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        let pos = context.relative_pos;
+        let replaced = context.world.get_block_state(&pos);
+        if is_water(replaced) || touches_water(context.world, pos) {
+            return Some(self.concrete());
+        }
+        Some(self.block.default_state())
+    }
+
+    fn on_place(
+        &self,
+        state: BlockStateId,
+        world: &World,
+        pos: BlockPos,
+        _old_state: BlockStateId,
+        _moved_by_piston: bool,
+    ) {
+        world.schedule_block_tick(pos, state.get_block(), FALL_DELAY);
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &World,
+        pos: BlockPos,
+        _direction: Direction,
+        _neighbor_pos: BlockPos,
+        _neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        if touches_water(world, pos) {
+            return self.concrete();
+        }
+        world.schedule_block_tick(pos, state.get_block(), FALL_DELAY);
+        state
+    }
+
+    fn tick(&self, state: BlockStateId, world: &World, pos: BlockPos) {
+        falling_block::fall_if_unsupported(state, world, pos);
+    }
+
+    fn on_land(
+        &self,
+        _state: BlockStateId,
+        world: &World,
+        pos: BlockPos,
+        replaced_state: BlockStateId,
+    ) {
+        if is_water(replaced_state) || touches_water(world, pos) {
+            world.set_block(pos, self.concrete(), UpdateFlags::UPDATE_ALL);
+        }
+    }
+}
+
+/// The key of the concrete that the concrete powder with `key` hardens into.
+fn concrete_key(key: &Identifier) -> Identifier {
+    let path = key.path.strip_suffix("_powder").unwrap_or(&key.path);
+    Identifier::new(key.namespace.clone(), path.to_owned())
+}
+
+/// Returns true if `state` is water or holds water.
+fn is_water(state: BlockStateId) -> bool {
+    ptr::eq(state.get_block(), vanilla_blocks::WATER)
+        || state
+            .try_get_value(&BlockStateProperties::WATERLOGGED)
+            .unwrap_or(false)
+}
+
+/// Returns true if there is water next to `pos`.
+///
+/// Vanilla: `ConcretePowderBlock.touchesLiquid`
+fn touches_water(world: &World, pos: BlockPos) -> bool {
+    Direction::UPDATE_SHAPE_ORDER
+        .iter()
+        .any(|direction| is_water(world.get_block_state(&direction.relative(&pos))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn powder_hardens_into_concrete_of_its_color() {
+        let key = Identifier::vanilla_static("light_blue_concrete_powder");
+        assert_eq!(
+            concrete_key(&key),
+            Identifier::vanilla_static("light_blue_concrete")
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Detector rail block behavior implementation.
+//!
+//! Detector rails power themselves while a minecart is on them.
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::blocks::shapes::AABBd;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::BlockPlaceContext;
+use crate::world::World;
+
+/// How many ticks a pressed detector rail waits before it checks whether the
+/// minecart left.
+///
+/// Vanilla: `DetectorRailBlock.PRESSED_CHECK_PERIOD`
+pub const PRESSED_CHECK_PERIOD: u32 = 20;
+
+/// Behavior for the detector rail.
+///
+/// A minecart rolling onto the rail powers it, and a scheduled tick turns it
+/// off again once no minecart is left on it. Comparators read how full a
+/// minecart on the rail is.
+pub struct DetectorRailBlock {
+    block: BlockRef,
+}
+
+impl DetectorRailBlock {
+    /// Creates a new detector rail block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehaviour for DetectorRailBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn tick(&self, state: BlockStateId, world: &World, pos: BlockPos) {
+        check_pressed(state, world, pos);
+    }
+
+    fn has_analog_output_signal(&self, _state: BlockStateId) -> bool {
+        true
+    }
+
+    fn get_analog_output_signal(&self, state: BlockStateId, world: &World, pos: BlockPos) -> i32 {
+        if !state.get_value(&BlockStateProperties::POWERED) {
+            return 0;
+        }
+        world.minecart_signal(&detection_box(pos)).unwrap_or(0)
+    }
+}
+
+/// Returns the box a minecart has to touch to press the detector rail at
+/// `pos`.
+fn detection_box(pos: BlockPos) -> AABBd {
+    let (x, y, z) = (f64::from(pos.x()), f64::from(pos.y()), f64::from(pos.z()));
+    AABBd::new(x + 0.2, y, z + 0.2, x + 0.8, y + 0.8, z + 0.8)
+}
+
+/// Powers the detector rail `state` at `pos` if a minecart is on it, or turns
+/// it off if there is none. Keeps checking while it is pressed.
+///
+/// Vanilla: `DetectorRailBlock.checkPressed`
+pub fn check_pressed(state: BlockStateId, world: &World, pos: BlockPos) {
+    let powered: bool = state.get_value(&BlockStateProperties::POWERED);
+    let pressed = world.minecart_signal(&detection_box(pos)).is_some();
+
+    if pressed != powered {
+        let new_state = state.set_value(&BlockStateProperties::POWERED, pressed);
+        world.set_block(pos, new_state, UpdateFlags::UPDATE_ALL);
+        world.update_neighbors_at(&Direction::Down.relative(&pos), state.get_block());
+        // TODO: Update comparators next to the rail once they exist
+    }
+    if pressed {
+        world.schedule_block_tick(pos, state.get_block(), PRESSED_CHECK_PERIOD);
+    }
+}
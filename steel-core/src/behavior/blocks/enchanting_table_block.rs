@@ -0,0 +1,69 @@
+//! Enchanting table block behavior implementation.
+//!
+//! Enchanting tables enchant items with experience levels and lapis lazuli.
+//! Right-clicking opens the enchanting table menu.
+
+use std::sync::Weak;
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::inventory::EnchantmentMenuProvider;
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for enchanting tables.
+///
+/// Based on Java's `EnchantingTableBlock`.
+pub struct EnchantingTableBlock {
+    block: BlockRef,
+}
+
+impl EnchantingTableBlock {
+    /// Creates a new enchanting table block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehaviour for EnchantingTableBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        _world: &World,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        // TODO: Use the table's custom name as the title once block entities keep it
+        player.open_menu(&EnchantmentMenuProvider::new(player.inventory.clone(), pos));
+        InteractionResult::Success
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(
+            vanilla_block_entity_types::ENCHANTING_TABLE,
+            level,
+            pos,
+            state,
+        )
+    }
+}
@@ -0,0 +1,97 @@
+//! Falling block behavior implementation.
+//!
+//! Sand, gravel and the like fall down as entities when nothing holds them up.
+
+use std::ptr;
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::Direction;
+use steel_registry::vanilla_blocks;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::BlockPlaceContext;
+use crate::world::World;
+
+/// How many ticks a falling block waits after it was placed or a neighbor
+/// changed before it checks whether it still has support.
+///
+/// Vanilla: `FallingBlock.getDelayAfterPlace`
+pub const FALL_DELAY: u32 = 2;
+
+/// Behavior for blocks that fall, like sand and gravel.
+///
+/// Placing the block or changing a block next to it schedules a tick. If
+/// there is nothing below the block by then, it turns into a falling block
+/// entity that is placed again where it lands.
+pub struct FallingBlock {
+    block: BlockRef,
+}
+
+impl FallingBlock {
+    /// Creates a new falling block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehaviour for FallingBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn on_place(
+        &self,
+        state: BlockStateId,
+        world: &World,
+        pos: BlockPos,
+        _old_state: BlockStateId,
+        _moved_by_piston: bool,
+    ) {
+        world.schedule_block_tick(pos, state.get_block(), FALL_DELAY);
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &World,
+        pos: BlockPos,
+        _direction: Direction,
+        _neighbor_pos: BlockPos,
+        _neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        world.schedule_block_tick(pos, state.get_block(), FALL_DELAY);
+        state
+    }
+
+    fn tick(&self, state: BlockStateId, world: &World, pos: BlockPos) {
+        fall_if_unsupported(state, world, pos);
+    }
+}
+
+/// Returns true if a falling block falls through `state`, like air, water
+/// or fire.
+///
+/// Vanilla: `FallingBlock.isFree`
+#[must_use]
+pub fn is_free(state: BlockStateId) -> bool {
+    let block = state.get_block();
+    block.config.is_air
+        || block.config.liquid
+        || block.config.replaceable
+        || ptr::eq(block, vanilla_blocks::FIRE)
+        || ptr::eq(block, vanilla_blocks::SOUL_FIRE)
+}
+
+/// Turns the block `state` at `pos` into a falling block entity if there is
+/// nothing below it.
+///
+/// Vanilla: `FallingBlock.tick`
+pub fn fall_if_unsupported(state: BlockStateId, world: &World, pos: BlockPos) {
+    let below = world.get_block_state(&Direction::Down.relative(&pos));
+    if is_free(below) && pos.y() >= world.get_min_y() {
+        world.spawn_falling_block(pos, state);
+    }
+}
@@ -0,0 +1,135 @@
+//! Furnace block behavior implementation.
+//!
+//! Furnaces, blast furnaces and smokers cook items with fuel. Right-clicking
+//! opens the furnace menu.
+
+use std::ptr;
+use std::sync::Weak;
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::recipe::CookingKind;
+use steel_registry::{vanilla_block_entity_types, vanilla_blocks};
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::inventory::container::calculate_redstone_signal_from_container;
+use crate::inventory::furnace_menu::FurnaceMenuProvider;
+use crate::player::Player;
+use crate::player::stats::{CustomStat, StatKey};
+use crate::world::World;
+
+/// Behavior for furnaces, blast furnaces and smokers.
+///
+/// Based on Java's `AbstractFurnaceBlock`.
+pub struct FurnaceBlock {
+    block: BlockRef,
+}
+
+impl FurnaceBlock {
+    /// Creates a new furnace block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Returns which recipes this block cooks with.
+    fn kind(&self) -> CookingKind {
+        if ptr::eq(self.block, vanilla_blocks::BLAST_FURNACE) {
+            CookingKind::Blasting
+        } else if ptr::eq(self.block, vanilla_blocks::SMOKER) {
+            CookingKind::Smoking
+        } else {
+            CookingKind::Smelting
+        }
+    }
+}
+
+impl BlockBehaviour for FurnaceBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        // Furnaces face the player who placed them
+        let facing = context.horizontal_direction.opposite();
+
+        Some(
+            self.block
+                .default_state()
+                .set_value(&BlockStateProperties::HORIZONTAL_FACING, facing),
+        )
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        world: &World,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        let Some(block_entity) = world.get_block_entity(&pos) else {
+            return InteractionResult::Pass;
+        };
+
+        let kind = self.kind();
+        let (title, stat) = match kind {
+            CookingKind::Smelting => (
+                translations::CONTAINER_FURNACE,
+                CustomStat::InteractWithFurnace,
+            ),
+            CookingKind::Blasting => (
+                translations::CONTAINER_BLAST_FURNACE,
+                CustomStat::InteractWithBlastFurnace,
+            ),
+            CookingKind::Smoking => (
+                translations::CONTAINER_SMOKER,
+                CustomStat::InteractWithSmoker,
+            ),
+        };
+
+        player.open_menu(&FurnaceMenuProvider::new(
+            player.inventory.clone(),
+            block_entity,
+            kind,
+            TextComponent::translated(title.msg()),
+        ));
+        player.award_stat(StatKey::custom(stat), 1);
+
+        InteractionResult::Success
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        let block_entity_type = match self.kind() {
+            CookingKind::Smelting => vanilla_block_entity_types::FURNACE,
+            CookingKind::Blasting => vanilla_block_entity_types::BLAST_FURNACE,
+            CookingKind::Smoking => vanilla_block_entity_types::SMOKER,
+        };
+        BLOCK_ENTITIES.create(block_entity_type, level, pos, state)
+    }
+
+    fn has_analog_output_signal(&self, _state: BlockStateId) -> bool {
+        true
+    }
+
+    fn get_analog_output_signal(&self, _state: BlockStateId, world: &World, pos: BlockPos) -> i32 {
+        world.get_block_entity(&pos).map_or(0, |be| {
+            let guard = be.lock();
+            if let Some(container) = guard.as_container() {
+                calculate_redstone_signal_from_container(container)
+            } else {
+                0
+            }
+        })
+    }
+}
@@ -0,0 +1,83 @@
+//! Grindstone block behavior implementation.
+//!
+//! Opens the grindstone menu when right-clicked. Grindstones have no block
+//! entity; the repair and disenchant logic lives in the menu.
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{AttachFace, BlockStateProperties, Direction};
+use steel_registry::level_events;
+use steel_utils::math::Vector3;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::inventory::GrindstoneMenuProvider;
+use crate::player::Player;
+use crate::player::stats::{CustomStat, StatKey};
+use crate::world::World;
+
+/// Behavior for the grindstone block.
+///
+/// Based on Java's `GrindstoneBlock`.
+pub struct GrindstoneBlock {
+    block: BlockRef,
+}
+
+impl GrindstoneBlock {
+    /// Creates a new grindstone block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Drops the experience of the removed enchantments and plays the use
+    /// sound after a player took a result from the grindstone at `pos`.
+    ///
+    /// Based on the block access callback in Java's `GrindstoneMenu` result
+    /// slot `onTake`.
+    pub fn on_used(world: &World, pos: BlockPos, experience: i32) {
+        if experience > 0 {
+            let center = Vector3::new(
+                f64::from(pos.x()) + 0.5,
+                f64::from(pos.y()) + 0.5,
+                f64::from(pos.z()) + 0.5,
+            );
+            world.spawn_experience_orbs(center, experience);
+        }
+        world.level_event(level_events::SOUND_GRINDSTONE_USED, pos, 0, None);
+    }
+}
+
+impl BlockBehaviour for GrindstoneBlock {
+    /// Grindstones attach to the face the player is looking at most directly,
+    /// and unlike levers they need nothing to hold them up.
+    ///
+    /// Based on Java's `FaceAttachedHorizontalDirectionalBlock.getStateForPlacement`.
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        let (face, facing) = match context.get_nearest_looking_direction() {
+            Direction::Up => (AttachFace::Ceiling, context.horizontal_direction),
+            Direction::Down => (AttachFace::Floor, context.horizontal_direction),
+            direction => (AttachFace::Wall, direction.opposite()),
+        };
+        Some(
+            self.block
+                .default_state()
+                .set_value(&BlockStateProperties::ATTACH_FACE, face)
+                .set_value(&BlockStateProperties::HORIZONTAL_FACING, facing),
+        )
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        _world: &World,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        player.open_menu(&GrindstoneMenuProvider::new(player.inventory.clone(), pos));
+        player.award_stat(StatKey::custom(CustomStat::InteractWithGrindstone), 1);
+        InteractionResult::Success
+    }
+}
@@ -0,0 +1,81 @@
+//! Hopper block behavior implementation.
+//!
+//! Places the hopper facing into the clicked block and creates its block entity,
+//! which handles the actual item transfer.
+
+use std::sync::Weak;
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::BlockPlaceContext;
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::inventory::container::calculate_redstone_signal_from_container;
+use crate::world::World;
+
+/// Behavior for hopper blocks.
+pub struct HopperBlock {
+    block: BlockRef,
+}
+
+impl HopperBlock {
+    /// Creates a new hopper block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehaviour for HopperBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        // Hoppers point into the clicked block, but never up.
+        let direction = context.clicked_face.opposite();
+        let facing = if matches!(direction, Direction::Up | Direction::Down) {
+            Direction::Down
+        } else {
+            direction
+        };
+
+        // TODO: Set ENABLED from the neighbor redstone signal once redstone power exists.
+        Some(
+            self.block
+                .default_state()
+                .set_value(&BlockStateProperties::FACING_HOPPER, facing)
+                .set_value(&BlockStateProperties::ENABLED, true),
+        )
+    }
+
+    // TODO: Open the hopper menu on use once a 5-slot menu exists.
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(vanilla_block_entity_types::HOPPER, level, pos, state)
+    }
+
+    fn has_analog_output_signal(&self, _state: BlockStateId) -> bool {
+        true
+    }
+
+    fn get_analog_output_signal(&self, _state: BlockStateId, world: &World, pos: BlockPos) -> i32 {
+        world.get_block_entity(&pos).map_or(0, |be| {
+            let guard = be.lock();
+            if let Some(container) = guard.as_container() {
+                calculate_redstone_signal_from_container(container)
+            } else {
+                0
+            }
+        })
+    }
+}
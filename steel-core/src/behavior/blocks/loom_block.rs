@@ -0,0 +1,54 @@
+//! Loom block behavior implementation.
+//!
+//! Opens the loom menu when right-clicked. Looms have no block entity; the
+//! patterning logic lives in the menu.
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::inventory::LoomMenuProvider;
+use crate::player::Player;
+use crate::player::stats::{CustomStat, StatKey};
+use crate::world::World;
+
+/// Behavior for the loom block.
+///
+/// Based on Java's `LoomBlock`.
+pub struct LoomBlock {
+    block: BlockRef,
+}
+
+impl LoomBlock {
+    /// Creates a new loom block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehaviour for LoomBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        // Looms face the player
+        Some(self.block.default_state().set_value(
+            &BlockStateProperties::HORIZONTAL_FACING,
+            context.horizontal_direction.opposite(),
+        ))
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        _world: &World,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        player.open_menu(&LoomMenuProvider::new(player.inventory.clone(), pos));
+        player.award_stat(StatKey::custom(CustomStat::InteractWithLoom), 1);
+        InteractionResult::Success
+    }
+}
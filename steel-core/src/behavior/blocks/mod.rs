@@ -3,22 +3,54 @@
 //! The actual behavior registration is auto-generated from classes.json.
 //! See `src/generated/behaviors.rs` for the generated registration code.
 
+mod anvil_block;
 mod barrel_block;
+mod brewing_stand_block;
+mod cartography_table_block;
+mod chest_block;
+mod concrete_powder_block;
 mod crafting_table_block;
 mod crop_block;
+pub mod detector_rail_block;
+mod enchanting_table_block;
 mod end_portal_frame_block;
+pub mod falling_block;
 mod farmland_block;
 mod fence_block;
+mod furnace_block;
+mod grindstone_block;
+mod hopper_block;
+mod loom_block;
 mod rotated_pillar_block;
+pub mod sculk;
 mod sign_block;
+mod smithing_table_block;
+mod stonecutter_block;
+mod tnt_block;
 
+pub use anvil_block::AnvilBlock;
 pub use barrel_block::BarrelBlock;
+pub use brewing_stand_block::BrewingStandBlock;
+pub use cartography_table_block::CartographyTableBlock;
+pub use chest_block::ChestBlock;
+pub use concrete_powder_block::ConcretePowderBlock;
 pub use crafting_table_block::CraftingTableBlock;
 pub use crop_block::CropBlock;
+pub use detector_rail_block::DetectorRailBlock;
+pub use enchanting_table_block::EnchantingTableBlock;
 pub use end_portal_frame_block::EndPortalFrameBlock;
+pub use falling_block::FallingBlock;
 pub use farmland_block::FarmlandBlock;
 pub use fence_block::FenceBlock;
+pub use furnace_block::FurnaceBlock;
+pub use grindstone_block::GrindstoneBlock;
+pub use hopper_block::HopperBlock;
+pub use loom_block::LoomBlock;
 pub use rotated_pillar_block::RotatedPillarBlock;
+pub use sculk::{CalibratedSculkSensorBlock, SculkSensorBlock};
 pub use sign_block::{
     CeilingHangingSignBlock, StandingSignBlock, WallHangingSignBlock, WallSignBlock,
 };
+pub use smithing_table_block::SmithingTableBlock;
+pub use stonecutter_block::StonecutterBlock;
+pub use tnt_block::TntBlock;
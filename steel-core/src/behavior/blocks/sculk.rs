@@ -0,0 +1,326 @@
+//! Sculk sensor block behavior implementation.
+//!
+//! Sculk sensors listen for vibrations nearby and give off a redstone signal
+//! for a moment when one reaches them. Calibrated sculk sensors can be set to
+//! a single frequency.
+
+use std::ptr;
+use std::sync::Weak;
+
+use steel_protocol::packets::game::SoundSource;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction, SculkSensorPhase};
+use steel_registry::{sound_events, vanilla_block_entity_types, vanilla_blocks};
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::BlockPlaceContext;
+use crate::block_entity::entities::SculkSensorBlockEntity;
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::world::World;
+use crate::world::vibrations::VibrationEvent;
+
+/// How many ticks a sculk sensor stays active after picking up a vibration.
+pub const ACTIVE_TICKS: u32 = 20;
+
+/// How many ticks a sculk sensor rests after being active before it listens
+/// again.
+///
+/// Vanilla: `SculkSensorBlock.COOLDOWN_TICKS`
+pub const COOLDOWN_TICKS: u32 = 10;
+
+/// Behavior for the sculk sensor.
+///
+/// The sensor listens for vibrations through its block entity. When one
+/// reaches it, it powers up with the vibration's frequency, then cools down
+/// before it listens again.
+pub struct SculkSensorBlock {
+    block: BlockRef,
+}
+
+impl SculkSensorBlock {
+    /// Creates a new sculk sensor block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehaviour for SculkSensorBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn affect_neighbors_after_removal(
+        &self,
+        state: BlockStateId,
+        world: &World,
+        pos: BlockPos,
+        _moved_by_piston: bool,
+    ) {
+        on_remove(state, world, pos);
+    }
+
+    fn tick(&self, state: BlockStateId, world: &World, pos: BlockPos) {
+        cool_down(state, world, pos);
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(vanilla_block_entity_types::SCULK_SENSOR, level, pos, state)
+    }
+
+    fn has_analog_output_signal(&self, _state: BlockStateId) -> bool {
+        true
+    }
+
+    fn get_analog_output_signal(&self, state: BlockStateId, world: &World, pos: BlockPos) -> i32 {
+        analog_output_signal(state, world, pos)
+    }
+}
+
+/// Behavior for the calibrated sculk sensor.
+///
+/// Works like the sculk sensor, but only reacts to the frequency given by the
+/// signal going into its back.
+pub struct CalibratedSculkSensorBlock {
+    block: BlockRef,
+}
+
+impl CalibratedSculkSensorBlock {
+    /// Creates a new calibrated sculk sensor block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehaviour for CalibratedSculkSensorBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        // The amethyst faces the player, the back faces away
+        Some(self.block.default_state().set_value(
+            &BlockStateProperties::HORIZONTAL_FACING,
+            context.horizontal_direction.opposite(),
+        ))
+    }
+
+    fn affect_neighbors_after_removal(
+        &self,
+        state: BlockStateId,
+        world: &World,
+        pos: BlockPos,
+        _moved_by_piston: bool,
+    ) {
+        on_remove(state, world, pos);
+    }
+
+    fn tick(&self, state: BlockStateId, world: &World, pos: BlockPos) {
+        cool_down(state, world, pos);
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(
+            vanilla_block_entity_types::CALIBRATED_SCULK_SENSOR,
+            level,
+            pos,
+            state,
+        )
+    }
+
+    fn has_analog_output_signal(&self, _state: BlockStateId) -> bool {
+        true
+    }
+
+    fn get_analog_output_signal(&self, state: BlockStateId, world: &World, pos: BlockPos) -> i32 {
+        analog_output_signal(state, world, pos)
+    }
+}
+
+/// Returns whether `state` is a sculk sensor of either kind.
+#[must_use]
+pub fn is_sculk_sensor(state: BlockStateId) -> bool {
+    let block = state.get_block();
+    ptr::eq(block, vanilla_blocks::SCULK_SENSOR)
+        || ptr::eq(block, vanilla_blocks::CALIBRATED_SCULK_SENSOR)
+}
+
+/// Returns whether the sculk sensor `state` at `pos` reacts to a vibration
+/// from `event` right now. Sensors only listen while inactive.
+///
+/// Vanilla: `SculkSensorBlockEntity.VibrationUser.canReceiveVibration`
+#[must_use]
+pub fn can_receive(
+    world: &World,
+    pos: BlockPos,
+    state: BlockStateId,
+    event: VibrationEvent,
+) -> bool {
+    if state.get_value(&BlockStateProperties::SCULK_SENSOR_PHASE) != SculkSensorPhase::Inactive {
+        return false;
+    }
+    if ptr::eq(state.get_block(), vanilla_blocks::CALIBRATED_SCULK_SENSOR) {
+        return accepts_frequency(selected_frequency(world, pos, state), event.frequency());
+    }
+    true
+}
+
+/// Returns whether a calibrated sculk sensor set to `selected` reacts to a
+/// vibration of `frequency`. Sensors set to 0 react to every frequency.
+const fn accepts_frequency(selected: u8, frequency: u8) -> bool {
+    selected == 0 || selected == frequency
+}
+
+/// Returns the frequency the calibrated sculk sensor `state` at `pos` is set
+/// to, or 0 if it reacts to every frequency.
+///
+/// Vanilla: `CalibratedSculkSensorBlock.getBackSignal`
+const fn selected_frequency(_world: &World, _pos: BlockPos, _state: BlockStateId) -> u8 {
+    // TODO: Read the signal going into the back of the sensor once redstone
+    // power exists
+    0
+}
+
+/// Activates the sculk sensor `state` at `pos` for a vibration from `event`:
+/// it powers up with the vibration's frequency, shows the vibration to
+/// clients and cools down after a while.
+///
+/// Vanilla: `SculkSensorBlock.activate`
+pub fn activate(world: &World, pos: BlockPos, state: BlockStateId, event: VibrationEvent) {
+    let frequency = event.frequency();
+    let active = state
+        .set_value(
+            &BlockStateProperties::SCULK_SENSOR_PHASE,
+            SculkSensorPhase::Active,
+        )
+        .set_value(&BlockStateProperties::POWER, frequency);
+    world.set_block(pos, active, UpdateFlags::UPDATE_ALL);
+    update_neighbours(world, pos, state);
+    world.schedule_block_tick(pos, state.get_block(), ACTIVE_TICKS);
+
+    let update = world.get_block_entity(&pos).and_then(|block_entity| {
+        let mut guard = block_entity.lock();
+        let sensor = guard
+            .as_any_mut()
+            .downcast_mut::<SculkSensorBlockEntity>()?;
+        sensor.set_last_vibration_frequency(i32::from(frequency));
+        Some((guard.get_type(), guard.get_update_tag()?))
+    });
+    if let Some((block_entity_type, nbt)) = update {
+        world.broadcast_block_entity_update(pos, block_entity_type, nbt);
+    }
+
+    world.play_sound(
+        sound_events::BLOCK_SCULK_SENSOR_CLICKING,
+        SoundSource::Blocks,
+        pos,
+        1.0,
+        rand::random::<f32>() * 0.2 + 0.8,
+        None,
+    );
+}
+
+/// Moves an active sculk sensor on to its cooldown, taking away its signal,
+/// or lets a cooled down sensor listen again.
+///
+/// Vanilla: `SculkSensorBlock.tick`
+fn cool_down(state: BlockStateId, world: &World, pos: BlockPos) {
+    match state.get_value(&BlockStateProperties::SCULK_SENSOR_PHASE) {
+        SculkSensorPhase::Active => {
+            let cooldown = state
+                .set_value(
+                    &BlockStateProperties::SCULK_SENSOR_PHASE,
+                    SculkSensorPhase::Cooldown,
+                )
+                .set_value(&BlockStateProperties::POWER, 0u8);
+            world.set_block(pos, cooldown, UpdateFlags::UPDATE_ALL);
+            update_neighbours(world, pos, state);
+            world.schedule_block_tick(pos, state.get_block(), COOLDOWN_TICKS);
+            world.play_sound(
+                sound_events::BLOCK_SCULK_SENSOR_CLICKING_STOP,
+                SoundSource::Blocks,
+                pos,
+                1.0,
+                rand::random::<f32>() * 0.2 + 0.8,
+                None,
+            );
+        }
+        SculkSensorPhase::Cooldown => {
+            let inactive = state.set_value(
+                &BlockStateProperties::SCULK_SENSOR_PHASE,
+                SculkSensorPhase::Inactive,
+            );
+            world.set_block(pos, inactive, UpdateFlags::UPDATE_ALL);
+        }
+        SculkSensorPhase::Inactive => {}
+    }
+}
+
+/// Stops listening at `pos` and takes away the signal of a sensor removed
+/// while active.
+fn on_remove(state: BlockStateId, world: &World, pos: BlockPos) {
+    world.remove_vibration_listener(pos);
+    if state.get_value(&BlockStateProperties::SCULK_SENSOR_PHASE) == SculkSensorPhase::Active {
+        update_neighbours(world, pos, state);
+    }
+}
+
+/// Tells the blocks around the sensor and around the block below it that its
+/// signal changed.
+///
+/// Vanilla: `SculkSensorBlock.updateNeighbours`
+fn update_neighbours(world: &World, pos: BlockPos, state: BlockStateId) {
+    let block = state.get_block();
+    world.update_neighbors_at(&pos, block);
+    world.update_neighbors_at(&Direction::Down.relative(&pos), block);
+}
+
+/// Returns the frequency of the last vibration an active sensor picked up,
+/// which comparators read.
+///
+/// Vanilla: `SculkSensorBlock.getAnalogOutputSignal`
+fn analog_output_signal(state: BlockStateId, world: &World, pos: BlockPos) -> i32 {
+    if state.get_value(&BlockStateProperties::SCULK_SENSOR_PHASE) != SculkSensorPhase::Active {
+        return 0;
+    }
+    world.get_block_entity(&pos).map_or(0, |be| {
+        be.lock()
+            .as_any()
+            .downcast_ref::<SculkSensorBlockEntity>()
+            .map_or(0, SculkSensorBlockEntity::last_vibration_frequency)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calibrated_sensors_filter_frequencies() {
+        assert!(accepts_frequency(0, VibrationEvent::Step.frequency()));
+        assert!(accepts_frequency(0, VibrationEvent::Explode.frequency()));
+        assert!(accepts_frequency(
+            12,
+            VibrationEvent::BlockDestroy.frequency()
+        ));
+        assert!(!accepts_frequency(12, VibrationEvent::Step.frequency()));
+    }
+}
@@ -0,0 +1,47 @@
+//! Smithing table block behavior implementation.
+//!
+//! Opens the smithing table menu when right-clicked.
+
+use steel_registry::blocks::BlockRef;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::inventory::SmithingMenuProvider;
+use crate::player::Player;
+use crate::player::stats::{CustomStat, StatKey};
+use crate::world::World;
+
+/// Behavior for the smithing table block.
+///
+/// Based on Java's `SmithingTableBlock`.
+pub struct SmithingTableBlock {
+    block: BlockRef,
+}
+
+impl SmithingTableBlock {
+    /// Creates a new smithing table block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehaviour for SmithingTableBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        _world: &World,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        player.open_menu(&SmithingMenuProvider::new(player.inventory.clone(), pos));
+        player.award_stat(StatKey::custom(CustomStat::InteractWithSmithingTable), 1);
+        InteractionResult::Success
+    }
+}
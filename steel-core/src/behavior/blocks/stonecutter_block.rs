@@ -0,0 +1,54 @@
+//! Stonecutter block behavior implementation.
+//!
+//! Opens the stonecutter menu when right-clicked. Stonecutters have no block
+//! entity; the cutting logic lives in the menu.
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::inventory::StonecutterMenuProvider;
+use crate::player::Player;
+use crate::player::stats::{CustomStat, StatKey};
+use crate::world::World;
+
+/// Behavior for the stonecutter block.
+///
+/// Based on Java's `StonecutterBlock`.
+pub struct StonecutterBlock {
+    block: BlockRef,
+}
+
+impl StonecutterBlock {
+    /// Creates a new stonecutter block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehaviour for StonecutterBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        // Stonecutters face the player
+        Some(self.block.default_state().set_value(
+            &BlockStateProperties::HORIZONTAL_FACING,
+            context.horizontal_direction.opposite(),
+        ))
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        _world: &World,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        player.open_menu(&StonecutterMenuProvider::new(player.inventory.clone(), pos));
+        player.award_stat(StatKey::custom(CustomStat::InteractWithStonecutter), 1);
+        InteractionResult::Success
+    }
+}
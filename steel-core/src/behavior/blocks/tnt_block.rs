@@ -0,0 +1,67 @@
+//! TNT block behavior implementation.
+//!
+//! TNT turns into primed TNT when it is lit or blown up by another explosion.
+
+use steel_protocol::packets::game::SoundSource;
+use steel_registry::blocks::BlockRef;
+use steel_registry::game_rules::GameRuleValue;
+use steel_registry::sound_events;
+use steel_registry::vanilla_game_rules::TNT_EXPLODES;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehaviour;
+use crate::behavior::context::BlockPlaceContext;
+use crate::entity::primed_tnt::{self, DEFAULT_FUSE};
+use crate::world::World;
+
+/// Behavior for the TNT block.
+///
+/// Lighting TNT with flint and steel replaces it with primed TNT, which
+/// explodes 4 seconds later. TNT caught in an explosion is primed with a
+/// shorter, random fuse, setting off chain reactions.
+pub struct TntBlock {
+    block: BlockRef,
+}
+
+impl TntBlock {
+    /// Creates a new TNT block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Spawns primed TNT in place of the TNT at `pos`. The caller removes the
+    /// block. Returns `false` if TNT can't explode in this world.
+    ///
+    /// Vanilla: `TntBlock.prime`
+    pub fn prime(world: &World, pos: BlockPos) -> bool {
+        if world.get_game_rule(TNT_EXPLODES) != GameRuleValue::Bool(true) {
+            return false;
+        }
+        world.spawn_primed_tnt(pos, DEFAULT_FUSE);
+        world.play_sound(
+            sound_events::ENTITY_TNT_PRIMED,
+            SoundSource::Blocks,
+            pos,
+            1.0,
+            1.0,
+            None,
+        );
+        true
+    }
+}
+
+impl BlockBehaviour for TntBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    // TODO: Prime the TNT when it is placed next to or receives a redstone
+    // signal, once redstone power exists.
+
+    fn was_exploded(&self, _state: BlockStateId, world: &World, pos: BlockPos) {
+        if world.get_game_rule(TNT_EXPLODES) == GameRuleValue::Bool(true) {
+            world.spawn_primed_tnt(pos, primed_tnt::chain_reaction_fuse());
+        }
+    }
+}
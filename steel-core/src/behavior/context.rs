@@ -126,3 +126,18 @@ pub struct UseOnContext<'a> {
     /// The item stack being used (mutable for consumption).
     pub item_stack: &'a mut ItemStack,
 }
+
+/// Context for using an item without targeting a block.
+pub struct UseItemContext<'a> {
+    /// The player using the item.
+    pub player: &'a Player,
+    /// Which hand the item is in.
+    pub hand: InteractionHand,
+    /// The world where the interaction is happening.
+    pub world: &'a World,
+    /// The item stack being used (mutable for consumption).
+    pub item_stack: &'a mut ItemStack,
+    /// Items the use hands to the player, added to their inventory once it
+    /// is no longer locked for `item_stack`.
+    pub items_to_give: Vec<ItemStack>,
+}
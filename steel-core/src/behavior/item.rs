@@ -3,7 +3,7 @@
 use steel_registry::REGISTRY;
 use steel_registry::items::ItemRef;
 
-use crate::behavior::context::{InteractionResult, UseOnContext};
+use crate::behavior::context::{InteractionResult, UseItemContext, UseOnContext};
 use crate::behavior::items::DefaultItemBehavior;
 
 /// Trait defining the behavior of an item.
@@ -15,6 +15,11 @@ use crate::behavior::items::DefaultItemBehavior;
 pub trait ItemBehavior: Send + Sync {
     /// Called when this item is used on a block.
     fn use_on(&self, context: &mut UseOnContext) -> InteractionResult;
+
+    /// Called when this item is used without targeting a block.
+    fn use_item(&self, _context: &mut UseItemContext) -> InteractionResult {
+        InteractionResult::Pass
+    }
 }
 
 /// Registry for item behaviors.
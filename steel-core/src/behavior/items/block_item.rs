@@ -1,9 +1,12 @@
 //! Block item behavior implementation.
 
-use steel_registry::REGISTRY;
+use std::ptr;
+
 use steel_registry::blocks::BlockRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
-use steel_registry::blocks::properties::Direction;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::{REGISTRY, vanilla_blocks};
+use steel_utils::BlockStateId;
 use steel_utils::types::UpdateFlags;
 
 use crate::behavior::context::{BlockPlaceContext, InteractionResult, UseOnContext};
@@ -23,6 +26,20 @@ impl BlockItemBehavior {
     }
 }
 
+/// Waterlogs `state` if it is placed into a water source and can hold water.
+///
+/// Vanilla checks the fluid at the position in each block's
+/// `getStateForPlacement`.
+#[must_use]
+pub fn waterlog_in_source(state: BlockStateId, replaced: BlockStateId) -> BlockStateId {
+    let in_source = ptr::eq(replaced.get_block(), vanilla_blocks::WATER)
+        && replaced.try_get_value(&BlockStateProperties::LEVEL) == Some(0);
+    if !in_source {
+        return state;
+    }
+    state.with_property("waterlogged", "true").unwrap_or(state)
+}
+
 impl ItemBehavior for BlockItemBehavior {
     fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
         let clicked_pos = context.hit_result.block_pos;
@@ -76,6 +93,7 @@ impl ItemBehavior for BlockItemBehavior {
         let Some(new_state) = behavior.get_state_for_placement(&place_context) else {
             return InteractionResult::Fail;
         };
+        let new_state = waterlog_in_source(new_state, existing_state);
 
         // Check if the block placement would intersect with any entity (vanilla: Level.isUnobstructed)
         let collision_shape = new_state.get_collision_shape();
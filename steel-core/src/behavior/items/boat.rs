@@ -0,0 +1,163 @@
+//! Boat item behavior implementation.
+
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_utils::BlockPos;
+use steel_utils::math::Vector3;
+
+use crate::behavior::ItemBehavior;
+use crate::behavior::context::{InteractionResult, UseItemContext, UseOnContext};
+use crate::entity::boat::WoodType;
+use crate::player::movement;
+use crate::world::World;
+
+/// How far players reach to place a boat.
+///
+/// Vanilla: `Attributes.BLOCK_INTERACTION_RANGE`
+const REACH: f64 = 4.5;
+
+/// Behavior for the boat and raft items.
+///
+/// Puts a boat on the water or the top of the block the player looks at.
+pub struct BoatBehavior {
+    wood_type: WoodType,
+}
+
+impl BoatBehavior {
+    /// Creates a new boat behavior placing boats of `wood_type`.
+    #[must_use]
+    pub const fn new(wood_type: WoodType) -> Self {
+        Self { wood_type }
+    }
+}
+
+impl ItemBehavior for BoatBehavior {
+    fn use_on(&self, _context: &mut UseOnContext) -> InteractionResult {
+        // Clients follow up with a plain use, which also sees water
+        InteractionResult::Pass
+    }
+
+    fn use_item(&self, context: &mut UseItemContext) -> InteractionResult {
+        let player = context.player;
+        let position = *player.position.lock();
+        let eye = Vector3::new(position.x, position.y + player.eye_height(), position.z);
+        let (yaw, pitch) = player.rotation.load();
+        let Some(hit) = pick_surface(context.world, eye, view_vector(yaw, pitch)) else {
+            return InteractionResult::Pass;
+        };
+
+        context.world.spawn_boat(self.wood_type, hit, yaw);
+        context.item_stack.shrink(1);
+        InteractionResult::Success
+    }
+}
+
+/// Returns the direction a player looking at `yaw` and `pitch` faces.
+///
+/// Vanilla: `Entity.calculateViewVector`
+fn view_vector(yaw: f32, pitch: f32) -> Vector3<f64> {
+    let (pitch, yaw) = (f64::from(pitch).to_radians(), f64::from(-yaw).to_radians());
+    Vector3::new(
+        yaw.sin() * pitch.cos(),
+        -pitch.sin(),
+        yaw.cos() * pitch.cos(),
+    )
+}
+
+/// Follows the line from `from` along `direction` block by block, up to the
+/// player's reach. Returns where it first enters water or the top of a block
+/// with a collision shape, or `None` if it hits the side or bottom of a block
+/// first.
+///
+/// Vanilla: `Item.getPlayerPOVHitResult` with `ClipContext.Fluid.ANY`
+#[allow(clippy::cast_possible_truncation)]
+fn pick_surface(
+    world: &World,
+    from: Vector3<f64>,
+    direction: Vector3<f64>,
+) -> Option<Vector3<f64>> {
+    let cell = |value: f64| value.floor() as i32;
+    let step = |delta: f64| {
+        if delta > 0.0 {
+            1
+        } else if delta < 0.0 {
+            -1
+        } else {
+            0
+        }
+    };
+    // How far along the line the next block boundary of an axis is
+    let boundary = |origin: f64, delta: f64| {
+        if delta.abs() < f64::EPSILON {
+            f64::INFINITY
+        } else if delta > 0.0 {
+            (origin.floor() + 1.0 - origin) / delta
+        } else {
+            (origin - origin.floor()) / -delta
+        }
+    };
+    let spacing = |delta: f64| {
+        if delta.abs() < f64::EPSILON {
+            f64::INFINITY
+        } else {
+            1.0 / delta.abs()
+        }
+    };
+
+    let (mut x, mut y, mut z) = (cell(from.x), cell(from.y), cell(from.z));
+    let (step_x, step_y, step_z) = (step(direction.x), step(direction.y), step(direction.z));
+    let mut next = [
+        boundary(from.x, direction.x),
+        boundary(from.y, direction.y),
+        boundary(from.z, direction.z),
+    ];
+    let spacing = [
+        spacing(direction.x),
+        spacing(direction.y),
+        spacing(direction.z),
+    ];
+    let mut distance = 0.0;
+    let mut entered_from_above = false;
+
+    while distance <= REACH {
+        let pos = BlockPos::new(x, y, z);
+        let hit = from + direction * distance;
+        if movement::water_height(world, pos).is_some() {
+            return Some(hit);
+        }
+        if !world.get_block_state(&pos).get_collision_shape().is_empty() {
+            return entered_from_above.then_some(hit);
+        }
+
+        let axis = if next[0] < next[1] && next[0] < next[2] {
+            0
+        } else if next[1] < next[2] {
+            1
+        } else {
+            2
+        };
+        distance = next[axis];
+        next[axis] += spacing[axis];
+        match axis {
+            0 => x += step_x,
+            1 => y += step_y,
+            _ => z += step_z,
+        }
+        entered_from_above = axis == 1 && step_y < 0;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looking_straight_down_faces_down() {
+        let down = view_vector(37.0, 90.0);
+        assert!(down.x.abs() < 1e-9 && down.z.abs() < 1e-9);
+        assert!((down.y + 1.0).abs() < 1e-9);
+
+        let south = view_vector(0.0, 0.0);
+        assert!((south.z - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,49 @@
+//! Empty map item behavior implementation.
+
+use steel_protocol::packets::game::SoundSource;
+use steel_registry::sound_events;
+
+use crate::behavior::ItemBehavior;
+use crate::behavior::context::{InteractionResult, UseItemContext, UseOnContext};
+use crate::player::stats::StatKey;
+use crate::world::map_data;
+
+/// Behavior for the empty map item.
+///
+/// Using it turns one empty map into a filled map of the area around the
+/// player.
+///
+/// Based on Java's `EmptyMapItem`.
+pub struct EmptyMapBehavior;
+
+impl ItemBehavior for EmptyMapBehavior {
+    fn use_on(&self, _context: &mut UseOnContext) -> InteractionResult {
+        InteractionResult::Pass
+    }
+
+    fn use_item(&self, context: &mut UseItemContext) -> InteractionResult {
+        let item = context.item_stack.item;
+        if !context.player.has_infinite_materials() {
+            context.item_stack.shrink(1);
+        }
+        context.player.award_stat(StatKey::used(item), 1);
+
+        let pos = context.player.position.lock().to_block_pos();
+        context.world.play_sound(
+            sound_events::UI_CARTOGRAPHY_TABLE_TAKE_RESULT,
+            SoundSource::Players,
+            pos,
+            1.0,
+            1.0,
+            None,
+        );
+
+        let map = map_data::create_filled_map(context.world, pos.x(), pos.z(), 0);
+        if context.item_stack.is_empty() {
+            *context.item_stack = map;
+        } else {
+            context.items_to_give.push(map);
+        }
+        InteractionResult::Success
+    }
+}
@@ -0,0 +1,31 @@
+//! Firework rocket item behavior implementation.
+
+use crate::behavior::ItemBehavior;
+use crate::behavior::context::{InteractionResult, UseItemContext, UseOnContext};
+use crate::entity::firework_rocket::DEFAULT_FLIGHT_DURATION;
+
+/// Behavior for the firework rocket item.
+///
+/// Used while gliding, the rocket attaches to the player and boosts them.
+pub struct FireworkRocketBehavior;
+
+impl ItemBehavior for FireworkRocketBehavior {
+    fn use_on(&self, _context: &mut UseOnContext) -> InteractionResult {
+        // TODO: Launch a free flying rocket from the clicked face
+        InteractionResult::Pass
+    }
+
+    fn use_item(&self, context: &mut UseItemContext) -> InteractionResult {
+        if !context.player.is_fall_flying() {
+            return InteractionResult::Pass;
+        }
+
+        context.world.spawn_firework_rocket(
+            context.player,
+            context.item_stack,
+            DEFAULT_FLIGHT_DURATION,
+        );
+        context.item_stack.shrink(1);
+        InteractionResult::Success
+    }
+}
@@ -0,0 +1,104 @@
+//! Flint and steel item behavior implementation.
+
+use std::ptr;
+
+use steel_protocol::packets::game::SoundSource;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::Direction;
+use steel_registry::vanilla_dimension_types::{OVERWORLD, THE_NETHER};
+use steel_registry::{sound_events, vanilla_blocks};
+use steel_utils::math::Axis;
+use steel_utils::types::UpdateFlags;
+
+use crate::behavior::ItemBehavior;
+use crate::behavior::blocks::TntBlock;
+use crate::behavior::context::{InteractionResult, UseOnContext};
+use crate::world::portal::PortalShape;
+
+/// Behavior for the flint and steel item.
+///
+/// Sets fire to the face of the clicked block. When the fire would be inside
+/// an obsidian frame in the overworld or the nether, the frame is filled with
+/// nether portal blocks instead. Clicked TNT is lit.
+pub struct FlintAndSteelBehavior;
+
+impl ItemBehavior for FlintAndSteelBehavior {
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        // TODO: Light campfires, candles and candle cakes
+
+        // Vanilla: TntBlock.useItemOn
+        let clicked = context.hit_result.block_pos;
+        if ptr::eq(
+            context.world.get_block_state(&clicked).get_block(),
+            vanilla_blocks::TNT,
+        ) {
+            if !TntBlock::prime(context.world, clicked) {
+                return InteractionResult::Fail;
+            }
+            context.world.set_block(
+                clicked,
+                vanilla_blocks::AIR.default_state(),
+                UpdateFlags::UPDATE_ALL_IMMEDIATE,
+            );
+            context
+                .item_stack
+                .hurt_and_break(1, context.player.has_infinite_materials());
+            return InteractionResult::Success;
+        }
+
+        let fire_pos = context
+            .hit_result
+            .direction
+            .relative(&context.hit_result.block_pos);
+        if !context.world.is_in_valid_bounds(&fire_pos)
+            || !context.world.get_block_state(&fire_pos).is_air()
+        {
+            return InteractionResult::Fail;
+        }
+
+        // Vanilla: BaseFireBlock.inPortalDimension and onPlace
+        let dimension = &context.world.dimension.key;
+        let portal = if *dimension == OVERWORLD.key || *dimension == THE_NETHER.key {
+            PortalShape::find_empty(context.world, fire_pos, Axis::X)
+        } else {
+            None
+        };
+
+        if let Some(portal) = portal {
+            portal.create_portal_blocks(context.world);
+        } else {
+            let below = Direction::Down.relative(&fire_pos);
+            // TODO: Fire can also be placed next to flammable blocks
+            if !context
+                .world
+                .get_block_state(&below)
+                .is_face_sturdy(Direction::Up)
+            {
+                return InteractionResult::Fail;
+            }
+            if !context.world.set_block(
+                fire_pos,
+                vanilla_blocks::FIRE.default_state(),
+                UpdateFlags::UPDATE_ALL_IMMEDIATE,
+            ) {
+                return InteractionResult::Fail;
+            }
+        }
+
+        // The client plays the sound itself for the player using the item
+        context.world.play_sound(
+            sound_events::ITEM_FLINTANDSTEEL_USE,
+            SoundSource::Blocks,
+            fire_pos,
+            1.0,
+            rand::random::<f32>() * 0.4 + 0.8,
+            Some(context.player.id),
+        );
+
+        context
+            .item_stack
+            .hurt_and_break(1, context.player.has_infinite_materials());
+
+        InteractionResult::Success
+    }
+}
@@ -0,0 +1,36 @@
+//! Item frame item behavior implementation.
+
+use crate::behavior::ItemBehavior;
+use crate::behavior::context::{InteractionResult, UseOnContext};
+
+/// Behavior for the item frame and glow item frame items.
+///
+/// Attaches an empty frame to the clicked face of a block.
+pub struct ItemFrameBehavior {
+    glowing: bool,
+}
+
+impl ItemFrameBehavior {
+    /// Creates a new item frame behavior, for glow item frames if `glowing`.
+    #[must_use]
+    pub const fn new(glowing: bool) -> Self {
+        Self { glowing }
+    }
+}
+
+impl ItemBehavior for ItemFrameBehavior {
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        // Unlike paintings, frames also go on floors and ceilings
+        let direction = context.hit_result.direction;
+        let pos = direction.relative(&context.hit_result.block_pos);
+        if !context.world.may_interact(context.player, &pos) {
+            return InteractionResult::Fail;
+        }
+
+        if !context.world.place_item_frame(pos, direction, self.glowing) {
+            return InteractionResult::Fail;
+        }
+        context.item_stack.shrink(1);
+        InteractionResult::Success
+    }
+}
@@ -0,0 +1,46 @@
+//! Minecart item behavior implementation.
+
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, RailShape};
+use steel_utils::math::Vector3;
+
+use crate::behavior::ItemBehavior;
+use crate::behavior::context::{InteractionResult, UseOnContext};
+use crate::entity::minecart::{self, MinecartKind};
+
+/// Behavior for the minecart items that carry a block.
+///
+/// Puts a minecart on the clicked rail.
+pub struct MinecartBehavior {
+    kind: MinecartKind,
+}
+
+impl MinecartBehavior {
+    /// Creates a new minecart behavior placing minecarts of `kind`.
+    #[must_use]
+    pub const fn new(kind: MinecartKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl ItemBehavior for MinecartBehavior {
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        let pos = context.hit_result.block_pos;
+        let state = context.world.get_block_state(&pos);
+        if !minecart::is_rail(state) {
+            return InteractionResult::Fail;
+        }
+
+        // Minecarts on slopes start halfway up the rail
+        let shape: RailShape = state.get_value(&BlockStateProperties::RAIL_SHAPE);
+        let height = if minecart::is_slope(&shape) { 0.5 } else { 0.0 };
+        let position = Vector3::new(
+            f64::from(pos.x()) + 0.5,
+            f64::from(pos.y()) + 0.0625 + height,
+            f64::from(pos.z()) + 0.5,
+        );
+        context.world.spawn_minecart(self.kind, position);
+        context.item_stack.shrink(1);
+        InteractionResult::Success
+    }
+}
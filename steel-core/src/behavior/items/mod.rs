@@ -4,13 +4,27 @@
 //! See `src/behavior/generated/items.rs` for the generated registration code.
 
 mod block_item;
+mod boat;
 mod bucket;
 mod default;
+mod empty_map;
 mod ender_eye;
+mod firework_rocket;
+mod flint_and_steel;
+mod item_frame;
+mod minecart;
+mod painting;
 mod sign_item;
 
 pub use block_item::BlockItemBehavior;
+pub use boat::BoatBehavior;
 pub use bucket::FilledBucketBehavior;
 pub use default::DefaultItemBehavior;
+pub use empty_map::EmptyMapBehavior;
 pub use ender_eye::EnderEyeBehavior;
+pub use firework_rocket::FireworkRocketBehavior;
+pub use flint_and_steel::FlintAndSteelBehavior;
+pub use item_frame::ItemFrameBehavior;
+pub use minecart::MinecartBehavior;
+pub use painting::PaintingBehavior;
 pub use sign_item::{HangingSignItemBehavior, SignItemBehavior};
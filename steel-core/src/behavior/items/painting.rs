@@ -0,0 +1,31 @@
+//! Painting item behavior implementation.
+
+use crate::behavior::ItemBehavior;
+use crate::behavior::context::{InteractionResult, UseOnContext};
+
+/// Behavior for the painting item.
+///
+/// Hangs a painting on the clicked side of a wall.
+pub struct PaintingBehavior;
+
+impl ItemBehavior for PaintingBehavior {
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        let direction = context.hit_result.direction;
+        if !direction.is_horizontal() {
+            return InteractionResult::Fail;
+        }
+
+        let pos = direction.relative(&context.hit_result.block_pos);
+        if !context.world.may_interact(context.player, &pos) {
+            return InteractionResult::Fail;
+        }
+
+        // TODO: Place the motive from the `painting/variant` component once it
+        // is read, instead of always picking a random one.
+        if !context.world.place_painting(pos, direction) {
+            return InteractionResult::Fail;
+        }
+        context.item_stack.shrink(1);
+        InteractionResult::Success
+    }
+}
@@ -12,6 +12,7 @@ use steel_utils::types::UpdateFlags;
 use steel_utils::{BlockPos, BlockStateId};
 
 use crate::behavior::context::{BlockPlaceContext, InteractionResult, UseOnContext};
+use crate::behavior::items::block_item::waterlog_in_source;
 use crate::behavior::{BLOCK_BEHAVIORS, ItemBehavior};
 use crate::world::World;
 
@@ -189,6 +190,7 @@ impl ItemBehavior for SignItemBehavior {
         else {
             return InteractionResult::Fail;
         };
+        let new_state = waterlog_in_source(new_state, existing_state);
 
         // Place the block
         if !context
@@ -378,6 +380,7 @@ impl ItemBehavior for HangingSignItemBehavior {
         let Some(state) = new_state else {
             return InteractionResult::Fail;
         };
+        let state = waterlog_in_source(state, existing_state);
 
         // Place the block
         if !context
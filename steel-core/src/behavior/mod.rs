@@ -42,14 +42,23 @@ pub mod item_behaviours;
 
 pub use block::{BlockBehaviorRegistry, BlockBehaviour, DefaultBlockBehaviour};
 use block_behaviours::register_block_behaviors;
-pub use context::{BlockHitResult, BlockPlaceContext, InteractionResult, UseOnContext};
+pub use context::{
+    BlockHitResult, BlockPlaceContext, InteractionResult, UseItemContext, UseOnContext,
+};
 pub use item::{ItemBehavior, ItemBehaviorRegistry};
 use item_behaviours::register_item_behaviors;
-pub use items::{BlockItemBehavior, DefaultItemBehavior, EnderEyeBehavior, FilledBucketBehavior};
+pub use items::{
+    BlockItemBehavior, BoatBehavior, DefaultItemBehavior, EmptyMapBehavior, EnderEyeBehavior,
+    FilledBucketBehavior, FireworkRocketBehavior, FlintAndSteelBehavior, ItemFrameBehavior,
+    MinecartBehavior, PaintingBehavior,
+};
 use std::ops::Deref;
 use std::sync::OnceLock;
 use steel_registry::{vanilla_blocks, vanilla_items};
 
+use crate::entity::boat::WoodType;
+use crate::entity::minecart::MinecartKind;
+
 /// Wrapper for the global block behavior registry that implements `Deref`.
 pub struct BlockBehaviorLock(OnceLock<BlockBehaviorRegistry>);
 
@@ -117,6 +126,37 @@ pub fn init_behaviors() {
         )),
     );
 
+    item_behaviors.set_behavior(
+        &vanilla_items::ITEMS.firework_rocket,
+        Box::new(FireworkRocketBehavior),
+    );
+
+    item_behaviors.set_behavior(&vanilla_items::ITEMS.painting, Box::new(PaintingBehavior));
+    item_behaviors.set_behavior(&vanilla_items::ITEMS.map, Box::new(EmptyMapBehavior));
+    item_behaviors.set_behavior(
+        &vanilla_items::ITEMS.item_frame,
+        Box::new(ItemFrameBehavior::new(false)),
+    );
+    item_behaviors.set_behavior(
+        &vanilla_items::ITEMS.glow_item_frame,
+        Box::new(ItemFrameBehavior::new(true)),
+    );
+    item_behaviors.set_behavior(
+        &vanilla_items::ITEMS.minecart,
+        Box::new(MinecartBehavior::new(MinecartKind::Rideable)),
+    );
+    item_behaviors.set_behavior(
+        &vanilla_items::ITEMS.chest_minecart,
+        Box::new(MinecartBehavior::new(MinecartKind::Chest)),
+    );
+    item_behaviors.set_behavior(
+        &vanilla_items::ITEMS.hopper_minecart,
+        Box::new(MinecartBehavior::new(MinecartKind::Hopper)),
+    );
+    for wood_type in WoodType::ALL {
+        item_behaviors.set_behavior(wood_type.item(), Box::new(BoatBehavior::new(wood_type)));
+    }
+
     assert!(
         ITEM_BEHAVIORS.0.set(item_behaviors).is_ok(),
         "Item behavior registry already initialized"
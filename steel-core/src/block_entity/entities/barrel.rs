@@ -17,6 +17,7 @@ use steel_registry::vanilla_block_entity_types;
 use steel_utils::{BlockPos, BlockStateId, Identifier};
 
 use crate::block_entity::BlockEntity;
+use crate::block_entity::nbt::{self, BlockEntityNbt, BlockEntityNbtError};
 use crate::inventory::container::Container;
 use crate::world::World;
 
@@ -156,6 +157,21 @@ impl BlockEntity for BarrelBlockEntity {
     }
 }
 
+impl BlockEntityNbt for BarrelBlockEntity {
+    /// Saves the non-empty slots, each item tagged with its `Slot` index.
+    fn to_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        self.save_additional(&mut nbt);
+        nbt
+    }
+
+    fn from_nbt(nbt: &NbtCompound) -> Result<Self, BlockEntityNbtError> {
+        let mut barrel = Self::new(Weak::new(), BlockPos::new(0, 0, 0), BlockStateId::default());
+        nbt::with_borrowed(nbt, |nbt| barrel.load_additional(nbt))?;
+        Ok(barrel)
+    }
+}
+
 impl Container for BarrelBlockEntity {
     fn get_container_size(&self) -> usize {
         BARREL_SLOTS
@@ -189,7 +205,7 @@ impl Container for BarrelBlockEntity {
 ///
 /// This mirrors the logic of `ItemStack::from_nbt_tag` but works directly with
 /// borrowed compound data, properly parsing component patches.
-fn item_from_borrowed_compound(compound: &NbtCompoundView<'_, '_>) -> Option<ItemStack> {
+pub(super) fn item_from_borrowed_compound(compound: &NbtCompoundView<'_, '_>) -> Option<ItemStack> {
     // Get the item ID
     let id_str = compound.string("id")?.to_str();
     let id = id_str.parse::<Identifier>().ok()?;
@@ -0,0 +1,371 @@
+//! Brewing stand block entity implementation.
+//!
+//! Brewing stands burn blaze powder to brew the ingredient into the potions
+//! in their three bottle slots.
+
+use std::any::Any;
+use std::sync::{Arc, Weak};
+
+use simdnbt::ToNbtTag;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use steel_registry::REGISTRY;
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::items::ItemRef;
+use steel_registry::{level_events, vanilla_block_entity_types, vanilla_items::ITEMS};
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+
+use super::barrel::item_from_borrowed_compound;
+use crate::block_entity::BlockEntity;
+use crate::inventory::container::Container;
+use crate::world::World;
+
+/// Number of slots in a brewing stand.
+pub const BREWING_STAND_SLOTS: usize = 5;
+
+/// The number of bottle slots, which come first.
+pub const BOTTLE_SLOTS: usize = 3;
+
+/// The slot holding the ingredient being brewed.
+pub const SLOT_INGREDIENT: usize = 3;
+
+/// The slot holding the blaze powder.
+pub const SLOT_BREWING_FUEL: usize = 4;
+
+/// How long brewing takes, in ticks.
+pub const BREWING_TIME: i32 = 400;
+
+/// How many brews one blaze powder fuels.
+pub const FUEL_USES: i32 = 20;
+
+/// Returns true if `stack` can fuel a brewing stand.
+#[must_use]
+pub fn is_brewing_fuel(stack: &ItemStack) -> bool {
+    REGISTRY
+        .items
+        .is_in_tag(stack.item, &Identifier::vanilla_static("brewing_fuel"))
+}
+
+/// Returns true if `stack` can be put into a bottle slot.
+#[must_use]
+pub fn is_bottle(stack: &ItemStack) -> bool {
+    stack.is(&ITEMS.potion)
+        || stack.is(&ITEMS.splash_potion)
+        || stack.is(&ITEMS.lingering_potion)
+        || stack.is(&ITEMS.glass_bottle)
+}
+
+/// Brewing stand block entity.
+///
+/// Based on Java's `BrewingStandBlockEntity`.
+pub struct BrewingStandBlockEntity {
+    /// Weak reference to the world for marking chunks dirty.
+    level: Weak<World>,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+    /// The bottle, ingredient and fuel slots.
+    items: Vec<ItemStack>,
+    /// Ticks left until the current brew finishes, or 0 when not brewing.
+    brew_time: i32,
+    /// How many more brews the blaze powder already used fuels.
+    fuel: i32,
+    /// The ingredient the current brew started with. Swapping it out
+    /// cancels the brew.
+    ingredient: Option<ItemRef>,
+}
+
+impl BrewingStandBlockEntity {
+    /// Creates a new brewing stand block entity.
+    #[must_use]
+    pub fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self {
+            level,
+            pos,
+            state,
+            removed: false,
+            items: vec![ItemStack::empty(); BREWING_STAND_SLOTS],
+            brew_time: 0,
+            fuel: 0,
+            ingredient: None,
+        }
+    }
+
+    /// Returns the values the brewing stand menu shows: the remaining brew
+    /// time and the fuel left.
+    ///
+    /// Based on Java's `BrewingStandBlockEntity.dataAccess`.
+    #[must_use]
+    pub const fn data(&self) -> [i32; 2] {
+        [self.brew_time, self.fuel]
+    }
+
+    /// Returns true if the ingredient changes at least one of the bottles.
+    fn is_brewable(&self) -> bool {
+        let ingredient = &self.items[SLOT_INGREDIENT];
+        if ingredient.is_empty() || !REGISTRY.brewing.is_ingredient(ingredient) {
+            return false;
+        }
+        self.items[..BOTTLE_SLOTS]
+            .iter()
+            .any(|bottle| !bottle.is_empty() && REGISTRY.brewing.has_mix(bottle, ingredient))
+    }
+
+    /// Advances the fuel and the brewing by one tick.
+    ///
+    /// Based on Java's `BrewingStandBlockEntity.serverTick`.
+    fn tick_brewing(&mut self, world: &World) {
+        let mut changed = false;
+        let progress_before = (self.brew_time > 0, self.fuel);
+
+        let fuel = &mut self.items[SLOT_BREWING_FUEL];
+        if self.fuel <= 0 && is_brewing_fuel(fuel) {
+            self.fuel = FUEL_USES;
+            fuel.shrink(1);
+            changed = true;
+        }
+
+        let brewable = self.is_brewable();
+        if self.brew_time > 0 {
+            self.brew_time -= 1;
+            if self.brew_time == 0 && brewable {
+                self.brew(world);
+            } else if !brewable
+                || self
+                    .ingredient
+                    .is_none_or(|ingredient| !self.items[SLOT_INGREDIENT].is(ingredient))
+            {
+                self.brew_time = 0;
+            }
+            changed = true;
+        } else if brewable && self.fuel > 0 {
+            self.fuel -= 1;
+            self.brew_time = BREWING_TIME;
+            self.ingredient = Some(self.items[SLOT_INGREDIENT].item);
+            changed = true;
+        }
+
+        let bottles = [
+            &BlockStateProperties::HAS_BOTTLE_0,
+            &BlockStateProperties::HAS_BOTTLE_1,
+            &BlockStateProperties::HAS_BOTTLE_2,
+        ];
+        for (slot, property) in bottles.into_iter().enumerate() {
+            self.state = self.state.set_value(property, !self.items[slot].is_empty());
+        }
+
+        if changed {
+            BlockEntity::set_changed(self);
+        }
+        // Clients are told when brewing starts or stops and when fuel is
+        // used, not about every tick of progress
+        if progress_before != (self.brew_time > 0, self.fuel)
+            && let Some(nbt) = self.get_update_tag()
+        {
+            world.broadcast_block_entity_update(self.pos, self.get_type(), nbt);
+        }
+    }
+
+    /// Brews the ingredient into every bottle and uses it up.
+    ///
+    /// Based on Java's `BrewingStandBlockEntity.doBrew`.
+    fn brew(&mut self, world: &World) {
+        let ingredient = self.items[SLOT_INGREDIENT].clone();
+        for bottle in &mut self.items[..BOTTLE_SLOTS] {
+            *bottle = REGISTRY.brewing.mix(&ingredient, bottle);
+        }
+
+        let remainder = ingredient.item.get_crafting_remainder();
+        let ingredient = &mut self.items[SLOT_INGREDIENT];
+        ingredient.shrink(1);
+        if !remainder.is_empty() {
+            if ingredient.is_empty() {
+                *ingredient = remainder;
+            } else {
+                world.drop_item_stack(self.pos, remainder);
+            }
+        }
+
+        world.level_event(level_events::SOUND_BREWING_STAND_BREW, self.pos, 0, None);
+    }
+}
+
+impl BlockEntity for BrewingStandBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        vanilla_block_entity_types::BREWING_STAND
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    fn set_removed(&mut self) {
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    fn pre_remove_side_effects(&mut self, pos: BlockPos, _state: BlockStateId) {
+        // Drop all items when the brewing stand is broken
+        if let Some(world) = self.level.upgrade() {
+            for item in self.items.drain(..) {
+                world.drop_item_stack(pos, item);
+            }
+        }
+    }
+
+    fn load_additional(&mut self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt_view: NbtCompoundView<'_, '_> = nbt.into();
+
+        if let Some(items_list) = nbt_view.list("Items")
+            && let Some(compounds) = items_list.compounds()
+        {
+            for compound in compounds {
+                if let Some(slot) = compound.byte("Slot") {
+                    let slot = slot as usize;
+                    if slot < BREWING_STAND_SLOTS
+                        && let Some(item) = item_from_borrowed_compound(&compound)
+                    {
+                        self.items[slot] = item;
+                    }
+                }
+            }
+        }
+
+        self.brew_time = nbt_view.short("BrewTime").map_or(0, i32::from);
+        if self.brew_time > 0 {
+            self.ingredient = Some(self.items[SLOT_INGREDIENT].item);
+        }
+        self.fuel = nbt_view.byte("Fuel").map_or(0, i32::from);
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("BrewTime", self.brew_time as i16);
+        nbt.insert("Fuel", self.fuel as i8);
+
+        let mut items: Vec<NbtCompound> = Vec::new();
+        for (slot, item) in self.items.iter().enumerate() {
+            if !item.is_empty()
+                && let NbtTag::Compound(mut item_nbt) = item.clone().to_nbt_tag()
+            {
+                item_nbt.insert("Slot", slot as i8);
+                items.push(item_nbt);
+            }
+        }
+        nbt.insert("Items", NbtList::Compound(items));
+    }
+
+    fn get_update_tag(&self) -> Option<NbtCompound> {
+        // Clients only need the brewing progress, not the contents
+        let mut nbt = NbtCompound::new();
+        nbt.insert("BrewTime", self.brew_time as i16);
+        nbt.insert("Fuel", self.fuel as i8);
+        Some(nbt)
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, world: &World) {
+        self.tick_brewing(world);
+    }
+
+    fn as_container(&self) -> Option<&(dyn Container + 'static)> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut (dyn Container + 'static)> {
+        Some(self)
+    }
+}
+
+impl Container for BrewingStandBlockEntity {
+    fn get_container_size(&self) -> usize {
+        BREWING_STAND_SLOTS
+    }
+
+    fn get_item(&self, slot: usize) -> &ItemStack {
+        &self.items[slot]
+    }
+
+    fn get_item_mut(&mut self, slot: usize) -> &mut ItemStack {
+        &mut self.items[slot]
+    }
+
+    fn set_item(&mut self, slot: usize, stack: ItemStack) {
+        if slot < BREWING_STAND_SLOTS {
+            self.items[slot] = stack;
+            self.set_changed();
+        }
+    }
+
+    fn get_max_stack_size(&self) -> i32 {
+        64
+    }
+
+    fn set_changed(&mut self) {
+        BlockEntity::set_changed(self);
+    }
+
+    /// Ingredients and blaze powder go into their own slots, and each bottle
+    /// slot holds a single bottle.
+    fn can_place_item(&self, slot: usize, stack: &ItemStack) -> bool {
+        match slot {
+            SLOT_INGREDIENT => REGISTRY.brewing.is_ingredient(stack),
+            SLOT_BREWING_FUEL => is_brewing_fuel(stack),
+            _ => is_bottle(stack) && self.items[slot].is_empty(),
+        }
+    }
+
+    /// Only the empty bottles left by some ingredients can be taken from the
+    /// ingredient slot.
+    fn can_take_item(&self, slot: usize, stack: &ItemStack) -> bool {
+        slot != SLOT_INGREDIENT || stack.is(&ITEMS.glass_bottle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn any_potion_bottle_fits_a_bottle_slot() {
+        assert!(is_bottle(&ItemStack::new(&ITEMS.potion)));
+        assert!(is_bottle(&ItemStack::new(&ITEMS.lingering_potion)));
+        assert!(is_bottle(&ItemStack::new(&ITEMS.glass_bottle)));
+        assert!(!is_bottle(&ItemStack::new(&ITEMS.blaze_powder)));
+    }
+}
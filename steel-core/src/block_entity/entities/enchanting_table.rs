@@ -0,0 +1,160 @@
+//! Enchanting table block entity implementation.
+//!
+//! Enchanting tables keep no items; the item and lapis live in the menu of
+//! the player using the table. The block entity exists for the book the
+//! client animates, and to find the bookshelves that power the table.
+
+use std::any::Any;
+use std::sync::{Arc, Weak};
+
+use simdnbt::borrow::BaseNbtCompound as BorrowedNbtCompound;
+use simdnbt::owned::NbtCompound;
+use steel_registry::REGISTRY;
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::enchantment::MAX_BOOKSHELVES;
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+
+use crate::block_entity::BlockEntity;
+use crate::world::World;
+
+/// Blocks that power an enchanting table, like bookshelves.
+pub const ENCHANTMENT_POWER_PROVIDER_TAG: Identifier =
+    Identifier::vanilla_static("enchantment_power_provider");
+
+/// Blocks a bookshelf can power an enchanting table through, like air.
+pub const ENCHANTMENT_POWER_TRANSMITTER_TAG: Identifier =
+    Identifier::vanilla_static("enchantment_power_transmitter");
+
+/// Returns the offsets a bookshelf can sit at to power an enchanting table:
+/// the ring two blocks out, on the table's level and the one above.
+///
+/// Based on Java's `EnchantingTableBlock.BOOKSHELF_OFFSETS`.
+fn bookshelf_offsets() -> impl Iterator<Item = (i32, i32, i32)> {
+    (0..=1).flat_map(|y| {
+        (-2..=2).flat_map(move |z| {
+            (-2_i32..=2)
+                .filter(move |x: &i32| x.abs() == 2 || z.abs() == 2)
+                .map(move |x| (x, y, z))
+        })
+    })
+}
+
+/// Returns true if the block at `offset` from the table powers it, which
+/// needs the block halfway between them to let the power through.
+///
+/// Based on Java's `EnchantingTableBlock.isValidBookShelf`.
+fn is_valid_bookshelf(world: &World, pos: BlockPos, (x, y, z): (i32, i32, i32)) -> bool {
+    let provider = world.get_block_state(&pos.offset(x, y, z)).get_block();
+    let transmitter = world
+        .get_block_state(&pos.offset(x / 2, y, z / 2))
+        .get_block();
+    REGISTRY
+        .blocks
+        .is_in_tag(provider, &ENCHANTMENT_POWER_PROVIDER_TAG)
+        && REGISTRY
+            .blocks
+            .is_in_tag(transmitter, &ENCHANTMENT_POWER_TRANSMITTER_TAG)
+}
+
+/// Counts the bookshelves powering the enchanting table at `pos`, up to
+/// [`MAX_BOOKSHELVES`].
+#[must_use]
+pub fn count_bookshelves(world: &World, pos: BlockPos) -> i32 {
+    let count = bookshelf_offsets()
+        .filter(|&offset| is_valid_bookshelf(world, pos, offset))
+        .count();
+    (count as i32).min(MAX_BOOKSHELVES)
+}
+
+/// Enchanting table block entity.
+///
+/// Based on Java's `EnchantingTableBlockEntity`.
+pub struct EnchantingTableBlockEntity {
+    /// Weak reference to the world for marking chunks dirty.
+    level: Weak<World>,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+}
+
+impl EnchantingTableBlockEntity {
+    /// Creates a new enchanting table block entity.
+    #[must_use]
+    pub fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self {
+            level,
+            pos,
+            state,
+            removed: false,
+        }
+    }
+}
+
+impl BlockEntity for EnchantingTableBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        vanilla_block_entity_types::ENCHANTING_TABLE
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    fn set_removed(&mut self) {
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    fn load_additional(&mut self, _nbt: &BorrowedNbtCompound<'_>) {}
+
+    fn save_additional(&self, _nbt: &mut NbtCompound) {}
+
+    fn get_update_tag(&self) -> Option<NbtCompound> {
+        // The client animates the book on its own
+        Some(NbtCompound::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bookshelves_ring_the_table_on_two_layers() {
+        let offsets: Vec<_> = bookshelf_offsets().collect();
+        assert_eq!(offsets.len(), 32);
+        assert!(offsets.contains(&(2, 1, -2)));
+        assert!(!offsets.contains(&(1, 0, 1)));
+    }
+}
@@ -0,0 +1,501 @@
+//! Furnace block entity implementation.
+//!
+//! Furnaces, blast furnaces and smokers share this block entity. They burn
+//! fuel to cook the item in their input slot with the recipes of their
+//! cooking kind.
+
+use std::any::Any;
+use std::sync::{Arc, Weak};
+
+use rand::Rng;
+use rustc_hash::FxHashMap;
+use simdnbt::ToNbtTag;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use steel_registry::REGISTRY;
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::recipe::{CookingKind, SmeltingRecipe};
+use steel_registry::{vanilla_block_entity_types, vanilla_items::ITEMS};
+use steel_utils::math::Vector3;
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+
+use super::barrel::item_from_borrowed_compound;
+use crate::block_entity::BlockEntity;
+use crate::inventory::container::Container;
+use crate::world::World;
+
+/// Number of slots in a furnace.
+pub const FURNACE_SLOTS: usize = 3;
+
+/// The slot holding the item being cooked.
+pub const SLOT_INPUT: usize = 0;
+
+/// The slot holding the fuel.
+pub const SLOT_FUEL: usize = 1;
+
+/// The slot holding the cooked items.
+pub const SLOT_RESULT: usize = 2;
+
+/// How long an item cooks when no recipe says otherwise, in ticks.
+pub const DEFAULT_COOKING_TIME: i32 = 200;
+
+/// Furnace, blast furnace or smoker block entity.
+///
+/// Based on Java's `AbstractFurnaceBlockEntity`.
+pub struct FurnaceBlockEntity {
+    /// Weak reference to the world for marking chunks dirty.
+    level: Weak<World>,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+    /// Which recipes this furnace cooks with.
+    kind: CookingKind,
+    /// The input, fuel and result slots.
+    items: Vec<ItemStack>,
+    /// Ticks left until the current fuel burns out.
+    lit_time_remaining: i32,
+    /// How many ticks the current fuel burns for in total.
+    lit_total_time: i32,
+    /// How many ticks the input item has cooked for.
+    cooking_time_spent: i32,
+    /// How many ticks the input item needs to cook.
+    cooking_total_time: i32,
+    /// How often each recipe was cooked since the result was last taken out,
+    /// for the experience it gives.
+    recipes_used: FxHashMap<Identifier, i32>,
+}
+
+impl FurnaceBlockEntity {
+    /// Creates a new furnace block entity.
+    #[must_use]
+    pub fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self::with_kind(level, pos, state, CookingKind::Smelting)
+    }
+
+    /// Creates a new blast furnace block entity.
+    #[must_use]
+    pub fn new_blast_furnace(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self::with_kind(level, pos, state, CookingKind::Blasting)
+    }
+
+    /// Creates a new smoker block entity.
+    #[must_use]
+    pub fn new_smoker(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self::with_kind(level, pos, state, CookingKind::Smoking)
+    }
+
+    fn with_kind(
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+        kind: CookingKind,
+    ) -> Self {
+        Self {
+            level,
+            pos,
+            state,
+            removed: false,
+            kind,
+            items: vec![ItemStack::empty(); FURNACE_SLOTS],
+            lit_time_remaining: 0,
+            lit_total_time: 0,
+            cooking_time_spent: 0,
+            cooking_total_time: 0,
+            recipes_used: FxHashMap::default(),
+        }
+    }
+
+    /// Returns which recipes this furnace cooks with.
+    #[must_use]
+    pub const fn kind(&self) -> CookingKind {
+        self.kind
+    }
+
+    /// Returns true while fuel is burning.
+    #[must_use]
+    pub const fn is_lit(&self) -> bool {
+        self.lit_time_remaining > 0
+    }
+
+    /// Returns the values the furnace menu shows: the remaining and total
+    /// burn time of the fuel, and the spent and total cooking time.
+    ///
+    /// Based on Java's `AbstractFurnaceBlockEntity.dataAccess`.
+    #[must_use]
+    pub const fn data(&self) -> [i32; 4] {
+        [
+            self.lit_time_remaining,
+            self.lit_total_time,
+            self.cooking_time_spent,
+            self.cooking_total_time,
+        ]
+    }
+
+    /// Finds the recipe for the item in the input slot.
+    fn current_recipe(&self) -> Option<&'static SmeltingRecipe> {
+        REGISTRY
+            .recipes
+            .cooking(self.kind)
+            .find_recipe(&self.items[SLOT_INPUT])
+    }
+
+    /// Returns how long the item in the input slot takes to cook.
+    fn total_cook_time(&self) -> i32 {
+        self.current_recipe()
+            .map_or(DEFAULT_COOKING_TIME, |recipe| recipe.cooking_time)
+    }
+
+    /// Advances the fuel and the cooking by one tick.
+    ///
+    /// Based on Java's `AbstractFurnaceBlockEntity.serverTick`.
+    fn tick_furnace(&mut self) {
+        let was_lit = self.is_lit();
+        let mut changed = false;
+        if self.is_lit() {
+            self.lit_time_remaining -= 1;
+        }
+
+        let has_input = !self.items[SLOT_INPUT].is_empty();
+        let has_fuel = !self.items[SLOT_FUEL].is_empty();
+        if self.is_lit() || (has_fuel && has_input) {
+            let recipe = self.current_recipe();
+            if !self.is_lit() && self.can_burn(recipe) {
+                self.lit_time_remaining = REGISTRY.fuels.burn_duration(&self.items[SLOT_FUEL]);
+                self.lit_total_time = self.lit_time_remaining;
+                if self.is_lit() {
+                    changed = true;
+                    self.consume_fuel();
+                }
+            }
+
+            if let Some(recipe) = recipe
+                && self.is_lit()
+                && self.can_burn(Some(recipe))
+            {
+                self.cooking_time_spent += 1;
+                if self.cooking_time_spent == self.cooking_total_time {
+                    self.cooking_time_spent = 0;
+                    self.burn(recipe);
+                    self.cooking_total_time = self.total_cook_time();
+                    *self.recipes_used.entry(recipe.id.clone()).or_insert(0) += 1;
+                    changed = true;
+                }
+            } else {
+                self.cooking_time_spent = 0;
+            }
+        } else if self.cooking_time_spent > 0 {
+            // Without fuel the item cools down again
+            self.cooking_time_spent =
+                (self.cooking_time_spent - 2).clamp(0, self.cooking_total_time);
+        }
+
+        if was_lit != self.is_lit() {
+            changed = true;
+            self.state = self
+                .state
+                .set_value(&BlockStateProperties::LIT, self.is_lit());
+        }
+        if changed {
+            BlockEntity::set_changed(self);
+        }
+    }
+
+    /// Uses up one item of fuel. A lava bucket leaves its bucket behind.
+    fn consume_fuel(&mut self) {
+        let fuel = &mut self.items[SLOT_FUEL];
+        if fuel.is_empty() {
+            return;
+        }
+        let remainder = fuel.item.get_crafting_remainder();
+        fuel.shrink(1);
+        if fuel.is_empty() {
+            *fuel = remainder;
+        }
+    }
+
+    /// Returns true if the input can be cooked and the result fits into the
+    /// result slot.
+    fn can_burn(&self, recipe: Option<&SmeltingRecipe>) -> bool {
+        let Some(recipe) = recipe else {
+            return false;
+        };
+        if self.items[SLOT_INPUT].is_empty() {
+            return false;
+        }
+        let result = recipe.result.to_item_stack();
+        let output = &self.items[SLOT_RESULT];
+        if output.is_empty() {
+            return true;
+        }
+        if !ItemStack::is_same_item_same_components(output, &result) {
+            return false;
+        }
+        let max = self.get_max_stack_size().min(output.max_stack_size());
+        output.count() + result.count() <= max
+    }
+
+    /// Turns one input item into the recipe's result.
+    fn burn(&mut self, recipe: &SmeltingRecipe) {
+        let input = self.items[SLOT_INPUT].clone();
+        let output = &mut self.items[SLOT_RESULT];
+        if output.is_empty() {
+            *output = recipe.result.to_item_stack();
+        } else {
+            output.grow(recipe.result.count);
+        }
+
+        // Drying a sponge fills an empty bucket in the fuel slot
+        if input.is(&ITEMS.wet_sponge) && self.items[SLOT_FUEL].is(&ITEMS.bucket) {
+            self.items[SLOT_FUEL] = ItemStack::new(&ITEMS.water_bucket);
+        }
+        self.items[SLOT_INPUT].shrink(1);
+    }
+
+    /// Drops the experience of every recipe cooked since it was last
+    /// awarded at `position`, and forgets the recipes.
+    ///
+    /// Based on Java's `AbstractFurnaceBlockEntity.getRecipesToAwardAndPopExperience`.
+    pub fn award_used_recipes(&mut self, world: &World, position: Vector3<f64>) {
+        let mut rng = rand::rng();
+        let mut amount = 0;
+        for (id, count) in self.recipes_used.drain() {
+            let Some(recipe) = REGISTRY.recipes.cooking(self.kind).get(&id) else {
+                continue;
+            };
+            amount += experience_for(count, recipe.experience, rng.random());
+        }
+        if amount > 0 {
+            world.spawn_experience_orbs(position, amount);
+        }
+    }
+}
+
+/// Returns the experience for cooking a recipe worth `experience` `count`
+/// times. The fraction left over is awarded as one more point with its
+/// chance, decided by `roll`.
+///
+/// Based on Java's `AbstractFurnaceBlockEntity.createExperience`.
+fn experience_for(count: i32, experience: f32, roll: f32) -> i32 {
+    let total = count as f32 * experience;
+    let whole = total.floor();
+    let fraction = total - whole;
+    let mut amount = whole as i32;
+    if roll < fraction {
+        amount += 1;
+    }
+    amount
+}
+
+impl BlockEntity for FurnaceBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        match self.kind {
+            CookingKind::Smelting => vanilla_block_entity_types::FURNACE,
+            CookingKind::Blasting => vanilla_block_entity_types::BLAST_FURNACE,
+            CookingKind::Smoking => vanilla_block_entity_types::SMOKER,
+        }
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    fn set_removed(&mut self) {
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    fn pre_remove_side_effects(&mut self, pos: BlockPos, _state: BlockStateId) {
+        // Drop all items and the stored experience when the furnace is broken
+        if let Some(world) = self.level.upgrade() {
+            for item in self.items.drain(..) {
+                world.drop_item_stack(pos, item);
+            }
+            let center = Vector3::new(
+                f64::from(pos.x()) + 0.5,
+                f64::from(pos.y()) + 0.5,
+                f64::from(pos.z()) + 0.5,
+            );
+            self.award_used_recipes(&world, center);
+        }
+    }
+
+    fn load_additional(&mut self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt_view: NbtCompoundView<'_, '_> = nbt.into();
+
+        if let Some(items_list) = nbt_view.list("Items")
+            && let Some(compounds) = items_list.compounds()
+        {
+            for compound in compounds {
+                if let Some(slot) = compound.byte("Slot") {
+                    let slot = slot as usize;
+                    if slot < FURNACE_SLOTS
+                        && let Some(item) = item_from_borrowed_compound(&compound)
+                    {
+                        self.items[slot] = item;
+                    }
+                }
+            }
+        }
+
+        self.cooking_time_spent = nbt_view.short("cooking_time_spent").map_or(0, i32::from);
+        self.cooking_total_time = nbt_view.short("cooking_total_time").map_or(0, i32::from);
+        self.lit_time_remaining = nbt_view.short("lit_time_remaining").map_or(0, i32::from);
+        self.lit_total_time = nbt_view.short("lit_total_time").map_or(0, i32::from);
+
+        self.recipes_used.clear();
+        if let Some(recipes) = nbt_view.compound("RecipesUsed") {
+            for (key, value) in recipes.iter() {
+                if let Ok(id) = key.to_str().parse::<Identifier>()
+                    && let Some(count) = value.int()
+                {
+                    self.recipes_used.insert(id, count);
+                }
+            }
+        }
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("cooking_time_spent", self.cooking_time_spent as i16);
+        nbt.insert("cooking_total_time", self.cooking_total_time as i16);
+        nbt.insert("lit_time_remaining", self.lit_time_remaining as i16);
+        nbt.insert("lit_total_time", self.lit_total_time as i16);
+
+        let mut items: Vec<NbtCompound> = Vec::new();
+        for (slot, item) in self.items.iter().enumerate() {
+            if !item.is_empty()
+                && let NbtTag::Compound(mut item_nbt) = item.clone().to_nbt_tag()
+            {
+                item_nbt.insert("Slot", slot as i8);
+                items.push(item_nbt);
+            }
+        }
+        nbt.insert("Items", NbtList::Compound(items));
+
+        let mut recipes = NbtCompound::new();
+        for (id, count) in &self.recipes_used {
+            recipes.insert(id.to_string(), *count);
+        }
+        nbt.insert("RecipesUsed", recipes);
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, _world: &World) {
+        self.tick_furnace();
+    }
+
+    fn as_container(&self) -> Option<&(dyn Container + 'static)> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut (dyn Container + 'static)> {
+        Some(self)
+    }
+}
+
+impl Container for FurnaceBlockEntity {
+    fn get_container_size(&self) -> usize {
+        FURNACE_SLOTS
+    }
+
+    fn get_item(&self, slot: usize) -> &ItemStack {
+        &self.items[slot]
+    }
+
+    fn get_item_mut(&mut self, slot: usize) -> &mut ItemStack {
+        &mut self.items[slot]
+    }
+
+    /// A different input item starts cooking from the beginning.
+    fn set_item(&mut self, slot: usize, stack: ItemStack) {
+        if slot >= FURNACE_SLOTS {
+            return;
+        }
+        let same_input =
+            !stack.is_empty() && ItemStack::is_same_item_same_components(&self.items[slot], &stack);
+        self.items[slot] = stack;
+        if slot == SLOT_INPUT && !same_input {
+            self.cooking_total_time = self.total_cook_time();
+            self.cooking_time_spent = 0;
+        }
+        self.set_changed();
+    }
+
+    fn get_max_stack_size(&self) -> i32 {
+        64
+    }
+
+    fn set_changed(&mut self) {
+        BlockEntity::set_changed(self);
+    }
+
+    /// Nothing goes into the result slot, and only fuel or an empty bucket
+    /// into the fuel slot.
+    fn can_place_item(&self, slot: usize, stack: &ItemStack) -> bool {
+        match slot {
+            SLOT_RESULT => false,
+            SLOT_FUEL => {
+                REGISTRY.fuels.is_fuel(stack)
+                    || (stack.is(&ITEMS.bucket) && !self.items[SLOT_FUEL].is(&ITEMS.bucket))
+            }
+            _ => true,
+        }
+    }
+
+    /// Only cooked items and empty buckets left by fuel can be taken out.
+    fn can_take_item(&self, slot: usize, stack: &ItemStack) -> bool {
+        match slot {
+            SLOT_RESULT => true,
+            SLOT_FUEL => stack.is(&ITEMS.water_bucket) || stack.is(&ITEMS.bucket),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leftover_experience_is_awarded_by_chance() {
+        // Nine iron ingots at 0.7 experience each make 6.3
+        assert_eq!(experience_for(9, 0.7, 0.5), 6);
+        assert_eq!(experience_for(9, 0.7, 0.2), 7);
+        assert_eq!(experience_for(2, 0.5, 0.0), 1);
+    }
+}
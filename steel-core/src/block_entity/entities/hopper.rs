@@ -0,0 +1,270 @@
+//! Hopper block entity implementation.
+//!
+//! Hoppers hold 5 slots and move one item at a time: they push into the
+//! container they face and pull from the container above them.
+
+use std::any::Any;
+use std::sync::{Arc, Weak};
+
+use simdnbt::ToNbtTag;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::{BlockPos, BlockStateId};
+
+use super::barrel::item_from_borrowed_compound;
+use crate::block_entity::BlockEntity;
+use crate::inventory::container::Container;
+use crate::world::World;
+
+/// Number of slots in a hopper.
+pub const HOPPER_SLOTS: usize = 5;
+
+/// Ticks a hopper waits after moving an item.
+pub const MOVE_ITEM_SPEED: i32 = 8;
+
+/// Hopper block entity.
+pub struct HopperBlockEntity {
+    /// Weak reference to the world for marking chunks dirty.
+    level: Weak<World>,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+    /// The 5 item slots.
+    items: Vec<ItemStack>,
+    /// Ticks left until the hopper tries to move items again.
+    cooldown: i32,
+}
+
+impl HopperBlockEntity {
+    /// Creates a new hopper block entity.
+    #[must_use]
+    pub fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self {
+            level,
+            pos,
+            state,
+            removed: false,
+            items: vec![ItemStack::empty(); HOPPER_SLOTS],
+            cooldown: -1,
+        }
+    }
+
+    /// Returns true if every slot holds a full stack.
+    fn is_full(&self) -> bool {
+        self.items
+            .iter()
+            .all(|item| !item.is_empty() && item.count() >= item.max_stack_size())
+    }
+
+    /// Tries to push and pull one item each.
+    ///
+    /// Based on Java's `HopperBlockEntity.tryMoveItems`. A hopper disabled by
+    /// redstone (`enabled=false`) does not move anything.
+    fn tick_hopper(&mut self, world: &World) {
+        self.cooldown -= 1;
+        if self.cooldown > 0 {
+            return;
+        }
+        self.cooldown = 0;
+
+        if !self.state.get_value(&BlockStateProperties::ENABLED) {
+            return;
+        }
+
+        let mut moved = false;
+        if !self.is_empty() {
+            moved = self.eject_items(world);
+        }
+        if !self.is_full() {
+            moved |= self.suck_in_items(world);
+        }
+
+        if moved {
+            self.cooldown = MOVE_ITEM_SPEED;
+            BlockEntity::set_changed(self);
+        }
+    }
+
+    /// Pushes one item into the container the hopper is facing.
+    fn eject_items(&mut self, world: &World) -> bool {
+        let facing: Direction = self.state.get_value(&BlockStateProperties::FACING_HOPPER);
+        let Some(target) = world.get_block_entity(&facing.relative(&self.pos)) else {
+            return false;
+        };
+        let mut target = target.lock();
+        let Some(container) = target.as_container_mut() else {
+            return false;
+        };
+
+        let target_was_empty = container.is_empty();
+        for slot in 0..HOPPER_SLOTS {
+            if self.transfer_item(slot, container) {
+                // Like vanilla, a hopper that just received its first item waits
+                // before passing it on, so items don't skip through hopper chains
+                if target_was_empty && let Some(hopper) = target.as_any_mut().downcast_mut::<Self>()
+                {
+                    hopper.cooldown = MOVE_ITEM_SPEED;
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Pulls one item from the container above the hopper.
+    fn suck_in_items(&mut self, world: &World) -> bool {
+        let Some(source) = world.get_block_entity(&Direction::Up.relative(&self.pos)) else {
+            return false;
+        };
+        let mut source = source.lock();
+        let Some(container) = source.as_container_mut() else {
+            return false;
+        };
+
+        for slot in 0..container.get_container_size() {
+            if container.transfer_item(slot, self) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl BlockEntity for HopperBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        vanilla_block_entity_types::HOPPER
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    fn set_removed(&mut self) {
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    fn pre_remove_side_effects(&mut self, pos: BlockPos, _state: BlockStateId) {
+        // Drop all items when the hopper is broken
+        if let Some(world) = self.level.upgrade() {
+            for item in self.items.drain(..) {
+                world.drop_item_stack(pos, item);
+            }
+        }
+    }
+
+    fn load_additional(&mut self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt_view: NbtCompoundView<'_, '_> = nbt.into();
+
+        if let Some(items_list) = nbt_view.list("Items")
+            && let Some(compounds) = items_list.compounds()
+        {
+            for compound in compounds {
+                if let Some(slot) = compound.byte("Slot") {
+                    let slot = slot as usize;
+                    if slot < HOPPER_SLOTS
+                        && let Some(item) = item_from_borrowed_compound(&compound)
+                    {
+                        self.items[slot] = item;
+                    }
+                }
+            }
+        }
+
+        self.cooldown = nbt_view.int("TransferCooldown").unwrap_or(-1);
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        let mut items: Vec<NbtCompound> = Vec::new();
+        for (slot, item) in self.items.iter().enumerate() {
+            if !item.is_empty()
+                && let NbtTag::Compound(mut item_nbt) = item.clone().to_nbt_tag()
+            {
+                item_nbt.insert("Slot", slot as i8);
+                items.push(item_nbt);
+            }
+        }
+        nbt.insert("Items", NbtList::Compound(items));
+        nbt.insert("TransferCooldown", self.cooldown);
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, world: &World) {
+        self.tick_hopper(world);
+    }
+
+    fn as_container(&self) -> Option<&(dyn Container + 'static)> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut (dyn Container + 'static)> {
+        Some(self)
+    }
+}
+
+impl Container for HopperBlockEntity {
+    fn get_container_size(&self) -> usize {
+        HOPPER_SLOTS
+    }
+
+    fn get_item(&self, slot: usize) -> &ItemStack {
+        &self.items[slot]
+    }
+
+    fn get_item_mut(&mut self, slot: usize) -> &mut ItemStack {
+        &mut self.items[slot]
+    }
+
+    fn set_item(&mut self, slot: usize, stack: ItemStack) {
+        if slot < HOPPER_SLOTS {
+            self.items[slot] = stack;
+            self.set_changed();
+        }
+    }
+
+    fn get_max_stack_size(&self) -> i32 {
+        64
+    }
+
+    fn set_changed(&mut self) {
+        BlockEntity::set_changed(self);
+    }
+}
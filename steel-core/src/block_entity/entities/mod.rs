@@ -1,7 +1,24 @@
 //! Block entity implementations.
 
 mod barrel;
+mod brewing_stand;
+mod chest;
+mod enchanting_table;
+mod furnace;
+mod hopper;
+mod sculk_sensor;
 mod sign;
 
 pub use barrel::{BARREL_SLOTS, BarrelBlockEntity};
+pub use brewing_stand::{
+    BOTTLE_SLOTS, BREWING_STAND_SLOTS, BrewingStandBlockEntity, SLOT_BREWING_FUEL, SLOT_INGREDIENT,
+    is_bottle, is_brewing_fuel,
+};
+pub use chest::{CHEST_SLOTS, ChestBlockEntity};
+pub use enchanting_table::{EnchantingTableBlockEntity, count_bookshelves};
+pub use furnace::{
+    DEFAULT_COOKING_TIME, FURNACE_SLOTS, FurnaceBlockEntity, SLOT_FUEL, SLOT_INPUT, SLOT_RESULT,
+};
+pub use hopper::{HOPPER_SLOTS, HopperBlockEntity, MOVE_ITEM_SPEED};
+pub use sculk_sensor::SculkSensorBlockEntity;
 pub use sign::{SIGN_LINES, SignBlockEntity, SignText};
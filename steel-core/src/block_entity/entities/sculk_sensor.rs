@@ -0,0 +1,145 @@
+//! Sculk sensor block entity implementation.
+//!
+//! Sculk sensors listen for vibrations through their block entity, which
+//! also remembers the frequency of the last vibration they picked up.
+
+use std::any::Any;
+use std::sync::{Arc, Weak};
+
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::NbtCompound;
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::block_entity::BlockEntity;
+use crate::world::World;
+
+/// Sculk sensor block entity, used by both sculk sensors and calibrated sculk
+/// sensors.
+pub struct SculkSensorBlockEntity {
+    /// Weak reference to the world for marking chunks dirty.
+    level: Weak<World>,
+    /// Whether this is a sculk sensor or a calibrated sculk sensor.
+    block_entity_type: BlockEntityTypeRef,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+    /// The frequency of the last vibration the sensor picked up.
+    last_vibration_frequency: i32,
+    /// Whether the sensor was added to the world's vibration listeners.
+    listening: bool,
+}
+
+impl SculkSensorBlockEntity {
+    /// Creates a new sculk sensor block entity.
+    #[must_use]
+    pub fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self {
+            level,
+            block_entity_type: vanilla_block_entity_types::SCULK_SENSOR,
+            pos,
+            state,
+            removed: false,
+            last_vibration_frequency: 0,
+            listening: false,
+        }
+    }
+
+    /// Creates a new calibrated sculk sensor block entity.
+    #[must_use]
+    pub fn new_calibrated(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self {
+            block_entity_type: vanilla_block_entity_types::CALIBRATED_SCULK_SENSOR,
+            ..Self::new(level, pos, state)
+        }
+    }
+
+    /// Returns the frequency of the last vibration the sensor picked up.
+    #[must_use]
+    pub const fn last_vibration_frequency(&self) -> i32 {
+        self.last_vibration_frequency
+    }
+
+    /// Remembers the frequency of a vibration the sensor picked up.
+    pub fn set_last_vibration_frequency(&mut self, frequency: i32) {
+        self.last_vibration_frequency = frequency;
+        self.set_changed();
+    }
+}
+
+impl BlockEntity for SculkSensorBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        self.block_entity_type
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    fn set_removed(&mut self) {
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    fn load_additional(&mut self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt_view: NbtCompoundView<'_, '_> = nbt.into();
+        if let Some(frequency) = nbt_view.int("last_vibration_frequency") {
+            self.last_vibration_frequency = frequency;
+        }
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("last_vibration_frequency", self.last_vibration_frequency);
+    }
+
+    fn get_update_tag(&self) -> Option<NbtCompound> {
+        // Clients show the vibration reaching the sensor
+        let mut nbt = NbtCompound::new();
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, world: &World) {
+        // Listeners are added once the sensor ticks, so sensors loaded with
+        // their chunk listen too
+        if !self.listening {
+            world.add_vibration_listener(self.pos);
+            self.listening = true;
+        }
+    }
+}
@@ -20,6 +20,7 @@ use text_components::{TextComponent, content::Content};
 use uuid::Uuid;
 
 use crate::block_entity::BlockEntity;
+use crate::block_entity::nbt::{self, BlockEntityNbt, BlockEntityNbtError};
 use crate::world::World;
 
 /// Maximum distance (in blocks) a player can be from a sign while editing.
@@ -231,6 +232,22 @@ impl SignBlockEntity {
     }
 }
 
+impl BlockEntityNbt for SignBlockEntity {
+    /// Saves both sides of the sign. Each line is a text component stored as
+    /// NBT, the way clients read sign text.
+    fn to_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        self.save_additional(&mut nbt);
+        nbt
+    }
+
+    fn from_nbt(nbt: &NbtCompound) -> Result<Self, BlockEntityNbtError> {
+        let mut sign = Self::new(Weak::new(), BlockPos::new(0, 0, 0), BlockStateId::default());
+        nbt::with_borrowed(nbt, |nbt| sign.load_additional(nbt))?;
+        Ok(sign)
+    }
+}
+
 impl BlockEntity for SignBlockEntity {
     fn as_any(&self) -> &dyn Any {
         self
@@ -385,3 +402,39 @@ fn dye_color_from_str(s: &str) -> DyeColor {
         _ => DyeColor::Black,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use text_components::Modifier;
+    use text_components::format::Color;
+
+    use super::*;
+
+    #[test]
+    fn colored_text_survives_nbt() {
+        let mut sign = SignBlockEntity::new(
+            Weak::new(),
+            BlockPos::new(0, 64, 0),
+            BlockStateId::default(),
+        );
+        let front = sign.get_text_mut(true);
+        front.set_message(0, TextComponent::plain("Hello").color(Color::Red));
+        front.color = DyeColor::Blue;
+        front.has_glowing_text = true;
+        sign.get_text_mut(false)
+            .set_message(3, TextComponent::plain("Back"));
+
+        let nbt = sign.to_nbt();
+        let loaded = SignBlockEntity::from_nbt(&nbt).expect("sign NBT should load");
+
+        assert_eq!(loaded.to_nbt(), nbt);
+        let front = loaded.get_text(true);
+        assert_eq!(front.color, DyeColor::Blue);
+        assert!(front.has_glowing_text);
+        let Some(Content::Text { text }) = front.get_message(0).map(|line| &line.content) else {
+            panic!("first line should be plain text");
+        };
+        assert_eq!(text, "Hello");
+        assert!(loaded.get_text(false).has_message());
+    }
+}
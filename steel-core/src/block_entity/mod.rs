@@ -24,6 +24,7 @@
 //! ```
 
 pub mod entities;
+pub mod nbt;
 mod registry;
 mod storage;
 
@@ -35,6 +36,7 @@ use simdnbt::owned::NbtCompound;
 use steel_registry::block_entity_type::BlockEntityTypeRef;
 use steel_utils::{BlockPos, BlockStateId, locks::SyncMutex};
 
+pub use nbt::{BlockEntityNbt, BlockEntityNbtError};
 pub use registry::{BLOCK_ENTITIES, BlockEntityFactory, BlockEntityRegistry, init_block_entities};
 pub use storage::BlockEntityStorage;
 
@@ -124,6 +126,17 @@ pub trait BlockEntity: Send + Sync {
     /// Called when saving the block entity to disk.
     fn save_additional(&self, nbt: &mut NbtCompound);
 
+    /// Loads data from an owned NBT compound, like the one given to `/setblock`,
+    /// and marks the block entity as changed.
+    ///
+    /// Vanilla: `BlockEntity.loadWithComponents`
+    fn apply_nbt(&mut self, nbt: &NbtCompound) {
+        match nbt::with_borrowed(nbt, |nbt| self.load_additional(nbt)) {
+            Ok(()) => self.set_changed(),
+            Err(err) => log::warn!("Failed to read block entity NBT: {err}"),
+        }
+    }
+
     // === Client Sync ===
 
     /// Returns the NBT data to send to clients for initial sync.
@@ -146,7 +159,9 @@ pub trait BlockEntity: Send + Sync {
 
     /// Called every game tick for ticking block entities.
     ///
-    /// Only called if `is_ticking()` returns `true`.
+    /// Only called if `is_ticking()` returns `true`. A block entity can change
+    /// its block, like a furnace lighting up, by setting its own block state;
+    /// the world is updated to match after the tick.
     #[allow(unused_variables)]
     fn tick(&mut self, world: &World) {
         // Default: no-op
@@ -0,0 +1,49 @@
+//! Owned NBT conversions for block entity data.
+//!
+//! Block entities save and load through borrowed NBT on disk. This module
+//! converts their data to and from owned compounds, like the one sent in
+//! `CBlockEntityData`.
+
+use std::io::Cursor;
+
+use simdnbt::borrow::BaseNbtCompound as BorrowedNbtCompound;
+use simdnbt::borrow::read_compound as read_borrowed_compound;
+use simdnbt::owned::NbtCompound;
+use thiserror::Error;
+
+/// Errors that can occur while loading block entity data from NBT.
+#[derive(Debug, Error)]
+pub enum BlockEntityNbtError {
+    /// The compound could not be read back as borrowed NBT.
+    #[error("Malformed NBT: {0}")]
+    Invalid(String),
+}
+
+/// Block entity data that can be turned into an owned NBT compound and back.
+///
+/// `to_nbt` gives the same compound as `save_additional`, so it can be sent
+/// in `CBlockEntityData`.
+pub trait BlockEntityNbt: Sized {
+    /// Saves the block entity's data to a new compound.
+    #[must_use]
+    fn to_nbt(&self) -> NbtCompound;
+
+    /// Creates a block entity from its saved data.
+    ///
+    /// The block entity isn't in a world yet: it has no level and sits at the
+    /// origin with an air state.
+    fn from_nbt(nbt: &NbtCompound) -> Result<Self, BlockEntityNbtError>;
+}
+
+/// Reads `nbt` as borrowed NBT and passes it to `read`, the way block
+/// entities load from disk.
+pub fn with_borrowed<R>(
+    nbt: &NbtCompound,
+    read: impl FnOnce(&BorrowedNbtCompound<'_>) -> R,
+) -> Result<R, BlockEntityNbtError> {
+    let mut bytes = Vec::new();
+    nbt.write(&mut bytes);
+    let borrowed = read_borrowed_compound(&mut Cursor::new(&bytes))
+        .map_err(|err| BlockEntityNbtError::Invalid(err.to_string()))?;
+    Ok(read(&borrowed))
+}
@@ -11,7 +11,10 @@ use steel_utils::locks::SyncMutex;
 use steel_utils::{BlockPos, BlockStateId};
 
 use super::SharedBlockEntity;
-use super::entities::{BarrelBlockEntity, SignBlockEntity};
+use super::entities::{
+    BarrelBlockEntity, BrewingStandBlockEntity, ChestBlockEntity, EnchantingTableBlockEntity,
+    FurnaceBlockEntity, HopperBlockEntity, SculkSensorBlockEntity, SignBlockEntity,
+};
 use crate::world::World;
 
 /// Factory function type for creating block entities.
@@ -151,6 +154,80 @@ pub fn init_block_entities() {
         Arc::new(SyncMutex::new(BarrelBlockEntity::new(level, pos, state)))
     });
 
+    // Register chest block entity factories
+    registry.register(vanilla_block_entity_types::CHEST, |level, pos, state| {
+        Arc::new(SyncMutex::new(ChestBlockEntity::new(level, pos, state)))
+    });
+    registry.register(
+        vanilla_block_entity_types::TRAPPED_CHEST,
+        |level, pos, state| {
+            Arc::new(SyncMutex::new(ChestBlockEntity::new_trapped(
+                level, pos, state,
+            )))
+        },
+    );
+
+    // Register furnace block entity factories
+    registry.register(vanilla_block_entity_types::FURNACE, |level, pos, state| {
+        Arc::new(SyncMutex::new(FurnaceBlockEntity::new(level, pos, state)))
+    });
+    registry.register(
+        vanilla_block_entity_types::BLAST_FURNACE,
+        |level, pos, state| {
+            Arc::new(SyncMutex::new(FurnaceBlockEntity::new_blast_furnace(
+                level, pos, state,
+            )))
+        },
+    );
+    registry.register(vanilla_block_entity_types::SMOKER, |level, pos, state| {
+        Arc::new(SyncMutex::new(FurnaceBlockEntity::new_smoker(
+            level, pos, state,
+        )))
+    });
+
+    // Register brewing stand block entity factory
+    registry.register(
+        vanilla_block_entity_types::BREWING_STAND,
+        |level, pos, state| {
+            Arc::new(SyncMutex::new(BrewingStandBlockEntity::new(
+                level, pos, state,
+            )))
+        },
+    );
+
+    // Register enchanting table block entity factory
+    registry.register(
+        vanilla_block_entity_types::ENCHANTING_TABLE,
+        |level, pos, state| {
+            Arc::new(SyncMutex::new(EnchantingTableBlockEntity::new(
+                level, pos, state,
+            )))
+        },
+    );
+
+    // Register hopper block entity factory
+    registry.register(vanilla_block_entity_types::HOPPER, |level, pos, state| {
+        Arc::new(SyncMutex::new(HopperBlockEntity::new(level, pos, state)))
+    });
+
+    // Register sculk sensor block entity factories
+    registry.register(
+        vanilla_block_entity_types::SCULK_SENSOR,
+        |level, pos, state| {
+            Arc::new(SyncMutex::new(SculkSensorBlockEntity::new(
+                level, pos, state,
+            )))
+        },
+    );
+    registry.register(
+        vanilla_block_entity_types::CALIBRATED_SCULK_SENSOR,
+        |level, pos, state| {
+            Arc::new(SyncMutex::new(SculkSensorBlockEntity::new_calibrated(
+                level, pos, state,
+            )))
+        },
+    );
+
     assert!(
         BLOCK_ENTITIES.set(registry).is_ok(),
         "Block entity registry already initialized"
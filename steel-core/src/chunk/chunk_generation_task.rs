@@ -8,6 +8,7 @@ use std::{
         Arc,
         atomic::{AtomicBool, Ordering},
     },
+    time::Instant,
 };
 
 use futures::future::join_all;
@@ -20,6 +21,7 @@ use crate::chunk::{
     chunk_map::ChunkMap,
     chunk_pyramid::{GENERATION_PYRAMID, LOADING_PYRAMID},
 };
+use crate::server::metrics::METRICS;
 
 /// A pre-filled 2D cache of elements, efficient for async creation.
 pub struct StaticCache2D<T> {
@@ -306,12 +308,20 @@ impl ChunkGenerationTask {
         //    self.pos,
         //    self.target_status
         //);
+        let start = Instant::now();
         loop {
             self.wait_for_scheduled_layers().await;
 
-            if self.marked_for_cancel.load(Ordering::Relaxed)
-                || *self.scheduled_status.lock() == Some(self.target_status)
-            {
+            let cancelled = self.marked_for_cancel.load(Ordering::Relaxed);
+            let reached_target = *self.scheduled_status.lock() == Some(self.target_status);
+            if cancelled || reached_target {
+                if reached_target
+                    && !cancelled
+                    && self.target_status == ChunkStatus::Full
+                    && self.needs_generation.load(Ordering::Relaxed)
+                {
+                    METRICS.chunk_generated(start.elapsed());
+                }
                 let center_chunk = self.cache.get(self.pos.0.x, self.pos.0.y);
                 center_chunk.cancel_generation_task();
                 return;
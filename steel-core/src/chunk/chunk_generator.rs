@@ -2,6 +2,7 @@
 
 use crate::chunk::chunk_access::ChunkAccess;
 use enum_dispatch::enum_dispatch;
+use steel_registry::structure_set::StructureSetRef;
 
 /// A trait for generating chunks.
 #[enum_dispatch]
@@ -23,4 +24,11 @@ pub trait ChunkGenerator: Send + Sync {
 
     /// Applies biome decorations to the chunk.
     fn apply_biome_decorations(&self, chunk: &ChunkAccess);
+
+    /// Returns the lowest y above the generated terrain at the given column,
+    /// like vanilla's `getBaseHeight`.
+    fn base_height(&self, x: i32, z: i32, min_y: i32) -> i32;
+
+    /// Returns the structure sets this generator places.
+    fn structure_sets(&self) -> &[StructureSetRef];
 }
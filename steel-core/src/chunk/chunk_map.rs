@@ -15,9 +15,9 @@ use rustc_hash::FxBuildHasher;
 use steel_protocol::packets::game::{
     BlockChange, CBlockUpdate, CSectionBlocksUpdate, CSetChunkCenter,
 };
-use steel_registry::{REGISTRY, dimension_type::DimensionTypeRef, vanilla_blocks};
+use steel_registry::dimension_type::DimensionTypeRef;
 use steel_utils::{BlockPos, ChunkPos, SectionPos, locks::SyncMutex};
-use tokio::runtime::Runtime;
+use tokio::{runtime::Runtime, time::sleep};
 use tokio_util::task::TaskTracker;
 use tracing::instrument;
 
@@ -26,16 +26,19 @@ use crate::chunk::chunk_ticket_manager::{
     ChunkTicketManager, LevelChange, MAX_VIEW_DISTANCE, is_full,
 };
 use crate::chunk::player_chunk_view::PlayerChunkView;
-use crate::chunk::world_gen_context::ChunkGeneratorType;
 use crate::chunk::{chunk_access::ChunkAccess, chunk_ticket_manager::is_ticked};
 use crate::chunk::{
-    chunk_access::ChunkStatus, chunk_generation_task::ChunkGenerationTask,
-    flat_chunk_generator::FlatChunkGenerator, world_gen_context::WorldGenContext,
+    chunk_access::ChunkStatus, chunk_generation_task::ChunkGenerationTask, generators,
+    world_gen_context::WorldGenContext,
 };
-use crate::chunk_saver::RegionManager;
+use crate::chunk_saver::{PersistentEntity, RegionManager};
+use crate::config::STEEL_CONFIG;
 use crate::player::Player;
 use crate::world::World;
 
+/// How often `load_area` checks whether the chunk holders it waits for exist yet.
+const AREA_LOAD_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Timing information for chunk map tick operations.
 #[derive(Debug, Default)]
 pub struct ChunkMapTickTimings {
@@ -99,16 +102,9 @@ impl ChunkMap {
         chunk_runtime: Arc<Runtime>,
         world: Weak<World>,
         dimension: &DimensionTypeRef,
+        seed: i64,
     ) -> Self {
-        let generator = Arc::new(ChunkGeneratorType::Flat(FlatChunkGenerator::new(
-            REGISTRY
-                .blocks
-                .get_default_state_id(vanilla_blocks::BEDROCK), // Bedrock
-            REGISTRY.blocks.get_default_state_id(vanilla_blocks::DIRT), // Dirt
-            REGISTRY
-                .blocks
-                .get_default_state_id(vanilla_blocks::GRASS_BLOCK), // Grass Block
-        )));
+        let generator = Arc::new(generators::from_config(dimension, seed));
 
         Self {
             chunks: scc::HashMap::default(),
@@ -117,7 +113,14 @@ impl ChunkMap {
             task_tracker: TaskTracker::new(),
             chunk_tickets: SyncMutex::new(ChunkTicketManager::new()),
             world_gen_context: Arc::new(WorldGenContext::new(generator, world)),
-            generation_pool: Arc::new(ThreadPoolBuilder::new().build().unwrap()),
+            generation_pool: Arc::new(
+                ThreadPoolBuilder::new()
+                    // 0 lets rayon pick one thread per core
+                    .num_threads(STEEL_CONFIG.load().chunk_generation_threads.unwrap_or(0))
+                    .thread_name(|index| format!("chunk-gen-{index}"))
+                    .build()
+                    .unwrap(),
+            ),
             //tick_pool: Arc::new(ThreadPoolBuilder::new().build().unwrap()),
             chunk_runtime,
             region_manager: Arc::new(RegionManager::new(format!("world/{}", dimension.key.path))),
@@ -471,10 +474,16 @@ impl ChunkMap {
         timings
     }
 
-    /// Saves a chunk to disk. Does not remove from `unloading_chunks`.
+    /// Saves a chunk to disk with its `entities`, which were saved before.
+    /// Does not remove from `unloading_chunks`.
     #[allow(clippy::missing_panics_doc, clippy::unwrap_used)]
-    #[instrument(level = "trace", skip(self, chunk_holder), fields(chunk = ?chunk_holder.get_pos()))]
-    async fn save_chunk(&self, chunk_holder: &Arc<ChunkHolder>) {
+    #[instrument(level = "trace", skip(self, chunk_holder, entities), fields(chunk = ?chunk_holder.get_pos()))]
+    async fn save_chunk(
+        &self,
+        chunk_holder: &Arc<ChunkHolder>,
+        entities: Vec<PersistentEntity>,
+        entities_changed: bool,
+    ) {
         // Prepare chunk data while holding the lock, then release before async I/O
         let prepared = {
             let Some(chunk_guard) = chunk_holder.try_chunk(ChunkStatus::StructureStarts) else {
@@ -486,7 +495,8 @@ impl ChunkMap {
                 .persisted_status()
                 .expect("The check above confirmed it exists");
 
-            let prepared = RegionManager::prepare_chunk_save(&chunk_guard);
+            let prepared =
+                RegionManager::prepare_chunk_save(&chunk_guard, entities, entities_changed);
 
             // Clear dirty flag while we still have the lock (only if we're actually saving)
             if prepared.is_some() {
@@ -508,11 +518,23 @@ impl ChunkMap {
         }
     }
 
+    /// Saves the entities in the chunk at `pos`, to store them with it, and
+    /// returns whether they changed since they were last stored. Entities
+    /// are saved before taking the chunk lock, they read blocks while ticking.
+    fn save_entities(&self, pos: ChunkPos) -> (Vec<PersistentEntity>, bool) {
+        self.world_gen_context
+            .weak_world()
+            .upgrade()
+            .map(|world| world.save_entities(pos))
+            .unwrap_or_default()
+    }
+
     /// Processes chunks that are pending unload.
     ///
     /// Iterates over `unloading_chunks`. For each chunk with `strong_count == 1`:
-    /// - If dirty: spawn save task (keep until saved and clean)
-    /// - If not dirty: release region handle and remove
+    /// - If dirty or its entities changed: spawn save task (keep until saved
+    ///   and clean)
+    /// - Otherwise: unload its entities, release region handle and remove
     #[instrument(level = "trace", skip(self))]
     pub fn process_unloads(self: &Arc<Self>) {
         self.unloading_chunks.retain_sync(|pos, holder| {
@@ -522,17 +544,26 @@ impl ChunkMap {
                     .try_chunk(ChunkStatus::StructureStarts)
                     .is_some_and(|chunk| chunk.is_dirty());
 
-                if is_dirty {
+                let (entities, entities_changed) = self.save_entities(*pos);
+
+                if is_dirty || entities_changed {
                     // Save the chunk, keep until next tick when it's clean
                     let holder_clone = holder.clone();
                     let map_clone = self.clone();
                     self.task_tracker.spawn(async move {
-                        map_clone.save_chunk(&holder_clone).await;
+                        map_clone
+                            .save_chunk(&holder_clone, entities, entities_changed)
+                            .await;
                     });
                     true // keep until clean
                 } else {
-                    // Clean and no refs - release region handle and remove
+                    // Clean and no refs - release region handle and remove. The
+                    // chunk's entities didn't change since they were saved with
+                    // it.
                     let pos = *pos;
+                    if let Some(world) = self.world_gen_context.weak_world().upgrade() {
+                        world.unload_entities(pos);
+                    }
                     let map_clone = self.clone();
                     self.task_tracker.spawn(async move {
                         if let Err(e) = map_clone.region_manager.release_chunk(pos).await {
@@ -561,6 +592,10 @@ impl ChunkMap {
             let connection = &player.connection;
             let world = self.world_gen_context.world();
 
+            // Track chunks for PlayerAreaMap update
+            let mut added_chunks = Vec::new();
+            let mut removed_chunks = Vec::new();
+
             if let Some(last_view) = last_view_guard.as_ref() {
                 if last_view.center != new_view.center
                     || last_view.view_distance != new_view.view_distance
@@ -580,10 +615,6 @@ impl ChunkMap {
                     });
                 }
 
-                // Track chunks for PlayerAreaMap update
-                let mut added_chunks = Vec::new();
-                let mut removed_chunks = Vec::new();
-
                 // We lock here to ensure we have unique access for the duration of the diff
                 let mut chunk_sender = player.chunk_sender.lock();
                 PlayerChunkView::difference(
@@ -613,9 +644,17 @@ impl ChunkMap {
                     MAX_VIEW_DISTANCE.saturating_sub(new_view.view_distance),
                 );
 
+                // The client only accepts chunks around its chunk center, which
+                // starts out at the origin of every world it enters
+                connection.send_packet(CSetChunkCenter {
+                    x: new_view.center.0.x,
+                    y: new_view.center.0.y,
+                });
+
                 let mut chunk_sender = player.chunk_sender.lock();
                 new_view.for_each(|pos| {
                     chunk_sender.mark_chunk_pending_to_send(pos);
+                    added_chunks.push(pos);
                 });
                 drop(chunk_sender);
 
@@ -624,9 +663,55 @@ impl ChunkMap {
             }
 
             *last_view_guard = Some(new_view);
+            drop(last_view_guard);
+            drop(chunk_tickets);
+
+            // The entities in the chunks that came into view are sent along
+            // with them
+            world.update_entity_view(player, &added_chunks, &removed_chunks);
         }
     }
 
+    /// Loads the chunks within `radius` chunks of `center` and waits until they are full.
+    ///
+    /// A ticket keeps them loaded until [`Self::release_area`] is called with
+    /// the same arguments. Returns `false` if any of them failed to load.
+    pub async fn load_area(&self, center: ChunkPos, radius: u8) -> bool {
+        self.chunk_tickets
+            .lock()
+            .add_ticket(center, MAX_VIEW_DISTANCE.saturating_sub(radius));
+
+        let radius = i32::from(radius);
+        for dx in -radius..=radius {
+            for dz in -radius..=radius {
+                let pos = ChunkPos::new(center.0.x + dx, center.0.y + dz);
+                // The holder is only created once the next tick processes the ticket
+                let holder = loop {
+                    if let Some(holder) = self
+                        .chunks
+                        .read_async(&pos, |_, holder| holder.clone())
+                        .await
+                    {
+                        break holder;
+                    }
+                    sleep(AREA_LOAD_POLL_INTERVAL).await;
+                };
+                let loaded = holder.await_chunk(ChunkStatus::Full).await.is_some();
+                if !loaded {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Removes the ticket added by [`Self::load_area`], letting the chunks unload.
+    pub fn release_area(&self, center: ChunkPos, radius: u8) {
+        self.chunk_tickets
+            .lock()
+            .remove_ticket(center, MAX_VIEW_DISTANCE.saturating_sub(radius));
+    }
+
     /// Removes a player from the chunk map.
     pub fn remove_player(&self, player: &Player) {
         // Okay to lock sync lock here cause it has low contention
@@ -672,6 +757,7 @@ impl ChunkMap {
 
         // Save all chunks that have data
         for holder in &all_chunks {
+            let (entities, entities_changed) = self.save_entities(holder.get_pos());
             let prepared = {
                 let Some(chunk) = holder.try_chunk(ChunkStatus::StructureStarts) else {
                     continue;
@@ -679,7 +765,9 @@ impl ChunkMap {
                 let Some(status) = holder.persisted_status() else {
                     continue;
                 };
-                let Some(prepared) = RegionManager::prepare_chunk_save(&chunk) else {
+                let Some(prepared) =
+                    RegionManager::prepare_chunk_save(&chunk, entities, entities_changed)
+                else {
                     continue; // Not dirty
                 };
                 chunk.clear_dirty();
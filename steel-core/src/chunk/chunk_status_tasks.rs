@@ -10,6 +10,7 @@ use crate::chunk::{
     chunk_pyramid::ChunkStep,
     proto_chunk::ProtoChunk,
     section::{ChunkSection, Sections},
+    structures::{self, GeneratorTerrain},
     world_gen_context::WorldGenContext,
 };
 
@@ -74,12 +75,17 @@ impl ChunkStatusTasks {
         Ok(())
     }
 
+    #[allow(clippy::missing_panics_doc)]
     pub fn generate_biomes(
-        _context: Arc<WorldGenContext>,
+        context: Arc<WorldGenContext>,
         _step: &ChunkStep,
         _cache: &Arc<StaticCache2D<Arc<ChunkHolder>>>,
-        _holder: Arc<ChunkHolder>,
+        holder: Arc<ChunkHolder>,
     ) -> Result<(), anyhow::Error> {
+        let chunk = holder
+            .try_chunk(ChunkStatus::StructureReferences)
+            .expect("Chunk not found at status StructureReferences");
+        context.generator.create_biomes(&chunk);
         Ok(())
     }
 
@@ -115,12 +121,28 @@ impl ChunkStatusTasks {
         Ok(())
     }
 
+    #[allow(clippy::missing_panics_doc)]
     pub fn generate_features(
-        _context: Arc<WorldGenContext>,
+        context: Arc<WorldGenContext>,
         _step: &ChunkStep,
         _cache: &Arc<StaticCache2D<Arc<ChunkHolder>>>,
-        _holder: Arc<ChunkHolder>,
+        holder: Arc<ChunkHolder>,
     ) -> Result<(), anyhow::Error> {
+        let chunk = holder
+            .try_chunk(ChunkStatus::Carvers)
+            .expect("Chunk not found at status Carvers");
+        let generator = &*context.generator;
+        let terrain = GeneratorTerrain {
+            generator,
+            min_y: context.min_y(),
+        };
+        structures::place_structures(
+            &chunk,
+            context.world().seed(),
+            generator.structure_sets(),
+            &terrain,
+        );
+        generator.apply_biome_decorations(&chunk);
         Ok(())
     }
 
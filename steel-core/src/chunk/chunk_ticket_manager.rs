@@ -27,7 +27,16 @@ pub fn is_full(level: u8) -> bool {
 
 #[must_use]
 pub fn is_ticked(level: u8) -> bool {
-    level <= MAX_VIEW_DISTANCE - STEEL_CONFIG.view_distance + STEEL_CONFIG.simulation_distance
+    level <= ticking_level(0)
+}
+
+/// The ticket level that keeps chunks up to `radius` away from the ticket
+/// ticking, matching how far a player's simulation distance reaches.
+#[must_use]
+pub fn ticking_level(radius: u8) -> u8 {
+    (MAX_VIEW_DISTANCE - STEEL_CONFIG.load().view_distance
+        + STEEL_CONFIG.load().simulation_distance)
+        .saturating_sub(radius)
 }
 
 #[must_use]
@@ -0,0 +1,267 @@
+//! End generation: the main island around the origin and the outer islands
+//! past 1000 blocks, shaped by vanilla's `EndIslandDensityFunction`.
+//!
+//! Vanilla blends the island height with 3D noise. Until there is a noise
+//! generator, each column is filled between a top and bottom interpolated
+//! from the island height, which gives the same island outlines.
+
+use steel_registry::{REGISTRY, structure_set::StructureSetRef, vanilla_blocks};
+use steel_utils::noise::SimplexNoise;
+use steel_utils::random::{Random, legacy_random::LegacyRandom};
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+
+use crate::chunk::{
+    chunk_access::ChunkAccess, chunk_generator::ChunkGenerator, paletted_container::BiomePalette,
+};
+
+/// Where players arriving in the End are placed, above the obsidian platform.
+pub const END_SPAWN_POINT: BlockPos = BlockPos::new(8, 64, 8);
+
+/// Blocks per island height sample, vanilla's End cell width.
+const CELL_WIDTH: i32 = 8;
+/// The island height above which there is land.
+const LAND_HEIGHT: f32 = 8.0;
+/// The y islands are centered on.
+const ISLAND_Y: i32 = 56;
+/// How far the tallest islands reach above and below `ISLAND_Y`.
+const ISLAND_TOP: f32 = 8.0;
+const ISLAND_BOTTOM: f32 = 40.0;
+/// Chunks within this squared distance of the origin are the main island's biome.
+const MAIN_ISLAND_CHUNKS_SQ: i64 = 64 * 64;
+
+/// A chunk generator for the End.
+pub struct TheEndGenerator {
+    island_noise: SimplexNoise,
+    end_stone: BlockStateId,
+    obsidian: BlockStateId,
+    main_biome: u8,
+    outer_biome: u8,
+    min_y: i32,
+}
+
+impl TheEndGenerator {
+    /// Creates a generator for the given world seed.
+    #[must_use]
+    pub fn new(seed: i64, min_y: i32) -> Self {
+        // Vanilla: EndIslandDensityFunction
+        let mut random = LegacyRandom::from_seed(seed as u64);
+        random.consume_count(17292);
+
+        let biome = |name: &'static str| {
+            REGISTRY
+                .biomes
+                .by_key(&Identifier::vanilla_static(name))
+                .map_or(0, |entry| *REGISTRY.biomes.get_id(entry) as u8)
+        };
+
+        Self {
+            island_noise: SimplexNoise::new(&mut random),
+            end_stone: vanilla_blocks::END_STONE.default_state(),
+            obsidian: vanilla_blocks::OBSIDIAN.default_state(),
+            main_biome: biome("the_end"),
+            outer_biome: biome("small_end_islands"),
+            min_y,
+        }
+    }
+
+    /// The island height of an 8 block cell, from -100 to 80. There is land
+    /// where it is above 8.
+    ///
+    /// Matches vanilla's `EndIslandDensityFunction.getHeightValue`, including
+    /// its float math and integer division.
+    fn height_value(&self, section_x: i32, section_z: i32) -> f32 {
+        let chunk_x = section_x / 2;
+        let chunk_z = section_z / 2;
+        let sub_section_x = section_x % 2;
+        let sub_section_z = section_z % 2;
+
+        let distance = ((section_x * section_x + section_z * section_z) as f32).sqrt();
+        let mut height = (100.0 - distance * 8.0).clamp(-100.0, 80.0);
+
+        for xo in -12..=12 {
+            for zo in -12..=12 {
+                let total_x = i64::from(chunk_x + xo);
+                let total_z = i64::from(chunk_z + zo);
+                // The outer islands start 1024 blocks out
+                if total_x * total_x + total_z * total_z <= 4096
+                    || self.island_noise.get_value(total_x as f64, total_z as f64)
+                        >= f64::from(-0.9_f32)
+                {
+                    continue;
+                }
+
+                let island_size =
+                    ((total_x as f32).abs() * 3439.0 + (total_z as f32).abs() * 147.0) % 13.0 + 9.0;
+                let xd = (sub_section_x - xo * 2) as f32;
+                let zd = (sub_section_z - zo * 2) as f32;
+                let island_height =
+                    (100.0 - (xd * xd + zd * zd).sqrt() * island_size).clamp(-100.0, 80.0);
+                height = height.max(island_height);
+            }
+        }
+        height
+    }
+
+    /// The lowest and highest filled y of a column with the given island height.
+    fn column_range(height: f32) -> Option<(i32, i32)> {
+        if height <= LAND_HEIGHT {
+            return None;
+        }
+        let scale = (height - LAND_HEIGHT) / (80.0 - LAND_HEIGHT);
+        Some((
+            ISLAND_Y - (scale * ISLAND_BOTTOM).round() as i32,
+            ISLAND_Y + (scale * ISLAND_TOP).round() as i32 - 1,
+        ))
+    }
+
+    /// The island height at a block column, interpolated between the corners
+    /// of its cell like vanilla interpolates the density.
+    fn interpolated_height(&self, x: i32, z: i32) -> f32 {
+        let cell_x = x.div_euclid(CELL_WIDTH);
+        let cell_z = z.div_euclid(CELL_WIDTH);
+        let fx = x.rem_euclid(CELL_WIDTH) as f32 / CELL_WIDTH as f32;
+        let fz = z.rem_euclid(CELL_WIDTH) as f32 / CELL_WIDTH as f32;
+
+        bilerp(
+            fx,
+            fz,
+            [
+                [
+                    self.height_value(cell_x, cell_z),
+                    self.height_value(cell_x, cell_z + 1),
+                ],
+                [
+                    self.height_value(cell_x + 1, cell_z),
+                    self.height_value(cell_x + 1, cell_z + 1),
+                ],
+            ],
+        )
+    }
+}
+
+impl ChunkGenerator for TheEndGenerator {
+    fn create_structures(&self, _chunk: &ChunkAccess) {}
+
+    fn create_biomes(&self, chunk: &ChunkAccess) {
+        let pos = chunk.pos();
+        let (x, z) = (i64::from(pos.0.x), i64::from(pos.0.y));
+        let biome = if x * x + z * z <= MAIN_ISLAND_CHUNKS_SQ {
+            self.main_biome
+        } else {
+            self.outer_biome
+        };
+        for section in &chunk.sections().sections {
+            section.write().biomes = BiomePalette::Homogeneous(biome);
+        }
+    }
+
+    fn fill_from_noise(&self, chunk: &ChunkAccess) {
+        let pos = chunk.pos();
+        let (base_x, base_z) = (pos.0.x * 16, pos.0.y * 16);
+
+        // The corners of the chunk's two by two cells
+        let mut corners = [[0.0; 3]; 3];
+        for (i, row) in corners.iter_mut().enumerate() {
+            for (j, height) in row.iter_mut().enumerate() {
+                *height = self.height_value(
+                    base_x / CELL_WIDTH + i as i32,
+                    base_z / CELL_WIDTH + j as i32,
+                );
+            }
+        }
+
+        for x in 0..16 {
+            for z in 0..16 {
+                let (cell_x, cell_z) = (x / 8, z / 8);
+                let fx = (x % 8) as f32 / CELL_WIDTH as f32;
+                let fz = (z % 8) as f32 / CELL_WIDTH as f32;
+                let height = bilerp(
+                    fx,
+                    fz,
+                    [
+                        [corners[cell_x][cell_z], corners[cell_x][cell_z + 1]],
+                        [corners[cell_x + 1][cell_z], corners[cell_x + 1][cell_z + 1]],
+                    ],
+                );
+
+                let Some((bottom, top)) = Self::column_range(height) else {
+                    continue;
+                };
+                for y in bottom..=top {
+                    chunk.set_relative_block(x, (y - self.min_y) as usize, z, self.end_stone);
+                }
+            }
+        }
+
+        // The flat obsidian platform players arrive on
+        if pos.0.x == 0 && pos.0.y == 0 {
+            let y = (END_SPAWN_POINT.y() - 1 - self.min_y) as usize;
+            for x in END_SPAWN_POINT.x() - 2..=END_SPAWN_POINT.x() + 2 {
+                for z in END_SPAWN_POINT.z() - 2..=END_SPAWN_POINT.z() + 2 {
+                    chunk.set_relative_block(x as usize, y, z as usize, self.obsidian);
+                }
+            }
+        }
+    }
+
+    fn build_surface(&self, _chunk: &ChunkAccess) {}
+
+    fn apply_carvers(&self, _chunk: &ChunkAccess) {}
+
+    // TODO: End spikes, the exit portal and chorus plants
+    fn apply_biome_decorations(&self, _chunk: &ChunkAccess) {}
+
+    fn base_height(&self, x: i32, z: i32, min_y: i32) -> i32 {
+        Self::column_range(self.interpolated_height(x, z)).map_or(min_y, |(_, top)| top + 1)
+    }
+
+    fn structure_sets(&self) -> &[StructureSetRef] {
+        &[]
+    }
+}
+
+/// Interpolates between the heights at the corners of a cell, indexed `[x][z]`.
+fn bilerp(fx: f32, fz: f32, corners: [[f32; 2]; 2]) -> f32 {
+    let lerp = |t: f32, a: f32, b: f32| a + t * (b - a);
+    lerp(
+        fx,
+        lerp(fz, corners[0][0], corners[0][1]),
+        lerp(fz, corners[1][0], corners[1][1]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn height_value_matches_vanilla() {
+        let generator = TheEndGenerator {
+            island_noise: {
+                let mut random = LegacyRandom::from_seed(42);
+                random.consume_count(17292);
+                SimplexNoise::new(&mut random)
+            },
+            end_stone: BlockStateId(0),
+            obsidian: BlockStateId(0),
+            main_biome: 0,
+            outer_biome: 0,
+            min_y: 0,
+        };
+
+        // Values from vanilla's EndIslandDensityFunction with seed 42
+        let expected = [
+            ((0, 0), 80.0),
+            ((3, -2), 71.155_594),
+            ((13, 0), -4.0),
+            ((200, 0), -99.218_475),
+            ((-180, 75), 55.0),
+            ((250, -260), 43.079_002),
+            ((-333, -444), 67.0),
+            ((1000, 37), -72.884_37),
+        ];
+        for ((x, z), height) in expected {
+            assert_eq!(generator.height_value(x, z).to_bits(), f32::to_bits(height));
+        }
+    }
+}
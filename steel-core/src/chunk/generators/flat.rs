@@ -0,0 +1,198 @@
+//! Flat world generation, matching vanilla's `FlatLevelSource`.
+//!
+//! The world is made of layers of blocks stacked from the bottom of the world,
+//! described by a preset string like vanilla's flat world customization:
+//! `minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block;minecraft:plains`.
+//! The part after the `;` picks the biome and may be left out.
+
+use std::iter;
+
+use steel_registry::{
+    REGISTRY, blocks::block_state_ext::BlockStateExt, structure_set::StructureSetRef,
+};
+use steel_utils::{BlockStateId, Identifier};
+
+use crate::chunk::{
+    chunk_access::ChunkAccess, chunk_generator::ChunkGenerator, paletted_container::BiomePalette,
+};
+
+/// The preset of vanilla's default flat world.
+pub const DEFAULT_PRESET: &str =
+    "minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block;minecraft:plains";
+
+const DEFAULT_BIOME: Identifier = Identifier::vanilla_static("plains");
+
+/// A chunk generator that generates a flat world.
+pub struct FlatWorldGenerator {
+    /// The block of each layer, from the bottom of the world up.
+    pub layers: Vec<BlockStateId>,
+    /// Registry id of the biome filling the world.
+    pub biome: u8,
+    /// The structure sets placed on top of the layers.
+    pub structure_sets: Vec<StructureSetRef>,
+}
+
+impl FlatWorldGenerator {
+    /// Creates a generator from a flat preset, keeping at most `height` layers.
+    ///
+    /// With `generate_structures`, villages are placed like in vanilla's
+    /// default flat world.
+    ///
+    /// # Errors
+    /// Returns an error if the preset is malformed or names an unknown block or biome.
+    pub fn from_preset(
+        preset: &str,
+        generate_structures: bool,
+        height: i32,
+    ) -> Result<Self, String> {
+        let (layers, biome) = match preset.split_once(';') {
+            Some((layers, biome)) => (layers, Some(biome.trim())),
+            None => (preset, None),
+        };
+
+        let mut states = Vec::new();
+        for (block, count) in parse_layers(layers)? {
+            let block = REGISTRY
+                .blocks
+                .by_key(&block)
+                .ok_or_else(|| format!("Unknown block {block} in flat preset"))?;
+            let state = REGISTRY.blocks.get_default_state_id(block);
+            states.extend(iter::repeat_n(state, count as usize));
+        }
+        states.truncate(height.max(0) as usize);
+
+        let biome = match biome {
+            Some(biome) => parse_identifier(biome)?,
+            None => DEFAULT_BIOME,
+        };
+        let biome = REGISTRY
+            .biomes
+            .by_key(&biome)
+            .map(|entry| *REGISTRY.biomes.get_id(entry) as u8)
+            .ok_or_else(|| format!("Unknown biome {biome} in flat preset"))?;
+
+        // The default flat preset only keeps villages
+        let structure_sets = if generate_structures {
+            REGISTRY
+                .structure_sets
+                .by_key(&Identifier::vanilla_static("villages"))
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            layers: states,
+            biome,
+            structure_sets,
+        })
+    }
+
+    /// Creates an empty world in the void biome, like vanilla's "The Void" preset.
+    #[must_use]
+    pub fn void() -> Self {
+        let biome = REGISTRY
+            .biomes
+            .by_key(&Identifier::vanilla_static("the_void"))
+            .map_or(0, |entry| *REGISTRY.biomes.get_id(entry) as u8);
+        Self {
+            layers: Vec::new(),
+            biome,
+            structure_sets: Vec::new(),
+        }
+    }
+}
+
+impl ChunkGenerator for FlatWorldGenerator {
+    fn create_structures(&self, _chunk: &ChunkAccess) {}
+
+    fn create_biomes(&self, chunk: &ChunkAccess) {
+        for section in &chunk.sections().sections {
+            section.write().biomes = BiomePalette::Homogeneous(self.biome);
+        }
+    }
+
+    fn fill_from_noise(&self, chunk: &ChunkAccess) {
+        for (y, &state) in self.layers.iter().enumerate() {
+            if state.is_air() {
+                continue;
+            }
+            for x in 0..16 {
+                for z in 0..16 {
+                    chunk.set_relative_block(x, y, z, state);
+                }
+            }
+        }
+    }
+
+    fn build_surface(&self, _chunk: &ChunkAccess) {}
+
+    fn apply_carvers(&self, _chunk: &ChunkAccess) {}
+
+    fn apply_biome_decorations(&self, _chunk: &ChunkAccess) {}
+
+    fn base_height(&self, _x: i32, _z: i32, min_y: i32) -> i32 {
+        self.layers
+            .iter()
+            .rposition(|state| !state.is_air())
+            .map_or(min_y, |top| min_y + top as i32 + 1)
+    }
+
+    fn structure_sets(&self) -> &[StructureSetRef] {
+        &self.structure_sets
+    }
+}
+
+/// Parses an identifier, defaulting to the `minecraft` namespace like vanilla.
+fn parse_identifier(id: &str) -> Result<Identifier, String> {
+    let parsed = if id.contains(':') {
+        id.parse()
+    } else {
+        format!("minecraft:{id}").parse()
+    };
+    parsed.map_err(|err| format!("{err}: {id}"))
+}
+
+/// Parses the comma separated layers of a flat preset, each a block with an
+/// optional `count*` prefix.
+fn parse_layers(layers: &str) -> Result<Vec<(Identifier, u32)>, String> {
+    layers
+        .split(',')
+        .map(|layer| {
+            let layer = layer.trim();
+            let (count, block) = match layer.split_once('*') {
+                Some((count, block)) => {
+                    let count = count
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid layer count in {layer}"))?;
+                    (count, block.trim())
+                }
+                None => (1, layer),
+            };
+            Ok((parse_identifier(block)?, count))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_layer_counts() {
+        let layers = parse_layers("minecraft:bedrock,2*minecraft:dirt,grass_block")
+            .expect("preset should parse");
+        assert_eq!(
+            layers,
+            vec![
+                (Identifier::vanilla_static("bedrock"), 1),
+                (Identifier::vanilla_static("dirt"), 2),
+                (Identifier::vanilla_static("grass_block"), 1),
+            ]
+        );
+        assert!(parse_layers("x*minecraft:dirt").is_err());
+        assert!(parse_layers("minecraft:Dirt").is_err());
+    }
+}
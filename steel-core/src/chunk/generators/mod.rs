@@ -0,0 +1,62 @@
+//! The chunk generators a world can use, picked by `generator_type` in the config.
+
+pub mod end;
+pub mod flat;
+
+use steel_registry::{
+    dimension_type::DimensionTypeRef,
+    vanilla_dimension_types::{THE_END, THE_NETHER},
+};
+
+use crate::{
+    chunk::{
+        generators::{
+            end::TheEndGenerator,
+            flat::{DEFAULT_PRESET, FlatWorldGenerator},
+        },
+        world_gen_context::ChunkGeneratorType,
+    },
+    config::{GeneratorType, STEEL_CONFIG},
+};
+
+/// A flat Nether: netherrack up to y 64 under a bedrock roof at the top of the
+/// logical height, standing in until there is a Nether noise generator.
+const NETHER_PRESET: &str = "minecraft:bedrock,63*minecraft:netherrack,63*minecraft:air,\
+minecraft:bedrock;minecraft:nether_wastes";
+
+/// Creates the chunk generator the config asks for.
+#[must_use]
+pub fn from_config(dimension: &DimensionTypeRef, seed: i64) -> ChunkGeneratorType {
+    if dimension.key == THE_END.key {
+        return ChunkGeneratorType::End(TheEndGenerator::new(seed, dimension.min_y));
+    }
+    if dimension.key == THE_NETHER.key {
+        let generator = FlatWorldGenerator::from_preset(NETHER_PRESET, false, dimension.height)
+            .unwrap_or_else(|_| FlatWorldGenerator::void());
+        return ChunkGeneratorType::Flat(generator);
+    }
+
+    let from_preset = |preset: &str| {
+        FlatWorldGenerator::from_preset(
+            preset,
+            STEEL_CONFIG.load().generate_structures,
+            dimension.height,
+        )
+    };
+
+    let generator = match STEEL_CONFIG.load().generator_type {
+        GeneratorType::Flat => {
+            from_preset(&STEEL_CONFIG.load().generator_settings).unwrap_or_else(|err| {
+                log::warn!("Invalid generator_settings, using the default flat preset: {err}");
+                from_preset(DEFAULT_PRESET).unwrap_or_else(|_| FlatWorldGenerator::void())
+            })
+        }
+        GeneratorType::Void => FlatWorldGenerator::void(),
+        GeneratorType::Noise => {
+            // TODO: Use the noise generator once it exists
+            log::warn!("Noise generation isn't implemented yet, generating a flat world instead");
+            from_preset(DEFAULT_PRESET).unwrap_or_else(|_| FlatWorldGenerator::void())
+        }
+    };
+    ChunkGeneratorType::Flat(generator)
+}
@@ -9,6 +9,8 @@ use rustc_hash::FxHashMap;
 use steel_registry::{REGISTRY, blocks::BlockRef, blocks::block_state_ext::BlockStateExt};
 use steel_utils::{BlockStateId, Identifier};
 
+use crate::chunk::level_chunk::LevelChunk;
+
 /// The different types of heightmaps.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HeightmapType {
@@ -203,6 +205,60 @@ impl Heightmap {
         self.data[index] = (height - self.min_y) as u16;
     }
 
+    /// Computes the `MotionBlocking` height of every column of `chunk`: one
+    /// above the highest block that blocks motion or holds a fluid.
+    #[must_use]
+    pub fn compute_motion_blocking(chunk: &LevelChunk) -> [i16; 256] {
+        Self::compute(HeightmapType::MotionBlocking, chunk)
+    }
+
+    /// Computes the `WorldSurface` height of every column of `chunk`: one
+    /// above the highest non-air block.
+    #[must_use]
+    pub fn compute_world_surface(chunk: &LevelChunk) -> [i16; 256] {
+        Self::compute(HeightmapType::WorldSurface, chunk)
+    }
+
+    /// Computes the height of every column of `chunk` for `map_type` by
+    /// scanning the sections from the top down, skipping empty ones.
+    ///
+    /// Heights are the first available Y coordinate, indexed like the
+    /// heightmap's own data. Columns without a matching block get `min_y`.
+    #[must_use]
+    pub fn compute(map_type: HeightmapType, chunk: &LevelChunk) -> [i16; 256] {
+        let mut heights = [None; 256];
+        for (section_index, section) in chunk.sections.sections.iter().enumerate().rev() {
+            let section = section.read();
+            if section.is_empty() {
+                continue;
+            }
+            let base_y = chunk.min_y() + section_index as i32 * 16;
+            for (index, height) in heights.iter_mut().enumerate() {
+                if height.is_some() {
+                    continue;
+                }
+                let (local_x, local_z) = (index % 16, index / 16);
+                *height = (0..16)
+                    .rev()
+                    .find(|&local_y| {
+                        map_type.is_opaque(section.states.get(local_x, local_y, local_z))
+                    })
+                    .map(|local_y| (base_y + local_y as i32 + 1) as i16);
+            }
+            if heights.iter().all(Option::is_some) {
+                break;
+            }
+        }
+        heights.map(|height| height.unwrap_or(chunk.min_y() as i16))
+    }
+
+    /// Sets the height of every column, as returned by [`Self::compute`].
+    pub fn set_heights(&mut self, heights: &[i16; 256]) {
+        for (index, &height) in heights.iter().enumerate() {
+            self.data[index] = (i32::from(height) - self.min_y) as u16;
+        }
+    }
+
     /// Updates the heightmap when a block changes.
     ///
     /// Returns `true` if the heightmap was modified.
@@ -417,6 +473,24 @@ mod tests {
         assert_eq!(Heightmap::calculate_bits_per_value(16), 5);
     }
 
+    #[test]
+    fn test_set_heights_packs_nine_bits() {
+        let mut heightmap = Heightmap::new(HeightmapType::MotionBlocking, -64, 384);
+        let mut heights = [-64; 256];
+        heights[0] = 320;
+        heights[7] = 63;
+        heightmap.set_heights(&heights);
+
+        assert_eq!(heightmap.get_first_available(0, 0), 320);
+        assert_eq!(heightmap.get_highest_taken(7, 0), 62);
+
+        // Seven 9-bit heights fit in a long
+        let raw = heightmap.get_raw_data();
+        assert_eq!(raw.len(), 37);
+        assert_eq!(raw[0] & 0x1FF, 384);
+        assert_eq!(raw[1] & 0x1FF, 127);
+    }
+
     #[test]
     fn test_get_index() {
         assert_eq!(Heightmap::get_index(0, 0), 0);
@@ -15,18 +15,26 @@ use steel_protocol::packets::game::{
 };
 use steel_registry::{REGISTRY, blocks::block_state_ext::BlockStateExt, vanilla_blocks};
 use steel_utils::{
-    BlockPos, BlockStateId, ChunkPos, codec::BitSet, locks::SyncRwLock, types::UpdateFlags,
+    BlockPos, BlockStateId, ChunkPos, locks::SyncRwLock, math::Vector3, types::UpdateFlags,
 };
 
 use crate::behavior::BLOCK_BEHAVIORS;
 use crate::block_entity::{BlockEntityStorage, SharedBlockEntity};
 use crate::chunk::{
-    heightmap::{ChunkHeightmaps, HeightmapType},
+    heightmap::{ChunkHeightmaps, Heightmap, HeightmapType},
+    light::ChunkLightData,
     proto_chunk::ProtoChunk,
     section::Sections,
 };
+use crate::entity::{self, LightningBoltEntity};
 use crate::world::World;
 
+/// The chance, one in this many, that lightning strikes a chunk in a tick
+/// while it thunders.
+///
+/// Vanilla: `ServerLevel.tickThunder`
+const THUNDER_CHANCE: u32 = 100_000;
+
 /// A chunk that is ready to be sent to the client.
 ///
 /// Similar to Java's `LevelChunk`, this holds a weak reference to the world
@@ -68,6 +76,10 @@ impl LevelChunk {
         // Tick block entities regardless of random tick speed
         self.tick_block_entities();
 
+        if let Some(world) = self.get_level() {
+            self.tick_thunder(&world);
+        }
+
         if random_tick_speed == 0 {
             return;
         }
@@ -127,6 +139,34 @@ impl LevelChunk {
         }
     }
 
+    /// Strikes lightning at a random spot on the surface of this chunk now
+    /// and then while it thunders.
+    ///
+    /// Vanilla: `ServerLevel.tickThunder`
+    // TODO: Aim for nearby lightning rods and skip biomes where it doesn't
+    // rain once biome precipitation is tracked
+    fn tick_thunder(&self, world: &World) {
+        if !world.is_thundering() || rand::rng().random_range(0..THUNDER_CHANCE) != 0 {
+            return;
+        }
+
+        let mut rng = rand::rng();
+        let local_x = rng.random_range(0..16);
+        let local_z = rng.random_range(0..16);
+        let surface = self
+            .heightmaps
+            .read()
+            .get(HeightmapType::MotionBlocking)
+            .get_first_available(local_x, local_z);
+        let position = Vector3::new(
+            f64::from(self.pos.0.x * 16 + local_x as i32) + 0.5,
+            f64::from(surface),
+            f64::from(self.pos.0.y * 16 + local_z as i32) + 0.5,
+        );
+        let bolt = LightningBoltEntity::new(entity::next_entity_id(), position);
+        world.add_entity(&world.entities.lightning_bolts, bolt);
+    }
+
     /// Creates a new `LevelChunk` from a `ProtoChunk`.
     ///
     /// Transfers final heightmaps from the proto chunk if available.
@@ -180,7 +220,8 @@ impl LevelChunk {
 
     /// Creates a new `LevelChunk` that was loaded from disk (not dirty).
     ///
-    /// Recalculates section block counts for random tick optimization.
+    /// Recalculates section block counts for random tick optimization and
+    /// computes the heightmaps.
     ///
     /// # Arguments
     /// * `sections` - The chunk sections
@@ -204,7 +245,7 @@ impl LevelChunk {
             section.write().recalculate_counts();
         }
 
-        Self {
+        let chunk = Self {
             sections,
             pos,
             dirty: AtomicBool::new(false),
@@ -213,6 +254,21 @@ impl LevelChunk {
             height,
             level,
             block_entities: BlockEntityStorage::new(),
+        };
+        chunk.recompute_heightmaps();
+        chunk
+    }
+
+    /// Recomputes every heightmap from the blocks in the chunk.
+    ///
+    /// Heightmaps are not saved with the chunk, so chunks loaded from disk
+    /// compute them again before they are sent to clients.
+    pub fn recompute_heightmaps(&self) {
+        let mut heightmaps = self.heightmaps.write();
+        for &hm_type in HeightmapType::final_types() {
+            heightmaps
+                .get_mut(hm_type)
+                .set_heights(&Heightmap::compute(hm_type, self));
         }
     }
 
@@ -330,7 +386,17 @@ impl LevelChunk {
             if guard.is_removed() {
                 continue;
             }
+            let old_state = guard.get_block_state();
             guard.tick(&world);
+
+            // A block entity that changed its own state, like a furnace
+            // lighting up, gets the block updated once it is unlocked
+            let new_state = guard.get_block_state();
+            if new_state != old_state {
+                let pos = guard.get_block_pos();
+                drop(guard);
+                world.set_block(pos, new_state, UpdateFlags::UPDATE_ALL);
+            }
         }
 
         // Clean up removed entities from the ticking list
@@ -574,33 +640,10 @@ impl LevelChunk {
     }
 
     /// Extracts the light data for sending to the client.
+    ///
+    /// Dimensions without a sky, like the Nether, send no sky light at all.
     #[must_use]
-    pub fn extract_light_data(&self) -> LightUpdatePacketData {
-        // Vanilla's light section count is sectionsCount + 2 (one below and one above the world)
-        let light_section_count = self.sections.sections.len() + 2;
-        let mut sky_y_mask = BitSet(vec![0; light_section_count.div_ceil(64)].into_boxed_slice());
-        let mut block_y_mask = BitSet(vec![0; light_section_count.div_ceil(64)].into_boxed_slice());
-        let empty_sky_y_mask = BitSet(vec![0; light_section_count.div_ceil(64)].into_boxed_slice());
-        let empty_block_y_mask =
-            BitSet(vec![0; light_section_count.div_ceil(64)].into_boxed_slice());
-
-        let mut sky_updates = Vec::new();
-        let mut block_updates = Vec::new();
-
-        for i in 0..light_section_count {
-            sky_y_mask.set(i, true);
-            block_y_mask.set(i, true);
-            sky_updates.push(vec![0xFF; 2048]);
-            block_updates.push(vec![0xFF; 2048]);
-        }
-
-        LightUpdatePacketData {
-            sky_y_mask,
-            block_y_mask,
-            empty_sky_y_mask,
-            empty_block_y_mask,
-            sky_updates,
-            block_updates,
-        }
+    pub fn extract_light_data(&self, has_skylight: bool) -> LightUpdatePacketData {
+        ChunkLightData::compute(self, has_skylight).serialize()
     }
 }
@@ -0,0 +1,219 @@
+//! Light data of a chunk, as sent to clients with the chunk.
+//!
+//! Sky light comes straight down from the sky to the `MotionBlocking`
+//! heightmap. It does not spread sideways yet, and block light stays full
+//! until light sources are tracked.
+
+use steel_protocol::packets::game::LightUpdatePacketData;
+use steel_utils::codec::BitSet;
+
+use crate::chunk::heightmap::{Heightmap, HeightmapType};
+use crate::chunk::level_chunk::LevelChunk;
+
+/// The brightest light level.
+pub const MAX_LIGHT: u8 = 15;
+
+/// The light levels of one 16x16x16 section, two levels per byte.
+///
+/// Vanilla: `DataLayer`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NibbleArray {
+    data: Box<[u8; 2048]>,
+}
+
+impl NibbleArray {
+    /// Creates an array with every block at `level`.
+    #[must_use]
+    pub fn filled(level: u8) -> Self {
+        let level = level & 0xF;
+        Self {
+            data: Box::new([level | (level << 4); 2048]),
+        }
+    }
+
+    /// Gets the index of the nibble for the given local coordinates.
+    #[inline]
+    const fn get_index(local_x: usize, local_y: usize, local_z: usize) -> usize {
+        (local_y << 8) | (local_z << 4) | local_x
+    }
+
+    /// Gets the light level at the given local coordinates.
+    #[must_use]
+    pub fn get(&self, local_x: usize, local_y: usize, local_z: usize) -> u8 {
+        let index = Self::get_index(local_x, local_y, local_z);
+        (self.data[index >> 1] >> ((index & 1) * 4)) & 0xF
+    }
+
+    /// Sets the light level at the given local coordinates.
+    pub fn set(&mut self, local_x: usize, local_y: usize, local_z: usize, level: u8) {
+        let index = Self::get_index(local_x, local_y, local_z);
+        let shift = (index & 1) * 4;
+        let byte = &mut self.data[index >> 1];
+        *byte = (*byte & !(0xF << shift)) | ((level & 0xF) << shift);
+    }
+
+    /// Returns whether every block is dark.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.iter().all(|&byte| byte == 0)
+    }
+
+    /// Returns the raw bytes, as sent to clients.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+}
+
+/// The sky and block light of every light section of a chunk.
+///
+/// There is one more light section below and one above the chunk's sections,
+/// so light at the edges of the world is known too.
+pub struct ChunkLightData {
+    /// Sky light per light section, bottom first. Empty in dimensions
+    /// without a sky.
+    sky: Vec<NibbleArray>,
+    /// Block light per light section, bottom first.
+    block: Vec<NibbleArray>,
+}
+
+impl ChunkLightData {
+    /// Computes the light of `chunk` from its `MotionBlocking` heightmap.
+    #[must_use]
+    pub fn compute(chunk: &LevelChunk, has_skylight: bool) -> Self {
+        let heights = if has_skylight {
+            Some(Heightmap::compute(HeightmapType::MotionBlocking, chunk))
+        } else {
+            None
+        };
+        Self::from_heights(
+            heights.as_ref(),
+            chunk.min_y(),
+            chunk.sections.sections.len(),
+        )
+    }
+
+    /// Computes the light of a chunk with `section_count` sections starting
+    /// at `min_y`. Sky light is full from each column's height up, and dark
+    /// below it; without heights there is no sky light at all.
+    #[must_use]
+    pub fn from_heights(heights: Option<&[i16; 256]>, min_y: i32, section_count: usize) -> Self {
+        let light_section_count = section_count + 2;
+        let sky = heights.map_or_else(Vec::new, |heights| {
+            (0..light_section_count)
+                .map(|light_index| {
+                    // The first light section is the one below the world
+                    let base_y = min_y + (light_index as i32 - 1) * 16;
+                    sky_section(heights, base_y)
+                })
+                .collect()
+        });
+        let block = vec![NibbleArray::filled(MAX_LIGHT); light_section_count];
+        Self { sky, block }
+    }
+
+    /// Formats the light for the chunk packet. Sections without any light go
+    /// in the empty masks, so their arrays don't have to be sent.
+    #[must_use]
+    pub fn serialize(&self) -> LightUpdatePacketData {
+        let (sky_y_mask, empty_sky_y_mask, sky_updates) =
+            Self::serialize_layer(&self.sky, self.block.len());
+        let (block_y_mask, empty_block_y_mask, block_updates) =
+            Self::serialize_layer(&self.block, self.block.len());
+        LightUpdatePacketData {
+            sky_y_mask,
+            block_y_mask,
+            empty_sky_y_mask,
+            empty_block_y_mask,
+            sky_updates,
+            block_updates,
+        }
+    }
+
+    /// Returns the mask of sections with light, the mask of dark sections
+    /// and the arrays of the sections with light.
+    fn serialize_layer(
+        layer: &[NibbleArray],
+        light_section_count: usize,
+    ) -> (BitSet, BitSet, Vec<Vec<u8>>) {
+        let words = light_section_count.div_ceil(64);
+        let mut mask = BitSet(vec![0; words].into_boxed_slice());
+        let mut empty_mask = BitSet(vec![0; words].into_boxed_slice());
+        let mut updates = Vec::new();
+        for (index, section) in layer.iter().enumerate() {
+            if section.is_empty() {
+                empty_mask.set(index, true);
+            } else {
+                mask.set(index, true);
+                updates.push(section.as_bytes().to_vec());
+            }
+        }
+        (mask, empty_mask, updates)
+    }
+
+    /// Returns the sky light of the light section at `light_index`, if the
+    /// dimension has a sky.
+    #[must_use]
+    pub fn sky_section(&self, light_index: usize) -> Option<&NibbleArray> {
+        self.sky.get(light_index)
+    }
+}
+
+/// Computes the sky light of the light section starting at `base_y`.
+fn sky_section(heights: &[i16; 256], base_y: i32) -> NibbleArray {
+    let lowest = heights.iter().copied().min().map_or(0, i32::from);
+    let highest = heights.iter().copied().max().map_or(0, i32::from);
+    if base_y >= highest {
+        return NibbleArray::filled(MAX_LIGHT);
+    }
+    let mut section = NibbleArray::filled(0);
+    if base_y + 16 <= lowest {
+        return section;
+    }
+    for (index, &height) in heights.iter().enumerate() {
+        let (local_x, local_z) = (index % 16, index / 16);
+        let lit_from = (i32::from(height) - base_y).clamp(0, 16) as usize;
+        for local_y in lit_from..16 {
+            section.set(local_x, local_y, local_z, MAX_LIGHT);
+        }
+    }
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nibbles_pack_two_per_byte() {
+        let mut array = NibbleArray::filled(0);
+        array.set(1, 0, 0, 7);
+        array.set(0, 1, 0, 15);
+        assert_eq!(array.get(1, 0, 0), 7);
+        assert_eq!(array.get(0, 0, 0), 0);
+        assert_eq!(array.as_bytes()[0], 0x70);
+        assert_eq!(array.get(0, 1, 0), 15);
+        assert!(!array.is_empty());
+    }
+
+    #[test]
+    fn stone_below_zero_is_dark_and_the_sky_above_is_full() {
+        // Stone from the bottom of the overworld up to y=0
+        let heights = [0; 256];
+        let light = ChunkLightData::from_heights(Some(&heights), -64, 24);
+
+        // The section below the world and the four stone sections
+        for light_index in 0..5 {
+            let section = light.sky_section(light_index).expect("overworld has sky");
+            assert!(section.is_empty());
+        }
+        for light_index in 5..26 {
+            let section = light.sky_section(light_index).expect("overworld has sky");
+            assert_eq!(*section, NibbleArray::filled(MAX_LIGHT));
+        }
+
+        let packet = light.serialize();
+        assert_eq!(packet.sky_updates.len(), 21);
+        assert_eq!(packet.block_updates.len(), 26);
+    }
+}
@@ -13,12 +13,19 @@ pub mod chunk_ticket_manager;
 pub mod heightmap;
 /// Tracks the chunks that are visible to a player.
 pub mod player_chunk_view;
+/// Decides which chunks structures may start in based on the world seed.
+pub mod structure_placement;
+/// Structure templates and the jigsaw assembly of structures from them.
+pub mod structures;
 
-/// Generates flat worlds with configurable layers.
-pub mod flat_chunk_generator;
+/// The chunk generators a world can use.
+pub mod generators;
 pub mod level_chunk;
+pub mod light;
 pub mod paletted_container;
 pub mod proto_chunk;
 pub mod section;
+/// Chunks kept loaded and ticking without players nearby.
+pub mod spawn_chunks;
 
 pub mod world_gen_context;
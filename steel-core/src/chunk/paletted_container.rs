@@ -141,6 +141,16 @@ impl<V: Hash + Eq + Copy + Default + Debug, const DIM: usize> PalettedContainer<
         }
     }
 
+    /// Returns whether any value in the container matches the predicate.
+    ///
+    /// Only the palette is checked, so this doesn't look at every position.
+    pub fn contains_any(&self, predicate: impl Fn(V) -> bool) -> bool {
+        match self {
+            Self::Homogeneous(value) => predicate(*value),
+            Self::Heterogeneous(data) => data.palette.iter().any(|(value, _)| predicate(*value)),
+        }
+    }
+
     /// Sets the value at the given coordinates.
     pub fn set(&mut self, x: usize, y: usize, z: usize, value: V) -> V {
         debug_assert!(x < Self::SIZE);
@@ -0,0 +1,113 @@
+//! Chunks that stay loaded and ticking without players nearby.
+//!
+//! The chunks around the overworld spawn are kept this way, like vanilla's
+//! spawn chunks. Other forced chunks, like the ones a `/forceload` command
+//! adds, can use a [`ForcedArea`] the same way.
+
+use steel_utils::{BlockPos, ChunkPos, SectionPos, locks::SyncMutex};
+
+use crate::chunk::{chunk_map::ChunkMap, chunk_ticket_manager::ticking_level};
+
+/// A square of chunks kept loaded and ticking by a ticket at its center.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForcedArea {
+    /// The chunk in the middle of the area.
+    pub center: ChunkPos,
+    /// How many chunks the area reaches out from the center.
+    pub radius: u8,
+    /// The level the ticket was added with, kept so it can be removed even
+    /// after the simulation distance changes.
+    level: u8,
+}
+
+impl ForcedArea {
+    /// Adds the ticket for the area. The chunks load on the next ticks.
+    pub fn add(chunk_map: &ChunkMap, center: ChunkPos, radius: u8) -> Self {
+        let level = ticking_level(radius);
+        chunk_map.chunk_tickets.lock().add_ticket(center, level);
+        Self {
+            center,
+            radius,
+            level,
+        }
+    }
+
+    /// Removes the ticket, letting the chunks unload once no player is near them.
+    pub fn remove(self, chunk_map: &ChunkMap) {
+        chunk_map
+            .chunk_tickets
+            .lock()
+            .remove_ticket(self.center, self.level);
+    }
+
+    /// Returns whether the chunk is inside the area.
+    #[must_use]
+    pub fn contains(&self, pos: ChunkPos) -> bool {
+        let distance = (pos.0.x - self.center.0.x)
+            .abs()
+            .max((pos.0.y - self.center.0.y).abs());
+        distance <= i32::from(self.radius)
+    }
+}
+
+/// The chunks around the world spawn.
+#[derive(Default)]
+pub struct SpawnChunks {
+    area: SyncMutex<Option<ForcedArea>>,
+}
+
+impl SpawnChunks {
+    /// Creates spawn chunks that don't force any chunks yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Centers the spawn chunks on the chunk holding `spawn`, replacing the
+    /// previous area. A radius of 0 keeps no chunks loaded.
+    pub fn set_center(&self, chunk_map: &ChunkMap, spawn: BlockPos, radius: u8) {
+        let center = ChunkPos::new(
+            SectionPos::block_to_section_coord(spawn.x()),
+            SectionPos::block_to_section_coord(spawn.z()),
+        );
+
+        let mut area = self.area.lock();
+        if let Some(old) = area.take() {
+            old.remove(chunk_map);
+        }
+        if radius > 0 {
+            *area = Some(ForcedArea::add(chunk_map, center, radius));
+        }
+    }
+
+    /// Returns the area the spawn chunks cover, if any.
+    #[must_use]
+    pub fn area(&self) -> Option<ForcedArea> {
+        *self.area.lock()
+    }
+
+    /// Returns whether the chunk is one of the spawn chunks.
+    #[must_use]
+    pub fn contains(&self, pos: ChunkPos) -> bool {
+        self.area().is_some_and(|area| area.contains(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_the_square_around_the_center() {
+        let area = ForcedArea {
+            center: ChunkPos::new(-3, 5),
+            radius: 2,
+            level: 0,
+        };
+        assert!(area.contains(ChunkPos::new(-3, 5)));
+        assert!(area.contains(ChunkPos::new(-5, 7)));
+        assert!(area.contains(ChunkPos::new(-1, 3)));
+        assert!(!area.contains(ChunkPos::new(-6, 5)));
+        assert!(!area.contains(ChunkPos::new(-3, 8)));
+    }
+}
@@ -0,0 +1,395 @@
+//! Seed-based structure placement, matching vanilla's `StructurePlacement` classes.
+//!
+//! These only decide which chunks a structure set may start in. Vanilla also
+//! checks that the biome at the start fits the structure, which needs a biome
+//! source we don't have yet, so every placement chunk is treated as a hit.
+
+use std::f64::consts::TAU;
+
+use steel_registry::REGISTRY;
+use steel_registry::structure_set::{
+    FrequencyReductionMethod, RandomSpreadType, StructurePlacement, StructurePlacementType,
+    StructureSet, StructureSetRef,
+};
+use steel_utils::random::{Random, legacy_random::LegacyRandom};
+use steel_utils::{BlockPos, ChunkPos};
+
+/// Salt used by `FrequencyReductionMethod::LegacyType2`.
+const LEGACY_TYPE_2_SALT: i32 = 10_387_320;
+
+/// Seeds a random the way vanilla's `WorldgenRandom.setLargeFeatureWithSalt` does.
+fn large_feature_with_salt(seed: i64, x: i32, z: i32, salt: i32) -> LegacyRandom {
+    let seed = i64::from(x)
+        .wrapping_mul(341_873_128_712)
+        .wrapping_add(i64::from(z).wrapping_mul(132_897_987_541))
+        .wrapping_add(seed)
+        .wrapping_add(i64::from(salt));
+    LegacyRandom::from_seed(seed as u64)
+}
+
+/// Seeds a random the way vanilla's `WorldgenRandom.setLargeFeatureSeed` does.
+pub(crate) fn large_feature(seed: i64, x: i32, z: i32) -> LegacyRandom {
+    let mut random = LegacyRandom::from_seed(seed as u64);
+    let a = random.next_i64();
+    let b = random.next_i64();
+    let seed = (i64::from(x).wrapping_mul(a)) ^ (i64::from(z).wrapping_mul(b)) ^ seed;
+    LegacyRandom::from_seed(seed as u64)
+}
+
+/// Seeds a random the way vanilla's `WorldgenRandom.setDecorationSeed` followed by
+/// `setFeatureSeed` does, which is the random structure pieces are placed with.
+pub(crate) fn feature_random(
+    seed: i64,
+    min_block_x: i32,
+    min_block_z: i32,
+    index: i32,
+    step: i32,
+) -> LegacyRandom {
+    let mut random = LegacyRandom::from_seed(seed as u64);
+    let a = random.next_i64() | 1;
+    let b = random.next_i64() | 1;
+    let decoration_seed = (i64::from(min_block_x).wrapping_mul(a))
+        .wrapping_add(i64::from(min_block_z).wrapping_mul(b))
+        ^ seed;
+    LegacyRandom::from_seed(
+        decoration_seed
+            .wrapping_add(i64::from(index))
+            .wrapping_add(i64::from(10_000 * step)) as u64,
+    )
+}
+
+/// Returns true if a structure survives the placement's frequency check.
+fn passes_frequency(placement: &StructurePlacement, seed: i64, x: i32, z: i32) -> bool {
+    let frequency = placement.frequency;
+    if frequency >= 1.0 {
+        return true;
+    }
+
+    match placement.frequency_reduction_method {
+        // Vanilla passes the salt and coordinates in the wrong order here, keep it for parity
+        FrequencyReductionMethod::Default => {
+            large_feature_with_salt(seed, placement.salt, x, z).next_f32() < frequency
+        }
+        FrequencyReductionMethod::LegacyType1 => {
+            let region_x = x >> 4;
+            let region_z = z >> 4;
+            let mut random =
+                LegacyRandom::from_seed((i64::from(region_x ^ (region_z << 4)) ^ seed) as u64);
+            random.next_i32();
+            random.next_i32_bounded((1.0 / frequency) as i32) == 0
+        }
+        FrequencyReductionMethod::LegacyType2 => {
+            large_feature_with_salt(seed, x, z, LEGACY_TYPE_2_SALT).next_f32() < frequency
+        }
+        FrequencyReductionMethod::LegacyType3 => {
+            large_feature(seed, x, z).next_f64() < f64::from(frequency)
+        }
+    }
+}
+
+/// Returns the chunk a random spread placement puts its structure in for the
+/// spacing cell containing the given chunk.
+#[must_use]
+pub fn potential_structure_chunk(
+    placement: &StructurePlacement,
+    seed: i64,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> Option<ChunkPos> {
+    let StructurePlacementType::RandomSpread {
+        spacing,
+        separation,
+        spread_type,
+    } = placement.placement_type
+    else {
+        return None;
+    };
+
+    let cell_x = chunk_x.div_euclid(spacing);
+    let cell_z = chunk_z.div_euclid(spacing);
+    let mut random = large_feature_with_salt(seed, cell_x, cell_z, placement.salt);
+    let range = spacing - separation;
+    let mut offset = || match spread_type {
+        RandomSpreadType::Linear => random.next_i32_bounded(range),
+        RandomSpreadType::Triangular => {
+            (random.next_i32_bounded(range) + random.next_i32_bounded(range)) / 2
+        }
+    };
+    let offset_x = offset();
+    let offset_z = offset();
+
+    Some(ChunkPos::new(
+        cell_x * spacing + offset_x,
+        cell_z * spacing + offset_z,
+    ))
+}
+
+/// Computes the start chunks of a concentric rings placement.
+///
+/// Vanilla moves each position to a nearby preferred biome if there is one,
+/// which we skip until biome sources exist.
+#[must_use]
+pub fn ring_positions(placement: &StructurePlacement, seed: i64) -> Vec<ChunkPos> {
+    let StructurePlacementType::ConcentricRings {
+        distance,
+        spread,
+        count,
+        ..
+    } = placement.placement_type
+    else {
+        return Vec::new();
+    };
+
+    let mut random = LegacyRandom::from_seed(seed as u64);
+    let mut positions = Vec::with_capacity(count.max(0) as usize);
+    let mut angle = random.next_f64() * TAU;
+    let mut spread = spread;
+    let mut placed_in_ring = 0;
+    let mut ring = 0;
+
+    for placed in 0..count {
+        let ring_distance = f64::from(4 * distance + distance * ring * 6)
+            + (random.next_f64() - 0.5) * (f64::from(distance) * 2.5);
+        // Java's `Math.round` rounds halves up, not away from zero
+        let x = (angle.cos() * ring_distance + 0.5).floor() as i32;
+        let z = (angle.sin() * ring_distance + 0.5).floor() as i32;
+        // TODO: Search for a preferred biome with this fork once biome sources exist
+        let _biome_random = random.fork();
+        positions.push(ChunkPos::new(x, z));
+
+        angle += TAU / f64::from(spread);
+        placed_in_ring += 1;
+        if placed_in_ring == spread {
+            ring += 1;
+            placed_in_ring = 0;
+            spread += 2 * spread / (ring + 1);
+            spread = spread.min(count - placed);
+            angle += random.next_f64() * TAU;
+        }
+    }
+
+    positions
+}
+
+/// Returns true if a structure of the set can start in the given chunk.
+#[must_use]
+pub fn is_structure_chunk(set: &StructureSet, seed: i64, chunk_x: i32, chunk_z: i32) -> bool {
+    let placement = &set.placement;
+    let is_placement_chunk = match placement.placement_type {
+        StructurePlacementType::RandomSpread { .. } => {
+            potential_structure_chunk(placement, seed, chunk_x, chunk_z)
+                == Some(ChunkPos::new(chunk_x, chunk_z))
+        }
+        StructurePlacementType::ConcentricRings { .. } => {
+            ring_positions(placement, seed).contains(&ChunkPos::new(chunk_x, chunk_z))
+        }
+    };
+    if !is_placement_chunk || !passes_frequency(placement, seed, chunk_x, chunk_z) {
+        return false;
+    }
+
+    let Some(zone) = &placement.exclusion_zone else {
+        return true;
+    };
+    let Some(other_set) = REGISTRY.structure_sets.by_key(&zone.other_set) else {
+        return true;
+    };
+    let range = zone.chunk_count;
+    !((chunk_x - range)..=(chunk_x + range)).any(|x| {
+        ((chunk_z - range)..=(chunk_z + range)).any(|z| is_structure_chunk(other_set, seed, x, z))
+    })
+}
+
+/// Returns the position `/locate` reports for a structure starting in the given chunk.
+#[must_use]
+pub fn locate_pos(placement: &StructurePlacement, chunk: ChunkPos) -> BlockPos {
+    let [offset_x, offset_y, offset_z] = placement.locate_offset;
+    BlockPos::new(
+        chunk.0.x * 16 + offset_x,
+        offset_y,
+        chunk.0.y * 16 + offset_z,
+    )
+}
+
+fn horizontal_distance_sqr(a: BlockPos, b: BlockPos) -> i64 {
+    let dx = i64::from(a.x() - b.x());
+    let dz = i64::from(a.z() - b.z());
+    dx * dx + dz * dz
+}
+
+/// Finds the nearest start of a random spread set by searching square rings
+/// of spacing cells around the origin, like vanilla's `getNearestGeneratedStructure`.
+fn nearest_random_spread(
+    set: &StructureSet,
+    seed: i64,
+    origin: ChunkPos,
+    radius: i32,
+) -> Option<ChunkPos> {
+    let StructurePlacementType::RandomSpread { spacing, .. } = set.placement.placement_type else {
+        return None;
+    };
+
+    for dx in -radius..=radius {
+        let edge_x = dx == -radius || dx == radius;
+        for dz in -radius..=radius {
+            let edge_z = dz == -radius || dz == radius;
+            if !edge_x && !edge_z {
+                continue;
+            }
+
+            let chunk = potential_structure_chunk(
+                &set.placement,
+                seed,
+                origin.0.x + spacing * dx,
+                origin.0.y + spacing * dz,
+            )?;
+            if is_structure_chunk(set, seed, chunk.0.x, chunk.0.y) {
+                return Some(chunk);
+            }
+        }
+    }
+    None
+}
+
+/// Finds the structure start of any of the sets that is closest to `origin`,
+/// searching up to `search_radius` spacing cells away for random spread sets.
+///
+/// Matches vanilla's `ChunkGenerator.findNearestMapStructure`, except that
+/// biomes aren't checked.
+#[must_use]
+pub fn find_nearest(
+    sets: &[StructureSetRef],
+    seed: i64,
+    origin: BlockPos,
+    search_radius: i32,
+) -> Option<BlockPos> {
+    let mut nearest: Option<(i64, BlockPos)> = None;
+    let mut consider = |pos: BlockPos, distance: i64| {
+        if nearest.is_none_or(|(best, _)| distance < best) {
+            nearest = Some((distance, pos));
+        }
+    };
+
+    for &set in sets {
+        if let StructurePlacementType::ConcentricRings { .. } = set.placement.placement_type {
+            for chunk in ring_positions(&set.placement, seed) {
+                let middle = BlockPos::new(chunk.0.x * 16 + 8, 32, chunk.0.y * 16 + 8);
+                consider(
+                    locate_pos(&set.placement, chunk),
+                    horizontal_distance_sqr(middle, origin),
+                );
+            }
+        }
+    }
+
+    let random_spread: Vec<_> = sets
+        .iter()
+        .copied()
+        .filter(|set| {
+            matches!(
+                set.placement.placement_type,
+                StructurePlacementType::RandomSpread { .. }
+            )
+        })
+        .collect();
+    if random_spread.is_empty() {
+        return nearest.map(|(_, pos)| pos);
+    }
+
+    let origin_chunk = ChunkPos::new(origin.x() >> 4, origin.z() >> 4);
+    for radius in 0..=search_radius {
+        let mut found = false;
+        for &set in &random_spread {
+            if let Some(chunk) = nearest_random_spread(set, seed, origin_chunk, radius) {
+                found = true;
+                let pos = locate_pos(&set.placement, chunk);
+                consider(pos, horizontal_distance_sqr(pos, origin));
+            }
+        }
+        if found {
+            break;
+        }
+    }
+
+    nearest.map(|(_, pos)| pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use steel_utils::Identifier;
+
+    fn random_spread(spacing: i32, separation: i32, spread_type: RandomSpreadType) -> StructureSet {
+        StructureSet {
+            key: Identifier::vanilla_static("test"),
+            structures: &[],
+            placement: StructurePlacement {
+                locate_offset: [0, 0, 0],
+                frequency_reduction_method: FrequencyReductionMethod::Default,
+                frequency: 1.0,
+                salt: 10_387_312,
+                exclusion_zone: None,
+                placement_type: StructurePlacementType::RandomSpread {
+                    spacing,
+                    separation,
+                    spread_type,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn random_spread_stays_inside_its_cell() {
+        for spread_type in [RandomSpreadType::Linear, RandomSpreadType::Triangular] {
+            let set = random_spread(34, 8, spread_type);
+            for cell_x in -5..5 {
+                for cell_z in -5..5 {
+                    let chunk = potential_structure_chunk(
+                        &set.placement,
+                        12_345,
+                        cell_x * 34 + 3,
+                        cell_z * 34 + 30,
+                    )
+                    .expect("random spread placement");
+                    assert!((0..26).contains(&(chunk.0.x - cell_x * 34)));
+                    assert!((0..26).contains(&(chunk.0.y - cell_z * 34)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_spread_is_deterministic_per_cell() {
+        let set = random_spread(32, 8, RandomSpreadType::Linear);
+        let a = potential_structure_chunk(&set.placement, 42, 0, 0);
+        let b = potential_structure_chunk(&set.placement, 42, 31, 31);
+        assert_eq!(a, b);
+        let chunk = a.expect("random spread placement");
+        assert!(is_structure_chunk(&set, 42, chunk.0.x, chunk.0.y));
+    }
+
+    #[test]
+    fn rings_place_every_structure_at_ring_distance() {
+        let placement = StructurePlacement {
+            locate_offset: [0, 0, 0],
+            frequency_reduction_method: FrequencyReductionMethod::Default,
+            frequency: 1.0,
+            salt: 0,
+            exclusion_zone: None,
+            placement_type: StructurePlacementType::ConcentricRings {
+                distance: 32,
+                spread: 3,
+                count: 128,
+                preferred_biomes: None,
+            },
+        };
+        let positions = ring_positions(&placement, 0);
+        assert_eq!(positions.len(), 128);
+
+        // The first ring holds three structures 128 ± 40 chunks from the origin
+        for chunk in &positions[..3] {
+            let distance = f64::from(chunk.0.x).hypot(f64::from(chunk.0.y));
+            assert!((87.0..=169.0).contains(&distance), "{distance}");
+        }
+    }
+}
@@ -0,0 +1,612 @@
+//! Jigsaw structures, matching vanilla's `JigsawStructure` and `JigsawPlacement`.
+//!
+//! A jigsaw structure starts with a piece from its start pool and keeps
+//! attaching pieces to the open jigsaw blocks of placed pieces, picking them
+//! from the pool each jigsaw names, until the maximum depth is reached or no
+//! piece fits anymore. The random calls follow vanilla's order, so the same
+//! seed assembles the same structure.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use steel_registry::{
+    REGISTRY,
+    blocks::properties::Direction,
+    template_pool::{StructurePoolElement, StructureProjection},
+};
+use steel_utils::{
+    BlockPos, ChunkPos, Identifier,
+    random::{Random, legacy_random::LegacyRandom},
+    types::UpdateFlags,
+};
+
+use crate::chunk::{
+    chunk_access::ChunkAccess,
+    structure_placement::large_feature,
+    structures::{
+        BoundingBox, Rotation, Structure, TerrainHeight,
+        template::{JigsawBlockInfo, StructureTemplate, rotate_state},
+    },
+};
+
+const ORIGIN: BlockPos = BlockPos::new(0, 0, 0);
+
+/// A structure assembled from template pools.
+#[derive(Debug)]
+pub struct JigsawStructure {
+    /// Pool the first piece is picked from.
+    pub start_pool: Identifier,
+    /// How many pieces deep the assembly may go from the start piece.
+    pub max_depth: i32,
+    /// How far pieces may reach from the center of the start piece.
+    pub max_distance_from_center: i32,
+    /// Height of the start piece, added to the terrain height when projected.
+    pub start_height: i32,
+    /// Whether the start piece is placed relative to the terrain height.
+    pub project_start_to_heightmap: bool,
+    /// Reserves room above small pieces for what attaches to them, used by villages.
+    pub use_expansion_hack: bool,
+}
+
+impl JigsawStructure {
+    /// Assembles the pieces of the structure starting in the given chunk.
+    ///
+    /// Returns an empty list if the start pool picked an empty element.
+    #[must_use]
+    pub fn assemble(
+        &self,
+        seed: i64,
+        chunk_pos: ChunkPos,
+        terrain: &dyn TerrainHeight,
+    ) -> Vec<PoolElementPiece> {
+        let mut random = large_feature(seed, chunk_pos.0.x, chunk_pos.0.y);
+        let rotation = Rotation::VALUES[random.next_i32_bounded(4) as usize];
+
+        let Some(pool) = REGISTRY.template_pools.by_key(&self.start_pool) else {
+            log::warn!("Unknown start pool {}", self.start_pool);
+            return Vec::new();
+        };
+        let size = pool.size();
+        if size == 0 {
+            return Vec::new();
+        }
+        let Some(element) = pool
+            .templates()
+            .nth(random.next_i32_bounded(size as i32) as usize)
+        else {
+            return Vec::new();
+        };
+        if matches!(element, StructurePoolElement::Empty) {
+            return Vec::new();
+        }
+
+        let start_pos = BlockPos::new(chunk_pos.0.x << 4, self.start_height, chunk_pos.0.y << 4);
+        let bounding_box = element_bounding_box(element, start_pos, rotation);
+        let center_x = bounding_box.center_x();
+        let center_z = bounding_box.center_z();
+        let height = if self.project_start_to_heightmap {
+            start_pos.y() + terrain.first_free_height(center_x, center_z)
+        } else {
+            start_pos.y()
+        };
+        let ground_level_delta = ground_level_delta(element);
+        let dy = height - (bounding_box.min.y() + ground_level_delta);
+        let start = PoolElementPiece {
+            element,
+            position: start_pos.offset(0, dy, 0),
+            rotation,
+            bounding_box: bounding_box.moved(0, dy, 0),
+            ground_level_delta,
+        };
+
+        if self.max_depth <= 0 {
+            return vec![start];
+        }
+
+        // TODO: Clamp the height range to the world's build limits
+        let distance = self.max_distance_from_center;
+        let bounds = BoundingBox::from_corners(
+            BlockPos::new(center_x - distance, height - distance, center_z - distance),
+            BlockPos::new(center_x + distance, height + distance, center_z + distance),
+        );
+        let mut placer = Placer {
+            random,
+            max_depth: self.max_depth,
+            use_expansion_hack: self.use_expansion_hack,
+            terrain,
+            free_spaces: vec![FreeSpace {
+                bounds,
+                occupied: vec![start.bounding_box],
+            }],
+            pieces: vec![start],
+            placing: BTreeMap::new(),
+        };
+        placer.add(
+            PieceState {
+                piece: 0,
+                free_space: 0,
+                depth: 0,
+            },
+            0,
+        );
+        while let Some(state) = placer.pop() {
+            placer.try_placing_children(state);
+        }
+        placer.pieces
+    }
+}
+
+impl Structure for JigsawStructure {
+    fn find_start_position(
+        &self,
+        seed: i64,
+        chunk_pos: ChunkPos,
+        terrain: &dyn TerrainHeight,
+    ) -> Option<BlockPos> {
+        // TODO: Check the biome at the start once we have biome sources
+        self.assemble(seed, chunk_pos, terrain)
+            .first()
+            .map(|piece| piece.position)
+    }
+
+    fn generate(
+        &self,
+        chunk: &ChunkAccess,
+        start: BlockPos,
+        seed: i64,
+        terrain: &dyn TerrainHeight,
+    ) {
+        let start_chunk = ChunkPos::new(start.x() >> 4, start.z() >> 4);
+        let chunk_box = BoundingBox::for_chunk(chunk.pos(), i32::MIN, i32::MAX);
+        for piece in self.assemble(seed, start_chunk, terrain) {
+            if piece.bounding_box.intersects(&chunk_box) {
+                piece.place(chunk, &chunk_box, terrain);
+            }
+        }
+    }
+}
+
+/// A placed element of a jigsaw structure.
+#[derive(Debug, Clone, Copy)]
+pub struct PoolElementPiece {
+    /// The pool element this piece places.
+    pub element: &'static StructurePoolElement,
+    /// Where the template's origin ends up after rotating.
+    pub position: BlockPos,
+    /// Rotation of the element around its origin.
+    pub rotation: Rotation,
+    /// The blocks the piece covers, including room reserved by the expansion hack.
+    pub bounding_box: BoundingBox,
+    /// Distance from the bottom of the piece to the ground it stands on.
+    pub ground_level_delta: i32,
+}
+
+impl PoolElementPiece {
+    /// Places the blocks of the piece that lie inside `area`.
+    pub fn place(&self, chunk: &ChunkAccess, area: &BoundingBox, terrain: &dyn TerrainHeight) {
+        place_element(
+            self.element,
+            self.position,
+            self.rotation,
+            chunk,
+            area,
+            terrain,
+        );
+    }
+}
+
+fn place_element(
+    element: &StructurePoolElement,
+    position: BlockPos,
+    rotation: Rotation,
+    chunk: &ChunkAccess,
+    area: &BoundingBox,
+    terrain: &dyn TerrainHeight,
+) {
+    match element {
+        StructurePoolElement::Single {
+            location,
+            projection,
+            legacy,
+            ..
+        } => {
+            let Some(template) = StructureTemplate::get(location) else {
+                return;
+            };
+            // TODO: Apply the element's processor list and load block entity data
+            let blocks = template
+                .blocks
+                .iter()
+                .filter(|block| !(*legacy && block.is_air))
+                .map(|block| (block.pos, block.state));
+            let jigsaws = template
+                .jigsaws
+                .iter()
+                .filter_map(|jigsaw| Some((jigsaw.pos, jigsaw.final_state?)));
+
+            for (local, state) in blocks.chain(jigsaws) {
+                let rotated = rotation.transform(local);
+                let mut pos = rotated.offset(position.x(), position.y(), position.z());
+                if *projection == StructureProjection::TerrainMatching {
+                    let y = terrain.first_free_height(pos.x(), pos.z()) - 1 + local.y();
+                    pos = BlockPos::new(pos.x(), y, pos.z());
+                }
+                if !area.is_inside(pos) {
+                    continue;
+                }
+                chunk.set_block_state(
+                    pos,
+                    rotate_state(state, rotation),
+                    UpdateFlags::UPDATE_CLIENTS | UpdateFlags::UPDATE_KNOWN_SHAPE,
+                );
+            }
+        }
+        StructurePoolElement::List { elements, .. } => {
+            for element in *elements {
+                place_element(element, position, rotation, chunk, area, terrain);
+            }
+        }
+        // TODO: Place features once we have placed features
+        StructurePoolElement::Feature { .. } | StructurePoolElement::Empty => {}
+    }
+}
+
+/// Space pieces may still be placed in: everything in `bounds` not covered by `occupied`.
+struct FreeSpace {
+    bounds: BoundingBox,
+    occupied: Vec<BoundingBox>,
+}
+
+impl FreeSpace {
+    fn fits(&self, bounding_box: &BoundingBox) -> bool {
+        self.bounds.contains(bounding_box)
+            && !self
+                .occupied
+                .iter()
+                .any(|occupied| occupied.intersects(bounding_box))
+    }
+}
+
+/// A piece whose jigsaws still have to be processed.
+struct PieceState {
+    piece: usize,
+    /// The free space shared with the pieces next to it.
+    free_space: usize,
+    depth: i32,
+}
+
+/// Attaches pieces to the open jigsaws, like vanilla's `JigsawPlacement.Placer`.
+struct Placer<'a> {
+    random: LegacyRandom,
+    max_depth: i32,
+    use_expansion_hack: bool,
+    terrain: &'a dyn TerrainHeight,
+    pieces: Vec<PoolElementPiece>,
+    free_spaces: Vec<FreeSpace>,
+    /// Pieces to process, highest placement priority first and in insertion order otherwise.
+    placing: BTreeMap<i32, VecDeque<PieceState>>,
+}
+
+impl Placer<'_> {
+    fn add(&mut self, state: PieceState, priority: i32) {
+        self.placing.entry(priority).or_default().push_back(state);
+    }
+
+    fn pop(&mut self) -> Option<PieceState> {
+        let mut entry = self.placing.last_entry()?;
+        let state = entry.get_mut().pop_front();
+        if entry.get().is_empty() {
+            entry.remove();
+        }
+        state
+    }
+
+    fn try_placing_children(&mut self, state: PieceState) {
+        let piece = self.pieces[state.piece];
+        let is_rigid = piece.element.projection() == StructureProjection::Rigid;
+        let bounding_box = piece.bounding_box;
+        let min_y = bounding_box.min.y();
+        // Free space of pieces attached inside this one, created when first needed
+        let mut inner_free_space = None;
+
+        'jigsaws: for jigsaw in shuffled_jigsaws(
+            piece.element,
+            piece.position,
+            piece.rotation,
+            &mut self.random,
+        ) {
+            let front = jigsaw.front;
+            let target = front.relative(&jigsaw.pos);
+            let relative_y = jigsaw.pos.y() - min_y;
+            let mut surface_height = None;
+
+            let Some(pool) = REGISTRY.template_pools.by_key(&jigsaw.pool) else {
+                log::warn!("Empty or non-existent pool: {}", jigsaw.pool);
+                continue;
+            };
+            let Some(fallback) = REGISTRY.template_pools.by_key(&pool.fallback) else {
+                log::warn!("Empty or non-existent fallback pool: {}", pool.fallback);
+                continue;
+            };
+
+            let free_space = if bounding_box.is_inside(target) {
+                *inner_free_space.get_or_insert_with(|| {
+                    self.free_spaces.push(FreeSpace {
+                        bounds: bounding_box,
+                        occupied: Vec::new(),
+                    });
+                    self.free_spaces.len() - 1
+                })
+            } else {
+                state.free_space
+            };
+
+            let mut candidates = Vec::new();
+            if state.depth != self.max_depth {
+                candidates.extend(shuffled(pool.templates().collect(), &mut self.random));
+            }
+            candidates.extend(shuffled(fallback.templates().collect(), &mut self.random));
+
+            for candidate in candidates {
+                if matches!(candidate, StructurePoolElement::Empty) {
+                    break;
+                }
+
+                for rotation in shuffled(Rotation::VALUES.to_vec(), &mut self.random) {
+                    let candidate_jigsaws =
+                        shuffled_jigsaws(candidate, ORIGIN, rotation, &mut self.random);
+                    let origin_box = element_bounding_box(candidate, ORIGIN, rotation);
+                    let expansion = if self.use_expansion_hack && origin_box.y_span() <= 16 {
+                        candidate_jigsaws
+                            .iter()
+                            .filter(|jigsaw| {
+                                origin_box.is_inside(jigsaw.front.relative(&jigsaw.pos))
+                            })
+                            .map(|jigsaw| pool_max_height(&jigsaw.pool))
+                            .max()
+                            .unwrap_or(0)
+                    } else {
+                        0
+                    };
+
+                    for candidate_jigsaw in &candidate_jigsaws {
+                        if !jigsaw.can_attach(candidate_jigsaw) {
+                            continue;
+                        }
+
+                        let delta = BlockPos::new(
+                            target.x() - candidate_jigsaw.pos.x(),
+                            target.y() - candidate_jigsaw.pos.y(),
+                            target.z() - candidate_jigsaw.pos.z(),
+                        );
+                        let candidate_box = element_bounding_box(candidate, delta, rotation);
+                        let candidate_rigid = candidate.projection() == StructureProjection::Rigid;
+                        let candidate_relative_y = candidate_jigsaw.pos.y();
+                        let offset = relative_y - candidate_relative_y + front.offset().1;
+                        let y = if is_rigid && candidate_rigid {
+                            min_y + offset
+                        } else {
+                            let surface = *surface_height.get_or_insert_with(|| {
+                                self.terrain
+                                    .first_free_height(jigsaw.pos.x(), jigsaw.pos.z())
+                            });
+                            surface - candidate_relative_y
+                        };
+                        let dy = y - candidate_box.min.y();
+                        let mut moved_box = candidate_box.moved(0, dy, 0);
+                        let position = delta.offset(0, dy, 0);
+
+                        if expansion > 0 {
+                            let height = (expansion + 1).max(moved_box.max.y() - moved_box.min.y());
+                            moved_box.encapsulate(BlockPos::new(
+                                moved_box.min.x(),
+                                moved_box.min.y() + height,
+                                moved_box.min.z(),
+                            ));
+                        }
+
+                        if !self.free_spaces[free_space].fits(&moved_box) {
+                            continue;
+                        }
+                        self.free_spaces[free_space].occupied.push(moved_box);
+
+                        let ground_level_delta = if candidate_rigid {
+                            piece.ground_level_delta - offset
+                        } else {
+                            ground_level_delta(candidate)
+                        };
+                        self.pieces.push(PoolElementPiece {
+                            element: candidate,
+                            position,
+                            rotation,
+                            bounding_box: moved_box,
+                            ground_level_delta,
+                        });
+                        if state.depth < self.max_depth {
+                            self.add(
+                                PieceState {
+                                    piece: self.pieces.len() - 1,
+                                    free_space,
+                                    depth: state.depth + 1,
+                                },
+                                jigsaw.placement_priority,
+                            );
+                        }
+                        continue 'jigsaws;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Shuffles a list like vanilla's `Util.shuffle`.
+fn shuffled<T>(mut list: Vec<T>, random: &mut LegacyRandom) -> Vec<T> {
+    for i in (2..=list.len()).rev() {
+        let j = random.next_i32_bounded(i as i32) as usize;
+        list.swap(i - 1, j);
+    }
+    list
+}
+
+/// Returns the jigsaws of an element in the order they should be tried.
+fn shuffled_jigsaws(
+    element: &StructurePoolElement,
+    position: BlockPos,
+    rotation: Rotation,
+    random: &mut LegacyRandom,
+) -> Vec<JigsawBlockInfo> {
+    let mut jigsaws = shuffled(element_jigsaws(element, position, rotation), random);
+    // Stable, so jigsaws with the same priority keep their shuffled order
+    jigsaws.sort_by_key(|jigsaw| -jigsaw.selection_priority);
+    jigsaws
+}
+
+fn element_jigsaws(
+    element: &StructurePoolElement,
+    position: BlockPos,
+    rotation: Rotation,
+) -> Vec<JigsawBlockInfo> {
+    match element {
+        StructurePoolElement::Single { location, .. } => StructureTemplate::get(location)
+            .map(|template| {
+                template
+                    .jigsaws
+                    .iter()
+                    .map(|jigsaw| jigsaw.transformed(position, rotation))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        StructurePoolElement::List { elements, .. } => elements
+            .first()
+            .map(|first| element_jigsaws(first, position, rotation))
+            .unwrap_or_default(),
+        // Features attach with a single jigsaw at their base
+        StructurePoolElement::Feature { .. } => vec![JigsawBlockInfo {
+            pos: position,
+            front: Direction::Down,
+            top: Direction::South,
+            name: Identifier::vanilla_static("bottom"),
+            target: Identifier::vanilla_static("empty"),
+            pool: Identifier::vanilla_static("empty"),
+            rollable: true,
+            final_state: None,
+            placement_priority: 0,
+            selection_priority: 0,
+        }],
+        StructurePoolElement::Empty => Vec::new(),
+    }
+}
+
+fn element_bounding_box(
+    element: &StructurePoolElement,
+    position: BlockPos,
+    rotation: Rotation,
+) -> BoundingBox {
+    match element {
+        StructurePoolElement::Single { location, .. } => StructureTemplate::get(location)
+            .map_or(BoundingBox::from_corners(position, position), |template| {
+                template.bounding_box(position, rotation)
+            }),
+        StructurePoolElement::List { elements, .. } => {
+            let mut boxes = elements
+                .iter()
+                .map(|element| element_bounding_box(element, position, rotation));
+            let mut bounding_box = boxes
+                .next()
+                .unwrap_or(BoundingBox::from_corners(position, position));
+            for other in boxes {
+                bounding_box.encapsulate(other.min);
+                bounding_box.encapsulate(other.max);
+            }
+            bounding_box
+        }
+        StructurePoolElement::Feature { .. } | StructurePoolElement::Empty => {
+            BoundingBox::from_corners(position, position)
+        }
+    }
+}
+
+fn ground_level_delta(element: &StructurePoolElement) -> i32 {
+    match element {
+        StructurePoolElement::Empty => 0,
+        _ => 1,
+    }
+}
+
+/// Returns the height of the tallest element of a pool or its fallback.
+fn pool_max_height(pool: &Identifier) -> i32 {
+    let max_height = |key: &Identifier| {
+        REGISTRY.template_pools.by_key(key).map_or(0, |pool| {
+            pool.elements
+                .iter()
+                .filter(|(element, _)| !matches!(element, StructurePoolElement::Empty))
+                .map(|(element, _)| element_bounding_box(element, ORIGIN, Rotation::None).y_span())
+                .max()
+                .unwrap_or(0)
+        })
+    };
+    let fallback = REGISTRY
+        .template_pools
+        .by_key(pool)
+        .map_or(0, |pool| max_height(&pool.fallback));
+    max_height(pool).max(fallback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jigsaw(
+        front: Direction,
+        top: Direction,
+        name: &'static str,
+        target: &'static str,
+    ) -> JigsawBlockInfo {
+        JigsawBlockInfo {
+            pos: ORIGIN,
+            front,
+            top,
+            name: Identifier::vanilla_static(name),
+            target: Identifier::vanilla_static(target),
+            pool: Identifier::vanilla_static("empty"),
+            rollable: !front.is_horizontal(),
+            final_state: None,
+            placement_priority: 0,
+            selection_priority: 0,
+        }
+    }
+
+    #[test]
+    fn jigsaws_attach_to_facing_targets() {
+        let street = jigsaw(
+            Direction::North,
+            Direction::Up,
+            "street",
+            "building_entrance",
+        );
+        let house = jigsaw(
+            Direction::South,
+            Direction::Up,
+            "building_entrance",
+            "street",
+        );
+        assert!(street.can_attach(&house));
+        assert!(house.can_attach(&street));
+
+        let sideways = jigsaw(
+            Direction::East,
+            Direction::Up,
+            "building_entrance",
+            "street",
+        );
+        assert!(!street.can_attach(&sideways));
+
+        let upside_down = jigsaw(
+            Direction::South,
+            Direction::Down,
+            "building_entrance",
+            "street",
+        );
+        assert!(!street.can_attach(&upside_down));
+    }
+}
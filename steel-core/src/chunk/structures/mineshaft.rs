@@ -0,0 +1,1392 @@
+//! Mineshafts, matching vanilla's `MineshaftStructure` and `MineshaftPieces`.
+//!
+//! A mineshaft starts with a room and branches out into corridors, crossings
+//! and stairs until pieces run into each other, get too deep or too far from
+//! the room. The whole mineshaft is then moved down below sea level.
+
+use steel_registry::{
+    REGISTRY,
+    blocks::{
+        BlockRef,
+        block_state_ext::BlockStateExt,
+        properties::{BlockStateProperties, Direction, RailShape},
+        shapes::SupportType,
+    },
+    vanilla_blocks,
+};
+use steel_utils::{
+    BlockPos, BlockStateId, ChunkPos,
+    random::{Random, legacy_random::LegacyRandom},
+    types::UpdateFlags,
+};
+
+use crate::chunk::{
+    chunk_access::ChunkAccess,
+    structure_placement::{feature_random, large_feature},
+    structures::{
+        BoundingBox, Structure, TerrainHeight,
+        piece::{PieceContext, StructurePiece, find_collision_piece},
+    },
+};
+
+/// Pieces deeper than this don't get children.
+const MAX_DEPTH: i32 = 8;
+/// How far pieces may start from the room, in blocks.
+const MAX_DISTANCE: i32 = 80;
+/// Vanilla's `GenerationStep.Decoration.UNDERGROUND_STRUCTURES`.
+const UNDERGROUND_STRUCTURES_STEP: i32 = 3;
+
+/// The wood a mineshaft is built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MineshaftType {
+    /// Oak mineshafts found in most biomes.
+    Normal,
+    /// Dark oak mineshafts found in badlands, built up to the surface.
+    Mesa,
+}
+
+impl MineshaftType {
+    fn wood(self) -> BlockStateId {
+        default_state(match self {
+            Self::Normal => vanilla_blocks::OAK_LOG,
+            Self::Mesa => vanilla_blocks::DARK_OAK_LOG,
+        })
+    }
+
+    fn planks(self) -> BlockStateId {
+        default_state(match self {
+            Self::Normal => vanilla_blocks::OAK_PLANKS,
+            Self::Mesa => vanilla_blocks::DARK_OAK_PLANKS,
+        })
+    }
+
+    fn fence(self) -> BlockStateId {
+        default_state(match self {
+            Self::Normal => vanilla_blocks::OAK_FENCE,
+            Self::Mesa => vanilla_blocks::DARK_OAK_FENCE,
+        })
+    }
+}
+
+/// A mineshaft structure.
+#[derive(Debug)]
+pub struct MineshaftStructure {
+    /// The wood the mineshaft is built from.
+    pub mineshaft_type: MineshaftType,
+}
+
+impl MineshaftStructure {
+    /// The mineshaft, `minecraft:mineshaft`.
+    pub const NORMAL: Self = Self {
+        mineshaft_type: MineshaftType::Normal,
+    };
+
+    /// The badlands mineshaft, `minecraft:mineshaft_mesa`.
+    pub const MESA: Self = Self {
+        mineshaft_type: MineshaftType::Mesa,
+    };
+
+    /// Assembles the pieces of a mineshaft starting in the given chunk.
+    ///
+    /// Returns the pieces and how far they were moved vertically.
+    #[must_use]
+    pub fn assemble(
+        &self,
+        seed: i64,
+        chunk_pos: ChunkPos,
+        terrain: &dyn TerrainHeight,
+    ) -> (Vec<MineshaftPiece>, i32) {
+        let mut random = large_feature(seed, chunk_pos.0.x, chunk_pos.0.y);
+        // Vanilla rolls the old generation chance here and ignores it
+        random.next_f64();
+
+        let room = MineshaftPiece::room(
+            &mut random,
+            (chunk_pos.0.x << 4) + 2,
+            (chunk_pos.0.y << 4) + 2,
+        );
+        let mut builder = Builder {
+            random,
+            start_box: room.piece().bounding_box,
+            pieces: vec![room],
+        };
+        builder.add_children(0);
+
+        let mut pieces = builder.pieces;
+        let mut random = builder.random;
+        let Some(mut bounds) = pieces.first().map(|piece| piece.piece().bounding_box) else {
+            return (pieces, 0);
+        };
+        for piece in &pieces {
+            bounds.encapsulate(piece.piece().bounding_box.min);
+            bounds.encapsulate(piece.piece().bounding_box.max);
+        }
+
+        let sea_level = terrain.sea_level();
+        let dy = match self.mineshaft_type {
+            MineshaftType::Mesa => {
+                let center_x = bounds.min.x() + (bounds.max.x() - bounds.min.x() + 1) / 2;
+                let center_y = bounds.min.y() + (bounds.max.y() - bounds.min.y() + 1) / 2;
+                let center_z = bounds.min.z() + (bounds.max.z() - bounds.min.z() + 1) / 2;
+                let surface = terrain.first_free_height(center_x, center_z);
+                let y = if surface <= sea_level {
+                    sea_level
+                } else {
+                    sea_level + random.next_i32_bounded(surface - sea_level + 1)
+                };
+                y - center_y
+            }
+            MineshaftType::Normal => {
+                // Vanilla's `moveBelowSeaLevel` with an offset of 10
+                let limit = sea_level - 10;
+                let mut top = bounds.y_span() + terrain.min_y() + 1;
+                if top < limit {
+                    top += random.next_i32_bounded(limit - top);
+                }
+                top - bounds.max.y()
+            }
+        };
+        for piece in &mut pieces {
+            piece.move_vertically(dy);
+        }
+        (pieces, dy)
+    }
+}
+
+impl Structure for MineshaftStructure {
+    fn find_start_position(
+        &self,
+        seed: i64,
+        chunk_pos: ChunkPos,
+        terrain: &dyn TerrainHeight,
+    ) -> Option<BlockPos> {
+        // TODO: Check the biome at the start once we have biome sources
+        let (_, dy) = self.assemble(seed, chunk_pos, terrain);
+        Some(BlockPos::new(
+            (chunk_pos.0.x << 4) + 8,
+            50 + dy,
+            chunk_pos.0.y << 4,
+        ))
+    }
+
+    fn generate(
+        &self,
+        chunk: &ChunkAccess,
+        start: BlockPos,
+        seed: i64,
+        terrain: &dyn TerrainHeight,
+    ) {
+        let start_chunk = ChunkPos::new(start.x() >> 4, start.z() >> 4);
+        let chunk_pos = chunk.pos();
+        let ctx = PieceContext {
+            chunk,
+            area: BoundingBox::for_chunk(chunk_pos, i32::MIN, i32::MAX),
+            terrain,
+        };
+        // TODO: Use the structure's index in its generation step instead of 0
+        let mut random = feature_random(
+            seed,
+            chunk_pos.0.x << 4,
+            chunk_pos.0.y << 4,
+            0,
+            UNDERGROUND_STRUCTURES_STEP,
+        );
+
+        let (mut pieces, _) = self.assemble(seed, start_chunk, terrain);
+        for piece in &mut pieces {
+            if piece.piece().bounding_box.intersects(&ctx.area) {
+                piece.post_process(&ctx, &mut random, self.mineshaft_type);
+            }
+        }
+    }
+}
+
+/// A piece of a mineshaft.
+#[derive(Debug, Clone)]
+pub enum MineshaftPiece {
+    /// The dirt room the mineshaft starts in.
+    Room {
+        /// The piece's shape.
+        piece: StructurePiece,
+        /// Openings to the pieces attached to the room.
+        child_entrance_boxes: Vec<BoundingBox>,
+    },
+    /// A straight corridor made of 5 block long sections.
+    Corridor {
+        /// The piece's shape.
+        piece: StructurePiece,
+        /// Whether rails run along the corridor.
+        has_rails: bool,
+        /// Whether the corridor is full of cobwebs and has a cave spider spawner.
+        spider_corridor: bool,
+        /// Whether the spawner was placed already.
+        has_placed_spider: bool,
+        /// Number of sections with a support.
+        num_sections: i32,
+    },
+    /// A crossing where corridors branch off, sometimes two floors high.
+    Crossing {
+        /// The piece's shape.
+        piece: StructurePiece,
+        /// The direction the crossing was entered from.
+        direction: Direction,
+        /// Whether the crossing has a second floor.
+        is_two_floored: bool,
+    },
+    /// Stairs going down.
+    Stairs {
+        /// The piece's shape.
+        piece: StructurePiece,
+    },
+}
+
+impl MineshaftPiece {
+    fn room(random: &mut LegacyRandom, x: i32, z: i32) -> Self {
+        let max_x = x + 7 + random.next_i32_bounded(6);
+        let max_y = 54 + random.next_i32_bounded(6);
+        let max_z = z + 7 + random.next_i32_bounded(6);
+        Self::Room {
+            piece: StructurePiece::new(0, BoundingBox::new(x, 50, z, max_x, max_y, max_z), None),
+            child_entrance_boxes: Vec::new(),
+        }
+    }
+
+    fn corridor(
+        random: &mut LegacyRandom,
+        gen_depth: i32,
+        bounding_box: BoundingBox,
+        orientation: Direction,
+    ) -> Self {
+        let has_rails = random.next_i32_bounded(3) == 0;
+        let spider_corridor = !has_rails && random.next_i32_bounded(23) == 0;
+        let length = if matches!(orientation, Direction::North | Direction::South) {
+            bounding_box.max.z() - bounding_box.min.z() + 1
+        } else {
+            bounding_box.max.x() - bounding_box.min.x() + 1
+        };
+        Self::Corridor {
+            piece: StructurePiece::new(gen_depth, bounding_box, Some(orientation)),
+            has_rails,
+            spider_corridor,
+            has_placed_spider: false,
+            num_sections: length / 5,
+        }
+    }
+
+    /// Returns the shape shared by every kind of piece.
+    #[must_use]
+    pub fn piece(&self) -> &StructurePiece {
+        match self {
+            Self::Room { piece, .. }
+            | Self::Corridor { piece, .. }
+            | Self::Crossing { piece, .. }
+            | Self::Stairs { piece } => piece,
+        }
+    }
+
+    fn move_vertically(&mut self, dy: i32) {
+        match self {
+            Self::Room {
+                piece,
+                child_entrance_boxes,
+            } => {
+                piece.bounding_box = piece.bounding_box.moved(0, dy, 0);
+                for entrance in child_entrance_boxes {
+                    *entrance = entrance.moved(0, dy, 0);
+                }
+            }
+            Self::Corridor { piece, .. }
+            | Self::Crossing { piece, .. }
+            | Self::Stairs { piece } => {
+                piece.bounding_box = piece.bounding_box.moved(0, dy, 0);
+            }
+        }
+    }
+
+    /// Places the blocks of the piece that lie inside the context's area.
+    pub fn post_process(
+        &mut self,
+        ctx: &PieceContext<'_>,
+        random: &mut LegacyRandom,
+        mineshaft_type: MineshaftType,
+    ) {
+        if is_in_invalid_location(self.piece(), ctx) {
+            return;
+        }
+
+        let cave_air = default_state(vanilla_blocks::CAVE_AIR);
+        match self {
+            Self::Room {
+                piece,
+                child_entrance_boxes,
+            } => {
+                let bb = piece.bounding_box;
+                piece.generate_box(
+                    ctx,
+                    BoundingBox::new(
+                        bb.min.x(),
+                        bb.min.y() + 1,
+                        bb.min.z(),
+                        bb.max.x(),
+                        (bb.min.y() + 3).min(bb.max.y()),
+                        bb.max.z(),
+                    ),
+                    cave_air,
+                    cave_air,
+                    false,
+                );
+                for entrance in child_entrance_boxes.iter() {
+                    piece.generate_box(
+                        ctx,
+                        BoundingBox::new(
+                            entrance.min.x(),
+                            entrance.max.y() - 2,
+                            entrance.min.z(),
+                            entrance.max.x(),
+                            entrance.max.y(),
+                            entrance.max.z(),
+                        ),
+                        cave_air,
+                        cave_air,
+                        false,
+                    );
+                }
+                piece.generate_upper_half_sphere(
+                    ctx,
+                    BoundingBox::new(
+                        bb.min.x(),
+                        bb.min.y() + 4,
+                        bb.min.z(),
+                        bb.max.x(),
+                        bb.max.y(),
+                        bb.max.z(),
+                    ),
+                    cave_air,
+                    false,
+                );
+            }
+            Self::Corridor {
+                piece,
+                has_rails,
+                spider_corridor,
+                has_placed_spider,
+                num_sections,
+            } => {
+                let piece = *piece;
+                let length = *num_sections * 5 - 1;
+                let planks = mineshaft_type.planks();
+                piece.generate_box(
+                    ctx,
+                    BoundingBox::new(0, 0, 0, 2, 1, length),
+                    cave_air,
+                    cave_air,
+                    false,
+                );
+                piece.generate_maybe_box(
+                    ctx,
+                    random,
+                    0.8,
+                    BoundingBox::new(0, 2, 0, 2, 2, length),
+                    cave_air,
+                    cave_air,
+                    false,
+                    false,
+                );
+                if *spider_corridor {
+                    piece.generate_maybe_box(
+                        ctx,
+                        random,
+                        0.6,
+                        BoundingBox::new(0, 0, 0, 2, 1, length),
+                        default_state(vanilla_blocks::COBWEB),
+                        cave_air,
+                        false,
+                        true,
+                    );
+                }
+
+                for section in 0..*num_sections {
+                    let z = 2 + section * 5;
+                    place_support(&piece, ctx, random, mineshaft_type, z);
+                    for (chance, x, dz) in [
+                        (0.1, 0, -1),
+                        (0.1, 2, -1),
+                        (0.1, 0, 1),
+                        (0.1, 2, 1),
+                        (0.05, 0, -2),
+                        (0.05, 2, -2),
+                        (0.05, 0, 2),
+                        (0.05, 2, 2),
+                    ] {
+                        maybe_place_cobweb(&piece, ctx, random, chance, x, 2, z + dz);
+                    }
+                    if random.next_i32_bounded(100) == 0 {
+                        create_chest(&piece, ctx, random, 2, 0, z - 1);
+                    }
+                    if random.next_i32_bounded(100) == 0 {
+                        create_chest(&piece, ctx, random, 0, 0, z + 1);
+                    }
+                    if *spider_corridor && !*has_placed_spider {
+                        let spawner_z = z - 1 + random.next_i32_bounded(3);
+                        let pos = piece.world_pos(1, 0, spawner_z);
+                        if ctx.area.is_inside(pos) && piece.is_interior(ctx, 1, 0, spawner_z) {
+                            *has_placed_spider = true;
+                            // TODO: Make the spawner spawn cave spiders once spawners have block entities
+                            ctx.chunk.set_block_state(
+                                pos,
+                                default_state(vanilla_blocks::SPAWNER),
+                                UpdateFlags::UPDATE_CLIENTS,
+                            );
+                        }
+                    }
+                }
+
+                for x in 0..=2 {
+                    for z in 0..=length {
+                        set_planks_block(&piece, ctx, planks, x, -1, z);
+                    }
+                }
+
+                place_double_lower_or_upper_support(&piece, ctx, mineshaft_type, 0, -1, 2);
+                if *num_sections > 1 {
+                    place_double_lower_or_upper_support(
+                        &piece,
+                        ctx,
+                        mineshaft_type,
+                        0,
+                        -1,
+                        length - 2,
+                    );
+                }
+
+                if *has_rails {
+                    let rail = default_state(vanilla_blocks::RAIL)
+                        .set_value(&BlockStateProperties::RAIL_SHAPE, RailShape::NorthSouth);
+                    for z in 0..=length {
+                        let below = piece.get_block(ctx, 1, -1, z);
+                        if !below.is_air() && is_solid_render(below) {
+                            let chance = if piece.is_interior(ctx, 1, 0, z) {
+                                0.7
+                            } else {
+                                0.9
+                            };
+                            piece.maybe_generate_block(ctx, random, chance, 1, 0, z, rail);
+                        }
+                    }
+                }
+            }
+            Self::Crossing {
+                piece,
+                is_two_floored,
+                ..
+            } => {
+                let piece = *piece;
+                let bb = piece.bounding_box;
+                let (min_x, min_y, min_z) = (bb.min.x(), bb.min.y(), bb.min.z());
+                let (max_x, max_y, max_z) = (bb.max.x(), bb.max.y(), bb.max.z());
+                let planks = mineshaft_type.planks();
+
+                let boxes: &[BoundingBox] = if *is_two_floored {
+                    &[
+                        BoundingBox::new(min_x + 1, min_y, min_z, max_x - 1, min_y + 2, max_z),
+                        BoundingBox::new(min_x, min_y, min_z + 1, max_x, min_y + 2, max_z - 1),
+                        BoundingBox::new(min_x + 1, max_y - 2, min_z, max_x - 1, max_y, max_z),
+                        BoundingBox::new(min_x, max_y - 2, min_z + 1, max_x, max_y, max_z - 1),
+                        BoundingBox::new(
+                            min_x + 1,
+                            min_y + 3,
+                            min_z + 1,
+                            max_x - 1,
+                            min_y + 3,
+                            max_z - 1,
+                        ),
+                    ]
+                } else {
+                    &[
+                        BoundingBox::new(min_x + 1, min_y, min_z, max_x - 1, max_y, max_z),
+                        BoundingBox::new(min_x, min_y, min_z + 1, max_x, max_y, max_z - 1),
+                    ]
+                };
+                for local in boxes {
+                    piece.generate_box(ctx, *local, cave_air, cave_air, false);
+                }
+
+                for (x, z) in [
+                    (min_x + 1, min_z + 1),
+                    (min_x + 1, max_z - 1),
+                    (max_x - 1, min_z + 1),
+                    (max_x - 1, max_z - 1),
+                ] {
+                    // Support pillars only go where there's a ceiling to hold up
+                    if !piece.get_block(ctx, x, max_y + 1, z).is_air() {
+                        piece.generate_box(
+                            ctx,
+                            BoundingBox::new(x, min_y, z, x, max_y, z),
+                            planks,
+                            cave_air,
+                            false,
+                        );
+                    }
+                }
+
+                for x in min_x..=max_x {
+                    for z in min_z..=max_z {
+                        set_planks_block(&piece, ctx, planks, x, min_y - 1, z);
+                    }
+                }
+            }
+            Self::Stairs { piece } => {
+                let piece = *piece;
+                piece.generate_box(
+                    ctx,
+                    BoundingBox::new(0, 5, 0, 2, 7, 1),
+                    cave_air,
+                    cave_air,
+                    false,
+                );
+                piece.generate_box(
+                    ctx,
+                    BoundingBox::new(0, 0, 7, 2, 2, 8),
+                    cave_air,
+                    cave_air,
+                    false,
+                );
+                for step in 0..5 {
+                    let min_y = 5 - step - i32::from(step < 4);
+                    piece.generate_box(
+                        ctx,
+                        BoundingBox::new(0, min_y, 2 + step, 2, 7 - step, 2 + step),
+                        cave_air,
+                        cave_air,
+                        false,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Attaches pieces to each other, like vanilla's `addChildren` methods.
+struct Builder {
+    random: LegacyRandom,
+    /// Box of the room, pieces may only start close to it.
+    start_box: BoundingBox,
+    pieces: Vec<MineshaftPiece>,
+}
+
+impl Builder {
+    fn collides(&self, bounding_box: &BoundingBox) -> bool {
+        find_collision_piece(self.pieces.iter().map(MineshaftPiece::piece), bounding_box).is_some()
+    }
+
+    /// Adds a random piece and its children, returning the piece's box.
+    fn generate_and_add_piece(
+        &mut self,
+        x: i32,
+        y: i32,
+        z: i32,
+        direction: Direction,
+        gen_depth: i32,
+    ) -> Option<BoundingBox> {
+        if gen_depth > MAX_DEPTH
+            || (x - self.start_box.min.x()).abs() > MAX_DISTANCE
+            || (z - self.start_box.min.z()).abs() > MAX_DISTANCE
+        {
+            return None;
+        }
+
+        let piece = self.create_random_shaft_piece(x, y, z, direction, gen_depth + 1)?;
+        let bounding_box = piece.piece().bounding_box;
+        self.pieces.push(piece);
+        self.add_children(self.pieces.len() - 1);
+        Some(bounding_box)
+    }
+
+    fn create_random_shaft_piece(
+        &mut self,
+        x: i32,
+        y: i32,
+        z: i32,
+        direction: Direction,
+        gen_depth: i32,
+    ) -> Option<MineshaftPiece> {
+        let roll = self.random.next_i32_bounded(100);
+        if roll >= 80 {
+            let height = if self.random.next_i32_bounded(4) == 0 {
+                6
+            } else {
+                2
+            };
+            let bounding_box = match direction {
+                Direction::South => BoundingBox::new(-1, 0, 0, 3, height, 4),
+                Direction::West => BoundingBox::new(-4, 0, -1, 0, height, 3),
+                Direction::East => BoundingBox::new(0, 0, -1, 4, height, 3),
+                _ => BoundingBox::new(-1, 0, -4, 3, height, 0),
+            }
+            .moved(x, y, z);
+            (!self.collides(&bounding_box)).then(|| MineshaftPiece::Crossing {
+                piece: StructurePiece::new(gen_depth, bounding_box, None),
+                direction,
+                is_two_floored: bounding_box.y_span() > 3,
+            })
+        } else if roll >= 70 {
+            let bounding_box = match direction {
+                Direction::South => BoundingBox::new(0, -5, 0, 2, 2, 8),
+                Direction::West => BoundingBox::new(-8, -5, 0, 0, 2, 2),
+                Direction::East => BoundingBox::new(0, -5, 0, 8, 2, 2),
+                _ => BoundingBox::new(0, -5, -8, 2, 2, 0),
+            }
+            .moved(x, y, z);
+            (!self.collides(&bounding_box)).then(|| MineshaftPiece::Stairs {
+                piece: StructurePiece::new(gen_depth, bounding_box, Some(direction)),
+            })
+        } else {
+            // Try shorter corridors until one fits
+            let mut sections = self.random.next_i32_bounded(3) + 2;
+            while sections > 0 {
+                let length = sections * 5;
+                let bounding_box = match direction {
+                    Direction::South => BoundingBox::new(0, 0, 0, 2, 2, length - 1),
+                    Direction::West => BoundingBox::new(-(length - 1), 0, 0, 0, 2, 2),
+                    Direction::East => BoundingBox::new(0, 0, 0, length - 1, 2, 2),
+                    _ => BoundingBox::new(0, 0, -(length - 1), 2, 2, 0),
+                }
+                .moved(x, y, z);
+                if !self.collides(&bounding_box) {
+                    return Some(MineshaftPiece::corridor(
+                        &mut self.random,
+                        gen_depth,
+                        bounding_box,
+                        direction,
+                    ));
+                }
+                sections -= 1;
+            }
+            None
+        }
+    }
+
+    fn add_children(&mut self, index: usize) {
+        let (bb, depth) = {
+            let piece = self.pieces[index].piece();
+            (piece.bounding_box, piece.gen_depth)
+        };
+        let (min_x, min_y, min_z) = (bb.min.x(), bb.min.y(), bb.min.z());
+        let (max_x, max_y, max_z) = (bb.max.x(), bb.max.y(), bb.max.z());
+
+        match self.pieces[index].clone() {
+            MineshaftPiece::Room { .. } => self.add_room_children(index, bb, depth),
+            MineshaftPiece::Corridor { piece, .. } => {
+                let orientation = piece.orientation().unwrap_or(Direction::North);
+                let roll = self.random.next_i32_bounded(4);
+                let y = min_y - 1 + self.random.next_i32_bounded(3);
+                match orientation {
+                    Direction::South => match roll {
+                        0 | 1 => {
+                            self.generate_and_add_piece(min_x, y, max_z + 1, orientation, depth)
+                        }
+                        2 => self.generate_and_add_piece(
+                            min_x - 1,
+                            y,
+                            max_z - 3,
+                            Direction::West,
+                            depth,
+                        ),
+                        _ => self.generate_and_add_piece(
+                            max_x + 1,
+                            y,
+                            max_z - 3,
+                            Direction::East,
+                            depth,
+                        ),
+                    },
+                    Direction::West => match roll {
+                        0 | 1 => {
+                            self.generate_and_add_piece(min_x - 1, y, min_z, orientation, depth)
+                        }
+                        2 => self.generate_and_add_piece(
+                            min_x,
+                            y,
+                            min_z - 1,
+                            Direction::North,
+                            depth,
+                        ),
+                        _ => self.generate_and_add_piece(
+                            min_x,
+                            y,
+                            max_z + 1,
+                            Direction::South,
+                            depth,
+                        ),
+                    },
+                    Direction::East => match roll {
+                        0 | 1 => {
+                            self.generate_and_add_piece(max_x + 1, y, min_z, orientation, depth)
+                        }
+                        2 => self.generate_and_add_piece(
+                            max_x - 3,
+                            y,
+                            min_z - 1,
+                            Direction::North,
+                            depth,
+                        ),
+                        _ => self.generate_and_add_piece(
+                            max_x - 3,
+                            y,
+                            max_z + 1,
+                            Direction::South,
+                            depth,
+                        ),
+                    },
+                    _ => match roll {
+                        0 | 1 => {
+                            self.generate_and_add_piece(min_x, y, min_z - 1, orientation, depth)
+                        }
+                        2 => {
+                            self.generate_and_add_piece(min_x - 1, y, min_z, Direction::West, depth)
+                        }
+                        _ => {
+                            self.generate_and_add_piece(max_x + 1, y, min_z, Direction::East, depth)
+                        }
+                    },
+                };
+
+                if depth < MAX_DEPTH {
+                    if matches!(orientation, Direction::North | Direction::South) {
+                        let mut z = min_z + 3;
+                        while z + 3 <= max_z {
+                            match self.random.next_i32_bounded(5) {
+                                0 => self.generate_and_add_piece(
+                                    min_x - 1,
+                                    min_y,
+                                    z,
+                                    Direction::West,
+                                    depth + 1,
+                                ),
+                                1 => self.generate_and_add_piece(
+                                    max_x + 1,
+                                    min_y,
+                                    z,
+                                    Direction::East,
+                                    depth + 1,
+                                ),
+                                _ => None,
+                            };
+                            z += 5;
+                        }
+                    } else {
+                        let mut x = min_x + 3;
+                        while x + 3 <= max_x {
+                            match self.random.next_i32_bounded(5) {
+                                0 => self.generate_and_add_piece(
+                                    x,
+                                    min_y,
+                                    min_z - 1,
+                                    Direction::North,
+                                    depth + 1,
+                                ),
+                                1 => self.generate_and_add_piece(
+                                    x,
+                                    min_y,
+                                    max_z + 1,
+                                    Direction::South,
+                                    depth + 1,
+                                ),
+                                _ => None,
+                            };
+                            x += 5;
+                        }
+                    }
+                }
+            }
+            MineshaftPiece::Crossing {
+                direction,
+                is_two_floored,
+                ..
+            } => {
+                match direction {
+                    Direction::South => {
+                        self.generate_and_add_piece(
+                            min_x + 1,
+                            min_y,
+                            max_z + 1,
+                            Direction::South,
+                            depth,
+                        );
+                        self.generate_and_add_piece(
+                            min_x - 1,
+                            min_y,
+                            min_z + 1,
+                            Direction::West,
+                            depth,
+                        );
+                        self.generate_and_add_piece(
+                            max_x + 1,
+                            min_y,
+                            min_z + 1,
+                            Direction::East,
+                            depth,
+                        );
+                    }
+                    Direction::West => {
+                        self.generate_and_add_piece(
+                            min_x + 1,
+                            min_y,
+                            min_z - 1,
+                            Direction::North,
+                            depth,
+                        );
+                        self.generate_and_add_piece(
+                            min_x + 1,
+                            min_y,
+                            max_z + 1,
+                            Direction::South,
+                            depth,
+                        );
+                        self.generate_and_add_piece(
+                            min_x - 1,
+                            min_y,
+                            min_z + 1,
+                            Direction::West,
+                            depth,
+                        );
+                    }
+                    Direction::East => {
+                        self.generate_and_add_piece(
+                            min_x + 1,
+                            min_y,
+                            min_z - 1,
+                            Direction::North,
+                            depth,
+                        );
+                        self.generate_and_add_piece(
+                            min_x + 1,
+                            min_y,
+                            max_z + 1,
+                            Direction::South,
+                            depth,
+                        );
+                        self.generate_and_add_piece(
+                            max_x + 1,
+                            min_y,
+                            min_z + 1,
+                            Direction::East,
+                            depth,
+                        );
+                    }
+                    _ => {
+                        self.generate_and_add_piece(
+                            min_x + 1,
+                            min_y,
+                            min_z - 1,
+                            Direction::North,
+                            depth,
+                        );
+                        self.generate_and_add_piece(
+                            min_x - 1,
+                            min_y,
+                            min_z + 1,
+                            Direction::West,
+                            depth,
+                        );
+                        self.generate_and_add_piece(
+                            max_x + 1,
+                            min_y,
+                            min_z + 1,
+                            Direction::East,
+                            depth,
+                        );
+                    }
+                }
+
+                if is_two_floored {
+                    let y = min_y + 3 + 1;
+                    if self.random.next_bool() {
+                        self.generate_and_add_piece(
+                            min_x + 1,
+                            y,
+                            min_z - 1,
+                            Direction::North,
+                            depth,
+                        );
+                    }
+                    if self.random.next_bool() {
+                        self.generate_and_add_piece(
+                            min_x - 1,
+                            y,
+                            min_z + 1,
+                            Direction::West,
+                            depth,
+                        );
+                    }
+                    if self.random.next_bool() {
+                        self.generate_and_add_piece(
+                            max_x + 1,
+                            y,
+                            min_z + 1,
+                            Direction::East,
+                            depth,
+                        );
+                    }
+                    if self.random.next_bool() {
+                        self.generate_and_add_piece(
+                            min_x + 1,
+                            y,
+                            max_z + 1,
+                            Direction::South,
+                            depth,
+                        );
+                    }
+                }
+            }
+            MineshaftPiece::Stairs { piece } => {
+                match piece.orientation().unwrap_or(Direction::North) {
+                    Direction::South => self.generate_and_add_piece(
+                        min_x,
+                        min_y,
+                        max_z + 1,
+                        Direction::South,
+                        depth,
+                    ),
+                    Direction::West => {
+                        self.generate_and_add_piece(min_x - 1, min_y, min_z, Direction::West, depth)
+                    }
+                    Direction::East => {
+                        self.generate_and_add_piece(max_x + 1, min_y, min_z, Direction::East, depth)
+                    }
+                    _ => self.generate_and_add_piece(
+                        min_x,
+                        min_y,
+                        min_z - 1,
+                        Direction::North,
+                        depth,
+                    ),
+                };
+            }
+        }
+    }
+
+    fn add_room_children(&mut self, index: usize, bb: BoundingBox, depth: i32) {
+        let x_span = bb.max.x() - bb.min.x() + 1;
+        let z_span = bb.max.z() - bb.min.z() + 1;
+        let height = (bb.y_span() - 3 - 1).max(1);
+        let mut entrances = Vec::new();
+
+        for side in [
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ] {
+            let span = if matches!(side, Direction::North | Direction::South) {
+                x_span
+            } else {
+                z_span
+            };
+            let mut offset = 0;
+            while offset < span {
+                offset += self.random.next_i32_bounded(span);
+                if offset + 3 > span {
+                    break;
+                }
+                let y = bb.min.y() + self.random.next_i32_bounded(height) + 1;
+                let (x, z) = match side {
+                    Direction::North => (bb.min.x() + offset, bb.min.z() - 1),
+                    Direction::South => (bb.min.x() + offset, bb.max.z() + 1),
+                    Direction::West => (bb.min.x() - 1, bb.min.z() + offset),
+                    _ => (bb.max.x() + 1, bb.min.z() + offset),
+                };
+                if let Some(child) = self.generate_and_add_piece(x, y, z, side, depth) {
+                    entrances.push(match side {
+                        Direction::North => BoundingBox::new(
+                            child.min.x(),
+                            child.min.y(),
+                            bb.min.z(),
+                            child.max.x(),
+                            child.max.y(),
+                            bb.min.z() + 1,
+                        ),
+                        Direction::South => BoundingBox::new(
+                            child.min.x(),
+                            child.min.y(),
+                            bb.max.z() - 1,
+                            child.max.x(),
+                            child.max.y(),
+                            bb.max.z(),
+                        ),
+                        Direction::West => BoundingBox::new(
+                            bb.min.x(),
+                            child.min.y(),
+                            child.min.z(),
+                            bb.min.x() + 1,
+                            child.max.y(),
+                            child.max.z(),
+                        ),
+                        _ => BoundingBox::new(
+                            bb.max.x() - 1,
+                            child.min.y(),
+                            child.min.z(),
+                            bb.max.x(),
+                            child.max.y(),
+                            child.max.z(),
+                        ),
+                    });
+                }
+                offset += 4;
+            }
+        }
+
+        if let MineshaftPiece::Room {
+            child_entrance_boxes,
+            ..
+        } = &mut self.pieces[index]
+        {
+            child_entrance_boxes.extend(entrances);
+        }
+    }
+}
+
+fn default_state(block: BlockRef) -> BlockStateId {
+    REGISTRY.blocks.get_default_state_id(block)
+}
+
+fn is_block(state: BlockStateId, block: BlockRef) -> bool {
+    REGISTRY
+        .blocks
+        .by_state_id(state)
+        .is_some_and(|other| other.key == block.key)
+}
+
+fn is_liquid(state: BlockStateId) -> bool {
+    state.get_block().config.liquid
+}
+
+fn is_solid_render(state: BlockStateId) -> bool {
+    state.get_block().config.can_occlude && state.is_face_sturdy(Direction::Up)
+}
+
+fn is_replaceable_by_structures(state: BlockStateId) -> bool {
+    state.is_air()
+        || is_liquid(state)
+        || is_block(state, vanilla_blocks::GLOW_LICHEN)
+        || is_block(state, vanilla_blocks::SEAGRASS)
+        || is_block(state, vanilla_blocks::TALL_SEAGRASS)
+}
+
+fn is_falling_block(state: BlockStateId) -> bool {
+    let path = &state.get_block().key.path;
+    matches!(
+        &**path,
+        "sand" | "red_sand" | "gravel" | "suspicious_sand" | "suspicious_gravel" | "dragon_egg"
+    ) || path.ends_with("concrete_powder")
+        || path.ends_with("anvil")
+}
+
+/// Returns true if any block around the piece touches a liquid, like vanilla's `isInInvalidLocation`.
+fn is_in_invalid_location(piece: &StructurePiece, ctx: &PieceContext<'_>) -> bool {
+    // TODO: Mineshafts also stay out of biomes tagged `mineshaft_blocking`, like the deep dark
+    let bb = &piece.bounding_box;
+    let area = &ctx.area;
+    let min_x = (bb.min.x() - 1).max(area.min.x());
+    let min_y = (bb.min.y() - 1).max(area.min.y());
+    let min_z = (bb.min.z() - 1).max(area.min.z());
+    let max_x = (bb.max.x() + 1).min(area.max.x());
+    let max_y = (bb.max.y() + 1).min(area.max.y());
+    let max_z = (bb.max.z() + 1).min(area.max.z());
+    let liquid_at = |x, y, z| is_liquid(ctx.chunk.get_block_state(BlockPos::new(x, y, z)));
+
+    for x in min_x..=max_x {
+        for z in min_z..=max_z {
+            if liquid_at(x, min_y, z) || liquid_at(x, max_y, z) {
+                return true;
+            }
+        }
+    }
+    for x in min_x..=max_x {
+        for y in min_y..=max_y {
+            if liquid_at(x, y, min_z) || liquid_at(x, y, max_z) {
+                return true;
+            }
+        }
+    }
+    for z in min_z..=max_z {
+        for y in min_y..=max_y {
+            if liquid_at(min_x, y, z) || liquid_at(max_x, y, z) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Places the fences and beam of a corridor section if there's a ceiling above it.
+fn place_support(
+    piece: &StructurePiece,
+    ctx: &PieceContext<'_>,
+    random: &mut LegacyRandom,
+    mineshaft_type: MineshaftType,
+    z: i32,
+) {
+    let (min_x, max_x, top) = (0, 2, 2);
+    if (min_x..=max_x).any(|x| piece.get_block(ctx, x, top + 1, z).is_air()) {
+        return;
+    }
+
+    let cave_air = default_state(vanilla_blocks::CAVE_AIR);
+    let planks = mineshaft_type.planks();
+    let fence = mineshaft_type.fence();
+    piece.generate_box(
+        ctx,
+        BoundingBox::new(min_x, 0, z, min_x, top - 1, z),
+        fence.set_value(&BlockStateProperties::WEST, true),
+        cave_air,
+        false,
+    );
+    piece.generate_box(
+        ctx,
+        BoundingBox::new(max_x, 0, z, max_x, top - 1, z),
+        fence.set_value(&BlockStateProperties::EAST, true),
+        cave_air,
+        false,
+    );
+
+    if random.next_i32_bounded(4) == 0 {
+        piece.generate_box(
+            ctx,
+            BoundingBox::new(min_x, top, z, min_x, top, z),
+            planks,
+            cave_air,
+            false,
+        );
+        piece.generate_box(
+            ctx,
+            BoundingBox::new(max_x, top, z, max_x, top, z),
+            planks,
+            cave_air,
+            false,
+        );
+    } else {
+        piece.generate_box(
+            ctx,
+            BoundingBox::new(min_x, top, z, max_x, top, z),
+            planks,
+            cave_air,
+            false,
+        );
+        let torch = default_state(vanilla_blocks::WALL_TORCH);
+        piece.maybe_generate_block(
+            ctx,
+            random,
+            0.05,
+            min_x + 1,
+            top,
+            z - 1,
+            torch.set_value(&BlockStateProperties::HORIZONTAL_FACING, Direction::South),
+        );
+        piece.maybe_generate_block(
+            ctx,
+            random,
+            0.05,
+            min_x + 1,
+            top,
+            z + 1,
+            torch.set_value(&BlockStateProperties::HORIZONTAL_FACING, Direction::North),
+        );
+    }
+}
+
+/// Places a cobweb if the block is underground and hangs between two sturdy blocks.
+#[allow(clippy::too_many_arguments)]
+fn maybe_place_cobweb(
+    piece: &StructurePiece,
+    ctx: &PieceContext<'_>,
+    random: &mut LegacyRandom,
+    chance: f32,
+    x: i32,
+    y: i32,
+    z: i32,
+) {
+    if !piece.is_interior(ctx, x, y, z) || random.next_f32() >= chance {
+        return;
+    }
+
+    let pos = piece.world_pos(x, y, z);
+    let sturdy_neighbours = [
+        Direction::Down,
+        Direction::Up,
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ]
+    .into_iter()
+    .filter(|direction| {
+        let neighbour = direction.relative(&pos);
+        ctx.area.is_inside(neighbour)
+            && ctx
+                .chunk
+                .get_block_state(neighbour)
+                .is_face_sturdy(direction.opposite())
+    })
+    .take(2)
+    .count();
+    if sturdy_neighbours >= 2 {
+        piece.place_block(ctx, default_state(vanilla_blocks::COBWEB), x, y, z);
+    }
+}
+
+/// Places a rail with a chest minecart if there's room for it.
+fn create_chest(
+    piece: &StructurePiece,
+    ctx: &PieceContext<'_>,
+    random: &mut LegacyRandom,
+    x: i32,
+    y: i32,
+    z: i32,
+) {
+    let pos = piece.world_pos(x, y, z);
+    if !ctx.area.is_inside(pos)
+        || !ctx.chunk.get_block_state(pos).is_air()
+        || ctx.chunk.get_block_state(pos.offset(0, -1, 0)).is_air()
+    {
+        return;
+    }
+
+    let shape = if random.next_bool() {
+        RailShape::NorthSouth
+    } else {
+        RailShape::EastWest
+    };
+    let rail =
+        default_state(vanilla_blocks::RAIL).set_value(&BlockStateProperties::RAIL_SHAPE, shape);
+    piece.place_block(ctx, rail, x, y, z);
+    // TODO: Spawn a chest minecart with the abandoned_mineshaft loot table and this seed
+    let _loot_table_seed = random.next_i64();
+}
+
+/// Places planks below the corridor where the floor isn't sturdy.
+fn set_planks_block(
+    piece: &StructurePiece,
+    ctx: &PieceContext<'_>,
+    planks: BlockStateId,
+    x: i32,
+    y: i32,
+    z: i32,
+) {
+    if !piece.is_interior(ctx, x, y, z) {
+        return;
+    }
+    let pos = piece.world_pos(x, y, z);
+    if !ctx.chunk.get_block_state(pos).is_face_sturdy(Direction::Up) {
+        ctx.chunk
+            .set_block_state(pos, planks, UpdateFlags::UPDATE_CLIENTS);
+    }
+}
+
+/// Props up the planks under a corridor's ends with a log pillar or hangs them from a chain.
+fn place_double_lower_or_upper_support(
+    piece: &StructurePiece,
+    ctx: &PieceContext<'_>,
+    mineshaft_type: MineshaftType,
+    x: i32,
+    y: i32,
+    z: i32,
+) {
+    let planks = mineshaft_type.planks();
+    for x in [x, x + 2] {
+        if piece.get_block(ctx, x, y, z).get_block().key == planks.get_block().key {
+            fill_pillar_down_or_chain_up(piece, ctx, mineshaft_type, x, y, z);
+        }
+    }
+}
+
+fn fill_pillar_down_or_chain_up(
+    piece: &StructurePiece,
+    ctx: &PieceContext<'_>,
+    mineshaft_type: MineshaftType,
+    x: i32,
+    y: i32,
+    z: i32,
+) {
+    let pos = piece.world_pos(x, y, z);
+    if !ctx.area.is_inside(pos) {
+        return;
+    }
+
+    let base = pos.y();
+    let at = |y| BlockPos::new(pos.x(), y, pos.z());
+    let fill_column = |state, from: i32, to: i32| {
+        for y in from..to {
+            ctx.chunk
+                .set_block_state(at(y), state, UpdateFlags::UPDATE_CLIENTS);
+        }
+    };
+
+    let mut search_down = true;
+    let mut search_up = true;
+    let mut distance = 1;
+    while search_down || search_up {
+        if search_down {
+            let below = at(base - distance);
+            let state = ctx.chunk.get_block_state(below);
+            let replaceable =
+                is_replaceable_by_structures(state) && !is_block(state, vanilla_blocks::LAVA);
+            if !replaceable && state.is_face_sturdy_for(Direction::Up, SupportType::Center) {
+                fill_column(mineshaft_type.wood(), base - distance + 1, base);
+                return;
+            }
+            search_down = distance <= 20 && replaceable && below.y() > ctx.terrain.min_y() + 1;
+        }
+
+        if search_up {
+            let above = at(base + distance);
+            let state = ctx.chunk.get_block_state(above);
+            let replaceable = is_replaceable_by_structures(state);
+            if !replaceable
+                && state.is_face_sturdy_for(Direction::Down, SupportType::Center)
+                && !is_falling_block(state)
+            {
+                ctx.chunk.set_block_state(
+                    at(base + 1),
+                    mineshaft_type.fence(),
+                    UpdateFlags::UPDATE_CLIENTS,
+                );
+                fill_column(
+                    default_state(vanilla_blocks::IRON_CHAIN),
+                    base + 2,
+                    base + distance,
+                );
+                return;
+            }
+            search_up = distance <= 50 && replaceable;
+        }
+
+        distance += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corridor_children_stay_within_depth() {
+        let mut random = LegacyRandom::from_seed(1234);
+        let room = MineshaftPiece::room(&mut random, 2, 2);
+        let mut builder = Builder {
+            random,
+            start_box: room.piece().bounding_box,
+            pieces: vec![room],
+        };
+        builder.add_children(0);
+
+        assert!(builder.pieces.len() > 1);
+        for (i, a) in builder.pieces.iter().enumerate() {
+            assert!(a.piece().gen_depth <= MAX_DEPTH + 1);
+            // Pieces never overlap each other
+            for b in &builder.pieces[i + 1..] {
+                assert!(!a.piece().bounding_box.intersects(&b.piece().bounding_box));
+            }
+        }
+    }
+}
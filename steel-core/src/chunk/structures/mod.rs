@@ -0,0 +1,416 @@
+//! Structure generation, matching vanilla's `Structure` and `StructurePiece` classes.
+//!
+//! A structure first picks where it starts in a chunk, then assembles its
+//! pieces from there. Assembling only depends on the seed and the start chunk,
+//! so every chunk a structure overlaps can rebuild the same pieces and place
+//! the blocks that fall inside it.
+
+pub mod jigsaw;
+pub mod mineshaft;
+pub mod piece;
+pub mod template;
+pub mod village;
+
+use steel_registry::{
+    blocks::properties::Direction,
+    structure_set::{StructureSelectionEntry, StructureSet, StructureSetRef},
+};
+use steel_utils::{BlockPos, ChunkPos, Identifier, random::Random};
+
+use crate::chunk::{
+    chunk_access::ChunkAccess,
+    chunk_generator::ChunkGenerator,
+    structure_placement::{is_structure_chunk, large_feature},
+    structures::{mineshaft::MineshaftStructure, village::VillageStructure},
+};
+
+/// How many chunks away from its start a structure may reach.
+const STRUCTURE_REACH: i32 = 8;
+
+static VILLAGE_PLAINS: VillageStructure = VillageStructure::PLAINS;
+static VILLAGE_DESERT: VillageStructure = VillageStructure::DESERT;
+static MINESHAFT: MineshaftStructure = MineshaftStructure::NORMAL;
+static MINESHAFT_MESA: MineshaftStructure = MineshaftStructure::MESA;
+
+/// Returns the implementation of the structure with the given key, if it has one.
+#[must_use]
+pub fn structure_by_key(key: &Identifier) -> Option<&'static dyn Structure> {
+    if key.namespace != "minecraft" {
+        return None;
+    }
+    match &*key.path {
+        "village_plains" => Some(&VILLAGE_PLAINS),
+        "village_desert" => Some(&VILLAGE_DESERT),
+        "mineshaft" => Some(&MINESHAFT),
+        "mineshaft_mesa" => Some(&MINESHAFT_MESA),
+        _ => None,
+    }
+}
+
+/// Places the parts of structures from the given sets that reach into the chunk.
+///
+/// Vanilla saves where structures start and which chunks they reach. We don't
+/// store starts yet, so every chunk in reach is checked again instead.
+pub fn place_structures(
+    chunk: &ChunkAccess,
+    seed: i64,
+    sets: &[StructureSetRef],
+    terrain: &dyn TerrainHeight,
+) {
+    let pos = chunk.pos();
+    for &set in sets {
+        if !set
+            .structures
+            .iter()
+            .any(|entry| structure_by_key(&entry.structure).is_some())
+        {
+            continue;
+        }
+
+        for x in pos.0.x - STRUCTURE_REACH..=pos.0.x + STRUCTURE_REACH {
+            for z in pos.0.y - STRUCTURE_REACH..=pos.0.y + STRUCTURE_REACH {
+                if !is_structure_chunk(set, seed, x, z) {
+                    continue;
+                }
+                if let Some((structure, start)) =
+                    select_structure(set, seed, ChunkPos::new(x, z), terrain)
+                {
+                    structure.generate(chunk, start, seed, terrain);
+                }
+            }
+        }
+    }
+}
+
+/// Picks the structure of a set that starts in the given chunk, like vanilla's
+/// `ChunkGenerator.createStructures`. Structures that can't start there are
+/// skipped and another one is picked.
+fn select_structure(
+    set: &StructureSet,
+    seed: i64,
+    chunk_pos: ChunkPos,
+    terrain: &dyn TerrainHeight,
+) -> Option<(&'static dyn Structure, BlockPos)> {
+    let try_start = |entry: &StructureSelectionEntry| {
+        let structure = structure_by_key(&entry.structure)?;
+        let start = structure.find_start_position(seed, chunk_pos, terrain)?;
+        Some((structure, start))
+    };
+    if let [entry] = set.structures {
+        return try_start(entry);
+    }
+
+    let mut random = large_feature(seed, chunk_pos.0.x, chunk_pos.0.y);
+    let mut entries: Vec<_> = set.structures.iter().collect();
+    let mut total_weight: i32 = entries.iter().map(|entry| entry.weight).sum();
+    while !entries.is_empty() && total_weight > 0 {
+        let mut roll = random.next_i32_bounded(total_weight);
+        let index = entries
+            .iter()
+            .position(|entry| {
+                roll -= entry.weight;
+                roll < 0
+            })
+            .unwrap_or(entries.len() - 1);
+        let entry = entries.remove(index);
+        if let Some(found) = try_start(entry) {
+            return Some(found);
+        }
+        total_weight -= entry.weight;
+    }
+    None
+}
+
+/// Gives structures the height of the terrain they are placed on.
+pub trait TerrainHeight {
+    /// Returns the lowest y above the surface at the given column, like
+    /// vanilla's `getFirstFreeHeight` with the `WORLD_SURFACE_WG` heightmap.
+    fn first_free_height(&self, x: i32, z: i32) -> i32;
+
+    /// Returns the lowest y blocks can be placed at.
+    fn min_y(&self) -> i32 {
+        -64
+    }
+
+    /// Returns the sea level of the world.
+    fn sea_level(&self) -> i32 {
+        63
+    }
+}
+
+impl<F: Fn(i32, i32) -> i32> TerrainHeight for F {
+    fn first_free_height(&self, x: i32, z: i32) -> i32 {
+        self(x, z)
+    }
+}
+
+/// The terrain a chunk generator generates, before anything is built on it.
+pub struct GeneratorTerrain<'a, G: ?Sized> {
+    /// The generator of the terrain.
+    pub generator: &'a G,
+    /// The lowest y of the world.
+    pub min_y: i32,
+}
+
+impl<G: ChunkGenerator + ?Sized> TerrainHeight for GeneratorTerrain<'_, G> {
+    fn first_free_height(&self, x: i32, z: i32) -> i32 {
+        self.generator.base_height(x, z, self.min_y)
+    }
+
+    fn min_y(&self) -> i32 {
+        self.min_y
+    }
+}
+
+/// A structure that can be generated in the world.
+pub trait Structure: Send + Sync {
+    /// Returns where the structure starts if it can start in the given chunk.
+    fn find_start_position(
+        &self,
+        seed: i64,
+        chunk_pos: ChunkPos,
+        terrain: &dyn TerrainHeight,
+    ) -> Option<BlockPos>;
+
+    /// Places the part of the structure starting at `start` that lies in `chunk`.
+    fn generate(
+        &self,
+        chunk: &ChunkAccess,
+        start: BlockPos,
+        seed: i64,
+        terrain: &dyn TerrainHeight,
+    );
+}
+
+/// An axis-aligned box of blocks with inclusive bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    /// The lowest corner.
+    pub min: BlockPos,
+    /// The highest corner.
+    pub max: BlockPos,
+}
+
+impl BoundingBox {
+    /// Creates a box from its lowest and highest coordinates.
+    #[must_use]
+    pub const fn new(
+        min_x: i32,
+        min_y: i32,
+        min_z: i32,
+        max_x: i32,
+        max_y: i32,
+        max_z: i32,
+    ) -> Self {
+        Self {
+            min: BlockPos::new(min_x, min_y, min_z),
+            max: BlockPos::new(max_x, max_y, max_z),
+        }
+    }
+
+    /// Creates the smallest box containing both corners.
+    #[must_use]
+    pub fn from_corners(a: BlockPos, b: BlockPos) -> Self {
+        Self {
+            min: BlockPos::new(a.x().min(b.x()), a.y().min(b.y()), a.z().min(b.z())),
+            max: BlockPos::new(a.x().max(b.x()), a.y().max(b.y()), a.z().max(b.z())),
+        }
+    }
+
+    /// Returns the box covering every block of the given chunk over the given height range.
+    #[must_use]
+    pub fn for_chunk(chunk_pos: ChunkPos, min_y: i32, max_y: i32) -> Self {
+        let min_x = chunk_pos.0.x << 4;
+        let min_z = chunk_pos.0.y << 4;
+        Self {
+            min: BlockPos::new(min_x, min_y, min_z),
+            max: BlockPos::new(min_x + 15, max_y, min_z + 15),
+        }
+    }
+
+    /// Returns true if the boxes share at least one block.
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.max.x() >= other.min.x()
+            && self.min.x() <= other.max.x()
+            && self.max.y() >= other.min.y()
+            && self.min.y() <= other.max.y()
+            && self.max.z() >= other.min.z()
+            && self.min.z() <= other.max.z()
+    }
+
+    /// Returns true if the position is inside the box.
+    #[must_use]
+    pub fn is_inside(&self, pos: BlockPos) -> bool {
+        (self.min.x()..=self.max.x()).contains(&pos.x())
+            && (self.min.y()..=self.max.y()).contains(&pos.y())
+            && (self.min.z()..=self.max.z()).contains(&pos.z())
+    }
+
+    /// Returns true if the other box lies completely inside this one.
+    #[must_use]
+    pub fn contains(&self, other: &Self) -> bool {
+        self.is_inside(other.min) && self.is_inside(other.max)
+    }
+
+    /// Returns the box moved by the given offset.
+    #[must_use]
+    pub fn moved(&self, dx: i32, dy: i32, dz: i32) -> Self {
+        Self {
+            min: self.min.offset(dx, dy, dz),
+            max: self.max.offset(dx, dy, dz),
+        }
+    }
+
+    /// Grows the box to contain the given position.
+    pub fn encapsulate(&mut self, pos: BlockPos) {
+        *self = Self::from_corners(
+            BlockPos::new(
+                self.min.x().min(pos.x()),
+                self.min.y().min(pos.y()),
+                self.min.z().min(pos.z()),
+            ),
+            BlockPos::new(
+                self.max.x().max(pos.x()),
+                self.max.y().max(pos.y()),
+                self.max.z().max(pos.z()),
+            ),
+        );
+    }
+
+    /// Returns the height of the box in blocks.
+    #[must_use]
+    pub fn y_span(&self) -> i32 {
+        self.max.y() - self.min.y() + 1
+    }
+
+    /// Returns the center column of the box, rounded like vanilla.
+    #[must_use]
+    pub fn center_x(&self) -> i32 {
+        (self.min.x() + self.max.x()) / 2
+    }
+
+    /// Returns the center column of the box, rounded like vanilla.
+    #[must_use]
+    pub fn center_z(&self) -> i32 {
+        (self.min.z() + self.max.z()) / 2
+    }
+}
+
+/// A rotation around the y axis, in the order of vanilla's `Rotation` enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// No rotation.
+    None,
+    /// 90 degrees clockwise.
+    Clockwise90,
+    /// 180 degrees.
+    Clockwise180,
+    /// 90 degrees counter clockwise.
+    CounterClockwise90,
+}
+
+impl Rotation {
+    /// Every rotation, in the order vanilla picks them from.
+    pub const VALUES: [Self; 4] = [
+        Self::None,
+        Self::Clockwise90,
+        Self::Clockwise180,
+        Self::CounterClockwise90,
+    ];
+
+    /// Rotates a position around the origin.
+    #[must_use]
+    pub fn transform(self, pos: BlockPos) -> BlockPos {
+        let (x, y, z) = (pos.x(), pos.y(), pos.z());
+        match self {
+            Self::None => pos,
+            Self::Clockwise90 => BlockPos::new(-z, y, x),
+            Self::Clockwise180 => BlockPos::new(-x, y, -z),
+            Self::CounterClockwise90 => BlockPos::new(z, y, -x),
+        }
+    }
+
+    /// Rotates a direction. Vertical directions stay the same.
+    #[must_use]
+    pub fn rotate(self, direction: Direction) -> Direction {
+        match self {
+            Self::None => direction,
+            _ if !direction.is_horizontal() => direction,
+            Self::Clockwise90 => direction.rotate_y_clockwise(),
+            Self::Clockwise180 => direction.opposite(),
+            Self::CounterClockwise90 => direction.rotate_y_counter_clockwise(),
+        }
+    }
+
+    /// Rotates one of the 16 values of a `rotation` block state property.
+    #[must_use]
+    pub fn rotate_segment(self, segment: u8) -> u8 {
+        let quarters = match self {
+            Self::None => 0,
+            Self::Clockwise90 => 1,
+            Self::Clockwise180 => 2,
+            Self::CounterClockwise90 => 3,
+        };
+        (segment + quarters * 4) % 16
+    }
+}
+
+/// A mirror of a structure, like vanilla's `Mirror` enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mirror {
+    /// Not mirrored.
+    None,
+    /// Mirrored along the z axis, swapping north and south.
+    LeftRight,
+    /// Mirrored along the x axis, swapping west and east.
+    FrontBack,
+}
+
+impl Mirror {
+    /// Mirrors a direction.
+    #[must_use]
+    pub fn mirror(self, direction: Direction) -> Direction {
+        match (self, direction) {
+            (Self::LeftRight, Direction::North | Direction::South)
+            | (Self::FrontBack, Direction::West | Direction::East) => direction.opposite(),
+            _ => direction,
+        }
+    }
+
+    /// Mirrors one of the 16 values of a `rotation` block state property.
+    #[must_use]
+    pub fn mirror_segment(self, segment: u8) -> u8 {
+        match self {
+            Self::None => segment,
+            Self::LeftRight => (24 - segment) % 16,
+            Self::FrontBack => (16 - segment) % 16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_matches_direction_rotation() {
+        for rotation in Rotation::VALUES {
+            let (dx, dy, dz) = rotation.rotate(Direction::North).offset();
+            assert_eq!(
+                rotation.transform(BlockPos::new(0, 0, -1)),
+                BlockPos::new(dx, dy, dz)
+            );
+        }
+    }
+
+    #[test]
+    fn bounding_box_intersection() {
+        let a = BoundingBox::from_corners(BlockPos::new(0, 0, 0), BlockPos::new(4, 4, 4));
+        let b = BoundingBox::from_corners(BlockPos::new(4, 4, 4), BlockPos::new(8, 8, 8));
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&b.moved(1, 0, 0)));
+        assert!(a.contains(&a));
+        assert!(!a.contains(&b));
+    }
+}
@@ -0,0 +1,246 @@
+//! Structure pieces built from code, matching vanilla's `StructurePiece`.
+//!
+//! Pieces describe their blocks in local coordinates. The orientation of a
+//! piece decides how those map to the world: x runs across the piece and z
+//! runs along the direction it faces.
+
+use steel_registry::{
+    REGISTRY, blocks::block_state_ext::BlockStateExt, blocks::properties::Direction, vanilla_blocks,
+};
+use steel_utils::{
+    BlockPos, BlockStateId,
+    random::{Random, legacy_random::LegacyRandom},
+    types::UpdateFlags,
+};
+
+use crate::chunk::{
+    chunk_access::ChunkAccess,
+    structures::{
+        BoundingBox, Mirror, Rotation, TerrainHeight,
+        template::{mirror_state, rotate_state},
+    },
+};
+
+/// Where pieces are placed: the chunk being generated, limited to `area`.
+pub struct PieceContext<'a> {
+    /// The chunk being generated.
+    pub chunk: &'a ChunkAccess,
+    /// Blocks outside this box are neither read nor written.
+    pub area: BoundingBox,
+    /// Height of the terrain, used to tell whether a block is underground.
+    pub terrain: &'a dyn TerrainHeight,
+}
+
+/// The shape and orientation shared by every piece.
+#[derive(Debug, Clone, Copy)]
+pub struct StructurePiece {
+    /// The blocks the piece covers.
+    pub bounding_box: BoundingBox,
+    /// How many pieces lie between this one and the start piece.
+    pub gen_depth: i32,
+    orientation: Option<Direction>,
+    mirror: Mirror,
+    rotation: Rotation,
+}
+
+impl StructurePiece {
+    /// Creates a piece. Pieces without an orientation use world coordinates.
+    #[must_use]
+    pub fn new(gen_depth: i32, bounding_box: BoundingBox, orientation: Option<Direction>) -> Self {
+        let (mirror, rotation) = match orientation {
+            Some(Direction::South) => (Mirror::LeftRight, Rotation::None),
+            Some(Direction::West) => (Mirror::LeftRight, Rotation::Clockwise90),
+            Some(Direction::East) => (Mirror::None, Rotation::Clockwise90),
+            _ => (Mirror::None, Rotation::None),
+        };
+        Self {
+            bounding_box,
+            gen_depth,
+            orientation,
+            mirror,
+            rotation,
+        }
+    }
+
+    /// Returns the direction the piece faces.
+    #[must_use]
+    pub fn orientation(&self) -> Option<Direction> {
+        self.orientation
+    }
+
+    /// Returns the world position of a local position.
+    #[must_use]
+    pub fn world_pos(&self, x: i32, y: i32, z: i32) -> BlockPos {
+        let bb = &self.bounding_box;
+        let Some(orientation) = self.orientation else {
+            return BlockPos::new(x, y, z);
+        };
+        let (world_x, world_z) = match orientation {
+            Direction::South => (bb.min.x() + x, bb.min.z() + z),
+            Direction::West => (bb.max.x() - z, bb.min.z() + x),
+            Direction::East => (bb.min.x() + z, bb.min.z() + x),
+            _ => (bb.min.x() + x, bb.max.z() - z),
+        };
+        BlockPos::new(world_x, y + bb.min.y(), world_z)
+    }
+
+    /// Returns the block at a local position, or air outside the context's area.
+    #[must_use]
+    pub fn get_block(&self, ctx: &PieceContext<'_>, x: i32, y: i32, z: i32) -> BlockStateId {
+        let pos = self.world_pos(x, y, z);
+        if ctx.area.is_inside(pos) {
+            ctx.chunk.get_block_state(pos)
+        } else {
+            REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR)
+        }
+    }
+
+    /// Places a block at a local position, turned to match the piece's orientation.
+    pub fn place_block(&self, ctx: &PieceContext<'_>, state: BlockStateId, x: i32, y: i32, z: i32) {
+        let pos = self.world_pos(x, y, z);
+        if !ctx.area.is_inside(pos) {
+            return;
+        }
+        let state = rotate_state(mirror_state(state, self.mirror), self.rotation);
+        // TODO: Schedule fluid ticks and shape updates like vanilla does
+        ctx.chunk
+            .set_block_state(pos, state, UpdateFlags::UPDATE_CLIENTS);
+    }
+
+    /// Returns true if the block above the local position is below the surface.
+    #[must_use]
+    pub fn is_interior(&self, ctx: &PieceContext<'_>, x: i32, y: i32, z: i32) -> bool {
+        let pos = self.world_pos(x, y + 1, z);
+        ctx.area.is_inside(pos) && pos.y() < ctx.terrain.first_free_height(pos.x(), pos.z())
+    }
+
+    /// Places a block with the given chance.
+    #[allow(clippy::too_many_arguments)]
+    pub fn maybe_generate_block(
+        &self,
+        ctx: &PieceContext<'_>,
+        random: &mut LegacyRandom,
+        chance: f32,
+        x: i32,
+        y: i32,
+        z: i32,
+        state: BlockStateId,
+    ) {
+        if random.next_f32() < chance {
+            self.place_block(ctx, state, x, y, z);
+        }
+    }
+
+    /// Fills a local box, using `edge` for its outer shell and `inner` for the rest.
+    ///
+    /// With `existing_only`, air blocks are left alone.
+    pub fn generate_box(
+        &self,
+        ctx: &PieceContext<'_>,
+        local: BoundingBox,
+        edge: BlockStateId,
+        inner: BlockStateId,
+        existing_only: bool,
+    ) {
+        for y in local.min.y()..=local.max.y() {
+            for x in local.min.x()..=local.max.x() {
+                for z in local.min.z()..=local.max.z() {
+                    if existing_only && self.get_block(ctx, x, y, z).is_air() {
+                        continue;
+                    }
+                    let state = if is_edge(&local, x, y, z) {
+                        edge
+                    } else {
+                        inner
+                    };
+                    self.place_block(ctx, state, x, y, z);
+                }
+            }
+        }
+    }
+
+    /// Like `generate_box`, but places each block with the given chance.
+    ///
+    /// With `interior_only`, only blocks below the surface are placed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_maybe_box(
+        &self,
+        ctx: &PieceContext<'_>,
+        random: &mut LegacyRandom,
+        chance: f32,
+        local: BoundingBox,
+        edge: BlockStateId,
+        inner: BlockStateId,
+        existing_only: bool,
+        interior_only: bool,
+    ) {
+        for y in local.min.y()..=local.max.y() {
+            for x in local.min.x()..=local.max.x() {
+                for z in local.min.z()..=local.max.z() {
+                    if random.next_f32() <= chance
+                        && (!existing_only || !self.get_block(ctx, x, y, z).is_air())
+                        && (!interior_only || self.is_interior(ctx, x, y, z))
+                    {
+                        let state = if is_edge(&local, x, y, z) {
+                            edge
+                        } else {
+                            inner
+                        };
+                        self.place_block(ctx, state, x, y, z);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fills the upper half of the ellipsoid inside a local box.
+    pub fn generate_upper_half_sphere(
+        &self,
+        ctx: &PieceContext<'_>,
+        local: BoundingBox,
+        state: BlockStateId,
+        skip_air: bool,
+    ) {
+        let width = (local.max.x() - local.min.x() + 1) as f32;
+        let height = (local.max.y() - local.min.y() + 1) as f32;
+        let depth = (local.max.z() - local.min.z() + 1) as f32;
+        let center_x = local.min.x() as f32 + width / 2.0;
+        let center_z = local.min.z() as f32 + depth / 2.0;
+
+        for y in local.min.y()..=local.max.y() {
+            let dy = (y - local.min.y()) as f32 / height;
+            for x in local.min.x()..=local.max.x() {
+                let dx = (x as f32 - center_x) / (width * 0.5);
+                for z in local.min.z()..=local.max.z() {
+                    let dz = (z as f32 - center_z) / (depth * 0.5);
+                    if skip_air && self.get_block(ctx, x, y, z).is_air() {
+                        continue;
+                    }
+                    if dx * dx + dy * dy + dz * dz <= 1.05 {
+                        self.place_block(ctx, state, x, y, z);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn is_edge(local: &BoundingBox, x: i32, y: i32, z: i32) -> bool {
+    y == local.min.y()
+        || y == local.max.y()
+        || x == local.min.x()
+        || x == local.max.x()
+        || z == local.min.z()
+        || z == local.max.z()
+}
+
+/// Returns the first piece whose box intersects `bounding_box`.
+#[must_use]
+pub fn find_collision_piece<'a>(
+    pieces: impl IntoIterator<Item = &'a StructurePiece>,
+    bounding_box: &BoundingBox,
+) -> Option<&'a StructurePiece> {
+    pieces
+        .into_iter()
+        .find(|piece| piece.bounding_box.intersects(bounding_box))
+}
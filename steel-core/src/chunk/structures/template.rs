@@ -0,0 +1,400 @@
+//! Structure templates loaded from the vanilla `.nbt` structure files.
+
+use std::{
+    io::{Cursor, Read},
+    sync::{Arc, LazyLock},
+};
+
+use flate2::read::GzDecoder;
+use rustc_hash::FxHashMap;
+use simdnbt::borrow::{NbtCompound as NbtCompoundView, read_compound as read_borrowed_compound};
+use steel_registry::{
+    REGISTRY,
+    blocks::properties::{Direction, PropertyEnum},
+    vanilla_structure_templates::structure_template_bytes,
+};
+use steel_utils::{BlockPos, BlockStateId, Identifier, locks::SyncRwLock};
+
+use crate::chunk::structures::{BoundingBox, Mirror, Rotation};
+
+/// Templates that were already parsed, or `None` if the template doesn't exist.
+static TEMPLATES: LazyLock<SyncRwLock<FxHashMap<Identifier, Option<Arc<StructureTemplate>>>>> =
+    LazyLock::new(|| SyncRwLock::new(FxHashMap::default()));
+
+/// A block of a template.
+#[derive(Debug, Clone, Copy)]
+pub struct StructureBlockInfo {
+    /// Position relative to the template's origin.
+    pub pos: BlockPos,
+    /// The unrotated block state.
+    pub state: BlockStateId,
+    /// Whether the block is air, which legacy pool elements don't place.
+    pub is_air: bool,
+}
+
+/// A jigsaw block of a template, used to connect pieces to each other.
+#[derive(Debug, Clone)]
+pub struct JigsawBlockInfo {
+    /// Position relative to the template's origin.
+    pub pos: BlockPos,
+    /// The direction the jigsaw connects towards.
+    pub front: Direction,
+    /// The direction the top of the jigsaw faces.
+    pub top: Direction,
+    /// Name other jigsaws target to connect to this one.
+    pub name: Identifier,
+    /// Name of the jigsaws this one connects to.
+    pub target: Identifier,
+    /// Pool the piece connected to this jigsaw is picked from.
+    pub pool: Identifier,
+    /// Rollable jigsaws connect regardless of which way their tops face.
+    pub rollable: bool,
+    /// The block that replaces the jigsaw, or `None` to leave the existing block.
+    pub final_state: Option<BlockStateId>,
+    /// Jigsaws of pieces with a higher placement priority are processed first.
+    pub placement_priority: i32,
+    /// Jigsaws with a higher selection priority of a piece are tried first.
+    pub selection_priority: i32,
+}
+
+impl JigsawBlockInfo {
+    /// Returns the jigsaw moved by `offset` after rotating it around the template's origin.
+    #[must_use]
+    pub fn transformed(&self, offset: BlockPos, rotation: Rotation) -> Self {
+        let pos = rotation.transform(self.pos);
+        Self {
+            pos: pos.offset(offset.x(), offset.y(), offset.z()),
+            front: rotation.rotate(self.front),
+            top: rotation.rotate(self.top),
+            ..self.clone()
+        }
+    }
+
+    /// Returns true if `candidate` can be attached to this jigsaw, like vanilla's `JigsawBlock.canAttach`.
+    #[must_use]
+    pub fn can_attach(&self, candidate: &Self) -> bool {
+        self.front == candidate.front.opposite()
+            && (self.rollable || self.top == candidate.top)
+            && self.target == candidate.name
+    }
+}
+
+/// A parsed structure template.
+#[derive(Debug)]
+pub struct StructureTemplate {
+    /// Size of the template in blocks.
+    pub size: [i32; 3],
+    /// Every block except jigsaws and structure blocks.
+    pub blocks: Vec<StructureBlockInfo>,
+    /// Jigsaw blocks, sorted by position like vanilla does.
+    pub jigsaws: Vec<JigsawBlockInfo>,
+}
+
+impl StructureTemplate {
+    /// Returns the template with the given id, loading it from the embedded vanilla files.
+    pub fn get(id: &Identifier) -> Option<Arc<Self>> {
+        if let Some(template) = TEMPLATES.read().get(id) {
+            return template.clone();
+        }
+
+        let template = (id.namespace == "minecraft")
+            .then(|| structure_template_bytes(&id.path))
+            .flatten()
+            .and_then(|bytes| {
+                let template = Self::parse(bytes);
+                if template.is_none() {
+                    log::warn!("Failed to parse structure template {id}");
+                }
+                template
+            })
+            .map(Arc::new);
+        TEMPLATES.write().insert(id.clone(), template.clone());
+        template
+    }
+
+    /// Parses a gzipped structure template.
+    #[must_use]
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let mut data = Vec::new();
+        GzDecoder::new(bytes).read_to_end(&mut data).ok()?;
+
+        // Skip the root tag's id and name, the reader expects a nameless compound
+        let name_len = u16::from_be_bytes([*data.get(1)?, *data.get(2)?]) as usize;
+        let root = read_borrowed_compound(&mut Cursor::new(data.get(3 + name_len..)?)).ok()?;
+        let root: NbtCompoundView<'_, '_> = (&root).into();
+
+        let size = root.list("size")?.ints()?.to_vec();
+        let size = [*size.first()?, *size.get(1)?, *size.get(2)?];
+
+        // TODO: Templates with several palettes, like shipwrecks, pick one at random
+        let palette: Vec<Option<BlockStateId>> = root
+            .list("palette")?
+            .compounds()?
+            .into_iter()
+            .map(|entry| {
+                let name: Identifier = entry.string("Name")?.to_str().parse().ok()?;
+                let properties: Vec<(String, String)> = entry
+                    .compound("Properties")
+                    .map(|properties| {
+                        properties
+                            .iter()
+                            .filter_map(|(key, value)| {
+                                Some((
+                                    key.to_str().into_owned(),
+                                    value.string()?.to_str().into_owned(),
+                                ))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let properties: Vec<(&str, &str)> = properties
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.as_str()))
+                    .collect();
+                REGISTRY.blocks.state_id_from_properties(&name, &properties)
+            })
+            .collect();
+
+        let mut blocks = Vec::new();
+        let mut jigsaws = Vec::new();
+        for block in root.list("blocks")?.compounds()? {
+            let pos = block.list("pos")?.ints()?.to_vec();
+            let pos = BlockPos::new(*pos.first()?, *pos.get(1)?, *pos.get(2)?);
+            let Some(state) = palette.get(block.int("state")? as usize).copied().flatten() else {
+                continue;
+            };
+            let Some(block_ref) = REGISTRY.blocks.by_state_id(state) else {
+                continue;
+            };
+
+            match block_ref.key.path.as_ref() {
+                "structure_block" => {}
+                "jigsaw" => {
+                    if let Some(nbt) = block.compound("nbt")
+                        && let Some(jigsaw) = parse_jigsaw(pos, state, &nbt)
+                    {
+                        jigsaws.push(jigsaw);
+                    }
+                }
+                path => blocks.push(StructureBlockInfo {
+                    pos,
+                    state,
+                    is_air: matches!(path, "air" | "cave_air" | "void_air"),
+                }),
+            }
+        }
+        jigsaws.sort_by_key(|jigsaw| (jigsaw.pos.y(), jigsaw.pos.x(), jigsaw.pos.z()));
+
+        Some(Self {
+            size,
+            blocks,
+            jigsaws,
+        })
+    }
+
+    /// Returns the box the template covers when rotated around its origin and moved to `pos`.
+    #[must_use]
+    pub fn bounding_box(&self, pos: BlockPos, rotation: Rotation) -> BoundingBox {
+        let [x, y, z] = self.size;
+        let corner = rotation.transform(BlockPos::new(x - 1, y - 1, z - 1));
+        BoundingBox::from_corners(BlockPos::new(0, 0, 0), corner).moved(pos.x(), pos.y(), pos.z())
+    }
+}
+
+/// Reads a jigsaw block's data, like vanilla's `JigsawBlockInfo.of`.
+fn parse_jigsaw(
+    pos: BlockPos,
+    state: BlockStateId,
+    nbt: &NbtCompoundView<'_, '_>,
+) -> Option<JigsawBlockInfo> {
+    let (_, orientation) = REGISTRY
+        .blocks
+        .get_properties(state)
+        .into_iter()
+        .find(|(name, _)| *name == "orientation")?;
+    let (front, top) = orientation.split_once('_')?;
+    let front = direction_from_name(front)?;
+    let top = direction_from_name(top)?;
+
+    let string = |key: &str| nbt.string(key).map(|value| value.to_str().into_owned());
+    let rollable = match string("joint").as_deref() {
+        Some("rollable") => true,
+        Some("aligned") => false,
+        _ => !front.is_horizontal(),
+    };
+    // Structure voids keep whatever block was there before
+    let final_state =
+        parse_block_state(&string("final_state").unwrap_or_else(|| "minecraft:air".to_string()))
+            .filter(|state| {
+                REGISTRY
+                    .blocks
+                    .by_state_id(*state)
+                    .is_some_and(|block| block.key.path != "structure_void")
+            });
+
+    Some(JigsawBlockInfo {
+        pos,
+        front,
+        top,
+        name: string("name")?.parse().ok()?,
+        target: string("target")?.parse().ok()?,
+        pool: string("pool")?.parse().ok()?,
+        rollable,
+        final_state,
+        placement_priority: nbt.int("placement_priority").unwrap_or(0),
+        selection_priority: nbt.int("selection_priority").unwrap_or(0),
+    })
+}
+
+/// Parses a block state such as `minecraft:oak_stairs[facing=east]`.
+fn parse_block_state(value: &str) -> Option<BlockStateId> {
+    let (name, properties) = match value.split_once('[') {
+        Some((name, properties)) => (name, properties.strip_suffix(']')?),
+        None => (value, ""),
+    };
+    let properties: Vec<(&str, &str)> = properties
+        .split(',')
+        .filter(|property| !property.is_empty())
+        .map(|property| property.split_once('='))
+        .collect::<Option<_>>()?;
+    REGISTRY
+        .blocks
+        .state_id_from_properties(&name.parse().ok()?, &properties)
+}
+
+fn direction_from_name(name: &str) -> Option<Direction> {
+    Some(match name {
+        "down" => Direction::Down,
+        "up" => Direction::Up,
+        "north" => Direction::North,
+        "south" => Direction::South,
+        "west" => Direction::West,
+        "east" => Direction::East,
+        _ => return None,
+    })
+}
+
+/// Rotates a block state, like vanilla's `BlockState.rotate`.
+///
+/// This handles the properties most blocks use for their orientation. Blocks
+/// with special rules, like stair shapes, keep the rest of their state.
+#[must_use]
+pub fn rotate_state(state: BlockStateId, rotation: Rotation) -> BlockStateId {
+    if rotation == Rotation::None {
+        return state;
+    }
+    transform_state(
+        state,
+        |direction| rotation.rotate(direction),
+        |segment| rotation.rotate_segment(segment),
+        matches!(
+            rotation,
+            Rotation::Clockwise90 | Rotation::CounterClockwise90
+        ),
+    )
+}
+
+/// Mirrors a block state, like vanilla's `BlockState.mirror`.
+#[must_use]
+pub fn mirror_state(state: BlockStateId, mirror: Mirror) -> BlockStateId {
+    if mirror == Mirror::None {
+        return state;
+    }
+    transform_state(
+        state,
+        |direction| mirror.mirror(direction),
+        |segment| mirror.mirror_segment(segment),
+        false,
+    )
+}
+
+fn transform_state(
+    state: BlockStateId,
+    map: impl Fn(Direction) -> Direction,
+    map_segment: impl Fn(u8) -> u8,
+    swap_axes: bool,
+) -> BlockStateId {
+    let Some(block) = REGISTRY.blocks.by_state_id(state) else {
+        return state;
+    };
+    let is_rail = block.key.path.ends_with("rail");
+
+    let properties = REGISTRY.blocks.get_properties(state);
+    let transformed: Vec<(&str, String)> = properties
+        .iter()
+        .map(|&(name, value)| {
+            let value = match name {
+                "facing" => direction_from_name(value).map_or(value.to_string(), |direction| {
+                    map(direction).as_str().to_string()
+                }),
+                "axis" if swap_axes => match value {
+                    "x" => "z".to_string(),
+                    "z" => "x".to_string(),
+                    other => other.to_string(),
+                },
+                "rotation" => value
+                    .parse::<u8>()
+                    .map_or(value.to_string(), |value| map_segment(value).to_string()),
+                "orientation" => value
+                    .split_once('_')
+                    .and_then(|(front, top)| {
+                        let front = map(direction_from_name(front)?);
+                        let top = map(direction_from_name(top)?);
+                        Some(format!("{}_{}", front.as_str(), top.as_str()))
+                    })
+                    .unwrap_or_else(|| value.to_string()),
+                "shape" if is_rail => {
+                    transform_rail_shape(value, &map).unwrap_or_else(|| value.to_string())
+                }
+                // Connections move with the block, so each side takes the value of the side moved onto it
+                "north" | "east" | "south" | "west" => [
+                    Direction::North,
+                    Direction::East,
+                    Direction::South,
+                    Direction::West,
+                ]
+                .into_iter()
+                .find(|source| map(*source).as_str() == name)
+                .and_then(|source| {
+                    properties
+                        .iter()
+                        .find(|(key, _)| *key == source.as_str())
+                        .map(|(_, value)| (*value).to_string())
+                })
+                .unwrap_or_else(|| value.to_string()),
+                _ => value.to_string(),
+            };
+            (name, value)
+        })
+        .collect();
+
+    let transformed: Vec<(&str, &str)> = transformed
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+    REGISTRY
+        .blocks
+        .state_id_from_properties(&block.key, &transformed)
+        .unwrap_or(state)
+}
+
+/// Transforms a rail shape such as `ascending_east` or `north_west`.
+fn transform_rail_shape(shape: &str, map: impl Fn(Direction) -> Direction) -> Option<String> {
+    if let Some(direction) = shape.strip_prefix("ascending_") {
+        return Some(format!(
+            "ascending_{}",
+            map(direction_from_name(direction)?).as_str()
+        ));
+    }
+
+    let (a, b) = shape.split_once('_')?;
+    let (a, b) = (map(direction_from_name(a)?), map(direction_from_name(b)?));
+    let is_z = |direction: Direction| matches!(direction, Direction::North | Direction::South);
+    Some(match (is_z(a), is_z(b)) {
+        (true, true) => "north_south".to_string(),
+        (false, false) => "east_west".to_string(),
+        // Curves always name the north or south side first
+        (true, false) => format!("{}_{}", a.as_str(), b.as_str()),
+        (false, true) => format!("{}_{}", b.as_str(), a.as_str()),
+    })
+}
@@ -0,0 +1,65 @@
+//! Villages, which vanilla defines as jigsaw structures in the
+//! `worldgen/structure/village_*.json` files.
+//!
+//! A village starts at a town center and grows along its streets, which have
+//! jigsaws for houses, decorations and more streets. Beds, job site blocks and
+//! the villagers themselves are all part of the templates.
+
+use steel_utils::{BlockPos, ChunkPos, Identifier};
+
+use crate::chunk::{
+    chunk_access::ChunkAccess,
+    structures::{Structure, TerrainHeight, jigsaw::JigsawStructure},
+};
+
+/// A village structure.
+#[derive(Debug)]
+pub struct VillageStructure {
+    /// How the village is assembled from its pools.
+    pub jigsaw: JigsawStructure,
+}
+
+impl VillageStructure {
+    /// The plains village, `minecraft:village_plains`.
+    pub const PLAINS: Self = Self::new("village/plains/town_centers");
+
+    /// The desert village, `minecraft:village_desert`.
+    pub const DESERT: Self = Self::new("village/desert/town_centers");
+
+    /// Every vanilla village shares these settings, only the start pool differs.
+    const fn new(start_pool: &'static str) -> Self {
+        Self {
+            jigsaw: JigsawStructure {
+                start_pool: Identifier::vanilla_static(start_pool),
+                max_depth: 6,
+                max_distance_from_center: 80,
+                start_height: 0,
+                project_start_to_heightmap: true,
+                use_expansion_hack: true,
+            },
+        }
+    }
+}
+
+impl Structure for VillageStructure {
+    fn find_start_position(
+        &self,
+        seed: i64,
+        chunk_pos: ChunkPos,
+        terrain: &dyn TerrainHeight,
+    ) -> Option<BlockPos> {
+        self.jigsaw.find_start_position(seed, chunk_pos, terrain)
+    }
+
+    fn generate(
+        &self,
+        chunk: &ChunkAccess,
+        start: BlockPos,
+        seed: i64,
+        terrain: &dyn TerrainHeight,
+    ) {
+        self.jigsaw.generate(chunk, start, seed, terrain);
+        // TODO: Spawn the villagers, cats and iron golems from the templates' entity lists.
+        // Villagers claim their beds and job sites through POIs once they exist.
+    }
+}
@@ -5,15 +5,17 @@ use std::sync::{Arc, Weak};
 use enum_dispatch::enum_dispatch;
 
 use crate::chunk::{
-    chunk_access::ChunkAccess, chunk_generator::ChunkGenerator,
-    flat_chunk_generator::FlatChunkGenerator,
+    chunk_access::ChunkAccess,
+    chunk_generator::ChunkGenerator,
+    generators::{end::TheEndGenerator, flat::FlatWorldGenerator},
 };
 use crate::world::World;
 
 #[allow(missing_docs)]
 #[enum_dispatch(ChunkGenerator)]
 pub enum ChunkGeneratorType {
-    Flat(FlatChunkGenerator),
+    Flat(FlatWorldGenerator),
+    End(TheEndGenerator),
     //Custom(Box<dyn ChunkGenerator>),
 }
 
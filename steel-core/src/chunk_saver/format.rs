@@ -28,6 +28,8 @@
 //! Block data uses power-of-2 bit packing (1, 2, 4, 8, 16 bits) to avoid entries
 //! spanning u64 boundaries.
 
+use std::io;
+
 use steel_utils::Identifier;
 use wincode::{SchemaRead, SchemaWrite};
 
@@ -37,7 +39,11 @@ use crate::chunk::chunk_access::ChunkStatus;
 pub const REGION_MAGIC: [u8; 4] = *b"STLR";
 
 /// Current format version. Increment when making breaking changes.
-pub const FORMAT_VERSION: u16 = 2;
+pub const FORMAT_VERSION: u16 = 3;
+
+/// Oldest format version that can still be read. Regions saved in it are
+/// rewritten in the current version when opened.
+pub const MIN_FORMAT_VERSION: u16 = 2;
 
 /// Number of chunks per region side (32×32 = 1024 chunks per region).
 pub const REGION_SIZE: usize = 32;
@@ -286,6 +292,55 @@ pub struct PersistentChunk {
     pub sections: Vec<PersistentSection>,
     /// Block entities (chests, signs, etc.). Currently placeholder.
     pub block_entities: Vec<PersistentBlockEntity>,
+    /// Entities in this chunk.
+    pub entities: Vec<PersistentEntity>,
+}
+
+impl PersistentChunk {
+    /// Decodes a chunk saved in format `version`. Chunks from before entities
+    /// were saved load without any.
+    pub fn decode(data: &[u8], version: u16) -> io::Result<Self> {
+        let chunk = match version {
+            2 => wincode::deserialize::<PersistentChunkV2>(data).map(Self::from),
+            FORMAT_VERSION => wincode::deserialize(data),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Chunks of format version {version} can't be read"),
+                ));
+            }
+        };
+        chunk.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+    }
+}
+
+/// A chunk saved in format version 2, before entities were saved with their
+/// chunk.
+#[derive(SchemaWrite, SchemaRead)]
+pub struct PersistentChunkV2 {
+    /// Unix timestamp of last modification.
+    pub last_modified: u32,
+    /// Block states used in this chunk.
+    pub block_states: Vec<PersistentBlockState>,
+    /// Biomes used in this chunk.
+    pub biomes: Vec<Identifier>,
+    /// Vertical sections.
+    pub sections: Vec<PersistentSection>,
+    /// Block entities.
+    pub block_entities: Vec<PersistentBlockEntity>,
+}
+
+impl From<PersistentChunkV2> for PersistentChunk {
+    fn from(chunk: PersistentChunkV2) -> Self {
+        Self {
+            last_modified: chunk.last_modified,
+            block_states: chunk.block_states,
+            biomes: chunk.biomes,
+            sections: chunk.sections,
+            block_entities: chunk.block_entities,
+            entities: Vec::new(),
+        }
+    }
 }
 
 /// A 16×16×16 section of a chunk.
@@ -349,6 +404,18 @@ pub struct PersistentBlockEntity {
     pub nbt_data: Vec<u8>,
 }
 
+/// An entity stored with the chunk it is in.
+///
+/// Entities are serialized with their type and NBT data, which holds their
+/// position. The NBT data is stored as raw bytes (simdnbt binary format).
+#[derive(SchemaWrite, SchemaRead, Hash)]
+pub struct PersistentEntity {
+    /// Entity type identifier (e.g., "minecraft:falling_block").
+    pub entity_type: Identifier,
+    /// Serialized NBT data (simdnbt binary format).
+    pub nbt_data: Vec<u8>,
+}
+
 /// Position of a region in region coordinates.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct RegionPos {
@@ -482,4 +549,69 @@ mod tests {
         // Needs more than gap, append at end
         assert_eq!(header.find_free_sectors(6, 12), 12);
     }
+
+    fn test_sections() -> Vec<PersistentSection> {
+        vec![PersistentSection::Homogeneous {
+            block_state: 0,
+            biomes: PersistentBiomeData::Homogeneous { biome: 0 },
+        }]
+    }
+
+    #[test]
+    fn test_v2_chunk_loads_without_entities() {
+        let chunk = PersistentChunkV2 {
+            last_modified: 1234,
+            block_states: vec![PersistentBlockState {
+                name: Identifier::vanilla_static("stone"),
+                properties: Vec::new(),
+            }],
+            biomes: vec![Identifier::vanilla_static("plains")],
+            sections: test_sections(),
+            block_entities: Vec::new(),
+        };
+        let data = wincode::serialize(&chunk).expect("v2 chunks serialize");
+
+        let decoded = PersistentChunk::decode(&data, 2).expect("v2 chunks decode");
+        assert_eq!(decoded.last_modified, 1234);
+        assert_eq!(decoded.block_states, chunk.block_states);
+        assert_eq!(decoded.biomes, chunk.biomes);
+        assert_eq!(decoded.sections.len(), 1);
+        assert!(decoded.entities.is_empty());
+
+        // Upgraded chunks are saved and read back in the current format
+        let data = wincode::serialize(&decoded).expect("chunks serialize");
+        let upgraded = PersistentChunk::decode(&data, FORMAT_VERSION).expect("chunks decode");
+        assert_eq!(upgraded.block_states, chunk.block_states);
+        assert!(upgraded.entities.is_empty());
+    }
+
+    #[test]
+    fn test_chunk_roundtrip_keeps_entities() {
+        let chunk = PersistentChunk {
+            last_modified: 0,
+            block_states: Vec::new(),
+            biomes: Vec::new(),
+            sections: test_sections(),
+            block_entities: Vec::new(),
+            entities: vec![PersistentEntity {
+                entity_type: Identifier::vanilla_static("armor_stand"),
+                nbt_data: vec![1, 2, 3],
+            }],
+        };
+        let data = wincode::serialize(&chunk).expect("chunks serialize");
+
+        let decoded = PersistentChunk::decode(&data, FORMAT_VERSION).expect("chunks decode");
+        assert_eq!(decoded.entities.len(), 1);
+        assert_eq!(
+            decoded.entities[0].entity_type,
+            Identifier::vanilla_static("armor_stand")
+        );
+        assert_eq!(decoded.entities[0].nbt_data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_unknown_versions_are_refused() {
+        assert!(PersistentChunk::decode(&[], 1).is_err());
+        assert!(PersistentChunk::decode(&[], FORMAT_VERSION + 1).is_err());
+    }
 }
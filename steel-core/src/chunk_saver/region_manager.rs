@@ -35,9 +35,9 @@ use super::{
     bit_pack::{bits_for_palette_len, pack_indices, unpack_indices},
     format::{
         BIOMES_PER_SECTION, BLOCKS_PER_SECTION, CHUNK_TABLE_SIZE, FILE_HEADER_SIZE,
-        FIRST_DATA_SECTOR, FORMAT_VERSION, MAX_CHUNK_SIZE, PersistentBiomeData,
-        PersistentBlockEntity, PersistentBlockState, PersistentChunk, PersistentSection,
-        REGION_MAGIC, RegionHeader, RegionPos, SECTOR_SIZE,
+        FIRST_DATA_SECTOR, FORMAT_VERSION, MAX_CHUNK_SIZE, MIN_FORMAT_VERSION, PersistentBiomeData,
+        PersistentBlockEntity, PersistentBlockState, PersistentChunk, PersistentEntity,
+        PersistentSection, REGION_MAGIC, RegionHeader, RegionPos, SECTOR_SIZE,
     },
 };
 
@@ -194,15 +194,28 @@ impl RegionManager {
                 ),
             ));
         }
+        if version < MIN_FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Region file version {version} is older than the oldest supported version {MIN_FORMAT_VERSION}"
+                ),
+            ));
+        }
 
         // Read chunk table
         let mut table_bytes = vec![0u8; CHUNK_TABLE_SIZE];
         file.read_exact(&mut table_bytes).await?;
-        let header = RegionHeader::from_bytes(&table_bytes);
+        let mut header = RegionHeader::from_bytes(&table_bytes);
 
         // Calculate file size in sectors
         let file_size = file.seek(io::SeekFrom::End(0)).await?;
-        let file_sectors = file_size.div_ceil(SECTOR_SIZE as u64) as u32;
+        let mut file_sectors = file_size.div_ceil(SECTOR_SIZE as u64) as u32;
+
+        if version < FORMAT_VERSION {
+            log::info!("Upgrading region {pos:?} from format version {version}");
+            Self::upgrade_region(&mut file, &mut header, &mut file_sectors, version).await?;
+        }
 
         Ok(RegionHandle {
             file,
@@ -213,6 +226,56 @@ impl RegionManager {
         })
     }
 
+    /// Rewrites every chunk of a region saved in an older format `version`
+    /// in the current one, so chunks saved later don't mix formats in one
+    /// file.
+    ///
+    /// The upgraded chunks are appended and the header is written last, so
+    /// the region stays readable in its old version if this is interrupted.
+    async fn upgrade_region(
+        file: &mut File,
+        header: &mut RegionHeader,
+        file_sectors: &mut u32,
+        version: u16,
+    ) -> io::Result<()> {
+        for index in 0..header.entries.len() {
+            let entry = header.entries[index];
+            if !entry.exists() {
+                continue;
+            }
+            let compressed =
+                Self::read_chunk_data(file, entry.sector_offset, entry.size_bytes).await?;
+            let data = zstd::decode_all(&compressed[..])?;
+            let persistent = PersistentChunk::decode(&data, version)?;
+            let compressed = Self::encode_chunk(&persistent)?;
+
+            let sector_offset = *file_sectors;
+            Self::write_chunk_data(file, sector_offset, &compressed, file_sectors).await?;
+            header.entries[index] = super::format::ChunkEntry::new(
+                sector_offset,
+                compressed.len() as u32,
+                entry.status,
+            );
+        }
+
+        // The new version and the table pointing at the upgraded chunks go
+        // out in one write
+        let mut header_bytes = vec![0u8; FILE_HEADER_SIZE];
+        header_bytes[0..4].copy_from_slice(&REGION_MAGIC);
+        header_bytes[4..6].copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+        header_bytes.extend_from_slice(&header.to_bytes());
+        file.seek(io::SeekFrom::Start(0)).await?;
+        file.write_all(&header_bytes).await?;
+        file.flush().await
+    }
+
+    /// Serializes and compresses a chunk in the current format.
+    fn encode_chunk(persistent: &PersistentChunk) -> io::Result<Vec<u8>> {
+        let data = wincode::serialize(persistent)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        zstd::encode_all(&data[..], 3)
+    }
+
     /// Creates a new empty region file.
     async fn create_region(&self, pos: RegionPos) -> io::Result<RegionHandle> {
         fs::create_dir_all(&self.base_path).await?;
@@ -307,9 +370,17 @@ impl RegionManager {
     /// Returns `Ok(true)` if the chunk was saved.
     /// Prepares chunk data for saving. Call this while holding the chunk lock,
     /// then pass the result to `save_chunk_data` after releasing the lock.
+    ///
+    /// `entities` are the entities in the chunk, saved by the world before
+    /// the chunk lock was taken. Returns `None` if neither the chunk nor its
+    /// entities changed since it was last saved.
     #[must_use]
-    pub fn prepare_chunk_save(chunk: &ChunkAccess) -> Option<PreparedChunkSave> {
-        if !chunk.is_dirty() {
+    pub fn prepare_chunk_save(
+        chunk: &ChunkAccess,
+        entities: Vec<PersistentEntity>,
+        entities_changed: bool,
+    ) -> Option<PreparedChunkSave> {
+        if !chunk.is_dirty() && !entities_changed {
             return None;
         }
 
@@ -321,7 +392,7 @@ impl RegionManager {
             .map(super::super::chunk::level_chunk::LevelChunk::get_block_entities)
             .unwrap_or_default();
 
-        let persistent = Self::to_persistent(chunk.sections(), &block_entities, pos);
+        let persistent = Self::to_persistent(chunk.sections(), &block_entities, entities, pos);
 
         Some(PreparedChunkSave { pos, persistent })
     }
@@ -353,13 +424,8 @@ impl RegionManager {
             regions.get_mut(&region_pos).expect("just inserted")
         };
 
-        // Serialize the prepared data
-        let persistent = prepared.persistent;
-        let data = wincode::serialize(&persistent)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-
-        // Compress with zstd
-        let compressed = zstd::encode_all(&data[..], 3)?;
+        // Serialize and compress the prepared data
+        let compressed = Self::encode_chunk(&prepared.persistent)?;
 
         if compressed.len() > MAX_CHUNK_SIZE {
             // Clean up if we opened the region
@@ -461,9 +527,8 @@ impl RegionManager {
         // Decompress
         let data = zstd::decode_all(&compressed[..])?;
 
-        // Deserialize
-        let persistent: PersistentChunk = wincode::deserialize(&data)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        // Deserialize. Regions are upgraded to the current format when opened
+        let persistent = PersistentChunk::decode(&data, FORMAT_VERSION)?;
 
         // Convert to runtime format (persistent is dropped after this - no duplication!)
         let status = entry.status;
@@ -604,6 +669,7 @@ impl RegionManager {
     fn to_persistent(
         sections: &Sections,
         block_entities: &[SharedBlockEntity],
+        entities: Vec<PersistentEntity>,
         chunk_pos: ChunkPos,
     ) -> PersistentChunk {
         let mut builder = ChunkBuilder::new(&REGISTRY);
@@ -645,6 +711,7 @@ impl RegionManager {
             biomes: builder.biomes,
             sections: persistent_sections,
             block_entities: persistent_block_entities,
+            entities,
         }
     }
 
@@ -770,6 +837,11 @@ impl RegionManager {
 
         match status {
             ChunkStatus::Full => {
+                // Entities wait for the chunk to finish loading before they tick
+                if let Some(world) = level.upgrade() {
+                    world.load_entities(pos, &persistent.entities);
+                }
+
                 let chunk = LevelChunk::from_disk(
                     Sections::from_owned(sections.into_boxed_slice()),
                     pos,
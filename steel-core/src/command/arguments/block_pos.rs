@@ -0,0 +1,45 @@
+//! A block position argument.
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+use steel_utils::BlockPos;
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// A block position, like `1 64 -3` or `~ ~-1 ~`.
+///
+/// Relative coordinates are resolved from the source position and rounded
+/// down to the block containing them.
+pub struct BlockPosArgument;
+
+impl CommandArgument for BlockPosArgument {
+    type Output = BlockPos;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let origin = context.position;
+        let x = parse_block_coordinate(arg.first()?, origin.map(|o| o.x))?;
+        let y = parse_block_coordinate(arg.get(1)?, origin.map(|o| o.y))?;
+        let z = parse_block_coordinate(arg.get(2)?, origin.map(|o| o.z))?;
+
+        Some((&arg[3..], BlockPos::new(x, y, z)))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::BlockPos, None)
+    }
+}
+
+/// Parses a single block coordinate. Absolute coordinates must be integers.
+///
+/// Vanilla: `WorldCoordinate.parseInt`
+fn parse_block_coordinate(s: &str, origin: Option<f64>) -> Option<i32> {
+    if let Some(s) = s.strip_prefix('~') {
+        let offset: f64 = if s.is_empty() { 0.0 } else { s.parse().ok()? };
+        Some((origin? + offset).floor() as i32)
+    } else {
+        s.parse().ok()
+    }
+}
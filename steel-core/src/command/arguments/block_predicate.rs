@@ -0,0 +1,93 @@
+//! A block predicate argument.
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+use steel_registry::REGISTRY;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_utils::{BlockStateId, Identifier};
+
+use crate::command::arguments::CommandArgument;
+use crate::command::arguments::block_state::{parse_block_key, split_properties};
+use crate::command::context::CommandContext;
+
+/// Which blocks a [`BlockPredicate`] matches.
+#[derive(Clone)]
+enum BlockSet {
+    Block(BlockRef),
+    Tag(Identifier),
+}
+
+/// Matches block states by block or block tag and a subset of their properties.
+///
+/// Vanilla: `BlockPredicateArgument.BlockPredicate`
+#[derive(Clone)]
+pub struct BlockPredicate {
+    blocks: BlockSet,
+    properties: Vec<(String, String)>,
+}
+
+impl BlockPredicate {
+    /// Returns whether `state` is one of the blocks and has all the properties.
+    #[must_use]
+    pub fn test(&self, state: BlockStateId) -> bool {
+        let block = state.get_block();
+        let block_matches = match &self.blocks {
+            BlockSet::Block(expected) => block.key == expected.key,
+            BlockSet::Tag(tag) => REGISTRY.blocks.is_in_tag(block, tag),
+        };
+        if !block_matches {
+            return false;
+        }
+
+        let values = REGISTRY.blocks.get_properties(state);
+        self.properties
+            .iter()
+            .all(|(name, value)| values.iter().any(|(n, v)| n == name && v == value))
+    }
+}
+
+/// A block predicate, like `stone`, `#minecraft:logs[axis=y]` or
+/// `oak_stairs[half=top]`.
+pub struct BlockPredicateArgument;
+
+impl CommandArgument for BlockPredicateArgument {
+    type Output = BlockPredicate;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let (id, properties) = split_properties(arg.first()?)?;
+        let blocks = match id.strip_prefix('#') {
+            Some(tag) => {
+                let tag = parse_block_key(tag)?;
+                REGISTRY.blocks.get_tag(&tag)?;
+                BlockSet::Tag(tag)
+            }
+            None => {
+                let block = REGISTRY.blocks.by_key(&parse_block_key(id)?)?;
+                // A single block's properties can be checked right away
+                let valid = properties.iter().all(|(name, value)| {
+                    block
+                        .properties
+                        .iter()
+                        .any(|p| p.get_name() == *name && p.get_possible_values().contains(value))
+                });
+                if !valid {
+                    return None;
+                }
+                BlockSet::Block(block)
+            }
+        };
+
+        let properties = properties
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), value.to_owned()))
+            .collect();
+        Some((&arg[1..], BlockPredicate { blocks, properties }))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::BlockPredicate, None)
+    }
+}
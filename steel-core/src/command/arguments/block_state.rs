@@ -0,0 +1,146 @@
+//! A block state argument.
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+use steel_registry::REGISTRY;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+
+use crate::command::arguments::CommandArgument;
+use crate::command::arguments::nbt::parse_compound;
+use crate::command::context::CommandContext;
+use crate::world::World;
+
+/// A block state to place, with the data of its block entity.
+///
+/// Vanilla: `BlockInput`
+#[derive(Clone)]
+pub struct BlockInput {
+    /// The state to place.
+    pub state: BlockStateId,
+    /// The data loaded into the block entity after placing, if any.
+    pub nbt: Option<NbtCompound>,
+}
+
+impl BlockInput {
+    /// Places the block and loads its block entity data, returning `false`
+    /// if the block couldn't be set or was already there.
+    ///
+    /// Vanilla: `BlockInput.place`
+    pub fn place(&self, world: &World, pos: BlockPos, flags: UpdateFlags) -> bool {
+        if !world.set_block(pos, self.state, flags) {
+            return false;
+        }
+        self.load_block_entity(world, pos);
+        true
+    }
+
+    /// Loads the NBT, if any, into the block entity at `pos` and sends the
+    /// new data to clients.
+    pub fn load_block_entity(&self, world: &World, pos: BlockPos) {
+        if let Some(nbt) = &self.nbt
+            && let Some(block_entity) = world.get_block_entity(&pos)
+        {
+            let mut block_entity = block_entity.lock();
+            block_entity.apply_nbt(nbt);
+            if let Some(tag) = block_entity.get_update_tag() {
+                world.broadcast_block_entity_update(pos, block_entity.get_type(), tag);
+            }
+        }
+    }
+}
+
+/// A block state, like `stone`, `minecraft:oak_stairs[facing=north,half=bottom]`
+/// or `chest{Lock:"key"}`.
+///
+/// Properties that aren't given keep their default value.
+pub struct BlockStateArgument;
+
+impl CommandArgument for BlockStateArgument {
+    type Output = BlockInput;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let first = arg.first()?;
+        let (block, nbt_start) = match first.find('{') {
+            Some(index) => (&first[..index], Some(&first[index..])),
+            None => (*first, None),
+        };
+        let state = parse_block_state(block)?;
+
+        let Some(nbt_start) = nbt_start else {
+            return Some((&arg[1..], BlockInput { state, nbt: None }));
+        };
+        // The NBT may contain spaces, so take arguments until it is complete
+        let mut snbt = nbt_start.to_owned();
+        let mut rest = &arg[1..];
+        loop {
+            if let Some(nbt) = parse_compound(&snbt) {
+                return Some((
+                    rest,
+                    BlockInput {
+                        state,
+                        nbt: Some(nbt),
+                    },
+                ));
+            }
+            let (next, remaining) = rest.split_first()?;
+            snbt.push(' ');
+            snbt.push_str(next);
+            rest = remaining;
+        }
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::BlockState, None)
+    }
+}
+
+/// Parses a block id with optional `[key=value,...]` properties.
+///
+/// Vanilla: `BlockStateParser.parseForBlock`
+fn parse_block_state(s: &str) -> Option<BlockStateId> {
+    let (id, properties) = split_properties(s)?;
+    let key = parse_block_key(id)?;
+    let block = REGISTRY.blocks.by_key(&key)?;
+
+    // Start from the default state, since missing properties would
+    // otherwise fall back to their first value
+    let mut values = REGISTRY
+        .blocks
+        .get_properties(REGISTRY.blocks.get_default_state_id(block));
+    for (name, value) in properties {
+        let slot = values.iter_mut().find(|(n, _)| *n == name)?;
+        slot.1 = value;
+    }
+
+    REGISTRY.blocks.state_id_from_properties(&key, &values)
+}
+
+/// Splits `id[key=value,...]` into the id and its properties.
+pub(super) fn split_properties(s: &str) -> Option<(&str, Vec<(&str, &str)>)> {
+    let Some((id, properties)) = s.split_once('[') else {
+        return Some((s, Vec::new()));
+    };
+    let properties = properties
+        .strip_suffix(']')?
+        .split(',')
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| {
+            let (name, value) = p.split_once('=')?;
+            Some((name.trim(), value.trim()))
+        })
+        .collect::<Option<_>>()?;
+    Some((id, properties))
+}
+
+/// Parses a block id, which is in the `minecraft` namespace if it has none.
+pub(super) fn parse_block_key(id: &str) -> Option<Identifier> {
+    if id.contains(':') {
+        id.parse().ok()
+    } else {
+        Some(Identifier::vanilla(id.to_owned()))
+    }
+}
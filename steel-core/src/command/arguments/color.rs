@@ -0,0 +1,26 @@
+//! A color argument.
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+use crate::scoreboard::TeamColor;
+
+/// A named color like `dark_red`, or `reset`.
+pub struct ColorArgument;
+
+impl CommandArgument for ColorArgument {
+    type Output = TeamColor;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let color = TeamColor::from_name(arg.first()?)?;
+        Some((&arg[1..], color))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::Color, None)
+    }
+}
@@ -0,0 +1,43 @@
+//! An entity type argument.
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+use steel_registry::REGISTRY;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_utils::Identifier;
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// An entity type, such as `minecraft:zombie` or `zombie`.
+///
+/// Clients suggest the summonable entity types.
+pub struct EntityTypeArgument;
+
+impl CommandArgument for EntityTypeArgument {
+    type Output = EntityTypeRef;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let s = arg.first()?;
+        let (namespace, path) = s
+            .split_once(':')
+            .unwrap_or((Identifier::VANILLA_NAMESPACE, s));
+        if namespace != Identifier::VANILLA_NAMESPACE {
+            return None;
+        }
+
+        let entity_type = REGISTRY.entity_types.by_key(path)?;
+        Some((&arg[1..], entity_type))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (
+            ArgumentType::Resource {
+                identifier: "minecraft:entity_type",
+            },
+            Some(SuggestionType::SummonableEntities),
+        )
+    }
+}
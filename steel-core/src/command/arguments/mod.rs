@@ -1,12 +1,22 @@
 //! This module contains types and utilities for parsing command arguments.
 pub mod anchor;
+pub mod block_pos;
+pub mod block_predicate;
+pub mod block_state;
 pub mod bool;
+pub mod color;
+pub mod double;
 pub mod entity;
+pub mod entity_type;
 pub mod float;
 pub mod gamemode;
 pub mod integer;
+pub mod nbt;
 pub mod player;
 pub mod rotation;
+pub mod string;
+pub mod structure;
+pub mod team;
 pub mod text_component;
 pub mod time;
 pub mod vector2;
@@ -0,0 +1,295 @@
+//! An NBT compound argument.
+use std::iter::Peekable;
+use std::str::Chars;
+
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// An NBT compound in SNBT form, like `{CustomName:"Bob",NoGravity:1b}`.
+///
+/// Consumes all remaining arguments.
+pub struct NbtCompoundArgument;
+
+impl CommandArgument for NbtCompoundArgument {
+    type Output = NbtCompound;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        if arg.is_empty() {
+            return None;
+        }
+        let compound = parse_compound(&arg.join(" "))?;
+        Some((&[], compound))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::Nbt, None)
+    }
+}
+
+/// Parses an SNBT compound, returning `None` if it is invalid or followed by
+/// anything but whitespace.
+///
+/// Lists must hold a single tag type.
+///
+/// Vanilla: `TagParser.parseCompoundFully`
+#[must_use]
+pub fn parse_compound(input: &str) -> Option<NbtCompound> {
+    let mut reader = SnbtReader {
+        chars: input.chars().peekable(),
+    };
+    let NbtTag::Compound(compound) = reader.read_value()? else {
+        return None;
+    };
+    reader.skip_whitespace();
+    reader.chars.peek().is_none().then_some(compound)
+}
+
+struct SnbtReader<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl SnbtReader<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    /// Skips whitespace, then consumes `expected` if it comes next.
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&expected).is_some()
+    }
+
+    fn read_value(&mut self) -> Option<NbtTag> {
+        self.skip_whitespace();
+        match *self.chars.peek()? {
+            '{' => self.read_compound().map(NbtTag::Compound),
+            '[' => self.read_list_or_array(),
+            '"' | '\'' => self.read_quoted().map(|s| NbtTag::String(s.into())),
+            _ => parse_unquoted(&self.read_unquoted()?),
+        }
+    }
+
+    fn read_compound(&mut self) -> Option<NbtCompound> {
+        self.chars.next();
+        let mut compound = NbtCompound::new();
+        if self.eat('}') {
+            return Some(compound);
+        }
+        loop {
+            self.skip_whitespace();
+            let key = match *self.chars.peek()? {
+                '"' | '\'' => self.read_quoted()?,
+                _ => self.read_unquoted()?,
+            };
+            if !self.eat(':') {
+                return None;
+            }
+            let value = self.read_value()?;
+            compound.insert(key, value);
+
+            if self.eat('}') {
+                return Some(compound);
+            }
+            if !self.eat(',') {
+                return None;
+            }
+        }
+    }
+
+    fn read_list_or_array(&mut self) -> Option<NbtTag> {
+        self.chars.next();
+        // Typed arrays start with `B;`, `I;` or `L;`
+        let mut lookahead = self.chars.clone();
+        let array_type = lookahead.next().filter(|c| matches!(c, 'B' | 'I' | 'L'));
+        if array_type.is_some() && lookahead.next() == Some(';') {
+            self.chars = lookahead;
+            return self.read_array(array_type?);
+        }
+
+        let mut tags = Vec::new();
+        if !self.eat(']') {
+            loop {
+                tags.push(self.read_value()?);
+                if self.eat(']') {
+                    break;
+                }
+                if !self.eat(',') {
+                    return None;
+                }
+            }
+        }
+        list_from_tags(tags).map(NbtTag::List)
+    }
+
+    fn read_array(&mut self, array_type: char) -> Option<NbtTag> {
+        let mut values = Vec::new();
+        if !self.eat(']') {
+            loop {
+                values.push(self.read_value()?);
+                if self.eat(']') {
+                    break;
+                }
+                if !self.eat(',') {
+                    return None;
+                }
+            }
+        }
+
+        let values = values.into_iter();
+        Some(match array_type {
+            'B' => NbtTag::ByteArray(
+                values
+                    .map(|tag| match tag {
+                        NbtTag::Byte(value) => Some(value as u8),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?,
+            ),
+            'I' => NbtTag::IntArray(
+                values
+                    .map(|tag| match tag {
+                        NbtTag::Byte(value) => Some(i32::from(value)),
+                        NbtTag::Short(value) => Some(i32::from(value)),
+                        NbtTag::Int(value) => Some(value),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?,
+            ),
+            _ => NbtTag::LongArray(
+                values
+                    .map(|tag| match tag {
+                        NbtTag::Byte(value) => Some(i64::from(value)),
+                        NbtTag::Short(value) => Some(i64::from(value)),
+                        NbtTag::Int(value) => Some(i64::from(value)),
+                        NbtTag::Long(value) => Some(value),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?,
+            ),
+        })
+    }
+
+    fn read_quoted(&mut self) -> Option<String> {
+        let quote = self.chars.next()?;
+        let mut value = String::new();
+        loop {
+            match self.chars.next()? {
+                '\\' => value.push(self.chars.next()?),
+                c if c == quote => return Some(value),
+                c => value.push(c),
+            }
+        }
+    }
+
+    fn read_unquoted(&mut self) -> Option<String> {
+        let mut value = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+'))
+        {
+            value.push(c);
+        }
+        (!value.is_empty()).then_some(value)
+    }
+}
+
+/// Parses an unquoted value as a number or boolean, falling back to a string.
+fn parse_unquoted(value: &str) -> Option<NbtTag> {
+    match value {
+        "true" => return Some(NbtTag::Byte(1)),
+        "false" => return Some(NbtTag::Byte(0)),
+        _ => {}
+    }
+
+    let (number, suffix) = value.split_at(value.len() - 1);
+    let number_tag = match suffix {
+        "b" | "B" => number.parse().ok().map(NbtTag::Byte),
+        "s" | "S" => number.parse().ok().map(NbtTag::Short),
+        "l" | "L" => number.parse().ok().map(NbtTag::Long),
+        "f" | "F" => number.parse().ok().map(NbtTag::Float),
+        "d" | "D" => number.parse().ok().map(NbtTag::Double),
+        _ => value
+            .parse()
+            .ok()
+            .map(NbtTag::Int)
+            .or_else(|| value.parse().ok().map(NbtTag::Double)),
+    };
+    Some(number_tag.unwrap_or_else(|| NbtTag::String(value.to_owned().into())))
+}
+
+/// Builds a list from tags that must all have the same type.
+fn list_from_tags(tags: Vec<NbtTag>) -> Option<NbtList> {
+    macro_rules! collect {
+        ($variant:ident) => {
+            NbtList::$variant(
+                tags.into_iter()
+                    .map(|tag| match tag {
+                        NbtTag::$variant(value) => Some(value),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?,
+            )
+        };
+    }
+
+    Some(match tags.first() {
+        None => NbtList::Empty,
+        Some(NbtTag::Byte(_)) => collect!(Byte),
+        Some(NbtTag::Short(_)) => collect!(Short),
+        Some(NbtTag::Int(_)) => collect!(Int),
+        Some(NbtTag::Long(_)) => collect!(Long),
+        Some(NbtTag::Float(_)) => collect!(Float),
+        Some(NbtTag::Double(_)) => collect!(Double),
+        Some(NbtTag::ByteArray(_)) => collect!(ByteArray),
+        Some(NbtTag::String(_)) => collect!(String),
+        Some(NbtTag::List(_)) => collect!(List),
+        Some(NbtTag::Compound(_)) => collect!(Compound),
+        Some(NbtTag::IntArray(_)) => collect!(IntArray),
+        Some(NbtTag::LongArray(_)) => collect!(LongArray),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_compounds() {
+        let nbt = parse_compound(
+            r#"{CustomName:"Bob \"the\" zombie", NoGravity:1b, Rotation:[90f,-10.5f], UUID:[I;1,2,3,4], Tags:[], Data:{'nested key':true}}"#,
+        )
+        .expect("valid snbt");
+
+        assert_eq!(
+            nbt.string("CustomName").map(|s| s.to_str().into_owned()),
+            Some("Bob \"the\" zombie".to_owned())
+        );
+        assert_eq!(nbt.byte("NoGravity"), Some(1));
+        assert_eq!(
+            nbt.list("Rotation").and_then(|list| list.floats()),
+            Some(&[90.0, -10.5][..])
+        );
+        assert_eq!(nbt.int_array("UUID"), Some(&[1, 2, 3, 4][..]));
+        assert_eq!(
+            nbt.compound("Data")
+                .and_then(|data| data.byte("nested key")),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_snbt() {
+        assert!(parse_compound("{a:1,}").is_none());
+        assert!(parse_compound("{a:[1,2b]}").is_none());
+        assert!(parse_compound("{a:1} trailing").is_none());
+        assert!(parse_compound("[1,2]").is_none());
+        assert!(parse_compound("{a:\"unterminated}").is_none());
+    }
+}
@@ -0,0 +1,39 @@
+//! A string argument.
+use steel_protocol::packets::game::{ArgumentStringTypeBehavior, ArgumentType, SuggestionType};
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// A single word argument, like brigadier's `StringArgumentType.word()`.
+pub struct WordArgument;
+
+impl CommandArgument for WordArgument {
+    type Output = String;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let s = arg.first()?;
+
+        // Brigadier's unquoted strings only allow these characters
+        if !s
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+'))
+        {
+            return None;
+        }
+
+        Some((&arg[1..], (*s).to_owned()))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (
+            ArgumentType::String {
+                behavior: ArgumentStringTypeBehavior::SingleWord,
+            },
+            None,
+        )
+    }
+}
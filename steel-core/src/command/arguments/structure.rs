@@ -0,0 +1,57 @@
+//! A structure argument.
+use steel_protocol::packets::game::{ArgumentType, SuggestionEntry, SuggestionType};
+use steel_registry::REGISTRY;
+use steel_utils::Identifier;
+
+use crate::command::arguments::{CommandArgument, SuggestionContext};
+use crate::command::context::CommandContext;
+
+/// A structure argument, such as `minecraft:village_plains`.
+///
+/// Any identifier is accepted so commands can report unknown structures themselves.
+pub struct StructureArgument;
+
+impl CommandArgument for StructureArgument {
+    type Output = Identifier;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let s = arg.first()?;
+
+        let structure = if s.contains(':') {
+            s.parse().ok()?
+        } else {
+            format!("{}:{s}", Identifier::VANILLA_NAMESPACE)
+                .parse()
+                .ok()?
+        };
+
+        Some((&arg[1..], structure))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (
+            ArgumentType::ResourceLocation,
+            Some(SuggestionType::AskServer),
+        )
+    }
+
+    fn suggest(&self, prefix: &str, _suggestion_ctx: &SuggestionContext) -> Vec<SuggestionEntry> {
+        REGISTRY
+            .structure_sets
+            .iter()
+            .flat_map(|(_, set)| set.structures)
+            .map(|entry| entry.structure.to_string())
+            .filter(|structure| {
+                structure.starts_with(prefix)
+                    || structure
+                        .strip_prefix("minecraft:")
+                        .is_some_and(|path| path.starts_with(prefix))
+            })
+            .map(SuggestionEntry::new)
+            .collect()
+    }
+}
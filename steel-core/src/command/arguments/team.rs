@@ -0,0 +1,27 @@
+//! A team argument.
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// The name of an existing team. Clients suggest the teams they know of.
+///
+/// Only the name is parsed, so executors report unknown teams themselves.
+pub struct TeamArgument;
+
+impl CommandArgument for TeamArgument {
+    type Output = String;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let s = arg.first()?;
+        Some((&arg[1..], (*s).to_owned()))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::Team, None)
+    }
+}
@@ -0,0 +1,587 @@
+//! Handler for the "clone" command.
+use simdnbt::owned::NbtCompound;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::is_shape_full_block;
+use steel_registry::vanilla_game_rules::MAX_BLOCK_MODIFICATIONS;
+use steel_registry::{REGISTRY, vanilla_blocks};
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use crate::chunk::structures::BoundingBox;
+use crate::command::arguments::block_pos::BlockPosArgument;
+use crate::command::arguments::block_predicate::{BlockPredicate, BlockPredicateArgument};
+use crate::command::arguments::block_state::BlockInput;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::world::World;
+
+/// The flags blocks are placed with while cloning, so nothing reacts to the
+/// half finished copy. Neighbors are updated once everything is placed.
+///
+/// Vanilla: `Block.UPDATE_SKIP_ALL_SIDEEFFECTS | Block.UPDATE_CLIENTS`
+const CLONE_FLAGS: UpdateFlags = UpdateFlags::UPDATE_CLIENTS
+    .union(UpdateFlags::UPDATE_KNOWN_SHAPE)
+    .union(UpdateFlags::UPDATE_SUPPRESS_DROPS)
+    .union(UpdateFlags::UPDATE_SKIP_BLOCK_ENTITY_SIDEEFFECTS)
+    .union(UpdateFlags::UPDATE_SKIP_ON_PLACE);
+
+/// Handler for the "clone" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["clone"],
+        "Copies blocks from one place to another.",
+        "minecraft:command.clone",
+    )
+    .then(
+        argument("begin", BlockPosArgument).then(
+            argument("end", BlockPosArgument).then(
+                argument("destination", BlockPosArgument)
+                    .executes(CloneExecutor(Mask::Replace, Mode::Normal))
+                    .then(
+                        literal("replace")
+                            .executes(CloneExecutor(Mask::Replace, Mode::Normal))
+                            .then(
+                                literal("force")
+                                    .executes(CloneExecutor(Mask::Replace, Mode::Force)),
+                            )
+                            .then(
+                                literal("move").executes(CloneExecutor(Mask::Replace, Mode::Move)),
+                            )
+                            .then(
+                                literal("normal")
+                                    .executes(CloneExecutor(Mask::Replace, Mode::Normal)),
+                            ),
+                    )
+                    .then(
+                        literal("masked")
+                            .executes(CloneExecutor(Mask::Masked, Mode::Normal))
+                            .then(
+                                literal("force").executes(CloneExecutor(Mask::Masked, Mode::Force)),
+                            )
+                            .then(literal("move").executes(CloneExecutor(Mask::Masked, Mode::Move)))
+                            .then(
+                                literal("normal")
+                                    .executes(CloneExecutor(Mask::Masked, Mode::Normal)),
+                            ),
+                    )
+                    .then(
+                        literal("filtered").then(
+                            argument("filter", BlockPredicateArgument)
+                                .executes(CloneExecutor(Mask::Filtered, Mode::Normal))
+                                .then(
+                                    literal("force")
+                                        .executes(CloneExecutor(Mask::Filtered, Mode::Force)),
+                                )
+                                .then(
+                                    literal("move")
+                                        .executes(CloneExecutor(Mask::Filtered, Mode::Move)),
+                                )
+                                .then(
+                                    literal("normal")
+                                        .executes(CloneExecutor(Mask::Filtered, Mode::Normal)),
+                                ),
+                        ),
+                    ),
+            ),
+        ),
+    )
+}
+
+/// Which source blocks are copied.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mask {
+    /// Copy every block.
+    Replace,
+    /// Copy every block but air.
+    Masked,
+    /// Copy the blocks matching the filter.
+    Filtered,
+}
+
+/// How the source and destination relate.
+///
+/// Vanilla: `CloneCommands.Mode`
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Copy the blocks, refusing overlapping regions.
+    Normal,
+    /// Copy the blocks even if the regions overlap.
+    Force,
+    /// Copy the blocks and replace the source with air.
+    Move,
+}
+
+/// When a cloned block is placed relative to the others. Blocks that can
+/// support others go first, so attached blocks like torches survive.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Layer {
+    /// Blocks with a full collision shape.
+    Full,
+    /// Blocks with a block entity.
+    BlockEntity,
+    /// Everything else.
+    Other,
+}
+
+struct CloneExecutor(Mask, Mode);
+
+type CloneArgs = ((((), BlockPos), BlockPos), BlockPos);
+
+impl CommandExecutor<CloneArgs> for CloneExecutor {
+    fn execute(&self, args: CloneArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        let ((((), begin), end), destination) = args;
+        let masked = self.0 == Mask::Masked;
+        clone_blocks(begin, end, destination, self.1, context, |state| {
+            !masked || !state.is_air()
+        })
+    }
+}
+
+impl CommandExecutor<(CloneArgs, BlockPredicate)> for CloneExecutor {
+    fn execute(
+        &self,
+        args: (CloneArgs, BlockPredicate),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((((), begin), end), destination), filter) = args;
+        clone_blocks(begin, end, destination, self.1, context, |state| {
+            filter.test(state)
+        })
+    }
+}
+
+/// Vanilla: `CloneCommands.clone`
+fn clone_blocks(
+    begin: BlockPos,
+    end: BlockPos,
+    destination: BlockPos,
+    mode: Mode,
+    context: &CommandContext,
+    filter: impl Fn(BlockStateId) -> bool,
+) -> Result<(), CommandError> {
+    let source = BoundingBox::from_corners(begin, end);
+    let offset = (
+        destination.x() - source.min.x(),
+        destination.y() - source.min.y(),
+        destination.z() - source.min.z(),
+    );
+    let target = source.moved(offset.0, offset.1, offset.2);
+
+    let world = context.get_world()?;
+    let volume = volume(&source);
+    let limit = world
+        .get_game_rule(MAX_BLOCK_MODIFICATIONS)
+        .as_int()
+        .unwrap_or(32768);
+    if volume > i64::from(limit) {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::COMMANDS_CLONE_TOOBIG
+                .message([
+                    TextComponent::plain(limit.to_string()),
+                    TextComponent::plain(volume.to_string()),
+                ])
+                .into(),
+        )));
+    }
+    if mode != Mode::Force && source.intersects(&target) {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::COMMANDS_CLONE_OVERLAP.msg().into(),
+        )));
+    }
+    if !world.has_chunks_at(source.min, source.max) || !world.has_chunks_at(target.min, target.max)
+    {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::ARGUMENT_POS_UNLOADED.msg().into(),
+        )));
+    }
+
+    let cloned = copy_blocks(world.as_ref(), source, offset, mode, filter);
+    if cloned == 0 {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::COMMANDS_CLONE_FAILED.msg().into(),
+        )));
+    }
+    context.sender.send_message(
+        &translations::COMMANDS_CLONE_SUCCESS
+            .message([TextComponent::plain(cloned.to_string())])
+            .into(),
+    );
+    Ok(())
+}
+
+/// The blocks `/clone` reads and writes.
+///
+/// This abstraction allows testing the copy without a full world instance.
+trait CloneWorld {
+    /// Gets the block state at `pos`.
+    fn get_block_state(&self, pos: &BlockPos) -> BlockStateId;
+
+    /// Saves the data of the block entity at `pos`, if there is one.
+    fn save_block_entity(&self, pos: &BlockPos) -> Option<NbtCompound>;
+
+    /// Sets the block at `pos`, returning `false` if it was already there.
+    fn set_block(&self, pos: BlockPos, state: BlockStateId, flags: UpdateFlags) -> bool;
+
+    /// Loads the block entity data of `block` into the block placed at `pos`.
+    fn load_block_entity(&self, pos: BlockPos, block: &BlockInput);
+
+    /// Tells the neighbors of `pos` that `state` was placed there.
+    fn update_neighbors_at(&self, pos: &BlockPos, state: BlockStateId);
+}
+
+impl CloneWorld for World {
+    fn get_block_state(&self, pos: &BlockPos) -> BlockStateId {
+        self.get_block_state(pos)
+    }
+
+    fn save_block_entity(&self, pos: &BlockPos) -> Option<NbtCompound> {
+        self.get_block_entity(pos).map(|block_entity| {
+            let mut nbt = NbtCompound::new();
+            block_entity.lock().save_additional(&mut nbt);
+            nbt
+        })
+    }
+
+    fn set_block(&self, pos: BlockPos, state: BlockStateId, flags: UpdateFlags) -> bool {
+        self.set_block(pos, state, flags)
+    }
+
+    fn load_block_entity(&self, pos: BlockPos, block: &BlockInput) {
+        block.load_block_entity(self, pos);
+    }
+
+    fn update_neighbors_at(&self, pos: &BlockPos, state: BlockStateId) {
+        self.update_neighbors_at(pos, state.get_block());
+    }
+}
+
+/// Copies the blocks in `source` that pass `filter` over by `offset`, and
+/// returns how many were placed.
+///
+/// Vanilla: `CloneCommands.clone`, once the regions were checked
+fn copy_blocks(
+    world: &impl CloneWorld,
+    source: BoundingBox,
+    offset: (i32, i32, i32),
+    mode: Mode,
+    filter: impl Fn(BlockStateId) -> bool,
+) -> i32 {
+    // Read everything before writing, so overlapping regions copy correctly
+    let mut blocks = Vec::new();
+    for pos in positions(source) {
+        let state = world.get_block_state(&pos);
+        if !filter(state) {
+            continue;
+        }
+
+        let nbt = world.save_block_entity(&pos);
+        let layer = if nbt.is_some() {
+            Layer::BlockEntity
+        } else if is_shape_full_block(state.get_collision_shape()) {
+            Layer::Full
+        } else {
+            Layer::Other
+        };
+        let dest = pos.offset(offset.0, offset.1, offset.2);
+        blocks.push((layer, pos, dest, BlockInput { state, nbt }));
+    }
+    blocks.sort_by_key(|(layer, ..)| *layer);
+
+    let barrier = REGISTRY.blocks.get_base_state_id(vanilla_blocks::BARRIER);
+    let air = REGISTRY.blocks.get_base_state_id(vanilla_blocks::AIR);
+
+    // Barriers keep fluids and falling blocks still while the copy is incomplete
+    if mode == Mode::Move {
+        for (_, pos, ..) in blocks.iter().rev() {
+            world.set_block(*pos, barrier, CLONE_FLAGS);
+        }
+        for (_, pos, ..) in blocks.iter().rev() {
+            world.set_block(*pos, air, UpdateFlags::UPDATE_ALL);
+        }
+    }
+    for (_, _, dest, _) in blocks.iter().rev() {
+        world.set_block(*dest, barrier, CLONE_FLAGS);
+    }
+
+    let mut cloned = 0;
+    for (_, _, dest, block) in &blocks {
+        if world.set_block(*dest, block.state, CLONE_FLAGS) {
+            cloned += 1;
+        }
+    }
+    for (_, _, dest, block) in &blocks {
+        world.load_block_entity(*dest, block);
+    }
+    for (_, _, dest, block) in blocks.iter().rev() {
+        world.update_neighbors_at(dest, block.state);
+    }
+    cloned
+}
+
+/// The number of blocks in `area`.
+fn volume(area: &BoundingBox) -> i64 {
+    (i64::from(area.max.x() - area.min.x()) + 1)
+        * i64::from(area.y_span())
+        * (i64::from(area.max.z() - area.min.z()) + 1)
+}
+
+/// Every position in `area`, in the order vanilla reads them.
+fn positions(area: BoundingBox) -> impl Iterator<Item = BlockPos> {
+    let (min, max) = (area.min, area.max);
+    (min.z()..=max.z()).flat_map(move |z| {
+        (min.y()..=max.y())
+            .flat_map(move |y| (min.x()..=max.x()).map(move |x| BlockPos::new(x, y, z)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::sync::Weak;
+
+    use rustc_hash::FxHashMap;
+    use steel_registry::blocks::BlockRef;
+    use steel_registry::item_stack::ItemStack;
+    use steel_registry::vanilla_items::ITEMS;
+
+    use super::*;
+    use crate::block_entity::BlockEntity;
+    use crate::block_entity::entities::{ChestBlockEntity, SignBlockEntity};
+    use crate::inventory::container::Container;
+
+    /// An in-memory world of blocks and block entity data.
+    #[derive(Default)]
+    struct MockWorld {
+        blocks: RefCell<FxHashMap<BlockPos, BlockStateId>>,
+        block_entities: RefCell<FxHashMap<BlockPos, NbtCompound>>,
+    }
+
+    impl CloneWorld for MockWorld {
+        fn get_block_state(&self, pos: &BlockPos) -> BlockStateId {
+            self.blocks
+                .borrow()
+                .get(pos)
+                .copied()
+                .unwrap_or_else(|| state(vanilla_blocks::AIR))
+        }
+
+        fn save_block_entity(&self, pos: &BlockPos) -> Option<NbtCompound> {
+            self.block_entities.borrow().get(pos).cloned()
+        }
+
+        fn set_block(&self, pos: BlockPos, state: BlockStateId, _flags: UpdateFlags) -> bool {
+            if self.get_block_state(&pos) == state {
+                return false;
+            }
+            self.blocks.borrow_mut().insert(pos, state);
+            // Replacing a block takes its block entity with it
+            self.block_entities.borrow_mut().remove(&pos);
+            true
+        }
+
+        fn load_block_entity(&self, pos: BlockPos, block: &BlockInput) {
+            if let Some(nbt) = &block.nbt {
+                self.block_entities.borrow_mut().insert(pos, nbt.clone());
+            }
+        }
+
+        fn update_neighbors_at(&self, _pos: &BlockPos, _state: BlockStateId) {}
+    }
+
+    fn state(block: BlockRef) -> BlockStateId {
+        REGISTRY.blocks.get_base_state_id(block)
+    }
+
+    /// The 3x3x3 region the tests copy.
+    fn room() -> BoundingBox {
+        BoundingBox::from_corners(BlockPos::new(0, 0, 0), BlockPos::new(2, 2, 2))
+    }
+
+    /// Furnishes the room: a stone floor with a glass pane in the corner, a
+    /// chest with diamonds in the middle, a sign and a torch beside it and
+    /// a plank ceiling block. Returns the chest's and the sign's data.
+    fn furnished_world() -> (MockWorld, NbtCompound, NbtCompound) {
+        let world = MockWorld::default();
+        let place = |x, y, z, block| {
+            world.set_block(BlockPos::new(x, y, z), state(block), CLONE_FLAGS);
+        };
+        for x in 0..3 {
+            for z in 0..3 {
+                place(x, 0, z, vanilla_blocks::STONE);
+            }
+        }
+        place(2, 0, 2, vanilla_blocks::GLASS);
+        place(1, 1, 1, vanilla_blocks::CHEST);
+        place(0, 1, 0, vanilla_blocks::OAK_SIGN);
+        place(2, 1, 2, vanilla_blocks::TORCH);
+        place(1, 2, 1, vanilla_blocks::OAK_PLANKS);
+
+        let chest_pos = BlockPos::new(1, 1, 1);
+        let mut chest = ChestBlockEntity::new(Weak::new(), chest_pos, state(vanilla_blocks::CHEST));
+        chest.set_item(0, ItemStack::with_count(&ITEMS.diamond, 5));
+        chest.set_item(13, ItemStack::new(&ITEMS.stick));
+        let mut chest_nbt = NbtCompound::new();
+        chest.save_additional(&mut chest_nbt);
+
+        let sign_pos = BlockPos::new(0, 1, 0);
+        let mut sign = SignBlockEntity::new(Weak::new(), sign_pos, state(vanilla_blocks::OAK_SIGN));
+        sign.get_text_mut(true)
+            .set_message(0, TextComponent::plain("Cloned"));
+        let mut sign_nbt = NbtCompound::new();
+        sign.save_additional(&mut sign_nbt);
+
+        let mut block_entities = world.block_entities.borrow_mut();
+        block_entities.insert(chest_pos, chest_nbt.clone());
+        block_entities.insert(sign_pos, sign_nbt.clone());
+        drop(block_entities);
+        (world, chest_nbt, sign_nbt)
+    }
+
+    #[test]
+    fn overlapping_regions_are_detected() {
+        let source = BoundingBox::from_corners(BlockPos::new(0, 64, 0), BlockPos::new(2, 66, 2));
+        assert_eq!(volume(&source), 27);
+
+        assert!(source.intersects(&source.moved(2, 0, 0)));
+        assert!(source.intersects(&source.moved(-1, 2, 1)));
+        assert!(!source.intersects(&source.moved(3, 0, 0)));
+        assert!(!source.intersects(&source.moved(0, -3, 0)));
+    }
+
+    #[test]
+    fn visits_every_position_once() {
+        let source = BoundingBox::from_corners(BlockPos::new(5, 10, -3), BlockPos::new(3, 12, -1));
+        let visited: Vec<_> = positions(source).collect();
+
+        assert_eq!(visited.len(), 27);
+        assert_eq!(visited.first(), Some(&BlockPos::new(3, 10, -3)));
+        assert_eq!(visited.last(), Some(&BlockPos::new(5, 12, -1)));
+        assert!(visited.iter().all(|pos| source.is_inside(*pos)));
+        for (i, pos) in visited.iter().enumerate() {
+            assert!(!visited[i + 1..].contains(pos));
+        }
+    }
+
+    #[test]
+    fn supports_are_placed_before_attached_blocks() {
+        // A 3x3x3 cube: stone floor, a chest in the middle, torches on top
+        let source = BoundingBox::from_corners(BlockPos::new(0, 0, 0), BlockPos::new(2, 2, 2));
+        let mut blocks: Vec<_> = positions(source)
+            .map(|pos| {
+                let layer = match pos.y() {
+                    0 => Layer::Full,
+                    1 if pos.x() == 1 && pos.z() == 1 => Layer::BlockEntity,
+                    _ => Layer::Other,
+                };
+                (layer, pos)
+            })
+            .collect();
+        blocks.sort_by_key(|(layer, _)| *layer);
+
+        assert!(blocks[..9].iter().all(|(layer, _)| *layer == Layer::Full));
+        assert_eq!(blocks[9], (Layer::BlockEntity, BlockPos::new(1, 1, 1)));
+        assert!(blocks[10..].iter().all(|(layer, _)| *layer == Layer::Other));
+        // The sort is stable, so each layer keeps the read order
+        assert_eq!(blocks[10].1, BlockPos::new(0, 1, 0));
+    }
+
+    #[test]
+    fn copies_blocks_and_block_entities() {
+        let (world, chest, sign) = furnished_world();
+
+        let cloned = copy_blocks(&world, room(), (10, 0, 0), Mode::Normal, |_| true);
+
+        assert_eq!(cloned, 27);
+        for pos in positions(room()) {
+            let dest = pos.offset(10, 0, 0);
+            assert_eq!(
+                world.get_block_state(&dest),
+                world.get_block_state(&pos),
+                "{dest:?}"
+            );
+        }
+        assert_eq!(
+            world.save_block_entity(&BlockPos::new(11, 1, 1)),
+            Some(chest.clone())
+        );
+        assert_eq!(
+            world.save_block_entity(&BlockPos::new(10, 1, 0)),
+            Some(sign.clone())
+        );
+        // The source is left as it was
+        assert_eq!(
+            world.get_block_state(&BlockPos::new(1, 1, 1)),
+            state(vanilla_blocks::CHEST)
+        );
+        assert_eq!(
+            world.save_block_entity(&BlockPos::new(1, 1, 1)),
+            Some(chest)
+        );
+        assert_eq!(world.save_block_entity(&BlockPos::new(0, 1, 0)), Some(sign));
+    }
+
+    #[test]
+    fn move_leaves_air_behind() {
+        let (world, chest, sign) = furnished_world();
+        let before: Vec<_> = positions(room())
+            .map(|pos| world.get_block_state(&pos))
+            .collect();
+
+        copy_blocks(&world, room(), (0, 0, 10), Mode::Move, |_| true);
+
+        for (pos, moved) in positions(room()).zip(before) {
+            assert!(world.get_block_state(&pos).is_air(), "{pos:?}");
+            assert_eq!(
+                world.get_block_state(&pos.offset(0, 0, 10)),
+                moved,
+                "{pos:?}"
+            );
+        }
+        assert_eq!(world.save_block_entity(&BlockPos::new(1, 1, 1)), None);
+        assert_eq!(world.save_block_entity(&BlockPos::new(0, 1, 0)), None);
+        assert_eq!(
+            world.save_block_entity(&BlockPos::new(1, 1, 11)),
+            Some(chest)
+        );
+        assert_eq!(
+            world.save_block_entity(&BlockPos::new(0, 1, 10)),
+            Some(sign)
+        );
+    }
+
+    #[test]
+    fn masked_clones_keep_what_air_would_replace() {
+        let (world, chest, _) = furnished_world();
+        let dirt = state(vanilla_blocks::DIRT);
+        for pos in positions(room()) {
+            world.set_block(pos.offset(10, 0, 0), dirt, CLONE_FLAGS);
+        }
+
+        let cloned = copy_blocks(&world, room(), (10, 0, 0), Mode::Normal, |state| {
+            !state.is_air()
+        });
+
+        let solid = positions(room())
+            .filter(|pos| !world.get_block_state(pos).is_air())
+            .count();
+        assert_eq!(usize::try_from(cloned), Ok(solid));
+        for pos in positions(room()) {
+            let source = world.get_block_state(&pos);
+            let expected = if source.is_air() { dirt } else { source };
+            assert_eq!(
+                world.get_block_state(&pos.offset(10, 0, 0)),
+                expected,
+                "{pos:?}"
+            );
+        }
+        assert_eq!(
+            world.save_block_entity(&BlockPos::new(11, 1, 1)),
+            Some(chest)
+        );
+    }
+}
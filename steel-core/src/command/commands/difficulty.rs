@@ -0,0 +1,80 @@
+//! Handler for the "difficulty" command.
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use steel_utils::translations;
+use steel_utils::types::Difficulty;
+use text_components::TextComponent;
+use text_components::translation::Translation;
+
+/// Handler for the "difficulty" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["difficulty"],
+        "Sets the difficulty level.",
+        "minecraft:command.difficulty",
+    )
+    .executes(DifficultyQueryExecutor)
+    .then(literal("peaceful").executes(DifficultySetExecutor(Difficulty::Peaceful)))
+    .then(literal("easy").executes(DifficultySetExecutor(Difficulty::Easy)))
+    .then(literal("normal").executes(DifficultySetExecutor(Difficulty::Normal)))
+    .then(literal("hard").executes(DifficultySetExecutor(Difficulty::Hard)))
+}
+
+struct DifficultyQueryExecutor;
+
+impl CommandExecutor<()> for DifficultyQueryExecutor {
+    fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
+        let difficulty = context.get_world()?.difficulty();
+
+        context.sender.send_message(
+            &translations::COMMANDS_DIFFICULTY_QUERY
+                .message([TextComponent::from(get_difficulty_translation(difficulty))])
+                .into(),
+        );
+
+        Ok(())
+    }
+}
+
+struct DifficultySetExecutor(Difficulty);
+
+impl CommandExecutor<()> for DifficultySetExecutor {
+    fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
+        let difficulty = self.0;
+        let translation = get_difficulty_translation(difficulty);
+
+        if context.get_world()?.difficulty() == difficulty {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_DIFFICULTY_FAILURE
+                    .message([TextComponent::from(translation)])
+                    .into(),
+            )));
+        }
+
+        // The difficulty is server-wide, like in vanilla
+        for world in &context.server.worlds {
+            world.set_difficulty(difficulty);
+        }
+
+        context.sender.send_message(
+            &translations::COMMANDS_DIFFICULTY_SUCCESS
+                .message([TextComponent::from(translation)])
+                .into(),
+        );
+
+        Ok(())
+    }
+}
+
+fn get_difficulty_translation(difficulty: Difficulty) -> &'static Translation<0> {
+    match difficulty {
+        Difficulty::Peaceful => &translations::OPTIONS_DIFFICULTY_PEACEFUL,
+        Difficulty::Easy => &translations::OPTIONS_DIFFICULTY_EASY,
+        Difficulty::Normal => &translations::OPTIONS_DIFFICULTY_NORMAL,
+        Difficulty::Hard => &translations::OPTIONS_DIFFICULTY_HARD,
+    }
+}
@@ -0,0 +1,296 @@
+//! Handler for the "fill" command.
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::vanilla_game_rules::MAX_BLOCK_MODIFICATIONS;
+use steel_registry::{REGISTRY, vanilla_blocks};
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, SectionPos, translations};
+use text_components::TextComponent;
+
+use crate::command::arguments::block_pos::BlockPosArgument;
+use crate::command::arguments::block_predicate::{BlockPredicate, BlockPredicateArgument};
+use crate::command::arguments::block_state::{BlockInput, BlockStateArgument};
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::command::sender::CommandSender;
+use crate::world::{BlockTask, World};
+
+/// Fills of up to this many blocks are done right away. Larger ones are
+/// spread over several ticks, one chunk section per tick.
+const MAX_INSTANT_VOLUME: i64 = 16 * 16 * 16;
+
+/// How many sections a spread out fill processes between progress reports.
+const PROGRESS_INTERVAL: usize = 20;
+
+/// Handler for the "fill" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["fill"],
+        "Fills a region with a specific block.",
+        "minecraft:command.fill",
+    )
+    .then(
+        argument("from", BlockPosArgument).then(
+            argument("to", BlockPosArgument).then(
+                argument("block", BlockStateArgument)
+                    .executes(FillExecutor(Mode::Replace))
+                    .then(literal("destroy").executes(FillExecutor(Mode::Destroy)))
+                    .then(literal("hollow").executes(FillExecutor(Mode::Hollow)))
+                    .then(literal("keep").executes(FillExecutor(Mode::Keep)))
+                    .then(literal("outline").executes(FillExecutor(Mode::Outline)))
+                    .then(
+                        literal("replace")
+                            .executes(FillExecutor(Mode::Replace))
+                            .then(
+                                argument("filter", BlockPredicateArgument)
+                                    .executes(FillExecutor(Mode::Replace)),
+                            ),
+                    ),
+            ),
+        ),
+    )
+}
+
+/// How the region is filled.
+///
+/// Vanilla: `FillCommand.Mode`
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Replace every block, or only the ones matching the filter.
+    Replace,
+    /// Only fill blocks that are air.
+    Keep,
+    /// Fill the outer layer and replace the inside with air.
+    Hollow,
+    /// Fill the outer layer and leave the inside untouched.
+    Outline,
+    /// Break the old blocks as if a player mined them.
+    Destroy,
+}
+
+struct FillExecutor(Mode);
+
+type FillArgs = ((((), BlockPos), BlockPos), BlockInput);
+
+impl CommandExecutor<FillArgs> for FillExecutor {
+    fn execute(&self, args: FillArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        let ((((), from), to), block) = args;
+        fill(from, to, block, self.0, None, context)
+    }
+}
+
+impl CommandExecutor<(FillArgs, BlockPredicate)> for FillExecutor {
+    fn execute(
+        &self,
+        args: (FillArgs, BlockPredicate),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((((), from), to), block), filter) = args;
+        fill(from, to, block, self.0, Some(filter), context)
+    }
+}
+
+/// Vanilla: `FillCommand.fillBlocks`
+fn fill(
+    from: BlockPos,
+    to: BlockPos,
+    block: BlockInput,
+    mode: Mode,
+    filter: Option<BlockPredicate>,
+    context: &CommandContext,
+) -> Result<(), CommandError> {
+    let min = BlockPos::new(
+        from.x().min(to.x()),
+        from.y().min(to.y()),
+        from.z().min(to.z()),
+    );
+    let max = BlockPos::new(
+        from.x().max(to.x()),
+        from.y().max(to.y()),
+        from.z().max(to.z()),
+    );
+
+    let volume = (i64::from(max.x() - min.x()) + 1)
+        * (i64::from(max.y() - min.y()) + 1)
+        * (i64::from(max.z() - min.z()) + 1);
+    let world = context.get_world()?;
+    let limit = world
+        .get_game_rule(MAX_BLOCK_MODIFICATIONS)
+        .as_int()
+        .unwrap_or(32768);
+    if volume > i64::from(limit) {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::COMMANDS_FILL_TOOBIG
+                .message([
+                    TextComponent::plain(limit.to_string()),
+                    TextComponent::plain(volume.to_string()),
+                ])
+                .into(),
+        )));
+    }
+    if !world.has_chunks_at(min, max) {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::ARGUMENT_POS_UNLOADED.msg().into(),
+        )));
+    }
+
+    let mut task = FillTask::new(context.sender.clone(), min, max, block, mode, filter);
+    if volume > MAX_INSTANT_VOLUME {
+        context.sender.send_message(&TextComponent::from(format!(
+            "Filling {volume} blocks over {} ticks",
+            task.sections.len()
+        )));
+        world.queue_block_task(Box::new(task));
+        return Ok(());
+    }
+
+    while let Some(&section) = task.sections.get(task.next) {
+        task.fill_section(world, section);
+        task.next += 1;
+    }
+    if task.filled == 0 {
+        return Err(CommandError::CommandFailed(Box::new(failed_message())));
+    }
+    context.sender.send_message(&task.success_message());
+    Ok(())
+}
+
+/// A fill that places its blocks one chunk section at a time.
+struct FillTask {
+    sender: CommandSender,
+    min: BlockPos,
+    max: BlockPos,
+    block: BlockInput,
+    mode: Mode,
+    filter: Option<BlockPredicate>,
+    /// The sections touched by the fill, grouped by chunk.
+    sections: Vec<SectionPos>,
+    /// The index of the next section to fill.
+    next: usize,
+    /// How many blocks were changed so far.
+    filled: u32,
+}
+
+impl FillTask {
+    fn new(
+        sender: CommandSender,
+        min: BlockPos,
+        max: BlockPos,
+        block: BlockInput,
+        mode: Mode,
+        filter: Option<BlockPredicate>,
+    ) -> Self {
+        let min_section = SectionPos::from_block_pos(min);
+        let max_section = SectionPos::from_block_pos(max);
+        let mut sections = Vec::new();
+        for x in min_section.x()..=max_section.x() {
+            for z in min_section.z()..=max_section.z() {
+                for y in min_section.y()..=max_section.y() {
+                    sections.push(SectionPos::new(x, y, z));
+                }
+            }
+        }
+
+        Self {
+            sender,
+            min,
+            max,
+            block,
+            mode,
+            filter,
+            sections,
+            next: 0,
+            filled: 0,
+        }
+    }
+
+    /// Fills the part of the region inside `section`.
+    fn fill_section(&mut self, world: &World, section: SectionPos) {
+        let air = BlockInput {
+            state: REGISTRY.blocks.get_base_state_id(vanilla_blocks::AIR),
+            nbt: None,
+        };
+        let (min, max) = (self.min, self.max);
+        let clamp =
+            |section: i32, min: i32, max: i32| (min.max(section * 16), max.min(section * 16 + 15));
+        let (min_x, max_x) = clamp(section.x(), min.x(), max.x());
+        let (min_y, max_y) = clamp(section.y(), min.y(), max.y());
+        let (min_z, max_z) = clamp(section.z(), min.z(), max.z());
+
+        for y in min_y..=max_y {
+            for z in min_z..=max_z {
+                for x in min_x..=max_x {
+                    let pos = BlockPos::new(x, y, z);
+                    let on_edge = x == min.x()
+                        || x == max.x()
+                        || y == min.y()
+                        || y == max.y()
+                        || z == min.z()
+                        || z == max.z();
+
+                    let block = match self.mode {
+                        Mode::Outline if !on_edge => continue,
+                        Mode::Hollow if !on_edge => &air,
+                        _ => &self.block,
+                    };
+                    if let Some(filter) = &self.filter
+                        && !filter.test(world.get_block_state(&pos))
+                    {
+                        continue;
+                    }
+                    if self.mode == Mode::Keep && !world.get_block_state(&pos).is_air() {
+                        continue;
+                    }
+                    if self.mode == Mode::Destroy {
+                        world.destroy_block(pos);
+                    }
+
+                    if block.place(world, pos, UpdateFlags::UPDATE_ALL) {
+                        self.filled += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn success_message(&self) -> TextComponent {
+        translations::COMMANDS_FILL_SUCCESS
+            .message([TextComponent::plain(self.filled.to_string())])
+            .into()
+    }
+}
+
+impl BlockTask for FillTask {
+    fn tick(&mut self, world: &World) -> bool {
+        if let Some(&section) = self.sections.get(self.next) {
+            self.fill_section(world, section);
+            self.next += 1;
+        }
+
+        if self.next < self.sections.len() {
+            if self.next.is_multiple_of(PROGRESS_INTERVAL) {
+                self.sender.send_message(&TextComponent::from(format!(
+                    "Filled {}/{} sections ({} blocks changed)",
+                    self.next,
+                    self.sections.len(),
+                    self.filled
+                )));
+            }
+            return true;
+        }
+
+        if self.filled == 0 {
+            self.sender.send_message(&failed_message());
+        } else {
+            self.sender.send_message(&self.success_message());
+        }
+        false
+    }
+}
+
+fn failed_message() -> TextComponent {
+    translations::COMMANDS_FILL_FAILED.msg().into()
+}
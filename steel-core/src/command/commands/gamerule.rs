@@ -55,9 +55,8 @@ struct QueryExecutor(GameRuleRef);
 
 impl CommandExecutor<()> for QueryExecutor {
     fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
-        let world = context.get_world()?;
         let rule_name = self.0.key.path.to_string();
-        let value = world.get_game_rule(self.0);
+        let value = context.server.get_game_rule(self.0);
 
         context.sender.send_message(
             &translations::COMMANDS_GAMERULE_QUERY
@@ -77,10 +76,11 @@ struct SetBoolExecutor(GameRuleRef);
 impl CommandExecutor<((), bool)> for SetBoolExecutor {
     fn execute(&self, args: ((), bool), context: &mut CommandContext) -> Result<(), CommandError> {
         let ((), value) = args;
-        let world = context.get_world()?;
         let rule_name = self.0.key.path.to_string();
 
-        world.set_game_rule(self.0, GameRuleValue::Bool(value));
+        context
+            .server
+            .set_game_rule(self.0, GameRuleValue::Bool(value));
 
         context.sender.send_message(
             &translations::COMMANDS_GAMERULE_SET
@@ -100,10 +100,11 @@ struct SetIntExecutor(GameRuleRef);
 impl CommandExecutor<((), i32)> for SetIntExecutor {
     fn execute(&self, args: ((), i32), context: &mut CommandContext) -> Result<(), CommandError> {
         let ((), value) = args;
-        let world = context.get_world()?;
         let rule_name = self.0.key.path.to_string();
 
-        world.set_game_rule(self.0, GameRuleValue::Int(value));
+        context
+            .server
+            .set_game_rule(self.0, GameRuleValue::Int(value));
 
         context.sender.send_message(
             &translations::COMMANDS_GAMERULE_SET
@@ -0,0 +1,106 @@
+//! Handler for the "locate" command.
+use crate::chunk::structure_placement;
+use crate::command::arguments::structure::StructureArgument;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use steel_registry::REGISTRY;
+use steel_utils::{BlockPos, Identifier, translations};
+use text_components::format::Color;
+use text_components::interactivity::{ClickEvent, HoverEvent};
+use text_components::{Modifier, TextComponent};
+
+/// How many spacing cells around the sender are searched for random spread structures.
+const STRUCTURE_SEARCH_RADIUS: i32 = 100;
+
+/// Handler for the "locate" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["locate"],
+        "Finds the nearest structure of the given type.",
+        "minecraft:command.locate",
+    )
+    .then(
+        literal("structure")
+            .then(argument("structure", StructureArgument).executes(LocateStructureExecutor)),
+    )
+}
+
+struct LocateStructureExecutor;
+
+impl CommandExecutor<((), Identifier)> for LocateStructureExecutor {
+    fn execute(
+        &self,
+        args: ((), Identifier),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((), structure) = args;
+        let name = structure.to_string();
+
+        let sets: Vec<_> = REGISTRY.structure_sets.containing(&structure).collect();
+        if sets.is_empty() {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_LOCATE_STRUCTURE_INVALID
+                    .message([TextComponent::from(name)])
+                    .into(),
+            )));
+        }
+
+        let world = context.get_world()?;
+        let position = context.position.ok_or(CommandError::InvalidRequirement)?;
+        let origin = BlockPos::new(
+            position.x.floor() as i32,
+            position.y.floor() as i32,
+            position.z.floor() as i32,
+        );
+
+        // TODO: Skip structures whose biomes can't generate in this dimension once biome
+        // sources exist, vanilla only searches sets the chunk generator can place
+        let Some(found) =
+            structure_placement::find_nearest(&sets, world.seed(), origin, STRUCTURE_SEARCH_RADIUS)
+        else {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_LOCATE_STRUCTURE_NOT_FOUND
+                    .message([TextComponent::from(name)])
+                    .into(),
+            )));
+        };
+
+        let distance = (f64::from(found.x()) - position.x).hypot(f64::from(found.z()) - position.z);
+        let coordinates = TextComponent::plain("[")
+            .add_children(vec![
+                translations::CHAT_COORDINATES
+                    .message([
+                        TextComponent::from(found.x().to_string()),
+                        TextComponent::plain("~"),
+                        TextComponent::from(found.z().to_string()),
+                    ])
+                    .component(),
+                TextComponent::plain("]"),
+            ])
+            .color(Color::Green)
+            .click_event(ClickEvent::suggest_command(format!(
+                "/tp @s {} ~ {}",
+                found.x(),
+                found.z()
+            )))
+            .hover_event(HoverEvent::show_text(
+                &translations::CHAT_COORDINATES_TOOLTIP,
+            ));
+
+        context.sender.send_message(
+            &translations::COMMANDS_LOCATE_STRUCTURE_SUCCESS
+                .message([
+                    TextComponent::from(name),
+                    coordinates,
+                    TextComponent::from((distance.floor() as i32).to_string()),
+                ])
+                .into(),
+        );
+
+        Ok(())
+    }
+}
@@ -1,13 +1,25 @@
 //! This module contains the command building structs.
+pub mod clone;
+pub mod difficulty;
 pub mod execute;
+pub mod fill;
 pub mod flyspeed;
 pub mod gamemode;
 pub mod gamerule;
+pub mod locate;
+pub mod perf;
+pub mod reload;
 pub mod seed;
+pub mod setblock;
+pub mod sprint;
+pub mod statistics;
 pub mod stop;
+pub mod summon;
+pub mod team;
 pub mod tellraw;
 pub mod tick;
 pub mod weather;
+pub mod worldborder;
 
 use std::marker::PhantomData;
 use std::ops::Not;
@@ -0,0 +1,43 @@
+//! Handler for the "perf" command.
+use crate::command::commands::{CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::server::profiler::PHASES;
+use text_components::TextComponent;
+
+/// Number of recent ticks summarised by the command.
+const REPORT_TICKS: usize = 20;
+
+/// Handler for the "perf" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["perf"],
+        "Shows recent tick timings.",
+        "minecraft:command.perf",
+    )
+    .executes(PerfCommandExecutor)
+}
+
+struct PerfCommandExecutor;
+
+impl CommandExecutor<()> for PerfCommandExecutor {
+    fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
+        let report = context.server.profiler.lock().report_last(REPORT_TICKS);
+
+        context.sender.send_message(&TextComponent::from(format!(
+            "Last {REPORT_TICKS} ticks: avg {:.2} ms, max {:.2} ms",
+            report.mspt_avg, report.mspt_max
+        )));
+
+        for phase in PHASES {
+            let duration = report.breakdown.get(phase).copied().unwrap_or_default();
+            context.sender.send_message(&TextComponent::from(format!(
+                "  {phase}: {:.2} ms",
+                duration.as_secs_f32() * 1000.0
+            )));
+        }
+
+        Ok(())
+    }
+}
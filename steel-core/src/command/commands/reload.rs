@@ -0,0 +1,32 @@
+//! Handler for the "reload" command.
+use text_components::TextComponent;
+
+use crate::command::commands::{CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::config::STEEL_CONFIG;
+
+/// Handler for the "reload" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["reload"],
+        "Reloads the server config.",
+        "minecraft:command.reload",
+    )
+    .executes(ReloadCommandExecutor)
+}
+
+struct ReloadCommandExecutor;
+
+impl CommandExecutor<()> for ReloadCommandExecutor {
+    fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
+        STEEL_CONFIG
+            .reload()
+            .map_err(|err| CommandError::CommandFailed(Box::new(TextComponent::from(err))))?;
+        context
+            .sender
+            .send_message(&TextComponent::const_plain("Reloaded the server config"));
+        Ok(())
+    }
+}
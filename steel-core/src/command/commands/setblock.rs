@@ -0,0 +1,92 @@
+//! Handler for the "setblock" command.
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_utils::BlockPos;
+use steel_utils::translations;
+use steel_utils::types::UpdateFlags;
+use text_components::TextComponent;
+
+use crate::command::arguments::block_pos::BlockPosArgument;
+use crate::command::arguments::block_state::{BlockInput, BlockStateArgument};
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+
+/// Handler for the "setblock" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["setblock"],
+        "Changes a block.",
+        "minecraft:command.setblock",
+    )
+    .then(
+        argument("pos", BlockPosArgument).then(
+            argument("block", BlockStateArgument)
+                .executes(SetBlockExecutor(Mode::Replace))
+                .then(literal("destroy").executes(SetBlockExecutor(Mode::Destroy)))
+                .then(literal("keep").executes(SetBlockExecutor(Mode::Keep)))
+                .then(literal("replace").executes(SetBlockExecutor(Mode::Replace))),
+        ),
+    )
+}
+
+/// What to do with the block that is already there.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Replace the block without dropping it.
+    Replace,
+    /// Only place the block if the position is empty.
+    Keep,
+    /// Break the old block as if a player mined it.
+    Destroy,
+}
+
+struct SetBlockExecutor(Mode);
+
+impl CommandExecutor<(((), BlockPos), BlockInput)> for SetBlockExecutor {
+    fn execute(
+        &self,
+        args: (((), BlockPos), BlockInput),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((), pos), block) = args;
+        let world = context.get_world()?;
+
+        if !world.is_in_valid_bounds(&pos) {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::ARGUMENT_POS_OUTOFWORLD.msg().into(),
+            )));
+        }
+
+        let old_state = world.get_block_state(&pos);
+        if self.0 == Mode::Keep && !old_state.is_air() {
+            return Err(failed());
+        }
+        if self.0 == Mode::Destroy {
+            world.destroy_block(pos);
+        }
+
+        if !block.place(world, pos, UpdateFlags::UPDATE_ALL) {
+            return Err(failed());
+        }
+
+        context.sender.send_message(
+            &translations::COMMANDS_SETBLOCK_SUCCESS
+                .message([
+                    TextComponent::plain(pos.x().to_string()),
+                    TextComponent::plain(pos.y().to_string()),
+                    TextComponent::plain(pos.z().to_string()),
+                ])
+                .into(),
+        );
+        Ok(())
+    }
+}
+
+fn failed() -> CommandError {
+    CommandError::CommandFailed(Box::new(
+        translations::COMMANDS_SETBLOCK_FAILED.msg().into(),
+    ))
+}
@@ -0,0 +1,55 @@
+//! Handler for the "sprint" command.
+use crate::command::arguments::player::PlayerArgument;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::player::Player;
+use std::sync::Arc;
+use text_components::TextComponent;
+
+/// Handler for the "sprint" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["sprint"],
+        "Makes players start or stop sprinting.",
+        "minecraft:command.sprint",
+    )
+    .then(
+        argument("targets", PlayerArgument::new())
+            .then(literal("on").executes(SprintExecutor(true)))
+            .then(literal("off").executes(SprintExecutor(false))),
+    )
+}
+
+struct SprintExecutor(bool);
+
+impl CommandExecutor<((), Vec<Arc<Player>>)> for SprintExecutor {
+    fn execute(
+        &self,
+        args: ((), Vec<Arc<Player>>),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((), targets) = args;
+        let sprinting = self.0;
+
+        for target in &targets {
+            target.set_sprinting(sprinting);
+        }
+
+        let state = if sprinting {
+            "sprinting"
+        } else {
+            "not sprinting"
+        };
+        let message = match targets.as_slice() {
+            [target] => format!("{} is now {state}", target.gameprofile.name),
+            _ => format!("{} players are now {state}", targets.len()),
+        };
+        context.sender.send_message(&TextComponent::from(message));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+//! Handler for the "statistics" command.
+use text_components::TextComponent;
+
+use crate::command::commands::{CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+
+/// How many statistics the command lists.
+const SHOWN_STATS: usize = 10;
+
+/// Handler for the "statistics" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["statistics"],
+        "Shows your highest statistics.",
+        "minecraft:command.statistics",
+    )
+    .executes(StatisticsExecutor)
+}
+
+struct StatisticsExecutor;
+
+impl CommandExecutor<()> for StatisticsExecutor {
+    fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
+        let player = context
+            .sender
+            .get_player()
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let top = player.stats.top(SHOWN_STATS);
+        if top.is_empty() {
+            context
+                .sender
+                .send_message(&TextComponent::plain("You have no statistics yet"));
+            return Ok(());
+        }
+
+        context
+            .sender
+            .send_message(&TextComponent::plain("Your top statistics:"));
+        for (key, amount) in top {
+            context
+                .sender
+                .send_message(&TextComponent::from(format!("{key}: {amount}")));
+        }
+        Ok(())
+    }
+}
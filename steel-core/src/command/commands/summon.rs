@@ -0,0 +1,140 @@
+//! Handler for the "summon" command.
+use simdnbt::owned::NbtCompound;
+use steel_registry::entity_types::{EntityTypeRef, MobCategory};
+use steel_utils::math::Vector3;
+use steel_utils::translations;
+use steel_utils::translations_registry::TRANSLATIONS;
+use steel_utils::types::Difficulty;
+use text_components::TextComponent;
+use text_components::translation::TranslatedMessage;
+
+use crate::command::arguments::entity_type::EntityTypeArgument;
+use crate::command::arguments::nbt::NbtCompoundArgument;
+use crate::command::arguments::vector3::Vector3Argument;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::entity::registry::ENTITIES;
+
+/// How far from the origin entities can be summoned horizontally.
+///
+/// Vanilla: `Level.MAX_LEVEL_SIZE`
+const MAX_LEVEL_SIZE: f64 = 30_000_000.0;
+
+/// Handler for the "summon" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["summon"],
+        "Summons an entity.",
+        "minecraft:command.summon",
+    )
+    .then(
+        argument("entity", EntityTypeArgument)
+            .executes(SummonCommandExecutor)
+            .then(
+                argument("pos", Vector3Argument)
+                    .executes(SummonCommandExecutor)
+                    .then(argument("nbt", NbtCompoundArgument).executes(SummonCommandExecutor)),
+            ),
+    )
+}
+
+struct SummonCommandExecutor;
+
+impl CommandExecutor<((), EntityTypeRef)> for SummonCommandExecutor {
+    fn execute(
+        &self,
+        args: ((), EntityTypeRef),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let pos = context.position.ok_or(CommandError::InvalidRequirement)?;
+        summon(args.1, pos, None, context)
+    }
+}
+
+impl CommandExecutor<(((), EntityTypeRef), Vector3<f64>)> for SummonCommandExecutor {
+    fn execute(
+        &self,
+        args: (((), EntityTypeRef), Vector3<f64>),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((), entity_type), pos) = args;
+        summon(entity_type, pos, None, context)
+    }
+}
+
+impl CommandExecutor<((((), EntityTypeRef), Vector3<f64>), NbtCompound)> for SummonCommandExecutor {
+    fn execute(
+        &self,
+        args: ((((), EntityTypeRef), Vector3<f64>), NbtCompound),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((((), entity_type), pos), nbt) = args;
+        summon(entity_type, pos, Some(&nbt), context)
+    }
+}
+
+/// Vanilla: `SummonCommand.createEntity`
+fn summon(
+    entity_type: EntityTypeRef,
+    pos: Vector3<f64>,
+    nbt: Option<&NbtCompound>,
+    context: &CommandContext,
+) -> Result<(), CommandError> {
+    if !entity_type.summonable {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::ENTITY_NOT_SUMMONABLE
+                .message([TextComponent::plain(format!(
+                    "minecraft:{}",
+                    entity_type.key
+                ))])
+                .into(),
+        )));
+    }
+
+    let world = context.get_world()?;
+    if world.is_outside_build_height(pos.y.floor() as i32)
+        || pos.x.abs() >= MAX_LEVEL_SIZE
+        || pos.z.abs() >= MAX_LEVEL_SIZE
+    {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::COMMANDS_SUMMON_INVALID_POSITION.msg().into(),
+        )));
+    }
+
+    if entity_type.mob_category == MobCategory::Monster
+        && world.difficulty() == Difficulty::Peaceful
+    {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::COMMANDS_SUMMON_FAILED_PEACEFUL.msg().into(),
+        )));
+    }
+
+    let id = context.server.next_entity_id();
+    let added = ENTITIES
+        .create(entity_type, world, id, pos, nbt)
+        .unwrap_or_else(|| world.spawn_entity(id, entity_type, pos, nbt));
+    if !added {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::COMMANDS_SUMMON_FAILED_UUID.msg().into(),
+        )));
+    }
+
+    let custom_name = nbt
+        .and_then(|nbt| nbt.get("CustomName"))
+        .and_then(TextComponent::from_nbt);
+    let name = custom_name.unwrap_or_else(|| {
+        let key = format!("entity.minecraft.{}", entity_type.key);
+        match TRANSLATIONS.get_entry(key.as_str()) {
+            Some((key, _)) => TextComponent::translated(TranslatedMessage::new(key, None)),
+            None => TextComponent::plain(key),
+        }
+    });
+    context
+        .sender
+        .send_message(&translations::COMMANDS_SUMMON_SUCCESS.message([name]).into());
+    Ok(())
+}
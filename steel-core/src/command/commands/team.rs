@@ -0,0 +1,649 @@
+//! Handler for the "team" command.
+use std::sync::Arc;
+
+use steel_utils::translations;
+use text_components::TextComponent;
+
+use crate::command::arguments::bool::BoolArgument;
+use crate::command::arguments::color::ColorArgument;
+use crate::command::arguments::player::PlayerArgument;
+use crate::command::arguments::string::WordArgument;
+use crate::command::arguments::team::TeamArgument;
+use crate::command::arguments::text_component::TextComponentArgument;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::player::Player;
+use crate::scoreboard::{CollisionRule, Team, TeamColor, Visibility};
+
+/// Handler for the "team" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(&["team"], "Manages teams.", "minecraft:command.team")
+        .then(
+            literal("list")
+                .executes(TeamListExecutor)
+                .then(argument("team", TeamArgument).executes(TeamListExecutor)),
+        )
+        .then(
+            literal("add").then(
+                argument("team", WordArgument)
+                    .executes(TeamAddExecutor)
+                    .then(argument("displayName", TextComponentArgument).executes(TeamAddExecutor)),
+            ),
+        )
+        .then(literal("remove").then(argument("team", TeamArgument).executes(TeamRemoveExecutor)))
+        .then(literal("empty").then(argument("team", TeamArgument).executes(TeamEmptyExecutor)))
+        .then(
+            literal("join").then(
+                argument("team", TeamArgument)
+                    .executes(TeamJoinExecutor)
+                    .then(argument("members", PlayerArgument::new()).executes(TeamJoinExecutor)),
+            ),
+        )
+        .then(
+            literal("leave")
+                .then(argument("members", PlayerArgument::new()).executes(TeamLeaveExecutor)),
+        )
+        .then(
+            literal("modify").then(
+                argument("team", TeamArgument)
+                    .then(
+                        literal("displayName").then(
+                            argument("displayName", TextComponentArgument)
+                                .executes(TeamModifyTextExecutor::DisplayName),
+                        ),
+                    )
+                    .then(
+                        literal("color").then(
+                            argument("value", ColorArgument).executes(TeamModifyColorExecutor),
+                        ),
+                    )
+                    .then(
+                        literal("friendlyFire").then(
+                            argument("allowed", BoolArgument)
+                                .executes(TeamModifyBoolExecutor::FriendlyFire),
+                        ),
+                    )
+                    .then(
+                        literal("seeFriendlyInvisibles").then(
+                            argument("allowed", BoolArgument)
+                                .executes(TeamModifyBoolExecutor::SeeFriendlyInvisibles),
+                        ),
+                    )
+                    .then(
+                        literal("nametagVisibility")
+                            .then(literal("never").executes(
+                                TeamModifyRuleExecutor::NametagVisibility(Visibility::Never),
+                            ))
+                            .then(literal("hideForOtherTeams").executes(
+                                TeamModifyRuleExecutor::NametagVisibility(
+                                    Visibility::HideForOtherTeams,
+                                ),
+                            ))
+                            .then(literal("hideForOwnTeam").executes(
+                                TeamModifyRuleExecutor::NametagVisibility(
+                                    Visibility::HideForOwnTeam,
+                                ),
+                            ))
+                            .then(literal("always").executes(
+                                TeamModifyRuleExecutor::NametagVisibility(Visibility::Always),
+                            )),
+                    )
+                    .then(
+                        literal("deathMessageVisibility")
+                            .then(literal("never").executes(
+                                TeamModifyRuleExecutor::DeathMessageVisibility(Visibility::Never),
+                            ))
+                            .then(literal("hideForOtherTeams").executes(
+                                TeamModifyRuleExecutor::DeathMessageVisibility(
+                                    Visibility::HideForOtherTeams,
+                                ),
+                            ))
+                            .then(literal("hideForOwnTeam").executes(
+                                TeamModifyRuleExecutor::DeathMessageVisibility(
+                                    Visibility::HideForOwnTeam,
+                                ),
+                            ))
+                            .then(literal("always").executes(
+                                TeamModifyRuleExecutor::DeathMessageVisibility(Visibility::Always),
+                            )),
+                    )
+                    .then(
+                        literal("collisionRule")
+                            .then(
+                                literal("never").executes(TeamModifyRuleExecutor::CollisionRule(
+                                    CollisionRule::Never,
+                                )),
+                            )
+                            .then(literal("pushOwnTeam").executes(
+                                TeamModifyRuleExecutor::CollisionRule(CollisionRule::PushOwnTeam),
+                            ))
+                            .then(literal("pushOtherTeams").executes(
+                                TeamModifyRuleExecutor::CollisionRule(
+                                    CollisionRule::PushOtherTeams,
+                                ),
+                            ))
+                            .then(literal("always").executes(
+                                TeamModifyRuleExecutor::CollisionRule(CollisionRule::Always),
+                            )),
+                    )
+                    .then(
+                        literal("prefix").then(
+                            argument("prefix", TextComponentArgument)
+                                .executes(TeamModifyTextExecutor::Prefix),
+                        ),
+                    )
+                    .then(
+                        literal("suffix").then(
+                            argument("suffix", TextComponentArgument)
+                                .executes(TeamModifyTextExecutor::Suffix),
+                        ),
+                    ),
+            ),
+        )
+}
+
+fn failed(message: TextComponent) -> CommandError {
+    CommandError::CommandFailed(Box::new(message))
+}
+
+fn team_not_found(name: &str) -> CommandError {
+    failed(
+        translations::TEAM_NOT_FOUND
+            .message([TextComponent::plain(name.to_owned())])
+            .into(),
+    )
+}
+
+/// Joins components with `, `, like vanilla's `ComponentUtils.formatList`.
+fn format_list(items: impl IntoIterator<Item = TextComponent>) -> TextComponent {
+    let mut children = Vec::new();
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            children.push(TextComponent::plain(", "));
+        }
+        children.push(item);
+    }
+    TextComponent::plain("").add_children(children)
+}
+
+// /team list [<team>]
+struct TeamListExecutor;
+
+impl CommandExecutor<()> for TeamListExecutor {
+    fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
+        let scoreboard = context.server.scoreboard.lock();
+        let mut teams: Vec<&Team> = scoreboard.teams().collect();
+        teams.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let message = if teams.is_empty() {
+            translations::COMMANDS_TEAM_LIST_TEAMS_EMPTY.msg().into()
+        } else {
+            translations::COMMANDS_TEAM_LIST_TEAMS_SUCCESS
+                .message([
+                    TextComponent::from(teams.len().to_string()),
+                    format_list(teams.iter().map(|team| team.formatted_display_name())),
+                ])
+                .into()
+        };
+        drop(scoreboard);
+
+        context.sender.send_message(&message);
+        Ok(())
+    }
+}
+
+impl CommandExecutor<((), String)> for TeamListExecutor {
+    fn execute(
+        &self,
+        args: ((), String),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((), name) = args;
+        let scoreboard = context.server.scoreboard.lock();
+        let team = scoreboard
+            .team(&name)
+            .ok_or_else(|| team_not_found(&name))?;
+
+        let message = if team.member_set.is_empty() {
+            translations::COMMANDS_TEAM_LIST_MEMBERS_EMPTY
+                .message([team.formatted_display_name()])
+                .into()
+        } else {
+            let mut members: Vec<&String> = team.member_set.iter().collect();
+            members.sort();
+            translations::COMMANDS_TEAM_LIST_MEMBERS_SUCCESS
+                .message([
+                    team.formatted_display_name(),
+                    TextComponent::from(members.len().to_string()),
+                    format_list(
+                        members
+                            .into_iter()
+                            .map(|member| TextComponent::plain(member.clone())),
+                    ),
+                ])
+                .into()
+        };
+        drop(scoreboard);
+
+        context.sender.send_message(&message);
+        Ok(())
+    }
+}
+
+// /team add <team> [<displayName>]
+struct TeamAddExecutor;
+
+impl CommandExecutor<((), String)> for TeamAddExecutor {
+    fn execute(
+        &self,
+        args: ((), String),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((), name) = args;
+        let display_name = TextComponent::plain(name.clone());
+        self.execute((((), name), display_name), context)
+    }
+}
+
+impl CommandExecutor<(((), String), TextComponent)> for TeamAddExecutor {
+    fn execute(
+        &self,
+        args: (((), String), TextComponent),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((), name), display_name) = args;
+        let mut team = Team::new(name);
+        team.display_name = display_name;
+        let formatted = team.formatted_display_name();
+
+        let packet = context
+            .server
+            .scoreboard
+            .lock()
+            .add_team(team)
+            .ok_or_else(|| failed(translations::COMMANDS_TEAM_ADD_DUPLICATE.msg().into()))?;
+        context.server.broadcast_team_packet(packet);
+
+        context.sender.send_message(
+            &translations::COMMANDS_TEAM_ADD_SUCCESS
+                .message([formatted])
+                .into(),
+        );
+        Ok(())
+    }
+}
+
+// /team remove <team>
+struct TeamRemoveExecutor;
+
+impl CommandExecutor<((), String)> for TeamRemoveExecutor {
+    fn execute(
+        &self,
+        args: ((), String),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((), name) = args;
+        let mut scoreboard = context.server.scoreboard.lock();
+        let formatted = scoreboard
+            .team(&name)
+            .ok_or_else(|| team_not_found(&name))?
+            .formatted_display_name();
+        let packet = scoreboard.remove_team(&name);
+        drop(scoreboard);
+
+        if let Some(packet) = packet {
+            context.server.broadcast_team_packet(packet);
+        }
+        context.sender.send_message(
+            &translations::COMMANDS_TEAM_REMOVE_SUCCESS
+                .message([formatted])
+                .into(),
+        );
+        Ok(())
+    }
+}
+
+// /team empty <team>
+struct TeamEmptyExecutor;
+
+impl CommandExecutor<((), String)> for TeamEmptyExecutor {
+    fn execute(
+        &self,
+        args: ((), String),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((), name) = args;
+        let mut scoreboard = context.server.scoreboard.lock();
+        let formatted = scoreboard
+            .team(&name)
+            .ok_or_else(|| team_not_found(&name))?
+            .formatted_display_name();
+        let emptied = scoreboard.empty_team(&name);
+        drop(scoreboard);
+
+        let (count, packet) = emptied
+            .ok_or_else(|| failed(translations::COMMANDS_TEAM_EMPTY_UNCHANGED.msg().into()))?;
+        context.server.broadcast_team_packet(packet);
+
+        context.sender.send_message(
+            &translations::COMMANDS_TEAM_EMPTY_SUCCESS
+                .message([TextComponent::from(count.to_string()), formatted])
+                .into(),
+        );
+        Ok(())
+    }
+}
+
+// /team join <team> [<members>]
+struct TeamJoinExecutor;
+
+impl CommandExecutor<((), String)> for TeamJoinExecutor {
+    fn execute(
+        &self,
+        args: ((), String),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let player = context
+            .player
+            .clone()
+            .ok_or(CommandError::InvalidRequirement)?;
+        self.execute((args, vec![player]), context)
+    }
+}
+
+impl CommandExecutor<(((), String), Vec<Arc<Player>>)> for TeamJoinExecutor {
+    fn execute(
+        &self,
+        args: (((), String), Vec<Arc<Player>>),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((), name), members) = args;
+        let mut scoreboard = context.server.scoreboard.lock();
+        let formatted = scoreboard
+            .team(&name)
+            .ok_or_else(|| team_not_found(&name))?
+            .formatted_display_name();
+        let packets: Vec<_> = members
+            .iter()
+            .filter_map(|member| scoreboard.join_team(&member.gameprofile.name, &name))
+            .collect();
+        drop(scoreboard);
+
+        for packet in packets {
+            context.server.broadcast_team_packet(packet);
+        }
+
+        // Like vanilla, members already on the team count as joined
+        let message = if let [member] = members.as_slice() {
+            translations::COMMANDS_TEAM_JOIN_SUCCESS_SINGLE
+                .message([
+                    TextComponent::plain(member.gameprofile.name.clone()),
+                    formatted,
+                ])
+                .into()
+        } else {
+            translations::COMMANDS_TEAM_JOIN_SUCCESS_MULTIPLE
+                .message([TextComponent::from(members.len().to_string()), formatted])
+                .into()
+        };
+        context.sender.send_message(&message);
+        Ok(())
+    }
+}
+
+// /team leave <members>
+struct TeamLeaveExecutor;
+
+impl CommandExecutor<((), Vec<Arc<Player>>)> for TeamLeaveExecutor {
+    fn execute(
+        &self,
+        args: ((), Vec<Arc<Player>>),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((), members) = args;
+        let packets: Vec<_> = {
+            let mut scoreboard = context.server.scoreboard.lock();
+            members
+                .iter()
+                .filter_map(|member| scoreboard.leave_team(&member.gameprofile.name))
+                .collect()
+        };
+
+        for packet in packets {
+            context.server.broadcast_team_packet(packet);
+        }
+
+        let message = if let [member] = members.as_slice() {
+            translations::COMMANDS_TEAM_LEAVE_SUCCESS_SINGLE
+                .message([TextComponent::plain(member.gameprofile.name.clone())])
+                .into()
+        } else {
+            translations::COMMANDS_TEAM_LEAVE_SUCCESS_MULTIPLE
+                .message([TextComponent::from(members.len().to_string())])
+                .into()
+        };
+        context.sender.send_message(&message);
+        Ok(())
+    }
+}
+
+/// Applies a change to a team and broadcasts it, returning the team's
+/// formatted display name afterwards.
+///
+/// `change` returns the error to fail with when the option already has the value.
+fn modify_team(
+    name: &str,
+    context: &CommandContext,
+    change: impl FnOnce(&mut Team) -> Result<(), CommandError>,
+) -> Result<TextComponent, CommandError> {
+    let mut scoreboard = context.server.scoreboard.lock();
+    let mut team = scoreboard
+        .team(name)
+        .ok_or_else(|| team_not_found(name))?
+        .clone();
+    change(&mut team)?;
+
+    let formatted = team.formatted_display_name();
+    let packet = scoreboard.modify_team(name, |existing| *existing = team);
+    drop(scoreboard);
+
+    if let Some(packet) = packet {
+        context.server.broadcast_team_packet(packet);
+    }
+    Ok(formatted)
+}
+
+// /team modify <team> displayName|prefix|suffix <value>
+enum TeamModifyTextExecutor {
+    DisplayName,
+    Prefix,
+    Suffix,
+}
+
+impl CommandExecutor<(((), String), TextComponent)> for TeamModifyTextExecutor {
+    fn execute(
+        &self,
+        args: (((), String), TextComponent),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((), name), value) = args;
+        let value_for_message = value.clone();
+        let formatted = modify_team(&name, context, |team| {
+            match self {
+                Self::DisplayName => team.display_name = value,
+                Self::Prefix => team.prefix = value,
+                Self::Suffix => team.suffix = value,
+            }
+            Ok(())
+        })?;
+
+        let message = match self {
+            Self::DisplayName => translations::COMMANDS_TEAM_OPTION_NAME_SUCCESS
+                .message([formatted])
+                .into(),
+            Self::Prefix => translations::COMMANDS_TEAM_OPTION_PREFIX_SUCCESS
+                .message([value_for_message])
+                .into(),
+            Self::Suffix => translations::COMMANDS_TEAM_OPTION_SUFFIX_SUCCESS
+                .message([value_for_message])
+                .into(),
+        };
+        context.sender.send_message(&message);
+        Ok(())
+    }
+}
+
+// /team modify <team> color <value>
+struct TeamModifyColorExecutor;
+
+impl CommandExecutor<(((), String), TeamColor)> for TeamModifyColorExecutor {
+    fn execute(
+        &self,
+        args: (((), String), TeamColor),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((), name), color) = args;
+        let formatted = modify_team(&name, context, |team| {
+            if team.color == color {
+                return Err(failed(
+                    translations::COMMANDS_TEAM_OPTION_COLOR_UNCHANGED
+                        .msg()
+                        .into(),
+                ));
+            }
+            team.color = color;
+            Ok(())
+        })?;
+
+        context.sender.send_message(
+            &translations::COMMANDS_TEAM_OPTION_COLOR_SUCCESS
+                .message([formatted, TextComponent::plain(color.name())])
+                .into(),
+        );
+        Ok(())
+    }
+}
+
+// /team modify <team> friendlyFire|seeFriendlyInvisibles <allowed>
+enum TeamModifyBoolExecutor {
+    FriendlyFire,
+    SeeFriendlyInvisibles,
+}
+
+impl CommandExecutor<(((), String), bool)> for TeamModifyBoolExecutor {
+    fn execute(
+        &self,
+        args: (((), String), bool),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((), name), allowed) = args;
+        let formatted = modify_team(&name, context, |team| {
+            let (option, already_enabled, already_disabled) = match self {
+                Self::FriendlyFire => (
+                    &mut team.friendly_fire,
+                    &translations::COMMANDS_TEAM_OPTION_FRIENDLYFIRE_ALREADY_ENABLED,
+                    &translations::COMMANDS_TEAM_OPTION_FRIENDLYFIRE_ALREADY_DISABLED,
+                ),
+                Self::SeeFriendlyInvisibles => (
+                    &mut team.can_see_invisible_friends,
+                    &translations::COMMANDS_TEAM_OPTION_SEE_FRIENDLY_INVISIBLES_ALREADY_ENABLED,
+                    &translations::COMMANDS_TEAM_OPTION_SEE_FRIENDLY_INVISIBLES_ALREADY_DISABLED,
+                ),
+            };
+            if *option == allowed {
+                let message = if allowed {
+                    already_enabled
+                } else {
+                    already_disabled
+                };
+                return Err(failed(message.msg().into()));
+            }
+            *option = allowed;
+            Ok(())
+        })?;
+
+        let message = match (self, allowed) {
+            (Self::FriendlyFire, true) => &translations::COMMANDS_TEAM_OPTION_FRIENDLYFIRE_ENABLED,
+            (Self::FriendlyFire, false) => {
+                &translations::COMMANDS_TEAM_OPTION_FRIENDLYFIRE_DISABLED
+            }
+            (Self::SeeFriendlyInvisibles, true) => {
+                &translations::COMMANDS_TEAM_OPTION_SEE_FRIENDLY_INVISIBLES_ENABLED
+            }
+            (Self::SeeFriendlyInvisibles, false) => {
+                &translations::COMMANDS_TEAM_OPTION_SEE_FRIENDLY_INVISIBLES_DISABLED
+            }
+        };
+        context
+            .sender
+            .send_message(&message.message([formatted]).into());
+        Ok(())
+    }
+}
+
+// /team modify <team> nametagVisibility|deathMessageVisibility|collisionRule <value>
+enum TeamModifyRuleExecutor {
+    NametagVisibility(Visibility),
+    DeathMessageVisibility(Visibility),
+    CollisionRule(CollisionRule),
+}
+
+impl CommandExecutor<((), String)> for TeamModifyRuleExecutor {
+    fn execute(
+        &self,
+        args: ((), String),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((), name) = args;
+        let formatted = modify_team(&name, context, |team| {
+            let unchanged = match *self {
+                Self::NametagVisibility(value) => {
+                    let unchanged = team.nametag_visibility == value;
+                    team.nametag_visibility = value;
+                    unchanged
+                        .then_some(&translations::COMMANDS_TEAM_OPTION_NAMETAG_VISIBILITY_UNCHANGED)
+                }
+                Self::DeathMessageVisibility(value) => {
+                    let unchanged = team.death_message_visibility == value;
+                    team.death_message_visibility = value;
+                    unchanged.then_some(
+                        &translations::COMMANDS_TEAM_OPTION_DEATH_MESSAGE_VISIBILITY_UNCHANGED,
+                    )
+                }
+                Self::CollisionRule(value) => {
+                    let unchanged = team.collision_rule == value;
+                    team.collision_rule = value;
+                    unchanged
+                        .then_some(&translations::COMMANDS_TEAM_OPTION_COLLISION_RULE_UNCHANGED)
+                }
+            };
+            match unchanged {
+                Some(message) => Err(failed(message.msg().into())),
+                None => Ok(()),
+            }
+        })?;
+
+        let (translation, value) = match *self {
+            Self::NametagVisibility(value) => (
+                &translations::COMMANDS_TEAM_OPTION_NAMETAG_VISIBILITY_SUCCESS,
+                value.display_name(),
+            ),
+            Self::DeathMessageVisibility(value) => (
+                &translations::COMMANDS_TEAM_OPTION_DEATH_MESSAGE_VISIBILITY_SUCCESS,
+                value.display_name(),
+            ),
+            Self::CollisionRule(value) => (
+                &translations::COMMANDS_TEAM_OPTION_COLLISION_RULE_SUCCESS,
+                value.display_name(),
+            ),
+        };
+        context
+            .sender
+            .send_message(&translation.message([formatted, value]).into());
+        Ok(())
+    }
+}
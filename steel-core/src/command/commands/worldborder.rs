@@ -0,0 +1,355 @@
+//! Handler for the "worldborder" command.
+use std::time::Duration;
+
+use steel_protocol::packets::game::{
+    CSetBorderCenter, CSetBorderSize, CSetBorderWarningDelay, CSetBorderWarningDistance,
+};
+use steel_utils::math::Vector2;
+use steel_utils::translations;
+use text_components::TextComponent;
+
+use crate::command::arguments::double::DoubleArgument;
+use crate::command::arguments::float::FloatArgument;
+use crate::command::arguments::integer::IntegerArgument;
+use crate::command::arguments::vector2::Vector2Argument;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::world::border::{MAX_CENTER_COORDINATE, MAX_SIZE};
+
+/// Handler for the "worldborder" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["worldborder"],
+        "Manages the world border.",
+        "minecraft:command.worldborder",
+    )
+    .then(
+        literal("add").then(
+            argument(
+                "distance",
+                DoubleArgument::bounded(Some(-MAX_SIZE), Some(MAX_SIZE)),
+            )
+            .executes(WorldBorderSetExecutor::Add)
+            .then(
+                argument("time", IntegerArgument::bounded(Some(0), None))
+                    .executes(WorldBorderSetExecutor::Add),
+            ),
+        ),
+    )
+    .then(
+        literal("set").then(
+            argument(
+                "distance",
+                DoubleArgument::bounded(Some(-MAX_SIZE), Some(MAX_SIZE)),
+            )
+            .executes(WorldBorderSetExecutor::Set)
+            .then(
+                argument("time", IntegerArgument::bounded(Some(0), None))
+                    .executes(WorldBorderSetExecutor::Set),
+            ),
+        ),
+    )
+    .then(
+        literal("center")
+            .then(argument("pos", Vector2Argument).executes(WorldBorderCenterExecutor)),
+    )
+    .then(
+        literal("damage")
+            .then(
+                literal("amount").then(
+                    argument("damagePerBlock", FloatArgument::bounded(Some(0.0), None))
+                        .executes(WorldBorderDamageExecutor::Amount),
+                ),
+            )
+            .then(
+                literal("buffer").then(
+                    argument("distance", FloatArgument::bounded(Some(0.0), None))
+                        .executes(WorldBorderDamageExecutor::Buffer),
+                ),
+            ),
+    )
+    .then(literal("get").executes(WorldBorderGetExecutor))
+    .then(
+        literal("warning")
+            .then(
+                literal("distance").then(
+                    argument("distance", IntegerArgument::bounded(Some(0), None))
+                        .executes(WorldBorderWarningExecutor::Distance),
+                ),
+            )
+            .then(
+                literal("time").then(
+                    argument("time", IntegerArgument::bounded(Some(0), None))
+                        .executes(WorldBorderWarningExecutor::Time),
+                ),
+            ),
+    )
+}
+
+// /worldborder add|set <distance> [<time>]
+enum WorldBorderSetExecutor {
+    Add,
+    Set,
+}
+
+impl CommandExecutor<((), f64)> for WorldBorderSetExecutor {
+    fn execute(&self, args: ((), f64), context: &mut CommandContext) -> Result<(), CommandError> {
+        self.execute((args, 0), context)
+    }
+}
+
+impl CommandExecutor<(((), f64), i32)> for WorldBorderSetExecutor {
+    /// Based on Java's `WorldBorderCommand.setSize`.
+    #[allow(clippy::float_cmp)] // Intentional: vanilla only rejects the exact same size
+    fn execute(
+        &self,
+        args: (((), f64), i32),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((), distance), seconds) = args;
+        let world = context.get_world()?;
+
+        let current = world.border.lock().size;
+        let new_size = match self {
+            WorldBorderSetExecutor::Add => current + distance,
+            WorldBorderSetExecutor::Set => distance,
+        };
+
+        if new_size == current {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_WORLDBORDER_SET_FAILED_NOCHANGE
+                    .msg()
+                    .into(),
+            )));
+        }
+        if new_size < 1.0 {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_WORLDBORDER_SET_FAILED_SMALL
+                    .msg()
+                    .into(),
+            )));
+        }
+        if new_size > MAX_SIZE {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_WORLDBORDER_SET_FAILED_BIG
+                    .message([TextComponent::from(format!("{MAX_SIZE:.0}"))])
+                    .into(),
+            )));
+        }
+
+        let size_string = format!("{new_size:.1}");
+        if seconds > 0 {
+            world.update_border(|border| {
+                border.lerp_to(new_size, Duration::from_secs(seconds as u64));
+                border.lerp_packet()
+            });
+
+            let message = if new_size > current {
+                &translations::COMMANDS_WORLDBORDER_SET_GROW
+            } else {
+                &translations::COMMANDS_WORLDBORDER_SET_SHRINK
+            };
+            context.sender.send_message(
+                &message
+                    .message([
+                        TextComponent::from(size_string),
+                        TextComponent::from(format!("{seconds}")),
+                    ])
+                    .into(),
+            );
+        } else {
+            world.update_border(|border| {
+                border.set_size(new_size);
+                CSetBorderSize { size: new_size }
+            });
+
+            context.sender.send_message(
+                &translations::COMMANDS_WORLDBORDER_SET_IMMEDIATE
+                    .message([TextComponent::from(size_string)])
+                    .into(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+// /worldborder center <pos>
+struct WorldBorderCenterExecutor;
+
+impl CommandExecutor<((), Vector2<f64>)> for WorldBorderCenterExecutor {
+    /// Based on Java's `WorldBorderCommand.setCenter`.
+    #[allow(clippy::float_cmp)] // Intentional: vanilla only rejects the exact same center
+    fn execute(
+        &self,
+        args: ((), Vector2<f64>),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((), pos) = args;
+        let world = context.get_world()?;
+
+        if world.border.lock().center == (pos.x, pos.y) {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_WORLDBORDER_CENTER_FAILED
+                    .msg()
+                    .into(),
+            )));
+        }
+        if pos.x.abs() > MAX_CENTER_COORDINATE || pos.y.abs() > MAX_CENTER_COORDINATE {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_WORLDBORDER_SET_FAILED_FAR
+                    .message([TextComponent::from(format!("{MAX_CENTER_COORDINATE:.0}"))])
+                    .into(),
+            )));
+        }
+
+        world.update_border(|border| {
+            border.center = (pos.x, pos.y);
+            CSetBorderCenter { x: pos.x, z: pos.y }
+        });
+
+        context.sender.send_message(
+            &translations::COMMANDS_WORLDBORDER_CENTER_SUCCESS
+                .message([
+                    TextComponent::from(format!("{:.2}", pos.x)),
+                    TextComponent::from(format!("{:.2}", pos.y)),
+                ])
+                .into(),
+        );
+        Ok(())
+    }
+}
+
+// /worldborder damage amount|buffer <value>
+enum WorldBorderDamageExecutor {
+    Amount,
+    Buffer,
+}
+
+impl CommandExecutor<((), f32)> for WorldBorderDamageExecutor {
+    #[allow(clippy::float_cmp)] // Intentional: vanilla only rejects the exact same value
+    fn execute(&self, args: ((), f32), context: &mut CommandContext) -> Result<(), CommandError> {
+        let ((), value) = args;
+        let value = f64::from(value);
+        let world = context.get_world()?;
+
+        {
+            let mut border = world.border.lock();
+            let (field, failed) = match self {
+                WorldBorderDamageExecutor::Amount => (
+                    &mut border.damage_per_block,
+                    &translations::COMMANDS_WORLDBORDER_DAMAGE_AMOUNT_FAILED,
+                ),
+                WorldBorderDamageExecutor::Buffer => (
+                    &mut border.safe_zone,
+                    &translations::COMMANDS_WORLDBORDER_DAMAGE_BUFFER_FAILED,
+                ),
+            };
+            if *field == value {
+                return Err(CommandError::CommandFailed(Box::new(failed.msg().into())));
+            }
+            *field = value;
+        }
+        // Clients don't know about border damage, so there is nothing to send
+        world.save_border();
+
+        let success = match self {
+            WorldBorderDamageExecutor::Amount => {
+                &translations::COMMANDS_WORLDBORDER_DAMAGE_AMOUNT_SUCCESS
+            }
+            WorldBorderDamageExecutor::Buffer => {
+                &translations::COMMANDS_WORLDBORDER_DAMAGE_BUFFER_SUCCESS
+            }
+        };
+        context.sender.send_message(
+            &success
+                .message([TextComponent::from(format!("{value:.2}"))])
+                .into(),
+        );
+        Ok(())
+    }
+}
+
+// /worldborder warning distance|time <value>
+enum WorldBorderWarningExecutor {
+    Distance,
+    Time,
+}
+
+impl CommandExecutor<((), i32)> for WorldBorderWarningExecutor {
+    fn execute(&self, args: ((), i32), context: &mut CommandContext) -> Result<(), CommandError> {
+        let ((), value) = args;
+        let world = context.get_world()?;
+
+        let current = {
+            let border = world.border.lock();
+            match self {
+                WorldBorderWarningExecutor::Distance => border.warning_blocks,
+                WorldBorderWarningExecutor::Time => border.warning_time,
+            }
+        };
+
+        match self {
+            WorldBorderWarningExecutor::Distance => {
+                if current == value {
+                    return Err(CommandError::CommandFailed(Box::new(
+                        translations::COMMANDS_WORLDBORDER_WARNING_DISTANCE_FAILED
+                            .msg()
+                            .into(),
+                    )));
+                }
+                world.update_border(|border| {
+                    border.warning_blocks = value;
+                    CSetBorderWarningDistance {
+                        warning_blocks: value,
+                    }
+                });
+                context.sender.send_message(
+                    &translations::COMMANDS_WORLDBORDER_WARNING_DISTANCE_SUCCESS
+                        .message([TextComponent::from(format!("{value}"))])
+                        .into(),
+                );
+            }
+            WorldBorderWarningExecutor::Time => {
+                if current == value {
+                    return Err(CommandError::CommandFailed(Box::new(
+                        translations::COMMANDS_WORLDBORDER_WARNING_TIME_FAILED
+                            .msg()
+                            .into(),
+                    )));
+                }
+                world.update_border(|border| {
+                    border.warning_time = value;
+                    CSetBorderWarningDelay {
+                        warning_delay: value,
+                    }
+                });
+                context.sender.send_message(
+                    &translations::COMMANDS_WORLDBORDER_WARNING_TIME_SUCCESS
+                        .message([TextComponent::from(format!("{value}"))])
+                        .into(),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+// /worldborder get
+struct WorldBorderGetExecutor;
+
+impl CommandExecutor<()> for WorldBorderGetExecutor {
+    fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
+        let size = context.get_world()?.border.lock().size;
+        context.sender.send_message(
+            &translations::COMMANDS_WORLDBORDER_GET
+                .message([TextComponent::from(format!("{size:.0}"))])
+                .into(),
+        );
+        Ok(())
+    }
+}
@@ -44,7 +44,7 @@ impl CommandContext {
     pub fn new(sender: CommandSender, server: Arc<Server>) -> Self {
         let player = sender.get_player().cloned();
         let position = player.as_ref().map(|p| *p.position.lock());
-        let world = player.as_ref().map(|p| Arc::clone(&p.world));
+        let world = player.as_ref().map(|p| p.world());
 
         Self {
             sender,
@@ -29,14 +29,26 @@ impl CommandDispatcher {
     #[must_use]
     pub fn new() -> Self {
         let dispatcher = CommandDispatcher::new_empty();
+        dispatcher.register(commands::clone::command_handler());
+        dispatcher.register(commands::difficulty::command_handler());
         dispatcher.register(commands::execute::command_handler());
+        dispatcher.register(commands::fill::command_handler());
         dispatcher.register(commands::flyspeed::command_handler());
         dispatcher.register(commands::gamemode::command_handler());
         dispatcher.register(commands::gamerule::command_handler());
+        dispatcher.register(commands::locate::command_handler());
+        dispatcher.register(commands::perf::command_handler());
+        dispatcher.register(commands::reload::command_handler());
         dispatcher.register(commands::seed::command_handler());
+        dispatcher.register(commands::setblock::command_handler());
+        dispatcher.register(commands::sprint::command_handler());
+        dispatcher.register(commands::statistics::command_handler());
         dispatcher.register(commands::stop::command_handler());
+        dispatcher.register(commands::summon::command_handler());
+        dispatcher.register(commands::team::command_handler());
         dispatcher.register(commands::tick::command_handler());
         dispatcher.register(commands::weather::command_handler());
+        dispatcher.register(commands::worldborder::command_handler());
         dispatcher.register(commands::tellraw::command_handler());
         dispatcher
     }
@@ -1,5 +1,9 @@
 //! Module defining the sender of a command.
-use std::{fmt, sync::Arc};
+use std::{
+    fmt::{self, Write},
+    sync::Arc,
+};
+use steel_utils::locks::SyncMutex;
 use text_components::TextComponent;
 
 use crate::player::Player;
@@ -12,7 +16,8 @@ pub enum CommandSender {
     /// The command was sent via the server's console.
     Console,
     /// The command was sent via Rcon.
-    Rcon,
+    /// Output is collected into the buffer and returned to the Rcon client.
+    Rcon(Arc<SyncMutex<String>>),
 }
 
 impl CommandSender {
@@ -30,8 +35,13 @@ impl CommandSender {
         match self {
             Self::Player(player) => player.send_message(text),
             Self::Console => log::info!("{text:p}"),
-            // TODO: Implement Rcon message sending
-            Self::Rcon => unimplemented!(),
+            Self::Rcon(output) => {
+                let mut output = output.lock();
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                let _ = write!(output, "{text:p}");
+            }
         }
     }
 }
@@ -44,7 +54,7 @@ impl fmt::Display for CommandSender {
             match self {
                 Self::Player(p) => &p.gameprofile.name,
                 Self::Console => "Server",
-                Self::Rcon => "Rcon",
+                Self::Rcon(_) => "Rcon",
             }
         )
     }
@@ -2,41 +2,93 @@
 //!
 //! The `ServerConfig` struct is defined here, but loading is handled by the `steel` crate.
 //! Steel-core accesses config via `STEEL_CONFIG` after steel initializes it.
+//! The config can be reloaded while the server runs, so read options where
+//! they are used instead of keeping copies around.
 
-use std::ops::Deref;
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
+use arc_swap::ArcSwap;
 use serde::Deserialize;
 use steel_protocol::packet_traits::CompressionInfo;
+use steel_protocol::packets::common::CAddResourcePack;
 use steel_protocol::packets::config::{CServerLinks, Link, ServerLinksType};
 use steel_utils::codec::Or;
-use text_components::TextComponent;
+use text_components::{TextComponent, resolving::TextResolutor};
+use uuid::Uuid;
+
+use crate::chunk::generators::flat::DEFAULT_PRESET;
+
+/// Reads the config file again and swaps it in, returning why it couldn't.
+///
+/// Provided by the `steel` crate, which owns the config file.
+pub type ConfigReloader = fn() -> Result<(), String>;
 
 /// Reference to the server configuration.
 ///
 /// This is initialized by the `steel` crate during server startup.
 /// Access configuration via the `STEEL_CONFIG` static.
-pub struct ServerConfigRef;
-
-static CONFIG: OnceLock<&'static ServerConfig> = OnceLock::new();
+pub struct ServerConfigRef {
+    /// The current config, swapped out as a whole on reload.
+    config: OnceLock<ArcSwap<ServerConfig>>,
+    /// Reloads the config from its file.
+    reloader: OnceLock<ConfigReloader>,
+}
 
 impl ServerConfigRef {
     /// Initializes the configuration reference.
     ///
     /// # Panics
     /// Panics if called more than once.
-    pub fn init(config: &'static ServerConfig) {
-        CONFIG
-            .set(config)
-            .expect("Server config already initialized");
+    pub fn init(config: ServerConfig, reloader: ConfigReloader) {
+        assert!(
+            STEEL_CONFIG
+                .config
+                .set(ArcSwap::from_pointee(config))
+                .is_ok(),
+            "Server config already initialized"
+        );
+        assert!(
+            STEEL_CONFIG.reloader.set(reloader).is_ok(),
+            "Config reloader already initialized"
+        );
+    }
+
+    /// Replaces the configuration. Code loading `STEEL_CONFIG` afterwards sees
+    /// the new values, while loads that are still held keep the old ones.
+    ///
+    /// # Panics
+    /// Panics if the config wasn't initialized.
+    pub fn replace(config: ServerConfig) {
+        STEEL_CONFIG.swap().store(Arc::new(config));
     }
-}
 
-impl Deref for ServerConfigRef {
-    type Target = ServerConfig;
+    /// Returns the current config.
+    ///
+    /// A reload doesn't change a config that was loaded before it, so load it
+    /// where the options are used instead of keeping it around.
+    ///
+    /// # Panics
+    /// Panics if the config wasn't initialized.
+    #[must_use]
+    pub fn load(&self) -> Arc<ServerConfig> {
+        self.swap().load_full()
+    }
 
-    fn deref(&self) -> &Self::Target {
-        CONFIG
+    /// Reads the config file again and swaps it in.
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read, parsed or validated. The
+    /// current config stays in use in that case.
+    pub fn reload(&self) -> Result<(), String> {
+        let reload = self
+            .reloader
+            .get()
+            .ok_or_else(|| "Config reloading isn't set up".to_owned())?;
+        reload()
+    }
+
+    fn swap(&self) -> &ArcSwap<ServerConfig> {
+        self.config
             .get()
             .expect("Server config not initialized - steel crate must call ServerConfigRef::init()")
     }
@@ -45,7 +97,10 @@ impl Deref for ServerConfigRef {
 /// The server configuration.
 ///
 /// Access via `STEEL_CONFIG` static after initialization by the steel crate.
-pub static STEEL_CONFIG: ServerConfigRef = ServerConfigRef;
+pub static STEEL_CONFIG: ServerConfigRef = ServerConfigRef {
+    config: OnceLock::new(),
+    reloader: OnceLock::new(),
+};
 
 /// Label type for server links - either built-in string or custom `TextComponent`
 #[derive(Debug, Clone, Deserialize)]
@@ -82,7 +137,8 @@ impl ServerLinks {
     /// Creates the server link package from the server config
     #[must_use]
     pub fn from_config() -> Option<CServerLinks> {
-        let server_links = STEEL_CONFIG.server_links.as_ref()?;
+        let config = STEEL_CONFIG.load();
+        let server_links = config.server_links.as_ref()?;
 
         if !server_links.enable || server_links.links.is_empty() {
             return None;
@@ -104,6 +160,98 @@ impl ServerLinks {
     }
 }
 
+/// A resource pack the client is asked to download while joining.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResourcePackConfig {
+    /// Where the client downloads the pack from.
+    pub url: String,
+    /// The SHA-1 hash of the pack as 40 hex characters.
+    pub sha1_hash: String,
+    /// Whether players who decline or fail to load the pack are disconnected.
+    #[serde(default)]
+    pub forced: bool,
+    /// Shown to the player in the download prompt. Uses the client's default when empty.
+    #[serde(default)]
+    pub prompt_message: String,
+}
+
+impl ResourcePackConfig {
+    /// The id the pack is sent with. Derived from the hash, so the client
+    /// treats a changed pack as a new one.
+    ///
+    /// # Panics
+    /// Panics if the hash was not validated when loading the config.
+    #[must_use]
+    pub fn id(&self) -> Uuid {
+        Uuid::parse_str(&self.sha1_hash[..32]).expect("resource pack hash should be validated")
+    }
+
+    /// Creates the packet asking the client to download the pack.
+    #[must_use]
+    pub fn to_packet<T: TextResolutor>(&self, player: &T) -> CAddResourcePack {
+        let prompt = (!self.prompt_message.is_empty())
+            .then(|| TextComponent::plain(self.prompt_message.clone()));
+        CAddResourcePack::new(
+            self.id(),
+            self.url.clone(),
+            self.sha1_hash.to_lowercase(),
+            self.forced,
+            prompt.as_ref(),
+            player,
+        )
+    }
+}
+
+/// The chunk generator used for new chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratorType {
+    /// Vanilla's noise based terrain. Not implemented yet, generates a flat world instead.
+    Noise,
+    /// Layers of blocks described by `generator_settings`.
+    #[default]
+    Flat,
+    /// No blocks at all.
+    Void,
+}
+
+/// How log lines are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Human readable lines with colors.
+    #[default]
+    Text,
+    /// One JSON object per line, for log aggregators.
+    Json,
+}
+
+/// Where logs are written to disk, on top of stdout.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct FileLogConfig {
+    /// The current log file. Rotated logs get `.1`, `.2` and so on appended.
+    #[serde(default = "default_log_path")]
+    pub path: String,
+    /// The log is rotated before it grows past this many megabytes.
+    #[serde(default = "default_max_file_size_mb")]
+    pub max_file_size_mb: u64,
+    /// How many rotated logs are kept.
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+}
+
+fn default_log_path() -> String {
+    "logs/steel.log".to_owned()
+}
+
+const fn default_max_file_size_mb() -> u64 {
+    10
+}
+
+const fn default_max_files() -> usize {
+    5
+}
+
 /// The server configuration.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ServerConfig {
@@ -121,6 +269,10 @@ pub struct ServerConfig {
     pub view_distance: u8,
     /// The simulation distance of the server.
     pub simulation_distance: u8,
+    /// How many chunks around the overworld spawn stay loaded and ticking
+    /// without players nearby. 0 keeps none loaded.
+    #[serde(default = "default_spawn_chunk_radius")]
+    pub spawn_chunk_radius: u8,
     /// Whether the server is in online mode.
     pub online_mode: bool,
     /// Whether the server should use encryption.
@@ -137,4 +289,80 @@ pub struct ServerConfig {
     pub compression: Option<CompressionInfo>,
     /// All settings and configurations for server links
     pub server_links: Option<ServerLinks>,
+    /// The resource pack players download while joining. None to send no pack.
+    #[serde(default)]
+    pub resource_pack: Option<ResourcePackConfig>,
+    /// Port for the Prometheus metrics endpoint. Disabled when unset.
+    #[serde(default)]
+    pub metrics_port: Option<u16>,
+    /// Password for remote console access. RCON is disabled when unset.
+    #[serde(default)]
+    pub rcon_password: Option<String>,
+    /// The port RCON listens on.
+    #[serde(default = "default_rcon_port")]
+    pub rcon_port: u16,
+    /// Whether to answer GS4 query requests on `server_port + 1`.
+    #[serde(default)]
+    pub enable_query: bool,
+    /// How long Mojang's profile keys are cached before being fetched again, in seconds.
+    #[serde(default = "default_profile_key_cache_ttl")]
+    pub profile_key_cache_ttl: u64,
+    /// How long a player's skin is cached before being refreshed, in seconds.
+    #[serde(default = "default_skin_cache_ttl")]
+    pub skin_cache_ttl: u64,
+    /// The chunk generator used for new chunks.
+    #[serde(default)]
+    pub generator_type: GeneratorType,
+    /// The flat world preset, like `minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block;minecraft:plains`.
+    #[serde(default = "default_generator_settings")]
+    pub generator_settings: String,
+    /// Whether structures like villages are generated.
+    #[serde(default = "default_generate_structures")]
+    pub generate_structures: bool,
+    /// Threads each world generates chunks on. Uses one per CPU core when unset.
+    #[serde(default)]
+    pub chunk_generation_threads: Option<usize>,
+    /// How log lines are written.
+    #[serde(default)]
+    pub log_format: LogFormat,
+    /// Writes logs to a rotated file as well as stdout. Disabled when unset.
+    #[serde(default)]
+    pub log_file: Option<FileLogConfig>,
+    /// Players idle for this many seconds are warned, then kicked. 0 disables kicking.
+    #[serde(default)]
+    pub afk_timeout_seconds: u64,
+    /// How long after the warning an idle player is kicked.
+    #[serde(default = "default_afk_warning_seconds")]
+    pub afk_warning_seconds: u64,
+    /// Players idle for this many seconds are announced as AFK. 0 disables announcements.
+    #[serde(default)]
+    pub afk_announce_seconds: u64,
+}
+
+const fn default_spawn_chunk_radius() -> u8 {
+    10
+}
+
+const fn default_rcon_port() -> u16 {
+    25575
+}
+
+const fn default_profile_key_cache_ttl() -> u64 {
+    3600
+}
+
+const fn default_skin_cache_ttl() -> u64 {
+    3600
+}
+
+fn default_generator_settings() -> String {
+    DEFAULT_PRESET.to_owned()
+}
+
+const fn default_generate_structures() -> bool {
+    true
+}
+
+const fn default_afk_warning_seconds() -> u64 {
+    30
 }
@@ -0,0 +1,4 @@
+//! The pieces mobs build their behavior from, like finding their way around.
+
+pub mod pathfinding;
+pub mod sight;
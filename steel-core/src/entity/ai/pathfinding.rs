@@ -0,0 +1,313 @@
+//! Finding paths for mobs over the block grid.
+//!
+//! Paths are lists of the blocks a mob's feet pass through. Walking mobs stand
+//! in passable blocks on top of solid ones, can jump up one block and drop
+//! down up to two; floating mobs like fish don't need the solid block below.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use rustc_hash::FxHashMap;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_utils::BlockPos;
+
+use crate::world::World;
+
+/// The most blocks a path leads through; the rest of a longer path is cut
+/// off and found again once the mob gets there.
+pub const MAX_PATH_LENGTH: usize = 32;
+
+/// How far the goal of a path may move before the path is found again.
+const REPATH_DISTANCE: i32 = 3;
+
+/// How far a walking mob drops down in a single step.
+const MAX_DROP: i32 = 2;
+
+/// Returns true if a mob can move through the block at `pos`.
+fn is_passable(world: &World, pos: BlockPos) -> bool {
+    world.get_block_state(&pos).get_collision_shape().is_empty()
+}
+
+/// Finds a path for a walking mob from `start` to `goal`, visiting at most
+/// `max_nodes` blocks.
+///
+/// If `goal` can't be reached, the path leads to the visited block closest
+/// to it. Returns the blocks to walk through after `start`, or `None` if the
+/// mob can't get any closer.
+#[must_use]
+pub fn find_path(
+    start: BlockPos,
+    goal: BlockPos,
+    world: &World,
+    max_nodes: usize,
+) -> Option<Vec<BlockPos>> {
+    search(start, goal, max_nodes, false, &|pos| {
+        is_passable(world, pos)
+    })
+}
+
+/// Like [`find_path`], but for mobs that float, like fish, which don't need
+/// solid ground below them.
+#[must_use]
+pub fn find_floating_path(
+    start: BlockPos,
+    goal: BlockPos,
+    world: &World,
+    max_nodes: usize,
+) -> Option<Vec<BlockPos>> {
+    search(start, goal, max_nodes, true, &|pos| is_passable(world, pos))
+}
+
+/// The Manhattan distance between two blocks, which never overestimates
+/// the cost of walking from one to the other.
+fn manhattan_distance(a: BlockPos, b: BlockPos) -> i32 {
+    (a.x() - b.x()).abs() + (a.y() - b.y()).abs() + (a.z() - b.z()).abs()
+}
+
+/// A block visited by the search.
+struct Node {
+    pos: BlockPos,
+    /// The index of the node the search came from.
+    parent: Option<usize>,
+    /// The cost of walking here from the start.
+    cost: i32,
+}
+
+/// Searches for a path from `start` to `goal` with A*. `is_passable` tells
+/// whether a block can be moved through.
+fn search(
+    start: BlockPos,
+    goal: BlockPos,
+    max_nodes: usize,
+    can_float: bool,
+    is_passable: &impl Fn(BlockPos) -> bool,
+) -> Option<Vec<BlockPos>> {
+    let can_stand = |pos: BlockPos| {
+        is_passable(pos)
+            && is_passable(pos.offset(0, 1, 0))
+            && (can_float || !is_passable(pos.offset(0, -1, 0)))
+    };
+
+    let mut nodes = vec![Node {
+        pos: start,
+        parent: None,
+        cost: 0,
+    }];
+    let mut best_costs = FxHashMap::from_iter([(start, 0)]);
+    // Ordered by the estimated total cost, then by the distance left
+    let mut open = BinaryHeap::from([Reverse((
+        manhattan_distance(start, goal),
+        manhattan_distance(start, goal),
+        0,
+    ))]);
+    let mut closest = (manhattan_distance(start, goal), 0);
+
+    while let Some(Reverse((_, distance, index))) = open.pop() {
+        let (pos, cost) = (nodes[index].pos, nodes[index].cost);
+        if best_costs.get(&pos).is_some_and(|&best| best < cost) {
+            continue;
+        }
+        if distance < closest.0 {
+            closest = (distance, index);
+        }
+        if pos == goal || nodes.len() >= max_nodes {
+            break;
+        }
+
+        for next in successors(pos, can_float, is_passable, &can_stand) {
+            let next_cost = cost + 1 + (next.y() - pos.y()).abs();
+            if best_costs.get(&next).is_some_and(|&best| best <= next_cost) {
+                continue;
+            }
+            best_costs.insert(next, next_cost);
+            let next_distance = manhattan_distance(next, goal);
+            open.push(Reverse((
+                next_cost + next_distance,
+                next_distance,
+                nodes.len(),
+            )));
+            nodes.push(Node {
+                pos: next,
+                parent: Some(index),
+                cost: next_cost,
+            });
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut index = closest.1;
+    while let Some(parent) = nodes[index].parent {
+        path.push(nodes[index].pos);
+        index = parent;
+    }
+    if path.is_empty() {
+        return None;
+    }
+    path.reverse();
+    path.truncate(MAX_PATH_LENGTH);
+    Some(path)
+}
+
+/// Returns the blocks a mob standing in `pos` can move to in one step.
+fn successors(
+    pos: BlockPos,
+    can_float: bool,
+    is_passable: &impl Fn(BlockPos) -> bool,
+    can_stand: &impl Fn(BlockPos) -> bool,
+) -> Vec<BlockPos> {
+    let mut successors = Vec::new();
+    if can_float {
+        for (dx, dy, dz) in [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ] {
+            let next = pos.offset(dx, dy, dz);
+            if can_stand(next) {
+                successors.push(next);
+            }
+        }
+        return successors;
+    }
+
+    for (dx, dz) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+        let level = pos.offset(dx, 0, dz);
+        if can_stand(level) {
+            successors.push(level);
+            continue;
+        }
+
+        // Jumping up needs room above the head
+        let up = pos.offset(dx, 1, dz);
+        if is_passable(pos.offset(0, 2, 0)) && can_stand(up) {
+            successors.push(up);
+            continue;
+        }
+
+        // Dropping down needs the blocks in between to be open
+        if !is_passable(level) || !is_passable(level.offset(0, 1, 0)) {
+            continue;
+        }
+        for drop in 1..=MAX_DROP {
+            let down = pos.offset(dx, -drop, dz);
+            if can_stand(down) {
+                successors.push(down);
+                break;
+            }
+            if !is_passable(down) {
+                break;
+            }
+        }
+    }
+    successors
+}
+
+/// The path a mob follows, kept until it is walked or its goal moves away.
+///
+/// Vanilla: `PathNavigation`
+#[derive(Debug, Default)]
+pub struct PathNavigation {
+    /// The blocks left to walk through.
+    nodes: VecDeque<BlockPos>,
+    /// The goal the path was found for.
+    goal: Option<BlockPos>,
+}
+
+impl PathNavigation {
+    /// Creates a navigation without a path.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Heads for `goal` from `start`, finding a new path if there is none
+    /// yet or the goal moved more than a few blocks since the path was
+    /// found. Returns true if a new path was searched for.
+    pub fn move_to(
+        &mut self,
+        start: BlockPos,
+        goal: BlockPos,
+        world: &World,
+        max_nodes: usize,
+    ) -> bool {
+        if !self.nodes.is_empty() && self.goal.is_some_and(|old| !goal_moved(old, goal)) {
+            return false;
+        }
+        self.goal = Some(goal);
+        self.nodes = find_path(start, goal, world, max_nodes)
+            .map(VecDeque::from)
+            .unwrap_or_default();
+        true
+    }
+
+    /// Returns the next block to walk into, if any.
+    #[must_use]
+    pub fn next_node(&self) -> Option<BlockPos> {
+        self.nodes.front().copied()
+    }
+
+    /// Marks the next block as reached.
+    pub fn advance(&mut self) {
+        self.nodes.pop_front();
+    }
+
+    /// Returns true once there is nothing left to walk.
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Forgets the path and its goal.
+    pub fn stop(&mut self) {
+        self.nodes.clear();
+        self.goal = None;
+    }
+}
+
+/// Returns true if the goal moved from `old` to `new` far enough to need a
+/// new path.
+fn goal_moved(old: BlockPos, new: BlockPos) -> bool {
+    let (dx, dy, dz) = (new.x() - old.x(), new.y() - old.y(), new.z() - old.z());
+    dx * dx + dy * dy + dz * dz > REPATH_DISTANCE * REPATH_DISTANCE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A floor below y = 0 with a one block step up at x = 2 and a two block
+    /// drop at x = 6.
+    fn is_passable(pos: BlockPos) -> bool {
+        if pos.x() >= 6 {
+            pos.y() >= -1
+        } else if pos.x() >= 2 {
+            pos.y() >= 1
+        } else {
+            pos.y() >= 0
+        }
+    }
+
+    #[test]
+    fn climbs_steps_and_drops_down() {
+        let goal = BlockPos::new(7, -1, 0);
+        let path = search(BlockPos::new(0, 0, 0), goal, 1000, false, &is_passable)
+            .expect("the goal can be reached");
+        assert_eq!(path.first(), Some(&BlockPos::new(1, 0, 0)));
+        assert!(path.contains(&BlockPos::new(2, 1, 0)));
+        assert!(path.contains(&BlockPos::new(6, -1, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        assert_eq!(path.len(), 7);
+    }
+
+    #[test]
+    fn long_paths_are_truncated() {
+        let goal = BlockPos::new(-100, 0, 0);
+        let path = search(BlockPos::new(0, 0, 0), goal, 10_000, false, &is_passable)
+            .expect("the goal can be reached");
+        assert_eq!(path.len(), MAX_PATH_LENGTH);
+        assert_eq!(path.last(), Some(&BlockPos::new(-32, 0, 0)));
+    }
+}
@@ -0,0 +1,113 @@
+//! Checking whether mobs can see each other through the blocks between them.
+
+use std::iter;
+
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_utils::BlockPos;
+use steel_utils::math::Vector3;
+
+use crate::world::World;
+
+/// Returns true if no block that light can't pass through is between `from`
+/// and `to`, like a mob's eyes and its target.
+///
+/// Vanilla: `LivingEntity.hasLineOfSight`
+#[must_use]
+pub fn has_line_of_sight(world: &World, from: Vector3<f64>, to: Vector3<f64>) -> bool {
+    !blocks_between(from, to).any(|pos| {
+        let block = world.get_block_state(&pos).get_block();
+        block.config.has_collision && block.config.can_occlude
+    })
+}
+
+/// Walks the blocks a line from `from` to `to` passes through, in order,
+/// starting with the block `from` is in.
+///
+/// Based on the grid traversal of Amanatides and Woo, like vanilla's
+/// `BlockGetter.traverseBlocks`.
+#[allow(clippy::cast_possible_truncation, clippy::needless_range_loop)]
+pub fn blocks_between(from: Vector3<f64>, to: Vector3<f64>) -> impl Iterator<Item = BlockPos> {
+    let delta = to.sub(&from);
+    let mut pos = [
+        from.x.floor() as i32,
+        from.y.floor() as i32,
+        from.z.floor() as i32,
+    ];
+    let end = [
+        to.x.floor() as i32,
+        to.y.floor() as i32,
+        to.z.floor() as i32,
+    ];
+    let start = [from.x, from.y, from.z];
+    let delta = [delta.x, delta.y, delta.z];
+
+    let mut step = [0; 3];
+    // How far along the line each axis crosses its next block border, and
+    // how far along the line one block on each axis is
+    let mut next_border = [f64::INFINITY; 3];
+    let mut border_distance = [f64::INFINITY; 3];
+    for axis in 0..3 {
+        if delta[axis].abs() < 1.0e-7 {
+            continue;
+        }
+        step[axis] = if delta[axis] > 0.0 { 1 } else { -1 };
+        border_distance[axis] = 1.0 / delta[axis].abs();
+        let offset = if delta[axis] > 0.0 {
+            f64::from(pos[axis]) + 1.0 - start[axis]
+        } else {
+            start[axis] - f64::from(pos[axis])
+        };
+        next_border[axis] = offset * border_distance[axis];
+    }
+
+    let mut done = false;
+    iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        let current = BlockPos::new(pos[0], pos[1], pos[2]);
+        if pos == end {
+            done = true;
+            return Some(current);
+        }
+
+        let axis = (0..3)
+            .min_by(|&a, &b| next_border[a].total_cmp(&next_border[b]))
+            .expect("there are three axes");
+        if next_border[axis] > 1.0 {
+            done = true;
+        } else {
+            pos[axis] += step[axis];
+            next_border[axis] += border_distance[axis];
+        }
+        Some(current)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walks_every_block_on_a_straight_line() {
+        let blocks: Vec<_> =
+            blocks_between(Vector3::new(0.5, 0.5, 0.5), Vector3::new(3.5, 0.5, 0.5)).collect();
+        let expected: Vec<_> = (0..=3).map(|x| BlockPos::new(x, 0, 0)).collect();
+        assert_eq!(blocks, expected);
+    }
+
+    #[test]
+    fn walks_connected_blocks_on_a_diagonal() {
+        let from = Vector3::new(0.2, 0.5, 0.7);
+        let to = Vector3::new(-4.6, 2.3, 3.1);
+        let blocks: Vec<_> = blocks_between(from, to).collect();
+        assert_eq!(blocks.first(), Some(&BlockPos::new(0, 0, 0)));
+        assert_eq!(blocks.last(), Some(&BlockPos::new(-5, 2, 3)));
+        for pair in blocks.windows(2) {
+            let distance = (pair[0].x() - pair[1].x()).abs()
+                + (pair[0].y() - pair[1].y()).abs()
+                + (pair[0].z() - pair[1].z()).abs();
+            assert_eq!(distance, 1);
+        }
+    }
+}
@@ -0,0 +1,681 @@
+//! Armor stands showing armor and held items in a pose.
+
+use std::mem;
+
+use simdnbt::ToNbtTag;
+use simdnbt::owned::{NbtCompound, NbtList};
+use steel_protocol::packets::game::{
+    CAddEntity, CEntityEvent, CEntityPositionSync, CSetEquipment, ENTITY_EVENT_ARMOR_STAND_HIT,
+    SoundSource,
+};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::{DataValue, Rotations};
+use steel_registry::entity_types::{EntityDimensions, EntityTypeRef};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entity_data::ArmorStandEntityData;
+use steel_registry::{sound_events, vanilla_entities, vanilla_items};
+use steel_utils::math::Vector3;
+use uuid::Uuid;
+
+use crate::behavior::InteractionResult;
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, GenericEntity, Remains, StoredEntity};
+use crate::inventory::equipment::{EntityEquipment, EquipmentSlot, EquipmentSlotType};
+use crate::physics::{
+    DEFAULT_DRAG, DEFAULT_GRAVITY, EntityPhysicsState, MoverType, WorldCollisionProvider,
+    move_entity, stop_blocked_axes,
+};
+use crate::world::World;
+
+/// The small bit of the armor stand flags (Vanilla: `ArmorStand.CLIENT_FLAG_SMALL`).
+const CLIENT_FLAG_SMALL: i8 = 1;
+/// The arms bit of the armor stand flags (Vanilla: `ArmorStand.CLIENT_FLAG_SHOW_ARMS`).
+const CLIENT_FLAG_SHOW_ARMS: i8 = 1 << 2;
+/// The base plate bit of the armor stand flags (Vanilla: `ArmorStand.CLIENT_FLAG_NO_BASEPLATE`).
+const CLIENT_FLAG_NO_BASEPLATE: i8 = 1 << 3;
+/// The marker bit of the armor stand flags (Vanilla: `ArmorStand.CLIENT_FLAG_MARKER`).
+const CLIENT_FLAG_MARKER: i8 = 1 << 4;
+
+/// How many ticks a second hit has to follow the first to break a stand.
+const BREAK_HIT_TICKS: u8 = 5;
+
+/// The slots an armor stand shows items in.
+const SLOTS: [EquipmentSlot; 6] = [
+    EquipmentSlot::MainHand,
+    EquipmentSlot::OffHand,
+    EquipmentSlot::Feet,
+    EquipmentSlot::Legs,
+    EquipmentSlot::Chest,
+    EquipmentSlot::Head,
+];
+
+/// A part of an armor stand that can be posed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyPart {
+    /// The head.
+    Head,
+    /// The torso.
+    Body,
+    /// The left arm, only shown with arms.
+    LeftArm,
+    /// The right arm, only shown with arms.
+    RightArm,
+    /// The left leg.
+    LeftLeg,
+    /// The right leg.
+    RightLeg,
+}
+
+impl BodyPart {
+    /// All body parts.
+    pub const ALL: [BodyPart; 6] = [
+        BodyPart::Head,
+        BodyPart::Body,
+        BodyPart::LeftArm,
+        BodyPart::RightArm,
+        BodyPart::LeftLeg,
+        BodyPart::RightLeg,
+    ];
+
+    /// Returns the name of the part in the `Pose` tag.
+    #[must_use]
+    pub const fn nbt_name(self) -> &'static str {
+        match self {
+            BodyPart::Head => "Head",
+            BodyPart::Body => "Body",
+            BodyPart::LeftArm => "LeftArm",
+            BodyPart::RightArm => "RightArm",
+            BodyPart::LeftLeg => "LeftLeg",
+            BodyPart::RightLeg => "RightLeg",
+        }
+    }
+
+    /// Returns the rotation of the part on a freshly placed stand, in degrees.
+    ///
+    /// Vanilla: `ArmorStand.DEFAULT_HEAD_POSE` and friends
+    #[must_use]
+    pub const fn default_pose(self) -> Rotations {
+        let (x, y, z) = match self {
+            BodyPart::Head | BodyPart::Body => (0.0, 0.0, 0.0),
+            BodyPart::LeftArm => (-10.0, 0.0, -10.0),
+            BodyPart::RightArm => (-15.0, 0.0, 10.0),
+            BodyPart::LeftLeg => (-1.0, 0.0, -1.0),
+            BodyPart::RightLeg => (1.0, 0.0, 1.0),
+        };
+        Rotations { x, y, z }
+    }
+}
+
+/// What a hit did to an armor stand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArmorStandHit {
+    /// The stand wobbled and breaks if hit again soon.
+    Wobbled,
+    /// The stand broke, dropping itself and its equipment.
+    Broken,
+    /// The stand was removed without drops, like by a creative player.
+    Removed,
+}
+
+/// An armor stand, showing the items in its armor and hand slots.
+///
+/// Vanilla: `ArmorStand`
+pub struct ArmorStandEntity {
+    /// The state every entity shares, like the id and custom name.
+    pub base: GenericEntity,
+    physics: EntityPhysicsState,
+    equipment: EntityEquipment,
+    entity_data: ArmorStandEntityData,
+    /// Ticks left in which another hit breaks the stand.
+    hit_cooldown: u8,
+}
+
+impl ArmorStandEntity {
+    /// Creates an armor stand with the default pose at `position`.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        Self {
+            base: GenericEntity::new(id, vanilla_entities::ARMOR_STAND, position),
+            physics: EntityPhysicsState::new(position, vanilla_entities::ARMOR_STAND),
+            equipment: EntityEquipment::new(),
+            entity_data: ArmorStandEntityData::new(),
+            hit_cooldown: 0,
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the pose, the flags and the equipment.
+    ///
+    /// Vanilla: `ArmorStand.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(invisible) = nbt.byte("Invisible") {
+            self.base.invisible = invisible != 0;
+        }
+
+        for (key, flag) in [
+            ("Small", CLIENT_FLAG_SMALL),
+            ("ShowArms", CLIENT_FLAG_SHOW_ARMS),
+            ("NoBasePlate", CLIENT_FLAG_NO_BASEPLATE),
+            ("Marker", CLIENT_FLAG_MARKER),
+        ] {
+            if let Some(value) = nbt.byte(key) {
+                self.set_client_flag(flag, value != 0);
+            }
+        }
+
+        if let Some(pose) = nbt.compound("Pose") {
+            for part in BodyPart::ALL {
+                let rotation = pose
+                    .list(part.nbt_name())
+                    .and_then(|list| list.floats())
+                    .and_then(|rotation| match *rotation {
+                        [x, y, z] => Some(Rotations::new(x, y, z)),
+                        _ => None,
+                    });
+                self.set_pose(part, rotation.unwrap_or(part.default_pose()));
+            }
+        }
+
+        if let Some(equipment) = nbt.compound("equipment") {
+            for slot in SLOTS {
+                if let Some(item) = equipment
+                    .compound(slot.name())
+                    .and_then(entity::item_from_nbt)
+                {
+                    self.equipment.set(slot, item);
+                }
+            }
+        }
+
+        // TODO: Read `DisabledSlots` once slots can be locked
+        self.physics.set_dimensions(self.dimensions());
+    }
+
+    /// Writes the pose, the flags and the equipment into `nbt`, the tags
+    /// [`read_nbt`](Self::read_nbt) loads.
+    ///
+    /// Vanilla: `ArmorStand.addAdditionalSaveData`
+    pub fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("Invisible", i8::from(self.base.invisible));
+        for (key, flag) in [
+            ("Small", CLIENT_FLAG_SMALL),
+            ("ShowArms", CLIENT_FLAG_SHOW_ARMS),
+            ("NoBasePlate", CLIENT_FLAG_NO_BASEPLATE),
+            ("Marker", CLIENT_FLAG_MARKER),
+        ] {
+            nbt.insert(key, i8::from(self.has_client_flag(flag)));
+        }
+
+        let mut pose = NbtCompound::new();
+        for part in BodyPart::ALL {
+            let rotation = self.pose(part);
+            pose.insert(
+                part.nbt_name(),
+                NbtList::Float(vec![rotation.x, rotation.y, rotation.z]),
+            );
+        }
+        nbt.insert("Pose", pose);
+
+        let mut equipment = NbtCompound::new();
+        for slot in SLOTS {
+            let item = self.item(slot);
+            if !item.is_empty() {
+                equipment.insert(slot.name(), item.clone().to_nbt_tag());
+            }
+        }
+        if !equipment.is_empty() {
+            nbt.insert("equipment", equipment);
+        }
+    }
+
+    /// Returns the rotation of `part`, in degrees.
+    #[must_use]
+    pub fn pose(&self, part: BodyPart) -> Rotations {
+        let data = &self.entity_data;
+        *match part {
+            BodyPart::Head => data.head_pose.get(),
+            BodyPart::Body => data.body_pose.get(),
+            BodyPart::LeftArm => data.left_arm_pose.get(),
+            BodyPart::RightArm => data.right_arm_pose.get(),
+            BodyPart::LeftLeg => data.left_leg_pose.get(),
+            BodyPart::RightLeg => data.right_leg_pose.get(),
+        }
+    }
+
+    /// Sets the rotation of `part`, in degrees.
+    pub fn set_pose(&mut self, part: BodyPart, rotation: Rotations) {
+        let data = &mut self.entity_data;
+        match part {
+            BodyPart::Head => data.head_pose.set(rotation),
+            BodyPart::Body => data.body_pose.set(rotation),
+            BodyPart::LeftArm => data.left_arm_pose.set(rotation),
+            BodyPart::RightArm => data.right_arm_pose.set(rotation),
+            BodyPart::LeftLeg => data.left_leg_pose.set(rotation),
+            BodyPart::RightLeg => data.right_leg_pose.set(rotation),
+        }
+    }
+
+    fn has_client_flag(&self, flag: i8) -> bool {
+        self.entity_data.client_flags.get() & flag != 0
+    }
+
+    fn set_client_flag(&mut self, flag: i8, set: bool) {
+        let flags = *self.entity_data.client_flags.get();
+        self.entity_data
+            .client_flags
+            .set(if set { flags | flag } else { flags & !flag });
+    }
+
+    /// Returns true for the half sized armor stand.
+    #[must_use]
+    pub fn is_small(&self) -> bool {
+        self.has_client_flag(CLIENT_FLAG_SMALL)
+    }
+
+    /// Returns true if the stand has arms to hold items with.
+    #[must_use]
+    pub fn shows_arms(&self) -> bool {
+        self.has_client_flag(CLIENT_FLAG_SHOW_ARMS)
+    }
+
+    /// Returns true for a marker, which has no hitbox and doesn't fall.
+    #[must_use]
+    pub fn is_marker(&self) -> bool {
+        self.has_client_flag(CLIENT_FLAG_MARKER)
+    }
+
+    /// Returns the item in `slot`, empty if there is none.
+    #[must_use]
+    pub fn item(&self, slot: EquipmentSlot) -> &ItemStack {
+        self.equipment.get_ref(slot)
+    }
+
+    /// Returns the size of the stand.
+    ///
+    /// Vanilla: `ArmorStand.getDefaultDimensions`
+    fn dimensions(&self) -> EntityDimensions {
+        let dimensions = vanilla_entities::ARMOR_STAND.dimensions;
+        if self.is_marker() {
+            EntityDimensions::new(0.0, 0.0, 0.0)
+        } else if self.is_small() {
+            EntityDimensions::new(
+                dimensions.width * 0.5,
+                dimensions.height * 0.5,
+                dimensions.eye_height * 0.5,
+            )
+        } else {
+            dimensions
+        }
+    }
+
+    /// Returns the box the stand takes up.
+    #[must_use]
+    pub fn bounding_box(&self) -> AABBd {
+        self.physics.bounding_box
+    }
+
+    /// The packet that adds this stand to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the stand is added, like its
+    /// pose.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// The packet showing the stand's items, if it has any.
+    #[must_use]
+    pub fn equipment_packet(&self) -> Option<CSetEquipment> {
+        let slots: Vec<_> = SLOTS
+            .into_iter()
+            .filter(|&slot| !self.equipment.is_slot_empty(slot))
+            .map(|slot| (slot.id(), self.equipment.get_ref(slot).clone()))
+            .collect();
+        (!slots.is_empty()).then(|| CSetEquipment::new(self.base.id, slots))
+    }
+
+    /// Puts `item` in `slot` and shows it to clients, returning the item that
+    /// was there.
+    fn set_item(&mut self, world: &World, slot: EquipmentSlot, item: ItemStack) -> ItemStack {
+        let shown = item.clone();
+        let old = self.equipment.set(slot, item);
+        world.broadcast_to_trackers(
+            *self.base.position.lock(),
+            CSetEquipment::new(self.base.id, vec![(slot.id(), shown)]),
+        );
+        old
+    }
+
+    /// Takes the items the stand drops when it breaks: itself and everything
+    /// it wears and holds.
+    pub fn take_drops(&mut self) -> Vec<ItemStack> {
+        let mut drops = vec![ItemStack::new(&vanilla_items::ITEMS.armor_stand)];
+        drops.extend(self.take_equipment());
+        drops
+    }
+
+    /// Takes everything the stand wears and holds.
+    fn take_equipment(&mut self) -> Vec<ItemStack> {
+        SLOTS
+            .into_iter()
+            .map(|slot| self.equipment.take(slot))
+            .filter(|item| !item.is_empty())
+            .collect()
+    }
+
+    /// Advances the stand by a tick, letting it fall and syncing where it
+    /// ended up.
+    ///
+    /// Vanilla: `LivingEntity.travel`
+    pub fn tick(&mut self, world: &World) {
+        self.hit_cooldown = self.hit_cooldown.saturating_sub(1);
+        if self.is_marker() {
+            return;
+        }
+
+        let delta = self.physics.velocity;
+        let result = move_entity(
+            &self.physics,
+            delta,
+            MoverType::SelfMovement,
+            &WorldCollisionProvider::new(world),
+        );
+        self.physics.set_position(result.final_position);
+        self.physics.on_ground = result.on_ground;
+        self.physics.horizontal_collision = result.horizontal_collision;
+        self.physics.vertical_collision = result.vertical_collision;
+
+        let friction = if result.on_ground {
+            let below = Vector3::new(
+                result.final_position.x,
+                result.final_position.y - 0.500_001,
+                result.final_position.z,
+            );
+            let state = world.get_block_state(&below.to_block_pos());
+            f64::from(state.get_block().config.friction)
+        } else {
+            1.0
+        };
+        let gravity = if self.base.no_gravity {
+            0.0
+        } else {
+            DEFAULT_GRAVITY
+        };
+        self.physics.velocity = next_velocity(delta, result.actual_movement, friction, gravity);
+
+        if result.actual_movement != Vector3::new(0.0, 0.0, 0.0) {
+            *self.base.position.lock() = result.final_position;
+            let (yaw, pitch) = self.base.rotation.load();
+            let velocity = self.physics.velocity;
+            world.broadcast_to_trackers(
+                result.final_position,
+                CEntityPositionSync {
+                    entity_id: self.base.id,
+                    x: result.final_position.x,
+                    y: result.final_position.y,
+                    z: result.final_position.z,
+                    velocity_x: velocity.x,
+                    velocity_y: velocity.y,
+                    velocity_z: velocity.z,
+                    yaw,
+                    pitch,
+                    on_ground: result.on_ground,
+                },
+            );
+        }
+    }
+
+    /// Right-clicks the stand at `hit_y` blocks above its feet, holding
+    /// `held`. An empty hand takes the item from the clicked part, anything
+    /// else is swapped with the item in the slot it goes in.
+    ///
+    /// Vanilla: `ArmorStand.interactAt`
+    pub fn interact_at(
+        &mut self,
+        world: &World,
+        held: &mut ItemStack,
+        hit_y: f64,
+        infinite_materials: bool,
+    ) -> InteractionResult {
+        if self.is_marker() || held.is(&vanilla_items::ITEMS.name_tag) {
+            return InteractionResult::Pass;
+        }
+
+        if held.is_empty() {
+            let slot = self.clicked_slot(hit_y);
+            if self.equipment.is_slot_empty(slot) {
+                return InteractionResult::Pass;
+            }
+            *held = self.set_item(world, slot, ItemStack::empty());
+            return InteractionResult::Success;
+        }
+
+        let slot = slot_for_item(held);
+        if slot.slot_type() == EquipmentSlotType::Hand && !self.shows_arms() {
+            return InteractionResult::Fail;
+        }
+
+        let current_empty = self.equipment.is_slot_empty(slot);
+        if infinite_materials && current_empty {
+            let mut item = held.clone();
+            item.set_count(1);
+            self.set_item(world, slot, item);
+        } else if held.count() > 1 {
+            if !current_empty {
+                return InteractionResult::Pass;
+            }
+            let mut item = held.clone();
+            item.set_count(1);
+            held.shrink(1);
+            self.set_item(world, slot, item);
+        } else {
+            let item = mem::take(held);
+            *held = self.set_item(world, slot, item);
+        }
+        InteractionResult::Success
+    }
+
+    /// Returns the slot of the part clicked at `hit_y` blocks above the feet,
+    /// falling back to the main hand.
+    ///
+    /// Vanilla: `ArmorStand.getClickedSlot`
+    fn clicked_slot(&self, hit_y: f64) -> EquipmentSlot {
+        let small = self.is_small();
+        let y = if small { hit_y / 0.5 } else { hit_y };
+        let has = |slot| !self.equipment.is_slot_empty(slot);
+        // Where the boots, chestplate and leggings are, by how tall the stand is
+        let (feet, chest, legs) = if small {
+            (0.1..0.9, 1.2..1.9, 0.4..1.4)
+        } else {
+            (0.1..0.55, 0.9..1.6, 0.4..1.2)
+        };
+
+        if feet.contains(&y) && has(EquipmentSlot::Feet) {
+            EquipmentSlot::Feet
+        } else if chest.contains(&y) && has(EquipmentSlot::Chest) {
+            EquipmentSlot::Chest
+        } else if legs.contains(&y) && has(EquipmentSlot::Legs) {
+            EquipmentSlot::Legs
+        } else if y >= 1.6 && has(EquipmentSlot::Head) {
+            EquipmentSlot::Head
+        } else if !has(EquipmentSlot::MainHand) && has(EquipmentSlot::OffHand) {
+            EquipmentSlot::OffHand
+        } else {
+            EquipmentSlot::MainHand
+        }
+    }
+
+    /// Hits the stand. A creative player removes it at once, anyone else
+    /// needs two quick hits.
+    ///
+    /// Vanilla: `ArmorStand.hurtServer`
+    pub fn hurt(&mut self, world: &World, infinite_materials: bool) -> ArmorStandHit {
+        if infinite_materials {
+            return ArmorStandHit::Removed;
+        }
+        if self.hit_cooldown > 0 {
+            return ArmorStandHit::Broken;
+        }
+
+        self.hit_cooldown = BREAK_HIT_TICKS;
+        world.broadcast_to_trackers(
+            *self.base.position.lock(),
+            CEntityEvent {
+                entity_id: self.base.id,
+                event: ENTITY_EVENT_ARMOR_STAND_HIT,
+            },
+        );
+        ArmorStandHit::Wobbled
+    }
+}
+
+impl Entity for ArmorStandEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for ArmorStandEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        *self.base.position.lock()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+        if let Some(packet) = self.equipment_packet() {
+            pairing.send(packet);
+        }
+    }
+
+    /// Saves the stand with its pose and equipment.
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.base.write_nbt(&mut nbt);
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.base.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, breaks_blocks: bool) -> Option<AABBd> {
+        (breaks_blocks && !self.base.invisible && !self.is_marker()).then(|| self.bounding_box())
+    }
+
+    /// Breaks the stand, dropping what it wears and holds but not itself.
+    ///
+    /// Vanilla: `ArmorStand.hurtServer` and `ArmorStand.brokenByAnything`
+    fn explosion_hit(
+        &mut self,
+        world: &World,
+        _damage: f32,
+        _impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        let pos = self.get_position().to_block_pos();
+        world.play_sound(
+            sound_events::ENTITY_ARMOR_STAND_BREAK,
+            SoundSource::Neutral,
+            pos,
+            1.0,
+            1.0,
+            None,
+        );
+        Some(Remains {
+            pos,
+            drops: self.take_equipment(),
+            passengers: Vec::new(),
+        })
+    }
+}
+
+/// Returns the slot an armor stand wears `item` in.
+///
+/// Vanilla: `LivingEntity.getEquipmentSlotForItem`
+fn slot_for_item(item: &ItemStack) -> EquipmentSlot {
+    match item.get_equippable_slot().map(EquipmentSlot::from) {
+        Some(slot) if !matches!(slot, EquipmentSlot::Body | EquipmentSlot::Saddle) => slot,
+        _ => EquipmentSlot::MainHand,
+    }
+}
+
+/// The velocity after a tick of moving `movement` out of `delta`, losing
+/// speed to `friction` and the air and speeding up the fall by `gravity`.
+/// Blocked axes stop.
+fn next_velocity(
+    delta: Vector3<f64>,
+    movement: Vector3<f64>,
+    friction: f64,
+    gravity: f64,
+) -> Vector3<f64> {
+    let velocity = stop_blocked_axes(delta, movement);
+    let horizontal = friction * 0.91;
+    Vector3::new(
+        velocity.x * horizontal,
+        (velocity.y - gravity) * DEFAULT_DRAG,
+        velocity.z * horizontal,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_faster_until_it_lands() {
+        let mut delta = Vector3::new(0.0, 0.0, 0.0);
+        delta = next_velocity(delta, delta, 1.0, DEFAULT_GRAVITY);
+        assert!((delta.y + 0.0784).abs() < 1e-9);
+        let falling = next_velocity(delta, delta, 1.0, DEFAULT_GRAVITY);
+        assert!(falling.y < delta.y);
+
+        let landed = next_velocity(delta, Vector3::new(0.0, 0.0, 0.0), 0.6, DEFAULT_GRAVITY);
+        assert!((landed.y + 0.0784).abs() < 1e-9);
+    }
+
+    #[test]
+    fn default_pose_matches_a_placed_stand() {
+        assert_eq!(
+            BodyPart::LeftArm.default_pose(),
+            Rotations::new(-10.0, 0.0, -10.0)
+        );
+        assert_eq!(BodyPart::Head.default_pose(), Rotations::ZERO);
+    }
+
+    #[test]
+    fn saved_stands_keep_their_pose_and_flags() {
+        let mut stand = ArmorStandEntity::new(1, Vector3::new(0.5, 64.0, 0.5));
+        stand.set_pose(BodyPart::RightArm, Rotations::new(-90.0, 15.0, 0.0));
+        stand.set_client_flag(CLIENT_FLAG_SHOW_ARMS, true);
+        stand.set_client_flag(CLIENT_FLAG_SMALL, true);
+        let nbt = stand.save().expect("armor stands are saved");
+
+        let mut loaded = ArmorStandEntity::new(2, Vector3::new(0.0, 0.0, 0.0));
+        loaded.read_nbt(&nbt);
+        assert_eq!(loaded.base.uuid, stand.base.uuid);
+        assert_eq!(
+            loaded.pose(BodyPart::RightArm),
+            Rotations::new(-90.0, 15.0, 0.0)
+        );
+        assert_eq!(
+            loaded.pose(BodyPart::LeftLeg),
+            BodyPart::LeftLeg.default_pose()
+        );
+        assert!(loaded.shows_arms() && loaded.is_small() && !loaded.is_marker());
+    }
+}
@@ -0,0 +1,353 @@
+//! Arrows flying until they hit a player or stick in a block.
+
+use std::sync::Arc;
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, CEntityPositionSync, SoundSource};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::vanilla_entity_data::ArrowEntityData;
+use steel_registry::{REGISTRY, sound_events, vanilla_entities};
+use steel_utils::math::Vector3;
+use steel_utils::types::GameType;
+use uuid::Uuid;
+
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, Remains, StoredEntity};
+use crate::physics::{EntityPhysicsState, MoverType, WorldCollisionProvider, move_entity};
+use crate::player::Player;
+use crate::world::World;
+
+/// How fast arrows fall, in blocks per tick squared.
+const GRAVITY: f64 = 0.05;
+/// How much of its speed an arrow keeps each tick in the air.
+const DRAG: f64 = 0.99;
+/// The damage an arrow deals at a speed of one block per tick.
+const BASE_DAMAGE: f64 = 2.0;
+/// How far around a player's box an arrow still hits them.
+const HIT_MARGIN: f64 = 0.3;
+/// How many ticks an arrow stays stuck in a block before it despawns.
+const MAX_GROUND_TICKS: u32 = 1200;
+
+/// An arrow, hurting the first player it flies into.
+///
+/// Vanilla: `Arrow`
+pub struct ArrowEntity {
+    /// The network id of the entity.
+    pub id: i32,
+    /// The persistent id of the entity.
+    pub uuid: Uuid,
+    /// The network id of the entity that shot the arrow, if any.
+    pub owner: Option<i32>,
+    physics: EntityPhysicsState,
+    /// Ticks the arrow has been stuck in a block.
+    ground_ticks: u32,
+    entity_data: ArrowEntityData,
+}
+
+impl ArrowEntity {
+    /// Creates an arrow at `position` flying with `velocity`, shot by the
+    /// entity with network id `owner`.
+    #[must_use]
+    pub fn new(
+        id: i32,
+        position: Vector3<f64>,
+        velocity: Vector3<f64>,
+        owner: Option<i32>,
+    ) -> Self {
+        let mut physics = EntityPhysicsState::new(position, vanilla_entities::ARROW);
+        physics.velocity = velocity;
+
+        Self {
+            id,
+            uuid: Uuid::new_v4(),
+            owner,
+            physics,
+            ground_ticks: 0,
+            entity_data: ArrowEntityData::new(),
+        }
+    }
+
+    /// Applies the arrow tags of `nbt`, like whether it is stuck in a block.
+    /// Tags that aren't set keep their current value, and the position is
+    /// never loaded. Who shot the arrow isn't saved.
+    ///
+    /// Vanilla: `AbstractArrow.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(uuid) = entity::uuid_from_nbt(nbt) {
+            self.uuid = uuid;
+        }
+        if let Some(velocity) = entity::motion_from_nbt(nbt) {
+            self.physics.velocity = velocity;
+        }
+        if let Some(in_ground) = nbt.byte("inGround") {
+            self.entity_data.in_ground.set(in_ground != 0);
+        }
+        if let Some(life) = nbt.short("life") {
+            self.ground_ticks = life.max(0) as u32;
+        }
+    }
+
+    /// Returns the position of the arrow.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.physics.position
+    }
+
+    /// Returns true once the arrow stuck in a block.
+    #[must_use]
+    pub fn is_in_ground(&self) -> bool {
+        *self.entity_data.in_ground.get()
+    }
+
+    /// The packet that adds this arrow to clients, pointing along its flight.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        let position = self.position();
+        let (yaw, pitch) = rotation_of(self.physics.velocity);
+        let mut packet = CAddEntity::new(
+            self.id,
+            self.uuid,
+            *REGISTRY.entity_types.get_id(vanilla_entities::ARROW) as i32,
+            position.x,
+            position.y,
+            position.z,
+            yaw,
+            pitch,
+        );
+        // Clients tell who shot the arrow from the data
+        packet.data = self.owner.unwrap_or(self.id);
+        packet
+    }
+
+    /// The entity data clients need right after the arrow is added.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        self.entity_data.pack_all()
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Advances the arrow by a tick, flying it forward until it hits a
+    /// player or a block. Returns false once it should be removed.
+    ///
+    /// Vanilla: `AbstractArrow.tick`
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn tick(&mut self, world: &World) -> bool {
+        if self.is_in_ground() {
+            self.ground_ticks += 1;
+            return self.ground_ticks < MAX_GROUND_TICKS;
+        }
+
+        let from = self.position();
+        let velocity = self.physics.velocity;
+        if let Some(player) = self.find_hit_player(world, from, from + velocity) {
+            let damage = (velocity.length() * BASE_DAMAGE).ceil() as f32;
+            player.take_damage(damage);
+            world.play_sound(
+                sound_events::ENTITY_ARROW_HIT,
+                SoundSource::Neutral,
+                player.position.lock().to_block_pos(),
+                1.0,
+                1.2,
+                None,
+            );
+            return false;
+        }
+
+        let result = move_entity(
+            &self.physics,
+            velocity,
+            MoverType::SelfMovement,
+            &WorldCollisionProvider::new(world),
+        );
+        self.physics.set_position(result.final_position);
+        if result.horizontal_collision || result.vertical_collision {
+            self.physics.velocity = Vector3::new(0.0, 0.0, 0.0);
+            self.entity_data.in_ground.set(true);
+            world.play_sound(
+                sound_events::ENTITY_ARROW_HIT,
+                SoundSource::Neutral,
+                result.final_position.to_block_pos(),
+                1.0,
+                1.2,
+                None,
+            );
+        } else {
+            self.physics.velocity = Vector3::new(
+                velocity.x * DRAG,
+                velocity.y * DRAG - GRAVITY,
+                velocity.z * DRAG,
+            );
+        }
+
+        let (yaw, pitch) = rotation_of(velocity);
+        world.broadcast_to_trackers(
+            result.final_position,
+            CEntityPositionSync {
+                entity_id: self.id,
+                x: result.final_position.x,
+                y: result.final_position.y,
+                z: result.final_position.z,
+                velocity_x: self.physics.velocity.x,
+                velocity_y: self.physics.velocity.y,
+                velocity_z: self.physics.velocity.z,
+                yaw,
+                pitch,
+                on_ground: false,
+            },
+        );
+        true
+    }
+
+    /// Returns the player the arrow flies into on its way from `from` to
+    /// `to`, if any.
+    fn find_hit_player(
+        &self,
+        world: &World,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+    ) -> Option<Arc<Player>> {
+        let mut hit = None;
+        world.players.iter_players(|_, player| {
+            if self.owner != Some(player.id)
+                && player.game_mode.load() != GameType::Spectator
+                && segment_hits_box(from, to, &player.bounding_box().inflate(HIT_MARGIN))
+            {
+                hit = Some(player.clone());
+                return false;
+            }
+            true
+        });
+        hit
+    }
+}
+
+impl Entity for ArrowEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
+impl StoredEntity for ArrowEntity {
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        vanilla_entities::ARROW
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    /// Vanilla: `AbstractArrow.addAdditionalSaveData`
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        entity::write_position(&mut nbt, self.position());
+        entity::write_uuid(&mut nbt, self.uuid);
+        entity::write_motion(&mut nbt, self.physics.velocity);
+        nbt.insert("inGround", i8::from(self.is_in_ground()));
+        nbt.insert("life", self.ground_ticks as i16);
+        Some(nbt)
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.physics.bounding_box)
+    }
+
+    /// Arrows stuck in a block stay where they are.
+    fn explosion_hit(
+        &mut self,
+        _world: &World,
+        _damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        if !self.is_in_ground() {
+            self.physics.velocity += impulse;
+        }
+        None
+    }
+}
+
+/// The yaw and pitch of an arrow flying with `velocity`.
+///
+/// Vanilla: `Projectile.updateRotation`
+#[allow(clippy::cast_possible_truncation)]
+fn rotation_of(velocity: Vector3<f64>) -> (f32, f32) {
+    let horizontal = velocity.x.hypot(velocity.z);
+    let yaw = velocity.x.atan2(velocity.z).to_degrees();
+    let pitch = velocity.y.atan2(horizontal).to_degrees();
+    (yaw as f32, pitch as f32)
+}
+
+/// Returns true if the line from `from` to `to` passes through `bounding_box`.
+fn segment_hits_box(from: Vector3<f64>, to: Vector3<f64>, bounding_box: &AABBd) -> bool {
+    let delta = to.sub(&from);
+    let mut enter: f64 = 0.0;
+    let mut exit: f64 = 1.0;
+    for (start, delta, min, max) in [
+        (from.x, delta.x, bounding_box.min_x, bounding_box.max_x),
+        (from.y, delta.y, bounding_box.min_y, bounding_box.max_y),
+        (from.z, delta.z, bounding_box.min_z, bounding_box.max_z),
+    ] {
+        if delta.abs() < 1.0e-7 {
+            if start < min || start > max {
+                return false;
+            }
+            continue;
+        }
+        let (near, far) = {
+            let a = (min - start) / delta;
+            let b = (max - start) / delta;
+            (a.min(b), a.max(b))
+        };
+        enter = enter.max(near);
+        exit = exit.min(far);
+        if enter > exit {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_through_boxes_hit_them() {
+        let bounding_box = AABBd::entity_box(5.0, 0.0, 0.0, 0.5, 2.0);
+        let hits = |from, to| segment_hits_box(from, to, &bounding_box);
+        assert!(hits(
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(10.0, 1.0, 0.0)
+        ));
+        assert!(hits(
+            Vector3::new(4.0, 3.0, 0.0),
+            Vector3::new(6.0, 0.5, 0.2)
+        ));
+        // Stopping short, passing above and going the other way
+        assert!(!hits(
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(4.0, 1.0, 0.0)
+        ));
+        assert!(!hits(
+            Vector3::new(0.0, 2.5, 0.0),
+            Vector3::new(10.0, 2.5, 0.0)
+        ));
+        assert!(!hits(
+            Vector3::new(4.0, 1.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0)
+        ));
+    }
+}
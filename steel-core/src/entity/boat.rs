@@ -0,0 +1,575 @@
+//! Boats and rafts, which players ride across water.
+
+use std::mem;
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, CEntityPositionSync};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::items::ItemRef;
+use steel_registry::vanilla_entity_data::OakBoatEntityData;
+use steel_registry::{vanilla_entities, vanilla_items};
+use steel_utils::BlockPos;
+use steel_utils::math::Vector3;
+use uuid::Uuid;
+
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, GenericEntity, Remains, StoredEntity, VehicleInput};
+use crate::physics::{EntityPhysicsState, MoverType, WorldCollisionProvider, move_entity};
+use crate::player::movement;
+use crate::world::World;
+
+/// How fast boats fall, in blocks per tick squared.
+const GRAVITY: f64 = 0.04;
+/// How much speed a boat keeps each tick while floating or in the air.
+const WATER_DRAG: f64 = 0.9;
+/// How much speed a boat keeps each tick while sunk below the surface.
+const UNDER_WATER_DRAG: f64 = 0.45;
+/// How much a boat speeds up each tick while its passenger paddles forward.
+const FORWARD_ACCELERATION: f64 = 0.04;
+/// How much a boat slows down each tick while its passenger paddles back.
+const BACKWARD_ACCELERATION: f64 = 0.005;
+/// How much a boat speeds up each tick while its passenger only turns.
+const TURN_ACCELERATION: f64 = 0.005;
+/// The horizontal speed, in blocks per tick, above which a boat crashing into
+/// a block breaks.
+const BREAK_SPEED: f64 = 0.2;
+/// The damage at which a boat breaks.
+const BREAK_DAMAGE: f32 = 40.0;
+/// How far above the boat's bottom its passenger's feet are: the boat's
+/// passenger attachment minus the player's vehicle attachment.
+pub const SEAT_HEIGHT: f64 = 0.5625 - 0.6;
+
+/// The woods boats are made of. Bamboo makes a raft instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WoodType {
+    /// An oak boat.
+    Oak,
+    /// A spruce boat.
+    Spruce,
+    /// A birch boat.
+    Birch,
+    /// A jungle boat.
+    Jungle,
+    /// An acacia boat.
+    Acacia,
+    /// A cherry boat.
+    Cherry,
+    /// A dark oak boat.
+    DarkOak,
+    /// A pale oak boat.
+    PaleOak,
+    /// A mangrove boat.
+    Mangrove,
+    /// A bamboo raft.
+    Bamboo,
+}
+
+impl WoodType {
+    /// Every wood type, in the order vanilla registers their boats.
+    pub const ALL: [Self; 10] = [
+        Self::Oak,
+        Self::Spruce,
+        Self::Birch,
+        Self::Jungle,
+        Self::Acacia,
+        Self::Cherry,
+        Self::DarkOak,
+        Self::PaleOak,
+        Self::Mangrove,
+        Self::Bamboo,
+    ];
+
+    /// Returns the entity type of boats of this wood.
+    #[must_use]
+    pub const fn entity_type(self) -> EntityTypeRef {
+        match self {
+            Self::Oak => vanilla_entities::OAK_BOAT,
+            Self::Spruce => vanilla_entities::SPRUCE_BOAT,
+            Self::Birch => vanilla_entities::BIRCH_BOAT,
+            Self::Jungle => vanilla_entities::JUNGLE_BOAT,
+            Self::Acacia => vanilla_entities::ACACIA_BOAT,
+            Self::Cherry => vanilla_entities::CHERRY_BOAT,
+            Self::DarkOak => vanilla_entities::DARK_OAK_BOAT,
+            Self::PaleOak => vanilla_entities::PALE_OAK_BOAT,
+            Self::Mangrove => vanilla_entities::MANGROVE_BOAT,
+            Self::Bamboo => vanilla_entities::BAMBOO_RAFT,
+        }
+    }
+
+    /// Returns the item that places boats of this wood.
+    #[must_use]
+    pub fn item(self) -> ItemRef {
+        let items = &vanilla_items::ITEMS;
+        match self {
+            Self::Oak => &items.oak_boat,
+            Self::Spruce => &items.spruce_boat,
+            Self::Birch => &items.birch_boat,
+            Self::Jungle => &items.jungle_boat,
+            Self::Acacia => &items.acacia_boat,
+            Self::Cherry => &items.cherry_boat,
+            Self::DarkOak => &items.dark_oak_boat,
+            Self::PaleOak => &items.pale_oak_boat,
+            Self::Mangrove => &items.mangrove_boat,
+            Self::Bamboo => &items.bamboo_raft,
+        }
+    }
+
+    /// Returns the planks boats of this wood are made of.
+    #[must_use]
+    pub fn planks(self) -> ItemRef {
+        let items = &vanilla_items::ITEMS;
+        match self {
+            Self::Oak => &items.oak_planks,
+            Self::Spruce => &items.spruce_planks,
+            Self::Birch => &items.birch_planks,
+            Self::Jungle => &items.jungle_planks,
+            Self::Acacia => &items.acacia_planks,
+            Self::Cherry => &items.cherry_planks,
+            Self::DarkOak => &items.dark_oak_planks,
+            Self::PaleOak => &items.pale_oak_planks,
+            Self::Mangrove => &items.mangrove_planks,
+            Self::Bamboo => &items.bamboo_planks,
+        }
+    }
+}
+
+/// Where a boat is, which decides how it floats and how much speed it keeps.
+///
+/// Vanilla: `AbstractBoat.Status`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BoatStatus {
+    /// Floating on water that reaches up to `surface`.
+    InWater { surface: f64 },
+    /// Sunk below the surface.
+    UnderWater,
+    /// Resting on blocks, sliding along with their `friction`.
+    OnLand { friction: f64 },
+    /// Neither touching water nor blocks.
+    InAir,
+}
+
+/// A boat a player can ride and steer, floating on water and sliding over
+/// ice.
+///
+/// Vanilla: `Boat`
+pub struct BoatEntity {
+    /// The state every entity shares, like the id and custom name.
+    pub base: GenericEntity,
+    /// The wood the boat is made of.
+    pub wood_type: WoodType,
+    physics: EntityPhysicsState,
+    /// The entity data of every boat type is laid out the same way.
+    entity_data: OakBoatEntityData,
+    input: VehicleInput,
+    /// How fast the boat turns, in degrees per tick.
+    turn_speed: f32,
+}
+
+impl BoatEntity {
+    /// Creates a boat of `wood_type` at rest at `position`, facing `yaw`.
+    #[must_use]
+    pub fn new(id: i32, wood_type: WoodType, position: Vector3<f64>, yaw: f32) -> Self {
+        let entity_type = wood_type.entity_type();
+        let base = GenericEntity::new(id, entity_type, position);
+        base.rotation.store((yaw, 0.0));
+        Self {
+            base,
+            wood_type,
+            physics: EntityPhysicsState::new(position, entity_type),
+            entity_data: OakBoatEntityData::new(),
+            input: VehicleInput::default(),
+            turn_speed: 0.0,
+        }
+    }
+
+    /// Applies the shared entity tags and the motion of `nbt`. The wood comes
+    /// from the entity type, so it isn't saved.
+    ///
+    /// Vanilla: `AbstractBoat.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(velocity) = entity::motion_from_nbt(nbt) {
+            self.physics.velocity = velocity;
+        }
+    }
+
+    /// Returns the position of the boat.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.physics.position
+    }
+
+    /// Returns where the boat's passenger stands.
+    #[must_use]
+    pub const fn seat_position(&self) -> Vector3<f64> {
+        let position = self.position();
+        Vector3::new(position.x, position.y + SEAT_HEIGHT, position.z)
+    }
+
+    /// Returns the box the boat takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.physics.bounding_box
+    }
+
+    /// The packet that adds this boat to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the boat is added.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Sets the keys the passenger holds down. The paddles follow them.
+    pub fn set_input(&mut self, input: VehicleInput) {
+        self.input = input;
+        // Each paddle rows while going forward or turning towards the other side
+        self.entity_data
+            .id_paddle_left
+            .set((input.right && !input.left) || input.forward);
+        self.entity_data
+            .id_paddle_right
+            .set((input.left && !input.right) || input.forward);
+    }
+
+    /// Returns the items the boat breaks into when it crashes.
+    #[must_use]
+    pub fn crash_drops(&self) -> Vec<ItemStack> {
+        vec![
+            ItemStack::with_count(self.wood_type.planks(), 3),
+            ItemStack::with_count(&vanilla_items::ITEMS.stick, 2),
+        ]
+    }
+
+    /// Hits the boat for `damage`. Returns true once it took enough to
+    /// break.
+    ///
+    /// Vanilla: `VehicleEntity.hurtServer`
+    pub fn hurt(&mut self, damage: f32) -> bool {
+        let hurt_direction = -*self.entity_data.id_hurtdir.get();
+        self.entity_data.id_hurtdir.set(hurt_direction);
+        self.entity_data.id_hurt.set(10);
+        let total = *self.entity_data.id_damage.get() + damage * 10.0;
+        self.entity_data.id_damage.set(total);
+        total > BREAK_DAMAGE
+    }
+
+    /// Advances the boat by a tick, floating it, steering it by its
+    /// passenger's input and moving it, and syncs where it ended up.
+    /// Returns true if it crashed into a block fast enough to break.
+    ///
+    /// Vanilla: `AbstractBoat.tick`
+    pub fn tick(&mut self, world: &World) -> bool {
+        // TODO: Clients move the boat they ride themselves. Accept their
+        // `SMoveVehicle` once it is read, instead of simulating it here.
+        let hurt_time = *self.entity_data.id_hurt.get();
+        if hurt_time > 0 {
+            self.entity_data.id_hurt.set(hurt_time - 1);
+        }
+        let damage = *self.entity_data.id_damage.get();
+        if damage > 0.0 {
+            self.entity_data.id_damage.set((damage - 1.0).max(0.0));
+        }
+
+        let status = self.status(world);
+        self.float(status);
+        if !self.base.passengers.is_empty() {
+            self.control();
+        } else if self.input != VehicleInput::default() {
+            self.set_input(VehicleInput::default());
+        }
+
+        let old_position = self.position();
+        let old_yaw = self.base.rotation.load().0;
+        let speed = self.physics.velocity.horizontal_length();
+        self.move_by(world);
+        if self.physics.horizontal_collision && speed > BREAK_SPEED {
+            return true;
+        }
+
+        let position = self.position();
+        let (yaw, pitch) = self.base.rotation.load();
+        if position == old_position && (yaw - old_yaw).abs() < f32::EPSILON {
+            return false;
+        }
+        *self.base.position.lock() = position;
+        let velocity = self.physics.velocity;
+        world.broadcast_to_trackers(
+            position,
+            CEntityPositionSync {
+                entity_id: self.base.id,
+                x: position.x,
+                y: position.y,
+                z: position.z,
+                velocity_x: velocity.x,
+                velocity_y: velocity.y,
+                velocity_z: velocity.z,
+                yaw,
+                pitch,
+                on_ground: self.physics.on_ground,
+            },
+        );
+        false
+    }
+
+    /// Works out whether the boat floats, sinks, rests on blocks or falls.
+    ///
+    /// Vanilla: `AbstractBoat.getStatus`
+    fn status(&self, world: &World) -> BoatStatus {
+        if self.is_under_water(world) {
+            return BoatStatus::UnderWater;
+        }
+        if let Some(surface) = self.water_surface(world) {
+            return BoatStatus::InWater { surface };
+        }
+        let friction = self.ground_friction(world);
+        if friction > 0.0 {
+            BoatStatus::OnLand { friction }
+        } else {
+            BoatStatus::InAir
+        }
+    }
+
+    /// Returns true if water covers the top of the boat.
+    ///
+    /// Vanilla: `AbstractBoat.isUnderwater`
+    #[allow(clippy::cast_possible_truncation)]
+    fn is_under_water(&self, world: &World) -> bool {
+        let aabb = self.bounding_box();
+        let top = aabb.max_y;
+        blocks_in(aabb, top.floor() as i32, (top + 0.001).ceil() as i32).any(|pos| {
+            movement::water_height(world, pos)
+                .is_some_and(|height| f64::from(pos.y()) + height >= top)
+        })
+    }
+
+    /// Returns the height of the surface of the water the boat floats in, or
+    /// `None` if its bottom is out of the water.
+    ///
+    /// Vanilla: `AbstractBoat.checkInWater`
+    #[allow(clippy::cast_possible_truncation)]
+    fn water_surface(&self, world: &World) -> Option<f64> {
+        let aabb = self.bounding_box();
+        let bottom = aabb.min_y;
+        let mut surface: Option<f64> = None;
+        for pos in blocks_in(aabb, bottom.floor() as i32, (bottom + 0.001).ceil() as i32) {
+            if let Some(height) = movement::water_height(world, pos) {
+                let height = f64::from(pos.y()) + height;
+                surface = Some(surface.map_or(height, |surface| surface.max(height)));
+            }
+        }
+        surface.filter(|&surface| bottom < surface)
+    }
+
+    /// Returns the average friction of the blocks right below the boat, or 0
+    /// if it doesn't rest on any.
+    ///
+    /// Vanilla: `AbstractBoat.getGroundFriction`
+    #[allow(clippy::cast_possible_truncation)]
+    fn ground_friction(&self, world: &World) -> f64 {
+        let aabb = self.bounding_box();
+        let y = (aabb.min_y - 0.001).floor() as i32;
+        let (total, count) = blocks_in(aabb, y, y + 1)
+            .map(|pos| world.get_block_state(&pos))
+            .filter(|state| !state.get_collision_shape().is_empty())
+            .fold((0.0, 0), |(total, count), state| {
+                (
+                    total + f64::from(state.get_block().config.friction),
+                    count + 1,
+                )
+            });
+        if count == 0 {
+            0.0
+        } else {
+            total / f64::from(count)
+        }
+    }
+
+    /// Slows the boat down by drag or the friction of the ground, pulls it
+    /// down and lifts it back up to the water's surface.
+    ///
+    /// Vanilla: `AbstractBoat.floatBoat`
+    #[allow(clippy::cast_possible_truncation)]
+    fn float(&mut self, status: BoatStatus) {
+        let gravity = if self.base.no_gravity { 0.0 } else { -GRAVITY };
+        let mut buoyancy = 0.0;
+        let drag = match status {
+            BoatStatus::InWater { surface } => {
+                buoyancy = (surface - self.position().y)
+                    / f64::from(self.base.entity_type.dimensions.height);
+                WATER_DRAG
+            }
+            BoatStatus::UnderWater => {
+                buoyancy = 0.01;
+                UNDER_WATER_DRAG
+            }
+            BoatStatus::InAir => WATER_DRAG,
+            // Players weigh boats down, so they stop quickly on land
+            BoatStatus::OnLand { friction } if !self.base.passengers.is_empty() => friction / 2.0,
+            BoatStatus::OnLand { friction } => friction,
+        };
+
+        let velocity = self.physics.velocity;
+        self.physics.velocity =
+            Vector3::new(velocity.x * drag, velocity.y + gravity, velocity.z * drag);
+        self.turn_speed *= drag as f32;
+        if buoyancy > 0.0 {
+            let velocity = self.physics.velocity;
+            self.physics.velocity.y = (velocity.y + buoyancy * (GRAVITY / 0.65)) * 0.75;
+        }
+    }
+
+    /// Turns and paddles the boat the way its passenger steers.
+    ///
+    /// Vanilla: `AbstractBoat.controlBoat`
+    fn control(&mut self) {
+        let input = self.input;
+        if input.left {
+            self.turn_speed -= 1.0;
+        }
+        if input.right {
+            self.turn_speed += 1.0;
+        }
+
+        let mut acceleration = 0.0;
+        if input.left != input.right && !input.forward && !input.backward {
+            acceleration += TURN_ACCELERATION;
+        }
+        let (yaw, pitch) = self.base.rotation.load();
+        let yaw = yaw + self.turn_speed;
+        self.base.rotation.store((yaw, pitch));
+
+        if input.forward {
+            acceleration += FORWARD_ACCELERATION;
+        }
+        if input.backward {
+            acceleration -= BACKWARD_ACCELERATION;
+        }
+        let yaw = f64::from(yaw).to_radians();
+        self.physics.velocity.x += (-yaw).sin() * acceleration;
+        self.physics.velocity.z += yaw.cos() * acceleration;
+    }
+
+    /// Moves the boat by its velocity, stopping at blocks in the way.
+    fn move_by(&mut self, world: &World) {
+        let delta = self.physics.velocity;
+        let result = move_entity(
+            &self.physics,
+            delta,
+            MoverType::SelfMovement,
+            &WorldCollisionProvider::new(world),
+        );
+        self.physics.set_position(result.final_position);
+        self.physics.on_ground = result.on_ground;
+        self.physics.horizontal_collision = result.horizontal_collision;
+        self.physics.vertical_collision = result.vertical_collision;
+
+        let blocked = |delta: f64, moved: f64| (delta - moved).abs() > 1.0e-7;
+        if blocked(delta.x, result.actual_movement.x) {
+            self.physics.velocity.x = 0.0;
+        }
+        if blocked(delta.y, result.actual_movement.y) {
+            self.physics.velocity.y = 0.0;
+        }
+        if blocked(delta.z, result.actual_movement.z) {
+            self.physics.velocity.z = 0.0;
+        }
+    }
+}
+
+impl Entity for BoatEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for BoatEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+        if !self.base.passengers.is_empty() {
+            pairing.send(world.passengers_packet(self.base.id, &self.base.passengers));
+        }
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.base.write_nbt(&mut nbt);
+        entity::write_motion(&mut nbt, self.physics.velocity);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.base.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    /// Breaks the boat into its item once the blast hurt it enough.
+    fn explosion_hit(
+        &mut self,
+        _world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.physics.velocity += impulse;
+        self.hurt(damage).then(|| Remains {
+            pos: self.position().to_block_pos(),
+            drops: vec![ItemStack::new(self.wood_type.item())],
+            passengers: mem::take(&mut self.base.passengers),
+        })
+    }
+}
+
+/// Returns the blocks from `min_y` up to but not including `max_y` below the
+/// horizontal extent of `aabb`.
+#[allow(clippy::cast_possible_truncation)]
+fn blocks_in(aabb: AABBd, min_y: i32, max_y: i32) -> impl Iterator<Item = BlockPos> {
+    let (min_x, max_x) = (aabb.min_x.floor() as i32, aabb.max_x.ceil() as i32);
+    let (min_z, max_z) = (aabb.min_z.floor() as i32, aabb.max_z.ceil() as i32);
+    (min_x..max_x).flat_map(move |x| {
+        (min_y..max_y).flat_map(move |y| (min_z..max_z).map(move |z| BlockPos::new(x, y, z)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_in_covers_the_horizontal_extent() {
+        let aabb = AABBd::new(0.2, 64.0, 0.2, 1.6, 64.5, 1.6);
+        let blocks: Vec<BlockPos> = blocks_in(aabb, 63, 64).collect();
+        assert_eq!(blocks.len(), 4);
+        assert!(blocks.contains(&BlockPos::new(1, 63, 1)));
+        assert!(blocks.iter().all(|pos| pos.y() == 63));
+    }
+}
@@ -0,0 +1,406 @@
+//! Creepers that sneak up on players and blow up next to them.
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, SoundSource};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::items::ItemRef;
+use steel_registry::vanilla_entity_data::CreeperEntityData;
+use steel_registry::{sound_events, vanilla_entities, vanilla_items};
+use steel_utils::math::Vector3;
+use uuid::Uuid;
+
+use crate::entity::ai::sight;
+use crate::entity::mob::{self, MobBase};
+use crate::entity::storage::EntityPairing;
+use crate::entity::{AttackableMob, Entity, GenericEntity, Remains, StoredEntity};
+use crate::player::Player;
+use crate::world::World;
+use crate::world::explosion::{Explosion, ExplosionSource};
+
+/// The health of a creeper.
+const MAX_HEALTH: f32 = 20.0;
+/// How far above its feet a creeper's eyes are.
+const EYE_HEIGHT: f64 = 1.445;
+/// How far creepers walk in a tick.
+const WALK_SPEED: f64 = 0.16;
+
+/// How far away creepers notice players.
+const FOLLOW_RANGE: f64 = 16.0;
+/// How many ticks pass between finding new paths to the target.
+const PATH_INTERVAL: u32 = 20;
+/// How close a target gets before the creeper lights its fuse.
+const IGNITE_DISTANCE: f64 = 3.0;
+/// How far a target gets away before a lit creeper defuses.
+const DEFUSE_DISTANCE: f64 = 7.0;
+/// How many ticks a lit creeper takes to blow up.
+///
+/// Vanilla: `Creeper.maxSwell`
+pub const MAX_FUSE: i32 = 30;
+/// How many ticks a creeper can't be hurt again after it was hurt.
+const HURT_COOLDOWN: u32 = 10;
+
+/// The experience a creeper drops when a player kills it.
+///
+/// Vanilla: `Monster.XP_REWARD_MEDIUM`
+pub const EXPERIENCE_REWARD: i32 = 5;
+
+/// Whether a creeper's fuse is burning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreeperState {
+    /// The fuse is out.
+    Idle,
+    /// The fuse burns down towards the explosion.
+    Fusing,
+    /// The target got away, so the fuse burns back up.
+    Defusing,
+}
+
+impl CreeperState {
+    /// The swell direction clients show the creeper growing or shrinking by.
+    const fn swell_dir(self) -> i32 {
+        match self {
+            Self::Fusing => 1,
+            Self::Idle | Self::Defusing => -1,
+        }
+    }
+}
+
+/// A creeper, walking up to the nearest player to blow up next to them.
+///
+/// Vanilla: `Creeper`
+pub struct CreeperEntity {
+    /// The state every entity shares, like the id and custom name.
+    pub base: GenericEntity,
+    /// The player the creeper goes after, if any.
+    pub target: Option<Uuid>,
+    mob: MobBase,
+    entity_data: CreeperEntityData,
+    /// Whether the fuse is burning.
+    state: CreeperState,
+    /// How many ticks of the fuse burnt, blowing up at [`MAX_FUSE`].
+    fuse_ticks: i32,
+    /// Ticks until the creeper can be hurt again.
+    hurt_cooldown: u32,
+}
+
+impl CreeperEntity {
+    /// Creates an uncharged creeper at `position`.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        let mut entity_data = CreeperEntityData::new();
+        entity_data.health.set(MAX_HEALTH);
+
+        Self {
+            base: GenericEntity::new(id, vanilla_entities::CREEPER, position),
+            target: None,
+            mob: MobBase::new(position, vanilla_entities::CREEPER),
+            entity_data,
+            state: CreeperState::Idle,
+            fuse_ticks: 0,
+            hurt_cooldown: 0,
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the health and whether it is charged.
+    ///
+    /// Vanilla: `Creeper.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(health) = nbt.float("Health") {
+            self.entity_data.health.set(health.clamp(0.0, MAX_HEALTH));
+        }
+        if let Some(powered) = nbt.byte("powered") {
+            self.entity_data.is_powered.set(powered != 0);
+        }
+    }
+
+    /// Writes the health and whether the creeper is charged into `nbt`.
+    ///
+    /// Vanilla: `Creeper.addAdditionalSaveData`
+    pub fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("Health", *self.entity_data.health.get());
+        if self.is_powered() {
+            nbt.insert("powered", 1i8);
+        }
+    }
+
+    /// Returns the position of the creeper.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.mob.position()
+    }
+
+    /// Returns the box the creeper takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.mob.bounding_box()
+    }
+
+    /// Returns whether the fuse is burning.
+    #[must_use]
+    pub const fn state(&self) -> CreeperState {
+        self.state
+    }
+
+    /// Returns true if lightning charged the creeper, doubling its blast.
+    #[must_use]
+    pub fn is_powered(&self) -> bool {
+        *self.entity_data.is_powered.get()
+    }
+
+    /// Charges the creeper, like lightning striking it does.
+    ///
+    /// Vanilla: `Creeper.thunderHit`
+    pub fn charge(&mut self) {
+        self.entity_data.is_powered.set(true);
+    }
+
+    /// The packet that adds this creeper to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the creeper is added.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Returns true once the creeper has no health left.
+    #[must_use]
+    pub fn is_dead(&self) -> bool {
+        *self.entity_data.health.get() <= 0.0
+    }
+
+    /// Hurts the creeper by `amount`. Returns false if it was hurt too
+    /// recently to be hurt again.
+    ///
+    /// Vanilla: `LivingEntity.hurtServer`
+    pub fn hurt(&mut self, amount: f32) -> bool {
+        if self.hurt_cooldown > 0 || self.is_dead() {
+            return false;
+        }
+        self.hurt_cooldown = HURT_COOLDOWN;
+        let health = *self.entity_data.health.get();
+        self.entity_data.health.set((health - amount).max(0.0));
+        true
+    }
+
+    /// Advances the creeper by a tick: it walks up to its target, lights its
+    /// fuse next to it and defuses once the target gets away. Returns the
+    /// explosion once the fuse burnt down, after which the creeper is gone.
+    ///
+    /// Vanilla: `Creeper.tick` and `SwellGoal`
+    // TODO: Run away from cats once there are cats
+    pub fn tick(&mut self, world: &World) -> Option<Explosion> {
+        self.hurt_cooldown = self.hurt_cooldown.saturating_sub(1);
+
+        let target = mob::find_target(world, self.position(), self.target, FOLLOW_RANGE);
+        self.target = target.as_ref().map(|player| player.gameprofile.id);
+        let position = self.position();
+        let mut sees_target_close = false;
+        match &target {
+            Some(player) => {
+                let target_pos = *player.position.lock();
+                let distance = target_pos.sub(&position).length();
+                let eyes = Vector3::new(position.x, position.y + EYE_HEIGHT, position.z);
+                let target_eyes = Vector3::new(
+                    target_pos.x,
+                    target_pos.y + player.eye_height(),
+                    target_pos.z,
+                );
+                let can_see = sight::has_line_of_sight(world, eyes, target_eyes);
+                let reach = if self.state == CreeperState::Fusing {
+                    DEFUSE_DISTANCE
+                } else {
+                    IGNITE_DISTANCE
+                };
+                sees_target_close = can_see && distance < reach;
+                if sees_target_close {
+                    // A creeper stands still while its fuse burns
+                    self.mob.stop();
+                } else {
+                    self.mob
+                        .move_to(world, target_pos.to_block_pos(), PATH_INTERVAL);
+                }
+            }
+            None => self.mob.stop(),
+        }
+
+        if sees_target_close {
+            if self.fuse_ticks == 0 {
+                world.play_sound(
+                    sound_events::ENTITY_CREEPER_PRIMED,
+                    SoundSource::Hostile,
+                    position.to_block_pos(),
+                    1.0,
+                    0.5,
+                    None,
+                );
+            }
+            self.state = CreeperState::Fusing;
+        } else if self.fuse_ticks > 0 {
+            self.state = CreeperState::Defusing;
+        } else {
+            self.state = CreeperState::Idle;
+        }
+        self.entity_data.swell_dir.set(self.state.swell_dir());
+
+        self.fuse_ticks = match self.state {
+            CreeperState::Fusing => self.fuse_ticks + 1,
+            CreeperState::Idle | CreeperState::Defusing => (self.fuse_ticks - 1).max(0),
+        };
+        if self.fuse_ticks >= MAX_FUSE {
+            let source = ExplosionSource::Creeper {
+                charged: self.is_powered(),
+            };
+            return Some(Explosion::new(position, source.default_power(), source));
+        }
+
+        self.mob.walk(&self.base, world, WALK_SPEED);
+        None
+    }
+}
+
+impl Entity for CreeperEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for CreeperEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.base.write_nbt(&mut nbt);
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.base.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    fn explosion_hit(
+        &mut self,
+        world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.hurt_by_explosion(world, damage);
+        self.mob.push(impulse);
+        None
+    }
+}
+
+impl AttackableMob for CreeperEntity {
+    fn take_hit(&mut self, amount: f32) -> Option<i32> {
+        self.hurt(amount)
+            .then_some(sound_events::ENTITY_CREEPER_HURT)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.is_dead()
+    }
+
+    fn retaliate(&mut self, _world: &World, player: &Player) {
+        self.target = Some(player.gameprofile.id);
+    }
+
+    fn death_sound(&self) -> i32 {
+        sound_events::ENTITY_CREEPER_DEATH
+    }
+
+    fn experience_reward(&self) -> i32 {
+        EXPERIENCE_REWARD
+    }
+
+    fn death_drops(&self) -> Vec<ItemStack> {
+        death_drops(false)
+    }
+}
+
+/// The music discs creepers drop when a skeleton shoots them.
+///
+/// Vanilla: `#creeper_drop_music_discs`
+fn music_discs() -> [ItemRef; 12] {
+    let items = &vanilla_items::ITEMS;
+    [
+        &items.music_disc_13,
+        &items.music_disc_cat,
+        &items.music_disc_blocks,
+        &items.music_disc_chirp,
+        &items.music_disc_far,
+        &items.music_disc_mall,
+        &items.music_disc_mellohi,
+        &items.music_disc_stal,
+        &items.music_disc_strad,
+        &items.music_disc_ward,
+        &items.music_disc_11,
+        &items.music_disc_wait,
+    ]
+}
+
+/// The items a creeper drops when it dies: up to two gunpowder, and a random
+/// music disc if a skeleton killed it.
+///
+/// Vanilla: `entities/creeper` loot table
+// TODO: Pass true once arrows hit mobs, so skeletons can kill creepers
+#[must_use]
+pub fn death_drops(killed_by_skeleton: bool) -> Vec<ItemStack> {
+    let mut drops = Vec::new();
+    let gunpowder = rand::random_range(0..=2);
+    if gunpowder > 0 {
+        drops.push(ItemStack::with_count(
+            &vanilla_items::ITEMS.gunpowder,
+            gunpowder,
+        ));
+    }
+    if killed_by_skeleton {
+        let discs = music_discs();
+        drops.push(ItemStack::new(discs[rand::random_range(0..discs.len())]));
+    }
+    drops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lit_creepers_grow_and_defused_ones_shrink() {
+        assert_eq!(CreeperState::Fusing.swell_dir(), 1);
+        assert_eq!(CreeperState::Defusing.swell_dir(), -1);
+        assert_eq!(CreeperState::Idle.swell_dir(), -1);
+    }
+}
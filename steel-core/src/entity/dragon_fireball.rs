@@ -0,0 +1,211 @@
+//! Fireballs the ender dragon spits at players, bursting into dragon's
+//! breath where they land.
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, CEntityPositionSync, SoundSource};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::{REGISTRY, level_events, sound_events, vanilla_entities};
+use steel_utils::math::Vector3;
+use steel_utils::types::GameType;
+use uuid::Uuid;
+
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, StoredEntity};
+use crate::world::World;
+
+/// How fast dragon fireballs fly, in blocks per tick.
+pub const SPEED: f64 = 1.0;
+/// How far around its landing spot the breath of a fireball hurts players.
+const BREATH_RADIUS: f64 = 3.0;
+/// How much the breath hurts players caught in it.
+///
+/// Vanilla: the instant damage of the breath cloud
+const BREATH_DAMAGE: f32 = 6.0;
+/// How many ticks a fireball flies before it burns out.
+const MAX_LIFETIME: u32 = 200;
+
+/// A fireball spat by the ender dragon, flying straight until it hits a
+/// player or a block.
+///
+/// Vanilla: `DragonFireball`
+pub struct DragonFireballEntity {
+    /// The network id of the entity.
+    pub id: i32,
+    /// The persistent id of the entity.
+    pub uuid: Uuid,
+    position: Vector3<f64>,
+    velocity: Vector3<f64>,
+    /// Ticks the fireball has flown.
+    age: u32,
+}
+
+impl DragonFireballEntity {
+    /// Creates a fireball at `position` flying with `velocity`.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>, velocity: Vector3<f64>) -> Self {
+        Self {
+            id,
+            uuid: Uuid::new_v4(),
+            position,
+            velocity,
+            age: 0,
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the way the fireball flies. Tags that
+    /// aren't set keep their current value, and the position is never loaded.
+    ///
+    /// Vanilla: `AbstractHurtingProjectile.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(uuid) = entity::uuid_from_nbt(nbt) {
+            self.uuid = uuid;
+        }
+        if let Some(velocity) = entity::motion_from_nbt(nbt) {
+            self.velocity = velocity;
+        }
+    }
+
+    /// Returns the position of the fireball.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.position
+    }
+
+    /// The packet that adds this fireball to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        CAddEntity::new(
+            self.id,
+            self.uuid,
+            *REGISTRY
+                .entity_types
+                .get_id(vanilla_entities::DRAGON_FIREBALL) as i32,
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            0.0,
+            0.0,
+        )
+    }
+
+    /// Advances the fireball by a tick, flying it forward until it hits a
+    /// player or a block. Returns false once it should be removed.
+    ///
+    /// Vanilla: `AbstractHurtingProjectile.tick`
+    pub fn tick(&mut self, world: &World) -> bool {
+        self.age += 1;
+        self.position += self.velocity;
+
+        let half_width = f64::from(vanilla_entities::DRAGON_FIREBALL.dimensions.width) / 2.0;
+        let height = f64::from(vanilla_entities::DRAGON_FIREBALL.dimensions.height);
+        let bounding_box = AABBd::entity_box(
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            half_width,
+            height,
+        );
+        let pos = self.position.to_block_pos();
+        let hit_block = !world.get_block_state(&pos).get_collision_shape().is_empty();
+        let hit_player = hits_player(world, &bounding_box);
+        if hit_block || hit_player {
+            self.burst(world);
+            return false;
+        }
+        if self.age >= MAX_LIFETIME || !world.is_in_valid_bounds(&pos) {
+            return false;
+        }
+
+        world.broadcast_to_trackers(
+            self.position,
+            CEntityPositionSync {
+                entity_id: self.id,
+                x: self.position.x,
+                y: self.position.y,
+                z: self.position.z,
+                velocity_x: self.velocity.x,
+                velocity_y: self.velocity.y,
+                velocity_z: self.velocity.z,
+                yaw: 0.0,
+                pitch: 0.0,
+                on_ground: false,
+            },
+        );
+        true
+    }
+
+    /// Bursts into dragon's breath, hurting the players around it.
+    ///
+    /// Vanilla: `DragonFireball.onHit`
+    // TODO: Leave a lingering cloud of breath once area effect clouds exist
+    fn burst(&self, world: &World) {
+        let pos = self.position.to_block_pos();
+        world.level_event(level_events::PARTICLES_DRAGON_FIREBALL_SPLASH, pos, 0, None);
+        world.play_sound(
+            sound_events::ENTITY_DRAGON_FIREBALL_EXPLODE,
+            SoundSource::Hostile,
+            pos,
+            1.0,
+            1.0,
+            None,
+        );
+        breathe_on_players(world, self.position, BREATH_RADIUS, BREATH_DAMAGE);
+    }
+}
+
+impl Entity for DragonFireballEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
+impl StoredEntity for DragonFireballEntity {
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        vanilla_entities::DRAGON_FIREBALL
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.send(self.add_entity_packet());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        entity::write_position(&mut nbt, self.position);
+        entity::write_uuid(&mut nbt, self.uuid);
+        entity::write_motion(&mut nbt, self.velocity);
+        Some(nbt)
+    }
+}
+
+/// Returns true if `bounding_box` touches a player that can be hit.
+fn hits_player(world: &World, bounding_box: &AABBd) -> bool {
+    let mut hit = false;
+    world.players.iter_players(|_, player| {
+        hit = player.game_mode.load() != GameType::Spectator
+            && player.bounding_box().intersects(bounding_box);
+        !hit
+    });
+    hit
+}
+
+/// Hurts the players within `radius` of `center` by `damage`, like the
+/// dragon's breath does.
+pub fn breathe_on_players(world: &World, center: Vector3<f64>, radius: f64, damage: f32) {
+    world.players.iter_players(|_, player| {
+        let distance = player.position.lock().sub(&center).length_squared();
+        if distance <= radius * radius && player.game_mode.load() != GameType::Spectator {
+            player.take_damage(damage);
+        }
+        true
+    });
+}
@@ -0,0 +1,734 @@
+//! The ender dragon, circling the fountain in the End, diving at players and
+//! spitting fireballs at them.
+
+use std::f64::consts::TAU;
+use std::ptr;
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, CEntityPositionSync};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::vanilla_entity_data::EnderDragonEntityData;
+use steel_registry::{REGISTRY, level_events, vanilla_blocks, vanilla_entities};
+use steel_utils::BlockPos;
+use steel_utils::math::Vector3;
+use steel_utils::types::UpdateFlags;
+use uuid::Uuid;
+
+use crate::entity::ai::sight;
+use crate::entity::dragon_fireball::{self, DragonFireballEntity};
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, GenericEntity, Remains, StoredEntity, mob};
+use crate::world::World;
+
+/// The health of the dragon.
+const MAX_HEALTH: f32 = 200.0;
+/// How many parts the dragon's body is split into for hitting it.
+pub const PART_COUNT: usize = 8;
+/// The width and height of each part: the head, neck, body, three tail
+/// pieces and two wings.
+///
+/// Vanilla: `EnderDragon.subEntities`
+const PARTS: [(f64, f64); PART_COUNT] = [
+    (1.0, 1.0),
+    (3.0, 3.0),
+    (5.0, 3.0),
+    (2.0, 2.0),
+    (2.0, 2.0),
+    (2.0, 2.0),
+    (4.0, 2.0),
+    (4.0, 2.0),
+];
+/// Where each part sits in level flight: how far in front of the dragon,
+/// to its right and above its feet.
+const PART_OFFSETS: [(f64, f64, f64); PART_COUNT] = [
+    (6.5, 0.0, 1.5),
+    (5.5, 0.0, 1.0),
+    (0.5, 0.0, 0.0),
+    (-3.5, 0.0, 0.0),
+    (-5.5, 0.0, 0.0),
+    (-7.5, 0.0, 0.0),
+    (0.0, 4.5, 2.0),
+    (0.0, -4.5, 2.0),
+];
+/// The part that takes full damage.
+const HEAD: usize = 0;
+/// The part hits on the dragon itself land on.
+const BODY: usize = 2;
+
+/// How far the dragon flies in a tick.
+const FLY_SPEED: f64 = 0.6;
+/// How many degrees the dragon turns at most in a tick.
+const TURN_SPEED: f32 = 10.0;
+/// How far from the fountain the dragon circles.
+const CIRCLE_RADIUS: f64 = 60.0;
+/// How many points the dragon flies between while circling.
+const CIRCLE_NODES: usize = 12;
+/// How high above the fountain the dragon circles.
+const CIRCLE_HEIGHT: f64 = 20.0;
+/// How close the dragon gets to a point before heading for the next.
+const NODE_REACHED: f64 = 10.0;
+/// How far away the dragon notices players to dive at.
+const TARGET_RANGE: f64 = 150.0;
+/// How far above a player the dragon dives to.
+const STRAFE_HEIGHT: f64 = 8.0;
+/// How far away the dragon spits fireballs at players.
+const FIREBALL_RANGE: f64 = 64.0;
+/// How many ticks the dragon looks at a player before spitting.
+const AIM_TICKS: u32 = 5;
+/// How many ticks the dragon chases a player before giving up.
+const MAX_STRAFE_TICKS: u32 = 200;
+/// How close to the fountain the dragon gets before it sits down.
+const LANDING_DISTANCE: f64 = 1.5;
+/// How many ticks the dragon sits on the fountain.
+const SITTING_TICKS: u32 = 200;
+/// How many ticks pass between breaths while the dragon sits.
+const BREATH_INTERVAL: u32 = 40;
+/// How far around its head the dragon's breath reaches.
+const BREATH_RADIUS: f64 = 4.0;
+/// How much the dragon's breath hurts.
+const BREATH_DAMAGE: f32 = 3.0;
+/// How close end crystals have to be to heal the dragon.
+const CRYSTAL_RANGE: f64 = 32.0;
+/// How many ticks pass between the crystals healing the dragon.
+const CRYSTAL_HEAL_INTERVAL: u32 = 10;
+/// How many ticks the dragon takes to die.
+const DEATH_TICKS: u32 = 200;
+/// How high the fountain is when its chunk isn't loaded.
+const DEFAULT_FOUNTAIN_Y: i32 = 64;
+/// How many ticks the dragon can't be hurt again after it was hurt.
+const HURT_COOLDOWN: u32 = 10;
+
+/// The experience the dragon drops while it dies.
+///
+/// Vanilla: `EnderDragon.tickDeath`
+pub const EXPERIENCE_REWARD: i32 = 12_000;
+
+/// What the dragon is doing.
+///
+/// Vanilla: `EnderDragonPhase`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DragonPhase {
+    /// Circling high above the fountain.
+    HoldingPattern,
+    /// Diving at a player to spit a fireball at them.
+    Strafing,
+    /// Flying down to sit on the fountain.
+    LandingApproach,
+    /// Sitting on the fountain, breathing fire around it.
+    Sitting,
+    /// Dying after it ran out of health.
+    Dying,
+}
+
+impl DragonPhase {
+    /// The id of the phase that clients know.
+    const fn id(self) -> i32 {
+        match self {
+            Self::HoldingPattern => 0,
+            Self::Strafing => 1,
+            Self::LandingApproach => 2,
+            Self::Sitting => 6,
+            Self::Dying => 9,
+        }
+    }
+
+    /// The phase with id `id`, treating the phases that aren't here as
+    /// circling.
+    const fn from_id(id: i32) -> Self {
+        match id {
+            1 | 8 => Self::Strafing,
+            2 | 3 => Self::LandingApproach,
+            5..=7 => Self::Sitting,
+            9 => Self::Dying,
+            _ => Self::HoldingPattern,
+        }
+    }
+}
+
+/// What a dragon did in a tick that the world has to act on.
+pub enum DragonAction {
+    /// It spat a fireball.
+    Shoot(DragonFireballEntity),
+    /// It dropped this much experience while dying.
+    DropExperience(i32),
+    /// It finished dying, dropping this much experience, and is gone.
+    Die(i32),
+}
+
+/// The ender dragon, circling the fountain and diving at players.
+///
+/// Vanilla: `EnderDragon`
+pub struct EnderDragonEntity {
+    /// The state every entity shares, like the id and custom name. Its parts
+    /// take the network ids right after its own.
+    pub base: GenericEntity,
+    /// The player the dragon dives at, if any.
+    pub target: Option<Uuid>,
+    position: Vector3<f64>,
+    entity_data: EnderDragonEntityData,
+    phase: DragonPhase,
+    /// Ticks since the phase started.
+    phase_ticks: u32,
+    /// The point it circles towards while holding its pattern.
+    node: usize,
+    /// Ticks it has looked at its target while strafing.
+    aim_ticks: u32,
+    /// Ticks the dragon lived, for timing the crystals' healing.
+    age: u32,
+    /// Whether it already flew to the fountain after losing half its health.
+    landed_at_half_health: bool,
+    /// Ticks until the dragon can be hurt again.
+    hurt_cooldown: u32,
+}
+
+impl EnderDragonEntity {
+    /// Creates a dragon circling at `position`. Its parts take the
+    /// [`PART_COUNT`] network ids after `id`, so allocate them together with
+    /// [`entity::next_entity_ids`].
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        let mut entity_data = EnderDragonEntityData::new();
+        entity_data.health.set(MAX_HEALTH);
+        entity_data.phase.set(DragonPhase::HoldingPattern.id());
+
+        Self {
+            base: GenericEntity::new(id, vanilla_entities::ENDER_DRAGON, position),
+            target: None,
+            position,
+            entity_data,
+            phase: DragonPhase::HoldingPattern,
+            phase_ticks: 0,
+            node: 0,
+            aim_ticks: 0,
+            age: 0,
+            landed_at_half_health: false,
+            hurt_cooldown: 0,
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the health and phase.
+    ///
+    /// Vanilla: `EnderDragon.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(health) = nbt.float("Health") {
+            self.entity_data.health.set(health.clamp(0.0, MAX_HEALTH));
+        }
+        if let Some(phase) = nbt.int("DragonPhase") {
+            self.set_phase(DragonPhase::from_id(phase));
+        }
+    }
+
+    /// Writes the health and the phase of the dragon into `nbt`.
+    ///
+    /// Vanilla: `EnderDragon.addAdditionalSaveData`
+    pub fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("Health", *self.entity_data.health.get());
+        nbt.insert("DragonPhase", self.phase.id());
+    }
+
+    /// Returns the position of the dragon.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.position
+    }
+
+    /// Returns what the dragon is doing.
+    #[must_use]
+    pub const fn phase(&self) -> DragonPhase {
+        self.phase
+    }
+
+    /// Returns the box around the whole dragon.
+    #[must_use]
+    pub fn bounding_box(&self) -> AABBd {
+        let dimensions = vanilla_entities::ENDER_DRAGON.dimensions;
+        AABBd::entity_box(
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            f64::from(dimensions.width) / 2.0,
+            f64::from(dimensions.height),
+        )
+    }
+
+    /// Returns the index of the part with network id `entity_id`, if it is
+    /// one of this dragon's.
+    #[must_use]
+    pub fn part_index(&self, entity_id: i32) -> Option<usize> {
+        let index = usize::try_from(entity_id - self.base.id - 1).ok()?;
+        (index < PART_COUNT).then_some(index)
+    }
+
+    /// Returns the boxes of the dragon's parts, which players hit instead
+    /// of the dragon as a whole.
+    ///
+    /// Vanilla: `EnderDragon.aiStep`, leaving out how the neck and tail
+    /// sway
+    #[must_use]
+    pub fn part_boxes(&self) -> [AABBd; PART_COUNT] {
+        let (yaw, _) = self.base.rotation.load();
+        let (sin, cos) = f64::from(yaw).to_radians().sin_cos();
+        // The dragon faces the other way than other entities
+        let forward = (sin, -cos);
+        let right = (cos, sin);
+        let mut boxes = [AABBd::entity_box(0.0, 0.0, 0.0, 0.0, 0.0); PART_COUNT];
+        for (index, part_box) in boxes.iter_mut().enumerate() {
+            let (ahead, aside, up) = PART_OFFSETS[index];
+            let (width, height) = PARTS[index];
+            *part_box = AABBd::entity_box(
+                self.position.x + forward.0 * ahead + right.0 * aside,
+                self.position.y + up,
+                self.position.z + forward.1 * ahead + right.1 * aside,
+                width / 2.0,
+                height,
+            );
+        }
+        boxes
+    }
+
+    /// The packet that adds this dragon to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the dragon is added.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Returns true once the dragon has no health left. It keeps dying for a
+    /// while after.
+    #[must_use]
+    pub fn is_dead(&self) -> bool {
+        *self.entity_data.health.get() <= 0.0
+    }
+
+    /// Hurts the dragon by `amount` through its part at `part`, or its body
+    /// if `None`. Only hits on the head deal full damage. Returns false if
+    /// it was hurt too recently to be hurt again.
+    ///
+    /// Vanilla: `EnderDragon.hurt`
+    pub fn hurt(&mut self, part: Option<usize>, amount: f32) -> bool {
+        if self.hurt_cooldown > 0 || self.is_dead() {
+            return false;
+        }
+        self.hurt_cooldown = HURT_COOLDOWN;
+        let health = *self.entity_data.health.get();
+        let health = (health - part_damage(part.unwrap_or(BODY), amount)).max(0.0);
+        self.entity_data.health.set(health);
+
+        if health <= 0.0 {
+            self.set_phase(DragonPhase::Dying);
+        } else if health < MAX_HEALTH / 2.0 && !self.landed_at_half_health {
+            // Hurt badly, the dragon goes down to the fountain
+            self.landed_at_half_health = true;
+            self.set_phase(DragonPhase::LandingApproach);
+        }
+        true
+    }
+
+    /// Advances the dragon by a tick: it circles, dives at players, lands
+    /// on the fountain and heals from nearby end crystals, or dies slowly
+    /// once it ran out of health.
+    ///
+    /// Vanilla: `EnderDragon.aiStep`
+    pub fn tick(&mut self, world: &World) -> Option<DragonAction> {
+        self.hurt_cooldown = self.hurt_cooldown.saturating_sub(1);
+        self.age += 1;
+        self.phase_ticks += 1;
+
+        if self.phase == DragonPhase::Dying {
+            return self.tick_death(world);
+        }
+        self.heal_from_crystals(world);
+
+        let fountain = fountain(world);
+        let action = match self.phase {
+            DragonPhase::HoldingPattern => {
+                self.tick_holding_pattern(world, fountain);
+                None
+            }
+            DragonPhase::Strafing => self.tick_strafing(world),
+            DragonPhase::LandingApproach => {
+                if self.fly_towards(fountain) < LANDING_DISTANCE {
+                    self.set_phase(DragonPhase::Sitting);
+                }
+                None
+            }
+            DragonPhase::Sitting => {
+                self.tick_sitting(world);
+                None
+            }
+            DragonPhase::Dying => unreachable!("dying dragons tick above"),
+        };
+        self.sync_position(world);
+        action
+    }
+
+    /// Circles the fountain, now and then diving at a player or going down
+    /// to sit on it.
+    ///
+    /// Vanilla: `DragonHoldingPatternPhase`
+    fn tick_holding_pattern(&mut self, world: &World, fountain: Vector3<f64>) {
+        if self.fly_towards(node_position(fountain, self.node)) > NODE_REACHED {
+            return;
+        }
+        self.node = (self.node + 1) % CIRCLE_NODES;
+
+        let crystals = count_crystals(world, fountain, f64::INFINITY);
+        if rand::random_range(0..crystals + 3) == 0 {
+            self.set_phase(DragonPhase::LandingApproach);
+        } else if rand::random_bool(0.5)
+            && let Some(player) = mob::find_target(world, self.position, None, TARGET_RANGE)
+        {
+            self.target = Some(player.gameprofile.id);
+            self.set_phase(DragonPhase::Strafing);
+        }
+    }
+
+    /// Dives at the target and spits a fireball at them once it has looked
+    /// at them for a moment.
+    ///
+    /// Vanilla: `DragonStrafePlayerPhase`
+    fn tick_strafing(&mut self, world: &World) -> Option<DragonAction> {
+        let player = self
+            .target
+            .and_then(|uuid| world.players.get_by_uuid(&uuid));
+        let Some(player) = player.filter(|_| self.phase_ticks < MAX_STRAFE_TICKS) else {
+            self.set_phase(DragonPhase::HoldingPattern);
+            return None;
+        };
+
+        let target_pos = *player.position.lock();
+        let above = Vector3::new(target_pos.x, target_pos.y + STRAFE_HEIGHT, target_pos.z);
+        self.fly_towards(above);
+
+        let head = head_center(&self.part_boxes()[HEAD]);
+        let eyes = Vector3::new(
+            target_pos.x,
+            target_pos.y + player.eye_height(),
+            target_pos.z,
+        );
+        let to_target = eyes.sub(&head);
+        let distance = to_target.length();
+        if distance < FIREBALL_RANGE && sight::has_line_of_sight(world, head, eyes) {
+            self.aim_ticks += 1;
+        } else {
+            self.aim_ticks = 0;
+        }
+        if self.aim_ticks < AIM_TICKS || distance < 1.0e-7 {
+            return None;
+        }
+
+        world.level_event(
+            level_events::SOUND_DRAGON_FIREBALL,
+            head.to_block_pos(),
+            0,
+            None,
+        );
+        let velocity = to_target * (dragon_fireball::SPEED / distance);
+        let fireball = DragonFireballEntity::new(entity::next_entity_id(), head, velocity);
+        self.set_phase(DragonPhase::HoldingPattern);
+        Some(DragonAction::Shoot(fireball))
+    }
+
+    /// Sits on the fountain, breathing fire in front of it now and then,
+    /// and takes off again after a while.
+    ///
+    /// Vanilla: `DragonSittingFlamingPhase`
+    fn tick_sitting(&mut self, world: &World) {
+        if self.phase_ticks >= SITTING_TICKS {
+            self.set_phase(DragonPhase::HoldingPattern);
+            return;
+        }
+        if self.phase_ticks.is_multiple_of(BREATH_INTERVAL) {
+            let head = head_center(&self.part_boxes()[HEAD]);
+            world.level_event(
+                level_events::PARTICLES_DRAGON_FIREBALL_SPLASH,
+                head.to_block_pos(),
+                0,
+                None,
+            );
+            dragon_fireball::breathe_on_players(world, head, BREATH_RADIUS, BREATH_DAMAGE);
+        }
+    }
+
+    /// Rises and spins while dying, dropping experience near the end and
+    /// finishing after [`DEATH_TICKS`].
+    ///
+    /// Vanilla: `EnderDragon.tickDeath`
+    #[allow(clippy::cast_possible_truncation)]
+    fn tick_death(&mut self, world: &World) -> Option<DragonAction> {
+        if self.phase_ticks == 1 {
+            world.global_level_event(
+                level_events::SOUND_DRAGON_DEATH,
+                self.position.to_block_pos(),
+                0,
+            );
+        }
+        self.position.y += 0.1;
+        let (yaw, pitch) = self.base.rotation.load();
+        self.base.rotation.store((yaw + 20.0, pitch));
+        self.sync_position(world);
+
+        if self.phase_ticks >= DEATH_TICKS {
+            let experience = (f64::from(EXPERIENCE_REWARD) * 0.2).floor() as i32;
+            return Some(DragonAction::Die(experience));
+        }
+        if self.phase_ticks > 150 && self.phase_ticks.is_multiple_of(5) {
+            let experience = (f64::from(EXPERIENCE_REWARD) * 0.08).floor() as i32;
+            return Some(DragonAction::DropExperience(experience));
+        }
+        None
+    }
+
+    /// Heals the dragon a little every now and then while end crystals are
+    /// near it.
+    ///
+    /// Vanilla: `EnderDragon.checkCrystals`
+    // TODO: Point the crystals' beams at the dragon
+    fn heal_from_crystals(&mut self, world: &World) {
+        if !self.age.is_multiple_of(CRYSTAL_HEAL_INTERVAL)
+            || count_crystals(world, self.position, CRYSTAL_RANGE) == 0
+        {
+            return;
+        }
+        let health = *self.entity_data.health.get();
+        self.entity_data.health.set((health + 1.0).min(MAX_HEALTH));
+    }
+
+    /// Flies a tick towards `goal`, turning to face it, and returns how far
+    /// away it still is.
+    #[allow(clippy::cast_possible_truncation)]
+    fn fly_towards(&mut self, goal: Vector3<f64>) -> f64 {
+        let delta = goal.sub(&self.position);
+        let distance = delta.length();
+        if distance < 1.0e-7 {
+            return 0.0;
+        }
+        self.position += delta * (FLY_SPEED.min(distance) / distance);
+
+        // The dragon faces the other way than other entities
+        let wanted = delta.x.atan2(-delta.z).to_degrees() as f32;
+        let (yaw, pitch) = self.base.rotation.load();
+        let turn = (wanted - yaw + 540.0).rem_euclid(360.0) - 180.0;
+        self.base
+            .rotation
+            .store((yaw + turn.clamp(-TURN_SPEED, TURN_SPEED), pitch));
+        (distance - FLY_SPEED).max(0.0)
+    }
+
+    /// Switches to `phase`, starting it over.
+    fn set_phase(&mut self, phase: DragonPhase) {
+        self.phase = phase;
+        self.phase_ticks = 0;
+        self.aim_ticks = 0;
+        self.entity_data.phase.set(phase.id());
+    }
+
+    /// Tells clients where the dragon is.
+    fn sync_position(&self, world: &World) {
+        *self.base.position.lock() = self.position;
+        let (yaw, pitch) = self.base.rotation.load();
+        world.broadcast_to_trackers(
+            self.position,
+            CEntityPositionSync {
+                entity_id: self.base.id,
+                x: self.position.x,
+                y: self.position.y,
+                z: self.position.z,
+                velocity_x: 0.0,
+                velocity_y: 0.0,
+                velocity_z: 0.0,
+                yaw,
+                pitch,
+                on_ground: false,
+            },
+        );
+    }
+}
+
+impl Entity for EnderDragonEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for EnderDragonEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.base.write_nbt(&mut nbt);
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        if entity_id == self.base.id {
+            return Some(self.bounding_box());
+        }
+        self.part_index(entity_id)
+            .map(|index| self.part_boxes()[index])
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    /// Hurts the dragon through its body. Its flight doesn't let the blast
+    /// push it around.
+    fn explosion_hit(
+        &mut self,
+        _world: &World,
+        damage: f32,
+        _impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.hurt(None, damage);
+        None
+    }
+}
+
+/// How much a hit of `amount` on the part at `part` hurts the dragon: fully
+/// on the head and far less anywhere else.
+///
+/// Vanilla: `EnderDragon.hurt`
+fn part_damage(part: usize, amount: f32) -> f32 {
+    if part == HEAD {
+        amount
+    } else {
+        amount / 4.0 + amount.min(1.0)
+    }
+}
+
+/// Returns the middle of the dragon's head.
+fn head_center(head: &AABBd) -> Vector3<f64> {
+    Vector3::new(
+        f64::midpoint(head.min_x, head.max_x),
+        f64::midpoint(head.min_y, head.max_y),
+        f64::midpoint(head.min_z, head.max_z),
+    )
+}
+
+/// Returns the top of the fountain in the middle of the End the dragon
+/// circles around and lands on.
+///
+/// Vanilla: `EndPodiumFeature.getLocation`
+#[must_use]
+pub fn fountain(world: &World) -> Vector3<f64> {
+    let y = world
+        .motion_blocking_height(0, 0)
+        .unwrap_or(DEFAULT_FOUNTAIN_Y);
+    Vector3::new(0.5, f64::from(y), 0.5)
+}
+
+/// Returns the point `index` of the circle the dragon flies around
+/// `fountain`, every other one a little higher.
+#[allow(clippy::cast_precision_loss)]
+fn node_position(fountain: Vector3<f64>, index: usize) -> Vector3<f64> {
+    let angle = TAU * index as f64 / CIRCLE_NODES as f64;
+    let height = if index % 2 == 0 { 0.0 } else { 5.0 };
+    Vector3::new(
+        fountain.x + angle.cos() * CIRCLE_RADIUS,
+        fountain.y + CIRCLE_HEIGHT + height,
+        fountain.z + angle.sin() * CIRCLE_RADIUS,
+    )
+}
+
+/// Counts the end crystals within `range` of `center`.
+fn count_crystals(world: &World, center: Vector3<f64>, range: f64) -> usize {
+    world
+        .entities
+        .generic
+        .lock()
+        .iter()
+        .filter(|entity| ptr::eq(entity.entity_type, vanilla_entities::END_CRYSTAL))
+        .filter(|entity| entity.position.lock().sub(&center).length_squared() <= range * range)
+        .count()
+}
+
+/// Builds the exit portal on `origin` once the dragon is slain: a bedrock
+/// bowl of end portal around a pillar with the dragon egg on top.
+///
+/// Vanilla: `EndPodiumFeature.place`
+// TODO: Only place the egg for the first dragon once the dragon fight is
+// saved
+pub fn place_exit_portal(world: &World, origin: BlockPos) {
+    let bedrock = REGISTRY.blocks.get_base_state_id(vanilla_blocks::BEDROCK);
+    let portal = REGISTRY
+        .blocks
+        .get_base_state_id(vanilla_blocks::END_PORTAL);
+    let air = REGISTRY.blocks.get_base_state_id(vanilla_blocks::AIR);
+    for dx in -4..=4 {
+        for dz in -4..=4 {
+            let distance = f64::from(dx * dx + dz * dz).sqrt();
+            if distance > 3.5 {
+                continue;
+            }
+            world.set_block(origin.offset(dx, -1, dz), bedrock, UpdateFlags::UPDATE_ALL);
+            let ring = if distance <= 2.5 { portal } else { bedrock };
+            world.set_block(origin.offset(dx, 0, dz), ring, UpdateFlags::UPDATE_ALL);
+            for dy in 1..=4 {
+                world.set_block(origin.offset(dx, dy, dz), air, UpdateFlags::UPDATE_ALL);
+            }
+        }
+    }
+    for dy in 0..4 {
+        world.set_block(origin.offset(0, dy, 0), bedrock, UpdateFlags::UPDATE_ALL);
+    }
+    let egg = REGISTRY
+        .blocks
+        .get_base_state_id(vanilla_blocks::DRAGON_EGG);
+    world.set_block(origin.offset(0, 4, 0), egg, UpdateFlags::UPDATE_ALL);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_head_takes_full_damage() {
+        assert!((part_damage(HEAD, 8.0) - 8.0).abs() < f32::EPSILON);
+        assert!((part_damage(BODY, 8.0) - 3.0).abs() < f32::EPSILON);
+        assert!((part_damage(BODY, 0.5) - 0.625).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn phases_survive_saving() {
+        for phase in [
+            DragonPhase::HoldingPattern,
+            DragonPhase::Strafing,
+            DragonPhase::LandingApproach,
+            DragonPhase::Sitting,
+            DragonPhase::Dying,
+        ] {
+            assert_eq!(DragonPhase::from_id(phase.id()), phase);
+        }
+    }
+}
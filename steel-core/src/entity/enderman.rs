@@ -0,0 +1,636 @@
+//! Endermen that move blocks around, teleport and turn on players who look
+//! them in the eyes.
+
+use std::sync::Arc;
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, SoundSource};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::game_rules::GameRuleValue;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entity_data::EndermanEntityData;
+use steel_registry::vanilla_game_rules::MOB_GRIEFING;
+use steel_registry::{REGISTRY, sound_events, vanilla_blocks, vanilla_entities, vanilla_items};
+use steel_utils::math::Vector3;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+use uuid::Uuid;
+
+use crate::entity::ai::sight;
+use crate::entity::mob::{self, MobBase};
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, AttackableMob, Entity, GenericEntity, Remains, StoredEntity};
+use crate::inventory::equipment::EquipmentSlot;
+use crate::player::{Player, movement};
+use crate::world::World;
+
+/// The health of an enderman.
+const MAX_HEALTH: f32 = 40.0;
+/// How far above its feet an enderman's eyes are.
+const EYE_HEIGHT: f64 = 2.55;
+/// How far endermen walk in a tick.
+const WALK_SPEED: f64 = 0.2;
+
+/// How far away endermen notice players staring at them.
+const FOLLOW_RANGE: f64 = 64.0;
+/// How far from looking straight at an enderman's eyes a player's view can
+/// be for the enderman to feel stared at, in degrees.
+const STARE_ANGLE: f64 = 5.0;
+/// How many ticks pass between finding new paths to the target.
+const PATH_INTERVAL: u32 = 20;
+/// How close an enderman gets to its target before hitting it.
+const ATTACK_RANGE: f64 = 1.0;
+/// How many ticks pass between hits.
+const ATTACK_INTERVAL: u32 = 20;
+/// The damage of an enderman's hit.
+///
+/// Vanilla: `Attributes.ATTACK_DAMAGE` of endermen
+const ATTACK_DAMAGE: f32 = 7.0;
+/// How many ticks an enderman can't be hurt again after it was hurt.
+const HURT_COOLDOWN: u32 = 10;
+
+/// How many ticks pass between teleports towards a target out of reach.
+const TELEPORT_INTERVAL: u32 = 60;
+/// How far away a target has to be for an angry enderman to teleport.
+const TELEPORT_CHASE_DISTANCE: f64 = 16.0;
+/// How far an enderman teleports away at the most, on each axis.
+const TELEPORT_RANGE: f64 = 32.0;
+/// How many spots an enderman tries before it gives up teleporting.
+const TELEPORT_TRIES: u32 = 16;
+/// The chance that harm from something other than a hit makes an enderman
+/// teleport away.
+const DODGE_CHANCE: f64 = 0.9;
+
+/// The chance each tick that an enderman tries to pick up a block.
+const TAKE_BLOCK_CHANCE: f64 = 1.0 / 20.0;
+/// The chance each tick that an enderman tries to put its block down.
+const PLACE_BLOCK_CHANCE: f64 = 1.0 / 2000.0;
+
+/// The blocks endermen pick up.
+const HOLDABLE_TAG: Identifier = Identifier::vanilla_static("enderman_holdable");
+/// The helmets that let players look at endermen safely.
+const GAZE_DISGUISE_TAG: Identifier = Identifier::vanilla_static("gaze_disguise_equipment");
+
+/// The experience an enderman drops when a player kills it.
+///
+/// Vanilla: `Monster.XP_REWARD_MEDIUM`
+pub const EXPERIENCE_REWARD: i32 = 5;
+
+/// An enderman, wandering around moving blocks until a player stares at it
+/// or hits it.
+///
+/// Vanilla: `EnderMan`
+pub struct EndermanEntity {
+    /// The state every entity shares, like the id and custom name.
+    pub base: GenericEntity,
+    /// The player the enderman is angry at, if any.
+    pub target: Option<Uuid>,
+    mob: MobBase,
+    entity_data: EndermanEntityData,
+    /// Ticks until the enderman may hit its target again.
+    attack_cooldown: u32,
+    /// Ticks until the enderman can be hurt again.
+    hurt_cooldown: u32,
+    /// Ticks until the enderman may teleport towards its target again.
+    teleport_cooldown: u32,
+    /// Whether the enderman got hurt by something other than a hit and
+    /// teleports away next tick.
+    dodging: bool,
+}
+
+impl EndermanEntity {
+    /// Creates an enderman at `position` that carries nothing.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        let mut entity_data = EndermanEntityData::new();
+        entity_data.health.set(MAX_HEALTH);
+
+        Self {
+            base: GenericEntity::new(id, vanilla_entities::ENDERMAN, position),
+            target: None,
+            mob: MobBase::new(position, vanilla_entities::ENDERMAN),
+            entity_data,
+            attack_cooldown: 0,
+            hurt_cooldown: 0,
+            teleport_cooldown: 0,
+            dodging: false,
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the health and the carried block.
+    ///
+    /// Vanilla: `EnderMan.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(health) = nbt.float("Health") {
+            self.entity_data.health.set(health.clamp(0.0, MAX_HEALTH));
+        }
+        if let Some(carried) = nbt
+            .compound("carriedBlockState")
+            .and_then(entity::block_state_from_nbt)
+        {
+            self.entity_data.carry_state.set(Some(carried));
+        }
+    }
+
+    /// Writes the health and the carried block into `nbt`.
+    ///
+    /// Vanilla: `EnderMan.addAdditionalSaveData`
+    pub fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("Health", *self.entity_data.health.get());
+        if let Some(carried) = self.carried_block() {
+            nbt.insert("carriedBlockState", entity::block_state_to_nbt(carried));
+        }
+    }
+
+    /// Returns the position of the enderman.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.mob.position()
+    }
+
+    /// Returns the box the enderman takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.mob.bounding_box()
+    }
+
+    /// Returns the block the enderman carries, if any.
+    #[must_use]
+    pub fn carried_block(&self) -> Option<BlockStateId> {
+        *self.entity_data.carry_state.get()
+    }
+
+    /// The packet that adds this enderman to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the enderman is added, like
+    /// the block it carries.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Adds `impulse` to the velocity of the enderman.
+    pub fn push(&mut self, impulse: Vector3<f64>) {
+        self.mob.push(impulse);
+    }
+
+    /// Returns true once the enderman has no health left.
+    #[must_use]
+    pub fn is_dead(&self) -> bool {
+        *self.entity_data.health.get() <= 0.0
+    }
+
+    /// Hurts the enderman by `amount`. Harm that isn't a `direct` hit, like
+    /// water or lightning, mostly makes it teleport away next tick. Returns
+    /// false if it was hurt too recently to be hurt again.
+    ///
+    /// Vanilla: `EnderMan.hurtServer`
+    pub fn hurt(&mut self, amount: f32, direct: bool) -> bool {
+        if self.hurt_cooldown > 0 || self.is_dead() {
+            return false;
+        }
+        self.hurt_cooldown = HURT_COOLDOWN;
+        let health = *self.entity_data.health.get();
+        self.entity_data.health.set((health - amount).max(0.0));
+        if !direct && rand::random_bool(DODGE_CHANCE) {
+            self.dodging = true;
+        }
+        true
+    }
+
+    /// Makes the enderman angry at `player`, screaming at them.
+    ///
+    /// Vanilla: `EnderMan.setTarget`
+    pub fn anger_at(&mut self, world: &World, player: &Player) {
+        if self.target == Some(player.gameprofile.id) {
+            return;
+        }
+        self.target = Some(player.gameprofile.id);
+        self.entity_data.creepy.set(true);
+        world.play_sound(
+            sound_events::ENTITY_ENDERMAN_STARE,
+            SoundSource::Hostile,
+            self.position().to_block_pos(),
+            2.5,
+            1.0,
+            None,
+        );
+    }
+
+    /// Calms the enderman down.
+    fn calm_down(&mut self) {
+        self.target = None;
+        self.entity_data.creepy.set(false);
+    }
+
+    /// Advances the enderman by a tick: water and rain hurt it, it turns on
+    /// players staring at it and chases its target, teleporting closer when
+    /// it is far away. Calm endermen pick up and put down blocks.
+    ///
+    /// Vanilla: `EnderMan.customServerAiStep` and its goals
+    pub fn tick(&mut self, world: &World) {
+        self.attack_cooldown = self.attack_cooldown.saturating_sub(1);
+        self.hurt_cooldown = self.hurt_cooldown.saturating_sub(1);
+        self.teleport_cooldown = self.teleport_cooldown.saturating_sub(1);
+
+        if self.is_in_water_or_rain(world) {
+            self.hurt(1.0, false);
+        }
+        if self.mob.tick_fire() {
+            self.hurt(1.0, false);
+        }
+        self.base.on_fire = self.mob.is_on_fire();
+        self.entity_data.shared_flags.set(self.base.shared_flags());
+        if self.is_dead() {
+            return;
+        }
+        if self.dodging {
+            self.dodging = false;
+            self.teleport_randomly(world);
+        }
+
+        let starer = self.find_starer(world);
+        self.entity_data.stared_at.set(starer.is_some());
+        if let Some(player) = starer {
+            self.anger_at(world, &player);
+        }
+
+        let target = self
+            .target
+            .and_then(|uuid| world.players.get_by_uuid(&uuid))
+            .filter(|player| {
+                mob::is_targetable(player)
+                    && player
+                        .position
+                        .lock()
+                        .sub(&self.position())
+                        .length_squared()
+                        <= FOLLOW_RANGE * FOLLOW_RANGE
+            });
+        match target {
+            Some(player) => self.chase(world, &player),
+            None => {
+                if self.target.is_some() {
+                    self.calm_down();
+                }
+                self.mob.stop();
+                if world.get_game_rule(MOB_GRIEFING) == GameRuleValue::Bool(true) {
+                    self.move_blocks(world);
+                }
+            }
+        }
+        self.mob.walk(&self.base, world, WALK_SPEED);
+    }
+
+    /// Walks after `player` and hits them once close enough, teleporting
+    /// towards them now and then while they are far away.
+    ///
+    /// Vanilla: `MeleeAttackGoal` and `EnderMan.teleportTowards`
+    fn chase(&mut self, world: &World, player: &Player) {
+        let target_pos = *player.position.lock();
+        let distance = target_pos.sub(&self.position()).length();
+        if distance > TELEPORT_CHASE_DISTANCE && self.teleport_cooldown == 0 {
+            self.teleport_cooldown = TELEPORT_INTERVAL;
+            self.teleport_towards(world, target_pos);
+        }
+
+        self.mob
+            .move_to(world, target_pos.to_block_pos(), PATH_INTERVAL);
+        let reach = self.bounding_box().inflate(ATTACK_RANGE);
+        if self.attack_cooldown == 0 && reach.intersects(&player.bounding_box()) {
+            self.attack_cooldown = ATTACK_INTERVAL;
+            if let Some(damage) = mob::player_damage(ATTACK_DAMAGE, world.difficulty()) {
+                player.take_damage(damage);
+            }
+        }
+    }
+
+    /// Returns the nearest player staring the enderman in the eyes, if any.
+    ///
+    /// Vanilla: `EnderMan.isBeingStaredBy`
+    fn find_starer(&self, world: &World) -> Option<Arc<Player>> {
+        let position = self.position();
+        let eyes = Vector3::new(position.x, position.y + EYE_HEIGHT, position.z);
+        let mut nearest: Option<(f64, Arc<Player>)> = None;
+        world.players.iter_players(|_, player| {
+            let player_pos = *player.position.lock();
+            let distance = player_pos.sub(&position).length_squared();
+            if distance > FOLLOW_RANGE * FOLLOW_RANGE
+                || !mob::is_targetable(player)
+                || nearest
+                    .as_ref()
+                    .is_some_and(|(nearest, _)| *nearest <= distance)
+            {
+                return true;
+            }
+
+            let player_eyes = Vector3::new(
+                player_pos.x,
+                player_pos.y + player.eye_height(),
+                player_pos.z,
+            );
+            let (yaw, pitch) = player.rotation.load();
+            let view = Vector3::rotation_vector(f64::from(pitch), f64::from(yaw));
+            if is_looking_at(player_eyes, view, eyes)
+                && !wears_gaze_disguise(player)
+                && sight::has_line_of_sight(world, player_eyes, eyes)
+            {
+                nearest = Some((distance, player.clone()));
+            }
+            true
+        });
+        nearest.map(|(_, player)| player)
+    }
+
+    /// Returns true while the enderman stands in water or in the rain.
+    ///
+    /// Vanilla: `Entity.isInWaterOrRain`
+    fn is_in_water_or_rain(&self, world: &World) -> bool {
+        let position = self.position();
+        let feet = position.to_block_pos();
+        let eyes = Vector3::new(position.x, position.y + EYE_HEIGHT, position.z).to_block_pos();
+        movement::water_height(world, feet).is_some()
+            || movement::water_height(world, eyes).is_some()
+            || (world.is_raining() && world.is_sky_visible(&eyes))
+    }
+
+    /// Picks up a block next to the enderman now and then, and puts the
+    /// block it carries down somewhere else.
+    ///
+    /// Vanilla: `EndermanTakeBlockGoal` and `EndermanLeaveBlockGoal`
+    fn move_blocks(&mut self, world: &World) {
+        let position = self.position();
+        match self.carried_block() {
+            None if rand::random_bool(TAKE_BLOCK_CHANCE) => {
+                let pos = BlockPos::new(
+                    (position.x - 2.0 + rand::random_range(0.0..4.0)).floor() as i32,
+                    (position.y + rand::random_range(0.0..3.0)).floor() as i32,
+                    (position.z - 2.0 + rand::random_range(0.0..4.0)).floor() as i32,
+                );
+                let state = world.get_block_state(&pos);
+                if !REGISTRY.blocks.is_in_tag(state.get_block(), &HOLDABLE_TAG) {
+                    return;
+                }
+                let eyes = Vector3::new(position.x, position.y + EYE_HEIGHT, position.z);
+                let center = Vector3::new(
+                    f64::from(pos.x()) + 0.5,
+                    f64::from(pos.y()) + 0.5,
+                    f64::from(pos.z()) + 0.5,
+                );
+                let reachable = sight::blocks_between(eyes, center)
+                    .take_while(|&between| between != pos)
+                    .all(|between| {
+                        world
+                            .get_block_state(&between)
+                            .get_collision_shape()
+                            .is_empty()
+                    });
+                if reachable {
+                    let air = REGISTRY.blocks.get_base_state_id(vanilla_blocks::AIR);
+                    world.set_block(pos, air, UpdateFlags::UPDATE_ALL);
+                    self.entity_data.carry_state.set(Some(state));
+                }
+            }
+            Some(carried) if rand::random_bool(PLACE_BLOCK_CHANCE) => {
+                let pos = BlockPos::new(
+                    (position.x - 1.0 + rand::random_range(0.0..2.0)).floor() as i32,
+                    (position.y + rand::random_range(0.0..2.0)).floor() as i32,
+                    (position.z - 1.0 + rand::random_range(0.0..2.0)).floor() as i32,
+                );
+                let below = world.get_block_state(&pos.offset(0, -1, 0));
+                // TODO: Check that the block survives there, like flowers on
+                // grass, once blocks can tell
+                if world.get_block_state(&pos).is_air() && !below.is_air() && below.is_solid() {
+                    world.set_block(pos, carried, UpdateFlags::UPDATE_ALL);
+                    self.entity_data.carry_state.set(None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Teleports to a random spot up to [`TELEPORT_RANGE`] blocks away.
+    ///
+    /// Vanilla: `EnderMan.teleport`
+    pub fn teleport_randomly(&mut self, world: &World) -> bool {
+        let position = self.position();
+        (0..TELEPORT_TRIES).any(|_| {
+            let destination = position.add_raw(
+                rand::random_range(-TELEPORT_RANGE..TELEPORT_RANGE),
+                rand::random_range(-TELEPORT_RANGE..TELEPORT_RANGE).floor(),
+                rand::random_range(-TELEPORT_RANGE..TELEPORT_RANGE),
+            );
+            self.teleport(world, destination)
+        })
+    }
+
+    /// Teleports to a random spot on the way to `target`.
+    ///
+    /// Vanilla: `EnderMan.teleportTowards`
+    fn teleport_towards(&mut self, world: &World, target: Vector3<f64>) -> bool {
+        let position = self.position();
+        let direction = position.sub(&target).normalize();
+        (0..TELEPORT_TRIES).any(|_| {
+            let destination = position.add_raw(
+                rand::random_range(-4.0..4.0) - direction.x * 16.0,
+                f64::from(rand::random_range(-8..8)) - direction.y * 16.0,
+                rand::random_range(-4.0..4.0) - direction.z * 16.0,
+            );
+            self.teleport(world, destination)
+        })
+    }
+
+    /// Teleports onto the ground below `destination`, unless there is no
+    /// ground, no room or water there. Returns true if the enderman moved.
+    ///
+    /// Vanilla: `EnderMan.teleport(double, double, double)`
+    fn teleport(&mut self, world: &World, destination: Vector3<f64>) -> bool {
+        let column = destination.to_block_pos();
+        let is_open = |pos: BlockPos| world.get_block_state(&pos).get_collision_shape().is_empty();
+        let Some(ground) = (0..=2 * TELEPORT_RANGE as i32)
+            .map(|depth| column.offset(0, -depth, 0))
+            .take_while(|&pos| pos.y() > world.get_min_y())
+            .find(|&pos| !is_open(pos.offset(0, -1, 0)))
+        else {
+            return false;
+        };
+        if !(0..3).all(|dy| is_open(ground.offset(0, dy, 0)))
+            || movement::water_height(world, ground).is_some()
+        {
+            return false;
+        }
+
+        let from = self.position();
+        let to = Vector3::new(destination.x, f64::from(ground.y()), destination.z);
+        self.mob.teleport(&self.base, world, to);
+        for pos in [from, to] {
+            world.play_sound(
+                sound_events::ENTITY_ENDERMAN_TELEPORT,
+                SoundSource::Hostile,
+                pos.to_block_pos(),
+                1.0,
+                1.0,
+                None,
+            );
+        }
+        true
+    }
+
+    /// The items the enderman drops when it dies: maybe an ender pearl, and
+    /// the block it carries.
+    ///
+    /// Vanilla: `entities/enderman` loot table and `EnderMan.dropCustomDeathLoot`
+    #[must_use]
+    pub fn death_drops(&self) -> Vec<ItemStack> {
+        let mut drops = Vec::new();
+        if rand::random_bool(0.5) {
+            drops.push(ItemStack::new(&vanilla_items::ITEMS.ender_pearl));
+        }
+        if let Some(carried) = self.carried_block()
+            && let Some(item) = REGISTRY.items.by_key(&carried.get_block().key)
+        {
+            drops.push(ItemStack::new(item));
+        }
+        drops
+    }
+}
+
+impl Entity for EndermanEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for EndermanEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.base.write_nbt(&mut nbt);
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.base.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    fn explosion_hit(
+        &mut self,
+        world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.hurt_by_explosion(world, damage);
+        self.push(impulse);
+        None
+    }
+}
+
+impl AttackableMob for EndermanEntity {
+    fn take_hit(&mut self, amount: f32) -> Option<i32> {
+        self.hurt(amount, true)
+            .then_some(sound_events::ENTITY_ENDERMAN_HURT)
+    }
+
+    fn take_blast(&mut self, amount: f32) -> Option<i32> {
+        self.hurt(amount, false)
+            .then_some(sound_events::ENTITY_ENDERMAN_HURT)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.is_dead()
+    }
+
+    fn retaliate(&mut self, world: &World, player: &Player) {
+        self.anger_at(world, player);
+    }
+
+    fn death_sound(&self) -> i32 {
+        sound_events::ENTITY_ENDERMAN_DEATH
+    }
+
+    fn experience_reward(&self) -> i32 {
+        EXPERIENCE_REWARD
+    }
+
+    fn death_drops(&self) -> Vec<ItemStack> {
+        self.death_drops()
+    }
+}
+
+/// Returns true if someone with eyes at `eyes` looking along `view` looks
+/// within a few degrees of `target`.
+#[must_use]
+pub fn is_looking_at(eyes: Vector3<f64>, view: Vector3<f64>, target: Vector3<f64>) -> bool {
+    let to_target = target.sub(&eyes);
+    if to_target.length_squared() < 1.0e-7 {
+        return false;
+    }
+    let view = view.normalize();
+    let to_target = to_target.normalize();
+    let dot = view.x * to_target.x + view.y * to_target.y + view.z * to_target.z;
+    dot > STARE_ANGLE.to_radians().cos()
+}
+
+/// Returns true if `player` wears a helmet that hides their gaze, like a
+/// carved pumpkin.
+fn wears_gaze_disguise(player: &Player) -> bool {
+    let inventory = player.inventory.lock();
+    let head = inventory.equipment().get_ref(EquipmentSlot::Head);
+    REGISTRY.items.is_in_tag(head.item(), &GAZE_DISGUISE_TAG)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_straight_look_counts_as_staring() {
+        let eyes = Vector3::new(0.0, 1.62, 0.0);
+        let enderman = Vector3::new(0.0, 2.55, 10.0);
+
+        let straight = enderman.sub(&eyes);
+        assert!(is_looking_at(eyes, straight, enderman));
+
+        let slightly_off = straight.add_raw(0.5, 0.0, 0.0);
+        assert!(is_looking_at(eyes, slightly_off, enderman));
+
+        let away = straight.add_raw(3.0, 0.0, 0.0);
+        assert!(!is_looking_at(eyes, away, enderman));
+        assert!(!is_looking_at(eyes, Vector3::new(0.0, 0.0, -1.0), enderman));
+    }
+}
@@ -0,0 +1,470 @@
+//! Experience orbs that fly to nearby players.
+
+use std::sync::Arc;
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, CEntityPositionSync, CTakeItemEntity};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::vanilla_entity_data::ExperienceOrbEntityData;
+use steel_registry::{REGISTRY, vanilla_entities};
+use steel_utils::BlockPos;
+use steel_utils::math::Vector3;
+use steel_utils::types::GameType;
+use uuid::Uuid;
+
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, Remains, StoredEntity};
+use crate::physics::{
+    DEFAULT_DRAG, EntityPhysicsState, MoverType, WorldCollisionProvider, move_entity,
+    stop_blocked_axes,
+};
+use crate::player::Player;
+use crate::world::World;
+
+/// The most experience a single orb can hold.
+pub const MAX_VALUE: i32 = 32_767;
+/// How many ticks an orb lasts before it despawns.
+pub const LIFETIME: i32 = 6000;
+
+/// How fast orbs fall, in blocks per tick squared.
+const GRAVITY: f64 = 0.03;
+/// How close a player has to be for orbs to fly towards them.
+const FOLLOW_DISTANCE: f64 = 8.0;
+/// How often an orb looks for orbs to merge with, in ticks.
+const MERGE_INTERVAL: i32 = 20;
+/// The values at which orbs get a bigger icon, from largest to smallest.
+///
+/// Vanilla: `ExperienceOrb.getExperienceValue`
+const ICON_VALUES: [i32; 11] = [2477, 1237, 617, 307, 149, 73, 37, 17, 7, 3, 1];
+
+/// How much harm an orb takes before it is destroyed.
+const MAX_HEALTH: i16 = 5;
+
+/// An orb of experience, flying towards the nearest player and giving them
+/// its value once it touches them.
+///
+/// Vanilla: `ExperienceOrb`
+pub struct ExperienceOrbEntity {
+    /// The network id of the entity.
+    pub id: i32,
+    /// The persistent id of the entity.
+    pub uuid: Uuid,
+    /// The experience points the orb gives.
+    pub value: i32,
+    /// How many ticks the orb has existed.
+    pub age: i32,
+    /// How much more harm the orb takes before it is destroyed.
+    health: i16,
+    physics: EntityPhysicsState,
+    /// The player the orb flies towards.
+    following_player: Option<Uuid>,
+    entity_data: ExperienceOrbEntityData,
+}
+
+impl ExperienceOrbEntity {
+    /// Creates an orb worth `value` points at `position`, flying off in a
+    /// random direction.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>, value: i32) -> Self {
+        let value = value.clamp(0, MAX_VALUE);
+        let mut entity_data = ExperienceOrbEntityData::new();
+        // Clients pick the icon from the value
+        entity_data.value.set(value);
+
+        let mut physics = EntityPhysicsState::new(position, vanilla_entities::EXPERIENCE_ORB);
+        physics.velocity = Vector3::new(
+            (rand::random::<f64>() * 0.2 - 0.1) * 2.0,
+            rand::random::<f64>() * 0.2 * 2.0,
+            (rand::random::<f64>() * 0.2 - 0.1) * 2.0,
+        );
+
+        Self {
+            id,
+            uuid: Uuid::new_v4(),
+            value,
+            age: 0,
+            health: MAX_HEALTH,
+            physics,
+            following_player: None,
+            entity_data,
+        }
+    }
+
+    /// Applies the orb tags of `nbt`, like its value and age. Tags that
+    /// aren't set keep their current value, and the position is never loaded.
+    ///
+    /// Vanilla: `ExperienceOrb.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(uuid) = entity::uuid_from_nbt(nbt) {
+            self.uuid = uuid;
+        }
+        if let Some(velocity) = entity::motion_from_nbt(nbt) {
+            self.physics.velocity = velocity;
+        }
+        if let Some(value) = nbt.short("Value") {
+            self.value = i32::from(value).clamp(0, MAX_VALUE);
+            self.entity_data.value.set(self.value);
+        }
+        if let Some(age) = nbt.short("Age") {
+            self.age = i32::from(age);
+        }
+        if let Some(health) = nbt.short("Health") {
+            self.health = health;
+        }
+    }
+
+    /// Returns the position of the orb.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.physics.position
+    }
+
+    /// Returns the box the orb takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.physics.bounding_box
+    }
+
+    /// The packet that adds this orb to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        let position = self.position();
+        CAddEntity::new(
+            self.id,
+            self.uuid,
+            *REGISTRY
+                .entity_types
+                .get_id(vanilla_entities::EXPERIENCE_ORB) as i32,
+            position.x,
+            position.y,
+            position.z,
+            0.0,
+            0.0,
+        )
+    }
+
+    /// The entity data clients need right after the orb is added, like its
+    /// value.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        self.entity_data.pack_all()
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Returns true if `other` is close enough to merge into this orb, shows
+    /// the same icon and their values fit in one orb.
+    #[must_use]
+    pub fn can_merge(&self, other: &Self) -> bool {
+        other.id != self.id
+            && icon_value(other.value) == icon_value(self.value)
+            && self.value + other.value <= MAX_VALUE
+            && self
+                .bounding_box()
+                .inflate(0.5)
+                .intersects(&other.bounding_box())
+    }
+
+    /// Returns true if this tick is one in which the orb looks for orbs to
+    /// merge with.
+    #[must_use]
+    pub const fn scans_for_merges(&self) -> bool {
+        self.age % MERGE_INTERVAL == 1
+    }
+
+    /// Takes the value of `other` into this orb, which lasts as long as the
+    /// younger of the two.
+    ///
+    /// Vanilla: `ExperienceOrb.merge`
+    pub fn merge(&mut self, other: &Self) {
+        self.value = (self.value + other.value).min(MAX_VALUE);
+        self.age = self.age.min(other.age);
+        self.entity_data.value.set(self.value);
+    }
+
+    /// Advances the orb by a tick, letting it fall and fly towards the nearest
+    /// player. Returns `false` once a player picked it up or it despawned.
+    ///
+    /// Vanilla: `ExperienceOrb.tick`
+    pub fn tick(&mut self, world: &World) -> bool {
+        self.physics.velocity.y -= GRAVITY;
+        self.follow_nearby_player(world);
+
+        let delta = self.physics.velocity;
+        let result = move_entity(
+            &self.physics,
+            delta,
+            MoverType::SelfMovement,
+            &WorldCollisionProvider::new(world),
+        );
+        self.physics.set_position(result.final_position);
+        self.physics.on_ground = result.on_ground;
+        self.physics.horizontal_collision = result.horizontal_collision;
+        self.physics.vertical_collision = result.vertical_collision;
+
+        let friction = if result.on_ground {
+            #[allow(clippy::cast_possible_truncation)]
+            let below = BlockPos::new(
+                result.final_position.x.floor() as i32,
+                (result.final_position.y - 0.500_001).floor() as i32,
+                result.final_position.z.floor() as i32,
+            );
+            let state = world.get_block_state(&below);
+            f64::from(state.get_block().config.friction) * 0.98
+        } else {
+            0.98
+        };
+        self.physics.velocity =
+            next_velocity(delta, result.actual_movement, friction, result.on_ground);
+
+        if result.actual_movement != Vector3::new(0.0, 0.0, 0.0) {
+            let velocity = self.physics.velocity;
+            world.broadcast_to_trackers(
+                result.final_position,
+                CEntityPositionSync {
+                    entity_id: self.id,
+                    x: result.final_position.x,
+                    y: result.final_position.y,
+                    z: result.final_position.z,
+                    velocity_x: velocity.x,
+                    velocity_y: velocity.y,
+                    velocity_z: velocity.z,
+                    yaw: 0.0,
+                    pitch: 0.0,
+                    on_ground: result.on_ground,
+                },
+            );
+        }
+
+        if self.try_pickup(world) {
+            return false;
+        }
+
+        self.age += 1;
+        self.age < LIFETIME
+    }
+
+    /// Speeds the orb up towards the player it follows, picking the nearest
+    /// one within 8 blocks if it lost track of them.
+    ///
+    /// Vanilla: `ExperienceOrb.followNearbyPlayer`
+    fn follow_nearby_player(&mut self, world: &World) {
+        let position = self.position();
+        let max_distance_sq = FOLLOW_DISTANCE * FOLLOW_DISTANCE;
+        let in_range = |player: &Player| {
+            player.position.lock().squared_distance_to_vec(position) <= max_distance_sq
+        };
+        let followed = self
+            .following_player
+            .and_then(|uuid| world.players.get_by_uuid(&uuid))
+            .filter(|player| player.game_mode.load() != GameType::Spectator && in_range(player))
+            .or_else(|| nearest_player(world, position, in_range));
+        self.following_player = followed.as_ref().map(|player| player.gameprofile.id);
+
+        let Some(player) = followed else {
+            return;
+        };
+        let target = *player.position.lock();
+        let towards = Vector3::new(
+            target.x - position.x,
+            target.y + player.eye_height() / 2.0 - position.y,
+            target.z - position.z,
+        );
+        let distance = towards.length();
+        let pull = 1.0 - distance / FOLLOW_DISTANCE;
+        if pull > 0.0 && distance > 0.0 {
+            let scale = pull * pull * 0.1 / distance;
+            self.physics.velocity += towards * scale;
+        }
+    }
+
+    /// Gives the orb's value to the nearest player touching it. Returns true if
+    /// a player picked it up.
+    ///
+    /// Vanilla: `ExperienceOrb.playerTouch`
+    fn try_pickup(&self, world: &World) -> bool {
+        let bounding_box = self.bounding_box();
+        // Players collect orbs that touch their box grown by a block sideways
+        // and half a block up and down
+        let touching = |player: &Player| {
+            let player_box = player.bounding_box();
+            AABBd::new(
+                player_box.min_x - 1.0,
+                player_box.min_y - 0.5,
+                player_box.min_z - 1.0,
+                player_box.max_x + 1.0,
+                player_box.max_y + 0.5,
+                player_box.max_z + 1.0,
+            )
+            .intersects(&bounding_box)
+        };
+        let Some(player) = nearest_player(world, self.position(), touching) else {
+            return false;
+        };
+        if !player.experience().lock().try_start_pickup() {
+            return false;
+        }
+
+        world.broadcast_to_trackers(
+            self.position(),
+            CTakeItemEntity {
+                item_id: self.id,
+                collector_id: player.id,
+                amount: 1,
+            },
+        );
+        // TODO: Repair items with mending first once enchantments are read
+        player.give_experience_points(self.value);
+        true
+    }
+}
+
+impl Entity for ExperienceOrbEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
+impl StoredEntity for ExperienceOrbEntity {
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        vanilla_entities::EXPERIENCE_ORB
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    /// Vanilla: `ExperienceOrb.addAdditionalSaveData`
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        entity::write_position(&mut nbt, self.position());
+        entity::write_uuid(&mut nbt, self.uuid);
+        entity::write_motion(&mut nbt, self.physics.velocity);
+        // Values are clamped to fit a short
+        nbt.insert("Value", self.value as i16);
+        nbt.insert("Age", self.age as i16);
+        nbt.insert("Health", self.health);
+        Some(nbt)
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    /// Destroys the orb once the blast took all its health.
+    ///
+    /// Vanilla: `ExperienceOrb.hurtServer`
+    #[allow(clippy::cast_possible_truncation)]
+    fn explosion_hit(
+        &mut self,
+        _world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.health = (f32::from(self.health) - damage) as i16;
+        self.physics.velocity += impulse;
+        (self.health <= 0).then(|| Remains {
+            pos: self.position().to_block_pos(),
+            drops: Vec::new(),
+            passengers: Vec::new(),
+        })
+    }
+}
+
+/// Splits `amount` into the values of the orbs it drops as, largest first.
+///
+/// Vanilla: `ExperienceOrb.award`
+#[must_use]
+pub fn split_value(mut amount: i32) -> Vec<i32> {
+    let mut values = Vec::new();
+    while amount > 0 {
+        let value = icon_value(amount).min(MAX_VALUE);
+        values.push(value);
+        amount -= value;
+    }
+    values
+}
+
+/// Returns the largest value with its own icon that `value` reaches.
+///
+/// Vanilla: `ExperienceOrb.getExperienceValue`
+fn icon_value(value: i32) -> i32 {
+    ICON_VALUES
+        .into_iter()
+        .find(|&icon| value >= icon)
+        .unwrap_or(1)
+}
+
+/// Returns the player nearest to `position` that isn't spectating and passes
+/// `filter`.
+fn nearest_player(
+    world: &World,
+    position: Vector3<f64>,
+    filter: impl Fn(&Player) -> bool,
+) -> Option<Arc<Player>> {
+    let mut nearest: Option<(f64, Arc<Player>)> = None;
+    world.players.iter_players(|_, player| {
+        if player.game_mode.load() != GameType::Spectator && filter(player) {
+            let distance_sq = player.position.lock().squared_distance_to_vec(position);
+            if nearest
+                .as_ref()
+                .is_none_or(|(nearest_sq, _)| distance_sq < *nearest_sq)
+            {
+                nearest = Some((distance_sq, player.clone()));
+            }
+        }
+        true
+    });
+    nearest.map(|(_, player)| player)
+}
+
+/// The velocity after a tick of moving `movement` out of `delta`, losing
+/// speed to `friction` sideways and the air vertically. Blocked axes stop,
+/// and orbs on the ground hop a little.
+fn next_velocity(
+    delta: Vector3<f64>,
+    movement: Vector3<f64>,
+    friction: f64,
+    on_ground: bool,
+) -> Vector3<f64> {
+    let bounce = if on_ground { -0.9 } else { 1.0 };
+    stop_blocked_axes(delta, movement).multiply(friction, DEFAULT_DRAG * bounce, friction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_split_into_icon_values() {
+        assert_eq!(split_value(10), [7, 3]);
+        assert_eq!(split_value(40), [37, 3]);
+        assert_eq!(split_value(0), Vec::<i32>::new());
+        assert_eq!(split_value(5000).iter().sum::<i32>(), 5000);
+    }
+
+    #[test]
+    fn slows_down_on_the_ground() {
+        let delta = Vector3::new(0.1, -0.2, 0.0);
+        let landed = next_velocity(delta, Vector3::new(0.1, -0.05, 0.0), 0.6 * 0.98, true);
+        assert!((landed.x - 0.1 * 0.588).abs() < 1e-9);
+        assert!(landed.y.abs() < 1e-9);
+
+        let falling = next_velocity(delta, delta, 0.98, false);
+        assert!((falling.y + 0.2 * 0.98).abs() < 1e-9);
+    }
+}
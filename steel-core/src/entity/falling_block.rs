@@ -0,0 +1,227 @@
+//! Blocks like sand and gravel falling down as entities.
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, CEntityPositionSync};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::Direction;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::{REGISTRY, vanilla_entities};
+use steel_utils::math::Vector3;
+use steel_utils::{BlockPos, BlockStateId};
+use uuid::Uuid;
+
+use crate::behavior::blocks::falling_block;
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, Remains, StoredEntity};
+use crate::physics::{self, EntityPhysicsState, GravityAffected, WorldCollisionProvider};
+use crate::world::World;
+
+/// How many ticks a block falls at most before it breaks.
+const MAX_FALL_TIME: u32 = 600;
+/// How many ticks a block may fall outside the world's height before it
+/// breaks.
+const MAX_OUT_OF_WORLD_TIME: u32 = 100;
+
+/// What became of a falling block that stopped falling.
+pub enum FallingBlockEnd {
+    /// It landed at a position it can be placed at.
+    Land(BlockPos),
+    /// It landed where it doesn't fit, or fell for too long, and breaks into
+    /// its item at the position.
+    Break(BlockPos),
+}
+
+/// A block falling down because nothing held it up, placed again where it
+/// lands.
+///
+/// Vanilla: `FallingBlockEntity`
+pub struct FallingBlockEntity {
+    /// The network id of the entity.
+    pub id: i32,
+    /// The persistent id of the entity.
+    pub uuid: Uuid,
+    /// The block state that is falling.
+    pub state: BlockStateId,
+    physics: EntityPhysicsState,
+    /// Ticks the block has been falling.
+    time: u32,
+}
+
+impl FallingBlockEntity {
+    /// Creates `state` falling from the block at `pos`. The caller removes
+    /// the block.
+    #[must_use]
+    pub fn new(id: i32, pos: BlockPos, state: BlockStateId) -> Self {
+        let position = Vector3::new(
+            f64::from(pos.x()) + 0.5,
+            f64::from(pos.y()),
+            f64::from(pos.z()) + 0.5,
+        );
+        Self::at(id, position, state)
+    }
+
+    /// Creates `state` falling from `position`, like a summoned block.
+    #[must_use]
+    pub fn at(id: i32, position: Vector3<f64>, state: BlockStateId) -> Self {
+        Self {
+            id,
+            uuid: Uuid::new_v4(),
+            state,
+            physics: EntityPhysicsState::new(position, vanilla_entities::FALLING_BLOCK),
+            time: 0,
+        }
+    }
+
+    /// Returns the position of the block.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.physics.position
+    }
+
+    /// The packet that adds this block to clients. Its data is the state
+    /// clients draw.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        let position = self.position();
+        let mut packet = CAddEntity::new(
+            self.id,
+            self.uuid,
+            *REGISTRY
+                .entity_types
+                .get_id(vanilla_entities::FALLING_BLOCK) as i32,
+            position.x,
+            position.y,
+            position.z,
+            0.0,
+            0.0,
+        );
+        packet.data = i32::from(self.state.0);
+        packet
+    }
+
+    /// Advances the block by a tick, letting it fall. Returns what became of
+    /// it once it stopped falling.
+    ///
+    /// Vanilla: `FallingBlockEntity.tick`
+    pub fn tick(&mut self, world: &World) -> Option<FallingBlockEnd> {
+        self.time += 1;
+        let result = physics::fall(self, &WorldCollisionProvider::new(world));
+
+        if result.actual_movement != Vector3::new(0.0, 0.0, 0.0) {
+            let velocity = self.physics.velocity;
+            world.broadcast_to_trackers(
+                result.final_position,
+                CEntityPositionSync {
+                    entity_id: self.id,
+                    x: result.final_position.x,
+                    y: result.final_position.y,
+                    z: result.final_position.z,
+                    velocity_x: velocity.x,
+                    velocity_y: velocity.y,
+                    velocity_z: velocity.z,
+                    yaw: 0.0,
+                    pitch: 0.0,
+                    on_ground: result.on_ground,
+                },
+            );
+        }
+
+        let pos = self.position().to_block_pos();
+        if result.on_ground {
+            let replaceable = world.get_block_state(&pos).get_block().config.replaceable;
+            let below = world.get_block_state(&Direction::Down.relative(&pos));
+            if replaceable && !falling_block::is_free(below) {
+                return Some(FallingBlockEnd::Land(pos));
+            }
+            return Some(FallingBlockEnd::Break(pos));
+        }
+
+        let out_of_world = pos.y() < world.get_min_y() || pos.y() > world.get_max_y();
+        if (self.time > MAX_OUT_OF_WORLD_TIME && out_of_world) || self.time > MAX_FALL_TIME {
+            return Some(FallingBlockEnd::Break(pos));
+        }
+        None
+    }
+
+    /// Applies the tags of `nbt`, like the falling state and how long it has
+    /// been falling. The position is never loaded.
+    ///
+    /// Vanilla: `FallingBlockEntity.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(uuid) = entity::uuid_from_nbt(nbt) {
+            self.uuid = uuid;
+        }
+        if let Some(state) = nbt
+            .compound("BlockState")
+            .and_then(entity::block_state_from_nbt)
+        {
+            self.state = state;
+        }
+        if let Some(velocity) = entity::motion_from_nbt(nbt) {
+            self.physics.velocity = velocity;
+        }
+        if let Some(time) = nbt.int("Time") {
+            self.time = time.max(0) as u32;
+        }
+    }
+}
+
+impl Entity for FallingBlockEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
+impl StoredEntity for FallingBlockEntity {
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        vanilla_entities::FALLING_BLOCK
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.send(self.add_entity_packet());
+    }
+
+    /// Saves the block to NBT, the way it is stored with its chunk.
+    ///
+    /// Vanilla: `FallingBlockEntity.addAdditionalSaveData`
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        entity::write_position(&mut nbt, self.position());
+        entity::write_uuid(&mut nbt, self.uuid);
+        entity::write_motion(&mut nbt, self.physics.velocity);
+        nbt.insert("BlockState", entity::block_state_to_nbt(self.state));
+        nbt.insert("Time", self.time as i32);
+        Some(nbt)
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.physics.bounding_box)
+    }
+
+    fn explosion_hit(
+        &mut self,
+        _world: &World,
+        _damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.physics.velocity += impulse;
+        None
+    }
+}
+
+impl GravityAffected for FallingBlockEntity {
+    const GRAVITY: f64 = 0.04;
+
+    fn physics_mut(&mut self) -> &mut EntityPhysicsState {
+        &mut self.physics
+    }
+}
@@ -0,0 +1,250 @@
+//! Firework rockets boosting gliding players.
+
+use simdnbt::ToNbtTag;
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, CEntityEvent, ENTITY_EVENT_FIREWORKS_EXPLODE};
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entity_data::FireworkRocketEntityData;
+use steel_registry::{REGISTRY, vanilla_entities};
+use steel_utils::math::Vector3;
+use uuid::Uuid;
+
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, StoredEntity};
+use crate::player::Player;
+use crate::world::World;
+
+/// How long a rocket flies without a flight duration of its own.
+///
+/// The `fireworks` component isn't read yet, so every rocket uses the
+/// duration of a rocket crafted with a single gunpowder.
+pub const DEFAULT_FLIGHT_DURATION: u8 = 1;
+
+/// A firework rocket attached to a player, pushing them forward while they
+/// glide until it bursts.
+///
+/// Vanilla: `FireworkRocketEntity`
+pub struct FireworkRocketEntity {
+    /// The network id of the entity.
+    pub id: i32,
+    /// The persistent id of the entity.
+    pub uuid: Uuid,
+    /// The position of the entity.
+    pub position: Vector3<f64>,
+    /// How many ticks the rocket has flown.
+    pub life: u8,
+    /// How many ticks the rocket flies before it bursts.
+    pub lifetime_ticks: u8,
+    /// The player the rocket is boosting.
+    pub attached_to_player: Option<Uuid>,
+    entity_data: FireworkRocketEntityData,
+}
+
+impl FireworkRocketEntity {
+    /// Creates a rocket boosting `player`, launched from `item`.
+    #[must_use]
+    pub fn attached(id: i32, player: &Player, item: &ItemStack, flight_duration: u8) -> Self {
+        let mut rocket = Self::at(id, *player.position.lock(), item, flight_duration);
+        // Clients make the rocket follow this entity and push it themselves
+        rocket
+            .entity_data
+            .attached_to_target
+            .set(Some(player.id.cast_unsigned()));
+        rocket.attached_to_player = Some(player.gameprofile.id);
+        rocket
+    }
+
+    /// Creates a rocket launched from `item` at `position`, attached to no
+    /// one. It bursts where it is once its flight is over.
+    #[must_use]
+    pub fn at(id: i32, position: Vector3<f64>, item: &ItemStack, flight_duration: u8) -> Self {
+        let mut item = item.clone();
+        item.set_count(1);
+        let mut entity_data = FireworkRocketEntityData::new();
+        entity_data.id_fireworks_item.set(item);
+
+        Self {
+            id,
+            uuid: Uuid::new_v4(),
+            position,
+            life: 0,
+            lifetime_ticks: lifetime_ticks(flight_duration),
+            attached_to_player: None,
+            entity_data,
+        }
+    }
+
+    /// Applies the rocket tags of `nbt`, like how long it has flown. Tags
+    /// that aren't set keep their current value. Loaded rockets are attached
+    /// to no one, like in vanilla.
+    ///
+    /// Vanilla: `FireworkRocketEntity.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(uuid) = entity::uuid_from_nbt(nbt) {
+            self.uuid = uuid;
+        }
+        if let Some(life) = nbt.int("Life") {
+            self.life = life.clamp(0, i32::from(u8::MAX)) as u8;
+        }
+        if let Some(lifetime) = nbt.int("LifeTime") {
+            self.lifetime_ticks = lifetime.clamp(0, i32::from(u8::MAX)) as u8;
+        }
+        if let Some(item) = nbt
+            .compound("FireworksItem")
+            .and_then(entity::item_from_nbt)
+        {
+            self.entity_data.id_fireworks_item.set(item);
+        }
+    }
+
+    /// The packet that adds this rocket to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        CAddEntity::new(
+            self.id,
+            self.uuid,
+            *REGISTRY
+                .entity_types
+                .get_id(vanilla_entities::FIREWORK_ROCKET) as i32,
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            0.0,
+            0.0,
+        )
+    }
+
+    /// The entity data clients need right after the rocket is added, like the
+    /// player it is attached to.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        self.entity_data.pack_all()
+    }
+
+    /// Advances the rocket by a tick, returning `false` once it burst.
+    ///
+    /// Vanilla: `FireworkRocketEntity.tick`
+    pub fn tick(&mut self, world: &World) -> bool {
+        if let Some(uuid) = self.attached_to_player {
+            let Some(player) = world.players.get_by_uuid(&uuid) else {
+                // The player left the world, there is nothing left to boost
+                self.explode(world);
+                return false;
+            };
+            if player.is_fall_flying() {
+                let (yaw, pitch) = player.rotation.load();
+                let look = Vector3::rotation_vector(f64::from(pitch), f64::from(yaw));
+                player.set_delta_movement(boost(player.get_delta_movement(), look));
+            }
+            self.position = *player.position.lock();
+        }
+
+        self.life = self.life.saturating_add(1);
+        if self.life > self.lifetime_ticks {
+            self.explode(world);
+            return false;
+        }
+        true
+    }
+
+    /// Detaches the rocket and lets it burst.
+    ///
+    /// Vanilla: `FireworkRocketEntity.explode`
+    fn explode(&mut self, world: &World) {
+        self.attached_to_player = None;
+        world.broadcast_to_trackers(
+            self.position,
+            CEntityEvent {
+                entity_id: self.id,
+                event: ENTITY_EVENT_FIREWORKS_EXPLODE,
+            },
+        );
+        // TODO: Rockets with firework stars hurt the attached player and
+        // entities within 5 blocks. That needs the explosions of the
+        // `fireworks` component and a way to damage players.
+    }
+}
+
+impl Entity for FireworkRocketEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
+impl StoredEntity for FireworkRocketEntity {
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        vanilla_entities::FIREWORK_ROCKET
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    /// Vanilla: `FireworkRocketEntity.addAdditionalSaveData`
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        entity::write_position(&mut nbt, self.position);
+        entity::write_uuid(&mut nbt, self.uuid);
+        nbt.insert("Life", i32::from(self.life));
+        nbt.insert("LifeTime", i32::from(self.lifetime_ticks));
+        let item = self.entity_data.id_fireworks_item.get();
+        if !item.is_empty() {
+            nbt.insert("FireworksItem", item.clone().to_nbt_tag());
+        }
+        Some(nbt)
+    }
+}
+
+/// Picks how many ticks a rocket flies, a little randomised so rockets
+/// launched together don't burst at once.
+fn lifetime_ticks(flight_duration: u8) -> u8 {
+    let lifetime =
+        10 * (u16::from(flight_duration) + 1) + rand::random_range(0..6) + rand::random_range(0..7);
+    u8::try_from(lifetime).unwrap_or(u8::MAX)
+}
+
+/// The velocity of a gliding player after a tick of boosting, pulling it
+/// towards 1.7 blocks per tick in the direction they look.
+fn boost(delta: Vector3<f64>, look: Vector3<f64>) -> Vector3<f64> {
+    let axis = |delta: f64, look: f64| delta + look * 0.1 + (look * 1.5 - delta) * 0.5;
+    Vector3::new(
+        axis(delta.x, look.x),
+        axis(delta.y, look.y),
+        axis(delta.z, look.z),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boost_approaches_top_speed() {
+        let look = Vector3::new(0.0, 0.0, 1.0);
+        let mut delta = Vector3::new(0.0, 0.0, 0.0);
+        for _ in 0..30 {
+            delta = boost(delta, look);
+        }
+        assert!((delta.z - 1.7).abs() < 1e-6);
+        assert!(delta.x.abs() < 1e-9 && delta.y.abs() < 1e-9);
+    }
+
+    #[test]
+    fn lifetime_grows_with_flight_duration() {
+        for _ in 0..100 {
+            assert!((20..=31).contains(&lifetime_ticks(1)));
+            assert!((40..=51).contains(&lifetime_ticks(3)));
+        }
+        assert_eq!(lifetime_ticks(u8::MAX), u8::MAX);
+    }
+}
@@ -0,0 +1,299 @@
+//! Entities without behaviour of their own.
+
+use crossbeam::atomic::AtomicCell;
+use simdnbt::ToNbtTag;
+use simdnbt::owned::{NbtCompound, NbtList};
+use steel_protocol::packets::game::CAddEntity;
+use steel_registry::REGISTRY;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::{DataValue, EntityData};
+use steel_registry::entity_types::EntityTypeRef;
+use steel_utils::locks::SyncMutex;
+use steel_utils::math::Vector3;
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, StoredEntity};
+use crate::world::World;
+
+// Serializer ids of the shared entity data, see `EntityDataSerializers`.
+const SERIALIZER_BYTE: i32 = 0;
+const SERIALIZER_OPTIONAL_COMPONENT: i32 = 6;
+const SERIALIZER_BOOLEAN: i32 = 8;
+
+/// The burning bit of the shared flags (Vanilla: `Entity.FLAG_ONFIRE`).
+const FLAG_ON_FIRE: i8 = 1;
+/// The invisible bit of the shared flags (Vanilla: `Entity.FLAG_INVISIBLE`).
+const FLAG_INVISIBLE: i8 = 1 << 5;
+/// The glowing bit of the shared flags (Vanilla: `Entity.FLAG_GLOWING`).
+const FLAG_GLOWING: i8 = 1 << 6;
+
+/// An entity that only has the state every entity shares, like its position
+/// and custom name. Every non-player entity is one of these until it gets
+/// behaviour of its own: it is shown to clients but doesn't move or tick.
+pub struct GenericEntity {
+    /// The network id of the entity.
+    pub id: i32,
+    /// The persistent id of the entity.
+    pub uuid: Uuid,
+    /// The type of the entity.
+    pub entity_type: EntityTypeRef,
+    /// The position of the entity.
+    pub position: SyncMutex<Vector3<f64>>,
+    /// The rotation of the entity (yaw, pitch).
+    pub rotation: AtomicCell<(f32, f32)>,
+    /// The name shown above the entity.
+    pub custom_name: Option<TextComponent>,
+    /// Whether the custom name is shown without looking at the entity.
+    pub custom_name_visible: bool,
+    /// Whether the entity makes no sounds.
+    pub silent: bool,
+    /// Whether the entity ignores gravity.
+    pub no_gravity: bool,
+    /// Whether the entity has an outline visible through blocks.
+    pub glowing: bool,
+    /// Whether the entity isn't rendered. Only the entities that save it, like
+    /// armor stands, load it from NBT.
+    pub invisible: bool,
+    /// Whether the entity is shown burning.
+    pub on_fire: bool,
+    /// The players riding the entity, the one steering it first.
+    pub passengers: Vec<Uuid>,
+}
+
+impl GenericEntity {
+    /// Creates an entity with a random UUID and default state.
+    #[must_use]
+    pub fn new(id: i32, entity_type: EntityTypeRef, position: Vector3<f64>) -> Self {
+        Self {
+            id,
+            uuid: Uuid::new_v4(),
+            entity_type,
+            position: SyncMutex::new(position),
+            rotation: AtomicCell::new((0.0, 0.0)),
+            custom_name: None,
+            custom_name_visible: false,
+            silent: false,
+            no_gravity: false,
+            glowing: false,
+            invisible: false,
+            on_fire: false,
+            passengers: Vec::new(),
+        }
+    }
+
+    /// Lets the player with `player_uuid` ride the entity. Returns false if
+    /// they already do. The world syncs the passengers to clients.
+    ///
+    /// Vanilla: `Entity.addPassenger`
+    pub fn add_passenger(&mut self, player_uuid: Uuid) -> bool {
+        if self.passengers.contains(&player_uuid) {
+            return false;
+        }
+        self.passengers.push(player_uuid);
+        true
+    }
+
+    /// Lets the player with `player_uuid` off the entity. Returns false if
+    /// they didn't ride it.
+    ///
+    /// Vanilla: `Entity.removePassenger`
+    pub fn remove_passenger(&mut self, player_uuid: Uuid) -> bool {
+        let count = self.passengers.len();
+        self.passengers
+            .retain(|passenger| *passenger != player_uuid);
+        self.passengers.len() != count
+    }
+
+    /// Returns the box the entity takes up, going by the size of its type.
+    #[must_use]
+    pub fn bounding_box(&self) -> AABBd {
+        let position = *self.position.lock();
+        let dimensions = self.entity_type.dimensions;
+        AABBd::entity_box(
+            position.x,
+            position.y,
+            position.z,
+            f64::from(dimensions.width) / 2.0,
+            f64::from(dimensions.height),
+        )
+    }
+
+    /// Applies the shared entity tags of `nbt`. Tags that aren't set keep
+    /// their current value, and the position is never loaded.
+    ///
+    /// Vanilla: `Entity.load`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(uuid) = entity::uuid_from_nbt(nbt) {
+            self.uuid = uuid;
+        }
+        if let Some(rotation) = nbt.list("Rotation").and_then(|list| list.floats())
+            && let [yaw, pitch] = *rotation
+        {
+            self.rotation.store((yaw, pitch.clamp(-90.0, 90.0)));
+        }
+        if let Some(name) = nbt.get("CustomName") {
+            self.custom_name = TextComponent::from_nbt(name);
+        }
+        if let Some(visible) = nbt.byte("CustomNameVisible") {
+            self.custom_name_visible = visible != 0;
+        }
+        if let Some(silent) = nbt.byte("Silent") {
+            self.silent = silent != 0;
+        }
+        if let Some(no_gravity) = nbt.byte("NoGravity") {
+            self.no_gravity = no_gravity != 0;
+        }
+        if let Some(glowing) = nbt.byte("Glowing") {
+            self.glowing = glowing != 0;
+        }
+    }
+
+    /// Saves the shared entity tags into `nbt`, the ones
+    /// [`read_nbt`](Self::read_nbt) loads along with the position.
+    ///
+    /// Vanilla: `Entity.saveWithoutId`
+    pub fn write_nbt(&self, nbt: &mut NbtCompound) {
+        entity::write_position(nbt, *self.position.lock());
+        let (yaw, pitch) = self.rotation.load();
+        nbt.insert("Rotation", NbtList::Float(vec![yaw, pitch]));
+        entity::write_uuid(nbt, self.uuid);
+        if let Some(name) = &self.custom_name {
+            nbt.insert("CustomName", name.clone().to_nbt_tag());
+        }
+        for (key, set) in [
+            ("CustomNameVisible", self.custom_name_visible),
+            ("Silent", self.silent),
+            ("NoGravity", self.no_gravity),
+            ("Glowing", self.glowing),
+        ] {
+            if set {
+                nbt.insert(key, 1i8);
+            }
+        }
+    }
+
+    /// The entity data that differs from the defaults, which clients need
+    /// right after the entity is added.
+    ///
+    /// Vanilla: `SynchedEntityData.getNonDefaultValues`
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = Vec::new();
+        let flags = self.shared_flags();
+        if flags != 0 {
+            values.push(DataValue {
+                index: 0,
+                serializer_id: SERIALIZER_BYTE,
+                value: EntityData::Byte(flags),
+            });
+        }
+        if let Some(name) = &self.custom_name {
+            values.push(DataValue {
+                index: 2,
+                serializer_id: SERIALIZER_OPTIONAL_COMPONENT,
+                value: EntityData::OptionalComponent(Some(Box::new(name.clone()))),
+            });
+        }
+        for (index, set) in [
+            (3, self.custom_name_visible),
+            (4, self.silent),
+            (5, self.no_gravity),
+        ] {
+            if set {
+                values.push(DataValue {
+                    index,
+                    serializer_id: SERIALIZER_BOOLEAN,
+                    value: EntityData::Boolean(true),
+                });
+            }
+        }
+        values
+    }
+
+    /// The shared flags byte of the entity data, telling clients whether the
+    /// entity burns, glows or is invisible.
+    ///
+    /// Vanilla: `Entity.DATA_SHARED_FLAGS_ID`
+    #[must_use]
+    pub fn shared_flags(&self) -> i8 {
+        let mut flags = 0;
+        if self.on_fire {
+            flags |= FLAG_ON_FIRE;
+        }
+        if self.invisible {
+            flags |= FLAG_INVISIBLE;
+        }
+        if self.glowing {
+            flags |= FLAG_GLOWING;
+        }
+        flags
+    }
+
+    /// The packet that adds this entity to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        let pos = *self.position.lock();
+        let (yaw, pitch) = self.rotation.load();
+        CAddEntity::new(
+            self.id,
+            self.uuid,
+            *REGISTRY.entity_types.get_id(self.entity_type) as i32,
+            pos.x,
+            pos.y,
+            pos.z,
+            yaw,
+            pitch,
+        )
+    }
+}
+
+impl Entity for GenericEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
+impl StoredEntity for GenericEntity {
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        *self.position.lock()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.write_nbt(&mut nbt);
+        Some(nbt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use steel_registry::vanilla_entities;
+
+    use super::*;
+
+    #[test]
+    fn passengers_get_on_once() {
+        let mut boat =
+            GenericEntity::new(1, vanilla_entities::OAK_BOAT, Vector3::new(0.0, 64.0, 0.0));
+        let player = Uuid::new_v4();
+        assert!(boat.add_passenger(player));
+        assert!(!boat.add_passenger(player));
+        assert_eq!(boat.passengers, vec![player]);
+        assert!(boat.remove_passenger(player));
+        assert!(!boat.remove_passenger(player));
+    }
+}
@@ -0,0 +1,476 @@
+//! Ghasts that float around and shoot fireballs at players they can see.
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, SoundSource};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entity_data::GhastEntityData;
+use steel_registry::{level_events, sound_events, vanilla_entities, vanilla_items};
+use steel_utils::math::Vector3;
+use uuid::Uuid;
+
+use crate::entity::ai::sight;
+use crate::entity::ghast_fireball::GhastFireballEntity;
+use crate::entity::mob::{self, MobBase};
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, AttackableMob, Entity, GenericEntity, Remains, StoredEntity};
+use crate::physics::{CollisionWorld, WorldCollisionProvider};
+use crate::player::Player;
+use crate::world::World;
+
+/// The health of a ghast.
+const MAX_HEALTH: f32 = 10.0;
+/// How many ticks a ghast can't be hurt again after it was hurt.
+const HURT_COOLDOWN: u32 = 10;
+
+/// How far a ghast floats off to, at the most, on each axis.
+const FLOAT_RANGE: f64 = 16.0;
+/// How far from its goal a ghast picks a new one.
+const FLOAT_GOAL_RESET_DISTANCE: f64 = 60.0;
+/// How much faster a ghast gets each time it pushes off towards its goal.
+const FLOAT_ACCELERATION: f64 = 0.1;
+
+/// How far away ghasts notice players.
+///
+/// Vanilla: `Attributes.FOLLOW_RANGE` of ghasts
+const FOLLOW_RANGE: f64 = 100.0;
+/// How many ticks a ghast charges up before it shoots.
+const CHARGE_TIME: i32 = 60;
+/// How many ticks a ghast waits after a shot before it charges again.
+const RELOAD_TIME: i32 = 40;
+/// How far in front of a ghast its fireballs come out.
+const MOUTH_DISTANCE: f64 = 4.0;
+
+/// The experience a ghast drops when a player kills it.
+///
+/// Vanilla: `Ghast.xpReward`
+pub const EXPERIENCE_REWARD: i32 = 5;
+/// How loud ghasts are, so they can be heard across the nether.
+///
+/// Vanilla: `Ghast.getSoundVolume`
+pub const SOUND_VOLUME: f32 = 5.0;
+
+/// A ghast, floating around at random until it sees a player and starts
+/// shooting fireballs at them.
+///
+/// Vanilla: `Ghast`
+pub struct GhastEntity {
+    /// The state every entity shares, like the id and custom name.
+    pub base: GenericEntity,
+    /// The player the ghast shoots at, if any.
+    pub target: Option<Uuid>,
+    mob: MobBase,
+    entity_data: GhastEntityData,
+    /// Where the ghast floats to, if anywhere.
+    float_goal: Option<Vector3<f64>>,
+    /// Ticks until the ghast pushes off towards its goal again.
+    float_cooldown: u32,
+    /// Ticks the ghast has charged up for while seeing its target, negative
+    /// while it waits after a shot.
+    charge: i32,
+    /// Ticks until the ghast can be hurt again.
+    hurt_cooldown: u32,
+    /// Grows each tick until the ghast cries out.
+    ///
+    /// Vanilla: `Mob.ambientSoundTime`
+    ambient_sound_time: i32,
+}
+
+impl GhastEntity {
+    /// Creates a ghast at `position` that floats in place.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        let mut entity_data = GhastEntityData::new();
+        entity_data.health.set(MAX_HEALTH);
+
+        Self {
+            base: GenericEntity::new(id, vanilla_entities::GHAST, position),
+            target: None,
+            mob: MobBase::new(position, vanilla_entities::GHAST),
+            entity_data,
+            float_goal: None,
+            float_cooldown: 0,
+            charge: 0,
+            hurt_cooldown: 0,
+            ambient_sound_time: 0,
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the health.
+    ///
+    /// Vanilla: `Ghast.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(health) = nbt.float("Health") {
+            self.entity_data.health.set(health.clamp(0.0, MAX_HEALTH));
+        }
+    }
+
+    /// Writes the health of the ghast into `nbt`.
+    ///
+    /// Vanilla: `LivingEntity.addAdditionalSaveData`
+    pub fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("Health", *self.entity_data.health.get());
+    }
+
+    /// Returns the position of the ghast.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.mob.position()
+    }
+
+    /// Returns the box the ghast takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.mob.bounding_box()
+    }
+
+    /// Returns true while the ghast charges up a shot, with its mouth open.
+    #[must_use]
+    pub fn is_charging(&self) -> bool {
+        *self.entity_data.is_charging.get()
+    }
+
+    /// The packet that adds this ghast to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the ghast is added, like
+    /// the health.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Adds `impulse` to the velocity of the ghast.
+    pub fn push(&mut self, impulse: Vector3<f64>) {
+        self.mob.push(impulse);
+    }
+
+    /// Returns true once the ghast has no health left.
+    #[must_use]
+    pub fn is_dead(&self) -> bool {
+        *self.entity_data.health.get() <= 0.0
+    }
+
+    /// Hurts the ghast by `amount`. Returns false if it was hurt too
+    /// recently to be hurt again.
+    ///
+    /// Vanilla: `Ghast.hurtServer`
+    pub fn hurt(&mut self, amount: f32) -> bool {
+        if self.hurt_cooldown > 0 || self.is_dead() {
+            return false;
+        }
+        self.hurt_cooldown = HURT_COOLDOWN;
+        let health = *self.entity_data.health.get();
+        self.entity_data.health.set((health - amount).max(0.0));
+        true
+    }
+
+    /// Advances the ghast by a tick: it cries now and then, floats around
+    /// and charges up shots at a player it can see, shrieking when it starts.
+    /// Returns the fireball it shot, if any.
+    ///
+    /// Vanilla: `Ghast.registerGoals` and `GhastMoveControl`
+    pub fn tick(&mut self, world: &World) -> Option<GhastFireballEntity> {
+        self.hurt_cooldown = self.hurt_cooldown.saturating_sub(1);
+        self.entity_data.shared_flags.set(self.base.shared_flags());
+        if self.is_dead() {
+            return None;
+        }
+        self.tick_ambient_sound(world);
+
+        let target = mob::find_target(world, self.position(), self.target, FOLLOW_RANGE);
+        self.target = target.as_ref().map(|player| player.gameprofile.id);
+        let fireball = target
+            .as_ref()
+            .and_then(|player| self.charge_at(world, player));
+        if target.is_none() {
+            self.charge = self.charge.clamp(-RELOAD_TIME, 0);
+        }
+        self.entity_data.is_charging.set(self.charge > 0);
+
+        // Ghasts face their target, or the way they float without one
+        let acceleration = self.float_around(world);
+        let facing = match &target {
+            Some(player) => player.position.lock().sub(&self.position()),
+            None => acceleration,
+        };
+        let yaw = if facing.x.hypot(facing.z) < 1.0e-7 {
+            self.base.rotation.load().0
+        } else {
+            mob::yaw_towards(facing.x, facing.z)
+        };
+        self.mob.fly(&self.base, world, acceleration, yaw);
+        fireball
+    }
+
+    /// Plays the ghast's cry now and then.
+    ///
+    /// Vanilla: `Mob.baseTick`
+    fn tick_ambient_sound(&mut self, world: &World) {
+        self.ambient_sound_time += 1;
+        if rand::random_range(0..1000) < self.ambient_sound_time {
+            self.ambient_sound_time = -80;
+            world.play_sound(
+                sound_events::ENTITY_GHAST_AMBIENT,
+                SoundSource::Hostile,
+                self.position().to_block_pos(),
+                SOUND_VOLUME,
+                1.0,
+                None,
+            );
+        }
+    }
+
+    /// Charges up a shot while `player` is in sight, shrieking when the
+    /// ghast starts. Returns the fireball once it is charged, and waits a
+    /// while before charging again.
+    ///
+    /// Vanilla: `GhastShootFireballGoal`
+    fn charge_at(&mut self, world: &World, player: &Player) -> Option<GhastFireballEntity> {
+        let position = self.position();
+        let eyes = Vector3::new(
+            position.x,
+            position.y + f64::from(vanilla_entities::GHAST.dimensions.eye_height),
+            position.z,
+        );
+        let target_pos = *player.position.lock();
+        let target_eyes = Vector3::new(
+            target_pos.x,
+            target_pos.y + player.eye_height(),
+            target_pos.z,
+        );
+        if !sight::has_line_of_sight(world, eyes, target_eyes) {
+            if self.charge > 0 {
+                self.charge -= 1;
+            }
+            return None;
+        }
+
+        self.charge += 1;
+        let pos = position.to_block_pos();
+        if self.charge == 1 {
+            world.level_event(level_events::SOUND_GHAST_WARNING, pos, 0, None);
+        }
+        if self.charge < CHARGE_TIME {
+            return None;
+        }
+
+        self.charge = -RELOAD_TIME;
+        world.level_event(level_events::SOUND_GHAST_FIREBALL, pos, 0, None);
+        let bounding_box = player.bounding_box();
+        let aim = Vector3::new(
+            target_pos.x,
+            (bounding_box.min_y + bounding_box.max_y) / 2.0,
+            target_pos.z,
+        );
+        Some(self.shoot_fireball(aim))
+    }
+
+    /// Shoots a fireball out of the ghast's mouth at `aim`.
+    ///
+    /// Vanilla: `GhastShootFireballGoal.tick`
+    fn shoot_fireball(&self, aim: Vector3<f64>) -> GhastFireballEntity {
+        let position = self.position();
+        let height = f64::from(vanilla_entities::GHAST.dimensions.height);
+        let mouth = Vector3::rotation_vector(
+            0.0,
+            f64::from(mob::yaw_towards(aim.x - position.x, aim.z - position.z)),
+        ) * MOUTH_DISTANCE;
+        let origin = Vector3::new(
+            position.x + mouth.x,
+            position.y + height / 2.0 + 0.5,
+            position.z + mouth.z,
+        );
+        GhastFireballEntity::new(
+            entity::next_entity_id(),
+            origin,
+            aim.sub(&origin),
+            Some(self.base.id),
+        )
+    }
+
+    /// Picks a random spot nearby to float to whenever the ghast has none,
+    /// and returns how much to speed up towards it this tick. Gives the spot
+    /// up if blocks are in the way.
+    ///
+    /// Vanilla: `RandomFloatAroundGoal` and `GhastMoveControl`
+    fn float_around(&mut self, world: &World) -> Vector3<f64> {
+        let position = self.position();
+        let needs_goal = self.float_goal.is_none_or(|goal| {
+            let distance = goal.sub(&position).length_squared();
+            !(1.0..=FLOAT_GOAL_RESET_DISTANCE * FLOAT_GOAL_RESET_DISTANCE).contains(&distance)
+        });
+        if needs_goal {
+            let offset = || rand::random_range(-FLOAT_RANGE..FLOAT_RANGE);
+            self.float_goal = Some(position.add_raw(offset(), offset(), offset()));
+        }
+
+        let stay = Vector3::new(0.0, 0.0, 0.0);
+        let Some(goal) = self.float_goal else {
+            return stay;
+        };
+        if self.float_cooldown > 0 {
+            self.float_cooldown -= 1;
+            return stay;
+        }
+        self.float_cooldown = rand::random_range(2..7);
+
+        let to_goal = goal.sub(&position);
+        let distance = to_goal.length();
+        if distance < 1.0e-7 {
+            return stay;
+        }
+        let direction = to_goal * (1.0 / distance);
+        if !self.can_reach(world, direction, distance.ceil() as u32) {
+            self.float_goal = None;
+            return stay;
+        }
+        direction * FLOAT_ACCELERATION
+    }
+
+    /// Returns true if the ghast can float `steps` blocks along `direction`
+    /// without bumping into a block.
+    ///
+    /// Vanilla: `GhastMoveControl.canReach`
+    fn can_reach(&self, world: &World, direction: Vector3<f64>, steps: u32) -> bool {
+        let collisions = WorldCollisionProvider::new(world);
+        let position = self.position();
+        let dimensions = vanilla_entities::GHAST.dimensions;
+        (1..steps).all(|step| {
+            let center = position + direction * f64::from(step);
+            let bounding_box = AABBd::entity_box(
+                center.x,
+                center.y,
+                center.z,
+                f64::from(dimensions.width) / 2.0,
+                f64::from(dimensions.height),
+            );
+            collisions.get_block_collisions(&bounding_box).is_empty()
+        })
+    }
+}
+
+impl Entity for GhastEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for GhastEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.base.write_nbt(&mut nbt);
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.base.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    fn explosion_hit(
+        &mut self,
+        world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.hurt_by_explosion(world, damage);
+        self.push(impulse);
+        None
+    }
+}
+
+impl AttackableMob for GhastEntity {
+    const SOUND_VOLUME: f32 = SOUND_VOLUME;
+
+    fn take_hit(&mut self, amount: f32) -> Option<i32> {
+        self.hurt(amount).then_some(sound_events::ENTITY_GHAST_HURT)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.is_dead()
+    }
+
+    fn death_sound(&self) -> i32 {
+        sound_events::ENTITY_GHAST_DEATH
+    }
+
+    fn experience_reward(&self) -> i32 {
+        EXPERIENCE_REWARD
+    }
+
+    fn death_drops(&self) -> Vec<ItemStack> {
+        death_drops()
+    }
+}
+
+/// The items a ghast drops when it dies: maybe a tear and some gunpowder.
+///
+/// Vanilla: `entities/ghast` loot table
+#[must_use]
+pub fn death_drops() -> Vec<ItemStack> {
+    let mut drops = Vec::new();
+    if rand::random_bool(0.5) {
+        drops.push(ItemStack::new(&vanilla_items::ITEMS.ghast_tear));
+    }
+    let gunpowder = rand::random_range(0..=2);
+    if gunpowder > 0 {
+        drops.push(ItemStack::with_count(
+            &vanilla_items::ITEMS.gunpowder,
+            gunpowder,
+        ));
+    }
+    drops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ghasts_drop_at_most_a_tear_and_two_gunpowder() {
+        for _ in 0..100 {
+            for item in death_drops() {
+                if item.is(&vanilla_items::ITEMS.ghast_tear) {
+                    assert_eq!(item.count, 1);
+                } else {
+                    assert!(item.is(&vanilla_items::ITEMS.gunpowder));
+                    assert!((1..=2).contains(&item.count));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! Fireballs ghasts shoot at players, blowing up where they land. Players
+//! can hit them back at the ghast.
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, CEntityPositionSync};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::{REGISTRY, vanilla_entities};
+use steel_utils::math::Vector3;
+use steel_utils::types::GameType;
+use uuid::Uuid;
+
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, StoredEntity, mob};
+use crate::world::World;
+
+/// How much faster a fireball gets each tick along the way it flies.
+///
+/// Vanilla: `AbstractHurtingProjectile.accelerationPower`
+pub const ACCELERATION: f64 = 0.1;
+/// How much of its speed a fireball keeps each tick.
+///
+/// Vanilla: `AbstractHurtingProjectile.getInertia`
+const INERTIA: f64 = 0.95;
+/// How much a fireball hurts what it hits, before the explosion.
+///
+/// Vanilla: `LargeFireball.onHitEntity`
+pub const DIRECT_DAMAGE: f32 = 6.0;
+/// How powerful the explosion of a fireball is unless told otherwise.
+pub const DEFAULT_EXPLOSION_POWER: u8 = 1;
+/// How many ticks a fireball flies before it burns out.
+const MAX_LIFETIME: u32 = 200;
+
+/// What a fireball did in a tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FireballFlight {
+    /// It flew on.
+    Flying,
+    /// It burnt out or left the world without hitting anything.
+    BurntOut,
+    /// It hit a block, a player or the ghast with the given network id, and
+    /// blows up.
+    Hit {
+        /// The ghast it hit, if any.
+        ghast: Option<i32>,
+    },
+}
+
+/// A fireball shot by a ghast, flying straight on until it hits something.
+///
+/// Vanilla: `LargeFireball`
+pub struct GhastFireballEntity {
+    /// The network id of the entity.
+    pub id: i32,
+    /// The persistent id of the entity.
+    pub uuid: Uuid,
+    position: Vector3<f64>,
+    velocity: Vector3<f64>,
+    /// Ticks the fireball has flown.
+    age: u32,
+    /// The ghast that shot the fireball, which it can't hit until it is
+    /// hit back.
+    owner: Option<i32>,
+    /// The player that hit the fireball back, if any.
+    deflected_by: Option<Uuid>,
+    /// How powerful the explosion is.
+    ///
+    /// Vanilla: `LargeFireball.explosionPower`
+    pub explosion_power: u8,
+}
+
+impl GhastFireballEntity {
+    /// Creates a fireball at `position` heading along `direction`, shot by
+    /// the ghast with network id `owner`.
+    ///
+    /// Vanilla: `AbstractHurtingProjectile.assignDirectionalMovement`
+    #[must_use]
+    pub fn new(
+        id: i32,
+        position: Vector3<f64>,
+        direction: Vector3<f64>,
+        owner: Option<i32>,
+    ) -> Self {
+        let velocity = if direction.length_squared() > 1.0e-7 {
+            direction.normalize() * ACCELERATION
+        } else {
+            Vector3::new(0.0, 0.0, 0.0)
+        };
+        Self {
+            id,
+            uuid: Uuid::new_v4(),
+            position,
+            velocity,
+            age: 0,
+            owner,
+            deflected_by: None,
+            explosion_power: DEFAULT_EXPLOSION_POWER,
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the explosion power.
+    ///
+    /// Vanilla: `LargeFireball.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(uuid) = entity::uuid_from_nbt(nbt) {
+            self.uuid = uuid;
+        }
+        if let Some(velocity) = entity::motion_from_nbt(nbt) {
+            self.velocity = velocity;
+        }
+        if let Some(power) = nbt.byte("ExplosionPower") {
+            self.explosion_power = power.max(0) as u8;
+        }
+    }
+
+    /// Returns the position of the fireball.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.position
+    }
+
+    /// Returns the velocity of the fireball.
+    #[must_use]
+    pub const fn velocity(&self) -> Vector3<f64> {
+        self.velocity
+    }
+
+    /// Returns the player that hit the fireball back, if any.
+    #[must_use]
+    pub const fn deflected_by(&self) -> Option<Uuid> {
+        self.deflected_by
+    }
+
+    /// Returns the box the fireball takes up.
+    #[must_use]
+    pub fn bounding_box(&self) -> AABBd {
+        let dimensions = vanilla_entities::FIREBALL.dimensions;
+        AABBd::entity_box(
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            f64::from(dimensions.width) / 2.0,
+            f64::from(dimensions.height),
+        )
+    }
+
+    /// The packet that adds this fireball to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        CAddEntity::new(
+            self.id,
+            self.uuid,
+            *REGISTRY.entity_types.get_id(vanilla_entities::FIREBALL) as i32,
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            0.0,
+            0.0,
+        )
+    }
+
+    /// Sends the fireball back the way it came, after `player` hit it. It can
+    /// hit the ghast that shot it from then on.
+    ///
+    /// Vanilla: `AbstractHurtingProjectile.hurtServer`, which sends it where
+    /// the player looks instead
+    pub fn deflect(&mut self, player: Uuid) {
+        self.velocity = -self.velocity;
+        self.deflected_by = Some(player);
+        self.age = 0;
+    }
+
+    /// Advances the fireball by a tick, flying it forward and speeding it up
+    /// until it hits a block, a player or one of the `ghasts`, given by
+    /// network id and box.
+    ///
+    /// Vanilla: `AbstractHurtingProjectile.tick`
+    pub fn tick(&mut self, world: &World, ghasts: &[(i32, AABBd)]) -> FireballFlight {
+        self.age += 1;
+        self.position += self.velocity;
+        if self.velocity.length_squared() > 1.0e-7 {
+            self.velocity = (self.velocity + self.velocity.normalize() * ACCELERATION) * INERTIA;
+        }
+
+        let bounding_box = self.bounding_box();
+        let ghast = ghasts
+            .iter()
+            .find(|(id, ghast)| {
+                (self.deflected_by.is_some() || self.owner != Some(*id))
+                    && ghast.intersects(&bounding_box)
+            })
+            .map(|(id, _)| *id);
+        let pos = self.position.to_block_pos();
+        if ghast.is_some()
+            || !world.get_block_state(&pos).get_collision_shape().is_empty()
+            || self.hit_player(world, &bounding_box)
+        {
+            return FireballFlight::Hit { ghast };
+        }
+        if self.age >= MAX_LIFETIME || !world.is_in_valid_bounds(&pos) {
+            return FireballFlight::BurntOut;
+        }
+
+        world.broadcast_to_trackers(
+            self.position,
+            CEntityPositionSync {
+                entity_id: self.id,
+                x: self.position.x,
+                y: self.position.y,
+                z: self.position.z,
+                velocity_x: self.velocity.x,
+                velocity_y: self.velocity.y,
+                velocity_z: self.velocity.z,
+                yaw: 0.0,
+                pitch: 0.0,
+                on_ground: false,
+            },
+        );
+        FireballFlight::Flying
+    }
+
+    /// Hurts the first player in `bounding_box`, other than the one who hit
+    /// the fireball back. Returns true if there was one.
+    ///
+    /// Vanilla: `LargeFireball.onHitEntity`
+    fn hit_player(&self, world: &World, bounding_box: &AABBd) -> bool {
+        let mut hit = false;
+        world.players.iter_players(|_, player| {
+            hit = player.game_mode.load() != GameType::Spectator
+                && self.deflected_by != Some(player.gameprofile.id)
+                && player.bounding_box().intersects(bounding_box);
+            if hit && let Some(damage) = mob::player_damage(DIRECT_DAMAGE, world.difficulty()) {
+                player.take_damage(damage);
+            }
+            !hit
+        });
+        hit
+    }
+}
+
+impl Entity for GhastFireballEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
+impl StoredEntity for GhastFireballEntity {
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        vanilla_entities::FIREBALL
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.send(self.add_entity_packet());
+    }
+
+    /// Vanilla: `LargeFireball.addAdditionalSaveData`
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        entity::write_position(&mut nbt, self.position);
+        entity::write_uuid(&mut nbt, self.uuid);
+        entity::write_motion(&mut nbt, self.velocity);
+        nbt.insert("ExplosionPower", self.explosion_power as i8);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.id).then(|| self.bounding_box())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hitting_a_fireball_sends_it_back() {
+        let mut fireball = GhastFireballEntity::new(
+            1,
+            Vector3::new(0.0, 64.0, 0.0),
+            Vector3::new(3.0, 0.0, 4.0),
+            Some(2),
+        );
+        let velocity = fireball.velocity();
+        assert!((velocity.length() - ACCELERATION).abs() < 1e-9);
+        assert!(fireball.deflected_by().is_none());
+
+        let player = Uuid::new_v4();
+        fireball.deflect(player);
+        assert_eq!(fireball.velocity(), -velocity);
+        assert_eq!(fireball.deflected_by(), Some(player));
+    }
+}
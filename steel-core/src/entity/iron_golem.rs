@@ -0,0 +1,625 @@
+//! Iron golems that guard villages, fighting the monsters around them.
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{
+    CAddEntity, CEntityEvent, ENTITY_EVENT_OFFER_FLOWER, ENTITY_EVENT_START_ATTACKING,
+    ENTITY_EVENT_STOP_OFFER_FLOWER, SoundSource,
+};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entity_data::IronGolemEntityData;
+use steel_registry::{sound_events, vanilla_entities, vanilla_items};
+use steel_utils::math::Vector3;
+use uuid::Uuid;
+
+use crate::entity::mob::{self, MobBase};
+use crate::entity::storage::EntityPairing;
+use crate::entity::{AttackableMob, Entity, GenericEntity, Remains, StoredEntity};
+use crate::player::Player;
+use crate::world::World;
+
+/// The health of an iron golem.
+pub const MAX_HEALTH: f32 = 100.0;
+/// How far iron golems walk in a tick.
+const WALK_SPEED: f64 = 0.15;
+
+/// How far away iron golems notice monsters.
+const FOLLOW_RANGE: f64 = 16.0;
+/// How many ticks pass between finding new paths to the target.
+const PATH_INTERVAL: u32 = 20;
+/// How close an iron golem gets to its target before hitting it.
+const ATTACK_RANGE: f64 = 2.0;
+/// How many ticks pass between hits.
+const ATTACK_INTERVAL: u32 = 10;
+/// The attack damage of an iron golem. A hit deals half of it plus up to
+/// all of it again.
+///
+/// Vanilla: `Attributes.ATTACK_DAMAGE` of iron golems
+const ATTACK_DAMAGE: f32 = 15.0;
+/// How fast a hit flings the target up.
+const FLING_SPEED: f64 = 0.4;
+/// How many ticks an iron golem can't be hurt again after it was hurt.
+const HURT_COOLDOWN: u32 = 10;
+
+/// How far from its village center an iron golem patrols.
+const PATROL_RADIUS: i32 = 32;
+/// The chance each tick that an idle iron golem walks somewhere else.
+const PATROL_CHANCE: f64 = 1.0 / 120.0;
+
+/// How close a baby villager has to be for an iron golem to offer it a
+/// poppy.
+const OFFER_FLOWER_RANGE: f64 = 6.0;
+/// The chance each tick that an iron golem offers a poppy by day.
+const OFFER_FLOWER_CHANCE: f64 = 1.0 / 8000.0;
+/// How many ticks an iron golem holds out a poppy.
+const OFFER_FLOWER_TICKS: u32 = 400;
+
+/// The flag set on iron golems players built.
+const PLAYER_CREATED_FLAG: i8 = 0x01;
+/// The day time at which the sun sets.
+const SUNSET: i64 = 12_000;
+
+/// How many ticks pass between iron golems spawning in villages.
+pub const SPAWN_INTERVAL: u64 = 700;
+/// How far around a villager other villagers count as its village.
+pub const VILLAGE_RADIUS: f64 = 32.0;
+/// How many villagers a village needs before iron golems spawn in it.
+///
+/// Vanilla (before 1.14): `Village.getNumVillagers() >= 10`
+pub const MIN_VILLAGERS: usize = 10;
+/// How many villagers a village needs for each of its iron golems.
+pub const VILLAGERS_PER_GOLEM: usize = 10;
+/// How far from the village center iron golems spawn.
+const SPAWN_DISTANCE: i32 = 8;
+/// How far above and below the village center iron golems spawn.
+const SPAWN_HEIGHT: i32 = 6;
+
+/// How damaged an iron golem looks. Clients draw the cracks from its health.
+///
+/// Vanilla: `Crackiness.Level`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Crackiness {
+    /// No cracks, above three quarters of its health.
+    None,
+    /// A few cracks, above half of its health.
+    Low,
+    /// Cracked, above a quarter of its health.
+    Medium,
+    /// Badly cracked.
+    High,
+}
+
+/// Returns how cracked an iron golem with `health` out of `max_health` looks.
+///
+/// Vanilla: `Crackiness.byFraction`
+#[must_use]
+pub fn crackiness(health: f32, max_health: f32) -> Crackiness {
+    let fraction = health / max_health;
+    if fraction < 0.25 {
+        Crackiness::High
+    } else if fraction < 0.5 {
+        Crackiness::Medium
+    } else if fraction < 0.75 {
+        Crackiness::Low
+    } else {
+        Crackiness::None
+    }
+}
+
+/// What an iron golem goes after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GolemTarget {
+    /// A player that hit it.
+    Player(Uuid),
+    /// The monster with this network id.
+    Monster(i32),
+}
+
+/// A hit an iron golem landed this tick, for the world to deal out.
+#[derive(Debug, Clone, Copy)]
+pub struct GolemAttack {
+    /// Who was hit.
+    pub target: GolemTarget,
+    /// The damage of the hit, before the difficulty scales it for players.
+    pub damage: f32,
+    /// The push that flings the target up.
+    pub fling: Vector3<f64>,
+}
+
+/// An iron golem, fighting the monsters around its village.
+///
+/// Vanilla: `IronGolem`
+pub struct IronGolemEntity {
+    /// The state every entity shares, like the id and custom name.
+    pub base: GenericEntity,
+    /// What the iron golem goes after, if anything.
+    pub target: Option<GolemTarget>,
+    /// The center of the village the iron golem patrols.
+    pub home: Vector3<f64>,
+    mob: MobBase,
+    entity_data: IronGolemEntityData,
+    /// Ticks until the iron golem may hit its target again.
+    attack_cooldown: u32,
+    /// Ticks until the iron golem can be hurt again.
+    hurt_cooldown: u32,
+    /// Ticks the iron golem keeps holding out a poppy for.
+    offer_flower_ticks: u32,
+}
+
+impl IronGolemEntity {
+    /// Creates an iron golem at `position`, guarding the village around it.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        let mut entity_data = IronGolemEntityData::new();
+        entity_data.health.set(MAX_HEALTH);
+
+        Self {
+            base: GenericEntity::new(id, vanilla_entities::IRON_GOLEM, position),
+            target: None,
+            home: position,
+            mob: MobBase::new(position, vanilla_entities::IRON_GOLEM),
+            entity_data,
+            attack_cooldown: 0,
+            hurt_cooldown: 0,
+            offer_flower_ticks: 0,
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the health and whether a player
+    /// built it.
+    ///
+    /// Vanilla: `IronGolem.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(health) = nbt.float("Health") {
+            self.entity_data.health.set(health.clamp(0.0, MAX_HEALTH));
+        }
+        if let Some(player_created) = nbt.byte("PlayerCreated") {
+            self.set_player_created(player_created != 0);
+        }
+    }
+
+    /// Writes the health and whether a player built the iron golem into
+    /// `nbt`.
+    ///
+    /// Vanilla: `IronGolem.addAdditionalSaveData`
+    pub fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("Health", *self.entity_data.health.get());
+        let player_created = self.entity_data.flags.get() & PLAYER_CREATED_FLAG != 0;
+        nbt.insert("PlayerCreated", i8::from(player_created));
+    }
+
+    /// Marks the iron golem as built by a player.
+    pub fn set_player_created(&mut self, player_created: bool) {
+        let flags = *self.entity_data.flags.get();
+        self.entity_data.flags.set(if player_created {
+            flags | PLAYER_CREATED_FLAG
+        } else {
+            flags & !PLAYER_CREATED_FLAG
+        });
+    }
+
+    /// Returns the position of the iron golem.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.mob.position()
+    }
+
+    /// Returns the box the iron golem takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.mob.bounding_box()
+    }
+
+    /// The packet that adds this iron golem to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the iron golem is added.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Returns true once the iron golem has no health left.
+    #[must_use]
+    pub fn is_dead(&self) -> bool {
+        *self.entity_data.health.get() <= 0.0
+    }
+
+    /// Returns how cracked the iron golem looks.
+    #[must_use]
+    pub fn crackiness(&self) -> Crackiness {
+        crackiness(*self.entity_data.health.get(), MAX_HEALTH)
+    }
+
+    /// Hurts the iron golem by `amount`. Returns false if it was hurt too
+    /// recently to be hurt again.
+    ///
+    /// Vanilla: `LivingEntity.hurtServer`
+    pub fn hurt(&mut self, amount: f32) -> bool {
+        if self.hurt_cooldown > 0 || self.is_dead() {
+            return false;
+        }
+        self.hurt_cooldown = HURT_COOLDOWN;
+        let health = *self.entity_data.health.get();
+        self.entity_data.health.set((health - amount).max(0.0));
+        true
+    }
+
+    /// The sound the iron golem makes when it is hurt, cracking further if
+    /// it looked less cracked before the hit.
+    ///
+    /// Vanilla: `IronGolem.hurtServer`
+    #[must_use]
+    pub fn hurt_sound(&self, before: Crackiness) -> i32 {
+        if self.crackiness() > before {
+            sound_events::ENTITY_IRON_GOLEM_DAMAGE
+        } else {
+            sound_events::ENTITY_IRON_GOLEM_HURT
+        }
+    }
+
+    /// Advances the iron golem by a tick: it goes after its target, or the
+    /// nearest of the `monsters` (network id and position), and patrols its
+    /// village without one. Idle iron golems sometimes hold out a poppy to
+    /// one of the `baby_villagers`. Returns the hit it landed, if any.
+    ///
+    /// Vanilla: `IronGolem.registerGoals`
+    // TODO: Go after players that hurt villagers once villagers remember
+    // them, and only after monsters attacking villagers once monsters do
+    pub fn tick(
+        &mut self,
+        world: &World,
+        monsters: &[(i32, Vector3<f64>)],
+        baby_villagers: &[Vector3<f64>],
+    ) -> Option<GolemAttack> {
+        self.attack_cooldown = self.attack_cooldown.saturating_sub(1);
+        self.hurt_cooldown = self.hurt_cooldown.saturating_sub(1);
+        if self.mob.tick_fire() {
+            self.hurt(1.0);
+        }
+        self.base.on_fire = self.mob.is_on_fire();
+        self.entity_data.shared_flags.set(self.base.shared_flags());
+        if self.is_dead() {
+            return None;
+        }
+
+        let position = self.position();
+        let target = self.find_target(world, monsters);
+        self.target = target.map(|(target, _)| target);
+        let attack = match target {
+            Some((target, target_pos)) => {
+                self.stop_offering_flower(world);
+                self.mob
+                    .move_to(world, target_pos.to_block_pos(), PATH_INTERVAL);
+                if self.attack_cooldown == 0
+                    && target_pos.sub(&position).length_squared() <= ATTACK_RANGE * ATTACK_RANGE
+                {
+                    Some(self.attack(world, target))
+                } else {
+                    None
+                }
+            }
+            None => {
+                self.patrol(world);
+                self.offer_flower(world, baby_villagers);
+                None
+            }
+        };
+        self.mob.walk(&self.base, world, WALK_SPEED);
+        attack
+    }
+
+    /// Keeps the current target while it is around, or picks the nearest
+    /// monster otherwise. Returns it with its position.
+    ///
+    /// Vanilla: `HurtByTargetGoal` and `NearestAttackableTargetGoal`
+    fn find_target(
+        &self,
+        world: &World,
+        monsters: &[(i32, Vector3<f64>)],
+    ) -> Option<(GolemTarget, Vector3<f64>)> {
+        let position = self.position();
+        let in_range = |target: Vector3<f64>| {
+            target.sub(&position).length_squared() <= FOLLOW_RANGE * FOLLOW_RANGE
+        };
+
+        match self.target {
+            Some(GolemTarget::Player(uuid)) => {
+                if let Some(player) = world.players.get_by_uuid(&uuid)
+                    && mob::is_targetable(&player)
+                {
+                    let player_pos = *player.position.lock();
+                    if in_range(player_pos) {
+                        return Some((GolemTarget::Player(uuid), player_pos));
+                    }
+                }
+            }
+            Some(GolemTarget::Monster(id)) => {
+                if let Some(&(_, monster_pos)) = monsters.iter().find(|(other, _)| *other == id)
+                    && in_range(monster_pos)
+                {
+                    return Some((GolemTarget::Monster(id), monster_pos));
+                }
+            }
+            None => {}
+        }
+
+        monsters
+            .iter()
+            .filter(|(_, monster_pos)| in_range(*monster_pos))
+            .min_by(|(_, a), (_, b)| {
+                a.sub(&position)
+                    .length_squared()
+                    .total_cmp(&b.sub(&position).length_squared())
+            })
+            .map(|&(id, monster_pos)| (GolemTarget::Monster(id), monster_pos))
+    }
+
+    /// Swings at `target`, rolling the damage of the hit.
+    ///
+    /// Vanilla: `IronGolem.doHurtTarget`
+    fn attack(&mut self, world: &World, target: GolemTarget) -> GolemAttack {
+        self.attack_cooldown = ATTACK_INTERVAL;
+        world.broadcast_to_trackers(
+            self.position(),
+            CEntityEvent {
+                entity_id: self.base.id,
+                event: ENTITY_EVENT_START_ATTACKING,
+            },
+        );
+        world.play_sound(
+            sound_events::ENTITY_IRON_GOLEM_ATTACK,
+            SoundSource::Neutral,
+            self.position().to_block_pos(),
+            1.0,
+            1.0,
+            None,
+        );
+        GolemAttack {
+            target,
+            damage: roll_damage(rand::random_range(0.0..1.0)),
+            fling: Vector3::new(0.0, FLING_SPEED, 0.0),
+        }
+    }
+
+    /// Walks to random spots around the village center now and then.
+    ///
+    /// Vanilla: `GolemRandomStrollInVillageGoal` and `MoveBackToVillageGoal`
+    fn patrol(&mut self, world: &World) {
+        if self.mob.has_path() || !rand::random_bool(PATROL_CHANCE) {
+            return;
+        }
+        let goal = self.home.to_block_pos().offset(
+            rand::random_range(-PATROL_RADIUS..=PATROL_RADIUS),
+            0,
+            rand::random_range(-PATROL_RADIUS..=PATROL_RADIUS),
+        );
+        self.mob.move_to(world, goal, PATH_INTERVAL);
+    }
+
+    /// Holds out a poppy by day to a baby villager close by now and then,
+    /// and puts it away again after a while.
+    ///
+    /// Vanilla: `OfferFlowerGoal`
+    // TODO: Let the baby villager take the poppy once villagers walk up to
+    // iron golems
+    fn offer_flower(&mut self, world: &World, baby_villagers: &[Vector3<f64>]) {
+        if self.offer_flower_ticks > 0 {
+            self.offer_flower_ticks -= 1;
+            if self.offer_flower_ticks == 0 {
+                world.broadcast_to_trackers(
+                    self.position(),
+                    CEntityEvent {
+                        entity_id: self.base.id,
+                        event: ENTITY_EVENT_STOP_OFFER_FLOWER,
+                    },
+                );
+            }
+            return;
+        }
+
+        let position = self.position();
+        let is_day = world.level_data.read().day_time().rem_euclid(24_000) < SUNSET;
+        if is_day
+            && rand::random_bool(OFFER_FLOWER_CHANCE)
+            && baby_villagers.iter().any(|villager| {
+                villager.sub(&position).length_squared() <= OFFER_FLOWER_RANGE * OFFER_FLOWER_RANGE
+            })
+        {
+            self.offer_flower_ticks = OFFER_FLOWER_TICKS;
+            world.broadcast_to_trackers(
+                self.position(),
+                CEntityEvent {
+                    entity_id: self.base.id,
+                    event: ENTITY_EVENT_OFFER_FLOWER,
+                },
+            );
+        }
+    }
+
+    /// Puts the poppy away if the iron golem holds one out.
+    fn stop_offering_flower(&mut self, world: &World) {
+        if self.offer_flower_ticks > 0 {
+            self.offer_flower_ticks = 0;
+            world.broadcast_to_trackers(
+                self.position(),
+                CEntityEvent {
+                    entity_id: self.base.id,
+                    event: ENTITY_EVENT_STOP_OFFER_FLOWER,
+                },
+            );
+        }
+    }
+}
+
+impl Entity for IronGolemEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for IronGolemEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.base.write_nbt(&mut nbt);
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.base.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    fn explosion_hit(
+        &mut self,
+        world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.hurt_by_explosion(world, damage);
+        self.mob.push(impulse);
+        None
+    }
+}
+
+impl AttackableMob for IronGolemEntity {
+    const SOUND_SOURCE: SoundSource = SoundSource::Neutral;
+
+    fn take_hit(&mut self, amount: f32) -> Option<i32> {
+        let before = self.crackiness();
+        self.hurt(amount).then(|| self.hurt_sound(before))
+    }
+
+    fn is_dead(&self) -> bool {
+        self.is_dead()
+    }
+
+    fn retaliate(&mut self, _world: &World, player: &Player) {
+        self.target = Some(GolemTarget::Player(player.gameprofile.id));
+    }
+
+    fn death_sound(&self) -> i32 {
+        sound_events::ENTITY_IRON_GOLEM_DEATH
+    }
+
+    fn experience_reward(&self) -> i32 {
+        0
+    }
+
+    fn death_drops(&self) -> Vec<ItemStack> {
+        death_drops()
+    }
+}
+
+/// The damage of an iron golem's hit for a `roll` between 0 and 1: half of
+/// its attack damage plus up to all of it again, so 7.5 to 21.5.
+///
+/// Vanilla: `IronGolem.doHurtTarget`
+#[must_use]
+pub fn roll_damage(roll: f64) -> f32 {
+    let bonus = (roll * f64::from(ATTACK_DAMAGE)).floor() as f32;
+    ATTACK_DAMAGE / 2.0 + bonus.min(ATTACK_DAMAGE - 1.0)
+}
+
+/// The items an iron golem drops when it dies: a few iron ingots and maybe
+/// some poppies.
+///
+/// Vanilla: `entities/iron_golem` loot table
+#[must_use]
+pub fn death_drops() -> Vec<ItemStack> {
+    let mut drops = vec![ItemStack::with_count(
+        &vanilla_items::ITEMS.iron_ingot,
+        rand::random_range(3..=5),
+    )];
+    let poppies = rand::random_range(0..=2);
+    if poppies > 0 {
+        drops.push(ItemStack::with_count(&vanilla_items::ITEMS.poppy, poppies));
+    }
+    drops
+}
+
+/// Picks a random spot around `center` where an iron golem could spawn:
+/// open for its body and on a solid block. Returns `None` if the spot
+/// picked isn't one.
+///
+/// Vanilla: `SpawnUtil.trySpawnMob`
+#[must_use]
+pub fn natural_spawn_position(world: &World, center: Vector3<f64>) -> Option<Vector3<f64>> {
+    let center = center.to_block_pos();
+    let dx = rand::random_range(-SPAWN_DISTANCE..=SPAWN_DISTANCE);
+    let dz = rand::random_range(-SPAWN_DISTANCE..=SPAWN_DISTANCE);
+
+    let is_open = |pos| world.get_block_state(&pos).get_collision_shape().is_empty();
+    (-SPAWN_HEIGHT..=SPAWN_HEIGHT)
+        .rev()
+        .map(|dy| center.offset(dx, dy, dz))
+        .find(|&pos| {
+            (0..3).all(|dy| is_open(pos.offset(0, dy, 0)))
+                && !is_open(pos.offset(0, -1, 0))
+                && !world.get_block_state(&pos).get_block().config.liquid
+        })
+        .map(|pos| {
+            Vector3::new(
+                f64::from(pos.x()) + 0.5,
+                f64::from(pos.y()),
+                f64::from(pos.z()) + 0.5,
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cracks_as_health_drops() {
+        assert_eq!(crackiness(100.0, MAX_HEALTH), Crackiness::None);
+        assert_eq!(crackiness(75.0, MAX_HEALTH), Crackiness::None);
+        assert_eq!(crackiness(60.0, MAX_HEALTH), Crackiness::Low);
+        assert_eq!(crackiness(50.0, MAX_HEALTH), Crackiness::Low);
+        assert_eq!(crackiness(49.0, MAX_HEALTH), Crackiness::Medium);
+        assert_eq!(crackiness(10.0, MAX_HEALTH), Crackiness::High);
+        assert!(Crackiness::High > Crackiness::Low);
+    }
+
+    #[test]
+    fn hits_deal_seven_and_a_half_to_twenty_one_and_a_half() {
+        assert!((roll_damage(0.0) - 7.5).abs() < f32::EPSILON);
+        assert!((roll_damage(0.999) - 21.5).abs() < f32::EPSILON);
+        assert!((roll_damage(1.0) - 21.5).abs() < f32::EPSILON);
+    }
+}
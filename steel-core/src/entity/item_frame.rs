@@ -0,0 +1,410 @@
+//! Item frames showing an item on a block face.
+
+use std::ptr;
+
+use simdnbt::ToNbtTag;
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_protocol::packets::game::{CAddEntity, SoundSource};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::Direction;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entity_data::ItemFrameEntityData;
+use steel_registry::{REGISTRY, sound_events, vanilla_blocks, vanilla_entities, vanilla_items};
+use steel_utils::BlockPos;
+use steel_utils::math::{Axis, Vector3};
+use uuid::Uuid;
+
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, Remains, StoredEntity};
+use crate::physics::{CollisionWorld, WorldCollisionProvider, join_is_not_empty};
+use crate::world::World;
+
+/// How many steps of 45° the item in a frame can be turned.
+pub const ROTATIONS: u8 = 8;
+
+/// How far a frame sticks out of the block it hangs on.
+const THICKNESS: f64 = 0.0625;
+/// The width and height of a frame.
+const SIZE: f64 = 0.75;
+
+/// An item frame, or a glow item frame, attached to a block face.
+///
+/// Vanilla: `ItemFrame`
+pub struct ItemFrameEntity {
+    /// The network id of the entity.
+    pub id: i32,
+    /// The persistent id of the entity.
+    pub uuid: Uuid,
+    /// The block the frame hangs in, in front of the face it is attached to.
+    pub pos: BlockPos,
+    /// The direction the frame faces, away from the block it is attached to.
+    pub direction: Direction,
+    /// Whether this is a glow item frame, which renders its item at full
+    /// brightness.
+    pub glowing: bool,
+    // Glow item frames sync the same data under the same indices
+    entity_data: ItemFrameEntityData,
+}
+
+impl ItemFrameEntity {
+    /// Creates an empty frame hanging in `pos`, facing `direction`.
+    #[must_use]
+    pub fn new(id: i32, pos: BlockPos, direction: Direction, glowing: bool) -> Self {
+        let mut entity_data = ItemFrameEntityData::new();
+        entity_data.direction.set(direction);
+
+        Self {
+            id,
+            uuid: Uuid::new_v4(),
+            pos,
+            direction,
+            glowing,
+            entity_data,
+        }
+    }
+
+    /// Returns the item shown in the frame, empty if there is none.
+    #[must_use]
+    pub fn item(&self) -> &ItemStack {
+        self.entity_data.item.get()
+    }
+
+    /// Puts `item` in the frame, replacing the one it showed, and resets its
+    /// rotation.
+    pub fn set_item(&mut self, item: ItemStack) {
+        self.entity_data.item.set(item);
+        self.entity_data.rotation.set(0);
+    }
+
+    /// Returns how many steps of 45° the item is turned clockwise.
+    #[must_use]
+    pub fn item_rotation(&self) -> u8 {
+        u8::try_from(*self.entity_data.rotation.get()).unwrap_or(0)
+    }
+
+    /// Turns the item by another 45°, back to upright after a full turn.
+    pub fn rotate_item(&mut self) {
+        let rotation = (self.item_rotation() + 1) % ROTATIONS;
+        self.entity_data.rotation.set(i32::from(rotation));
+    }
+
+    /// Applies the tags of `nbt`, like the block the frame hangs in, the
+    /// item it shows and its rotation.
+    ///
+    /// Vanilla: `ItemFrame.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(uuid) = entity::uuid_from_nbt(nbt) {
+            self.uuid = uuid;
+        }
+        if let Some(&[x, y, z]) = nbt.int_array("block_pos") {
+            self.pos = BlockPos::new(x, y, z);
+        }
+        if let Some(direction) = nbt.byte("Facing").and_then(direction_from_nbt) {
+            self.direction = direction;
+            self.entity_data.direction.set(direction);
+        }
+        if let Some(item) = nbt.compound("Item").and_then(entity::item_from_nbt) {
+            self.entity_data.item.set(item);
+        }
+        if let Some(rotation) = nbt.byte("ItemRotation") {
+            let rotation = i32::from(rotation).rem_euclid(i32::from(ROTATIONS));
+            self.entity_data.rotation.set(rotation);
+        }
+    }
+
+    /// The item dropped when the frame itself breaks.
+    #[must_use]
+    pub fn frame_item(&self) -> ItemStack {
+        if self.glowing {
+            ItemStack::new(&vanilla_items::ITEMS.glow_item_frame)
+        } else {
+            ItemStack::new(&vanilla_items::ITEMS.item_frame)
+        }
+    }
+
+    /// The packet that adds this frame to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        let entity_type = if self.glowing {
+            vanilla_entities::GLOW_ITEM_FRAME
+        } else {
+            vanilla_entities::ITEM_FRAME
+        };
+        let mut packet = CAddEntity::new(
+            self.id,
+            self.uuid,
+            *REGISTRY.entity_types.get_id(entity_type) as i32,
+            f64::from(self.pos.x()),
+            f64::from(self.pos.y()),
+            f64::from(self.pos.z()),
+            0.0,
+            0.0,
+        );
+        // Clients read the direction from the data instead of the rotation
+        packet.data = self.direction as i32;
+        packet
+    }
+
+    /// The entity data clients need right after the frame is added, like the
+    /// item it shows.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        self.entity_data.pack_all()
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Returns the box the frame takes up in front of the block face.
+    ///
+    /// Vanilla: `ItemFrame.calculateBoundingBox`
+    #[must_use]
+    pub fn bounding_box(&self) -> AABBd {
+        let (dx, dy, dz) = self.direction.offset();
+        let center =
+            |block: i32, offset: i32| f64::from(block) + 0.5 - f64::from(offset) * 0.468_75;
+        let size = |axis: Axis| {
+            if self.direction.get_axis() == axis {
+                THICKNESS
+            } else {
+                SIZE
+            }
+        };
+        let (cx, cy, cz) = (
+            center(self.pos.x(), dx),
+            center(self.pos.y(), dy),
+            center(self.pos.z(), dz),
+        );
+        let (sx, sy, sz) = (size(Axis::X), size(Axis::Y), size(Axis::Z));
+        AABBd::new(
+            cx - sx / 2.0,
+            cy - sy / 2.0,
+            cz - sz / 2.0,
+            cx + sx / 2.0,
+            cy + sy / 2.0,
+            cz + sz / 2.0,
+        )
+    }
+
+    /// Returns the block the frame is attached to.
+    #[must_use]
+    pub fn support_pos(&self) -> BlockPos {
+        self.direction.opposite().relative(&self.pos)
+    }
+
+    /// Returns true if the frame can stay where it is, free of blocks and
+    /// attached to a solid block or, on a wall, a diode. `overlaps_hanging`
+    /// finds other paintings or frames in the way.
+    ///
+    /// Vanilla: `ItemFrame.survives`
+    pub fn survives(&self, world: &World, overlaps_hanging: impl Fn(&AABBd) -> bool) -> bool {
+        let bounding_box = self.bounding_box();
+        let collision_world = WorldCollisionProvider::new(world);
+        if collision_world
+            .get_block_collisions(&bounding_box)
+            .iter()
+            .any(|collision| join_is_not_empty(&bounding_box, collision))
+        {
+            return false;
+        }
+
+        let state = world.get_block_state(&self.support_pos());
+        let block = state.get_block();
+        let is_diode =
+            ptr::eq(block, vanilla_blocks::REPEATER) || ptr::eq(block, vanilla_blocks::COMPARATOR);
+        let supported = state.is_solid() || (self.direction.is_horizontal() && is_diode);
+        supported && !overlaps_hanging(&bounding_box)
+    }
+
+    /// The sound played when the frame is hung.
+    #[must_use]
+    pub const fn place_sound(&self) -> i32 {
+        if self.glowing {
+            sound_events::ENTITY_GLOW_ITEM_FRAME_PLACE
+        } else {
+            sound_events::ENTITY_ITEM_FRAME_PLACE
+        }
+    }
+
+    /// The sound played when the frame breaks.
+    #[must_use]
+    pub const fn break_sound(&self) -> i32 {
+        if self.glowing {
+            sound_events::ENTITY_GLOW_ITEM_FRAME_BREAK
+        } else {
+            sound_events::ENTITY_ITEM_FRAME_BREAK
+        }
+    }
+
+    /// The sound played when an item is put in the frame.
+    #[must_use]
+    pub const fn add_item_sound(&self) -> i32 {
+        if self.glowing {
+            sound_events::ENTITY_GLOW_ITEM_FRAME_ADD_ITEM
+        } else {
+            sound_events::ENTITY_ITEM_FRAME_ADD_ITEM
+        }
+    }
+
+    /// The sound played when the item in the frame is turned.
+    #[must_use]
+    pub const fn rotate_item_sound(&self) -> i32 {
+        if self.glowing {
+            sound_events::ENTITY_GLOW_ITEM_FRAME_ROTATE_ITEM
+        } else {
+            sound_events::ENTITY_ITEM_FRAME_ROTATE_ITEM
+        }
+    }
+
+    /// The sound played when the item is knocked out of the frame.
+    #[must_use]
+    pub const fn remove_item_sound(&self) -> i32 {
+        if self.glowing {
+            sound_events::ENTITY_GLOW_ITEM_FRAME_REMOVE_ITEM
+        } else {
+            sound_events::ENTITY_ITEM_FRAME_REMOVE_ITEM
+        }
+    }
+}
+
+impl Entity for ItemFrameEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
+impl StoredEntity for ItemFrameEntity {
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        if self.glowing {
+            vanilla_entities::GLOW_ITEM_FRAME
+        } else {
+            vanilla_entities::ITEM_FRAME
+        }
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        Vector3::new(
+            f64::from(self.pos.x()) + 0.5,
+            f64::from(self.pos.y()),
+            f64::from(self.pos.z()) + 0.5,
+        )
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    /// Saves the frame with the item it shows.
+    ///
+    /// Vanilla: `ItemFrame.addAdditionalSaveData`
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        entity::write_position(&mut nbt, self.get_position());
+        entity::write_uuid(&mut nbt, self.uuid);
+        nbt.insert(
+            "block_pos",
+            NbtTag::IntArray(vec![self.pos.x(), self.pos.y(), self.pos.z()]),
+        );
+        nbt.insert("Facing", self.direction as i8);
+        if !self.item().is_empty() {
+            nbt.insert("Item", self.item().clone().to_nbt_tag());
+            nbt.insert("ItemRotation", self.item_rotation() as i8);
+        }
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, breaks_blocks: bool) -> Option<AABBd> {
+        breaks_blocks.then(|| self.bounding_box())
+    }
+
+    /// Knocks the frame off its block, dropping it with its item.
+    ///
+    /// Vanilla: `ItemFrame.hurtServer`
+    fn explosion_hit(
+        &mut self,
+        world: &World,
+        _damage: f32,
+        _impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        world.play_sound(
+            self.break_sound(),
+            SoundSource::Neutral,
+            self.pos,
+            1.0,
+            1.0,
+            None,
+        );
+        Some(Remains {
+            pos: self.pos,
+            drops: vec![self.frame_item(), self.item().clone()],
+            passengers: Vec::new(),
+        })
+    }
+}
+
+/// Reads a direction saved as its 3D data value.
+fn direction_from_nbt(value: i8) -> Option<Direction> {
+    Some(match value {
+        0 => Direction::Down,
+        1 => Direction::Up,
+        2 => Direction::North,
+        3 => Direction::South,
+        4 => Direction::West,
+        5 => Direction::East,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_wraps_after_a_full_turn() {
+        let mut frame = ItemFrameEntity::new(1, BlockPos::new(0, 64, 0), Direction::North, false);
+        for _ in 0..7 {
+            frame.rotate_item();
+        }
+        assert_eq!(frame.item_rotation(), 7);
+        frame.rotate_item();
+        assert_eq!(frame.item_rotation(), 0);
+    }
+
+    #[test]
+    fn hangs_against_the_supporting_face() {
+        let frame = ItemFrameEntity::new(1, BlockPos::new(0, 64, 0), Direction::Up, false);
+        assert_eq!(frame.support_pos(), BlockPos::new(0, 63, 0));
+
+        let bounding_box = frame.bounding_box();
+        assert!((bounding_box.min_y - 64.0).abs() < 1e-9);
+        assert!((bounding_box.max_y - 64.0 - THICKNESS).abs() < 1e-9);
+        assert!((bounding_box.min_x - 0.125).abs() < 1e-9);
+        assert!((bounding_box.max_x - 0.875).abs() < 1e-9);
+    }
+
+    #[test]
+    fn saved_frames_load_where_they_hung() {
+        let frame = ItemFrameEntity::new(1, BlockPos::new(3, 70, -5), Direction::West, true);
+        let nbt = frame.save().expect("frames are saved");
+
+        let mut loaded = ItemFrameEntity::new(2, BlockPos::new(0, 0, 0), Direction::South, true);
+        loaded.read_nbt(&nbt);
+        assert_eq!(loaded.uuid, frame.uuid);
+        assert_eq!(loaded.pos, frame.pos);
+        assert_eq!(loaded.direction, Direction::West);
+        assert_eq!(entity::position_from_nbt(&nbt), Some(frame.get_position()));
+    }
+}
@@ -0,0 +1,202 @@
+//! Lightning bolts, struck by thunderstorms or `/summon`.
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::CAddEntity;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::Direction;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::{REGISTRY, vanilla_blocks, vanilla_entities};
+use steel_utils::BlockPos;
+use steel_utils::math::Vector3;
+use steel_utils::types::{Difficulty, UpdateFlags};
+use uuid::Uuid;
+
+use crate::entity::storage::EntityPairing;
+use crate::entity::{Entity, StoredEntity};
+use crate::world::World;
+
+/// How much a bolt hurts the entities it strikes.
+///
+/// Vanilla: `Entity.thunderHit`
+pub const DAMAGE: f32 = 5.0;
+/// How far around the bolt entities get struck.
+const REACH: f64 = 3.0;
+/// How tall the part of the bolt is that strikes entities, above its foot.
+const HEIGHT: f64 = 6.0;
+/// How many ticks a bolt lasts before it fades.
+const LIFETIME: u32 = 2;
+/// How many more fires a bolt lights around where it struck on normal and
+/// hard difficulty.
+const EXTRA_FIRES: u32 = 4;
+
+/// A lightning bolt, striking the entities around it and setting the ground
+/// on fire. Clients draw the flash and play the thunder themselves once the
+/// bolt is added.
+///
+/// Vanilla: `LightningBolt`
+pub struct LightningBoltEntity {
+    /// The network id of the entity.
+    pub id: i32,
+    /// The persistent id of the entity.
+    pub uuid: Uuid,
+    position: Vector3<f64>,
+    /// Ticks the bolt has lasted.
+    age: u32,
+}
+
+impl LightningBoltEntity {
+    /// Creates a bolt striking down at `position`.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        Self {
+            id,
+            uuid: Uuid::new_v4(),
+            position,
+            age: 0,
+        }
+    }
+
+    /// Returns the position the bolt strikes.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.position
+    }
+
+    /// Returns the box that entities get struck in.
+    ///
+    /// Vanilla: `LightningBolt.tick`
+    #[must_use]
+    pub fn strike_box(&self) -> AABBd {
+        strike_box(self.position)
+    }
+
+    /// Returns true on the tick the bolt strikes, when it hurts the entities
+    /// in its [`strike_box`](Self::strike_box).
+    #[must_use]
+    pub const fn has_just_struck(&self) -> bool {
+        self.age == 1
+    }
+
+    /// The packet that adds this bolt to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        CAddEntity::new(
+            self.id,
+            self.uuid,
+            *REGISTRY
+                .entity_types
+                .get_id(vanilla_entities::LIGHTNING_BOLT) as i32,
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            0.0,
+            0.0,
+        )
+    }
+
+    /// Advances the bolt by a tick, lighting fires where it struck on its
+    /// first tick. Returns false once it faded.
+    ///
+    /// Vanilla: `LightningBolt.tick`
+    pub fn tick(&mut self, world: &World) -> bool {
+        if self.age == 0 {
+            let extra_fires = match world.difficulty() {
+                Difficulty::Normal | Difficulty::Hard => EXTRA_FIRES,
+                Difficulty::Peaceful | Difficulty::Easy => 0,
+            };
+            self.spawn_fire(world, extra_fires);
+        }
+        self.age += 1;
+        self.age <= LIFETIME
+    }
+
+    /// Lights a fire where the bolt struck, and `extra` more at random spots
+    /// right around it.
+    ///
+    /// Vanilla: `LightningBolt.spawnFire`
+    // TODO: Power lightning rods and scrape the wax off copper once they exist
+    fn spawn_fire(&self, world: &World, extra: u32) {
+        let pos = self.position.to_block_pos();
+        place_fire(world, pos);
+        for _ in 0..extra {
+            let near = pos.offset(
+                rand::random_range(-1..=1),
+                rand::random_range(-1..=1),
+                rand::random_range(-1..=1),
+            );
+            place_fire(world, near);
+        }
+    }
+}
+
+impl Entity for LightningBoltEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
+impl StoredEntity for LightningBoltEntity {
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        vanilla_entities::LIGHTNING_BOLT
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.send(self.add_entity_packet());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        // Bolts only last a few ticks, vanilla doesn't save them either
+        None
+    }
+}
+
+/// The box entities get struck in by a bolt at `position`.
+fn strike_box(position: Vector3<f64>) -> AABBd {
+    AABBd {
+        min_x: position.x - REACH,
+        min_y: position.y - REACH,
+        min_z: position.z - REACH,
+        max_x: position.x + REACH,
+        max_y: position.y + HEIGHT + REACH,
+        max_z: position.z + REACH,
+    }
+}
+
+/// Sets `pos` on fire if it is air with a sturdy block below.
+// TODO: Fire can also be placed next to flammable blocks
+fn place_fire(world: &World, pos: BlockPos) {
+    if !world.is_in_valid_bounds(&pos) || !world.get_block_state(&pos).is_air() {
+        return;
+    }
+    let below = Direction::Down.relative(&pos);
+    if !world.get_block_state(&below).is_face_sturdy(Direction::Up) {
+        return;
+    }
+    world.set_block(
+        pos,
+        vanilla_blocks::FIRE.default_state(),
+        UpdateFlags::UPDATE_ALL,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bolts_strike_further_up_than_down() {
+        let area = strike_box(Vector3::new(0.5, 64.0, 0.5));
+        assert!((area.min_y - 61.0).abs() < f64::EPSILON);
+        assert!((area.max_y - 73.0).abs() < f64::EPSILON);
+        assert!((area.max_x - area.min_x - 2.0 * REACH).abs() < f64::EPSILON);
+    }
+}
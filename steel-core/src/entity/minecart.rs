@@ -0,0 +1,1122 @@
+//! Minecarts riding along rails, and the ones carrying chests and hoppers.
+
+use std::mem;
+use std::ptr;
+use std::sync::Arc;
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, CEntityPositionSync};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction, RailShape};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entity_data::MinecartEntityData;
+use steel_registry::{REGISTRY, vanilla_blocks, vanilla_entities, vanilla_items};
+use steel_utils::locks::SyncMutex;
+use steel_utils::math::Vector3;
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+use uuid::Uuid;
+
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, GenericEntity, Remains, StoredEntity, VehicleInput};
+use crate::inventory::container::{
+    Container, SimpleContainer, calculate_redstone_signal_from_container,
+};
+use crate::inventory::lock::GenericContainer;
+use crate::physics::{EntityPhysicsState, MoverType, WorldCollisionProvider, move_entity};
+use crate::world::World;
+
+/// The tag of the blocks minecarts ride along.
+pub const RAILS_TAG: Identifier = Identifier::vanilla_static("rails");
+/// The fastest a minecart moves, in blocks per tick.
+pub const MAX_SPEED: f64 = 0.4;
+/// The fastest a minecart moves along a powered rail, in blocks per tick.
+pub const POWERED_RAIL_MAX_SPEED: f64 = 0.8;
+/// How far above the minecart's bottom its passenger's feet are: the
+/// minecart's passenger attachment minus the player's vehicle attachment.
+pub const SEAT_HEIGHT: f64 = 0.1875 - 0.6;
+/// How many slots a chest minecart has.
+pub const CHEST_MINECART_SLOTS: usize = 27;
+/// How many slots a hopper minecart has.
+pub const HOPPER_MINECART_SLOTS: usize = 5;
+
+/// How fast minecarts fall, in blocks per tick squared.
+const GRAVITY: f64 = 0.04;
+/// How much speed a sloped rail adds each tick, downhill.
+const SLOPE_SPEED: f64 = 0.007_812_5;
+/// How much speed a powered rail adds each tick.
+const POWERED_RAIL_BOOST: f64 = 0.06;
+/// The damage at which a minecart breaks.
+const BREAK_DAMAGE: f32 = 40.0;
+
+/// What a hit did to a minecart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinecartHit {
+    /// The minecart shook and took damage.
+    Damaged,
+    /// The minecart broke, dropping itself and what it carries.
+    Broken,
+    /// The minecart was removed without dropping itself, like by a creative
+    /// player.
+    Removed,
+}
+
+/// The minecarts players can put on rails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinecartKind {
+    /// A minecart players ride in.
+    Rideable,
+    /// A minecart carrying a chest.
+    Chest,
+    /// A minecart carrying a hopper.
+    Hopper,
+}
+
+/// Returns true if `state` is a rail minecarts can ride along.
+#[must_use]
+pub fn is_rail(state: BlockStateId) -> bool {
+    REGISTRY.blocks.is_in_tag(state.get_block(), &RAILS_TAG)
+}
+
+/// Returns the two blocks, relative to the rail, that a rail of `shape`
+/// connects. On slopes, the lower end is one block down.
+///
+/// Vanilla: `AbstractMinecart.EXITS`
+#[must_use]
+pub const fn exits(shape: &RailShape) -> [(i32, i32, i32); 2] {
+    const NORTH: (i32, i32, i32) = (0, 0, -1);
+    const SOUTH: (i32, i32, i32) = (0, 0, 1);
+    const WEST: (i32, i32, i32) = (-1, 0, 0);
+    const EAST: (i32, i32, i32) = (1, 0, 0);
+    match shape {
+        RailShape::NorthSouth => [NORTH, SOUTH],
+        RailShape::EastWest => [WEST, EAST],
+        RailShape::AscendingEast => [(-1, -1, 0), EAST],
+        RailShape::AscendingWest => [WEST, (1, -1, 0)],
+        RailShape::AscendingNorth => [NORTH, (0, -1, 1)],
+        RailShape::AscendingSouth => [(0, -1, -1), SOUTH],
+        RailShape::SouthEast => [SOUTH, EAST],
+        RailShape::SouthWest => [SOUTH, WEST],
+        RailShape::NorthWest => [NORTH, WEST],
+        RailShape::NorthEast => [NORTH, EAST],
+    }
+}
+
+/// Returns true if a rail of `shape` leads up or down a block.
+#[must_use]
+pub const fn is_slope(shape: &RailShape) -> bool {
+    matches!(
+        shape,
+        RailShape::AscendingEast
+            | RailShape::AscendingWest
+            | RailShape::AscendingNorth
+            | RailShape::AscendingSouth
+    )
+}
+
+/// The state every minecart shares: riding along rails, falling off them and
+/// taking damage.
+///
+/// Vanilla: `AbstractMinecart` with `OldMinecartBehavior`
+pub struct MinecartEntity {
+    /// The state every entity shares, like the id and custom name.
+    pub base: GenericEntity,
+    /// The rail the minecart was on last tick, if any.
+    pub rails_pos: Option<BlockPos>,
+    /// How far the minecart moves each tick.
+    pub velocity: Vector3<f64>,
+    physics: EntityPhysicsState,
+    entity_data: MinecartEntityData,
+    /// Whether the minecart faces against its direction of travel, so it
+    /// doesn't spin around when it reverses.
+    flipped: bool,
+    /// The way the passenger walks, which pushes a standing minecart.
+    move_intent: Vector3<f64>,
+}
+
+impl MinecartEntity {
+    /// Creates a minecart of `entity_type` at rest at `position`.
+    #[must_use]
+    pub fn new(id: i32, entity_type: EntityTypeRef, position: Vector3<f64>) -> Self {
+        Self {
+            base: GenericEntity::new(id, entity_type, position),
+            rails_pos: None,
+            velocity: Vector3::new(0.0, 0.0, 0.0),
+            physics: EntityPhysicsState::new(position, entity_type),
+            entity_data: MinecartEntityData::new(),
+            flipped: false,
+            move_intent: Vector3::new(0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the UUID and the motion. The position
+    /// is never loaded.
+    ///
+    /// Vanilla: `Entity.load`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(velocity) = entity::motion_from_nbt(nbt) {
+            self.velocity = velocity;
+        }
+    }
+
+    /// Saves the shared entity tags and the motion into `nbt`.
+    ///
+    /// Vanilla: `Entity.saveWithoutId`
+    pub fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.base.write_nbt(nbt);
+        entity::write_motion(nbt, self.velocity);
+    }
+
+    /// Sets the keys the passenger holds down while looking at `yaw`.
+    ///
+    /// Vanilla: `ServerPlayer.setLastClientInput`
+    pub fn set_input(&mut self, input: VehicleInput, yaw: f32) {
+        let axis =
+            |positive: bool, negative: bool| f64::from(i8::from(positive) - i8::from(negative));
+        let (strafe, forward) = (
+            axis(input.left, input.right),
+            axis(input.forward, input.backward),
+        );
+        let length = strafe.hypot(forward);
+        if length < 1.0e-7 {
+            self.move_intent = Vector3::new(0.0, 0.0, 0.0);
+            return;
+        }
+        let (strafe, forward) = if length > 1.0 {
+            (strafe / length, forward / length)
+        } else {
+            (strafe, forward)
+        };
+        let yaw = f64::from(yaw).to_radians();
+        let (sin, cos) = yaw.sin_cos();
+        self.move_intent = Vector3::new(
+            strafe * cos - forward * sin,
+            0.0,
+            forward * cos + strafe * sin,
+        );
+    }
+
+    /// How much speed a minecart without a container keeps each tick on
+    /// rails. Carrying someone lets it roll further.
+    ///
+    /// Vanilla: `AbstractMinecart.applyNaturalSlowdown`
+    #[must_use]
+    pub fn natural_slowdown(&self) -> f64 {
+        if self.base.passengers.is_empty() {
+            0.96
+        } else {
+            0.997
+        }
+    }
+
+    /// Returns the position of the minecart.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.physics.position
+    }
+
+    /// Returns true if the minecart was on a rail last tick.
+    #[must_use]
+    pub const fn on_rail(&self) -> bool {
+        self.rails_pos.is_some()
+    }
+
+    /// Returns where the minecart's passenger stands.
+    #[must_use]
+    pub const fn seat_position(&self) -> Vector3<f64> {
+        let position = self.position();
+        Vector3::new(position.x, position.y + SEAT_HEIGHT, position.z)
+    }
+
+    /// Returns the box the minecart takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.physics.bounding_box
+    }
+
+    /// Bumps into `other` if the two touch, sharing their speed and pushing
+    /// them apart.
+    ///
+    /// Vanilla: `AbstractMinecart.push`
+    pub fn collide(&mut self, other: &mut Self) {
+        if !self
+            .bounding_box()
+            .inflate(0.2)
+            .intersects(&other.bounding_box())
+        {
+            return;
+        }
+        let (yaw, _) = self.base.rotation.load();
+        if let Some((velocity, other_velocity)) = bump(
+            self.position(),
+            self.velocity,
+            yaw,
+            other.position(),
+            other.velocity,
+        ) {
+            self.velocity = velocity;
+            other.velocity = other_velocity;
+        }
+    }
+
+    /// The packet that adds this minecart to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the minecart is added, like
+    /// how damaged it is.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Hits the minecart for `damage`, breaking it once it took enough.
+    /// Players with infinite materials remove it at once.
+    ///
+    /// Vanilla: `VehicleEntity.hurtServer`
+    pub fn hurt(&mut self, damage: f32, infinite_materials: bool) -> MinecartHit {
+        let hurt_direction = -*self.entity_data.id_hurtdir.get();
+        self.entity_data.id_hurtdir.set(hurt_direction);
+        self.entity_data.id_hurt.set(10);
+        let total = *self.entity_data.id_damage.get() + damage * 10.0;
+        self.entity_data.id_damage.set(total);
+
+        if infinite_materials {
+            MinecartHit::Removed
+        } else if total > BREAK_DAMAGE {
+            MinecartHit::Broken
+        } else {
+            MinecartHit::Damaged
+        }
+    }
+
+    /// Pushes the minecart by `impulse` and hurts it by `damage` with an
+    /// explosion. Returns true if that broke it.
+    fn blast(&mut self, damage: f32, impulse: Vector3<f64>) -> bool {
+        self.velocity += impulse;
+        self.hurt(damage, false) == MinecartHit::Broken
+    }
+
+    /// Advances the minecart by a tick, following the rail it is on or
+    /// rolling freely off rails, and syncs where it ended up.
+    /// `slowdown` is how much speed it keeps each tick on rails.
+    ///
+    /// Vanilla: `OldMinecartBehavior.tick`
+    pub fn tick(&mut self, world: &World, slowdown: f64) {
+        let hurt_time = *self.entity_data.id_hurt.get();
+        if hurt_time > 0 {
+            self.entity_data.id_hurt.set(hurt_time - 1);
+        }
+        let damage = *self.entity_data.id_damage.get();
+        if damage > 0.0 {
+            self.entity_data.id_damage.set((damage - 1.0).max(0.0));
+        }
+        if self.base.passengers.is_empty() {
+            self.move_intent = Vector3::new(0.0, 0.0, 0.0);
+        }
+
+        let old_position = self.position();
+        if !self.base.no_gravity {
+            self.velocity.y -= GRAVITY;
+        }
+
+        let mut pos = block_pos_at(old_position);
+        if is_rail(world.get_block_state(&pos.offset(0, -1, 0))) {
+            pos = pos.offset(0, -1, 0);
+        }
+        let state = world.get_block_state(&pos);
+        if is_rail(state) {
+            self.rails_pos = Some(pos);
+            self.move_along_track(world, pos, state, slowdown);
+        } else {
+            self.rails_pos = None;
+            self.come_off_track(world);
+        }
+
+        let position = self.position();
+        if position == old_position {
+            return;
+        }
+        self.turn_towards_movement(old_position, position);
+        *self.base.position.lock() = position;
+        let (yaw, pitch) = self.base.rotation.load();
+        world.broadcast_to_trackers(
+            position,
+            CEntityPositionSync {
+                entity_id: self.base.id,
+                x: position.x,
+                y: position.y,
+                z: position.z,
+                velocity_x: self.velocity.x,
+                velocity_y: self.velocity.y,
+                velocity_z: self.velocity.z,
+                yaw,
+                pitch,
+                on_ground: self.physics.on_ground,
+            },
+        );
+    }
+
+    /// Moves the minecart to `position`.
+    fn set_position(&mut self, position: Vector3<f64>) {
+        self.physics.set_position(position);
+    }
+
+    /// Moves the minecart by `delta`, stopping at blocks in the way, and
+    /// stops its velocity along the axes that were blocked.
+    fn move_by(&mut self, world: &World, delta: Vector3<f64>) {
+        let result = move_entity(
+            &self.physics,
+            delta,
+            MoverType::SelfMovement,
+            &WorldCollisionProvider::new(world),
+        );
+        self.physics.set_position(result.final_position);
+        self.physics.on_ground = result.on_ground;
+        self.physics.horizontal_collision = result.horizontal_collision;
+        self.physics.vertical_collision = result.vertical_collision;
+
+        let blocked = |delta: f64, moved: f64| (delta - moved).abs() > 1.0e-7;
+        if blocked(delta.x, result.actual_movement.x) {
+            self.velocity.x = 0.0;
+        }
+        if blocked(delta.y, result.actual_movement.y) {
+            self.velocity.y = 0.0;
+        }
+        if blocked(delta.z, result.actual_movement.z) {
+            self.velocity.z = 0.0;
+        }
+    }
+
+    /// Rolls along the rail at `pos`, speeding up downhill and on powered
+    /// rails and braking on unpowered ones.
+    ///
+    /// Vanilla: `OldMinecartBehavior.moveAlongTrack`
+    fn move_along_track(
+        &mut self,
+        world: &World,
+        pos: BlockPos,
+        state: BlockStateId,
+        slowdown: f64,
+    ) {
+        let start = self.position();
+        let start_on_rail = rail_position(world, start);
+        let mut y = f64::from(pos.y());
+
+        let powered_rail = ptr::eq(state.get_block(), vanilla_blocks::POWERED_RAIL);
+        let powered = powered_rail && state.get_value(&BlockStateProperties::POWERED);
+        let mut braking = powered_rail && !powered;
+
+        let shape: RailShape = state.get_value(&BlockStateProperties::RAIL_SHAPE);
+        // Slopes pull the minecart downhill
+        let slope = match shape {
+            RailShape::AscendingEast => Some((-SLOPE_SPEED, 0.0)),
+            RailShape::AscendingWest => Some((SLOPE_SPEED, 0.0)),
+            RailShape::AscendingNorth => Some((0.0, SLOPE_SPEED)),
+            RailShape::AscendingSouth => Some((0.0, -SLOPE_SPEED)),
+            _ => None,
+        };
+        if let Some((dx, dz)) = slope {
+            self.velocity.x += dx;
+            self.velocity.z += dz;
+            y += 1.0;
+        }
+
+        let [first, second] = exits(&shape);
+        self.velocity = along_rail(self.velocity, first, second);
+
+        // Passengers walking push a standing minecart, even off a braking rail
+        let intent = self.move_intent;
+        if intent.horizontal_length_squared() > 1.0e-4
+            && self.velocity.horizontal_length_squared() < 0.01
+        {
+            self.velocity.x += intent.x * 0.1;
+            self.velocity.z += intent.z * 0.1;
+            braking = false;
+        }
+
+        if braking {
+            if self.velocity.horizontal_length() < 0.03 {
+                self.velocity = Vector3::new(0.0, 0.0, 0.0);
+            } else {
+                self.velocity = self.velocity.multiply(0.5, 0.0, 0.5);
+            }
+        }
+
+        // Snap the minecart onto the line between the rail's two ends
+        let block_x = f64::from(pos.x());
+        let block_z = f64::from(pos.z());
+        let (start_x, start_z) = (
+            block_x + 0.5 + f64::from(first.0) * 0.5,
+            block_z + 0.5 + f64::from(first.2) * 0.5,
+        );
+        let (dx, dz) = (
+            f64::from(second.0 - first.0) * 0.5,
+            f64::from(second.2 - first.2) * 0.5,
+        );
+        let progress = if dx == 0.0 {
+            start.z - block_z
+        } else if dz == 0.0 {
+            start.x - block_x
+        } else {
+            ((start.x - start_x) * dx + (start.z - start_z) * dz) * 2.0
+        };
+        self.set_position(Vector3::new(
+            start_x + dx * progress,
+            y,
+            start_z + dz * progress,
+        ));
+
+        // Passengers weigh the minecart down
+        let load = if self.base.passengers.is_empty() {
+            1.0
+        } else {
+            0.75
+        };
+        let max_speed = if powered {
+            POWERED_RAIL_MAX_SPEED
+        } else {
+            MAX_SPEED
+        };
+        let delta = Vector3::new(
+            (self.velocity.x * load).clamp(-max_speed, max_speed),
+            0.0,
+            (self.velocity.z * load).clamp(-max_speed, max_speed),
+        );
+        self.move_by(world, delta);
+
+        // Climb onto the next rail at the top of a slope
+        let moved = block_pos_at(self.position());
+        for exit in [first, second] {
+            if exit.1 != 0 && moved.x() - pos.x() == exit.0 && moved.z() - pos.z() == exit.2 {
+                let position = self.position();
+                self.set_position(Vector3::new(
+                    position.x,
+                    position.y + f64::from(exit.1),
+                    position.z,
+                ));
+                break;
+            }
+        }
+
+        self.velocity = self.velocity.multiply(slowdown, 0.0, slowdown);
+
+        // Trade height for speed, like rolling down a hill
+        let position = self.position();
+        if let Some(end_on_rail) = rail_position(world, position) {
+            if let Some(start_on_rail) = start_on_rail {
+                let gained = (start_on_rail.y - end_on_rail.y) * 0.05;
+                let speed = self.velocity.horizontal_length();
+                if speed > 0.0 {
+                    let scale = (speed + gained) / speed;
+                    self.velocity = self.velocity.multiply(scale, 1.0, scale);
+                }
+            }
+            self.set_position(Vector3::new(position.x, end_on_rail.y, position.z));
+        }
+
+        // Turn the speed onto the rail of the block the minecart moved into
+        let moved = block_pos_at(self.position());
+        if moved.x() != pos.x() || moved.z() != pos.z() {
+            let speed = self.velocity.horizontal_length();
+            self.velocity = Vector3::new(
+                speed * f64::from(moved.x() - pos.x()),
+                self.velocity.y,
+                speed * f64::from(moved.z() - pos.z()),
+            );
+        }
+
+        if powered {
+            self.boost(world, pos, &shape);
+        }
+    }
+
+    /// Speeds the minecart up on a powered rail, or pushes it off the rail
+    /// away from a solid block if it stands still.
+    fn boost(&mut self, world: &World, pos: BlockPos, shape: &RailShape) {
+        let speed = self.velocity.horizontal_length();
+        if speed > 0.01 {
+            self.velocity.x += self.velocity.x / speed * POWERED_RAIL_BOOST;
+            self.velocity.z += self.velocity.z / speed * POWERED_RAIL_BOOST;
+            return;
+        }
+
+        let is_solid =
+            |direction: Direction| world.get_block_state(&direction.relative(&pos)).is_solid();
+        match shape {
+            RailShape::EastWest if is_solid(Direction::West) => self.velocity.x = 0.02,
+            RailShape::EastWest if is_solid(Direction::East) => self.velocity.x = -0.02,
+            RailShape::NorthSouth if is_solid(Direction::North) => self.velocity.z = 0.02,
+            RailShape::NorthSouth if is_solid(Direction::South) => self.velocity.z = -0.02,
+            _ => {}
+        }
+    }
+
+    /// Rolls freely off rails, slowed down a lot on the ground.
+    ///
+    /// Vanilla: `OldMinecartBehavior.comeOffTrack`
+    fn come_off_track(&mut self, world: &World) {
+        self.velocity.x = self.velocity.x.clamp(-MAX_SPEED, MAX_SPEED);
+        self.velocity.z = self.velocity.z.clamp(-MAX_SPEED, MAX_SPEED);
+        if self.physics.on_ground {
+            self.velocity = self.velocity * 0.5;
+        }
+
+        let delta = self.velocity;
+        self.move_by(world, delta);
+        if !self.physics.on_ground {
+            self.velocity = self.velocity * 0.95;
+        }
+    }
+
+    /// Turns the minecart to face the way it moved, flipping it around
+    /// instead of spinning when it reverses.
+    #[allow(clippy::cast_possible_truncation)]
+    fn turn_towards_movement(&mut self, from: Vector3<f64>, to: Vector3<f64>) {
+        let (dx, dz) = (from.x - to.x, from.z - to.z);
+        if dx * dx + dz * dz <= 0.001 {
+            return;
+        }
+        let (current, _) = self.base.rotation.load();
+        let mut yaw = dz.atan2(dx).to_degrees() as f32;
+        if self.flipped {
+            yaw += 180.0;
+        }
+        let turn = (yaw - current + 180.0).rem_euclid(360.0) - 180.0;
+        if !(-170.0..170.0).contains(&turn) {
+            yaw += 180.0;
+            self.flipped = !self.flipped;
+        }
+        self.base.rotation.store((yaw.rem_euclid(360.0), 0.0));
+    }
+}
+
+impl Entity for MinecartEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for MinecartEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+        if !self.base.passengers.is_empty() {
+            pairing.send(world.passengers_packet(self.base.id, &self.base.passengers));
+        }
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.write_nbt(&mut nbt);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.base.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    fn explosion_hit(
+        &mut self,
+        _world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.blast(damage, impulse).then(|| Remains {
+            pos: block_pos_at(self.position()),
+            drops: vec![ItemStack::new(&vanilla_items::ITEMS.minecart)],
+            passengers: mem::take(&mut self.base.passengers),
+        })
+    }
+}
+
+/// A minecart carrying a chest, opened like one by right-clicking it.
+///
+/// Vanilla: `MinecartChest`
+pub struct ChestMinecartEntity {
+    /// The minecart carrying the chest.
+    pub minecart: MinecartEntity,
+    items: GenericContainer,
+}
+
+impl ChestMinecartEntity {
+    /// Creates an empty chest minecart at rest at `position`.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        Self {
+            minecart: MinecartEntity::new(id, vanilla_entities::CHEST_MINECART, position),
+            items: Arc::new(SyncMutex::new(SimpleContainer::new(CHEST_MINECART_SLOTS))),
+        }
+    }
+
+    /// Returns the 27 slots of the chest.
+    #[must_use]
+    pub fn items(&self) -> &GenericContainer {
+        &self.items
+    }
+
+    /// Applies the tags of `nbt`, like the items in the chest.
+    ///
+    /// Vanilla: `AbstractMinecartContainer.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.minecart.read_nbt(nbt);
+        entity::read_items(nbt, &mut *self.items.lock());
+    }
+
+    /// Advances the minecart by a tick. Fuller chests slow it down less.
+    pub fn tick(&mut self, world: &World) {
+        let slowdown = container_slowdown(&*self.items.lock());
+        self.minecart.tick(world, slowdown);
+    }
+
+    /// Takes the items the minecart drops when it is destroyed: everything
+    /// in the chest, and itself if `with_minecart` is set.
+    pub fn take_drops(&mut self, with_minecart: bool) -> Vec<ItemStack> {
+        let mut drops = take_all(&mut *self.items.lock());
+        if with_minecart {
+            drops.push(ItemStack::new(&vanilla_items::ITEMS.chest_minecart));
+        }
+        drops
+    }
+}
+
+impl Entity for ChestMinecartEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.minecart.base.uuid
+    }
+}
+
+impl StoredEntity for ChestMinecartEntity {
+    fn get_id(&self) -> i32 {
+        self.minecart.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.minecart.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.minecart.position()
+    }
+
+    fn pair(&self, world: &World, pairing: &mut EntityPairing) {
+        self.minecart.pair(world, pairing);
+    }
+
+    /// Saves the minecart with what it carries.
+    ///
+    /// Vanilla: `AbstractMinecartContainer.addAdditionalSaveData`
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.minecart.write_nbt(&mut nbt);
+        entity::write_items(&mut nbt, &*self.items.lock());
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        self.minecart.hitbox(entity_id)
+    }
+
+    fn explosion_box(&self, breaks_blocks: bool) -> Option<AABBd> {
+        self.minecart.explosion_box(breaks_blocks)
+    }
+
+    fn explosion_hit(
+        &mut self,
+        _world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.minecart.blast(damage, impulse).then(|| Remains {
+            pos: block_pos_at(self.minecart.position()),
+            drops: self.take_drops(true),
+            passengers: Vec::new(),
+        })
+    }
+}
+
+/// A minecart carrying a hopper, which pulls items out of the container above
+/// it as it rolls by.
+///
+/// Vanilla: `MinecartHopper`
+pub struct HopperMinecartEntity {
+    /// The minecart carrying the hopper.
+    pub minecart: MinecartEntity,
+    items: GenericContainer,
+}
+
+impl HopperMinecartEntity {
+    /// Creates an empty hopper minecart at rest at `position`.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        Self {
+            minecart: MinecartEntity::new(id, vanilla_entities::HOPPER_MINECART, position),
+            items: Arc::new(SyncMutex::new(SimpleContainer::new(HOPPER_MINECART_SLOTS))),
+        }
+    }
+
+    /// Returns the 5 slots of the hopper.
+    #[must_use]
+    pub fn items(&self) -> &GenericContainer {
+        &self.items
+    }
+
+    /// Applies the tags of `nbt`, like the items in the hopper.
+    ///
+    /// Vanilla: `MinecartHopper.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.minecart.read_nbt(nbt);
+        entity::read_items(nbt, &mut *self.items.lock());
+    }
+
+    /// Advances the minecart by a tick and pulls an item from the container
+    /// above it. Powered activator rails disable the hopper.
+    ///
+    /// Vanilla: `MinecartHopper.tick`
+    pub fn tick(&mut self, world: &World) {
+        let slowdown = container_slowdown(&*self.items.lock());
+        self.minecart.tick(world, slowdown);
+
+        let disabled = self.minecart.rails_pos.is_some_and(|pos| {
+            let state = world.get_block_state(&pos);
+            ptr::eq(state.get_block(), vanilla_blocks::ACTIVATOR_RAIL)
+                && state.get_value(&BlockStateProperties::POWERED)
+        });
+        if !disabled {
+            self.suck_in_items(world);
+        }
+    }
+
+    /// Pulls one item from the container in the block above the hopper.
+    /// Returns true if an item was moved.
+    ///
+    /// Vanilla: `HopperBlockEntity.suckInItems`
+    fn suck_in_items(&self, world: &World) -> bool {
+        // The hopper sits half a block above the minecart's feet
+        let position = self.minecart.position();
+        let above =
+            block_pos_at(Vector3::new(position.x, position.y + 0.5, position.z)).offset(0, 1, 0);
+        let Some(source) = world.get_block_entity(&above) else {
+            // TODO: Pick up item entities within a block above once they exist
+            return false;
+        };
+        let mut source = source.lock();
+        let Some(container) = source.as_container_mut() else {
+            return false;
+        };
+
+        let mut items = self.items.lock();
+        (0..container.get_container_size()).any(|slot| container.transfer_item(slot, &mut *items))
+    }
+
+    /// Takes the items the minecart drops when it is destroyed: everything
+    /// in the hopper, and itself if `with_minecart` is set.
+    pub fn take_drops(&mut self, with_minecart: bool) -> Vec<ItemStack> {
+        let mut drops = take_all(&mut *self.items.lock());
+        if with_minecart {
+            drops.push(ItemStack::new(&vanilla_items::ITEMS.hopper_minecart));
+        }
+        drops
+    }
+}
+
+impl Entity for HopperMinecartEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.minecart.base.uuid
+    }
+}
+
+impl StoredEntity for HopperMinecartEntity {
+    fn get_id(&self) -> i32 {
+        self.minecart.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.minecart.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.minecart.position()
+    }
+
+    fn pair(&self, world: &World, pairing: &mut EntityPairing) {
+        self.minecart.pair(world, pairing);
+    }
+
+    /// Saves the minecart with what it carries.
+    ///
+    /// Vanilla: `AbstractMinecartContainer.addAdditionalSaveData`
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.minecart.write_nbt(&mut nbt);
+        entity::write_items(&mut nbt, &*self.items.lock());
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        self.minecart.hitbox(entity_id)
+    }
+
+    fn explosion_box(&self, breaks_blocks: bool) -> Option<AABBd> {
+        self.minecart.explosion_box(breaks_blocks)
+    }
+
+    fn explosion_hit(
+        &mut self,
+        _world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.minecart.blast(damage, impulse).then(|| Remains {
+            pos: block_pos_at(self.minecart.position()),
+            drops: self.take_drops(true),
+            passengers: Vec::new(),
+        })
+    }
+}
+
+/// Returns the block containing `position`.
+#[allow(clippy::cast_possible_truncation)]
+pub fn block_pos_at(position: Vector3<f64>) -> BlockPos {
+    BlockPos::new(
+        position.x.floor() as i32,
+        position.y.floor() as i32,
+        position.z.floor() as i32,
+    )
+}
+
+/// Returns where on the rail at `position` a minecart sits, with the height
+/// it has partway up a slope, or `None` if there is no rail there.
+///
+/// Vanilla: `OldMinecartBehavior.getPos`
+fn rail_position(world: &World, position: Vector3<f64>) -> Option<Vector3<f64>> {
+    let mut pos = block_pos_at(position);
+    if is_rail(world.get_block_state(&pos.offset(0, -1, 0))) {
+        pos = pos.offset(0, -1, 0);
+    }
+    let state = world.get_block_state(&pos);
+    if !is_rail(state) {
+        return None;
+    }
+
+    let shape: RailShape = state.get_value(&BlockStateProperties::RAIL_SHAPE);
+    let [first, second] = exits(&shape);
+    let end = |exit: (i32, i32, i32)| {
+        Vector3::new(
+            f64::from(pos.x()) + 0.5 + f64::from(exit.0) * 0.5,
+            f64::from(pos.y()) + 0.0625 + f64::from(exit.1) * 0.5,
+            f64::from(pos.z()) + 0.5 + f64::from(exit.2) * 0.5,
+        )
+    };
+    let (start, finish) = (end(first), end(second));
+    let (dx, dy, dz) = (
+        finish.x - start.x,
+        (finish.y - start.y) * 2.0,
+        finish.z - start.z,
+    );
+    let progress = if dx == 0.0 {
+        position.z - f64::from(pos.z())
+    } else if dz == 0.0 {
+        position.x - f64::from(pos.x())
+    } else {
+        ((position.x - start.x) * dx + (position.z - start.z) * dz) * 2.0
+    };
+
+    let mut y = start.y + dy * progress;
+    if dy < 0.0 {
+        y += 1.0;
+    } else if dy > 0.0 {
+        y += 0.5;
+    }
+    Some(Vector3::new(
+        start.x + dx * progress,
+        y,
+        start.z + dz * progress,
+    ))
+}
+
+/// Returns `velocity` turned to run along a rail between the ends `first`
+/// and `second`, keeping its horizontal speed up to 2 blocks per tick.
+fn along_rail(
+    velocity: Vector3<f64>,
+    first: (i32, i32, i32),
+    second: (i32, i32, i32),
+) -> Vector3<f64> {
+    let mut dx = f64::from(second.0 - first.0);
+    let mut dz = f64::from(second.2 - first.2);
+    let length = dx.hypot(dz);
+    if velocity.x * dx + velocity.z * dz < 0.0 {
+        dx = -dx;
+        dz = -dz;
+    }
+    let speed = velocity.horizontal_length().min(2.0);
+    Vector3::new(speed * dx / length, velocity.y, speed * dz / length)
+}
+
+/// Returns the velocities of a minecart at `position` facing `yaw` and one at
+/// `other_position` after they bump into each other: both keep a fifth of
+/// their own speed, share the rest and are pushed apart. Returns `None` if
+/// they don't bump, like when one passes the other sideways.
+///
+/// Vanilla: `AbstractMinecart.push`
+fn bump(
+    position: Vector3<f64>,
+    velocity: Vector3<f64>,
+    yaw: f32,
+    other_position: Vector3<f64>,
+    other_velocity: Vector3<f64>,
+) -> Option<(Vector3<f64>, Vector3<f64>)> {
+    let (mut dx, mut dz) = (other_position.x - position.x, other_position.z - position.z);
+    let distance = dx.hypot(dz);
+    if distance < 0.01 {
+        return None;
+    }
+    dx /= distance;
+    dz /= distance;
+
+    // Only minecarts in line with where this one faces bump into each other
+    let yaw = f64::from(yaw).to_radians();
+    if (dx * yaw.cos() + dz * yaw.sin()).abs() < 0.8 {
+        return None;
+    }
+
+    let push = (1.0 / distance).min(1.0) * 0.05;
+    let (push_x, push_z) = (dx * push, dz * push);
+    let shared_x = (velocity.x + other_velocity.x) / 2.0;
+    let shared_z = (velocity.z + other_velocity.z) / 2.0;
+    Some((
+        Vector3::new(
+            velocity.x * 0.2 + shared_x - push_x,
+            velocity.y,
+            velocity.z * 0.2 + shared_z - push_z,
+        ),
+        Vector3::new(
+            other_velocity.x * 0.2 + shared_x + push_x,
+            other_velocity.y,
+            other_velocity.z * 0.2 + shared_z + push_z,
+        ),
+    ))
+}
+
+/// How much speed a minecart carrying `container` keeps each tick. The
+/// emptier the container, the faster it slows down.
+///
+/// Vanilla: `AbstractMinecartContainer.applyNaturalSlowdown`
+fn container_slowdown(container: &dyn Container) -> f64 {
+    let signal = calculate_redstone_signal_from_container(container);
+    0.98 + f64::from(15 - signal) * 0.001
+}
+
+/// Empties `container`, returning the stacks it held.
+fn take_all(container: &mut dyn Container) -> Vec<ItemStack> {
+    (0..container.get_container_size())
+        .map(|slot| container.remove_item_no_update(slot))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn velocity_follows_the_rail() {
+        let [first, second] = exits(&RailShape::NorthSouth);
+        let turned = along_rail(Vector3::new(0.3, 0.0, -0.4), first, second);
+        assert!(turned.x.abs() < 1e-9);
+        assert!((turned.z + 0.5).abs() < 1e-9);
+
+        let [first, second] = exits(&RailShape::SouthEast);
+        let diagonal = along_rail(Vector3::new(0.0, 0.0, 1.0), first, second);
+        assert!(diagonal.z > 0.0 && (diagonal.x + diagonal.z).abs() < 1e-9);
+        assert!((diagonal.horizontal_length() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bumped_minecarts_share_their_speed() {
+        let (first, second) = bump(
+            Vector3::new(0.0, 64.0, 0.0),
+            Vector3::new(0.4, 0.0, 0.0),
+            0.0,
+            Vector3::new(0.9, 64.0, 0.0),
+            Vector3::new(0.0, 0.0, 0.0),
+        )
+        .expect("minecarts in line bump");
+        assert!(first.x > 0.0 && second.x > first.x);
+        assert!((first.x + second.x - 0.48).abs() < 1e-9);
+
+        let sideways = bump(
+            Vector3::new(0.0, 64.0, 0.0),
+            Vector3::new(0.4, 0.0, 0.0),
+            0.0,
+            Vector3::new(0.0, 64.0, 0.9),
+            Vector3::new(0.0, 0.0, 0.0),
+        );
+        assert!(sideways.is_none());
+    }
+
+    #[test]
+    fn slopes_lead_down_one_block() {
+        for shape in [
+            RailShape::AscendingEast,
+            RailShape::AscendingWest,
+            RailShape::AscendingNorth,
+            RailShape::AscendingSouth,
+        ] {
+            let [first, second] = exits(&shape);
+            assert_eq!(first.1 + second.1, -1);
+        }
+    }
+
+    #[test]
+    fn saved_chest_minecarts_keep_their_items_and_motion() {
+        let mut minecart = ChestMinecartEntity::new(1, Vector3::new(0.5, 64.0, 0.5));
+        minecart.minecart.velocity = Vector3::new(0.2, 0.0, -0.1);
+        minecart.items().lock().set_item(
+            4,
+            ItemStack::with_count(&vanilla_items::ITEMS.cobblestone, 12),
+        );
+        let nbt = minecart.save().expect("chest minecarts are saved");
+
+        let items = nbt
+            .list("Items")
+            .and_then(|items| items.compounds())
+            .expect("the items are saved");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].byte("Slot"), Some(4));
+        assert_eq!(items[0].int("count"), Some(12));
+
+        let mut loaded = ChestMinecartEntity::new(2, Vector3::new(0.0, 0.0, 0.0));
+        loaded.read_nbt(&nbt);
+        assert_eq!(loaded.get_uuid(), minecart.get_uuid());
+        assert!((loaded.minecart.velocity.x - 0.2).abs() < 1e-9);
+        assert!((loaded.minecart.velocity.z + 0.1).abs() < 1e-9);
+    }
+}
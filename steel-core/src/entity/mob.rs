@@ -0,0 +1,428 @@
+//! The state every mob shares: its physics, the path it walks and whether it
+//! burns.
+
+use std::sync::Arc;
+
+use steel_protocol::packets::game::{CEntityPositionSync, CRotateHead, to_angle_byte};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_types::{EntityDimensions, EntityTypeRef};
+use steel_utils::BlockPos;
+use steel_utils::math::Vector3;
+use steel_utils::types::{Difficulty, GameType};
+use uuid::Uuid;
+
+use crate::config::STEEL_CONFIG;
+use crate::entity::GenericEntity;
+use crate::entity::ai::pathfinding::PathNavigation;
+use crate::physics::{
+    DEFAULT_DRAG, DEFAULT_GRAVITY, EntityPhysicsState, MoveResult, MoverType,
+    WorldCollisionProvider, move_entity,
+};
+use crate::player::Player;
+use crate::world::World;
+
+/// The upward velocity of a jump onto the next block.
+const JUMP_POWER: f64 = 0.42;
+/// The most blocks a path search visits before giving up.
+const MAX_PATH_NODES: usize = 2048;
+/// How many ticks pass between burn damage.
+const BURN_INTERVAL: u32 = 20;
+/// The day time at which the sun sets.
+const SUNSET: i64 = 12_000;
+/// How long undead mobs burn after the sun set them on fire.
+const SUN_BURN_SECONDS: u32 = 8;
+/// How much of their speed flying mobs keep each tick.
+///
+/// Vanilla: `FlyingMob.travel`
+const FLYING_DRAG: f64 = 0.91;
+
+/// Movement and burning shared by mobs, driven by the entity that owns it.
+///
+/// Vanilla: `Mob`
+pub struct MobBase {
+    physics: EntityPhysicsState,
+    navigation: PathNavigation,
+    /// Ticks until the mob may search for a path again.
+    path_cooldown: u32,
+    /// Ticks the mob keeps burning for.
+    fire_ticks: u32,
+}
+
+impl MobBase {
+    /// Creates a mob of `entity_type` standing still at `position`.
+    #[must_use]
+    pub fn new(position: Vector3<f64>, entity_type: EntityTypeRef) -> Self {
+        Self {
+            physics: EntityPhysicsState::new(position, entity_type),
+            navigation: PathNavigation::new(),
+            path_cooldown: 0,
+            fire_ticks: 0,
+        }
+    }
+
+    /// Returns the position of the mob.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.physics.position
+    }
+
+    /// Returns the box the mob takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.physics.bounding_box
+    }
+
+    /// Heads for `goal`, searching for a path at most every
+    /// `repath_interval` ticks, or sooner if the goal moves away.
+    pub fn move_to(&mut self, world: &World, goal: BlockPos, repath_interval: u32) {
+        if self.path_cooldown == 0 {
+            self.navigation.stop();
+        } else if self.navigation.is_done() {
+            // Don't search again every tick for a goal that can't be reached
+            return;
+        }
+        let start = self.position().to_block_pos();
+        if self.navigation.move_to(start, goal, world, MAX_PATH_NODES) {
+            self.path_cooldown = repath_interval;
+        }
+    }
+
+    /// Returns true while the mob has a path left to walk.
+    #[must_use]
+    pub fn has_path(&self) -> bool {
+        !self.navigation.is_done()
+    }
+
+    /// Adds `impulse` to the velocity of the mob, like the fling of an iron
+    /// golem's hit.
+    pub fn push(&mut self, impulse: Vector3<f64>) {
+        self.physics.velocity += impulse;
+    }
+
+    /// Stops walking the current path.
+    pub fn stop(&mut self) {
+        self.navigation.stop();
+    }
+
+    /// Changes the size of the mob, like when a slime is made bigger.
+    pub fn set_dimensions(&mut self, dimensions: EntityDimensions) {
+        self.physics.set_dimensions(dimensions);
+    }
+
+    /// Sets how fast the mob moves, like when it is thrown out of something.
+    pub const fn set_velocity(&mut self, velocity: Vector3<f64>) {
+        self.physics.velocity = velocity;
+    }
+
+    /// Moves the mob straight to `position`, like an enderman teleporting,
+    /// and tells clients it is there.
+    ///
+    /// Vanilla: `Entity.teleportTo`
+    pub fn teleport(&mut self, base: &GenericEntity, world: &World, position: Vector3<f64>) {
+        self.navigation.stop();
+        self.physics.set_position(position);
+        self.physics.velocity = Vector3::new(0.0, 0.0, 0.0);
+        *base.position.lock() = position;
+        let (yaw, pitch) = base.rotation.load();
+        world.broadcast_to_trackers(
+            position,
+            CEntityPositionSync {
+                entity_id: base.id,
+                x: position.x,
+                y: position.y,
+                z: position.z,
+                velocity_x: 0.0,
+                velocity_y: 0.0,
+                velocity_z: 0.0,
+                yaw,
+                pitch,
+                on_ground: self.physics.on_ground,
+            },
+        );
+    }
+
+    /// Returns true while the mob stands on a block.
+    #[must_use]
+    pub const fn on_ground(&self) -> bool {
+        self.physics.on_ground
+    }
+
+    /// Moves the mob a tick along its path at `speed` blocks per tick,
+    /// letting it fall and jump up blocks, and tells clients where it went.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn walk(&mut self, base: &GenericEntity, world: &World, speed: f64) {
+        self.path_cooldown = self.path_cooldown.saturating_sub(1);
+        let position = self.position();
+        let mut velocity = Vector3::new(0.0, self.physics.velocity.y, 0.0);
+        let mut yaw = None;
+
+        if let Some(next) = self.navigation.next_node() {
+            let dx = f64::from(next.x()) + 0.5 - position.x;
+            let dz = f64::from(next.z()) + 0.5 - position.z;
+            let distance = dx.hypot(dz);
+            if distance < 0.35 && position.y.floor() as i32 == next.y() {
+                self.navigation.advance();
+            } else {
+                if distance > 1.0e-7 {
+                    let speed = speed.min(distance);
+                    velocity.x = dx / distance * speed;
+                    velocity.z = dz / distance * speed;
+                    yaw = Some(yaw_towards(dx, dz));
+                }
+                if next.y() > position.y.floor() as i32 && self.physics.on_ground {
+                    velocity.y = JUMP_POWER;
+                }
+            }
+        }
+
+        self.travel(base, world, velocity, yaw);
+    }
+
+    /// Moves the mob a tick through the air, jumping off with `jump` if it
+    /// stands on the ground, and tells clients where it went. Mobs keep
+    /// their speed through a hop and stop once they land.
+    ///
+    /// Vanilla: `Slime.SlimeMoveControl`
+    pub fn hop(&mut self, base: &GenericEntity, world: &World, jump: Option<Vector3<f64>>) {
+        let mut velocity = self.physics.velocity;
+        let mut yaw = None;
+        if self.physics.on_ground {
+            match jump {
+                Some(jump) => {
+                    velocity = jump;
+                    yaw = Some(yaw_towards(jump.x, jump.z));
+                }
+                None => {
+                    velocity.x = 0.0;
+                    velocity.z = 0.0;
+                }
+            }
+        }
+        self.travel(base, world, velocity, yaw);
+    }
+
+    /// Moves the mob by `velocity` this tick, pulling it down for the next,
+    /// and tells clients where it went and, if given, where it looks.
+    fn travel(
+        &mut self,
+        base: &GenericEntity,
+        world: &World,
+        velocity: Vector3<f64>,
+        yaw: Option<f32>,
+    ) {
+        let result = move_entity(
+            &self.physics,
+            velocity,
+            MoverType::SelfMovement,
+            &WorldCollisionProvider::new(world),
+        );
+        self.physics.set_position(result.final_position);
+        self.physics.on_ground = result.on_ground;
+        self.physics.horizontal_collision = result.horizontal_collision;
+        self.physics.vertical_collision = result.vertical_collision;
+        let vertical = if result.vertical_collision {
+            0.0
+        } else {
+            velocity.y
+        };
+        self.physics.velocity = Vector3::new(
+            velocity.x,
+            (vertical - DEFAULT_GRAVITY) * DEFAULT_DRAG,
+            velocity.z,
+        );
+        self.sync_movement(base, world, &result, yaw);
+    }
+
+    /// Speeds the mob up by `acceleration` and moves it a tick through the
+    /// air without falling, slowing it down for the next, and tells clients
+    /// where it went and where it looks.
+    ///
+    /// Vanilla: `FlyingMob.travel`
+    pub fn fly(
+        &mut self,
+        base: &GenericEntity,
+        world: &World,
+        acceleration: Vector3<f64>,
+        yaw: f32,
+    ) {
+        let velocity = self.physics.velocity + acceleration;
+        let result = move_entity(
+            &self.physics,
+            velocity,
+            MoverType::SelfMovement,
+            &WorldCollisionProvider::new(world),
+        );
+        self.physics.set_position(result.final_position);
+        self.physics.on_ground = result.on_ground;
+        self.physics.horizontal_collision = result.horizontal_collision;
+        self.physics.vertical_collision = result.vertical_collision;
+        self.physics.velocity = result.actual_movement * FLYING_DRAG;
+        self.sync_movement(base, world, &result, Some(yaw));
+    }
+
+    /// Tells clients where the mob went after a move and, if given, where it
+    /// looks.
+    fn sync_movement(
+        &self,
+        base: &GenericEntity,
+        world: &World,
+        result: &MoveResult,
+        yaw: Option<f32>,
+    ) {
+        if let Some(yaw) = yaw {
+            base.rotation.store((yaw, 0.0));
+            world.broadcast_to_trackers(
+                *base.position.lock(),
+                CRotateHead {
+                    entity_id: base.id,
+                    head_y_rot: to_angle_byte(yaw),
+                },
+            );
+        }
+        if result.actual_movement != Vector3::new(0.0, 0.0, 0.0) {
+            *base.position.lock() = result.final_position;
+            let (yaw, pitch) = base.rotation.load();
+            world.broadcast_to_trackers(
+                result.final_position,
+                CEntityPositionSync {
+                    entity_id: base.id,
+                    x: result.final_position.x,
+                    y: result.final_position.y,
+                    z: result.final_position.z,
+                    velocity_x: result.actual_movement.x,
+                    velocity_y: result.actual_movement.y,
+                    velocity_z: result.actual_movement.z,
+                    yaw,
+                    pitch,
+                    on_ground: result.on_ground,
+                },
+            );
+        }
+    }
+
+    /// Returns true if the mob stands in daylight under the open sky, where
+    /// undead mobs catch fire.
+    ///
+    /// Vanilla: `Mob.isSunBurnTick`
+    #[must_use]
+    pub fn is_in_sunlight(&self, world: &World, eye_height: f64) -> bool {
+        let day_time = world.level_data.read().day_time().rem_euclid(24_000);
+        let position = self.position();
+        let eyes = Vector3::new(position.x, position.y + eye_height, position.z);
+        day_time < SUNSET && world.is_sky_visible(&eyes.to_block_pos())
+    }
+
+    /// Sets the mob on fire while it stands in the sunlight and burns it for
+    /// a tick, returning true once a second when the fire hurts it. Used by
+    /// undead mobs.
+    pub fn burn_in_sunlight(&mut self, world: &World, eye_height: f64) -> bool {
+        if self.is_in_sunlight(world, eye_height) {
+            self.set_on_fire(SUN_BURN_SECONDS);
+        }
+        self.tick_fire()
+    }
+
+    /// Sets the mob on fire for at least `seconds`.
+    ///
+    /// Vanilla: `Entity.igniteForSeconds`
+    pub fn set_on_fire(&mut self, seconds: u32) {
+        self.fire_ticks = self.fire_ticks.max(seconds * 20);
+    }
+
+    /// Returns true while the mob burns.
+    #[must_use]
+    pub const fn is_on_fire(&self) -> bool {
+        self.fire_ticks > 0
+    }
+
+    /// Burns for a tick, returning true once a second when the fire hurts the
+    /// mob.
+    pub fn tick_fire(&mut self) -> bool {
+        if self.fire_ticks == 0 {
+            return false;
+        }
+        self.fire_ticks -= 1;
+        self.fire_ticks % BURN_INTERVAL == 0
+    }
+}
+
+/// The yaw of a mob facing along `dx` and `dz`.
+#[allow(clippy::cast_possible_truncation)]
+#[must_use]
+pub fn yaw_towards(dx: f64, dz: f64) -> f32 {
+    (dz.atan2(dx).to_degrees() - 90.0) as f32
+}
+
+/// Returns the player a hostile mob at `position` goes after, keeping
+/// `current` while they stay within `range` and picking the nearest player
+/// otherwise.
+///
+/// Vanilla: `NearestAttackableTargetGoal`
+#[must_use]
+pub fn find_target(
+    world: &World,
+    position: Vector3<f64>,
+    current: Option<Uuid>,
+    range: f64,
+) -> Option<Arc<Player>> {
+    let in_range = |player: &Player| {
+        let player_pos = *player.position.lock();
+        is_targetable(player) && player_pos.sub(&position).length_squared() <= range * range
+    };
+
+    if let Some(uuid) = current
+        && let Some(player) = world.players.get_by_uuid(&uuid)
+        && in_range(&player)
+    {
+        return Some(player);
+    }
+
+    let mut nearest: Option<(f64, Arc<Player>)> = None;
+    world.players.iter_players(|_, player| {
+        let distance = player.position.lock().sub(&position).length_squared();
+        if in_range(player)
+            && is_simulated_for(player, position)
+            && nearest
+                .as_ref()
+                .is_none_or(|(nearest, _)| distance < *nearest)
+        {
+            nearest = Some((distance, player.clone()));
+        }
+        true
+    });
+    nearest.map(|(_, player)| player)
+}
+
+/// How much a mob's hit of `damage` hurts players on `difficulty`, or `None`
+/// on peaceful where mobs don't hurt players.
+///
+/// Vanilla: `Player.hurtServer`
+#[must_use]
+pub fn player_damage(damage: f32, difficulty: Difficulty) -> Option<f32> {
+    match difficulty {
+        Difficulty::Peaceful => None,
+        Difficulty::Easy => Some((damage / 2.0 + 1.0).min(damage)),
+        Difficulty::Normal => Some(damage),
+        Difficulty::Hard => Some(damage * 1.5),
+    }
+}
+
+/// Returns true if hostile mobs go after `player`, which they don't in
+/// creative or spectator mode.
+pub fn is_targetable(player: &Player) -> bool {
+    matches!(
+        player.game_mode.load(),
+        GameType::Survival | GameType::Adventure
+    )
+}
+
+/// Returns true if `position` is within the simulation distance of
+/// `player`, where mobs tick for them.
+fn is_simulated_for(player: &Player, position: Vector3<f64>) -> bool {
+    let player_pos = player.position.lock().to_block_pos();
+    let pos = position.to_block_pos();
+    let chunk_distance = ((pos.x() >> 4) - (player_pos.x() >> 4))
+        .abs()
+        .max(((pos.z() >> 4) - (player_pos.z() >> 4)).abs());
+    chunk_distance <= i32::from(STEEL_CONFIG.load().simulation_distance)
+}
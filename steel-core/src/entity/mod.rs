@@ -0,0 +1,540 @@
+//! This module contains entity-related traits and types.
+
+use std::io::Cursor;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+use simdnbt::borrow::{NbtCompound as NbtCompoundView, read_compound as read_borrowed_compound};
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use simdnbt::{FromNbtTag, ToNbtTag};
+use steel_protocol::packets::game::SoundSource;
+use steel_registry::REGISTRY;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_utils::math::Vector3;
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+use uuid::Uuid;
+
+use crate::inventory::container::Container;
+use crate::{inventory::equipment::EquipmentSlot, player::Player, world::World};
+
+pub mod ai;
+pub mod armor_stand;
+pub mod arrow;
+pub mod boat;
+pub mod creeper;
+pub mod dragon_fireball;
+pub mod ender_dragon;
+pub mod enderman;
+pub mod experience_orb;
+pub mod falling_block;
+pub mod firework_rocket;
+mod generic;
+pub mod ghast;
+pub mod ghast_fireball;
+pub mod iron_golem;
+pub mod item_frame;
+pub mod lightning_bolt;
+pub mod minecart;
+pub mod mob;
+pub mod painting;
+pub mod primed_tnt;
+pub mod registry;
+pub mod skeleton;
+pub mod slime;
+pub mod storage;
+pub mod villager;
+pub mod zombie;
+
+pub use armor_stand::ArmorStandEntity;
+pub use arrow::ArrowEntity;
+pub use boat::BoatEntity;
+pub use creeper::CreeperEntity;
+pub use dragon_fireball::DragonFireballEntity;
+pub use ender_dragon::EnderDragonEntity;
+pub use enderman::EndermanEntity;
+pub use experience_orb::ExperienceOrbEntity;
+pub use falling_block::FallingBlockEntity;
+pub use firework_rocket::FireworkRocketEntity;
+pub use generic::GenericEntity;
+pub use ghast::GhastEntity;
+pub use ghast_fireball::GhastFireballEntity;
+pub use iron_golem::IronGolemEntity;
+pub use item_frame::ItemFrameEntity;
+pub use lightning_bolt::LightningBoltEntity;
+pub use minecart::{ChestMinecartEntity, HopperMinecartEntity, MinecartEntity};
+pub use painting::PaintingEntity;
+pub use primed_tnt::PrimedTntEntity;
+pub use skeleton::SkeletonEntity;
+pub use slime::SlimeEntity;
+pub use villager::VillagerEntity;
+pub use zombie::ZombieEntity;
+
+/// The next network id handed out, shared by players and other entities.
+/// Starts at 1 since 0 is reserved.
+static ENTITY_COUNTER: AtomicI32 = AtomicI32::new(1);
+
+/// Allocates a new unique entity ID.
+///
+/// Vanilla: `Entity.ENTITY_COUNTER`
+pub fn next_entity_id() -> i32 {
+    ENTITY_COUNTER.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Allocates `count` entity IDs in a row and returns the first, for entities
+/// made of several parts that clients number one after another.
+pub fn next_entity_ids(count: i32) -> i32 {
+    ENTITY_COUNTER.fetch_add(count, Ordering::Relaxed)
+}
+
+/// Reads the UUID saved as four ints under `UUID`.
+///
+/// Vanilla: `UUIDUtil.uuidFromIntArray`
+#[must_use]
+pub fn uuid_from_nbt(nbt: &NbtCompound) -> Option<Uuid> {
+    let [a, b, c, d] = *nbt.int_array("UUID")? else {
+        return None;
+    };
+    let most = (i64::from(a) << 32) | i64::from(b as u32);
+    let least = (i64::from(c) << 32) | i64::from(d as u32);
+    Some(Uuid::from_u64_pair(most as u64, least as u64))
+}
+
+/// Saves `uuid` as four ints under `UUID`, the way vanilla stores it.
+///
+/// Vanilla: `UUIDUtil.uuidToIntArray`
+pub fn write_uuid(nbt: &mut NbtCompound, uuid: Uuid) {
+    let (most, least) = uuid.as_u64_pair();
+    nbt.insert(
+        "UUID",
+        NbtTag::IntArray(vec![
+            (most >> 32) as i32,
+            most as i32,
+            (least >> 32) as i32,
+            least as i32,
+        ]),
+    );
+}
+
+/// Reads the position saved under `Pos`.
+#[must_use]
+pub fn position_from_nbt(nbt: &NbtCompound) -> Option<Vector3<f64>> {
+    let [x, y, z] = *nbt.list("Pos")?.doubles()? else {
+        return None;
+    };
+    Some(Vector3::new(x, y, z))
+}
+
+/// Saves `position` under `Pos`, which decides the chunk an entity is loaded
+/// into.
+pub fn write_position(nbt: &mut NbtCompound, position: Vector3<f64>) {
+    nbt.insert(
+        "Pos",
+        NbtList::Double(vec![position.x, position.y, position.z]),
+    );
+}
+
+/// Reads the velocity saved under `Motion`.
+#[must_use]
+pub fn motion_from_nbt(nbt: &NbtCompound) -> Option<Vector3<f64>> {
+    let [x, y, z] = *nbt.list("Motion")?.doubles()? else {
+        return None;
+    };
+    Some(Vector3::new(x, y, z))
+}
+
+/// Saves `velocity` under `Motion`, in blocks per tick.
+pub fn write_motion(nbt: &mut NbtCompound, velocity: Vector3<f64>) {
+    nbt.insert(
+        "Motion",
+        NbtList::Double(vec![velocity.x, velocity.y, velocity.z]),
+    );
+}
+
+/// Saves `state` like `{Name: "minecraft:sand", Properties: {..}}`.
+///
+/// Vanilla: `NbtUtils.writeBlockState`
+#[must_use]
+pub fn block_state_to_nbt(state: BlockStateId) -> NbtCompound {
+    let mut nbt = NbtCompound::new();
+    nbt.insert("Name", state.get_block().key.to_string());
+    let properties = REGISTRY.blocks.get_properties(state);
+    if !properties.is_empty() {
+        let mut values = NbtCompound::new();
+        for (name, value) in properties {
+            values.insert(name, value);
+        }
+        nbt.insert("Properties", values);
+    }
+    nbt
+}
+
+/// Reads a block state saved with [`block_state_to_nbt`]. Returns `None` if
+/// it isn't a known state.
+///
+/// Vanilla: `NbtUtils.readBlockState`
+#[must_use]
+pub fn block_state_from_nbt(nbt: &NbtCompound) -> Option<BlockStateId> {
+    let name: Identifier = nbt.string("Name")?.to_str().parse().ok()?;
+    let properties: Vec<(String, String)> = nbt
+        .compound("Properties")
+        .map(|properties| {
+            properties
+                .iter()
+                .filter_map(|(key, value)| {
+                    Some((
+                        key.to_str().into_owned(),
+                        value.string()?.to_str().into_owned(),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let properties: Vec<(&str, &str)> = properties
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+    REGISTRY.blocks.state_id_from_properties(&name, &properties)
+}
+
+/// Parses an item stack saved like `{id: "minecraft:stone", count: 2}`,
+/// components included. Returns `None` for an unknown item.
+#[must_use]
+pub fn item_from_nbt(nbt: &NbtCompound) -> Option<ItemStack> {
+    // Item stacks only load from borrowed NBT, the way they are read from disk
+    let mut item = NbtCompound::new();
+    item.insert("item", nbt.clone());
+    let mut bytes = Vec::new();
+    item.write(&mut bytes);
+    let item = read_borrowed_compound(&mut Cursor::new(&bytes)).ok()?;
+    let item: NbtCompoundView<'_, '_> = (&item).into();
+    ItemStack::from_nbt_tag(item.get("item")?)
+}
+
+/// Saves the non-empty slots of `container` under `Items`, each with its
+/// `Slot`.
+///
+/// Vanilla: `ContainerHelper.saveAllItems`
+pub fn write_items(nbt: &mut NbtCompound, container: &dyn Container) {
+    let mut items = Vec::new();
+    for slot in 0..container.get_container_size() {
+        let item = container.get_item(slot);
+        if !item.is_empty()
+            && let NbtTag::Compound(mut item_nbt) = item.clone().to_nbt_tag()
+        {
+            item_nbt.insert("Slot", slot as i8);
+            items.push(item_nbt);
+        }
+    }
+    nbt.insert("Items", NbtList::Compound(items));
+}
+
+/// Puts the items saved under `Items` back into their slots of `container`.
+///
+/// Vanilla: `ContainerHelper.loadAllItems`
+pub fn read_items(nbt: &NbtCompound, container: &mut dyn Container) {
+    let Some(items) = nbt.list("Items").and_then(|items| items.compounds()) else {
+        return;
+    };
+    for item_nbt in items {
+        let Some(slot) = item_nbt.byte("Slot").map(|slot| slot as u8 as usize) else {
+            continue;
+        };
+        if slot < container.get_container_size()
+            && let Some(item) = item_from_nbt(item_nbt)
+        {
+            container.set_item(slot, item);
+        }
+    }
+}
+
+/// The movement keys a player riding an entity holds down.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VehicleInput {
+    /// Moving forward.
+    pub forward: bool,
+    /// Moving back.
+    pub backward: bool,
+    /// Moving or turning left.
+    pub left: bool,
+    /// Moving or turning right.
+    pub right: bool,
+}
+
+/// A trait for  entities.
+///
+/// This trait provides the core functionality for entities.
+/// It's based on Minecraft's `Entity` class.
+pub trait Entity {
+    /// Gets the Uuid of the entity.
+    fn get_uuid(&self) -> Uuid;
+
+    /// Gets the entity as a Player
+    fn as_player(self: Arc<Self>) -> Option<Arc<Player>> {
+        None
+    }
+}
+
+/// An entity kept in a world's [`storage::EntityStorage`].
+///
+/// Vanilla: `Entity`, as far as `PersistentEntitySectionManager` and
+/// `ServerEntity` use it
+pub trait StoredEntity: Entity + Send {
+    /// Gets the network id of the entity.
+    fn get_id(&self) -> i32;
+
+    /// Gets the type of the entity.
+    fn get_type(&self) -> EntityTypeRef;
+
+    /// Gets the entity's position, which decides the chunk it is stored in.
+    fn get_position(&self) -> Vector3<f64>;
+
+    /// Adds the packets that show the entity to a player that starts
+    /// tracking it.
+    ///
+    /// Vanilla: `ServerEntity.sendPairingData`
+    fn pair(&self, world: &World, pairing: &mut storage::EntityPairing);
+
+    /// Saves the entity to be stored with its chunk. Entities returning
+    /// `None`, like lightning bolts, are discarded once their chunk unloads.
+    ///
+    /// Vanilla: `Entity.saveWithoutId`, or `None` for `EntityType.canSerialize`
+    fn save(&self) -> Option<NbtCompound>;
+
+    /// Returns the box players click for the entity, or the part of it, with
+    /// network id `entity_id`, if it is one of them and can be clicked.
+    fn hitbox(&self, _entity_id: i32) -> Option<AABBd> {
+        None
+    }
+
+    /// Returns the box an explosion sees the entity by, or `None` if
+    /// explosions leave it alone. Block-like entities, like paintings and
+    /// armor stands, are only hit by explosions that `breaks_blocks`.
+    ///
+    /// Vanilla: `Entity.ignoreExplosion`
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        None
+    }
+
+    /// Returns how far above its feet an explosion pushes the entity from.
+    ///
+    /// Vanilla: `Entity.getEyeY`
+    fn explosion_push_height(&self) -> f64 {
+        f64::from(self.get_type().dimensions.eye_height)
+    }
+
+    /// Hurts the entity by `damage` and pushes it by `impulse` after an
+    /// explosion reached it. Returns what is left of it if that destroyed
+    /// it, which takes it out of the world. Mobs that died are removed on
+    /// their next tick instead, like after any other harm.
+    ///
+    /// Vanilla: `Entity.hurtServer` and `Entity.push`
+    fn explosion_hit(
+        &mut self,
+        _world: &World,
+        _damage: f32,
+        _impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        None
+    }
+}
+
+/// What is left of an entity that was destroyed.
+pub struct Remains {
+    /// Where the items drop.
+    pub pos: BlockPos,
+    /// The items the entity drops.
+    pub drops: Vec<ItemStack>,
+    /// The players that were riding the entity and get off.
+    pub passengers: Vec<Uuid>,
+}
+
+/// A mob players can hit and kill. It only describes how it sounds, what it
+/// drops and how it fights back; `World` hurts it, takes it out once it died
+/// and rewards the player that killed it.
+///
+/// Vanilla: `Mob`, as far as `Player.attack` and `LivingEntity.die` use it
+pub trait AttackableMob: StoredEntity + Sized {
+    /// The source the mob's hurt and death sounds play from.
+    const SOUND_SOURCE: SoundSource = SoundSource::Hostile;
+
+    /// The volume of the mob's hurt and death sounds.
+    const SOUND_VOLUME: f32 = 1.0;
+
+    /// Hurts the mob by `amount`. Returns the sound it makes, or `None` if
+    /// it was hurt too recently to be hurt again.
+    fn take_hit(&mut self, amount: f32) -> Option<i32>;
+
+    /// Hurts the mob by `amount` with an explosion, which most mobs take
+    /// like any other hit. Returns the sound it makes, like
+    /// [`AttackableMob::take_hit`].
+    fn take_blast(&mut self, amount: f32) -> Option<i32> {
+        self.take_hit(amount)
+    }
+
+    /// Hurts the mob by `damage` with an explosion, making its hurt sound if
+    /// it survives. It dies on its next tick otherwise.
+    fn hurt_by_explosion(&mut self, world: &World, damage: f32) {
+        if let Some(hurt_sound) = self.take_blast(damage)
+            && !self.is_dead()
+        {
+            world.play_sound(
+                hurt_sound,
+                Self::SOUND_SOURCE,
+                self.get_position().to_block_pos(),
+                Self::SOUND_VOLUME,
+                1.0,
+                None,
+            );
+        }
+    }
+
+    /// Returns whether the mob died.
+    fn is_dead(&self) -> bool;
+
+    /// Turns the mob against `player`, who just hurt it.
+    fn retaliate(&mut self, _world: &World, _player: &Player) {}
+
+    /// The sound the mob makes when it dies.
+    fn death_sound(&self) -> i32;
+
+    /// How much experience the player that kills the mob gets.
+    fn experience_reward(&self) -> i32;
+
+    /// The items the mob drops when it dies.
+    fn death_drops(&self) -> Vec<ItemStack> {
+        Vec::new()
+    }
+}
+
+/// A trait for living entities that can take damage, heal, and die.
+///
+/// This trait provides the core functionality for entities that have health,
+/// can be damaged, and can die. It's based on Minecraft's `LivingEntity` class.
+pub trait LivingEntity: Entity {
+    /// Gets the current health of the entity.
+    fn get_health(&self) -> f32;
+
+    /// Sets the health of the entity, clamped between 0 and max health.
+    fn set_health(&mut self, health: f32);
+
+    /// Gets the maximum health of the entity.
+    fn get_max_health(&self) -> f32;
+
+    /// Heals the entity by the specified amount.
+    fn heal(&mut self, amount: f32) {
+        let current_health = self.get_health();
+        if current_health > 0.0 {
+            self.set_health(current_health + amount);
+        }
+    }
+
+    /// Returns true if the entity is dead or dying (health <= 0).
+    fn is_dead_or_dying(&self) -> bool {
+        self.get_health() <= 0.0
+    }
+
+    /// Returns true if the entity is alive (health > 0).
+    fn is_alive(&self) -> bool {
+        !self.is_dead_or_dying()
+    }
+
+    /// Returns how much experience the entity drops when a player kills it.
+    ///
+    /// Vanilla: `LivingEntity.getBaseExperienceReward`
+    // TODO: Drop it through `World::spawn_experience_orbs` once entities die
+    // from a damage source
+    fn experience_reward(&self) -> i32 {
+        0
+    }
+
+    /// Gets the entity's position.
+    fn get_position(&self) -> Vector3<f64>;
+
+    /// Gets the absorption amount (extra health from effects like absorption).
+    fn get_absorption_amount(&self) -> f32;
+
+    /// Sets the absorption amount.
+    fn set_absorption_amount(&mut self, amount: f32);
+
+    /// Gets the entity's armor value.
+    fn get_armor_value(&self) -> i32;
+
+    /// Checks if the entity can be affected by potions.
+    fn is_affected_by_potions(&self) -> bool {
+        true
+    }
+
+    /// Checks if the entity is attackable.
+    fn attackable(&self) -> bool {
+        true
+    }
+
+    /// Checks if the entity is currently using an item.
+    fn is_using_item(&self) -> bool {
+        false
+    }
+
+    /// Checks if the entity is blocking with a shield or similar item.
+    fn is_blocking(&self) -> bool {
+        false
+    }
+
+    /// Checks if the entity is fall flying (using elytra).
+    fn is_fall_flying(&self) -> bool {
+        false
+    }
+
+    /// Checks if the entity is sleeping.
+    fn is_sleeping(&self) -> bool {
+        false
+    }
+
+    /// Stops the entity from sleeping.
+    fn stop_sleeping(&mut self) {}
+
+    /// Checks if the entity is sprinting.
+    fn is_sprinting(&self) -> bool {
+        false
+    }
+
+    /// Sets whether the entity is sprinting.
+    fn set_sprinting(&mut self, sprinting: bool);
+
+    /// Gets the entity's speed attribute value.
+    fn get_speed(&self) -> f32;
+
+    /// Sets the entity's speed.
+    fn set_speed(&mut self, speed: f32);
+
+    // Equipment methods
+
+    /// Gets a clone of the item in the specified equipment slot.
+    ///
+    /// Default implementation returns an empty stack.
+    fn get_item_by_slot(&self, _slot: EquipmentSlot) -> ItemStack {
+        ItemStack::empty()
+    }
+
+    /// Gets the main hand item.
+    fn get_main_hand_item(&self) -> ItemStack {
+        self.get_item_by_slot(EquipmentSlot::MainHand)
+    }
+
+    /// Gets the off hand item.
+    fn get_off_hand_item(&self) -> ItemStack {
+        self.get_item_by_slot(EquipmentSlot::OffHand)
+    }
+
+    /// Checks if the main hand slot is empty.
+    fn is_main_hand_empty(&self) -> bool {
+        self.get_item_by_slot(EquipmentSlot::MainHand).is_empty()
+    }
+
+    /// Checks if the off hand slot is empty.
+    fn is_off_hand_empty(&self) -> bool {
+        self.get_item_by_slot(EquipmentSlot::OffHand).is_empty()
+    }
+}
@@ -0,0 +1,420 @@
+//! Paintings hanging on walls.
+
+use std::ptr;
+
+use rand::seq::IteratorRandom;
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_protocol::packets::game::{CAddEntity, SoundSource};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::Direction;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::painting_variant::{PaintingVariant, PaintingVariantRef};
+use steel_registry::vanilla_entity_data::PaintingEntityData;
+use steel_registry::{REGISTRY, sound_events, vanilla_blocks, vanilla_entities, vanilla_items};
+use steel_utils::math::{Axis, Vector3};
+use steel_utils::{BlockPos, Identifier};
+use uuid::Uuid;
+
+use crate::entity::storage::{EntityList, EntityPairing};
+use crate::entity::{self, Entity, Remains, StoredEntity};
+use crate::physics::{CollisionWorld, WorldCollisionProvider, join_is_not_empty};
+use crate::world::World;
+
+/// The tag of the painting variants players can place without picking one.
+pub const PLACEABLE_TAG: Identifier = Identifier::vanilla_static("placeable");
+
+/// How far a painting sticks out of the wall it hangs on.
+const THICKNESS: f64 = 0.0625;
+
+/// A painting hanging on a wall.
+///
+/// Vanilla: `Painting`
+pub struct PaintingEntity {
+    /// The network id of the entity.
+    pub id: i32,
+    /// The persistent id of the entity.
+    pub uuid: Uuid,
+    /// The block the painting hangs in, in front of the wall.
+    pub pos: BlockPos,
+    /// The direction the painting faces, away from the wall.
+    pub direction: Direction,
+    /// The motive shown on the painting, which also decides its size.
+    pub variant: PaintingVariantRef,
+    entity_data: PaintingEntityData,
+}
+
+impl PaintingEntity {
+    /// Creates a painting of `variant` hanging in `pos`, facing `direction`.
+    #[must_use]
+    pub fn new(id: i32, pos: BlockPos, direction: Direction, variant: PaintingVariantRef) -> Self {
+        let mut entity_data = PaintingEntityData::new();
+        entity_data.direction.set(direction);
+        entity_data
+            .painting_variant
+            .set(*REGISTRY.painting_variants.get_id(variant) as i32);
+
+        Self {
+            id,
+            uuid: Uuid::new_v4(),
+            pos,
+            direction,
+            variant,
+            entity_data,
+        }
+    }
+
+    /// Creates a painting for a player hanging one on a wall, picking a random
+    /// placeable motive among the largest that fit. Returns `None` if none fit.
+    ///
+    /// Vanilla: `Painting.create`
+    #[must_use]
+    pub fn create(
+        world: &World,
+        id: i32,
+        pos: BlockPos,
+        direction: Direction,
+        others: &EntityList<PaintingEntity>,
+    ) -> Option<Self> {
+        let fitting: Vec<_> = REGISTRY
+            .painting_variants
+            .iter_tag(&PLACEABLE_TAG)
+            .filter(|&variant| {
+                let bounding_box = bounding_box(pos, direction, variant);
+                survives(world, &bounding_box, direction, |other_box| {
+                    others
+                        .iter()
+                        .any(|other| other.bounding_box().intersects(other_box))
+                })
+            })
+            .collect();
+
+        let largest = fitting.iter().map(|&variant| area(variant)).max()?;
+        let variant = fitting
+            .into_iter()
+            .filter(|&variant| area(variant) == largest)
+            .choose(&mut rand::rng())?;
+        Some(Self::new(id, pos, direction, variant))
+    }
+
+    /// Applies the painting tags of `nbt`, like where it hangs and its motive.
+    /// Tags that aren't set keep their current value.
+    ///
+    /// Vanilla: `Painting.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(uuid) = entity::uuid_from_nbt(nbt) {
+            self.uuid = uuid;
+        }
+        if let Some(&[x, y, z]) = nbt.int_array("block_pos") {
+            self.pos = BlockPos::new(x, y, z);
+        }
+        if let Some(direction) = nbt.byte("facing").and_then(direction_from_nbt) {
+            self.direction = direction;
+            self.entity_data.direction.set(direction);
+        }
+        if let Some(variant) = nbt
+            .string("variant")
+            .and_then(|key| key.to_str().parse::<Identifier>().ok())
+            .and_then(|key| REGISTRY.painting_variants.by_key(&key))
+        {
+            self.variant = variant;
+            self.entity_data
+                .painting_variant
+                .set(*REGISTRY.painting_variants.get_id(variant) as i32);
+        }
+    }
+
+    /// The packet that adds this painting to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        let mut packet = CAddEntity::new(
+            self.id,
+            self.uuid,
+            *REGISTRY.entity_types.get_id(vanilla_entities::PAINTING) as i32,
+            f64::from(self.pos.x()),
+            f64::from(self.pos.y()),
+            f64::from(self.pos.z()),
+            0.0,
+            0.0,
+        );
+        // Clients read the direction from the data instead of the rotation
+        packet.data = self.direction as i32;
+        packet
+    }
+
+    /// The entity data clients need right after the painting is added, like
+    /// its motive.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        self.entity_data.pack_all()
+    }
+
+    /// Returns the box the painting takes up in front of the wall.
+    #[must_use]
+    pub fn bounding_box(&self) -> AABBd {
+        bounding_box(self.pos, self.direction, self.variant)
+    }
+
+    /// Returns true if changing the block at `pos` can knock the painting off,
+    /// because it is part of the wall behind or of the space the painting
+    /// takes up.
+    #[must_use]
+    pub fn depends_on(&self, pos: BlockPos) -> bool {
+        let bounding_box = self.bounding_box();
+        let block = AABBd::new(
+            f64::from(pos.x()),
+            f64::from(pos.y()),
+            f64::from(pos.z()),
+            f64::from(pos.x() + 1),
+            f64::from(pos.y() + 1),
+            f64::from(pos.z() + 1),
+        );
+        bounding_box.intersects(&block)
+            || support_positions(&bounding_box, self.direction).any(|support| support == pos)
+    }
+
+    /// Returns true if the painting can stay on the wall, `others` being the
+    /// other paintings in the world.
+    ///
+    /// Vanilla: `HangingEntity.survives`
+    #[must_use]
+    pub fn survives(&self, world: &World, others: &EntityList<PaintingEntity>) -> bool {
+        survives(world, &self.bounding_box(), self.direction, |other_box| {
+            others
+                .iter()
+                .any(|other| other.id != self.id && other.bounding_box().intersects(other_box))
+        })
+    }
+}
+
+impl Entity for PaintingEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
+impl StoredEntity for PaintingEntity {
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        vanilla_entities::PAINTING
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        Vector3::new(
+            f64::from(self.pos.x()) + 0.5,
+            f64::from(self.pos.y()),
+            f64::from(self.pos.z()) + 0.5,
+        )
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        entity::write_position(&mut nbt, self.get_position());
+        entity::write_uuid(&mut nbt, self.uuid);
+        nbt.insert(
+            "block_pos",
+            NbtTag::IntArray(vec![self.pos.x(), self.pos.y(), self.pos.z()]),
+        );
+        nbt.insert("facing", direction_to_nbt(self.direction));
+        nbt.insert("variant", self.variant.key.to_string());
+        Some(nbt)
+    }
+
+    fn explosion_box(&self, breaks_blocks: bool) -> Option<AABBd> {
+        breaks_blocks.then(|| self.bounding_box())
+    }
+
+    /// Knocks the painting off the wall.
+    ///
+    /// Vanilla: `HangingEntity.hurtServer`
+    fn explosion_hit(
+        &mut self,
+        world: &World,
+        _damage: f32,
+        _impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        world.play_sound(
+            sound_events::ENTITY_PAINTING_BREAK,
+            SoundSource::Neutral,
+            self.pos,
+            1.0,
+            1.0,
+            None,
+        );
+        Some(Remains {
+            pos: self.pos,
+            drops: vec![ItemStack::new(&vanilla_items::ITEMS.painting)],
+            passengers: Vec::new(),
+        })
+    }
+}
+
+/// Saves a horizontal direction as its 2D data value.
+const fn direction_to_nbt(direction: Direction) -> i8 {
+    match direction {
+        Direction::West => 1,
+        Direction::North => 2,
+        Direction::East => 3,
+        _ => 0,
+    }
+}
+
+/// Reads a horizontal direction saved as its 2D data value.
+const fn direction_from_nbt(value: i8) -> Option<Direction> {
+    Some(match value {
+        0 => Direction::South,
+        1 => Direction::West,
+        2 => Direction::North,
+        3 => Direction::East,
+        _ => return None,
+    })
+}
+
+/// Returns how many blocks a painting of `variant` covers.
+fn area(variant: &PaintingVariant) -> i32 {
+    variant.width * variant.height
+}
+
+/// Returns the box a painting of `variant` hanging in `pos` takes up.
+///
+/// Vanilla: `Painting.calculateBoundingBox`
+fn bounding_box(pos: BlockPos, direction: Direction, variant: PaintingVariantRef) -> AABBd {
+    // Paintings with an even size are centered on the edge between blocks
+    let offset = |size: i32| if size % 2 == 0 { 0.5 } else { 0.0 };
+    let (fx, _, fz) = direction.offset();
+    let (lx, _, lz) = direction.rotate_y_counter_clockwise().offset();
+    let along_wall = offset(variant.width);
+    let center = Vector3::new(
+        f64::from(pos.x()) + 0.5 - f64::from(fx) * 0.468_75 + f64::from(lx) * along_wall,
+        f64::from(pos.y()) + 0.5 + offset(variant.height),
+        f64::from(pos.z()) + 0.5 - f64::from(fz) * 0.468_75 + f64::from(lz) * along_wall,
+    );
+
+    let width = f64::from(variant.width);
+    let (size_x, size_z) = match direction.get_axis() {
+        Axis::X => (THICKNESS, width),
+        _ => (width, THICKNESS),
+    };
+    let half_height = f64::from(variant.height) / 2.0;
+    AABBd::new(
+        center.x - size_x / 2.0,
+        center.y - half_height,
+        center.z - size_z / 2.0,
+        center.x + size_x / 2.0,
+        center.y + half_height,
+        center.z + size_z / 2.0,
+    )
+}
+
+/// Returns the blocks of the wall behind a painting.
+///
+/// Vanilla: `HangingEntity.calculateSupportBox`
+fn support_positions(bounding_box: &AABBd, direction: Direction) -> impl Iterator<Item = BlockPos> {
+    let (dx, dy, dz) = direction.offset();
+    let support = AABBd::new(
+        bounding_box.min_x - f64::from(dx) * 0.5,
+        bounding_box.min_y - f64::from(dy) * 0.5,
+        bounding_box.min_z - f64::from(dz) * 0.5,
+        bounding_box.max_x - f64::from(dx) * 0.5,
+        bounding_box.max_y - f64::from(dy) * 0.5,
+        bounding_box.max_z - f64::from(dz) * 0.5,
+    )
+    .deflate(1.0E-7);
+
+    #[allow(clippy::cast_possible_truncation)]
+    let (min, max) = (
+        BlockPos::new(
+            support.min_x.floor() as i32,
+            support.min_y.floor() as i32,
+            support.min_z.floor() as i32,
+        ),
+        BlockPos::new(
+            support.max_x.floor() as i32,
+            support.max_y.floor() as i32,
+            support.max_z.floor() as i32,
+        ),
+    );
+    (min.y()..=max.y()).flat_map(move |y| {
+        (min.z()..=max.z())
+            .flat_map(move |z| (min.x()..=max.x()).map(move |x| BlockPos::new(x, y, z)))
+    })
+}
+
+/// Returns true if a painting taking up `bounding_box` is free of blocks,
+/// hangs on a wall of solid blocks or diodes, and `overlaps_painting` doesn't
+/// find a painting in the way.
+///
+/// Vanilla: `HangingEntity.survives`
+fn survives(
+    world: &World,
+    bounding_box: &AABBd,
+    direction: Direction,
+    overlaps_painting: impl Fn(&AABBd) -> bool,
+) -> bool {
+    let collision_world = WorldCollisionProvider::new(world);
+    if collision_world
+        .get_block_collisions(bounding_box)
+        .iter()
+        .any(|collision| join_is_not_empty(bounding_box, collision))
+    {
+        return false;
+    }
+
+    let supported = support_positions(bounding_box, direction).all(|pos| {
+        let state = world.get_block_state(&pos);
+        let block = state.get_block();
+        state.is_solid()
+            || ptr::eq(block, vanilla_blocks::REPEATER)
+            || ptr::eq(block, vanilla_blocks::COMPARATOR)
+    });
+    supported && !overlaps_painting(bounding_box)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(width: i32, height: i32) -> PaintingVariantRef {
+        Box::leak(Box::new(PaintingVariant {
+            key: Identifier::vanilla_static("test"),
+            width,
+            height,
+            asset_id: Identifier::vanilla_static("test"),
+            title: None,
+            author: None,
+        }))
+    }
+
+    #[test]
+    fn even_paintings_center_on_block_edges() {
+        let pos = BlockPos::new(0, 64, 0);
+        let small = bounding_box(pos, Direction::South, variant(1, 1));
+        assert!((small.min_x - 0.0).abs() < 1e-9 && (small.max_x - 1.0).abs() < 1e-9);
+        assert!((small.min_y - 64.0).abs() < 1e-9 && (small.max_y - 65.0).abs() < 1e-9);
+        assert!((small.max_z - small.min_z - THICKNESS).abs() < 1e-9);
+
+        let wide = bounding_box(pos, Direction::South, variant(2, 2));
+        assert!((wide.min_x - 0.0).abs() < 1e-9 && (wide.max_x - 2.0).abs() < 1e-9);
+        assert!((wide.min_y - 64.0).abs() < 1e-9 && (wide.max_y - 66.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn supported_by_the_wall_behind() {
+        let pos = BlockPos::new(0, 64, 0);
+        let bounding_box = bounding_box(pos, Direction::South, variant(2, 1));
+        let support: Vec<_> = support_positions(&bounding_box, Direction::South).collect();
+        assert_eq!(
+            support,
+            [BlockPos::new(0, 64, -1), BlockPos::new(1, 64, -1)]
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Lit TNT counting down to its explosion.
+
+use std::f64::consts::TAU;
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, CEntityPositionSync};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::vanilla_entity_data::TntEntityData;
+use steel_registry::{REGISTRY, vanilla_entities};
+use steel_utils::BlockPos;
+use steel_utils::math::Vector3;
+use uuid::Uuid;
+
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, Entity, Remains, StoredEntity};
+use crate::physics::{self, EntityPhysicsState, GravityAffected, WorldCollisionProvider};
+use crate::world::World;
+use crate::world::explosion::{Explosion, ExplosionSource};
+
+/// How many ticks lit TNT burns before it explodes.
+pub const DEFAULT_FUSE: u8 = 80;
+
+/// A block of TNT that was lit, falling like an entity until its fuse runs
+/// out.
+///
+/// Vanilla: `PrimedTnt`
+pub struct PrimedTntEntity {
+    /// The network id of the entity.
+    pub id: i32,
+    /// The persistent id of the entity.
+    pub uuid: Uuid,
+    /// How many ticks are left until the TNT explodes.
+    pub fuse: u8,
+    physics: EntityPhysicsState,
+    entity_data: TntEntityData,
+}
+
+impl PrimedTntEntity {
+    /// Creates TNT lit in the block at `pos`, exploding after `fuse` ticks.
+    /// It hops up and a little to a random side.
+    #[must_use]
+    pub fn new(id: i32, pos: BlockPos, fuse: u8) -> Self {
+        let position = Vector3::new(
+            f64::from(pos.x()) + 0.5,
+            f64::from(pos.y()),
+            f64::from(pos.z()) + 0.5,
+        );
+        Self::at(id, position, fuse)
+    }
+
+    /// Creates TNT lit at `position`, like summoned TNT, exploding after
+    /// `fuse` ticks.
+    #[must_use]
+    pub fn at(id: i32, position: Vector3<f64>, fuse: u8) -> Self {
+        let mut physics = EntityPhysicsState::new(position, vanilla_entities::TNT);
+        let angle = rand::random::<f64>() * TAU;
+        physics.velocity = Vector3::new(-angle.sin() * 0.02, 0.2, -angle.cos() * 0.02);
+
+        let mut entity_data = TntEntityData::new();
+        // Clients count down and flash the TNT from the synced fuse
+        entity_data.fuse.set(i32::from(fuse));
+
+        Self {
+            id,
+            uuid: Uuid::new_v4(),
+            fuse,
+            physics,
+            entity_data,
+        }
+    }
+
+    /// Applies the TNT tags of `nbt`, like the fuse left. Tags that aren't
+    /// set keep their current value, and the position is never loaded.
+    ///
+    /// Vanilla: `PrimedTnt.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(uuid) = entity::uuid_from_nbt(nbt) {
+            self.uuid = uuid;
+        }
+        if let Some(velocity) = entity::motion_from_nbt(nbt) {
+            self.physics.velocity = velocity;
+        }
+        if let Some(fuse) = nbt.short("fuse") {
+            self.fuse = fuse.clamp(0, i16::from(u8::MAX)) as u8;
+            self.entity_data.fuse.set(i32::from(self.fuse));
+        }
+    }
+
+    /// Returns the position of the TNT.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.physics.position
+    }
+
+    /// Returns the box the TNT takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.physics.bounding_box
+    }
+
+    /// Adds `impulse` to the velocity of the TNT, like the push of another
+    /// explosion.
+    pub fn push(&mut self, impulse: Vector3<f64>) {
+        self.physics.velocity += impulse;
+    }
+
+    /// The packet that adds this TNT to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        let position = self.position();
+        CAddEntity::new(
+            self.id,
+            self.uuid,
+            *REGISTRY.entity_types.get_id(vanilla_entities::TNT) as i32,
+            position.x,
+            position.y,
+            position.z,
+            0.0,
+            0.0,
+        )
+    }
+
+    /// The entity data clients need right after the TNT is added, like its
+    /// fuse.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        self.entity_data.pack_all()
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Advances the TNT by a tick, letting it fall and burn. Returns the
+    /// explosion it sets off once the fuse ran out.
+    ///
+    /// Vanilla: `PrimedTnt.tick`
+    pub fn tick(&mut self, world: &World) -> Option<Explosion> {
+        let result = physics::fall(self, &WorldCollisionProvider::new(world));
+        if result.on_ground {
+            self.physics.velocity = bounce(self.physics.velocity);
+        }
+
+        if result.actual_movement != Vector3::new(0.0, 0.0, 0.0) {
+            let velocity = self.physics.velocity;
+            world.broadcast_to_trackers(
+                result.final_position,
+                CEntityPositionSync {
+                    entity_id: self.id,
+                    x: result.final_position.x,
+                    y: result.final_position.y,
+                    z: result.final_position.z,
+                    velocity_x: velocity.x,
+                    velocity_y: velocity.y,
+                    velocity_z: velocity.z,
+                    yaw: 0.0,
+                    pitch: 0.0,
+                    on_ground: result.on_ground,
+                },
+            );
+        }
+
+        self.fuse = self.fuse.saturating_sub(1);
+        self.entity_data.fuse.set(i32::from(self.fuse));
+        if self.fuse > 0 {
+            return None;
+        }
+
+        // The blast comes from just above the bottom of the block
+        let position = self.position();
+        let height = f64::from(vanilla_entities::TNT.dimensions.height);
+        let center = Vector3::new(position.x, position.y + height * 0.0625, position.z);
+        let source = ExplosionSource::Tnt;
+        Some(Explosion::new(center, source.default_power(), source))
+    }
+}
+
+impl Entity for PrimedTntEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
+impl StoredEntity for PrimedTntEntity {
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        vanilla_entities::TNT
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    /// Vanilla: `PrimedTnt.addAdditionalSaveData`
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        entity::write_position(&mut nbt, self.position());
+        entity::write_uuid(&mut nbt, self.uuid);
+        entity::write_motion(&mut nbt, self.physics.velocity);
+        nbt.insert("fuse", i16::from(self.fuse));
+        Some(nbt)
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    /// TNT is pushed from its feet rather than its eyes.
+    fn explosion_push_height(&self) -> f64 {
+        0.0
+    }
+
+    fn explosion_hit(
+        &mut self,
+        _world: &World,
+        _damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.push(impulse);
+        None
+    }
+}
+
+impl GravityAffected for PrimedTntEntity {
+    const GRAVITY: f64 = 0.04;
+
+    fn physics_mut(&mut self) -> &mut EntityPhysicsState {
+        &mut self.physics
+    }
+}
+
+/// Returns a fuse for TNT lit by another explosion, so TNT blown up together
+/// goes off shortly after in a spread out chain.
+///
+/// Vanilla: `TntBlock.wasExploded`
+#[must_use]
+pub fn chain_reaction_fuse() -> u8 {
+    rand::random_range(0..DEFAULT_FUSE / 4) + DEFAULT_FUSE / 8
+}
+
+/// The velocity of TNT that landed with `velocity`, sliding less and
+/// bouncing a little off the ground.
+fn bounce(velocity: Vector3<f64>) -> Vector3<f64> {
+    velocity.multiply(0.7, -0.5, 0.7)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::stop_blocked_axes;
+
+    #[test]
+    fn chain_reactions_burn_shorter() {
+        for _ in 0..100 {
+            assert!((10..30).contains(&chain_reaction_fuse()));
+        }
+    }
+
+    #[test]
+    fn landing_stops_the_fall() {
+        let delta = Vector3::new(0.1, -0.5, 0.0);
+        let movement = Vector3::new(0.1, -0.2, 0.0);
+        let velocity = bounce(stop_blocked_axes(delta, movement) * PrimedTntEntity::DRAG);
+        assert!((velocity.x - 0.1 * 0.98 * 0.7).abs() < 1e-9);
+        assert!(velocity.y.abs() < 1e-9);
+    }
+
+    #[test]
+    fn saved_tnt_keeps_burning() {
+        let mut tnt = PrimedTntEntity::new(1, BlockPos::new(4, 70, -2), DEFAULT_FUSE);
+        tnt.fuse = 17;
+        let nbt = tnt.save().expect("TNT is saved");
+
+        let mut loaded = PrimedTntEntity::at(2, tnt.position(), DEFAULT_FUSE);
+        loaded.read_nbt(&nbt);
+        assert_eq!(loaded.uuid, tnt.uuid);
+        assert_eq!(loaded.fuse, 17);
+        assert_eq!(loaded.physics.velocity, tnt.physics.velocity);
+        assert_eq!(entity::position_from_nbt(&nbt), Some(tnt.position()));
+    }
+}
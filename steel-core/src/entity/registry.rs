@@ -0,0 +1,393 @@
+//! Entity registry for creating entities by their type.
+
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+use simdnbt::owned::NbtCompound;
+use steel_registry::blocks::properties::Direction;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::{
+    REGISTRY, vanilla_blocks, vanilla_entities, vanilla_items, vanilla_painting_variants,
+};
+use steel_utils::math::Vector3;
+
+use crate::entity::boat::WoodType;
+use crate::entity::{
+    self, ArmorStandEntity, ArrowEntity, BoatEntity, ChestMinecartEntity, CreeperEntity,
+    DragonFireballEntity, EnderDragonEntity, EndermanEntity, ExperienceOrbEntity,
+    FallingBlockEntity, FireworkRocketEntity, GhastEntity, GhastFireballEntity,
+    HopperMinecartEntity, IronGolemEntity, ItemFrameEntity, LightningBoltEntity, MinecartEntity,
+    PaintingEntity, PrimedTntEntity, SkeletonEntity, SlimeEntity, VillagerEntity, ZombieEntity,
+    ender_dragon, firework_rocket, primed_tnt, slime,
+};
+use crate::world::World;
+
+/// Factory function type for adding entities to a world.
+///
+/// Takes the world, the network id and position of the entity and the NBT
+/// applied over its defaults. Returns `false` if the NBT gives it the UUID
+/// of an entity that already exists.
+pub type EntityFactory = fn(&World, i32, Vector3<f64>, Option<&NbtCompound>) -> bool;
+
+/// Registry entry for an entity type.
+struct EntityEntry {
+    /// Factory function to create instances.
+    factory: Option<EntityFactory>,
+}
+
+/// Registry for entity factories.
+///
+/// Maps `EntityType` to factory functions for the entities that have
+/// behaviour of their own. Entities of other types are spawned as
+/// [`entity::GenericEntity`].
+pub struct EntityRegistry {
+    entries: Vec<EntityEntry>,
+}
+
+impl EntityRegistry {
+    /// Creates a new empty registry with entries for all entity types.
+    #[must_use]
+    pub fn new() -> Self {
+        let count = REGISTRY.entity_types.len();
+        let entries = (0..count).map(|_| EntityEntry { factory: None }).collect();
+
+        Self { entries }
+    }
+
+    /// Registers a factory function for an entity type.
+    pub fn register(&mut self, entity_type: EntityTypeRef, factory: EntityFactory) {
+        let id = *REGISTRY.entity_types.get_id(entity_type);
+        self.entries[id].factory = Some(factory);
+    }
+
+    /// Adds a new entity to `world`, returning whether it was added.
+    ///
+    /// Returns `None` if no factory is registered for the given type.
+    #[must_use]
+    pub fn create(
+        &self,
+        entity_type: EntityTypeRef,
+        world: &World,
+        id: i32,
+        pos: Vector3<f64>,
+        nbt: Option<&NbtCompound>,
+    ) -> Option<bool> {
+        let type_id = *REGISTRY.entity_types.get_id(entity_type);
+        self.entries
+            .get(type_id)?
+            .factory
+            .map(|f| f(world, id, pos, nbt))
+    }
+
+    /// Returns whether a factory is registered for the given type.
+    #[must_use]
+    pub fn has_factory(&self, entity_type: EntityTypeRef) -> bool {
+        let id = *REGISTRY.entity_types.get_id(entity_type);
+        self.entries.get(id).is_some_and(|e| e.factory.is_some())
+    }
+}
+
+impl Default for EntityRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wrapper for the global entity registry that implements `Deref`.
+pub struct EntityRegistryLock(OnceLock<EntityRegistry>);
+
+impl Deref for EntityRegistryLock {
+    type Target = EntityRegistry;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get().expect("Entity registry not initialized")
+    }
+}
+
+impl EntityRegistryLock {
+    /// Sets the registry. Returns `Err` if already initialized.
+    pub fn set(&self, registry: EntityRegistry) -> Result<(), EntityRegistry> {
+        self.0.set(registry)
+    }
+}
+
+/// Global entity registry.
+///
+/// Access via deref: `ENTITIES.create(type, world, id, pos, nbt)`
+pub static ENTITIES: EntityRegistryLock = EntityRegistryLock(OnceLock::new());
+
+/// Initializes the global entity registry.
+///
+/// This should be called once after the main registry is frozen.
+///
+/// # Panics
+///
+/// Panics if called more than once.
+pub fn init_entities() {
+    let mut registry = EntityRegistry::new();
+
+    registry.register(vanilla_entities::ARMOR_STAND, |world, id, pos, nbt| {
+        let mut stand = ArmorStandEntity::new(id, pos);
+        if let Some(nbt) = nbt {
+            stand.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.armor_stands, stand)
+    });
+
+    registry.register(vanilla_entities::PAINTING, |world, id, pos, nbt| {
+        let mut painting = PaintingEntity::new(
+            id,
+            pos.to_block_pos(),
+            Direction::South,
+            vanilla_painting_variants::KEBAB,
+        );
+        if let Some(nbt) = nbt {
+            painting.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.paintings, painting)
+    });
+
+    registry.register(vanilla_entities::ITEM_FRAME, |world, id, pos, nbt| {
+        add_item_frame(world, id, pos, nbt, false)
+    });
+
+    registry.register(vanilla_entities::GLOW_ITEM_FRAME, |world, id, pos, nbt| {
+        add_item_frame(world, id, pos, nbt, true)
+    });
+
+    registry.register(vanilla_entities::FALLING_BLOCK, |world, id, pos, nbt| {
+        // Vanilla falls back to sand for blocks without a state
+        let mut block = FallingBlockEntity::at(id, pos, vanilla_blocks::SAND.default_state());
+        if let Some(nbt) = nbt {
+            block.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.falling_blocks, block)
+    });
+
+    registry.register(vanilla_entities::MINECART, |world, id, pos, nbt| {
+        let mut minecart = MinecartEntity::new(id, vanilla_entities::MINECART, pos);
+        if let Some(nbt) = nbt {
+            minecart.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.minecarts, minecart)
+    });
+
+    registry.register(vanilla_entities::CHEST_MINECART, |world, id, pos, nbt| {
+        let mut minecart = ChestMinecartEntity::new(id, pos);
+        if let Some(nbt) = nbt {
+            minecart.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.chest_minecarts, minecart)
+    });
+
+    registry.register(vanilla_entities::HOPPER_MINECART, |world, id, pos, nbt| {
+        let mut minecart = HopperMinecartEntity::new(id, pos);
+        if let Some(nbt) = nbt {
+            minecart.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.hopper_minecarts, minecart)
+    });
+
+    registry.register(vanilla_entities::OAK_BOAT, |world, id, pos, nbt| {
+        add_boat(world, id, pos, nbt, WoodType::Oak)
+    });
+    registry.register(vanilla_entities::SPRUCE_BOAT, |world, id, pos, nbt| {
+        add_boat(world, id, pos, nbt, WoodType::Spruce)
+    });
+    registry.register(vanilla_entities::BIRCH_BOAT, |world, id, pos, nbt| {
+        add_boat(world, id, pos, nbt, WoodType::Birch)
+    });
+    registry.register(vanilla_entities::JUNGLE_BOAT, |world, id, pos, nbt| {
+        add_boat(world, id, pos, nbt, WoodType::Jungle)
+    });
+    registry.register(vanilla_entities::ACACIA_BOAT, |world, id, pos, nbt| {
+        add_boat(world, id, pos, nbt, WoodType::Acacia)
+    });
+    registry.register(vanilla_entities::CHERRY_BOAT, |world, id, pos, nbt| {
+        add_boat(world, id, pos, nbt, WoodType::Cherry)
+    });
+    registry.register(vanilla_entities::DARK_OAK_BOAT, |world, id, pos, nbt| {
+        add_boat(world, id, pos, nbt, WoodType::DarkOak)
+    });
+    registry.register(vanilla_entities::PALE_OAK_BOAT, |world, id, pos, nbt| {
+        add_boat(world, id, pos, nbt, WoodType::PaleOak)
+    });
+    registry.register(vanilla_entities::MANGROVE_BOAT, |world, id, pos, nbt| {
+        add_boat(world, id, pos, nbt, WoodType::Mangrove)
+    });
+    registry.register(vanilla_entities::BAMBOO_RAFT, |world, id, pos, nbt| {
+        add_boat(world, id, pos, nbt, WoodType::Bamboo)
+    });
+
+    registry.register(vanilla_entities::TNT, |world, id, pos, nbt| {
+        let mut tnt = PrimedTntEntity::at(id, pos, primed_tnt::DEFAULT_FUSE);
+        if let Some(nbt) = nbt {
+            tnt.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.primed_tnt, tnt)
+    });
+
+    registry.register(vanilla_entities::EXPERIENCE_ORB, |world, id, pos, nbt| {
+        let mut orb = ExperienceOrbEntity::new(id, pos, 0);
+        if let Some(nbt) = nbt {
+            orb.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.experience_orbs, orb)
+    });
+
+    registry.register(vanilla_entities::FIREWORK_ROCKET, |world, id, pos, nbt| {
+        let mut rocket = FireworkRocketEntity::at(
+            id,
+            pos,
+            &ItemStack::new(&vanilla_items::ITEMS.firework_rocket),
+            firework_rocket::DEFAULT_FLIGHT_DURATION,
+        );
+        if let Some(nbt) = nbt {
+            rocket.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.firework_rockets, rocket)
+    });
+
+    registry.register(vanilla_entities::ARROW, |world, id, pos, nbt| {
+        let mut arrow = ArrowEntity::new(id, pos, Vector3::new(0.0, 0.0, 0.0), None);
+        if let Some(nbt) = nbt {
+            arrow.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.arrows, arrow)
+    });
+
+    registry.register(vanilla_entities::VILLAGER, |world, id, pos, nbt| {
+        let mut villager = VillagerEntity::new(id, pos);
+        if let Some(nbt) = nbt {
+            villager.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.villagers, villager)
+    });
+
+    registry.register(vanilla_entities::ZOMBIE, |world, id, pos, nbt| {
+        let mut zombie = ZombieEntity::new(id, pos);
+        if let Some(nbt) = nbt {
+            zombie.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.zombies, zombie)
+    });
+
+    registry.register(vanilla_entities::SKELETON, |world, id, pos, nbt| {
+        let mut skeleton = SkeletonEntity::new(id, pos);
+        if let Some(nbt) = nbt {
+            skeleton.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.skeletons, skeleton)
+    });
+
+    registry.register(vanilla_entities::CREEPER, |world, id, pos, nbt| {
+        let mut creeper = CreeperEntity::new(id, pos);
+        if let Some(nbt) = nbt {
+            creeper.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.creepers, creeper)
+    });
+
+    registry.register(vanilla_entities::SLIME, |world, id, pos, nbt| {
+        let mut slime = SlimeEntity::new(id, pos, rand::random_range(0..=slime::MAX_SIZE));
+        if let Some(nbt) = nbt {
+            slime.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.slimes, slime)
+    });
+
+    registry.register(vanilla_entities::IRON_GOLEM, |world, id, pos, nbt| {
+        let mut golem = IronGolemEntity::new(id, pos);
+        if let Some(nbt) = nbt {
+            golem.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.iron_golems, golem)
+    });
+
+    registry.register(vanilla_entities::ENDERMAN, |world, id, pos, nbt| {
+        let mut enderman = EndermanEntity::new(id, pos);
+        if let Some(nbt) = nbt {
+            enderman.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.endermen, enderman)
+    });
+
+    registry.register(vanilla_entities::GHAST, |world, id, pos, nbt| {
+        let mut ghast = GhastEntity::new(id, pos);
+        if let Some(nbt) = nbt {
+            ghast.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.ghasts, ghast)
+    });
+
+    registry.register(vanilla_entities::ENDER_DRAGON, |world, _, pos, nbt| {
+        // The dragon's parts take the ids right after its own
+        let id = entity::next_entity_ids(1 + ender_dragon::PART_COUNT as i32);
+        let mut dragon = EnderDragonEntity::new(id, pos);
+        if let Some(nbt) = nbt {
+            dragon.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.ender_dragons, dragon)
+    });
+
+    registry.register(vanilla_entities::LIGHTNING_BOLT, |world, id, pos, _| {
+        world.add_entity(
+            &world.entities.lightning_bolts,
+            LightningBoltEntity::new(id, pos),
+        )
+    });
+
+    registry.register(vanilla_entities::DRAGON_FIREBALL, |world, id, pos, nbt| {
+        let mut fireball = DragonFireballEntity::new(id, pos, Vector3::new(0.0, 0.0, 0.0));
+        if let Some(nbt) = nbt {
+            fireball.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.dragon_fireballs, fireball)
+    });
+
+    registry.register(vanilla_entities::FIREBALL, |world, id, pos, nbt| {
+        // Summoned fireballs hang still until something hits them
+        let mut fireball = GhastFireballEntity::new(id, pos, Vector3::new(0.0, 0.0, 0.0), None);
+        if let Some(nbt) = nbt {
+            fireball.read_nbt(nbt);
+        }
+        world.add_entity(&world.entities.ghast_fireballs, fireball)
+    });
+
+    assert!(
+        ENTITIES.set(registry).is_ok(),
+        "Entity registry already initialized"
+    );
+}
+
+/// Adds an item frame, hanging in the block at `pos` and facing south unless
+/// `nbt` says otherwise.
+fn add_item_frame(
+    world: &World,
+    id: i32,
+    pos: Vector3<f64>,
+    nbt: Option<&NbtCompound>,
+    glowing: bool,
+) -> bool {
+    let mut frame = ItemFrameEntity::new(id, pos.to_block_pos(), Direction::South, glowing);
+    if let Some(nbt) = nbt {
+        frame.read_nbt(nbt);
+    }
+    world.add_entity(&world.entities.item_frames, frame)
+}
+
+/// Adds a boat of `wood_type`, facing south unless `nbt` says otherwise.
+fn add_boat(
+    world: &World,
+    id: i32,
+    pos: Vector3<f64>,
+    nbt: Option<&NbtCompound>,
+    wood_type: WoodType,
+) -> bool {
+    let mut boat = BoatEntity::new(id, wood_type, pos, 0.0);
+    if let Some(nbt) = nbt {
+        boat.read_nbt(nbt);
+    }
+    world.add_entity(&world.entities.boats, boat)
+}
@@ -0,0 +1,416 @@
+//! Skeletons that keep their distance from players and shoot arrows at them.
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, CSetEquipment};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entity_data::SkeletonEntityData;
+use steel_registry::{sound_events, vanilla_entities, vanilla_items};
+use steel_utils::math::Vector3;
+use steel_utils::types::Difficulty;
+use uuid::Uuid;
+
+use crate::entity::ai::sight;
+use crate::entity::arrow::ArrowEntity;
+use crate::entity::mob::{self, MobBase};
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, AttackableMob, Entity, GenericEntity, Remains, StoredEntity};
+use crate::inventory::equipment::EquipmentSlot;
+use crate::player::Player;
+use crate::world::World;
+
+/// The health of a skeleton.
+const MAX_HEALTH: f32 = 20.0;
+/// How far above its feet a skeleton's eyes are.
+const EYE_HEIGHT: f64 = 1.74;
+/// How far skeletons walk in a tick.
+const WALK_SPEED: f64 = 0.15;
+
+/// How far away skeletons notice players.
+const FOLLOW_RANGE: f64 = 32.0;
+/// How far skeletons shoot; they walk closer to targets farther away.
+const SHOOT_RANGE: f64 = 16.0;
+/// How close a target gets before the skeleton backs away.
+const FLEE_DISTANCE: f64 = 4.0;
+/// How far a skeleton backs away from a target that got too close.
+const FLEE_STEP: f64 = 8.0;
+/// How many ticks pass between finding new paths.
+const PATH_INTERVAL: u32 = 20;
+/// The fewest and most ticks between shots.
+const SHOOT_INTERVAL: (u32, u32) = (20, 60);
+/// How many ticks a skeleton can't be hurt again after it was hurt.
+const HURT_COOLDOWN: u32 = 10;
+
+/// How fast arrows leave a skeleton's bow, in blocks per tick.
+const ARROW_POWER: f64 = 1.6;
+/// How far the aim spreads per point of inaccuracy.
+///
+/// Vanilla: `Projectile.getMovementToShoot`
+const SPREAD: f64 = 0.017_227_5;
+/// The chance that a skeleton drops its bow when it dies.
+const BOW_DROP_CHANCE: f64 = 0.085;
+
+/// The experience a skeleton drops when a player kills it.
+///
+/// Vanilla: `Monster.XP_REWARD_MEDIUM`
+pub const EXPERIENCE_REWARD: i32 = 5;
+
+/// A skeleton, shooting arrows at the nearest player from a distance.
+///
+/// Vanilla: `Skeleton`
+pub struct SkeletonEntity {
+    /// The state every entity shares, like the id and custom name.
+    pub base: GenericEntity,
+    /// The player the skeleton shoots at, if any.
+    pub target: Option<Uuid>,
+    mob: MobBase,
+    entity_data: SkeletonEntityData,
+    /// Ticks until the skeleton may shoot again.
+    attack_cooldown: u32,
+    /// Ticks until the skeleton can be hurt again.
+    hurt_cooldown: u32,
+}
+
+impl SkeletonEntity {
+    /// Creates a skeleton holding a bow at `position`.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        let mut entity_data = SkeletonEntityData::new();
+        entity_data.health.set(MAX_HEALTH);
+
+        Self {
+            base: GenericEntity::new(id, vanilla_entities::SKELETON, position),
+            target: None,
+            mob: MobBase::new(position, vanilla_entities::SKELETON),
+            entity_data,
+            attack_cooldown: SHOOT_INTERVAL.0,
+            hurt_cooldown: 0,
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the health.
+    ///
+    /// Vanilla: `Mob.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(health) = nbt.float("Health") {
+            self.entity_data.health.set(health.clamp(0.0, MAX_HEALTH));
+        }
+    }
+
+    /// Writes the health of the skeleton into `nbt`.
+    ///
+    /// Vanilla: `LivingEntity.addAdditionalSaveData`
+    pub fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("Health", *self.entity_data.health.get());
+    }
+
+    /// Returns the position of the skeleton.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.mob.position()
+    }
+
+    /// Returns the box the skeleton takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.mob.bounding_box()
+    }
+
+    /// The packet that adds this skeleton to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the skeleton is added.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// The packet showing the bow in the skeleton's hand.
+    #[must_use]
+    pub fn equipment_packet(&self) -> CSetEquipment {
+        CSetEquipment::new(
+            self.base.id,
+            vec![(
+                EquipmentSlot::MainHand.id(),
+                ItemStack::new(&vanilla_items::ITEMS.bow),
+            )],
+        )
+    }
+
+    /// Adds `impulse` to the velocity of the skeleton.
+    pub fn push(&mut self, impulse: Vector3<f64>) {
+        self.mob.push(impulse);
+    }
+
+    /// Returns true once the skeleton has no health left.
+    #[must_use]
+    pub fn is_dead(&self) -> bool {
+        *self.entity_data.health.get() <= 0.0
+    }
+
+    /// Hurts the skeleton by `amount`. Returns false if it was hurt too
+    /// recently to be hurt again.
+    ///
+    /// Vanilla: `LivingEntity.hurtServer`
+    pub fn hurt(&mut self, amount: f32) -> bool {
+        if self.hurt_cooldown > 0 || self.is_dead() {
+            return false;
+        }
+        self.hurt_cooldown = HURT_COOLDOWN;
+        let health = *self.entity_data.health.get();
+        self.entity_data.health.set((health - amount).max(0.0));
+        true
+    }
+
+    /// The items the skeleton drops when it dies: up to two arrows, maybe a
+    /// bone, and rarely its bow.
+    ///
+    /// Vanilla: `entities/skeleton` loot table
+    #[must_use]
+    pub fn death_drops(&self) -> Vec<ItemStack> {
+        let items = &vanilla_items::ITEMS;
+        let mut drops = vec![
+            ItemStack::with_count(&items.arrow, rand::random_range(0..=2)),
+            ItemStack::with_count(&items.bone, rand::random_range(0..=1)),
+        ];
+        if rand::random_bool(BOW_DROP_CHANCE) {
+            drops.push(ItemStack::new(&items.bow));
+        }
+        drops.retain(|item| !item.is_empty());
+        drops
+    }
+
+    /// Advances the skeleton by a tick: it burns in the sun, keeps its
+    /// distance from its target and shoots at it. Returns the arrow it shot,
+    /// if any.
+    ///
+    /// Vanilla: `RangedBowAttackGoal` and `AvoidEntityGoal`
+    pub fn tick(&mut self, world: &World) -> Option<ArrowEntity> {
+        self.attack_cooldown = self.attack_cooldown.saturating_sub(1);
+        self.hurt_cooldown = self.hurt_cooldown.saturating_sub(1);
+
+        // TODO: Don't burn with a helmet on once mobs wear equipment
+        if self.mob.burn_in_sunlight(world, EYE_HEIGHT) {
+            self.hurt(1.0);
+        }
+        self.base.on_fire = self.mob.is_on_fire();
+        self.entity_data.shared_flags.set(self.base.shared_flags());
+        if self.is_dead() {
+            return None;
+        }
+
+        let target = mob::find_target(world, self.position(), self.target, FOLLOW_RANGE);
+        self.target = target.as_ref().map(|player| player.gameprofile.id);
+        let Some(player) = target else {
+            self.mob.stop();
+            self.mob.walk(&self.base, world, WALK_SPEED);
+            return None;
+        };
+
+        let position = self.position();
+        let target_pos = *player.position.lock();
+        let eyes = Vector3::new(position.x, position.y + EYE_HEIGHT, position.z);
+        let target_eyes = Vector3::new(
+            target_pos.x,
+            target_pos.y + player.eye_height(),
+            target_pos.z,
+        );
+        let can_see = sight::has_line_of_sight(world, eyes, target_eyes);
+        let away = position.sub(&target_pos);
+        let distance = away.length();
+
+        if distance < FLEE_DISTANCE && distance > 1.0e-7 {
+            let goal = position + away * (FLEE_STEP / distance);
+            self.mob.move_to(world, goal.to_block_pos(), PATH_INTERVAL);
+        } else if distance > SHOOT_RANGE || !can_see {
+            self.mob
+                .move_to(world, target_pos.to_block_pos(), PATH_INTERVAL);
+        } else {
+            self.mob.stop();
+        }
+
+        let mut arrow = None;
+        if can_see && distance <= SHOOT_RANGE && self.attack_cooldown == 0 {
+            // Vanilla aims a third of the way up the target
+            let bounding_box = player.bounding_box();
+            let aim = Vector3::new(
+                target_pos.x,
+                bounding_box.min_y + (bounding_box.max_y - bounding_box.min_y) / 3.0,
+                target_pos.z,
+            );
+            arrow = Some(self.shoot_arrow(aim, shot_inaccuracy(world.difficulty())));
+        }
+        self.mob.walk(&self.base, world, WALK_SPEED);
+        arrow
+    }
+
+    /// Shoots an arrow from the skeleton's eyes at `target_pos`, aiming a
+    /// little high to make up for the arrow dropping and spreading by
+    /// `inaccuracy`, and waits a while before the next shot.
+    ///
+    /// Vanilla: `AbstractSkeleton.performRangedAttack`
+    pub fn shoot_arrow(&mut self, target_pos: Vector3<f64>, inaccuracy: f32) -> ArrowEntity {
+        self.attack_cooldown = rand::random_range(SHOOT_INTERVAL.0..=SHOOT_INTERVAL.1);
+        let position = self.position();
+        let origin = Vector3::new(position.x, position.y + EYE_HEIGHT - 0.1, position.z);
+        let velocity = arrow_velocity(origin, target_pos, inaccuracy);
+        ArrowEntity::new(
+            entity::next_entity_id(),
+            origin,
+            velocity,
+            Some(self.base.id),
+        )
+    }
+}
+
+impl Entity for SkeletonEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for SkeletonEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+        pairing.send(self.equipment_packet());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.base.write_nbt(&mut nbt);
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.base.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    fn explosion_hit(
+        &mut self,
+        world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.hurt_by_explosion(world, damage);
+        self.push(impulse);
+        None
+    }
+}
+
+impl AttackableMob for SkeletonEntity {
+    fn take_hit(&mut self, amount: f32) -> Option<i32> {
+        self.hurt(amount)
+            .then_some(sound_events::ENTITY_SKELETON_HURT)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.is_dead()
+    }
+
+    fn retaliate(&mut self, _world: &World, player: &Player) {
+        self.target = Some(player.gameprofile.id);
+    }
+
+    fn death_sound(&self) -> i32 {
+        sound_events::ENTITY_SKELETON_DEATH
+    }
+
+    fn experience_reward(&self) -> i32 {
+        EXPERIENCE_REWARD
+    }
+
+    fn death_drops(&self) -> Vec<ItemStack> {
+        self.death_drops()
+    }
+}
+
+/// How much skeletons miss by on `difficulty`.
+///
+/// Vanilla: `AbstractSkeleton.performRangedAttack`
+#[must_use]
+pub const fn shot_inaccuracy(difficulty: Difficulty) -> f32 {
+    match difficulty {
+        Difficulty::Peaceful => 14.0,
+        Difficulty::Easy => 10.0,
+        Difficulty::Normal => 6.0,
+        Difficulty::Hard => 2.0,
+    }
+}
+
+/// The velocity of an arrow shot from `origin` at `target_pos`, aimed above
+/// the target by a fifth of the distance so it drops onto it, with a random
+/// spread growing with `inaccuracy`.
+///
+/// Vanilla: `Projectile.getMovementToShoot`
+fn arrow_velocity(origin: Vector3<f64>, target_pos: Vector3<f64>, inaccuracy: f32) -> Vector3<f64> {
+    let delta = target_pos.sub(&origin);
+    let horizontal = delta.x.hypot(delta.z);
+    let aim = Vector3::new(delta.x, delta.y + horizontal * 0.2, delta.z);
+    let length = aim.length();
+    if length < 1.0e-7 {
+        return Vector3::new(0.0, 0.0, 0.0);
+    }
+
+    let spread = SPREAD * f64::from(inaccuracy);
+    let triangle = || spread * (rand::random::<f64>() - rand::random::<f64>());
+    let direction = Vector3::new(
+        aim.x / length + triangle(),
+        aim.y / length + triangle(),
+        aim.z / length + triangle(),
+    );
+    direction * ARROW_POWER
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrows_fly_towards_the_target() {
+        let origin = Vector3::new(0.0, 1.64, 0.0);
+        let target = Vector3::new(6.0, 0.6, 8.0);
+        for _ in 0..100 {
+            let velocity = arrow_velocity(origin, target, shot_inaccuracy(Difficulty::Hard));
+            let cos = (velocity.x * 6.0 + velocity.z * 8.0) / (velocity.x.hypot(velocity.z) * 10.0);
+            assert!(cos.acos().to_degrees() < 5.0);
+            // Aimed above the target to make up for the drop
+            assert!(velocity.y > 0.0);
+        }
+    }
+
+    #[test]
+    fn skeletons_aim_better_on_harder_difficulties() {
+        assert!(shot_inaccuracy(Difficulty::Hard) < shot_inaccuracy(Difficulty::Normal));
+        assert!(shot_inaccuracy(Difficulty::Normal) < shot_inaccuracy(Difficulty::Easy));
+    }
+}
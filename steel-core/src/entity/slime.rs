@@ -0,0 +1,447 @@
+//! Slimes that hop after players and split into smaller slimes when they die.
+
+use std::f64::consts::TAU;
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CAddEntity, SoundSource};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entity_data::SlimeEntityData;
+use steel_registry::{sound_events, vanilla_entities, vanilla_items};
+use steel_utils::math::Vector3;
+use uuid::Uuid;
+
+use crate::entity::mob::{self, MobBase};
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, AttackableMob, Entity, GenericEntity, Remains, StoredEntity};
+use crate::player::Player;
+use crate::world::World;
+
+/// The size of the largest slimes.
+pub const MAX_SIZE: u8 = 2;
+/// Where the slimes a slime splits into land, relative to it and scaled by
+/// its size.
+const SPLIT_OFFSETS: [(f64, f64); 4] = [(-0.5, -0.5), (0.5, -0.5), (-0.5, 0.5), (0.5, 0.5)];
+
+/// How far away slimes notice players.
+const FOLLOW_RANGE: f64 = 16.0;
+/// How many ticks pass between hops.
+const HOP_INTERVAL: u32 = 40;
+/// The upward velocity of a hop.
+const HOP_POWER: f64 = 0.42;
+/// How many ticks pass between hits.
+const ATTACK_INTERVAL: u32 = 10;
+/// How many ticks a slime can't be hurt again after it was hurt.
+const HURT_COOLDOWN: u32 = 10;
+
+/// A slime, hopping after the nearest player. Slimes come in three sizes:
+/// tiny (0), small (1) and large (2), and all but tiny ones hurt players
+/// they touch.
+///
+/// Vanilla: `Slime`
+pub struct SlimeEntity {
+    /// The state every entity shares, like the id and custom name.
+    pub base: GenericEntity,
+    /// The player the slime hops after, if any.
+    pub target: Option<Uuid>,
+    /// How big the slime is, from 0 for tiny up to [`MAX_SIZE`].
+    size: u8,
+    mob: MobBase,
+    entity_data: SlimeEntityData,
+    /// Ticks until the slime hops again.
+    hop_cooldown: u32,
+    /// Ticks until the slime may hit a player again.
+    attack_cooldown: u32,
+    /// Ticks until the slime can be hurt again.
+    hurt_cooldown: u32,
+}
+
+impl SlimeEntity {
+    /// Creates a slime of `size` at `position`, at full health.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>, size: u8) -> Self {
+        let mut slime = Self {
+            base: GenericEntity::new(id, vanilla_entities::SLIME, position),
+            target: None,
+            size: 0,
+            mob: MobBase::new(position, vanilla_entities::SLIME),
+            entity_data: SlimeEntityData::new(),
+            hop_cooldown: rand::random_range(0..HOP_INTERVAL),
+            attack_cooldown: 0,
+            hurt_cooldown: 0,
+        };
+        slime.set_size(size);
+        slime
+    }
+
+    /// Makes the slime `size` big, healing it fully.
+    ///
+    /// Vanilla: `Slime.setSize`
+    pub fn set_size(&mut self, size: u8) {
+        self.size = size.min(MAX_SIZE);
+        let scale = scale_of(self.size);
+        self.entity_data.id_size.set(i32::from(scale));
+        self.entity_data.health.set(max_health(self.size));
+        self.mob
+            .set_dimensions(vanilla_entities::SLIME.dimensions.scale(f32::from(scale)));
+    }
+
+    /// Returns how big the slime is, from 0 for tiny up to [`MAX_SIZE`].
+    #[must_use]
+    pub const fn size(&self) -> u8 {
+        self.size
+    }
+
+    /// Applies the tags of `nbt`, like the size and health.
+    ///
+    /// Vanilla: `Slime.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(size) = nbt.int("Size") {
+            self.set_size(size_from_nbt(size));
+        }
+        if let Some(health) = nbt.float("Health") {
+            self.entity_data
+                .health
+                .set(health.clamp(0.0, max_health(self.size)));
+        }
+    }
+
+    /// Writes the size and health of the slime into `nbt`.
+    ///
+    /// Vanilla: `Slime.addAdditionalSaveData`
+    pub fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("Size", size_to_nbt(self.size));
+        nbt.insert("Health", *self.entity_data.health.get());
+    }
+
+    /// Returns the position of the slime.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.mob.position()
+    }
+
+    /// Returns the box the slime takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.mob.bounding_box()
+    }
+
+    /// The packet that adds this slime to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the slime is added.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Adds `impulse` to the velocity of the slime.
+    pub fn push(&mut self, impulse: Vector3<f64>) {
+        self.mob.push(impulse);
+    }
+
+    /// Returns true once the slime has no health left.
+    #[must_use]
+    pub fn is_dead(&self) -> bool {
+        *self.entity_data.health.get() <= 0.0
+    }
+
+    /// Hurts the slime by `amount`. Returns false if it was hurt too recently
+    /// to be hurt again.
+    ///
+    /// Vanilla: `LivingEntity.hurtServer`
+    pub fn hurt(&mut self, amount: f32) -> bool {
+        if self.hurt_cooldown > 0 || self.is_dead() {
+            return false;
+        }
+        self.hurt_cooldown = HURT_COOLDOWN;
+        let health = *self.entity_data.health.get();
+        self.entity_data.health.set((health - amount).max(0.0));
+        true
+    }
+
+    /// The sound the slime makes, the squeakier one for tiny slimes.
+    #[must_use]
+    pub const fn sound(&self, small: i32, large: i32) -> i32 {
+        if self.size == 0 { small } else { large }
+    }
+
+    /// Advances the slime by a tick: it hops towards its target, or in a
+    /// random direction without one, and hurts players it touches.
+    ///
+    /// Vanilla: `Slime.tick` and `Slime.SlimeMoveControl`
+    pub fn tick(&mut self, world: &World) {
+        self.hurt_cooldown = self.hurt_cooldown.saturating_sub(1);
+        self.attack_cooldown = self.attack_cooldown.saturating_sub(1);
+        if self.mob.on_ground() {
+            self.hop_cooldown = self.hop_cooldown.saturating_sub(1);
+        }
+
+        let target = mob::find_target(world, self.position(), self.target, FOLLOW_RANGE);
+        self.target = target.as_ref().map(|player| player.gameprofile.id);
+
+        let mut jump = None;
+        if self.hop_cooldown == 0 && self.mob.on_ground() {
+            self.hop_cooldown = HOP_INTERVAL;
+            let position = self.position();
+            let angle = match &target {
+                Some(player) => {
+                    let towards = player.position.lock().sub(&position);
+                    towards.z.atan2(towards.x)
+                }
+                None => rand::random::<f64>() * TAU,
+            };
+            let speed = hop_speed(self.size);
+            jump = Some(Vector3::new(
+                angle.cos() * speed,
+                HOP_POWER,
+                angle.sin() * speed,
+            ));
+            world.play_sound(
+                self.sound(
+                    sound_events::ENTITY_SLIME_JUMP_SMALL,
+                    sound_events::ENTITY_SLIME_JUMP,
+                ),
+                SoundSource::Hostile,
+                position.to_block_pos(),
+                0.4 * f32::from(scale_of(self.size)),
+                1.0,
+                None,
+            );
+        }
+        self.mob.hop(&self.base, world, jump);
+
+        if let Some(player) = target
+            && let Some(damage) = attack_damage(self.size)
+            && self.attack_cooldown == 0
+            && self.bounding_box().intersects(&player.bounding_box())
+        {
+            self.attack_cooldown = ATTACK_INTERVAL;
+            if player.take_damage(damage) {
+                world.play_sound(
+                    sound_events::ENTITY_SLIME_ATTACK,
+                    SoundSource::Hostile,
+                    self.position().to_block_pos(),
+                    1.0,
+                    1.0,
+                    None,
+                );
+            }
+        }
+    }
+
+    /// The slimes this slime splits into when it dies: four of the next
+    /// size down, scattered around it, or none if it is tiny.
+    ///
+    /// Vanilla: `Slime.remove`
+    #[must_use]
+    pub fn split(&self) -> Vec<Self> {
+        let Some(size) = self.size.checked_sub(1) else {
+            return Vec::new();
+        };
+        let position = self.position();
+        let offset = f64::from(scale_of(self.size)) / 4.0;
+        SPLIT_OFFSETS
+            .iter()
+            .map(|(dx, dz)| {
+                let child_position = Vector3::new(
+                    position.x + dx * offset,
+                    position.y + 0.5,
+                    position.z + dz * offset,
+                );
+                let mut child = Self::new(entity::next_entity_id(), child_position, size);
+                child.base.custom_name.clone_from(&self.base.custom_name);
+                child.mob.set_velocity(Vector3::new(
+                    (rand::random::<f64>() - 0.5) * 0.2,
+                    0.2,
+                    (rand::random::<f64>() - 0.5) * 0.2,
+                ));
+                child
+            })
+            .collect()
+    }
+
+    /// The items the slime drops when it dies: tiny slimes drop up to two
+    /// slimeballs, bigger ones split instead.
+    ///
+    /// Vanilla: `entities/slime` loot table
+    #[must_use]
+    pub fn death_drops(&self) -> Vec<ItemStack> {
+        if self.size > 0 {
+            return Vec::new();
+        }
+        let count = rand::random_range(0..=2);
+        if count == 0 {
+            return Vec::new();
+        }
+        vec![ItemStack::with_count(
+            &vanilla_items::ITEMS.slime_ball,
+            count,
+        )]
+    }
+
+    /// The experience the slime drops when a player kills it.
+    ///
+    /// Vanilla: `Slime.getBaseExperienceReward`
+    #[must_use]
+    pub fn experience_reward(&self) -> i32 {
+        i32::from(scale_of(self.size))
+    }
+}
+
+impl Entity for SlimeEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for SlimeEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.base.write_nbt(&mut nbt);
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.base.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    fn explosion_hit(
+        &mut self,
+        world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.hurt_by_explosion(world, damage);
+        self.push(impulse);
+        None
+    }
+}
+
+impl AttackableMob for SlimeEntity {
+    fn take_hit(&mut self, amount: f32) -> Option<i32> {
+        self.hurt(amount).then(|| {
+            self.sound(
+                sound_events::ENTITY_SLIME_HURT_SMALL,
+                sound_events::ENTITY_SLIME_HURT,
+            )
+        })
+    }
+
+    fn is_dead(&self) -> bool {
+        self.is_dead()
+    }
+
+    fn retaliate(&mut self, _world: &World, player: &Player) {
+        self.target = Some(player.gameprofile.id);
+    }
+
+    fn death_sound(&self) -> i32 {
+        self.sound(
+            sound_events::ENTITY_SLIME_DEATH_SMALL,
+            sound_events::ENTITY_SLIME_DEATH,
+        )
+    }
+
+    fn experience_reward(&self) -> i32 {
+        self.experience_reward()
+    }
+
+    fn death_drops(&self) -> Vec<ItemStack> {
+        self.death_drops()
+    }
+}
+
+/// How many times bigger than a tiny slime a slime of `size` is: 1, 2 or 4.
+const fn scale_of(size: u8) -> u8 {
+    1 << size
+}
+
+/// The health of a slime of `size`, its scale squared.
+fn max_health(size: u8) -> f32 {
+    let scale = f32::from(scale_of(size));
+    scale * scale
+}
+
+/// How far a slime of `size` hops in a tick.
+fn hop_speed(size: u8) -> f64 {
+    0.2 + 0.1 * f64::from(scale_of(size))
+}
+
+/// How much a slime of `size` hurts players it touches, or `None` for tiny
+/// slimes, which are harmless.
+///
+/// Vanilla: `Slime.isDealsDamage`
+#[must_use]
+pub fn attack_damage(size: u8) -> Option<f32> {
+    (size >= 1).then(|| f32::from(scale_of(size)))
+}
+
+/// The size of a slime saved as `size` in NBT, where vanilla stores its
+/// scale minus one.
+fn size_from_nbt(size: i32) -> u8 {
+    match size {
+        ..=0 => 0,
+        1..=2 => 1,
+        _ => MAX_SIZE,
+    }
+}
+
+/// The number vanilla saves in NBT for a slime of `size`.
+fn size_to_nbt(size: u8) -> i32 {
+    i32::from(scale_of(size)) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_survive_saving() {
+        for size in 0..=MAX_SIZE {
+            assert_eq!(size_from_nbt(size_to_nbt(size)), size);
+        }
+        assert_eq!(size_to_nbt(MAX_SIZE), 3);
+    }
+
+    #[test]
+    fn only_bigger_slimes_hurt() {
+        assert!(attack_damage(0).is_none());
+        assert!(attack_damage(1).is_some_and(|damage| (damage - 2.0).abs() < f32::EPSILON));
+        assert!(attack_damage(2).is_some_and(|damage| (damage - 4.0).abs() < f32::EPSILON));
+    }
+}
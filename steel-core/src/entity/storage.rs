@@ -0,0 +1,700 @@
+//! Entity storage for worlds, keeping every entity with the chunk it is in.
+//!
+//! Players only see the entities in the chunks they track: they are paired
+//! with an entity once they start tracking its chunk or it moves into a
+//! chunk they track, and it is removed for them once either changes back.
+
+use std::hash::BuildHasher;
+use std::mem;
+
+use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
+use steel_protocol::packet_traits::{ClientPacket, EncodedPacket};
+use steel_protocol::packets::game::{CAddEntity, CRemoveEntities, CSetEntityData};
+use steel_protocol::utils::ConnectionProtocol;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_utils::locks::SyncMutex;
+use steel_utils::math::Vector3;
+use steel_utils::{ChunkPos, Identifier, SectionPos};
+use uuid::Uuid;
+
+use super::{
+    ArmorStandEntity, ArrowEntity, BoatEntity, ChestMinecartEntity, CreeperEntity,
+    DragonFireballEntity, EnderDragonEntity, EndermanEntity, ExperienceOrbEntity,
+    FallingBlockEntity, FireworkRocketEntity, GenericEntity, GhastEntity, GhastFireballEntity,
+    HopperMinecartEntity, IronGolemEntity, ItemFrameEntity, LightningBoltEntity, MinecartEntity,
+    PaintingEntity, PrimedTntEntity, Remains, SkeletonEntity, SlimeEntity, StoredEntity,
+    VillagerEntity, ZombieEntity,
+};
+use crate::chunk_saver::PersistentEntity;
+use crate::config::STEEL_CONFIG;
+use crate::player::Player;
+use crate::world::World;
+use crate::world::explosion::Explosion;
+
+/// Returns the chunk `position` is in.
+#[must_use]
+pub fn chunk_at(position: Vector3<f64>) -> ChunkPos {
+    let pos = position.to_block_pos();
+    ChunkPos::new(
+        SectionPos::block_to_section_coord(pos.x()),
+        SectionPos::block_to_section_coord(pos.z()),
+    )
+}
+
+/// The packets that show entities to a player that starts tracking them,
+/// encoded once for every player that gets them.
+///
+/// Vanilla: `ServerEntity.sendPairingData`
+#[derive(Default)]
+pub struct EntityPairing {
+    packets: Vec<EncodedPacket>,
+}
+
+impl EntityPairing {
+    /// Creates an empty pairing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `packet`. Packets that fail to encode are skipped, like in
+    /// broadcasts.
+    pub fn send<P: ClientPacket>(&mut self, packet: P) {
+        if let Ok(encoded) = EncodedPacket::from_bare(
+            packet,
+            STEEL_CONFIG.load().compression,
+            ConnectionProtocol::Play,
+        ) {
+            self.packets.push(encoded);
+        }
+    }
+
+    /// Adds the packet that adds an entity, followed by its entity data if
+    /// it has any.
+    pub fn add_entity(&mut self, packet: CAddEntity, data: Vec<DataValue>) {
+        let entity_id = packet.id;
+        self.send(packet);
+        if !data.is_empty() {
+            self.send(CSetEntityData::new(entity_id, data));
+        }
+    }
+
+    /// Returns whether there are no packets to send.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.packets.is_empty()
+    }
+
+    /// Sends the packets to `player`.
+    pub fn send_to(&self, player: &Player) {
+        for packet in &self.packets {
+            player.connection.send_encoded_packet(packet.clone());
+        }
+    }
+}
+
+/// An entity that was taken out of a list, which the players tracking
+/// `chunk` still have to remove.
+struct Removal {
+    entity_id: i32,
+    uuid: Uuid,
+    chunk: ChunkPos,
+}
+
+/// Entities of one kind, grouped by the chunk they are in.
+///
+/// An entity stays in the chunk it was added to until
+/// [`EntityList::rebucket`] moves it to the chunk it walked into, which the
+/// world does once a tick after the entities moved.
+pub struct EntityList<T> {
+    chunks: FxHashMap<ChunkPos, Vec<T>>,
+    /// The chunk every entity is stored in, by network id.
+    index: FxHashMap<i32, ChunkPos>,
+    removed: Vec<Removal>,
+}
+
+impl<T: StoredEntity> EntityList<T> {
+    /// Creates an empty list.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            chunks: FxHashMap::default(),
+            index: FxHashMap::default(),
+            removed: Vec::new(),
+        }
+    }
+
+    /// Returns the number of entities in the list.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns whether the list has no entities.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Iterates over the entities.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.chunks.values().flatten()
+    }
+
+    /// Iterates mutably over the entities.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.chunks.values_mut().flatten()
+    }
+
+    /// Returns the entity with network id `entity_id`.
+    #[must_use]
+    pub fn get(&self, entity_id: i32) -> Option<&T> {
+        let chunk = self.index.get(&entity_id)?;
+        self.chunks
+            .get(chunk)?
+            .iter()
+            .find(|entity| entity.get_id() == entity_id)
+    }
+
+    /// Returns the entity with network id `entity_id` mutably.
+    pub fn get_mut(&mut self, entity_id: i32) -> Option<&mut T> {
+        let chunk = self.index.get(&entity_id)?;
+        self.chunks
+            .get_mut(chunk)?
+            .iter_mut()
+            .find(|entity| entity.get_id() == entity_id)
+    }
+
+    /// Returns the entities stored in `chunk`.
+    #[must_use]
+    pub fn in_chunk(&self, chunk: ChunkPos) -> &[T] {
+        self.chunks.get(&chunk).map_or(&[], Vec::as_slice)
+    }
+
+    /// Adds `entity` to the chunk it is in.
+    pub fn insert(&mut self, entity: T) {
+        let chunk = chunk_at(entity.get_position());
+        self.index.insert(entity.get_id(), chunk);
+        self.chunks.entry(chunk).or_default().push(entity);
+    }
+
+    /// Takes the entity with network id `entity_id` out of the list.
+    pub fn remove(&mut self, entity_id: i32) -> Option<T> {
+        let chunk = *self.index.get(&entity_id)?;
+        let entities = self.chunks.get_mut(&chunk)?;
+        let index = entities
+            .iter()
+            .position(|entity| entity.get_id() == entity_id)?;
+        let entity = entities.remove(index);
+        if entities.is_empty() {
+            self.chunks.remove(&chunk);
+        }
+        self.index.remove(&entity_id);
+        self.removed.push(Removal {
+            entity_id,
+            uuid: entity.get_uuid(),
+            chunk,
+        });
+        Some(entity)
+    }
+
+    /// Keeps only the entities `keep` returns true for, taking the others
+    /// out of the list.
+    pub fn retain_mut(&mut self, mut keep: impl FnMut(&mut T) -> bool) {
+        let Self {
+            chunks,
+            index,
+            removed,
+        } = self;
+        chunks.retain(|chunk, entities| {
+            entities.retain_mut(|entity| {
+                if keep(entity) {
+                    return true;
+                }
+                index.remove(&entity.get_id());
+                removed.push(Removal {
+                    entity_id: entity.get_id(),
+                    uuid: entity.get_uuid(),
+                    chunk: *chunk,
+                });
+                false
+            });
+            !entities.is_empty()
+        });
+    }
+
+    /// Takes every entity out of the list.
+    pub fn drain(&mut self) -> Vec<T> {
+        let chunks: Vec<ChunkPos> = self.chunks.keys().copied().collect();
+        chunks
+            .into_iter()
+            .flat_map(|chunk| self.take_chunk(chunk))
+            .collect()
+    }
+
+    /// Takes the entities stored in `chunk` out of the list.
+    pub fn take_chunk(&mut self, chunk: ChunkPos) -> Vec<T> {
+        let entities = self.chunks.remove(&chunk).unwrap_or_default();
+        for entity in &entities {
+            self.index.remove(&entity.get_id());
+            self.removed.push(Removal {
+                entity_id: entity.get_id(),
+                uuid: entity.get_uuid(),
+                chunk,
+            });
+        }
+        entities
+    }
+
+    /// Moves the entities that left the chunk they are stored in to the
+    /// chunk they are in now. Returns the network id of every entity that
+    /// moved, with the chunk it left and the one it entered.
+    pub fn rebucket(&mut self) -> Vec<(i32, ChunkPos, ChunkPos)> {
+        let mut moved = Vec::new();
+        for (chunk, entities) in &mut self.chunks {
+            let mut index = 0;
+            while index < entities.len() {
+                let to = chunk_at(entities[index].get_position());
+                if to == *chunk {
+                    index += 1;
+                } else {
+                    moved.push((*chunk, to, entities.remove(index)));
+                }
+            }
+        }
+        self.chunks.retain(|_, entities| !entities.is_empty());
+
+        moved
+            .into_iter()
+            .map(|(from, to, entity)| {
+                let entity_id = entity.get_id();
+                self.index.insert(entity_id, to);
+                self.chunks.entry(to).or_default().push(entity);
+                (entity_id, from, to)
+            })
+            .collect()
+    }
+
+    /// Takes the entities removed since the last call, with the chunk each
+    /// was stored in.
+    fn take_removed(&mut self) -> Vec<Removal> {
+        mem::take(&mut self.removed)
+    }
+}
+
+impl<T: StoredEntity> Default for EntityList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The work the world does over every kind of entity at once, so it doesn't
+/// have to know each list.
+pub trait EntityListDyn: Send + Sync {
+    /// Returns the number of entities in the list.
+    fn len(&self) -> usize;
+
+    /// Adds the packets that show the entities in `chunk` to `pairing`.
+    fn pair_chunk(&self, world: &World, chunk: ChunkPos, pairing: &mut EntityPairing);
+
+    /// Adds the network ids of the entities in `chunk` to `entity_ids`.
+    fn ids_in_chunk(&self, chunk: ChunkPos, entity_ids: &mut Vec<i32>);
+
+    /// Saves the entities in `chunk` that are stored with it to `saved`.
+    fn save_chunk(&self, chunk: ChunkPos, saved: &mut Vec<PersistentEntity>);
+
+    /// Takes the entities in `chunk` out of the list once it unloaded. They
+    /// were saved with it, except the ones that are never saved.
+    fn unload_chunk(&self, chunk: ChunkPos);
+
+    /// Returns the box players click for the entity or entity part with
+    /// network id `entity_id`, if it is in the list and can be clicked.
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd>;
+
+    /// Removes the entities taken out of the list for the players tracking
+    /// them and moves the entities that changed chunks, pairing and removing
+    /// them for the players that start and stop tracking them. Adds the
+    /// UUIDs of the removed entities to `released`.
+    fn update_tracking(&self, world: &World, released: &mut Vec<Uuid>);
+
+    /// Hurts and pushes the entities `explosion` reaches. The ones it
+    /// destroys are taken out of the list, and what is left of them is
+    /// added to `remains`.
+    fn explode(
+        &self,
+        world: &World,
+        explosion: &Explosion,
+        breaks_blocks: bool,
+        remains: &mut Vec<Remains>,
+    );
+}
+
+impl<T: StoredEntity> EntityListDyn for SyncMutex<EntityList<T>> {
+    fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn pair_chunk(&self, world: &World, chunk: ChunkPos, pairing: &mut EntityPairing) {
+        for entity in self.lock().in_chunk(chunk) {
+            entity.pair(world, pairing);
+        }
+    }
+
+    fn ids_in_chunk(&self, chunk: ChunkPos, entity_ids: &mut Vec<i32>) {
+        entity_ids.extend(self.lock().in_chunk(chunk).iter().map(StoredEntity::get_id));
+    }
+
+    fn save_chunk(&self, chunk: ChunkPos, saved: &mut Vec<PersistentEntity>) {
+        for entity in self.lock().in_chunk(chunk) {
+            if let Some(nbt) = entity.save() {
+                let mut nbt_data = Vec::new();
+                nbt.write(&mut nbt_data);
+                saved.push(PersistentEntity {
+                    entity_type: Identifier::vanilla_static(entity.get_type().key),
+                    nbt_data,
+                });
+            }
+        }
+    }
+
+    fn unload_chunk(&self, chunk: ChunkPos) {
+        self.lock().take_chunk(chunk);
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        self.lock()
+            .iter()
+            .find_map(|entity| entity.hitbox(entity_id))
+    }
+
+    fn update_tracking(&self, world: &World, released: &mut Vec<Uuid>) {
+        let mut list = self.lock();
+
+        let mut removed: FxHashMap<ChunkPos, Vec<i32>> = FxHashMap::default();
+        for removal in list.take_removed() {
+            released.push(removal.uuid);
+            removed
+                .entry(removal.chunk)
+                .or_default()
+                .push(removal.entity_id);
+        }
+        for (chunk, entity_ids) in removed {
+            world.broadcast_to_nearby(chunk, CRemoveEntities { entity_ids }, None);
+        }
+
+        for (entity_id, from, to) in list.rebucket() {
+            let before = world.player_area_map.get_tracking_players(from);
+            let after = world.player_area_map.get_tracking_players(to);
+            for player_id in before.iter().filter(|id| !after.contains(id)) {
+                if let Some(player) = world.players.get_by_entity_id(*player_id) {
+                    player
+                        .connection
+                        .send_packet(CRemoveEntities::single(entity_id));
+                }
+            }
+
+            let entering: Vec<i32> = after
+                .into_iter()
+                .filter(|id| !before.contains(id))
+                .collect();
+            if entering.is_empty() {
+                continue;
+            }
+            let Some(entity) = list.get(entity_id) else {
+                continue;
+            };
+            let mut pairing = EntityPairing::new();
+            entity.pair(world, &mut pairing);
+            for player_id in entering {
+                if let Some(player) = world.players.get_by_entity_id(player_id) {
+                    pairing.send_to(&player);
+                }
+            }
+        }
+    }
+
+    fn explode(
+        &self,
+        world: &World,
+        explosion: &Explosion,
+        breaks_blocks: bool,
+        remains: &mut Vec<Remains>,
+    ) {
+        self.lock().retain_mut(|entity| {
+            let Some(bounding_box) = entity.explosion_box(breaks_blocks) else {
+                return true;
+            };
+            let position = entity.get_position();
+            let push_y = position.y + entity.explosion_push_height();
+            let Some(hit) = explosion.hit(world, position, push_y, &bounding_box) else {
+                return true;
+            };
+            match entity.explosion_hit(world, hit.damage, hit.impulse) {
+                Some(remainder) => {
+                    remains.push(remainder);
+                    false
+                }
+                None => true,
+            }
+        });
+    }
+}
+
+/// Every entity of a world other than its players, kept with the chunk it
+/// is in.
+pub struct EntityStorage {
+    /// Entities without behaviour of their own.
+    pub generic: SyncMutex<EntityList<GenericEntity>>,
+    /// Firework rockets flying or boosting a gliding player.
+    pub firework_rockets: SyncMutex<EntityList<FireworkRocketEntity>>,
+    /// Paintings hanging on walls.
+    pub paintings: SyncMutex<EntityList<PaintingEntity>>,
+    /// Item frames hanging on blocks.
+    pub item_frames: SyncMutex<EntityList<ItemFrameEntity>>,
+    /// Armor stands.
+    pub armor_stands: SyncMutex<EntityList<ArmorStandEntity>>,
+    /// Experience orbs waiting to be picked up.
+    pub experience_orbs: SyncMutex<EntityList<ExperienceOrbEntity>>,
+    /// Rideable minecarts.
+    pub minecarts: SyncMutex<EntityList<MinecartEntity>>,
+    /// Minecarts with a chest.
+    pub chest_minecarts: SyncMutex<EntityList<ChestMinecartEntity>>,
+    /// Minecarts with a hopper.
+    pub hopper_minecarts: SyncMutex<EntityList<HopperMinecartEntity>>,
+    /// Boats.
+    pub boats: SyncMutex<EntityList<BoatEntity>>,
+    /// Lit TNT waiting to explode.
+    pub primed_tnt: SyncMutex<EntityList<PrimedTntEntity>>,
+    /// Blocks falling down.
+    pub falling_blocks: SyncMutex<EntityList<FallingBlockEntity>>,
+    /// Lightning bolts.
+    pub lightning_bolts: SyncMutex<EntityList<LightningBoltEntity>>,
+    /// Villagers.
+    pub villagers: SyncMutex<EntityList<VillagerEntity>>,
+    /// Zombies.
+    pub zombies: SyncMutex<EntityList<ZombieEntity>>,
+    /// Skeletons.
+    pub skeletons: SyncMutex<EntityList<SkeletonEntity>>,
+    /// Creepers.
+    pub creepers: SyncMutex<EntityList<CreeperEntity>>,
+    /// Slimes.
+    pub slimes: SyncMutex<EntityList<SlimeEntity>>,
+    /// Iron golems.
+    pub iron_golems: SyncMutex<EntityList<IronGolemEntity>>,
+    /// Endermen.
+    pub endermen: SyncMutex<EntityList<EndermanEntity>>,
+    /// Ghasts.
+    pub ghasts: SyncMutex<EntityList<GhastEntity>>,
+    /// Ender dragons.
+    pub ender_dragons: SyncMutex<EntityList<EnderDragonEntity>>,
+    /// Fireballs spat by ender dragons.
+    pub dragon_fireballs: SyncMutex<EntityList<DragonFireballEntity>>,
+    /// Fireballs shot by ghasts.
+    pub ghast_fireballs: SyncMutex<EntityList<GhastFireballEntity>>,
+    /// Arrows.
+    pub arrows: SyncMutex<EntityList<ArrowEntity>>,
+    /// The UUIDs of the entities in the storage, which have to be unique.
+    uuids: SyncMutex<FxHashSet<Uuid>>,
+    /// A hash of the entities last stored with each chunk that has any, to
+    /// only save the chunk again for its entities once they changed.
+    stored_chunks: SyncMutex<FxHashMap<ChunkPos, u64>>,
+}
+
+impl EntityStorage {
+    /// Creates an empty storage.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            generic: SyncMutex::new(EntityList::new()),
+            firework_rockets: SyncMutex::new(EntityList::new()),
+            paintings: SyncMutex::new(EntityList::new()),
+            item_frames: SyncMutex::new(EntityList::new()),
+            armor_stands: SyncMutex::new(EntityList::new()),
+            experience_orbs: SyncMutex::new(EntityList::new()),
+            minecarts: SyncMutex::new(EntityList::new()),
+            chest_minecarts: SyncMutex::new(EntityList::new()),
+            hopper_minecarts: SyncMutex::new(EntityList::new()),
+            boats: SyncMutex::new(EntityList::new()),
+            primed_tnt: SyncMutex::new(EntityList::new()),
+            falling_blocks: SyncMutex::new(EntityList::new()),
+            lightning_bolts: SyncMutex::new(EntityList::new()),
+            villagers: SyncMutex::new(EntityList::new()),
+            zombies: SyncMutex::new(EntityList::new()),
+            skeletons: SyncMutex::new(EntityList::new()),
+            creepers: SyncMutex::new(EntityList::new()),
+            slimes: SyncMutex::new(EntityList::new()),
+            iron_golems: SyncMutex::new(EntityList::new()),
+            endermen: SyncMutex::new(EntityList::new()),
+            ghasts: SyncMutex::new(EntityList::new()),
+            ender_dragons: SyncMutex::new(EntityList::new()),
+            dragon_fireballs: SyncMutex::new(EntityList::new()),
+            ghast_fireballs: SyncMutex::new(EntityList::new()),
+            arrows: SyncMutex::new(EntityList::new()),
+            uuids: SyncMutex::new(FxHashSet::default()),
+            stored_chunks: SyncMutex::new(FxHashMap::default()),
+        }
+    }
+
+    /// Returns every list, to run the same work over all kinds of entities.
+    #[must_use]
+    pub fn lists(&self) -> [&dyn EntityListDyn; 25] {
+        [
+            &self.generic,
+            &self.firework_rockets,
+            &self.paintings,
+            &self.item_frames,
+            &self.armor_stands,
+            &self.experience_orbs,
+            &self.minecarts,
+            &self.chest_minecarts,
+            &self.hopper_minecarts,
+            &self.boats,
+            &self.primed_tnt,
+            &self.falling_blocks,
+            &self.lightning_bolts,
+            &self.villagers,
+            &self.zombies,
+            &self.skeletons,
+            &self.creepers,
+            &self.slimes,
+            &self.iron_golems,
+            &self.endermen,
+            &self.ghasts,
+            &self.ender_dragons,
+            &self.dragon_fireballs,
+            &self.ghast_fireballs,
+            &self.arrows,
+        ]
+    }
+
+    /// Returns the number of entities in the storage.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lists().iter().map(|list| list.len()).sum()
+    }
+
+    /// Returns whether the storage has no entities.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Claims `uuid` for an entity being added. Returns `false` if an entity
+    /// in the storage already has it.
+    pub fn claim_uuid(&self, uuid: Uuid) -> bool {
+        self.uuids.lock().insert(uuid)
+    }
+
+    /// Frees the UUIDs of entities that were taken out of the storage.
+    pub fn release_uuids(&self, uuids: &[Uuid]) {
+        let mut claimed = self.uuids.lock();
+        for uuid in uuids {
+            claimed.remove(uuid);
+        }
+    }
+
+    /// Remembers `entities` as the ones stored with `chunk`. Returns `false`
+    /// if they are the same as the ones stored with it before, so the chunk
+    /// doesn't have to be saved again for them.
+    pub fn mark_stored(&self, chunk: ChunkPos, entities: &[PersistentEntity]) -> bool {
+        let mut stored_chunks = self.stored_chunks.lock();
+        if entities.is_empty() {
+            return stored_chunks.remove(&chunk).is_some();
+        }
+        let hash = FxBuildHasher.hash_one(entities);
+        stored_chunks.insert(chunk, hash) != Some(hash)
+    }
+
+    /// Forgets the entities stored with `chunk` once it unloaded.
+    pub fn forget_stored(&self, chunk: ChunkPos) {
+        self.stored_chunks.lock().remove(&chunk);
+    }
+}
+
+impl Default for EntityStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use steel_registry::vanilla_entities;
+
+    use super::*;
+
+    fn pig(id: i32, x: f64, z: f64) -> GenericEntity {
+        GenericEntity::new(id, vanilla_entities::PIG, Vector3::new(x, 64.0, z))
+    }
+
+    #[test]
+    fn entities_are_kept_with_their_chunk() {
+        let mut list = EntityList::new();
+        list.insert(pig(1, 0.5, 0.5));
+        list.insert(pig(2, 17.0, 0.5));
+        list.insert(pig(3, -0.5, 0.5));
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.in_chunk(ChunkPos::new(0, 0)).len(), 1);
+        assert_eq!(list.in_chunk(ChunkPos::new(1, 0)).len(), 1);
+        assert_eq!(list.in_chunk(ChunkPos::new(-1, 0)).len(), 1);
+        assert_eq!(list.get(2).map(|entity| entity.id), Some(2));
+    }
+
+    #[test]
+    fn rebucket_moves_entities_that_changed_chunks() {
+        let mut list = EntityList::new();
+        list.insert(pig(1, 0.5, 0.5));
+        list.insert(pig(2, 1.5, 0.5));
+        if let Some(entity) = list.get_mut(1) {
+            *entity.position.lock() = Vector3::new(0.5, 64.0, 16.5);
+        }
+
+        let moved = list.rebucket();
+        assert_eq!(moved, vec![(1, ChunkPos::new(0, 0), ChunkPos::new(0, 1))]);
+        assert_eq!(list.in_chunk(ChunkPos::new(0, 0)).len(), 1);
+        assert_eq!(list.in_chunk(ChunkPos::new(0, 1)).len(), 1);
+        assert!(list.get(1).is_some());
+    }
+
+    #[test]
+    fn removed_entities_are_reported_once() {
+        let mut list = EntityList::new();
+        list.insert(pig(1, 0.5, 0.5));
+        list.insert(pig(2, 0.5, 0.5));
+
+        assert!(list.remove(1).is_some());
+        assert!(list.remove(1).is_none());
+        list.retain_mut(|_| false);
+
+        let removed: Vec<i32> = list
+            .take_removed()
+            .iter()
+            .map(|removal| removal.entity_id)
+            .collect();
+        assert_eq!(removed, vec![1, 2]);
+        assert!(list.take_removed().is_empty());
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn chunks_are_saved_again_once_their_entities_changed() {
+        let storage = EntityStorage::new();
+        let chunk = ChunkPos::new(0, 0);
+        let stored = |nbt_data: Vec<u8>| {
+            vec![PersistentEntity {
+                entity_type: Identifier::vanilla_static("pig"),
+                nbt_data,
+            }]
+        };
+
+        assert!(!storage.mark_stored(chunk, &[]));
+        assert!(storage.mark_stored(chunk, &stored(vec![1])));
+        assert!(!storage.mark_stored(chunk, &stored(vec![1])));
+        assert!(storage.mark_stored(chunk, &stored(vec![2])));
+        // The entities left, the stored ones have to be cleared
+        assert!(storage.mark_stored(chunk, &[]));
+        assert!(!storage.mark_stored(chunk, &[]));
+    }
+}
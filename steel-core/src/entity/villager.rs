@@ -0,0 +1,824 @@
+//! Villagers that trade with players and walk between their bed and their job
+//! site.
+
+use std::ptr;
+
+use simdnbt::ToNbtTag;
+use simdnbt::owned::{NbtCompound, NbtList};
+use steel_protocol::packets::game::{CAddEntity, SoundSource};
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::{DataValue, VillagerData};
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::items::ItemRef;
+use steel_registry::vanilla_entity_data::VillagerEntityData;
+use steel_registry::{REGISTRY, sound_events, vanilla_blocks, vanilla_entities, vanilla_items};
+use steel_utils::math::Vector3;
+use steel_utils::{BlockPos, Identifier, translations};
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::entity::mob::MobBase;
+use crate::entity::storage::EntityPairing;
+use crate::entity::{self, AttackableMob, Entity, GenericEntity, Remains, StoredEntity};
+use crate::inventory::merchant_menu::{MerchantOffer, MerchantState};
+use crate::world::World;
+
+/// The beds villagers sleep in.
+pub const BEDS_TAG: Identifier = Identifier::vanilla_static("beds");
+
+/// The highest level a villager reaches, master.
+pub const MAX_LEVEL: u8 = 5;
+
+/// The experience a villager needs to reach the next level, by level.
+///
+/// Vanilla: `VillagerData.NEXT_LEVEL_XP_THRESHOLDS`
+const XP_THRESHOLDS: [i32; 5] = [0, 10, 70, 150, 250];
+
+/// How many new trades a villager learns on each level.
+const TRADES_PER_LEVEL: usize = 2;
+
+/// The registry id of the plains villager type.
+// TODO: Pick the type from the biome the villager spawns in
+const PLAINS_TYPE: i32 = 2;
+
+/// The health of a villager.
+const MAX_HEALTH: f32 = 20.0;
+/// How many ticks a villager can't be hurt again after it was hurt.
+const HURT_COOLDOWN: u32 = 10;
+
+/// How far villagers walk in a tick.
+const WALK_SPEED: f64 = 0.1;
+
+/// How far around itself a villager looks for its bed and job site.
+const SEARCH_RADIUS: i32 = 16;
+/// How far up and down a villager looks for its bed and job site.
+const SEARCH_HEIGHT: i32 = 4;
+/// How many ticks pass between looking for the bed and job site.
+const SEARCH_INTERVAL: u32 = 100;
+/// How many ticks pass between planning paths.
+const PATH_INTERVAL: u32 = 40;
+
+/// The day time at which villagers go to bed.
+const BED_TIME: i64 = 12_000;
+
+/// A job a villager can have, which decides the trades it offers.
+///
+/// Vanilla: `VillagerProfession`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VillagerProfession {
+    /// No job yet; looks for a job site to take.
+    None,
+    /// Works at a blast furnace.
+    Armorer,
+    /// Works at a smoker.
+    Butcher,
+    /// Works at a cartography table.
+    Cartographer,
+    /// Works at a brewing stand.
+    Cleric,
+    /// Works at a composter.
+    Farmer,
+    /// Works at a barrel.
+    Fisherman,
+    /// Works at a fletching table.
+    Fletcher,
+    /// Works at a cauldron.
+    Leatherworker,
+    /// Works at a lectern.
+    Librarian,
+    /// Works at a stonecutter.
+    Mason,
+    /// Never takes a job.
+    Nitwit,
+    /// Works at a loom.
+    Shepherd,
+    /// Works at a smithing table.
+    Toolsmith,
+    /// Works at a grindstone.
+    Weaponsmith,
+}
+
+impl VillagerProfession {
+    /// All professions, in registry order.
+    pub const ALL: [VillagerProfession; 15] = [
+        VillagerProfession::None,
+        VillagerProfession::Armorer,
+        VillagerProfession::Butcher,
+        VillagerProfession::Cartographer,
+        VillagerProfession::Cleric,
+        VillagerProfession::Farmer,
+        VillagerProfession::Fisherman,
+        VillagerProfession::Fletcher,
+        VillagerProfession::Leatherworker,
+        VillagerProfession::Librarian,
+        VillagerProfession::Mason,
+        VillagerProfession::Nitwit,
+        VillagerProfession::Shepherd,
+        VillagerProfession::Toolsmith,
+        VillagerProfession::Weaponsmith,
+    ];
+
+    /// Returns the key of the profession in the registry.
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            VillagerProfession::None => "none",
+            VillagerProfession::Armorer => "armorer",
+            VillagerProfession::Butcher => "butcher",
+            VillagerProfession::Cartographer => "cartographer",
+            VillagerProfession::Cleric => "cleric",
+            VillagerProfession::Farmer => "farmer",
+            VillagerProfession::Fisherman => "fisherman",
+            VillagerProfession::Fletcher => "fletcher",
+            VillagerProfession::Leatherworker => "leatherworker",
+            VillagerProfession::Librarian => "librarian",
+            VillagerProfession::Mason => "mason",
+            VillagerProfession::Nitwit => "nitwit",
+            VillagerProfession::Shepherd => "shepherd",
+            VillagerProfession::Toolsmith => "toolsmith",
+            VillagerProfession::Weaponsmith => "weaponsmith",
+        }
+    }
+
+    /// Returns the profession with the registry key `key`, with or without
+    /// the `minecraft:` namespace.
+    #[must_use]
+    pub fn from_key(key: &str) -> Option<Self> {
+        let key = key.strip_prefix("minecraft:").unwrap_or(key);
+        Self::ALL
+            .into_iter()
+            .find(|profession| profession.key() == key)
+    }
+
+    /// Returns the id of the profession in the registry.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub fn registry_id(self) -> i32 {
+        Self::ALL
+            .iter()
+            .position(|&profession| profession == self)
+            .map_or(0, |id| id as i32)
+    }
+
+    /// Returns the block the profession works at, if it has one.
+    #[must_use]
+    pub fn job_site(self) -> Option<BlockRef> {
+        Some(match self {
+            VillagerProfession::None | VillagerProfession::Nitwit => return None,
+            VillagerProfession::Armorer => vanilla_blocks::BLAST_FURNACE,
+            VillagerProfession::Butcher => vanilla_blocks::SMOKER,
+            VillagerProfession::Cartographer => vanilla_blocks::CARTOGRAPHY_TABLE,
+            VillagerProfession::Cleric => vanilla_blocks::BREWING_STAND,
+            VillagerProfession::Farmer => vanilla_blocks::COMPOSTER,
+            VillagerProfession::Fisherman => vanilla_blocks::BARREL,
+            VillagerProfession::Fletcher => vanilla_blocks::FLETCHING_TABLE,
+            VillagerProfession::Leatherworker => vanilla_blocks::CAULDRON,
+            VillagerProfession::Librarian => vanilla_blocks::LECTERN,
+            VillagerProfession::Mason => vanilla_blocks::STONECUTTER,
+            VillagerProfession::Shepherd => vanilla_blocks::LOOM,
+            VillagerProfession::Toolsmith => vanilla_blocks::SMITHING_TABLE,
+            VillagerProfession::Weaponsmith => vanilla_blocks::GRINDSTONE,
+        })
+    }
+
+    /// Returns the profession that works at `block`, if any.
+    #[must_use]
+    pub fn from_job_site(block: BlockRef) -> Option<Self> {
+        Self::ALL.into_iter().find(|profession| {
+            profession
+                .job_site()
+                .is_some_and(|site| ptr::eq(site, block))
+        })
+    }
+
+    /// Returns the title of the trading screen of a villager with this
+    /// profession.
+    #[must_use]
+    pub fn title(self) -> TextComponent {
+        let message = match self {
+            VillagerProfession::None => translations::ENTITY_MINECRAFT_VILLAGER_NONE,
+            VillagerProfession::Armorer => translations::ENTITY_MINECRAFT_VILLAGER_ARMORER,
+            VillagerProfession::Butcher => translations::ENTITY_MINECRAFT_VILLAGER_BUTCHER,
+            VillagerProfession::Cartographer => {
+                translations::ENTITY_MINECRAFT_VILLAGER_CARTOGRAPHER
+            }
+            VillagerProfession::Cleric => translations::ENTITY_MINECRAFT_VILLAGER_CLERIC,
+            VillagerProfession::Farmer => translations::ENTITY_MINECRAFT_VILLAGER_FARMER,
+            VillagerProfession::Fisherman => translations::ENTITY_MINECRAFT_VILLAGER_FISHERMAN,
+            VillagerProfession::Fletcher => translations::ENTITY_MINECRAFT_VILLAGER_FLETCHER,
+            VillagerProfession::Leatherworker => {
+                translations::ENTITY_MINECRAFT_VILLAGER_LEATHERWORKER
+            }
+            VillagerProfession::Librarian => translations::ENTITY_MINECRAFT_VILLAGER_LIBRARIAN,
+            VillagerProfession::Mason => translations::ENTITY_MINECRAFT_VILLAGER_MASON,
+            VillagerProfession::Nitwit => translations::ENTITY_MINECRAFT_VILLAGER_NITWIT,
+            VillagerProfession::Shepherd => translations::ENTITY_MINECRAFT_VILLAGER_SHEPHERD,
+            VillagerProfession::Toolsmith => translations::ENTITY_MINECRAFT_VILLAGER_TOOLSMITH,
+            VillagerProfession::Weaponsmith => translations::ENTITY_MINECRAFT_VILLAGER_WEAPONSMITH,
+        };
+        TextComponent::translated(message.msg())
+    }
+}
+
+/// A villager, trading with players and walking to its job site by day and
+/// its bed by night.
+///
+/// Vanilla: `Villager`
+pub struct VillagerEntity {
+    /// The state every entity shares, like the id and custom name.
+    pub base: GenericEntity,
+    /// The villager's job.
+    pub profession: VillagerProfession,
+    /// The villager's level, from 1 (novice) to 5 (master).
+    pub level: u8,
+    /// The experience the villager gained from trading.
+    pub xp: i32,
+    /// The trades the villager offers.
+    pub trades: Vec<MerchantOffer>,
+    /// Whether a player has the trading screen open.
+    pub trading: bool,
+    mob: MobBase,
+    entity_data: VillagerEntityData,
+    /// The bed the villager sleeps in, if it found one.
+    home: Option<BlockPos>,
+    /// The block the villager works at, if it found one.
+    job_site: Option<BlockPos>,
+    /// Ticks until the villager looks for its bed and job site again.
+    search_cooldown: u32,
+    /// Ticks until the villager can be hurt again.
+    hurt_cooldown: u32,
+}
+
+impl VillagerEntity {
+    /// Creates an unemployed novice villager at `position`.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        let mut entity_data = VillagerEntityData::new();
+        entity_data.health.set(MAX_HEALTH);
+        entity_data.villager_data.set(VillagerData::new(
+            PLAINS_TYPE,
+            VillagerProfession::None.registry_id(),
+            1,
+        ));
+
+        Self {
+            base: GenericEntity::new(id, vanilla_entities::VILLAGER, position),
+            profession: VillagerProfession::None,
+            level: 1,
+            xp: 0,
+            trades: Vec::new(),
+            trading: false,
+            mob: MobBase::new(position, vanilla_entities::VILLAGER),
+            entity_data,
+            home: None,
+            job_site: None,
+            search_cooldown: 0,
+            hurt_cooldown: 0,
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the profession and the level.
+    ///
+    /// Vanilla: `Villager.readAdditionalSaveData`
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(xp) = nbt.int("Xp") {
+            self.xp = xp.max(0);
+        }
+        // TODO: Let babies grow up
+        if let Some(age) = nbt.int("Age") {
+            self.entity_data.baby.set(age < 0);
+        }
+
+        // Saved trades are kept, a new profession only adds trades to none
+        if let Some(recipes) = nbt
+            .compound("Offers")
+            .and_then(|offers| offers.list("Recipes"))
+            .and_then(|recipes| recipes.compounds())
+        {
+            self.trades = recipes.iter().filter_map(offer_from_nbt).collect();
+        }
+        if let Some(data) = nbt.compound("VillagerData") {
+            if let Some(level) = data.int("level") {
+                self.level = level.clamp(1, i32::from(MAX_LEVEL)) as u8;
+            }
+            let profession = data
+                .string("profession")
+                .and_then(|key| VillagerProfession::from_key(&key.to_str()));
+            if let Some(profession) = profession {
+                self.set_profession(profession);
+            }
+        }
+        self.sync_villager_data();
+    }
+
+    /// Writes the job, the level, the experience and the trades into `nbt`.
+    ///
+    /// Vanilla: `Villager.addAdditionalSaveData`
+    pub fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("Xp", self.xp);
+        nbt.insert("Age", if self.is_baby() { -24_000 } else { 0 });
+
+        let mut data = NbtCompound::new();
+        data.insert("level", i32::from(self.level));
+        data.insert(
+            "profession",
+            Identifier::vanilla_static(self.profession.key()).to_string(),
+        );
+        data.insert("type", "minecraft:plains");
+        nbt.insert("VillagerData", data);
+
+        let mut offers = NbtCompound::new();
+        offers.insert(
+            "Recipes",
+            NbtList::Compound(self.trades.iter().map(offer_to_nbt).collect()),
+        );
+        nbt.insert("Offers", offers);
+    }
+
+    /// Returns the position of the villager.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.mob.position()
+    }
+
+    /// Returns true if the villager is a baby.
+    #[must_use]
+    pub fn is_baby(&self) -> bool {
+        *self.entity_data.baby.get()
+    }
+
+    /// Returns the box the villager takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.mob.bounding_box()
+    }
+
+    /// Returns true once the villager has no health left.
+    #[must_use]
+    pub fn is_dead(&self) -> bool {
+        *self.entity_data.health.get() <= 0.0
+    }
+
+    /// Hurts the villager by `amount`. Returns false if it was hurt too
+    /// recently to be hurt again.
+    ///
+    /// Vanilla: `LivingEntity.hurtServer`
+    pub fn hurt(&mut self, amount: f32) -> bool {
+        if self.hurt_cooldown > 0 || self.is_dead() {
+            return false;
+        }
+        self.hurt_cooldown = HURT_COOLDOWN;
+        let health = *self.entity_data.health.get();
+        self.entity_data.health.set((health - amount).max(0.0));
+        true
+    }
+
+    /// The packet that adds this villager to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the villager is added, like
+    /// its profession.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Gives the villager a new job, learning the novice trades of it if it
+    /// has none yet.
+    pub fn set_profession(&mut self, profession: VillagerProfession) {
+        self.profession = profession;
+        if self.trades.is_empty() {
+            for level in 1..=self.level {
+                self.unlock_trades(level);
+            }
+        }
+        self.sync_villager_data();
+    }
+
+    /// Learns up to [`TRADES_PER_LEVEL`] random trades of the villager's
+    /// profession at `level`.
+    ///
+    /// Vanilla: `Villager.updateTrades`
+    fn unlock_trades(&mut self, level: u8) {
+        let mut pool = trade_pool(self.profession, level);
+        for _ in 0..TRADES_PER_LEVEL {
+            if pool.is_empty() {
+                break;
+            }
+            let index = rand::random_range(0..pool.len());
+            self.trades.push(pool.swap_remove(index));
+        }
+    }
+
+    fn sync_villager_data(&mut self) {
+        self.entity_data.villager_data.set(VillagerData::new(
+            PLAINS_TYPE,
+            self.profession.registry_id(),
+            i32::from(self.level),
+        ));
+    }
+
+    /// The offers and progress shown in the trading screen.
+    #[must_use]
+    pub fn merchant_state(&self) -> MerchantState {
+        MerchantState::new(self.base.id, self.trades.clone(), self.level, self.xp)
+    }
+
+    /// Uses the trade at `index`, gaining its experience and levelling up
+    /// once the villager has enough. Returns whether it levelled up, or
+    /// `None` if there is no such trade.
+    ///
+    /// Vanilla: `Villager.notifyTrade` and `Villager.rewardTradeXp`
+    pub fn trade(&mut self, index: usize) -> Option<bool> {
+        let offer = self.trades.get_mut(index)?;
+        offer.uses += 1;
+        self.xp += offer.xp;
+
+        let level_up = should_level_up(self.level, self.xp);
+        if level_up {
+            self.level += 1;
+            self.unlock_trades(self.level);
+            self.sync_villager_data();
+        }
+        Some(level_up)
+    }
+
+    /// Advances the villager by a tick, walking towards its job site by day
+    /// and its bed by night.
+    ///
+    /// Vanilla: `Villager.customServerAiStep`
+    pub fn tick(&mut self, world: &World) {
+        self.hurt_cooldown = self.hurt_cooldown.saturating_sub(1);
+        if self.search_cooldown == 0 {
+            self.search_cooldown = SEARCH_INTERVAL;
+            self.find_points_of_interest(world);
+        }
+        self.search_cooldown -= 1;
+
+        let goal = if self.trading {
+            None
+        } else if world.level_data.read().day_time().rem_euclid(24_000) >= BED_TIME {
+            self.home
+        } else {
+            self.job_site
+        };
+        self.plan_path(world, goal);
+        self.mob.walk(&self.base, world, WALK_SPEED);
+    }
+
+    /// Forgets a bed or job site that is gone and looks for one nearby if
+    /// the villager has none. Unemployed villagers take the job of the job
+    /// site they find.
+    fn find_points_of_interest(&mut self, world: &World) {
+        let profession = self.profession;
+        let is_job_site = |block: BlockRef| match profession {
+            VillagerProfession::None => VillagerProfession::from_job_site(block).is_some(),
+            _ => profession
+                .job_site()
+                .is_some_and(|site| ptr::eq(site, block)),
+        };
+        let is_bed = |block: BlockRef| REGISTRY.blocks.is_in_tag(block, &BEDS_TAG);
+
+        if self
+            .home
+            .is_some_and(|pos| !is_bed(world.get_block_state(&pos).get_block()))
+        {
+            self.home = None;
+        }
+        if self
+            .job_site
+            .is_some_and(|pos| !is_job_site(world.get_block_state(&pos).get_block()))
+        {
+            self.job_site = None;
+        }
+
+        let center = self.position().to_block_pos();
+        if self.home.is_none() {
+            self.home = find_nearest_block(world, center, is_bed);
+        }
+        if self.job_site.is_none() && profession != VillagerProfession::Nitwit {
+            self.job_site = find_nearest_block(world, center, is_job_site);
+            if profession == VillagerProfession::None
+                && let Some(pos) = self.job_site
+                && let Some(job) =
+                    VillagerProfession::from_job_site(world.get_block_state(&pos).get_block())
+            {
+                self.set_profession(job);
+            }
+        }
+    }
+
+    /// Plans a path to next to `goal`, or stops walking if there is none.
+    fn plan_path(&mut self, world: &World, goal: Option<BlockPos>) {
+        match goal {
+            Some(goal) if !is_next_to(self.position().to_block_pos(), goal) => {
+                self.mob.move_to(world, goal, PATH_INTERVAL);
+            }
+            _ => self.mob.stop(),
+        }
+    }
+}
+
+impl Entity for VillagerEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for VillagerEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.base.write_nbt(&mut nbt);
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.base.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    fn explosion_hit(
+        &mut self,
+        world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.hurt_by_explosion(world, damage);
+        self.mob.push(impulse);
+        None
+    }
+}
+
+impl AttackableMob for VillagerEntity {
+    const SOUND_SOURCE: SoundSource = SoundSource::Neutral;
+
+    fn take_hit(&mut self, amount: f32) -> Option<i32> {
+        self.hurt(amount)
+            .then_some(sound_events::ENTITY_VILLAGER_HURT)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.is_dead()
+    }
+
+    fn death_sound(&self) -> i32 {
+        sound_events::ENTITY_VILLAGER_DEATH
+    }
+
+    fn experience_reward(&self) -> i32 {
+        0
+    }
+}
+
+/// Returns true once a villager at `level` with `xp` experience reaches the
+/// next level.
+///
+/// Vanilla: `Villager.shouldIncreaseLevel`
+#[must_use]
+pub fn should_level_up(level: u8, xp: i32) -> bool {
+    level < MAX_LEVEL && xp >= XP_THRESHOLDS[usize::from(level)]
+}
+
+/// Returns true if standing in `pos` is close enough to use the block at
+/// `target`.
+fn is_next_to(pos: BlockPos, target: BlockPos) -> bool {
+    (pos.x() - target.x()).abs() <= 1
+        && (pos.z() - target.z()).abs() <= 1
+        && (pos.y() - target.y()).abs() <= 1
+}
+
+/// Returns the block closest to `center` that `matches`, searching
+/// [`SEARCH_RADIUS`] blocks around it.
+fn find_nearest_block(
+    world: &World,
+    center: BlockPos,
+    matches: impl Fn(BlockRef) -> bool,
+) -> Option<BlockPos> {
+    let mut nearest: Option<(i32, BlockPos)> = None;
+    for dy in -SEARCH_HEIGHT..=SEARCH_HEIGHT {
+        for dx in -SEARCH_RADIUS..=SEARCH_RADIUS {
+            for dz in -SEARCH_RADIUS..=SEARCH_RADIUS {
+                let distance = dx * dx + dy * dy + dz * dz;
+                if nearest.is_some_and(|(nearest, _)| nearest <= distance) {
+                    continue;
+                }
+                let pos = center.offset(dx, dy, dz);
+                if matches(world.get_block_state(&pos).get_block()) {
+                    nearest = Some((distance, pos));
+                }
+            }
+        }
+    }
+    nearest.map(|(_, pos)| pos)
+}
+
+/// A trade that buys `count` of `item` for an emerald.
+///
+/// Vanilla: `VillagerTrades.EmeraldForItems`
+fn emerald_for_items(item: ItemRef, count: i32, max_uses: i32, xp: i32) -> MerchantOffer {
+    MerchantOffer {
+        buy1: ItemStack::with_count(item, count),
+        buy2: None,
+        sell: ItemStack::new(&vanilla_items::ITEMS.emerald),
+        uses: 0,
+        max_uses,
+        xp,
+    }
+}
+
+/// Saves a trade like vanilla's `Offers.Recipes` entries.
+///
+/// Vanilla: `MerchantOffer.CODEC`
+fn offer_to_nbt(offer: &MerchantOffer) -> NbtCompound {
+    let mut nbt = NbtCompound::new();
+    nbt.insert("buy", offer.buy1.clone().to_nbt_tag());
+    if let Some(buy2) = &offer.buy2 {
+        nbt.insert("buyB", buy2.clone().to_nbt_tag());
+    }
+    nbt.insert("sell", offer.sell.clone().to_nbt_tag());
+    nbt.insert("uses", offer.uses);
+    nbt.insert("maxUses", offer.max_uses);
+    nbt.insert("xp", offer.xp);
+    nbt
+}
+
+/// Reads a trade saved with [`offer_to_nbt`]. Returns `None` if one of its
+/// items is unknown.
+fn offer_from_nbt(nbt: &NbtCompound) -> Option<MerchantOffer> {
+    Some(MerchantOffer {
+        buy1: nbt.compound("buy").and_then(entity::item_from_nbt)?,
+        buy2: nbt.compound("buyB").and_then(entity::item_from_nbt),
+        sell: nbt.compound("sell").and_then(entity::item_from_nbt)?,
+        uses: nbt.int("uses").unwrap_or(0),
+        max_uses: nbt.int("maxUses").unwrap_or(4),
+        xp: nbt.int("xp").unwrap_or(1),
+    })
+}
+
+/// A trade that sells `count` of `item` for `emeralds` emeralds.
+///
+/// Vanilla: `VillagerTrades.ItemsForEmeralds`
+fn items_for_emeralds(
+    item: ItemRef,
+    emeralds: i32,
+    count: i32,
+    max_uses: i32,
+    xp: i32,
+) -> MerchantOffer {
+    MerchantOffer {
+        buy1: ItemStack::with_count(&vanilla_items::ITEMS.emerald, emeralds),
+        buy2: None,
+        sell: ItemStack::with_count(item, count),
+        uses: 0,
+        max_uses,
+        xp,
+    }
+}
+
+/// Returns the trades a villager with `profession` may learn at `level`.
+///
+/// Vanilla: `VillagerTrades.TRADES`
+// TODO: Add the trades of the other professions, and the enchanted books and
+// gear once items can be enchanted
+#[allow(clippy::too_many_lines)]
+fn trade_pool(profession: VillagerProfession, level: u8) -> Vec<MerchantOffer> {
+    let items = &vanilla_items::ITEMS;
+    match (profession, level) {
+        (VillagerProfession::Farmer, 1) => vec![
+            emerald_for_items(&items.wheat, 20, 16, 2),
+            emerald_for_items(&items.potato, 26, 16, 2),
+            emerald_for_items(&items.carrot, 22, 16, 2),
+            emerald_for_items(&items.beetroot, 15, 16, 2),
+            items_for_emeralds(&items.bread, 1, 6, 16, 1),
+        ],
+        (VillagerProfession::Farmer, 2) => vec![
+            emerald_for_items(&items.pumpkin, 6, 12, 10),
+            items_for_emeralds(&items.pumpkin_pie, 1, 4, 12, 5),
+            items_for_emeralds(&items.apple, 1, 4, 16, 5),
+        ],
+        (VillagerProfession::Farmer, 3) => vec![
+            items_for_emeralds(&items.cookie, 3, 18, 12, 10),
+            emerald_for_items(&items.melon, 4, 12, 20),
+        ],
+        (VillagerProfession::Farmer, 4) => vec![items_for_emeralds(&items.cake, 1, 1, 12, 15)],
+        (VillagerProfession::Farmer, 5) => vec![
+            items_for_emeralds(&items.golden_carrot, 3, 3, 12, 30),
+            items_for_emeralds(&items.glistering_melon_slice, 4, 3, 12, 30),
+        ],
+        (VillagerProfession::Librarian, 1) => vec![
+            emerald_for_items(&items.paper, 24, 16, 2),
+            items_for_emeralds(&items.bookshelf, 9, 1, 12, 1),
+        ],
+        (VillagerProfession::Librarian, 2) => vec![
+            emerald_for_items(&items.book, 4, 12, 10),
+            items_for_emeralds(&items.lantern, 1, 1, 12, 5),
+        ],
+        (VillagerProfession::Librarian, 3) => vec![
+            emerald_for_items(&items.ink_sac, 5, 12, 20),
+            items_for_emeralds(&items.glass, 1, 4, 12, 10),
+        ],
+        (VillagerProfession::Librarian, 4) => vec![
+            emerald_for_items(&items.writable_book, 2, 12, 30),
+            items_for_emeralds(&items.clock, 5, 1, 12, 15),
+            items_for_emeralds(&items.compass, 4, 1, 12, 15),
+        ],
+        (VillagerProfession::Librarian, 5) => {
+            vec![items_for_emeralds(&items.name_tag, 20, 1, 12, 30)]
+        }
+        (VillagerProfession::Armorer, 1) => vec![
+            emerald_for_items(&items.coal, 15, 16, 2),
+            items_for_emeralds(&items.iron_leggings, 7, 1, 12, 1),
+            items_for_emeralds(&items.iron_boots, 4, 1, 12, 1),
+            items_for_emeralds(&items.iron_helmet, 5, 1, 12, 1),
+            items_for_emeralds(&items.iron_chestplate, 9, 1, 12, 1),
+        ],
+        (VillagerProfession::Armorer, 2) => vec![
+            emerald_for_items(&items.iron_ingot, 4, 12, 10),
+            items_for_emeralds(&items.bell, 36, 1, 12, 5),
+            items_for_emeralds(&items.chainmail_boots, 1, 1, 12, 5),
+            items_for_emeralds(&items.chainmail_leggings, 3, 1, 12, 5),
+        ],
+        (VillagerProfession::Armorer, 3) => vec![
+            emerald_for_items(&items.lava_bucket, 1, 12, 20),
+            emerald_for_items(&items.diamond, 1, 12, 20),
+            items_for_emeralds(&items.chainmail_helmet, 1, 1, 12, 10),
+            items_for_emeralds(&items.chainmail_chestplate, 4, 1, 12, 10),
+            items_for_emeralds(&items.shield, 5, 1, 12, 10),
+        ],
+        (VillagerProfession::Toolsmith, 1) => vec![
+            emerald_for_items(&items.coal, 15, 16, 2),
+            items_for_emeralds(&items.stone_axe, 1, 1, 12, 1),
+            items_for_emeralds(&items.stone_shovel, 1, 1, 12, 1),
+            items_for_emeralds(&items.stone_pickaxe, 1, 1, 12, 1),
+            items_for_emeralds(&items.stone_hoe, 1, 1, 12, 1),
+        ],
+        (VillagerProfession::Toolsmith | VillagerProfession::Weaponsmith, 2) => vec![
+            emerald_for_items(&items.iron_ingot, 4, 12, 10),
+            items_for_emeralds(&items.bell, 36, 1, 12, 5),
+        ],
+        (VillagerProfession::Toolsmith, 3) => vec![
+            emerald_for_items(&items.flint, 30, 12, 20),
+            items_for_emeralds(&items.diamond_hoe, 4, 1, 3, 10),
+        ],
+        (VillagerProfession::Toolsmith | VillagerProfession::Weaponsmith, 4) => {
+            vec![emerald_for_items(&items.diamond, 1, 12, 30)]
+        }
+        (VillagerProfession::Weaponsmith, 1) => vec![
+            emerald_for_items(&items.coal, 15, 16, 2),
+            items_for_emeralds(&items.iron_axe, 3, 1, 12, 1),
+        ],
+        (VillagerProfession::Weaponsmith, 3) => {
+            vec![emerald_for_items(&items.flint, 24, 12, 20)]
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_up_at_thresholds() {
+        assert!(!should_level_up(1, 9));
+        assert!(should_level_up(1, 10));
+        assert!(!should_level_up(2, 69));
+        assert!(should_level_up(4, 250));
+        assert!(!should_level_up(MAX_LEVEL, 10_000));
+    }
+}
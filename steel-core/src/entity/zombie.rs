@@ -0,0 +1,347 @@
+//! Zombies that chase players, hit them and burn in the sun.
+
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::CAddEntity;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::vanilla_entity_data::ZombieEntityData;
+use steel_registry::{sound_events, vanilla_entities};
+use steel_utils::math::Vector3;
+use steel_utils::types::Difficulty;
+use uuid::Uuid;
+
+use crate::entity::mob::{self, MobBase};
+use crate::entity::storage::EntityPairing;
+use crate::entity::{AttackableMob, Entity, GenericEntity, Remains, StoredEntity};
+use crate::player::Player;
+use crate::world::World;
+
+/// The health of a zombie.
+const MAX_HEALTH: f32 = 20.0;
+/// How far above its feet a zombie's eyes are.
+const EYE_HEIGHT: f64 = 1.74;
+/// How far zombies walk in a tick.
+const WALK_SPEED: f64 = 0.15;
+
+/// How far away zombies notice players.
+const FOLLOW_RANGE: f64 = 40.0;
+/// How many ticks pass between finding new paths to the target.
+const PATH_INTERVAL: u32 = 20;
+/// How close a zombie gets to its target before hitting it.
+const ATTACK_RANGE: f64 = 1.0;
+/// How many ticks pass between hits.
+const ATTACK_INTERVAL: u32 = 20;
+/// How many ticks a zombie can't be hurt again after it was hurt.
+const HURT_COOLDOWN: u32 = 10;
+
+/// The experience a zombie drops when a player kills it.
+///
+/// Vanilla: `Monster.XP_REWARD_MEDIUM`
+pub const EXPERIENCE_REWARD: i32 = 5;
+
+/// How many ticks pass between zombies spawning in the dark.
+pub const SPAWN_INTERVAL: u64 = 20;
+/// How many zombies a world holds before no more spawn in the dark.
+///
+/// Vanilla: `MobCategory.MONSTER` spawn cap
+pub const MONSTER_CAP: usize = 70;
+/// How far from a player zombies spawn at the least.
+const MIN_SPAWN_DISTANCE: i32 = 24;
+/// How far from a player zombies spawn at the most.
+const MAX_SPAWN_DISTANCE: i32 = 44;
+/// How far above and below a player zombies spawn.
+const SPAWN_HEIGHT: i32 = 8;
+
+/// A zombie, chasing the nearest player to hit them.
+///
+/// Vanilla: `Zombie`
+pub struct ZombieEntity {
+    /// The state every entity shares, like the id and custom name.
+    pub base: GenericEntity,
+    /// The player the zombie chases, if any.
+    pub target: Option<Uuid>,
+    mob: MobBase,
+    entity_data: ZombieEntityData,
+    /// Ticks until the zombie may hit its target again.
+    attack_cooldown: u32,
+    /// Ticks until the zombie can be hurt again.
+    hurt_cooldown: u32,
+}
+
+impl ZombieEntity {
+    /// Creates an adult zombie at `position`.
+    #[must_use]
+    pub fn new(id: i32, position: Vector3<f64>) -> Self {
+        let mut entity_data = ZombieEntityData::new();
+        entity_data.health.set(MAX_HEALTH);
+
+        Self {
+            base: GenericEntity::new(id, vanilla_entities::ZOMBIE, position),
+            target: None,
+            mob: MobBase::new(position, vanilla_entities::ZOMBIE),
+            entity_data,
+            attack_cooldown: 0,
+            hurt_cooldown: 0,
+        }
+    }
+
+    /// Applies the tags of `nbt`, like the health and whether it is a baby.
+    ///
+    /// Vanilla: `Zombie.readAdditionalSaveData`
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.base.read_nbt(nbt);
+        if let Some(health) = nbt.float("Health") {
+            self.entity_data.health.set(health.clamp(0.0, MAX_HEALTH));
+        }
+        // TODO: Make babies smaller and faster
+        if let Some(baby) = nbt.byte("IsBaby") {
+            self.entity_data.baby.set(baby != 0);
+        }
+    }
+
+    /// Writes the health and whether the zombie is a baby into `nbt`.
+    ///
+    /// Vanilla: `Zombie.addAdditionalSaveData`
+    pub fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("Health", *self.entity_data.health.get());
+        nbt.insert("IsBaby", i8::from(*self.entity_data.baby.get()));
+    }
+
+    /// Returns the position of the zombie.
+    #[must_use]
+    pub const fn position(&self) -> Vector3<f64> {
+        self.mob.position()
+    }
+
+    /// Returns the box the zombie takes up.
+    #[must_use]
+    pub const fn bounding_box(&self) -> AABBd {
+        self.mob.bounding_box()
+    }
+
+    /// The packet that adds this zombie to clients.
+    #[must_use]
+    pub fn add_entity_packet(&self) -> CAddEntity {
+        self.base.add_entity_packet()
+    }
+
+    /// The entity data clients need right after the zombie is added.
+    #[must_use]
+    pub fn non_default_data(&self) -> Vec<DataValue> {
+        let mut values = self.base.non_default_data();
+        values.extend(self.entity_data.pack_all());
+        values
+    }
+
+    /// Returns the data that changed since it was last synced, if any.
+    pub fn pack_dirty_data(&mut self) -> Option<Vec<DataValue>> {
+        self.entity_data.pack_dirty()
+    }
+
+    /// Adds `impulse` to the velocity of the zombie.
+    pub fn push(&mut self, impulse: Vector3<f64>) {
+        self.mob.push(impulse);
+    }
+
+    /// Returns true once the zombie has no health left.
+    #[must_use]
+    pub fn is_dead(&self) -> bool {
+        *self.entity_data.health.get() <= 0.0
+    }
+
+    /// Hurts the zombie by `amount`. Returns false if it was hurt too
+    /// recently to be hurt again.
+    ///
+    /// Vanilla: `LivingEntity.hurtServer`
+    pub fn hurt(&mut self, amount: f32) -> bool {
+        if self.hurt_cooldown > 0 || self.is_dead() {
+            return false;
+        }
+        self.hurt_cooldown = HURT_COOLDOWN;
+        let health = *self.entity_data.health.get();
+        self.entity_data.health.set((health - amount).max(0.0));
+        true
+    }
+
+    /// Advances the zombie by a tick: it burns in the sun, and chases and
+    /// hits its target.
+    ///
+    /// Vanilla: `Zombie.aiStep`
+    pub fn tick(&mut self, world: &World) {
+        self.attack_cooldown = self.attack_cooldown.saturating_sub(1);
+        self.hurt_cooldown = self.hurt_cooldown.saturating_sub(1);
+
+        // TODO: Don't burn with a helmet on once mobs wear equipment
+        if self.mob.burn_in_sunlight(world, EYE_HEIGHT) {
+            self.hurt(1.0);
+        }
+        self.base.on_fire = self.mob.is_on_fire();
+        self.entity_data.shared_flags.set(self.base.shared_flags());
+        if self.is_dead() {
+            return;
+        }
+
+        let target = mob::find_target(world, self.position(), self.target, FOLLOW_RANGE);
+        self.target = target.as_ref().map(|player| player.gameprofile.id);
+        match target {
+            Some(player) => {
+                let target_pos = *player.position.lock();
+                self.mob
+                    .move_to(world, target_pos.to_block_pos(), PATH_INTERVAL);
+                let reach = self.bounding_box().inflate(ATTACK_RANGE);
+                if self.attack_cooldown == 0 && reach.intersects(&player.bounding_box()) {
+                    self.attack_cooldown = ATTACK_INTERVAL;
+                    if let Some(damage) = attack_damage(world.difficulty()) {
+                        player.take_damage(damage);
+                    }
+                }
+            }
+            None => self.mob.stop(),
+        }
+        self.mob.walk(&self.base, world, WALK_SPEED);
+    }
+}
+
+impl Entity for ZombieEntity {
+    fn get_uuid(&self) -> Uuid {
+        self.base.uuid
+    }
+}
+
+impl StoredEntity for ZombieEntity {
+    fn get_id(&self) -> i32 {
+        self.base.id
+    }
+
+    fn get_type(&self) -> EntityTypeRef {
+        self.base.entity_type
+    }
+
+    fn get_position(&self) -> Vector3<f64> {
+        self.position()
+    }
+
+    fn pair(&self, _world: &World, pairing: &mut EntityPairing) {
+        pairing.add_entity(self.add_entity_packet(), self.non_default_data());
+    }
+
+    fn save(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.base.write_nbt(&mut nbt);
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn hitbox(&self, entity_id: i32) -> Option<AABBd> {
+        (entity_id == self.base.id).then(|| self.bounding_box())
+    }
+
+    fn explosion_box(&self, _breaks_blocks: bool) -> Option<AABBd> {
+        Some(self.bounding_box())
+    }
+
+    fn explosion_hit(
+        &mut self,
+        world: &World,
+        damage: f32,
+        impulse: Vector3<f64>,
+    ) -> Option<Remains> {
+        self.hurt_by_explosion(world, damage);
+        self.push(impulse);
+        None
+    }
+}
+
+impl AttackableMob for ZombieEntity {
+    fn take_hit(&mut self, amount: f32) -> Option<i32> {
+        self.hurt(amount)
+            .then_some(sound_events::ENTITY_ZOMBIE_HURT)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.is_dead()
+    }
+
+    fn retaliate(&mut self, _world: &World, player: &Player) {
+        self.target = Some(player.gameprofile.id);
+    }
+
+    fn death_sound(&self) -> i32 {
+        sound_events::ENTITY_ZOMBIE_DEATH
+    }
+
+    fn experience_reward(&self) -> i32 {
+        EXPERIENCE_REWARD
+    }
+}
+
+/// How much a zombie hurts players with a hit on `difficulty`, or `None` on
+/// peaceful where zombies don't fight.
+///
+/// Vanilla: `Zombie` attack damage scaled by `Player.hurtServer`
+#[must_use]
+pub const fn attack_damage(difficulty: Difficulty) -> Option<f32> {
+    match difficulty {
+        Difficulty::Peaceful => None,
+        Difficulty::Easy => Some(3.0),
+        Difficulty::Normal => Some(4.0),
+        Difficulty::Hard => Some(6.0),
+    }
+}
+
+/// Picks a random spot around `center` where a zombie could spawn in the
+/// dark: open for its body, on a solid block, and out of the sunlight.
+/// Returns `None` if the spot picked isn't one.
+///
+/// Vanilla: `NaturalSpawner.spawnCategoryForPosition`
+// TODO: Check the block light once the light engine tracks it, so zombies
+// don't spawn in lit rooms
+#[must_use]
+pub fn natural_spawn_position(world: &World, center: Vector3<f64>) -> Option<Vector3<f64>> {
+    let center = center.to_block_pos();
+    let dx = rand::random_range(-MAX_SPAWN_DISTANCE..=MAX_SPAWN_DISTANCE);
+    let dz = rand::random_range(-MAX_SPAWN_DISTANCE..=MAX_SPAWN_DISTANCE);
+    if dx.abs().max(dz.abs()) < MIN_SPAWN_DISTANCE {
+        return None;
+    }
+
+    let is_open = |pos| world.get_block_state(&pos).get_collision_shape().is_empty();
+    let is_night = world.level_data.read().day_time().rem_euclid(24_000) >= 13_000;
+    (-SPAWN_HEIGHT..=SPAWN_HEIGHT)
+        .rev()
+        .map(|dy| center.offset(dx, dy, dz))
+        .find(|&pos| {
+            is_open(pos)
+                && is_open(pos.offset(0, 1, 0))
+                && !is_open(pos.offset(0, -1, 0))
+                && !world.get_block_state(&pos).get_block().config.liquid
+                && (is_night || !world.is_sky_visible(&pos))
+        })
+        .map(|pos| {
+            Vector3::new(
+                f64::from(pos.x()) + 0.5,
+                f64::from(pos.y()),
+                f64::from(pos.z()) + 0.5,
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hits_harder_on_higher_difficulties() {
+        assert!(attack_damage(Difficulty::Peaceful).is_none());
+        for (difficulty, expected) in [
+            (Difficulty::Easy, 3.0),
+            (Difficulty::Normal, 4.0),
+            (Difficulty::Hard, 6.0),
+        ] {
+            let damage = attack_damage(difficulty).expect("zombies fight");
+            assert!((damage - expected).abs() < f32::EPSILON);
+        }
+    }
+}
@@ -0,0 +1,504 @@
+//! The anvil menu for renaming and repairing items.
+//!
+//! Slot layout (39 total):
+//! - Slot 0: Item to repair or rename
+//! - Slot 1: Additional item (a second copy of the item to combine with)
+//! - Slot 2: Result
+//! - Slots 3-29: Main inventory (27 slots)
+//! - Slots 30-38: Hotbar (9 slots)
+
+use std::{mem, sync::Arc};
+
+use steel_registry::data_components::vanilla_components::{
+    CUSTOM_NAME, ENCHANTMENTS, ITEM_NAME, REPAIR_COST, STORED_ENCHANTMENTS,
+};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::vanilla_menu_types;
+use steel_utils::locks::SyncMutex;
+use steel_utils::translations_registry::TRANSLATIONS;
+use steel_utils::{BlockPos, translations};
+use text_components::TextComponent;
+use text_components::content::Content;
+
+use crate::inventory::{
+    SyncPlayerInv,
+    container::{Container, SimpleContainer},
+    crafting::ResultContainer,
+    lock::{ContainerId, ContainerLockGuard, ContainerRef, GenericContainer},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{
+        AnvilResultSlot, NormalSlot, Slot, SlotType, SyncResultContainer,
+        add_standard_inventory_slots,
+    },
+};
+use crate::player::Player;
+
+/// Maximum length of a name typed into the anvil.
+pub const MAX_NAME_LENGTH: usize = 50;
+
+/// Costs at or above this many levels are "Too Expensive!" outside of creative.
+pub const MAX_COST: i32 = 40;
+
+/// Slot indices for the anvil menu.
+pub mod slots {
+    /// Slot index for the item being repaired or renamed (slot 0).
+    pub const INPUT_SLOT: usize = 0;
+    /// Slot index for the additional item (slot 1).
+    pub const ADDITIONAL_SLOT: usize = 1;
+    /// Slot index for the result (slot 2).
+    pub const RESULT_SLOT: usize = 2;
+    /// Start of main inventory (slot 3).
+    pub const INV_SLOT_START: usize = 3;
+    /// End of main inventory (slot 30, exclusive).
+    pub const INV_SLOT_END: usize = 30;
+    /// Start of hotbar (slot 30).
+    pub const HOTBAR_SLOT_START: usize = 30;
+    /// End of hotbar (slot 39, exclusive).
+    pub const HOTBAR_SLOT_END: usize = 39;
+    /// Total number of slots in the anvil menu.
+    pub const TOTAL_SLOTS: usize = 39;
+}
+
+/// Anvil state shared between [`AnvilMenu`] and its result slot.
+#[derive(Default)]
+pub struct AnvilState {
+    /// Experience level cost of taking the current result.
+    pub cost: i32,
+    /// How many additional items a material repair consumes.
+    pub repair_item_count_cost: i32,
+    /// Whether the current result only renames the input.
+    pub only_renaming: bool,
+}
+
+/// A synchronized anvil state.
+pub type SyncAnvilState = Arc<SyncMutex<AnvilState>>;
+
+/// The anvil menu.
+///
+/// Based on Java's `AnvilMenu`.
+pub struct AnvilMenu {
+    behavior: MenuBehavior,
+    /// The two input slots.
+    input_container: GenericContainer,
+    /// The result slot.
+    result_container: SyncResultContainer,
+    /// Cost and consumption info shared with the result slot.
+    state: SyncAnvilState,
+    /// The name typed into the name field, if any.
+    item_name: Option<String>,
+    /// Index of the data slot that syncs the cost to the client.
+    cost_data_slot: usize,
+    /// The position of the anvil block.
+    block_pos: BlockPos,
+}
+
+impl AnvilMenu {
+    /// Creates a new anvil menu for a player.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `block_pos` - The position of the anvil block
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, container_id: u8, block_pos: BlockPos) -> Self {
+        let mut menu_slots = Vec::with_capacity(slots::TOTAL_SLOTS);
+
+        let input_container: GenericContainer = Arc::new(SyncMutex::new(SimpleContainer::new(2)));
+        let result_container: SyncResultContainer =
+            Arc::new(SyncMutex::new(ResultContainer::new()));
+        let state: SyncAnvilState = Arc::new(SyncMutex::new(AnvilState::default()));
+
+        // Slots 0-1: Inputs
+        for i in 0..2 {
+            menu_slots.push(SlotType::Normal(NormalSlot::new(
+                ContainerRef::Other(input_container.clone()),
+                i,
+            )));
+        }
+
+        // Slot 2: Result
+        menu_slots.push(SlotType::AnvilResult(AnvilResultSlot::new(
+            result_container.clone(),
+            input_container.clone(),
+            state.clone(),
+            block_pos,
+        )));
+
+        // Slots 3-38: Standard inventory (main inventory + hotbar)
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        let mut behavior =
+            MenuBehavior::new(menu_slots, container_id, Some(vanilla_menu_types::ANVIL));
+        let cost_data_slot = behavior.add_data_slot(0);
+
+        Self {
+            behavior,
+            input_container,
+            result_container,
+            state,
+            item_name: None,
+            cost_data_slot,
+            block_pos,
+        }
+    }
+
+    /// Returns the menu type for the anvil.
+    #[must_use]
+    pub fn menu_type() -> MenuTypeRef {
+        vanilla_menu_types::ANVIL
+    }
+
+    /// Returns the position of the anvil block.
+    #[must_use]
+    pub fn block_pos(&self) -> BlockPos {
+        self.block_pos
+    }
+
+    /// Returns the experience level cost of the current result.
+    #[must_use]
+    pub fn cost(&self) -> i32 {
+        self.state.lock().cost
+    }
+
+    /// Recomputes the result slot and the cost from the inputs.
+    fn update_result(&mut self, has_infinite_materials: bool) {
+        let mut guard = self.behavior.lock_all_containers();
+
+        let (input, addition) = {
+            let inputs = guard
+                .get(ContainerId::from_arc(&self.input_container))
+                .expect("input container not locked");
+            (inputs.get_item(0).clone(), inputs.get_item(1).clone())
+        };
+
+        let mut state = self.state.lock();
+        let result = create_result(
+            &input,
+            &addition,
+            self.item_name.as_deref(),
+            has_infinite_materials,
+            &mut state,
+        );
+        let cost = state.cost;
+        drop(state);
+
+        guard
+            .get_result_container_mut(ContainerId::from_arc(&self.result_container))
+            .expect("result container not locked")
+            .set_item(0, result);
+        drop(guard);
+
+        self.behavior.set_data(
+            self.cost_data_slot,
+            cost.clamp(0, i32::from(i16::MAX)) as i16,
+        );
+    }
+}
+
+/// Computes the anvil output for the given inputs and stores its cost in `state`.
+///
+/// Based on Java's `AnvilMenu::createResult`. The cost is the sum of both
+/// inputs' prior work penalty (`repair_cost`) plus one level per operation.
+/// Every time an item goes through the anvil its penalty grows to `2n + 1`.
+fn create_result(
+    input: &ItemStack,
+    addition: &ItemStack,
+    item_name: Option<&str>,
+    has_infinite_materials: bool,
+    state: &mut AnvilState,
+) -> ItemStack {
+    state.cost = 0;
+    state.repair_item_count_cost = 0;
+    state.only_renaming = false;
+
+    if input.is_empty() || !(input.has(ENCHANTMENTS) || input.has(STORED_ENCHANTMENTS)) {
+        return ItemStack::empty();
+    }
+
+    let mut result = input.clone();
+    let mut price = 0;
+    let tax = i64::from(input.get_or_default(REPAIR_COST, 0))
+        + i64::from(addition.get_or_default(REPAIR_COST, 0));
+    let mut naming_cost = 0;
+
+    if !addition.is_empty() {
+        let using_book = addition.has(STORED_ENCHANTMENTS);
+
+        // TODO: Repair with the item's repair material (e.g. iron ingots) once the
+        // `repairable` component is parsed. This also sets `repair_item_count_cost`.
+        if !using_book && (!result.is(addition.item()) || !result.is_damageable_item()) {
+            return ItemStack::empty();
+        }
+
+        if result.is_damageable_item() && !using_book {
+            let remaining = input.get_max_damage() - input.get_damage_value();
+            let additional = addition.get_max_damage() - addition.get_damage_value()
+                + result.get_max_damage() * 12 / 100;
+            let result_damage = (result.get_max_damage() - (remaining + additional)).max(0);
+            if result_damage < result.get_damage_value() {
+                result.set_damage_value(result_damage);
+                price += 2;
+            }
+        }
+
+        // TODO: Merge enchantments from the additional item or enchanted book once
+        // enchantments are stored on item stacks.
+    }
+
+    match item_name {
+        Some(name) if !name.trim().is_empty() => {
+            if hover_name(input).as_deref() != Some(name) {
+                naming_cost = 1;
+                price += naming_cost;
+                result.set(CUSTOM_NAME, TextComponent::plain(name.to_owned()));
+            }
+        }
+        _ => {
+            if input.has(CUSTOM_NAME) {
+                naming_cost = 1;
+                price += naming_cost;
+                result.remove(CUSTOM_NAME);
+            }
+        }
+    }
+
+    if price <= 0 {
+        return ItemStack::empty();
+    }
+    state.cost = (tax + i64::from(price)).clamp(0, i64::from(i32::MAX)) as i32;
+
+    // Renaming alone is never too expensive
+    if naming_cost == price && naming_cost > 0 {
+        state.cost = state.cost.min(MAX_COST - 1);
+        state.only_renaming = true;
+    }
+
+    if state.cost >= MAX_COST && !has_infinite_materials {
+        return ItemStack::empty();
+    }
+
+    let mut repair_cost = result
+        .get_or_default(REPAIR_COST, 0)
+        .max(addition.get_or_default(REPAIR_COST, 0));
+    if !state.only_renaming {
+        repair_cost = calculate_increased_repair_cost(repair_cost);
+    }
+    result.set(REPAIR_COST, repair_cost);
+
+    result
+}
+
+/// Returns the prior work penalty after one more anvil operation.
+///
+/// Based on Java's `AnvilMenu::calculateIncreasedRepairCost`.
+#[must_use]
+pub fn calculate_increased_repair_cost(cost: i32) -> i32 {
+    (i64::from(cost) * 2 + 1).min(i64::from(i32::MAX)) as i32
+}
+
+/// Returns the plain display name of an item, as the client shows it in the
+/// anvil's name field.
+fn hover_name(stack: &ItemStack) -> Option<String> {
+    let name = stack.get(CUSTOM_NAME).or_else(|| stack.get(ITEM_NAME))?;
+    match &name.content {
+        Content::Text { text } => Some(text.to_string()),
+        Content::Translate(message) => TRANSLATIONS
+            .get(&*message.key)
+            .map(|name| (*name).to_string()),
+        _ => None,
+    }
+}
+
+/// Strips formatting and control characters from a typed name.
+///
+/// Returns `None` if the name is too long, like Java's `AnvilMenu::validateName`.
+fn validate_name(name: &str) -> Option<String> {
+    let filtered: String = name
+        .chars()
+        .filter(|&c| c != '§' && c >= ' ' && c != '\u{7f}')
+        .collect();
+    (filtered.chars().count() <= MAX_NAME_LENGTH).then_some(filtered)
+}
+
+impl Menu for AnvilMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `ItemCombinerMenu::quickMoveStack`:
+    /// - Result slot (2) -> inventory (3-38), prefer existing stacks
+    /// - Input slots (0-1) -> inventory (3-38)
+    /// - Inventory (3-38) -> first free input slot
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        let moved = if slot_index == slots::RESULT_SLOT {
+            if !self.behavior.slots[slot_index].may_pickup() {
+                return ItemStack::empty();
+            }
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                true,
+            )
+        } else if slot_index < slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                false,
+            )
+        } else {
+            // Java: moveItemStackTo(stack, getSlotToQuickMoveTo(stack), resultSlot, false)
+            let first_empty = self.behavior.slots[slots::INPUT_SLOT]
+                .get_item(guard)
+                .is_empty()
+                && self.behavior.slots[slots::ADDITIONAL_SLOT]
+                    .get_item(guard)
+                    .is_empty();
+            let target = if first_empty {
+                slots::INPUT_SLOT
+            } else {
+                slots::ADDITIONAL_SLOT
+            };
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                target,
+                slots::RESULT_SLOT,
+                false,
+            )
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        if slot_index == slots::RESULT_SLOT
+            && let Some(remainder) =
+                self.behavior.slots[slot_index].on_take(guard, &clicked, player)
+        {
+            player.add_item_or_drop_with_guard(guard, remainder);
+        }
+
+        clicked
+    }
+
+    /// Prevents collecting items from the result slot.
+    fn can_take_item_for_pick_all(&self, _carried: &ItemStack, slot_index: usize) -> bool {
+        slot_index != slots::RESULT_SLOT
+    }
+
+    /// Called when the anvil menu is closed.
+    /// Returns the input items to the player's inventory.
+    ///
+    /// Based on Java's `ItemCombinerMenu::removed`.
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.add_item_or_drop(carried);
+        }
+
+        let input_items: Vec<ItemStack> = {
+            let mut inputs = self.input_container.lock();
+            (0..inputs.get_container_size())
+                .map(|i| inputs.remove_item_no_update(i))
+                .filter(|item| !item.is_empty())
+                .collect()
+        };
+
+        for item in input_items {
+            player.add_item_or_drop(item);
+        }
+
+        self.result_container.lock().set_item(0, ItemStack::empty());
+    }
+
+    fn slots_changed(&mut self, player: &Player) {
+        self.update_result(player.has_infinite_materials());
+    }
+
+    /// Based on Java's `AnvilMenu::setItemName`.
+    fn set_item_name(&mut self, name: &str, player: &Player) {
+        let Some(name) = validate_name(name) else {
+            return;
+        };
+        if self.item_name.as_deref() == Some(name.as_str()) {
+            return;
+        }
+        self.item_name = Some(name);
+        self.update_result(player.has_infinite_materials());
+    }
+}
+
+impl MenuInstance for AnvilMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::ANVIL
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating an anvil menu.
+pub struct AnvilMenuProvider {
+    inventory: SyncPlayerInv,
+    pos: BlockPos,
+}
+
+impl AnvilMenuProvider {
+    /// Creates a new anvil menu provider.
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, pos: BlockPos) -> Self {
+        Self { inventory, pos }
+    }
+}
+
+impl MenuProvider for AnvilMenuProvider {
+    fn title(&self) -> TextComponent {
+        TextComponent::translated(translations::CONTAINER_REPAIR.msg())
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(AnvilMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.pos,
+        ))
+    }
+}
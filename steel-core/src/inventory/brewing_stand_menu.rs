@@ -0,0 +1,302 @@
+//! The brewing stand menu.
+//!
+//! Slot layout (41 total):
+//! - Slots 0-2: Bottles
+//! - Slot 3: Ingredient
+//! - Slot 4: Fuel
+//! - Slots 5-31: Main inventory (27 slots)
+//! - Slots 32-40: Hotbar (9 slots)
+
+use steel_registry::REGISTRY;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::vanilla_menu_types;
+use text_components::TextComponent;
+
+use crate::block_entity::SharedBlockEntity;
+use crate::block_entity::entities::{
+    BOTTLE_SLOTS, BrewingStandBlockEntity, SLOT_BREWING_FUEL, SLOT_INGREDIENT, is_bottle,
+    is_brewing_fuel,
+};
+use crate::inventory::{
+    SyncPlayerInv,
+    lock::{ContainerLockGuard, ContainerRef},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{
+        BrewingFuelSlot, BrewingIngredientSlot, PotionSlot, Slot, SlotType,
+        add_standard_inventory_slots,
+    },
+};
+use crate::player::Player;
+
+/// Slot indices for the brewing stand menu.
+pub mod slots {
+    /// Start of the bottle slots (slot 0).
+    pub const BOTTLE_SLOT_START: usize = 0;
+    /// End of the bottle slots (slot 3, exclusive).
+    pub const BOTTLE_SLOT_END: usize = 3;
+    /// Slot index for the ingredient (slot 3).
+    pub const INGREDIENT_SLOT: usize = 3;
+    /// Slot index for the fuel (slot 4).
+    pub const FUEL_SLOT: usize = 4;
+    /// Start of main inventory (slot 5).
+    pub const INV_SLOT_START: usize = 5;
+    /// End of main inventory (slot 32, exclusive).
+    pub const INV_SLOT_END: usize = 32;
+    /// Start of hotbar (slot 32).
+    pub const HOTBAR_SLOT_START: usize = 32;
+    /// End of hotbar (slot 41, exclusive).
+    pub const HOTBAR_SLOT_END: usize = 41;
+}
+
+/// Number of data slots the brewing stand menu syncs: remaining brew time
+/// and fuel.
+pub const DATA_COUNT: usize = 2;
+
+/// The menu of a brewing stand.
+///
+/// Based on Java's `BrewingStandMenu`.
+pub struct BrewingStandMenu {
+    behavior: MenuBehavior,
+    /// The brewing stand block entity, read for the progress shown by the client.
+    block_entity: SharedBlockEntity,
+    /// The brewing stand as a container.
+    container: ContainerRef,
+}
+
+impl BrewingStandMenu {
+    /// Creates a new brewing stand menu for the brewing stand `block_entity`.
+    ///
+    /// # Panics
+    /// Panics if `block_entity` is not a container.
+    #[must_use]
+    pub fn new(
+        inventory: SyncPlayerInv,
+        container_id: u8,
+        block_entity: SharedBlockEntity,
+    ) -> Self {
+        let container = ContainerRef::from_block_entity(block_entity.clone())
+            .expect("brewing stands are containers");
+
+        let mut menu_slots = Vec::with_capacity(slots::HOTBAR_SLOT_END);
+        for index in 0..BOTTLE_SLOTS {
+            menu_slots.push(SlotType::Potion(PotionSlot::new(container.clone(), index)));
+        }
+        menu_slots.push(SlotType::BrewingIngredient(BrewingIngredientSlot::new(
+            container.clone(),
+            SLOT_INGREDIENT,
+        )));
+        menu_slots.push(SlotType::BrewingFuel(BrewingFuelSlot::new(
+            container.clone(),
+            SLOT_BREWING_FUEL,
+        )));
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        let mut behavior = MenuBehavior::new(
+            menu_slots,
+            container_id,
+            Some(vanilla_menu_types::BREWING_STAND),
+        );
+        behavior.add_data_slots(DATA_COUNT);
+
+        let mut menu = Self {
+            behavior,
+            block_entity,
+            container,
+        };
+        menu.update_data_slots();
+        menu
+    }
+
+    /// Returns a reference to the brewing stand's container.
+    #[must_use]
+    pub fn container(&self) -> &ContainerRef {
+        &self.container
+    }
+}
+
+impl Menu for BrewingStandMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `BrewingStandMenu::quickMoveStack`:
+    /// - Brewing stand slots (0-4) -> inventory (5-41), prefer the hotbar
+    /// - Inventory (5-41) -> fuel, ingredient or bottle slots if the item
+    ///   fits there, otherwise between main inventory and hotbar
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        let moved = if slot_index < slots::INV_SLOT_START {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                true,
+            )
+        } else if is_brewing_fuel(&stack_mut) {
+            // Blaze powder is also an ingredient, so it overflows into the
+            // ingredient slot
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::FUEL_SLOT,
+                slots::FUEL_SLOT + 1,
+                false,
+            ) || (REGISTRY.brewing.is_ingredient(&stack_mut)
+                && self.behavior.move_item_stack_to(
+                    guard,
+                    &mut stack_mut,
+                    slots::INGREDIENT_SLOT,
+                    slots::INGREDIENT_SLOT + 1,
+                    false,
+                ))
+        } else if REGISTRY.brewing.is_ingredient(&stack_mut) {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INGREDIENT_SLOT,
+                slots::INGREDIENT_SLOT + 1,
+                false,
+            )
+        } else if is_bottle(&stack_mut) {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::BOTTLE_SLOT_START,
+                slots::BOTTLE_SLOT_END,
+                false,
+            )
+        } else if slot_index < slots::HOTBAR_SLOT_START {
+            // Main inventory -> hotbar
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::HOTBAR_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                false,
+            )
+        } else {
+            // Hotbar -> main inventory
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::INV_SLOT_END,
+                false,
+            )
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        // Update the source slot with the remaining items
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+
+        // Check if unchanged
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_changed(guard);
+        self.behavior.slots[slot_index].on_take(guard, &clicked, player);
+
+        clicked
+    }
+
+    /// Returns true while the brewing stand block entity is still in the world.
+    fn still_valid(&self) -> bool {
+        !self.block_entity.lock().is_removed()
+    }
+
+    /// Copies the brewing stand's brew time and fuel into the data slots.
+    fn update_data_slots(&mut self) {
+        let data = {
+            let guard = self.block_entity.lock();
+            let Some(brewing_stand) = guard.as_any().downcast_ref::<BrewingStandBlockEntity>()
+            else {
+                return;
+            };
+            brewing_stand.data()
+        };
+        for (index, value) in data.into_iter().enumerate() {
+            self.behavior.set_data(index, value as i16);
+        }
+    }
+}
+
+impl MenuInstance for BrewingStandMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::BREWING_STAND
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating brewing stand menus.
+pub struct BrewingStandMenuProvider {
+    inventory: SyncPlayerInv,
+    block_entity: SharedBlockEntity,
+    title: TextComponent,
+}
+
+impl BrewingStandMenuProvider {
+    /// Creates a new brewing stand menu provider.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `block_entity` - The brewing stand block entity
+    /// * `title` - Display title for the menu
+    #[must_use]
+    pub fn new(
+        inventory: SyncPlayerInv,
+        block_entity: SharedBlockEntity,
+        title: TextComponent,
+    ) -> Self {
+        Self {
+            inventory,
+            block_entity,
+            title,
+        }
+    }
+}
+
+impl MenuProvider for BrewingStandMenuProvider {
+    fn title(&self) -> TextComponent {
+        self.title.clone()
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(BrewingStandMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.block_entity.clone(),
+        ))
+    }
+}
@@ -0,0 +1,352 @@
+//! The cartography table menu for zooming out, copying and locking maps.
+//!
+//! Slot layout (39 total):
+//! - Slot 0: Map
+//! - Slot 1: Additional item (paper, empty map or glass pane)
+//! - Slot 2: Result
+//! - Slots 3-29: Main inventory (27 slots)
+//! - Slots 30-38: Hotbar (9 slots)
+
+use std::{mem, sync::Arc};
+
+use steel_registry::data_components::vanilla_components::{
+    MAP_ID, MAP_POST_PROCESSING, MapPostProcessing,
+};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::vanilla_items::ITEMS;
+use steel_registry::vanilla_menu_types;
+use steel_utils::locks::SyncMutex;
+use steel_utils::{BlockPos, translations};
+use text_components::TextComponent;
+
+use crate::inventory::{
+    SyncPlayerInv,
+    container::{Container, SimpleContainer},
+    crafting::ResultContainer,
+    lock::{ContainerId, ContainerLockGuard, ContainerRef, GenericContainer},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{
+        CartographyAdditionalSlot, CartographyMapSlot, CartographyResultSlot, Slot, SlotType,
+        SyncResultContainer, add_standard_inventory_slots,
+    },
+};
+use crate::player::Player;
+use crate::world::map_data::{self, MAP_ITEM_DATA, MAX_SCALE};
+
+/// Slot indices for the cartography table menu.
+pub mod slots {
+    /// Slot index for the map (slot 0).
+    pub const MAP_SLOT: usize = 0;
+    /// Slot index for the additional item (slot 1).
+    pub const ADDITIONAL_SLOT: usize = 1;
+    /// Slot index for the result (slot 2).
+    pub const RESULT_SLOT: usize = 2;
+    /// Start of main inventory (slot 3).
+    pub const INV_SLOT_START: usize = 3;
+    /// End of main inventory (slot 30, exclusive).
+    pub const INV_SLOT_END: usize = 30;
+    /// Start of hotbar (slot 30).
+    pub const HOTBAR_SLOT_START: usize = 30;
+    /// End of hotbar (slot 39, exclusive).
+    pub const HOTBAR_SLOT_END: usize = 39;
+    /// Total number of slots in the cartography table menu.
+    pub const TOTAL_SLOTS: usize = 39;
+}
+
+/// Returns true if the item goes in the additional slot: paper to zoom out,
+/// an empty map to copy or a glass pane to lock.
+#[must_use]
+pub fn is_additional_item(stack: &ItemStack) -> bool {
+    stack.is(&ITEMS.paper) || stack.is(&ITEMS.map) || stack.is(&ITEMS.glass_pane)
+}
+
+/// Works out the result for a map and an additional item.
+///
+/// Based on Java's `CartographyTableMenu::setupResultSlot`.
+fn create_result(map: &ItemStack, additional: &ItemStack) -> ItemStack {
+    let Some(id) = map_data::map_id(map) else {
+        return ItemStack::empty();
+    };
+    let Some((locked, scale)) = MAP_ITEM_DATA.with_map(id, |data| (data.locked, data.scale)) else {
+        return ItemStack::empty();
+    };
+
+    let mut result = map.clone();
+    if additional.is(&ITEMS.paper) && !locked && scale < MAX_SCALE {
+        result.set_count(1);
+        result.set(MAP_POST_PROCESSING, MapPostProcessing::Scale);
+    } else if additional.is(&ITEMS.glass_pane) && !locked {
+        result.set_count(1);
+        result.set(MAP_POST_PROCESSING, MapPostProcessing::Lock);
+    } else if additional.is(&ITEMS.map) {
+        result.set_count(2);
+    } else {
+        return ItemStack::empty();
+    }
+    result
+}
+
+/// The cartography table menu.
+///
+/// Based on Java's `CartographyTableMenu`.
+pub struct CartographyTableMenu {
+    behavior: MenuBehavior,
+    /// The map and additional item slots.
+    input_slots: GenericContainer,
+    /// The result slot.
+    result_slots: SyncResultContainer,
+    /// The position of the cartography table block.
+    block_pos: BlockPos,
+}
+
+impl CartographyTableMenu {
+    /// Creates a new cartography table menu for a player.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `block_pos` - The position of the cartography table block
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, container_id: u8, block_pos: BlockPos) -> Self {
+        let mut menu_slots = Vec::with_capacity(slots::TOTAL_SLOTS);
+
+        let input_slots: GenericContainer = Arc::new(SyncMutex::new(SimpleContainer::new(2)));
+        let result_slots: SyncResultContainer = Arc::new(SyncMutex::new(ResultContainer::new()));
+
+        // Slot 0: Map
+        menu_slots.push(SlotType::CartographyMap(CartographyMapSlot::new(
+            ContainerRef::Other(input_slots.clone()),
+            slots::MAP_SLOT,
+        )));
+
+        // Slot 1: Additional item
+        menu_slots.push(SlotType::CartographyAdditional(
+            CartographyAdditionalSlot::new(
+                ContainerRef::Other(input_slots.clone()),
+                slots::ADDITIONAL_SLOT,
+            ),
+        ));
+
+        // Slot 2: Result
+        menu_slots.push(SlotType::CartographyResult(CartographyResultSlot::new(
+            result_slots.clone(),
+            input_slots.clone(),
+            block_pos,
+        )));
+
+        // Slots 3-38: Standard inventory (main inventory + hotbar)
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        Self {
+            behavior: MenuBehavior::new(
+                menu_slots,
+                container_id,
+                Some(vanilla_menu_types::CARTOGRAPHY_TABLE),
+            ),
+            input_slots,
+            result_slots,
+            block_pos,
+        }
+    }
+
+    /// Returns the menu type for the cartography table.
+    #[must_use]
+    pub fn menu_type() -> MenuTypeRef {
+        vanilla_menu_types::CARTOGRAPHY_TABLE
+    }
+
+    /// Returns the position of the cartography table block.
+    #[must_use]
+    pub fn block_pos(&self) -> BlockPos {
+        self.block_pos
+    }
+}
+
+impl Menu for CartographyTableMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `CartographyTableMenu::quickMoveStack`:
+    /// - Result slot (2) -> inventory (3-38), prefer existing stacks
+    /// - Input slots (0-1) -> inventory (3-38)
+    /// - Inventory (3-38) -> the map or additional slot if it fits one,
+    ///   otherwise between main inventory and hotbar
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let mut stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        if slot_index == slots::RESULT_SLOT {
+            map_data::apply_post_processing(&mut stack);
+        }
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        let moved = if slot_index == slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                true,
+            )
+        } else if slot_index == slots::MAP_SLOT || slot_index == slots::ADDITIONAL_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                false,
+            )
+        } else {
+            let (start, end) = if stack_mut.has(MAP_ID) {
+                (slots::MAP_SLOT, slots::MAP_SLOT + 1)
+            } else if is_additional_item(&stack_mut) {
+                (slots::ADDITIONAL_SLOT, slots::ADDITIONAL_SLOT + 1)
+            } else if slot_index < slots::INV_SLOT_END {
+                (slots::HOTBAR_SLOT_START, slots::HOTBAR_SLOT_END)
+            } else {
+                (slots::INV_SLOT_START, slots::INV_SLOT_END)
+            };
+            self.behavior
+                .move_item_stack_to(guard, &mut stack_mut, start, end, false)
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        if slot_index == slots::RESULT_SLOT
+            && let Some(remainder) =
+                self.behavior.slots[slot_index].on_take(guard, &clicked, player)
+        {
+            player.add_item_or_drop_with_guard(guard, remainder);
+        }
+
+        clicked
+    }
+
+    /// Prevents collecting items from the result slot.
+    fn can_take_item_for_pick_all(&self, _carried: &ItemStack, slot_index: usize) -> bool {
+        slot_index != slots::RESULT_SLOT
+    }
+
+    /// Called when the cartography table menu is closed.
+    /// Returns the input items to the player's inventory.
+    ///
+    /// Based on Java's `CartographyTableMenu::removed`.
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.add_item_or_drop(carried);
+        }
+
+        let mut inputs = self.input_slots.lock();
+        for slot in [slots::MAP_SLOT, slots::ADDITIONAL_SLOT] {
+            let item = inputs.remove_item_no_update(slot);
+            if !item.is_empty() {
+                player.add_item_or_drop(item);
+            }
+        }
+        drop(inputs);
+
+        self.result_slots.lock().set_item(0, ItemStack::empty());
+    }
+
+    /// Updates the result when the map or additional item changes.
+    ///
+    /// Based on Java's `CartographyTableMenu::slotsChanged`.
+    fn slots_changed(&mut self, _player: &Player) {
+        let mut guard = self.behavior.lock_all_containers();
+        let inputs = guard
+            .get(ContainerId::from_arc(&self.input_slots))
+            .expect("input container not locked");
+        let result = create_result(
+            inputs.get_item(slots::MAP_SLOT),
+            inputs.get_item(slots::ADDITIONAL_SLOT),
+        );
+        guard
+            .get_result_container_mut(ContainerId::from_arc(&self.result_slots))
+            .expect("result container not locked")
+            .set_item(0, result);
+    }
+}
+
+impl MenuInstance for CartographyTableMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::CARTOGRAPHY_TABLE
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating a cartography table menu.
+pub struct CartographyTableMenuProvider {
+    inventory: SyncPlayerInv,
+    pos: BlockPos,
+}
+
+impl CartographyTableMenuProvider {
+    /// Creates a new cartography table menu provider.
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, pos: BlockPos) -> Self {
+        Self { inventory, pos }
+    }
+}
+
+impl MenuProvider for CartographyTableMenuProvider {
+    fn title(&self) -> TextComponent {
+        TextComponent::translated(translations::CONTAINER_CARTOGRAPHY_TABLE.msg())
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(CartographyTableMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.pos,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_paper_maps_and_glass_panes_are_additional_items() {
+        assert!(is_additional_item(&ItemStack::new(&ITEMS.paper)));
+        assert!(is_additional_item(&ItemStack::new(&ITEMS.map)));
+        assert!(is_additional_item(&ItemStack::new(&ITEMS.glass_pane)));
+        assert!(!is_additional_item(&ItemStack::new(&ITEMS.glass)));
+        assert!(!is_additional_item(&ItemStack::new(&ITEMS.filled_map)));
+    }
+}
@@ -75,6 +75,8 @@ pub struct ChestMenu {
     behavior: MenuBehavior,
     /// Reference to the container (chest, barrel, etc.).
     container: ContainerRef,
+    /// The second half of a double chest, shown as rows 4-6.
+    second_container: Option<ContainerRef>,
     /// Number of rows in the container (1-6).
     rows: usize,
 }
@@ -121,10 +123,46 @@ impl ChestMenu {
                 Some(Self::menu_type_for_rows(rows)),
             ),
             container,
+            second_container: None,
             rows,
         }
     }
 
+    /// Creates a 6-row menu over two 3-row containers (a double chest).
+    ///
+    /// `first` fills the top three rows and `second` the bottom three, like
+    /// Java's `CompoundContainer`.
+    #[must_use]
+    pub fn double(
+        inventory: SyncPlayerInv,
+        container_id: u8,
+        first: ContainerRef,
+        second: ContainerRef,
+    ) -> Self {
+        let half_slots = slots::container_slot_count(3);
+        let mut menu_slots = Vec::with_capacity(slots::total_slots(6));
+
+        for i in 0..half_slots {
+            menu_slots.push(SlotType::Normal(NormalSlot::new(first.clone(), i)));
+        }
+        for i in 0..half_slots {
+            menu_slots.push(SlotType::Normal(NormalSlot::new(second.clone(), i)));
+        }
+
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        Self {
+            behavior: MenuBehavior::new(
+                menu_slots,
+                container_id,
+                Some(Self::menu_type_for_rows(6)),
+            ),
+            container: first,
+            second_container: Some(second),
+            rows: 6,
+        }
+    }
+
     /// Creates a 1-row chest menu.
     #[must_use]
     pub fn one_row(inventory: SyncPlayerInv, container_id: u8, container: ContainerRef) -> Self {
@@ -189,6 +227,12 @@ impl ChestMenu {
     pub fn container(&self) -> &ContainerRef {
         &self.container
     }
+
+    /// Returns the second half of a double chest, if this is one.
+    #[must_use]
+    pub fn second_container(&self) -> Option<&ContainerRef> {
+        self.second_container.as_ref()
+    }
 }
 
 impl Menu for ChestMenu {
@@ -263,12 +307,18 @@ impl Menu for ChestMenu {
 
     /// Returns true if the container is still valid for interaction.
     ///
-    /// Delegates to the container's `still_valid` method.
+    /// Delegates to the container's `still_valid` method. Both halves of a
+    /// double chest must still be valid.
     fn still_valid(&self) -> bool {
         let guard = self.behavior.lock_all_containers();
-        guard
-            .get(self.container.container_id())
-            .is_some_and(super::container::Container::still_valid)
+        [&self.container]
+            .into_iter()
+            .chain(&self.second_container)
+            .all(|container| {
+                guard
+                    .get(container.container_id())
+                    .is_some_and(super::container::Container::still_valid)
+            })
     }
 
     /// Called when the menu is closed.
@@ -298,6 +348,7 @@ impl MenuInstance for ChestMenu {
 pub struct ChestMenuProvider {
     inventory: SyncPlayerInv,
     container: ContainerRef,
+    second_container: Option<ContainerRef>,
     rows: usize,
     title: TextComponent,
 }
@@ -320,6 +371,7 @@ impl ChestMenuProvider {
         Self {
             inventory,
             container,
+            second_container: None,
             rows,
             title,
         }
@@ -344,6 +396,23 @@ impl ChestMenuProvider {
     ) -> Self {
         Self::new(inventory, container, 6, title)
     }
+
+    /// Creates a provider for a double chest made of two 3-row containers.
+    #[must_use]
+    pub fn double(
+        inventory: SyncPlayerInv,
+        first: ContainerRef,
+        second: ContainerRef,
+        title: TextComponent,
+    ) -> Self {
+        Self {
+            inventory,
+            container: first,
+            second_container: Some(second),
+            rows: 6,
+            title,
+        }
+    }
 }
 
 impl MenuProvider for ChestMenuProvider {
@@ -352,6 +421,15 @@ impl MenuProvider for ChestMenuProvider {
     }
 
     fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        if let Some(second) = &self.second_container {
+            return Box::new(ChestMenu::double(
+                self.inventory.clone(),
+                container_id,
+                self.container.clone(),
+                second.clone(),
+            ));
+        }
+
         Box::new(ChestMenu::new(
             self.inventory.clone(),
             container_id,
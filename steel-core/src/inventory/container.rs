@@ -164,6 +164,89 @@ pub trait Container {
 
         stack.is_empty()
     }
+
+    /// Moves a single item from `slot` into `target`.
+    ///
+    /// The item goes into the first slot of `target` that accepts it, either an
+    /// empty slot or one holding the same item with room left.
+    /// Returns true if an item was moved.
+    ///
+    /// Based on Java's `HopperBlockEntity.addItem`, used by hoppers and anything
+    /// else that moves items between containers one at a time.
+    fn transfer_item(&mut self, slot: usize, target: &mut dyn Container) -> bool {
+        let item = self.get_item(slot);
+        if item.is_empty() || !self.can_take_item(slot, item) {
+            return false;
+        }
+        let mut single = item.clone();
+        single.set_count(1);
+
+        for target_slot in 0..target.get_container_size() {
+            if !target.can_place_item(target_slot, &single) {
+                continue;
+            }
+
+            let existing = target.get_item(target_slot);
+            if existing.is_empty() {
+                target.set_item(target_slot, single);
+            } else if ItemStack::is_same_item_same_components(existing, &single)
+                && existing.count() < target.get_max_stack_size_for_item(existing)
+            {
+                target.get_item_mut(target_slot).grow(1);
+            } else {
+                continue;
+            }
+
+            self.get_item_mut(slot).shrink(1);
+            self.set_changed();
+            target.set_changed();
+            return true;
+        }
+
+        false
+    }
+}
+
+/// A plain fixed-size container that isn't backed by a block or entity.
+///
+/// Used for temporary menu inputs such as the anvil's two input slots.
+/// Based on Java's `SimpleContainer`.
+pub struct SimpleContainer {
+    items: Vec<ItemStack>,
+}
+
+impl SimpleContainer {
+    /// Creates a new empty container with `size` slots.
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        Self {
+            items: vec![ItemStack::empty(); size],
+        }
+    }
+}
+
+impl Container for SimpleContainer {
+    fn get_container_size(&self) -> usize {
+        self.items.len()
+    }
+
+    fn get_item(&self, slot: usize) -> &ItemStack {
+        &self.items[slot]
+    }
+
+    fn get_item_mut(&mut self, slot: usize) -> &mut ItemStack {
+        &mut self.items[slot]
+    }
+
+    fn set_item(&mut self, slot: usize, stack: ItemStack) {
+        if slot < self.items.len() {
+            self.items[slot] = stack;
+        }
+    }
+
+    fn set_changed(&mut self) {
+        // The owning menu recalculates its outputs in `Menu::slots_changed`.
+    }
 }
 
 /// Calculates the redstone comparator signal strength (0-15) from a container.
@@ -0,0 +1,439 @@
+//! The enchanting table menu.
+//!
+//! Slot layout (38 total):
+//! - Slot 0: Item to enchant
+//! - Slot 1: Lapis lazuli
+//! - Slots 2-28: Main inventory (27 slots)
+//! - Slots 29-37: Hotbar (9 slots)
+
+use std::{mem, sync::Arc};
+
+use steel_registry::enchantment::{EnchantmentInstance, get_enchantment_cost, select_enchantment};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::vanilla_items::ITEMS;
+use steel_registry::{REGISTRY, sound_events, vanilla_menu_types};
+use steel_utils::locks::SyncMutex;
+use steel_utils::random::{Random, legacy_random::LegacyRandom};
+use steel_utils::{BlockPos, translations};
+use text_components::TextComponent;
+
+use crate::block_entity::entities::count_bookshelves;
+use crate::inventory::{
+    SyncPlayerInv,
+    container::{Container, SimpleContainer},
+    lock::{ContainerId, ContainerLockGuard, ContainerRef, GenericContainer},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{EnchantingItemSlot, LapisSlot, Slot, SlotType, add_standard_inventory_slots},
+};
+use crate::player::Player;
+use crate::player::stats::{CustomStat, StatKey};
+
+/// Slot indices for the enchanting table menu.
+pub mod slots {
+    /// Slot index for the item being enchanted (slot 0).
+    pub const ITEM_SLOT: usize = 0;
+    /// Slot index for the lapis lazuli (slot 1).
+    pub const LAPIS_SLOT: usize = 1;
+    /// Start of main inventory (slot 2).
+    pub const INV_SLOT_START: usize = 2;
+    /// End of main inventory (slot 29, exclusive).
+    pub const INV_SLOT_END: usize = 29;
+    /// Start of hotbar (slot 29).
+    pub const HOTBAR_SLOT_START: usize = 29;
+    /// End of hotbar (slot 38, exclusive).
+    pub const HOTBAR_SLOT_END: usize = 38;
+    /// Total number of slots in the enchanting table menu.
+    pub const TOTAL_SLOTS: usize = 38;
+}
+
+/// The number of enchantment options the table offers.
+const OPTIONS: usize = 3;
+
+/// The enchanting table menu.
+///
+/// Based on Java's `EnchantmentMenu`.
+pub struct EnchantmentMenu {
+    behavior: MenuBehavior,
+    /// The item and lapis slots.
+    enchant_slots: GenericContainer,
+    /// The position of the enchanting table block.
+    block_pos: BlockPos,
+    /// The level cost of each option, or 0 if the option is unavailable.
+    costs: [i32; OPTIONS],
+    /// The registry id of one enchantment each option applies, shown as a
+    /// hint, or -1 if there is none.
+    enchant_clue: [i32; OPTIONS],
+    /// The level of the hinted enchantment, or -1 if there is none.
+    level_clue: [i32; OPTIONS],
+    /// Index of the first data slot: the costs, then the seed, then the
+    /// enchantment hints, then their levels.
+    data_start: usize,
+}
+
+impl EnchantmentMenu {
+    /// Creates a new enchanting table menu for a player.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `block_pos` - The position of the enchanting table block
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, container_id: u8, block_pos: BlockPos) -> Self {
+        let mut menu_slots = Vec::with_capacity(slots::TOTAL_SLOTS);
+
+        let enchant_slots: GenericContainer = Arc::new(SyncMutex::new(SimpleContainer::new(2)));
+
+        // Slot 0: Item to enchant
+        menu_slots.push(SlotType::EnchantingItem(EnchantingItemSlot::new(
+            ContainerRef::Other(enchant_slots.clone()),
+            slots::ITEM_SLOT,
+        )));
+
+        // Slot 1: Lapis
+        menu_slots.push(SlotType::Lapis(LapisSlot::new(
+            ContainerRef::Other(enchant_slots.clone()),
+            slots::LAPIS_SLOT,
+        )));
+
+        // Slots 2-37: Standard inventory (main inventory + hotbar)
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        let mut behavior = MenuBehavior::new(
+            menu_slots,
+            container_id,
+            Some(vanilla_menu_types::ENCHANTMENT),
+        );
+        let data_start = behavior.add_data_slots(OPTIONS * 3 + 1);
+
+        let mut menu = Self {
+            behavior,
+            enchant_slots,
+            block_pos,
+            costs: [0; OPTIONS],
+            enchant_clue: [-1; OPTIONS],
+            level_clue: [-1; OPTIONS],
+            data_start,
+        };
+        menu.sync_data(0);
+        menu
+    }
+
+    /// Returns the menu type for the enchanting table.
+    #[must_use]
+    pub fn menu_type() -> MenuTypeRef {
+        vanilla_menu_types::ENCHANTMENT
+    }
+
+    /// Returns the position of the enchanting table block.
+    #[must_use]
+    pub fn block_pos(&self) -> BlockPos {
+        self.block_pos
+    }
+
+    /// Returns the item and lapis currently in the menu.
+    fn inputs(&self) -> (ItemStack, ItemStack) {
+        let guard = self.behavior.lock_all_containers();
+        let inputs = guard
+            .get(ContainerId::from_arc(&self.enchant_slots))
+            .expect("enchant container not locked");
+        (
+            inputs.get_item(slots::ITEM_SLOT).clone(),
+            inputs.get_item(slots::LAPIS_SLOT).clone(),
+        )
+    }
+
+    /// Copies the costs and hints into the data slots the client reads.
+    fn sync_data(&mut self, seed: i32) {
+        for i in 0..OPTIONS {
+            self.behavior
+                .set_data(self.data_start + i, self.costs[i] as i16);
+            self.behavior.set_data(
+                self.data_start + OPTIONS + 1 + i,
+                self.enchant_clue[i] as i16,
+            );
+            self.behavior.set_data(
+                self.data_start + OPTIONS * 2 + 1 + i,
+                self.level_clue[i] as i16,
+            );
+        }
+        // Only the low bits of the seed pick the runes the client shows
+        self.behavior
+            .set_data(self.data_start + OPTIONS, (seed & -16) as i16);
+    }
+
+    /// Recomputes the options from the item and the surrounding bookshelves.
+    ///
+    /// Based on Java's `EnchantmentMenu::slotsChanged`.
+    fn update_options(&mut self, player: &Player) {
+        let (item, _) = self.inputs();
+        let seed = player.enchantment_seed();
+
+        self.costs = [0; OPTIONS];
+        self.enchant_clue = [-1; OPTIONS];
+        self.level_clue = [-1; OPTIONS];
+
+        if !item.is_empty() && item.is_enchantable() {
+            let bookshelves = count_bookshelves(&player.world(), self.block_pos);
+            let enchantability = item.get_enchantability();
+            let mut random = LegacyRandom::from_seed(i64::from(seed) as u64);
+            for (slot, cost) in self.costs.iter_mut().enumerate() {
+                *cost = get_enchantment_cost(&mut random, slot as i32, bookshelves, enchantability);
+                if *cost < slot as i32 + 1 {
+                    *cost = 0;
+                }
+            }
+
+            for slot in 0..OPTIONS {
+                if self.costs[slot] <= 0 {
+                    continue;
+                }
+                let list = get_enchantment_list(seed, &item, slot, self.costs[slot]);
+                if !list.is_empty() {
+                    let hint = list[random.next_i32_bounded(list.len() as i32) as usize];
+                    self.enchant_clue[slot] =
+                        *REGISTRY.enchantments.get_id(hint.enchantment) as i32;
+                    self.level_clue[slot] = hint.level;
+                }
+            }
+        }
+
+        self.sync_data(seed);
+    }
+}
+
+/// Rolls the enchantments option `slot` applies to `item` for `cost` levels.
+/// The same seed always rolls the same enchantments, so the hint shown
+/// matches what the player gets.
+///
+/// Based on Java's `EnchantmentMenu::getEnchantmentList`.
+fn get_enchantment_list(
+    seed: i32,
+    item: &ItemStack,
+    slot: usize,
+    cost: i32,
+) -> Vec<EnchantmentInstance> {
+    let mut random = LegacyRandom::from_seed(i64::from(seed.wrapping_add(slot as i32)) as u64);
+    let candidates = REGISTRY.enchantments.in_enchanting_table();
+    let mut list = select_enchantment(&mut random, item, cost, &candidates);
+    // Books lose one of their enchantments to make up for taking any
+    if item.is(&ITEMS.book) && list.len() > 1 {
+        list.remove(random.next_i32_bounded(list.len() as i32) as usize);
+    }
+    list
+}
+
+impl Menu for EnchantmentMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `EnchantmentMenu::quickMoveStack`:
+    /// - Item and lapis slots (0-1) -> inventory (2-37)
+    /// - Lapis from inventory -> lapis slot (1)
+    /// - Anything else from inventory -> a single item into the item slot (0)
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        _player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        if slot_index <= slots::LAPIS_SLOT {
+            if !self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                true,
+            ) {
+                return ItemStack::empty();
+            }
+        } else if stack_mut.is(&ITEMS.lapis_lazuli) {
+            if !self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::LAPIS_SLOT,
+                slots::LAPIS_SLOT + 1,
+                true,
+            ) {
+                return ItemStack::empty();
+            }
+        } else {
+            let item_slot = &self.behavior.slots[slots::ITEM_SLOT];
+            if !item_slot.get_item(guard).is_empty() || !item_slot.may_place(&stack_mut) {
+                return ItemStack::empty();
+            }
+            let mut single = stack_mut.clone();
+            single.set_count(1);
+            stack_mut.shrink(1);
+            item_slot.set_item(guard, single);
+        }
+
+        if stack_mut.is_empty() {
+            self.behavior.slots[slot_index].set_item(guard, ItemStack::empty());
+        } else {
+            self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+            self.behavior.slots[slot_index].set_changed(guard);
+        }
+
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+
+        clicked
+    }
+
+    /// Called when the enchanting table menu is closed.
+    /// Returns the item and lapis to the player's inventory.
+    ///
+    /// Based on Java's `EnchantmentMenu::removed`.
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.add_item_or_drop(carried);
+        }
+
+        let input_items: Vec<ItemStack> = {
+            let mut inputs = self.enchant_slots.lock();
+            (0..inputs.get_container_size())
+                .map(|i| inputs.remove_item_no_update(i))
+                .filter(|item| !item.is_empty())
+                .collect()
+        };
+
+        for item in input_items {
+            player.add_item_or_drop(item);
+        }
+    }
+
+    fn slots_changed(&mut self, player: &Player) {
+        self.update_options(player);
+    }
+
+    /// Enchants the item with one of the three options.
+    ///
+    /// Based on Java's `EnchantmentMenu::clickMenuButton`.
+    fn click_menu_button(&mut self, button_id: i32, player: &Player) -> bool {
+        let Ok(option) = usize::try_from(button_id) else {
+            return false;
+        };
+        if option >= OPTIONS {
+            return false;
+        }
+
+        let (item, lapis) = self.inputs();
+        let infinite = player.has_infinite_materials();
+        let needed = button_id + 1;
+        if (lapis.is_empty() || lapis.count < needed) && !infinite {
+            return false;
+        }
+
+        let cost = self.costs[option];
+        let level = player.experience().lock().level();
+        if cost <= 0 || item.is_empty() || (!infinite && (level < needed || level < cost)) {
+            return false;
+        }
+
+        let list = get_enchantment_list(player.enchantment_seed(), &item, option, cost);
+        if list.is_empty() {
+            return false;
+        }
+
+        player.on_enchantment_performed(needed);
+
+        let mut result = if item.is(&ITEMS.book) {
+            ItemStack::with_count_and_patch(&ITEMS.enchanted_book, item.count, item.patch().clone())
+        } else {
+            item
+        };
+        for instance in &list {
+            result.enchant(instance.enchantment.key.clone(), instance.level);
+        }
+
+        {
+            let mut guard = self.behavior.lock_all_containers();
+            let inputs = guard
+                .get_mut(ContainerId::from_arc(&self.enchant_slots))
+                .expect("enchant container not locked");
+            inputs.set_item(slots::ITEM_SLOT, result);
+            if !infinite {
+                let mut lapis = lapis;
+                lapis.shrink(needed);
+                inputs.set_item(slots::LAPIS_SLOT, lapis);
+            }
+            inputs.set_changed();
+        }
+
+        player.award_stat(StatKey::custom(CustomStat::EnchantItem), 1);
+        // TODO: Trigger the enchanted_item advancement criterion
+
+        self.update_options(player);
+
+        player.world().play_block_sound(
+            sound_events::BLOCK_ENCHANTMENT_TABLE_USE,
+            self.block_pos,
+            1.0,
+            rand::random::<f32>() * 0.1 + 0.9,
+            None,
+        );
+
+        true
+    }
+}
+
+impl MenuInstance for EnchantmentMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::ENCHANTMENT
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating an enchanting table menu.
+pub struct EnchantmentMenuProvider {
+    inventory: SyncPlayerInv,
+    pos: BlockPos,
+}
+
+impl EnchantmentMenuProvider {
+    /// Creates a new enchanting table menu provider.
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, pos: BlockPos) -> Self {
+        Self { inventory, pos }
+    }
+}
+
+impl MenuProvider for EnchantmentMenuProvider {
+    fn title(&self) -> TextComponent {
+        TextComponent::translated(translations::CONTAINER_ENCHANT.msg())
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(EnchantmentMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.pos,
+        ))
+    }
+}
@@ -1,5 +1,7 @@
 //! Equipment slot definitions for entities.
 
+use steel_registry::data_components::EquippableSlot;
+
 /// Equipment slot types for categorization.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EquipmentSlotType {
@@ -86,6 +88,21 @@ impl EquipmentSlot {
         }
     }
 
+    /// Returns the id of this slot in packets like `CSetEquipment`.
+    #[must_use]
+    pub const fn id(self) -> u8 {
+        match self {
+            EquipmentSlot::MainHand => 0,
+            EquipmentSlot::Feet => 1,
+            EquipmentSlot::Legs => 2,
+            EquipmentSlot::Chest => 3,
+            EquipmentSlot::Head => 4,
+            EquipmentSlot::OffHand => 5,
+            EquipmentSlot::Body => 6,
+            EquipmentSlot::Saddle => 7,
+        }
+    }
+
     /// Returns true if this is an armor slot (humanoid or animal).
     #[must_use]
     pub const fn is_armor(self) -> bool {
@@ -126,3 +143,18 @@ impl EquipmentSlot {
         }
     }
 }
+
+impl From<EquippableSlot> for EquipmentSlot {
+    fn from(slot: EquippableSlot) -> Self {
+        match slot {
+            EquippableSlot::Head => EquipmentSlot::Head,
+            EquippableSlot::Chest => EquipmentSlot::Chest,
+            EquippableSlot::Legs => EquipmentSlot::Legs,
+            EquippableSlot::Feet => EquipmentSlot::Feet,
+            EquippableSlot::Body => EquipmentSlot::Body,
+            EquippableSlot::Mainhand => EquipmentSlot::MainHand,
+            EquippableSlot::Offhand => EquipmentSlot::OffHand,
+            EquippableSlot::Saddle => EquipmentSlot::Saddle,
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! The furnace menu, shared by furnaces, blast furnaces and smokers.
+//!
+//! Slot layout (39 total):
+//! - Slot 0: Input
+//! - Slot 1: Fuel
+//! - Slot 2: Result
+//! - Slots 3-29: Main inventory (27 slots)
+//! - Slots 30-38: Hotbar (9 slots)
+
+use steel_registry::REGISTRY;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::recipe::CookingKind;
+use steel_registry::vanilla_menu_types;
+use text_components::TextComponent;
+
+use crate::block_entity::SharedBlockEntity;
+use crate::block_entity::entities::{FurnaceBlockEntity, SLOT_FUEL, SLOT_INPUT, SLOT_RESULT};
+use crate::inventory::{
+    SyncPlayerInv,
+    lock::{ContainerLockGuard, ContainerRef},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{
+        FurnaceFuelSlot, FurnaceResultSlot, NormalSlot, Slot, SlotType,
+        add_standard_inventory_slots,
+    },
+};
+use crate::player::Player;
+
+/// Slot indices for the furnace menu.
+pub mod slots {
+    /// Slot index for the input (slot 0).
+    pub const INGREDIENT_SLOT: usize = 0;
+    /// Slot index for the fuel (slot 1).
+    pub const FUEL_SLOT: usize = 1;
+    /// Slot index for the result (slot 2).
+    pub const RESULT_SLOT: usize = 2;
+    /// Start of main inventory (slot 3).
+    pub const INV_SLOT_START: usize = 3;
+    /// End of main inventory (slot 30, exclusive).
+    pub const INV_SLOT_END: usize = 30;
+    /// Start of hotbar (slot 30).
+    pub const HOTBAR_SLOT_START: usize = 30;
+    /// End of hotbar (slot 39, exclusive).
+    pub const HOTBAR_SLOT_END: usize = 39;
+}
+
+/// Number of data slots the furnace menu syncs: remaining burn time, total
+/// burn time, cooking progress and total cooking time.
+pub const DATA_COUNT: usize = 4;
+
+/// The menu of a furnace, blast furnace or smoker.
+///
+/// Based on Java's `AbstractFurnaceMenu`.
+pub struct FurnaceMenu {
+    behavior: MenuBehavior,
+    /// The furnace block entity, read for the progress shown by the client.
+    block_entity: SharedBlockEntity,
+    /// The furnace as a container.
+    container: ContainerRef,
+    /// Which recipes the furnace cooks with.
+    kind: CookingKind,
+}
+
+impl FurnaceMenu {
+    /// Creates a new furnace menu for the furnace `block_entity`.
+    ///
+    /// # Panics
+    /// Panics if `block_entity` is not a container.
+    #[must_use]
+    pub fn new(
+        inventory: SyncPlayerInv,
+        container_id: u8,
+        block_entity: SharedBlockEntity,
+        kind: CookingKind,
+    ) -> Self {
+        let container =
+            ContainerRef::from_block_entity(block_entity.clone()).expect("furnaces are containers");
+
+        let mut menu_slots = Vec::with_capacity(slots::HOTBAR_SLOT_END);
+        menu_slots.push(SlotType::Normal(NormalSlot::new(
+            container.clone(),
+            SLOT_INPUT,
+        )));
+        menu_slots.push(SlotType::FurnaceFuel(FurnaceFuelSlot::new(
+            container.clone(),
+            SLOT_FUEL,
+        )));
+        menu_slots.push(SlotType::FurnaceResult(FurnaceResultSlot::new(
+            container.clone(),
+            SLOT_RESULT,
+        )));
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        let mut behavior =
+            MenuBehavior::new(menu_slots, container_id, Some(Self::menu_type_for(kind)));
+        behavior.add_data_slots(DATA_COUNT);
+
+        let mut menu = Self {
+            behavior,
+            block_entity,
+            container,
+            kind,
+        };
+        menu.update_data_slots();
+        menu
+    }
+
+    /// Returns the menu type for furnaces cooking `kind` recipes.
+    #[must_use]
+    pub const fn menu_type_for(kind: CookingKind) -> MenuTypeRef {
+        match kind {
+            CookingKind::Smelting => vanilla_menu_types::FURNACE,
+            CookingKind::Blasting => vanilla_menu_types::BLAST_FURNACE,
+            CookingKind::Smoking => vanilla_menu_types::SMOKER,
+        }
+    }
+
+    /// Returns a reference to the furnace's container.
+    #[must_use]
+    pub fn container(&self) -> &ContainerRef {
+        &self.container
+    }
+
+    /// Returns true if `stack` can be cooked by this furnace.
+    fn can_smelt(&self, stack: &ItemStack) -> bool {
+        REGISTRY
+            .recipes
+            .cooking(self.kind)
+            .find_recipe(stack)
+            .is_some()
+    }
+}
+
+impl Menu for FurnaceMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `AbstractFurnaceMenu::quickMoveStack`:
+    /// - Result slot (2) -> inventory (3-39), prefer existing stacks
+    /// - Input and fuel slots (0-1) -> inventory (3-39)
+    /// - Inventory (3-39) -> input if it can be cooked, fuel if it burns,
+    ///   otherwise between main inventory and hotbar
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        let moved = if slot_index == slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                true,
+            )
+        } else if slot_index == slots::INGREDIENT_SLOT || slot_index == slots::FUEL_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                false,
+            )
+        } else if self.can_smelt(&stack_mut) {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INGREDIENT_SLOT,
+                slots::INGREDIENT_SLOT + 1,
+                false,
+            )
+        } else if REGISTRY.fuels.is_fuel(&stack_mut) {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::FUEL_SLOT,
+                slots::FUEL_SLOT + 1,
+                false,
+            )
+        } else if slot_index < slots::HOTBAR_SLOT_START {
+            // Main inventory -> hotbar
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::HOTBAR_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                false,
+            )
+        } else {
+            // Hotbar -> main inventory
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::INV_SLOT_END,
+                false,
+            )
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        // Update the source slot with the remaining items
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+
+        // Check if unchanged
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        // Taking cooked items drops their experience
+        if slot_index == slots::RESULT_SLOT {
+            self.behavior.slots[slot_index].on_take(guard, &clicked, player);
+        }
+
+        clicked
+    }
+
+    /// Returns true while the furnace block entity is still in the world.
+    fn still_valid(&self) -> bool {
+        !self.block_entity.lock().is_removed()
+    }
+
+    /// Copies the furnace's burn time and cooking progress into the data slots.
+    fn update_data_slots(&mut self) {
+        let data = {
+            let guard = self.block_entity.lock();
+            let Some(furnace) = guard.as_any().downcast_ref::<FurnaceBlockEntity>() else {
+                return;
+            };
+            furnace.data()
+        };
+        for (index, value) in data.into_iter().enumerate() {
+            self.behavior.set_data(index, value as i16);
+        }
+    }
+}
+
+impl MenuInstance for FurnaceMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        Self::menu_type_for(self.kind)
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating furnace menus.
+pub struct FurnaceMenuProvider {
+    inventory: SyncPlayerInv,
+    block_entity: SharedBlockEntity,
+    kind: CookingKind,
+    title: TextComponent,
+}
+
+impl FurnaceMenuProvider {
+    /// Creates a new furnace menu provider.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `block_entity` - The furnace block entity
+    /// * `kind` - Which recipes the furnace cooks with
+    /// * `title` - Display title for the menu
+    #[must_use]
+    pub fn new(
+        inventory: SyncPlayerInv,
+        block_entity: SharedBlockEntity,
+        kind: CookingKind,
+        title: TextComponent,
+    ) -> Self {
+        Self {
+            inventory,
+            block_entity,
+            kind,
+            title,
+        }
+    }
+}
+
+impl MenuProvider for FurnaceMenuProvider {
+    fn title(&self) -> TextComponent {
+        self.title.clone()
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(FurnaceMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.block_entity.clone(),
+            self.kind,
+        ))
+    }
+}
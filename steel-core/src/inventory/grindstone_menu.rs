@@ -0,0 +1,452 @@
+//! The grindstone menu for repairing and disenchanting items.
+//!
+//! Slot layout (39 total):
+//! - Slot 0: Item to repair or disenchant
+//! - Slot 1: Additional item (a second copy of the item to combine with)
+//! - Slot 2: Result
+//! - Slots 3-29: Main inventory (27 slots)
+//! - Slots 30-38: Hotbar (9 slots)
+
+use std::{mem, sync::Arc};
+
+use steel_registry::REGISTRY;
+use steel_registry::data_components::vanilla_components::{MAX_DAMAGE, REPAIR_COST};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::vanilla_items::ITEMS;
+use steel_registry::vanilla_menu_types;
+use steel_utils::locks::SyncMutex;
+use steel_utils::{BlockPos, Identifier, translations};
+use text_components::TextComponent;
+
+use crate::inventory::anvil_menu::calculate_increased_repair_cost;
+use crate::inventory::{
+    SyncPlayerInv,
+    container::{Container, SimpleContainer},
+    crafting::ResultContainer,
+    lock::{ContainerId, ContainerLockGuard, ContainerRef, GenericContainer},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{
+        GrindstoneInputSlot, GrindstoneResultSlot, Slot, SlotType, SyncResultContainer,
+        add_standard_inventory_slots,
+    },
+};
+use crate::player::Player;
+
+/// Slot indices for the grindstone menu.
+pub mod slots {
+    /// Slot index for the item being repaired or disenchanted (slot 0).
+    pub const INPUT_SLOT: usize = 0;
+    /// Slot index for the additional item (slot 1).
+    pub const ADDITIONAL_SLOT: usize = 1;
+    /// Slot index for the result (slot 2).
+    pub const RESULT_SLOT: usize = 2;
+    /// Start of main inventory (slot 3).
+    pub const INV_SLOT_START: usize = 3;
+    /// End of main inventory (slot 30, exclusive).
+    pub const INV_SLOT_END: usize = 30;
+    /// Start of hotbar (slot 30).
+    pub const HOTBAR_SLOT_START: usize = 30;
+    /// End of hotbar (slot 39, exclusive).
+    pub const HOTBAR_SLOT_END: usize = 39;
+    /// Total number of slots in the grindstone menu.
+    pub const TOTAL_SLOTS: usize = 39;
+}
+
+/// The grindstone menu.
+///
+/// Based on Java's `GrindstoneMenu`.
+pub struct GrindstoneMenu {
+    behavior: MenuBehavior,
+    /// The two input slots.
+    repair_slots: GenericContainer,
+    /// The result slot.
+    result_slots: SyncResultContainer,
+    /// The position of the grindstone block.
+    block_pos: BlockPos,
+}
+
+impl GrindstoneMenu {
+    /// Creates a new grindstone menu for a player.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `block_pos` - The position of the grindstone block
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, container_id: u8, block_pos: BlockPos) -> Self {
+        let mut menu_slots = Vec::with_capacity(slots::TOTAL_SLOTS);
+
+        let repair_slots: GenericContainer = Arc::new(SyncMutex::new(SimpleContainer::new(2)));
+        let result_slots: SyncResultContainer = Arc::new(SyncMutex::new(ResultContainer::new()));
+
+        // Slots 0-1: Inputs
+        for i in 0..2 {
+            menu_slots.push(SlotType::GrindstoneInput(GrindstoneInputSlot::new(
+                ContainerRef::Other(repair_slots.clone()),
+                i,
+            )));
+        }
+
+        // Slot 2: Result
+        menu_slots.push(SlotType::GrindstoneResult(GrindstoneResultSlot::new(
+            result_slots.clone(),
+            repair_slots.clone(),
+            block_pos,
+        )));
+
+        // Slots 3-38: Standard inventory (main inventory + hotbar)
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        let behavior = MenuBehavior::new(
+            menu_slots,
+            container_id,
+            Some(vanilla_menu_types::GRINDSTONE),
+        );
+
+        Self {
+            behavior,
+            repair_slots,
+            result_slots,
+            block_pos,
+        }
+    }
+
+    /// Returns the menu type for the grindstone.
+    #[must_use]
+    pub fn menu_type() -> MenuTypeRef {
+        vanilla_menu_types::GRINDSTONE
+    }
+
+    /// Returns the position of the grindstone block.
+    #[must_use]
+    pub fn block_pos(&self) -> BlockPos {
+        self.block_pos
+    }
+
+    /// Recomputes the result slot from the inputs.
+    fn update_result(&mut self) {
+        let mut guard = self.behavior.lock_all_containers();
+
+        let (input, additional) = {
+            let inputs = guard
+                .get(ContainerId::from_arc(&self.repair_slots))
+                .expect("input container not locked");
+            (inputs.get_item(0).clone(), inputs.get_item(1).clone())
+        };
+
+        guard
+            .get_result_container_mut(ContainerId::from_arc(&self.result_slots))
+            .expect("result container not locked")
+            .set_item(0, create_result(&input, &additional));
+    }
+}
+
+/// Returns true if `key` names a curse, which grindstones leave on items.
+fn is_curse(key: &Identifier) -> bool {
+    REGISTRY
+        .enchantments
+        .by_key(key)
+        .is_some_and(|enchantment| enchantment.is_curse)
+}
+
+/// Computes the grindstone output for the given inputs.
+///
+/// Based on Java's `GrindstoneMenu::computeResult`. A single enchanted item
+/// loses its enchantments; two of the same item are combined into one.
+fn create_result(input: &ItemStack, additional: &ItemStack) -> ItemStack {
+    if input.is_empty() && additional.is_empty() {
+        return ItemStack::empty();
+    }
+    if input.count() > 1 || additional.count() > 1 {
+        return ItemStack::empty();
+    }
+
+    if input.is_empty() || additional.is_empty() {
+        let item = if input.is_empty() { additional } else { input };
+        if !item.has_any_enchantments() {
+            return ItemStack::empty();
+        }
+        return remove_non_curses_from(item.clone());
+    }
+
+    merge_items(input, additional)
+}
+
+/// Combines two of the same item, adding up their durability with a 5% bonus
+/// and keeping only the curses.
+///
+/// Based on Java's `GrindstoneMenu::mergeItems`.
+fn merge_items(input: &ItemStack, additional: &ItemStack) -> ItemStack {
+    if !input.is(additional.item()) {
+        return ItemStack::empty();
+    }
+
+    let max_damage = input.get_max_damage().max(additional.get_max_damage());
+    let input_durability = input.get_max_damage() - input.get_damage_value();
+    let additional_durability = additional.get_max_damage() - additional.get_damage_value();
+    let durability = input_durability + additional_durability + max_damage * 5 / 100;
+
+    let mut count = 1;
+    if !input.is_damageable_item() {
+        if input.max_stack_size() < 2 || !ItemStack::matches(input, additional) {
+            return ItemStack::empty();
+        }
+        count = 2;
+    }
+
+    let mut result = input.clone();
+    result.set_count(count);
+    if result.is_damageable_item() {
+        result.set(MAX_DAMAGE, max_damage);
+        result.set_damage_value((max_damage - durability).max(0));
+    }
+
+    // Curses already on the result are kept at their own level
+    let additional_enchantments = additional.get_enchantments_for_crafting();
+    result.update_enchantments(|enchantments| {
+        for (key, level) in additional_enchantments.iter() {
+            if !is_curse(key) || enchantments.get_level(key) == 0 {
+                enchantments.upgrade(key.clone(), level);
+            }
+        }
+    });
+
+    remove_non_curses_from(result)
+}
+
+/// Strips every enchantment but the curses from `item`, turning enchanted
+/// books without curses back into books, and lowers the prior work penalty
+/// to match the curses left.
+///
+/// Based on Java's `GrindstoneMenu::removeNonCursesFrom`.
+fn remove_non_curses_from(mut item: ItemStack) -> ItemStack {
+    item.update_enchantments(|enchantments| enchantments.retain(|key, _| is_curse(key)));
+
+    let remaining = item.get_enchantments_for_crafting().len();
+    if item.is(&ITEMS.enchanted_book) && remaining == 0 {
+        item = ItemStack::with_count_and_patch(&ITEMS.book, item.count(), item.patch().clone());
+    }
+
+    let repair_cost = (0..remaining).fold(0, |cost, _| calculate_increased_repair_cost(cost));
+    item.set(REPAIR_COST, repair_cost);
+    item
+}
+
+/// Returns the experience taken out of the enchantments on `item`, not
+/// counting curses.
+///
+/// Based on Java's `GrindstoneMenu::getExperienceFromItem`.
+fn get_experience_from_item(item: &ItemStack) -> i32 {
+    item.get_enchantments_for_crafting()
+        .iter()
+        .filter_map(|(key, level)| {
+            let enchantment = REGISTRY.enchantments.by_key(key)?;
+            (!enchantment.is_curse).then(|| enchantment.min_cost(level))
+        })
+        .sum()
+}
+
+/// Returns the experience a player gets for grinding the two inputs, between
+/// half and all of what their enchantments are worth.
+///
+/// Based on Java's `GrindstoneMenu::getExperienceAmount`.
+#[must_use]
+pub fn get_experience_amount(input: &ItemStack, additional: &ItemStack) -> i32 {
+    let experience = get_experience_from_item(input) + get_experience_from_item(additional);
+    if experience <= 0 {
+        return 0;
+    }
+    let half = (experience + 1) / 2;
+    half + rand::random_range(0..half)
+}
+
+impl Menu for GrindstoneMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `GrindstoneMenu::quickMoveStack`:
+    /// - Result slot (2) -> inventory (3-38), prefer existing stacks
+    /// - Input slots (0-1) -> inventory (3-38)
+    /// - Inventory (3-38) -> an empty input slot, or between main inventory
+    ///   and hotbar if both inputs are full
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        let moved = if slot_index == slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                true,
+            )
+        } else if slot_index < slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                false,
+            )
+        } else {
+            let inputs_full = !self.behavior.slots[slots::INPUT_SLOT]
+                .get_item(guard)
+                .is_empty()
+                && !self.behavior.slots[slots::ADDITIONAL_SLOT]
+                    .get_item(guard)
+                    .is_empty();
+            let (start, end) = if !inputs_full {
+                (slots::INPUT_SLOT, slots::RESULT_SLOT)
+            } else if slot_index < slots::INV_SLOT_END {
+                (slots::HOTBAR_SLOT_START, slots::HOTBAR_SLOT_END)
+            } else {
+                (slots::INV_SLOT_START, slots::INV_SLOT_END)
+            };
+            self.behavior
+                .move_item_stack_to(guard, &mut stack_mut, start, end, false)
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        if slot_index == slots::RESULT_SLOT
+            && let Some(remainder) =
+                self.behavior.slots[slot_index].on_take(guard, &clicked, player)
+        {
+            player.add_item_or_drop_with_guard(guard, remainder);
+        }
+
+        clicked
+    }
+
+    /// Prevents collecting items from the result slot.
+    fn can_take_item_for_pick_all(&self, _carried: &ItemStack, slot_index: usize) -> bool {
+        slot_index != slots::RESULT_SLOT
+    }
+
+    /// Called when the grindstone menu is closed.
+    /// Returns the input items to the player's inventory.
+    ///
+    /// Based on Java's `GrindstoneMenu::removed`.
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.add_item_or_drop(carried);
+        }
+
+        let input_items: Vec<ItemStack> = {
+            let mut inputs = self.repair_slots.lock();
+            (0..inputs.get_container_size())
+                .map(|i| inputs.remove_item_no_update(i))
+                .filter(|item| !item.is_empty())
+                .collect()
+        };
+
+        for item in input_items {
+            player.add_item_or_drop(item);
+        }
+
+        self.result_slots.lock().set_item(0, ItemStack::empty());
+    }
+
+    fn slots_changed(&mut self, _player: &Player) {
+        self.update_result();
+    }
+}
+
+impl MenuInstance for GrindstoneMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::GRINDSTONE
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating a grindstone menu.
+pub struct GrindstoneMenuProvider {
+    inventory: SyncPlayerInv,
+    pos: BlockPos,
+}
+
+impl GrindstoneMenuProvider {
+    /// Creates a new grindstone menu provider.
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, pos: BlockPos) -> Self {
+        Self { inventory, pos }
+    }
+}
+
+impl MenuProvider for GrindstoneMenuProvider {
+    fn title(&self) -> TextComponent {
+        TextComponent::translated(translations::CONTAINER_GRINDSTONE_TITLE.msg())
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(GrindstoneMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.pos,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merging_adds_durability_with_a_bonus() {
+        let mut input = ItemStack::new(&ITEMS.iron_sword);
+        let max_damage = input.get_max_damage();
+        input.set_damage_value(max_damage - 10);
+        let mut additional = input.clone();
+        additional.set_damage_value(max_damage - 20);
+
+        let result = create_result(&input, &additional);
+        let durability = result.get_max_damage() - result.get_damage_value();
+        assert_eq!(durability, 30 + max_damage * 5 / 100);
+        assert_eq!(result.get_or_default(REPAIR_COST, -1), 0);
+    }
+
+    #[test]
+    fn items_without_enchantments_alone_give_nothing() {
+        let input = ItemStack::new(&ITEMS.iron_sword);
+        assert!(create_result(&input, &ItemStack::empty()).is_empty());
+        assert!(create_result(&ItemStack::new(&ITEMS.stick), &input).is_empty());
+    }
+}
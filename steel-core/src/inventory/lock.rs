@@ -301,6 +301,22 @@ impl ContainerLockGuard {
             })
     }
 
+    /// Get mutable access to a locked block entity, like a furnace whose
+    /// result slot was taken from.
+    pub fn get_block_entity_mut(
+        &mut self,
+        id: impl Into<ContainerId>,
+    ) -> Option<&mut dyn BlockEntity> {
+        self.id_to_index
+            .get(&id.into())
+            .copied()
+            .and_then(|idx| self.guards.get_mut(idx))
+            .and_then(|(_, guard)| match guard {
+                LockedContainer::BlockEntity(g) => Some(&mut **g),
+                _ => None,
+            })
+    }
+
     /// Check if a container is locked.
     #[must_use]
     pub fn contains(&self, id: ContainerId) -> bool {
@@ -0,0 +1,476 @@
+//! The loom menu for drawing patterns on banners.
+//!
+//! Slot layout (40 total):
+//! - Slot 0: Banner
+//! - Slot 1: Dye
+//! - Slot 2: Banner pattern item
+//! - Slot 3: Result
+//! - Slots 4-30: Main inventory (27 slots)
+//! - Slots 31-39: Hotbar (9 slots)
+
+use std::{mem, ptr, sync::Arc};
+
+use steel_registry::banner_pattern::BannerPatternRef;
+use steel_registry::data_components::vanilla_components::{
+    BANNER_PATTERNS, BannerPatternLayers, MAX_BANNER_LAYERS, PROVIDES_BANNER_PATTERNS,
+};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::loot_table::DyeColor;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::{REGISTRY, vanilla_menu_types};
+use steel_utils::locks::SyncMutex;
+use steel_utils::{BlockPos, Identifier, translations};
+use text_components::TextComponent;
+
+use crate::inventory::{
+    SyncPlayerInv,
+    container::{Container, SimpleContainer},
+    crafting::ResultContainer,
+    lock::{ContainerId, ContainerLockGuard, ContainerRef, GenericContainer},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{
+        LoomBannerSlot, LoomDyeSlot, LoomPatternSlot, LoomResultSlot, Slot, SlotType,
+        SyncResultContainer, add_standard_inventory_slots,
+    },
+};
+use crate::player::Player;
+
+/// Slot indices for the loom menu.
+pub mod slots {
+    /// Slot index for the banner (slot 0).
+    pub const BANNER_SLOT: usize = 0;
+    /// Slot index for the dye (slot 1).
+    pub const DYE_SLOT: usize = 1;
+    /// Slot index for the banner pattern item (slot 2).
+    pub const PATTERN_SLOT: usize = 2;
+    /// Slot index for the result (slot 3).
+    pub const RESULT_SLOT: usize = 3;
+    /// Start of main inventory (slot 4).
+    pub const INV_SLOT_START: usize = 4;
+    /// End of main inventory (slot 31, exclusive).
+    pub const INV_SLOT_END: usize = 31;
+    /// Start of hotbar (slot 31).
+    pub const HOTBAR_SLOT_START: usize = 31;
+    /// End of hotbar (slot 40, exclusive).
+    pub const HOTBAR_SLOT_END: usize = 40;
+    /// Total number of slots in the loom menu.
+    pub const TOTAL_SLOTS: usize = 40;
+}
+
+/// Items that can be patterned in a loom.
+const BANNERS_TAG: Identifier = Identifier::vanilla_static("banners");
+
+/// Patterns the loom offers when no banner pattern item is in it.
+const NO_ITEM_REQUIRED_TAG: Identifier = Identifier::vanilla_static("no_item_required");
+
+/// Returns true if `stack` is a banner.
+#[must_use]
+pub fn is_banner(stack: &ItemStack) -> bool {
+    REGISTRY.items.is_in_tag(stack.item(), &BANNERS_TAG)
+}
+
+/// Returns the color of `stack` if it is a dye.
+#[must_use]
+pub fn dye_color(stack: &ItemStack) -> Option<DyeColor> {
+    let key = &stack.item().key;
+    if key.namespace != Identifier::VANILLA_NAMESPACE {
+        return None;
+    }
+    key.path.strip_suffix("_dye").and_then(DyeColor::by_name)
+}
+
+/// Returns the patterns the loom offers for the item in the pattern slot.
+///
+/// Based on Java's `LoomMenu::getSelectablePatterns`.
+fn get_selectable_patterns(pattern_item: &ItemStack) -> Vec<BannerPatternRef> {
+    let tag = if pattern_item.is_empty() {
+        &NO_ITEM_REQUIRED_TAG
+    } else if let Some(provides) = pattern_item.get(PROVIDES_BANNER_PATTERNS) {
+        &provides.0
+    } else {
+        return Vec::new();
+    };
+    REGISTRY.banner_patterns.iter_tag(tag).collect()
+}
+
+/// Returns a copy of a single `banner` with `pattern` drawn over it in
+/// `color`.
+fn create_result(banner: &ItemStack, pattern: BannerPatternRef, color: DyeColor) -> ItemStack {
+    let mut result = banner.clone();
+    result.set_count(1);
+    let layers = banner.get_or_default(BANNER_PATTERNS, BannerPatternLayers::default());
+    result.set(
+        BANNER_PATTERNS,
+        layers.with_layer(pattern.key.clone(), color),
+    );
+    result
+}
+
+/// The loom menu.
+///
+/// Based on Java's `LoomMenu`.
+pub struct LoomMenu {
+    behavior: MenuBehavior,
+    /// The banner, dye and pattern item slots.
+    input_slots: GenericContainer,
+    /// The result slot.
+    result_slots: SyncResultContainer,
+    /// The position of the loom block.
+    block_pos: BlockPos,
+    /// The patterns the player can pick from, in the order the client
+    /// shows them.
+    selectable_patterns: Vec<BannerPatternRef>,
+    /// Index of the data slot holding the selected pattern, -1 for none.
+    selected_pattern_data: usize,
+}
+
+impl LoomMenu {
+    /// Creates a new loom menu for a player.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `block_pos` - The position of the loom block
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, container_id: u8, block_pos: BlockPos) -> Self {
+        let mut menu_slots = Vec::with_capacity(slots::TOTAL_SLOTS);
+
+        let input_slots: GenericContainer = Arc::new(SyncMutex::new(SimpleContainer::new(3)));
+        let result_slots: SyncResultContainer = Arc::new(SyncMutex::new(ResultContainer::new()));
+
+        // Slots 0-2: Banner, dye and pattern item
+        menu_slots.push(SlotType::LoomBanner(LoomBannerSlot::new(
+            ContainerRef::Other(input_slots.clone()),
+            slots::BANNER_SLOT,
+        )));
+        menu_slots.push(SlotType::LoomDye(LoomDyeSlot::new(
+            ContainerRef::Other(input_slots.clone()),
+            slots::DYE_SLOT,
+        )));
+        menu_slots.push(SlotType::LoomPattern(LoomPatternSlot::new(
+            ContainerRef::Other(input_slots.clone()),
+            slots::PATTERN_SLOT,
+        )));
+
+        // Slot 3: Result
+        menu_slots.push(SlotType::LoomResult(LoomResultSlot::new(
+            result_slots.clone(),
+            input_slots.clone(),
+            block_pos,
+        )));
+
+        // Slots 4-39: Standard inventory (main inventory + hotbar)
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        let mut behavior =
+            MenuBehavior::new(menu_slots, container_id, Some(vanilla_menu_types::LOOM));
+        let selected_pattern_data = behavior.add_data_slot(-1);
+
+        Self {
+            behavior,
+            input_slots,
+            result_slots,
+            block_pos,
+            selectable_patterns: Vec::new(),
+            selected_pattern_data,
+        }
+    }
+
+    /// Returns the menu type for the loom.
+    #[must_use]
+    pub fn menu_type() -> MenuTypeRef {
+        vanilla_menu_types::LOOM
+    }
+
+    /// Returns the position of the loom block.
+    #[must_use]
+    pub fn block_pos(&self) -> BlockPos {
+        self.block_pos
+    }
+
+    /// Returns the banner, dye and pattern item currently in the menu.
+    fn inputs(&self) -> (ItemStack, ItemStack, ItemStack) {
+        let guard = self.behavior.lock_all_containers();
+        let inputs = guard
+            .get(ContainerId::from_arc(&self.input_slots))
+            .expect("input container not locked");
+        (
+            inputs.get_item(slots::BANNER_SLOT).clone(),
+            inputs.get_item(slots::DYE_SLOT).clone(),
+            inputs.get_item(slots::PATTERN_SLOT).clone(),
+        )
+    }
+
+    /// Returns the index of the selected pattern, if any.
+    fn selected_index(&self) -> Option<usize> {
+        let index = self.behavior.get_data(self.selected_pattern_data)?;
+        usize::try_from(index)
+            .ok()
+            .filter(|&index| index < self.selectable_patterns.len())
+    }
+
+    /// Selects the pattern at `index`, or none.
+    fn set_selected_index(&mut self, index: Option<usize>) {
+        let value = index.map_or(-1, |index| index as i16);
+        self.behavior.set_data(self.selected_pattern_data, value);
+    }
+
+    /// Puts `result` in the result slot.
+    fn set_result(&mut self, result: ItemStack) {
+        let mut guard = self.behavior.lock_all_containers();
+        guard
+            .get_result_container_mut(ContainerId::from_arc(&self.result_slots))
+            .expect("result container not locked")
+            .set_item(0, result);
+    }
+
+    /// Draws `pattern` on the banner in the result slot.
+    ///
+    /// Based on Java's `LoomMenu::setupResultSlot`.
+    fn setup_result_slot(&mut self, pattern: BannerPatternRef) {
+        let (banner, dye, _) = self.inputs();
+        let result = match dye_color(&dye) {
+            Some(color) if !banner.is_empty() => create_result(&banner, pattern, color),
+            _ => ItemStack::empty(),
+        };
+        self.set_result(result);
+    }
+
+    /// Recomputes the selectable patterns and the result from the inputs,
+    /// keeping the selected pattern if it is still offered.
+    ///
+    /// Based on Java's `LoomMenu::slotsChanged`.
+    fn update_result(&mut self) {
+        let (banner, dye, pattern_item) = self.inputs();
+        if banner.is_empty() || dye.is_empty() {
+            self.selectable_patterns.clear();
+            self.set_selected_index(None);
+            self.set_result(ItemStack::empty());
+            return;
+        }
+
+        let old_pattern = self
+            .selected_index()
+            .map(|index| self.selectable_patterns[index]);
+        self.selectable_patterns = get_selectable_patterns(&pattern_item);
+
+        let selected = if self.selectable_patterns.len() == 1 {
+            Some(0)
+        } else {
+            old_pattern.and_then(|old| {
+                self.selectable_patterns
+                    .iter()
+                    .position(|&pattern| ptr::eq(pattern, old))
+            })
+        };
+        self.set_selected_index(selected);
+
+        let Some(index) = selected else {
+            self.set_result(ItemStack::empty());
+            return;
+        };
+
+        let layers = banner
+            .get(BANNER_PATTERNS)
+            .map_or(0, |layers| layers.layers.len());
+        if layers >= MAX_BANNER_LAYERS {
+            self.set_selected_index(None);
+            self.set_result(ItemStack::empty());
+        } else {
+            self.setup_result_slot(self.selectable_patterns[index]);
+        }
+    }
+}
+
+impl Menu for LoomMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `LoomMenu::quickMoveStack`:
+    /// - Result slot (3) -> inventory (4-39), prefer existing stacks
+    /// - Input slots (0-2) -> inventory (4-39)
+    /// - Inventory (4-39) -> the matching input slot, or between main
+    ///   inventory and hotbar for anything else
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        let moved = if slot_index == slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                true,
+            )
+        } else if slot_index < slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                false,
+            )
+        } else {
+            let (start, end) = if is_banner(&stack_mut) {
+                (slots::BANNER_SLOT, slots::BANNER_SLOT + 1)
+            } else if dye_color(&stack_mut).is_some() {
+                (slots::DYE_SLOT, slots::DYE_SLOT + 1)
+            } else if stack_mut.has(PROVIDES_BANNER_PATTERNS) {
+                (slots::PATTERN_SLOT, slots::PATTERN_SLOT + 1)
+            } else if slot_index < slots::INV_SLOT_END {
+                (slots::HOTBAR_SLOT_START, slots::HOTBAR_SLOT_END)
+            } else {
+                (slots::INV_SLOT_START, slots::INV_SLOT_END)
+            };
+            self.behavior
+                .move_item_stack_to(guard, &mut stack_mut, start, end, false)
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        if slot_index == slots::RESULT_SLOT
+            && let Some(remainder) =
+                self.behavior.slots[slot_index].on_take(guard, &clicked, player)
+        {
+            player.add_item_or_drop_with_guard(guard, remainder);
+        }
+
+        clicked
+    }
+
+    /// Prevents collecting items from the result slot.
+    fn can_take_item_for_pick_all(&self, _carried: &ItemStack, slot_index: usize) -> bool {
+        slot_index != slots::RESULT_SLOT
+    }
+
+    /// Called when the loom menu is closed.
+    /// Returns the input items to the player's inventory.
+    ///
+    /// Based on Java's `LoomMenu::removed`.
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.add_item_or_drop(carried);
+        }
+
+        let input_items: Vec<ItemStack> = {
+            let mut inputs = self.input_slots.lock();
+            (0..inputs.get_container_size())
+                .map(|i| inputs.remove_item_no_update(i))
+                .filter(|item| !item.is_empty())
+                .collect()
+        };
+
+        for item in input_items {
+            player.add_item_or_drop(item);
+        }
+
+        self.result_slots.lock().set_item(0, ItemStack::empty());
+    }
+
+    fn slots_changed(&mut self, _player: &Player) {
+        self.update_result();
+    }
+
+    /// Selects one of the offered patterns.
+    ///
+    /// Based on Java's `LoomMenu::clickMenuButton`.
+    fn click_menu_button(&mut self, button_id: i32, _player: &Player) -> bool {
+        let Ok(index) = usize::try_from(button_id) else {
+            return false;
+        };
+        let Some(&pattern) = self.selectable_patterns.get(index) else {
+            return false;
+        };
+        self.set_selected_index(Some(index));
+        self.setup_result_slot(pattern);
+        true
+    }
+}
+
+impl MenuInstance for LoomMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::LOOM
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating a loom menu.
+pub struct LoomMenuProvider {
+    inventory: SyncPlayerInv,
+    pos: BlockPos,
+}
+
+impl LoomMenuProvider {
+    /// Creates a new loom menu provider.
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, pos: BlockPos) -> Self {
+        Self { inventory, pos }
+    }
+}
+
+impl MenuProvider for LoomMenuProvider {
+    fn title(&self) -> TextComponent {
+        TextComponent::translated(translations::CONTAINER_LOOM.msg())
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(LoomMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.pos,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use steel_registry::vanilla_items::ITEMS;
+
+    use super::*;
+
+    #[test]
+    fn dyes_map_to_their_color() {
+        assert_eq!(
+            dye_color(&ItemStack::new(&ITEMS.light_blue_dye)),
+            Some(DyeColor::LightBlue)
+        );
+        assert_eq!(dye_color(&ItemStack::new(&ITEMS.stick)), None);
+    }
+}
@@ -23,7 +23,10 @@ use steel_protocol::packets::game::{
     HashedPatchMap, HashedStack,
 };
 use steel_registry::{
-    REGISTRY, data_components::DataComponentPatch, item_stack::ItemStack, menu_type::MenuTypeRef,
+    REGISTRY,
+    data_components::{DataComponentPatch, vanilla_components::MAX_STACK_SIZE},
+    item_stack::ItemStack,
+    menu_type::MenuTypeRef,
 };
 
 use crate::{
@@ -128,6 +131,47 @@ fn hashed_stack_matches(hash: &HashedStack, item: &ItemStack) -> bool {
     }
 }
 
+/// The largest stack size an item can be given through components.
+const MAX_STACK_SIZE_LIMIT: i32 = 99;
+
+/// Returns true if a stack the client reported could exist at all.
+///
+/// The item has to exist and the count has to fit in a stack of it. Since
+/// only hashes of the components are sent, a stack that overrides its max
+/// size is only checked against the largest size a component can set.
+#[must_use]
+pub fn is_valid_remote_stack(hash: &HashedStack) -> bool {
+    let HashedStack::Item {
+        item_id,
+        count,
+        components,
+    } = hash
+    else {
+        return true;
+    };
+    let Some(item) = usize::try_from(*item_id)
+        .ok()
+        .and_then(|id| REGISTRY.items.by_id(id))
+    else {
+        return false;
+    };
+
+    let max_size_overridden = REGISTRY
+        .data_components
+        .get_id_by_key(&MAX_STACK_SIZE.key)
+        .is_some_and(|id| {
+            let id = id as i32;
+            components.added_components.contains_key(&id)
+                || components.removed_components.contains(&id)
+        });
+    let max_size = if max_size_overridden {
+        MAX_STACK_SIZE_LIMIT
+    } else {
+        ItemStack::new(item).max_stack_size()
+    };
+    (1..=max_size).contains(count)
+}
+
 /// Validates that the hashed component patch matches the local patch.
 fn validate_component_hashes(hashed: &HashedPatchMap, patch: &DataComponentPatch) -> bool {
     use rustc_hash::FxHashSet;
@@ -516,6 +560,14 @@ impl MenuBehavior {
         self.state_id
     }
 
+    /// Returns true if the state ID the client sent with a click is the
+    /// current one. If it isn't, the click was based on an outdated view of
+    /// the menu and the client needs a full resync afterwards.
+    #[must_use]
+    pub fn verify_state_id(&self, client_id: i32) -> bool {
+        u32::try_from(client_id).is_ok_and(|id| id == self.state_id)
+    }
+
     /// Suppresses remote updates during click handling.
     /// Call this before processing a click.
     pub fn suppress_remote_updates(&mut self) {
@@ -1200,6 +1252,35 @@ pub trait Menu {
         }
     }
 
+    /// Called after a click that may have changed the menu's slots.
+    /// Override to recompute outputs that depend on several slots, like the anvil result.
+    ///
+    /// Based on Java's `AbstractContainerMenu::slotsChanged`.
+    fn slots_changed(&mut self, _player: &Player) {}
+
+    /// Called every tick before changes are sent to the client.
+    /// Override to copy values the client shows, like a furnace's cooking
+    /// progress, into the data slots.
+    ///
+    /// Based on Java's `ContainerData` being read by `AbstractContainerMenu::broadcastChanges`.
+    fn update_data_slots(&mut self) {}
+
+    /// Sets the name typed into an anvil's name field.
+    /// Only the anvil menu reacts to this; other menus ignore it.
+    fn set_item_name(&mut self, _name: &str, _player: &Player) {}
+
+    /// Picks the trade at `index` in a merchant's list.
+    /// Only the merchant menu reacts to this; other menus ignore it.
+    fn select_trade(&mut self, _index: usize, _player: &Player) {}
+
+    /// Handles one of the menu's buttons being clicked, like an enchanting
+    /// table option. Returns true if the click changed something.
+    ///
+    /// Based on Java's `AbstractContainerMenu::clickMenuButton`.
+    fn click_menu_button(&mut self, _button_id: i32, _player: &Player) -> bool {
+        false
+    }
+
     /// Handles a click action in this menu.
     /// Based on Java's `AbstractContainerMenu::clicked` and doClick.
     ///
@@ -1245,6 +1326,7 @@ pub trait Menu {
                 ClickType::QuickCraft => unreachable!(),
             }
         }
+        self.slots_changed(player);
     }
 
     /// Handles quick move (shift-click).
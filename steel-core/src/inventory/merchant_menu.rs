@@ -0,0 +1,539 @@
+//! The merchant menu for trading with villagers.
+//!
+//! Slot layout (39 total):
+//! - Slot 0: First payment
+//! - Slot 1: Second payment
+//! - Slot 2: Result
+//! - Slots 3-29: Main inventory (27 slots)
+//! - Slots 30-38: Hotbar (9 slots)
+
+use std::{mem, sync::Arc};
+
+use steel_protocol::packets::game::{CMerchantOffers, MerchantOfferEntry};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::vanilla_menu_types;
+use steel_utils::locks::SyncMutex;
+use text_components::TextComponent;
+
+use crate::inventory::{
+    SyncPlayerInv,
+    container::{Container, SimpleContainer},
+    crafting::ResultContainer,
+    lock::{ContainerId, ContainerLockGuard, ContainerRef, GenericContainer},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{
+        MerchantResultSlot, NormalSlot, Slot, SlotType, SyncResultContainer,
+        add_standard_inventory_slots,
+    },
+};
+use crate::player::Player;
+
+/// How much the price of a trade reacts to demand.
+///
+/// Vanilla: `VillagerTrades.DEFAULT_PRICE_MULTIPLIER`
+const PRICE_MULTIPLIER: f32 = 0.05;
+
+/// Slot indices for the merchant menu.
+pub mod slots {
+    /// Slot index for the first payment (slot 0).
+    pub const PAYMENT_A_SLOT: usize = 0;
+    /// Slot index for the second payment (slot 1).
+    pub const PAYMENT_B_SLOT: usize = 1;
+    /// Slot index for the result (slot 2).
+    pub const RESULT_SLOT: usize = 2;
+    /// Start of main inventory (slot 3).
+    pub const INV_SLOT_START: usize = 3;
+    /// End of main inventory (slot 30, exclusive).
+    pub const INV_SLOT_END: usize = 30;
+    /// Start of hotbar (slot 30).
+    pub const HOTBAR_SLOT_START: usize = 30;
+    /// End of hotbar (slot 39, exclusive).
+    pub const HOTBAR_SLOT_END: usize = 39;
+    /// Total number of slots in the merchant menu.
+    pub const TOTAL_SLOTS: usize = 39;
+}
+
+/// A trade a merchant offers: one or two stacks of items in exchange for
+/// another.
+///
+/// Vanilla: `MerchantOffer`
+#[derive(Debug, Clone)]
+pub struct MerchantOffer {
+    /// The first item the merchant wants.
+    pub buy1: ItemStack,
+    /// The second item the merchant wants, if any.
+    pub buy2: Option<ItemStack>,
+    /// The item the merchant gives.
+    pub sell: ItemStack,
+    /// How often the trade was used.
+    pub uses: i32,
+    /// How often the trade can be used before it runs out.
+    pub max_uses: i32,
+    /// The experience the merchant gains from the trade.
+    pub xp: i32,
+}
+
+impl MerchantOffer {
+    /// Returns true once the trade was used up.
+    #[must_use]
+    pub fn is_out_of_stock(&self) -> bool {
+        self.uses >= self.max_uses
+    }
+
+    /// Returns true if `a` and `b` pay for the trade.
+    ///
+    /// Vanilla: `MerchantOffer.satisfiedBy`
+    #[must_use]
+    pub fn satisfied_by(&self, a: &ItemStack, b: &ItemStack) -> bool {
+        pays_for(a, &self.buy1)
+            && self
+                .buy2
+                .as_ref()
+                .map_or_else(|| b.is_empty(), |buy2| pays_for(b, buy2))
+    }
+
+    /// The trade as shown in the trading screen.
+    #[must_use]
+    pub fn entry(&self) -> MerchantOfferEntry {
+        MerchantOfferEntry {
+            cost_a: self.buy1.clone(),
+            result: self.sell.clone(),
+            cost_b: self.buy2.clone(),
+            out_of_stock: self.is_out_of_stock(),
+            uses: self.uses,
+            max_uses: self.max_uses,
+            xp: self.xp,
+            special_price_diff: 0,
+            price_multiplier: PRICE_MULTIPLIER,
+            demand: 0,
+        }
+    }
+}
+
+/// Returns true if `stack` is enough of the item `cost` asks for.
+fn pays_for(stack: &ItemStack, cost: &ItemStack) -> bool {
+    !stack.is_empty() && stack.is(cost.item()) && stack.count() >= cost.count()
+}
+
+/// Merchant state shared between [`MerchantMenu`], its result slot and the
+/// provider that opened it.
+#[derive(Debug, Clone)]
+pub struct MerchantState {
+    /// The network id of the villager being traded with.
+    pub villager_id: i32,
+    /// The container ID of the menu, set once it is created.
+    pub container_id: u8,
+    /// The trades the villager offers.
+    pub offers: Vec<MerchantOffer>,
+    /// The villager's level, from 1 (novice) to 5 (master).
+    pub level: u8,
+    /// The experience the villager gained from trading.
+    pub xp: i32,
+    /// The trade the player picked in the list, if any.
+    pub selected: Option<usize>,
+    /// The trade the payments currently pay for, if any.
+    pub active: Option<usize>,
+}
+
+impl MerchantState {
+    /// Creates the state for trading with the villager with network id
+    /// `villager_id`.
+    #[must_use]
+    pub fn new(villager_id: i32, offers: Vec<MerchantOffer>, level: u8, xp: i32) -> Self {
+        Self {
+            villager_id,
+            container_id: 0,
+            offers,
+            level,
+            xp,
+            selected: None,
+            active: None,
+        }
+    }
+
+    /// Returns the trade that `a` and `b` pay for, preferring the picked one.
+    ///
+    /// Vanilla: `MerchantOffers.getRecipeFor`
+    fn offer_for(&self, a: &ItemStack, b: &ItemStack) -> Option<usize> {
+        if let Some(selected) = self.selected
+            && self
+                .offers
+                .get(selected)
+                .is_some_and(|offer| offer.satisfied_by(a, b))
+        {
+            return Some(selected);
+        }
+        self.offers
+            .iter()
+            .position(|offer| offer.satisfied_by(a, b))
+    }
+
+    /// The packet that fills the trading screen.
+    #[must_use]
+    pub fn offers_packet(&self) -> CMerchantOffers {
+        CMerchantOffers {
+            container_id: i32::from(self.container_id),
+            offers: self.offers.iter().map(MerchantOffer::entry).collect(),
+            villager_level: i32::from(self.level),
+            villager_xp: self.xp,
+            show_progress: true,
+            can_restock: true,
+        }
+    }
+}
+
+/// A synchronized merchant state.
+pub type SyncMerchantState = Arc<SyncMutex<MerchantState>>;
+
+/// The merchant menu.
+///
+/// Based on Java's `MerchantMenu`.
+pub struct MerchantMenu {
+    behavior: MenuBehavior,
+    /// The two payment slots.
+    payment_container: GenericContainer,
+    /// The result slot.
+    result_container: SyncResultContainer,
+    /// The offers and the trade in progress, shared with the result slot.
+    state: SyncMerchantState,
+}
+
+impl MerchantMenu {
+    /// Creates a new merchant menu for a player.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `state` - The villager's offers, shared with the menu's provider
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, container_id: u8, state: SyncMerchantState) -> Self {
+        let mut menu_slots = Vec::with_capacity(slots::TOTAL_SLOTS);
+
+        let payment_container: GenericContainer = Arc::new(SyncMutex::new(SimpleContainer::new(2)));
+        let result_container: SyncResultContainer =
+            Arc::new(SyncMutex::new(ResultContainer::new()));
+        state.lock().container_id = container_id;
+
+        // Slots 0-1: Payments
+        for i in 0..2 {
+            menu_slots.push(SlotType::Normal(NormalSlot::new(
+                ContainerRef::Other(payment_container.clone()),
+                i,
+            )));
+        }
+
+        // Slot 2: Result
+        menu_slots.push(SlotType::MerchantResult(MerchantResultSlot::new(
+            result_container.clone(),
+            payment_container.clone(),
+            state.clone(),
+        )));
+
+        // Slots 3-38: Standard inventory (main inventory + hotbar)
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        Self {
+            behavior: MenuBehavior::new(
+                menu_slots,
+                container_id,
+                Some(vanilla_menu_types::MERCHANT),
+            ),
+            payment_container,
+            result_container,
+            state,
+        }
+    }
+
+    /// Returns the menu type for the merchant.
+    #[must_use]
+    pub fn menu_type() -> MenuTypeRef {
+        vanilla_menu_types::MERCHANT
+    }
+
+    /// Puts the item of the trade the payments pay for in the result slot.
+    ///
+    /// Based on Java's `MerchantContainer::updateSellItem`.
+    fn update_result(&mut self) {
+        let mut guard = self.behavior.lock_all_containers();
+
+        let (mut a, mut b) = {
+            let payments = guard
+                .get(ContainerId::from_arc(&self.payment_container))
+                .expect("payment container not locked");
+            (payments.get_item(0).clone(), payments.get_item(1).clone())
+        };
+        if a.is_empty() {
+            mem::swap(&mut a, &mut b);
+        }
+
+        let mut state = self.state.lock();
+        state.active = state
+            .offer_for(&a, &b)
+            .filter(|&index| !state.offers[index].is_out_of_stock());
+        let result = state
+            .active
+            .map_or_else(ItemStack::empty, |index| state.offers[index].sell.clone());
+        drop(state);
+
+        guard
+            .get_result_container_mut(ContainerId::from_arc(&self.result_container))
+            .expect("result container not locked")
+            .set_item(0, result);
+    }
+
+    /// Moves as many of the items `cost` asks for as fit from the player's
+    /// inventory into the payment slot `slot_index`.
+    ///
+    /// Based on Java's `MerchantMenu::moveFromInventoryToPaymentSlot`.
+    fn fill_payment_slot(
+        &self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        cost: &ItemStack,
+    ) {
+        for i in slots::INV_SLOT_START..slots::HOTBAR_SLOT_END {
+            let current = self.behavior.slots[slot_index].get_item(guard).clone();
+            if !current.is_empty() && !current.is(cost.item()) {
+                return;
+            }
+            let max = cost.max_stack_size();
+            if current.count() >= max {
+                return;
+            }
+
+            let mut source = self.behavior.slots[i].get_item(guard).clone();
+            if source.is_empty() || !ItemStack::is_same_item_same_components(&source, cost) {
+                continue;
+            }
+            let moved = source.count().min(max - current.count());
+            let mut payment = if current.is_empty() {
+                let mut payment = source.clone();
+                payment.set_count(0);
+                payment
+            } else {
+                current
+            };
+            payment.grow(moved);
+            source.shrink(moved);
+            self.behavior.slots[i].set_item(guard, source);
+            self.behavior.slots[slot_index].set_item(guard, payment);
+        }
+    }
+}
+
+impl Menu for MerchantMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `MerchantMenu::quickMoveStack`:
+    /// - Result slot (2) -> inventory (3-38), prefer the hotbar
+    /// - Payment slots (0-1) -> inventory (3-38)
+    /// - Main inventory (3-29) <-> hotbar (30-38)
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        let moved = if slot_index == slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                true,
+            )
+        } else if slot_index < slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                false,
+            )
+        } else if slot_index < slots::INV_SLOT_END {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::HOTBAR_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                false,
+            )
+        } else {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::INV_SLOT_END,
+                false,
+            )
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        if slot_index == slots::RESULT_SLOT
+            && let Some(remainder) =
+                self.behavior.slots[slot_index].on_take(guard, &clicked, player)
+        {
+            player.add_item_or_drop_with_guard(guard, remainder);
+        }
+
+        clicked
+    }
+
+    /// Prevents collecting items from the result slot.
+    fn can_take_item_for_pick_all(&self, _carried: &ItemStack, slot_index: usize) -> bool {
+        slot_index != slots::RESULT_SLOT
+    }
+
+    /// Called when the merchant menu is closed.
+    /// Returns the payments to the player's inventory and lets the villager
+    /// go back to its routine.
+    ///
+    /// Based on Java's `MerchantMenu::removed`.
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.add_item_or_drop(carried);
+        }
+
+        let payments: Vec<ItemStack> = {
+            let mut payments = self.payment_container.lock();
+            (0..payments.get_container_size())
+                .map(|i| payments.remove_item_no_update(i))
+                .filter(|item| !item.is_empty())
+                .collect()
+        };
+
+        for item in payments {
+            player.add_item_or_drop(item);
+        }
+
+        self.result_container.lock().set_item(0, ItemStack::empty());
+
+        let villager_id = self.state.lock().villager_id;
+        player.world().stop_trading_with_villager(villager_id);
+    }
+
+    fn slots_changed(&mut self, _player: &Player) {
+        self.update_result();
+    }
+
+    /// Based on Java's `MerchantMenu::setSelectionHint` and `tryMoveItems`.
+    fn select_trade(&mut self, index: usize, _player: &Player) {
+        let Some(offer) = self.state.lock().offers.get(index).cloned() else {
+            return;
+        };
+        self.state.lock().selected = Some(index);
+
+        let mut guard = self.behavior.lock_all_containers();
+
+        // Hand back payments that don't go into this trade
+        for (slot_index, cost) in [
+            (slots::PAYMENT_A_SLOT, Some(&offer.buy1)),
+            (slots::PAYMENT_B_SLOT, offer.buy2.as_ref()),
+        ] {
+            let mut payment = self.behavior.slots[slot_index].get_item(&guard).clone();
+            if payment.is_empty() || cost.is_some_and(|cost| payment.is(cost.item())) {
+                continue;
+            }
+            self.behavior.move_item_stack_to(
+                &mut guard,
+                &mut payment,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                true,
+            );
+            self.behavior.slots[slot_index].set_item(&mut guard, payment);
+        }
+
+        self.fill_payment_slot(&mut guard, slots::PAYMENT_A_SLOT, &offer.buy1);
+        if let Some(buy2) = &offer.buy2 {
+            self.fill_payment_slot(&mut guard, slots::PAYMENT_B_SLOT, buy2);
+        }
+        drop(guard);
+
+        self.update_result();
+    }
+}
+
+impl MenuInstance for MerchantMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::MERCHANT
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating a merchant menu.
+pub struct MerchantMenuProvider {
+    inventory: SyncPlayerInv,
+    title: TextComponent,
+    state: SyncMerchantState,
+}
+
+impl MerchantMenuProvider {
+    /// Creates a new merchant menu provider titled `title`.
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, title: TextComponent, state: MerchantState) -> Self {
+        Self {
+            inventory,
+            title,
+            state: Arc::new(SyncMutex::new(state)),
+        }
+    }
+
+    /// The packet that fills the trading screen of the menu this provider
+    /// created.
+    #[must_use]
+    pub fn offers_packet(&self) -> CMerchantOffers {
+        self.state.lock().offers_packet()
+    }
+}
+
+impl MenuProvider for MerchantMenuProvider {
+    fn title(&self) -> TextComponent {
+        self.title.clone()
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(MerchantMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.state.clone(),
+        ))
+    }
+}
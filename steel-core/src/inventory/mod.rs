@@ -3,19 +3,39 @@
 //! This module provides the core inventory system including containers,
 //! menus, crafting, equipment, and recipes.
 
+pub mod anvil_menu;
+pub mod brewing_stand_menu;
+pub mod cartography_table_menu;
 pub mod chest_menu;
 pub mod container;
 pub mod crafting;
 pub mod crafting_menu;
+pub mod enchantment_menu;
 pub mod equipment;
+pub mod furnace_menu;
+pub mod grindstone_menu;
 pub mod inventory_menu;
 pub mod lock;
+pub mod loom_menu;
 pub mod menu;
 pub mod menu_provider;
+pub mod merchant_menu;
 pub mod recipe_manager;
 pub mod slot;
+pub mod smithing_menu;
+pub mod stonecutter_menu;
 
+pub use anvil_menu::{AnvilMenu, AnvilMenuProvider};
+pub use brewing_stand_menu::{BrewingStandMenu, BrewingStandMenuProvider};
+pub use cartography_table_menu::{CartographyTableMenu, CartographyTableMenuProvider};
 pub use chest_menu::{ChestMenu, ChestMenuProvider};
 pub use crafting_menu::{CraftingMenu, CraftingMenuProvider};
+pub use enchantment_menu::{EnchantmentMenu, EnchantmentMenuProvider};
+pub use furnace_menu::{FurnaceMenu, FurnaceMenuProvider};
+pub use grindstone_menu::{GrindstoneMenu, GrindstoneMenuProvider};
 pub use lock::SyncPlayerInv;
+pub use loom_menu::{LoomMenu, LoomMenuProvider};
 pub use menu_provider::{MenuInstance, MenuProvider};
+pub use merchant_menu::{MerchantMenu, MerchantMenuProvider};
+pub use smithing_menu::{SmithingMenu, SmithingMenuProvider};
+pub use stonecutter_menu::{StonecutterMenu, StonecutterMenuProvider};
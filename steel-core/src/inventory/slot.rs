@@ -9,16 +9,32 @@
 use std::{mem, sync::Arc};
 
 use enum_dispatch::enum_dispatch;
-use steel_registry::data_components::vanilla_components::EquippableSlot;
+use steel_registry::data_components::vanilla_components::{
+    EquippableSlot, MAP_ID, PROVIDES_BANNER_PATTERNS,
+};
 use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_items::ITEMS;
+use steel_registry::{REGISTRY, level_events, sound_events};
+use steel_utils::BlockPos;
 use steel_utils::locks::SyncMutex;
 
+use crate::behavior::blocks::{AnvilBlock, GrindstoneBlock};
+use crate::block_entity::entities::{FurnaceBlockEntity, is_bottle, is_brewing_fuel};
+use crate::entity::Entity;
 use crate::inventory::SyncPlayerInv;
+use crate::inventory::anvil_menu::SyncAnvilState;
+use crate::inventory::cartography_table_menu::{self, is_additional_item};
 use crate::inventory::container::Container;
 use crate::inventory::crafting::{CraftingContainer, ResultContainer};
-use crate::inventory::lock::{ContainerId, ContainerLockGuard, ContainerRef};
+use crate::inventory::grindstone_menu::get_experience_amount;
+use crate::inventory::lock::{ContainerId, ContainerLockGuard, ContainerRef, GenericContainer};
+use crate::inventory::loom_menu::{self, dye_color, is_banner};
+use crate::inventory::merchant_menu::SyncMerchantState;
 use crate::inventory::recipe_manager;
+use crate::inventory::smithing_menu;
 use crate::player::Player;
+use crate::player::stats::{CustomStat, StatKey};
+use crate::world::map_data;
 
 /// A synchronized crafting container.
 pub type SyncCraftingContainer = Arc<SyncMutex<CraftingContainer>>;
@@ -591,13 +607,13 @@ impl Slot for CraftingResultSlot {
     fn on_take(
         &self,
         guard: &mut ContainerLockGuard,
-        _stack: &ItemStack,
-        _player: &Player,
+        stack: &ItemStack,
+        player: &Player,
     ) -> Option<ItemStack> {
-        // TODO: Add statistics/achievement tracking here.
-        // Java calls checkTakeAchievements(carried) which triggers:
-        // - carried.onCraftedBy(player, removeCount) for achievements
-        // - recipeCraftingHolder.awardUsedRecipes(player, items) for recipe unlocks
+        // Java's checkTakeAchievements -> ItemStack.onCraftedBy
+        player.award_stat(StatKey::crafted(stack.item), stack.count);
+        // TODO: Add achievement tracking here.
+        // Java also calls recipeCraftingHolder.awardUsedRecipes(player, items) for recipe unlocks
 
         let mut remainder_overflow: Vec<ItemStack> = Vec::new();
         let crafting_id = ContainerId::from_arc(&self.crafting_container);
@@ -675,16 +691,14 @@ impl Slot for CraftingResultSlot {
             .expect("result container not locked")
             .set_item(0, result_stack);
 
-        // Return overflow remainders
-        if remainder_overflow.is_empty() {
-            None
-        } else if remainder_overflow.len() == 1 {
-            Some(remainder_overflow.remove(0))
-        } else {
-            // Multiple different remainders - return the first one
-            // The caller should ideally handle multiple remainders, but this is rare
-            Some(remainder_overflow.remove(0))
+        // Return the first overflow remainder to the caller. Any further ones are
+        // given back to the player here, like Java's `placeItemBackInInventory`.
+        let mut overflow = remainder_overflow.into_iter();
+        let first = overflow.next();
+        for remainder in overflow {
+            player.add_item_or_drop_with_guard(guard, remainder);
         }
+        first
     }
 
     /// Crafting result slots are "fake" - they don't persist items.
@@ -693,31 +707,1790 @@ impl Slot for CraftingResultSlot {
     }
 }
 
-/// Enum of all slot types that implement the Slot trait.
-#[enum_dispatch(Slot)]
-pub enum SlotType {
-    /// Normal inventory slot with no restrictions.
-    Normal(NormalSlot),
-    /// Armor slot that only accepts armor items.
-    Armor(ArmorSlot),
-    /// Crafting grid slot for crafting input.
-    CraftingGrid(CraftingGridSlot),
-    /// Crafting result slot (fake, doesn't persist items).
-    CraftingResult(CraftingResultSlot),
+/// The anvil output slot.
+///
+/// Taking the result consumes the inputs and may wear down the anvil.
+pub struct AnvilResultSlot {
+    result_container: SyncResultContainer,
+    input_container: GenericContainer,
+    state: SyncAnvilState,
+    /// The position of the anvil block.
+    block_pos: BlockPos,
 }
 
-impl SlotType {
-    /// Returns all container references for this slot.
-    /// For most slots this is just one container, but crafting slots
-    /// reference both the crafting grid and result containers.
+impl AnvilResultSlot {
+    /// Creates a new anvil result slot.
+    pub fn new(
+        result_container: SyncResultContainer,
+        input_container: GenericContainer,
+        state: SyncAnvilState,
+        block_pos: BlockPos,
+    ) -> Self {
+        Self {
+            result_container,
+            input_container,
+            state,
+            block_pos,
+        }
+    }
+
+    /// Returns a reference to the result container.
     #[must_use]
-    pub fn all_container_refs(&self) -> Vec<ContainerRef> {
-        match self {
-            SlotType::Normal(s) => vec![s.container_ref()],
-            SlotType::Armor(s) => vec![s.container_ref()],
-            SlotType::CraftingGrid(s) => vec![s.container_ref(), s.result_container_ref()],
-            SlotType::CraftingResult(s) => {
-                vec![s.result_container_ref(), s.crafting_container_ref()]
+    pub fn result_container_ref(&self) -> ContainerRef {
+        ContainerRef::ResultContainer(Arc::clone(&self.result_container))
+    }
+
+    /// Returns a reference to the input container.
+    #[must_use]
+    pub fn input_container_ref(&self) -> ContainerRef {
+        ContainerRef::Other(Arc::clone(&self.input_container))
+    }
+}
+
+impl Slot for AnvilResultSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item(0)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item_mut(0)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_item(0, stack);
+    }
+
+    /// Cannot place items directly in the result slot.
+    fn may_place(&self, _stack: &ItemStack) -> bool {
+        false
+    }
+
+    /// The result can only be taken while it costs something.
+    ///
+    /// TODO: Also require `cost` experience levels outside of creative once
+    /// players track experience.
+    fn may_pickup(&self) -> bool {
+        self.state.lock().cost > 0
+    }
+
+    /// Result slots don't allow partial removal.
+    fn allow_modification(&self, _guard: &ContainerLockGuard) -> bool {
+        false
+    }
+
+    /// Always takes the entire result stack.
+    fn remove(&self, guard: &mut ContainerLockGuard, _amount: i32) -> ItemStack {
+        mem::take(self.get_item_mut(guard))
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_changed();
+    }
+
+    fn get_container_slot(&self) -> usize {
+        0
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_max_stack_size()
+    }
+
+    /// Consumes the inputs and uses up the anvil.
+    ///
+    /// Based on Java's `AnvilMenu::onTake`.
+    fn on_take(
+        &self,
+        guard: &mut ContainerLockGuard,
+        _stack: &ItemStack,
+        player: &Player,
+    ) -> Option<ItemStack> {
+        // TODO: Take `cost` experience levels outside of creative once players
+        // track experience.
+        let mut state = self.state.lock();
+        let inputs = guard
+            .get_mut(ContainerId::from_arc(&self.input_container))
+            .expect("container not locked");
+
+        inputs.set_item(0, ItemStack::empty());
+        if state.repair_item_count_cost > 0 {
+            let addition = inputs.get_item_mut(1);
+            if addition.count() > state.repair_item_count_cost {
+                addition.shrink(state.repair_item_count_cost);
+            } else {
+                *addition = ItemStack::empty();
+            }
+        } else if !state.only_renaming {
+            inputs.set_item(1, ItemStack::empty());
+        }
+        state.cost = 0;
+        drop(state);
+
+        AnvilBlock::on_used(&player.world(), self.block_pos, player);
+        None
+    }
+
+    /// Anvil result slots are "fake" - the result is recomputed from the inputs.
+    fn is_fake(&self) -> bool {
+        true
+    }
+}
+
+/// The result slot of a merchant menu.
+/// Taking the result pays with the payment slots and uses up the trade.
+///
+/// Based on Java's `MerchantResultSlot`.
+pub struct MerchantResultSlot {
+    result_container: SyncResultContainer,
+    payment_container: GenericContainer,
+    state: SyncMerchantState,
+}
+
+impl MerchantResultSlot {
+    /// Creates a new merchant result slot.
+    pub fn new(
+        result_container: SyncResultContainer,
+        payment_container: GenericContainer,
+        state: SyncMerchantState,
+    ) -> Self {
+        Self {
+            result_container,
+            payment_container,
+            state,
+        }
+    }
+
+    /// Returns a reference to the result container.
+    #[must_use]
+    pub fn result_container_ref(&self) -> ContainerRef {
+        ContainerRef::ResultContainer(Arc::clone(&self.result_container))
+    }
+
+    /// Returns a reference to the payment container.
+    #[must_use]
+    pub fn payment_container_ref(&self) -> ContainerRef {
+        ContainerRef::Other(Arc::clone(&self.payment_container))
+    }
+}
+
+impl Slot for MerchantResultSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item(0)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item_mut(0)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_item(0, stack);
+    }
+
+    /// Cannot place items directly in the result slot.
+    fn may_place(&self, _stack: &ItemStack) -> bool {
+        false
+    }
+
+    /// Result slots don't allow partial removal.
+    fn allow_modification(&self, _guard: &ContainerLockGuard) -> bool {
+        false
+    }
+
+    /// Always takes the entire result stack.
+    fn remove(&self, guard: &mut ContainerLockGuard, _amount: i32) -> ItemStack {
+        mem::take(self.get_item_mut(guard))
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_changed();
+    }
+
+    fn get_container_slot(&self) -> usize {
+        0
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_max_stack_size()
+    }
+
+    /// Pays for the trade and lets the villager know it was used.
+    ///
+    /// Based on Java's `MerchantResultSlot::onTake`.
+    fn on_take(
+        &self,
+        guard: &mut ContainerLockGuard,
+        _stack: &ItemStack,
+        player: &Player,
+    ) -> Option<ItemStack> {
+        let mut state = self.state.lock();
+        let index = state.active?;
+        let offer = state.offers[index].clone();
+
+        let payments = guard
+            .get_mut(ContainerId::from_arc(&self.payment_container))
+            .expect("container not locked");
+        // A lone payment in the second slot pays for the first cost
+        let (first, second) = if payments.get_item(0).is_empty() {
+            (1, 0)
+        } else {
+            (0, 1)
+        };
+        payments.get_item_mut(first).shrink(offer.buy1.count());
+        if let Some(buy2) = &offer.buy2 {
+            payments.get_item_mut(second).shrink(buy2.count());
+        }
+
+        player.award_stat(StatKey::custom(CustomStat::TradedWithVillager), 1);
+        if player.world().complete_villager_trade(&mut state, index) {
+            player.connection.send_packet(state.offers_packet());
+        }
+        None
+    }
+
+    /// Merchant result slots are "fake" - the result is recomputed from the payments.
+    fn is_fake(&self) -> bool {
+        true
+    }
+}
+
+/// The fuel slot of a furnace menu. Only takes fuel and empty buckets.
+///
+/// Based on Java's `FurnaceFuelSlot`.
+pub struct FurnaceFuelSlot {
+    inner: NormalSlot,
+}
+
+impl FurnaceFuelSlot {
+    /// Creates a new fuel slot for the furnace behind `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the furnace's container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for FurnaceFuelSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        REGISTRY.fuels.is_fuel(stack) || stack.is(&ITEMS.bucket)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+}
+
+/// The result slot of a furnace menu.
+/// Taking the cooked items drops the experience the furnace stored for them.
+///
+/// Based on Java's `FurnaceResultSlot`.
+pub struct FurnaceResultSlot {
+    inner: NormalSlot,
+}
+
+impl FurnaceResultSlot {
+    /// Creates a new result slot for the furnace behind `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the furnace's container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for FurnaceResultSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    /// Cannot place items directly in the result slot.
+    fn may_place(&self, _stack: &ItemStack) -> bool {
+        false
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+
+    /// Drops the stored experience at the player.
+    ///
+    /// Based on Java's `FurnaceResultSlot::checkTakeAchievements`.
+    fn on_take(
+        &self,
+        guard: &mut ContainerLockGuard,
+        _stack: &ItemStack,
+        player: &Player,
+    ) -> Option<ItemStack> {
+        if let Some(furnace) = guard
+            .get_block_entity_mut(self.inner.container_ref().container_id())
+            .and_then(|be| be.as_any_mut().downcast_mut::<FurnaceBlockEntity>())
+        {
+            furnace.award_used_recipes(&player.world(), player.get_position());
+        }
+        None
+    }
+}
+
+/// A bottle slot of a brewing stand menu. Holds a single potion or glass bottle.
+///
+/// Based on Java's `BrewingStandMenu.PotionSlot`.
+pub struct PotionSlot {
+    inner: NormalSlot,
+}
+
+impl PotionSlot {
+    /// Creates a new bottle slot for the brewing stand behind `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the brewing stand's container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for PotionSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        is_bottle(stack)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    /// Bottles don't stack in the brewing stand.
+    fn get_max_stack_size(&self, _guard: &ContainerLockGuard) -> i32 {
+        1
+    }
+}
+
+/// The ingredient slot of a brewing stand menu. Only takes brewing ingredients.
+///
+/// Based on Java's `BrewingStandMenu.IngredientsSlot`.
+pub struct BrewingIngredientSlot {
+    inner: NormalSlot,
+}
+
+impl BrewingIngredientSlot {
+    /// Creates a new ingredient slot for the brewing stand behind `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the brewing stand's container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for BrewingIngredientSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        REGISTRY.brewing.is_ingredient(stack)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+}
+
+/// The fuel slot of a brewing stand menu. Only takes blaze powder.
+///
+/// Based on Java's `BrewingStandMenu.FuelSlot`.
+pub struct BrewingFuelSlot {
+    inner: NormalSlot,
+}
+
+impl BrewingFuelSlot {
+    /// Creates a new fuel slot for the brewing stand behind `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the brewing stand's container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for BrewingFuelSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        is_brewing_fuel(stack)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+}
+
+/// The item slot of an enchanting table menu. Holds the single item being
+/// enchanted.
+///
+/// Based on the anonymous item slot in Java's `EnchantmentMenu`.
+pub struct EnchantingItemSlot {
+    inner: NormalSlot,
+}
+
+impl EnchantingItemSlot {
+    /// Creates a new item slot for the enchanting table's input `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the enchanting table's input container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for EnchantingItemSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    /// Only one item is enchanted at a time.
+    fn get_max_stack_size(&self, _guard: &ContainerLockGuard) -> i32 {
+        1
+    }
+}
+
+/// The lapis slot of an enchanting table menu. Only takes lapis lazuli.
+///
+/// Based on the anonymous lapis slot in Java's `EnchantmentMenu`.
+pub struct LapisSlot {
+    inner: NormalSlot,
+}
+
+impl LapisSlot {
+    /// Creates a new lapis slot for the enchanting table's input `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the enchanting table's input container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for LapisSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        stack.is(&ITEMS.lapis_lazuli)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+}
+
+/// An input slot of a grindstone menu. Only takes items that can be
+/// repaired or have enchantments to remove.
+///
+/// Based on the anonymous input slots in Java's `GrindstoneMenu`.
+pub struct GrindstoneInputSlot {
+    inner: NormalSlot,
+}
+
+impl GrindstoneInputSlot {
+    /// Creates a new input slot for the grindstone's input `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the grindstone's input container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for GrindstoneInputSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        stack.is_damageable_item() || stack.has_any_enchantments()
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+}
+
+/// The result slot of a grindstone menu.
+/// Taking the result uses up both inputs and pays out the experience of the
+/// enchantments removed.
+///
+/// Based on the anonymous result slot in Java's `GrindstoneMenu`.
+pub struct GrindstoneResultSlot {
+    result_container: SyncResultContainer,
+    input_container: GenericContainer,
+    /// The position of the grindstone block.
+    block_pos: BlockPos,
+}
+
+impl GrindstoneResultSlot {
+    /// Creates a new grindstone result slot.
+    pub fn new(
+        result_container: SyncResultContainer,
+        input_container: GenericContainer,
+        block_pos: BlockPos,
+    ) -> Self {
+        Self {
+            result_container,
+            input_container,
+            block_pos,
+        }
+    }
+
+    /// Returns a reference to the result container.
+    #[must_use]
+    pub fn result_container_ref(&self) -> ContainerRef {
+        ContainerRef::ResultContainer(Arc::clone(&self.result_container))
+    }
+
+    /// Returns a reference to the input container.
+    #[must_use]
+    pub fn input_container_ref(&self) -> ContainerRef {
+        ContainerRef::Other(Arc::clone(&self.input_container))
+    }
+}
+
+impl Slot for GrindstoneResultSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item(0)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item_mut(0)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_item(0, stack);
+    }
+
+    /// Cannot place items directly in the result slot.
+    fn may_place(&self, _stack: &ItemStack) -> bool {
+        false
+    }
+
+    /// Result slots don't allow partial removal.
+    fn allow_modification(&self, _guard: &ContainerLockGuard) -> bool {
+        false
+    }
+
+    /// Always takes the entire result stack.
+    fn remove(&self, guard: &mut ContainerLockGuard, _amount: i32) -> ItemStack {
+        mem::take(self.get_item_mut(guard))
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_changed();
+    }
+
+    fn get_container_slot(&self) -> usize {
+        0
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_max_stack_size()
+    }
+
+    /// Consumes both inputs and drops the experience of the removed
+    /// enchantments.
+    ///
+    /// Based on Java's `GrindstoneMenu` result slot `onTake`.
+    fn on_take(
+        &self,
+        guard: &mut ContainerLockGuard,
+        _stack: &ItemStack,
+        player: &Player,
+    ) -> Option<ItemStack> {
+        let inputs = guard
+            .get_mut(ContainerId::from_arc(&self.input_container))
+            .expect("container not locked");
+
+        let experience = get_experience_amount(inputs.get_item(0), inputs.get_item(1));
+        inputs.set_item(0, ItemStack::empty());
+        inputs.set_item(1, ItemStack::empty());
+
+        GrindstoneBlock::on_used(&player.world(), self.block_pos, experience);
+        None
+    }
+
+    /// Grindstone result slots are "fake" - the result is recomputed from the inputs.
+    fn is_fake(&self) -> bool {
+        true
+    }
+}
+
+/// The banner slot of a loom menu. Only takes banners.
+///
+/// Based on the anonymous banner slot in Java's `LoomMenu`.
+pub struct LoomBannerSlot {
+    inner: NormalSlot,
+}
+
+impl LoomBannerSlot {
+    /// Creates a new slot for the loom's input `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the loom's input container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for LoomBannerSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        is_banner(stack)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+}
+
+/// The dye slot of a loom menu. Only takes dyes.
+///
+/// Based on the anonymous dye slot in Java's `LoomMenu`.
+pub struct LoomDyeSlot {
+    inner: NormalSlot,
+}
+
+impl LoomDyeSlot {
+    /// Creates a new slot for the loom's input `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the loom's input container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for LoomDyeSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        dye_color(stack).is_some()
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+}
+
+/// The pattern slot of a loom menu. Only takes banner pattern items.
+///
+/// Based on the anonymous pattern slot in Java's `LoomMenu`.
+pub struct LoomPatternSlot {
+    inner: NormalSlot,
+}
+
+impl LoomPatternSlot {
+    /// Creates a new slot for the loom's input `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the loom's input container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for LoomPatternSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        stack.has(PROVIDES_BANNER_PATTERNS)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+}
+
+/// The result slot of a loom menu.
+/// Taking the result uses up one banner and one dye; the pattern item is kept.
+///
+/// Based on the anonymous result slot in Java's `LoomMenu`.
+pub struct LoomResultSlot {
+    result_container: SyncResultContainer,
+    input_container: GenericContainer,
+    /// The position of the loom block.
+    block_pos: BlockPos,
+}
+
+impl LoomResultSlot {
+    /// Creates a new loom result slot.
+    pub fn new(
+        result_container: SyncResultContainer,
+        input_container: GenericContainer,
+        block_pos: BlockPos,
+    ) -> Self {
+        Self {
+            result_container,
+            input_container,
+            block_pos,
+        }
+    }
+
+    /// Returns a reference to the result container.
+    #[must_use]
+    pub fn result_container_ref(&self) -> ContainerRef {
+        ContainerRef::ResultContainer(Arc::clone(&self.result_container))
+    }
+
+    /// Returns a reference to the input container.
+    #[must_use]
+    pub fn input_container_ref(&self) -> ContainerRef {
+        ContainerRef::Other(Arc::clone(&self.input_container))
+    }
+}
+
+impl Slot for LoomResultSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item(0)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item_mut(0)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_item(0, stack);
+    }
+
+    /// Cannot place items directly in the result slot.
+    fn may_place(&self, _stack: &ItemStack) -> bool {
+        false
+    }
+
+    /// Result slots don't allow partial removal.
+    fn allow_modification(&self, _guard: &ContainerLockGuard) -> bool {
+        false
+    }
+
+    /// Always takes the entire result stack.
+    fn remove(&self, guard: &mut ContainerLockGuard, _amount: i32) -> ItemStack {
+        mem::take(self.get_item_mut(guard))
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_changed();
+    }
+
+    fn get_container_slot(&self) -> usize {
+        0
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_max_stack_size()
+    }
+
+    /// Consumes one banner and one dye.
+    ///
+    /// Based on Java's `LoomMenu` result slot `onTake`.
+    fn on_take(
+        &self,
+        guard: &mut ContainerLockGuard,
+        _stack: &ItemStack,
+        player: &Player,
+    ) -> Option<ItemStack> {
+        let inputs = guard
+            .get_mut(ContainerId::from_arc(&self.input_container))
+            .expect("container not locked");
+
+        inputs.get_item_mut(loom_menu::slots::BANNER_SLOT).shrink(1);
+        inputs.get_item_mut(loom_menu::slots::DYE_SLOT).shrink(1);
+        inputs.set_changed();
+
+        player.world().play_block_sound(
+            sound_events::UI_LOOM_TAKE_RESULT,
+            self.block_pos,
+            1.0,
+            1.0,
+            None,
+        );
+        None
+    }
+
+    /// Loom result slots are "fake" - the result is recomputed from the inputs.
+    fn is_fake(&self) -> bool {
+        true
+    }
+}
+
+/// The result slot of a stonecutter menu.
+/// Taking the result uses up one input item; the same result stays in the
+/// slot while there is input left.
+///
+/// Based on the anonymous result slot in Java's `StonecutterMenu`.
+pub struct StonecutterResultSlot {
+    result_container: SyncResultContainer,
+    input_container: GenericContainer,
+    /// The position of the stonecutter block.
+    block_pos: BlockPos,
+}
+
+impl StonecutterResultSlot {
+    /// Creates a new stonecutter result slot.
+    pub fn new(
+        result_container: SyncResultContainer,
+        input_container: GenericContainer,
+        block_pos: BlockPos,
+    ) -> Self {
+        Self {
+            result_container,
+            input_container,
+            block_pos,
+        }
+    }
+
+    /// Returns a reference to the result container.
+    #[must_use]
+    pub fn result_container_ref(&self) -> ContainerRef {
+        ContainerRef::ResultContainer(Arc::clone(&self.result_container))
+    }
+
+    /// Returns a reference to the input container.
+    #[must_use]
+    pub fn input_container_ref(&self) -> ContainerRef {
+        ContainerRef::Other(Arc::clone(&self.input_container))
+    }
+}
+
+impl Slot for StonecutterResultSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item(0)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item_mut(0)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_item(0, stack);
+    }
+
+    /// Cannot place items directly in the result slot.
+    fn may_place(&self, _stack: &ItemStack) -> bool {
+        false
+    }
+
+    /// Result slots don't allow partial removal.
+    fn allow_modification(&self, _guard: &ContainerLockGuard) -> bool {
+        false
+    }
+
+    /// Always takes the entire result stack.
+    fn remove(&self, guard: &mut ContainerLockGuard, _amount: i32) -> ItemStack {
+        mem::take(self.get_item_mut(guard))
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_changed();
+    }
+
+    fn get_container_slot(&self) -> usize {
+        0
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_max_stack_size()
+    }
+
+    /// Consumes one input item and puts the result back if there is input
+    /// left for another.
+    ///
+    /// Based on Java's `StonecutterMenu` result slot `onTake`.
+    fn on_take(
+        &self,
+        guard: &mut ContainerLockGuard,
+        stack: &ItemStack,
+        player: &Player,
+    ) -> Option<ItemStack> {
+        // Java's ItemStack.onCraftedBy
+        player.award_stat(StatKey::crafted(stack.item), stack.count);
+
+        let has_input_left = {
+            let inputs = guard
+                .get_mut(ContainerId::from_arc(&self.input_container))
+                .expect("container not locked");
+            let input = inputs.get_item_mut(0);
+            input.shrink(1);
+            let has_input_left = !input.is_empty();
+            inputs.set_changed();
+            has_input_left
+        };
+
+        if has_input_left {
+            self.set_item(guard, stack.clone());
+        }
+
+        player.world().play_block_sound(
+            sound_events::UI_STONECUTTER_TAKE_RESULT,
+            self.block_pos,
+            1.0,
+            1.0,
+            None,
+        );
+        None
+    }
+
+    /// Stonecutter result slots are "fake" - the result is recomputed from
+    /// the input.
+    fn is_fake(&self) -> bool {
+        true
+    }
+}
+
+/// The map slot of a cartography table menu. Only takes filled maps.
+///
+/// Based on the anonymous map slot in Java's `CartographyTableMenu`.
+pub struct CartographyMapSlot {
+    inner: NormalSlot,
+}
+
+impl CartographyMapSlot {
+    /// Creates a new slot for the cartography table's input `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the cartography table's input container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for CartographyMapSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        stack.has(MAP_ID)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+}
+
+/// The additional slot of a cartography table menu. Only takes paper, empty
+/// maps and glass panes.
+///
+/// Based on the anonymous additional slot in Java's `CartographyTableMenu`.
+pub struct CartographyAdditionalSlot {
+    inner: NormalSlot,
+}
+
+impl CartographyAdditionalSlot {
+    /// Creates a new slot for the cartography table's input `container`.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the cartography table's input container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for CartographyAdditionalSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        is_additional_item(stack)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+}
+
+/// The result slot of a cartography table menu.
+/// Taking the result uses up one map and one additional item, and zooms out
+/// or locks the map the result was marked for.
+///
+/// Based on the anonymous result slot in Java's `CartographyTableMenu`.
+pub struct CartographyResultSlot {
+    result_container: SyncResultContainer,
+    input_container: GenericContainer,
+    /// The position of the cartography table block.
+    block_pos: BlockPos,
+}
+
+impl CartographyResultSlot {
+    /// Creates a new cartography table result slot.
+    pub fn new(
+        result_container: SyncResultContainer,
+        input_container: GenericContainer,
+        block_pos: BlockPos,
+    ) -> Self {
+        Self {
+            result_container,
+            input_container,
+            block_pos,
+        }
+    }
+
+    /// Returns a reference to the result container.
+    #[must_use]
+    pub fn result_container_ref(&self) -> ContainerRef {
+        ContainerRef::ResultContainer(Arc::clone(&self.result_container))
+    }
+
+    /// Returns a reference to the input container.
+    #[must_use]
+    pub fn input_container_ref(&self) -> ContainerRef {
+        ContainerRef::Other(Arc::clone(&self.input_container))
+    }
+}
+
+impl Slot for CartographyResultSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item(0)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item_mut(0)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_item(0, stack);
+    }
+
+    /// Cannot place items directly in the result slot.
+    fn may_place(&self, _stack: &ItemStack) -> bool {
+        false
+    }
+
+    /// Result slots don't allow partial removal.
+    fn allow_modification(&self, _guard: &ContainerLockGuard) -> bool {
+        false
+    }
+
+    /// Always takes the entire result stack, with its map zoomed out or
+    /// locked.
+    ///
+    /// Java does this in `onTake`, which can change the carried stack.
+    fn remove(&self, guard: &mut ContainerLockGuard, _amount: i32) -> ItemStack {
+        let mut taken = mem::take(self.get_item_mut(guard));
+        map_data::apply_post_processing(&mut taken);
+        taken
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_changed();
+    }
+
+    fn get_container_slot(&self) -> usize {
+        0
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_max_stack_size()
+    }
+
+    /// Consumes one map and one additional item.
+    ///
+    /// Based on Java's `CartographyTableMenu` result slot `onTake`.
+    fn on_take(
+        &self,
+        guard: &mut ContainerLockGuard,
+        stack: &ItemStack,
+        player: &Player,
+    ) -> Option<ItemStack> {
+        let inputs = guard
+            .get_mut(ContainerId::from_arc(&self.input_container))
+            .expect("container not locked");
+
+        inputs
+            .get_item_mut(cartography_table_menu::slots::MAP_SLOT)
+            .shrink(1);
+        inputs
+            .get_item_mut(cartography_table_menu::slots::ADDITIONAL_SLOT)
+            .shrink(1);
+        inputs.set_changed();
+
+        player.award_stat(StatKey::crafted(stack.item), stack.count);
+        player.world().play_block_sound(
+            sound_events::UI_CARTOGRAPHY_TABLE_TAKE_RESULT,
+            self.block_pos,
+            1.0,
+            1.0,
+            None,
+        );
+        None
+    }
+
+    /// Cartography table result slots are "fake" - the result is recomputed
+    /// from the inputs.
+    fn is_fake(&self) -> bool {
+        true
+    }
+}
+
+/// One of the input slots of a smithing table menu. Only takes items that
+/// go in that slot of some smithing recipe.
+///
+/// Based on the input slots Java's `SmithingMenu` sets up in
+/// `createInputSlotDefinitions`.
+pub struct SmithingInputSlot {
+    inner: NormalSlot,
+}
+
+impl SmithingInputSlot {
+    /// Creates a new slot for the smithing table's input `container`. The
+    /// index picks whether it takes templates, bases or additions.
+    pub fn new(container: ContainerRef, index: usize) -> Self {
+        Self {
+            inner: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the smithing table's input container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.inner.container_ref()
+    }
+}
+
+impl Slot for SmithingInputSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.inner.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.inner.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.inner.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        smithing_menu::accepts_in_slot(self.inner.get_container_slot(), stack)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.inner.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.inner.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.inner.get_max_stack_size(guard)
+    }
+}
+
+/// The result slot of a smithing table menu.
+/// Taking the result uses up one of each input.
+///
+/// Based on the result slot of Java's `ItemCombinerMenu` and
+/// `SmithingMenu::onTake`.
+pub struct SmithingResultSlot {
+    result_container: SyncResultContainer,
+    input_container: GenericContainer,
+    /// The position of the smithing table block.
+    block_pos: BlockPos,
+}
+
+impl SmithingResultSlot {
+    /// Creates a new smithing table result slot.
+    pub fn new(
+        result_container: SyncResultContainer,
+        input_container: GenericContainer,
+        block_pos: BlockPos,
+    ) -> Self {
+        Self {
+            result_container,
+            input_container,
+            block_pos,
+        }
+    }
+
+    /// Returns a reference to the result container.
+    #[must_use]
+    pub fn result_container_ref(&self) -> ContainerRef {
+        ContainerRef::ResultContainer(Arc::clone(&self.result_container))
+    }
+
+    /// Returns a reference to the input container.
+    #[must_use]
+    pub fn input_container_ref(&self) -> ContainerRef {
+        ContainerRef::Other(Arc::clone(&self.input_container))
+    }
+}
+
+impl Slot for SmithingResultSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item(0)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item_mut(0)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_item(0, stack);
+    }
+
+    /// Cannot place items directly in the result slot.
+    fn may_place(&self, _stack: &ItemStack) -> bool {
+        false
+    }
+
+    /// Result slots don't allow partial removal.
+    fn allow_modification(&self, _guard: &ContainerLockGuard) -> bool {
+        false
+    }
+
+    /// Always takes the entire result stack.
+    fn remove(&self, guard: &mut ContainerLockGuard, _amount: i32) -> ItemStack {
+        mem::take(self.get_item_mut(guard))
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_changed();
+    }
+
+    fn get_container_slot(&self) -> usize {
+        0
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_max_stack_size()
+    }
+
+    /// Consumes one template, base and addition.
+    ///
+    /// Based on Java's `SmithingMenu::onTake`.
+    fn on_take(
+        &self,
+        guard: &mut ContainerLockGuard,
+        stack: &ItemStack,
+        player: &Player,
+    ) -> Option<ItemStack> {
+        let inputs = guard
+            .get_mut(ContainerId::from_arc(&self.input_container))
+            .expect("container not locked");
+
+        for slot in [
+            smithing_menu::slots::TEMPLATE_SLOT,
+            smithing_menu::slots::BASE_SLOT,
+            smithing_menu::slots::ADDITION_SLOT,
+        ] {
+            inputs.get_item_mut(slot).shrink(1);
+        }
+        inputs.set_changed();
+
+        player.award_stat(StatKey::crafted(stack.item), stack.count);
+        player.world().level_event(
+            level_events::SOUND_SMITHING_TABLE_USED,
+            self.block_pos,
+            0,
+            None,
+        );
+        None
+    }
+
+    /// Smithing table result slots are "fake" - the result is recomputed
+    /// from the inputs.
+    fn is_fake(&self) -> bool {
+        true
+    }
+}
+
+/// Enum of all slot types that implement the Slot trait.
+#[enum_dispatch(Slot)]
+pub enum SlotType {
+    /// Normal inventory slot with no restrictions.
+    Normal(NormalSlot),
+    /// Armor slot that only accepts armor items.
+    Armor(ArmorSlot),
+    /// Crafting grid slot for crafting input.
+    CraftingGrid(CraftingGridSlot),
+    /// Crafting result slot (fake, doesn't persist items).
+    CraftingResult(CraftingResultSlot),
+    /// Anvil result slot (fake, doesn't persist items).
+    AnvilResult(AnvilResultSlot),
+    /// Merchant result slot (fake, doesn't persist items).
+    MerchantResult(MerchantResultSlot),
+    /// Furnace fuel slot that only accepts fuel.
+    FurnaceFuel(FurnaceFuelSlot),
+    /// Furnace result slot that drops the cooking experience when taken from.
+    FurnaceResult(FurnaceResultSlot),
+    /// Brewing stand bottle slot that holds a single potion.
+    Potion(PotionSlot),
+    /// Brewing stand ingredient slot that only accepts brewing ingredients.
+    BrewingIngredient(BrewingIngredientSlot),
+    /// Brewing stand fuel slot that only accepts blaze powder.
+    BrewingFuel(BrewingFuelSlot),
+    /// Enchanting table slot that holds the item being enchanted.
+    EnchantingItem(EnchantingItemSlot),
+    /// Enchanting table slot that only accepts lapis lazuli.
+    Lapis(LapisSlot),
+    /// Grindstone input slot that only accepts repairable or enchanted items.
+    GrindstoneInput(GrindstoneInputSlot),
+    /// Grindstone result slot (fake, doesn't persist items).
+    GrindstoneResult(GrindstoneResultSlot),
+    /// Loom slot that only accepts banners.
+    LoomBanner(LoomBannerSlot),
+    /// Loom slot that only accepts dyes.
+    LoomDye(LoomDyeSlot),
+    /// Loom slot that only accepts banner pattern items.
+    LoomPattern(LoomPatternSlot),
+    /// Loom result slot (fake, doesn't persist items).
+    LoomResult(LoomResultSlot),
+    /// Stonecutter result slot (fake, doesn't persist items).
+    StonecutterResult(StonecutterResultSlot),
+    /// Cartography table slot that only accepts filled maps.
+    CartographyMap(CartographyMapSlot),
+    /// Cartography table slot that only accepts paper, empty maps and glass
+    /// panes.
+    CartographyAdditional(CartographyAdditionalSlot),
+    /// Cartography table result slot (fake, doesn't persist items).
+    CartographyResult(CartographyResultSlot),
+    /// Smithing table slot that only accepts templates, bases or additions.
+    SmithingInput(SmithingInputSlot),
+    /// Smithing table result slot (fake, doesn't persist items).
+    SmithingResult(SmithingResultSlot),
+}
+
+impl SlotType {
+    /// Returns all container references for this slot.
+    /// For most slots this is just one container, but crafting slots
+    /// reference both the crafting grid and result containers.
+    #[must_use]
+    pub fn all_container_refs(&self) -> Vec<ContainerRef> {
+        match self {
+            SlotType::Normal(s) => vec![s.container_ref()],
+            SlotType::Armor(s) => vec![s.container_ref()],
+            SlotType::CraftingGrid(s) => vec![s.container_ref(), s.result_container_ref()],
+            SlotType::CraftingResult(s) => {
+                vec![s.result_container_ref(), s.crafting_container_ref()]
+            }
+            SlotType::AnvilResult(s) => vec![s.result_container_ref(), s.input_container_ref()],
+            SlotType::MerchantResult(s) => {
+                vec![s.result_container_ref(), s.payment_container_ref()]
+            }
+            SlotType::FurnaceFuel(s) => vec![s.container_ref()],
+            SlotType::FurnaceResult(s) => vec![s.container_ref()],
+            SlotType::Potion(s) => vec![s.container_ref()],
+            SlotType::BrewingIngredient(s) => vec![s.container_ref()],
+            SlotType::BrewingFuel(s) => vec![s.container_ref()],
+            SlotType::EnchantingItem(s) => vec![s.container_ref()],
+            SlotType::Lapis(s) => vec![s.container_ref()],
+            SlotType::GrindstoneInput(s) => vec![s.container_ref()],
+            SlotType::GrindstoneResult(s) => {
+                vec![s.result_container_ref(), s.input_container_ref()]
+            }
+            SlotType::LoomBanner(s) => vec![s.container_ref()],
+            SlotType::LoomDye(s) => vec![s.container_ref()],
+            SlotType::LoomPattern(s) => vec![s.container_ref()],
+            SlotType::LoomResult(s) => vec![s.result_container_ref(), s.input_container_ref()],
+            SlotType::StonecutterResult(s) => {
+                vec![s.result_container_ref(), s.input_container_ref()]
+            }
+            SlotType::CartographyMap(s) => vec![s.container_ref()],
+            SlotType::CartographyAdditional(s) => vec![s.container_ref()],
+            SlotType::CartographyResult(s) => {
+                vec![s.result_container_ref(), s.input_container_ref()]
+            }
+            SlotType::SmithingInput(s) => vec![s.container_ref()],
+            SlotType::SmithingResult(s) => {
+                vec![s.result_container_ref(), s.input_container_ref()]
             }
         }
     }
@@ -0,0 +1,335 @@
+//! The smithing table menu for upgrading gear to netherite and trimming
+//! armor.
+//!
+//! Slot layout (40 total):
+//! - Slot 0: Template
+//! - Slot 1: Base
+//! - Slot 2: Addition
+//! - Slot 3: Result
+//! - Slots 4-30: Main inventory (27 slots)
+//! - Slots 31-39: Hotbar (9 slots)
+
+use std::{mem, sync::Arc};
+
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::recipe::SmithingInput;
+use steel_registry::{REGISTRY, vanilla_menu_types};
+use steel_utils::locks::SyncMutex;
+use steel_utils::{BlockPos, translations};
+use text_components::TextComponent;
+
+use crate::inventory::{
+    SyncPlayerInv,
+    container::{Container, SimpleContainer},
+    crafting::ResultContainer,
+    lock::{ContainerId, ContainerLockGuard, ContainerRef, GenericContainer},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{
+        Slot, SlotType, SmithingInputSlot, SmithingResultSlot, SyncResultContainer,
+        add_standard_inventory_slots,
+    },
+};
+use crate::player::Player;
+
+/// Slot indices for the smithing table menu.
+pub mod slots {
+    /// Slot index for the template (slot 0).
+    pub const TEMPLATE_SLOT: usize = 0;
+    /// Slot index for the base item (slot 1).
+    pub const BASE_SLOT: usize = 1;
+    /// Slot index for the addition (slot 2).
+    pub const ADDITION_SLOT: usize = 2;
+    /// Slot index for the result (slot 3).
+    pub const RESULT_SLOT: usize = 3;
+    /// Start of main inventory (slot 4).
+    pub const INV_SLOT_START: usize = 4;
+    /// End of main inventory (slot 31, exclusive).
+    pub const INV_SLOT_END: usize = 31;
+    /// Start of hotbar (slot 31).
+    pub const HOTBAR_SLOT_START: usize = 31;
+    /// End of hotbar (slot 40, exclusive).
+    pub const HOTBAR_SLOT_END: usize = 40;
+    /// Total number of slots in the smithing table menu.
+    pub const TOTAL_SLOTS: usize = 40;
+}
+
+/// Returns true if some smithing recipe takes the item in the given input
+/// slot.
+#[must_use]
+pub fn accepts_in_slot(slot: usize, stack: &ItemStack) -> bool {
+    let recipes = REGISTRY.recipes.smithing();
+    match slot {
+        slots::TEMPLATE_SLOT => recipes.is_template(stack),
+        slots::BASE_SLOT => recipes.is_base(stack),
+        slots::ADDITION_SLOT => recipes.is_addition(stack),
+        _ => false,
+    }
+}
+
+/// The smithing table menu.
+///
+/// Based on Java's `SmithingMenu`.
+pub struct SmithingMenu {
+    behavior: MenuBehavior,
+    /// The template, base and addition slots.
+    input_slots: GenericContainer,
+    /// The result slot.
+    result_slots: SyncResultContainer,
+    /// The position of the smithing table block.
+    block_pos: BlockPos,
+    /// Index of the data slot telling the client all inputs are filled but
+    /// make nothing, 1 when they don't.
+    recipe_error_data: usize,
+}
+
+impl SmithingMenu {
+    /// Creates a new smithing table menu for a player.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `block_pos` - The position of the smithing table block
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, container_id: u8, block_pos: BlockPos) -> Self {
+        let mut menu_slots = Vec::with_capacity(slots::TOTAL_SLOTS);
+
+        let input_slots: GenericContainer = Arc::new(SyncMutex::new(SimpleContainer::new(3)));
+        let result_slots: SyncResultContainer = Arc::new(SyncMutex::new(ResultContainer::new()));
+
+        // Slots 0-2: Template, base and addition
+        for slot in [slots::TEMPLATE_SLOT, slots::BASE_SLOT, slots::ADDITION_SLOT] {
+            menu_slots.push(SlotType::SmithingInput(SmithingInputSlot::new(
+                ContainerRef::Other(input_slots.clone()),
+                slot,
+            )));
+        }
+
+        // Slot 3: Result
+        menu_slots.push(SlotType::SmithingResult(SmithingResultSlot::new(
+            result_slots.clone(),
+            input_slots.clone(),
+            block_pos,
+        )));
+
+        // Slots 4-39: Standard inventory (main inventory + hotbar)
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        let mut behavior =
+            MenuBehavior::new(menu_slots, container_id, Some(vanilla_menu_types::SMITHING));
+        let recipe_error_data = behavior.add_data_slot(0);
+
+        Self {
+            behavior,
+            input_slots,
+            result_slots,
+            block_pos,
+            recipe_error_data,
+        }
+    }
+
+    /// Returns the menu type for the smithing table.
+    #[must_use]
+    pub fn menu_type() -> MenuTypeRef {
+        vanilla_menu_types::SMITHING
+    }
+
+    /// Returns the position of the smithing table block.
+    #[must_use]
+    pub fn block_pos(&self) -> BlockPos {
+        self.block_pos
+    }
+
+    /// Returns the first input slot a shift-clicked item can go in.
+    ///
+    /// Based on Java's `SmithingMenu::findSlotToQuickMoveTo`.
+    fn slot_to_quick_move_to(stack: &ItemStack) -> Option<usize> {
+        [slots::TEMPLATE_SLOT, slots::BASE_SLOT, slots::ADDITION_SLOT]
+            .into_iter()
+            .find(|&slot| accepts_in_slot(slot, stack))
+    }
+}
+
+impl Menu for SmithingMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `ItemCombinerMenu::quickMoveStack`:
+    /// - Result slot (3) -> inventory (4-39), prefer existing stacks
+    /// - Input slots (0-2) -> inventory (4-39)
+    /// - Inventory (4-39) -> the first input slot that takes the item,
+    ///   otherwise between main inventory and hotbar
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        let moved = if slot_index == slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                true,
+            )
+        } else if slot_index < slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                false,
+            )
+        } else {
+            let (start, end) = if let Some(slot) = Self::slot_to_quick_move_to(&stack_mut) {
+                (slot, slots::RESULT_SLOT)
+            } else if slot_index < slots::INV_SLOT_END {
+                (slots::HOTBAR_SLOT_START, slots::HOTBAR_SLOT_END)
+            } else {
+                (slots::INV_SLOT_START, slots::INV_SLOT_END)
+            };
+            self.behavior
+                .move_item_stack_to(guard, &mut stack_mut, start, end, false)
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        if slot_index == slots::RESULT_SLOT
+            && let Some(remainder) =
+                self.behavior.slots[slot_index].on_take(guard, &clicked, player)
+        {
+            player.add_item_or_drop_with_guard(guard, remainder);
+        }
+
+        clicked
+    }
+
+    /// Prevents collecting items from the result slot.
+    fn can_take_item_for_pick_all(&self, _carried: &ItemStack, slot_index: usize) -> bool {
+        slot_index != slots::RESULT_SLOT
+    }
+
+    /// Called when the smithing table menu is closed.
+    /// Returns the input items to the player's inventory.
+    ///
+    /// Based on Java's `ItemCombinerMenu::removed`.
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.add_item_or_drop(carried);
+        }
+
+        let mut inputs = self.input_slots.lock();
+        for slot in [slots::TEMPLATE_SLOT, slots::BASE_SLOT, slots::ADDITION_SLOT] {
+            let item = inputs.remove_item_no_update(slot);
+            if !item.is_empty() {
+                player.add_item_or_drop(item);
+            }
+        }
+        drop(inputs);
+
+        self.result_slots.lock().set_item(0, ItemStack::empty());
+    }
+
+    /// Updates the result when an input changes, and flags inputs that are
+    /// all filled but make nothing.
+    ///
+    /// Based on Java's `SmithingMenu::createResult` and `slotsChanged`.
+    fn slots_changed(&mut self, _player: &Player) {
+        let mut guard = self.behavior.lock_all_containers();
+        let inputs = guard
+            .get(ContainerId::from_arc(&self.input_slots))
+            .expect("input container not locked");
+        let input = SmithingInput {
+            template: inputs.get_item(slots::TEMPLATE_SLOT),
+            base: inputs.get_item(slots::BASE_SLOT),
+            addition: inputs.get_item(slots::ADDITION_SLOT),
+        };
+        let all_filled =
+            !input.template.is_empty() && !input.base.is_empty() && !input.addition.is_empty();
+        let result = REGISTRY
+            .recipes
+            .smithing()
+            .find(input)
+            .map_or_else(ItemStack::empty, |recipe| recipe.assemble(input));
+
+        let has_error = all_filled && result.is_empty();
+        guard
+            .get_result_container_mut(ContainerId::from_arc(&self.result_slots))
+            .expect("result container not locked")
+            .set_item(0, result);
+        drop(guard);
+
+        self.behavior
+            .set_data(self.recipe_error_data, i16::from(has_error));
+    }
+}
+
+impl MenuInstance for SmithingMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::SMITHING
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating a smithing table menu.
+pub struct SmithingMenuProvider {
+    inventory: SyncPlayerInv,
+    pos: BlockPos,
+}
+
+impl SmithingMenuProvider {
+    /// Creates a new smithing table menu provider.
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, pos: BlockPos) -> Self {
+        Self { inventory, pos }
+    }
+}
+
+impl MenuProvider for SmithingMenuProvider {
+    fn title(&self) -> TextComponent {
+        TextComponent::translated(translations::CONTAINER_UPGRADE.msg())
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(SmithingMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.pos,
+        ))
+    }
+}
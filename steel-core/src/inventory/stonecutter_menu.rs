@@ -0,0 +1,358 @@
+//! The stonecutter menu for cutting blocks into their variants.
+//!
+//! Slot layout (38 total):
+//! - Slot 0: Input
+//! - Slot 1: Result
+//! - Slots 2-28: Main inventory (27 slots)
+//! - Slots 29-37: Hotbar (9 slots)
+
+use std::{mem, sync::Arc};
+
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::recipe::StonecuttingRecipe;
+use steel_registry::{REGISTRY, vanilla_menu_types};
+use steel_utils::locks::SyncMutex;
+use steel_utils::{BlockPos, translations};
+use text_components::TextComponent;
+
+use crate::inventory::{
+    SyncPlayerInv,
+    container::{Container, SimpleContainer},
+    crafting::ResultContainer,
+    lock::{ContainerId, ContainerLockGuard, ContainerRef, GenericContainer},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{
+        NormalSlot, Slot, SlotType, StonecutterResultSlot, SyncResultContainer,
+        add_standard_inventory_slots,
+    },
+};
+use crate::player::Player;
+
+/// Slot indices for the stonecutter menu.
+pub mod slots {
+    /// Slot index for the input (slot 0).
+    pub const INPUT_SLOT: usize = 0;
+    /// Slot index for the result (slot 1).
+    pub const RESULT_SLOT: usize = 1;
+    /// Start of main inventory (slot 2).
+    pub const INV_SLOT_START: usize = 2;
+    /// End of main inventory (slot 29, exclusive).
+    pub const INV_SLOT_END: usize = 29;
+    /// Start of hotbar (slot 29).
+    pub const HOTBAR_SLOT_START: usize = 29;
+    /// End of hotbar (slot 38, exclusive).
+    pub const HOTBAR_SLOT_END: usize = 38;
+    /// Total number of slots in the stonecutter menu.
+    pub const TOTAL_SLOTS: usize = 38;
+}
+
+/// The stonecutter menu.
+///
+/// Based on Java's `StonecutterMenu`.
+pub struct StonecutterMenu {
+    behavior: MenuBehavior,
+    /// The input slot.
+    input_slots: GenericContainer,
+    /// The result slot.
+    result_slots: SyncResultContainer,
+    /// The position of the stonecutter block.
+    block_pos: BlockPos,
+    /// The item the recipe list was built for, to tell when it changes.
+    input: ItemStack,
+    /// The recipes for the current input, in the order the client lists
+    /// them.
+    recipes_for_input: Vec<&'static StonecuttingRecipe>,
+    /// Index of the data slot holding the selected recipe, -1 for none.
+    selected_recipe_data: usize,
+}
+
+impl StonecutterMenu {
+    /// Creates a new stonecutter menu for a player.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `block_pos` - The position of the stonecutter block
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, container_id: u8, block_pos: BlockPos) -> Self {
+        let mut menu_slots = Vec::with_capacity(slots::TOTAL_SLOTS);
+
+        let input_slots: GenericContainer = Arc::new(SyncMutex::new(SimpleContainer::new(1)));
+        let result_slots: SyncResultContainer = Arc::new(SyncMutex::new(ResultContainer::new()));
+
+        // Slot 0: Input
+        menu_slots.push(SlotType::Normal(NormalSlot::new(
+            ContainerRef::Other(input_slots.clone()),
+            0,
+        )));
+
+        // Slot 1: Result
+        menu_slots.push(SlotType::StonecutterResult(StonecutterResultSlot::new(
+            result_slots.clone(),
+            input_slots.clone(),
+            block_pos,
+        )));
+
+        // Slots 2-37: Standard inventory (main inventory + hotbar)
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        let mut behavior = MenuBehavior::new(
+            menu_slots,
+            container_id,
+            Some(vanilla_menu_types::STONECUTTER),
+        );
+        let selected_recipe_data = behavior.add_data_slot(-1);
+
+        Self {
+            behavior,
+            input_slots,
+            result_slots,
+            block_pos,
+            input: ItemStack::empty(),
+            recipes_for_input: Vec::new(),
+            selected_recipe_data,
+        }
+    }
+
+    /// Returns the menu type for the stonecutter.
+    #[must_use]
+    pub fn menu_type() -> MenuTypeRef {
+        vanilla_menu_types::STONECUTTER
+    }
+
+    /// Returns the position of the stonecutter block.
+    #[must_use]
+    pub fn block_pos(&self) -> BlockPos {
+        self.block_pos
+    }
+
+    /// Returns the item currently in the input slot.
+    fn current_input(&self) -> ItemStack {
+        let guard = self.behavior.lock_all_containers();
+        guard
+            .get(ContainerId::from_arc(&self.input_slots))
+            .expect("input container not locked")
+            .get_item(slots::INPUT_SLOT)
+            .clone()
+    }
+
+    /// Returns the index of the selected recipe, if any.
+    fn selected_index(&self) -> Option<usize> {
+        let index = self.behavior.get_data(self.selected_recipe_data)?;
+        usize::try_from(index)
+            .ok()
+            .filter(|&index| index < self.recipes_for_input.len())
+    }
+
+    /// Selects the recipe at `index`, or none.
+    fn set_selected_index(&mut self, index: Option<usize>) {
+        let value = index.map_or(-1, |index| index as i16);
+        self.behavior.set_data(self.selected_recipe_data, value);
+    }
+
+    /// Rebuilds the recipe list for a new input and clears the selection.
+    ///
+    /// Based on Java's `StonecutterMenu::setupRecipeList`.
+    fn setup_recipe_list(&mut self, input: &ItemStack) {
+        self.set_selected_index(None);
+        self.recipes_for_input = REGISTRY.recipes.stonecutting().recipes_for(input);
+    }
+
+    /// Puts the result of the selected recipe in the result slot.
+    ///
+    /// Based on Java's `StonecutterMenu::setupResultSlot`.
+    fn setup_result_slot(&mut self) {
+        let result = self
+            .selected_index()
+            .map_or_else(ItemStack::empty, |index| {
+                self.recipes_for_input[index].result.to_item_stack()
+            });
+
+        let mut guard = self.behavior.lock_all_containers();
+        guard
+            .get_result_container_mut(ContainerId::from_arc(&self.result_slots))
+            .expect("result container not locked")
+            .set_item(0, result);
+    }
+}
+
+impl Menu for StonecutterMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `StonecutterMenu::quickMoveStack`:
+    /// - Result slot (1) -> inventory (2-37), prefer existing stacks
+    /// - Input slot (0) -> inventory (2-37)
+    /// - Inventory (2-37) -> the input slot if it can be cut, otherwise
+    ///   between main inventory and hotbar
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        let moved = if slot_index == slots::RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                true,
+            )
+        } else if slot_index == slots::INPUT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                slots::INV_SLOT_START,
+                slots::HOTBAR_SLOT_END,
+                false,
+            )
+        } else {
+            let (start, end) = if REGISTRY.recipes.stonecutting().accepts_input(&stack_mut) {
+                (slots::INPUT_SLOT, slots::INPUT_SLOT + 1)
+            } else if slot_index < slots::INV_SLOT_END {
+                (slots::HOTBAR_SLOT_START, slots::HOTBAR_SLOT_END)
+            } else {
+                (slots::INV_SLOT_START, slots::INV_SLOT_END)
+            };
+            self.behavior
+                .move_item_stack_to(guard, &mut stack_mut, start, end, false)
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        if slot_index == slots::RESULT_SLOT
+            && let Some(remainder) =
+                self.behavior.slots[slot_index].on_take(guard, &clicked, player)
+        {
+            player.add_item_or_drop_with_guard(guard, remainder);
+        }
+
+        clicked
+    }
+
+    /// Prevents collecting items from the result slot.
+    fn can_take_item_for_pick_all(&self, _carried: &ItemStack, slot_index: usize) -> bool {
+        slot_index != slots::RESULT_SLOT
+    }
+
+    /// Called when the stonecutter menu is closed.
+    /// Returns the input item to the player's inventory.
+    ///
+    /// Based on Java's `StonecutterMenu::removed`.
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.add_item_or_drop(carried);
+        }
+
+        let input = self
+            .input_slots
+            .lock()
+            .remove_item_no_update(slots::INPUT_SLOT);
+        if !input.is_empty() {
+            player.add_item_or_drop(input);
+        }
+
+        self.result_slots.lock().set_item(0, ItemStack::empty());
+    }
+
+    /// Rebuilds the recipe list when a different item is put in, and keeps
+    /// the result in step with the input otherwise.
+    ///
+    /// Based on Java's `StonecutterMenu::slotsChanged`.
+    fn slots_changed(&mut self, _player: &Player) {
+        let input = self.current_input();
+        if !input.is(self.input.item()) {
+            self.setup_recipe_list(&input);
+            self.input = input;
+        }
+        self.setup_result_slot();
+    }
+
+    /// Selects one of the recipes for the input.
+    ///
+    /// Based on Java's `StonecutterMenu::clickMenuButton`.
+    fn click_menu_button(&mut self, button_id: i32, _player: &Player) -> bool {
+        let Ok(index) = usize::try_from(button_id) else {
+            return false;
+        };
+        if self.selected_index() == Some(index) {
+            return false;
+        }
+        if index < self.recipes_for_input.len() {
+            self.set_selected_index(Some(index));
+            self.setup_result_slot();
+        }
+        true
+    }
+}
+
+impl MenuInstance for StonecutterMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::STONECUTTER
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating a stonecutter menu.
+pub struct StonecutterMenuProvider {
+    inventory: SyncPlayerInv,
+    pos: BlockPos,
+}
+
+impl StonecutterMenuProvider {
+    /// Creates a new stonecutter menu provider.
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, pos: BlockPos) -> Self {
+        Self { inventory, pos }
+    }
+}
+
+impl MenuProvider for StonecutterMenuProvider {
+    fn title(&self) -> TextComponent {
+        TextComponent::translated(translations::CONTAINER_STONECUTTER.msg())
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(StonecutterMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.pos,
+        ))
+    }
+}
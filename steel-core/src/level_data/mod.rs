@@ -13,9 +13,11 @@ use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use steel_registry::REGISTRY;
 use steel_registry::game_rules::{GameRuleValue, GameRuleValues};
-use steel_utils::BlockPos;
+use steel_utils::{BlockPos, types::Difficulty};
 use tokio::fs;
 
+use crate::world::WorldBorder;
+
 /// Persistent level data that gets saved to disk.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LevelData {
@@ -29,11 +31,17 @@ pub struct LevelData {
     pub spawn: SpawnPoint,
     /// Weather state.
     pub weather: WeatherState,
+    /// Difficulty of the game.
+    #[serde(default)]
+    pub difficulty: Difficulty,
     /// Game rules (stored as name -> value pairs for serialization).
     pub game_rules: FxHashMap<String, GameRuleValue>,
     /// Runtime game rule values (not serialized, loaded from `game_rules`).
     #[serde(skip)]
     pub game_rules_values: GameRuleValues,
+    /// World border state.
+    #[serde(default)]
+    pub world_border: WorldBorder,
     /// Whether the world has been initialized.
     pub initialized: bool,
 }
@@ -93,8 +101,10 @@ impl LevelData {
             day_time: 0,
             spawn: SpawnPoint::default(),
             weather: WeatherState::default(),
+            difficulty: Difficulty::default(),
             game_rules: FxHashMap::default(),
             game_rules_values: GameRuleValues::new(&REGISTRY.game_rules),
+            world_border: WorldBorder::default(),
             initialized: false,
         }
     }
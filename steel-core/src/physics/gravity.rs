@@ -0,0 +1,93 @@
+//! Gravity and air drag for entities that fall.
+//!
+//! Entities that fall on their own, like primed TNT, implement
+//! [`GravityAffected`] and call [`fall`] once per tick. Entities with movement
+//! of their own only share [`stop_blocked_axes`].
+
+use steel_utils::math::Vector3;
+
+use crate::physics::{CollisionWorld, EntityPhysicsState, MoveResult, MoverType, move_entity};
+
+/// How much most entities speed up downwards each tick.
+///
+/// Vanilla: `LivingEntity.DEFAULT_BASE_GRAVITY`
+pub const DEFAULT_GRAVITY: f64 = 0.08;
+
+/// How much of its velocity a falling entity keeps each tick.
+pub const DEFAULT_DRAG: f64 = 0.98;
+
+/// An entity that falls, slowed down by the air. [`fall`] moves it.
+pub trait GravityAffected {
+    /// How much the entity speeds up downwards each tick.
+    ///
+    /// Vanilla: `Entity.getDefaultGravity`
+    const GRAVITY: f64 = DEFAULT_GRAVITY;
+
+    /// How much of its velocity the entity keeps each tick.
+    const DRAG: f64 = DEFAULT_DRAG;
+
+    /// The physics state that [`fall`] moves.
+    fn physics_mut(&mut self) -> &mut EntityPhysicsState;
+
+    /// Returns true if the entity floats in place instead of falling, like
+    /// with the `NoGravity` tag.
+    fn has_no_gravity(&self) -> bool {
+        false
+    }
+}
+
+/// Pulls `entity` down and moves it by its velocity through `world`, then
+/// slows it down by its drag. Axes the entity bumped into stop, and its
+/// on-ground flag is updated. Returns where the entity went.
+///
+/// Vanilla: `Entity.applyGravity` followed by `Entity.move`
+pub fn fall<E: GravityAffected>(entity: &mut E, world: &impl CollisionWorld) -> MoveResult {
+    let gravity = if entity.has_no_gravity() {
+        0.0
+    } else {
+        E::GRAVITY
+    };
+    let state = entity.physics_mut();
+    state.velocity.y -= gravity;
+
+    let delta = state.velocity;
+    let result = move_entity(state, delta, MoverType::SelfMovement, world);
+    state.set_position(result.final_position);
+    state.on_ground = result.on_ground;
+    state.horizontal_collision = result.horizontal_collision;
+    state.vertical_collision = result.vertical_collision;
+    state.velocity = stop_blocked_axes(delta, result.actual_movement) * E::DRAG;
+    result
+}
+
+/// `delta` with the axes zeroed on which a collision kept the entity from
+/// moving all of it, so it doesn't keep pushing into the block it hit.
+#[must_use]
+pub fn stop_blocked_axes(delta: Vector3<f64>, movement: Vector3<f64>) -> Vector3<f64> {
+    let axis = |delta: f64, movement: f64| {
+        if (delta - movement).abs() > 1.0e-7 {
+            0.0
+        } else {
+            delta
+        }
+    };
+    Vector3::new(
+        axis(delta.x, movement.x),
+        axis(delta.y, movement.y),
+        axis(delta.z, movement.z),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_blocked_axes_stop() {
+        let delta = Vector3::new(0.3, -0.5, -0.2);
+        let velocity = stop_blocked_axes(delta, Vector3::new(0.3, -0.1, 0.0));
+        assert!((velocity.x - 0.3).abs() < 1e-9);
+        assert!(velocity.y.abs() < 1e-9);
+        assert!(velocity.z.abs() < 1e-9);
+    }
+}
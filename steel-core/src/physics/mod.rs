@@ -11,12 +11,14 @@
 
 mod collision;
 mod entity_move;
+mod gravity;
 mod physics_state;
 mod shapes;
 
 // Public API
 pub use collision::{CollisionWorld, WorldCollisionProvider};
 pub use entity_move::{MoveResult, MoverType, move_entity};
+pub use gravity::{DEFAULT_DRAG, DEFAULT_GRAVITY, GravityAffected, fall, stop_blocked_axes};
 pub use physics_state::EntityPhysicsState;
 pub use shapes::{collide, join_is_not_empty, translate_shape};
 
@@ -0,0 +1,185 @@
+//! Per-player advancement progress.
+//!
+//! Progress is stored in `world/advancements/<uuid>.json` as the completed
+//! criteria of every advancement, similar to vanilla's advancement files.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use steel_protocol::packets::game::{CUpdateAdvancements, CriteriaProgress};
+use steel_registry::{REGISTRY, advancement::AdvancementRef};
+use steel_utils::Identifier;
+use uuid::Uuid;
+
+/// Directory the advancement files of all players are stored in.
+const ADVANCEMENTS_DIR: &str = "world/advancements";
+
+/// Progress of a single advancement.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AdvancementProgress {
+    /// Completed criteria and the time they were completed at, in milliseconds since the epoch.
+    pub criteria: BTreeMap<String, i64>,
+    /// Whether every requirement of the advancement is met.
+    pub done: bool,
+}
+
+/// Tracks which advancements a player has made progress on.
+pub struct PlayerAdvancements {
+    /// Path to the player's advancement file.
+    path: PathBuf,
+    /// Progress per advancement. Advancements without progress are left out.
+    progress: FxHashMap<Identifier, AdvancementProgress>,
+    /// Whether progress changed since the last save.
+    dirty: bool,
+}
+
+impl PlayerAdvancements {
+    /// Loads the advancement progress of the given player, starting fresh if
+    /// there is no file yet or it can't be read.
+    #[must_use]
+    pub fn load(uuid: Uuid) -> Self {
+        let path = Path::new(ADVANCEMENTS_DIR).join(format!("{uuid}.json"));
+
+        let mut progress: FxHashMap<Identifier, AdvancementProgress> =
+            match fs::read_to_string(&path) {
+                Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                    log::error!("Invalid advancement file {}: {e}", path.display());
+                    FxHashMap::default()
+                }),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => FxHashMap::default(),
+                Err(e) => {
+                    log::error!("Failed to read advancement file {}: {e}", path.display());
+                    FxHashMap::default()
+                }
+            };
+
+        // Drop progress on advancements or criteria that no longer exist
+        progress.retain(|key, entry| {
+            let Some(advancement) = REGISTRY.advancements.by_key(key) else {
+                return false;
+            };
+            entry.criteria.retain(|criterion, _| {
+                advancement
+                    .criteria
+                    .iter()
+                    .any(|known| *criterion == known.name)
+            });
+            let done = advancement.is_done(|criterion| entry.criteria.contains_key(criterion));
+            entry.done = done;
+            !entry.criteria.is_empty()
+        });
+
+        Self {
+            path,
+            progress,
+            dirty: false,
+        }
+    }
+
+    /// Saves the progress to disk if it changed since the last save.
+    pub fn save(&mut self) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(&self.progress)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&self.path, content)?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Returns true if the advancement is completed.
+    #[must_use]
+    pub fn is_done(&self, advancement: AdvancementRef) -> bool {
+        self.progress
+            .get(&advancement.key)
+            .is_some_and(|entry| entry.done)
+    }
+
+    /// Marks a criterion of the advancement as completed.
+    ///
+    /// Returns false if the criterion doesn't exist or was already completed.
+    pub fn award(&mut self, advancement: AdvancementRef, criterion: &str) -> bool {
+        if !advancement
+            .criteria
+            .iter()
+            .any(|known| known.name == criterion)
+        {
+            return false;
+        }
+
+        let entry = self.progress.entry(advancement.key.clone()).or_default();
+        if entry.criteria.contains_key(criterion) {
+            return false;
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis() as i64);
+        entry.criteria.insert(criterion.to_string(), now);
+        let done = advancement.is_done(|criterion| entry.criteria.contains_key(criterion));
+        entry.done = done;
+        self.dirty = true;
+        true
+    }
+
+    /// Returns every criterion of the advancement with the time it was completed at.
+    #[must_use]
+    pub fn criteria_progress(&self, advancement: AdvancementRef) -> CriteriaProgress {
+        let entry = self.progress.get(&advancement.key);
+        advancement
+            .criteria
+            .iter()
+            .map(|criterion| {
+                let obtained = entry.and_then(|entry| entry.criteria.get(criterion.name).copied());
+                (criterion.name.to_string(), obtained)
+            })
+            .collect()
+    }
+
+    /// Builds the packet that replaces the client's advancement tree with every
+    /// registered advancement and the player's progress on them.
+    #[must_use]
+    pub fn full_update_packet(&self) -> CUpdateAdvancements {
+        let added: Vec<_> = REGISTRY
+            .advancements
+            .iter()
+            .map(|(_, advancement)| advancement)
+            .collect();
+        let progress = added
+            .iter()
+            .map(|&advancement| (advancement.key.clone(), self.criteria_progress(advancement)))
+            .collect();
+
+        CUpdateAdvancements {
+            reset: true,
+            added,
+            removed: Vec::new(),
+            progress,
+            // Don't show toasts for everything the player already had
+            show_advancements: false,
+        }
+    }
+
+    /// Builds the packet that updates the client's progress on a single advancement.
+    #[must_use]
+    pub fn progress_packet(&self, advancement: AdvancementRef) -> CUpdateAdvancements {
+        CUpdateAdvancements {
+            reset: false,
+            added: Vec::new(),
+            removed: Vec::new(),
+            progress: vec![(advancement.key.clone(), self.criteria_progress(advancement))],
+            show_advancements: true,
+        }
+    }
+}
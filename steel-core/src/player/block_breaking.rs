@@ -3,15 +3,24 @@
 //! This module implements the logic from Java's `ServerPlayerGameMode` for handling
 //! block breaking, including progress tracking and validation.
 
+use std::ops::RangeInclusive;
+use std::ptr;
+
 use steel_protocol::packets::game::CBlockUpdate;
+use steel_registry::blocks::BlockRef;
+use steel_registry::game_rules::GameRuleValue;
+use steel_registry::vanilla_game_rules::BLOCK_DROPS;
 use steel_registry::{REGISTRY, blocks::properties::Direction, vanilla_blocks};
 use steel_utils::{
     BlockPos, BlockStateId,
+    math::Vector3,
     types::{GameType, InteractionHand, UpdateFlags},
 };
 
 use crate::player::Player;
+use crate::player::stats::StatKey;
 use crate::world::World;
+use crate::world::vibrations::{self, VibrationEvent};
 
 /// Manages the block breaking state for a player.
 ///
@@ -272,9 +281,8 @@ impl BlockBreakingManager {
         // TODO: Check for GameMasterBlock (command blocks, etc.)
         // TODO: Check blockActionRestricted
 
-        // Remove the block
-        let air_state = REGISTRY.blocks.get_base_state_id(vanilla_blocks::AIR);
-        let changed = world.set_block(pos, air_state, UpdateFlags::UPDATE_ALL);
+        // Remove the block, leaving the water of a waterlogged block behind
+        let changed = world.set_block(pos, state.fluid_block_state(), UpdateFlags::UPDATE_ALL);
 
         if changed {
             // Play block destruction particles and sound (skip for fire blocks like vanilla)
@@ -286,6 +294,7 @@ impl BlockBreakingManager {
             if !is_fire {
                 world.destroy_block_effect(pos, u32::from(state.0), Some(player.id));
             }
+            world.post_vibration(VibrationEvent::BlockDestroy, vibrations::block_center(pos));
 
             // Check if player has correct tool for drops
             let has_correct_tool = {
@@ -326,7 +335,13 @@ impl BlockBreakingManager {
                 && has_correct_tool
             {
                 // TODO: Call playerDestroy to spawn drops
+                if let Some(block) = block {
+                    player.award_stat(StatKey::mined(block), 1);
+                }
                 drop_block_loot(player, world, pos, state);
+                if let Some(block) = block {
+                    drop_block_experience(player, world, pos, block);
+                }
             }
         }
 
@@ -428,3 +443,67 @@ fn get_destroy_progress(player: &Player, block_state: BlockStateId) -> f32 {
 fn drop_block_loot(_player: &Player, _world: &World, _pos: BlockPos, _state: BlockStateId) {
     // Noop for now - will be implemented with loot tables
 }
+
+/// Drops the experience a mined ore gives, unless it was mined with silk
+/// touch.
+///
+/// Based on Java's `DropExperienceBlock::spawnAfterBreak` and
+/// `RedStoneOreBlock::spawnAfterBreak`.
+fn drop_block_experience(player: &Player, world: &World, pos: BlockPos, block: BlockRef) {
+    let Some(range) = experience_dropped(block) else {
+        return;
+    };
+    if world.get_game_rule(BLOCK_DROPS) != GameRuleValue::Bool(true) {
+        return;
+    }
+    let silk_touch = {
+        let inv = player.inventory.lock();
+        inv.get_item_in_hand(InteractionHand::MainHand)
+            .get_enchantment_level_by_name("silk_touch")
+            > 0
+    };
+    if silk_touch {
+        return;
+    }
+
+    let amount = rand::random_range(range);
+    if amount > 0 {
+        let center = Vector3::new(
+            f64::from(pos.x()) + 0.5,
+            f64::from(pos.y()) + 0.5,
+            f64::from(pos.z()) + 0.5,
+        );
+        world.spawn_experience_orbs(center, amount);
+    }
+}
+
+/// Returns how much experience mining `block` can drop, or `None` if it drops
+/// none. Ancient debris, like the copper, iron and gold ores, drops none.
+fn experience_dropped(block: BlockRef) -> Option<RangeInclusive<i32>> {
+    let is_any = |blocks: &[BlockRef]| blocks.iter().any(|&other| ptr::eq(block, other));
+    if is_any(&[vanilla_blocks::COAL_ORE, vanilla_blocks::DEEPSLATE_COAL_ORE]) {
+        Some(0..=2)
+    } else if is_any(&[
+        vanilla_blocks::DIAMOND_ORE,
+        vanilla_blocks::DEEPSLATE_DIAMOND_ORE,
+        vanilla_blocks::EMERALD_ORE,
+        vanilla_blocks::DEEPSLATE_EMERALD_ORE,
+    ]) {
+        Some(3..=7)
+    } else if is_any(&[
+        vanilla_blocks::LAPIS_ORE,
+        vanilla_blocks::DEEPSLATE_LAPIS_ORE,
+        vanilla_blocks::NETHER_QUARTZ_ORE,
+    ]) {
+        Some(2..=5)
+    } else if is_any(&[
+        vanilla_blocks::REDSTONE_ORE,
+        vanilla_blocks::DEEPSLATE_REDSTONE_ORE,
+    ]) {
+        Some(1..=5)
+    } else if is_any(&[vanilla_blocks::NETHER_GOLD_ORE]) {
+        Some(0..=1)
+    } else {
+        None
+    }
+}
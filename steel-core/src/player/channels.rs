@@ -0,0 +1,175 @@
+//! Dispatches plugin channel messages sent by clients.
+//!
+//! Clients and mods talk to the server over named channels carried by custom
+//! payload packets. Every channel has at most one handler, and messages on
+//! channels without one are ignored. Plugins add their own channels with
+//! [`PluginChannelDispatcher::register_channel`] on [`PLUGIN_CHANNELS`].
+
+use std::io::Cursor;
+use std::sync::{Arc, LazyLock};
+
+use rustc_hash::FxHashMap;
+use steel_utils::Identifier;
+use steel_utils::codec::VarInt;
+use steel_utils::locks::SyncRwLock;
+use steel_utils::serial::PrefixedRead;
+
+use crate::player::Player;
+
+/// The channel the client reports its brand on, like `vanilla` or `fabric`.
+pub const BRAND: Identifier = Identifier::vanilla_static("brand");
+/// The channel the client lists the channels it listens on.
+pub const REGISTER: Identifier = Identifier::vanilla_static("register");
+/// The channel the client lists the channels it stopped listening on.
+pub const UNREGISTER: Identifier = Identifier::vanilla_static("unregister");
+
+/// The longest brand that is stored.
+const MAX_BRAND_LENGTH: usize = 256;
+/// How many channels a single client may register.
+const MAX_CLIENT_CHANNELS: usize = 128;
+
+/// The channel handlers of the server.
+pub static PLUGIN_CHANNELS: LazyLock<PluginChannelDispatcher> =
+    LazyLock::new(PluginChannelDispatcher::new);
+
+/// Handles the messages sent on a plugin channel.
+pub trait ChannelHandler: Send + Sync {
+    /// Handles a message `player` sent on the channel.
+    fn handle(&self, player: &Player, data: &[u8]);
+}
+
+/// Routes custom payloads to the handler of their channel.
+pub struct PluginChannelDispatcher {
+    handlers: SyncRwLock<FxHashMap<Identifier, Arc<dyn ChannelHandler>>>,
+}
+
+impl PluginChannelDispatcher {
+    /// Creates a dispatcher with the built-in channels registered.
+    #[must_use]
+    pub fn new() -> Self {
+        let dispatcher = Self {
+            handlers: SyncRwLock::new(FxHashMap::default()),
+        };
+        dispatcher.register_channel(BRAND, Arc::new(BrandHandler));
+        dispatcher.register_channel(REGISTER, Arc::new(RegisterHandler));
+        dispatcher.register_channel(UNREGISTER, Arc::new(UnregisterHandler));
+        dispatcher
+    }
+
+    /// Sets the handler of a channel, returning the handler it replaced.
+    pub fn register_channel(
+        &self,
+        channel: Identifier,
+        handler: Arc<dyn ChannelHandler>,
+    ) -> Option<Arc<dyn ChannelHandler>> {
+        self.handlers.write().insert(channel, handler)
+    }
+
+    /// Removes the handler of a channel, returning it if there was one.
+    pub fn unregister_channel(&self, channel: &Identifier) -> Option<Arc<dyn ChannelHandler>> {
+        self.handlers.write().remove(channel)
+    }
+
+    /// Returns whether the channel has a handler.
+    #[must_use]
+    pub fn is_registered(&self, channel: &Identifier) -> bool {
+        self.handlers.read().contains_key(channel)
+    }
+
+    /// Passes a message to the handler of its channel, returning `false` if
+    /// the channel has none.
+    pub fn dispatch(&self, player: &Player, channel: &Identifier, data: &[u8]) -> bool {
+        // Clone the handler out, so it can register channels itself
+        let Some(handler) = self.handlers.read().get(channel).cloned() else {
+            return false;
+        };
+        handler.handle(player, data);
+        true
+    }
+}
+
+impl Default for PluginChannelDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Stores the client's brand.
+struct BrandHandler;
+
+impl ChannelHandler for BrandHandler {
+    fn handle(&self, player: &Player, data: &[u8]) {
+        match read_brand(data) {
+            Some(brand) => {
+                log::debug!("{} uses client brand {brand}", player.gameprofile.name);
+                *player.client_brand.lock() = Some(brand);
+            }
+            None => log::debug!("{} sent an invalid brand", player.gameprofile.name),
+        }
+    }
+}
+
+/// Records the channels the client listens on.
+struct RegisterHandler;
+
+impl ChannelHandler for RegisterHandler {
+    fn handle(&self, player: &Player, data: &[u8]) {
+        let mut channels = player.client_channels.lock();
+        for channel in read_channel_list(data) {
+            if channels.len() >= MAX_CLIENT_CHANNELS {
+                log::debug!(
+                    "{} registered more than {MAX_CLIENT_CHANNELS} channels",
+                    player.gameprofile.name
+                );
+                break;
+            }
+            channels.insert(channel);
+        }
+    }
+}
+
+/// Forgets the channels the client stopped listening on.
+struct UnregisterHandler;
+
+impl ChannelHandler for UnregisterHandler {
+    fn handle(&self, player: &Player, data: &[u8]) {
+        let mut channels = player.client_channels.lock();
+        for channel in read_channel_list(data) {
+            channels.remove(&channel);
+        }
+    }
+}
+
+/// Reads a brand, which is a length prefixed string.
+fn read_brand(data: &[u8]) -> Option<String> {
+    let mut cursor = Cursor::new(data);
+    let bytes = Vec::<u8>::read_prefixed_bound::<VarInt>(&mut cursor, MAX_BRAND_LENGTH).ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// Reads a list of channels separated by NUL bytes, skipping invalid ones.
+fn read_channel_list(data: &[u8]) -> impl Iterator<Item = Identifier> + '_ {
+    data.split(|&b| b == 0)
+        .filter_map(|channel| str::from_utf8(channel).ok()?.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_brand() {
+        assert_eq!(read_brand(b"\x07vanilla"), Some("vanilla".to_owned()));
+        assert_eq!(read_brand(b"\x07vanil"), None);
+        assert_eq!(read_brand(b"\x02\xff\xfe"), None);
+    }
+
+    #[test]
+    fn reads_channel_lists() {
+        let channels: Vec<_> = read_channel_list(b"fabric:registry/sync\0bad channel\0mod:a")
+            .map(|channel| channel.to_string())
+            .collect();
+        assert_eq!(channels, ["fabric:registry/sync", "mod:a"]);
+        assert_eq!(read_channel_list(b"").count(), 0);
+    }
+}
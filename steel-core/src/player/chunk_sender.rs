@@ -1,6 +1,8 @@
 //! This module is responsible for sending chunks to the client.
 use rustc_hash::FxHashSet;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
 
 use steel_protocol::packets::game::{
     CChunkBatchFinished, CChunkBatchStart, CForgetLevelChunk, CLevelChunkWithLight,
@@ -12,6 +14,7 @@ use crate::{
     chunk::{
         chunk_access::{ChunkAccess, ChunkStatus},
         chunk_holder::ChunkHolder,
+        player_chunk_view::PlayerChunkView,
     },
     player::networking::JavaConnection,
     world::World,
@@ -58,13 +61,16 @@ impl ChunkSender {
 
     /// Sends the next batch of chunks to the client.
     ///
+    /// Only chunks inside `view` are sent, nearest to its center first. The
+    /// view's radius is the player's negotiated view distance.
+    ///
     /// # Panics
     /// Panics if a chunk is not at Full status when it should be.
     pub fn send_next_chunks(
         &mut self,
         connection: Arc<JavaConnection>,
         world: &World,
-        player_chunk_pos: ChunkPos,
+        view: &PlayerChunkView,
     ) {
         if self.unacknowledged_batches < self.max_unacknowledged_batches {
             let max_batch_size = self.desired_chunks_per_tick.max(1.0);
@@ -72,10 +78,12 @@ impl ChunkSender {
                 (self.batch_quota + self.desired_chunks_per_tick).min(max_batch_size);
 
             if self.batch_quota >= 1.0 && !self.pending_chunks.is_empty() {
-                let chunks_to_process = self.collect_candidates(world, player_chunk_pos);
+                let chunks_to_process = self.collect_candidates(world, view);
                 if !chunks_to_process.is_empty() {
                     self.unacknowledged_batches += 1;
                     self.batch_quota -= chunks_to_process.len() as f32;
+                    let has_skylight = world.dimension.has_skylight;
+                    let light_nanos = world.light_nanos.clone();
 
                     #[allow(clippy::let_underscore_future)]
                     let _ = spawn_blocking(move || {
@@ -83,10 +91,17 @@ impl ChunkSender {
                         for holder in chunks_to_process {
                             if let Some(chunk_guard) = holder.try_chunk(ChunkStatus::Full) {
                                 if let ChunkAccess::Full(chunk) = &*chunk_guard {
+                                    let light_start = Instant::now();
+                                    let light_data = chunk.extract_light_data(has_skylight);
+                                    let light_time = light_start.elapsed().as_nanos();
+                                    light_nanos.fetch_add(
+                                        u64::try_from(light_time).unwrap_or(u64::MAX),
+                                        Ordering::Relaxed,
+                                    );
                                     chunks_to_send.push(CLevelChunkWithLight {
                                         pos: holder.get_pos(),
                                         chunk_data: chunk.extract_chunk_data(),
-                                        light_data: chunk.extract_light_data(),
+                                        light_data,
                                     });
                                 } else {
                                     panic!("Chunk must be at Full status to be sent to the client");
@@ -113,15 +128,19 @@ impl ChunkSender {
     fn collect_candidates(
         &mut self,
         world: &World,
-        player_chunk_pos: ChunkPos,
+        view: &PlayerChunkView,
     ) -> Vec<Arc<ChunkHolder>> {
         let max_batch_size = self.batch_quota.floor() as usize;
+
+        // Chunks that fell out of range (e.g. the view distance shrank) are never
+        // sent; the client was never told about them, so there's nothing to forget
+        self.pending_chunks.retain(|pos| view.contains(*pos));
         let mut candidates: Vec<ChunkPos> = self.pending_chunks.iter().copied().collect();
 
         // Sort by distance to player
         candidates.sort_by_key(|pos| {
-            let dx = pos.0.x - player_chunk_pos.0.x;
-            let dz = pos.0.y - player_chunk_pos.0.y;
+            let dx = pos.0.x - view.center.0.x;
+            let dz = pos.0.y - view.center.0.y;
             dx * dx + dz * dz
         });
 
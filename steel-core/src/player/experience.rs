@@ -0,0 +1,145 @@
+//! The experience levels of a player.
+
+/// Ticks a player has to wait between picking up two experience orbs.
+pub const PICKUP_DELAY: i32 = 2;
+
+/// Tracks the experience a player collected.
+///
+/// Points fill up the bar towards the next level, with higher levels needing
+/// more points. The total counts every point collected since the player last
+/// died.
+///
+/// Vanilla: the `experienceLevel`, `experienceProgress` and
+/// `totalExperience` fields of `Player`
+#[derive(Debug, Clone, Default)]
+pub struct Experience {
+    level: i32,
+    progress: f32,
+    total: i32,
+    pickup_delay: i32,
+}
+
+impl Experience {
+    /// Returns the player's level.
+    #[must_use]
+    pub const fn level(&self) -> i32 {
+        self.level
+    }
+
+    /// Returns how far the bar is filled towards the next level, from 0 to 1.
+    #[must_use]
+    pub const fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    /// Returns the points collected since the player last died.
+    #[must_use]
+    pub const fn total(&self) -> i32 {
+        self.total
+    }
+
+    /// Returns the points needed to fill the bar at the current level.
+    ///
+    /// Vanilla: `Player.getXpNeededForNextLevel`
+    #[must_use]
+    pub const fn points_needed_for_next_level(&self) -> i32 {
+        if self.level >= 30 {
+            112 + (self.level - 30) * 9
+        } else if self.level >= 15 {
+            37 + (self.level - 15) * 5
+        } else {
+            7 + self.level * 2
+        }
+    }
+
+    /// Adds `points` to the bar, levelling up or down as it fills or empties.
+    ///
+    /// Vanilla: `Player.giveExperiencePoints`
+    #[allow(clippy::cast_precision_loss)]
+    pub fn give_points(&mut self, points: i32) {
+        self.progress += points as f32 / self.points_needed_for_next_level() as f32;
+        self.total = self.total.saturating_add(points).max(0);
+
+        while self.progress < 0.0 {
+            let remaining = self.progress * self.points_needed_for_next_level() as f32;
+            let had_levels = self.level > 0;
+            // Dropping below level 0 empties the bar
+            self.give_levels(-1);
+            if had_levels {
+                self.progress = 1.0 + remaining / self.points_needed_for_next_level() as f32;
+            }
+        }
+
+        while self.progress >= 1.0 {
+            let overflow = (self.progress - 1.0) * self.points_needed_for_next_level() as f32;
+            self.give_levels(1);
+            self.progress = overflow / self.points_needed_for_next_level() as f32;
+        }
+    }
+
+    /// Adds `levels`, taking all experience away if the level would drop
+    /// below 0.
+    ///
+    /// Vanilla: `Player.giveExperienceLevels`
+    pub fn give_levels(&mut self, levels: i32) {
+        self.level = self.level.saturating_add(levels);
+        if self.level < 0 {
+            self.level = 0;
+            self.progress = 0.0;
+            self.total = 0;
+        }
+    }
+
+    /// Returns true if the player can pick up an experience orb, starting the
+    /// delay until the next one if so.
+    pub fn try_start_pickup(&mut self) -> bool {
+        if self.pickup_delay > 0 {
+            return false;
+        }
+        self.pickup_delay = PICKUP_DELAY;
+        true
+    }
+
+    /// Counts down the delay between picking up orbs.
+    pub fn tick(&mut self) {
+        self.pickup_delay = (self.pickup_delay - 1).max(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_fill_levels() {
+        let mut experience = Experience::default();
+        experience.give_points(7);
+        assert_eq!(experience.level(), 1);
+        assert!(experience.progress().abs() < 1e-6);
+
+        // Level 1 needs 9 points, so 12 more reach level 2 with 3 to spare
+        experience.give_points(12);
+        assert_eq!(experience.level(), 2);
+        assert!((experience.progress() - 3.0 / 11.0).abs() < 1e-6);
+        assert_eq!(experience.total(), 19);
+    }
+
+    #[test]
+    fn losing_levels_stops_at_zero() {
+        let mut experience = Experience::default();
+        experience.give_levels(3);
+        experience.give_levels(-5);
+        assert_eq!(experience.level(), 0);
+        assert_eq!(experience.total(), 0);
+    }
+
+    #[test]
+    fn orbs_are_picked_up_one_at_a_time() {
+        let mut experience = Experience::default();
+        assert!(experience.try_start_pickup());
+        assert!(!experience.try_start_pickup());
+        experience.tick();
+        experience.tick();
+        assert!(experience.try_start_pickup());
+    }
+}
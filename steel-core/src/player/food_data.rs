@@ -0,0 +1,200 @@
+//! The hunger of a player.
+
+use steel_utils::types::Difficulty;
+
+/// The food level of a player with a full hunger bar.
+pub const MAX_FOOD_LEVEL: i32 = 20;
+/// Players can only sprint with more food than this.
+pub const SPRINT_FOOD_LEVEL: i32 = 6;
+/// Exhaustion caused per meter sprinted.
+pub const EXHAUSTION_SPRINT: f32 = 0.1;
+/// Exhaustion caused per meter swum or walked in water.
+pub const EXHAUSTION_SWIM: f32 = 0.01;
+
+/// Exhaustion that uses up a point of saturation or food.
+const EXHAUSTION_PER_FOOD: f32 = 4.0;
+/// The most exhaustion that can build up.
+const MAX_EXHAUSTION: f32 = 40.0;
+/// Food level from which players heal over time.
+const HEAL_FOOD_LEVEL: i32 = 18;
+
+/// What a tick of hunger does to the player's health.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FoodEffect {
+    /// Nothing happens.
+    None,
+    /// The player heals by the amount.
+    Heal(f32),
+    /// The player starves and takes a point of damage.
+    Starve,
+}
+
+/// Tracks how hungry a player is.
+///
+/// Exhaustion builds up while the player is active and first uses up their
+/// saturation, then their food. A well fed player heals over time, while a
+/// starving one takes damage.
+///
+/// Vanilla: `FoodData`
+#[derive(Debug, Clone)]
+pub struct FoodData {
+    food_level: i32,
+    saturation_level: f32,
+    exhaustion_level: f32,
+    tick_timer: i32,
+}
+
+impl Default for FoodData {
+    fn default() -> Self {
+        Self {
+            food_level: MAX_FOOD_LEVEL,
+            saturation_level: 5.0,
+            exhaustion_level: 0.0,
+            tick_timer: 0,
+        }
+    }
+}
+
+impl FoodData {
+    /// Returns the food level, from 0 to 20.
+    #[must_use]
+    pub const fn food_level(&self) -> i32 {
+        self.food_level
+    }
+
+    /// Sets the food level, clamped from 0 to 20.
+    pub fn set_food_level(&mut self, food_level: i32) {
+        self.food_level = food_level.clamp(0, MAX_FOOD_LEVEL);
+    }
+
+    /// Returns the saturation, which is used up before food.
+    #[must_use]
+    pub const fn saturation_level(&self) -> f32 {
+        self.saturation_level
+    }
+
+    /// Sets the saturation, which can't exceed the food level.
+    pub fn set_saturation_level(&mut self, saturation_level: f32) {
+        #[allow(clippy::cast_precision_loss)]
+        let max = self.food_level as f32;
+        self.saturation_level = saturation_level.clamp(0.0, max);
+    }
+
+    /// Returns the exhaustion built up since the last point of saturation or
+    /// food was used.
+    #[must_use]
+    pub const fn exhaustion_level(&self) -> f32 {
+        self.exhaustion_level
+    }
+
+    /// Adds exhaustion from the player being active.
+    pub fn add_exhaustion(&mut self, exhaustion: f32) {
+        self.exhaustion_level = (self.exhaustion_level + exhaustion).min(MAX_EXHAUSTION);
+    }
+
+    /// Returns true if the player isn't too hungry to sprint.
+    #[must_use]
+    pub const fn can_sprint(&self) -> bool {
+        self.food_level > SPRINT_FOOD_LEVEL
+    }
+
+    /// Advances hunger by a tick, returning what it does to the player's
+    /// health.
+    ///
+    /// Vanilla: `FoodData.tick`
+    pub fn tick(
+        &mut self,
+        difficulty: Difficulty,
+        natural_regeneration: bool,
+        health: f32,
+        max_health: f32,
+    ) -> FoodEffect {
+        if self.exhaustion_level > EXHAUSTION_PER_FOOD {
+            self.exhaustion_level -= EXHAUSTION_PER_FOOD;
+            if self.saturation_level > 0.0 {
+                self.saturation_level = (self.saturation_level - 1.0).max(0.0);
+            } else if difficulty != Difficulty::Peaceful {
+                self.food_level = (self.food_level - 1).max(0);
+            }
+        }
+
+        let hurt = health > 0.0 && health < max_health;
+        if natural_regeneration
+            && hurt
+            && self.saturation_level > 0.0
+            && self.food_level >= MAX_FOOD_LEVEL
+        {
+            // Saturated players heal quickly, burning their saturation
+            self.tick_timer += 1;
+            if self.tick_timer >= 10 {
+                let amount = self.saturation_level.min(6.0);
+                self.add_exhaustion(amount);
+                self.tick_timer = 0;
+                return FoodEffect::Heal(amount / 6.0);
+            }
+        } else if natural_regeneration && hurt && self.food_level >= HEAL_FOOD_LEVEL {
+            self.tick_timer += 1;
+            if self.tick_timer >= 80 {
+                self.add_exhaustion(6.0);
+                self.tick_timer = 0;
+                return FoodEffect::Heal(1.0);
+            }
+        } else if self.food_level <= 0 {
+            self.tick_timer += 1;
+            if self.tick_timer >= 80 {
+                self.tick_timer = 0;
+                if health > difficulty.starvation_health_floor() {
+                    return FoodEffect::Starve;
+                }
+            }
+        } else {
+            self.tick_timer = 0;
+        }
+        FoodEffect::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exhaustion_uses_saturation_before_food() {
+        let mut food = FoodData::default();
+        food.add_exhaustion(4.5);
+        assert_eq!(
+            food.tick(Difficulty::Normal, true, 20.0, 20.0),
+            FoodEffect::None
+        );
+        assert!((food.saturation_level() - 4.0).abs() < f32::EPSILON);
+        assert_eq!(food.food_level(), MAX_FOOD_LEVEL);
+
+        food.set_saturation_level(0.0);
+        food.add_exhaustion(4.5);
+        food.tick(Difficulty::Normal, true, 20.0, 20.0);
+        assert_eq!(food.food_level(), MAX_FOOD_LEVEL - 1);
+    }
+
+    #[test]
+    fn hungry_players_cannot_sprint() {
+        let mut food = FoodData::default();
+        assert!(food.can_sprint());
+        food.set_food_level(SPRINT_FOOD_LEVEL);
+        assert!(!food.can_sprint());
+    }
+
+    #[test]
+    fn starving_stops_at_half_health_on_easy() {
+        let mut food = FoodData::default();
+        food.set_food_level(0);
+        let mut effect = FoodEffect::None;
+        for _ in 0..80 {
+            effect = food.tick(Difficulty::Easy, true, 10.0, 20.0);
+        }
+        assert_eq!(effect, FoodEffect::None);
+        for _ in 0..80 {
+            effect = food.tick(Difficulty::Easy, true, 11.0, 20.0);
+        }
+        assert_eq!(effect, FoodEffect::Starve);
+    }
+}
@@ -4,11 +4,16 @@
 //! the `useItemOn` method that handles block placement and block interactions.
 
 use steel_registry::REGISTRY;
+use steel_utils::translations;
 use steel_utils::types::{GameType, InteractionHand};
+use text_components::TextComponent;
 
 use crate::behavior::{
-    BLOCK_BEHAVIORS, BlockHitResult, ITEM_BEHAVIORS, InteractionResult, UseOnContext,
+    BLOCK_BEHAVIORS, BlockHitResult, ITEM_BEHAVIORS, InteractionResult, UseItemContext,
+    UseOnContext,
 };
+use crate::inventory::chest_menu::ChestMenuProvider;
+use crate::inventory::lock::ContainerRef;
 use crate::player::Player;
 use crate::world::World;
 
@@ -115,3 +120,118 @@ pub fn use_item_on(
 
     InteractionResult::Pass
 }
+
+/// Handles using an item without targeting a block.
+///
+/// This implements the logic from Java's `ServerPlayerGameMode.useItem()`.
+pub fn use_item(player: &Player, world: &World, hand: InteractionHand) -> InteractionResult {
+    if player.game_mode.load() == GameType::Spectator {
+        return InteractionResult::Pass;
+    }
+
+    // The trading screen syncs the inventory, so it opens before it is locked
+    if let Some(result) = world.interact_with_villager(player, entity_id) {
+        return result;
+    }
+
+    let mut inv = player.inventory.lock();
+    let item_stack = inv.get_item_in_hand_mut(hand);
+    if item_stack.is_empty() {
+        return InteractionResult::Pass;
+    }
+
+    // TODO: Check item cooldowns
+    let original_count = item_stack.count;
+
+    let mut context = UseItemContext {
+        player,
+        hand,
+        world,
+        item_stack,
+        items_to_give: Vec::new(),
+    };
+
+    let item_behavior = ITEM_BEHAVIORS.get_behavior(context.item_stack.item);
+    let result = item_behavior.use_item(&mut context);
+
+    // Restore count for creative mode (infinite materials)
+    if player.has_infinite_materials() && context.item_stack.count < original_count {
+        context.item_stack.count = original_count;
+    }
+
+    let items_to_give = context.items_to_give;
+    drop(inv);
+    for item in items_to_give {
+        player.add_item_or_drop(item);
+    }
+
+    result
+}
+
+/// Handles a player right-clicking the entity with network id `entity_id`.
+///
+/// This implements the logic from Java's `Player.interactOn()`.
+pub fn interact_on(
+    player: &Player,
+    world: &World,
+    entity_id: i32,
+    hand: InteractionHand,
+) -> InteractionResult {
+    // Chest minecarts open their chest even for spectators
+    if let Some(items) = world.chest_minecart_items(entity_id) {
+        player.open_menu(&ChestMenuProvider::three_rows(
+            player.inventory.clone(),
+            ContainerRef::Other(items),
+            TextComponent::translated(translations::ENTITY_MINECRAFT_CHEST_MINECART.msg()),
+        ));
+        return InteractionResult::Success;
+    }
+    // TODO: Open hopper minecarts once there is a hopper menu
+
+    if player.game_mode.load() == GameType::Spectator {
+        // TODO: Spectators open the menus of entities like chest boats
+        return InteractionResult::Pass;
+    }
+
+    let ride = world.start_riding(player, entity_id);
+    if ride.consumes_action() {
+        return ride;
+    }
+
+    let mut inv = player.inventory.lock();
+    let item_stack = inv.get_item_in_hand_mut(hand);
+    let original_count = item_stack.count;
+
+    let result = world.interact_with_item_frame(entity_id, item_stack);
+
+    // Restore count for creative mode (infinite materials)
+    if result.consumes_action()
+        && player.has_infinite_materials()
+        && item_stack.count < original_count
+    {
+        item_stack.count = original_count;
+    }
+
+    result
+}
+
+/// Handles a player right-clicking the entity with network id `entity_id`
+/// `hit_y` blocks above its feet.
+///
+/// This implements the logic from Java's `Entity.interactAt()`, which only
+/// armor stands make use of.
+pub fn interact_at(
+    player: &Player,
+    world: &World,
+    entity_id: i32,
+    hit_y: f64,
+    hand: InteractionHand,
+) -> InteractionResult {
+    if player.game_mode.load() == GameType::Spectator {
+        return InteractionResult::Pass;
+    }
+
+    let mut inv = player.inventory.lock();
+    let held = inv.get_item_in_hand_mut(hand);
+    world.interact_at_armor_stand(entity_id, held, hit_y, player.has_infinite_materials())
+}
@@ -1,7 +1,11 @@
 //! This module contains all things player-related.
 mod abilities;
+pub mod advancements;
 pub mod block_breaking;
+pub mod channels;
 pub mod chunk_sender;
+pub mod experience;
+pub mod food_data;
 mod game_mode;
 mod game_profile;
 pub mod message_chain;
@@ -12,40 +16,58 @@ pub mod networking;
 pub mod player_inventory;
 pub mod profile_key;
 mod signature_cache;
+pub mod skin_cache;
+pub mod stats;
+pub mod tab_list;
+mod teleport;
 
 pub use abilities::Abilities;
+use advancements::PlayerAdvancements;
 
+use arc_swap::ArcSwap;
 use block_breaking::BlockBreakingManager;
 use crossbeam::atomic::AtomicCell;
 pub use game_profile::{GameProfile, GameProfileAction};
 use message_chain::SignedMessageChain;
 use message_validator::LastSeenMessagesValidator;
 use profile_key::RemoteChatSession;
+use rustc_hash::{FxHashMap, FxHashSet};
 pub use signature_cache::{LastSeen, MessageCache};
+use stats::{CustomStat, PlayerStats, StatKey};
 use std::{
     sync::{
         Arc, Weak,
         atomic::{AtomicBool, AtomicI32, AtomicU8, Ordering},
     },
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
-use steel_protocol::packets::game::CSystemChatMessage;
 use steel_protocol::packets::game::{
-    AnimateAction, CAnimate, CEntityPositionSync, COpenSignEditor, CPlayerPosition, CSetEntityData,
-    CSetHeldSlot, PlayerAction, SAcceptTeleportation, SPickItemFromBlock, SPlayerAbilities,
-    SPlayerAction, SSetCarriedItem, SUseItem, SUseItemOn,
+    AnimateAction, CAnimate, CEntityPositionSync, COpenSignEditor, CPlayerPosition, CRespawn,
+    CSetEntityData, CSetEntityMotion, CSetExperience, CSetHealth, CSetHeldSlot, InteractAction,
+    KEEP_ALL_DATA, PlayerAction, PlayerCommandAction, SAcceptTeleportation, SInteract,
+    SPickItemFromBlock, SPlayerAbilities, SPlayerAction, SSetCarriedItem, SUseItem, SUseItemOn,
+    SoundSource,
 };
+use steel_protocol::packets::game::{CSystemChat, CSystemChatMessage};
+use steel_registry::advancement::{AdvancementFrame, AdvancementRef};
 use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::data_components::EquippableSlot;
+use steel_registry::data_components::vanilla_components::GLIDER;
 use steel_registry::entity_data::EntityPose;
 use steel_registry::game_rules::GameRuleValue;
 use steel_registry::vanilla_entity_data::PlayerEntityData;
-use steel_registry::vanilla_game_rules::{ELYTRA_MOVEMENT_CHECK, PLAYER_MOVEMENT_CHECK};
-use steel_registry::{REGISTRY, vanilla_chat_types};
+use steel_registry::vanilla_game_rules::{
+    ELYTRA_MOVEMENT_CHECK, NATURAL_HEALTH_REGENERATION, PLAYER_MOVEMENT_CHECK,
+    PLAYERS_NETHER_PORTAL_CREATIVE_DELAY, PLAYERS_NETHER_PORTAL_DEFAULT_DELAY,
+    SHOW_ADVANCEMENT_MESSAGES,
+};
+use steel_registry::{REGISTRY, sound_events, vanilla_chat_types};
 
 use steel_utils::locks::SyncMutex;
-use steel_utils::types::GameType;
+use steel_utils::types::{Difficulty, GameType};
 use text_components::resolving::TextResolutor;
-use text_components::{Modifier, TextComponent};
+use text_components::{Modifier, TextComponent, format::Color};
 use text_components::{
     content::Resolvable,
     custom::CustomData,
@@ -54,10 +76,11 @@ use text_components::{
 use uuid::Uuid;
 
 use crate::inventory::SyncPlayerInv;
+use crate::inventory::equipment::EquipmentSlot;
 use crate::player::player_inventory::PlayerInventory;
 use crate::{config::STEEL_CONFIG, entity::Entity};
 
-use steel_crypto::{SignatureValidator, public_key_from_bytes, signature::NoValidation};
+use steel_crypto::{SignatureValidator, mojang_api, public_key_from_bytes};
 use steel_protocol::packets::{
     common::{SClientInformation, SCustomPayload},
     game::{
@@ -65,8 +88,8 @@ use steel_protocol::packets::{
         CMoveEntityRot, COpenScreen, CPlayerChat, CPlayerInfoUpdate, CRotateHead,
         CSetChunkCacheRadius, ChatTypeBound, FilterType, GameEventType, PreviousMessage, SChat,
         SChatAck, SChatSessionUpdate, SContainerButtonClick, SContainerClick, SContainerClose,
-        SContainerSlotStateChanged, SMovePlayer, SPlayerInput, SSetCreativeModeSlot, SSignUpdate,
-        calc_delta, to_angle_byte,
+        SContainerSlotStateChanged, SMovePlayer, SPlayerCommand, SPlayerInput, SRenameItem,
+        SSelectTrade, SSetCreativeModeSlot, SSignUpdate, calc_delta, to_angle_byte,
     },
 };
 use steel_registry::{blocks::properties::Direction, item_stack::ItemStack};
@@ -77,15 +100,16 @@ use crate::block_entity::entities::SignBlockEntity;
 use steel_utils::BlockPos;
 
 use steel_utils::types::InteractionHand;
-use steel_utils::{ChunkPos, math::Vector3, translations};
+use steel_utils::{ChunkPos, Identifier, math::Vector3, translations};
 
 use crate::entity::LivingEntity;
+use crate::entity::VehicleInput;
 use crate::inventory::{
     MenuInstance, MenuProvider,
     container::Container,
     inventory_menu::InventoryMenu,
     lock::{ContainerId, ContainerLockGuard},
-    menu::Menu,
+    menu::{Menu, is_valid_remote_stack},
     slot::Slot,
 };
 
@@ -94,6 +118,36 @@ pub type PreviousMessageEntry = PreviousMessage;
 
 pub use steel_protocol::packets::common::{ChatVisibility, HumanoidArm, ParticleStatus};
 
+/// Fraction of the distance past the world border's safe zone a player is pushed back each time.
+const BORDER_PUSH_FACTOR: f64 = 0.5;
+
+/// Ticks between sending changed statistics to the client.
+const STATS_SYNC_INTERVAL: i32 = 300;
+
+/// Ticks before a player that went through a portal can use one again.
+/// Matches vanilla `Player.getDimensionChangingDelay`.
+const PORTAL_COOLDOWN: i32 = 10;
+
+/// The exhaustion a player gains from taking damage.
+/// Matches vanilla `DamageSource.getFoodExhaustion` for most damage types.
+const DAMAGE_EXHAUSTION: f32 = 0.1;
+
+/// The fall flying bit (bit 7) of the shared entity flags.
+/// Vanilla: `Entity.FLAG_FALL_FLYING`
+const FLAG_FALL_FLYING: i8 = i8::MIN;
+
+/// The crouching bit (bit 1) of the shared entity flags.
+/// Vanilla: `Entity.FLAG_SHIFT_KEY_DOWN`
+const FLAG_CROUCHING: i8 = 1 << 1;
+
+/// The sprinting bit (bit 3) of the shared entity flags.
+/// Vanilla: `Entity.FLAG_SPRINTING`
+const FLAG_SPRINTING: i8 = 1 << 3;
+
+/// The swimming bit (bit 4) of the shared entity flags.
+/// Vanilla: `Entity.FLAG_SWIMMING`
+const FLAG_SWIMMING: i8 = 1 << 4;
+
 /// Client-side settings sent via `SClientInformation` packet.
 /// This is stored separately from the packet struct to allow default initialization.
 #[derive(Debug, Clone)]
@@ -135,8 +189,20 @@ impl Default for ClientInformation {
 }
 
 use crate::chunk::player_chunk_view::PlayerChunkView;
+use crate::player::channels::PLUGIN_CHANNELS;
+use crate::player::experience::Experience;
+use crate::player::food_data::{
+    EXHAUSTION_SPRINT, EXHAUSTION_SWIM, FoodData, FoodEffect, MAX_FOOD_LEVEL,
+};
+use crate::player::teleport::{PendingTeleport, TeleportQueue};
 use crate::player::{chunk_sender::ChunkSender, networking::JavaConnection};
-use crate::world::World;
+use crate::server::Server;
+use crate::world::{
+    World,
+    map_data::{self, MAP_ITEM_DATA},
+    portal::{self, PortalType},
+    vibrations::VibrationEvent,
+};
 
 /// A struct representing a player.
 pub struct Player {
@@ -145,8 +211,11 @@ pub struct Player {
     /// The player's connection.
     pub connection: Arc<JavaConnection>,
 
-    /// The world the player is in.
-    pub world: Arc<World>,
+    /// The server the player is on, for messages that reach every world.
+    server: Weak<Server>,
+
+    /// The world the player is in, swapped out when they change dimension.
+    world: ArcSwap<World>,
 
     /// The entity ID assigned to this player.
     pub id: i32,
@@ -170,6 +239,16 @@ pub struct Player {
     speed: AtomicCell<f32>,
     /// Whether the player is sprinting.
     sprinting: AtomicBool,
+    /// How hungry the player is.
+    food_data: SyncMutex<FoodData>,
+    /// The health, food level and whether saturation was zero when they were
+    /// last sent to the client.
+    last_sent_health: SyncMutex<Option<(f32, i32, bool)>>,
+    /// The experience the player collected.
+    experience: SyncMutex<Experience>,
+    /// Seeds the enchanting table's offers, which stay the same until the
+    /// player enchants an item.
+    enchantment_seed: AtomicI32,
 
     /// The last chunk position of the player.
     pub last_chunk_pos: SyncMutex<ChunkPos>,
@@ -181,6 +260,10 @@ pub struct Player {
     /// The client's settings/information (language, view distance, chat visibility, etc.).
     /// Updated when the client sends `SClientInformation` during config or play phase.
     client_information: SyncMutex<ClientInformation>,
+    /// The brand the client reported, like `vanilla` or `fabric`.
+    pub client_brand: SyncMutex<Option<String>>,
+    /// The plugin channels the client listens on.
+    pub client_channels: SyncMutex<FxHashSet<Identifier>>,
 
     /// Counter for chat messages sent BY this player
     messages_sent: AtomicI32,
@@ -221,9 +304,18 @@ pub struct Player {
     /// Whether the player is sneaking (shift key down).
     shift_key_down: AtomicBool,
 
-    /// Position we're waiting for the client to confirm via teleport ack.
-    /// If Some, we should reject interaction packets until confirmed.
-    awaiting_position_from_client: SyncMutex<Option<Vector3<f64>>>,
+    /// Whether the jump key was held in the last input packet.
+    jump_key_down: AtomicBool,
+
+    /// The network id of the entity the player rides, if any.
+    vehicle: AtomicCell<Option<i32>>,
+
+    /// How far the player walked since their last step made a vibration.
+    step_distance: AtomicCell<f64>,
+
+    /// Teleports the client hasn't confirmed yet.
+    /// While any are pending, movement and interaction packets are rejected.
+    teleports: SyncMutex<TeleportQueue>,
 
     /// Incrementing teleport ID counter (wraps at `i32::MAX`).
     awaiting_teleport_id: AtomicI32,
@@ -260,6 +352,15 @@ pub struct Player {
     /// Whether the player is currently fall flying (elytra gliding).
     fall_flying: AtomicBool,
 
+    /// How many ticks the player has been fall flying.
+    fall_fly_ticks: AtomicI32,
+
+    /// Whether the player is swimming, lying flat in the water.
+    swimming: AtomicBool,
+
+    /// Whether the player is touching water.
+    in_water: AtomicBool,
+
     /// Whether the player is on the ground.
     on_ground: AtomicBool,
 
@@ -275,6 +376,42 @@ pub struct Player {
 
     /// Last `on_ground` state sent to tracking players (for detecting changes).
     last_sent_on_ground: AtomicBool,
+
+    /// Custom name shown in the tab list instead of the profile name (None = profile name).
+    tab_display_name: SyncMutex<Option<TextComponent>>,
+
+    /// Progress on every advancement.
+    pub advancements: SyncMutex<PlayerAdvancements>,
+
+    /// Statistics shown in the statistics screen.
+    pub stats: PlayerStats,
+
+    /// When the player last did something, used to detect idle players.
+    last_action_time: SyncMutex<Instant>,
+    /// Whether the player was warned that they are about to be kicked for idling.
+    idle_warned: AtomicBool,
+    /// Whether the player was announced as AFK.
+    afk_announced: AtomicBool,
+
+    /// Ticks the player has been standing in a nether portal.
+    portal_time: AtomicI32,
+    /// Ticks until the player can use a portal again.
+    portal_cooldown: AtomicI32,
+    /// The portal block the player is waiting to travel through.
+    portal_entrance: SyncMutex<Option<(BlockPos, PortalType)>>,
+    /// Whether the player is queued for or in the middle of portal travel.
+    portal_traveling: AtomicBool,
+
+    /// The version of each map last sent to the client, by map id.
+    sent_map_versions: SyncMutex<FxHashMap<i32, u32>>,
+}
+
+/// Returns the enchantment seed a player starts with, derived from the world
+/// seed and their UUID so their offers survive a rejoin.
+fn initial_enchantment_seed(world_seed: i64, uuid: Uuid) -> i32 {
+    let (high, low) = uuid.as_u64_pair();
+    let mixed = world_seed ^ (high ^ low) as i64;
+    (mixed ^ (mixed >> 32)) as i32
 }
 
 impl Player {
@@ -282,6 +419,7 @@ impl Player {
     pub fn new(
         gameprofile: GameProfile,
         connection: Arc<JavaConnection>,
+        server: Weak<Server>,
         world: Arc<World>,
         entity_id: i32,
         player: &Weak<Player>,
@@ -291,12 +429,16 @@ impl Player {
         let inventory = Arc::new(SyncMutex::new(PlayerInventory::new(player.clone())));
 
         let pos = Vector3::new(0.0, 0.0, 0.0);
+        let advancements = PlayerAdvancements::load(gameprofile.id);
+        let enchantment_seed = initial_enchantment_seed(world.seed(), gameprofile.id);
+        let stats = PlayerStats::load(gameprofile.id);
 
         Self {
             gameprofile,
             connection,
 
-            world,
+            server,
+            world: ArcSwap::new(world),
             id: entity_id,
             client_loaded: AtomicBool::new(false),
             position: SyncMutex::new(pos),
@@ -306,10 +448,16 @@ impl Player {
             entity_data: SyncMutex::new(PlayerEntityData::new()),
             speed: AtomicCell::new(0.1), // Default walking speed
             sprinting: AtomicBool::new(false),
+            food_data: SyncMutex::new(FoodData::default()),
+            last_sent_health: SyncMutex::new(None),
+            experience: SyncMutex::new(Experience::default()),
+            enchantment_seed: AtomicI32::new(enchantment_seed),
             last_chunk_pos: SyncMutex::new(ChunkPos::new(0, 0)),
             last_tracking_view: SyncMutex::new(None),
             chunk_sender: SyncMutex::new(ChunkSender::default()),
             client_information: SyncMutex::new(client_information),
+            client_brand: SyncMutex::new(None),
+            client_channels: SyncMutex::new(FxHashSet::default()),
             messages_sent: AtomicI32::new(0),
             messages_received: AtomicI32::new(0),
             signature_cache: SyncMutex::new(MessageCache::new()),
@@ -323,7 +471,10 @@ impl Player {
             container_counter: AtomicU8::new(0),
             ack_block_changes_up_to: AtomicI32::new(-1),
             shift_key_down: AtomicBool::new(false),
-            awaiting_position_from_client: SyncMutex::new(None),
+            jump_key_down: AtomicBool::new(false),
+            vehicle: AtomicCell::new(None),
+            step_distance: AtomicCell::new(0.0),
+            teleports: SyncMutex::new(TeleportQueue::default()),
             awaiting_teleport_id: AtomicI32::new(0),
             awaiting_teleport_time: AtomicI32::new(0),
             tick_count: AtomicI32::new(0),
@@ -335,14 +486,34 @@ impl Player {
             sleeping: AtomicBool::new(false),
             abilities: SyncMutex::new(Abilities::default()),
             fall_flying: AtomicBool::new(false),
+            fall_fly_ticks: AtomicI32::new(0),
+            swimming: AtomicBool::new(false),
+            in_water: AtomicBool::new(false),
             on_ground: AtomicBool::new(false),
             last_impulse_tick: AtomicI32::new(0),
             block_breaking: SyncMutex::new(BlockBreakingManager::new()),
             position_sync_delay: AtomicI32::new(0),
             last_sent_on_ground: AtomicBool::new(false),
+            tab_display_name: SyncMutex::new(None),
+            advancements: SyncMutex::new(advancements),
+            stats,
+            last_action_time: SyncMutex::new(Instant::now()),
+            idle_warned: AtomicBool::new(false),
+            afk_announced: AtomicBool::new(false),
+            portal_time: AtomicI32::new(0),
+            portal_cooldown: AtomicI32::new(0),
+            portal_entrance: SyncMutex::new(None),
+            portal_traveling: AtomicBool::new(false),
+            sent_map_versions: SyncMutex::new(FxHashMap::default()),
         }
     }
 
+    /// Returns the world the player is currently in.
+    #[must_use]
+    pub fn world(&self) -> Arc<World> {
+        self.world.load_full()
+    }
+
     /// Ticks the player.
     #[allow(clippy::cast_possible_truncation)]
     pub fn tick(&self) {
@@ -354,7 +525,9 @@ impl Player {
 
         // Apply gravity to delta_movement (vanilla: applyGravity in Entity.tick/LivingEntity.travel)
         // This must happen after resetPosition so the speed check has the correct expected velocity
+        self.tick_swim();
         self.apply_gravity();
+        self.tick_glide();
 
         // Sync packet counts for rate limiting (vanilla: knownMovePacketCount = receivedMovePacketCount)
         self.known_move_packet_count.store(
@@ -376,17 +549,36 @@ impl Player {
 
         *self.last_chunk_pos.lock() = chunk_pos;
 
-        self.world.chunk_map.update_player_status(self);
+        self.world().chunk_map.update_player_status(self);
 
-        self.chunk_sender
-            .lock()
-            .send_next_chunks(self.connection.clone(), &self.world, chunk_pos);
+        self.tick_world_border(current_pos);
+
+        self.tick_portal(current_pos);
+
+        let tracking_view = self.last_tracking_view.lock().clone();
+        if let Some(view) = tracking_view {
+            self.chunk_sender.lock().send_next_chunks(
+                self.connection.clone(),
+                &self.world(),
+                &view,
+            );
+        }
 
         // Broadcast inventory changes to client
         self.broadcast_inventory_changes();
 
+        self.tick_held_maps(current_pos);
+
+        self.tick_stats();
+
+        self.tick_food();
+
+        self.experience.lock().tick();
+
+        self.tick_idle();
+
         // Tick block breaking
-        self.block_breaking.lock().tick(self, &self.world);
+        self.block_breaking.lock().tick(self, &self.world());
 
         // Update pose based on current state
         self.update_pose();
@@ -401,24 +593,74 @@ impl Player {
         // - Checking if the player is alive
         // - Handling movement
         // - Updating inventory
-        // - Handling food/health regeneration
         // - Managing game mode specific logic
         // - Updating advancements
         // - Handling falling
     }
 
+    /// Marks the player as active, resetting the idle timeout.
+    pub fn reset_last_action_time(&self) {
+        *self.last_action_time.lock() = Instant::now();
+        self.idle_warned.store(false, Ordering::Relaxed);
+        self.afk_announced.store(false, Ordering::Relaxed);
+    }
+
+    /// Announces idle players as AFK and kicks them once `afk_timeout_seconds` and
+    /// the warning after it run out. A value of 0 disables either.
+    fn tick_idle(&self) {
+        let idle = self.last_action_time.lock().elapsed();
+
+        let announce_after = STEEL_CONFIG.load().afk_announce_seconds;
+        if announce_after > 0
+            && idle >= Duration::from_secs(announce_after)
+            && !self.afk_announced.swap(true, Ordering::Relaxed)
+        {
+            let message = TextComponent::plain(format!("{} is now AFK", self.gameprofile.name))
+                .color(Color::Gray);
+            if let Some(server) = self.server.upgrade() {
+                server.broadcast_system_message(&message);
+            }
+        }
+
+        let timeout = STEEL_CONFIG.load().afk_timeout_seconds;
+        if timeout == 0 || idle < Duration::from_secs(timeout) {
+            return;
+        }
+
+        let warning = STEEL_CONFIG.load().afk_warning_seconds;
+        if idle >= Duration::from_secs(timeout.saturating_add(warning)) {
+            self.connection
+                .disconnect(translations::MULTIPLAYER_DISCONNECT_IDLING.msg());
+        } else if !self.idle_warned.swap(true, Ordering::Relaxed) {
+            let message = TextComponent::plain(format!(
+                "You have been idle for {}. You will be kicked in {}",
+                duration_text(timeout),
+                duration_text(warning)
+            ))
+            .color(Color::Red);
+            self.connection
+                .send_packet(CSystemChat::new(&message, true, self));
+        }
+    }
+
     /// Syncs dirty entity data to nearby players.
     fn sync_entity_data(&self) {
         if let Some(dirty_values) = self.entity_data.lock().pack_dirty() {
             let packet = CSetEntityData::new(self.id, dirty_values);
             let chunk_pos = *self.last_chunk_pos.lock();
-            self.world.broadcast_to_nearby(chunk_pos, packet, None);
+            self.world().broadcast_to_nearby(chunk_pos, packet, None);
         }
     }
 
-    /// Handles a custom payload packet.
+    /// Handles a custom payload packet by passing it to the handler of its channel.
     pub fn handle_custom_payload(&self, packet: SCustomPayload) {
-        log::info!("Hello from the other side! {packet:?}");
+        if !PLUGIN_CHANNELS.dispatch(self, &packet.identifier, &packet.payload.0) {
+            log::debug!(
+                "{} sent a message on unknown channel {}",
+                self.gameprofile.name,
+                packet.identifier
+            );
+        }
     }
 
     /// Handles the end of a client tick.
@@ -505,9 +747,10 @@ impl Player {
         }
     }
 
-    /// Handles a chat message from the player.
+    /// Handles a chat message from the player, sending it to everyone on
+    /// the server.
     #[allow(clippy::too_many_lines)]
-    pub fn handle_chat(&self, packet: SChat, player: Arc<Player>) {
+    pub fn handle_chat(&self, packet: SChat, player: Arc<Player>, server: &Server) {
         let chat_message = packet.message.clone();
 
         let verification_result = if let Some(_signature) = &packet.signature {
@@ -529,7 +772,7 @@ impl Player {
             None
         };
 
-        if STEEL_CONFIG.enforce_secure_chat {
+        if STEEL_CONFIG.load().enforce_secure_chat {
             match &verification_result {
                 Some(Ok(_)) => {}
                 Some(Err(err)) => {
@@ -596,25 +839,16 @@ impl Player {
                 };
 
                 log::info!("<{}> {}", player.gameprofile.name, chat_message);
-                self.world.broadcast_chat(
-                    chat_packet,
-                    Arc::clone(&player),
-                    last_seen,
-                    Some(sig_array),
-                );
+                server.broadcast_chat(chat_packet, Arc::clone(&player), last_seen, Some(sig_array));
             } else {
-                self.world.broadcast_unsigned_chat(
+                server.broadcast_unsigned_chat(
                     chat_packet,
                     &player.gameprofile.name,
                     &chat_message,
                 );
             }
         } else {
-            self.world.broadcast_unsigned_chat(
-                chat_packet,
-                &player.gameprofile.name,
-                &chat_message,
-            );
+            server.broadcast_unsigned_chat(chat_packet, &player.gameprofile.name, &chat_message);
         }
     }
 
@@ -641,8 +875,11 @@ impl Player {
     /// Returns `true` if awaiting teleport (movement should be rejected),
     /// `false` if normal movement processing should continue.
     fn update_awaiting_teleport(&self) -> bool {
-        let awaiting = self.awaiting_position_from_client.lock();
-        if let Some(pos) = *awaiting {
+        let mut teleports = self.teleports.lock();
+        if let Some(teleport) = teleports.pop_confirmed() {
+            self.snap_to_teleport(teleport.position);
+        }
+        if let Some(&PendingTeleport { id, position: pos }) = teleports.latest() {
             let current_tick = self.tick_count.load(Ordering::Relaxed);
             let last_time = self.awaiting_teleport_time.load(Ordering::Relaxed);
 
@@ -650,18 +887,12 @@ impl Player {
             if current_tick.wrapping_sub(last_time) > 20 {
                 self.awaiting_teleport_time
                     .store(current_tick, Ordering::Relaxed);
-                drop(awaiting);
+                drop(teleports);
 
-                // Resend the teleport packet
+                // Resend the newest teleport, older ones are outdated anyway
                 let (yaw, pitch) = self.rotation.load();
-                let teleport_id = self.awaiting_teleport_id.load(Ordering::Relaxed);
                 self.connection.send_packet(CPlayerPosition::absolute(
-                    teleport_id,
-                    pos.x,
-                    pos.y,
-                    pos.z,
-                    yaw,
-                    pitch,
+                    id, pos.x, pos.y, pos.z, yaw, pitch,
                 ));
             }
             return true; // Still awaiting, reject movement
@@ -699,14 +930,14 @@ impl Player {
     /// Returns `true` if movement should be validated, `false` to skip validation.
     fn should_validate_movement(&self, is_fall_flying: bool) -> bool {
         // Check playerMovementCheck gamerule
-        let player_check = self.world.get_game_rule(PLAYER_MOVEMENT_CHECK);
+        let player_check = self.world().get_game_rule(PLAYER_MOVEMENT_CHECK);
         if player_check != GameRuleValue::Bool(true) {
             return false;
         }
 
         // If fall flying, also check elytraMovementCheck gamerule
         if is_fall_flying {
-            let elytra_check = self.world.get_game_rule(ELYTRA_MOVEMENT_CHECK);
+            let elytra_check = self.world().get_game_rule(ELYTRA_MOVEMENT_CHECK);
             return elytra_check == GameRuleValue::Bool(true);
         }
 
@@ -743,6 +974,14 @@ impl Player {
             return;
         }
 
+        // Passengers only look around, their vehicle carries them
+        if self.vehicle().is_some() {
+            if packet.has_rot {
+                self.rotation.store((packet.y_rot, packet.x_rot));
+            }
+            return;
+        }
+
         let prev_pos = *self.prev_position.lock();
         let prev_rot = self.prev_rotation.load();
         let start_pos = *self.position.lock();
@@ -751,9 +990,10 @@ impl Player {
         let is_creative = game_mode == GameType::Creative;
         let is_sleeping = self.sleeping.load(Ordering::Relaxed);
         let is_fall_flying = self.fall_flying.load(Ordering::Relaxed);
+        let is_in_water = self.in_water.load(Ordering::Relaxed);
         let was_on_ground = self.on_ground.load(Ordering::Relaxed);
         // Skip movement checks when tick rate is frozen (vanilla: tickRateManager().runsNormally())
-        let tick_frozen = !self.world.tick_runs_normally();
+        let tick_frozen = !self.world().tick_runs_normally();
 
         // Handle position updates
         if packet.has_pos {
@@ -800,7 +1040,7 @@ impl Player {
 
                 // Validate movement using physics simulation
                 let mut validation = movement::validate_movement(
-                    &self.world,
+                    &self.world(),
                     &movement::MovementInput {
                         target_pos,
                         first_good_pos: first_good,
@@ -808,6 +1048,7 @@ impl Player {
                         expected_velocity_sq: self.get_delta_movement_length_sq(),
                         delta_packets,
                         is_fall_flying,
+                        is_in_water,
                         skip_checks,
                         in_impulse_grace: self.is_in_post_impulse_grace_time(),
                         is_crouching: self.shift_key_down.load(Ordering::Relaxed),
@@ -836,8 +1077,7 @@ impl Player {
                 // Jump detection (vanilla: jumpFromGround)
                 let moved_upwards = validation.move_delta.y > 0.0;
                 if was_on_ground && !packet.on_ground && moved_upwards {
-                    // Player jumped - could trigger jump-related mechanics here
-                    // For now, this is a placeholder for future jump handling
+                    self.award_stat(StatKey::custom(CustomStat::Jump), 1);
                 }
             }
         }
@@ -848,6 +1088,11 @@ impl Player {
         // Update current state
         if packet.has_pos {
             *self.position.lock() = packet.position;
+            self.check_movement_statistics(
+                packet.position.x - start_pos.x,
+                packet.position.y - start_pos.y,
+                packet.position.z - start_pos.z,
+            );
         }
         if packet.has_rot {
             self.rotation.store((packet.y_rot, packet.x_rot));
@@ -902,7 +1147,7 @@ impl Player {
                             pitch,
                             on_ground: packet.on_ground,
                         };
-                        self.world
+                        self.world()
                             .broadcast_to_nearby(new_chunk, sync_packet, Some(self.id));
                     } else {
                         let move_packet = CMoveEntityPosRot {
@@ -914,7 +1159,7 @@ impl Player {
                             x_rot: to_angle_byte(pitch),
                             on_ground: packet.on_ground,
                         };
-                        self.world
+                        self.world()
                             .broadcast_to_nearby(new_chunk, move_packet, Some(self.id));
                     }
                 } else {
@@ -936,7 +1181,7 @@ impl Player {
                         pitch,
                         on_ground: packet.on_ground,
                     };
-                    self.world
+                    self.world()
                         .broadcast_to_nearby(new_chunk, sync_packet, Some(self.id));
                 }
             } else {
@@ -946,7 +1191,7 @@ impl Player {
                     x_rot: to_angle_byte(pitch),
                     on_ground: packet.on_ground,
                 };
-                self.world
+                self.world()
                     .broadcast_to_nearby(new_chunk, rot_packet, Some(self.id));
             }
 
@@ -955,7 +1200,7 @@ impl Player {
                     entity_id: self.id,
                     head_y_rot: to_angle_byte(yaw),
                 };
-                self.world
+                self.world()
                     .broadcast_to_nearby(new_chunk, head_packet, Some(self.id));
             }
 
@@ -998,7 +1243,7 @@ impl Player {
         // Broadcast the chat session to all players so they can verify this player's signatures
         let update_packet =
             CPlayerInfoUpdate::update_chat_session(self.gameprofile.id, protocol_data);
-        self.world.broadcast_to_all(update_packet);
+        self.world().tab_list.broadcast(&update_packet);
     }
 
     /// Gets a reference to the player's chat session if present
@@ -1029,7 +1274,7 @@ impl Player {
                     self.gameprofile.name
                 );
                 // Phase 4: Kick if enforcement is enabled
-                if STEEL_CONFIG.enforce_secure_chat {
+                if STEEL_CONFIG.load().enforce_secure_chat {
                     log::error!(
                         "Player {} kicked for invalid public key",
                         self.gameprofile.name
@@ -1043,14 +1288,23 @@ impl Player {
         let profile_key_data =
             profile_key::ProfilePublicKeyData::new(expires_at, public_key, packet.key_signature);
 
-        let validator = Box::new(NoValidation) as Box<dyn SignatureValidator>;
-
         let session_data = profile_key::RemoteChatSessionData {
             session_id: packet.session_id,
             profile_public_key: profile_key_data,
         };
 
-        match session_data.validate(self.gameprofile.id, &*validator) {
+        // Offline players have no Mojang-signed key, so there is nothing to check
+        if !STEEL_CONFIG.load().online_mode {
+            self.set_chat_session(session_data.into_unvalidated());
+            return;
+        }
+
+        let result = match mojang_api::profile_key_validator() {
+            Some(validator) => session_data.validate(self.gameprofile.id, &*validator),
+            None => Err(profile_key::ValidationError::MissingMojangKeys),
+        };
+
+        match result {
             Ok(session) => {
                 self.set_chat_session(session);
             }
@@ -1059,7 +1313,7 @@ impl Player {
                     "Player {} sent invalid chat session: {err}",
                     self.gameprofile.name
                 );
-                if STEEL_CONFIG.enforce_secure_chat {
+                if STEEL_CONFIG.load().enforce_secure_chat {
                     self.connection
                         .disconnect(format!("Chat session validation failed: {err}"));
                 }
@@ -1078,6 +1332,9 @@ impl Player {
     }
 
     /// Handles client information updates during play phase.
+    ///
+    /// The view distance is clamped to the server's; if the effective value
+    /// changes, the player's chunk view is resized immediately.
     pub fn handle_client_information(&self, packet: SClientInformation) {
         let old_view_distance = self.view_distance();
 
@@ -1099,6 +1356,11 @@ impl Player {
             self.connection.send_packet(CSetChunkCacheRadius {
                 radius: i32::from(new_view_distance),
             });
+            // Queue newly visible chunks and forget the ones now out of range
+            // right away instead of waiting for the next tick
+            if self.last_tracking_view.lock().is_some() {
+                self.world().chunk_map.update_player_status(self);
+            }
         }
     }
 
@@ -1128,11 +1390,27 @@ impl Player {
         // This updates PlayerInfo on clients, which is used for isSpectator() checks
         let update_packet =
             CPlayerInfoUpdate::update_game_mode(self.gameprofile.id, gamemode as i32);
-        self.world.broadcast_to_all(update_packet);
+        self.world().tab_list.broadcast(&update_packet);
 
         true
     }
 
+    /// Returns the custom tab list display name, if one is set.
+    #[must_use]
+    pub fn tab_display_name(&self) -> Option<TextComponent> {
+        self.tab_display_name.lock().clone()
+    }
+
+    /// Sets the name shown for this player in the tab list.
+    ///
+    /// Passing `None` resets the entry back to the player's profile name.
+    pub fn set_tab_display_name(&self, name: Option<TextComponent>) {
+        *self.tab_display_name.lock() = name.clone();
+
+        let update_packet = CPlayerInfoUpdate::update_display_name(self.gameprofile.id, name);
+        self.world().tab_list.broadcast(&update_packet);
+    }
+
     /// Sends the player abilities packet to the client.
     /// This tells the client about flight, invulnerability, speeds, etc.
     pub fn send_abilities(&self) {
@@ -1141,19 +1419,20 @@ impl Player {
     }
 
     /// Handles a container button click packet (e.g., enchanting table buttons).
+    ///
+    /// Based on Java's `ServerGamePacketListenerImpl::handleContainerButtonClick`.
     pub fn handle_container_button_click(&self, packet: SContainerButtonClick) {
-        log::debug!(
-            "Player {} clicked button {} in container {}",
-            self.gameprofile.name,
-            packet.button_id,
-            packet.container_id
-        );
-        // TODO: Implement container button click handling
-        // This is used for things like:
-        // - Enchanting table level selection
-        // - Stonecutter recipe selection
-        // - Loom pattern selection
-        // - Lectern page turning
+        if self.game_mode.load() == GameType::Spectator {
+            return;
+        }
+        let mut open_menu = self.open_menu.lock();
+        if let Some(ref mut menu) = *open_menu
+            && i32::from(menu.container_id()) == packet.container_id
+            && menu.still_valid()
+            && menu.click_menu_button(packet.button_id, self)
+        {
+            menu.behavior_mut().broadcast_changes(&self.connection);
+        }
     }
 
     /// Handles a container click packet (slot interaction).
@@ -1206,8 +1485,23 @@ impl Player {
             return;
         }
 
+        // Reject clicks reporting slots or stacks that can't exist, the client
+        // is out of sync or forging the packet
+        let slot_count = menu.behavior().slot_count();
+        let valid_changes = packet.changed_slots.iter().all(|(&slot, hash)| {
+            usize::try_from(slot).is_ok_and(|slot| slot < slot_count) && is_valid_remote_stack(hash)
+        }) && is_valid_remote_stack(&packet.carried_item);
+        if !valid_changes {
+            log::debug!(
+                "Player {} sent an invalid container click, resyncing",
+                self.gameprofile.name
+            );
+            menu.behavior_mut().broadcast_full_state(&self.connection);
+            return;
+        }
+
         // Check if we need a full resync (state ID mismatch)
-        let full_resync_needed = packet.state_id as u32 != menu.behavior().get_state_id();
+        let full_resync_needed = !menu.behavior().verify_state_id(packet.state_id);
 
         // Suppress remote updates during click handling
         menu.behavior_mut().suppress_remote_updates();
@@ -1285,6 +1579,35 @@ impl Player {
         // This is used for the crafter block to enable/disable slots
     }
 
+    /// Handles the anvil name field being edited.
+    ///
+    /// Based on Java's `ServerGamePacketListenerImpl::handleRenameItem`.
+    pub fn handle_rename_item(&self, packet: SRenameItem) {
+        let mut open_menu = self.open_menu.lock();
+        if let Some(ref mut menu) = *open_menu
+            && menu.still_valid()
+        {
+            menu.set_item_name(&packet.name, self);
+            menu.behavior_mut().broadcast_changes(&self.connection);
+        }
+    }
+
+    /// Handles a trade being picked in the trading screen.
+    ///
+    /// Based on Java's `ServerGamePacketListenerImpl::handleSelectTrade`.
+    pub fn handle_select_trade(&self, packet: SSelectTrade) {
+        let Ok(index) = usize::try_from(packet.item) else {
+            return;
+        };
+        let mut open_menu = self.open_menu.lock();
+        if let Some(ref mut menu) = *open_menu
+            && menu.still_valid()
+        {
+            menu.select_trade(index, self);
+            menu.behavior_mut().broadcast_changes(&self.connection);
+        }
+    }
+
     /// Handles a creative mode slot set packet.
     pub fn handle_set_creative_mode_slot(&self, packet: SSetCreativeModeSlot) {
         // Only allow in creative mode
@@ -1346,21 +1669,54 @@ impl Player {
         }
     }
 
-    /// Returns true if player is within block interaction range.
+    /// Returns true if player is within block interaction range, measured
+    /// from their eyes to the closest point of the block.
     /// Base range is ~4.5 blocks, plus 1.0 tolerance.
+    ///
+    /// Based on Java's `Player::isWithinBlockInteractionRange`.
     #[must_use]
     pub fn is_within_block_interaction_range(&self, pos: &BlockPos) -> bool {
         let player_pos = *self.position.lock();
-        let block_center_x = f64::from(pos.x()) + 0.5;
-        let block_center_y = f64::from(pos.y()) + 0.5;
-        let block_center_z = f64::from(pos.z()) + 0.5;
+        let eye_y = player_pos.y + self.eye_height();
+        let distance_to_block = |eye: f64, block: i32| {
+            let min = f64::from(block);
+            eye - eye.clamp(min, min + 1.0)
+        };
 
         // Base range is ~4.5 blocks, plus 1.0 tolerance
         let max_range = 4.5 + 1.0;
-        let dx = player_pos.x - block_center_x;
-        let dy = player_pos.y - block_center_y;
-        let dz = player_pos.z - block_center_z;
-        (dx * dx + dy * dy + dz * dz).sqrt() <= max_range
+        let dx = distance_to_block(player_pos.x, pos.x());
+        let dy = distance_to_block(eye_y, pos.y());
+        let dz = distance_to_block(player_pos.z, pos.z());
+        dx * dx + dy * dy + dz * dz < max_range * max_range
+    }
+
+    /// Returns true if the player can reach an entity taking up
+    /// `bounding_box`.
+    #[must_use]
+    pub fn is_within_entity_interaction_range(&self, bounding_box: &AABBd) -> bool {
+        let player_pos = *self.position.lock();
+        let eye_y = player_pos.y + self.eye_height();
+        let distance = |eye: f64, min: f64, max: f64| eye - eye.clamp(min, max);
+
+        // Base range is 3 blocks, plus 3.0 tolerance
+        let max_range = 3.0 + 3.0;
+        let dx = distance(player_pos.x, bounding_box.min_x, bounding_box.max_x);
+        let dy = distance(eye_y, bounding_box.min_y, bounding_box.max_y);
+        let dz = distance(player_pos.z, bounding_box.min_z, bounding_box.max_z);
+        dx * dx + dy * dy + dz * dz < max_range * max_range
+    }
+
+    /// Returns the network id of the entity the player rides, if any.
+    #[must_use]
+    pub fn vehicle(&self) -> Option<i32> {
+        self.vehicle.load()
+    }
+
+    /// Sets the entity the player rides. Only tracks it, the world lets the
+    /// player on and off.
+    pub fn set_vehicle(&self, vehicle: Option<i32>) {
+        self.vehicle.store(vehicle);
     }
 
     /// Returns true if player is sneaking (secondary use active).
@@ -1369,6 +1725,232 @@ impl Player {
         self.shift_key_down.load(Ordering::Relaxed)
     }
 
+    /// Stores whether the player holds the sneak key and syncs it to clients
+    /// with the crouching bit of the shared flags.
+    ///
+    /// Based on Java's `Entity::setShiftKeyDown`.
+    pub fn set_shift_key_down(&self, shift_key_down: bool) {
+        if self.shift_key_down.swap(shift_key_down, Ordering::Relaxed) == shift_key_down {
+            return;
+        }
+
+        let mut entity_data = self.entity_data.lock();
+        let flags = *entity_data.shared_flags.get();
+        entity_data.shared_flags.set(if shift_key_down {
+            flags | FLAG_CROUCHING
+        } else {
+            flags & !FLAG_CROUCHING
+        });
+    }
+
+    /// Returns true if the player is sprinting.
+    #[must_use]
+    pub fn is_sprinting(&self) -> bool {
+        self.sprinting.load(Ordering::Relaxed)
+    }
+
+    /// Sets the player's sprinting state and syncs it to clients with the
+    /// sprinting bit of the shared flags.
+    pub fn set_sprinting(&self, sprinting: bool) {
+        self.sprinting.store(sprinting, Ordering::Relaxed);
+
+        let mut entity_data = self.entity_data.lock();
+        let flags = *entity_data.shared_flags.get();
+        entity_data.shared_flags.set(if sprinting {
+            flags | FLAG_SPRINTING
+        } else {
+            flags & !FLAG_SPRINTING
+        });
+        // TODO: Apply speed modifiers when attribute system is implemented
+        // TODO: Stop sprinting after a sprint attack once melee attacks exist
+    }
+
+    /// Returns true if the player isn't too hungry to sprint.
+    ///
+    /// Based on Java's `LocalPlayer::hasEnoughFoodToDoExhaustiveManoeuvres`.
+    #[must_use]
+    pub fn can_sprint(&self) -> bool {
+        self.abilities.lock().may_fly || self.food_data.lock().can_sprint()
+    }
+
+    /// Returns the player's hunger.
+    #[must_use]
+    pub fn food_data(&self) -> &SyncMutex<FoodData> {
+        &self.food_data
+    }
+
+    /// Adds exhaustion from the player being active. Players that can't
+    /// take damage don't get hungry.
+    ///
+    /// Based on Java's `Player::causeFoodExhaustion`.
+    pub fn cause_food_exhaustion(&self, exhaustion: f32) {
+        if self.abilities.lock().invulnerable {
+            return;
+        }
+        self.food_data.lock().add_exhaustion(exhaustion);
+    }
+
+    /// Hurts the player by `amount`, like when a mob hits them. Returns
+    /// false if the player can't take damage.
+    ///
+    /// Based on Java's `Player::hurtServer`.
+    // TODO: Take damage sources, armor and the hurt cooldown into account once
+    // the damage system exists
+    pub fn take_damage(&self, amount: f32) -> bool {
+        if amount <= 0.0 || self.abilities.lock().invulnerable {
+            return false;
+        }
+
+        let health = *self.entity_data.lock().health.get();
+        // Players can't respawn yet, so damage never kills them
+        self.set_health_internal((health - amount).max(health.min(1.0)));
+        self.cause_food_exhaustion(DAMAGE_EXHAUSTION);
+        self.send_health_if_changed();
+        self.world().play_sound(
+            sound_events::ENTITY_PLAYER_HURT,
+            SoundSource::Players,
+            self.position.lock().to_block_pos(),
+            1.0,
+            1.0,
+            None,
+        );
+        true
+    }
+
+    /// Advances the player's hunger, heals or starves them, and tells the
+    /// client about their health and food.
+    ///
+    /// Based on Java's `Player::aiStep` and `ServerPlayer::doTick`.
+    fn tick_food(&self) {
+        let world = self.world();
+        let difficulty = world.difficulty();
+        let natural_regeneration =
+            world.get_game_rule(NATURAL_HEALTH_REGENERATION) == GameRuleValue::Bool(true);
+        let health = *self.entity_data.lock().health.get();
+        let max_health = self.get_max_health();
+
+        if difficulty == Difficulty::Peaceful && natural_regeneration {
+            let tick = self.tick_count.load(Ordering::Relaxed);
+            if health < max_health && tick % 20 == 0 {
+                self.set_health_internal(health + 1.0);
+            }
+            let mut food_data = self.food_data.lock();
+            if tick % 10 == 0 && food_data.food_level() < MAX_FOOD_LEVEL {
+                let food_level = food_data.food_level();
+                food_data.set_food_level(food_level + 1);
+            }
+        }
+
+        let health = *self.entity_data.lock().health.get();
+        let effect =
+            self.food_data
+                .lock()
+                .tick(difficulty, natural_regeneration, health, max_health);
+        match effect {
+            FoodEffect::None => {}
+            FoodEffect::Heal(amount) => self.set_health_internal(health + amount),
+            // TODO: Hurt through the damage system with the starve damage type
+            // once it exists, so hard difficulty starvation kills the player
+            FoodEffect::Starve => {
+                self.set_health_internal((health - 1.0).max(difficulty.starvation_health_floor()));
+            }
+        }
+
+        if self.is_sprinting() && !self.can_sprint() {
+            self.set_sprinting(false);
+        }
+
+        self.send_health_if_changed();
+    }
+
+    /// Sets the player's health, clamped to their maximum health.
+    fn set_health_internal(&self, health: f32) {
+        let clamped = health.clamp(0.0, self.get_max_health());
+        self.entity_data.lock().health.set(clamped);
+    }
+
+    /// Sends the player's health and food to the client if they changed
+    /// since they were last sent.
+    fn send_health_if_changed(&self) {
+        let health = *self.entity_data.lock().health.get();
+        let (food, saturation) = {
+            let food_data = self.food_data.lock();
+            (food_data.food_level(), food_data.saturation_level())
+        };
+
+        let state = (health, food, saturation <= 0.0);
+        let mut last_sent = self.last_sent_health.lock();
+        if *last_sent == Some(state) {
+            return;
+        }
+        *last_sent = Some(state);
+        self.connection.send_packet(CSetHealth {
+            health,
+            food,
+            saturation,
+        });
+    }
+
+    /// Returns the experience the player collected.
+    #[must_use]
+    pub fn experience(&self) -> &SyncMutex<Experience> {
+        &self.experience
+    }
+
+    /// Adds experience points, levelling the player up as the bar fills, and
+    /// shows the new bar to the client.
+    ///
+    /// Based on Java's `Player::giveExperiencePoints`.
+    pub fn give_experience_points(&self, points: i32) {
+        self.experience.lock().give_points(points);
+        self.send_experience();
+    }
+
+    /// Returns the seed of the enchanting table's offers.
+    #[must_use]
+    pub fn enchantment_seed(&self) -> i32 {
+        self.enchantment_seed.load(Ordering::Relaxed)
+    }
+
+    /// Takes the levels spent on an enchantment and rolls new enchanting
+    /// table offers.
+    ///
+    /// Based on Java's `Player::onEnchantmentPerformed`.
+    pub fn on_enchantment_performed(&self, levels: i32) {
+        self.experience.lock().give_levels(-levels);
+        self.enchantment_seed
+            .store(rand::random(), Ordering::Relaxed);
+        self.send_experience();
+    }
+
+    /// Sends the player's experience bar and level to the client.
+    pub fn send_experience(&self) {
+        let packet = {
+            let experience = self.experience.lock();
+            CSetExperience {
+                progress: experience.progress(),
+                level: experience.level(),
+                total: experience.total(),
+            }
+        };
+        self.connection.send_packet(packet);
+    }
+
+    /// Returns true if the player is in the crouching pose.
+    ///
+    /// Based on Java's `Entity::isCrouching`.
+    #[must_use]
+    pub fn is_crouching(&self) -> bool {
+        *self.entity_data.lock().pose.get() == EntityPose::Sneaking
+    }
+
+    /// Returns the player's bounding box, which is lower while crouching.
+    #[must_use]
+    pub fn bounding_box(&self) -> AABBd {
+        let height = movement::player_height(self.is_crouching());
+        movement::make_player_aabb_with_height(*self.position.lock(), height)
+    }
+
     /// Returns true if player has infinite materials (Creative mode).
     #[must_use]
     pub fn has_infinite_materials(&self) -> bool {
@@ -1392,9 +1974,176 @@ impl Player {
         self.fall_flying.load(Ordering::Relaxed)
     }
 
-    /// Sets the player's fall flying state.
+    /// Sets the player's fall flying state and syncs it to clients with the
+    /// fall flying bit of the shared flags.
     pub fn set_fall_flying(&self, fall_flying: bool) {
-        self.fall_flying.store(fall_flying, Ordering::Relaxed);
+        if self.fall_flying.swap(fall_flying, Ordering::Relaxed) != fall_flying {
+            self.fall_fly_ticks.store(0, Ordering::Relaxed);
+        }
+
+        let mut entity_data = self.entity_data.lock();
+        let flags = *entity_data.shared_flags.get();
+        entity_data.shared_flags.set(if fall_flying {
+            flags | FLAG_FALL_FLYING
+        } else {
+            flags & !FLAG_FALL_FLYING
+        });
+    }
+
+    /// Returns true if the player is in the air and wears a glider that
+    /// isn't about to break.
+    ///
+    /// Based on Java's `Player::canGlide`.
+    #[must_use]
+    pub fn can_glide(&self) -> bool {
+        if self.on_ground.load(Ordering::Relaxed) || self.abilities.lock().flying {
+            return false;
+        }
+        // TODO: Check for levitation and riding once those exist
+        let inventory = self.inventory.lock();
+        let chest = inventory.equipment().get_ref(EquipmentSlot::Chest);
+        chest.has(GLIDER)
+            && chest.is_equippable_in_slot(EquippableSlot::Chest)
+            && !chest.next_damage_will_break()
+    }
+
+    /// Starts fall flying if the player can glide, returning whether they
+    /// are gliding now.
+    ///
+    /// Based on Java's `Player::tryToStartFallFlying`.
+    pub fn try_to_start_fall_flying(&self) -> bool {
+        if !self.is_fall_flying() && !self.is_in_water() && self.can_glide() {
+            self.set_fall_flying(true);
+            return true;
+        }
+        false
+    }
+
+    /// Stops fall flying, resyncing the client even if the server already
+    /// thought the player wasn't gliding.
+    ///
+    /// Based on Java's `LivingEntity::stopFallFlying`.
+    pub fn stop_fall_flying(&self) {
+        self.set_fall_flying(true);
+        self.set_fall_flying(false);
+    }
+
+    /// Keeps a gliding player's elytra in use and moves them like vanilla
+    /// elytra physics would, so movement validation expects their speed.
+    ///
+    /// Based on Java's `LivingEntity::updateFallFlying` and
+    /// `LivingEntity::updateFallFlyingMovement`.
+    fn tick_glide(&self) {
+        if !self.is_fall_flying() {
+            return;
+        }
+        if !self.can_glide() {
+            self.set_fall_flying(false);
+            return;
+        }
+
+        let ticks = self.fall_fly_ticks.fetch_add(1, Ordering::Relaxed) + 1;
+        if ticks % 10 == 0 {
+            let infinite_materials = self.has_infinite_materials();
+            self.inventory
+                .lock()
+                .equipment_mut()
+                .get_mut(EquipmentSlot::Chest)
+                .hurt_and_break(1, infinite_materials);
+        }
+
+        let (yaw, pitch) = self.rotation.load();
+        let mut delta = self.delta_movement.lock();
+        *delta = movement::fall_flying_movement(*delta, yaw, pitch, self.get_gravity());
+    }
+
+    /// Returns true if the player is swimming.
+    #[must_use]
+    pub fn is_swimming(&self) -> bool {
+        self.swimming.load(Ordering::Relaxed)
+    }
+
+    /// Sets the player's swimming state and syncs it to clients with the
+    /// swimming bit of the shared flags.
+    pub fn set_swimming(&self, swimming: bool) {
+        self.swimming.store(swimming, Ordering::Relaxed);
+
+        let mut entity_data = self.entity_data.lock();
+        let flags = *entity_data.shared_flags.get();
+        entity_data.shared_flags.set(if swimming {
+            flags | FLAG_SWIMMING
+        } else {
+            flags & !FLAG_SWIMMING
+        });
+    }
+
+    /// Returns true if the player is touching water.
+    #[must_use]
+    pub fn is_in_water(&self) -> bool {
+        self.in_water.load(Ordering::Relaxed)
+    }
+
+    /// Returns the height of the player's eyes above their feet.
+    ///
+    /// Based on Java's `Player.getDefaultDimensions` eye heights.
+    #[must_use]
+    pub fn eye_height(&self) -> f64 {
+        match *self.entity_data.lock().pose.get() {
+            EntityPose::Swimming | EntityPose::FallFlying | EntityPose::SpinAttack => 0.4,
+            EntityPose::Sneaking => f64::from(movement::PLAYER_CROUCHING_DIMENSIONS.eye_height),
+            EntityPose::Sleeping => 0.2,
+            _ => 1.62,
+        }
+    }
+
+    /// Updates whether the player is in water and swimming, and moves them
+    /// like vanilla water physics would, so movement validation expects
+    /// their speed.
+    ///
+    /// Based on Java's `Player::updateSwimming` and
+    /// `LivingEntity::travelInWater`.
+    #[allow(clippy::cast_possible_truncation)]
+    fn tick_swim(&self) {
+        let pos = *self.position.lock();
+        let world = self.world();
+        let in_water = movement::is_in_water(&world, pos);
+        self.in_water.store(in_water, Ordering::Relaxed);
+
+        let flying = self.abilities.lock().flying;
+        let sprinting = self.sprinting.load(Ordering::Relaxed);
+        let swimming = if flying || self.game_mode.load() == GameType::Spectator {
+            false
+        } else if self.is_swimming() {
+            // Keep swimming until the player stops sprinting or surfaces
+            sprinting && in_water
+        } else {
+            let feet = BlockPos::new(
+                pos.x.floor() as i32,
+                pos.y.floor() as i32,
+                pos.z.floor() as i32,
+            );
+            sprinting
+                && movement::is_eye_in_water(&world, pos, self.eye_height())
+                && movement::water_height(&world, feet).is_some()
+        };
+        if swimming != self.is_swimming() {
+            self.set_swimming(swimming);
+        }
+
+        if !in_water || flying || self.is_fall_flying() {
+            return;
+        }
+        let (yaw, pitch) = self.rotation.load();
+        let look = Vector3::rotation_vector(f64::from(pitch), f64::from(yaw));
+        let mut delta = self.delta_movement.lock();
+        *delta = movement::water_movement(
+            *delta,
+            look.y,
+            swimming,
+            sprinting,
+            self.jump_key_down.load(Ordering::Relaxed),
+            self.get_gravity(),
+        );
     }
 
     /// Returns true if the player is flying (creative/spectator flight).
@@ -1446,8 +2195,7 @@ impl Player {
     }
 
     /// Determines the desired pose based on current player state.
-    /// Priority: `Sleeping` > `FallFlying` > `Sneaking` > `Standing`
-    // TODO: Add Swimming pose (requires water detection)
+    /// Priority: `Sleeping` > `FallFlying` > `Swimming` > `Sneaking` > `Standing`
     // TODO: Add SpinAttack pose (requires riptide trident)
     // TODO: Add pose collision checks (force crouch in low ceilings)
     fn get_desired_pose(&self) -> EntityPose {
@@ -1455,6 +2203,8 @@ impl Player {
             EntityPose::Sleeping
         } else if self.fall_flying.load(Ordering::Relaxed) {
             EntityPose::FallFlying
+        } else if self.swimming.load(Ordering::Relaxed) {
+            EntityPose::Swimming
         } else if self.shift_key_down.load(Ordering::Relaxed) && !self.abilities.lock().flying {
             EntityPose::Sneaking
         } else {
@@ -1486,7 +2236,7 @@ impl Player {
     #[must_use]
     pub fn view_distance(&self) -> u8 {
         let client_view_distance = self.client_information.lock().view_distance;
-        client_view_distance.min(STEEL_CONFIG.view_distance)
+        client_view_distance.min(STEEL_CONFIG.load().view_distance)
     }
 
     /// Returns the player's current velocity.
@@ -1500,6 +2250,19 @@ impl Player {
         *self.delta_movement.lock() = velocity;
     }
 
+    /// Adds `impulse` to the player's velocity and sends it to their client,
+    /// which moves them.
+    ///
+    /// Vanilla: `Entity.push`, sent like `ServerEntity.sendChanges` does for
+    /// a hurt player
+    pub fn push(&self, impulse: Vector3<f64>) {
+        let velocity = self.get_delta_movement() + impulse;
+        self.set_delta_movement(velocity);
+        self.apply_impulse();
+        self.connection
+            .send_packet(CSetEntityMotion::new(self.id, velocity));
+    }
+
     /// Returns the player's current gravity value.
     ///
     /// Matches vanilla `LivingEntity.getGravity()` which reads from `Attributes.GRAVITY`.
@@ -1518,15 +2281,17 @@ impl Player {
     /// - Player is in spectator mode (no physics)
     /// - Player is in creative mode and flying
     /// - Player is fall flying (elytra - uses different physics)
+    /// - Player is in water (water physics sink them slower)
     fn apply_gravity(&self) {
         let on_ground = self.on_ground.load(Ordering::Relaxed);
         let game_mode = self.game_mode.load();
         let is_spectator = game_mode == GameType::Spectator;
         let is_creative_flying = game_mode == GameType::Creative; // TODO: check actual flying state
         let is_fall_flying = self.fall_flying.load(Ordering::Relaxed);
+        let is_in_water = self.in_water.load(Ordering::Relaxed);
 
-        // Skip gravity when on ground, spectating, creative flying, or elytra flying
-        if on_ground || is_spectator || is_creative_flying || is_fall_flying {
+        // Skip gravity when on ground, spectating, creative flying, elytra flying or in water
+        if on_ground || is_spectator || is_creative_flying || is_fall_flying || is_in_water {
             return;
         }
 
@@ -1537,10 +2302,191 @@ impl Player {
         }
     }
 
+    /// Pushes the player back towards the world border once they are further
+    /// outside it than its safe zone.
+    fn tick_world_border(&self, pos: Vector3<f64>) {
+        if matches!(
+            self.game_mode.load(),
+            GameType::Creative | GameType::Spectator
+        ) || self.is_awaiting_teleport()
+        {
+            return;
+        }
+
+        let (center, overshoot) = {
+            let world = self.world();
+            let border = world.border.lock();
+            (
+                border.center,
+                -(border.distance_to_border(pos.x, pos.z) + border.safe_zone),
+            )
+        };
+        if overshoot <= 0.0 {
+            return;
+        }
+
+        // TODO: Deal `damage_per_block * overshoot` damage (vanilla: LivingEntity.baseTick)
+        // once a damage system with the outside_border damage type exists.
+
+        let (dx, dz) = (center.0 - pos.x, center.1 - pos.z);
+        let length = dx.hypot(dz);
+        if length < f64::EPSILON {
+            return;
+        }
+        let push = (overshoot * BORDER_PUSH_FACTOR).max(1.0);
+        let (yaw, pitch) = self.rotation.load();
+        self.teleport(
+            pos.x + dx / length * push,
+            pos.y,
+            pos.z + dz / length * push,
+            yaw,
+            pitch,
+        );
+    }
+
+    /// Counts how long the player has been standing in a nether portal and
+    /// queues them for travel once the wait time runs out. End portals queue
+    /// them right away.
+    ///
+    /// Matches vanilla `PortalProcessor`, the server picks the queued player up
+    /// with [`Self::take_portal_entrance`].
+    fn tick_portal(&self, pos: Vector3<f64>) {
+        if self.portal_traveling.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let cooldown = self.portal_cooldown.load(Ordering::Relaxed);
+        if cooldown > 0 {
+            self.portal_cooldown.store(cooldown - 1, Ordering::Relaxed);
+        }
+
+        let world = self.world();
+        let Some((entrance, portal_type)) = Self::portal_block_inside(&world, pos) else {
+            let time = self.portal_time.load(Ordering::Relaxed);
+            self.portal_time.store((time - 4).max(0), Ordering::Relaxed);
+            return;
+        };
+
+        // Standing in the portal we arrived through keeps the cooldown going
+        if cooldown > 0 {
+            self.portal_cooldown
+                .store(PORTAL_COOLDOWN, Ordering::Relaxed);
+            return;
+        }
+
+        if portal_type == PortalType::End {
+            *self.portal_entrance.lock() = Some((entrance, portal_type));
+            self.portal_traveling.store(true, Ordering::Relaxed);
+            return;
+        }
+
+        // Vanilla: Player.getPortalWaitTime
+        let delay_rule = if self.abilities.lock().invulnerable {
+            PLAYERS_NETHER_PORTAL_CREATIVE_DELAY
+        } else {
+            PLAYERS_NETHER_PORTAL_DEFAULT_DELAY
+        };
+        let wait_time = match world.get_game_rule(delay_rule) {
+            GameRuleValue::Int(ticks) => ticks.max(1),
+            GameRuleValue::Bool(_) => 1,
+        };
+
+        let time = self.portal_time.fetch_add(1, Ordering::Relaxed) + 1;
+        if time >= wait_time {
+            self.portal_time.store(0, Ordering::Relaxed);
+            *self.portal_entrance.lock() = Some((entrance, portal_type));
+            self.portal_traveling.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns a portal block the player's bounding box is inside of.
+    fn portal_block_inside(world: &World, pos: Vector3<f64>) -> Option<(BlockPos, PortalType)> {
+        let half_width = movement::PLAYER_WIDTH / 2.0 - movement::COLLISION_EPSILON;
+        let height = movement::PLAYER_HEIGHT - movement::COLLISION_EPSILON;
+        let (min_x, max_x) = (
+            (pos.x - half_width).floor() as i32,
+            (pos.x + half_width).floor() as i32,
+        );
+        let (min_y, max_y) = (pos.y.floor() as i32, (pos.y + height).floor() as i32);
+        let (min_z, max_z) = (
+            (pos.z - half_width).floor() as i32,
+            (pos.z + half_width).floor() as i32,
+        );
+
+        for y in min_y..=max_y {
+            for z in min_z..=max_z {
+                for x in min_x..=max_x {
+                    let block_pos = BlockPos::new(x, y, z);
+                    if let Some(portal_type) =
+                        portal::portal_type(world.get_block_state(&block_pos))
+                    {
+                        return Some((block_pos, portal_type));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Takes the portal the player is waiting to travel through, if any.
+    ///
+    /// The player stays marked as traveling until [`Self::finish_portal_travel`].
+    pub fn take_portal_entrance(&self) -> Option<(BlockPos, PortalType)> {
+        self.portal_entrance.lock().take()
+    }
+
+    /// Marks portal travel as done and starts the portal cooldown, so the
+    /// player isn't sent straight back through the portal they arrived at.
+    pub fn finish_portal_travel(&self) {
+        self.portal_cooldown
+            .store(PORTAL_COOLDOWN, Ordering::Relaxed);
+        self.portal_traveling.store(false, Ordering::Relaxed);
+    }
+
+    /// Moves the player into another world at the given position.
+    ///
+    /// The client is sent `CRespawn`, which makes it drop everything from the
+    /// old world, followed by the new world's state like on join.
+    ///
+    /// Matches vanilla `ServerPlayer.teleport` for a dimension change.
+    pub async fn change_dimension(self: &Arc<Self>, target_world: Arc<World>, pos: Vector3<f64>) {
+        let source_world = self.world();
+        if !source_world.detach_player(self).await {
+            // The player left the server in the meantime
+            return;
+        }
+
+        self.chunk_sender.lock().pending_chunks.clear();
+        self.world.store(target_world.clone());
+
+        self.connection.send_packet(CRespawn {
+            common_player_spawn_info: target_world.spawn_info(self.game_mode.load()),
+            data_to_keep: KEEP_ALL_DATA,
+        });
+
+        let (yaw, pitch) = self.rotation.load();
+        *self.prev_position.lock() = pos;
+        *self.last_good_position.lock() = pos;
+        *self.first_good_position.lock() = pos;
+        *self.delta_movement.lock() = Vector3::default();
+        self.teleport(pos.x, pos.y, pos.z, yaw, pitch);
+
+        // Vanilla: PlayerList.sendLevelInfo and sendAllPlayerInfo
+        self.send_abilities();
+        let border_packet = target_world.border.lock().initialize_packet();
+        self.connection.send_packet(border_packet);
+        self.connection
+            .send_packet(target_world.difficulty_packet());
+        self.send_inventory_to_remote();
+        self.send_experience();
+
+        target_world.add_player(self.clone());
+    }
+
     /// Returns true if we're waiting for a teleport confirmation.
     #[must_use]
     pub fn is_awaiting_teleport(&self) -> bool {
-        self.awaiting_position_from_client.lock().is_some()
+        !self.teleports.lock().is_empty()
     }
 
     /// Teleports the player to a new position.
@@ -1566,9 +2512,9 @@ impl Player {
         *self.position.lock() = Vector3::new(x, y, z);
         self.rotation.store((yaw, pitch));
 
-        // Store the position we're waiting for confirmation of
+        // Queue the position we're waiting for confirmation of
         // (vanilla stores player.position() after teleportSetPosition)
-        *self.awaiting_position_from_client.lock() = Some(Vector3::new(x, y, z));
+        self.teleports.lock().push(new_id, Vector3::new(x, y, z));
 
         // Send the teleport packet with the new ID
         self.connection
@@ -1579,39 +2525,41 @@ impl Player {
     ///
     /// Matches vanilla `ServerGamePacketListenerImpl.handleAcceptTeleportPacket()`.
     pub fn handle_accept_teleportation(&self, packet: SAcceptTeleportation) {
-        let expected_id = self.awaiting_teleport_id.load(Ordering::Relaxed);
-
-        if packet.teleport_id == expected_id {
-            let mut awaiting = self.awaiting_position_from_client.lock();
-            if awaiting.is_none() {
-                // Client sent confirmation without server sending teleport
+        let mut teleports = self.teleports.lock();
+        if !teleports.confirm(packet.teleport_id) {
+            // Confirming the newest teleport again means the client made it up
+            if teleports.is_empty()
+                && packet.teleport_id == self.awaiting_teleport_id.load(Ordering::Relaxed)
+            {
+                drop(teleports);
                 self.connection
                     .disconnect(translations::MULTIPLAYER_DISCONNECT_INVALID_PLAYER_MOVEMENT.msg());
-                return;
-            }
-
-            // Snap player to awaited position (vanilla: player.absSnapTo)
-            if let Some(pos) = *awaiting {
-                *self.position.lock() = pos;
-                *self.last_good_position.lock() = pos;
             }
+            // Otherwise it's an old or delayed packet
+            return;
+        }
 
-            // Clear awaiting state
-            *awaiting = None;
+        if let Some(teleport) = teleports.pop_confirmed() {
+            self.snap_to_teleport(teleport.position);
         }
-        // If ID doesn't match, silently ignore (could be old/delayed packet)
+    }
+
+    /// Moves the player to a teleport the client confirmed (vanilla: `player.absSnapTo`).
+    fn snap_to_teleport(&self, pos: Vector3<f64>) {
+        *self.position.lock() = pos;
+        *self.last_good_position.lock() = pos;
     }
 
     /// Sends block update packets for a position and its neighbor.
     fn send_block_updates(&self, pos: &BlockPos, direction: Direction) {
-        let state = self.world.get_block_state(pos);
+        let state = self.world().get_block_state(pos);
         self.connection.send_packet(CBlockUpdate {
             pos: *pos,
             block_state: state,
         });
 
         let neighbor_pos = direction.relative(pos);
-        let neighbor_state = self.world.get_block_state(&neighbor_pos);
+        let neighbor_state = self.world().get_block_state(&neighbor_pos);
         self.connection.send_packet(CBlockUpdate {
             pos: neighbor_pos,
             block_state: neighbor_state,
@@ -1628,15 +2576,71 @@ impl Player {
 
         let chunk = *self.last_chunk_pos.lock();
         let exclude = if update_self { None } else { Some(self.id) };
-        self.world.broadcast_to_nearby(chunk, packet, exclude);
+        self.world().broadcast_to_nearby(chunk, packet, exclude);
     }
 
     /// Handles a player input packet (movement keys, sneaking, sprinting).
     pub fn handle_player_input(&self, packet: SPlayerInput) {
-        self.shift_key_down.store(packet.shift(), Ordering::Relaxed);
+        // Passengers steer their vehicle, and sneaking gets them out
+        if self.vehicle().is_some() {
+            let world = self.world();
+            if packet.shift() {
+                world.stop_riding(self);
+            } else {
+                world.steer_vehicle(
+                    self,
+                    VehicleInput {
+                        forward: packet.forward(),
+                        backward: packet.backward(),
+                        left: packet.left(),
+                        right: packet.right(),
+                    },
+                );
+            }
+        }
+
+        // The crouching pose follows in the next tick's pose update
+        self.set_shift_key_down(packet.shift());
+        // Pressing jump in the air opens the elytra. Clients also send a
+        // command for this, which only arrives if they agree.
+        let jump_pressed = packet.jump() && !self.jump_key_down.swap(true, Ordering::Relaxed);
+        if !packet.jump() {
+            self.jump_key_down.store(false, Ordering::Relaxed);
+        }
+        if jump_pressed && !self.is_fall_flying() {
+            self.try_to_start_fall_flying();
+        }
         // Note: sprinting is handled via SPlayerCommand packet
     }
 
+    /// Handles a player command packet (sprinting, starting to glide, etc.).
+    ///
+    /// Based on Java's `ServerGamePacketListenerImpl::handlePlayerCommand`.
+    pub fn handle_player_command(&self, packet: SPlayerCommand) {
+        match packet.action {
+            PlayerCommandAction::StartSprinting => {
+                if self.can_sprint() {
+                    self.set_sprinting(true);
+                }
+            }
+            PlayerCommandAction::StopSprinting => {
+                self.set_sprinting(false);
+            }
+            PlayerCommandAction::StartFallFlying => {
+                // Already gliding if the jump input arrived first
+                if !self.is_fall_flying() && !self.try_to_start_fall_flying() {
+                    self.stop_fall_flying();
+                }
+            }
+            action => {
+                log::debug!(
+                    "Player {} sent unhandled command {action:?}",
+                    self.gameprofile.name
+                );
+            }
+        }
+    }
+
     /// Handles the use of an item on a block.
     ///
     /// Implements the logic from Java's `ServerGamePacketListenerImpl.handleUseItemOn()`.
@@ -1680,7 +2684,7 @@ impl Player {
         }
 
         // 5. Validate Y height
-        if pos.y() >= self.world.max_build_height() {
+        if pos.y() >= self.world().max_build_height() {
             // TODO: Send "build.tooHigh" message to player
             self.send_block_updates(pos, direction);
             return;
@@ -1693,13 +2697,13 @@ impl Player {
         }
 
         // 7. Check may_interact permission
-        if !self.world.may_interact(self, pos) {
+        if !self.world().may_interact(self, pos) {
             self.send_block_updates(pos, direction);
             return;
         }
 
         // 8. Call use_item_on
-        let result = game_mode::use_item_on(self, &self.world, packet.hand, &packet.block_hit);
+        let result = game_mode::use_item_on(self, &self.world(), packet.hand, &packet.block_hit);
 
         // 9. Handle result
         if let InteractionResult::Success = result {
@@ -1722,7 +2726,7 @@ impl Player {
             PlayerAction::StartDestroyBlock => {
                 self.block_breaking.lock().handle_block_break_action(
                     self,
-                    &self.world,
+                    &self.world(),
                     packet.pos,
                     BlockBreakAction::Start,
                     packet.direction,
@@ -1733,7 +2737,7 @@ impl Player {
             PlayerAction::StopDestroyBlock => {
                 self.block_breaking.lock().handle_block_break_action(
                     self,
-                    &self.world,
+                    &self.world(),
                     packet.pos,
                     BlockBreakAction::Stop,
                     packet.direction,
@@ -1744,7 +2748,7 @@ impl Player {
             PlayerAction::AbortDestroyBlock => {
                 self.block_breaking.lock().handle_block_break_action(
                     self,
-                    &self.world,
+                    &self.world(),
                     packet.pos,
                     BlockBreakAction::Abort,
                     packet.direction,
@@ -1775,17 +2779,79 @@ impl Player {
         }
     }
 
-    /// Handles the use of an item.
+    /// Handles the use of an item without targeting a block.
+    ///
+    /// Based on Java's `ServerGamePacketListenerImpl::handleUseItem`.
     pub fn handle_use_item(&self, packet: SUseItem) {
-        log::info!(
-            "Player {} used {:?} (sequence: {}, yaw: {}, pitch: {})",
-            self.gameprofile.name,
-            packet.hand,
-            packet.sequence,
-            packet.y_rot,
-            packet.x_rot
-        );
-        // TODO: Implement use item handler
+        if !self.client_loaded.load(Ordering::Relaxed) {
+            return;
+        }
+
+        self.ack_block_changes_up_to(packet.sequence);
+
+        // The client sends where it looks, so the item is used in that direction
+        self.rotation
+            .store((packet.y_rot, packet.x_rot.clamp(-90.0, 90.0)));
+
+        let result = game_mode::use_item(self, &self.world(), packet.hand);
+        if let InteractionResult::Success = result {
+            self.swing(packet.hand, true);
+        }
+
+        self.broadcast_inventory_changes();
+    }
+
+    /// Handles the player right-clicking or attacking an entity.
+    pub fn handle_interact(&self, packet: SInteract) {
+        if !self.client_loaded.load(Ordering::Relaxed) {
+            return;
+        }
+
+        self.set_shift_key_down(packet.using_secondary_action);
+
+        let world = self.world();
+        let Some(bounding_box) = world.interactable_bounding_box(packet.entity_id) else {
+            return;
+        };
+        if !self.is_within_entity_interaction_range(&bounding_box) {
+            return;
+        }
+
+        match packet.action {
+            InteractAction::Interact { hand } => {
+                let result = game_mode::interact_on(self, &world, packet.entity_id, hand);
+                if let InteractionResult::Success = result {
+                    self.swing(hand, true);
+                }
+            }
+            InteractAction::InteractAt { location, hand } => {
+                let (_, hit_y, _) = location;
+                let result =
+                    game_mode::interact_at(self, &world, packet.entity_id, f64::from(hit_y), hand);
+                if let InteractionResult::Success = result {
+                    self.swing(hand, true);
+                }
+            }
+            InteractAction::Attack => {
+                if self.game_mode.load() != GameType::Spectator
+                    && !world.attack_item_frame(self, packet.entity_id)
+                    && !world.attack_armor_stand(self, packet.entity_id)
+                    && !world.attack_zombie(self, packet.entity_id)
+                    && !world.attack_skeleton(self, packet.entity_id)
+                    && !world.attack_creeper(self, packet.entity_id)
+                    && !world.attack_slime(self, packet.entity_id)
+                    && !world.attack_iron_golem(self, packet.entity_id)
+                    && !world.attack_enderman(self, packet.entity_id)
+                    && !world.attack_ghast(self, packet.entity_id)
+                    && !world.attack_ghast_fireball(self, packet.entity_id)
+                    && !world.attack_ender_dragon(self, packet.entity_id)
+                {
+                    world.attack_minecart(self, packet.entity_id);
+                }
+            }
+        }
+
+        self.broadcast_inventory_changes();
     }
 
     /// Handles the pick block action (middle click on a block).
@@ -1800,7 +2866,7 @@ impl Player {
         }
 
         // Get block state at position
-        let state = self.world.get_block_state(&packet.pos);
+        let state = self.world().get_block_state(&packet.pos);
         if state.is_air() {
             return;
         }
@@ -1874,7 +2940,7 @@ impl Player {
         }
 
         // Get the block entity at the position
-        let Some(block_entity) = self.world.get_block_entity(&packet.pos) else {
+        let Some(block_entity) = self.world().get_block_entity(&packet.pos) else {
             return;
         };
 
@@ -1926,7 +2992,7 @@ impl Player {
 
         // Broadcast block entity update to nearby players
         if let Some(nbt) = update_tag {
-            self.world
+            self.world()
                 .broadcast_block_entity_update(pos, block_entity_type, nbt);
         }
     }
@@ -1938,7 +3004,7 @@ impl Player {
     /// * `is_front_text` - Whether to edit front (true) or back (false) text
     pub fn open_sign_editor(&self, pos: BlockPos, is_front_text: bool) {
         // Set this player as the one who may edit the sign
-        if let Some(block_entity) = self.world.get_block_entity(&pos) {
+        if let Some(block_entity) = self.world().get_block_entity(&pos) {
             let mut guard = block_entity.lock();
             if let Some(sign) = guard.as_any_mut().downcast_mut::<SignBlockEntity>() {
                 sign.set_player_who_may_edit(Some(self.gameprofile.id));
@@ -1946,7 +3012,7 @@ impl Player {
         }
 
         // Send the block update first to ensure client has latest state
-        let state = self.world.get_block_state(&pos);
+        let state = self.world().get_block_state(&pos);
         self.connection.send_packet(CBlockUpdate {
             pos,
             block_state: state,
@@ -2053,6 +3119,7 @@ impl Player {
         // First, broadcast changes for any open external menu
         let mut open_menu = self.open_menu.lock();
         if let Some(ref mut menu) = *open_menu {
+            menu.update_data_slots();
             menu.behavior_mut().broadcast_changes(&self.connection);
         } else {
             drop(open_menu);
@@ -2133,8 +3200,215 @@ impl Player {
         }
     }
 
+    /// Completes a single criterion of an advancement.
+    ///
+    /// Returns true if the criterion wasn't completed before.
+    pub fn award_criterion(&self, advancement: AdvancementRef, criterion: &str) -> bool {
+        let (packet, completed) = {
+            let mut advancements = self.advancements.lock();
+            let was_done = advancements.is_done(advancement);
+            if !advancements.award(advancement, criterion) {
+                return false;
+            }
+            (
+                advancements.progress_packet(advancement),
+                !was_done && advancements.is_done(advancement),
+            )
+        };
+
+        self.connection.send_packet(packet);
+        if completed {
+            self.on_advancement_done(advancement);
+        }
+        true
+    }
+
+    /// Completes every remaining criterion of an advancement.
+    ///
+    /// Returns true if the advancement exists and any criterion wasn't completed before.
+    pub fn grant_advancement(&self, key: &Identifier) -> bool {
+        let Some(advancement) = REGISTRY.advancements.by_key(key) else {
+            return false;
+        };
+        let mut changed = false;
+        for criterion in advancement.criteria {
+            changed |= self.award_criterion(advancement, criterion.name);
+        }
+        changed
+    }
+
+    /// Based on Java's `PlayerAdvancements.award`, after an advancement got completed.
+    fn on_advancement_done(&self, advancement: AdvancementRef) {
+        // TODO: Give the experience, recipe and function rewards once those systems exist
+
+        let Some(display) = &advancement.display else {
+            return;
+        };
+        if !display.announce_to_chat
+            || self.world().get_game_rule(SHOW_ADVANCEMENT_MESSAGES) != GameRuleValue::Bool(true)
+        {
+            return;
+        }
+
+        let (message, color) = match display.frame {
+            AdvancementFrame::Task => (&translations::CHAT_TYPE_ADVANCEMENT_TASK, Color::Green),
+            AdvancementFrame::Challenge => (
+                &translations::CHAT_TYPE_ADVANCEMENT_CHALLENGE,
+                Color::DarkPurple,
+            ),
+            AdvancementFrame::Goal => (&translations::CHAT_TYPE_ADVANCEMENT_GOAL, Color::Green),
+        };
+        let title = TextComponent::plain("[")
+            .add_children(vec![display.title.clone(), TextComponent::plain("]")])
+            .color(color);
+        let message: TextComponent = message
+            .message([TextComponent::from(self.gameprofile.name.clone()), title])
+            .into();
+
+        self.world()
+            .broadcast_to_all_with(|player| CSystemChat::new(&message, false, player));
+    }
+
+    /// Saves the player's advancement progress to disk.
+    pub fn save_advancements(&self) {
+        if let Err(e) = self.advancements.lock().save() {
+            log::error!(
+                "Failed to save advancements of {}: {e}",
+                self.gameprofile.name
+            );
+        }
+    }
+
+    /// Adds `amount` to one of the player's statistics.
+    pub fn award_stat(&self, key: StatKey, amount: i32) {
+        self.stats.increment(key, amount);
+    }
+
+    /// Draws the maps in the player's hands and sends the client any it
+    /// hasn't seen the latest version of.
+    ///
+    /// Based on Java's `MapItem::inventoryTick` and the map updates in
+    /// `ServerPlayer::synchronizeSpecialItemUpdates`, limited to held maps.
+    fn tick_held_maps(&self, pos: Vector3<f64>) {
+        let held = {
+            let inventory = self.inventory.lock();
+            [
+                map_data::map_id(inventory.get_selected_item()),
+                map_data::map_id(inventory.get_offhand_item()),
+            ]
+        };
+
+        let world = self.world();
+        let tick = self.tick_count.load(Ordering::Relaxed);
+        for map_id in held.into_iter().flatten() {
+            let packet = MAP_ITEM_DATA.with_map(map_id, |data| {
+                data.update(&world, pos.x, pos.z, tick);
+                let mut sent = self.sent_map_versions.lock();
+                (sent.insert(map_id, data.version()) != Some(data.version()))
+                    .then(|| data.update_packet(map_id))
+            });
+            if let Some(Some(packet)) = packet {
+                self.connection.send_packet(packet);
+            }
+        }
+    }
+
+    /// Based on Java's `ServerPlayer.tick` stat updates and `ServerPlayerConnection`'s
+    /// periodic stat sync.
+    fn tick_stats(&self) {
+        self.award_stat(StatKey::custom(CustomStat::PlayTime), 1);
+        self.award_stat(StatKey::custom(CustomStat::TotalWorldTime), 1);
+        // TODO: Only count while alive once deaths exist
+        self.award_stat(StatKey::custom(CustomStat::TimeSinceDeath), 1);
+        if self.shift_key_down.load(Ordering::Relaxed) {
+            self.award_stat(StatKey::custom(CustomStat::CrouchTime), 1);
+        }
+        if !self.sleeping.load(Ordering::Relaxed) {
+            self.award_stat(StatKey::custom(CustomStat::TimeSinceRest), 1);
+        }
+
+        if self.tick_count.load(Ordering::Relaxed) % STATS_SYNC_INTERVAL == 0
+            && let Some(packet) = self.stats.take_changed_packet()
+        {
+            self.connection.send_packet(packet);
+        }
+    }
+
+    /// Awards the distance stats for a movement of the player.
+    ///
+    /// Based on Java's `ServerPlayer.checkMovementStatistics`.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn check_movement_statistics(&self, dx: f64, dy: f64, dz: f64) {
+        // TODO: Underwater walking and climbing stats once eye and climbable checks exist
+        let horizontal = ((dx * dx + dz * dz).sqrt() * 100.0).round() as i32;
+        if self.is_swimming() {
+            let distance = ((dx * dx + dy * dy + dz * dz).sqrt() * 100.0).round() as i32;
+            if distance > 0 {
+                self.award_stat(StatKey::custom(CustomStat::SwimOneCm), distance);
+                self.cause_food_exhaustion(EXHAUSTION_SWIM * distance as f32 * 0.01);
+            }
+        } else if self.is_in_water() {
+            if horizontal > 0 {
+                self.award_stat(StatKey::custom(CustomStat::WalkOnWaterOneCm), horizontal);
+                self.cause_food_exhaustion(EXHAUSTION_SWIM * horizontal as f32 * 0.01);
+            }
+        } else if self.on_ground.load(Ordering::Relaxed) {
+            if horizontal > 0 {
+                let stat = if self.sprinting.load(Ordering::Relaxed) {
+                    self.cause_food_exhaustion(EXHAUSTION_SPRINT * horizontal as f32 * 0.01);
+                    CustomStat::SprintOneCm
+                } else if self.shift_key_down.load(Ordering::Relaxed) {
+                    CustomStat::CrouchOneCm
+                } else {
+                    CustomStat::WalkOneCm
+                };
+                self.award_stat(StatKey::custom(stat), horizontal);
+                self.check_step_vibration(dx.hypot(dz));
+            }
+        } else if self.fall_flying.load(Ordering::Relaxed) {
+            let distance = ((dx * dx + dy * dy + dz * dz).sqrt() * 100.0).round() as i32;
+            self.award_stat(StatKey::custom(CustomStat::AviateOneCm), distance);
+        } else if horizontal > 25 {
+            self.award_stat(StatKey::custom(CustomStat::FlyOneCm), horizontal);
+        }
+    }
+
+    /// Makes a step vibration every block the player walks. Sneaking players
+    /// step too quietly for sculk sensors to notice.
+    ///
+    /// Vanilla: `Entity.move`, emitting `GameEvent.STEP`
+    fn check_step_vibration(&self, distance: f64) {
+        if self.shift_key_down.load(Ordering::Relaxed) {
+            return;
+        }
+        let walked = self.step_distance.load() + distance;
+        if walked < 1.0 {
+            self.step_distance.store(walked);
+            return;
+        }
+        self.step_distance.store(0.0);
+        let position = *self.position.lock();
+        self.world().post_vibration(VibrationEvent::Step, position);
+    }
+
+    /// Sends every statistic to the client, in response to it opening the statistics screen.
+    pub fn send_all_stats(&self) {
+        self.connection.send_packet(self.stats.full_packet());
+    }
+
+    /// Saves the player's statistics to disk.
+    pub fn save_stats(&self) {
+        if let Err(e) = self.stats.save() {
+            log::error!("Failed to save stats of {}: {e}", self.gameprofile.name);
+        }
+    }
+
     /// Cleans up player resources.
-    pub fn cleanup(&self) {}
+    pub fn cleanup(&self) {
+        self.award_stat(StatKey::custom(CustomStat::LeaveGame), 1);
+        self.save_advancements();
+        self.save_stats();
+    }
 }
 
 impl Entity for Player {
@@ -2187,12 +3461,11 @@ impl LivingEntity for Player {
     }
 
     fn is_sprinting(&self) -> bool {
-        self.sprinting.load(Ordering::Relaxed)
+        Player::is_sprinting(self)
     }
 
     fn set_sprinting(&mut self, sprinting: bool) {
-        self.sprinting.store(sprinting, Ordering::Relaxed);
-        // TODO: Apply speed modifiers when attribute system is implemented
+        Player::set_sprinting(self, sprinting);
     }
 
     fn get_speed(&self) -> f32 {
@@ -2223,6 +3496,20 @@ fn strip_formatting_codes(text: &str) -> String {
     result
 }
 
+/// Writes out a number of seconds for chat, in whole minutes when it is one.
+fn duration_text(seconds: u64) -> String {
+    let (amount, unit) = if seconds >= 60 && seconds.is_multiple_of(60) {
+        (seconds / 60, "minute")
+    } else {
+        (seconds, "second")
+    };
+    if amount == 1 {
+        format!("1 {unit}")
+    } else {
+        format!("{amount} {unit}s")
+    }
+}
+
 impl TextResolutor for Player {
     fn resolve_content(&self, _resolvable: &Resolvable) -> TextComponent {
         TextComponent::new()
@@ -2236,3 +3523,17 @@ impl TextResolutor for Player {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_durations_read_naturally() {
+        assert_eq!(duration_text(30), "30 seconds");
+        assert_eq!(duration_text(1), "1 second");
+        assert_eq!(duration_text(60), "1 minute");
+        assert_eq!(duration_text(600), "10 minutes");
+        assert_eq!(duration_text(90), "90 seconds");
+    }
+}
@@ -3,10 +3,14 @@
 //! This module handles server-side movement simulation and anti-cheat checks.
 //! It implements collision detection and physics similar to vanilla Minecraft.
 
+use std::ptr;
+
 use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
 use steel_registry::blocks::shapes::AABBd;
-use steel_registry::vanilla_entities;
-use steel_utils::{BlockPos, math::Vector3};
+use steel_registry::entity_types::EntityDimensions;
+use steel_registry::{vanilla_blocks, vanilla_entities};
+use steel_utils::{BlockPos, BlockStateId, math::Vector3};
 
 use crate::physics::{
     CollisionWorld, EntityPhysicsState, MoverType, WorldCollisionProvider, join_is_not_empty,
@@ -18,6 +22,12 @@ use crate::world::World;
 pub const PLAYER_WIDTH: f64 = vanilla_entities::PLAYER.dimensions.width as f64;
 /// Player bounding box height (from entity type registry).
 pub const PLAYER_HEIGHT: f64 = vanilla_entities::PLAYER.dimensions.height as f64;
+/// Player dimensions while crouching.
+/// Matches vanilla `Player.CROUCHING_BB_HEIGHT` and its eye height.
+pub const PLAYER_CROUCHING_DIMENSIONS: EntityDimensions =
+    EntityDimensions::new(vanilla_entities::PLAYER.dimensions.width, 1.5, 1.27);
+/// Player bounding box height while crouching.
+pub const PLAYER_CROUCHING_HEIGHT: f64 = PLAYER_CROUCHING_DIMENSIONS.height as f64;
 
 /// Small epsilon for AABB deflation (matches vanilla 1.0E-5).
 pub const COLLISION_EPSILON: f64 = 1.0E-5;
@@ -29,6 +39,19 @@ pub const DEFAULT_GRAVITY: f64 = 0.08;
 pub const SPEED_THRESHOLD_NORMAL: f64 = 100.0;
 /// Maximum movement speed threshold for elytra flight (meters per tick squared).
 pub const SPEED_THRESHOLD_FLYING: f64 = 300.0;
+/// How much of its horizontal speed a player keeps each tick in water.
+/// Matches vanilla `LivingEntity.getWaterSlowDown`.
+pub const WATER_SPEED_MULTIPLIER: f64 = 0.8;
+/// Maximum movement speed threshold in water (meters per tick squared).
+/// The threshold is a squared distance, so the multiplier applies twice.
+pub const SPEED_THRESHOLD_WATER: f64 =
+    SPEED_THRESHOLD_NORMAL * WATER_SPEED_MULTIPLIER * WATER_SPEED_MULTIPLIER;
+/// How much of its walking speed a crouching player keeps.
+/// Matches the default of vanilla `Attributes.SNEAKING_SPEED`.
+pub const CROUCHING_SPEED_MULTIPLIER: f64 = 0.3;
+/// Maximum movement speed threshold while crouching on the ground (meters per tick squared).
+pub const SPEED_THRESHOLD_CROUCHING: f64 =
+    SPEED_THRESHOLD_NORMAL * CROUCHING_SPEED_MULTIPLIER * CROUCHING_SPEED_MULTIPLIER;
 
 /// Movement error threshold - if player ends up more than this far from target, reject.
 /// Matches vanilla's 0.0625 (1/16 of a block squared).
@@ -46,16 +69,32 @@ pub const Y_TOLERANCE: f64 = 0.5;
 /// Post-impulse grace period in ticks (vanilla uses ~10-20 ticks).
 pub const IMPULSE_GRACE_TICKS: i32 = 20;
 
+/// Returns the height of a player's bounding box.
+#[must_use]
+pub const fn player_height(is_crouching: bool) -> f64 {
+    if is_crouching {
+        PLAYER_CROUCHING_HEIGHT
+    } else {
+        PLAYER_HEIGHT
+    }
+}
+
 /// Creates a player bounding box at the given position.
 #[must_use]
 pub fn make_player_aabb(pos: Vector3<f64>) -> AABBd {
-    AABBd::entity_box(pos.x, pos.y, pos.z, PLAYER_WIDTH / 2.0, PLAYER_HEIGHT)
+    make_player_aabb_with_height(pos, PLAYER_HEIGHT)
+}
+
+/// Creates a player bounding box of the given height at the given position.
+#[must_use]
+pub fn make_player_aabb_with_height(pos: Vector3<f64>, height: f64) -> AABBd {
+    AABBd::entity_box(pos.x, pos.y, pos.z, PLAYER_WIDTH / 2.0, height)
 }
 
 /// Creates a player bounding box at the given position, deflated by the collision epsilon.
 #[must_use]
-pub fn make_player_aabb_deflated(pos: Vector3<f64>) -> AABBd {
-    make_player_aabb(pos).deflate(COLLISION_EPSILON)
+pub fn make_player_aabb_deflated(pos: Vector3<f64>, is_crouching: bool) -> AABBd {
+    make_player_aabb_with_height(pos, player_height(is_crouching)).deflate(COLLISION_EPSILON)
 }
 
 /// Clamps a horizontal coordinate to vanilla limits.
@@ -118,6 +157,9 @@ pub fn simulate_move(
 ) -> MoveResult {
     // Create physics state for the player
     let mut state = EntityPhysicsState::new(start_pos, vanilla_entities::PLAYER);
+    if is_crouching {
+        state.set_dimensions(PLAYER_CROUCHING_DIMENSIONS);
+    }
     state.is_crouching = is_crouching;
     state.on_ground = on_ground;
 
@@ -142,8 +184,8 @@ pub fn simulate_move(
 ///
 /// Used to allow movement when already stuck in blocks.
 #[must_use]
-pub fn is_in_collision(world: &World, pos: Vector3<f64>) -> bool {
-    let aabb = make_player_aabb_deflated(pos);
+pub fn is_in_collision(world: &World, pos: Vector3<f64>, is_crouching: bool) -> bool {
+    let aabb = make_player_aabb_deflated(pos, is_crouching);
 
     let min_x = aabb.min_x.floor() as i32;
     let max_x = aabb.max_x.ceil() as i32;
@@ -186,9 +228,10 @@ pub fn is_colliding_with_new_blocks(
     world: &World,
     old_pos: Vector3<f64>,
     new_pos: Vector3<f64>,
+    is_crouching: bool,
 ) -> bool {
-    let old_aabb = make_player_aabb_deflated(old_pos);
-    let new_aabb = make_player_aabb_deflated(new_pos);
+    let old_aabb = make_player_aabb_deflated(old_pos, is_crouching);
+    let new_aabb = make_player_aabb_deflated(new_pos, is_crouching);
 
     // Use physics collision provider for consistency
     let collision_world = WorldCollisionProvider::new(world);
@@ -222,12 +265,15 @@ pub struct MovementInput {
     pub delta_packets: i32,
     /// Whether the player is using elytra.
     pub is_fall_flying: bool,
+    /// Whether the player is touching water.
+    pub is_in_water: bool,
     /// Whether to skip anti-cheat checks (spectator, creative, tick frozen, gamerules).
     /// When true, all validation checks are bypassed.
     pub skip_checks: bool,
     /// Whether the player is in post-impulse grace period.
     pub in_impulse_grace: bool,
-    /// Whether the player is crouching (for sneak-edge prevention).
+    /// Whether the player is crouching (for sneak-edge prevention, the
+    /// lower bounding box and the slower speed).
     pub is_crouching: bool,
     /// Whether the player was on ground before this movement (affects step-up).
     pub on_ground: bool,
@@ -277,6 +323,10 @@ pub fn validate_movement(world: &World, input: &MovementInput) -> MovementValida
     if !input.skip_checks {
         let threshold = if input.is_fall_flying {
             SPEED_THRESHOLD_FLYING
+        } else if input.is_in_water {
+            SPEED_THRESHOLD_WATER
+        } else if input.is_crouching && input.on_ground {
+            SPEED_THRESHOLD_CROUCHING
         } else {
             SPEED_THRESHOLD_NORMAL
         } * f64::from(input.delta_packets);
@@ -330,13 +380,13 @@ pub fn validate_movement(world: &World, input: &MovementInput) -> MovementValida
     let error_check_failed = !input.in_impulse_grace && error_dist_sq > MOVEMENT_ERROR_THRESHOLD;
 
     // Collision checks
-    let was_in_collision = is_in_collision(world, last_good);
+    let was_in_collision = is_in_collision(world, last_good, input.is_crouching);
     let collision_check_failed = error_check_failed
         && was_in_collision
-        && is_colliding_with_new_blocks(world, last_good, target_pos);
+        && is_colliding_with_new_blocks(world, last_good, target_pos, input.is_crouching);
 
-    let new_collision_without_error =
-        !error_check_failed && is_colliding_with_new_blocks(world, last_good, target_pos);
+    let new_collision_without_error = !error_check_failed
+        && is_colliding_with_new_blocks(world, last_good, target_pos, input.is_crouching);
 
     // Determine if movement failed
     let movement_failed = !input.skip_checks
@@ -367,6 +417,173 @@ pub fn validate_movement(world: &World, input: &MovementInput) -> MovementValida
     }
 }
 
+// ============================================================================
+// Water
+// ============================================================================
+
+/// Returns true if the block state is water or waterlogged.
+fn is_water(state: BlockStateId) -> bool {
+    ptr::eq(state.get_block(), vanilla_blocks::WATER)
+        || state
+            .try_get_value(&BlockStateProperties::WATERLOGGED)
+            .unwrap_or(false)
+}
+
+/// Returns how high the water in the block at `pos` reaches, from 0 to 1,
+/// or `None` if there is no water.
+///
+/// Vanilla: `FluidState.getHeight`
+#[must_use]
+pub fn water_height(world: &World, pos: BlockPos) -> Option<f64> {
+    let state = world.get_block_state(&pos);
+    if !is_water(state) {
+        return None;
+    }
+    // Water with more water above fills the whole block
+    if is_water(world.get_block_state(&pos.offset(0, 1, 0))) {
+        return Some(1.0);
+    }
+
+    // Sources and falling water have 8 levels, flowing water loses one per block
+    let level: u8 = state
+        .try_get_value(&BlockStateProperties::LEVEL)
+        .unwrap_or(0);
+    let amount = if level == 0 || level >= 8 {
+        8
+    } else {
+        8 - level
+    };
+    Some(f64::from(amount) / 9.0)
+}
+
+/// Returns true if the bounding box of a player at `pos` touches water.
+///
+/// Vanilla: `Entity.updateInWaterStateAndDoWaterCurrentPushing`
+#[allow(clippy::cast_possible_truncation)]
+#[must_use]
+pub fn is_in_water(world: &World, pos: Vector3<f64>) -> bool {
+    let aabb = make_player_aabb(pos).deflate(0.001);
+
+    for bx in aabb.min_x.floor() as i32..aabb.max_x.ceil() as i32 {
+        for by in aabb.min_y.floor() as i32..aabb.max_y.ceil() as i32 {
+            for bz in aabb.min_z.floor() as i32..aabb.max_z.ceil() as i32 {
+                if let Some(height) = water_height(world, BlockPos::new(bx, by, bz))
+                    && f64::from(by) + height >= aabb.min_y
+                {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Returns true if the eyes of a player at `pos` are under water.
+///
+/// Vanilla: `Entity.updateFluidOnEyes`
+#[allow(clippy::cast_possible_truncation)]
+#[must_use]
+pub fn is_eye_in_water(world: &World, pos: Vector3<f64>, eye_height: f64) -> bool {
+    let eye_y = pos.y + eye_height - 0.111_111_11;
+    let eye = BlockPos::new(
+        pos.x.floor() as i32,
+        eye_y.floor() as i32,
+        pos.z.floor() as i32,
+    );
+    water_height(world, eye).is_some_and(|height| f64::from(eye.y()) + height > eye_y)
+}
+
+/// Applies a tick of water physics to a player's velocity.
+///
+/// Water slows the player down, sprinting less so, and lets them sink
+/// slowly. Holding jump swims upwards, and swimming players drift towards
+/// the direction they look in.
+///
+/// Vanilla: `LivingEntity.travelInWater` and `Player.travel`
+#[must_use]
+pub fn water_movement(
+    delta: Vector3<f64>,
+    look_y: f64,
+    swimming: bool,
+    sprinting: bool,
+    jumping: bool,
+    gravity: f64,
+) -> Vector3<f64> {
+    let mut movement = delta;
+    if swimming {
+        let pull = if look_y < -0.2 { 0.085 } else { 0.06 };
+        if look_y <= 0.0 || jumping {
+            movement.y += (look_y - movement.y) * pull;
+        }
+    }
+    // Vanilla: `LivingEntity.jumpInLiquid`
+    if jumping {
+        movement.y += 0.04;
+    }
+
+    let slow_down = if sprinting {
+        0.9
+    } else {
+        WATER_SPEED_MULTIPLIER
+    };
+    movement = movement.multiply(slow_down, 0.8, slow_down);
+
+    // TODO: Slow falling and dolphin's grace once mob effects exist
+    if !sprinting {
+        movement.y -= gravity / 16.0;
+    }
+    movement
+}
+
+/// Applies a tick of elytra physics to a gliding player's velocity.
+///
+/// Looking down trades height for speed and looking up trades speed for
+/// height, while lift, which is strongest when looking straight ahead,
+/// counters gravity. The velocity slowly turns to where the player looks.
+///
+/// Vanilla: `LivingEntity.updateFallFlyingMovement`
+#[must_use]
+pub fn fall_flying_movement(
+    delta: Vector3<f64>,
+    yaw: f32,
+    pitch: f32,
+    gravity: f64,
+) -> Vector3<f64> {
+    let look = Vector3::rotation_vector(f64::from(pitch), f64::from(yaw));
+    let pitch = f64::from(pitch).to_radians();
+    let horizontal_look = look.horizontal_length();
+    let horizontal_speed = delta.horizontal_length();
+    let lift = pitch.cos().powi(2);
+
+    let mut movement = delta.add_raw(0.0, gravity * (-1.0 + lift * 0.75), 0.0);
+    if horizontal_look > 0.0 {
+        // Falling speeds the player up in the direction they look
+        if movement.y < 0.0 {
+            let convert = movement.y * -0.1 * lift;
+            movement = movement.add_raw(
+                look.x * convert / horizontal_look,
+                convert,
+                look.z * convert / horizontal_look,
+            );
+        }
+        // Looking up climbs at the cost of speed
+        if pitch < 0.0 {
+            let convert = horizontal_speed * -pitch.sin() * 0.04;
+            movement = movement.add_raw(
+                -look.x * convert / horizontal_look,
+                convert * 3.2,
+                -look.z * convert / horizontal_look,
+            );
+        }
+        movement = movement.add_raw(
+            (look.x / horizontal_look * horizontal_speed - movement.x) * 0.1,
+            0.0,
+            (look.z / horizontal_look * horizontal_speed - movement.z) * 0.1,
+        );
+    }
+    movement.multiply(0.99, 0.98, 0.99)
+}
+
 #[cfg(test)]
 #[allow(clippy::float_cmp)]
 mod tests {
@@ -398,4 +615,58 @@ mod tests {
         assert!((aabb.min_z - (-0.3)).abs() < 0.001);
         assert!((aabb.max_z - 0.3).abs() < 0.001);
     }
+
+    #[test]
+    fn test_fall_flying_keeps_speed_level() {
+        // Gliding straight ahead barely loses height or speed
+        let mut delta = Vector3::new(0.0, 0.0, 1.0);
+        for _ in 0..20 {
+            delta = fall_flying_movement(delta, 0.0, 0.0, DEFAULT_GRAVITY);
+        }
+        assert!(delta.z > 0.7);
+        assert!(delta.y > -0.5 && delta.y < 0.0);
+        assert!(delta.x.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fall_flying_dive_and_climb() {
+        let start = Vector3::new(0.0, 0.0, 1.0);
+        let dive = fall_flying_movement(start, 0.0, 60.0, DEFAULT_GRAVITY);
+        let climb = fall_flying_movement(start, 0.0, -60.0, DEFAULT_GRAVITY);
+        assert!(dive.y < climb.y);
+        assert!(climb.y > 0.0);
+        assert!(climb.z < dive.z);
+    }
+
+    #[test]
+    fn test_water_movement_slows_down() {
+        let delta = Vector3::new(1.0, 0.0, 0.0);
+        let walking = water_movement(delta, 0.0, false, false, false, DEFAULT_GRAVITY);
+        let sprinting = water_movement(delta, 0.0, true, true, false, DEFAULT_GRAVITY);
+        assert!((walking.x - WATER_SPEED_MULTIPLIER).abs() < 1e-9);
+        assert!(walking.y < 0.0);
+        assert!(sprinting.x > walking.x);
+        assert!(sprinting.y.abs() < 1e-9);
+
+        let jumping = water_movement(Vector3::default(), 0.0, false, false, true, DEFAULT_GRAVITY);
+        assert!(jumping.y > 0.0);
+    }
+
+    #[test]
+    fn test_swimming_follows_look() {
+        let down = water_movement(Vector3::default(), -1.0, true, true, false, DEFAULT_GRAVITY);
+        let up = water_movement(Vector3::default(), 1.0, true, true, false, DEFAULT_GRAVITY);
+        assert!(down.y < 0.0);
+        // Looking up only rises while holding jump
+        assert!(up.y.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_crouching_aabb_is_lower() {
+        let pos = Vector3::new(0.0, 64.0, 0.0);
+        let standing = make_player_aabb_deflated(pos, false);
+        let crouching = make_player_aabb_deflated(pos, true);
+        assert!((standing.max_y - crouching.max_y - 0.3).abs() < 1e-6);
+        assert!((standing.max_x - crouching.max_x).abs() < 1e-9);
+    }
 }
@@ -1,8 +1,8 @@
 //! This module contains the `JavaConnection` struct, which is used to represent a connection to a Java client.
 use std::io::Cursor;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicI32, Ordering};
 use std::sync::{Arc, Weak};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant};
 
 use steel_protocol::packet_reader::TCPNetworkDecoder;
 use steel_protocol::packet_traits::{ClientPacket, CompressionInfo, EncodedPacket, ServerPacket};
@@ -12,11 +12,12 @@ use steel_protocol::packets::common::{
     SPingRequest,
 };
 use steel_protocol::packets::game::{
-    SAcceptTeleportation, SChat, SChatAck, SChatCommand, SChatSessionUpdate, SChunkBatchReceived,
-    SClientTickEnd, SCommandSuggestion, SContainerButtonClick, SContainerClick, SContainerClose,
-    SContainerSlotStateChanged, SMovePlayerPos, SMovePlayerPosRot, SMovePlayerRot,
-    SMovePlayerStatusOnly, SPickItemFromBlock, SPlayerAbilities, SPlayerAction, SPlayerInput,
-    SPlayerLoad, SSetCarriedItem, SSetCreativeModeSlot, SSignUpdate, SSwing, SUseItem, SUseItemOn,
+    ClientCommandAction, SAcceptTeleportation, SChat, SChatAck, SChatCommand, SChatSessionUpdate,
+    SChunkBatchReceived, SClientCommand, SClientTickEnd, SCommandSuggestion, SContainerButtonClick,
+    SContainerClick, SContainerClose, SContainerSlotStateChanged, SInteract, SMovePlayerPos,
+    SMovePlayerPosRot, SMovePlayerRot, SMovePlayerStatusOnly, SPickItemFromBlock, SPlayerAbilities,
+    SPlayerAction, SPlayerCommand, SPlayerInput, SPlayerLoad, SRenameItem, SSelectTrade,
+    SSetCarriedItem, SSetCreativeModeSlot, SSignUpdate, SSwing, SUseItem, SUseItemOn,
 };
 use steel_protocol::utils::{ConnectionProtocol, PacketError, RawPacket};
 use steel_registry::packets::play;
@@ -35,12 +36,18 @@ use tokio_util::sync::CancellationToken;
 use crate::command::sender::CommandSender;
 use crate::player::Player;
 use crate::server::Server;
+use crate::server::metrics::METRICS;
+
+/// How often a keep-alive is sent to the client.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+/// How long the client has to answer a keep-alive before being kicked.
+const KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(30);
 
-#[allow(clippy::struct_field_names)]
 struct KeepAliveTracker {
-    alive_time: u64,
-    alive_pending: bool,
-    alive_id: u64,
+    /// When the last keep-alive was sent.
+    last_sent: Instant,
+    /// The id and send time of the keep-alive we're waiting on, if any.
+    pending: Option<(i64, Instant)>,
 }
 
 /// A connection to a Java client.
@@ -53,7 +60,7 @@ pub struct JavaConnection {
 
     player: Weak<Player>,
     keep_alive_tracker: SyncMutex<KeepAliveTracker>,
-    latency: SyncMutex<u32>,
+    latency: AtomicI32,
 }
 
 impl JavaConnection {
@@ -74,11 +81,10 @@ impl JavaConnection {
             id,
             player,
             keep_alive_tracker: SyncMutex::new(KeepAliveTracker {
-                alive_time: 0,
-                alive_pending: false,
-                alive_id: 0,
+                last_sent: Instant::now(),
+                pending: None,
             }),
-            latency: SyncMutex::new(0),
+            latency: AtomicI32::new(0),
         }
     }
 
@@ -87,43 +93,49 @@ impl JavaConnection {
         self.keep_connection_alive();
     }
 
-    #[allow(clippy::unwrap_used)]
     fn keep_connection_alive(&self) {
         let mut tracker = self.keep_alive_tracker.lock();
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("System time before UNIX EPOCH")
-            .as_millis() as u64;
+        let now = Instant::now();
 
-        if now - tracker.alive_time >= 15000 {
-            if tracker.alive_pending {
+        if let Some((_, sent_at)) = tracker.pending {
+            if now.duration_since(sent_at) >= KEEP_ALIVE_TIMEOUT {
+                drop(tracker);
                 self.disconnect(translations::DISCONNECT_TIMEOUT.msg());
-            } else {
-                tracker.alive_pending = true;
-                tracker.alive_id = now;
-                tracker.alive_time = now;
-                self.send_packet(CKeepAlive::new(tracker.alive_id as i64));
             }
+            return;
+        }
+
+        if now.duration_since(tracker.last_sent) >= KEEP_ALIVE_INTERVAL {
+            let id = rand::random::<i64>();
+            tracker.pending = Some((id, now));
+            tracker.last_sent = now;
+            drop(tracker);
+            self.send_packet(CKeepAlive::new(id));
         }
     }
 
     /// Handles a keep alive packet.
-    #[allow(clippy::cast_possible_truncation)]
+    ///
+    /// Disconnects the client if the id doesn't match the keep-alive we're waiting on.
     fn handle_keep_alive(&self, packet: SKeepAlive) {
         let mut tracker = self.keep_alive_tracker.lock();
-        if tracker.alive_pending && packet.id as u64 == tracker.alive_id {
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("System time before UNIX EPOCH")
-                .as_millis() as u64;
+        match tracker.pending {
+            Some((id, sent_at)) if id == packet.id => {
+                tracker.pending = None;
+                drop(tracker);
 
-            let time = now.saturating_sub(tracker.alive_time) as u32;
-            tracker.alive_pending = false;
-            drop(tracker);
-            let mut latency = self.latency.lock();
-            *latency = (*latency * 3 + time) / 4;
-        } else {
-            self.disconnect(translations::DISCONNECT_TIMEOUT.msg());
+                let round_trip = i32::try_from(sent_at.elapsed().as_millis()).unwrap_or(i32::MAX);
+                // Smooth the same way vanilla does so a single spike doesn't dominate
+                let _ =
+                    self.latency
+                        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |latency| {
+                            Some((latency * 3 + round_trip) / 4)
+                        });
+            }
+            _ => {
+                drop(tracker);
+                self.disconnect(translations::DISCONNECT_TIMEOUT.msg());
+            }
         }
     }
 
@@ -131,7 +143,7 @@ impl JavaConnection {
     /// This is a smoothed average calculated from keep-alive round-trip times.
     #[must_use]
     pub fn latency(&self) -> i32 {
-        *self.latency.lock() as i32
+        self.latency.load(Ordering::Relaxed)
     }
 
     /// Disconnects the client.
@@ -188,6 +200,7 @@ impl JavaConnection {
         server: Arc<Server>,
     ) -> Result<(), PacketError> {
         let data = &mut Cursor::new(packet.payload.as_slice());
+        let position_before = (*player.position.lock(), player.rotation.load());
 
         match packet.id {
             play::S_ACCEPT_TELEPORTATION => {
@@ -197,7 +210,7 @@ impl JavaConnection {
                 player.handle_custom_payload(SCustomPayload::read_packet(data)?);
             }
             play::S_CHAT => {
-                player.handle_chat(SChat::read_packet(data)?, Arc::clone(&player));
+                player.handle_chat(SChat::read_packet(data)?, Arc::clone(&player), &server);
             }
             play::S_CHAT_SESSION_UPDATE => {
                 player.handle_chat_session_update(SChatSessionUpdate::read_packet(data)?);
@@ -208,6 +221,11 @@ impl JavaConnection {
             play::S_CLIENT_INFORMATION => {
                 player.handle_client_information(SClientInformation::read_packet(data)?);
             }
+            play::S_CLIENT_COMMAND => match SClientCommand::read_packet(data)?.action {
+                ClientCommandAction::RequestStats => player.send_all_stats(),
+                // TODO: Respawn once players can die
+                ClientCommandAction::PerformRespawn => {}
+            },
             play::S_CLIENT_TICK_END => {
                 let _ = SClientTickEnd::read_packet(data)?;
                 player.handle_client_tick_end();
@@ -276,6 +294,9 @@ impl JavaConnection {
             play::S_PLAYER_INPUT => {
                 player.handle_player_input(SPlayerInput::read_packet(data)?);
             }
+            play::S_PLAYER_COMMAND => {
+                player.handle_player_command(SPlayerCommand::read_packet(data)?);
+            }
             play::S_PLAYER_ABILITIES => {
                 player.handle_player_abilities(SPlayerAbilities::read_packet(data)?);
             }
@@ -288,6 +309,9 @@ impl JavaConnection {
             play::S_SET_CARRIED_ITEM => {
                 player.handle_set_carried_item(SSetCarriedItem::read_packet(data)?);
             }
+            play::S_INTERACT => {
+                player.handle_interact(SInteract::read_packet(data)?);
+            }
             play::S_SWING => {
                 let packet = SSwing::read_packet(data)?;
                 player.swing(packet.hand, false);
@@ -304,6 +328,12 @@ impl JavaConnection {
                 let packet = SSignUpdate::read_packet(data)?;
                 player.handle_sign_update(packet);
             }
+            play::S_RENAME_ITEM => {
+                player.handle_rename_item(SRenameItem::read_packet(data)?);
+            }
+            play::S_SELECT_TRADE => {
+                player.handle_select_trade(SSelectTrade::read_packet(data)?);
+            }
             play::S_PING_REQUEST => {
                 let packet = SPingRequest::read_packet(data)?;
                 player
@@ -312,6 +342,24 @@ impl JavaConnection {
             }
             id => log::info!("play packet id {id} is not known"),
         }
+
+        // The client sends these on its own, so they don't mean the player is active
+        match packet.id {
+            play::S_KEEP_ALIVE
+            | play::S_CLIENT_TICK_END
+            | play::S_CHUNK_BATCH_RECEIVED
+            | play::S_CHAT_ACK
+            | play::S_PING_REQUEST => {}
+            play::S_MOVE_PLAYER_POS
+            | play::S_MOVE_PLAYER_POS_ROT
+            | play::S_MOVE_PLAYER_ROT
+            | play::S_MOVE_PLAYER_STATUS_ONLY => {
+                if (*player.position.lock(), player.rotation.load()) != position_before {
+                    player.reset_last_action_time();
+                }
+            }
+            _ => player.reset_last_action_time(),
+        }
         Ok(())
     }
 
@@ -329,6 +377,7 @@ impl JavaConnection {
                 packet = reader.get_raw_packet() => {
                     match packet {
                         Ok(packet) => {
+                            METRICS.packet_received();
                             if let Some(player) = self.player.upgrade()
                                 && let Err(err) = self.process_packet(packet, player, server.clone()) {
                                 log::warn!(
@@ -363,6 +412,8 @@ impl JavaConnection {
                         {
                             log::warn!("Failed to send packet to client {}: {err}", self.id);
                             self.close();
+                        } else {
+                            METRICS.packet_sent();
                         }
                     } else {
                         //log::warn!(
@@ -376,7 +427,7 @@ impl JavaConnection {
         }
 
         let player = self.player.upgrade().expect("Player is not available");
-        let world = player.world.clone();
+        let world = player.world();
         world.remove_player(player).await;
     }
 }
@@ -30,6 +30,10 @@ pub enum ValidationError {
     #[error("Key has expired")]
     KeyExpired,
 
+    /// Mojang's public keys have not been fetched yet
+    #[error("Mojang public keys are unavailable")]
+    MissingMojangKeys,
+
     /// Cryptographic error
     #[error("Cryptographic error: {0}")]
     CryptoError(#[from] CryptError),
@@ -326,6 +330,17 @@ impl RemoteChatSessionData {
         Ok(RemoteChatSession::new(self.session_id, public_key))
     }
 
+    /// Creates a `RemoteChatSession` without checking the key signature.
+    ///
+    /// Only used in offline mode, where there is no Mojang signature to check.
+    #[must_use]
+    pub fn into_unvalidated(self) -> RemoteChatSession {
+        RemoteChatSession::new(
+            self.session_id,
+            ProfilePublicKey::new(self.profile_public_key),
+        )
+    }
+
     /// Converts to network-serializable format for transmission
     ///
     /// # Errors
@@ -0,0 +1,352 @@
+//! Caches the skins of online-mode players.
+//!
+//! The session server signs a `textures` property holding a player's skin and
+//! cape. It is kept per player so a profile missing it can still be given the
+//! last known skin, and refreshed in the background once it is older than
+//! `skin_cache_ttl` so logins never wait on an outdated entry.
+
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use reqwest::Client;
+use serde::Deserialize;
+use steel_protocol::packets::login::GameProfileProperty;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::config::STEEL_CONFIG;
+use crate::player::GameProfile;
+
+const SESSION_PROFILE_URL: &str = "https://sessionserver.mojang.com/session/minecraft/profile/";
+
+/// How long a single request to the session server may take.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The name of the profile property holding the skin and cape.
+pub const TEXTURES_PROPERTY: &str = "textures";
+
+/// Domains textures may be loaded from (Vanilla: `ALLOWED_DOMAINS`).
+const ALLOWED_DOMAINS: [&str; 2] = [".minecraft.net", ".mojang.com"];
+
+/// Hosts under the allowed domains that don't serve textures (Vanilla: `BLOCKED_DOMAINS`).
+const BLOCKED_DOMAINS: [&str; 3] = [
+    "bugs.mojang.com",
+    "education.minecraft.net",
+    "feedback.minecraft.net",
+];
+
+static HTTP_CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .expect("Failed to build HTTP client")
+});
+
+/// The skins of all players that joined since the server started.
+pub static SKIN_CACHE: LazyLock<SkinCache> = LazyLock::new(SkinCache::new);
+
+/// An error that can occur with textures.
+#[derive(Error, Debug)]
+pub enum TextureError {
+    /// Invalid URL.
+    #[error("Invalid URL")]
+    InvalidURL,
+    /// Invalid URL scheme for player texture.
+    #[error("Invalid URL scheme for player texture: {0}")]
+    DisallowedUrlScheme(String),
+    /// Invalid URL domain for player texture.
+    #[error("Invalid URL domain for player texture: {0}")]
+    DisallowedUrlDomain(String),
+    /// Failed to decode base64 player texture.
+    #[error("Failed to decode base64 player texture: {0}")]
+    DecodeError(String),
+    /// Failed to parse JSON from player texture.
+    #[error("Failed to parse JSON from player texture: {0}")]
+    JSONError(String),
+}
+
+/// An error that can occur while fetching a skin from the session server.
+#[derive(Error, Debug)]
+pub enum SkinFetchError {
+    /// The request failed or the response wasn't a profile.
+    #[error("Request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    /// The profile has no `textures` property.
+    #[error("Profile has no textures")]
+    MissingTextures,
+    /// The `textures` property is invalid.
+    #[error(transparent)]
+    Texture(#[from] TextureError),
+}
+
+/// The decoded value of a `textures` property.
+#[derive(Deserialize)]
+struct TexturesPayload {
+    textures: Textures,
+}
+
+#[derive(Deserialize)]
+struct Textures {
+    #[serde(rename = "SKIN")]
+    skin: Option<Texture>,
+    #[serde(rename = "CAPE")]
+    cape: Option<Texture>,
+}
+
+#[derive(Deserialize)]
+struct Texture {
+    url: String,
+    metadata: Option<TextureMetadata>,
+}
+
+#[derive(Deserialize)]
+struct TextureMetadata {
+    model: Option<String>,
+}
+
+/// A player's skin and cape, as signed by the session server.
+#[derive(Debug, Clone)]
+pub struct SkinData {
+    /// The signed `textures` property, forwarded to clients as is.
+    pub property: GameProfileProperty,
+    /// Where the skin is loaded from, if the player has one.
+    pub skin_url: Option<String>,
+    /// Where the cape is loaded from, if the player has one.
+    pub cape_url: Option<String>,
+    /// Whether the skin uses the slim arm model.
+    pub slim: bool,
+    fetched_at: Instant,
+}
+
+impl SkinData {
+    /// Decodes a `textures` property, checking that its URLs point to Mojang.
+    ///
+    /// # Errors
+    /// Returns an error if the value isn't base64 encoded texture JSON or a
+    /// URL is from a domain textures aren't allowed to be loaded from.
+    pub fn from_property(property: GameProfileProperty) -> Result<Self, TextureError> {
+        let json = BASE64_STANDARD
+            .decode(&property.value)
+            .map_err(|err| TextureError::DecodeError(err.to_string()))?;
+        let payload: TexturesPayload = serde_json::from_slice(&json)
+            .map_err(|err| TextureError::JSONError(err.to_string()))?;
+        let Textures { skin, cape } = payload.textures;
+
+        for texture in skin.iter().chain(&cape) {
+            validate_texture_url(&texture.url)?;
+        }
+
+        let slim = skin
+            .as_ref()
+            .and_then(|skin| skin.metadata.as_ref())
+            .and_then(|metadata| metadata.model.as_deref())
+            == Some("slim");
+
+        Ok(Self {
+            property,
+            skin_url: skin.map(|skin| skin.url),
+            cape_url: cape.map(|cape| cape.url),
+            slim,
+            fetched_at: Instant::now(),
+        })
+    }
+
+    /// Returns whether the skin was fetched longer than `skin_cache_ttl` ago.
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.fetched_at.elapsed() >= Duration::from_secs(STEEL_CONFIG.load().skin_cache_ttl)
+    }
+}
+
+/// Checks that a texture URL is http(s) and on one of Mojang's texture domains.
+///
+/// Vanilla: `YggdrasilMinecraftSessionService.isAllowedTextureDomain`
+fn validate_texture_url(url: &str) -> Result<(), TextureError> {
+    let (scheme, rest) = url.split_once("://").ok_or(TextureError::InvalidURL)?;
+    if scheme != "http" && scheme != "https" {
+        return Err(TextureError::DisallowedUrlScheme(scheme.to_owned()));
+    }
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    let host = authority
+        .rsplit_once(':')
+        .map_or(authority, |(host, _port)| host);
+    if host.is_empty() {
+        return Err(TextureError::InvalidURL);
+    }
+
+    if !ALLOWED_DOMAINS.iter().any(|domain| host.ends_with(domain))
+        || BLOCKED_DOMAINS.contains(&host)
+    {
+        return Err(TextureError::DisallowedUrlDomain(host.to_owned()));
+    }
+    Ok(())
+}
+
+/// Player skins by profile id.
+pub struct SkinCache {
+    skins: scc::HashMap<Uuid, SkinData>,
+    /// Players whose skin is being refreshed, so each is only fetched once at a time.
+    refreshing: scc::HashSet<Uuid>,
+}
+
+impl SkinCache {
+    fn new() -> Self {
+        Self {
+            skins: scc::HashMap::new(),
+            refreshing: scc::HashSet::new(),
+        }
+    }
+
+    /// Decodes and caches a player's `textures` property, replacing any older skin.
+    ///
+    /// # Errors
+    /// Returns an error if the property is invalid, see [`SkinData::from_property`].
+    pub fn store(&self, id: Uuid, property: GameProfileProperty) -> Result<SkinData, TextureError> {
+        let skin = SkinData::from_property(property)?;
+        self.skins.upsert_sync(id, skin.clone());
+        Ok(skin)
+    }
+
+    /// Gets a player's skin.
+    ///
+    /// A cached skin is returned right away, starting a refresh in the
+    /// background if it is stale. Otherwise the skin is fetched from the
+    /// session server first, returning `None` if that fails.
+    pub async fn get(&'static self, id: Uuid) -> Option<SkinData> {
+        if let Some(skin) = self.skins.read_sync(&id, |_, skin| skin.clone()) {
+            if skin.is_stale() {
+                self.refresh_in_background(id);
+            }
+            return Some(skin);
+        }
+
+        match self.fetch(id).await {
+            Ok(skin) => Some(skin),
+            Err(err) => {
+                log::warn!("Failed to fetch the skin of {id}: {err}");
+                None
+            }
+        }
+    }
+
+    /// Caches the skin a profile came with, or adds the cached skin to a
+    /// profile that has none.
+    ///
+    /// A `textures` property that fails to decode is removed from the profile
+    /// so it isn't forwarded to other players.
+    pub async fn apply_to(&'static self, profile: &mut GameProfile) {
+        let textures = profile
+            .properties
+            .iter()
+            .position(|property| property.name == TEXTURES_PROPERTY);
+
+        if let Some(index) = textures {
+            let property = profile.properties[index].clone();
+            if let Err(err) = self.store(profile.id, property) {
+                log::warn!("Dropping the invalid skin of {}: {err}", profile.name);
+                profile.properties.remove(index);
+            }
+        } else if let Some(skin) = self.get(profile.id).await {
+            profile.properties.push(skin.property);
+        }
+    }
+
+    fn refresh_in_background(&'static self, id: Uuid) {
+        if self.refreshing.insert_sync(id).is_err() {
+            return;
+        }
+        tokio::spawn(async move {
+            if let Err(err) = self.fetch(id).await {
+                // The stale skin is kept until a refresh succeeds
+                log::warn!("Failed to refresh the skin of {id}: {err}");
+            }
+            self.refreshing.remove_sync(&id);
+        });
+    }
+
+    /// Fetches a player's signed profile from the session server and caches its skin.
+    async fn fetch(&self, id: Uuid) -> Result<SkinData, SkinFetchError> {
+        let url = format!("{SESSION_PROFILE_URL}{}?unsigned=false", id.simple());
+        let profile: GameProfile = HTTP_CLIENT
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let property = profile
+            .properties
+            .into_iter()
+            .find(|property| property.name == TEXTURES_PROPERTY)
+            .ok_or(SkinFetchError::MissingTextures)?;
+        Ok(self.store(id, property)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn property(json: &str) -> GameProfileProperty {
+        GameProfileProperty {
+            name: TEXTURES_PROPERTY.to_owned(),
+            value: BASE64_STANDARD.encode(json),
+            signature: Some("signature".to_owned()),
+        }
+    }
+
+    #[test]
+    fn decodes_skin_and_cape() {
+        let skin = SkinData::from_property(property(
+            r#"{
+                "timestamp": 1700000000000,
+                "profileId": "069a79f444e94726a5befca90e38aaf5",
+                "profileName": "Notch",
+                "textures": {
+                    "SKIN": {
+                        "url": "http://textures.minecraft.net/texture/abc",
+                        "metadata": {"model": "slim"}
+                    },
+                    "CAPE": {"url": "https://textures.minecraft.net/texture/def"}
+                }
+            }"#,
+        ))
+        .expect("valid textures");
+
+        assert_eq!(
+            skin.skin_url.as_deref(),
+            Some("http://textures.minecraft.net/texture/abc")
+        );
+        assert_eq!(
+            skin.cape_url.as_deref(),
+            Some("https://textures.minecraft.net/texture/def")
+        );
+        assert!(skin.slim);
+        assert_eq!(skin.property.signature.as_deref(), Some("signature"));
+    }
+
+    #[test]
+    fn rejects_foreign_texture_urls() {
+        assert!(validate_texture_url("https://textures.minecraft.net/texture/abc").is_ok());
+        assert!(validate_texture_url("http://textures.minecraft.net:80/texture/abc").is_ok());
+        assert!(matches!(
+            validate_texture_url("ftp://textures.minecraft.net/abc"),
+            Err(TextureError::DisallowedUrlScheme(_))
+        ));
+        assert!(matches!(
+            validate_texture_url("https://evil.example/minecraft.net"),
+            Err(TextureError::DisallowedUrlDomain(_))
+        ));
+        assert!(matches!(
+            validate_texture_url("https://bugs.mojang.com/abc"),
+            Err(TextureError::DisallowedUrlDomain(_))
+        ));
+        assert!(matches!(
+            validate_texture_url("not a url"),
+            Err(TextureError::InvalidURL)
+        ));
+    }
+}
@@ -0,0 +1,423 @@
+//! Player statistics shown in the statistics screen.
+//!
+//! Stats are stored in `world/stats/<uuid>.json` grouped by stat type, like
+//! vanilla's stats files.
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use scc::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use steel_protocol::packets::game::{AwardedStat, CAwardStats};
+use steel_registry::{REGISTRY, blocks::BlockRef, entity_types::EntityTypeRef, items::ItemRef};
+use steel_utils::Identifier;
+use uuid::Uuid;
+
+/// Directory the stats files of all players are stored in.
+const STATS_DIR: &str = "world/stats";
+
+/// The kind of a statistic. The discriminant is the stat type's registry id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatType {
+    /// Blocks mined, keyed by block.
+    Mined = 0,
+    /// Items crafted, keyed by item.
+    Crafted = 1,
+    /// Items used, keyed by item.
+    Used = 2,
+    /// Tools broken, keyed by item.
+    Broken = 3,
+    /// Items picked up, keyed by item.
+    PickedUp = 4,
+    /// Items dropped, keyed by item.
+    Dropped = 5,
+    /// Entities killed, keyed by entity type.
+    Killed = 6,
+    /// Deaths caused by an entity, keyed by entity type.
+    KilledBy = 7,
+    /// Everything else, keyed by [`CustomStat`].
+    Custom = 8,
+}
+
+impl StatType {
+    const ALL: [StatType; 9] = [
+        StatType::Mined,
+        StatType::Crafted,
+        StatType::Used,
+        StatType::Broken,
+        StatType::PickedUp,
+        StatType::Dropped,
+        StatType::Killed,
+        StatType::KilledBy,
+        StatType::Custom,
+    ];
+
+    /// Returns the path of the stat type's identifier.
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            StatType::Mined => "mined",
+            StatType::Crafted => "crafted",
+            StatType::Used => "used",
+            StatType::Broken => "broken",
+            StatType::PickedUp => "picked_up",
+            StatType::Dropped => "dropped",
+            StatType::Killed => "killed",
+            StatType::KilledBy => "killed_by",
+            StatType::Custom => "custom",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|stat_type| stat_type.key() == key)
+    }
+}
+
+macro_rules! custom_stats {
+    ($($variant:ident => $key:literal,)*) => {
+        /// Statistics that aren't tied to a block, item or entity type.
+        ///
+        /// Variants are in vanilla's registration order, so the discriminant is
+        /// the registry id.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[allow(missing_docs)]
+        pub enum CustomStat {
+            $($variant,)*
+        }
+
+        impl CustomStat {
+            const ALL: &[CustomStat] = &[$(CustomStat::$variant,)*];
+
+            /// Returns the path of the stat's identifier.
+            #[must_use]
+            pub const fn key(self) -> &'static str {
+                match self {
+                    $(CustomStat::$variant => $key,)*
+                }
+            }
+        }
+    };
+}
+
+custom_stats! {
+    LeaveGame => "leave_game",
+    PlayTime => "play_time",
+    TotalWorldTime => "total_world_time",
+    TimeSinceDeath => "time_since_death",
+    TimeSinceRest => "time_since_rest",
+    CrouchTime => "sneak_time",
+    WalkOneCm => "walk_one_cm",
+    CrouchOneCm => "crouch_one_cm",
+    SprintOneCm => "sprint_one_cm",
+    WalkOnWaterOneCm => "walk_on_water_one_cm",
+    FallOneCm => "fall_one_cm",
+    ClimbOneCm => "climb_one_cm",
+    FlyOneCm => "fly_one_cm",
+    WalkUnderWaterOneCm => "walk_under_water_one_cm",
+    MinecartOneCm => "minecart_one_cm",
+    BoatOneCm => "boat_one_cm",
+    PigOneCm => "pig_one_cm",
+    HappyGhastOneCm => "happy_ghast_one_cm",
+    HorseOneCm => "horse_one_cm",
+    AviateOneCm => "aviate_one_cm",
+    SwimOneCm => "swim_one_cm",
+    StriderOneCm => "strider_one_cm",
+    Jump => "jump",
+    Drop => "drop",
+    DamageDealt => "damage_dealt",
+    DamageDealtAbsorbed => "damage_dealt_absorbed",
+    DamageDealtResisted => "damage_dealt_resisted",
+    DamageTaken => "damage_taken",
+    DamageBlockedByShield => "damage_blocked_by_shield",
+    DamageAbsorbed => "damage_absorbed",
+    DamageResisted => "damage_resisted",
+    Deaths => "deaths",
+    MobKills => "mob_kills",
+    AnimalsBred => "animals_bred",
+    PlayerKills => "player_kills",
+    FishCaught => "fish_caught",
+    TalkedToVillager => "talked_to_villager",
+    TradedWithVillager => "traded_with_villager",
+    EatCakeSlice => "eat_cake_slice",
+    FillCauldron => "fill_cauldron",
+    UseCauldron => "use_cauldron",
+    CleanArmor => "clean_armor",
+    CleanBanner => "clean_banner",
+    CleanShulkerBox => "clean_shulker_box",
+    InteractWithBrewingstand => "interact_with_brewingstand",
+    InteractWithBeacon => "interact_with_beacon",
+    InspectDropper => "inspect_dropper",
+    InspectHopper => "inspect_hopper",
+    InspectDispenser => "inspect_dispenser",
+    PlayNoteblock => "play_noteblock",
+    TuneNoteblock => "tune_noteblock",
+    PotFlower => "pot_flower",
+    TriggerTrappedChest => "trigger_trapped_chest",
+    OpenEnderchest => "open_enderchest",
+    EnchantItem => "enchant_item",
+    PlayRecord => "play_record",
+    InteractWithFurnace => "interact_with_furnace",
+    InteractWithCraftingTable => "interact_with_crafting_table",
+    OpenChest => "open_chest",
+    SleepInBed => "sleep_in_bed",
+    OpenShulkerBox => "open_shulker_box",
+    OpenBarrel => "open_barrel",
+    InteractWithBlastFurnace => "interact_with_blast_furnace",
+    InteractWithSmoker => "interact_with_smoker",
+    InteractWithLectern => "interact_with_lectern",
+    InteractWithCampfire => "interact_with_campfire",
+    InteractWithCartographyTable => "interact_with_cartography_table",
+    InteractWithLoom => "interact_with_loom",
+    InteractWithStonecutter => "interact_with_stonecutter",
+    BellRing => "bell_ring",
+    RaidTrigger => "raid_trigger",
+    RaidWin => "raid_win",
+    InteractWithAnvil => "interact_with_anvil",
+    InteractWithGrindstone => "interact_with_grindstone",
+    TargetHit => "target_hit",
+    InteractWithSmithingTable => "interact_with_smithing_table",
+}
+
+/// Identifies a single statistic, e.g. `minecraft:mined[minecraft:stone]` or
+/// `minecraft:custom[minecraft:walk_one_cm]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StatKey {
+    /// The kind of stat.
+    pub stat_type: StatType,
+    /// The block, item, entity type or custom stat within the stat type.
+    pub value: Identifier,
+}
+
+impl StatKey {
+    /// Blocks of the given type mined.
+    #[must_use]
+    pub fn mined(block: BlockRef) -> Self {
+        Self::new(StatType::Mined, block.key.clone())
+    }
+
+    /// Items of the given type crafted.
+    #[must_use]
+    pub fn crafted(item: ItemRef) -> Self {
+        Self::new(StatType::Crafted, item.key.clone())
+    }
+
+    /// Items of the given type used.
+    #[must_use]
+    pub fn used(item: ItemRef) -> Self {
+        Self::new(StatType::Used, item.key.clone())
+    }
+
+    /// Items of the given type dropped.
+    #[must_use]
+    pub fn dropped(item: ItemRef) -> Self {
+        Self::new(StatType::Dropped, item.key.clone())
+    }
+
+    /// Entities of the given type killed.
+    #[must_use]
+    pub fn killed(entity_type: EntityTypeRef) -> Self {
+        Self::new(
+            StatType::Killed,
+            Identifier::vanilla_static(entity_type.key),
+        )
+    }
+
+    /// A custom stat.
+    #[must_use]
+    pub fn custom(stat: CustomStat) -> Self {
+        Self::new(StatType::Custom, Identifier::vanilla_static(stat.key()))
+    }
+
+    const fn new(stat_type: StatType, value: Identifier) -> Self {
+        Self { stat_type, value }
+    }
+
+    /// Returns the registry id of the value within the stat type, or `None`
+    /// if it isn't registered.
+    fn value_id(&self) -> Option<usize> {
+        match self.stat_type {
+            StatType::Mined => REGISTRY
+                .blocks
+                .by_key(&self.value)
+                .map(|block| *REGISTRY.blocks.get_id(block)),
+            StatType::Crafted
+            | StatType::Used
+            | StatType::Broken
+            | StatType::PickedUp
+            | StatType::Dropped => REGISTRY
+                .items
+                .by_key(&self.value)
+                .map(|item| *REGISTRY.items.get_id(item)),
+            StatType::Killed | StatType::KilledBy => REGISTRY
+                .entity_types
+                .by_key(&self.value.path)
+                .map(|entity_type| *REGISTRY.entity_types.get_id(entity_type)),
+            StatType::Custom => CustomStat::ALL
+                .iter()
+                .position(|stat| stat.key() == self.value.path),
+        }
+    }
+}
+
+impl fmt::Display for StatKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "minecraft:{}[{}]", self.stat_type.key(), self.value)
+    }
+}
+
+/// On-disk layout of a stats file.
+#[derive(Default, Serialize, Deserialize)]
+struct StatsFile {
+    /// Stat values grouped by stat type, then by value.
+    stats: BTreeMap<String, BTreeMap<String, i32>>,
+}
+
+/// Tracks the statistics of a player.
+pub struct PlayerStats {
+    /// Path to the player's stats file.
+    path: PathBuf,
+    /// Current value of every stat that was ever awarded.
+    values: HashMap<StatKey, i32>,
+    /// Stats changed since they were last sent to the client.
+    changed: HashSet<StatKey>,
+}
+
+impl PlayerStats {
+    /// Loads the stats of the given player, starting fresh if there is no
+    /// file yet or it can't be read.
+    #[must_use]
+    pub fn load(uuid: Uuid) -> Self {
+        let path = Path::new(STATS_DIR).join(format!("{uuid}.json"));
+        let values = HashMap::new();
+
+        let file = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::error!("Invalid stats file {}: {e}", path.display());
+                StatsFile::default()
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => StatsFile::default(),
+            Err(e) => {
+                log::error!("Failed to read stats file {}: {e}", path.display());
+                StatsFile::default()
+            }
+        };
+
+        for (stat_type, stats) in file.stats {
+            let Some(stat_type) = stat_type
+                .strip_prefix("minecraft:")
+                .and_then(StatType::from_key)
+            else {
+                continue;
+            };
+            for (value, amount) in stats {
+                let Ok(value) = Identifier::from_str(&value) else {
+                    continue;
+                };
+                let key = StatKey::new(stat_type, value);
+                // Skip stats for things that no longer exist
+                if key.value_id().is_some() {
+                    let _ = values.insert_sync(key, amount);
+                }
+            }
+        }
+
+        Self {
+            path,
+            values,
+            changed: HashSet::new(),
+        }
+    }
+
+    /// Saves the stats to disk.
+    pub fn save(&self) -> io::Result<()> {
+        let mut file = StatsFile::default();
+        self.values.iter_sync(|key, &amount| {
+            file.stats
+                .entry(format!("minecraft:{}", key.stat_type.key()))
+                .or_default()
+                .insert(key.value.to_string(), amount);
+            true
+        });
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(&file)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&self.path, content)
+    }
+
+    /// Returns the current value of a stat.
+    #[must_use]
+    pub fn get(&self, key: &StatKey) -> i32 {
+        self.values.read_sync(key, |_, &amount| amount).unwrap_or(0)
+    }
+
+    /// Adds `amount` to a stat, saturating at `i32::MAX`.
+    pub fn increment(&self, key: StatKey, amount: i32) {
+        self.values
+            .entry_sync(key.clone())
+            .and_modify(|value| *value = value.saturating_add(amount))
+            .or_insert(amount);
+        let _ = self.changed.insert_sync(key);
+    }
+
+    /// Sets a stat to the given value.
+    pub fn set(&self, key: StatKey, amount: i32) {
+        self.values.upsert_sync(key.clone(), amount);
+        let _ = self.changed.insert_sync(key);
+    }
+
+    /// Returns the `count` highest stats, highest first.
+    #[must_use]
+    pub fn top(&self, count: usize) -> Vec<(StatKey, i32)> {
+        let mut stats = Vec::with_capacity(self.values.len());
+        self.values.iter_sync(|key, &amount| {
+            stats.push((key.clone(), amount));
+            true
+        });
+        stats.sort_by(|(a_key, a), (b_key, b)| {
+            b.cmp(a)
+                .then_with(|| a_key.to_string().cmp(&b_key.to_string()))
+        });
+        stats.truncate(count);
+        stats
+    }
+
+    /// Builds a packet with every stat that changed since the last call, or
+    /// `None` if nothing changed.
+    pub fn take_changed_packet(&self) -> Option<CAwardStats> {
+        let mut stats = Vec::new();
+        self.changed.retain_sync(|key| {
+            stats.extend(Self::awarded_stat(key, self.get(key)));
+            false
+        });
+        (!stats.is_empty()).then_some(CAwardStats { stats })
+    }
+
+    /// Builds a packet with every stat, for when the client opens the
+    /// statistics screen.
+    pub fn full_packet(&self) -> CAwardStats {
+        self.changed.clear_sync();
+        let mut stats = Vec::with_capacity(self.values.len());
+        self.values.iter_sync(|key, &amount| {
+            stats.extend(Self::awarded_stat(key, amount));
+            true
+        });
+        CAwardStats { stats }
+    }
+
+    fn awarded_stat(key: &StatKey, amount: i32) -> Option<AwardedStat> {
+        Some(AwardedStat {
+            stat_type: key.stat_type as i32,
+            value: key.value_id()? as i32,
+            amount,
+        })
+    }
+}
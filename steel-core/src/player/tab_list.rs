@@ -0,0 +1,272 @@
+//! The player list shown when holding the tab key.
+
+use std::sync::Arc;
+
+use steel_protocol::packets::game::{CPlayerInfoUpdate, CRemovePlayerInfo, CTabList};
+use steel_utils::locks::SyncMutex;
+use text_components::TextComponent;
+use text_components::resolving::TextResolutor;
+use uuid::Uuid;
+
+use crate::player::Player;
+
+/// Someone listed in the tab list.
+pub trait TabListMember: TextResolutor + Send + Sync {
+    /// The UUID of the member's entry.
+    fn uuid(&self) -> Uuid;
+
+    /// The packets that add the member's entry to a client, with its chat
+    /// session if it has one.
+    fn entry_packets(&self) -> Vec<CPlayerInfoUpdate>;
+
+    /// Sends an entry update to the member.
+    fn send_player_info(&self, packet: CPlayerInfoUpdate);
+
+    /// Sends the removal of entries to the member.
+    fn send_remove_player_info(&self, packet: CRemovePlayerInfo);
+
+    /// Sends the header and footer to the member.
+    fn send_header_footer(&self, packet: CTabList);
+}
+
+/// The tab list entries and the header and footer above and below them.
+///
+/// Every member sees the entries of all members, its own included.
+pub struct TabList<M: TabListMember = Player> {
+    /// The listed members, in the order they joined.
+    members: SyncMutex<Vec<Arc<M>>>,
+    /// The header and footer, resolved for every member when sent.
+    header_footer: SyncMutex<(TextComponent, TextComponent)>,
+}
+
+impl<M: TabListMember> Default for TabList<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: TabListMember> TabList<M> {
+    /// Creates an empty tab list without header and footer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            members: SyncMutex::new(Vec::new()),
+            header_footer: SyncMutex::new((TextComponent::new(), TextComponent::new())),
+        }
+    }
+
+    /// Lists `member`, sending it the entries of everyone listed along with
+    /// the header and footer, and its own entry to everyone. Returns `false`
+    /// if a member with the same UUID is already listed.
+    pub fn add(&self, member: Arc<M>) -> bool {
+        let mut members = self.members.lock();
+        let uuid = member.uuid();
+        if members.iter().any(|listed| listed.uuid() == uuid) {
+            return false;
+        }
+        members.push(member.clone());
+
+        let entry = member.entry_packets();
+        for listed in members.iter() {
+            if listed.uuid() != uuid {
+                for packet in listed.entry_packets() {
+                    member.send_player_info(packet);
+                }
+            }
+            for packet in &entry {
+                listed.send_player_info(packet.clone());
+            }
+        }
+
+        let (header, footer) = &*self.header_footer.lock();
+        member.send_header_footer(CTabList::new(header, footer, &*member));
+        true
+    }
+
+    /// Unlists the member with `uuid`, removing its entry from everyone
+    /// still listed. Returns `false` if it wasn't listed.
+    pub fn remove(&self, uuid: Uuid) -> bool {
+        let mut members = self.members.lock();
+        let count = members.len();
+        members.retain(|listed| listed.uuid() != uuid);
+        if members.len() == count {
+            return false;
+        }
+        for listed in members.iter() {
+            listed.send_remove_player_info(CRemovePlayerInfo::single(uuid));
+        }
+        true
+    }
+
+    /// Sends an entry update to every member.
+    pub fn broadcast(&self, packet: &CPlayerInfoUpdate) {
+        for listed in self.members.lock().iter() {
+            listed.send_player_info(packet.clone());
+        }
+    }
+
+    /// Sets the header and footer, sending them to every member. Members
+    /// listed later get them when they join.
+    ///
+    /// The components are resolved per member, so translatable and
+    /// player-dependent content renders correctly for each client.
+    pub fn set_header_footer(&self, header: TextComponent, footer: TextComponent) {
+        for listed in self.members.lock().iter() {
+            listed.send_header_footer(CTabList::new(&header, &footer, &**listed));
+        }
+        *self.header_footer.lock() = (header, footer);
+    }
+}
+
+impl TabListMember for Player {
+    fn uuid(&self) -> Uuid {
+        self.gameprofile.id
+    }
+
+    fn entry_packets(&self) -> Vec<CPlayerInfoUpdate> {
+        let mut packets = vec![CPlayerInfoUpdate::create_player_initializing(
+            self.gameprofile.id,
+            self.gameprofile.name.clone(),
+            self.gameprofile.properties.clone(),
+            self.game_mode.load().into(),
+            self.connection.latency(),
+            self.tab_display_name(),
+            true, // show_hat
+        )];
+        if let Some(session) = self.chat_session()
+            && let Ok(protocol_data) = session.as_data().to_protocol_data()
+        {
+            packets.push(CPlayerInfoUpdate::update_chat_session(
+                self.gameprofile.id,
+                protocol_data,
+            ));
+        }
+        packets
+    }
+
+    fn send_player_info(&self, packet: CPlayerInfoUpdate) {
+        self.connection.send_packet(packet);
+    }
+
+    fn send_remove_player_info(&self, packet: CRemovePlayerInfo) {
+        self.connection.send_packet(packet);
+    }
+
+    fn send_header_footer(&self, packet: CTabList) {
+        self.connection.send_packet(packet);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use text_components::content::Resolvable;
+    use text_components::custom::CustomData;
+
+    use super::*;
+
+    /// A member that records what it was sent instead of having a connection.
+    struct FakeMember {
+        uuid: Uuid,
+        name: &'static str,
+        entries: SyncMutex<Vec<Uuid>>,
+        removed: SyncMutex<Vec<Uuid>>,
+        header_footer: SyncMutex<Option<CTabList>>,
+    }
+
+    impl FakeMember {
+        fn new(name: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                uuid: Uuid::new_v4(),
+                name,
+                entries: SyncMutex::new(Vec::new()),
+                removed: SyncMutex::new(Vec::new()),
+                header_footer: SyncMutex::new(None),
+            })
+        }
+    }
+
+    impl TextResolutor for FakeMember {
+        fn resolve_content(&self, _resolvable: &Resolvable) -> TextComponent {
+            TextComponent::new()
+        }
+
+        fn resolve_custom(&self, _data: &CustomData) -> Option<TextComponent> {
+            None
+        }
+
+        fn translate(&self, _key: &str) -> Option<String> {
+            None
+        }
+    }
+
+    impl TabListMember for FakeMember {
+        fn uuid(&self) -> Uuid {
+            self.uuid
+        }
+
+        fn entry_packets(&self) -> Vec<CPlayerInfoUpdate> {
+            vec![CPlayerInfoUpdate::create_player_initializing(
+                self.uuid,
+                self.name.to_owned(),
+                Vec::new(),
+                0,
+                0,
+                None,
+                true,
+            )]
+        }
+
+        fn send_player_info(&self, packet: CPlayerInfoUpdate) {
+            let mut entries = self.entries.lock();
+            entries.extend(packet.entries.iter().map(|entry| entry.uuid));
+        }
+
+        fn send_remove_player_info(&self, packet: CRemovePlayerInfo) {
+            self.removed.lock().extend(packet.uuids);
+        }
+
+        fn send_header_footer(&self, packet: CTabList) {
+            *self.header_footer.lock() = Some(packet);
+        }
+    }
+
+    fn header_footer_of(member: &FakeMember) -> Option<(String, String)> {
+        member.header_footer.lock().as_ref().map(|packet| {
+            (
+                format!("{:?}", packet.header),
+                format!("{:?}", packet.footer),
+            )
+        })
+    }
+
+    #[test]
+    fn two_players_see_each_other_and_the_header_footer() {
+        let tab_list = TabList::new();
+        let header = TextComponent::plain("Steel");
+        let footer = TextComponent::plain("TPS: 20.0");
+        let alice = FakeMember::new("Alice");
+        let bob = FakeMember::new("Bob");
+
+        assert!(tab_list.add(alice.clone()));
+        tab_list.set_header_footer(header.clone(), footer.clone());
+        assert!(tab_list.add(bob.clone()));
+        assert!(!tab_list.add(bob.clone()));
+
+        let expected = Some((format!("{header:?}"), format!("{footer:?}")));
+        for member in [&alice, &bob] {
+            let entries = member.entries.lock();
+            assert!(
+                entries.contains(&alice.uuid),
+                "{} misses Alice",
+                member.name
+            );
+            assert!(entries.contains(&bob.uuid), "{} misses Bob", member.name);
+            drop(entries);
+            assert_eq!(header_footer_of(member), expected, "{}", member.name);
+        }
+
+        assert!(tab_list.remove(bob.uuid));
+        assert!(!tab_list.remove(bob.uuid));
+        assert_eq!(*alice.removed.lock(), vec![bob.uuid]);
+    }
+}
@@ -0,0 +1,116 @@
+//! Tracks the teleports a client still has to confirm.
+
+use std::collections::VecDeque;
+
+use rustc_hash::FxHashSet;
+use steel_utils::math::Vector3;
+
+/// A teleport that was sent to the client.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PendingTeleport {
+    /// The id the client confirms the teleport with.
+    pub id: i32,
+    /// Where the player was teleported to.
+    pub position: Vector3<f64>,
+}
+
+/// The teleports sent to a client, oldest first.
+///
+/// Movement from the client is only accepted once every teleport is
+/// confirmed, since until then it's based on a position the server already
+/// moved the player away from.
+#[derive(Default)]
+pub struct TeleportQueue {
+    pending: VecDeque<PendingTeleport>,
+    /// Ids of pending teleports the client confirmed, possibly out of order.
+    confirmed: FxHashSet<i32>,
+}
+
+impl TeleportQueue {
+    /// Adds a teleport that was just sent.
+    pub fn push(&mut self, id: i32, position: Vector3<f64>) {
+        // Ids wrap around, so a stale confirmation must not carry over
+        self.confirmed.remove(&id);
+        self.pending.push_back(PendingTeleport { id, position });
+    }
+
+    /// Marks a teleport as confirmed, returning `false` if it isn't pending.
+    pub fn confirm(&mut self, id: i32) -> bool {
+        if !self.pending.iter().any(|teleport| teleport.id == id) {
+            return false;
+        }
+        self.confirmed.insert(id);
+        true
+    }
+
+    /// Removes the confirmed teleports from the front of the queue, returning
+    /// the last one removed. That is where the client now thinks it is.
+    pub fn pop_confirmed(&mut self) -> Option<PendingTeleport> {
+        let mut last = None;
+        while let Some(teleport) = self.pending.front()
+            && self.confirmed.remove(&teleport.id)
+        {
+            last = self.pending.pop_front();
+        }
+        last
+    }
+
+    /// Returns the newest teleport that isn't confirmed yet.
+    #[must_use]
+    pub fn latest(&self) -> Option<&PendingTeleport> {
+        self.pending.back()
+    }
+
+    /// Returns whether every teleport is confirmed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(x: f64) -> Vector3<f64> {
+        Vector3::new(x, 64.0, 0.0)
+    }
+
+    #[test]
+    fn waits_for_every_teleport() {
+        let mut queue = TeleportQueue::default();
+        queue.push(1, pos(1.0));
+        queue.push(2, pos(2.0));
+
+        assert!(queue.confirm(1));
+        assert_eq!(queue.pop_confirmed().map(|t| t.id), Some(1));
+        assert!(!queue.is_empty());
+        assert_eq!(queue.latest().map(|t| t.position), Some(pos(2.0)));
+
+        assert!(queue.confirm(2));
+        assert_eq!(queue.pop_confirmed().map(|t| t.position), Some(pos(2.0)));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn handles_out_of_order_confirmations() {
+        let mut queue = TeleportQueue::default();
+        queue.push(1, pos(1.0));
+        queue.push(2, pos(2.0));
+
+        assert!(queue.confirm(2));
+        assert_eq!(queue.pop_confirmed(), None);
+        assert!(queue.confirm(1));
+        assert_eq!(queue.pop_confirmed().map(|t| t.id), Some(2));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn ignores_unknown_ids() {
+        let mut queue = TeleportQueue::default();
+        assert!(!queue.confirm(5));
+        queue.push(6, pos(6.0));
+        assert!(!queue.confirm(5));
+        assert_eq!(queue.pop_confirmed(), None);
+    }
+}
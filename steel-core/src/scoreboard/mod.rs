@@ -0,0 +1,202 @@
+//! The server's scoreboard. Only teams are implemented so far.
+//!
+//! Every change returns the packet that tells clients about it, which the
+//! caller broadcasts with [`Server::broadcast_team_packet`](crate::server::Server::broadcast_team_packet).
+//! Clients color member names in the tab list and above their heads from
+//! these packets themselves.
+
+pub mod team;
+
+use rustc_hash::FxHashMap;
+use steel_protocol::packets::game::CSetPlayerTeam;
+
+pub use team::{CollisionRule, Team, TeamColor, Visibility};
+
+/// The teams of the server, shared by all worlds like vanilla's `ServerScoreboard`.
+#[derive(Default)]
+pub struct Scoreboard {
+    teams: FxHashMap<String, Team>,
+    /// The team of each member, by member name.
+    member_teams: FxHashMap<String, String>,
+}
+
+impl Scoreboard {
+    /// Creates a scoreboard without teams.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets a team by name.
+    #[must_use]
+    pub fn team(&self, name: &str) -> Option<&Team> {
+        self.teams.get(name)
+    }
+
+    /// Returns all teams, in no particular order.
+    pub fn teams(&self) -> impl Iterator<Item = &Team> {
+        self.teams.values()
+    }
+
+    /// Gets the team a member is on.
+    #[must_use]
+    pub fn members_team(&self, member: &str) -> Option<&Team> {
+        self.teams.get(self.member_teams.get(member)?)
+    }
+
+    /// Adds an empty team, returning `None` if one with the name already exists.
+    pub fn add_team(&mut self, team: Team) -> Option<CSetPlayerTeam> {
+        if self.teams.contains_key(&team.name) {
+            return None;
+        }
+        let packet = team.add_packet();
+        self.teams.insert(team.name.clone(), team);
+        Some(packet)
+    }
+
+    /// Removes a team and takes its members off it.
+    pub fn remove_team(&mut self, name: &str) -> Option<CSetPlayerTeam> {
+        let team = self.teams.remove(name)?;
+        for member in &team.member_set {
+            self.member_teams.remove(member);
+        }
+        Some(CSetPlayerTeam::remove(team.name))
+    }
+
+    /// Changes a team's display settings. Returns `None` if the team doesn't exist.
+    pub fn modify_team(
+        &mut self,
+        name: &str,
+        change: impl FnOnce(&mut Team),
+    ) -> Option<CSetPlayerTeam> {
+        let team = self.teams.get_mut(name)?;
+        change(team);
+        Some(team.change_packet())
+    }
+
+    /// Puts a member on a team, taking them off their previous one. Returns
+    /// `None` if the team doesn't exist or the member is already on it.
+    ///
+    /// Clients move the member out of their previous team themselves.
+    pub fn join_team(&mut self, member: &str, team_name: &str) -> Option<CSetPlayerTeam> {
+        if !self.teams.contains_key(team_name)
+            || self
+                .member_teams
+                .get(member)
+                .is_some_and(|t| t == team_name)
+        {
+            return None;
+        }
+
+        if let Some(old) = self
+            .member_teams
+            .insert(member.to_owned(), team_name.to_owned())
+            && let Some(old_team) = self.teams.get_mut(&old)
+        {
+            old_team.member_set.remove(member);
+        }
+        self.teams
+            .get_mut(team_name)?
+            .member_set
+            .insert(member.to_owned());
+
+        Some(CSetPlayerTeam::join(
+            team_name.to_owned(),
+            vec![member.to_owned()],
+        ))
+    }
+
+    /// Takes a member off their team. Returns `None` if they aren't on one.
+    pub fn leave_team(&mut self, member: &str) -> Option<CSetPlayerTeam> {
+        let team_name = self.member_teams.remove(member)?;
+        if let Some(team) = self.teams.get_mut(&team_name) {
+            team.member_set.remove(member);
+        }
+        Some(CSetPlayerTeam::leave(team_name, vec![member.to_owned()]))
+    }
+
+    /// Takes every member off a team. Returns `None` if the team doesn't
+    /// exist or is already empty.
+    pub fn empty_team(&mut self, name: &str) -> Option<(usize, CSetPlayerTeam)> {
+        let team = self.teams.get_mut(name)?;
+        if team.member_set.is_empty() {
+            return None;
+        }
+
+        let members: Vec<String> = team.member_set.drain().collect();
+        for member in &members {
+            self.member_teams.remove(member);
+        }
+        Some((
+            members.len(),
+            CSetPlayerTeam::leave(name.to_owned(), members),
+        ))
+    }
+
+    /// Returns whether `attacker` may hurt `target`, which is only forbidden
+    /// between members of the same team with friendly fire disabled.
+    ///
+    /// Vanilla: `Player.canHarmPlayer`
+    #[must_use]
+    pub fn can_harm(&self, attacker: &str, target: &str) -> bool {
+        match (
+            self.member_teams.get(attacker),
+            self.member_teams.get(target),
+        ) {
+            (Some(attacker_team), Some(target_team)) if attacker_team == target_team => self
+                .teams
+                .get(attacker_team)
+                .is_none_or(|team| team.friendly_fire),
+            _ => true,
+        }
+    }
+
+    /// The packets that create every team, sent to players when they join.
+    #[must_use]
+    pub fn all_team_packets(&self) -> Vec<CSetPlayerTeam> {
+        self.teams.values().map(Team::add_packet).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joining_moves_members_between_teams() {
+        let mut scoreboard = Scoreboard::new();
+        assert!(scoreboard.add_team(Team::new("red".to_owned())).is_some());
+        assert!(scoreboard.add_team(Team::new("blue".to_owned())).is_some());
+        assert!(scoreboard.add_team(Team::new("red".to_owned())).is_none());
+
+        assert!(scoreboard.join_team("Alex", "red").is_some());
+        assert!(scoreboard.join_team("Alex", "red").is_none());
+        assert!(scoreboard.join_team("Alex", "blue").is_some());
+        assert!(
+            scoreboard
+                .team("red")
+                .is_some_and(|t| t.member_set.is_empty())
+        );
+        assert_eq!(
+            scoreboard.members_team("Alex").map(|t| t.name.as_str()),
+            Some("blue")
+        );
+
+        assert!(scoreboard.remove_team("blue").is_some());
+        assert!(scoreboard.members_team("Alex").is_none());
+        assert!(scoreboard.leave_team("Alex").is_none());
+    }
+
+    #[test]
+    fn friendly_fire_only_protects_teammates() {
+        let mut scoreboard = Scoreboard::new();
+        scoreboard.add_team(Team::new("red".to_owned()));
+        scoreboard.join_team("Alex", "red");
+        scoreboard.join_team("Steve", "red");
+
+        assert!(scoreboard.can_harm("Alex", "Steve"));
+        scoreboard.modify_team("red", |team| team.friendly_fire = false);
+        assert!(!scoreboard.can_harm("Alex", "Steve"));
+        assert!(scoreboard.can_harm("Alex", "Notch"));
+    }
+}
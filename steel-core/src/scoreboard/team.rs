@@ -0,0 +1,326 @@
+//! Scoreboard teams, matching vanilla's `PlayerTeam`.
+
+use rustc_hash::FxHashSet;
+use steel_protocol::packets::game::{CSetPlayerTeam, TeamParameters};
+use steel_utils::translations;
+use text_components::{TextComponent, format::Color};
+
+/// Who can see a team's name tags or death messages (Vanilla: `Team.Visibility`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    /// Everyone.
+    Always,
+    /// No one.
+    Never,
+    /// Only members of the same team.
+    HideForOtherTeams,
+    /// Everyone but members of the same team.
+    HideForOwnTeam,
+}
+
+impl Visibility {
+    /// All visibilities, in id order.
+    pub const ALL: [Self; 4] = [
+        Self::Always,
+        Self::Never,
+        Self::HideForOtherTeams,
+        Self::HideForOwnTeam,
+    ];
+
+    /// The network id.
+    #[must_use]
+    pub const fn id(self) -> i32 {
+        self as i32
+    }
+
+    /// The name used by commands.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Never => "never",
+            Self::HideForOtherTeams => "hideForOtherTeams",
+            Self::HideForOwnTeam => "hideForOwnTeam",
+        }
+    }
+
+    /// The translated name shown in command feedback.
+    #[must_use]
+    pub fn display_name(self) -> TextComponent {
+        match self {
+            Self::Always => translations::TEAM_VISIBILITY_ALWAYS.msg().into(),
+            Self::Never => translations::TEAM_VISIBILITY_NEVER.msg().into(),
+            Self::HideForOtherTeams => translations::TEAM_VISIBILITY_HIDE_FOR_OTHER_TEAMS
+                .msg()
+                .into(),
+            Self::HideForOwnTeam => translations::TEAM_VISIBILITY_HIDE_FOR_OWN_TEAM.msg().into(),
+        }
+    }
+}
+
+/// Which entities members of a team push (Vanilla: `Team.CollisionRule`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionRule {
+    /// Everyone.
+    Always,
+    /// No one.
+    Never,
+    /// Everyone but members of the same team.
+    PushOtherTeams,
+    /// Only members of the same team.
+    PushOwnTeam,
+}
+
+impl CollisionRule {
+    /// All collision rules, in id order.
+    pub const ALL: [Self; 4] = [
+        Self::Always,
+        Self::Never,
+        Self::PushOtherTeams,
+        Self::PushOwnTeam,
+    ];
+
+    /// The network id.
+    #[must_use]
+    pub const fn id(self) -> i32 {
+        self as i32
+    }
+
+    /// The name used by commands.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Never => "never",
+            Self::PushOtherTeams => "pushOtherTeams",
+            Self::PushOwnTeam => "pushOwnTeam",
+        }
+    }
+
+    /// The translated name shown in command feedback.
+    #[must_use]
+    pub fn display_name(self) -> TextComponent {
+        match self {
+            Self::Always => translations::TEAM_COLLISION_ALWAYS.msg().into(),
+            Self::Never => translations::TEAM_COLLISION_NEVER.msg().into(),
+            Self::PushOtherTeams => translations::TEAM_COLLISION_PUSH_OTHER_TEAMS.msg().into(),
+            Self::PushOwnTeam => translations::TEAM_COLLISION_PUSH_OWN_TEAM.msg().into(),
+        }
+    }
+}
+
+/// A team color, the color entries of vanilla's `ChatFormatting` plus `reset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TeamColor {
+    /// `black`
+    Black,
+    /// `dark_blue`
+    DarkBlue,
+    /// `dark_green`
+    DarkGreen,
+    /// `dark_aqua`
+    DarkAqua,
+    /// `dark_red`
+    DarkRed,
+    /// `dark_purple`
+    DarkPurple,
+    /// `gold`
+    Gold,
+    /// `gray`
+    Gray,
+    /// `dark_gray`
+    DarkGray,
+    /// `blue`
+    Blue,
+    /// `green`
+    Green,
+    /// `aqua`
+    Aqua,
+    /// `red`
+    Red,
+    /// `light_purple`
+    LightPurple,
+    /// `yellow`
+    Yellow,
+    /// `white`
+    White,
+    /// No color.
+    Reset,
+}
+
+impl TeamColor {
+    /// All colors, in `ChatFormatting` order.
+    pub const ALL: [Self; 17] = [
+        Self::Black,
+        Self::DarkBlue,
+        Self::DarkGreen,
+        Self::DarkAqua,
+        Self::DarkRed,
+        Self::DarkPurple,
+        Self::Gold,
+        Self::Gray,
+        Self::DarkGray,
+        Self::Blue,
+        Self::Green,
+        Self::Aqua,
+        Self::Red,
+        Self::LightPurple,
+        Self::Yellow,
+        Self::White,
+        Self::Reset,
+    ];
+
+    /// The `ChatFormatting` id. The formatting codes between `white` and
+    /// `reset` aren't colors.
+    #[must_use]
+    pub const fn id(self) -> i32 {
+        match self {
+            Self::Reset => 21,
+            color => color as i32,
+        }
+    }
+
+    /// The name used by commands.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Black => "black",
+            Self::DarkBlue => "dark_blue",
+            Self::DarkGreen => "dark_green",
+            Self::DarkAqua => "dark_aqua",
+            Self::DarkRed => "dark_red",
+            Self::DarkPurple => "dark_purple",
+            Self::Gold => "gold",
+            Self::Gray => "gray",
+            Self::DarkGray => "dark_gray",
+            Self::Blue => "blue",
+            Self::Green => "green",
+            Self::Aqua => "aqua",
+            Self::Red => "red",
+            Self::LightPurple => "light_purple",
+            Self::Yellow => "yellow",
+            Self::White => "white",
+            Self::Reset => "reset",
+        }
+    }
+
+    /// Parses a color by its command name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|color| color.name() == name)
+    }
+
+    /// The text color, or `None` for `reset`.
+    #[must_use]
+    pub fn text_color(self) -> Option<Color> {
+        Some(match self {
+            Self::Black => Color::Black,
+            Self::DarkBlue => Color::DarkBlue,
+            Self::DarkGreen => Color::DarkGreen,
+            Self::DarkAqua => Color::DarkAqua,
+            Self::DarkRed => Color::DarkRed,
+            Self::DarkPurple => Color::DarkPurple,
+            Self::Gold => Color::Gold,
+            Self::Gray => Color::Gray,
+            Self::DarkGray => Color::DarkGray,
+            Self::Blue => Color::Blue,
+            Self::Green => Color::Green,
+            Self::Aqua => Color::Aqua,
+            Self::Red => Color::Red,
+            Self::LightPurple => Color::LightPurple,
+            Self::Yellow => Color::Yellow,
+            Self::White => Color::White,
+            Self::Reset => return None,
+        })
+    }
+}
+
+/// A scoreboard team. Members are stored by name, like vanilla's score holders.
+#[derive(Debug, Clone)]
+pub struct Team {
+    /// The unique name of the team.
+    pub name: String,
+    /// The name shown in commands, defaulting to `name`.
+    pub display_name: TextComponent,
+    /// Shown before the names of members.
+    pub prefix: TextComponent,
+    /// Shown after the names of members.
+    pub suffix: TextComponent,
+    /// The color of members' names in the tab list and above their heads.
+    pub color: TeamColor,
+    /// Whether members can hurt each other.
+    pub friendly_fire: bool,
+    /// Whether members see invisible teammates as translucent.
+    pub can_see_invisible_friends: bool,
+    /// Who can see the name tags of members.
+    pub nametag_visibility: Visibility,
+    /// Who gets the death messages of members.
+    pub death_message_visibility: Visibility,
+    /// Which entities members push.
+    pub collision_rule: CollisionRule,
+    /// The names of the members.
+    pub member_set: FxHashSet<String>,
+}
+
+impl Team {
+    /// Creates an empty team with vanilla's default options.
+    #[must_use]
+    pub fn new(name: String) -> Self {
+        Self {
+            display_name: TextComponent::plain(name.clone()),
+            name,
+            prefix: TextComponent::plain(""),
+            suffix: TextComponent::plain(""),
+            color: TeamColor::Reset,
+            friendly_fire: true,
+            can_see_invisible_friends: true,
+            nametag_visibility: Visibility::Always,
+            death_message_visibility: Visibility::Always,
+            collision_rule: CollisionRule::Always,
+            member_set: FxHashSet::default(),
+        }
+    }
+
+    /// The display name in brackets and in the team color, for command feedback.
+    ///
+    /// Vanilla: `PlayerTeam.getFormattedDisplayName`
+    #[must_use]
+    pub fn formatted_display_name(&self) -> TextComponent {
+        let name = TextComponent::plain("[")
+            .add_children(vec![self.display_name.clone(), TextComponent::plain("]")]);
+        match self.color.text_color() {
+            Some(color) => name.color(color),
+            None => name,
+        }
+    }
+
+    /// The display settings sent to clients.
+    #[must_use]
+    pub fn parameters(&self) -> TeamParameters {
+        TeamParameters {
+            display_name: self.display_name.clone(),
+            options: u8::from(self.friendly_fire) | u8::from(self.can_see_invisible_friends) << 1,
+            nametag_visibility: self.nametag_visibility.id(),
+            collision_rule: self.collision_rule.id(),
+            color: self.color.id(),
+            prefix: self.prefix.clone(),
+            suffix: self.suffix.clone(),
+        }
+    }
+
+    /// The packet that creates this team with its members.
+    #[must_use]
+    pub fn add_packet(&self) -> CSetPlayerTeam {
+        CSetPlayerTeam::add(
+            self.name.clone(),
+            self.parameters(),
+            self.member_set.iter().cloned().collect(),
+        )
+    }
+
+    /// The packet that updates this team's display settings.
+    #[must_use]
+    pub fn change_packet(&self) -> CSetPlayerTeam {
+        CSetPlayerTeam::change(self.name.clone(), self.parameters())
+    }
+}
@@ -0,0 +1,99 @@
+//! Process-wide counters that are exported by the metrics endpoint.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bounds (in seconds) of the chunk generation duration histogram buckets.
+pub const CHUNK_GEN_BUCKETS: [f64; 10] =
+    [0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5];
+
+/// Global server metrics.
+pub static METRICS: ServerMetrics = ServerMetrics::new();
+
+/// Counters collected from the network and chunk systems.
+pub struct ServerMetrics {
+    packets_received: AtomicU64,
+    packets_sent: AtomicU64,
+    chunk_generations: AtomicU64,
+    /// Cumulative counts per bucket of [`CHUNK_GEN_BUCKETS`].
+    chunk_gen_buckets: [AtomicU64; CHUNK_GEN_BUCKETS.len()],
+    chunk_gen_sum_micros: AtomicU64,
+}
+
+/// A snapshot of the chunk generation duration histogram.
+pub struct HistogramSnapshot {
+    /// Cumulative count for each bucket in [`CHUNK_GEN_BUCKETS`].
+    pub buckets: [u64; CHUNK_GEN_BUCKETS.len()],
+    /// Total number of observations.
+    pub count: u64,
+    /// Sum of all observations in seconds.
+    pub sum: f64,
+}
+
+impl ServerMetrics {
+    const fn new() -> Self {
+        Self {
+            packets_received: AtomicU64::new(0),
+            packets_sent: AtomicU64::new(0),
+            chunk_generations: AtomicU64::new(0),
+            chunk_gen_buckets: [const { AtomicU64::new(0) }; CHUNK_GEN_BUCKETS.len()],
+            chunk_gen_sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    /// Counts a packet read from a client.
+    #[inline]
+    pub fn packet_received(&self) {
+        self.packets_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a packet written to a client.
+    #[inline]
+    pub fn packet_sent(&self) {
+        self.packets_sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a chunk that finished generating and how long it took.
+    pub fn chunk_generated(&self, duration: Duration) {
+        self.chunk_generations.fetch_add(1, Ordering::Relaxed);
+        self.chunk_gen_sum_micros
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+
+        let seconds = duration.as_secs_f64();
+        for (bucket, bound) in self.chunk_gen_buckets.iter().zip(CHUNK_GEN_BUCKETS) {
+            if seconds <= bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Total packets received from clients.
+    #[must_use]
+    pub fn packets_received(&self) -> u64 {
+        self.packets_received.load(Ordering::Relaxed)
+    }
+
+    /// Total packets sent to clients.
+    #[must_use]
+    pub fn packets_sent(&self) -> u64 {
+        self.packets_sent.load(Ordering::Relaxed)
+    }
+
+    /// Total chunks generated.
+    #[must_use]
+    pub fn chunk_generations(&self) -> u64 {
+        self.chunk_generations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the current state of the chunk generation histogram.
+    #[must_use]
+    pub fn chunk_gen_histogram(&self) -> HistogramSnapshot {
+        HistogramSnapshot {
+            buckets: self
+                .chunk_gen_buckets
+                .each_ref()
+                .map(|bucket| bucket.load(Ordering::Relaxed)),
+            count: self.chunk_generations(),
+            sum: self.chunk_gen_sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0,
+        }
+    }
+}
@@ -1,27 +1,32 @@
 //! This module contains the `Server` struct, which is the main entry point for the server.
+/// Global counters exported by the metrics endpoint.
+pub mod metrics;
+/// Per-phase tick timing for diagnosing lag.
+pub mod profiler;
 /// The registry cache for the server.
 pub mod registry_cache;
 /// The tick rate manager for the server.
 pub mod tick_rate_manager;
 
 use std::{
-    sync::{
-        Arc,
-        atomic::{AtomicI32, Ordering},
-    },
+    sync::Arc,
     time::{Duration, Instant},
 };
 
 use steel_crypto::key_store::KeyStore;
 use steel_protocol::packets::game::{
-    CLogin, CSystemChat, CTabList, CTickingState, CTickingStep, CommonPlayerSpawnInfo,
+    CLogin, CPlayerChat, CSetPlayerTeam, CSystemChat, CTickingState, CTickingStep, CUpdateRecipes,
+};
+use steel_registry::game_rules::{GameRuleRef, GameRuleValue};
+use steel_registry::vanilla_dimension_types::{OVERWORLD, THE_END, THE_NETHER};
+use steel_registry::vanilla_game_rules::{
+    ALLOW_ENTERING_NETHER_USING_PORTALS, IMMEDIATE_RESPAWN, LIMITED_CRAFTING, REDUCED_DEBUG_INFO,
 };
-use steel_registry::game_rules::GameRuleValue;
-use steel_registry::vanilla_dimension_types::OVERWORLD;
-use steel_registry::vanilla_game_rules::{IMMEDIATE_RESPAWN, LIMITED_CRAFTING, REDUCED_DEBUG_INFO};
 use steel_registry::{REGISTRY, Registry};
-use steel_utils::locks::SyncRwLock;
+use steel_utils::locks::{SyncMutex, SyncRwLock};
+use steel_utils::math::{Axis, Vector3};
 use steel_utils::types::GameType;
+use steel_utils::{BlockPos, ChunkPos, Identifier};
 use text_components::{Modifier, TextComponent, format::Color};
 use tick_rate_manager::{SprintReport, TickRateManager};
 use tokio::{runtime::Runtime, task::spawn_blocking, time::sleep};
@@ -29,15 +34,41 @@ use tokio_util::sync::CancellationToken;
 
 use crate::behavior::init_behaviors;
 use crate::block_entity::init_block_entities;
+use crate::chunk::generators::end::END_SPAWN_POINT;
 use crate::command::CommandDispatcher;
 use crate::config::STEEL_CONFIG;
-use crate::player::Player;
+use crate::entity;
+use crate::entity::registry::init_entities;
+use crate::player::{LastSeen, Player, tab_list::TabList};
+use crate::scoreboard::Scoreboard;
+use crate::server::profiler::TickProfiler;
 use crate::server::registry_cache::RegistryCache;
-use crate::world::{World, WorldTickTimings};
+use crate::world::{
+    World, WorldTickTimings,
+    portal::{self, PortalType},
+};
 
 /// Interval in ticks between tab list updates (20 ticks = 1 second).
 const TAB_LIST_UPDATE_INTERVAL: u64 = 20;
 
+/// Converts the configured seed into a numeric world seed, or `None` if it is empty.
+///
+/// Matches vanilla's `WorldOptions.parseSeed`: numbers are used as-is and any
+/// other text is hashed with Java's `String.hashCode`.
+fn parse_seed(seed: &str) -> Option<i64> {
+    let seed = seed.trim();
+    if seed.is_empty() {
+        return None;
+    }
+
+    Some(seed.parse().unwrap_or_else(|_| {
+        let hash = seed.encode_utf16().fold(0i32, |hash, c| {
+            hash.wrapping_mul(31).wrapping_add(i32::from(c))
+        });
+        i64::from(hash)
+    }))
+}
+
 /// The main server struct.
 pub struct Server {
     /// The cancellation token for graceful shutdown.
@@ -50,10 +81,14 @@ pub struct Server {
     pub worlds: Vec<Arc<World>>,
     /// The tick rate manager for the server.
     pub tick_rate_manager: SyncRwLock<TickRateManager>,
+    /// Per-phase timings of recent ticks.
+    pub profiler: SyncMutex<TickProfiler>,
     /// Saves and dispatches commands to appropriate handlers.
     pub command_dispatcher: SyncRwLock<CommandDispatcher>,
-    /// Counter for assigning unique entity IDs.
-    next_entity_id: AtomicI32,
+    /// The teams shared by all worlds.
+    pub scoreboard: SyncMutex<Scoreboard>,
+    /// The tab list shared by all worlds, so players see everyone online.
+    pub tab_list: Arc<TabList>,
 }
 
 impl Server {
@@ -75,41 +110,100 @@ impl Server {
         // Initialize behavior registries after the main registry is frozen
         init_behaviors();
         init_block_entities();
+        init_entities();
         log::info!("Behavior registries initialized");
 
         let registry_cache = RegistryCache::new();
 
-        let seed: i64 = if STEEL_CONFIG.seed.is_empty() {
-            rand::random()
-        } else {
-            STEEL_CONFIG.seed.parse().unwrap_or_else(|_| {
-                let mut hash: i64 = 0;
-                for byte in STEEL_CONFIG.seed.bytes() {
-                    hash = hash.wrapping_mul(31).wrapping_add(i64::from(byte));
-                }
-                hash
-            })
-        };
-
-        let overworld = World::new(chunk_runtime, OVERWORLD, seed)
+        let seed = parse_seed(&STEEL_CONFIG.load().seed).unwrap_or_else(rand::random);
+
+        let tab_list = Arc::new(TabList::new());
+        let overworld = World::new(
+            chunk_runtime.clone(),
+            OVERWORLD,
+            seed,
+            None,
+            tab_list.clone(),
+        )
+        .await
+        .expect("Failed to create overworld");
+        // Each world has its own chunk map, so the Nether generates and saves
+        // its chunks independently of the overworld. The game rules are the
+        // overworld's, like vanilla keeps them in its level.dat
+        let game_rules = Some(overworld.game_rules.clone());
+        let nether = World::new(
+            chunk_runtime.clone(),
+            THE_NETHER,
+            seed,
+            game_rules.clone(),
+            tab_list.clone(),
+        )
+        .await
+        .expect("Failed to create the nether");
+        let end = World::new(chunk_runtime, THE_END, seed, game_rules, tab_list.clone())
             .await
-            .expect("Failed to create overworld");
+            .expect("Failed to create the end");
 
         Server {
             cancel_token,
             key_store: KeyStore::create(),
-            worlds: vec![overworld],
+            worlds: vec![overworld, nether, end],
             registry_cache,
             tick_rate_manager: SyncRwLock::new(TickRateManager::new()),
+            profiler: SyncMutex::new(TickProfiler::new()),
             command_dispatcher: SyncRwLock::new(CommandDispatcher::new()),
-            next_entity_id: AtomicI32::new(1), // Start at 1, 0 is reserved
+            scoreboard: SyncMutex::new(Scoreboard::new()),
+            tab_list,
+        }
+    }
+
+    /// Returns the world of the given dimension, if the server has one.
+    #[must_use]
+    pub fn world(&self, dimension: &Identifier) -> Option<&Arc<World>> {
+        self.worlds
+            .iter()
+            .find(|world| &world.dimension.key == dimension)
+    }
+
+    /// Returns the overworld, where players join and the spawn is.
+    ///
+    /// Vanilla: `MinecraftServer.overworld`
+    ///
+    /// # Panics
+    /// Panics if the server has no overworld, which it always creates.
+    #[must_use]
+    pub fn overworld(&self) -> &Arc<World> {
+        self.world(&OVERWORLD.key)
+            .expect("The server always has an overworld")
+    }
+
+    /// Gets the value of a game rule, which is the same in every world.
+    #[must_use]
+    pub fn get_game_rule(&self, rule: GameRuleRef) -> GameRuleValue {
+        self.overworld().get_game_rule(rule)
+    }
+
+    /// Sets the value of a game rule for every world and tells clients about
+    /// rules they depend on. Returns `false` if the value doesn't fit the rule.
+    pub fn set_game_rule(&self, rule: GameRuleRef, value: GameRuleValue) -> bool {
+        let (old_value, changed) = {
+            let mut game_rules = self.overworld().game_rules.write();
+            let old_value = game_rules.get(rule, &REGISTRY.game_rules);
+            (old_value, game_rules.set(rule, value, &REGISTRY.game_rules))
+        };
+
+        if changed && old_value != value {
+            for world in &self.worlds {
+                world.on_game_rule_changed(rule, value);
+            }
         }
+        changed
     }
 
     /// Allocates a new unique entity ID.
     #[must_use]
     pub fn next_entity_id(&self) -> i32 {
-        self.next_entity_id.fetch_add(1, Ordering::Relaxed)
+        entity::next_entity_id()
     }
 
     /// Adds a player to the server.
@@ -117,7 +211,7 @@ impl Server {
     /// # Panics
     /// Panics if the registry is not initialized.
     pub fn add_player(&self, player: Arc<Player>) {
-        let world = &self.worlds[0];
+        let world = self.overworld();
 
         // Get gamerule values
         let reduced_debug_info =
@@ -126,50 +220,54 @@ impl Server {
         let do_limited_crafting =
             world.get_game_rule(LIMITED_CRAFTING) == GameRuleValue::Bool(true);
 
-        // Get world data
-        let hashed_seed = world.obfuscated_seed();
-        let dimension_key = world.dimension.key.clone();
-
         player.connection.send_packet(CLogin {
             player_id: player.id,
             hardcore: false,
-            levels: vec![dimension_key.clone()],
-            max_players: STEEL_CONFIG.max_players as i32,
+            levels: self
+                .worlds
+                .iter()
+                .map(|world| world.dimension.key.clone())
+                .collect(),
+            max_players: STEEL_CONFIG.load().max_players as i32,
             chunk_radius: player.view_distance().into(),
-            simulation_distance: STEEL_CONFIG.simulation_distance.into(),
+            simulation_distance: STEEL_CONFIG.load().simulation_distance.into(),
             reduced_debug_info,
             show_death_screen: !immediate_respawn,
             do_limited_crafting,
-            common_player_spawn_info: CommonPlayerSpawnInfo {
-                dimension_type: *(REGISTRY.dimension_types.get_id(
-                    REGISTRY
-                        .dimension_types
-                        .by_key(&dimension_key)
-                        .expect("Should be registered"),
-                )) as i32,
-                dimension: dimension_key,
-                seed: hashed_seed,
-                game_type: GameType::Survival,
-                previous_game_type: None,
-                is_debug: false,
-                // TODO: Change once we add a normal generator
-                is_flat: true,
-                last_death_location: None,
-                portal_cooldown: 0,
-                sea_level: 63, // Standard overworld sea level
-            },
-            enforces_secure_chat: STEEL_CONFIG.enforce_secure_chat,
+            common_player_spawn_info: world.spawn_info(GameType::Survival),
+            enforces_secure_chat: STEEL_CONFIG.load().enforce_secure_chat,
         });
 
         // Send player abilities (flight, invulnerability, etc.)
         player.send_abilities();
 
+        // Send the stonecutter recipes (vanilla: PlayerList.placeNewPlayer)
+        player.connection.send_packet(CUpdateRecipes {
+            stonecutter_recipes: REGISTRY.recipes.stonecutting().iter().collect(),
+        });
+
         let commands = self.command_dispatcher.read().get_commands();
         player.connection.send_packet(commands);
 
         // Send current ticking state to the joining player
         self.send_ticking_state_to_player(&player);
 
+        // Send the world border (vanilla: PlayerList.sendLevelInfo)
+        let border_packet = world.border.lock().initialize_packet();
+        player.connection.send_packet(border_packet);
+
+        // Send the difficulty (vanilla: PlayerList.placeNewPlayer)
+        player.connection.send_packet(world.difficulty_packet());
+
+        // Send the advancement tree and the player's progress on it
+        let advancements_packet = player.advancements.lock().full_update_packet();
+        player.connection.send_packet(advancements_packet);
+
+        // Send the teams (vanilla: PlayerList.updateEntireScoreboard)
+        for packet in self.scoreboard.lock().all_team_packets() {
+            player.connection.send_packet(packet);
+        }
+
         world.add_player(player);
     }
 
@@ -185,6 +283,109 @@ impl Server {
         players
     }
 
+    /// Runs `f` for every player on the server, stopping once it returns
+    /// `false`.
+    pub fn for_each_player(&self, mut f: impl FnMut(&Arc<Player>) -> bool) {
+        for world in &self.worlds {
+            let mut keep_going = true;
+            world.players.iter_players(|_, player| {
+                keep_going = f(player);
+                keep_going
+            });
+            if !keep_going {
+                return;
+            }
+        }
+    }
+
+    /// Broadcasts a signed chat message to all players on the server.
+    ///
+    /// # Panics
+    /// Panics if `message_signature` is `None` after checking `is_some()` (should never happen).
+    pub fn broadcast_chat(
+        &self,
+        mut packet: CPlayerChat,
+        _sender: Arc<Player>,
+        sender_last_seen: LastSeen,
+        message_signature: Option<[u8; 256]>,
+    ) {
+        log::debug!(
+            "broadcast_chat: sender_last_seen has {} signatures, message_signature present: {}",
+            sender_last_seen.len(),
+            message_signature.is_some()
+        );
+
+        self.for_each_player(|recipient| {
+            let messages_received = recipient.get_and_increment_messages_received();
+            packet.global_index = messages_received;
+
+            log::debug!(
+                "Broadcasting to player {} (UUID: {}), global_index={}",
+                recipient.gameprofile.name,
+                recipient.gameprofile.id,
+                messages_received
+            );
+
+            // IMPORTANT: Index previous messages BEFORE updating the cache
+            // This matches vanilla's order: pack() then push()
+            let previous_messages = {
+                let recipient_cache = recipient.signature_cache.lock();
+                recipient_cache.index_previous_messages(&sender_last_seen)
+            };
+
+            log::debug!(
+                "  Indexed {} previous messages for recipient",
+                previous_messages.len()
+            );
+
+            packet.previous_messages.clone_from(&previous_messages);
+
+            // Send the packet
+            recipient.connection.send_packet(packet.clone());
+
+            // AFTER sending, update the recipient's cache using vanilla's push algorithm
+            // This adds all lastSeen signatures + current signature to the cache
+            if let Some(signature) = message_signature {
+                recipient
+                    .signature_cache
+                    .lock()
+                    .push(&sender_last_seen, Some(&signature));
+
+                log::debug!("  Added signature to recipient's cache and pending list");
+
+                // Add to pending messages for acknowledgment tracking
+                recipient
+                    .message_validator
+                    .lock()
+                    .add_pending(Some(Box::new(signature) as Box<[u8]>));
+            } else {
+                // Even unsigned messages update the pending tracker
+                recipient.message_validator.lock().add_pending(None);
+                log::debug!("  Added unsigned message to pending list");
+            }
+
+            true
+        });
+    }
+
+    /// Broadcasts an unsigned player chat message to all players.
+    pub fn broadcast_unsigned_chat(
+        &self,
+        mut packet: CPlayerChat,
+        sender_name: &str,
+        message: &str,
+    ) {
+        log::info!("<{sender_name}> {message}");
+
+        self.for_each_player(|recipient| {
+            let messages_received = recipient.get_and_increment_messages_received();
+            packet.global_index = messages_received;
+
+            recipient.connection.send_packet(packet.clone());
+            true
+        });
+    }
+
     /// Runs the server tick loop.
     pub async fn run(self: Arc<Self>, cancel_token: CancellationToken) {
         let mut next_tick_time = Instant::now();
@@ -245,11 +446,15 @@ impl Server {
 
             // Always tick worlds (for chunk loading/gen), but pass runs_normally
             // so game elements like random ticks only run when not frozen
-            self.tick_worlds(tick_count, runs_normally).await;
+            let world_timings = self.tick_worlds(tick_count, runs_normally).await;
+
+            self.start_portal_travel();
 
             // Record tick duration for TPS/MSPT tracking
             let (tps, mspt) = {
-                let tick_duration_nanos = tick_start.elapsed().as_nanos() as u64;
+                let tick_duration = tick_start.elapsed();
+                self.profiler.lock().record(tick_duration, &world_timings);
+                let tick_duration_nanos = tick_duration.as_nanos() as u64;
                 let mut tick_manager = self.tick_rate_manager.write();
                 tick_manager.record_tick_time(tick_duration_nanos);
                 (tick_manager.get_tps(), tick_manager.get_average_mspt())
@@ -267,8 +472,159 @@ impl Server {
         }
     }
 
+    /// Sends the players that stood in a portal long enough through it.
+    ///
+    /// Finding or building the exit portal can mean generating chunks, so
+    /// each trip runs in its own task instead of holding up the tick.
+    fn start_portal_travel(self: &Arc<Self>) {
+        for world in &self.worlds {
+            world.players.iter_players(|_, player| {
+                if let Some((entrance, portal_type)) = player.take_portal_entrance() {
+                    let server = self.clone();
+                    let source_world = world.clone();
+                    let player = player.clone();
+                    tokio::spawn(async move {
+                        match portal_type {
+                            PortalType::Nether => {
+                                server
+                                    .travel_through_portal(&source_world, &player, entrance)
+                                    .await;
+                            }
+                            PortalType::End => {
+                                server
+                                    .travel_through_end_portal(&source_world, &player)
+                                    .await;
+                            }
+                        }
+                        player.finish_portal_travel();
+                    });
+                }
+                true
+            });
+        }
+    }
+
+    /// Moves a player through the nether portal at `entrance` into the
+    /// matching world, building an exit portal if there is none nearby.
+    ///
+    /// Matches vanilla `NetherPortalBlock.getPortalDestination`.
+    async fn travel_through_portal(
+        &self,
+        source_world: &Arc<World>,
+        player: &Arc<Player>,
+        entrance: BlockPos,
+    ) {
+        let to_nether = source_world.dimension.key != THE_NETHER.key;
+        if to_nether
+            && source_world.get_game_rule(ALLOW_ENTERING_NETHER_USING_PORTALS)
+                != GameRuleValue::Bool(true)
+        {
+            return;
+        }
+        let target_key = if to_nether {
+            &THE_NETHER.key
+        } else {
+            &OVERWORLD.key
+        };
+        let Some(target_world) = self.world(target_key).cloned() else {
+            return;
+        };
+
+        let scale =
+            source_world.dimension.coordinate_scale / target_world.dimension.coordinate_scale;
+        let pos = *player.position.lock();
+        let (x, z) = {
+            let border = target_world.border.lock();
+            (
+                (pos.x * scale).clamp(border.min_x(), border.max_x() - 1.0),
+                (pos.z * scale).clamp(border.min_z(), border.max_z() - 1.0),
+            )
+        };
+        let target = BlockPos::new(x.floor() as i32, entrance.y(), z.floor() as i32);
+
+        let search_radius = if to_nether {
+            portal::NETHER_SEARCH_RADIUS
+        } else {
+            portal::SEARCH_RADIUS
+        };
+        let center = ChunkPos::new(target.x() >> 4, target.z() >> 4);
+        let chunk_radius = (search_radius >> 4) as u8;
+        if !target_world.chunk_map.load_area(center, chunk_radius).await {
+            target_world.chunk_map.release_area(center, chunk_radius);
+            log::warn!("Failed to load the chunks around a portal exit at {target:?}");
+            return;
+        }
+
+        // The entrance may have been broken while the player waited
+        let entrance_state = source_world.get_block_state(&entrance);
+        let axis = if portal::is_portal(entrance_state) {
+            portal::portal_axis(entrance_state)
+        } else {
+            Axis::X
+        };
+        let search_world = target_world.clone();
+        let exit = spawn_blocking(move || {
+            portal::find_portal(&search_world, target, search_radius)
+                .or_else(|| portal::create_portal(&search_world, target, axis))
+        })
+        .await
+        .ok()
+        .flatten();
+        target_world.chunk_map.release_area(center, chunk_radius);
+
+        let Some(exit) = exit else {
+            log::warn!("Couldn't find or build a portal exit at {target:?}");
+            return;
+        };
+        let exit_pos = Vector3::new(
+            f64::from(exit.x()) + 0.5,
+            f64::from(exit.y()),
+            f64::from(exit.z()) + 0.5,
+        );
+        player.change_dimension(target_world, exit_pos).await;
+    }
+
+    /// Moves a player through an end portal: into the End onto its obsidian
+    /// platform, or out of it to the overworld spawn.
+    ///
+    /// Matches vanilla `EndPortalBlock.getPortalDestination`.
+    async fn travel_through_end_portal(&self, source_world: &Arc<World>, player: &Arc<Player>) {
+        let to_end = source_world.dimension.key != THE_END.key;
+        let target_key = if to_end { &THE_END.key } else { &OVERWORLD.key };
+        let Some(target_world) = self.world(target_key).cloned() else {
+            return;
+        };
+
+        // TODO: Use the player's respawn point once beds and anchors set one
+        let target = if to_end {
+            END_SPAWN_POINT
+        } else {
+            target_world.level_data.read().data().spawn_pos()
+        };
+        let center = ChunkPos::new(target.x() >> 4, target.z() >> 4);
+        if !target_world.chunk_map.load_area(center, 1).await {
+            target_world.chunk_map.release_area(center, 1);
+            log::warn!("Failed to load the chunks around an end portal exit at {target:?}");
+            return;
+        }
+
+        if to_end {
+            let platform_world = target_world.clone();
+            let _ =
+                spawn_blocking(move || portal::create_end_platform(&platform_world, target)).await;
+        }
+        target_world.chunk_map.release_area(center, 1);
+
+        let exit_pos = Vector3::new(
+            f64::from(target.x()) + 0.5,
+            f64::from(target.y()),
+            f64::from(target.z()) + 0.5,
+        );
+        player.change_dimension(target_world, exit_pos).await;
+    }
+
     #[tracing::instrument(level = "trace", skip(self), name = "tick_worlds")]
-    async fn tick_worlds(&self, tick_count: u64, runs_normally: bool) {
+    async fn tick_worlds(&self, tick_count: u64, runs_normally: bool) -> Vec<WorldTickTimings> {
         let mut tasks = Vec::with_capacity(self.worlds.len());
         for world in &self.worlds {
             let world_clone = world.clone();
@@ -293,6 +649,9 @@ impl Server {
                     ?elapsed,
                     tick_count,
                     player_tick = ?timings.player_tick,
+                    entity_tick = ?timings.entity_tick,
+                    fluid_tick = ?timings.fluid_tick,
+                    light_updates = ?timings.light_updates,
                     ticket_updates = ?cm.ticket_updates,
                     holder_creation = ?cm.holder_creation,
                     schedule_generation = ?cm.schedule_generation,
@@ -308,6 +667,7 @@ impl Server {
                 );
             }
         }
+        all_timings
     }
 
     /// Broadcasts the tab list header/footer with current TPS and MSPT values.
@@ -341,10 +701,7 @@ impl Server {
             TextComponent::plain("\n"),
         ]);
 
-        // Broadcast to all players in all worlds
-        for world in &self.worlds {
-            world.broadcast_to_all_with(|player| CTabList::new(&header, &footer, player));
-        }
+        self.tab_list.set_header_footer(header, footer);
     }
 
     /// Broadcasts a sprint completion report to all players.
@@ -358,8 +715,13 @@ impl Server {
             ])
             .into();
 
+        self.broadcast_system_message(&message);
+    }
+
+    /// Sends a system chat message to every player in every world.
+    pub fn broadcast_system_message(&self, message: &TextComponent) {
         for world in &self.worlds {
-            world.broadcast_to_all_with(|player| CSystemChat::new(&message, false, player));
+            world.broadcast_to_all_with(|player| CSystemChat::new(message, false, player));
         }
     }
 
@@ -375,6 +737,13 @@ impl Server {
         }
     }
 
+    /// Broadcasts a team change to all clients.
+    pub fn broadcast_team_packet(&self, packet: CSetPlayerTeam) {
+        for world in &self.worlds {
+            world.broadcast_to_all(packet.clone());
+        }
+    }
+
     /// Broadcasts the current step tick count to all clients.
     /// This should be called whenever the step tick count changes.
     pub fn broadcast_ticking_step(&self) {
@@ -399,3 +768,29 @@ impl Server {
         player.connection.send_packet(step_packet);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_seed;
+
+    #[test]
+    fn parse_seed_uses_numbers_as_is() {
+        assert_eq!(
+            parse_seed("-4172144997902289642"),
+            Some(-4_172_144_997_902_289_642)
+        );
+        assert_eq!(parse_seed(" 42 "), Some(42));
+    }
+
+    #[test]
+    fn parse_seed_hashes_text_like_java() {
+        assert_eq!(parse_seed("hello"), Some(99_162_322));
+        assert_eq!(parse_seed("Steel seed"), Some(-138_268_730));
+    }
+
+    #[test]
+    fn parse_seed_empty_is_random() {
+        assert_eq!(parse_seed(""), None);
+        assert_eq!(parse_seed("   "), None);
+    }
+}
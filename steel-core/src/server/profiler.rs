@@ -0,0 +1,146 @@
+//! Per-phase tick timing used to diagnose lag.
+use std::time::Duration;
+
+use rustc_hash::FxHashMap;
+
+use crate::world::WorldTickTimings;
+
+/// Number of ticks kept in the rolling window.
+const PROFILER_WINDOW: usize = 100;
+
+/// Ticks slower than this count towards the lag warning.
+const SLOW_TICK_THRESHOLD: Duration = Duration::from_millis(50);
+
+/// Number of consecutive slow ticks before a warning is logged.
+const SLOW_TICKS_BEFORE_WARNING: u32 = 3;
+
+/// Phases of a world tick that are measured separately.
+///
+/// Durations are summed across all worlds.
+pub const PHASES: [&str; 8] = [
+    "players",
+    "entities",
+    "fluids",
+    "light",
+    "chunk_tickets",
+    "chunk_generation",
+    "chunk_ticks",
+    "block_changes",
+];
+
+#[derive(Clone, Copy, Default)]
+struct TickSample {
+    total: Duration,
+    phases: [Duration; PHASES.len()],
+}
+
+/// Summary of the recorded ticks.
+#[derive(Debug, Clone)]
+pub struct TickReport {
+    /// Average milliseconds per tick.
+    pub mspt_avg: f32,
+    /// Slowest tick in milliseconds.
+    pub mspt_max: f32,
+    /// Average time spent per phase, keyed by phase name.
+    pub breakdown: FxHashMap<&'static str, Duration>,
+}
+
+/// Records how long each tick and each of its phases took over the last
+/// [`PROFILER_WINDOW`] ticks.
+pub struct TickProfiler {
+    samples: Box<[TickSample; PROFILER_WINDOW]>,
+    /// Index the next sample is written to.
+    next: usize,
+    /// Number of valid samples (saturates at the window size).
+    len: usize,
+    consecutive_slow_ticks: u32,
+}
+
+impl TickProfiler {
+    /// Creates an empty profiler.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            samples: Box::new([TickSample::default(); PROFILER_WINDOW]),
+            next: 0,
+            len: 0,
+            consecutive_slow_ticks: 0,
+        }
+    }
+
+    /// Records a finished tick.
+    ///
+    /// Logs a warning once [`SLOW_TICKS_BEFORE_WARNING`] ticks in a row exceed 50ms.
+    pub fn record(&mut self, total: Duration, worlds: &[WorldTickTimings]) {
+        let mut phases = [Duration::ZERO; PHASES.len()];
+        for timings in worlds {
+            let cm = &timings.chunk_map;
+            phases[0] += timings.player_tick;
+            phases[1] += timings.entity_tick;
+            phases[2] += timings.fluid_tick;
+            phases[3] += timings.light_updates;
+            phases[4] += cm.ticket_updates + cm.holder_creation;
+            phases[5] += cm.schedule_generation + cm.run_generation + cm.process_unloads;
+            phases[6] += cm.collect_tickable + cm.tick_chunks;
+            phases[7] += cm.broadcast_changes;
+        }
+
+        self.samples[self.next] = TickSample { total, phases };
+        self.next = (self.next + 1) % PROFILER_WINDOW;
+        self.len = (self.len + 1).min(PROFILER_WINDOW);
+
+        if total > SLOW_TICK_THRESHOLD {
+            self.consecutive_slow_ticks += 1;
+            if self.consecutive_slow_ticks == SLOW_TICKS_BEFORE_WARNING {
+                log::warn!(
+                    "Server is lagging: {SLOW_TICKS_BEFORE_WARNING} consecutive ticks took over {}ms (last: {:.2}ms)",
+                    SLOW_TICK_THRESHOLD.as_millis(),
+                    total.as_secs_f32() * 1000.0,
+                );
+            }
+        } else {
+            self.consecutive_slow_ticks = 0;
+        }
+    }
+
+    /// Returns a report over the whole window.
+    #[must_use]
+    pub fn report(&self) -> TickReport {
+        self.report_last(PROFILER_WINDOW)
+    }
+
+    /// Returns a report over the last `ticks` recorded ticks.
+    #[must_use]
+    pub fn report_last(&self, ticks: usize) -> TickReport {
+        let count = ticks.min(self.len);
+        let mut total = Duration::ZERO;
+        let mut max = Duration::ZERO;
+        let mut phases = [Duration::ZERO; PHASES.len()];
+
+        for i in 1..=count {
+            let sample = &self.samples[(self.next + PROFILER_WINDOW - i) % PROFILER_WINDOW];
+            total += sample.total;
+            max = max.max(sample.total);
+            for (sum, phase) in phases.iter_mut().zip(sample.phases) {
+                *sum += phase;
+            }
+        }
+
+        let divisor = count.max(1) as u32;
+        TickReport {
+            mspt_avg: (total / divisor).as_secs_f32() * 1000.0,
+            mspt_max: max.as_secs_f32() * 1000.0,
+            breakdown: PHASES
+                .iter()
+                .zip(phases)
+                .map(|(name, sum)| (*name, sum / divisor))
+                .collect(),
+        }
+    }
+}
+
+impl Default for TickProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -13,11 +13,11 @@ use steel_protocol::{
 use steel_registry::{
     BANNER_PATTERN_REGISTRY, BIOMES_REGISTRY, BLOCKS_REGISTRY, CAT_VARIANT_REGISTRY,
     CHAT_TYPE_REGISTRY, CHICKEN_VARIANT_REGISTRY, COW_VARIANT_REGISTRY, DAMAGE_TYPE_REGISTRY,
-    DIALOG_REGISTRY, DIMENSION_TYPE_REGISTRY, FLUID_REGISTRY, FROG_VARIANT_REGISTRY,
-    INSTRUMENT_REGISTRY, ITEMS_REGISTRY, JUKEBOX_SONG_REGISTRY, PAINTING_VARIANT_REGISTRY,
-    PIG_VARIANT_REGISTRY, REGISTRY, Registry, TIMELINE_REGISTRY, TRIM_MATERIAL_REGISTRY,
-    TRIM_PATTERN_REGISTRY, WOLF_SOUND_VARIANT_REGISTRY, WOLF_VARIANT_REGISTRY,
-    ZOMBIE_NAUTILUS_VARIANT_REGISTRY,
+    DIALOG_REGISTRY, DIMENSION_TYPE_REGISTRY, ENCHANTMENT_REGISTRY, FLUID_REGISTRY,
+    FROG_VARIANT_REGISTRY, INSTRUMENT_REGISTRY, ITEMS_REGISTRY, JUKEBOX_SONG_REGISTRY,
+    PAINTING_VARIANT_REGISTRY, PIG_VARIANT_REGISTRY, REGISTRY, Registry, TIMELINE_REGISTRY,
+    TRIM_MATERIAL_REGISTRY, TRIM_PATTERN_REGISTRY, WOLF_SOUND_VARIANT_REGISTRY,
+    WOLF_VARIANT_REGISTRY, ZOMBIE_NAUTILUS_VARIANT_REGISTRY,
 };
 use steel_utils::Identifier;
 use steel_utils::codec::VarInt;
@@ -89,8 +89,7 @@ impl RegistryCache {
         add_registry!(BANNER_PATTERN_REGISTRY, banner_patterns);
         add_registry!(ZOMBIE_NAUTILUS_VARIANT_REGISTRY, zombie_nautilus_variants);
 
-        // TODO: Add enchantments when implemented in the registry
-        //add_registry!(Identifier::vanilla_static("enchantments"), enchantments);
+        add_registry!(ENCHANTMENT_REGISTRY, enchantments);
 
         add_registry!(JUKEBOX_SONG_REGISTRY, jukebox_songs);
         add_registry!(INSTRUMENT_REGISTRY, instruments);
@@ -167,6 +166,40 @@ impl RegistryCache {
 
         tags_by_registry.push((DIALOG_REGISTRY, dialog_tags));
 
+        // Build painting variant tags
+        let mut painting_variant_tags: Vec<(Identifier, Vec<VarInt>)> =
+            Vec::with_capacity(registry.painting_variants.tag_keys().count());
+        for tag_key in registry.painting_variants.tag_keys() {
+            let mut variant_ids =
+                Vec::with_capacity(registry.painting_variants.iter_tag(tag_key).count());
+
+            for variant in registry.painting_variants.iter_tag(tag_key) {
+                let variant_id = *registry.painting_variants.get_id(variant);
+                variant_ids.push(VarInt::from(variant_id as i32));
+            }
+
+            painting_variant_tags.push((tag_key.clone(), variant_ids));
+        }
+
+        tags_by_registry.push((PAINTING_VARIANT_REGISTRY, painting_variant_tags));
+
+        // Build banner pattern tags
+        let mut banner_pattern_tags: Vec<(Identifier, Vec<VarInt>)> =
+            Vec::with_capacity(registry.banner_patterns.tag_keys().count());
+        for tag_key in registry.banner_patterns.tag_keys() {
+            let mut pattern_ids =
+                Vec::with_capacity(registry.banner_patterns.iter_tag(tag_key).count());
+
+            for pattern in registry.banner_patterns.iter_tag(tag_key) {
+                let pattern_id = *registry.banner_patterns.get_id(pattern);
+                pattern_ids.push(VarInt::from(pattern_id as i32));
+            }
+
+            banner_pattern_tags.push((tag_key.clone(), pattern_ids));
+        }
+
+        tags_by_registry.push((BANNER_PATTERN_REGISTRY, banner_pattern_tags));
+
         // Build fluid tags
         let mut fluid_tags: Vec<(Identifier, Vec<VarInt>)> =
             Vec::with_capacity(registry.fluids.tag_keys().count());
@@ -189,7 +222,7 @@ impl RegistryCache {
 
 /// Compresses a packet.
 fn compress_packet<P: ClientPacket>(packet: P) -> Option<EncodedPacket> {
-    let compression_info = STEEL_CONFIG.compression;
+    let compression_info = STEEL_CONFIG.load().compression;
     let id = packet.get_id(ConnectionProtocol::Config);
 
     EncodedPacket::from_bare(packet, compression_info, ConnectionProtocol::Config)
@@ -0,0 +1,31 @@
+//! This module contains the queue of block operations that are spread over several ticks.
+use std::mem;
+
+use crate::world::World;
+
+/// A block operation that is too large to do in a single tick, like a big `/fill`.
+pub trait BlockTask: Send {
+    /// Processes the next part of the task, returning `false` once it is done.
+    fn tick(&mut self, world: &World) -> bool;
+}
+
+impl World {
+    /// Queues a task that is advanced once every tick until it is done.
+    pub fn queue_block_task(&self, task: Box<dyn BlockTask>) {
+        self.block_tasks.lock().push(task);
+    }
+
+    /// Advances every queued block task by one step.
+    pub(super) fn tick_block_tasks(&self) {
+        // Tasks are taken out of the queue while ticking, so they can queue new ones
+        let mut tasks = mem::take(&mut *self.block_tasks.lock());
+        if tasks.is_empty() {
+            return;
+        }
+        tasks.retain_mut(|task| task.tick(self));
+
+        let mut queue = self.block_tasks.lock();
+        tasks.append(&mut queue);
+        *queue = tasks;
+    }
+}
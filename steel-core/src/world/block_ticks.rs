@@ -0,0 +1,125 @@
+//! This module contains the block and fluid ticks that are scheduled to run
+//! after a delay.
+use std::ptr;
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_utils::BlockPos;
+use steel_utils::locks::SyncMutex;
+
+use crate::behavior::BLOCK_BEHAVIORS;
+use crate::world::World;
+
+/// A block tick waiting for its delay to run out.
+///
+/// Vanilla: `ScheduledTick`
+pub struct ScheduledTick {
+    /// The position of the block to tick.
+    pub pos: BlockPos,
+    /// The block the tick is for. The tick is skipped if the block changed.
+    pub block: BlockRef,
+    /// Ticks left until the tick runs.
+    delay: u32,
+}
+
+/// The block or fluid ticks scheduled in a world.
+///
+/// Vanilla: `LevelTicks`
+#[derive(Default)]
+pub struct LevelTicks {
+    ticks: Vec<ScheduledTick>,
+}
+
+impl LevelTicks {
+    /// Schedules a tick for `block` at `pos` in `delay` ticks. Does nothing
+    /// if one is already scheduled for that block there.
+    pub fn schedule(&mut self, pos: BlockPos, block: BlockRef, delay: u32) {
+        let scheduled = self
+            .ticks
+            .iter()
+            .any(|tick| tick.pos == pos && ptr::eq(tick.block, block));
+        if !scheduled {
+            self.ticks.push(ScheduledTick { pos, block, delay });
+        }
+    }
+
+    /// Counts every scheduled tick down by one and takes out the ones that are
+    /// due, in the order they were scheduled.
+    pub fn take_due(&mut self) -> Vec<ScheduledTick> {
+        let mut due = Vec::new();
+        self.ticks.retain_mut(|tick| {
+            tick.delay = tick.delay.saturating_sub(1);
+            if tick.delay > 0 {
+                return true;
+            }
+            due.push(ScheduledTick {
+                pos: tick.pos,
+                block: tick.block,
+                delay: 0,
+            });
+            false
+        });
+        due
+    }
+}
+
+impl World {
+    /// Schedules a tick for `block` at `pos` in `delay` ticks, unless one is
+    /// already scheduled. The block's behavior gets the tick if the block is
+    /// still there by then.
+    pub fn schedule_block_tick(&self, pos: BlockPos, block: BlockRef, delay: u32) {
+        self.block_ticks.lock().schedule(pos, block, delay);
+    }
+
+    /// Schedules a fluid tick for the fluid `block` at `pos` in `delay` ticks,
+    /// unless one is already scheduled. Fluid ticks run after the block ticks
+    /// of the same tick.
+    pub fn schedule_fluid_tick(&self, pos: BlockPos, block: BlockRef, delay: u32) {
+        self.fluid_ticks.lock().schedule(pos, block, delay);
+    }
+
+    /// Runs the scheduled block ticks that are due.
+    pub(super) fn tick_block_ticks(&self) {
+        self.run_due_ticks(&self.block_ticks);
+    }
+
+    /// Runs the scheduled fluid ticks that are due.
+    pub(super) fn tick_fluid_ticks(&self) {
+        self.run_due_ticks(&self.fluid_ticks);
+    }
+
+    fn run_due_ticks(&self, ticks: &SyncMutex<LevelTicks>) {
+        // Ticks are taken out first, so blocks can schedule new ones
+        let due = ticks.lock().take_due();
+        for tick in due {
+            let state = self.get_block_state(&tick.pos);
+            if !ptr::eq(state.get_block(), tick.block) {
+                continue;
+            }
+            BLOCK_BEHAVIORS
+                .get_behavior(tick.block)
+                .tick(state, self, tick.pos);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use steel_registry::vanilla_blocks;
+
+    use super::*;
+
+    #[test]
+    fn ticks_run_once_after_their_delay() {
+        let mut ticks = LevelTicks::default();
+        let pos = BlockPos::new(0, 64, 0);
+        ticks.schedule(pos, vanilla_blocks::SAND, 2);
+        ticks.schedule(pos, vanilla_blocks::SAND, 5);
+
+        assert!(ticks.take_due().is_empty());
+        let due = ticks.take_due();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].pos, pos);
+        assert!(ticks.take_due().is_empty());
+    }
+}
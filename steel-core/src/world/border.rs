@@ -0,0 +1,185 @@
+//! World border state.
+//!
+//! The border is a square around `center` that can grow or shrink over time.
+//! Clients draw it and collide with it on their own, so the server mostly keeps
+//! them in sync and deals with players that still end up outside.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use steel_protocol::packets::game::{CInitializeBorder, CSetBorderLerpSize};
+
+/// Largest diameter the border may have. Matches vanilla `WorldBorder.MAX_SIZE`.
+pub const MAX_SIZE: f64 = 5.999_997E7;
+
+/// Largest absolute center coordinate. Matches vanilla `WorldBorder.MAX_CENTER_COORDINATE`.
+pub const MAX_CENTER_COORDINATE: f64 = 2.999_998_4E7;
+
+/// Time that passes on the border each server tick.
+const TICK_DURATION: Duration = Duration::from_millis(50);
+
+/// The world border of a single world.
+///
+/// Based on Java's `WorldBorder`. Lerps advance with the server tick instead of
+/// wall clock time, so a frozen server also freezes a moving border.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldBorder {
+    /// Center of the border on the X/Z plane.
+    pub center: (f64, f64),
+    /// Current diameter of the border.
+    pub size: f64,
+    /// Size the border is moving towards and the time left until it gets there.
+    pub lerp_target: Option<(f64, Duration)>,
+    /// Size the current lerp started from.
+    lerp_from: f64,
+    /// Total duration of the current lerp.
+    lerp_duration: Duration,
+    /// Damage dealt per block a player is past the safe zone, per tick.
+    pub damage_per_block: f64,
+    /// Distance outside the border in which players take no damage.
+    pub safe_zone: f64,
+    /// Distance in blocks at which clients start showing the warning overlay.
+    pub warning_blocks: i32,
+    /// Seconds before a shrinking border arrives at which the warning starts.
+    pub warning_time: i32,
+    /// Largest distance from the origin the border edges may ever reach.
+    pub absolute_max_size: i32,
+}
+
+impl Default for WorldBorder {
+    fn default() -> Self {
+        Self {
+            center: (0.0, 0.0),
+            size: MAX_SIZE,
+            lerp_target: None,
+            lerp_from: MAX_SIZE,
+            lerp_duration: Duration::ZERO,
+            damage_per_block: 0.2,
+            safe_zone: 5.0,
+            warning_blocks: 5,
+            warning_time: 15,
+            absolute_max_size: 29_999_984,
+        }
+    }
+}
+
+impl WorldBorder {
+    /// Returns the size the border will end up at.
+    #[must_use]
+    pub fn target_size(&self) -> f64 {
+        self.lerp_target.map_or(self.size, |(target, _)| target)
+    }
+
+    /// Returns the time left on the current lerp in milliseconds, or 0 if the
+    /// border isn't moving.
+    #[must_use]
+    pub fn lerp_remaining_millis(&self) -> i64 {
+        self.lerp_target
+            .map_or(0, |(_, remaining)| remaining.as_millis() as i64)
+    }
+
+    /// Sets the size immediately, cancelling any lerp in progress.
+    pub fn set_size(&mut self, size: f64) {
+        self.size = size;
+        self.lerp_target = None;
+    }
+
+    /// Starts moving the border from its current size to `target` over `duration`.
+    pub fn lerp_to(&mut self, target: f64, duration: Duration) {
+        if duration.is_zero() {
+            self.set_size(target);
+            return;
+        }
+        self.lerp_from = self.size;
+        self.lerp_duration = duration;
+        self.lerp_target = Some((target, duration));
+    }
+
+    /// Advances the border by one tick.
+    ///
+    /// Returns true if the border is still moving afterwards.
+    pub fn tick(&mut self) -> bool {
+        let Some((target, remaining)) = self.lerp_target else {
+            return false;
+        };
+
+        let remaining = remaining.saturating_sub(TICK_DURATION);
+        if remaining.is_zero() {
+            self.set_size(target);
+            return false;
+        }
+
+        let progress = 1.0 - remaining.as_secs_f64() / self.lerp_duration.as_secs_f64();
+        self.size = self.lerp_from + (target - self.lerp_from) * progress;
+        self.lerp_target = Some((target, remaining));
+        true
+    }
+
+    /// Returns the lowest X coordinate inside the border.
+    #[must_use]
+    pub fn min_x(&self) -> f64 {
+        (self.center.0 - self.size / 2.0).max(-f64::from(self.absolute_max_size))
+    }
+
+    /// Returns the highest X coordinate inside the border.
+    #[must_use]
+    pub fn max_x(&self) -> f64 {
+        (self.center.0 + self.size / 2.0).min(f64::from(self.absolute_max_size))
+    }
+
+    /// Returns the lowest Z coordinate inside the border.
+    #[must_use]
+    pub fn min_z(&self) -> f64 {
+        (self.center.1 - self.size / 2.0).max(-f64::from(self.absolute_max_size))
+    }
+
+    /// Returns the highest Z coordinate inside the border.
+    #[must_use]
+    pub fn max_z(&self) -> f64 {
+        (self.center.1 + self.size / 2.0).min(f64::from(self.absolute_max_size))
+    }
+
+    /// Returns the distance from the given position to the closest border edge.
+    ///
+    /// The result is negative when the position is outside the border.
+    #[must_use]
+    pub fn distance_to_border(&self, x: f64, z: f64) -> f64 {
+        let to_min_x = x - self.min_x();
+        let to_max_x = self.max_x() - x;
+        let to_min_z = z - self.min_z();
+        let to_max_z = self.max_z() - z;
+        to_min_x.min(to_max_x).min(to_min_z.min(to_max_z))
+    }
+
+    /// Returns true if the given position is inside the border.
+    #[must_use]
+    pub fn is_within_bounds(&self, x: f64, z: f64) -> bool {
+        x >= self.min_x() && x < self.max_x() && z >= self.min_z() && z < self.max_z()
+    }
+
+    /// Builds the packet that sends the whole border state to a client.
+    #[must_use]
+    pub fn initialize_packet(&self) -> CInitializeBorder {
+        CInitializeBorder {
+            center_x: self.center.0,
+            center_z: self.center.1,
+            old_size: self.size,
+            new_size: self.target_size(),
+            lerp_time: self.lerp_remaining_millis(),
+            absolute_max_size: self.absolute_max_size,
+            warning_blocks: self.warning_blocks,
+            warning_time: self.warning_time,
+        }
+    }
+
+    /// Builds the packet that starts or resynchronizes the current lerp.
+    #[must_use]
+    pub fn lerp_packet(&self) -> CSetBorderLerpSize {
+        CSetBorderLerpSize {
+            old_size: self.size,
+            new_size: self.target_size(),
+            lerp_time: self.lerp_remaining_millis(),
+        }
+    }
+}
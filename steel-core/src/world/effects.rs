@@ -0,0 +1,269 @@
+//! This module contains the implementation of the world's sound and effect broadcasting.
+use steel_protocol::packet_traits::{ClientPacket, EncodedPacket};
+use steel_protocol::packets::game::{CBlockEvent, CLevelEvent, CSound, SoundEvent, SoundSource};
+use steel_protocol::utils::ConnectionProtocol;
+use steel_registry::blocks::BlockRef;
+use steel_registry::{REGISTRY, level_events};
+use steel_utils::math::Vector3;
+use steel_utils::{BlockPos, ChunkPos, Identifier, SectionPos};
+
+use crate::config::STEEL_CONFIG;
+use crate::world::World;
+
+/// How far away level events and block events are sent.
+const EVENT_RANGE: f64 = 64.0;
+
+/// How far away a sound played at `volume` can be heard.
+///
+/// Vanilla: `SoundEvent.getRange`
+#[must_use]
+pub fn sound_range(volume: f32) -> f64 {
+    if volume > 1.0 {
+        16.0 * f64::from(volume)
+    } else {
+        16.0
+    }
+}
+
+/// Returns whether a listener is within `range` of an effect.
+fn is_in_range(effect: Vector3<f64>, listener: Vector3<f64>, range: f64) -> bool {
+    effect.squared_distance_to_vec(listener) <= range * range
+}
+
+fn block_center(pos: BlockPos) -> Vector3<f64> {
+    Vector3::new(
+        f64::from(pos.x()) + 0.5,
+        f64::from(pos.y()) + 0.5,
+        f64::from(pos.z()) + 0.5,
+    )
+}
+
+impl World {
+    /// Sends a packet to the players within `range` of `center`.
+    ///
+    /// Only players tracking the chunk at `center` are considered, which
+    /// covers every player close enough to see or hear an effect there.
+    fn broadcast_in_range<P: ClientPacket>(
+        &self,
+        center: Vector3<f64>,
+        range: f64,
+        packet: P,
+        exclude: Option<i32>,
+    ) {
+        let chunk = ChunkPos::new(
+            SectionPos::block_to_section_coord(center.x.floor() as i32),
+            SectionPos::block_to_section_coord(center.z.floor() as i32),
+        );
+        let Ok(encoded) = EncodedPacket::from_bare(
+            packet,
+            STEEL_CONFIG.load().compression,
+            ConnectionProtocol::Play,
+        ) else {
+            log::warn!("Failed to encode effect packet");
+            return;
+        };
+
+        for entity_id in self.player_area_map.get_tracking_players(chunk) {
+            // Skip excluded player (they play the effect client-side)
+            if exclude == Some(entity_id) {
+                continue;
+            }
+            if let Some(player) = self.players.get_by_entity_id(entity_id)
+                && is_in_range(center, *player.position.lock(), range)
+            {
+                player.connection.send_encoded_packet(encoded.clone());
+            }
+        }
+    }
+
+    /// Broadcasts a level event to nearby players within 64 blocks.
+    ///
+    /// Level events trigger sounds, particles, and animations on the client.
+    /// See `steel_registry::level_events` for available event type constants.
+    ///
+    /// # Arguments
+    /// * `event_type` - The event type ID from `steel_registry::level_events`
+    /// * `pos` - The position where the event occurs
+    /// * `data` - Event-specific data (e.g., block state ID for block destruction)
+    /// * `exclude` - Optional entity ID to exclude from receiving the event
+    pub fn level_event(&self, event_type: i32, pos: BlockPos, data: i32, exclude: Option<i32>) {
+        let packet = CLevelEvent::new(event_type, pos, data, false);
+        self.broadcast_in_range(block_center(pos), EVENT_RANGE, packet, exclude);
+    }
+
+    /// Broadcasts a global level event to all players in the world.
+    ///
+    /// Unlike `level_event`, this sends the event to all players regardless of distance.
+    /// Used for events like the ender dragon death or wither spawn.
+    ///
+    /// # Arguments
+    /// * `event_type` - The event type ID from `steel_registry::level_events`
+    /// * `pos` - The position where the event occurs
+    /// * `data` - Event-specific data
+    pub fn global_level_event(&self, event_type: i32, pos: BlockPos, data: i32) {
+        let packet = CLevelEvent::new(event_type, pos, data, true);
+        self.players.iter_players(|_, player| {
+            player.connection.send_packet(packet.clone());
+            true
+        });
+    }
+
+    /// Broadcasts block destruction particles and sound for a destroyed block.
+    ///
+    /// This is a convenience method that sends the `PARTICLES_DESTROY_BLOCK` level event.
+    ///
+    /// # Arguments
+    /// * `pos` - The position of the destroyed block
+    /// * `block_state_id` - The block state ID of the destroyed block
+    /// * `exclude` - Optional entity ID to exclude from receiving the event
+    pub fn destroy_block_effect(&self, pos: BlockPos, block_state_id: u32, exclude: Option<i32>) {
+        self.level_event(
+            level_events::PARTICLES_DESTROY_BLOCK,
+            pos,
+            block_state_id as i32,
+            exclude,
+        );
+    }
+
+    /// Broadcasts a block event to nearby players within 64 blocks.
+    ///
+    /// Block events are used for special block behaviors like pistons, note blocks,
+    /// chests, and bells. Each block type interprets the parameters differently.
+    ///
+    /// # Arguments
+    /// * `pos` - The position of the block
+    /// * `block` - The block reference
+    /// * `action_id` - The action ID (block-specific meaning)
+    /// * `action_param` - The action parameter (block-specific meaning)
+    pub fn block_event(&self, pos: BlockPos, block: BlockRef, action_id: u8, action_param: u8) {
+        let block_id = *REGISTRY.blocks.get_id(block) as i32;
+        let packet = CBlockEvent::new(pos, action_id, action_param, block_id);
+        self.broadcast_in_range(block_center(pos), EVENT_RANGE, packet, None);
+    }
+
+    /// Plays a sound at a specific position, broadcasting to nearby players.
+    ///
+    /// The sound is sent to all players within hearing range of the position
+    /// (16 blocks, or more for volumes above 1), except for the excluded player
+    /// (if any). The excluded player is typically the one who triggered the
+    /// sound, as they hear it client-side.
+    ///
+    /// # Arguments
+    /// * `sound_id` - The sound event registry ID (from `steel_registry::sound_events`)
+    /// * `source` - The sound source category
+    /// * `pos` - The block position (sound plays at center of block)
+    /// * `volume` - Volume multiplier (1.0 = normal)
+    /// * `pitch` - Pitch multiplier (1.0 = normal)
+    /// * `exclude` - Optional entity ID to exclude from receiving the sound
+    pub fn play_sound(
+        &self,
+        sound_id: i32,
+        source: SoundSource,
+        pos: BlockPos,
+        volume: f32,
+        pitch: f32,
+        exclude: Option<i32>,
+    ) {
+        self.broadcast_sound(
+            SoundEvent::Registered(sound_id),
+            source,
+            block_center(pos),
+            volume,
+            pitch,
+            exclude,
+        );
+    }
+
+    /// Plays a sound by name at a specific position, broadcasting to nearby players.
+    ///
+    /// Unlike [`Self::play_sound`], the sound doesn't have to be registered, so
+    /// this can play sounds added by resource packs.
+    ///
+    /// # Arguments
+    /// * `sound` - The sound's location, like `minecraft:block.chest.open`
+    /// * `source` - The sound source category
+    /// * `pos` - The block position (sound plays at center of block)
+    /// * `volume` - Volume multiplier (1.0 = normal)
+    /// * `pitch` - Pitch multiplier (1.0 = normal)
+    /// * `exclude` - Optional entity ID to exclude from receiving the sound
+    pub fn play_custom_sound(
+        &self,
+        sound: &Identifier,
+        source: SoundSource,
+        pos: BlockPos,
+        volume: f32,
+        pitch: f32,
+        exclude: Option<i32>,
+    ) {
+        let sound = SoundEvent::Direct {
+            location: sound.clone(),
+            fixed_range: None,
+        };
+        self.broadcast_sound(sound, source, block_center(pos), volume, pitch, exclude);
+    }
+
+    /// Plays a block sound at a specific position.
+    ///
+    /// Convenience method that uses the BLOCKS sound source and applies
+    /// the sound type's volume and pitch modifiers.
+    ///
+    /// # Arguments
+    /// * `sound_id` - The sound event registry ID
+    /// * `pos` - The block position
+    /// * `volume` - Base volume (typically from `SoundType`)
+    /// * `pitch` - Base pitch (typically from `SoundType`)
+    /// * `exclude` - Optional entity ID to exclude from receiving the sound
+    pub fn play_block_sound(
+        &self,
+        sound_id: i32,
+        pos: BlockPos,
+        volume: f32,
+        pitch: f32,
+        exclude: Option<i32>,
+    ) {
+        self.play_sound(sound_id, SoundSource::Blocks, pos, volume, pitch, exclude);
+    }
+
+    fn broadcast_sound(
+        &self,
+        sound: SoundEvent,
+        source: SoundSource,
+        pos: Vector3<f64>,
+        volume: f32,
+        pitch: f32,
+        exclude: Option<i32>,
+    ) {
+        let range = match &sound {
+            SoundEvent::Direct {
+                fixed_range: Some(range),
+                ..
+            } => f64::from(*range),
+            _ => sound_range(volume),
+        };
+        // Generate a random seed for sound variations
+        let seed = rand::random::<i64>();
+        let packet = CSound::new(sound, source, pos.x, pos.y, pos.z, volume, pitch, seed);
+        self.broadcast_in_range(pos, range, packet, exclude);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chest_open_sound_reaches_nearby_players_only() {
+        // ChestBlock plays its open sound at half volume
+        let range = sound_range(0.5);
+        let chest = block_center(BlockPos::new(0, 64, 0));
+
+        assert!(is_in_range(chest, Vector3::new(8.0, 65.0, 3.0), range));
+        assert!(!is_in_range(chest, Vector3::new(100.5, 64.0, 0.5), range));
+    }
+
+    #[test]
+    fn loud_sounds_carry_further() {
+        assert!((sound_range(0.2) - 16.0).abs() < f64::EPSILON);
+        assert!((sound_range(4.0) - 64.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,419 @@
+//! Explosions, matching vanilla's `ServerExplosion`.
+//!
+//! An explosion sends rays out in every direction that lose power as they
+//! pass through blocks, blowing up every block they still have power for.
+//! Entities and players in range are hurt and pushed away, less so the
+//! further away and the more hidden from the blast they are.
+
+use rustc_hash::FxHashSet;
+use steel_protocol::packets::game::SoundSource;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::game_rules::GameRuleValue;
+use steel_registry::vanilla_game_rules::{ENTITY_DROPS, MOB_GRIEFING};
+use steel_registry::{REGISTRY, sound_events, vanilla_blocks};
+use steel_utils::math::Vector3;
+use steel_utils::types::{GameType, UpdateFlags};
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::BLOCK_BEHAVIORS;
+use crate::entity::mob;
+use crate::world::World;
+use crate::world::vibrations::VibrationEvent;
+
+/// How far a ray travels between two blocks it checks.
+const RAY_STEP: f64 = 0.3;
+/// How much power a ray loses each step, on top of what blocks absorb.
+const RAY_DECAY: f32 = 0.225_000_01;
+/// How much fluids resist explosions, also inside waterlogged blocks.
+const FLUID_RESISTANCE: f32 = 100.0;
+
+/// What set an explosion off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplosionSource {
+    /// Lit TNT.
+    Tnt,
+    /// A creeper blowing up, twice as powerful when it was charged by
+    /// lightning.
+    Creeper {
+        /// Whether the creeper was struck by lightning.
+        charged: bool,
+    },
+    /// A ghast's fireball landing, which sets the blown up blocks on fire.
+    Fireball,
+}
+
+impl ExplosionSource {
+    /// Returns how powerful explosions from this source are.
+    ///
+    /// Vanilla: `PrimedTnt.explode` and `Creeper.explodeCreeper`
+    #[must_use]
+    pub const fn default_power(self) -> f32 {
+        match self {
+            Self::Tnt => 4.0,
+            Self::Creeper { charged: false } => 3.0,
+            Self::Creeper { charged: true } => 6.0,
+            Self::Fireball => 1.0,
+        }
+    }
+
+    /// Returns true if the explosion may destroy blocks. Mobs only do so when
+    /// mob griefing is on.
+    fn destroys_blocks(self, world: &World) -> bool {
+        match self {
+            Self::Tnt => true,
+            Self::Creeper { .. } | Self::Fireball => {
+                world.get_game_rule(MOB_GRIEFING) == GameRuleValue::Bool(true)
+            }
+        }
+    }
+
+    /// Returns true if the explosion sets blocks on fire.
+    ///
+    /// Vanilla: `LargeFireball.onHit`
+    fn creates_fire(self, world: &World) -> bool {
+        matches!(self, Self::Fireball) && self.destroys_blocks(world)
+    }
+}
+
+/// An explosion about to go off.
+#[derive(Debug, Clone, Copy)]
+pub struct Explosion {
+    /// Where the explosion comes from.
+    pub center: Vector3<f64>,
+    /// How powerful the explosion is. Entities are pushed up to twice as
+    /// many blocks away.
+    pub power: f32,
+    /// What set the explosion off.
+    pub source: ExplosionSource,
+}
+
+impl Explosion {
+    /// Creates an explosion of `power` at `center`.
+    #[must_use]
+    pub const fn new(center: Vector3<f64>, power: f32, source: ExplosionSource) -> Self {
+        Self {
+            center,
+            power,
+            source,
+        }
+    }
+
+    /// Sets the explosion off, hurting and pushing away entities and
+    /// blowing up the blocks around it.
+    ///
+    /// Vanilla: `ServerExplosion.explode`
+    pub fn explode(&self, world: &World) {
+        let center_pos = block_pos_at(self.center);
+        // TODO: Send the explode packet instead, so clients show the particles
+        // and get knocked back
+        let pitch = (1.0 + (rand::random::<f32>() - rand::random::<f32>()) * 0.2) * 0.7;
+        world.play_sound(
+            sound_events::ENTITY_GENERIC_EXPLODE,
+            SoundSource::Blocks,
+            center_pos,
+            4.0,
+            pitch,
+            None,
+        );
+
+        let breaks_blocks = self.source.destroys_blocks(world);
+        let positions = if breaks_blocks {
+            self.exploded_positions(world)
+        } else {
+            Vec::new()
+        };
+        self.hurt_entities(world, breaks_blocks);
+        world.post_vibration(VibrationEvent::Explode, self.center);
+        destroy_blocks(world, &positions);
+        if self.source.creates_fire(world) {
+            create_fires(world, &positions);
+        }
+    }
+
+    /// Casts rays from the center and returns the blocks they blow up.
+    ///
+    /// Vanilla: `ServerExplosion.calculateExplodedPositions`
+    fn exploded_positions(&self, world: &World) -> Vec<BlockPos> {
+        let mut positions = FxHashSet::default();
+        for direction in ray_directions() {
+            let mut power = self.power * (0.7 + rand::random::<f32>() * 0.6);
+            let mut point = self.center;
+            while power > 0.0 {
+                let pos = block_pos_at(point);
+                if !world.is_in_valid_bounds(&pos) {
+                    break;
+                }
+                let state = world.get_block_state(&pos);
+                if let Some(resistance) = explosion_resistance(state) {
+                    power -= (resistance + 0.3) * 0.3;
+                }
+                if power > 0.0 && !state.is_air() {
+                    positions.insert(pos);
+                }
+                point += direction * RAY_STEP;
+                power -= RAY_DECAY;
+            }
+        }
+        positions.into_iter().collect()
+    }
+
+    /// Hurts the entities and players in range and pushes them away from
+    /// the center, less so the further away and the more hidden from the
+    /// blast they are. Block-like entities, like paintings, are only hit
+    /// when the explosion `breaks_blocks`.
+    ///
+    /// Vanilla: `ServerExplosion.hurtEntities`
+    fn hurt_entities(&self, world: &World, breaks_blocks: bool) {
+        let mut remains = Vec::new();
+        for list in world.entities.lists() {
+            list.explode(world, self, breaks_blocks, &mut remains);
+        }
+        for remainder in remains {
+            world.drop_passengers(&remainder.passengers);
+            if world.get_game_rule(ENTITY_DROPS) != GameRuleValue::Bool(true) {
+                continue;
+            }
+            for item in remainder.drops {
+                world.drop_item_stack(remainder.pos, item);
+            }
+        }
+
+        let difficulty = world.difficulty();
+        world.players.iter_players(|_, player| {
+            if player.game_mode.load() == GameType::Spectator {
+                return true;
+            }
+            let position = *player.position.lock();
+            let push_y = position.y + player.eye_height();
+            let Some(hit) = self.hit(world, position, push_y, &player.bounding_box()) else {
+                return true;
+            };
+            if let Some(damage) = mob::player_damage(hit.damage, difficulty) {
+                player.take_damage(damage);
+            }
+            // Creative players flying around aren't knocked back
+            if !(player.has_infinite_materials() && player.is_flying()) {
+                player.push(hit.impulse);
+            }
+            true
+        });
+    }
+
+    /// Returns how hard the explosion hits an entity at `position` taking up
+    /// `bounding_box`, pushed away from `push_y` up, or `None` if it is out
+    /// of range.
+    ///
+    /// Vanilla: `ServerExplosion.hurtEntities`, for a single entity
+    #[must_use]
+    pub fn hit(
+        &self,
+        world: &World,
+        position: Vector3<f64>,
+        push_y: f64,
+        bounding_box: &AABBd,
+    ) -> Option<ExplosionHit> {
+        let diameter = f64::from(self.power) * 2.0;
+        let distance = position.squared_distance_to_vec(self.center).sqrt() / diameter;
+        if distance > 1.0 {
+            return None;
+        }
+        let away = Vector3::new(
+            position.x - self.center.x,
+            push_y - self.center.y,
+            position.z - self.center.z,
+        );
+        let length = away.length();
+        if length == 0.0 {
+            return None;
+        }
+
+        let impact = (1.0 - distance) * seen_percent(world, self.center, bounding_box);
+        #[allow(clippy::cast_possible_truncation)]
+        let damage = ((impact * impact + impact) / 2.0 * 7.0 * diameter + 1.0) as f32;
+        Some(ExplosionHit {
+            damage,
+            impulse: away * (impact / length),
+        })
+    }
+}
+
+/// How hard an explosion hits an entity.
+#[derive(Debug, Clone, Copy)]
+pub struct ExplosionHit {
+    /// The harm the entity takes.
+    pub damage: f32,
+    /// How far the entity is pushed away from the center.
+    pub impulse: Vector3<f64>,
+}
+
+/// Replaces the blown up blocks with air, letting blocks like TNT react.
+///
+/// Vanilla: `ServerExplosion.interactWithBlocks`
+fn destroy_blocks(world: &World, positions: &[BlockPos]) {
+    let air = REGISTRY.blocks.get_base_state_id(vanilla_blocks::AIR);
+    for &pos in positions {
+        let state = world.get_block_state(&pos);
+        if state.is_air() {
+            continue;
+        }
+        // TODO: Drop the block's loot once loot tables are implemented.
+        // Creeper explosions keep each item with a chance of 1 / power.
+        world.set_block(pos, air, UpdateFlags::UPDATE_ALL);
+        BLOCK_BEHAVIORS
+            .get_behavior(state.get_block())
+            .was_exploded(state, world, pos);
+    }
+}
+
+/// Sets a third of the blown up blocks on fire, where there is a solid block
+/// below to burn on.
+///
+/// Vanilla: `ServerExplosion.createFire`
+fn create_fires(world: &World, positions: &[BlockPos]) {
+    let fire = vanilla_blocks::FIRE.default_state();
+    for &pos in positions {
+        if rand::random_range(0..3) == 0
+            && world.get_block_state(&pos).is_air()
+            && world.get_block_state(&pos.offset(0, -1, 0)).is_solid()
+        {
+            world.set_block(pos, fire, UpdateFlags::UPDATE_ALL);
+        }
+    }
+}
+
+/// Returns the directions of the rays an explosion casts, pointing at the
+/// faces of a 16 by 16 by 16 grid around the center.
+fn ray_directions() -> impl Iterator<Item = Vector3<f64>> {
+    let on_face = |i: i32| i == 0 || i == 15;
+    (0..16)
+        .flat_map(|x| (0..16).flat_map(move |y| (0..16).map(move |z| (x, y, z))))
+        .filter(move |&(x, y, z)| on_face(x) || on_face(y) || on_face(z))
+        .map(|(x, y, z)| {
+            let axis = |i: i32| f64::from(i) / 15.0 * 2.0 - 1.0;
+            Vector3::new(axis(x), axis(y), axis(z)).normalize()
+        })
+}
+
+/// Returns how much power a ray loses to `state`, or `None` for air.
+///
+/// Vanilla: `ExplosionDamageCalculator.getBlockExplosionResistance`
+fn explosion_resistance(state: BlockStateId) -> Option<f32> {
+    if state.is_air() {
+        return None;
+    }
+    let resistance = state.get_block().config.explosion_resistance;
+    let waterlogged = state
+        .try_get_value(&BlockStateProperties::WATERLOGGED)
+        .unwrap_or(false);
+    Some(if waterlogged {
+        resistance.max(FLUID_RESISTANCE)
+    } else {
+        resistance
+    })
+}
+
+/// Returns the share of points spread over `bounding_box` that can see
+/// `center` without a block in the way.
+///
+/// Vanilla: `ServerExplosion.getSeenPercent`
+fn seen_percent(world: &World, center: Vector3<f64>, bounding_box: &AABBd) -> f64 {
+    let step = |size: f64| 1.0 / (size * 2.0 + 1.0);
+    let step_x = step(bounding_box.max_x - bounding_box.min_x);
+    let step_y = step(bounding_box.max_y - bounding_box.min_y);
+    let step_z = step(bounding_box.max_z - bounding_box.min_z);
+    // Centers the points that don't evenly fill the box
+    let offset_x = (1.0 - (1.0 / step_x).floor() * step_x) / 2.0;
+    let offset_z = (1.0 - (1.0 / step_z).floor() * step_z) / 2.0;
+
+    let lerp = |t: f64, min: f64, max: f64| min + t * (max - min);
+    let mut visible = 0_u32;
+    let mut total = 0_u32;
+    let mut x = 0.0;
+    while x <= 1.0 {
+        let mut y = 0.0;
+        while y <= 1.0 {
+            let mut z = 0.0;
+            while z <= 1.0 {
+                let point = Vector3::new(
+                    lerp(x, bounding_box.min_x, bounding_box.max_x) + offset_x,
+                    lerp(y, bounding_box.min_y, bounding_box.max_y),
+                    lerp(z, bounding_box.min_z, bounding_box.max_z) + offset_z,
+                );
+                if !is_blocked(world, point, center) {
+                    visible += 1;
+                }
+                total += 1;
+                z += step_z;
+            }
+            y += step_y;
+        }
+        x += step_x;
+    }
+
+    if total == 0 {
+        0.0
+    } else {
+        f64::from(visible) / f64::from(total)
+    }
+}
+
+/// Returns true if a block's collision shape lies between `from` and `to`.
+///
+/// Walks the line in tenth of a block steps, which can miss shapes thinner
+/// than that, like carpets.
+fn is_blocked(world: &World, from: Vector3<f64>, to: Vector3<f64>) -> bool {
+    let line = Vector3::new(to.x - from.x, to.y - from.y, to.z - from.z);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let steps = (line.length() * 10.0).ceil() as u32;
+    (0..=steps).any(|i| {
+        let point = from + line * (f64::from(i) / f64::from(steps.max(1)));
+        let pos = block_pos_at(point);
+        #[allow(clippy::cast_possible_truncation)]
+        let local = (
+            (point.x - f64::from(pos.x())) as f32,
+            (point.y - f64::from(pos.y())) as f32,
+            (point.z - f64::from(pos.z())) as f32,
+        );
+        world
+            .get_block_state(&pos)
+            .get_collision_shape()
+            .iter()
+            .any(|shape| shape.contains(local.0, local.1, local.2))
+    })
+}
+
+/// Returns the block containing `position`.
+#[allow(clippy::cast_possible_truncation)]
+fn block_pos_at(position: Vector3<f64>) -> BlockPos {
+    BlockPos::new(
+        position.x.floor() as i32,
+        position.y.floor() as i32,
+        position.z.floor() as i32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rays_cover_the_grid_faces() {
+        let directions: Vec<_> = ray_directions().collect();
+        assert_eq!(directions.len(), 16 * 16 * 16 - 14 * 14 * 14);
+        assert!(
+            directions
+                .iter()
+                .all(|direction| (direction.length() - 1.0).abs() < 1e-9)
+        );
+    }
+
+    #[test]
+    fn charged_creepers_blast_harder() {
+        assert!((ExplosionSource::Tnt.default_power() - 4.0).abs() < f32::EPSILON);
+        let charged = ExplosionSource::Creeper { charged: true }.default_power();
+        let normal = ExplosionSource::Creeper { charged: false }.default_power();
+        assert!((charged - normal * 2.0).abs() < f32::EPSILON);
+    }
+}
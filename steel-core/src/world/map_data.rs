@@ -0,0 +1,299 @@
+//! Map contents, shared by every filled map with the same id.
+//!
+//! Vanilla keeps these in the overworld's saved data as `map_<id>.dat`. They
+//! only live in memory here so far, and rendering is a stub that shades the
+//! terrain height without looking at the blocks' map colors.
+
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+use rustc_hash::FxHashMap;
+use steel_protocol::packets::game::{CMapItemData, MapPatch};
+use steel_registry::data_components::vanilla_components::{
+    MAP_ID, MAP_POST_PROCESSING, MapPostProcessing,
+};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_items::ITEMS;
+use steel_utils::Identifier;
+use steel_utils::locks::SyncMutex;
+
+use crate::world::World;
+
+/// The width and height of a map in pixels.
+pub const MAP_SIZE: usize = 128;
+
+/// The furthest a map can be zoomed out.
+pub const MAX_SCALE: u8 = 4;
+
+/// The color every rendered pixel gets until blocks know their map colors.
+///
+/// Vanilla: `MapColor.GRASS`
+const TERRAIN_COLOR: u8 = 1;
+
+/// All maps on the server by id.
+pub static MAP_ITEM_DATA: LazyLock<MapItemDataRegistry> =
+    LazyLock::new(MapItemDataRegistry::default);
+
+/// The contents of a map.
+///
+/// Based on Java's `MapItemSavedData`.
+#[derive(Clone)]
+pub struct MapItemData {
+    /// The block x coordinate the map is centered on.
+    pub center_x: i32,
+    /// The block z coordinate the map is centered on.
+    pub center_z: i32,
+    /// The zoom level, each one doubles the blocks per pixel.
+    pub scale: u8,
+    /// The dimension the map shows.
+    pub dimension: Identifier,
+    /// Whether the map stopped updating after being locked.
+    pub locked: bool,
+    /// The packed colors, row by row. 0 means unexplored.
+    colors: Box<[u8]>,
+    /// Bumped on every color change, so viewers can tell they are behind.
+    version: u32,
+}
+
+impl MapItemData {
+    /// Creates an empty map covering the grid cell around `x` and `z`.
+    ///
+    /// Based on Java's `MapItemSavedData::createFresh`.
+    #[must_use]
+    pub fn new(x: i32, z: i32, scale: u8, dimension: Identifier) -> Self {
+        let scale = scale.min(MAX_SCALE);
+        Self {
+            center_x: Self::grid_center(x, scale),
+            center_z: Self::grid_center(z, scale),
+            scale,
+            dimension,
+            locked: false,
+            colors: vec![0; MAP_SIZE * MAP_SIZE].into_boxed_slice(),
+            version: 0,
+        }
+    }
+
+    /// Snaps a coordinate to the center of the map grid cell it is in, so
+    /// maps of the same scale line up next to each other.
+    fn grid_center(coordinate: i32, scale: u8) -> i32 {
+        let size = (MAP_SIZE as i32) << scale;
+        let cell = (coordinate + MAP_SIZE as i32 / 2).div_euclid(size);
+        cell * size + size / 2 - MAP_SIZE as i32 / 2
+    }
+
+    /// Returns an empty map one zoom level further out, covering this one.
+    ///
+    /// Based on Java's `MapItemSavedData::scaled`.
+    #[must_use]
+    pub fn scaled(&self) -> Self {
+        Self::new(
+            self.center_x,
+            self.center_z,
+            self.scale + 1,
+            self.dimension.clone(),
+        )
+    }
+
+    /// Returns a copy of this map that no longer updates.
+    ///
+    /// Based on Java's `MapItemSavedData::locked`.
+    #[must_use]
+    pub fn locked(&self) -> Self {
+        Self {
+            locked: true,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the version of the colors, bumped whenever one changes.
+    #[must_use]
+    pub const fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Gets the packed color of a pixel.
+    #[must_use]
+    pub fn color(&self, x: usize, y: usize) -> u8 {
+        self.colors[y * MAP_SIZE + x]
+    }
+
+    /// Sets the packed color of a pixel.
+    pub fn set_color(&mut self, x: usize, y: usize, color: u8) {
+        let pixel = &mut self.colors[y * MAP_SIZE + x];
+        if *pixel != color {
+            *pixel = color;
+            self.version = self.version.wrapping_add(1);
+        }
+    }
+
+    /// Redraws a sixteenth of the pixel columns near a player holding the
+    /// map, a different sixteenth each tick like vanilla.
+    ///
+    /// Based on Java's `MapItem::update`, but every pixel gets the same
+    /// color and only the height differences shade it.
+    pub fn update(&mut self, world: &World, x: f64, z: f64, tick: i32) {
+        if self.locked || world.dimension.key != self.dimension {
+            return;
+        }
+
+        let blocks_per_pixel = 1i32 << self.scale;
+        let player_x = (x.floor() as i32 - self.center_x) / blocks_per_pixel + 64;
+        let player_z = (z.floor() as i32 - self.center_z) / blocks_per_pixel + 64;
+        let radius = MAP_SIZE as i32 / blocks_per_pixel;
+
+        let (center_x, center_z) = (self.center_x, self.center_z);
+        let sample = |pixel_x: i32, pixel_z: i32| {
+            world.motion_blocking_height(
+                center_x + (pixel_x - 64) * blocks_per_pixel,
+                center_z + (pixel_z - 64) * blocks_per_pixel,
+            )
+        };
+
+        let first_z = (player_z - radius).max(0);
+        let last_z = (player_z + radius).min(MAP_SIZE as i32 - 1);
+        for pixel_x in (player_x - radius).max(0)..=(player_x + radius).min(MAP_SIZE as i32 - 1) {
+            if pixel_x & 15 != tick & 15 {
+                continue;
+            }
+            let mut previous = sample(pixel_x, first_z - 1);
+            for pixel_z in first_z..=last_z {
+                let dx = pixel_x - player_x;
+                let dz = pixel_z - player_z;
+                let height = sample(pixel_x, pixel_z);
+                if dx * dx + dz * dz < radius * radius
+                    && let Some(height) = height
+                {
+                    let brightness = brightness(
+                        height - previous.unwrap_or(height),
+                        self.scale,
+                        pixel_x + pixel_z,
+                    );
+                    self.set_color(
+                        pixel_x as usize,
+                        pixel_z as usize,
+                        TERRAIN_COLOR * 4 + brightness,
+                    );
+                }
+                previous = height;
+            }
+        }
+    }
+
+    /// Creates the packet that sends all of this map to a client.
+    ///
+    /// Vanilla tracks the changed rectangle per viewer and only sends that.
+    #[must_use]
+    pub fn update_packet(&self, map_id: i32) -> CMapItemData {
+        CMapItemData {
+            map_id,
+            scale: self.scale as i8,
+            locked: self.locked,
+            color_patch: Some(MapPatch {
+                start_x: 0,
+                start_y: 0,
+                width: MAP_SIZE as u8,
+                height: MAP_SIZE as u8,
+                colors: self.colors.to_vec(),
+            }),
+        }
+    }
+}
+
+/// Picks the shade of a pixel from how much higher it is than the one north
+/// of it, with a checkerboard dither on flat ground.
+///
+/// Based on the shading in Java's `MapItem::update`.
+fn brightness(height_difference: i32, scale: u8, parity: i32) -> u8 {
+    let shade = f64::from(height_difference) * 4.0 / f64::from(scale + 4)
+        + (f64::from(parity & 1) - 0.5) * 0.4;
+    if shade > 0.6 {
+        2
+    } else if shade < -0.6 {
+        0
+    } else {
+        1
+    }
+}
+
+/// The maps of the server, by the id in the `minecraft:map_id` component.
+#[derive(Default)]
+pub struct MapItemDataRegistry {
+    maps: SyncMutex<FxHashMap<i32, MapItemData>>,
+    next_id: AtomicI32,
+}
+
+impl MapItemDataRegistry {
+    /// Stores a new map under a fresh id and returns the id.
+    ///
+    /// Based on Java's `ServerLevel::getFreeMapId` and `setMapData`.
+    pub fn add(&self, data: MapItemData) -> i32 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.maps.lock().insert(id, data);
+        id
+    }
+
+    /// Runs `f` on the map with the given id, if it exists.
+    pub fn with_map<R>(&self, id: i32, f: impl FnOnce(&mut MapItemData) -> R) -> Option<R> {
+        self.maps.lock().get_mut(&id).map(f)
+    }
+}
+
+/// Returns the id of the map an item shows, if it is a filled map.
+#[must_use]
+pub fn map_id(stack: &ItemStack) -> Option<i32> {
+    stack.get(MAP_ID).copied()
+}
+
+/// Creates a filled map of the grid cell around `x` and `z`.
+///
+/// Based on Java's `MapItem::create`.
+#[must_use]
+pub fn create_filled_map(world: &World, x: i32, z: i32, scale: u8) -> ItemStack {
+    let id = MAP_ITEM_DATA.add(MapItemData::new(x, z, scale, world.dimension.key.clone()));
+    let mut stack = ItemStack::new(&ITEMS.filled_map);
+    stack.set(MAP_ID, id);
+    stack
+}
+
+/// Applies the change a cartography table marked a map for, giving it a new
+/// zoomed out or locked map.
+///
+/// Based on Java's `MapItem::onCraftedPostProcess`.
+pub fn apply_post_processing(stack: &mut ItemStack) {
+    let Some(&post_processing) = stack.get(MAP_POST_PROCESSING) else {
+        return;
+    };
+    stack.remove(MAP_POST_PROCESSING);
+
+    let Some(id) = map_id(stack) else {
+        return;
+    };
+    let Some(data) = MAP_ITEM_DATA.with_map(id, |data| match post_processing {
+        MapPostProcessing::Scale => data.scaled(),
+        MapPostProcessing::Lock => data.locked(),
+    }) else {
+        return;
+    };
+    stack.set(MAP_ID, MAP_ITEM_DATA.add(data));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_snap_to_the_grid_of_their_scale() {
+        let dimension = Identifier::vanilla_static("overworld");
+
+        let map = MapItemData::new(10, -10, 0, dimension.clone());
+        assert_eq!((map.center_x, map.center_z), (0, 0));
+
+        let map = MapItemData::new(100, 0, 0, dimension);
+        assert_eq!(map.center_x, 128);
+
+        let scaled = map.scaled();
+        assert_eq!(scaled.scale, 1);
+        assert_eq!(scaled.center_x, 64);
+        assert_eq!(scaled.scaled().scaled().scaled().scaled().scale, MAX_SCALE);
+    }
+}
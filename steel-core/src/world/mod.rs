@@ -3,18 +3,21 @@ use std::{
     io,
     sync::{
         Arc, Weak,
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
     },
     time::Duration,
 };
 
 use crate::chunk::chunk_map::ChunkMapTickTimings;
+use crate::chunk::heightmap::HeightmapType;
+use crate::chunk::spawn_chunks::SpawnChunks;
 
 use sha2::{Digest, Sha256};
 use steel_protocol::packet_traits::{ClientPacket, EncodedPacket};
 use steel_protocol::packets::game::{
-    CBlockDestruction, CBlockEvent, CLevelEvent, CPlayerChat, CPlayerInfoUpdate, CSound,
-    CSystemChat, SoundSource,
+    CBlockDestruction, CChangeDifficulty, CEntityEvent, CGameEvent, CPlayerInfoUpdate, CSystemChat,
+    CommonPlayerSpawnInfo, ENTITY_EVENT_REDUCED_DEBUG_INFO_DISABLED,
+    ENTITY_EVENT_REDUCED_DEBUG_INFO_ENABLED, GameEventType,
 };
 use steel_protocol::utils::ConnectionProtocol;
 
@@ -23,16 +26,22 @@ use steel_registry::block_entity_type::BlockEntityTypeRef;
 use steel_registry::blocks::BlockRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::blocks::properties::Direction;
-use steel_registry::game_rules::{GameRuleRef, GameRuleValue};
+use steel_registry::game_rules::{GameRuleRef, GameRuleValue, GameRuleValues};
 use steel_registry::item_stack::ItemStack;
-use steel_registry::level_events;
 use steel_registry::vanilla_blocks;
-use steel_registry::vanilla_game_rules::RANDOM_TICK_SPEED;
+use steel_registry::vanilla_dimension_types::OVERWORLD;
+use steel_registry::vanilla_game_rules::{
+    IMMEDIATE_RESPAWN, LIMITED_CRAFTING, RANDOM_TICK_SPEED, REDUCED_DEBUG_INFO,
+};
 use steel_registry::{REGISTRY, dimension_type::DimensionTypeRef};
 
-use steel_registry::blocks::shapes::{AABBd, VoxelShape};
-use steel_utils::locks::SyncRwLock;
-use steel_utils::{BlockPos, BlockStateId, ChunkPos, SectionPos, types::UpdateFlags};
+use steel_registry::blocks::shapes::VoxelShape;
+use steel_utils::locks::{SyncMutex, SyncRwLock};
+use steel_utils::math::Vector3;
+use steel_utils::{
+    BlockPos, BlockStateId, ChunkPos, SectionPos,
+    types::{Difficulty, GameType, UpdateFlags},
+};
 use tokio::{runtime::Runtime, time::Instant};
 
 use crate::{
@@ -40,16 +49,30 @@ use crate::{
     behavior::BLOCK_BEHAVIORS,
     block_entity::SharedBlockEntity,
     config::STEEL_CONFIG,
+    entity::storage::{self, EntityStorage},
     level_data::LevelDataManager,
-    player::{LastSeen, Player},
+    player::{Player, tab_list::TabList},
 };
 
+mod block_tasks;
+mod block_ticks;
+pub mod border;
+mod effects;
+pub mod explosion;
+pub mod map_data;
 mod player_area_map;
 mod player_map;
+pub mod portal;
+pub mod vibrations;
 mod world_entities;
 
+pub use block_tasks::BlockTask;
+use block_ticks::LevelTicks;
+pub use border::WorldBorder;
+pub use effects::sound_range;
 pub use player_area_map::PlayerAreaMap;
 pub use player_map::PlayerMap;
+use vibrations::{VibrationEvent, VibrationSystem};
 
 /// Timing information for a world tick.
 #[derive(Debug)]
@@ -58,24 +81,58 @@ pub struct WorldTickTimings {
     pub chunk_map: ChunkMapTickTimings,
     /// Time spent ticking players.
     pub player_tick: Duration,
+    /// Time spent ticking non-player entities.
+    pub entity_tick: Duration,
+    /// Time spent running scheduled fluid ticks.
+    pub fluid_tick: Duration,
+    /// Time spent computing light for the chunks sent since the last tick.
+    /// The light is computed off the tick thread, so this doesn't add to the
+    /// tick's own duration.
+    pub light_updates: Duration,
 }
 
 /// Interval in ticks between player info broadcasts (600 ticks = 30 seconds).
 /// Matches vanilla `PlayerList.SEND_PLAYER_INFO_INTERVAL`.
 const SEND_PLAYER_INFO_INTERVAL: u64 = 600;
 
+/// Interval in ticks between world border lerp resyncs while the border is moving.
+const BORDER_SYNC_INTERVAL: u64 = 20;
+
 /// A struct that represents a world.
 pub struct World {
     /// The chunk map of the world.
     pub chunk_map: Arc<ChunkMap>,
     /// All players in the world with dual indexing by UUID and entity ID.
     pub players: PlayerMap,
+    /// The tab list, shared by all worlds of the server.
+    pub tab_list: Arc<TabList>,
     /// Spatial index for player proximity queries.
     pub player_area_map: PlayerAreaMap,
     /// The dimension of the world.
     pub dimension: DimensionTypeRef,
     /// Level data manager for persistent world state.
     pub level_data: SyncRwLock<LevelDataManager>,
+    /// The game rules, shared by all worlds of the server. Saved with the
+    /// level data of every world, but only the overworld's is loaded.
+    pub game_rules: Arc<SyncRwLock<GameRuleValues>>,
+    /// The world border. Copied back into the level data whenever it settles.
+    pub border: SyncMutex<WorldBorder>,
+    /// The chunks around the spawn, kept loaded and ticking in the overworld.
+    pub spawn_chunks: SpawnChunks,
+    /// The non-player entities of the world.
+    pub entities: EntityStorage,
+    /// Block operations that are spread over several ticks.
+    block_tasks: SyncMutex<Vec<Box<dyn BlockTask>>>,
+    /// Block ticks scheduled to run after a delay.
+    block_ticks: SyncMutex<LevelTicks>,
+    /// Fluid ticks scheduled to run after a delay.
+    fluid_ticks: SyncMutex<LevelTicks>,
+    /// Nanoseconds spent computing light for chunk packets since the last
+    /// tick. Shared with the chunk sending tasks.
+    pub(crate) light_nanos: Arc<AtomicU64>,
+    /// The blocks listening for vibrations and the vibrations travelling to
+    /// them.
+    vibrations: SyncMutex<VibrationSystem>,
     /// Whether the tick rate is running normally (not frozen/paused).
     /// When false, movement validation checks are skipped.
     tick_runs_normally: AtomicBool,
@@ -84,6 +141,10 @@ pub struct World {
 impl World {
     /// Creates a new world.
     ///
+    /// `game_rules` are the rules of the world the new one shares them with,
+    /// or `None` to load them from the new world's level data, like the
+    /// overworld does. The `tab_list` is the server's.
+    ///
     /// Uses `Arc::new_cyclic` to create a cyclic reference between
     /// the World and its `ChunkMap`'s `WorldGenContext`.
     #[allow(clippy::new_without_default)]
@@ -91,24 +152,64 @@ impl World {
         chunk_runtime: Arc<Runtime>,
         dimension: DimensionTypeRef,
         seed: i64,
+        game_rules: Option<Arc<SyncRwLock<GameRuleValues>>>,
+        tab_list: Arc<TabList>,
     ) -> io::Result<Arc<Self>> {
         let level_data =
             LevelDataManager::new(format!("world/{}", dimension.key.path), seed).await?;
-
-        Ok(Arc::new_cyclic(|weak_self: &Weak<World>| Self {
-            chunk_map: Arc::new(ChunkMap::new(chunk_runtime, weak_self.clone(), &dimension)),
+        let game_rules = game_rules.unwrap_or_else(|| {
+            Arc::new(SyncRwLock::new(level_data.data().game_rules_values.clone()))
+        });
+        let border = level_data.data().world_border.clone();
+        let seed = level_data.data().seed;
+        let spawn = level_data.data().spawn_pos();
+
+        let world = Arc::new_cyclic(|weak_self: &Weak<World>| Self {
+            chunk_map: Arc::new(ChunkMap::new(
+                chunk_runtime,
+                weak_self.clone(),
+                &dimension,
+                seed,
+            )),
             players: PlayerMap::new(),
+            tab_list,
             player_area_map: PlayerAreaMap::new(),
             dimension,
             level_data: SyncRwLock::new(level_data),
+            game_rules,
+            border: SyncMutex::new(border),
+            spawn_chunks: SpawnChunks::new(),
+            entities: EntityStorage::new(),
+            block_tasks: SyncMutex::new(Vec::new()),
+            block_ticks: SyncMutex::new(LevelTicks::default()),
+            fluid_ticks: SyncMutex::new(LevelTicks::default()),
+            light_nanos: Arc::new(AtomicU64::new(0)),
+            vibrations: SyncMutex::new(VibrationSystem::default()),
             tick_runs_normally: AtomicBool::new(true),
-        }))
+        });
+
+        // Like vanilla, only the overworld keeps its spawn chunks loaded
+        if dimension.key == OVERWORLD.key {
+            world.spawn_chunks.set_center(
+                &world.chunk_map,
+                spawn,
+                STEEL_CONFIG.load().spawn_chunk_radius,
+            );
+        }
+        Ok(world)
     }
 
     /// Cleans up the world by saving all chunks.
     /// `await_holding_lock` is safe here cause it's only done on shutdown
     #[allow(clippy::await_holding_lock)]
     pub async fn cleanup(&self, total_saved: &mut usize) {
+        self.players.iter_players(|_, player| {
+            player.save_advancements();
+            player.save_stats();
+            true
+        });
+        self.save_border();
+        self.save_game_rules();
         match self.level_data.write().save_force().await {
             Ok(()) => log::info!(
                 "World {} level data saved successfully",
@@ -170,10 +271,6 @@ impl World {
         self.is_in_valid_bounds(pos)
     }
 
-    /// Player dimensions matching vanilla Minecraft.
-    const PLAYER_WIDTH: f64 = 0.6;
-    const PLAYER_HEIGHT: f64 = 1.8;
-
     /// Checks if a block's collision shape at the given position is unobstructed by entities.
     ///
     /// This is the Rust equivalent of vanilla's `Level.isUnobstructed(BlockState, BlockPos, CollisionContext)`.
@@ -190,16 +287,8 @@ impl World {
         // TODO: Check other entities with blocksBuilding=true (mobs, boats, minecarts, etc.)
         let mut obstructed = false;
         self.players.iter_players(|_uuid, player| {
-            let player_pos = player.position.lock();
-            let half_width = Self::PLAYER_WIDTH / 2.0;
-            let player_aabb = AABBd::new(
-                player_pos.x - half_width,
-                player_pos.y,
-                player_pos.z - half_width,
-                player_pos.x + half_width,
-                player_pos.y + Self::PLAYER_HEIGHT,
-                player_pos.z + half_width,
-            );
+            // Crouching players leave room for a block above their head
+            let player_aabb = player.bounding_box();
 
             // Check if any block AABB intersects with the player
             for block_aabb in collision_shape {
@@ -236,20 +325,123 @@ impl World {
     /// Gets the value of a game rule.
     #[must_use]
     pub fn get_game_rule(&self, rule: GameRuleRef) -> GameRuleValue {
+        self.game_rules.read().get(rule, &REGISTRY.game_rules)
+    }
+
+    /// Copies the shared game rules into the level data so they are saved
+    /// with it.
+    pub fn save_game_rules(&self) {
+        let game_rules = self.game_rules.read().clone();
+        self.level_data.write().data_mut().game_rules_values = game_rules;
+    }
+
+    /// Tells the world's clients about a changed rule they depend on.
+    ///
+    /// Based on the change callbacks registered in Java's `GameRules`.
+    pub(crate) fn on_game_rule_changed(&self, rule: GameRuleRef, value: GameRuleValue) {
+        let enabled = value == GameRuleValue::Bool(true);
+        let event_data = if enabled { 1.0 } else { 0.0 };
+
+        if rule.key == IMMEDIATE_RESPAWN.key {
+            self.broadcast_to_all(CGameEvent {
+                event: GameEventType::ImmediateRespawn,
+                data: event_data,
+            });
+        } else if rule.key == LIMITED_CRAFTING.key {
+            self.broadcast_to_all(CGameEvent {
+                event: GameEventType::LimitedCrafting,
+                data: event_data,
+            });
+        } else if rule.key == REDUCED_DEBUG_INFO.key {
+            let event = if enabled {
+                ENTITY_EVENT_REDUCED_DEBUG_INFO_ENABLED
+            } else {
+                ENTITY_EVENT_REDUCED_DEBUG_INFO_DISABLED
+            };
+            self.players.iter_players(|_, player| {
+                player.connection.send_packet(CEntityEvent {
+                    entity_id: player.id,
+                    event,
+                });
+                true
+            });
+        }
+    }
+
+    /// Returns true while it rains or snows in the world, which only happens
+    /// under an open sky.
+    ///
+    /// Vanilla: `Level.isRaining`
+    #[must_use]
+    pub fn is_raining(&self) -> bool {
+        if !self.dimension.has_skylight || self.dimension.has_ceiling {
+            return false;
+        }
+        self.level_data.read().data().weather.raining
+    }
+
+    /// Returns true while a thunderstorm rages in the world, which only
+    /// happens under an open sky.
+    ///
+    /// Vanilla: `Level.isThundering`
+    #[must_use]
+    pub fn is_thundering(&self) -> bool {
+        if !self.dimension.has_skylight || self.dimension.has_ceiling {
+            return false;
+        }
         let level_data = self.level_data.read();
-        level_data
-            .data()
-            .game_rules_values
-            .get(rule, &REGISTRY.game_rules)
+        let weather = &level_data.data().weather;
+        weather.raining && weather.thundering
     }
 
-    /// Sets the value of a game rule.
-    pub fn set_game_rule(&self, rule: GameRuleRef, value: GameRuleValue) -> bool {
-        let mut level_data = self.level_data.write();
-        level_data
-            .data_mut()
-            .game_rules_values
-            .set(rule, value, &REGISTRY.game_rules)
+    /// Gets the difficulty of the world.
+    #[must_use]
+    pub fn difficulty(&self) -> Difficulty {
+        self.level_data.read().data().difficulty
+    }
+
+    /// Sets the difficulty of the world and sends it to every player in it.
+    ///
+    /// Returns false if the world already had this difficulty.
+    pub fn set_difficulty(&self, difficulty: Difficulty) -> bool {
+        {
+            let mut level_data = self.level_data.write();
+            if level_data.data().difficulty == difficulty {
+                return false;
+            }
+            level_data.data_mut().difficulty = difficulty;
+        }
+
+        self.broadcast_to_all(self.difficulty_packet());
+        true
+    }
+
+    /// Builds the packet that tells a client the difficulty of the world.
+    #[must_use]
+    pub fn difficulty_packet(&self) -> CChangeDifficulty {
+        CChangeDifficulty {
+            difficulty: self.difficulty(),
+            // Only the host of a singleplayer world can lock the difficulty
+            locked: false,
+        }
+    }
+
+    /// Builds the spawn info a client needs to enter this world.
+    #[must_use]
+    pub fn spawn_info(&self, game_type: GameType) -> CommonPlayerSpawnInfo {
+        CommonPlayerSpawnInfo {
+            dimension_type: *REGISTRY.dimension_types.get_id(self.dimension) as i32,
+            dimension: self.dimension.key.clone(),
+            seed: self.obfuscated_seed(),
+            game_type,
+            previous_game_type: None,
+            is_debug: false,
+            // TODO: Change once we add a normal generator
+            is_flat: true,
+            last_death_location: None,
+            portal_cooldown: 0,
+            sea_level: 63, // Standard overworld sea level
+        }
     }
 
     /// Gets the world seed.
@@ -289,6 +481,47 @@ impl World {
             .unwrap_or_else(|| REGISTRY.blocks.get_base_state_id(vanilla_blocks::AIR))
     }
 
+    /// Returns true if no block that stops motion is above `pos`, so the sun
+    /// shines on it. Always false in dimensions without a sky.
+    ///
+    /// Vanilla: `Level.canSeeSky`
+    // TODO: Check the sky light instead once the light engine tracks it, so
+    // glass and leaves let the sky through
+    #[must_use]
+    pub fn is_sky_visible(&self, pos: &BlockPos) -> bool {
+        if !self.dimension.has_skylight {
+            return false;
+        }
+        if pos.y() > self.get_max_y() {
+            return true;
+        }
+
+        self.motion_blocking_height(pos.x(), pos.z())
+            .is_some_and(|surface| pos.y() >= surface)
+    }
+
+    /// Returns the lowest height above every block that blocks motion or
+    /// holds fluid in the column at `x` and `z`, or `None` if its chunk isn't
+    /// loaded.
+    ///
+    /// Vanilla: `Heightmap.Types.MOTION_BLOCKING`
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn motion_blocking_height(&self, x: i32, z: i32) -> Option<i32> {
+        let chunk_pos = Self::chunk_pos_for_block(&BlockPos::new(x, 0, z));
+        self.chunk_map
+            .with_full_chunk(&chunk_pos, |chunk| {
+                chunk.as_full().map(|chunk| {
+                    chunk
+                        .heightmaps
+                        .read()
+                        .get(HeightmapType::MotionBlocking)
+                        .get_first_available((x & 15) as usize, (z & 15) as usize)
+                })
+            })
+            .flatten()
+    }
+
     /// Sets a block at the given position.
     ///
     /// Returns `true` if the block was successfully set, `false` otherwise.
@@ -366,6 +599,32 @@ impl World {
         true
     }
 
+    /// Breaks the block at the given position, playing its break effect to
+    /// everyone nearby, and replaces it with air.
+    ///
+    /// Returns `false` if there was nothing to break or the block couldn't be set.
+    ///
+    /// TODO: Drop the block's loot once loot tables are implemented.
+    ///
+    /// Vanilla: `Level.destroyBlock`
+    pub fn destroy_block(&self, pos: BlockPos) -> bool {
+        let state = self.get_block_state(&pos);
+        if state.is_air() {
+            return false;
+        }
+
+        let block = state.get_block();
+        if block.key != vanilla_blocks::FIRE.key && block.key != vanilla_blocks::SOUL_FIRE.key {
+            self.destroy_block_effect(pos, u32::from(state.0), None);
+        }
+
+        let destroyed = self.set_block(pos, state.fluid_block_state(), UpdateFlags::UPDATE_ALL);
+        if destroyed {
+            self.post_vibration(VibrationEvent::BlockDestroy, vibrations::block_center(pos));
+        }
+        destroyed
+    }
+
     /// Order in which neighbors are updated (matches vanilla's `NeighborUpdater.UPDATE_ORDER`).
     const NEIGHBOR_UPDATE_ORDER: [Direction; 6] = [
         Direction::West,
@@ -379,12 +638,14 @@ impl World {
     /// Updates all neighbors of the given position about a block change.
     ///
     /// This is the Rust equivalent of vanilla's `Level.updateNeighborsAt()`.
-    fn update_neighbors_at(&self, pos: &BlockPos, source_block: BlockRef) {
+    pub fn update_neighbors_at(&self, pos: &BlockPos, source_block: BlockRef) {
         for direction in Self::NEIGHBOR_UPDATE_ORDER {
             let (dx, dy, dz) = direction.offset();
             let neighbor_pos = pos.offset(dx, dy, dz);
             self.neighbor_changed(neighbor_pos, source_block, false);
         }
+        self.paintings_neighbor_changed(*pos);
+        self.item_frames_neighbor_changed(*pos);
     }
 
     /// Called when a neighbor's shape changes, to update this block's state.
@@ -463,6 +724,31 @@ impl World {
             .flatten()
     }
 
+    /// Returns whether every block between `from` and `to` is inside the build
+    /// height and in a loaded chunk.
+    ///
+    /// Vanilla: `LevelReader.hasChunksAt`
+    #[must_use]
+    pub fn has_chunks_at(&self, from: BlockPos, to: BlockPos) -> bool {
+        if self.is_outside_build_height(from.y().min(to.y()))
+            || self.is_outside_build_height(from.y().max(to.y()))
+        {
+            return false;
+        }
+
+        let (from, to) = (
+            Self::chunk_pos_for_block(&from).0,
+            Self::chunk_pos_for_block(&to).0,
+        );
+        (from.x.min(to.x)..=from.x.max(to.x)).all(|x| {
+            (from.y.min(to.y)..=from.y.max(to.y)).all(|z| {
+                self.chunk_map
+                    .with_full_chunk(&ChunkPos::new(x, z), |_| ())
+                    .is_some()
+            })
+        })
+    }
+
     /// Called when a block entity's data changes.
     ///
     /// Marks the containing chunk as unsaved so it will be persisted to disk.
@@ -501,6 +787,52 @@ impl World {
             start.elapsed()
         };
 
+        if runs_normally {
+            self.tick_border(tick_count);
+        }
+
+        self.tick_block_tasks();
+
+        let mut fluid_tick = Duration::ZERO;
+        let mut entity_tick = Duration::ZERO;
+        if runs_normally {
+            self.tick_block_ticks();
+            fluid_tick = {
+                let _span = tracing::trace_span!("fluid_tick").entered();
+                let start = Instant::now();
+                self.tick_fluid_ticks();
+                start.elapsed()
+            };
+            self.tick_vibrations();
+
+            let _span = tracing::trace_span!("entity_tick").entered();
+            let start = Instant::now();
+            self.tick_firework_rockets();
+            self.tick_armor_stands();
+            self.tick_experience_orbs();
+            self.tick_minecarts();
+            self.tick_boats();
+            self.tick_primed_tnt();
+            self.tick_falling_blocks();
+            self.tick_lightning_bolts();
+            self.tick_villagers();
+            self.tick_zombies(tick_count);
+            self.tick_skeletons();
+            self.tick_creepers();
+            self.tick_slimes();
+            self.tick_iron_golems(tick_count);
+            self.tick_endermen();
+            self.tick_ghasts();
+            self.tick_ender_dragons();
+            self.tick_dragon_fireballs();
+            self.tick_ghast_fireballs();
+            self.tick_arrows();
+            entity_tick = start.elapsed();
+        }
+
+        // Entities can also be added and removed while the world is frozen
+        self.update_entity_tracking();
+
         // Broadcast player latency updates periodically
         if tick_count.is_multiple_of(SEND_PLAYER_INFO_INTERVAL) {
             let _span = tracing::trace_span!("broadcast_latency").entered();
@@ -510,9 +842,44 @@ impl World {
         WorldTickTimings {
             chunk_map: chunk_map_timings,
             player_tick,
+            entity_tick,
+            fluid_tick,
+            light_updates: Duration::from_nanos(self.light_nanos.swap(0, Ordering::Relaxed)),
         }
     }
 
+    /// Advances a moving world border and periodically resyncs it to clients,
+    /// so they don't drift away from the server's idea of its size.
+    fn tick_border(&self, tick_count: u64) {
+        let mut border = self.border.lock();
+        if border.lerp_target.is_none() {
+            return;
+        }
+
+        if border.tick() {
+            if tick_count.is_multiple_of(BORDER_SYNC_INTERVAL) {
+                self.broadcast_to_all(border.lerp_packet());
+            }
+        } else {
+            drop(border);
+            self.save_border();
+        }
+    }
+
+    /// Changes the world border, sends `update`'s packet to all players and
+    /// saves the new state to the level data.
+    pub fn update_border<P: ClientPacket>(&self, update: impl FnOnce(&mut WorldBorder) -> P) {
+        let packet = update(&mut self.border.lock());
+        self.broadcast_to_all(packet);
+        self.save_border();
+    }
+
+    /// Copies the current world border into the level data.
+    pub fn save_border(&self) {
+        let border = self.border.lock().clone();
+        self.level_data.write().data_mut().world_border = border;
+    }
+
     /// Broadcasts latency updates for all players to all players.
     /// This is called every `SEND_PLAYER_INFO_INTERVAL` ticks to update the ping display.
     fn broadcast_player_latency_updates(&self) {
@@ -526,80 +893,10 @@ impl World {
         // Only broadcast if there are players
         if !latency_entries.is_empty() {
             let packet = CPlayerInfoUpdate::update_latency(latency_entries);
-            self.broadcast_to_all(packet);
+            self.tab_list.broadcast(&packet);
         }
     }
 
-    /// Broadcasts a signed chat message to all players in the world.
-    ///
-    /// # Panics
-    /// Panics if `message_signature` is `None` after checking `is_some()` (should never happen).
-    pub fn broadcast_chat(
-        &self,
-        mut packet: CPlayerChat,
-        _sender: Arc<Player>,
-        sender_last_seen: LastSeen,
-        message_signature: Option<[u8; 256]>,
-    ) {
-        log::debug!(
-            "broadcast_chat: sender_last_seen has {} signatures, message_signature present: {}",
-            sender_last_seen.len(),
-            message_signature.is_some()
-        );
-
-        self.players.iter_players(|_, recipient| {
-            let messages_received = recipient.get_and_increment_messages_received();
-            packet.global_index = messages_received;
-
-            log::debug!(
-                "Broadcasting to player {} (UUID: {}), global_index={}",
-                recipient.gameprofile.name,
-                recipient.gameprofile.id,
-                messages_received
-            );
-
-            // IMPORTANT: Index previous messages BEFORE updating the cache
-            // This matches vanilla's order: pack() then push()
-            let previous_messages = {
-                let recipient_cache = recipient.signature_cache.lock();
-                recipient_cache.index_previous_messages(&sender_last_seen)
-            };
-
-            log::debug!(
-                "  Indexed {} previous messages for recipient",
-                previous_messages.len()
-            );
-
-            packet.previous_messages.clone_from(&previous_messages);
-
-            // Send the packet
-            recipient.connection.send_packet(packet.clone());
-
-            // AFTER sending, update the recipient's cache using vanilla's push algorithm
-            // This adds all lastSeen signatures + current signature to the cache
-            if let Some(signature) = message_signature {
-                recipient
-                    .signature_cache
-                    .lock()
-                    .push(&sender_last_seen, Some(&signature));
-
-                log::debug!("  Added signature to recipient's cache and pending list");
-
-                // Add to pending messages for acknowledgment tracking
-                recipient
-                    .message_validator
-                    .lock()
-                    .add_pending(Some(Box::new(signature) as Box<[u8]>));
-            } else {
-                // Even unsigned messages update the pending tracker
-                recipient.message_validator.lock().add_pending(None);
-                log::debug!("  Added unsigned message to pending list");
-            }
-
-            true
-        });
-    }
-
     /// Broadcasts a system chat message to all players.
     pub fn broadcast_system_chat(&self, packet: CSystemChat) {
         self.broadcast_to_all(packet);
@@ -610,9 +907,11 @@ impl World {
     /// This method handles encoding the packet once and sending it to all players,
     /// avoiding repeated cloning of unencoded packets.
     pub fn broadcast_to_all<P: ClientPacket>(&self, packet: P) {
-        let Ok(encoded) =
-            EncodedPacket::from_bare(packet, STEEL_CONFIG.compression, ConnectionProtocol::Play)
-        else {
+        let Ok(encoded) = EncodedPacket::from_bare(
+            packet,
+            STEEL_CONFIG.load().compression,
+            ConnectionProtocol::Play,
+        ) else {
             return;
         };
         self.broadcast_to_all_encoded(encoded);
@@ -625,7 +924,7 @@ impl World {
         self.players.iter_players(|_, player| {
             let Ok(encoded) = EncodedPacket::from_bare(
                 packet(player),
-                STEEL_CONFIG.compression,
+                STEEL_CONFIG.load().compression,
                 ConnectionProtocol::Play,
             ) else {
                 return false;
@@ -645,24 +944,6 @@ impl World {
         });
     }
 
-    /// Broadcasts an unsigned player chat message to all players.
-    pub fn broadcast_unsigned_chat(
-        &self,
-        mut packet: CPlayerChat,
-        sender_name: &str,
-        message: &str,
-    ) {
-        log::info!("<{sender_name}> {message}");
-
-        self.players.iter_players(|_, recipient| {
-            let messages_received = recipient.get_and_increment_messages_received();
-            packet.global_index = messages_received;
-
-            recipient.connection.send_packet(packet.clone());
-            true
-        });
-    }
-
     /// Broadcasts a packet to all players tracking the given chunk.
     ///
     /// This method handles encoding the packet internally, avoiding boilerplate at call sites.
@@ -673,14 +954,22 @@ impl World {
         packet: P,
         exclude: Option<i32>,
     ) {
-        let Ok(encoded) =
-            EncodedPacket::from_bare(packet, STEEL_CONFIG.compression, ConnectionProtocol::Play)
-        else {
+        let Ok(encoded) = EncodedPacket::from_bare(
+            packet,
+            STEEL_CONFIG.load().compression,
+            ConnectionProtocol::Play,
+        ) else {
             return;
         };
         self.broadcast_to_nearby_encoded(chunk, encoded, exclude);
     }
 
+    /// Broadcasts a packet to all players tracking the chunk `position` is
+    /// in, like the updates of an entity standing there.
+    pub fn broadcast_to_trackers<P: ClientPacket>(&self, position: Vector3<f64>, packet: P) {
+        self.broadcast_to_nearby(storage::chunk_at(position), packet, None);
+    }
+
     /// Broadcasts an already-encoded packet to all players tracking the given chunk.
     ///
     /// Use this when you have a pre-encoded packet to avoid re-encoding.
@@ -787,235 +1076,4 @@ impl World {
             item.count()
         );
     }
-
-    /// Broadcasts a level event to nearby players within 64 blocks.
-    ///
-    /// Level events trigger sounds, particles, and animations on the client.
-    /// See `steel_registry::level_events` for available event type constants.
-    ///
-    /// # Arguments
-    /// * `event_type` - The event type ID from `steel_registry::level_events`
-    /// * `pos` - The position where the event occurs
-    /// * `data` - Event-specific data (e.g., block state ID for block destruction)
-    /// * `exclude` - Optional entity ID to exclude from receiving the event
-    pub fn level_event(&self, event_type: i32, pos: BlockPos, data: i32, exclude: Option<i32>) {
-        const MAX_DISTANCE_SQ: f64 = 64.0 * 64.0;
-
-        let chunk = ChunkPos::new(
-            SectionPos::block_to_section_coord(pos.x()),
-            SectionPos::block_to_section_coord(pos.z()),
-        );
-        let packet = CLevelEvent::new(event_type, pos, data, false);
-        let Ok(encoded) =
-            EncodedPacket::from_bare(packet, STEEL_CONFIG.compression, ConnectionProtocol::Play)
-        else {
-            log::warn!("Failed to encode level event packet");
-            return;
-        };
-
-        // Get players tracking this chunk, then filter by 64-block distance
-        let event_pos = (
-            f64::from(pos.x()) + 0.5,
-            f64::from(pos.y()) + 0.5,
-            f64::from(pos.z()) + 0.5,
-        );
-
-        for entity_id in self.player_area_map.get_tracking_players(chunk) {
-            // Skip excluded player (they hear the effect client-side)
-            if exclude == Some(entity_id) {
-                continue;
-            }
-            if let Some(player) = self.players.get_by_entity_id(entity_id) {
-                let player_pos = *player.position.lock();
-                let dx = player_pos.x - event_pos.0;
-                let dy = player_pos.y - event_pos.1;
-                let dz = player_pos.z - event_pos.2;
-                let dist_sq = dx * dx + dy * dy + dz * dz;
-
-                if dist_sq <= MAX_DISTANCE_SQ {
-                    player.connection.send_encoded_packet(encoded.clone());
-                }
-            }
-        }
-    }
-
-    /// Broadcasts a global level event to all players in the world.
-    ///
-    /// Unlike `level_event`, this sends the event to all players regardless of distance.
-    /// Used for events like the ender dragon death or wither spawn.
-    ///
-    /// # Arguments
-    /// * `event_type` - The event type ID from `steel_registry::level_events`
-    /// * `pos` - The position where the event occurs
-    /// * `data` - Event-specific data
-    pub fn global_level_event(&self, event_type: i32, pos: BlockPos, data: i32) {
-        let packet = CLevelEvent::new(event_type, pos, data, true);
-        self.players.iter_players(|_, player| {
-            player.connection.send_packet(packet.clone());
-            true
-        });
-    }
-
-    /// Broadcasts block destruction particles and sound for a destroyed block.
-    ///
-    /// This is a convenience method that sends the `PARTICLES_DESTROY_BLOCK` level event.
-    ///
-    /// # Arguments
-    /// * `pos` - The position of the destroyed block
-    /// * `block_state_id` - The block state ID of the destroyed block
-    /// * `exclude` - Optional entity ID to exclude from receiving the event
-    pub fn destroy_block_effect(&self, pos: BlockPos, block_state_id: u32, exclude: Option<i32>) {
-        self.level_event(
-            level_events::PARTICLES_DESTROY_BLOCK,
-            pos,
-            block_state_id as i32,
-            exclude,
-        );
-    }
-
-    /// Broadcasts a block event to nearby players within 64 blocks.
-    ///
-    /// Block events are used for special block behaviors like pistons, note blocks,
-    /// chests, and bells. Each block type interprets the parameters differently.
-    ///
-    /// # Arguments
-    /// * `pos` - The position of the block
-    /// * `block` - The block reference
-    /// * `action_id` - The action ID (block-specific meaning)
-    /// * `action_param` - The action parameter (block-specific meaning)
-    pub fn block_event(&self, pos: BlockPos, block: BlockRef, action_id: u8, action_param: u8) {
-        const MAX_DISTANCE_SQ: f64 = 64.0 * 64.0;
-
-        let block_id = *REGISTRY.blocks.get_id(block) as i32;
-
-        let chunk = ChunkPos::new(
-            SectionPos::block_to_section_coord(pos.x()),
-            SectionPos::block_to_section_coord(pos.z()),
-        );
-        let packet = CBlockEvent::new(pos, action_id, action_param, block_id);
-        let Ok(encoded) =
-            EncodedPacket::from_bare(packet, STEEL_CONFIG.compression, ConnectionProtocol::Play)
-        else {
-            log::warn!("Failed to encode block event packet");
-            return;
-        };
-
-        // Get players tracking this chunk, then filter by 64-block distance
-        let event_pos = (
-            f64::from(pos.x()) + 0.5,
-            f64::from(pos.y()) + 0.5,
-            f64::from(pos.z()) + 0.5,
-        );
-
-        for entity_id in self.player_area_map.get_tracking_players(chunk) {
-            if let Some(player) = self.players.get_by_entity_id(entity_id) {
-                let player_pos = *player.position.lock();
-                let dx = player_pos.x - event_pos.0;
-                let dy = player_pos.y - event_pos.1;
-                let dz = player_pos.z - event_pos.2;
-                let dist_sq = dx * dx + dy * dy + dz * dz;
-
-                if dist_sq <= MAX_DISTANCE_SQ {
-                    player.connection.send_encoded_packet(encoded.clone());
-                }
-            }
-        }
-    }
-
-    /// Plays a sound at a specific position, broadcasting to nearby players.
-    ///
-    /// The sound is sent to all players within 64 blocks of the position,
-    /// except for the excluded player (if any). The excluded player is typically
-    /// the one who triggered the sound, as they hear it client-side.
-    ///
-    /// # Arguments
-    /// * `sound_id` - The sound event registry ID (from `steel_registry::sound_events`)
-    /// * `source` - The sound source category
-    /// * `pos` - The block position (sound plays at center of block)
-    /// * `volume` - Volume multiplier (1.0 = normal)
-    /// * `pitch` - Pitch multiplier (1.0 = normal)
-    /// * `exclude` - Optional entity ID to exclude from receiving the sound
-    pub fn play_sound(
-        &self,
-        sound_id: i32,
-        source: SoundSource,
-        pos: BlockPos,
-        volume: f32,
-        pitch: f32,
-        exclude: Option<i32>,
-    ) {
-        const MAX_DISTANCE_SQ: f64 = 64.0 * 64.0;
-
-        let chunk = ChunkPos::new(
-            SectionPos::block_to_section_coord(pos.x()),
-            SectionPos::block_to_section_coord(pos.z()),
-        );
-
-        // Generate a random seed for sound variations
-        let seed = rand::random::<i64>();
-
-        let packet = CSound::new(
-            sound_id,
-            source,
-            f64::from(pos.x()) + 0.5,
-            f64::from(pos.y()) + 0.5,
-            f64::from(pos.z()) + 0.5,
-            volume,
-            pitch,
-            seed,
-        );
-        let Ok(encoded) =
-            EncodedPacket::from_bare(packet, STEEL_CONFIG.compression, ConnectionProtocol::Play)
-        else {
-            log::warn!("Failed to encode sound packet");
-            return;
-        };
-
-        // Get players tracking this chunk, then filter by 64-block distance
-        let sound_pos = (
-            f64::from(pos.x()) + 0.5,
-            f64::from(pos.y()) + 0.5,
-            f64::from(pos.z()) + 0.5,
-        );
-
-        for entity_id in self.player_area_map.get_tracking_players(chunk) {
-            // Skip excluded player (they hear the sound client-side)
-            if exclude == Some(entity_id) {
-                continue;
-            }
-            if let Some(player) = self.players.get_by_entity_id(entity_id) {
-                let player_pos = *player.position.lock();
-                let dx = player_pos.x - sound_pos.0;
-                let dy = player_pos.y - sound_pos.1;
-                let dz = player_pos.z - sound_pos.2;
-                let dist_sq = dx * dx + dy * dy + dz * dz;
-
-                if dist_sq <= MAX_DISTANCE_SQ {
-                    player.connection.send_encoded_packet(encoded.clone());
-                }
-            }
-        }
-    }
-
-    /// Plays a block sound at a specific position.
-    ///
-    /// Convenience method that uses the BLOCKS sound source and applies
-    /// the sound type's volume and pitch modifiers.
-    ///
-    /// # Arguments
-    /// * `sound_id` - The sound event registry ID
-    /// * `pos` - The block position
-    /// * `volume` - Base volume (typically from `SoundType`)
-    /// * `pitch` - Base pitch (typically from `SoundType`)
-    /// * `exclude` - Optional entity ID to exclude from receiving the sound
-    pub fn play_block_sound(
-        &self,
-        sound_id: i32,
-        pos: BlockPos,
-        volume: f32,
-        pitch: f32,
-        exclude: Option<i32>,
-    ) {
-        self.play_sound(sound_id, SoundSource::Blocks, pos, volume, pitch, exclude);
-    }
 }
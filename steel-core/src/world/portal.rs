@@ -0,0 +1,466 @@
+//! Nether portals, matching vanilla's `PortalShape` and `PortalForcer`.
+//!
+//! A portal is an obsidian frame whose inside is 2 to 21 blocks wide and 3 to
+//! 21 blocks tall, filled with nether portal blocks. When a player travels
+//! through one, the closest portal near the scaled position in the other world
+//! is used, and a new one is built there if there is none.
+//!
+//! End portals send players to an obsidian platform in the End, see
+//! [`create_end_platform`].
+
+use std::ptr;
+
+use steel_registry::{
+    blocks::{
+        block_state_ext::BlockStateExt,
+        properties::{BlockStateProperties, Direction},
+    },
+    vanilla_blocks,
+};
+use steel_utils::{BlockPos, BlockStateId, ChunkPos, SectionPos, math::Axis, types::UpdateFlags};
+
+use crate::world::World;
+
+const MIN_WIDTH: i32 = 2;
+const MAX_WIDTH: i32 = 21;
+const MIN_HEIGHT: i32 = 3;
+const MAX_HEIGHT: i32 = 21;
+
+/// How far from the target position an existing portal is looked for in the Nether.
+pub const NETHER_SEARCH_RADIUS: i32 = 16;
+/// How far from the target position an existing portal is looked for in other worlds.
+pub const SEARCH_RADIUS: i32 = 128;
+/// How far from the target position a new portal may be built.
+const CREATE_RADIUS: i32 = 16;
+
+/// An obsidian frame that can hold a portal.
+pub struct PortalShape {
+    axis: Axis,
+    /// The direction the inside of the frame extends in from `bottom_left`.
+    right: Direction,
+    bottom_left: BlockPos,
+    width: i32,
+    height: i32,
+    /// How many portal blocks are already inside the frame.
+    portal_blocks: i32,
+}
+
+impl PortalShape {
+    /// Finds a frame around `pos` with nothing inside it, so a portal can be lit there.
+    ///
+    /// Frames along `preferred_axis` are checked first.
+    #[must_use]
+    pub fn find_empty(world: &World, pos: BlockPos, preferred_axis: Axis) -> Option<Self> {
+        let other_axis = if preferred_axis == Axis::X {
+            Axis::Z
+        } else {
+            Axis::X
+        };
+        [preferred_axis, other_axis]
+            .into_iter()
+            .filter_map(|axis| Self::find(world, pos, axis))
+            .find(|shape| shape.portal_blocks == 0)
+    }
+
+    fn find(world: &World, pos: BlockPos, axis: Axis) -> Option<Self> {
+        let right = if axis == Axis::X {
+            Direction::West
+        } else {
+            Direction::South
+        };
+        let bottom_left = Self::bottom_left(world, pos, right)?;
+
+        let width = distance_until_edge_above_frame(world, bottom_left, right);
+        if !(MIN_WIDTH..=MAX_WIDTH).contains(&width) {
+            return None;
+        }
+
+        let (height, portal_blocks) = Self::distance_until_top(world, bottom_left, right, width);
+        if !(MIN_HEIGHT..=MAX_HEIGHT).contains(&height) {
+            return None;
+        }
+        let has_top_frame = (0..width).all(|i| {
+            is_frame(world.get_block_state(&offset(bottom_left, right, i).offset(0, height, 0)))
+        });
+        if !has_top_frame {
+            return None;
+        }
+
+        Some(Self {
+            axis,
+            right,
+            bottom_left,
+            width,
+            height,
+            portal_blocks,
+        })
+    }
+
+    /// Walks down to the bottom of the frame, then along it to its first inside block.
+    fn bottom_left(world: &World, pos: BlockPos, right: Direction) -> Option<BlockPos> {
+        let min_y = (pos.y() - MAX_HEIGHT).max(world.get_min_y());
+        let mut pos = pos;
+        while pos.y() > min_y && is_empty(world.get_block_state(&pos.offset(0, -1, 0))) {
+            pos = pos.offset(0, -1, 0);
+        }
+
+        let left = right.opposite();
+        let distance = distance_until_edge_above_frame(world, pos, left) - 1;
+        (distance >= 0).then(|| offset(pos, left, distance))
+    }
+
+    /// Measures how tall the inside of the frame is, counting the portal blocks in it.
+    fn distance_until_top(
+        world: &World,
+        bottom_left: BlockPos,
+        right: Direction,
+        width: i32,
+    ) -> (i32, i32) {
+        let mut portal_blocks = 0;
+        for height in 0..MAX_HEIGHT {
+            let row = bottom_left.offset(0, height, 0);
+            if !is_frame(world.get_block_state(&offset(row, right, -1)))
+                || !is_frame(world.get_block_state(&offset(row, right, width)))
+            {
+                return (height, portal_blocks);
+            }
+            for i in 0..width {
+                let state = world.get_block_state(&offset(row, right, i));
+                if !is_empty(state) {
+                    return (height, portal_blocks);
+                }
+                if is_portal(state) {
+                    portal_blocks += 1;
+                }
+            }
+        }
+        (MAX_HEIGHT, portal_blocks)
+    }
+
+    /// Fills the inside of the frame with portal blocks.
+    pub fn create_portal_blocks(&self, world: &World) {
+        let state = portal_state(self.axis);
+        for height in 0..self.height {
+            for i in 0..self.width {
+                let pos = offset(self.bottom_left, self.right, i).offset(0, height, 0);
+                world.set_block(pos, state, UpdateFlags::UPDATE_CLIENTS);
+            }
+        }
+    }
+}
+
+/// The kinds of portal that move players between worlds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortalType {
+    /// A nether portal, linking the overworld and the Nether.
+    Nether,
+    /// An end portal, taking players to the End and back.
+    End,
+}
+
+/// Returns the kind of portal the state is part of, if any.
+#[must_use]
+pub fn portal_type(state: BlockStateId) -> Option<PortalType> {
+    let block = state.get_block();
+    if ptr::eq(block, vanilla_blocks::NETHER_PORTAL) {
+        Some(PortalType::Nether)
+    } else if ptr::eq(block, vanilla_blocks::END_PORTAL) {
+        Some(PortalType::End)
+    } else {
+        None
+    }
+}
+
+/// Builds the 5 by 5 obsidian platform under `pos` and clears the three
+/// blocks above it, so players arriving in the End at `pos` have room.
+///
+/// Matches vanilla `EndPlatformFeature.createEndPlatform`.
+pub fn create_end_platform(world: &World, pos: BlockPos) {
+    let obsidian = vanilla_blocks::OBSIDIAN.default_state();
+    let air = vanilla_blocks::AIR.default_state();
+    for dx in -2..=2 {
+        for dz in -2..=2 {
+            for dy in -1..3 {
+                let block_pos = pos.offset(dx, dy, dz);
+                let state = if dy == -1 { obsidian } else { air };
+                // TODO: Drop the replaced blocks like vanilla's destroyBlock
+                if world.get_block_state(&block_pos) != state {
+                    world.set_block(block_pos, state, UpdateFlags::UPDATE_ALL);
+                }
+            }
+        }
+    }
+}
+
+/// Returns whether the state is a nether portal block.
+#[must_use]
+pub fn is_portal(state: BlockStateId) -> bool {
+    ptr::eq(state.get_block(), vanilla_blocks::NETHER_PORTAL)
+}
+
+/// Returns the axis the portal block faces along.
+#[must_use]
+pub fn portal_axis(state: BlockStateId) -> Axis {
+    state.get_value(&BlockStateProperties::HORIZONTAL_AXIS)
+}
+
+/// Finds the portal closest to `center` within `radius` blocks horizontally.
+///
+/// Only loaded chunks are searched. Returns the lowest portal block of the
+/// closest portal column.
+#[must_use]
+pub fn find_portal(world: &World, center: BlockPos, radius: i32) -> Option<BlockPos> {
+    let border = world.border.lock().clone();
+    let min_y = world.get_min_y();
+    let distance = |pos: &BlockPos| {
+        let dx = i64::from(pos.x() - center.x());
+        let dy = i64::from(pos.y() - center.y());
+        let dz = i64::from(pos.z() - center.z());
+        (dx * dx + dy * dy + dz * dz, pos.y())
+    };
+
+    let mut closest: Option<BlockPos> = None;
+    let min_chunk = |coord: i32| SectionPos::block_to_section_coord(coord - radius);
+    let max_chunk = |coord: i32| SectionPos::block_to_section_coord(coord + radius);
+    for chunk_x in min_chunk(center.x())..=max_chunk(center.x()) {
+        for chunk_z in min_chunk(center.z())..=max_chunk(center.z()) {
+            let chunk_pos = ChunkPos::new(chunk_x, chunk_z);
+            world.chunk_map.with_full_chunk(&chunk_pos, |chunk| {
+                for (index, section) in chunk.sections().sections.iter().enumerate() {
+                    let section = section.read();
+                    if !section.states.contains_any(is_portal) {
+                        continue;
+                    }
+                    let section_y = min_y + index as i32 * 16;
+                    for y in 0..16 {
+                        for z in 0..16 {
+                            for x in 0..16 {
+                                if !is_portal(section.states.get(x, y, z)) {
+                                    continue;
+                                }
+                                let pos = BlockPos::new(
+                                    chunk_x * 16 + x as i32,
+                                    section_y + y as i32,
+                                    chunk_z * 16 + z as i32,
+                                );
+                                if (pos.x() - center.x()).abs() > radius
+                                    || (pos.z() - center.z()).abs() > radius
+                                    || !border
+                                        .is_within_bounds(f64::from(pos.x()), f64::from(pos.z()))
+                                {
+                                    continue;
+                                }
+                                if closest.is_none_or(|best| distance(&pos) < distance(&best)) {
+                                    closest = Some(pos);
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    }
+
+    let mut pos = closest?;
+    while is_portal(world.get_block_state(&pos.offset(0, -1, 0))) {
+        pos = pos.offset(0, -1, 0);
+    }
+    Some(pos)
+}
+
+/// Builds a portal near `pos`, facing along `axis`, and returns its lowest,
+/// leftmost portal block.
+///
+/// Like vanilla, the closest spot with solid ground and room for the frame is
+/// preferred. Without one, the portal is built floating on a small obsidian
+/// platform at the target height.
+#[must_use]
+pub fn create_portal(world: &World, pos: BlockPos, axis: Axis) -> Option<BlockPos> {
+    let direction = if axis == Axis::X {
+        Direction::East
+    } else {
+        Direction::South
+    };
+    let max_placeable_y = max_placeable_y(world);
+
+    let origin = match find_portal_spot(world, pos, direction) {
+        Some(origin) => origin,
+        None => {
+            let min_y = (world.get_min_y() + 1).max(70);
+            let max_y = max_placeable_y - 9;
+            if max_y < min_y {
+                return None;
+            }
+            let origin = offset(pos, direction, -1);
+            let origin = BlockPos::new(origin.x(), pos.y().clamp(min_y, max_y), origin.z());
+            build_platform(world, origin, direction);
+            origin
+        }
+    };
+
+    let obsidian = vanilla_blocks::OBSIDIAN.default_state();
+    for i in -1..3 {
+        for height in -1..4 {
+            if i == -1 || i == 2 || height == -1 || height == 3 {
+                let frame = offset(origin, direction, i).offset(0, height, 0);
+                world.set_block(frame, obsidian, UpdateFlags::UPDATE_ALL);
+            }
+        }
+    }
+
+    let portal = portal_state(axis);
+    for i in 0..2 {
+        for height in 0..3 {
+            let inside = offset(origin, direction, i).offset(0, height, 0);
+            world.set_block(inside, portal, UpdateFlags::UPDATE_CLIENTS);
+        }
+    }
+
+    Some(origin)
+}
+
+/// Finds the closest spot near `pos` with solid ground and room for a frame.
+///
+/// Spots with room for a portal on either side as well are preferred.
+fn find_portal_spot(world: &World, pos: BlockPos, direction: Direction) -> Option<BlockPos> {
+    let border = world.border.lock().clone();
+    let within_border =
+        |pos: BlockPos| border.is_within_bounds(f64::from(pos.x()), f64::from(pos.z()));
+    let max_placeable_y = max_placeable_y(world);
+    let distance = |other: BlockPos| {
+        let dx = i64::from(other.x() - pos.x());
+        let dy = i64::from(other.y() - pos.y());
+        let dz = i64::from(other.z() - pos.z());
+        dx * dx + dy * dy + dz * dz
+    };
+
+    // The closest spot with room for portals on both sides, and the closest spot with room for one
+    let mut closest_full: Option<(i64, BlockPos)> = None;
+    let mut closest: Option<(i64, BlockPos)> = None;
+    for dx in -CREATE_RADIUS..=CREATE_RADIUS {
+        for dz in -CREATE_RADIUS..=CREATE_RADIUS {
+            let column = pos.offset(dx, 0, dz);
+            if !within_border(column) || !within_border(offset(column, direction, 1)) {
+                continue;
+            }
+
+            let mut y = max_placeable_y;
+            while y >= world.get_min_y() {
+                let top = BlockPos::new(column.x(), y, column.z());
+                if !can_portal_replace(world.get_block_state(&top)) {
+                    y -= 1;
+                    continue;
+                }
+                let bottom = y;
+                while y > world.get_min_y()
+                    && can_portal_replace(world.get_block_state(&BlockPos::new(
+                        column.x(),
+                        y - 1,
+                        column.z(),
+                    )))
+                {
+                    y -= 1;
+                }
+
+                let gap = bottom - y;
+                if y + 4 <= max_placeable_y && (gap <= 0 || gap >= 3) {
+                    let origin = BlockPos::new(column.x(), y, column.z());
+                    if can_host_frame(world, origin, direction, 0) {
+                        let dist = distance(origin);
+                        if can_host_frame(world, origin, direction, -1)
+                            && can_host_frame(world, origin, direction, 1)
+                            && closest_full.is_none_or(|(best, _)| dist < best)
+                        {
+                            closest_full = Some((dist, origin));
+                        }
+                        if closest_full.is_none() && closest.is_none_or(|(best, _)| dist < best) {
+                            closest = Some((dist, origin));
+                        }
+                    }
+                }
+                y -= 1;
+            }
+        }
+    }
+
+    closest_full.or(closest).map(|(_, origin)| origin)
+}
+
+/// The highest y a portal frame may reach.
+fn max_placeable_y(world: &World) -> i32 {
+    world
+        .get_max_y()
+        .min(world.get_min_y() + world.dimension.logical_height - 1)
+}
+
+/// Clears the space for a portal and puts an obsidian floor under it.
+fn build_platform(world: &World, origin: BlockPos, direction: Direction) {
+    let obsidian = vanilla_blocks::OBSIDIAN.default_state();
+    let air = vanilla_blocks::AIR.default_state();
+    let side = direction.rotate_y_clockwise();
+    for i in -1..2 {
+        for j in 0..2 {
+            for height in -1..3 {
+                let state = if height < 0 { obsidian } else { air };
+                let pos = offset(offset(origin, direction, j), side, i).offset(0, height, 0);
+                world.set_block(pos, state, UpdateFlags::UPDATE_ALL);
+            }
+        }
+    }
+}
+
+/// Whether a frame built at `origin` would stand on solid ground with nothing in the way.
+fn can_host_frame(world: &World, origin: BlockPos, direction: Direction, side_offset: i32) -> bool {
+    let side = direction.rotate_y_clockwise();
+    for i in -1..3 {
+        for height in -1..4 {
+            let pos = offset(offset(origin, direction, i), side, side_offset).offset(0, height, 0);
+            let state = world.get_block_state(&pos);
+            if height < 0 && !state.is_solid() {
+                return false;
+            }
+            if height >= 0 && !can_portal_replace(state) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn portal_state(axis: Axis) -> BlockStateId {
+    vanilla_blocks::NETHER_PORTAL
+        .default_state()
+        .set_value(&BlockStateProperties::HORIZONTAL_AXIS, axis)
+}
+
+fn distance_until_edge_above_frame(world: &World, pos: BlockPos, direction: Direction) -> i32 {
+    for i in 0..=MAX_WIDTH {
+        let pos = offset(pos, direction, i);
+        let state = world.get_block_state(&pos);
+        if !is_empty(state) {
+            return if is_frame(state) { i } else { 0 };
+        }
+        if !is_frame(world.get_block_state(&pos.offset(0, -1, 0))) {
+            return 0;
+        }
+    }
+    0
+}
+
+fn offset(pos: BlockPos, direction: Direction, distance: i32) -> BlockPos {
+    let (dx, dy, dz) = direction.offset();
+    pos.offset(dx * distance, dy * distance, dz * distance)
+}
+
+fn is_frame(state: BlockStateId) -> bool {
+    ptr::eq(state.get_block(), vanilla_blocks::OBSIDIAN)
+}
+
+fn is_empty(state: BlockStateId) -> bool {
+    state.is_air() || ptr::eq(state.get_block(), vanilla_blocks::FIRE) || is_portal(state)
+}
+
+fn can_portal_replace(state: BlockStateId) -> bool {
+    let config = &state.get_block().config;
+    config.replaceable && !config.liquid
+}
@@ -0,0 +1,227 @@
+//! This module contains the vibrations that sculk sensors pick up.
+use steel_utils::BlockPos;
+use steel_utils::math::Vector3;
+
+use crate::behavior::blocks::sculk;
+use crate::world::World;
+
+/// How far a vibration travels to reach a listener, in blocks.
+///
+/// Vanilla: `SculkSensorBlockEntity.VibrationUser.LISTENER_RANGE`
+pub const LISTENER_RANGE: f64 = 8.0;
+
+/// How many ticks a vibration takes to reach the listener that picked it up.
+pub const DETECTION_DELAY: u32 = 2;
+
+/// Something that happened in the world and makes a vibration.
+///
+/// Vanilla: `GameEvent`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VibrationEvent {
+    /// An entity took a step on the ground.
+    Step,
+    /// A block was broken.
+    BlockDestroy,
+    /// A living entity died.
+    EntityDie,
+    /// Something exploded.
+    Explode,
+}
+
+impl VibrationEvent {
+    /// Returns the frequency of the vibration, from 1 to 15. Sculk sensors
+    /// give it off as their signal.
+    ///
+    /// Vanilla: `VibrationSystem.getGameEventFrequency`
+    #[must_use]
+    pub const fn frequency(self) -> u8 {
+        match self {
+            Self::Step => 1,
+            Self::BlockDestroy => 12,
+            Self::EntityDie | Self::Explode => 15,
+        }
+    }
+}
+
+/// A vibration on its way to the listener that picked it up.
+pub struct PendingVibration {
+    /// The position of the listener.
+    pub pos: BlockPos,
+    /// What made the vibration.
+    pub event: VibrationEvent,
+    /// Ticks left until the vibration arrives.
+    delay: u32,
+}
+
+/// The blocks in a world listening for vibrations, and the vibrations
+/// travelling to them.
+///
+/// Vanilla: `EuclideanGameEventListenerRegistry`
+#[derive(Default)]
+pub struct VibrationSystem {
+    listeners: Vec<BlockPos>,
+    pending: Vec<PendingVibration>,
+}
+
+impl VibrationSystem {
+    /// Starts listening for vibrations at `pos`. Does nothing if something
+    /// listens there already.
+    pub fn add_listener(&mut self, pos: BlockPos) {
+        if !self.listeners.contains(&pos) {
+            self.listeners.push(pos);
+        }
+    }
+
+    /// Stops listening for vibrations at `pos`, dropping the vibration
+    /// travelling there.
+    pub fn remove_listener(&mut self, pos: BlockPos) {
+        self.listeners.retain(|listener| *listener != pos);
+        self.pending.retain(|vibration| vibration.pos != pos);
+    }
+
+    /// Returns the listeners within range of a vibration at `source` that
+    /// are free to pick it up, nearest first.
+    #[must_use]
+    pub fn listeners_in_range(&self, source: Vector3<f64>) -> Vec<BlockPos> {
+        let mut listeners: Vec<(f64, BlockPos)> = self
+            .listeners
+            .iter()
+            .filter(|pos| !self.pending.iter().any(|vibration| vibration.pos == **pos))
+            .map(|pos| (distance_sq(*pos, source), *pos))
+            .filter(|(distance_sq, _)| *distance_sq <= LISTENER_RANGE * LISTENER_RANGE)
+            .collect();
+        listeners.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        listeners.into_iter().map(|(_, pos)| pos).collect()
+    }
+
+    /// Sends a vibration from `event` to the listener at `pos`, arriving
+    /// after the detection delay.
+    pub fn enqueue(&mut self, pos: BlockPos, event: VibrationEvent) {
+        self.pending.push(PendingVibration {
+            pos,
+            event,
+            delay: DETECTION_DELAY,
+        });
+    }
+
+    /// Counts every travelling vibration down by one and takes out the ones
+    /// that arrived.
+    pub fn take_arrived(&mut self) -> Vec<PendingVibration> {
+        let mut arrived = Vec::new();
+        self.pending.retain_mut(|vibration| {
+            vibration.delay = vibration.delay.saturating_sub(1);
+            if vibration.delay > 0 {
+                return true;
+            }
+            arrived.push(PendingVibration {
+                pos: vibration.pos,
+                event: vibration.event,
+                delay: 0,
+            });
+            false
+        });
+        arrived
+    }
+}
+
+/// Returns the center of the block at `pos`, where vibrations of the block
+/// start.
+#[must_use]
+pub fn block_center(pos: BlockPos) -> Vector3<f64> {
+    Vector3::new(
+        f64::from(pos.x()) + 0.5,
+        f64::from(pos.y()) + 0.5,
+        f64::from(pos.z()) + 0.5,
+    )
+}
+
+/// Returns the squared distance from the center of the block at `pos` to
+/// `source`.
+fn distance_sq(pos: BlockPos, source: Vector3<f64>) -> f64 {
+    block_center(pos).sub(&source).length_squared()
+}
+
+impl World {
+    /// Starts listening for vibrations at `pos`, like a sculk sensor whose
+    /// block entity got loaded.
+    pub fn add_vibration_listener(&self, pos: BlockPos) {
+        self.vibrations.lock().add_listener(pos);
+    }
+
+    /// Stops listening for vibrations at `pos`.
+    pub fn remove_vibration_listener(&self, pos: BlockPos) {
+        self.vibrations.lock().remove_listener(pos);
+    }
+
+    /// Makes a vibration from `event` at `source`. The nearest sculk sensor in
+    /// range that reacts to it picks it up.
+    ///
+    /// Vanilla: `ServerLevel.gameEvent`
+    pub fn post_vibration(&self, event: VibrationEvent, source: Vector3<f64>) {
+        // Block states are read without the listeners locked, since removing
+        // a sensor's block entity takes the lock
+        let listeners = self.vibrations.lock().listeners_in_range(source);
+        for pos in listeners {
+            let state = self.get_block_state(&pos);
+            if !sculk::is_sculk_sensor(state) {
+                self.remove_vibration_listener(pos);
+                continue;
+            }
+            if sculk::can_receive(self, pos, state, event) {
+                self.vibrations.lock().enqueue(pos, event);
+                return;
+            }
+        }
+    }
+
+    /// Lets the sculk sensors react to the vibrations that reached them.
+    pub(super) fn tick_vibrations(&self) {
+        // Vibrations are taken out first, so sensors can pick up new ones
+        let arrived = self.vibrations.lock().take_arrived();
+        for vibration in arrived {
+            let state = self.get_block_state(&vibration.pos);
+            if sculk::is_sculk_sensor(state)
+                && sculk::can_receive(self, vibration.pos, state, vibration.event)
+            {
+                sculk::activate(self, vibration.pos, state, vibration.event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_free_listener_comes_first() {
+        let mut system = VibrationSystem::default();
+        system.add_listener(BlockPos::new(6, 0, 0));
+        system.add_listener(BlockPos::new(2, 0, 0));
+        system.add_listener(BlockPos::new(20, 0, 0));
+        let source = Vector3::new(0.5, 0.5, 0.5);
+        assert_eq!(
+            system.listeners_in_range(source),
+            vec![BlockPos::new(2, 0, 0), BlockPos::new(6, 0, 0)]
+        );
+
+        system.enqueue(BlockPos::new(2, 0, 0), VibrationEvent::Step);
+        assert_eq!(
+            system.listeners_in_range(source),
+            vec![BlockPos::new(6, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn vibrations_arrive_after_the_delay() {
+        let mut system = VibrationSystem::default();
+        system.add_listener(BlockPos::new(0, 0, 0));
+        system.enqueue(BlockPos::new(0, 0, 0), VibrationEvent::Explode);
+        for _ in 1..DETECTION_DELAY {
+            assert!(system.take_arrived().is_empty());
+        }
+        let arrived = system.take_arrived();
+        assert_eq!(arrived.len(), 1);
+        assert_eq!(arrived[0].event.frequency(), 15);
+    }
+}
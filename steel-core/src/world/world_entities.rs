@@ -1,30 +1,2031 @@
 //! This module contains the implementation of the world's entity-related methods.
+use std::io::Cursor;
+use std::mem;
+use std::ptr;
 use std::sync::Arc;
 
+use simdnbt::borrow::{NbtCompound as NbtCompoundView, read_compound as read_borrowed_compound};
+use simdnbt::owned::NbtCompound;
 use steel_protocol::packets::game::{
-    CAddEntity, CGameEvent, CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo, GameEventType,
+    CAddEntity, CGameEvent, CRemoveEntities, CSetEntityData, CSetPassengers, GameEventType,
+    SoundSource,
 };
-use steel_registry::{REGISTRY, vanilla_entities};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::game_rules::GameRuleValue;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_game_rules::{ENTITY_DROPS, SPAWN_MOBS, SPAWN_MONSTERS};
+use steel_registry::{REGISTRY, sound_events, vanilla_blocks, vanilla_entities, vanilla_items};
+use steel_utils::locks::SyncMutex;
+use steel_utils::math::Vector3;
+use steel_utils::types::{Difficulty, GameType, UpdateFlags};
+use steel_utils::{BlockPos, BlockStateId, ChunkPos, Identifier};
 use tokio::time::Instant;
+use uuid::Uuid;
 
+use crate::behavior::blocks::detector_rail_block;
+use crate::behavior::{BLOCK_BEHAVIORS, InteractionResult};
+use crate::chunk_saver::PersistentEntity;
+use crate::entity::armor_stand::ArmorStandHit;
+use crate::entity::boat::WoodType;
+use crate::entity::ender_dragon::DragonAction;
+use crate::entity::falling_block::FallingBlockEnd;
+use crate::entity::ghast_fireball::{self, FireballFlight};
+use crate::entity::iron_golem::{GolemAttack, GolemTarget};
+use crate::entity::minecart::{self, MinecartHit, MinecartKind};
+use crate::entity::registry::ENTITIES;
+use crate::entity::storage::{self, EntityList, EntityPairing};
+use crate::entity::{
+    self, AttackableMob, BoatEntity, ChestMinecartEntity, ExperienceOrbEntity, FallingBlockEntity,
+    FireworkRocketEntity, GenericEntity, HopperMinecartEntity, IronGolemEntity, ItemFrameEntity,
+    MinecartEntity, PaintingEntity, PrimedTntEntity, StoredEntity, VehicleInput, VillagerEntity,
+    ZombieEntity, ender_dragon, experience_orb, iron_golem, lightning_bolt, zombie,
+};
+use crate::inventory::MerchantMenuProvider;
+use crate::inventory::container::calculate_redstone_signal_from_container;
+use crate::inventory::lock::GenericContainer;
+use crate::inventory::merchant_menu::MerchantState;
+use crate::player::movement;
+use crate::player::stats::{CustomStat, StatKey};
+use crate::world::explosion::{Explosion, ExplosionSource};
+use crate::world::vibrations::VibrationEvent;
 use crate::{player::Player, world::World};
 
+/// The damage a player's hit deals without a weapon.
+///
+/// Vanilla: `Attributes.ATTACK_DAMAGE` of players
+const PLAYER_ATTACK_DAMAGE: f32 = 1.0;
+/// The damage a fireball a player hit back deals to a ghast, enough to
+/// kill it outright.
+///
+/// Vanilla: `Ghast.hurtServer`
+const REFLECTED_FIREBALL_DAMAGE: f32 = 1000.0;
+/// The advancement for killing a ghast with its own fireball.
+const RETURN_TO_SENDER: Identifier = Identifier::vanilla_static("nether/return_to_sender");
+
+/// What hurting a mob did.
+enum MobHurt<T> {
+    /// There is no mob with the network id.
+    Missing,
+    /// The mob is still alive, whether the hit hurt it or not.
+    Survived,
+    /// The mob died and was taken out of the world.
+    Died(T),
+}
+
 impl World {
-    /// Removes a player from the world.
+    /// Removes a player from the world and the tab list, as they leave the
+    /// server.
     pub async fn remove_player(self: &Arc<Self>, player: Arc<Player>) {
+        let start = Instant::now();
+        if self.detach_player(&player).await {
+            self.tab_list.remove(player.gameprofile.id);
+            player.cleanup();
+            log::info!(
+                "Player {} removed in {:?}",
+                player.gameprofile.id,
+                start.elapsed()
+            );
+        }
+    }
+
+    /// Takes a player out of the world without them leaving the server, like
+    /// when they move to another world. They stay in the tab list, which is
+    /// the same in every world.
+    ///
+    /// Returns `false` if the player wasn't in this world.
+    pub async fn detach_player(self: &Arc<Self>, player: &Arc<Player>) -> bool {
         let uuid = player.gameprofile.id;
         let entity_id = player.id;
 
-        if self.players.remove(&uuid).await.is_some() {
-            let start = Instant::now();
+        if self.players.remove(&uuid).await.is_none() {
+            return false;
+        }
 
-            self.player_area_map.on_player_leave(&player);
-            self.broadcast_to_all(CRemoveEntities::single(entity_id));
-            self.broadcast_to_all(CRemovePlayerInfo::single(uuid));
+        self.player_area_map.on_player_leave(player);
+        self.leave_vehicle(player);
+        self.broadcast_to_all(CRemoveEntities::single(entity_id));
 
-            self.chunk_map.remove_player(&player);
-            player.cleanup();
-            log::info!("Player {uuid} removed in {:?}", start.elapsed());
+        self.chunk_map.remove_player(player);
+        true
+    }
+
+    /// Spawns an entity without behaviour of its own, applying `nbt` over
+    /// its defaults like vanilla's `EntityType.create`. Returns `false` if the
+    /// NBT gives it the UUID of an entity that already exists.
+    pub fn spawn_entity(
+        &self,
+        id: i32,
+        entity_type: EntityTypeRef,
+        pos: Vector3<f64>,
+        nbt: Option<&NbtCompound>,
+    ) -> bool {
+        let mut entity = GenericEntity::new(id, entity_type, pos);
+        if let Some(nbt) = nbt {
+            entity.read_nbt(nbt);
+        }
+        self.add_entity(&self.entities.generic, entity)
+    }
+
+    /// Adds `entity` to `list`, showing it to the players tracking the chunk
+    /// it is in. Returns `false` if its UUID is taken by an entity that
+    /// already exists.
+    ///
+    /// Vanilla: `ServerLevel.addFreshEntity`
+    pub fn add_entity<T: StoredEntity>(&self, list: &SyncMutex<EntityList<T>>, entity: T) -> bool {
+        self.insert_entity(&mut list.lock(), entity)
+    }
+
+    /// Like [`World::add_entity`], for a list that is already locked.
+    pub fn insert_entity<T: StoredEntity>(&self, list: &mut EntityList<T>, entity: T) -> bool {
+        let uuid = entity.get_uuid();
+        if self.players.get_by_uuid(&uuid).is_some() || !self.entities.claim_uuid(uuid) {
+            return false;
+        }
+
+        let mut pairing = EntityPairing::new();
+        entity.pair(self, &mut pairing);
+        let chunk = storage::chunk_at(entity.get_position());
+        for player_id in self.player_area_map.get_tracking_players(chunk) {
+            if let Some(player) = self.players.get_by_entity_id(player_id) {
+                pairing.send_to(&player);
+            }
+        }
+        list.insert(entity);
+        true
+    }
+
+    /// Removes the entities taken out of the world this tick for the players
+    /// tracking them, and moves the entities that walked into another chunk
+    /// over to the players tracking that one.
+    ///
+    /// Vanilla: `ChunkMap.tick`, as far as it updates the entities' trackers
+    pub(super) fn update_entity_tracking(&self) {
+        let mut released = Vec::new();
+        for list in self.entities.lists() {
+            list.update_tracking(self, &mut released);
+        }
+        self.entities.release_uuids(&released);
+    }
+
+    /// Shows `player` the entities in the chunks in `added` they started
+    /// tracking, and removes the ones in the chunks in `removed` they
+    /// stopped tracking.
+    ///
+    /// Vanilla: `ChunkMap.updatePlayerStatus` via `TrackedEntity.updatePlayer`
+    pub(crate) fn update_entity_view(
+        &self,
+        player: &Player,
+        added: &[ChunkPos],
+        removed: &[ChunkPos],
+    ) {
+        let lists = self.entities.lists();
+        let mut pairing = EntityPairing::new();
+        for chunk in added {
+            for list in &lists {
+                list.pair_chunk(self, *chunk, &mut pairing);
+            }
+        }
+        pairing.send_to(player);
+
+        let mut entity_ids = Vec::new();
+        for chunk in removed {
+            for list in &lists {
+                list.ids_in_chunk(*chunk, &mut entity_ids);
+            }
+        }
+        if !entity_ids.is_empty() {
+            player
+                .connection
+                .send_packet(CRemoveEntities { entity_ids });
+        }
+    }
+
+    /// Ticks the armor stands, letting them fall.
+    pub(super) fn tick_armor_stands(&self) {
+        for stand in self.entities.armor_stands.lock().iter_mut() {
+            stand.tick(self);
+        }
+    }
+
+    /// Right-clicks the armor stand with network id `entity_id` at `hit_y`
+    /// blocks above its feet, holding `held`.
+    ///
+    /// Returns [`InteractionResult::Pass`] if there is no such stand.
+    pub fn interact_at_armor_stand(
+        &self,
+        entity_id: i32,
+        held: &mut ItemStack,
+        hit_y: f64,
+        infinite_materials: bool,
+    ) -> InteractionResult {
+        let mut armor_stands = self.entities.armor_stands.lock();
+        let Some(stand) = armor_stands.get_mut(entity_id) else {
+            return InteractionResult::Pass;
+        };
+        stand.interact_at(self, held, hit_y, infinite_materials)
+    }
+
+    /// Hits the armor stand with network id `entity_id`, breaking it on the
+    /// second quick hit. Returns `false` if there is no such stand.
+    pub fn attack_armor_stand(&self, player: &Player, entity_id: i32) -> bool {
+        let mut armor_stands = self.entities.armor_stands.lock();
+        let Some(stand) = armor_stands
+            .get_mut(entity_id)
+            .filter(|stand| !stand.is_marker())
+        else {
+            return false;
+        };
+
+        let hit = stand.hurt(self, player.has_infinite_materials());
+        if hit == ArmorStandHit::Wobbled {
+            return true;
+        }
+
+        let Some(mut stand) = armor_stands.remove(entity_id) else {
+            return true;
+        };
+        let pos = stand.base.position.lock().to_block_pos();
+        self.play_sound(
+            sound_events::ENTITY_ARMOR_STAND_BREAK,
+            SoundSource::Neutral,
+            pos,
+            1.0,
+            1.0,
+            None,
+        );
+        if hit == ArmorStandHit::Broken {
+            for item in stand.take_drops() {
+                self.drop_item_stack(pos, item);
+            }
+        }
+        true
+    }
+
+    /// Launches a firework rocket that boosts `player` while they glide.
+    ///
+    /// Vanilla: `FireworkRocketItem.use`
+    pub fn spawn_firework_rocket(&self, player: &Player, item: &ItemStack, flight_duration: u8) {
+        let rocket =
+            FireworkRocketEntity::attached(entity::next_entity_id(), player, item, flight_duration);
+        self.play_sound(
+            sound_events::ENTITY_FIREWORK_ROCKET_LAUNCH,
+            SoundSource::Ambient,
+            rocket.position.to_block_pos(),
+            3.0,
+            1.0,
+            None,
+        );
+        self.add_entity(&self.entities.firework_rockets, rocket);
+    }
+
+    /// Ticks the firework rockets, removing the ones that burst.
+    pub(super) fn tick_firework_rockets(&self) {
+        self.entities
+            .firework_rockets
+            .lock()
+            .retain_mut(|rocket| rocket.tick(self));
+    }
+
+    /// Drops `amount` experience at `position`, split into orbs of the values
+    /// that have their own icon.
+    ///
+    /// Vanilla: `ExperienceOrb.award`
+    pub fn spawn_experience_orbs(&self, position: Vector3<f64>, amount: i32) {
+        for value in experience_orb::split_value(amount) {
+            let orb = ExperienceOrbEntity::new(entity::next_entity_id(), position, value);
+            self.add_entity(&self.entities.experience_orbs, orb);
+        }
+    }
+
+    /// Ticks the experience orbs, removing the ones that were picked up or
+    /// despawned and merging the ones close to each other.
+    pub(super) fn tick_experience_orbs(&self) {
+        let mut experience_orbs = self.entities.experience_orbs.lock();
+        experience_orbs.retain_mut(|orb| orb.tick(self));
+
+        // Vanilla: `ExperienceOrb.scanForMerges`
+        let orb_ids: Vec<i32> = experience_orbs.iter().map(|orb| orb.id).collect();
+        for &orb_id in &orb_ids {
+            if !experience_orbs
+                .get(orb_id)
+                .is_some_and(ExperienceOrbEntity::scans_for_merges)
+            {
+                continue;
+            }
+            for &other_id in &orb_ids {
+                let mergeable = match (experience_orbs.get(orb_id), experience_orbs.get(other_id)) {
+                    (Some(orb), Some(other)) => orb.can_merge(other),
+                    _ => false,
+                };
+                if mergeable
+                    && let Some(merged) = experience_orbs.remove(other_id)
+                    && let Some(orb) = experience_orbs.get_mut(orb_id)
+                {
+                    orb.merge(&merged);
+                }
+            }
+        }
+
+        for orb in experience_orbs.iter_mut() {
+            if let Some(data) = orb.pack_dirty_data() {
+                self.broadcast_to_trackers(orb.position(), CSetEntityData::new(orb.id, data));
+            }
+        }
+    }
+
+    /// Puts a minecart of `kind` at rest at `position`, like one placed on a
+    /// rail by a player.
+    pub fn spawn_minecart(&self, kind: MinecartKind, position: Vector3<f64>) {
+        let id = entity::next_entity_id();
+        match kind {
+            MinecartKind::Rideable => {
+                let cart = MinecartEntity::new(id, vanilla_entities::MINECART, position);
+                self.add_entity(&self.entities.minecarts, cart);
+            }
+            MinecartKind::Chest => {
+                let cart = ChestMinecartEntity::new(id, position);
+                self.add_entity(&self.entities.chest_minecarts, cart);
+            }
+            MinecartKind::Hopper => {
+                let cart = HopperMinecartEntity::new(id, position);
+                self.add_entity(&self.entities.hopper_minecarts, cart);
+            }
+        }
+    }
+
+    /// Ticks the minecarts, rolling them along rails, letting hopper
+    /// minecarts pull in items and bumping minecarts into each other. Detector
+    /// rails they roll onto are pressed once every minecart moved.
+    pub(super) fn tick_minecarts(&self) {
+        let mut rails = Vec::new();
+        let mut seats = Vec::new();
+        {
+            let mut rideable_minecarts = self.entities.minecarts.lock();
+            let mut chest_minecarts = self.entities.chest_minecarts.lock();
+            let mut hopper_minecarts = self.entities.hopper_minecarts.lock();
+            for cart in rideable_minecarts.iter_mut() {
+                let slowdown = cart.natural_slowdown();
+                cart.tick(self, slowdown);
+            }
+            for cart in chest_minecarts.iter_mut() {
+                cart.tick(self);
+            }
+            for cart in hopper_minecarts.iter_mut() {
+                cart.tick(self);
+            }
+
+            let mut minecarts: Vec<&mut MinecartEntity> = rideable_minecarts
+                .iter_mut()
+                .chain(chest_minecarts.iter_mut().map(|cart| &mut cart.minecart))
+                .chain(hopper_minecarts.iter_mut().map(|cart| &mut cart.minecart))
+                .collect();
+            for index in 1..minecarts.len() {
+                let (before, after) = minecarts.split_at_mut(index);
+                let minecart = &mut *after[0];
+                for other in before {
+                    minecart.collide(other);
+                }
+            }
+
+            for minecart in minecarts {
+                rails.extend(minecart.rails_pos);
+                if let Some(data) = minecart.pack_dirty_data() {
+                    self.broadcast_to_trackers(
+                        minecart.position(),
+                        CSetEntityData::new(minecart.base.id, data),
+                    );
+                }
+                let seat = minecart.seat_position();
+                seats.extend(minecart.base.passengers.iter().map(|uuid| (*uuid, seat)));
+            }
+        }
+
+        self.carry_passengers(seats);
+        for pos in rails {
+            let state = self.get_block_state(&pos);
+            if ptr::eq(state.get_block(), vanilla_blocks::DETECTOR_RAIL)
+                && !state.get_value(&BlockStateProperties::POWERED)
+            {
+                detector_rail_block::check_pressed(state, self, pos);
+            }
+        }
+    }
+
+    /// Returns the comparator signal of the first minecart touching `aabb`:
+    /// how full it is, or 0 if it carries nothing. Returns `None` if no
+    /// minecart touches it.
+    #[must_use]
+    pub fn minecart_signal(&self, aabb: &AABBd) -> Option<i32> {
+        let chest = self
+            .entities
+            .chest_minecarts
+            .lock()
+            .iter()
+            .find(|cart| cart.minecart.bounding_box().intersects(aabb))
+            .map(|cart| calculate_redstone_signal_from_container(&*cart.items().lock()));
+        chest
+            .or_else(|| {
+                self.entities
+                    .hopper_minecarts
+                    .lock()
+                    .iter()
+                    .find(|cart| cart.minecart.bounding_box().intersects(aabb))
+                    .map(|cart| calculate_redstone_signal_from_container(&*cart.items().lock()))
+            })
+            .or_else(|| {
+                self.entities
+                    .minecarts
+                    .lock()
+                    .iter()
+                    .any(|cart| cart.bounding_box().intersects(aabb))
+                    .then_some(0)
+            })
+    }
+
+    /// Returns the chest of the chest minecart with network id `entity_id`,
+    /// if there is one.
+    #[must_use]
+    pub fn chest_minecart_items(&self, entity_id: i32) -> Option<GenericContainer> {
+        self.entities
+            .chest_minecarts
+            .lock()
+            .get(entity_id)
+            .map(|cart| cart.items().clone())
+    }
+
+    /// Hits the minecart with network id `entity_id`, breaking it once it
+    /// took enough damage. Returns `false` if there is no such minecart.
+    pub fn attack_minecart(&self, player: &Player, entity_id: i32) -> bool {
+        // TODO: Use the player's attack damage once held weapons modify it
+        let damage = 1.0;
+        let infinite_materials = player.has_infinite_materials();
+
+        let (position, drops, passengers) = {
+            let mut rideable_minecarts = self.entities.minecarts.lock();
+            let mut chest_minecarts = self.entities.chest_minecarts.lock();
+            let mut hopper_minecarts = self.entities.hopper_minecarts.lock();
+            let hit = if let Some(cart) = rideable_minecarts.get_mut(entity_id) {
+                cart.hurt(damage, infinite_materials)
+            } else if let Some(cart) = chest_minecarts.get_mut(entity_id) {
+                cart.minecart.hurt(damage, infinite_materials)
+            } else if let Some(cart) = hopper_minecarts.get_mut(entity_id) {
+                cart.minecart.hurt(damage, infinite_materials)
+            } else {
+                return false;
+            };
+            if hit == MinecartHit::Damaged {
+                return true;
+            }
+
+            let broken = hit == MinecartHit::Broken;
+            if let Some(cart) = rideable_minecarts.remove(entity_id) {
+                let drops = if broken {
+                    vec![ItemStack::new(&vanilla_items::ITEMS.minecart)]
+                } else {
+                    Vec::new()
+                };
+                (cart.position(), drops, cart.base.passengers)
+            } else if let Some(mut cart) = chest_minecarts.remove(entity_id) {
+                let drops = cart.take_drops(broken);
+                (cart.minecart.position(), drops, Vec::new())
+            } else if let Some(mut cart) = hopper_minecarts.remove(entity_id) {
+                let drops = cart.take_drops(broken);
+                (cart.minecart.position(), drops, Vec::new())
+            } else {
+                return true;
+            }
+        };
+
+        // The contents spill out even when a creative player removes the cart
+        self.drop_passengers(&passengers);
+        let pos = minecart::block_pos_at(position);
+        for item in drops {
+            self.drop_item_stack(pos, item);
+        }
+        true
+    }
+
+    /// Puts a boat of `wood_type` at `position` facing `yaw`, like one placed
+    /// by a player.
+    pub fn spawn_boat(&self, wood_type: WoodType, position: Vector3<f64>, yaw: f32) {
+        let boat = BoatEntity::new(entity::next_entity_id(), wood_type, position, yaw);
+        self.add_entity(&self.entities.boats, boat);
+    }
+
+    /// Ticks the boats, carrying their passengers along. Boats that crashed
+    /// break into planks and sticks and let their passengers off.
+    pub(super) fn tick_boats(&self) {
+        let mut seats = Vec::new();
+        let mut crashed = Vec::new();
+        self.entities.boats.lock().retain_mut(|boat| {
+            if boat.tick(self) {
+                let passengers = mem::take(&mut boat.base.passengers);
+                crashed.push((boat.position(), boat.crash_drops(), passengers));
+                return false;
+            }
+            if let Some(data) = boat.pack_dirty_data() {
+                self.broadcast_to_trackers(
+                    boat.position(),
+                    CSetEntityData::new(boat.base.id, data),
+                );
+            }
+            let seat = boat.seat_position();
+            seats.extend(boat.base.passengers.iter().map(|uuid| (*uuid, seat)));
+            true
+        });
+
+        self.carry_passengers(seats);
+        for (position, drops, passengers) in crashed {
+            self.drop_passengers(&passengers);
+            if self.get_game_rule(ENTITY_DROPS) != GameRuleValue::Bool(true) {
+                continue;
+            }
+            let pos = position.to_block_pos();
+            for item in drops {
+                self.drop_item_stack(pos, item);
+            }
+        }
+    }
+
+    /// Runs `f` on the base of the boat or rideable minecart with network id
+    /// `entity_id`, along with where its passenger sits. Returns `None` if
+    /// there is no such vehicle.
+    fn with_vehicle<R>(
+        &self,
+        entity_id: i32,
+        f: impl FnOnce(&mut GenericEntity, Vector3<f64>) -> R,
+    ) -> Option<R> {
+        if let Some(boat) = self.entities.boats.lock().get_mut(entity_id) {
+            let seat = boat.seat_position();
+            return Some(f(&mut boat.base, seat));
+        }
+        let mut minecarts = self.entities.minecarts.lock();
+        let cart = minecarts.get_mut(entity_id)?;
+        let seat = cart.seat_position();
+        Some(f(&mut cart.base, seat))
+    }
+
+    /// The packet telling clients which players ride the entity with network
+    /// id `vehicle_id`.
+    pub(crate) fn passengers_packet(&self, vehicle_id: i32, passengers: &[Uuid]) -> CSetPassengers {
+        CSetPassengers {
+            vehicle_id,
+            passenger_ids: passengers
+                .iter()
+                .filter_map(|uuid| self.players.get_by_uuid(uuid))
+                .map(|player| player.id)
+                .collect(),
+        }
+    }
+
+    /// Lets `player` ride the boat or minecart with network id `entity_id`,
+    /// if no one rides it yet. Sneaking players don't get in.
+    ///
+    /// Vanilla: `Entity.startRiding`
+    pub fn start_riding(&self, player: &Player, entity_id: i32) -> InteractionResult {
+        if player.is_secondary_use_active() || player.vehicle().is_some() {
+            return InteractionResult::Pass;
+        }
+        // TODO: Horses only carry players once they are tamed and saddled,
+        // once they exist
+        let uuid = player.gameprofile.id;
+        let mounted = self.with_vehicle(entity_id, |base, seat| {
+            (base.passengers.is_empty() && base.add_passenger(uuid))
+                .then(|| (seat, base.passengers.clone()))
+        });
+        let Some((seat, passengers)) = mounted.flatten() else {
+            return InteractionResult::Pass;
+        };
+
+        *player.position.lock() = seat;
+        player.set_vehicle(Some(entity_id));
+        self.broadcast_to_trackers(seat, self.passengers_packet(entity_id, &passengers));
+        InteractionResult::Success
+    }
+
+    /// Steers what `player` rides with the keys they hold down: boats paddle
+    /// and turn, minecarts get a push the way the player walks.
+    pub fn steer_vehicle(&self, player: &Player, input: VehicleInput) {
+        let Some(vehicle_id) = player.vehicle() else {
+            return;
+        };
+        if let Some(boat) = self.entities.boats.lock().get_mut(vehicle_id) {
+            boat.set_input(input);
+            return;
+        }
+        if let Some(cart) = self.entities.minecarts.lock().get_mut(vehicle_id) {
+            let (yaw, _) = player.rotation.load();
+            cart.set_input(input, yaw);
+        }
+    }
+
+    /// Lets `player` off what they ride and puts them down next to it.
+    ///
+    /// Vanilla: `Entity.stopRiding`
+    pub fn stop_riding(&self, player: &Player) {
+        if let Some(vehicle) = self.leave_vehicle(player) {
+            let spot = self.dismount_location(vehicle);
+            let (yaw, pitch) = player.rotation.load();
+            player.teleport(spot.x, spot.y, spot.z, yaw, pitch);
+        }
+    }
+
+    /// Takes `player` off what they ride, if anything. Returns the box of
+    /// the vehicle they left.
+    fn leave_vehicle(&self, player: &Player) -> Option<AABBd> {
+        let vehicle_id = player.vehicle()?;
+        player.set_vehicle(None);
+        let uuid = player.gameprofile.id;
+        let (vehicle, seat, passengers) = self.with_vehicle(vehicle_id, |base, seat| {
+            base.remove_passenger(uuid);
+            (base.bounding_box(), seat, base.passengers.clone())
+        })?;
+        self.broadcast_to_trackers(seat, self.passengers_packet(vehicle_id, &passengers));
+        Some(vehicle)
+    }
+
+    /// Lets the players in `passengers` off a vehicle that was removed. Clients
+    /// let them off along with removing it.
+    pub(super) fn drop_passengers(&self, passengers: &[Uuid]) {
+        for player in passengers
+            .iter()
+            .filter_map(|uuid| self.players.get_by_uuid(uuid))
+        {
+            player.set_vehicle(None);
+        }
+    }
+
+    /// Moves the passengers to their seats. Clients place passengers on their
+    /// vehicle themselves, so only the server's copy of their position moves.
+    fn carry_passengers(&self, seats: Vec<(Uuid, Vector3<f64>)>) {
+        for (passenger, seat) in seats {
+            if let Some(player) = self.players.get_by_uuid(&passenger) {
+                *player.position.lock() = seat;
+            }
+        }
+    }
+
+    /// Returns a spot right next to the vehicle taking up `vehicle` where a
+    /// player fits, or the top of the vehicle if there is none.
+    ///
+    /// Vanilla: `AbstractBoat.getDismountLocationForPassenger`
+    fn dismount_location(&self, vehicle: AABBd) -> Vector3<f64> {
+        let center_x = f64::midpoint(vehicle.min_x, vehicle.max_x);
+        let center_z = f64::midpoint(vehicle.min_z, vehicle.max_z);
+        let reach = (vehicle.max_x - vehicle.min_x + movement::PLAYER_WIDTH) / 2.0 + 0.1;
+        [(1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0)]
+            .into_iter()
+            .map(|(dx, dz)| {
+                Vector3::new(center_x + dx * reach, vehicle.min_y, center_z + dz * reach)
+            })
+            .find(|spot| !movement::is_in_collision(self, *spot, false))
+            .unwrap_or_else(|| Vector3::new(center_x, vehicle.max_y, center_z))
+    }
+
+    /// Spawns TNT lit in the block at `pos` that explodes after `fuse` ticks.
+    pub fn spawn_primed_tnt(&self, pos: BlockPos, fuse: u8) {
+        let tnt = PrimedTntEntity::new(entity::next_entity_id(), pos, fuse);
+        self.add_entity(&self.entities.primed_tnt, tnt);
+    }
+
+    /// Ticks the primed TNT, setting off the explosions of TNT whose fuse ran
+    /// out once every TNT moved.
+    pub(super) fn tick_primed_tnt(&self) {
+        let mut explosions = Vec::new();
+        self.entities.primed_tnt.lock().retain_mut(|tnt| {
+            if let Some(explosion) = tnt.tick(self) {
+                explosions.push(explosion);
+                return false;
+            }
+            if let Some(data) = tnt.pack_dirty_data() {
+                self.broadcast_to_trackers(tnt.position(), CSetEntityData::new(tnt.id, data));
+            }
+            true
+        });
+
+        // Explosions push and light other TNT, so they go off without the lock
+        for explosion in explosions {
+            explosion.explode(self);
+        }
+    }
+
+    /// Turns the block `state` at `pos` into a falling block, leaving air
+    /// behind.
+    ///
+    /// Vanilla: `FallingBlockEntity.fall`
+    pub fn spawn_falling_block(&self, pos: BlockPos, state: BlockStateId) {
+        let block = FallingBlockEntity::new(entity::next_entity_id(), pos, state);
+        self.set_block(
+            pos,
+            vanilla_blocks::AIR.default_state(),
+            UpdateFlags::UPDATE_ALL,
+        );
+        self.add_entity(&self.entities.falling_blocks, block);
+    }
+
+    /// Ticks the falling blocks in loaded chunks, placing the ones that
+    /// landed once every block moved.
+    pub(super) fn tick_falling_blocks(&self) {
+        let mut ended = Vec::new();
+        self.entities.falling_blocks.lock().retain_mut(|block| {
+            // Blocks in chunks that are still loading wait, so they don't fall through them
+            let chunk = Self::chunk_pos_for_block(&block.position().to_block_pos());
+            if self.chunk_map.with_full_chunk(&chunk, |_| ()).is_none() {
+                return true;
+            }
+            let Some(end) = block.tick(self) else {
+                return true;
+            };
+            ended.push((block.state, end));
+            false
+        });
+
+        // Placed blocks update their neighbors, which can make more blocks
+        // fall, so they are placed without the lock
+        for (state, end) in ended {
+            match end {
+                FallingBlockEnd::Land(pos) => self.land_falling_block(pos, state),
+                FallingBlockEnd::Break(pos) => self.break_falling_block(pos, state),
+            }
+        }
+    }
+
+    /// Places a falling block that landed at `pos`, or breaks it if the
+    /// block there can't be replaced.
+    fn land_falling_block(&self, pos: BlockPos, state: BlockStateId) {
+        let replaced = self.get_block_state(&pos);
+        if self.set_block(pos, state, UpdateFlags::UPDATE_ALL) {
+            BLOCK_BEHAVIORS
+                .get_behavior(state.get_block())
+                .on_land(state, self, pos, replaced);
+        } else {
+            self.break_falling_block(pos, state);
+        }
+    }
+
+    /// Drops the item of a falling block that broke at `pos`.
+    ///
+    /// Vanilla: `FallingBlockEntity.spawnAtLocation`
+    fn break_falling_block(&self, pos: BlockPos, state: BlockStateId) {
+        if self.get_game_rule(ENTITY_DROPS) != GameRuleValue::Bool(true) {
+            return;
+        }
+        if let Some(item) = REGISTRY.items.by_key(&state.get_block().key) {
+            self.drop_item_stack(pos, ItemStack::new(item));
+        }
+    }
+
+    /// Saves the entities in the chunk at `chunk_pos`, to store them with it.
+    /// Also returns whether they changed since they were last stored, in
+    /// which case the chunk has to be saved even if its blocks didn't change.
+    pub(crate) fn save_entities(&self, chunk_pos: ChunkPos) -> (Vec<PersistentEntity>, bool) {
+        let mut saved = Vec::new();
+        for list in self.entities.lists() {
+            list.save_chunk(chunk_pos, &mut saved);
+        }
+        let changed = self.entities.mark_stored(chunk_pos, &saved);
+        (saved, changed)
+    }
+
+    /// Adds the entities stored with the chunk at `chunk_pos`, which is being
+    /// loaded.
+    pub(crate) fn load_entities(&self, chunk_pos: ChunkPos, entities: &[PersistentEntity]) {
+        self.entities.mark_stored(chunk_pos, entities);
+        for persistent in entities {
+            let Some(entity_type) = REGISTRY.entity_types.by_key(&persistent.entity_type.path)
+            else {
+                log::warn!("Skipping stored entity {:?}", persistent.entity_type);
+                continue;
+            };
+            let Ok(nbt) = read_borrowed_compound(&mut Cursor::new(&persistent.nbt_data)) else {
+                continue;
+            };
+            let nbt: NbtCompoundView<'_, '_> = (&nbt).into();
+            let nbt = nbt.to_owned();
+            let Some(pos) = entity::position_from_nbt(&nbt) else {
+                continue;
+            };
+
+            let id = entity::next_entity_id();
+            let added = ENTITIES
+                .create(entity_type, self, id, pos, Some(&nbt))
+                .unwrap_or_else(|| self.spawn_entity(id, entity_type, pos, Some(&nbt)));
+            if !added {
+                log::warn!("Skipping stored {} with a duplicate UUID", entity_type.key);
+            }
+        }
+    }
+
+    /// Removes the entities in the chunk at `chunk_pos` once it unloaded.
+    /// They were saved with the chunk, the last time
+    /// [`World::save_entities`] reported them as changed.
+    pub(crate) fn unload_entities(&self, chunk_pos: ChunkPos) {
+        for list in self.entities.lists() {
+            list.unload_chunk(chunk_pos);
+        }
+        self.entities.forget_stored(chunk_pos);
+    }
+
+    /// Ticks the lightning bolts, striking the entities around the ones that
+    /// just came down and removing the ones that faded.
+    pub(super) fn tick_lightning_bolts(&self) {
+        let mut strikes = Vec::new();
+        self.entities.lightning_bolts.lock().retain_mut(|bolt| {
+            let alive = bolt.tick(self);
+            if bolt.has_just_struck() {
+                strikes.push(bolt.strike_box());
+            }
+            alive
+        });
+
+        // Striking turns entities into others, so it happens without the lock
+        for area in strikes {
+            self.strike_entities(&area);
+        }
+    }
+
+    /// Strikes the entities in `area` with lightning: they get hurt,
+    /// creepers get charged, and villagers and pigs turn into witches and
+    /// zombified piglins.
+    ///
+    /// Vanilla: `Entity.thunderHit` and its overrides
+    // TODO: Set struck entities on fire, and turn red mooshrooms brown once
+    // mooshrooms keep their variant
+    fn strike_entities(&self, area: &AABBd) {
+        self.players.iter_players(|_, player| {
+            if player.game_mode.load() != GameType::Spectator
+                && player.bounding_box().intersects(area)
+            {
+                player.take_damage(lightning_bolt::DAMAGE);
+            }
+            true
+        });
+
+        let struck_villagers: Vec<i32> = self
+            .entities
+            .villagers
+            .lock()
+            .iter()
+            .filter(|villager| villager.bounding_box().intersects(area))
+            .map(|villager| villager.base.id)
+            .collect();
+        for entity_id in struck_villagers {
+            self.villager_struck_by_lightning(entity_id);
+        }
+
+        for zombie in self.entities.zombies.lock().iter_mut() {
+            if zombie.bounding_box().intersects(area) {
+                zombie.hurt(lightning_bolt::DAMAGE);
+            }
+        }
+        for skeleton in self.entities.skeletons.lock().iter_mut() {
+            if skeleton.bounding_box().intersects(area) {
+                skeleton.hurt(lightning_bolt::DAMAGE);
+            }
+        }
+        for creeper in self.entities.creepers.lock().iter_mut() {
+            if creeper.bounding_box().intersects(area) {
+                creeper.charge();
+                creeper.hurt(lightning_bolt::DAMAGE);
+            }
+        }
+        for slime in self.entities.slimes.lock().iter_mut() {
+            if slime.bounding_box().intersects(area) {
+                slime.hurt(lightning_bolt::DAMAGE);
+            }
+        }
+        for golem in self.entities.iron_golems.lock().iter_mut() {
+            if golem.bounding_box().intersects(area) {
+                golem.hurt(lightning_bolt::DAMAGE);
+            }
+        }
+        for enderman in self.entities.endermen.lock().iter_mut() {
+            if enderman.bounding_box().intersects(area) {
+                enderman.hurt(lightning_bolt::DAMAGE, false);
+            }
+        }
+        for ghast in self.entities.ghasts.lock().iter_mut() {
+            if ghast.bounding_box().intersects(area) {
+                ghast.hurt(lightning_bolt::DAMAGE);
+            }
+        }
+
+        self.pigs_struck_by_lightning(area);
+    }
+
+    /// Turns the pigs in `area` into zombified piglins, unless the difficulty
+    /// is peaceful.
+    ///
+    /// Vanilla: `Pig.thunderHit`
+    fn pigs_struck_by_lightning(&self, area: &AABBd) {
+        if self.difficulty() == Difficulty::Peaceful {
+            return;
+        }
+
+        let pigs: Vec<GenericEntity> = {
+            let mut entities = self.entities.generic.lock();
+            let struck: Vec<i32> = entities
+                .iter()
+                .filter(|entity| {
+                    ptr::eq(entity.entity_type, vanilla_entities::PIG)
+                        && entity.bounding_box().intersects(area)
+                })
+                .map(|entity| entity.id)
+                .collect();
+            struck
+                .into_iter()
+                .filter_map(|entity_id| entities.remove(entity_id))
+                .collect()
+        };
+        for pig in pigs {
+            // TODO: Carry over the custom name and rotation of the pig, and
+            // give the piglin a golden sword
+            self.spawn_entity(
+                entity::next_entity_id(),
+                vanilla_entities::ZOMBIFIED_PIGLIN,
+                *pig.position.lock(),
+                None,
+            );
+        }
+    }
+
+    /// Ticks the villagers, walking them to their job site by day and their
+    /// bed by night. Villagers that died are removed.
+    pub(super) fn tick_villagers(&self) {
+        self.entities.villagers.lock().retain_mut(|villager| {
+            villager.tick(self);
+            if let Some(data) = villager.pack_dirty_data() {
+                self.broadcast_to_trackers(
+                    villager.position(),
+                    CSetEntityData::new(villager.base.id, data),
+                );
+            }
+            if villager.is_dead() {
+                self.remove_dead_mob(villager);
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Opens the trading screen of the villager with network id `entity_id`
+    /// for `player`. Returns `None` if there is no such villager.
+    ///
+    /// Vanilla: `Villager.mobInteract`
+    pub fn interact_with_villager(
+        &self,
+        player: &Player,
+        entity_id: i32,
+    ) -> Option<InteractionResult> {
+        let (title, state) = {
+            let mut villagers = self.entities.villagers.lock();
+            let villager = villagers.get_mut(entity_id)?;
+            if villager.trading || player.is_secondary_use_active() {
+                return Some(InteractionResult::Pass);
+            }
+
+            player.award_stat(StatKey::custom(CustomStat::TalkedToVillager), 1);
+            if villager.trades.is_empty() {
+                return Some(InteractionResult::Success);
+            }
+            villager.trading = true;
+            let title = villager
+                .base
+                .custom_name
+                .clone()
+                .unwrap_or_else(|| villager.profession.title());
+            (title, villager.merchant_state())
+        };
+
+        // Trades lock the villagers, so the menu opens without holding them
+        let provider = MerchantMenuProvider::new(player.inventory.clone(), title, state);
+        player.open_menu(&provider);
+        player.connection.send_packet(provider.offers_packet());
+        Some(InteractionResult::Success)
+    }
+
+    /// Lets the villager with network id `entity_id` go back to its routine
+    /// once its trading screen was closed.
+    pub fn stop_trading_with_villager(&self, entity_id: i32) {
+        if let Some(villager) = self.entities.villagers.lock().get_mut(entity_id) {
+            villager.trading = false;
+        }
+    }
+
+    /// Uses the trade at `index` of the villager `state` trades with, handing
+    /// out experience orbs and refreshing `state` with the villager's offers
+    /// and level. Returns `false` if the villager is gone.
+    ///
+    /// Vanilla: `Villager.rewardTradeXp`
+    pub fn complete_villager_trade(&self, state: &mut MerchantState, index: usize) -> bool {
+        let (position, level_up) = {
+            let mut villagers = self.entities.villagers.lock();
+            let Some(villager) = villagers.get_mut(state.villager_id) else {
+                return false;
+            };
+            let Some(level_up) = villager.trade(index) else {
+                return false;
+            };
+            state.offers.clone_from(&villager.trades);
+            state.level = villager.level;
+            state.xp = villager.xp;
+            (villager.position(), level_up)
+        };
+
+        self.play_sound(
+            sound_events::ENTITY_VILLAGER_YES,
+            SoundSource::Neutral,
+            position.to_block_pos(),
+            1.0,
+            1.0,
+            None,
+        );
+        let mut experience = 3 + rand::random_range(0..4);
+        if level_up {
+            experience += 5;
+        }
+        self.spawn_experience_orbs(
+            Vector3::new(position.x, position.y + 0.5, position.z),
+            experience,
+        );
+        true
+    }
+
+    /// Strikes the villager with network id `entity_id` with lightning,
+    /// turning it into a witch unless the difficulty is peaceful. Returns
+    /// `false` if there is no such villager.
+    ///
+    /// Vanilla: `Villager.thunderHit`
+    pub fn villager_struck_by_lightning(&self, entity_id: i32) -> bool {
+        let mut villagers = self.entities.villagers.lock();
+        if villagers.get(entity_id).is_none() {
+            return false;
+        }
+        // TODO: Set the villager on fire instead once entities can burn
+        if self.difficulty() == Difficulty::Peaceful {
+            return true;
+        }
+
+        let Some(villager) = villagers.remove(entity_id) else {
+            return true;
+        };
+        drop(villagers);
+        // TODO: Carry over the custom name and rotation of the villager
+        self.spawn_entity(
+            entity::next_entity_id(),
+            vanilla_entities::WITCH,
+            villager.position(),
+            None,
+        );
+        true
+    }
+
+    /// Hits the mob with network id `entity_id` in `mobs`. Returns `false` if
+    /// there is no such mob.
+    ///
+    /// Vanilla: `Player.attack`
+    // TODO: Use the attack damage of the held item and knock the mob back once
+    // attributes exist
+    fn attack_mob<T: AttackableMob>(
+        &self,
+        mobs: &SyncMutex<EntityList<T>>,
+        player: &Player,
+        entity_id: i32,
+    ) -> bool {
+        !matches!(
+            self.hurt_mob(mobs, entity_id, PLAYER_ATTACK_DAMAGE, Some(player)),
+            MobHurt::Missing
+        )
+    }
+
+    /// Hurts the mob with network id `entity_id` in `mobs` by `damage`,
+    /// turning it against `player` if they dealt it. A mob that dies is
+    /// taken out of the world, and `player` is rewarded for the kill.
+    ///
+    /// Vanilla: `LivingEntity.hurtServer` and `LivingEntity.die`
+    fn hurt_mob<T: AttackableMob>(
+        &self,
+        mobs: &SyncMutex<EntityList<T>>,
+        entity_id: i32,
+        damage: f32,
+        player: Option<&Player>,
+    ) -> MobHurt<T> {
+        let mut list = mobs.lock();
+        let Some(mob) = list.get_mut(entity_id) else {
+            return MobHurt::Missing;
+        };
+
+        let Some(hurt_sound) = mob.take_hit(damage) else {
+            return MobHurt::Survived;
+        };
+        if let Some(player) = player {
+            mob.retaliate(self, player);
+        }
+        if !mob.is_dead() {
+            self.play_sound(
+                hurt_sound,
+                T::SOUND_SOURCE,
+                mob.get_position().to_block_pos(),
+                T::SOUND_VOLUME,
+                1.0,
+                None,
+            );
+            return MobHurt::Survived;
+        }
+
+        let Some(mob) = list.remove(entity_id) else {
+            return MobHurt::Survived;
+        };
+        drop(list);
+        self.remove_dead_mob(&mob);
+        if let Some(player) = player {
+            self.spawn_experience_orbs(mob.get_position(), mob.experience_reward());
+            player.award_stat(StatKey::killed(mob.get_type()), 1);
+            player.award_stat(StatKey::custom(CustomStat::MobKills), 1);
+        }
+        MobHurt::Died(mob)
+    }
+
+    /// Takes a mob that died out of the world, dropping its loot.
+    fn remove_dead_mob<T: AttackableMob>(&self, mob: &T) {
+        self.post_vibration(VibrationEvent::EntityDie, mob.get_position());
+        let pos = mob.get_position().to_block_pos();
+        self.play_sound(
+            mob.death_sound(),
+            T::SOUND_SOURCE,
+            pos,
+            T::SOUND_VOLUME,
+            1.0,
+            None,
+        );
+        for item in mob.death_drops() {
+            self.drop_item_stack(pos, item);
+        }
+    }
+
+    /// Ticks the zombies, letting them chase and hit players, and spawns new
+    /// ones in the dark around players. Zombies that died are removed, and
+    /// on peaceful all of them are.
+    pub(super) fn tick_zombies(&self, tick_count: u64) {
+        if self.difficulty() == Difficulty::Peaceful {
+            self.entities.zombies.lock().drain();
+            return;
+        }
+        if tick_count.is_multiple_of(zombie::SPAWN_INTERVAL) {
+            self.spawn_zombies_naturally();
+        }
+
+        self.entities.zombies.lock().retain_mut(|zombie| {
+            zombie.tick(self);
+            if let Some(data) = zombie.pack_dirty_data() {
+                self.broadcast_to_trackers(
+                    zombie.position(),
+                    CSetEntityData::new(zombie.base.id, data),
+                );
+            }
+            if zombie.is_dead() {
+                self.remove_dead_mob(zombie);
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Tries to spawn a zombie in the dark around each player.
+    ///
+    /// Vanilla: `NaturalSpawner.spawnForChunk`
+    // TODO: Spawn zombies from monster spawners once spawners have block
+    // entities
+    fn spawn_zombies_naturally(&self) {
+        if self.get_game_rule(SPAWN_MONSTERS) != GameRuleValue::Bool(true) {
+            return;
+        }
+
+        let mut positions = Vec::new();
+        self.players.iter_players(|_, player| {
+            let center = *player.position.lock();
+            if let Some(position) = zombie::natural_spawn_position(self, center) {
+                positions.push(position);
+            }
+            true
+        });
+        for position in positions {
+            if self.entities.zombies.lock().len() >= zombie::MONSTER_CAP {
+                break;
+            }
+            self.add_entity(
+                &self.entities.zombies,
+                ZombieEntity::new(entity::next_entity_id(), position),
+            );
+        }
+    }
+
+    /// Hits the zombie with network id `entity_id`. Returns `false` if
+    /// there is no such zombie.
+    ///
+    /// Vanilla: `Player.attack`
+    pub fn attack_zombie(&self, player: &Player, entity_id: i32) -> bool {
+        self.attack_mob(&self.entities.zombies, player, entity_id)
+    }
+
+    /// Ticks the skeletons, letting them keep their distance from players
+    /// and shoot at them. Skeletons that died are removed, and on peaceful
+    /// all of them are.
+    pub(super) fn tick_skeletons(&self) {
+        if self.difficulty() == Difficulty::Peaceful {
+            self.entities.skeletons.lock().drain();
+            return;
+        }
+
+        let mut arrows = Vec::new();
+        self.entities.skeletons.lock().retain_mut(|skeleton| {
+            if let Some(arrow) = skeleton.tick(self) {
+                self.play_sound(
+                    sound_events::ENTITY_SKELETON_SHOOT,
+                    SoundSource::Hostile,
+                    skeleton.position().to_block_pos(),
+                    1.0,
+                    0.8 + rand::random::<f32>() * 0.4,
+                    None,
+                );
+                arrows.push(arrow);
+            }
+            if let Some(data) = skeleton.pack_dirty_data() {
+                self.broadcast_to_trackers(
+                    skeleton.position(),
+                    CSetEntityData::new(skeleton.base.id, data),
+                );
+            }
+            if skeleton.is_dead() {
+                self.remove_dead_mob(skeleton);
+                return false;
+            }
+            true
+        });
+        for arrow in arrows {
+            self.add_entity(&self.entities.arrows, arrow);
+        }
+    }
+
+    /// Hits the skeleton with network id `entity_id`. Returns `false` if
+    /// there is no such skeleton.
+    ///
+    /// Vanilla: `Player.attack`
+    pub fn attack_skeleton(&self, player: &Player, entity_id: i32) -> bool {
+        self.attack_mob(&self.entities.skeletons, player, entity_id)
+    }
+
+    /// Ticks the creepers, letting them walk up to players and blow up next
+    /// to them once every creeper moved. Creepers that died are removed, and
+    /// on peaceful all of them are.
+    pub(super) fn tick_creepers(&self) {
+        if self.difficulty() == Difficulty::Peaceful {
+            self.entities.creepers.lock().drain();
+            return;
+        }
+
+        let mut explosions = Vec::new();
+        self.entities.creepers.lock().retain_mut(|creeper| {
+            if let Some(explosion) = creeper.tick(self) {
+                explosions.push(explosion);
+                return false;
+            }
+            if let Some(data) = creeper.pack_dirty_data() {
+                self.broadcast_to_trackers(
+                    creeper.position(),
+                    CSetEntityData::new(creeper.base.id, data),
+                );
+            }
+            if creeper.is_dead() {
+                self.remove_dead_mob(creeper);
+                return false;
+            }
+            true
+        });
+
+        // Explosions push and light other TNT, so they go off without the lock
+        for explosion in explosions {
+            explosion.explode(self);
+        }
+    }
+
+    /// Hits the creeper with network id `entity_id`. Returns `false` if
+    /// there is no such creeper.
+    ///
+    /// Vanilla: `Player.attack`
+    pub fn attack_creeper(&self, player: &Player, entity_id: i32) -> bool {
+        self.attack_mob(&self.entities.creepers, player, entity_id)
+    }
+
+    /// Ticks the slimes, letting them hop after players. Slimes that died
+    /// are removed and split into smaller ones, and on peaceful all of them
+    /// are removed.
+    pub(super) fn tick_slimes(&self) {
+        if self.difficulty() == Difficulty::Peaceful {
+            self.entities.slimes.lock().drain();
+            return;
+        }
+
+        let mut dead = Vec::new();
+        self.entities.slimes.lock().retain_mut(|slime| {
+            slime.tick(self);
+            if let Some(data) = slime.pack_dirty_data() {
+                self.broadcast_to_trackers(
+                    slime.position(),
+                    CSetEntityData::new(slime.base.id, data),
+                );
+            }
+            if slime.is_dead() {
+                dead.push(slime.split());
+                self.remove_dead_mob(slime);
+                return false;
+            }
+            true
+        });
+        for child in dead.into_iter().flatten() {
+            self.add_entity(&self.entities.slimes, child);
+        }
+    }
+
+    /// Hits the slime with network id `entity_id`, splitting it into smaller
+    /// ones if it dies. Returns `false` if there is no such slime.
+    ///
+    /// Vanilla: `Player.attack`
+    pub fn attack_slime(&self, player: &Player, entity_id: i32) -> bool {
+        match self.hurt_mob(
+            &self.entities.slimes,
+            entity_id,
+            PLAYER_ATTACK_DAMAGE,
+            Some(player),
+        ) {
+            MobHurt::Missing => false,
+            MobHurt::Survived => true,
+            MobHurt::Died(slime) => {
+                for child in slime.split() {
+                    self.add_entity(&self.entities.slimes, child);
+                }
+                true
+            }
+        }
+    }
+
+    /// Ticks the iron golems, letting them fight the monsters around them
+    /// and patrol their villages, and spawns new ones in big villages. Iron
+    /// golems that died are removed.
+    pub(super) fn tick_iron_golems(&self, tick_count: u64) {
+        if tick_count.is_multiple_of(iron_golem::SPAWN_INTERVAL) {
+            self.spawn_iron_golems_naturally();
+        }
+
+        // Iron golems leave creepers alone, so they don't blow up the village
+        let mut monsters: Vec<(i32, Vector3<f64>)> = Vec::new();
+        monsters.extend(
+            self.entities
+                .zombies
+                .lock()
+                .iter()
+                .map(|zombie| (zombie.base.id, zombie.position())),
+        );
+        monsters.extend(
+            self.entities
+                .skeletons
+                .lock()
+                .iter()
+                .map(|skeleton| (skeleton.base.id, skeleton.position())),
+        );
+        monsters.extend(
+            self.entities
+                .slimes
+                .lock()
+                .iter()
+                .map(|slime| (slime.base.id, slime.position())),
+        );
+        monsters.extend(
+            self.entities
+                .endermen
+                .lock()
+                .iter()
+                .map(|enderman| (enderman.base.id, enderman.position())),
+        );
+        let baby_villagers: Vec<Vector3<f64>> = self
+            .entities
+            .villagers
+            .lock()
+            .iter()
+            .filter(|villager| villager.is_baby())
+            .map(VillagerEntity::position)
+            .collect();
+
+        let mut attacks = Vec::new();
+        self.entities.iron_golems.lock().retain_mut(|golem| {
+            attacks.extend(golem.tick(self, &monsters, &baby_villagers));
+            if let Some(data) = golem.pack_dirty_data() {
+                self.broadcast_to_trackers(
+                    golem.position(),
+                    CSetEntityData::new(golem.base.id, data),
+                );
+            }
+            if golem.is_dead() {
+                self.remove_dead_mob(golem);
+                return false;
+            }
+            true
+        });
+        for attack in attacks {
+            self.deal_iron_golem_attack(attack);
+        }
+    }
+
+    /// Hurts the target of an iron golem's hit and flings it up.
+    ///
+    /// Vanilla: `IronGolem.doHurtTarget`
+    fn deal_iron_golem_attack(&self, attack: GolemAttack) {
+        let id = match attack.target {
+            GolemTarget::Player(uuid) => {
+                // TODO: Fling players too once their velocity can be set
+                if let Some(player) = self.players.get_by_uuid(&uuid)
+                    && let Some(damage) =
+                        entity::mob::player_damage(attack.damage, self.difficulty())
+                {
+                    player.take_damage(damage);
+                }
+                return;
+            }
+            GolemTarget::Monster(id) => id,
+        };
+
+        if let Some(zombie) = self.entities.zombies.lock().get_mut(id) {
+            if zombie.hurt(attack.damage) {
+                zombie.push(attack.fling);
+            }
+            return;
+        }
+        if let Some(skeleton) = self.entities.skeletons.lock().get_mut(id) {
+            if skeleton.hurt(attack.damage) {
+                skeleton.push(attack.fling);
+            }
+            return;
+        }
+        if let Some(slime) = self.entities.slimes.lock().get_mut(id) {
+            if slime.hurt(attack.damage) {
+                slime.push(attack.fling);
+            }
+            return;
+        }
+        if let Some(enderman) = self.entities.endermen.lock().get_mut(id)
+            && enderman.hurt(attack.damage, true)
+        {
+            enderman.push(attack.fling);
+        }
+    }
+
+    /// Tries to spawn an iron golem next to a random villager, if enough
+    /// villagers live around it and they don't have enough iron golems yet.
+    /// The iron golem patrols around the middle of those villagers.
+    ///
+    /// Vanilla (before 1.14): `Village.tick`
+    // TODO: Also require 21 doors once villages keep track of their doors
+    fn spawn_iron_golems_naturally(&self) {
+        if self.get_game_rule(SPAWN_MOBS) != GameRuleValue::Bool(true) {
+            return;
+        }
+
+        let (center, villager_count) = {
+            let villagers = self.entities.villagers.lock();
+            let Some(position) = villagers
+                .iter()
+                .nth(rand::random_range(0..villagers.len().max(1)))
+                .map(VillagerEntity::position)
+            else {
+                return;
+            };
+            let radius = iron_golem::VILLAGE_RADIUS;
+            let neighbors: Vec<Vector3<f64>> = villagers
+                .iter()
+                .map(VillagerEntity::position)
+                .filter(|other| other.sub(&position).length_squared() <= radius * radius)
+                .collect();
+            if neighbors.len() < iron_golem::MIN_VILLAGERS {
+                return;
+            }
+            let count = neighbors.len() as f64;
+            let sum = neighbors
+                .iter()
+                .fold(Vector3::new(0.0, 0.0, 0.0), |sum, other| sum.add(other));
+            (
+                Vector3::new(sum.x / count, sum.y / count, sum.z / count),
+                neighbors.len(),
+            )
+        };
+
+        let radius = iron_golem::VILLAGE_RADIUS;
+        let golems = self
+            .entities
+            .iron_golems
+            .lock()
+            .iter()
+            .filter(|golem| golem.position().sub(&center).length_squared() <= radius * radius)
+            .count();
+        if golems >= villager_count / iron_golem::VILLAGERS_PER_GOLEM {
+            return;
+        }
+
+        if let Some(position) = iron_golem::natural_spawn_position(self, center) {
+            let mut golem = IronGolemEntity::new(entity::next_entity_id(), position);
+            golem.home = center;
+            self.add_entity(&self.entities.iron_golems, golem);
+        }
+    }
+
+    /// Hits the iron golem with network id `entity_id`. Returns `false` if
+    /// there is no such iron golem.
+    ///
+    /// Vanilla: `Player.attack`
+    pub fn attack_iron_golem(&self, player: &Player, entity_id: i32) -> bool {
+        self.attack_mob(&self.entities.iron_golems, player, entity_id)
+    }
+
+    /// Ticks the endermen, letting them move blocks around, teleport and go
+    /// after players that stared at them. Endermen that died are removed,
+    /// and on peaceful all of them are.
+    pub(super) fn tick_endermen(&self) {
+        if self.difficulty() == Difficulty::Peaceful {
+            self.entities.endermen.lock().drain();
+            return;
+        }
+
+        self.entities.endermen.lock().retain_mut(|enderman| {
+            enderman.tick(self);
+            if let Some(data) = enderman.pack_dirty_data() {
+                self.broadcast_to_trackers(
+                    enderman.position(),
+                    CSetEntityData::new(enderman.base.id, data),
+                );
+            }
+            if enderman.is_dead() {
+                self.remove_dead_mob(enderman);
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Hits the enderman with network id `entity_id`. Returns `false` if
+    /// there is no such enderman.
+    ///
+    /// Vanilla: `Player.attack`
+    pub fn attack_enderman(&self, player: &Player, entity_id: i32) -> bool {
+        self.attack_mob(&self.entities.endermen, player, entity_id)
+    }
+
+    /// Ticks the ghasts, letting them float around and shoot fireballs at
+    /// the players they see. Ghasts that died are removed, and on peaceful
+    /// all of them are.
+    pub(super) fn tick_ghasts(&self) {
+        if self.difficulty() == Difficulty::Peaceful {
+            self.entities.ghasts.lock().drain();
+            return;
+        }
+
+        let mut fireballs = Vec::new();
+        self.entities.ghasts.lock().retain_mut(|ghast| {
+            if let Some(fireball) = ghast.tick(self) {
+                fireballs.push(fireball);
+            }
+            if let Some(data) = ghast.pack_dirty_data() {
+                self.broadcast_to_trackers(
+                    ghast.position(),
+                    CSetEntityData::new(ghast.base.id, data),
+                );
+            }
+            if ghast.is_dead() {
+                self.remove_dead_mob(ghast);
+                return false;
+            }
+            true
+        });
+        for fireball in fireballs {
+            self.add_entity(&self.entities.ghast_fireballs, fireball);
+        }
+    }
+
+    /// Hits the ghast with network id `entity_id`. Returns `false` if
+    /// there is no such ghast.
+    ///
+    /// Vanilla: `Player.attack`
+    pub fn attack_ghast(&self, player: &Player, entity_id: i32) -> bool {
+        self.attack_mob(&self.entities.ghasts, player, entity_id)
+    }
+
+    /// Ticks the ender dragons, letting them fly and spit fireballs. Once a
+    /// dragon finished dying it is removed and the exit portal is built on
+    /// the fountain.
+    pub(super) fn tick_ender_dragons(&self) {
+        let mut fireballs = Vec::new();
+        let mut experience = Vec::new();
+        let mut slain = false;
+        self.entities.ender_dragons.lock().retain_mut(|dragon| {
+            let action = dragon.tick(self);
+            if let Some(data) = dragon.pack_dirty_data() {
+                self.broadcast_to_trackers(
+                    dragon.position(),
+                    CSetEntityData::new(dragon.base.id, data),
+                );
+            }
+            match action {
+                Some(DragonAction::Shoot(fireball)) => fireballs.push(fireball),
+                Some(DragonAction::DropExperience(amount)) => {
+                    experience.push((dragon.position(), amount));
+                }
+                Some(DragonAction::Die(amount)) => {
+                    experience.push((dragon.position(), amount));
+                    slain = true;
+                    return false;
+                }
+                None => {}
+            }
+            true
+        });
+
+        for fireball in fireballs {
+            self.play_sound(
+                sound_events::ENTITY_ENDER_DRAGON_SHOOT,
+                SoundSource::Hostile,
+                fireball.position().to_block_pos(),
+                1.0,
+                1.0,
+                None,
+            );
+            self.add_entity(&self.entities.dragon_fireballs, fireball);
+        }
+        for (position, amount) in experience {
+            self.spawn_experience_orbs(position, amount);
+        }
+        if slain {
+            ender_dragon::place_exit_portal(self, ender_dragon::fountain(self).to_block_pos());
+        }
+    }
+
+    /// Ticks the dragon fireballs, removing the ones that burst or burnt out.
+    pub(super) fn tick_dragon_fireballs(&self) {
+        self.entities
+            .dragon_fireballs
+            .lock()
+            .retain_mut(|fireball| fireball.tick(self));
+    }
+
+    /// Ticks the ghast fireballs, blowing up the ones that hit something and
+    /// removing the ones that burnt out.
+    pub(super) fn tick_ghast_fireballs(&self) {
+        let ghasts: Vec<(i32, AABBd)> = self
+            .entities
+            .ghasts
+            .lock()
+            .iter()
+            .map(|ghast| (ghast.base.id, ghast.bounding_box()))
+            .collect();
+
+        let mut impacts = Vec::new();
+        self.entities.ghast_fireballs.lock().retain_mut(|fireball| {
+            match fireball.tick(self, &ghasts) {
+                FireballFlight::Flying => return true,
+                FireballFlight::BurntOut => {}
+                FireballFlight::Hit { ghast } => impacts.push((
+                    fireball.position(),
+                    fireball.explosion_power,
+                    fireball.deflected_by(),
+                    ghast,
+                )),
+            }
+            false
+        });
+
+        for (position, power, deflected_by, ghast) in impacts {
+            if let Some(entity_id) = ghast {
+                self.fireball_hit_ghast(entity_id, deflected_by);
+            }
+            Explosion::new(position, f32::from(power), ExplosionSource::Fireball).explode(self);
+        }
+    }
+
+    /// Hurts the ghast with network id `entity_id` with a fireball. A
+    /// fireball the player `deflected_by` hit back kills it outright, which
+    /// earns them the "Return to Sender" advancement.
+    ///
+    /// Vanilla: `LargeFireball.onHitEntity` and `Ghast.hurtServer`
+    fn fireball_hit_ghast(&self, entity_id: i32, deflected_by: Option<Uuid>) {
+        let player = deflected_by.and_then(|uuid| self.players.get_by_uuid(&uuid));
+        let damage = if player.is_some() {
+            REFLECTED_FIREBALL_DAMAGE
+        } else {
+            ghast_fireball::DIRECT_DAMAGE
+        };
+        if let MobHurt::Died(_) =
+            self.hurt_mob(&self.entities.ghasts, entity_id, damage, player.as_deref())
+            && let Some(player) = player
+        {
+            player.grant_advancement(&RETURN_TO_SENDER);
+        }
+    }
+
+    /// Hits the ghast fireball with network id `entity_id`, sending it back
+    /// the way it came. Returns `false` if there is no such fireball.
+    ///
+    /// Vanilla: `AbstractHurtingProjectile.hurtServer`
+    pub fn attack_ghast_fireball(&self, player: &Player, entity_id: i32) -> bool {
+        let mut fireballs = self.entities.ghast_fireballs.lock();
+        let Some(fireball) = fireballs.get_mut(entity_id) else {
+            return false;
+        };
+        fireball.deflect(player.gameprofile.id);
+        true
+    }
+
+    /// Hits the ender dragon that has network id `entity_id` or a part with
+    /// it. Returns `false` if there is no such dragon.
+    ///
+    /// Vanilla: `Player.attack`
+    // TODO: Use the attack damage of the held item once attributes exist
+    pub fn attack_ender_dragon(&self, player: &Player, entity_id: i32) -> bool {
+        let mut ender_dragons = self.entities.ender_dragons.lock();
+        let Some((dragon, part)) = ender_dragons.iter_mut().find_map(|dragon| {
+            if dragon.base.id == entity_id {
+                return Some((dragon, None));
+            }
+            let part = dragon.part_index(entity_id)?;
+            Some((dragon, Some(part)))
+        }) else {
+            return false;
+        };
+
+        if !dragon.hurt(part, PLAYER_ATTACK_DAMAGE) {
+            return true;
+        }
+        if !dragon.is_dead() {
+            self.play_sound(
+                sound_events::ENTITY_ENDER_DRAGON_HURT,
+                SoundSource::Hostile,
+                dragon.position().to_block_pos(),
+                1.0,
+                1.0,
+                None,
+            );
+            return true;
+        }
+
+        // The dragon takes a while to die, dropping its experience on the way
+        player.award_stat(StatKey::killed(vanilla_entities::ENDER_DRAGON), 1);
+        player.award_stat(StatKey::custom(CustomStat::MobKills), 1);
+        true
+    }
+
+    /// Ticks the arrows, removing the ones that hit a player or despawned.
+    pub(super) fn tick_arrows(&self) {
+        self.entities.arrows.lock().retain_mut(|arrow| {
+            if !arrow.tick(self) {
+                return false;
+            }
+            if let Some(data) = arrow.pack_dirty_data() {
+                self.broadcast_to_trackers(arrow.position(), CSetEntityData::new(arrow.id, data));
+            }
+            true
+        });
+    }
+
+    /// Hangs a painting in `pos` facing `direction`, with a random motive
+    /// among the largest that fit. Returns `false` if none fit.
+    ///
+    /// Vanilla: `HangingEntityItem.useOn`
+    pub fn place_painting(&self, pos: BlockPos, direction: Direction) -> bool {
+        let mut paintings = self.entities.paintings.lock();
+        let Some(painting) =
+            PaintingEntity::create(self, entity::next_entity_id(), pos, direction, &paintings)
+        else {
+            return false;
+        };
+
+        self.play_sound(
+            sound_events::ENTITY_PAINTING_PLACE,
+            SoundSource::Neutral,
+            pos,
+            1.0,
+            1.0,
+            None,
+        );
+        self.insert_entity(&mut paintings, painting);
+        true
+    }
+
+    /// Drops the paintings that fall off once the block at `pos` changed.
+    ///
+    /// Vanilla checks this every 100 ticks in `HangingEntity.tick`, the
+    /// server checks it as soon as a block of the wall changes instead.
+    pub(super) fn paintings_neighbor_changed(&self, pos: BlockPos) {
+        let mut paintings = self.entities.paintings.lock();
+        let broken: Vec<i32> = paintings
+            .iter()
+            .filter(|painting| painting.depends_on(pos) && !painting.survives(self, &paintings))
+            .map(|painting| painting.id)
+            .collect();
+
+        for entity_id in broken {
+            let Some(painting) = paintings.remove(entity_id) else {
+                continue;
+            };
+            self.play_sound(
+                sound_events::ENTITY_PAINTING_BREAK,
+                SoundSource::Neutral,
+                painting.pos,
+                1.0,
+                1.0,
+                None,
+            );
+            self.drop_item_stack(painting.pos, ItemStack::new(&vanilla_items::ITEMS.painting));
+        }
+    }
+
+    /// Attaches an empty item frame in `pos`, facing `direction`. Returns
+    /// `false` if the block behind can't hold it or something is in the way.
+    ///
+    /// Vanilla: `HangingEntityItem.useOn`
+    pub fn place_item_frame(&self, pos: BlockPos, direction: Direction, glowing: bool) -> bool {
+        let mut item_frames = self.entities.item_frames.lock();
+        let frame = ItemFrameEntity::new(entity::next_entity_id(), pos, direction, glowing);
+        if !self.item_frame_survives(&frame, &item_frames) {
+            return false;
+        }
+
+        self.play_sound(
+            frame.place_sound(),
+            SoundSource::Neutral,
+            pos,
+            1.0,
+            1.0,
+            None,
+        );
+        self.insert_entity(&mut item_frames, frame)
+    }
+
+    /// Right-clicks the item frame with network id `entity_id` holding
+    /// `item_stack`. An empty frame takes one of the held items, a filled one
+    /// turns its item by 45°.
+    ///
+    /// Returns [`InteractionResult::Pass`] if there is no such frame.
+    ///
+    /// Vanilla: `ItemFrame.interact`
+    pub fn interact_with_item_frame(
+        &self,
+        entity_id: i32,
+        item_stack: &mut ItemStack,
+    ) -> InteractionResult {
+        let mut item_frames = self.entities.item_frames.lock();
+        let Some(frame) = item_frames.get_mut(entity_id) else {
+            return InteractionResult::Pass;
+        };
+
+        if frame.item().is_empty() {
+            if item_stack.is_empty() {
+                return InteractionResult::Pass;
+            }
+            let mut item = item_stack.clone();
+            item.set_count(1);
+            // TODO: Maps show their contents in a frame once map data is
+            // tracked, which needs `CMapItemData` sent to the viewers here.
+            frame.set_item(item);
+            item_stack.shrink(1);
+            self.play_sound(
+                frame.add_item_sound(),
+                SoundSource::Neutral,
+                frame.pos,
+                1.0,
+                1.0,
+                None,
+            );
+        } else {
+            frame.rotate_item();
+            self.play_sound(
+                frame.rotate_item_sound(),
+                SoundSource::Neutral,
+                frame.pos,
+                1.0,
+                1.0,
+                None,
+            );
+        }
+
+        if let Some(data) = frame.pack_dirty_data() {
+            self.broadcast_to_trackers(frame.get_position(), CSetEntityData::new(frame.id, data));
+        }
+        InteractionResult::Success
+    }
+
+    /// Hits the item frame with network id `entity_id`. A filled frame drops
+    /// its item, an empty one breaks. Nothing drops for `player` with infinite
+    /// materials.
+    ///
+    /// Returns `false` if there is no such frame.
+    ///
+    /// Vanilla: `ItemFrame.hurtServer`
+    pub fn attack_item_frame(&self, player: &Player, entity_id: i32) -> bool {
+        let mut item_frames = self.entities.item_frames.lock();
+        let Some(frame) = item_frames.get_mut(entity_id) else {
+            return false;
+        };
+        let drops = !player.has_infinite_materials();
+
+        if frame.item().is_empty() {
+            if let Some(frame) = item_frames.remove(entity_id) {
+                self.break_item_frame(frame, drops);
+            }
+            return true;
+        }
+
+        let item = frame.item().clone();
+        frame.set_item(ItemStack::empty());
+        if let Some(data) = frame.pack_dirty_data() {
+            self.broadcast_to_trackers(frame.get_position(), CSetEntityData::new(frame.id, data));
+        }
+        self.play_sound(
+            frame.remove_item_sound(),
+            SoundSource::Neutral,
+            frame.pos,
+            1.0,
+            1.0,
+            None,
+        );
+        if drops {
+            self.drop_item_stack(frame.pos, item);
+        }
+        true
+    }
+
+    /// Returns the box taken up by the item frame, armor stand, minecart,
+    /// boat, villager, zombie, skeleton, creeper, slime, iron golem, enderman,
+    /// ghast, ghast fireball or ender dragon part with network id
+    /// `entity_id`, the entities players can click so far.
+    #[must_use]
+    pub fn interactable_bounding_box(&self, entity_id: i32) -> Option<AABBd> {
+        self.entities
+            .lists()
+            .iter()
+            .find_map(|list| list.hitbox(entity_id))
+    }
+
+    /// Breaks the item frames that fall off once the block at `pos` changed.
+    pub(super) fn item_frames_neighbor_changed(&self, pos: BlockPos) {
+        let mut item_frames = self.entities.item_frames.lock();
+        let broken: Vec<i32> = item_frames
+            .iter()
+            .filter(|frame| {
+                (frame.support_pos() == pos || frame.pos == pos)
+                    && !self.item_frame_survives(frame, &item_frames)
+            })
+            .map(|frame| frame.id)
+            .collect();
+
+        for entity_id in broken {
+            if let Some(frame) = item_frames.remove(entity_id) {
+                self.break_item_frame(frame, true);
+            }
+        }
+    }
+
+    /// Returns true if `frame` can stay where it is. `item_frames` are the
+    /// frames in the world, which may include `frame` itself.
+    fn item_frame_survives(
+        &self,
+        frame: &ItemFrameEntity,
+        item_frames: &EntityList<ItemFrameEntity>,
+    ) -> bool {
+        let paintings = self.entities.paintings.lock();
+        frame.survives(self, |bounding_box| {
+            item_frames
+                .iter()
+                .filter(|other| other.id != frame.id)
+                .any(|other| other.bounding_box().intersects(bounding_box))
+                || paintings
+                    .iter()
+                    .any(|painting| painting.bounding_box().intersects(bounding_box))
+        })
+    }
+
+    /// Removes a frame that was taken out of the world from clients, dropping
+    /// the frame and its item if `drops` is set.
+    fn break_item_frame(&self, frame: ItemFrameEntity, drops: bool) {
+        self.play_sound(
+            frame.break_sound(),
+            SoundSource::Neutral,
+            frame.pos,
+            1.0,
+            1.0,
+            None,
+        );
+        if drops {
+            self.drop_item_stack(frame.pos, frame.frame_item());
+            self.drop_item_stack(frame.pos, frame.item().clone());
         }
     }
 
@@ -41,32 +2042,13 @@ impl World {
         let pos = *player.position.lock();
         let (yaw, pitch) = player.rotation.load();
 
-        // Send existing players to the new player (tab list + entity spawn)
+        // Exchange tab list entries with the other players, unless the
+        // player is already listed since they came from another world
+        self.tab_list.add(player.clone());
+
+        // Send existing players to the new player
         self.players.iter_players(|_, existing_player| {
             if existing_player.gameprofile.id != player.gameprofile.id {
-                // Add to tab list with full player info
-                let add_existing = CPlayerInfoUpdate::create_player_initializing(
-                    existing_player.gameprofile.id,
-                    existing_player.gameprofile.name.clone(),
-                    existing_player.gameprofile.properties.clone(),
-                    existing_player.game_mode.load().into(),
-                    existing_player.connection.latency(),
-                    None, // display_name
-                    true, // show_hat
-                );
-                player.connection.send_packet(add_existing);
-
-                // Send chat session if available
-                if let Some(session) = existing_player.chat_session()
-                    && let Ok(protocol_data) = session.as_data().to_protocol_data()
-                {
-                    let session_packet = CPlayerInfoUpdate::update_chat_session(
-                        existing_player.gameprofile.id,
-                        protocol_data,
-                    );
-                    player.connection.send_packet(session_packet);
-                }
-
                 // Spawn existing player entity for new player
                 let existing_pos = *existing_player.position.lock();
                 let (existing_yaw, existing_pitch) = existing_player.rotation.load();
@@ -85,16 +2067,7 @@ impl World {
             true
         });
 
-        // Broadcast new player to all existing players (tab list + entity spawn)
-        let player_info_packet = CPlayerInfoUpdate::create_player_initializing(
-            player.gameprofile.id,
-            player.gameprofile.name.clone(),
-            player.gameprofile.properties.clone(),
-            player.game_mode.load().into(),
-            player.connection.latency(),
-            None, // display_name
-            true, // show_hat
-        );
+        // Broadcast new player to all existing players
         let player_type_id = *REGISTRY.entity_types.get_id(vanilla_entities::PLAYER) as i32;
         let spawn_packet = CAddEntity::player(
             player.id,
@@ -108,7 +2081,6 @@ impl World {
         );
 
         self.players.iter_players(|_, p| {
-            p.connection.send_packet(player_info_packet.clone());
             // Don't send spawn packet to self
             if p.gameprofile.id != player.gameprofile.id {
                 p.connection.send_packet(spawn_packet.clone());
@@ -116,6 +2088,9 @@ impl World {
             true
         });
 
+        // The other entities are sent with the chunks they are in, once the
+        // player's view is computed
+
         player.connection.send_packet(CGameEvent {
             event: GameEventType::LevelChunksLoadStart,
             data: 0.0,
@@ -2,7 +2,8 @@
 //!
 //! Ported from net/minecraft/util/Signer.java and SignatureValidator.java
 
-use rsa::pkcs1v15::SigningKey;
+use rsa::pkcs1v15::{SigningKey, VerifyingKey};
+use rsa::sha1::Sha1;
 use rsa::sha2::Sha256;
 use rsa::signature::{SignatureEncoding, Signer as RsaSigner, Verifier};
 use rsa::{RsaPrivateKey, RsaPublicKey};
@@ -118,53 +119,42 @@ impl SignatureValidator for RsaPublicKeyValidator {
     }
 }
 
-/// A multi-key validator that tries each public key until one validates.
+/// Validates profile key signatures against Mojang's Yggdrasil keys using SHA1withRSA.
 ///
-/// Used for Mojang's multiple player certificate keys.
-pub struct MultiKeyValidator {
-    validators: Vec<RsaPublicKeyValidator>,
+/// Mojang publishes several player certificate keys, a signature is valid if any of
+/// them verifies it. Equivalent to `ServicesKeySet.profileKeySignatureValidator()`
+/// in Minecraft.
+pub struct YggdrasilKeyValidator {
+    verifying_keys: Vec<VerifyingKey<Sha1>>,
 }
 
-impl MultiKeyValidator {
+impl YggdrasilKeyValidator {
     pub fn new(public_keys: Vec<RsaPublicKey>) -> Self {
         Self {
-            validators: public_keys
-                .into_iter()
-                .map(RsaPublicKeyValidator::new)
-                .collect(),
+            verifying_keys: public_keys.into_iter().map(VerifyingKey::new).collect(),
         }
     }
 }
 
-impl SignatureValidator for MultiKeyValidator {
+impl SignatureValidator for YggdrasilKeyValidator {
     fn validate(
         &self,
         updater: &dyn SignatureUpdater,
-        signature: &[u8],
+        signature_bytes: &[u8],
     ) -> Result<bool, CryptError> {
-        // Try each validator - if any succeeds, the signature is valid
-        for validator in &self.validators {
-            if validator.validate(updater, signature)? {
-                return Ok(true);
-            }
-        }
-        // None of the keys validated the signature
-        Ok(false)
-    }
-}
+        let mut collector = ByteCollector::new();
+        updater.update(&mut collector)?;
 
-/// A no-validation validator that always returns true.
-///
-/// Equivalent to SignatureValidator.NO_VALIDATION in Minecraft.
-pub struct NoValidation;
+        let signature = match rsa::pkcs1v15::Signature::try_from(signature_bytes) {
+            Ok(sig) => sig,
+            Err(_) => return Ok(false),
+        };
 
-impl SignatureValidator for NoValidation {
-    fn validate(
-        &self,
-        _updater: &dyn SignatureUpdater,
-        _signature: &[u8],
-    ) -> Result<bool, CryptError> {
-        Ok(true)
+        // Try each key - if any succeeds, the signature is valid
+        Ok(self
+            .verifying_keys
+            .iter()
+            .any(|key| key.verify(&collector.bytes, &signature).is_ok()))
     }
 }
 
@@ -238,15 +228,21 @@ mod tests {
     }
 
     #[test]
-    fn test_no_validation() {
-        let validator = NoValidation;
-        let data = b"Any data";
+    fn test_yggdrasil_validator() {
+        let (private_key, public_key) = generate_key_pair().unwrap();
+        let (_, other_key) = generate_key_pair().unwrap();
+        let validator = YggdrasilKeyValidator::new(vec![other_key, public_key]);
+
+        let data = b"expiry and key bytes";
         let updater = TestUpdater {
             data: data.to_vec(),
         };
-        let signature = vec![0u8; 10];
 
-        let is_valid = validator.validate(&updater, &signature).unwrap();
-        assert!(is_valid);
+        let signing_key = SigningKey::<Sha1>::new(private_key);
+        let signature = signing_key.sign(data).to_bytes();
+        assert!(validator.validate(&updater, &signature).unwrap());
+
+        let bad_signature = vec![0u8; crate::SIGNATURE_BYTES];
+        assert!(!validator.validate(&updater, &bad_signature).unwrap());
     }
 }
@@ -2,14 +2,27 @@
 //!
 //! Handles authentication with Mojang's session servers for online mode.
 
-use reqwest::StatusCode;
-use steel_core::player::GameProfile;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use steel_core::player::{GameProfile, skin_cache::TextureError};
 use thiserror::Error;
 
 const MOJANG_AUTH_URL: &str =
     "https://sessionserver.mojang.com/session/minecraft/hasJoined?username=";
 const SERVER_ID_ARG: &str = "&serverId=";
 
+/// How long a single request to the session server may take.
+const AUTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+static HTTP_CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    Client::builder()
+        .timeout(AUTH_TIMEOUT)
+        .build()
+        .expect("Failed to build HTTP client")
+});
+
 /// An error that can occur during Mojang authentication.
 #[derive(Error, Debug)]
 pub enum AuthError {
@@ -36,26 +49,6 @@ pub enum AuthError {
     UnknownStatusCode(StatusCode),
 }
 
-/// An error that can occur with textures.
-#[derive(Error, Debug)]
-pub enum TextureError {
-    /// Invalid URL.
-    #[error("Invalid URL")]
-    InvalidURL,
-    /// Invalid URL scheme for player texture.
-    #[error("Invalid URL scheme for player texture: {0}")]
-    DisallowedUrlScheme(String),
-    /// Invalid URL domain for player texture.
-    #[error("Invalid URL domain for player texture: {0}")]
-    DisallowedUrlDomain(String),
-    /// Failed to decode base64 player texture.
-    #[error("Failed to decode base64 player texture: {0}")]
-    DecodeError(String),
-    /// Failed to parse JSON from player texture.
-    #[error("Failed to parse JSON from player texture: {0}")]
-    JSONError(String),
-}
-
 const MAX_RETRIES: u32 = 3;
 
 /// Authenticates a player with Mojang's servers.
@@ -73,7 +66,7 @@ pub async fn mojang_authenticate(
     let mut last_error = AuthError::FailedResponse;
 
     for _ in 0..MAX_RETRIES {
-        let Ok(response) = reqwest::get(&auth_url).await else {
+        let Ok(response) = HTTP_CLIENT.get(&auth_url).send().await else {
             last_error = AuthError::FailedResponse;
             continue;
         };
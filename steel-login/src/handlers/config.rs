@@ -1,27 +1,41 @@
 //! Configuration state packet handlers.
 
+use std::mem;
 use std::sync::Arc;
 
 use steel_core::config::{STEEL_CONFIG, ServerLinks};
 use steel_core::player::networking::JavaConnection;
 use steel_core::player::{ClientInformation, Player};
 use steel_protocol::packets::common::CCustomPayload;
-use steel_protocol::packets::common::{SClientInformation, SCustomPayload};
+use steel_protocol::packets::common::{
+    ResourcePackAction, SClientInformation, SCustomPayload, SResourcePack,
+};
 use steel_protocol::packets::config::CFinishConfiguration;
 use steel_protocol::packets::config::CSelectKnownPacks;
 use steel_protocol::packets::config::SSelectKnownPacks;
 use steel_protocol::packets::shared_implementation::KnownPack;
 use steel_protocol::utils::ConnectionProtocol;
 use steel_utils::Identifier;
+use text_components::TextComponent;
 
 use crate::tcp_client::{ConnectionUpdate, JavaTcpClient};
 
 const BRAND_PAYLOAD: [u8; 5] = *b"Steel";
 
+/// How many custom payloads are kept from the configuration state.
+const MAX_CONFIG_PAYLOADS: usize = 32;
+
 impl JavaTcpClient {
     /// Handles a custom payload packet during the configuration state.
-    pub fn handle_config_custom_payload(&self, packet: SCustomPayload) {
+    ///
+    /// Channel handlers work on players, so the packet is kept until the
+    /// player is created.
+    pub async fn handle_config_custom_payload(&self, packet: SCustomPayload) {
         log::debug!("Custom payload packet: {packet:?}");
+        let mut payloads = self.config_payloads.lock().await;
+        if payloads.len() < MAX_CONFIG_PAYLOADS {
+            payloads.push(packet);
+        }
     }
 
     /// Handles the client information packet during the configuration state.
@@ -60,7 +74,7 @@ impl JavaTcpClient {
         self.send_bare_packet_now(CSelectKnownPacks::new(vec![KnownPack::new(
             "minecraft".to_string(),
             "core".to_string(),
-            STEEL_CONFIG.mc_version.to_string(),
+            STEEL_CONFIG.load().mc_version.to_string(),
         )]))
         .await;
     }
@@ -78,10 +92,49 @@ impl JavaTcpClient {
         self.send_packet_now(&self.server.registry_cache.tags_packet)
             .await;
 
+        // Configuration finishes once the client is done with the resource pack
+        if let Some(resource_pack) = &STEEL_CONFIG.load().resource_pack {
+            self.send_bare_packet_now(resource_pack.to_packet(self))
+                .await;
+            return;
+        }
+
         // Finish configuration with CFinishConfigurationPacket
         self.send_bare_packet_now(CFinishConfiguration {}).await;
     }
 
+    /// Handles the client's progress on the resource pack sent during configuration.
+    pub async fn handle_resource_pack(&self, packet: SResourcePack) {
+        log::debug!("Resource pack packet: {packet:?}");
+
+        let config = STEEL_CONFIG.load();
+        let Some(resource_pack) = &config.resource_pack else {
+            return;
+        };
+        if packet.id != resource_pack.id() || !packet.action.is_terminal() {
+            return;
+        }
+
+        match packet.action {
+            ResourcePackAction::FailedDownload if !self.resource_pack_retried.swap(true) => {
+                self.send_bare_packet_now(resource_pack.to_packet(self))
+                    .await;
+                return;
+            }
+            ResourcePackAction::SuccessfullyLoaded => {}
+            _ if resource_pack.forced => {
+                self.kick(TextComponent::plain(
+                    "You must accept the resource pack to play on this server",
+                ))
+                .await;
+                return;
+            }
+            _ => {}
+        }
+
+        self.send_bare_packet_now(CFinishConfiguration {}).await;
+    }
+
     /// Finishes the configuration process and transitions to the play state.
     ///
     /// # Panics
@@ -98,7 +151,7 @@ impl JavaTcpClient {
 
         let client_info = self.client_information.lock().await.clone();
 
-        let world = self.server.worlds[0].clone();
+        let world = self.server.overworld().clone();
         let entity_id = self.server.next_entity_id();
 
         let player = Arc::new_cyclic(|player_weak| {
@@ -114,6 +167,7 @@ impl JavaTcpClient {
             Player::new(
                 gameprofile,
                 connection,
+                Arc::downgrade(&self.server),
                 world,
                 entity_id,
                 player_weak,
@@ -125,6 +179,10 @@ impl JavaTcpClient {
             .send(ConnectionUpdate::Upgrade(player.connection.clone()))
             .expect("Failed to send connection update");
 
+        for packet in mem::take(&mut *self.config_payloads.lock().await) {
+            player.handle_custom_payload(packet);
+        }
+
         self.server.add_player(player);
     }
 }
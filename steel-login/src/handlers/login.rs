@@ -3,7 +3,10 @@
 use rsa::Pkcs1v15Encrypt;
 use sha1::Sha1;
 use sha2::Digest;
-use steel_core::{config::STEEL_CONFIG, player::GameProfile};
+use steel_core::{
+    config::STEEL_CONFIG,
+    player::{GameProfile, skin_cache::SKIN_CACHE},
+};
 use steel_protocol::{
     packets::login::{CHello, CLoginCompression, CLoginFinished, SHello, SKey},
     utils::ConnectionProtocol,
@@ -27,7 +30,7 @@ impl JavaTcpClient {
             return;
         }
 
-        let id = if STEEL_CONFIG.online_mode {
+        let id = if STEEL_CONFIG.load().online_mode {
             packet.profile_id
         } else {
             offline_uuid(&packet.name).expect("Failed to generate offline UUID")
@@ -43,7 +46,7 @@ impl JavaTcpClient {
             });
         }
 
-        if STEEL_CONFIG.encryption {
+        if STEEL_CONFIG.load().encryption {
             let challenge: [u8; 4] = rand::random();
             self.challenge.store(challenge);
 
@@ -118,7 +121,7 @@ impl JavaTcpClient {
             return;
         };
 
-        if STEEL_CONFIG.online_mode {
+        if STEEL_CONFIG.load().online_mode {
             let server_hash = &Sha1::new()
                 .chain_update(secret_key)
                 .chain_update(&self.server.key_store.public_key_der)
@@ -144,6 +147,10 @@ impl JavaTcpClient {
             }
         }
 
+        if STEEL_CONFIG.load().online_mode {
+            SKIN_CACHE.apply_to(profile).await;
+        }
+
         //TODO: Check for duplicate player UUID or name
 
         self.finish_login(profile).await;
@@ -154,7 +161,7 @@ impl JavaTcpClient {
     /// # Panics
     /// This function will panic if the compression threshold cannot be converted to an i32.
     pub async fn finish_login(&self, profile: &GameProfile) {
-        if let Some(compression) = STEEL_CONFIG.compression {
+        if let Some(compression) = STEEL_CONFIG.load().compression {
             self.send_bare_packet_now(CLoginCompression::new(
                 compression
                     .threshold
@@ -12,18 +12,19 @@ use crate::tcp_client::JavaTcpClient;
 impl JavaTcpClient {
     /// Handles a status request from the client.
     pub async fn handle_status_request(&self) {
+        let config = STEEL_CONFIG.load();
         let res_packet = CStatusResponse::new(Status {
-            description: &STEEL_CONFIG.motd,
+            description: &config.motd,
             players: Some(Players {
-                max: STEEL_CONFIG.max_players.cast_signed(),
+                max: config.max_players.cast_signed(),
                 //TODO: Get online players count
                 online: 0,
                 sample: vec![],
             }),
-            enforce_secure_chat: STEEL_CONFIG.enforce_secure_chat,
+            enforce_secure_chat: config.enforce_secure_chat,
             favicon: load_favicon(),
             version: Some(Version {
-                name: STEEL_CONFIG.mc_version,
+                name: config.mc_version,
                 protocol: CURRENT_MC_PROTOCOL,
             }),
         });
@@ -46,11 +47,12 @@ fn load_favicon() -> Option<String> {
 
     const ICON_PREFIX: &str = "data:image/png;base64,";
 
-    if !STEEL_CONFIG.use_favicon {
+    let config = STEEL_CONFIG.load();
+    if !config.use_favicon {
         return None;
     }
 
-    let path = Path::new(&STEEL_CONFIG.favicon);
+    let path = Path::new(&config.favicon);
     let Ok(icon) = fs::read(path) else {
         return None;
     };
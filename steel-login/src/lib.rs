@@ -13,7 +13,8 @@ mod login;
 mod tcp_client;
 
 // Authentication
-pub use authentication::{AuthError, TextureError, mojang_authenticate, signed_bytes_be_to_hex};
+pub use authentication::{AuthError, mojang_authenticate, signed_bytes_be_to_hex};
+pub use steel_core::player::skin_cache::TextureError;
 
 // Login helpers
 pub use login::{is_valid_player_name, offline_uuid};
@@ -13,12 +13,13 @@ use std::{
 use crossbeam::atomic::AtomicCell;
 use steel_core::player::{ClientInformation, GameProfile, networking::JavaConnection};
 use steel_core::server::Server;
+use steel_core::server::metrics::METRICS;
 use steel_protocol::{
     packet_reader::TCPNetworkDecoder,
     packet_traits::{ClientPacket, CompressionInfo, EncodedPacket, ServerPacket},
     packet_writer::TCPNetworkEncoder,
     packets::{
-        common::{CDisconnect, SClientInformation, SCustomPayload, SPingRequest},
+        common::{CDisconnect, SClientInformation, SCustomPayload, SPingRequest, SResourcePack},
         config::SSelectKnownPacks,
         handshake::{ClientIntent, SClientIntention},
         login::{CLoginDisconnect, SHello, SKey},
@@ -78,6 +79,8 @@ pub struct JavaTcpClient {
     pub gameprofile: AsyncMutex<Option<GameProfile>>,
     /// The client's settings (view distance, language, etc.) received during config.
     pub client_information: AsyncMutex<ClientInformation>,
+    /// Custom payloads received during config, handled once the player exists.
+    pub config_payloads: AsyncMutex<Vec<SCustomPayload>>,
     /// The current connection state of the client (e.g., Handshaking, Status, Play).
     pub protocol: Arc<AtomicCell<ConnectionProtocol>>,
     /// The client's IP address.
@@ -96,6 +99,8 @@ pub struct JavaTcpClient {
     pub server: Arc<Server>,
     /// The challenge sent to the client during login.
     pub challenge: AtomicCell<[u8; 4]>,
+    /// Whether the resource pack was already resent after a failed download.
+    pub resource_pack_retried: AtomicCell<bool>,
 
     /// Channel for broadcasting connection state updates.
     pub connection_updates: Sender<ConnectionUpdate>,
@@ -128,6 +133,7 @@ impl JavaTcpClient {
             id,
             gameprofile: AsyncMutex::new(None),
             client_information: AsyncMutex::new(ClientInformation::default()),
+            config_payloads: AsyncMutex::new(Vec::new()),
             address,
             protocol: Arc::new(AtomicCell::new(ConnectionProtocol::Handshake)),
             cancel_token,
@@ -139,6 +145,7 @@ impl JavaTcpClient {
             compression: Arc::new(AtomicCell::new(None)),
             server,
             challenge: AtomicCell::new([0; 4]),
+            resource_pack_retried: AtomicCell::new(false),
             connection_updates,
             connection_updated: Arc::new(Notify::new()),
             task_tracker,
@@ -230,6 +237,8 @@ impl JavaTcpClient {
                             {
                                 log::warn!("Failed to send packet to client {id}: {err}");
                                 cancel_token.cancel();
+                            } else {
+                                METRICS.packet_sent();
                             }
                         } else {
                             cancel_token.cancel();
@@ -295,6 +304,7 @@ impl JavaTcpClient {
                     packet = reader.get_raw_packet() => {
                         match packet {
                             Ok(packet) => {
+                                METRICS.packet_received();
                                 if let Err(err) = self_clone.process_packet(packet).await {
                                     log::warn!(
                                         "Failed to get packet from client {id}: {err}",
@@ -416,7 +426,8 @@ impl JavaTcpClient {
 
         match packet.id {
             config::S_CUSTOM_PAYLOAD => {
-                self.handle_config_custom_payload(SCustomPayload::read_packet(data)?);
+                self.handle_config_custom_payload(SCustomPayload::read_packet(data)?)
+                    .await;
             }
             config::S_CLIENT_INFORMATION => {
                 self.handle_client_information(SClientInformation::read_packet(data)?)
@@ -426,6 +437,10 @@ impl JavaTcpClient {
                 self.handle_select_known_packs(SSelectKnownPacks::read_packet(data)?)
                     .await;
             }
+            config::S_RESOURCE_PACK => {
+                self.handle_resource_pack(SResourcePack::read_packet(data)?)
+                    .await;
+            }
             config::S_FINISH_CONFIGURATION => {
                 self.finish_configuration().await;
             }
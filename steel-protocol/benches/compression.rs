@@ -0,0 +1,107 @@
+#![allow(missing_docs)]
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::num::NonZeroU32;
+use steel_protocol::packet_traits::{CompressionInfo, EncodedPacket};
+use steel_protocol::packets::game::{
+    CLevelChunkWithLight, ChunkPacketData, HeightmapType, Heightmaps, LightUpdatePacketData,
+};
+use steel_protocol::utils::ConnectionProtocol;
+use steel_utils::ChunkPos;
+use steel_utils::codec::BitSet;
+
+const SECTIONS: usize = 24;
+const STONE: u8 = 1;
+const COAL_ORE: u8 = 2;
+
+/// A chunk of stone with a few ores, encoded like vanilla's section data.
+fn stone_chunk() -> CLevelChunkWithLight {
+    let mut data = Vec::new();
+    for _ in 0..SECTIONS {
+        data.extend_from_slice(&4096i16.to_be_bytes());
+        // 4 bits per entry, palette of stone and coal ore
+        data.extend_from_slice(&[4, 2, STONE, COAL_ORE]);
+        for i in 0..256u64 {
+            let long = if i % 37 == 0 {
+                0x0000_0010_0000_0000
+            } else {
+                0
+            };
+            data.extend_from_slice(&u64::to_be_bytes(long));
+        }
+        // Single valued biomes
+        data.extend_from_slice(&[0, 0]);
+    }
+
+    let heightmap = vec![0x0102_0408_1020_4081; 37];
+    CLevelChunkWithLight {
+        pos: ChunkPos::new(0, 0),
+        chunk_data: ChunkPacketData {
+            heightmaps: Heightmaps {
+                heightmaps: vec![
+                    (HeightmapType::WorldSurface, heightmap.clone()),
+                    (HeightmapType::MotionBlocking, heightmap),
+                ],
+            },
+            data,
+            block_entities: Vec::new(),
+        },
+        light_data: LightUpdatePacketData {
+            sky_y_mask: BitSet(Box::new([(1 << (SECTIONS + 2)) - 1])),
+            block_y_mask: BitSet(Box::new([])),
+            empty_sky_y_mask: BitSet(Box::new([])),
+            empty_block_y_mask: BitSet(Box::new([])),
+            sky_updates: vec![vec![0xFF; 2048]; SECTIONS + 2],
+            block_updates: Vec::new(),
+        },
+    }
+}
+
+fn encoded_len(compression: Option<CompressionInfo>) -> usize {
+    EncodedPacket::from_bare(stone_chunk(), compression, ConnectionProtocol::Play)
+        .expect("chunk should encode")
+        .encoded_data
+        .len()
+}
+
+fn bench_compression(c: &mut Criterion) {
+    let compression = Some(CompressionInfo {
+        threshold: NonZeroU32::new(256).expect("threshold is not zero"),
+        level: 4,
+    });
+
+    let uncompressed = encoded_len(None);
+    let compressed = encoded_len(compression);
+    println!(
+        "stone chunk: {uncompressed} bytes uncompressed, {compressed} bytes compressed ({:.1}% smaller)",
+        100.0 - compressed as f64 / uncompressed as f64 * 100.0
+    );
+
+    let mut group = c.benchmark_group("chunk packet encoding");
+    group.throughput(Throughput::Bytes(uncompressed as u64));
+
+    group.bench_function("uncompressed", |b| {
+        b.iter(|| {
+            black_box(EncodedPacket::from_bare(
+                black_box(stone_chunk()),
+                None,
+                ConnectionProtocol::Play,
+            ))
+        });
+    });
+
+    group.bench_function("compressed, threshold 256", |b| {
+        b.iter(|| {
+            black_box(EncodedPacket::from_bare(
+                black_box(stone_chunk()),
+                compression,
+                ConnectionProtocol::Play,
+            ))
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_compression);
+criterion_main!(benches);
@@ -52,7 +52,7 @@ pub trait ClientPacket: WriteTo {
 }
 
 /// Information about compression.
-#[derive(Copy, Clone, Debug, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
 pub struct CompressionInfo {
     /// The compression threshold used when compression is enabled.
     /// Its an `NonZeroU32` to allow for nullptr optimization in `Option<Self>` cases
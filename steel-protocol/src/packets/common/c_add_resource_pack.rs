@@ -0,0 +1,38 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::config::C_RESOURCE_PACK_PUSH;
+use steel_registry::packets::play::C_RESOURCE_PACK_PUSH as PLAY_C_RESOURCE_PACK_PUSH;
+use text_components::TextComponent;
+use text_components::resolving::TextResolutor;
+use uuid::Uuid;
+
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Config = C_RESOURCE_PACK_PUSH, Play = PLAY_C_RESOURCE_PACK_PUSH)]
+pub struct CAddResourcePack {
+    pub id: Uuid,
+    #[write(as = Prefixed(VarInt))]
+    pub url: String,
+    #[write(as = Prefixed(VarInt))]
+    pub hash: String,
+    pub required: bool,
+    pub prompt: Option<TextComponent>,
+}
+
+impl CAddResourcePack {
+    #[must_use]
+    pub fn new<T: TextResolutor>(
+        id: Uuid,
+        url: String,
+        hash: String,
+        required: bool,
+        prompt: Option<&TextComponent>,
+        player: &T,
+    ) -> Self {
+        Self {
+            id,
+            url,
+            hash,
+            required,
+            prompt: prompt.map(|prompt| prompt.resolve(player)),
+        }
+    }
+}
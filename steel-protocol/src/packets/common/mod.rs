@@ -1,3 +1,4 @@
+mod c_add_resource_pack;
 mod c_custom_payload;
 mod c_disconnect;
 mod c_keep_alive;
@@ -7,7 +8,9 @@ mod s_client_information;
 mod s_custom_payload;
 mod s_keep_alive;
 mod s_ping_request;
+mod s_resource_pack;
 
+pub use c_add_resource_pack::CAddResourcePack;
 pub use c_custom_payload::CCustomPayload;
 pub use c_disconnect::CDisconnect;
 pub use c_keep_alive::CKeepAlive;
@@ -18,3 +21,4 @@ pub use s_client_information::{ChatVisibility, HumanoidArm, ParticleStatus, SCli
 pub use s_custom_payload::SCustomPayload;
 pub use s_keep_alive::SKeepAlive;
 pub use s_ping_request::SPingRequest;
+pub use s_resource_pack::{ResourcePackAction, SResourcePack};
@@ -0,0 +1,28 @@
+use steel_macros::{ReadFrom, ServerPacket};
+use uuid::Uuid;
+
+#[derive(ReadFrom, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourcePackAction {
+    SuccessfullyLoaded = 0,
+    Declined = 1,
+    FailedDownload = 2,
+    Accepted = 3,
+    Downloaded = 4,
+    InvalidUrl = 5,
+    FailedReload = 6,
+    Discarded = 7,
+}
+
+impl ResourcePackAction {
+    /// Whether the client is done with the pack, either loaded or given up on.
+    #[must_use]
+    pub const fn is_terminal(self) -> bool {
+        !matches!(self, Self::Accepted | Self::Downloaded)
+    }
+}
+
+#[derive(ReadFrom, ServerPacket, Clone, Debug)]
+pub struct SResourcePack {
+    pub id: Uuid,
+    pub action: ResourcePackAction,
+}
@@ -50,10 +50,10 @@ impl WriteTo for CAddEntity {
 }
 
 impl CAddEntity {
-    /// Creates a new CAddEntity packet for spawning a player.
+    /// Creates a new CAddEntity packet for spawning an entity without velocity.
     #[must_use]
     #[allow(clippy::too_many_arguments)]
-    pub fn player(
+    pub fn new(
         id: i32,
         uuid: Uuid,
         entity_type_id: i32,
@@ -76,4 +76,20 @@ impl CAddEntity {
             data: 0,
         }
     }
+
+    /// Creates a new CAddEntity packet for spawning a player.
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    pub fn player(
+        id: i32,
+        uuid: Uuid,
+        entity_type_id: i32,
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: f32,
+        pitch: f32,
+    ) -> Self {
+        Self::new(id, uuid, entity_type_id, x, y, z, yaw, pitch)
+    }
 }
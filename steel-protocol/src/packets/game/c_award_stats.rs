@@ -0,0 +1,21 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_AWARD_STATS;
+
+/// A single statistic and its new value.
+#[derive(WriteTo, Clone, Debug)]
+pub struct AwardedStat {
+    /// Registry id of the stat type (mined, crafted, custom, ...).
+    #[write(as = VarInt)]
+    pub stat_type: i32,
+    /// Registry id of the block, item, entity type or custom stat within the stat type.
+    #[write(as = VarInt)]
+    pub value: i32,
+    #[write(as = VarInt)]
+    pub amount: i32,
+}
+
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_AWARD_STATS)]
+pub struct CAwardStats {
+    pub stats: Vec<AwardedStat>,
+}
@@ -0,0 +1,12 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_CHANGE_DIFFICULTY;
+use steel_utils::types::Difficulty;
+
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_CHANGE_DIFFICULTY)]
+pub struct CChangeDifficulty {
+    #[write(as = VarInt)]
+    pub difficulty: Difficulty,
+    /// Whether the difficulty button is locked in the client's options screen.
+    pub locked: bool,
+}
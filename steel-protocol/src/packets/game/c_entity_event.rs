@@ -0,0 +1,29 @@
+//! Packet for triggering a client-side entity event.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_ENTITY_EVENT;
+
+/// Event id that makes an armor stand wobble after a hit.
+pub const ENTITY_EVENT_ARMOR_STAND_HIT: i8 = 32;
+/// Event id that makes a mob swing its arms, like an iron golem hitting
+/// something.
+pub const ENTITY_EVENT_START_ATTACKING: i8 = 4;
+/// Event id that makes an iron golem hold out a poppy.
+pub const ENTITY_EVENT_OFFER_FLOWER: i8 = 11;
+/// Event id that makes an iron golem put its poppy away.
+pub const ENTITY_EVENT_STOP_OFFER_FLOWER: i8 = 34;
+/// Event id that makes a firework rocket burst.
+pub const ENTITY_EVENT_FIREWORKS_EXPLODE: i8 = 17;
+/// Event id that enables reduced debug info for the receiving player.
+pub const ENTITY_EVENT_REDUCED_DEBUG_INFO_ENABLED: i8 = 22;
+/// Event id that disables reduced debug info for the receiving player.
+pub const ENTITY_EVENT_REDUCED_DEBUG_INFO_DISABLED: i8 = 23;
+
+/// Triggers an event on an entity, such as an animation or a status change.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_ENTITY_EVENT)]
+pub struct CEntityEvent {
+    /// Entity id, sent as a plain int rather than a `VarInt`.
+    pub entity_id: i32,
+    pub event: i8,
+}
@@ -0,0 +1,28 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_INITIALIZE_BORDER;
+
+/// Sends the full world border state, e.g. when a player joins.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_INITIALIZE_BORDER)]
+pub struct CInitializeBorder {
+    /// X coordinate of the border center.
+    pub center_x: f64,
+    /// Z coordinate of the border center.
+    pub center_z: f64,
+    /// Current diameter of the border.
+    pub old_size: f64,
+    /// Diameter the border is moving towards. Equal to `old_size` if it isn't moving.
+    pub new_size: f64,
+    /// Milliseconds until `new_size` is reached.
+    #[write(as = VarLong)]
+    pub lerp_time: i64,
+    /// Largest distance from the origin the border may ever reach.
+    #[write(as = VarInt)]
+    pub absolute_max_size: i32,
+    /// Distance in blocks at which the warning overlay starts.
+    #[write(as = VarInt)]
+    pub warning_blocks: i32,
+    /// Seconds before a shrinking border reaches the player at which the warning starts.
+    #[write(as = VarInt)]
+    pub warning_time: i32,
+}
@@ -0,0 +1,62 @@
+//! Clientbound map item data packet - sent to fill in the contents of a map
+//! the player holds or sees in an item frame.
+
+use std::io::{Result, Write};
+
+use steel_macros::ClientPacket;
+use steel_registry::packets::play::C_MAP_ITEM_DATA;
+use steel_utils::{codec::VarInt, serial::WriteTo};
+
+/// A rectangle of map colors that changed.
+#[derive(Clone, Debug)]
+pub struct MapPatch {
+    /// The left edge of the rectangle, in map pixels.
+    pub start_x: u8,
+    /// The top edge of the rectangle, in map pixels.
+    pub start_y: u8,
+    /// The width of the rectangle. Must not be 0.
+    pub width: u8,
+    /// The height of the rectangle.
+    pub height: u8,
+    /// The packed colors of the rectangle, row by row.
+    pub colors: Vec<u8>,
+}
+
+/// Updates a map on the client.
+///
+/// Decorations are never sent yet, so the client keeps whatever it had.
+#[derive(ClientPacket, Clone, Debug)]
+#[packet_id(Play = C_MAP_ITEM_DATA)]
+pub struct CMapItemData {
+    /// The id of the map, from the `minecraft:map_id` component.
+    pub map_id: i32,
+    /// The zoom level, 0 to 4.
+    pub scale: i8,
+    /// Whether the map was locked in a cartography table.
+    pub locked: bool,
+    /// The colors that changed, if any.
+    pub color_patch: Option<MapPatch>,
+}
+
+impl WriteTo for CMapItemData {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        VarInt(self.map_id).write(writer)?;
+        self.scale.write(writer)?;
+        self.locked.write(writer)?;
+        // Decorations
+        false.write(writer)?;
+
+        match &self.color_patch {
+            // A width of 0 marks a packet without color changes
+            None => 0u8.write(writer),
+            Some(patch) => {
+                patch.width.write(writer)?;
+                patch.height.write(writer)?;
+                patch.start_x.write(writer)?;
+                patch.start_y.write(writer)?;
+                VarInt(patch.colors.len() as i32).write(writer)?;
+                writer.write_all(&patch.colors)
+            }
+        }
+    }
+}
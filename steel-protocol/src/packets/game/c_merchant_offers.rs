@@ -0,0 +1,90 @@
+//! Clientbound merchant offers packet - sent to fill the trading screen.
+
+use std::io::{Result, Write};
+
+use steel_macros::ClientPacket;
+use steel_registry::{REGISTRY, item_stack::ItemStack, packets::play::C_MERCHANT_OFFERS};
+use steel_utils::{codec::VarInt, serial::WriteTo};
+
+/// A single trade shown in the trading screen.
+#[derive(Clone, Debug)]
+pub struct MerchantOfferEntry {
+    /// The first item the merchant wants.
+    pub cost_a: ItemStack,
+    /// The item the merchant gives.
+    pub result: ItemStack,
+    /// The second item the merchant wants, if any.
+    pub cost_b: Option<ItemStack>,
+    /// Whether the trade is used up until the merchant restocks.
+    pub out_of_stock: bool,
+    /// How often the trade was used since the last restock.
+    pub uses: i32,
+    /// How often the trade can be used before it runs out.
+    pub max_uses: i32,
+    /// The experience the merchant gains from the trade.
+    pub xp: i32,
+    /// How much cheaper or more expensive the first cost currently is.
+    pub special_price_diff: i32,
+    /// How much the price reacts to demand.
+    pub price_multiplier: f32,
+    /// How often the trade was used recently, raising its price.
+    pub demand: i32,
+}
+
+/// Writes a trade cost the way vanilla's `ItemCost` does: the item, the count
+/// and the components it has to match, which we never require.
+fn write_item_cost(stack: &ItemStack, writer: &mut impl Write) -> Result<()> {
+    VarInt(*REGISTRY.items.get_id(stack.item()) as i32).write(writer)?;
+    VarInt(stack.count()).write(writer)?;
+    VarInt(0).write(writer)
+}
+
+impl WriteTo for MerchantOfferEntry {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        write_item_cost(&self.cost_a, writer)?;
+        self.result.write(writer)?;
+        if let Some(cost_b) = &self.cost_b {
+            true.write(writer)?;
+            write_item_cost(cost_b, writer)?;
+        } else {
+            false.write(writer)?;
+        }
+        self.out_of_stock.write(writer)?;
+        self.uses.write(writer)?;
+        self.max_uses.write(writer)?;
+        self.xp.write(writer)?;
+        self.special_price_diff.write(writer)?;
+        self.price_multiplier.write(writer)?;
+        self.demand.write(writer)
+    }
+}
+
+/// Sent after opening a merchant menu to show the trades and the merchant's
+/// progress.
+#[derive(ClientPacket, Clone, Debug)]
+#[packet_id(Play = C_MERCHANT_OFFERS)]
+pub struct CMerchantOffers {
+    /// The container ID of the open merchant menu.
+    pub container_id: i32,
+    /// The trades the merchant offers.
+    pub offers: Vec<MerchantOfferEntry>,
+    /// The merchant's level, from 1 (novice) to 5 (master).
+    pub villager_level: i32,
+    /// The experience the merchant gained from trading.
+    pub villager_xp: i32,
+    /// Whether the screen shows the level and experience bar.
+    pub show_progress: bool,
+    /// Whether the merchant restocks its trades over time.
+    pub can_restock: bool,
+}
+
+impl WriteTo for CMerchantOffers {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        VarInt(self.container_id).write(writer)?;
+        self.offers.write(writer)?;
+        VarInt(self.villager_level).write(writer)?;
+        VarInt(self.villager_xp).write(writer)?;
+        self.show_progress.write(writer)?;
+        self.can_restock.write(writer)
+    }
+}
@@ -0,0 +1,19 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_RESPAWN;
+
+use super::c_login::CommonPlayerSpawnInfo;
+
+/// Keep the player's attribute modifiers through the respawn.
+pub const KEEP_ATTRIBUTE_MODIFIERS: u8 = 0x01;
+/// Keep the player's entity data (health, effects, etc.) through the respawn.
+pub const KEEP_ENTITY_DATA: u8 = 0x02;
+/// Keep everything, used when changing dimension rather than dying.
+pub const KEEP_ALL_DATA: u8 = KEEP_ATTRIBUTE_MODIFIERS | KEEP_ENTITY_DATA;
+
+/// Moves the client into a new world, dropping all chunks and entities it knows about.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_RESPAWN)]
+pub struct CRespawn {
+    pub common_player_spawn_info: CommonPlayerSpawnInfo,
+    pub data_to_keep: u8,
+}
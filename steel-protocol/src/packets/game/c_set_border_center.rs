@@ -0,0 +1,12 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_BORDER_CENTER;
+
+/// Moves the center of the world border.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_BORDER_CENTER)]
+pub struct CSetBorderCenter {
+    /// X coordinate of the new center.
+    pub x: f64,
+    /// Z coordinate of the new center.
+    pub z: f64,
+}
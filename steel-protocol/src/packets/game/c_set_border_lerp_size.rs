@@ -0,0 +1,15 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_BORDER_LERP_SIZE;
+
+/// Starts (or resynchronizes) a world border size change over time.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_BORDER_LERP_SIZE)]
+pub struct CSetBorderLerpSize {
+    /// Current diameter of the border.
+    pub old_size: f64,
+    /// Diameter the border is moving towards.
+    pub new_size: f64,
+    /// Milliseconds until `new_size` is reached.
+    #[write(as = VarLong)]
+    pub lerp_time: i64,
+}
@@ -0,0 +1,10 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_BORDER_SIZE;
+
+/// Sets the world border diameter immediately.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_BORDER_SIZE)]
+pub struct CSetBorderSize {
+    /// The new diameter.
+    pub size: f64,
+}
@@ -0,0 +1,11 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_BORDER_WARNING_DELAY;
+
+/// Sets how many seconds ahead of a shrinking border the warning overlay starts.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_BORDER_WARNING_DELAY)]
+pub struct CSetBorderWarningDelay {
+    /// Warning time in seconds.
+    #[write(as = VarInt)]
+    pub warning_delay: i32,
+}
@@ -0,0 +1,11 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_BORDER_WARNING_DISTANCE;
+
+/// Sets how close to the border the warning overlay starts.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_BORDER_WARNING_DISTANCE)]
+pub struct CSetBorderWarningDistance {
+    /// Warning distance in blocks.
+    #[write(as = VarInt)]
+    pub warning_blocks: i32,
+}
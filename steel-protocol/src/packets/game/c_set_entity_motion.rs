@@ -0,0 +1,30 @@
+//! Packet sent when an entity's velocity changes abruptly.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_ENTITY_MOTION;
+use steel_utils::codec::LpVec3;
+use steel_utils::math::Vector3;
+
+/// Sets the velocity of an entity, like after an explosion pushed it. Sent
+/// to a player about themselves, it moves them, since their client moves
+/// them.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_ENTITY_MOTION)]
+pub struct CSetEntityMotion {
+    /// The entity whose velocity changed.
+    #[write(as = VarInt)]
+    pub entity_id: i32,
+    /// The new velocity, in blocks per tick.
+    pub velocity: LpVec3,
+}
+
+impl CSetEntityMotion {
+    /// Creates the packet setting the velocity of `entity_id` to `velocity`.
+    #[must_use]
+    pub const fn new(entity_id: i32, velocity: Vector3<f64>) -> Self {
+        Self {
+            entity_id,
+            velocity: LpVec3(velocity),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! Clientbound set equipment packet - sent to show what an entity wears and holds.
+
+use std::io::{Result, Write};
+
+use steel_macros::ClientPacket;
+use steel_registry::{item_stack::ItemStack, packets::play::C_SET_EQUIPMENT};
+use steel_utils::{codec::VarInt, serial::WriteTo};
+
+/// Bit set on a slot id when another slot follows it.
+const CONTINUE_MASK: u8 = 0x80;
+
+/// Sent to show the items in an entity's armor and hand slots.
+#[derive(ClientPacket, Clone, Debug)]
+#[packet_id(Play = C_SET_EQUIPMENT)]
+pub struct CSetEquipment {
+    /// The entity ID whose equipment is shown.
+    pub entity_id: i32,
+    /// The changed slots, by the id of the equipment slot.
+    pub slots: Vec<(u8, ItemStack)>,
+}
+
+impl CSetEquipment {
+    /// Creates a new set equipment packet.
+    #[must_use]
+    pub fn new(entity_id: i32, slots: Vec<(u8, ItemStack)>) -> Self {
+        Self { entity_id, slots }
+    }
+}
+
+impl WriteTo for CSetEquipment {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        VarInt(self.entity_id).write(writer)?;
+        for (index, (slot, item)) in self.slots.iter().enumerate() {
+            let last = index + 1 == self.slots.len();
+            let slot = if last { *slot } else { *slot | CONTINUE_MASK };
+            slot.write(writer)?;
+            item.write(writer)?;
+        }
+        Ok(())
+    }
+}
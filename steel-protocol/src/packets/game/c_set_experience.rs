@@ -0,0 +1,18 @@
+//! Packet for updating the receiving player's experience bar.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_EXPERIENCE;
+
+/// Updates the experience bar and level shown in the receiving player's HUD.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_EXPERIENCE)]
+pub struct CSetExperience {
+    /// How far the bar is filled towards the next level, from 0 to 1.
+    pub progress: f32,
+    /// The player's level.
+    #[write(as = VarInt)]
+    pub level: i32,
+    /// The experience points collected since the player last died.
+    #[write(as = VarInt)]
+    pub total: i32,
+}
@@ -0,0 +1,15 @@
+//! Packet for updating the receiving player's health and food.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_HEALTH;
+
+/// Updates the health, food level and saturation shown in the receiving
+/// player's HUD.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_HEALTH)]
+pub struct CSetHealth {
+    pub health: f32,
+    #[write(as = VarInt)]
+    pub food: i32,
+    pub saturation: f32,
+}
@@ -0,0 +1,17 @@
+//! Packet sent to set which entities ride an entity.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_PASSENGERS;
+
+/// Sets the passengers of an entity, replacing the ones it had. An empty
+/// list lets every passenger off.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_PASSENGERS)]
+pub struct CSetPassengers {
+    /// The entity the passengers ride.
+    #[write(as = VarInt)]
+    pub vehicle_id: i32,
+    /// The entity ids of the passengers.
+    #[write(as = Prefixed(VarInt, inner = VarInt))]
+    pub passenger_ids: Vec<i32>,
+}
@@ -0,0 +1,146 @@
+//! Packet to create, change or remove a scoreboard team and its members.
+
+use std::io::{Result, Write};
+
+use steel_macros::ClientPacket;
+use steel_registry::packets::play::C_SET_PLAYER_TEAM;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::{PrefixedWrite, WriteTo};
+use text_components::TextComponent;
+
+/// A team's display settings, sent when the team is created or changed.
+#[derive(Debug, Clone)]
+pub struct TeamParameters {
+    pub display_name: TextComponent,
+    /// Bit 0 allows friendly fire, bit 1 shows invisible teammates.
+    pub options: u8,
+    /// The `Team.Visibility` id of the name tags.
+    pub nametag_visibility: i32,
+    /// The `Team.CollisionRule` id.
+    pub collision_rule: i32,
+    /// The `ChatFormatting` id of the team color.
+    pub color: i32,
+    pub prefix: TextComponent,
+    pub suffix: TextComponent,
+}
+
+/// What the packet does to the team.
+#[derive(Debug, Clone)]
+pub enum TeamMethod {
+    /// Creates the team with its initial members.
+    Add {
+        parameters: Box<TeamParameters>,
+        players: Vec<String>,
+    },
+    Remove,
+    Change(Box<TeamParameters>),
+    Join(Vec<String>),
+    Leave(Vec<String>),
+}
+
+/// Creates, changes or removes a team, or adds or removes its members.
+#[derive(ClientPacket, Clone, Debug)]
+#[packet_id(Play = C_SET_PLAYER_TEAM)]
+pub struct CSetPlayerTeam {
+    pub name: String,
+    pub method: TeamMethod,
+}
+
+impl CSetPlayerTeam {
+    /// Creates a packet that adds a team with the given members.
+    #[must_use]
+    pub fn add(name: String, parameters: TeamParameters, players: Vec<String>) -> Self {
+        Self {
+            name,
+            method: TeamMethod::Add {
+                parameters: Box::new(parameters),
+                players,
+            },
+        }
+    }
+
+    /// Creates a packet that removes a team.
+    #[must_use]
+    pub fn remove(name: String) -> Self {
+        Self {
+            name,
+            method: TeamMethod::Remove,
+        }
+    }
+
+    /// Creates a packet that updates a team's display settings.
+    #[must_use]
+    pub fn change(name: String, parameters: TeamParameters) -> Self {
+        Self {
+            name,
+            method: TeamMethod::Change(Box::new(parameters)),
+        }
+    }
+
+    /// Creates a packet that adds members to a team.
+    #[must_use]
+    pub fn join(name: String, players: Vec<String>) -> Self {
+        Self {
+            name,
+            method: TeamMethod::Join(players),
+        }
+    }
+
+    /// Creates a packet that removes members from a team.
+    #[must_use]
+    pub fn leave(name: String, players: Vec<String>) -> Self {
+        Self {
+            name,
+            method: TeamMethod::Leave(players),
+        }
+    }
+}
+
+impl WriteTo for TeamParameters {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        self.display_name.write(writer)?;
+        self.options.write(writer)?;
+        VarInt(self.nametag_visibility).write(writer)?;
+        VarInt(self.collision_rule).write(writer)?;
+        VarInt(self.color).write(writer)?;
+        self.prefix.write(writer)?;
+        self.suffix.write(writer)
+    }
+}
+
+fn write_players(players: &[String], writer: &mut impl Write) -> Result<()> {
+    VarInt(players.len() as i32).write(writer)?;
+    for player in players {
+        player.write_prefixed::<VarInt>(writer)?;
+    }
+    Ok(())
+}
+
+impl WriteTo for CSetPlayerTeam {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        self.name.write_prefixed::<VarInt>(writer)?;
+        match &self.method {
+            TeamMethod::Add {
+                parameters,
+                players,
+            } => {
+                0u8.write(writer)?;
+                parameters.write(writer)?;
+                write_players(players, writer)
+            }
+            TeamMethod::Remove => 1u8.write(writer),
+            TeamMethod::Change(parameters) => {
+                2u8.write(writer)?;
+                parameters.write(writer)
+            }
+            TeamMethod::Join(players) => {
+                3u8.write(writer)?;
+                write_players(players, writer)
+            }
+            TeamMethod::Leave(players) => {
+                4u8.write(writer)?;
+                write_players(players, writer)
+            }
+        }
+    }
+}
@@ -1,5 +1,10 @@
-use steel_macros::{ClientPacket, WriteTo};
+use std::io::{Result, Write};
+
+use steel_macros::ClientPacket;
 use steel_registry::packets::play::C_SOUND;
+use steel_utils::Identifier;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::WriteTo;
 
 /// Sound source categories (matches vanilla SoundSource enum order).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,19 +31,47 @@ impl SoundSource {
     }
 }
 
+/// The sound a [`CSound`] plays.
+#[derive(Clone, Debug)]
+pub enum SoundEvent {
+    /// A sound event registry ID from `steel_registry::sound_events`.
+    Registered(i32),
+    /// A sound by name, such as one added by a resource pack.
+    Direct {
+        /// The sound's location in the client's sound definitions.
+        location: Identifier,
+        /// The distance it can be heard from, instead of one based on volume.
+        fixed_range: Option<f32>,
+    },
+}
+
+impl WriteTo for SoundEvent {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        // Holders are written as the registry ID plus one, or 0 followed by the value
+        match self {
+            Self::Registered(id) => VarInt(id + 1).write(writer),
+            Self::Direct {
+                location,
+                fixed_range,
+            } => {
+                VarInt(0).write(writer)?;
+                location.write(writer)?;
+                fixed_range.write(writer)
+            }
+        }
+    }
+}
+
 /// Sent to play a sound effect at a specific position.
 ///
 /// The position is encoded at 8x precision (divide by 8 to get actual block coordinates).
 /// This allows sub-block positioning for more accurate sound placement.
-#[derive(WriteTo, ClientPacket, Clone, Debug)]
+#[derive(ClientPacket, Clone, Debug)]
 #[packet_id(Play = C_SOUND)]
 pub struct CSound {
-    /// The sound event registry ID (VarInt).
-    /// Use `steel_registry::sound_events` for sound constants.
-    #[write(as = VarInt)]
-    pub sound_id: i32,
+    /// The sound to play.
+    pub sound: SoundEvent,
     /// The sound source category (VarInt).
-    #[write(as = VarInt)]
     pub source: i32,
     /// X position multiplied by 8 (fixed-point).
     pub x: i32,
@@ -58,7 +91,7 @@ impl CSound {
     /// Creates a new sound packet.
     ///
     /// # Arguments
-    /// * `sound_id` - Sound event registry ID
+    /// * `sound` - The sound to play
     /// * `source` - Sound source category
     /// * `x`, `y`, `z` - Position in block coordinates (will be scaled by 8)
     /// * `volume` - Volume multiplier (1.0 = normal)
@@ -67,7 +100,7 @@ impl CSound {
     #[must_use]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
-        sound_id: i32,
+        sound: SoundEvent,
         source: SoundSource,
         x: f64,
         y: f64,
@@ -77,7 +110,7 @@ impl CSound {
         seed: i64,
     ) -> Self {
         Self {
-            sound_id,
+            sound,
             source: source.as_varint(),
             x: (x * 8.0) as i32,
             y: (y * 8.0) as i32,
@@ -105,7 +138,7 @@ impl CSound {
         seed: i64,
     ) -> Self {
         Self::new(
-            sound_id,
+            SoundEvent::Registered(sound_id),
             SoundSource::Blocks,
             f64::from(pos.x()) + 0.5,
             f64::from(pos.y()) + 0.5,
@@ -116,3 +149,16 @@ impl CSound {
         )
     }
 }
+
+impl WriteTo for CSound {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        self.sound.write(writer)?;
+        VarInt(self.source).write(writer)?;
+        self.x.write(writer)?;
+        self.y.write(writer)?;
+        self.z.write(writer)?;
+        self.volume.write(writer)?;
+        self.pitch.write(writer)?;
+        self.seed.write(writer)
+    }
+}
@@ -0,0 +1,20 @@
+//! Packet sent when an entity picks up an item or experience orb.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_TAKE_ITEM_ENTITY;
+
+/// Plays the animation of an item or experience orb flying into the entity
+/// that collected it.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_TAKE_ITEM_ENTITY)]
+pub struct CTakeItemEntity {
+    /// The entity that was picked up.
+    #[write(as = VarInt)]
+    pub item_id: i32,
+    /// The entity that picked it up.
+    #[write(as = VarInt)]
+    pub collector_id: i32,
+    /// How many items were picked up, 1 for experience orbs.
+    #[write(as = VarInt)]
+    pub amount: i32,
+}
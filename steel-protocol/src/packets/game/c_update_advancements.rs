@@ -0,0 +1,108 @@
+use std::io::{Result, Write};
+
+use steel_macros::ClientPacket;
+use steel_registry::{
+    REGISTRY,
+    advancement::{AdvancementFrame, AdvancementRef},
+    item_stack::ItemStack,
+    packets::play::C_UPDATE_ADVANCEMENTS,
+    vanilla_items::ITEMS,
+};
+use steel_utils::{
+    Identifier,
+    codec::VarInt,
+    serial::{PrefixedWrite, WriteTo},
+};
+
+const FLAG_BACKGROUND: i32 = 0x1;
+const FLAG_SHOW_TOAST: i32 = 0x2;
+const FLAG_HIDDEN: i32 = 0x4;
+
+/// Progress of a single advancement: every criterion with the time it was
+/// completed at in milliseconds since the epoch, or `None` if it isn't done.
+pub type CriteriaProgress = Vec<(String, Option<i64>)>;
+
+#[derive(ClientPacket, Clone, Debug)]
+#[packet_id(Play = C_UPDATE_ADVANCEMENTS)]
+pub struct CUpdateAdvancements {
+    /// Clears all advancements the client knows about before applying this update.
+    pub reset: bool,
+    pub added: Vec<AdvancementRef>,
+    pub removed: Vec<Identifier>,
+    pub progress: Vec<(Identifier, CriteriaProgress)>,
+    /// Whether the client should show toasts for advancements completed by this update.
+    pub show_advancements: bool,
+}
+
+fn write_advancement(advancement: AdvancementRef, writer: &mut impl Write) -> Result<()> {
+    advancement.key.write(writer)?;
+    advancement.parent.write(writer)?;
+
+    match &advancement.display {
+        Some(display) => {
+            true.write(writer)?;
+            display.title.write(writer)?;
+            display.description.write(writer)?;
+            let icon = REGISTRY.items.by_key(&display.icon).unwrap_or(&ITEMS.air);
+            ItemStack::with_count(icon, display.icon_count).write(writer)?;
+            let frame = match display.frame {
+                AdvancementFrame::Task => 0,
+                AdvancementFrame::Challenge => 1,
+                AdvancementFrame::Goal => 2,
+            };
+            VarInt(frame).write(writer)?;
+
+            let mut flags = 0;
+            if display.background.is_some() {
+                flags |= FLAG_BACKGROUND;
+            }
+            if display.show_toast {
+                flags |= FLAG_SHOW_TOAST;
+            }
+            if display.hidden {
+                flags |= FLAG_HIDDEN;
+            }
+            flags.write(writer)?;
+            if let Some(background) = &display.background {
+                background.write(writer)?;
+            }
+            display.x.write(writer)?;
+            display.y.write(writer)?;
+        }
+        None => false.write(writer)?,
+    }
+
+    VarInt(advancement.requirements.len() as i32).write(writer)?;
+    for group in advancement.requirements {
+        VarInt(group.len() as i32).write(writer)?;
+        for criterion in *group {
+            criterion.write_prefixed::<VarInt>(writer)?;
+        }
+    }
+    advancement.sends_telemetry_event.write(writer)
+}
+
+impl WriteTo for CUpdateAdvancements {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        self.reset.write(writer)?;
+
+        VarInt(self.added.len() as i32).write(writer)?;
+        for &advancement in &self.added {
+            write_advancement(advancement, writer)?;
+        }
+
+        self.removed.write(writer)?;
+
+        VarInt(self.progress.len() as i32).write(writer)?;
+        for (key, criteria) in &self.progress {
+            key.write(writer)?;
+            VarInt(criteria.len() as i32).write(writer)?;
+            for (criterion, obtained) in criteria {
+                criterion.write_prefixed::<VarInt>(writer)?;
+                obtained.write(writer)?;
+            }
+        }
+
+        self.show_advancements.write(writer)
+    }
+}
@@ -0,0 +1,54 @@
+//! Clientbound update recipes packet - sent on join with the recipes the
+//! client needs to know about.
+
+use std::io::{Result, Write};
+
+use steel_macros::ClientPacket;
+use steel_registry::{
+    REGISTRY,
+    packets::play::C_UPDATE_RECIPES,
+    recipe::{Ingredient, StonecuttingRecipe},
+};
+use steel_utils::{codec::VarInt, serial::WriteTo};
+
+/// The `minecraft:item_stack` slot display type id.
+const ITEM_STACK_SLOT_DISPLAY: i32 = 3;
+
+/// Sends the stonecutter recipes the client lists in the stonecutter screen.
+///
+/// The client also gets the item property sets here, which drive which items
+/// it lets into furnace and smithing slots before the server answers. None
+/// are sent yet, so the client waits for the server on those slots.
+#[derive(ClientPacket, Clone, Debug)]
+#[packet_id(Play = C_UPDATE_RECIPES)]
+pub struct CUpdateRecipes {
+    /// The stonecutter recipes, in the order the server selects them by.
+    pub stonecutter_recipes: Vec<&'static StonecuttingRecipe>,
+}
+
+/// Writes an ingredient as the item holder set vanilla uses, spelling tags
+/// out as their items.
+fn write_ingredient(ingredient: &Ingredient, writer: &mut impl Write) -> Result<()> {
+    let items = ingredient.get_items();
+    // 0 marks a tag, anything else is the number of items plus one
+    VarInt(items.len() as i32 + 1).write(writer)?;
+    for item in items {
+        VarInt(*REGISTRY.items.get_id(item) as i32).write(writer)?;
+    }
+    Ok(())
+}
+
+impl WriteTo for CUpdateRecipes {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        // Item property sets
+        VarInt(0).write(writer)?;
+
+        VarInt(self.stonecutter_recipes.len() as i32).write(writer)?;
+        for recipe in &self.stonecutter_recipes {
+            write_ingredient(&recipe.ingredient, writer)?;
+            VarInt(ITEM_STACK_SLOT_DISPLAY).write(writer)?;
+            recipe.result.to_item_stack().write(writer)?;
+        }
+        Ok(())
+    }
+}
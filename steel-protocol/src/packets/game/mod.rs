@@ -1,10 +1,12 @@
 mod c_add_entity;
 mod c_animate;
+mod c_award_stats;
 mod c_block_changed_ack;
 mod c_block_destruction;
 mod c_block_entity_data;
 mod c_block_event;
 mod c_block_update;
+mod c_change_difficulty;
 mod c_chunk_batch_finished;
 mod c_chunk_batch_start;
 mod c_command_suggestions;
@@ -14,12 +16,16 @@ mod c_container_set_content;
 mod c_container_set_data;
 mod c_container_set_slot;
 mod c_disguised_chat;
+mod c_entity_event;
 mod c_entity_position_sync;
 mod c_forget_level_chunk;
 mod c_game_event;
+mod c_initialize_border;
 mod c_level_chunk_with_light;
 mod c_level_event;
 mod c_login;
+mod c_map_item_data;
+mod c_merchant_offers;
 mod c_move_entity;
 mod c_open_screen;
 mod c_open_sign_editor;
@@ -29,19 +35,34 @@ mod c_player_info_update;
 mod c_player_position;
 mod c_remove_entities;
 mod c_remove_player_info;
+mod c_respawn;
 mod c_rotate_head;
 mod c_section_blocks_update;
+mod c_set_border_center;
+mod c_set_border_lerp_size;
+mod c_set_border_size;
+mod c_set_border_warning_delay;
+mod c_set_border_warning_distance;
 mod c_set_chunk_cache_radius;
 mod c_set_chunk_center;
 mod c_set_cursor_item;
 mod c_set_entity_data;
+mod c_set_entity_motion;
+mod c_set_equipment;
+mod c_set_experience;
+mod c_set_health;
 mod c_set_held_slot;
+mod c_set_passengers;
+mod c_set_player_team;
 mod c_sound;
 mod c_system_chat;
 mod c_system_chat_message;
 mod c_tab_list;
+mod c_take_item_entity;
 mod c_ticking_state;
 mod c_ticking_step;
+mod c_update_advancements;
+mod c_update_recipes;
 mod chat_session_data;
 mod s_accept_teleportation;
 mod s_chat;
@@ -50,18 +71,23 @@ mod s_chat_command;
 mod s_chat_command_signed;
 mod s_chat_session_update;
 mod s_chunk_batch_received;
+mod s_client_command;
 mod s_client_tick_end;
 mod s_command_suggestion;
 mod s_container_button_click;
 mod s_container_click;
 mod s_container_close;
 mod s_container_slot_state_changed;
+mod s_interact;
 mod s_move_player;
 mod s_pick_item_from_block;
 mod s_player_abilities;
 mod s_player_action;
+mod s_player_command;
 mod s_player_input;
 mod s_player_load;
+mod s_rename_item;
+mod s_select_trade;
 mod s_set_carried_item;
 mod s_set_creative_mode_slot;
 mod s_set_held_item;
@@ -72,24 +98,36 @@ mod s_use_item_on;
 
 pub use c_add_entity::CAddEntity;
 pub use c_animate::{AnimateAction, CAnimate};
+pub use c_award_stats::{AwardedStat, CAwardStats};
 pub use c_block_changed_ack::CBlockChangedAck;
 pub use c_block_destruction::CBlockDestruction;
 pub use c_block_entity_data::CBlockEntityData;
 pub use c_block_event::CBlockEvent;
 pub use c_block_update::CBlockUpdate;
+pub use c_change_difficulty::CChangeDifficulty;
 pub use c_chunk_batch_finished::CChunkBatchFinished;
 pub use c_chunk_batch_start::CChunkBatchStart;
 pub use c_command_suggestions::{CCommandSuggestions, SuggestionEntry};
-pub use c_commands::{ArgumentType, CCommands, CommandNode, CommandNodeInfo, SuggestionType};
+pub use c_commands::{
+    ArgumentStringTypeBehavior, ArgumentType, CCommands, CommandNode, CommandNodeInfo,
+    SuggestionType,
+};
 pub use c_container_close::CContainerClose;
 pub use c_container_set_content::CContainerSetContent;
 pub use c_container_set_data::CContainerSetData;
 pub use c_container_set_slot::CContainerSetSlot;
 pub use c_disguised_chat::CDisguisedChat;
+pub use c_entity_event::{
+    CEntityEvent, ENTITY_EVENT_ARMOR_STAND_HIT, ENTITY_EVENT_FIREWORKS_EXPLODE,
+    ENTITY_EVENT_OFFER_FLOWER, ENTITY_EVENT_REDUCED_DEBUG_INFO_DISABLED,
+    ENTITY_EVENT_REDUCED_DEBUG_INFO_ENABLED, ENTITY_EVENT_START_ATTACKING,
+    ENTITY_EVENT_STOP_OFFER_FLOWER,
+};
 pub use c_entity_position_sync::CEntityPositionSync;
 pub use c_forget_level_chunk::CForgetLevelChunk;
 pub use c_game_event::CGameEvent;
 pub use c_game_event::GameEventType;
+pub use c_initialize_border::CInitializeBorder;
 pub use c_level_chunk_with_light::{
     BlockEntityInfo, CLevelChunkWithLight, ChunkPacketData, HeightmapType, Heightmaps,
     LightUpdatePacketData,
@@ -97,6 +135,8 @@ pub use c_level_chunk_with_light::{
 pub use c_level_event::CLevelEvent;
 pub use c_login::CLogin;
 pub use c_login::CommonPlayerSpawnInfo;
+pub use c_map_item_data::{CMapItemData, MapPatch};
+pub use c_merchant_offers::{CMerchantOffers, MerchantOfferEntry};
 pub use c_move_entity::{
     CMoveEntityPos, CMoveEntityPosRot, CMoveEntityRot, calc_delta, to_angle_byte,
 };
@@ -110,19 +150,34 @@ pub use c_player_info_update::{
 pub use c_player_position::{CPlayerPosition, RelativeMovement};
 pub use c_remove_entities::CRemoveEntities;
 pub use c_remove_player_info::CRemovePlayerInfo;
+pub use c_respawn::{CRespawn, KEEP_ALL_DATA, KEEP_ATTRIBUTE_MODIFIERS, KEEP_ENTITY_DATA};
 pub use c_rotate_head::CRotateHead;
 pub use c_section_blocks_update::{BlockChange, CSectionBlocksUpdate};
+pub use c_set_border_center::CSetBorderCenter;
+pub use c_set_border_lerp_size::CSetBorderLerpSize;
+pub use c_set_border_size::CSetBorderSize;
+pub use c_set_border_warning_delay::CSetBorderWarningDelay;
+pub use c_set_border_warning_distance::CSetBorderWarningDistance;
 pub use c_set_chunk_cache_radius::CSetChunkCacheRadius;
 pub use c_set_chunk_center::CSetChunkCenter;
 pub use c_set_cursor_item::CSetCursorItem;
 pub use c_set_entity_data::CSetEntityData;
+pub use c_set_entity_motion::CSetEntityMotion;
+pub use c_set_equipment::CSetEquipment;
+pub use c_set_experience::CSetExperience;
+pub use c_set_health::CSetHealth;
 pub use c_set_held_slot::CSetHeldSlot;
-pub use c_sound::{CSound, SoundSource};
+pub use c_set_passengers::CSetPassengers;
+pub use c_set_player_team::{CSetPlayerTeam, TeamMethod, TeamParameters};
+pub use c_sound::{CSound, SoundEvent, SoundSource};
 pub use c_system_chat::CSystemChat;
 pub use c_system_chat_message::CSystemChatMessage;
 pub use c_tab_list::CTabList;
+pub use c_take_item_entity::CTakeItemEntity;
 pub use c_ticking_state::CTickingState;
 pub use c_ticking_step::CTickingStep;
+pub use c_update_advancements::{CUpdateAdvancements, CriteriaProgress};
+pub use c_update_recipes::CUpdateRecipes;
 pub use chat_session_data::ProtocolRemoteChatSessionData;
 pub use s_accept_teleportation::SAcceptTeleportation;
 pub use s_chat::SChat;
@@ -131,20 +186,25 @@ pub use s_chat_command::SChatCommand;
 pub use s_chat_command_signed::{ArgumentSignature, LastSeenMessagesUpdate, SChatCommandSigned};
 pub use s_chat_session_update::SChatSessionUpdate;
 pub use s_chunk_batch_received::SChunkBatchReceived;
+pub use s_client_command::{ClientCommandAction, SClientCommand};
 pub use s_client_tick_end::SClientTickEnd;
 pub use s_command_suggestion::SCommandSuggestion;
 pub use s_container_button_click::SContainerButtonClick;
 pub use s_container_click::{ClickType, HashedPatchMap, HashedStack, SContainerClick};
 pub use s_container_close::SContainerClose;
 pub use s_container_slot_state_changed::SContainerSlotStateChanged;
+pub use s_interact::{InteractAction, SInteract};
 pub use s_move_player::{
     SMovePlayer, SMovePlayerPos, SMovePlayerPosRot, SMovePlayerRot, SMovePlayerStatusOnly,
 };
 pub use s_pick_item_from_block::SPickItemFromBlock;
 pub use s_player_abilities::SPlayerAbilities;
 pub use s_player_action::{PlayerAction, SPlayerAction};
+pub use s_player_command::{PlayerCommandAction, SPlayerCommand};
 pub use s_player_input::SPlayerInput;
 pub use s_player_load::SPlayerLoad;
+pub use s_rename_item::SRenameItem;
+pub use s_select_trade::SSelectTrade;
 pub use s_set_carried_item::SSetCarriedItem;
 pub use s_set_creative_mode_slot::SSetCreativeModeSlot;
 pub use s_set_held_item::SSetHeldItem;
@@ -0,0 +1,14 @@
+use steel_macros::{ReadFrom, ServerPacket};
+
+/// Actions for the client command packet.
+#[derive(ReadFrom, Clone, Copy, Debug, PartialEq, Eq)]
+#[read(as = VarInt)]
+pub enum ClientCommandAction {
+    PerformRespawn = 0,
+    RequestStats = 1,
+}
+
+#[derive(ReadFrom, ServerPacket, Clone, Debug)]
+pub struct SClientCommand {
+    pub action: ClientCommandAction,
+}
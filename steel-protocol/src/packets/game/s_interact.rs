@@ -0,0 +1,63 @@
+//! Serverbound interact packet - sent when the player clicks an entity.
+
+use std::io::{self, Cursor};
+
+use steel_macros::ServerPacket;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::ReadFrom;
+use steel_utils::types::InteractionHand;
+
+/// What the player did to the entity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InteractAction {
+    /// Right-clicked the entity with `hand`.
+    Interact {
+        /// The hand the player clicked with.
+        hand: InteractionHand,
+    },
+    /// Left-clicked the entity.
+    Attack,
+    /// Right-clicked a point on the entity, relative to its position. The
+    /// client follows this with an [`InteractAction::Interact`].
+    InteractAt {
+        /// Where the entity was clicked, relative to its position.
+        location: (f32, f32, f32),
+        /// The hand the player clicked with.
+        hand: InteractionHand,
+    },
+}
+
+/// Sent when the player right-clicks or attacks an entity.
+#[derive(ServerPacket, Clone, Debug)]
+pub struct SInteract {
+    /// The network id of the entity.
+    pub entity_id: i32,
+    /// What the player did to the entity.
+    pub action: InteractAction,
+    /// Whether the player was sneaking.
+    pub using_secondary_action: bool,
+}
+
+impl ReadFrom for SInteract {
+    fn read(data: &mut Cursor<&[u8]>) -> io::Result<Self> {
+        let entity_id = VarInt::read(data)?.0;
+        let action = match VarInt::read(data)?.0 {
+            0 => InteractAction::Interact {
+                hand: InteractionHand::read(data)?,
+            },
+            1 => InteractAction::Attack,
+            2 => InteractAction::InteractAt {
+                location: (f32::read(data)?, f32::read(data)?, f32::read(data)?),
+                hand: InteractionHand::read(data)?,
+            },
+            _ => return Err(io::Error::other("Invalid interact action id")),
+        };
+        let using_secondary_action = bool::read(data)?;
+
+        Ok(Self {
+            entity_id,
+            action,
+            using_secondary_action,
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use steel_macros::{ReadFrom, ServerPacket};
+
+/// Actions for the player command packet.
+#[derive(ReadFrom, Clone, Copy, Debug, PartialEq, Eq)]
+#[read(as = VarInt)]
+pub enum PlayerCommandAction {
+    StopSleeping = 0,
+    StartSprinting = 1,
+    StopSprinting = 2,
+    StartRidingJump = 3,
+    StopRidingJump = 4,
+    OpenInventory = 5,
+    StartFallFlying = 6,
+}
+
+/// Serverbound packet sent when a player changes state, like starting to
+/// sprint or to glide with an elytra.
+#[derive(ReadFrom, ServerPacket, Clone, Debug)]
+pub struct SPlayerCommand {
+    #[read(as = VarInt)]
+    pub entity_id: i32,
+    pub action: PlayerCommandAction,
+    /// The jump boost for riding jumps, unused otherwise.
+    #[read(as = VarInt)]
+    pub data: i32,
+}
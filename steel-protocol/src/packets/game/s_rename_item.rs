@@ -0,0 +1,20 @@
+use std::io::Cursor;
+
+use steel_macros::ServerPacket;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::{PrefixedRead, ReadFrom};
+
+/// Serverbound packet sent whenever the player edits the name field of an anvil.
+#[derive(ServerPacket, Clone, Debug)]
+pub struct SRenameItem {
+    /// The new item name. The server truncates it to the anvil's 50 character limit.
+    pub name: String,
+}
+
+impl ReadFrom for SRenameItem {
+    fn read(data: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+        Ok(Self {
+            name: String::read_prefixed_bound::<VarInt>(data, i16::MAX as usize)?,
+        })
+    }
+}
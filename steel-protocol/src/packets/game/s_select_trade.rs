@@ -0,0 +1,9 @@
+use steel_macros::{ReadFrom, ServerPacket};
+
+/// Serverbound packet sent when the player picks a trade in the trading screen.
+#[derive(ServerPacket, ReadFrom, Clone, Debug)]
+pub struct SSelectTrade {
+    /// The index of the picked trade in the merchant's offers.
+    #[read(as = VarInt)]
+    pub item: i32,
+}
@@ -0,0 +1,307 @@
+//! Build script for generating vanilla advancement definitions.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use heck::ToShoutySnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct AdvancementJson {
+    #[serde(default)]
+    parent: Option<String>,
+    #[serde(default)]
+    display: Option<DisplayJson>,
+    criteria: BTreeMap<String, CriterionJson>,
+    #[serde(default)]
+    requirements: Option<Vec<Vec<String>>>,
+    #[serde(default)]
+    rewards: Option<RewardsJson>,
+    #[serde(default)]
+    sends_telemetry_event: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct DisplayJson {
+    title: TextComponentJson,
+    description: TextComponentJson,
+    icon: IconJson,
+    #[serde(default)]
+    frame: Option<String>,
+    #[serde(default)]
+    background: Option<String>,
+    #[serde(default = "default_true")]
+    show_toast: bool,
+    #[serde(default = "default_true")]
+    announce_to_chat: bool,
+    #[serde(default)]
+    hidden: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct TextComponentJson {
+    translate: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct IconJson {
+    id: String,
+    #[serde(default = "default_count")]
+    count: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct CriterionJson {
+    trigger: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct RewardsJson {
+    #[serde(default)]
+    experience: i32,
+    #[serde(default)]
+    recipes: Vec<String>,
+    #[serde(default)]
+    function: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_count() -> i32 {
+    1
+}
+
+fn generate_identifier(resource: &str) -> TokenStream {
+    let (namespace, path) = resource.split_once(':').unwrap_or(("minecraft", resource));
+    quote! { Identifier { namespace: Cow::Borrowed(#namespace), path: Cow::Borrowed(#path) } }
+}
+
+fn generate_option<T>(value: Option<T>, f: impl FnOnce(T) -> TokenStream) -> TokenStream {
+    match value.map(f) {
+        Some(tokens) => quote! { Some(#tokens) },
+        None => quote! { None },
+    }
+}
+
+fn advancement_ident(name: &str) -> Ident {
+    Ident::new(
+        &name.replace('/', "_").to_shouty_snake_case(),
+        Span::call_site(),
+    )
+}
+
+fn read_advancements(dir: &Path, prefix: &str, out: &mut BTreeMap<String, AdvancementJson>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let entry = entry.unwrap();
+        let path = entry.path();
+        let file_name = path.file_stem().unwrap().to_str().unwrap().to_string();
+
+        if path.is_dir() {
+            read_advancements(&path, &format!("{prefix}{file_name}/"), out);
+        } else if path.extension().and_then(|s| s.to_str()) == Some("json") {
+            let name = format!("{prefix}{file_name}");
+            let content = fs::read_to_string(&path).unwrap();
+            let advancement: AdvancementJson = serde_json::from_str(&content)
+                .unwrap_or_else(|e| panic!("Failed to parse advancement {name}: {e}"));
+            out.insert(name, advancement);
+        }
+    }
+}
+
+/// Computes the position of every displayed advancement in its tab.
+///
+/// Each advancement is placed one column right of its closest displayed
+/// ancestor, leaves take one row each and parents are centered on their
+/// children. This is a simplified version of Java's `TreeNodePosition`, which
+/// the client doesn't care about as long as nodes don't overlap.
+fn compute_layout(
+    advancements: &BTreeMap<String, AdvancementJson>,
+) -> BTreeMap<String, (f32, f32)> {
+    // Children of each displayed advancement, skipping over hidden-from-display parents
+    let mut children: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let mut roots = Vec::new();
+    for (name, advancement) in advancements {
+        if advancement.display.is_none() {
+            continue;
+        }
+        let mut parent = advancement.parent.as_deref();
+        while let Some(parent_name) = parent {
+            let parent_name = parent_name
+                .strip_prefix("minecraft:")
+                .unwrap_or(parent_name);
+            match advancements.get(parent_name) {
+                Some(p) if p.display.is_some() => break,
+                Some(p) => parent = p.parent.as_deref(),
+                None => parent = None,
+            }
+        }
+        match parent {
+            Some(parent) => children
+                .entry(parent.strip_prefix("minecraft:").unwrap_or(parent))
+                .or_default()
+                .push(name),
+            None => roots.push(name.as_str()),
+        }
+    }
+
+    let mut positions = BTreeMap::new();
+    for root in roots {
+        // Every root starts its own tab
+        let mut next_row = 0.0;
+        place(root, 0.0, &mut next_row, &children, &mut positions);
+    }
+    positions
+}
+
+/// Places `name` and its subtree, returning the row it ended up on.
+fn place<'a>(
+    name: &'a str,
+    depth: f32,
+    next_row: &mut f32,
+    children: &BTreeMap<&'a str, Vec<&'a str>>,
+    positions: &mut BTreeMap<String, (f32, f32)>,
+) -> f32 {
+    let row = match children.get(name) {
+        Some(kids) if !kids.is_empty() => {
+            let rows: Vec<f32> = kids
+                .iter()
+                .map(|kid| place(kid, depth + 1.0, next_row, children, positions))
+                .collect();
+            (rows[0] + rows[rows.len() - 1]) / 2.0
+        }
+        _ => {
+            let row = *next_row;
+            *next_row += 1.0;
+            row
+        }
+    };
+    positions.insert(name.to_string(), (depth, row));
+    row
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!(
+        "cargo:rerun-if-changed=build_assets/builtin_datapacks/minecraft/data/minecraft/advancement/"
+    );
+
+    let advancement_dir =
+        Path::new("build_assets/builtin_datapacks/minecraft/data/minecraft/advancement");
+    let mut advancements = BTreeMap::new();
+    read_advancements(advancement_dir, "", &mut advancements);
+
+    let positions = compute_layout(&advancements);
+
+    let mut stream = TokenStream::new();
+
+    stream.extend(quote! {
+        use crate::advancement::{
+            Advancement, AdvancementCriterion, AdvancementDisplay, AdvancementFrame,
+            AdvancementRegistry, AdvancementRewards,
+        };
+        use steel_utils::Identifier;
+        use text_components::{TextComponent, translation::TranslatedMessage};
+        use std::borrow::Cow;
+    });
+
+    let mut register_stream = TokenStream::new();
+
+    for (name, advancement) in &advancements {
+        let ident = advancement_ident(name);
+        let key = quote! { Identifier::vanilla_static(#name) };
+        let parent = generate_option(advancement.parent.as_deref(), generate_identifier);
+
+        let display = generate_option(advancement.display.as_ref(), |display| {
+            let title = display.title.translate.as_str();
+            let description = display.description.translate.as_str();
+            let icon = generate_identifier(&display.icon.id);
+            let icon_count = display.icon.count;
+            let frame = match display.frame.as_deref() {
+                None | Some("task") => quote! { AdvancementFrame::Task },
+                Some("challenge") => quote! { AdvancementFrame::Challenge },
+                Some("goal") => quote! { AdvancementFrame::Goal },
+                Some(other) => panic!("Unknown advancement frame {other} in {name}"),
+            };
+            let background = generate_option(display.background.as_deref(), generate_identifier);
+            let show_toast = display.show_toast;
+            let announce_to_chat = display.announce_to_chat;
+            let hidden = display.hidden;
+            let (x, y) = positions[name.as_str()];
+            quote! {
+                AdvancementDisplay {
+                    title: TextComponent::translated(TranslatedMessage::new(#title, None)),
+                    description: TextComponent::translated(TranslatedMessage::new(#description, None)),
+                    icon: #icon,
+                    icon_count: #icon_count,
+                    frame: #frame,
+                    background: #background,
+                    show_toast: #show_toast,
+                    announce_to_chat: #announce_to_chat,
+                    hidden: #hidden,
+                    x: #x,
+                    y: #y,
+                }
+            }
+        });
+
+        let criteria = advancement.criteria.iter().map(|(criterion, json)| {
+            let trigger = generate_identifier(&json.trigger);
+            quote! { AdvancementCriterion { name: #criterion, trigger: #trigger } }
+        });
+
+        // Without explicit requirements every criterion has to be completed
+        let requirements = advancement.requirements.clone().unwrap_or_else(|| {
+            advancement
+                .criteria
+                .keys()
+                .map(|criterion| vec![criterion.clone()])
+                .collect()
+        });
+        let requirements = requirements.iter().map(|group| quote! { &[#(#group),*] });
+
+        let (experience, recipes, function) = match &advancement.rewards {
+            Some(rewards) => (
+                rewards.experience,
+                rewards
+                    .recipes
+                    .iter()
+                    .map(|recipe| generate_identifier(recipe))
+                    .collect(),
+                rewards.function.as_deref(),
+            ),
+            None => (0, Vec::new(), None),
+        };
+        let function = generate_option(function, generate_identifier);
+        let sends_telemetry_event = advancement.sends_telemetry_event;
+
+        stream.extend(quote! {
+            pub static #ident: &Advancement = &Advancement {
+                key: #key,
+                parent: #parent,
+                display: #display,
+                criteria: &[#(#criteria),*],
+                requirements: &[#(#requirements),*],
+                rewards: AdvancementRewards {
+                    experience: #experience,
+                    recipes: &[#(#recipes),*],
+                    function: #function,
+                },
+                sends_telemetry_event: #sends_telemetry_event,
+            };
+        });
+
+        register_stream.extend(quote! {
+            registry.register(#ident);
+        });
+    }
+
+    stream.extend(quote! {
+        pub fn register_advancements(registry: &mut AdvancementRegistry) {
+            #register_stream
+        }
+    });
+
+    stream
+}
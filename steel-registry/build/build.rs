@@ -1,5 +1,7 @@
 use std::{fs, path::Path, process::Command};
 
+mod advancements;
+mod banner_pattern_tags;
 mod banner_patterns;
 mod biomes;
 mod block_entity_types;
@@ -13,6 +15,7 @@ mod damage_types;
 mod dialog_tags;
 mod dialogs;
 mod dimension_types;
+mod enchantments;
 mod entities;
 mod entity_data;
 mod fluid_tags;
@@ -28,11 +31,16 @@ mod level_events;
 mod loot_tables;
 mod menu_types;
 mod packets;
+mod painting_variant_tags;
 mod painting_variants;
 mod pig_variants;
+mod potions;
 mod recipes;
 mod sound_events;
 mod sound_types;
+mod structure_sets;
+mod structure_templates;
+mod template_pools;
 mod timeline_tags;
 mod timelines;
 mod trim_materials;
@@ -51,6 +59,7 @@ const ITEMS: &str = "items";
 const ITEM_TAGS: &str = "item_tags";
 const PACKETS: &str = "packets";
 const BANNER_PATTERNS: &str = "banner_patterns";
+const BANNER_PATTERN_TAGS: &str = "banner_pattern_tags";
 const BIOMES: &str = "biomes";
 const CHAT_TYPES: &str = "chat_types";
 const TRIM_PATTERNS: &str = "trim_patterns";
@@ -63,6 +72,7 @@ const CAT_VARIANTS: &str = "cat_variants";
 const COW_VARIANTS: &str = "cow_variants";
 const CHICKEN_VARIANTS: &str = "chicken_variants";
 const PAINTING_VARIANTS: &str = "painting_variants";
+const PAINTING_VARIANT_TAGS: &str = "painting_variant_tags";
 const DIMENSIONS: &str = "dimension_types";
 const DAMAGE_TYPES: &str = "damage_types";
 const JUKEBOX_SONGS: &str = "jukebox_songs";
@@ -74,6 +84,8 @@ const TIMELINES: &str = "timelines";
 const TIMELINE_TAGS: &str = "timeline_tags";
 const ZOMBIE_NAUTILUS_VARIANTS: &str = "zombie_nautilus_variants";
 const RECIPES: &str = "recipes";
+const POTIONS: &str = "potions";
+const ENCHANTMENTS: &str = "enchantments";
 const VANILLA_ENTITIES: &str = "entities";
 const ENTITY_DATA: &str = "entity_data";
 const FLUIDS: &str = "fluids";
@@ -85,6 +97,10 @@ const GAME_RULES: &str = "game_rules";
 const LEVEL_EVENTS: &str = "level_events";
 const SOUND_EVENTS: &str = "sound_events";
 const SOUND_TYPES: &str = "sound_types";
+const ADVANCEMENTS: &str = "advancements";
+const STRUCTURE_SETS: &str = "structure_sets";
+const STRUCTURE_TEMPLATES: &str = "structure_templates";
+const TEMPLATE_POOLS: &str = "template_pools";
 
 pub fn main() {
     // Rerun build script when any file in the build/ directory changes
@@ -102,6 +118,7 @@ pub fn main() {
         (item_tags::build(), ITEM_TAGS),
         (packets::build(), PACKETS),
         (banner_patterns::build(), BANNER_PATTERNS),
+        (banner_pattern_tags::build(), BANNER_PATTERN_TAGS),
         (biomes::build(), BIOMES),
         (chat_types::build(), CHAT_TYPES),
         (trim_patterns::build(), TRIM_PATTERNS),
@@ -114,6 +131,7 @@ pub fn main() {
         (cow_variants::build(), COW_VARIANTS),
         (chicken_variants::build(), CHICKEN_VARIANTS),
         (painting_variants::build(), PAINTING_VARIANTS),
+        (painting_variant_tags::build(), PAINTING_VARIANT_TAGS),
         (dimension_types::build(), DIMENSIONS),
         (damage_types::build(), DAMAGE_TYPES),
         (jukebox_songs::build(), JUKEBOX_SONGS),
@@ -125,6 +143,8 @@ pub fn main() {
         (timeline_tags::build(), TIMELINE_TAGS),
         (zombie_nautilus_variants::build(), ZOMBIE_NAUTILUS_VARIANTS),
         (recipes::build(), RECIPES),
+        (potions::build(), POTIONS),
+        (enchantments::build(), ENCHANTMENTS),
         (entities::build(), VANILLA_ENTITIES),
         (entity_data::build(), ENTITY_DATA),
         (fluids::build(), FLUIDS),
@@ -135,6 +155,10 @@ pub fn main() {
         (level_events::build(), LEVEL_EVENTS),
         (sound_events::build(), SOUND_EVENTS),
         (sound_types::build(), SOUND_TYPES),
+        (advancements::build(), ADVANCEMENTS),
+        (structure_sets::build(), STRUCTURE_SETS),
+        (structure_templates::build(), STRUCTURE_TEMPLATES),
+        (template_pools::build(), TEMPLATE_POOLS),
     ];
 
     // Track which files we're generating this run
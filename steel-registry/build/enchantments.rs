@@ -0,0 +1,160 @@
+use std::fs;
+
+use heck::ToShoutySnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde::Deserialize;
+
+const DATA_DIR: &str = "build_assets/builtin_datapacks/minecraft/data/minecraft";
+
+#[derive(Deserialize, Debug)]
+pub struct EnchantmentJson {
+    max_level: i32,
+    min_cost: EnchantmentCostJson,
+    max_cost: EnchantmentCostJson,
+    weight: i32,
+    anvil_cost: i32,
+    supported_items: String,
+    primary_items: Option<String>,
+    exclusive_set: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EnchantmentCostJson {
+    base: i32,
+    per_level_above_first: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct TagJson {
+    values: Vec<String>,
+}
+
+/// Reads an enchantment tag, following references to other enchantment tags.
+fn read_enchantment_tag(tag: &str) -> Vec<String> {
+    let tag = tag.strip_prefix("minecraft:").unwrap_or(tag);
+    let path = format!("{DATA_DIR}/tags/enchantment/{tag}.json");
+    let content =
+        fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {path}: {e}"));
+    let tag_json: TagJson =
+        serde_json::from_str(&content).unwrap_or_else(|e| panic!("Failed to parse {path}: {e}"));
+
+    let mut values = Vec::new();
+    for value in tag_json.values {
+        if let Some(nested) = value.strip_prefix('#') {
+            values.extend(read_enchantment_tag(nested));
+        } else {
+            let value = value.strip_prefix("minecraft:").unwrap_or(&value);
+            values.push(value.to_string());
+        }
+    }
+    values
+}
+
+/// Turns an item tag reference like `#minecraft:enchantable/armor` into the
+/// tag's path.
+fn item_tag_path(reference: &str) -> &str {
+    let tag = reference
+        .strip_prefix('#')
+        .unwrap_or_else(|| panic!("Expected an item tag, got {reference}"));
+    tag.strip_prefix("minecraft:").unwrap_or(tag)
+}
+
+fn generate_cost(cost: &EnchantmentCostJson) -> TokenStream {
+    let base = cost.base;
+    let per_level_above_first = cost.per_level_above_first;
+    quote! {
+        EnchantmentCost {
+            base: #base,
+            per_level_above_first: #per_level_above_first,
+        }
+    }
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed={DATA_DIR}/enchantment/");
+    println!("cargo:rerun-if-changed={DATA_DIR}/tags/enchantment/");
+
+    let mut enchantments = Vec::new();
+    for entry in fs::read_dir(format!("{DATA_DIR}/enchantment")).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|s| s.to_str()) == Some("json") {
+            let name = path.file_stem().unwrap().to_str().unwrap().to_string();
+            let content = fs::read_to_string(&path).unwrap();
+            let enchantment: EnchantmentJson = serde_json::from_str(&content)
+                .unwrap_or_else(|e| panic!("Failed to parse {}: {}", name, e));
+            enchantments.push((name, enchantment));
+        }
+    }
+    // Directory order is not stable, and the order decides the network ids
+    enchantments.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let in_enchanting_table = read_enchantment_tag("in_enchanting_table");
+    let curses = read_enchantment_tag("curse");
+
+    let mut stream = TokenStream::new();
+
+    stream.extend(quote! {
+        use crate::enchantment::{Enchantment, EnchantmentCost, EnchantmentRegistry};
+        use steel_utils::Identifier;
+    });
+
+    for (name, enchantment) in &enchantments {
+        let ident = Ident::new(&name.to_shouty_snake_case(), Span::call_site());
+        let name_str = name.as_str();
+
+        let max_level = enchantment.max_level;
+        let min_cost = generate_cost(&enchantment.min_cost);
+        let max_cost = generate_cost(&enchantment.max_cost);
+        let weight = enchantment.weight;
+        let anvil_cost = enchantment.anvil_cost;
+        let supported_items = item_tag_path(&enchantment.supported_items);
+        let primary_items = match &enchantment.primary_items {
+            Some(tag) => {
+                let tag = item_tag_path(tag);
+                quote! { Some(Identifier::vanilla_static(#tag)) }
+            }
+            None => quote! { None },
+        };
+        let exclusive_set = enchantment
+            .exclusive_set
+            .as_deref()
+            .map(|tag| read_enchantment_tag(tag.strip_prefix('#').unwrap_or(tag)))
+            .unwrap_or_default();
+        let exclusive_set = exclusive_set.iter().map(String::as_str);
+        let in_enchanting_table = in_enchanting_table.contains(name);
+        let is_curse = curses.contains(name);
+
+        stream.extend(quote! {
+            pub static #ident: &Enchantment = &Enchantment {
+                key: Identifier::vanilla_static(#name_str),
+                max_level: #max_level,
+                min_cost: #min_cost,
+                max_cost: #max_cost,
+                weight: #weight,
+                anvil_cost: #anvil_cost,
+                supported_items: Identifier::vanilla_static(#supported_items),
+                primary_items: #primary_items,
+                exclusive_set: &[#(Identifier::vanilla_static(#exclusive_set)),*],
+                in_enchanting_table: #in_enchanting_table,
+                is_curse: #is_curse,
+            };
+        });
+    }
+
+    let mut register_stream = TokenStream::new();
+    for (name, _) in &enchantments {
+        let ident = Ident::new(&name.to_shouty_snake_case(), Span::call_site());
+        register_stream.extend(quote! {
+            registry.register(#ident);
+        });
+    }
+
+    stream.extend(quote! {
+        pub fn register_enchantments(registry: &mut EnchantmentRegistry) {
+            #register_stream
+        }
+    });
+
+    stream
+}
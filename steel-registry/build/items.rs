@@ -222,6 +222,29 @@ fn generate_builder_calls(item: &Item) -> Vec<TokenStream> {
                     );
                 }
             }
+            "minecraft:enchantable" => {
+                let val = value.get("value").and_then(|v| v.as_i64()).unwrap() as i32;
+                builder_calls.push(
+                    quote! { .builder_set(vanilla_components::#component_ident, Some(#val)) },
+                );
+            }
+            "minecraft:potion_contents" => {
+                builder_calls.push(quote! { .builder_set(vanilla_components::POTION_CONTENTS, Some(vanilla_components::PotionContents::default())) });
+            }
+            "minecraft:banner_patterns" => {
+                builder_calls.push(quote! { .builder_set(vanilla_components::BANNER_PATTERNS, Some(vanilla_components::BannerPatternLayers::default())) });
+            }
+            "minecraft:provides_banner_patterns" => {
+                // Values are item tags like "#minecraft:pattern_item/creeper"
+                let tag = value.as_str().unwrap().trim_start_matches('#');
+                let path = tag.strip_prefix("minecraft:").unwrap_or(tag);
+                builder_calls.push(quote! { .builder_set(vanilla_components::PROVIDES_BANNER_PATTERNS, Some(vanilla_components::ProvidesBannerPatterns(Identifier::vanilla_static(#path)))) });
+            }
+            "minecraft:provides_trim_material" => {
+                let material = value.as_str().unwrap();
+                let path = material.strip_prefix("minecraft:").unwrap_or(material);
+                builder_calls.push(quote! { .builder_set(vanilla_components::PROVIDES_TRIM_MATERIAL, Some(vanilla_components::ProvidesTrimMaterial(Identifier::vanilla_static(#path)))) });
+            }
             "minecraft:tool" => {
                 let tool_token = generate_tool_component(value);
                 builder_calls
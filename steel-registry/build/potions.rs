@@ -0,0 +1,78 @@
+use std::fs;
+
+use heck::ToShoutySnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct PotionJson {
+    name: String,
+    effects: Vec<PotionEffectJson>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PotionEffectJson {
+    effect: String,
+    duration: i32,
+    amplifier: i32,
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed=build_assets/potions.json");
+
+    let potions_file = "build_assets/potions.json";
+    let content = fs::read_to_string(potions_file).unwrap();
+    let potions: Vec<PotionJson> = serde_json::from_str(&content)
+        .unwrap_or_else(|e| panic!("Failed to parse potions.json: {}", e));
+
+    let mut stream = TokenStream::new();
+
+    stream.extend(quote! {
+        use crate::potion::{Potion, PotionEffect, PotionRegistry};
+        use steel_utils::Identifier;
+    });
+
+    // Generate static potion definitions
+    for potion in &potions {
+        let potion_ident = Ident::new(&potion.name.to_shouty_snake_case(), Span::call_site());
+        let potion_name_str = potion.name.as_str();
+
+        let effects = potion.effects.iter().map(|effect| {
+            let effect_name = effect.effect.as_str();
+            let duration = effect.duration;
+            let amplifier = effect.amplifier;
+            quote! {
+                PotionEffect {
+                    effect: Identifier::vanilla_static(#effect_name),
+                    duration: #duration,
+                    amplifier: #amplifier,
+                }
+            }
+        });
+
+        stream.extend(quote! {
+            pub static #potion_ident: &Potion = &Potion {
+                key: Identifier::vanilla_static(#potion_name_str),
+                effects: &[#(#effects),*],
+            };
+        });
+    }
+
+    // Generate registration function, in network id order
+    let mut register_stream = TokenStream::new();
+    for potion in &potions {
+        let potion_ident = Ident::new(&potion.name.to_shouty_snake_case(), Span::call_site());
+        register_stream.extend(quote! {
+            registry.register(#potion_ident);
+        });
+    }
+
+    stream.extend(quote! {
+        pub fn register_potions(registry: &mut PotionRegistry) {
+            #register_stream
+        }
+    });
+
+    stream
+}
@@ -19,10 +19,24 @@ struct RecipeJson {
     #[serde(default)]
     key: Option<serde_json::Map<String, Value>>,
     #[serde(default)]
-    pattern: Option<Vec<String>>,
+    pattern: Option<PatternJson>,
     // Shapeless recipe fields
     #[serde(default)]
     ingredients: Option<Vec<Value>>,
+    // Cooking recipe fields
+    #[serde(default)]
+    ingredient: Option<Value>,
+    #[serde(default)]
+    cookingtime: Option<i32>,
+    #[serde(default)]
+    experience: Option<f32>,
+    // Smithing recipe fields
+    #[serde(default)]
+    template: Option<Value>,
+    #[serde(default)]
+    base: Option<Value>,
+    #[serde(default)]
+    addition: Option<Value>,
     // Common fields
     #[serde(default)]
     result: Option<RecipeResult>,
@@ -30,6 +44,15 @@ struct RecipeJson {
     show_notification: Option<bool>,
 }
 
+/// The `pattern` field, which holds the grid rows of a shaped recipe but the
+/// trim pattern of a smithing trim recipe.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum PatternJson {
+    Rows(Vec<String>),
+    TrimPattern(String),
+}
+
 #[derive(Deserialize, Debug)]
 struct RecipeResult {
     id: String,
@@ -108,9 +131,153 @@ struct ShapelessRecipeData {
     result_count: i32,
 }
 
+struct CookingRecipeData {
+    name: String,
+    ident: Ident,
+    kind: TokenStream,
+    ingredient: TokenStream,
+    result_item_ident: Ident,
+    result_count: i32,
+    experience: f32,
+    cooking_time: i32,
+}
+
+struct StonecuttingRecipeData {
+    name: String,
+    ident: Ident,
+    ingredient: TokenStream,
+    result_item_ident: Ident,
+    result_count: i32,
+}
+
+struct SmithingTransformRecipeData {
+    name: String,
+    ident: Ident,
+    template: TokenStream,
+    base: TokenStream,
+    addition: TokenStream,
+    result_item_ident: Ident,
+    result_count: i32,
+}
+
+struct SmithingTrimRecipeData {
+    name: String,
+    ident: Ident,
+    template: TokenStream,
+    base: TokenStream,
+    addition: TokenStream,
+    pattern: String,
+}
+
+/// Generates an optional smithing ingredient, `None` meaning the slot must
+/// be empty.
+fn generate_optional_ingredient(value: Option<&Value>) -> TokenStream {
+    match value {
+        Some(value) => {
+            let ingredient = generate_ingredient(value);
+            quote! { Some(#ingredient) }
+        }
+        None => quote! { None },
+    }
+}
+
+/// Generates a smithing transform recipe.
+fn parse_smithing_transform_recipe(
+    recipe_name: &str,
+    recipe: &RecipeJson,
+) -> Option<SmithingTransformRecipeData> {
+    let base = generate_ingredient(recipe.base.as_ref()?);
+    let result = recipe.result.as_ref()?;
+
+    // Result item
+    let result_item_id = result.id.strip_prefix("minecraft:").unwrap_or(&result.id);
+    let result_item_ident = Ident::new(result_item_id, Span::call_site());
+
+    Some(SmithingTransformRecipeData {
+        name: recipe_name.to_string(),
+        ident: Ident::new(&recipe_name.to_snake_case(), Span::call_site()),
+        template: generate_optional_ingredient(recipe.template.as_ref()),
+        base,
+        addition: generate_optional_ingredient(recipe.addition.as_ref()),
+        result_item_ident,
+        result_count: result.count,
+    })
+}
+
+/// Generates a smithing trim recipe.
+fn parse_smithing_trim_recipe(
+    recipe_name: &str,
+    recipe: &RecipeJson,
+) -> Option<SmithingTrimRecipeData> {
+    let Some(PatternJson::TrimPattern(pattern)) = recipe.pattern.as_ref() else {
+        return None;
+    };
+
+    Some(SmithingTrimRecipeData {
+        name: recipe_name.to_string(),
+        ident: Ident::new(&recipe_name.to_snake_case(), Span::call_site()),
+        template: generate_ingredient(recipe.template.as_ref()?),
+        base: generate_ingredient(recipe.base.as_ref()?),
+        addition: generate_ingredient(recipe.addition.as_ref()?),
+        pattern: pattern
+            .strip_prefix("minecraft:")
+            .unwrap_or(pattern)
+            .to_string(),
+    })
+}
+
+/// Generates a stonecutter recipe.
+fn parse_stonecutting_recipe(
+    recipe_name: &str,
+    recipe: &RecipeJson,
+) -> Option<StonecuttingRecipeData> {
+    let ingredient = generate_ingredient(recipe.ingredient.as_ref()?);
+    let result = recipe.result.as_ref()?;
+
+    // Result item
+    let result_item_id = result.id.strip_prefix("minecraft:").unwrap_or(&result.id);
+    let result_item_ident = Ident::new(result_item_id, Span::call_site());
+
+    Some(StonecuttingRecipeData {
+        name: recipe_name.to_string(),
+        ident: Ident::new(&recipe_name.to_snake_case(), Span::call_site()),
+        ingredient,
+        result_item_ident,
+        result_count: result.count,
+    })
+}
+
+/// Generates a cooking recipe (smelting, blasting or smoking).
+fn parse_cooking_recipe(
+    recipe_name: &str,
+    recipe: &RecipeJson,
+    kind: TokenStream,
+    default_cooking_time: i32,
+) -> Option<CookingRecipeData> {
+    let ingredient = generate_ingredient(recipe.ingredient.as_ref()?);
+    let result = recipe.result.as_ref()?;
+
+    // Result item
+    let result_item_id = result.id.strip_prefix("minecraft:").unwrap_or(&result.id);
+    let result_item_ident = Ident::new(result_item_id, Span::call_site());
+
+    Some(CookingRecipeData {
+        name: recipe_name.to_string(),
+        ident: Ident::new(&recipe_name.to_snake_case(), Span::call_site()),
+        kind,
+        ingredient,
+        result_item_ident,
+        result_count: result.count,
+        experience: recipe.experience.unwrap_or(0.0),
+        cooking_time: recipe.cookingtime.unwrap_or(default_cooking_time),
+    })
+}
+
 /// Generates a shaped recipe.
 fn parse_shaped_recipe(recipe_name: &str, recipe: &RecipeJson) -> Option<ShapedRecipeData> {
-    let pattern = recipe.pattern.as_ref()?;
+    let Some(PatternJson::Rows(pattern)) = recipe.pattern.as_ref() else {
+        return None;
+    };
     let key = recipe.key.as_ref()?;
     let result = recipe.result.as_ref()?;
 
@@ -236,19 +403,35 @@ pub(crate) fn build() -> TokenStream {
 
     let mut shaped_recipes: Vec<ShapedRecipeData> = Vec::new();
     let mut shapeless_recipes: Vec<ShapelessRecipeData> = Vec::new();
+    let mut cooking_recipes: Vec<CookingRecipeData> = Vec::new();
+    let mut stonecutting_recipes: Vec<StonecuttingRecipeData> = Vec::new();
+    let mut smithing_transform_recipes: Vec<SmithingTransformRecipeData> = Vec::new();
+    let mut smithing_trim_recipes: Vec<SmithingTrimRecipeData> = Vec::new();
 
     // Read all recipe files
     fn read_recipes(
         dir: &Path,
         shaped: &mut Vec<ShapedRecipeData>,
         shapeless: &mut Vec<ShapelessRecipeData>,
+        cooking: &mut Vec<CookingRecipeData>,
+        stonecutting: &mut Vec<StonecuttingRecipeData>,
+        smithing_transform: &mut Vec<SmithingTransformRecipeData>,
+        smithing_trim: &mut Vec<SmithingTrimRecipeData>,
     ) {
         for entry in fs::read_dir(dir).unwrap() {
             let entry = entry.unwrap();
             let path = entry.path();
 
             if path.is_dir() {
-                read_recipes(&path, shaped, shapeless);
+                read_recipes(
+                    &path,
+                    shaped,
+                    shapeless,
+                    cooking,
+                    stonecutting,
+                    smithing_transform,
+                    smithing_trim,
+                );
             } else if path.extension().and_then(|s| s.to_str()) == Some("json") {
                 let recipe_name = path
                     .file_stem()
@@ -276,7 +459,41 @@ pub(crate) fn build() -> TokenStream {
                             shapeless.push(r);
                         }
                     }
-                    // Skip other recipe types for now (smelting, stonecutting, smithing, etc.)
+                    // Default cooking times match Java's `AbstractCookingRecipe` serializers
+                    "minecraft:smelting" => {
+                        let kind = quote! { CookingKind::Smelting };
+                        if let Some(r) = parse_cooking_recipe(recipe_name, &recipe, kind, 200) {
+                            cooking.push(r);
+                        }
+                    }
+                    "minecraft:blasting" => {
+                        let kind = quote! { CookingKind::Blasting };
+                        if let Some(r) = parse_cooking_recipe(recipe_name, &recipe, kind, 100) {
+                            cooking.push(r);
+                        }
+                    }
+                    "minecraft:smoking" => {
+                        let kind = quote! { CookingKind::Smoking };
+                        if let Some(r) = parse_cooking_recipe(recipe_name, &recipe, kind, 100) {
+                            cooking.push(r);
+                        }
+                    }
+                    "minecraft:stonecutting" => {
+                        if let Some(r) = parse_stonecutting_recipe(recipe_name, &recipe) {
+                            stonecutting.push(r);
+                        }
+                    }
+                    "minecraft:smithing_transform" => {
+                        if let Some(r) = parse_smithing_transform_recipe(recipe_name, &recipe) {
+                            smithing_transform.push(r);
+                        }
+                    }
+                    "minecraft:smithing_trim" => {
+                        if let Some(r) = parse_smithing_trim_recipe(recipe_name, &recipe) {
+                            smithing_trim.push(r);
+                        }
+                    }
+                    // Skip other recipe types for now (campfire, special crafting, etc.)
                     _ => {}
                 }
             }
@@ -287,6 +504,10 @@ pub(crate) fn build() -> TokenStream {
         Path::new(recipe_dir),
         &mut shaped_recipes,
         &mut shapeless_recipes,
+        &mut cooking_recipes,
+        &mut stonecutting_recipes,
+        &mut smithing_transform_recipes,
+        &mut smithing_trim_recipes,
     );
 
     // Generate struct fields
@@ -306,6 +527,38 @@ pub(crate) fn build() -> TokenStream {
         })
         .collect();
 
+    let cooking_fields: Vec<TokenStream> = cooking_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            quote! { pub #ident: SmeltingRecipe, }
+        })
+        .collect();
+
+    let stonecutting_fields: Vec<TokenStream> = stonecutting_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            quote! { pub #ident: StonecuttingRecipe, }
+        })
+        .collect();
+
+    let smithing_transform_fields: Vec<TokenStream> = smithing_transform_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            quote! { pub #ident: SmithingTransformRecipe, }
+        })
+        .collect();
+
+    let smithing_trim_fields: Vec<TokenStream> = smithing_trim_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            quote! { pub #ident: SmithingTrimRecipe, }
+        })
+        .collect();
+
     // Generate recipe initializers
     let shaped_inits: Vec<TokenStream> = shaped_recipes
         .iter()
@@ -363,6 +616,102 @@ pub(crate) fn build() -> TokenStream {
         })
         .collect();
 
+    let cooking_inits: Vec<TokenStream> = cooking_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            let name = &r.name;
+            let ingredient = &r.ingredient;
+            let result_item_ident = &r.result_item_ident;
+            let result_count = r.result_count;
+            let experience = r.experience;
+            let cooking_time = r.cooking_time;
+
+            quote! {
+                #ident: SmeltingRecipe {
+                    id: Identifier::vanilla_static(#name),
+                    ingredient: #ingredient,
+                    result: RecipeResult {
+                        item: &ITEMS.#result_item_ident,
+                        count: #result_count,
+                    },
+                    experience: #experience,
+                    cooking_time: #cooking_time,
+                },
+            }
+        })
+        .collect();
+
+    let stonecutting_inits: Vec<TokenStream> = stonecutting_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            let name = &r.name;
+            let ingredient = &r.ingredient;
+            let result_item_ident = &r.result_item_ident;
+            let result_count = r.result_count;
+
+            quote! {
+                #ident: StonecuttingRecipe {
+                    id: Identifier::vanilla_static(#name),
+                    ingredient: #ingredient,
+                    result: RecipeResult {
+                        item: &ITEMS.#result_item_ident,
+                        count: #result_count,
+                    },
+                },
+            }
+        })
+        .collect();
+
+    let smithing_transform_inits: Vec<TokenStream> = smithing_transform_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            let name = &r.name;
+            let template = &r.template;
+            let base = &r.base;
+            let addition = &r.addition;
+            let result_item_ident = &r.result_item_ident;
+            let result_count = r.result_count;
+
+            quote! {
+                #ident: SmithingTransformRecipe {
+                    id: Identifier::vanilla_static(#name),
+                    template: #template,
+                    base: #base,
+                    addition: #addition,
+                    result: RecipeResult {
+                        item: &ITEMS.#result_item_ident,
+                        count: #result_count,
+                    },
+                },
+            }
+        })
+        .collect();
+
+    let smithing_trim_inits: Vec<TokenStream> = smithing_trim_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            let name = &r.name;
+            let template = &r.template;
+            let base = &r.base;
+            let addition = &r.addition;
+            let pattern = &r.pattern;
+
+            quote! {
+                #ident: SmithingTrimRecipe {
+                    id: Identifier::vanilla_static(#name),
+                    template: #template,
+                    base: #base,
+                    addition: #addition,
+                    pattern: Identifier::vanilla_static(#pattern),
+                },
+            }
+        })
+        .collect();
+
     // Generate registration calls
     let shaped_registers: Vec<TokenStream> = shaped_recipes
         .iter()
@@ -380,11 +729,45 @@ pub(crate) fn build() -> TokenStream {
         })
         .collect();
 
+    let cooking_registers: Vec<TokenStream> = cooking_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            let kind = &r.kind;
+            quote! { registry.register_cooking(#kind, &RECIPES.cooking.#ident); }
+        })
+        .collect();
+
+    let stonecutting_registers: Vec<TokenStream> = stonecutting_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            quote! { registry.register_stonecutting(&RECIPES.stonecutting.#ident); }
+        })
+        .collect();
+
+    let smithing_transform_registers: Vec<TokenStream> = smithing_transform_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            quote! { registry.register_smithing_transform(&RECIPES.smithing_transform.#ident); }
+        })
+        .collect();
+
+    let smithing_trim_registers: Vec<TokenStream> = smithing_trim_recipes
+        .iter()
+        .map(|r| {
+            let ident = &r.ident;
+            quote! { registry.register_smithing_trim(&RECIPES.smithing_trim.#ident); }
+        })
+        .collect();
+
     quote! {
         use crate::{
             recipe::{
-                CraftingCategory, Ingredient, RecipeRegistry, RecipeResult,
-                ShapedRecipe, ShapelessRecipe,
+                CookingKind, CraftingCategory, Ingredient, RecipeRegistry, RecipeResult,
+                ShapedRecipe, ShapelessRecipe, SmeltingRecipe, SmithingTransformRecipe,
+                SmithingTrimRecipe, StonecuttingRecipe,
             },
             vanilla_items::ITEMS,
         };
@@ -401,9 +784,29 @@ pub(crate) fn build() -> TokenStream {
             #(#shapeless_fields)*
         }
 
+        pub struct CookingRecipes {
+            #(#cooking_fields)*
+        }
+
+        pub struct StonecuttingRecipes {
+            #(#stonecutting_fields)*
+        }
+
+        pub struct SmithingTransformRecipes {
+            #(#smithing_transform_fields)*
+        }
+
+        pub struct SmithingTrimRecipes {
+            #(#smithing_trim_fields)*
+        }
+
         pub struct Recipes {
             pub shaped: ShapedRecipes,
             pub shapeless: ShapelessRecipes,
+            pub cooking: CookingRecipes,
+            pub stonecutting: StonecuttingRecipes,
+            pub smithing_transform: SmithingTransformRecipes,
+            pub smithing_trim: SmithingTrimRecipes,
         }
 
         impl Recipes {
@@ -415,6 +818,18 @@ pub(crate) fn build() -> TokenStream {
                     shapeless: ShapelessRecipes {
                         #(#shapeless_inits)*
                     },
+                    cooking: CookingRecipes {
+                        #(#cooking_inits)*
+                    },
+                    stonecutting: StonecuttingRecipes {
+                        #(#stonecutting_inits)*
+                    },
+                    smithing_transform: SmithingTransformRecipes {
+                        #(#smithing_transform_inits)*
+                    },
+                    smithing_trim: SmithingTrimRecipes {
+                        #(#smithing_trim_inits)*
+                    },
                 }
             }
         }
@@ -424,6 +839,10 @@ pub(crate) fn build() -> TokenStream {
             let _ = &*RECIPES;
             #(#shaped_registers)*
             #(#shapeless_registers)*
+            #(#cooking_registers)*
+            #(#stonecutting_registers)*
+            #(#smithing_transform_registers)*
+            #(#smithing_trim_registers)*
         }
     }
 }
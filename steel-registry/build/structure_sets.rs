@@ -0,0 +1,210 @@
+//! Build script for generating vanilla structure set definitions.
+
+use std::fs;
+
+use heck::ToShoutySnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct StructureSetJson {
+    structures: Vec<StructureEntryJson>,
+    placement: PlacementJson,
+}
+
+#[derive(Deserialize, Debug)]
+struct StructureEntryJson {
+    structure: String,
+    weight: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct PlacementJson {
+    #[serde(rename = "type")]
+    placement_type: String,
+    salt: i32,
+    #[serde(default = "default_frequency")]
+    frequency: f32,
+    #[serde(default)]
+    frequency_reduction_method: Option<String>,
+    #[serde(default)]
+    locate_offset: [i32; 3],
+    #[serde(default)]
+    exclusion_zone: Option<ExclusionZoneJson>,
+    // random_spread
+    #[serde(default)]
+    spacing: i32,
+    #[serde(default)]
+    separation: i32,
+    #[serde(default)]
+    spread_type: Option<String>,
+    // concentric_rings
+    #[serde(default)]
+    distance: i32,
+    #[serde(default)]
+    spread: i32,
+    #[serde(default)]
+    count: i32,
+    #[serde(default)]
+    preferred_biomes: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ExclusionZoneJson {
+    other_set: String,
+    chunk_count: i32,
+}
+
+fn default_frequency() -> f32 {
+    1.0
+}
+
+fn generate_identifier(resource: &str) -> TokenStream {
+    let resource = resource.strip_prefix('#').unwrap_or(resource);
+    let (namespace, path) = resource.split_once(':').unwrap_or(("minecraft", resource));
+    quote! { Identifier { namespace: Cow::Borrowed(#namespace), path: Cow::Borrowed(#path) } }
+}
+
+fn generate_placement(name: &str, placement: &PlacementJson) -> TokenStream {
+    let [offset_x, offset_y, offset_z] = placement.locate_offset;
+    let frequency = placement.frequency;
+    let salt = placement.salt;
+
+    let frequency_reduction_method = match placement.frequency_reduction_method.as_deref() {
+        None | Some("default") => quote! { FrequencyReductionMethod::Default },
+        Some("legacy_type_1") => quote! { FrequencyReductionMethod::LegacyType1 },
+        Some("legacy_type_2") => quote! { FrequencyReductionMethod::LegacyType2 },
+        Some("legacy_type_3") => quote! { FrequencyReductionMethod::LegacyType3 },
+        Some(other) => panic!("Unknown frequency reduction method {other} in {name}"),
+    };
+
+    let exclusion_zone = match &placement.exclusion_zone {
+        Some(zone) => {
+            let other_set = generate_identifier(&zone.other_set);
+            let chunk_count = zone.chunk_count;
+            quote! { Some(ExclusionZone { other_set: #other_set, chunk_count: #chunk_count }) }
+        }
+        None => quote! { None },
+    };
+
+    let placement_type = match placement.placement_type.as_str() {
+        "minecraft:random_spread" => {
+            let spacing = placement.spacing;
+            let separation = placement.separation;
+            let spread_type = match placement.spread_type.as_deref() {
+                None | Some("linear") => quote! { RandomSpreadType::Linear },
+                Some("triangular") => quote! { RandomSpreadType::Triangular },
+                Some(other) => panic!("Unknown spread type {other} in {name}"),
+            };
+            quote! {
+                StructurePlacementType::RandomSpread {
+                    spacing: #spacing,
+                    separation: #separation,
+                    spread_type: #spread_type,
+                }
+            }
+        }
+        "minecraft:concentric_rings" => {
+            let distance = placement.distance;
+            let spread = placement.spread;
+            let count = placement.count;
+            let preferred_biomes = match placement.preferred_biomes.as_deref() {
+                Some(biomes) => {
+                    let biomes = generate_identifier(biomes);
+                    quote! { Some(#biomes) }
+                }
+                None => quote! { None },
+            };
+            quote! {
+                StructurePlacementType::ConcentricRings {
+                    distance: #distance,
+                    spread: #spread,
+                    count: #count,
+                    preferred_biomes: #preferred_biomes,
+                }
+            }
+        }
+        other => panic!("Unknown structure placement type {other} in {name}"),
+    };
+
+    quote! {
+        StructurePlacement {
+            locate_offset: [#offset_x, #offset_y, #offset_z],
+            frequency_reduction_method: #frequency_reduction_method,
+            frequency: #frequency,
+            salt: #salt,
+            exclusion_zone: #exclusion_zone,
+            placement_type: #placement_type,
+        }
+    }
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!(
+        "cargo:rerun-if-changed=build_assets/builtin_datapacks/minecraft/data/minecraft/worldgen/structure_set/"
+    );
+
+    let structure_set_dir =
+        "build_assets/builtin_datapacks/minecraft/data/minecraft/worldgen/structure_set";
+    let mut structure_sets = Vec::new();
+
+    for entry in fs::read_dir(structure_set_dir).unwrap() {
+        let entry = entry.unwrap();
+        let path = entry.path();
+
+        if path.extension().and_then(|s| s.to_str()) == Some("json") {
+            let name = path.file_stem().unwrap().to_str().unwrap().to_string();
+            let content = fs::read_to_string(&path).unwrap();
+            let structure_set: StructureSetJson = serde_json::from_str(&content)
+                .unwrap_or_else(|e| panic!("Failed to parse structure set {name}: {e}"));
+            structure_sets.push((name, structure_set));
+        }
+    }
+    structure_sets.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut stream = TokenStream::new();
+
+    stream.extend(quote! {
+        use crate::structure_set::{
+            ExclusionZone, FrequencyReductionMethod, RandomSpreadType, StructurePlacement,
+            StructurePlacementType, StructureSelectionEntry, StructureSet, StructureSetRegistry,
+        };
+        use steel_utils::Identifier;
+        use std::borrow::Cow;
+    });
+
+    let mut register_stream = TokenStream::new();
+
+    for (name, structure_set) in &structure_sets {
+        let ident = Ident::new(&name.to_shouty_snake_case(), Span::call_site());
+        let key = quote! { Identifier::vanilla_static(#name) };
+
+        let structures = structure_set.structures.iter().map(|entry| {
+            let structure = generate_identifier(&entry.structure);
+            let weight = entry.weight;
+            quote! { StructureSelectionEntry { structure: #structure, weight: #weight } }
+        });
+        let placement = generate_placement(name, &structure_set.placement);
+
+        stream.extend(quote! {
+            pub static #ident: &StructureSet = &StructureSet {
+                key: #key,
+                structures: &[#(#structures),*],
+                placement: #placement,
+            };
+        });
+
+        register_stream.extend(quote! {
+            registry.register(#ident);
+        });
+    }
+
+    stream.extend(quote! {
+        pub fn register_structure_sets(registry: &mut StructureSetRegistry) {
+            #register_stream
+        }
+    });
+
+    stream
+}
@@ -0,0 +1,49 @@
+//! Build script for embedding the vanilla structure template files.
+
+use std::{fs, path::Path};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+const STRUCTURE_DIR: &str = "build_assets/builtin_datapacks/minecraft/data/minecraft/structure";
+
+fn read_templates(dir: &Path, prefix: &str, out: &mut Vec<String>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let entry = entry.unwrap();
+        let path = entry.path();
+        let file_name = path.file_stem().unwrap().to_str().unwrap().to_string();
+
+        if path.is_dir() {
+            read_templates(&path, &format!("{prefix}{file_name}/"), out);
+        } else if path.extension().and_then(|s| s.to_str()) == Some("nbt") {
+            out.push(format!("{prefix}{file_name}"));
+        }
+    }
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed={STRUCTURE_DIR}/");
+
+    let mut templates = Vec::new();
+    read_templates(Path::new(STRUCTURE_DIR), "", &mut templates);
+    templates.sort();
+
+    let arms = templates.iter().map(|name| {
+        let file = format!("/{STRUCTURE_DIR}/{name}.nbt");
+        quote! {
+            #name => Some(include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), #file))),
+        }
+    });
+
+    quote! {
+        /// Returns the gzipped NBT of the vanilla structure template at `path`,
+        /// e.g. `village/plains/houses/plains_small_house_1`.
+        #[must_use]
+        pub fn structure_template_bytes(path: &str) -> Option<&'static [u8]> {
+            match path {
+                #(#arms)*
+                _ => None,
+            }
+        }
+    }
+}
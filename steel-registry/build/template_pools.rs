@@ -0,0 +1,187 @@
+//! Build script for generating vanilla template pool definitions.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use heck::ToShoutySnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize, Debug)]
+struct TemplatePoolJson {
+    fallback: String,
+    elements: Vec<WeightedElementJson>,
+}
+
+#[derive(Deserialize, Debug)]
+struct WeightedElementJson {
+    element: ElementJson,
+    weight: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct ElementJson {
+    element_type: String,
+    #[serde(default)]
+    location: Option<String>,
+    /// Either the id of a processor list or an inline one.
+    #[serde(default)]
+    processors: Option<Value>,
+    #[serde(default)]
+    projection: Option<String>,
+    #[serde(default)]
+    feature: Option<String>,
+    #[serde(default)]
+    elements: Vec<ElementJson>,
+}
+
+fn generate_identifier(resource: &str) -> TokenStream {
+    let (namespace, path) = resource.split_once(':').unwrap_or(("minecraft", resource));
+    quote! { Identifier { namespace: Cow::Borrowed(#namespace), path: Cow::Borrowed(#path) } }
+}
+
+fn generate_projection(name: &str, projection: Option<&str>) -> TokenStream {
+    match projection {
+        None | Some("rigid") => quote! { StructureProjection::Rigid },
+        Some("terrain_matching") => quote! { StructureProjection::TerrainMatching },
+        Some(other) => panic!("Unknown projection {other} in {name}"),
+    }
+}
+
+fn generate_element(name: &str, element: &ElementJson) -> TokenStream {
+    let projection = generate_projection(name, element.projection.as_deref());
+
+    match element.element_type.as_str() {
+        "minecraft:single_pool_element" | "minecraft:legacy_single_pool_element" => {
+            let location = generate_identifier(
+                element
+                    .location
+                    .as_deref()
+                    .unwrap_or_else(|| panic!("Single pool element without location in {name}")),
+            );
+            // Inline processor lists aren't supported yet, only references to one
+            let processors = match &element.processors {
+                Some(Value::String(processors)) => {
+                    let processors = generate_identifier(processors);
+                    quote! { Some(#processors) }
+                }
+                _ => quote! { None },
+            };
+            let legacy = element.element_type == "minecraft:legacy_single_pool_element";
+            quote! {
+                StructurePoolElement::Single {
+                    location: #location,
+                    processors: #processors,
+                    projection: #projection,
+                    legacy: #legacy,
+                }
+            }
+        }
+        "minecraft:list_pool_element" => {
+            let elements = element
+                .elements
+                .iter()
+                .map(|element| generate_element(name, element));
+            quote! {
+                StructurePoolElement::List {
+                    elements: &[#(#elements),*],
+                    projection: #projection,
+                }
+            }
+        }
+        "minecraft:feature_pool_element" => {
+            let feature = generate_identifier(
+                element
+                    .feature
+                    .as_deref()
+                    .unwrap_or_else(|| panic!("Feature pool element without feature in {name}")),
+            );
+            quote! {
+                StructurePoolElement::Feature {
+                    feature: #feature,
+                    projection: #projection,
+                }
+            }
+        }
+        "minecraft:empty_pool_element" => quote! { StructurePoolElement::Empty },
+        other => panic!("Unknown pool element type {other} in {name}"),
+    }
+}
+
+fn read_template_pools(dir: &Path, prefix: &str, out: &mut BTreeMap<String, TemplatePoolJson>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let entry = entry.unwrap();
+        let path = entry.path();
+        let file_name = path.file_stem().unwrap().to_str().unwrap().to_string();
+
+        if path.is_dir() {
+            read_template_pools(&path, &format!("{prefix}{file_name}/"), out);
+        } else if path.extension().and_then(|s| s.to_str()) == Some("json") {
+            let name = format!("{prefix}{file_name}");
+            let content = fs::read_to_string(&path).unwrap();
+            let template_pool: TemplatePoolJson = serde_json::from_str(&content)
+                .unwrap_or_else(|e| panic!("Failed to parse template pool {name}: {e}"));
+            out.insert(name, template_pool);
+        }
+    }
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!(
+        "cargo:rerun-if-changed=build_assets/builtin_datapacks/minecraft/data/minecraft/worldgen/template_pool/"
+    );
+
+    let template_pool_dir =
+        Path::new("build_assets/builtin_datapacks/minecraft/data/minecraft/worldgen/template_pool");
+    let mut template_pools = BTreeMap::new();
+    read_template_pools(template_pool_dir, "", &mut template_pools);
+
+    let mut stream = TokenStream::new();
+
+    stream.extend(quote! {
+        use crate::template_pool::{
+            StructurePoolElement, StructureProjection, StructureTemplatePool,
+            TemplatePoolRegistry,
+        };
+        use steel_utils::Identifier;
+        use std::borrow::Cow;
+    });
+
+    let mut register_stream = TokenStream::new();
+
+    for (name, template_pool) in &template_pools {
+        let ident = Ident::new(
+            &name.replace('/', "_").to_shouty_snake_case(),
+            Span::call_site(),
+        );
+        let key = quote! { Identifier::vanilla_static(#name) };
+        let fallback = generate_identifier(&template_pool.fallback);
+
+        let elements = template_pool.elements.iter().map(|entry| {
+            let element = generate_element(name, &entry.element);
+            let weight = entry.weight;
+            quote! { (#element, #weight) }
+        });
+
+        stream.extend(quote! {
+            pub static #ident: &StructureTemplatePool = &StructureTemplatePool {
+                key: #key,
+                fallback: #fallback,
+                elements: &[#(#elements),*],
+            };
+        });
+
+        register_stream.extend(quote! {
+            registry.register(#ident);
+        });
+    }
+
+    stream.extend(quote! {
+        pub fn register_template_pools(registry: &mut TemplatePoolRegistry) {
+            #register_stream
+        }
+    });
+
+    stream
+}
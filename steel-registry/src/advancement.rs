@@ -0,0 +1,152 @@
+use rustc_hash::FxHashMap;
+use steel_utils::Identifier;
+use text_components::TextComponent;
+
+use crate::RegistryExt;
+
+/// The frame drawn around an advancement's icon, which also picks the toast
+/// and chat message used when it is completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdvancementFrame {
+    Task,
+    Challenge,
+    Goal,
+}
+
+/// How an advancement is shown in the advancements screen.
+#[derive(Debug)]
+pub struct AdvancementDisplay {
+    pub title: TextComponent,
+    pub description: TextComponent,
+    /// The item shown as the advancement's icon.
+    pub icon: Identifier,
+    pub icon_count: i32,
+    pub frame: AdvancementFrame,
+    /// Background texture of the tab. Only set on root advancements.
+    pub background: Option<Identifier>,
+    pub show_toast: bool,
+    pub announce_to_chat: bool,
+    pub hidden: bool,
+    /// Column in the advancement tab, computed when the registry is generated.
+    pub x: f32,
+    /// Row in the advancement tab, computed when the registry is generated.
+    pub y: f32,
+}
+
+/// A single criterion of an advancement.
+#[derive(Debug)]
+pub struct AdvancementCriterion {
+    pub name: &'static str,
+    /// The trigger that completes this criterion, e.g. `minecraft:inventory_changed`.
+    pub trigger: Identifier,
+}
+
+/// What a player receives when completing an advancement.
+#[derive(Debug)]
+pub struct AdvancementRewards {
+    pub experience: i32,
+    pub recipes: &'static [Identifier],
+    /// Function to run as the player, if any.
+    pub function: Option<Identifier>,
+}
+
+/// Represents an advancement definition from a data pack JSON file.
+#[derive(Debug)]
+pub struct Advancement {
+    pub key: Identifier,
+    pub parent: Option<Identifier>,
+    pub display: Option<AdvancementDisplay>,
+    pub criteria: &'static [AdvancementCriterion],
+    /// The advancement is done once every group has at least one completed criterion.
+    pub requirements: &'static [&'static [&'static str]],
+    pub rewards: AdvancementRewards,
+    pub sends_telemetry_event: bool,
+}
+
+impl Advancement {
+    /// Returns true if the given completed criteria satisfy every requirement group.
+    pub fn is_done(&self, completed: impl Fn(&str) -> bool) -> bool {
+        self.requirements
+            .iter()
+            .all(|group| group.iter().any(|criterion| completed(criterion)))
+    }
+}
+
+pub type AdvancementRef = &'static Advancement;
+
+pub struct AdvancementRegistry {
+    advancements_by_id: Vec<AdvancementRef>,
+    advancements_by_key: FxHashMap<Identifier, usize>,
+    allows_registering: bool,
+}
+
+impl AdvancementRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            advancements_by_id: Vec::new(),
+            advancements_by_key: FxHashMap::default(),
+            allows_registering: true,
+        }
+    }
+
+    pub fn register(&mut self, advancement: AdvancementRef) -> usize {
+        assert!(
+            self.allows_registering,
+            "Cannot register advancements after the registry has been frozen"
+        );
+
+        let id = self.advancements_by_id.len();
+        self.advancements_by_key.insert(advancement.key.clone(), id);
+        self.advancements_by_id.push(advancement);
+        id
+    }
+
+    #[must_use]
+    pub fn by_id(&self, id: usize) -> Option<AdvancementRef> {
+        self.advancements_by_id.get(id).copied()
+    }
+
+    #[must_use]
+    pub fn get_id(&self, advancement: AdvancementRef) -> &usize {
+        self.advancements_by_key
+            .get(&advancement.key)
+            .expect("Advancement not found")
+    }
+
+    #[must_use]
+    pub fn by_key(&self, key: &Identifier) -> Option<AdvancementRef> {
+        self.advancements_by_key
+            .get(key)
+            .and_then(|id| self.by_id(*id))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, AdvancementRef)> + '_ {
+        self.advancements_by_id
+            .iter()
+            .enumerate()
+            .map(|(id, &advancement)| (id, advancement))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.advancements_by_id.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.advancements_by_id.is_empty()
+    }
+}
+
+impl RegistryExt for AdvancementRegistry {
+    fn freeze(&mut self) {
+        self.allows_registering = false;
+    }
+}
+
+impl Default for AdvancementRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
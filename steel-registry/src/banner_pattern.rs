@@ -1,3 +1,5 @@
+use std::ptr;
+
 use rustc_hash::FxHashMap;
 use steel_utils::Identifier;
 
@@ -16,6 +18,7 @@ pub type BannerPatternRef = &'static BannerPattern;
 pub struct BannerPatternRegistry {
     banner_patterns_by_id: Vec<BannerPatternRef>,
     banner_patterns_by_key: FxHashMap<Identifier, usize>,
+    tags: FxHashMap<Identifier, Vec<BannerPatternRef>>,
     allows_registering: bool,
 }
 
@@ -25,6 +28,7 @@ impl BannerPatternRegistry {
         Self {
             banner_patterns_by_id: Vec::new(),
             banner_patterns_by_key: FxHashMap::default(),
+            tags: FxHashMap::default(),
             allows_registering: true,
         }
     }
@@ -77,6 +81,44 @@ impl BannerPatternRegistry {
     pub fn is_empty(&self) -> bool {
         self.banner_patterns_by_id.is_empty()
     }
+
+    /// Registers a tag with a list of banner pattern keys.
+    /// Banner pattern keys that don't exist in the registry are silently skipped.
+    pub fn register_tag(&mut self, tag: Identifier, pattern_keys: &[&'static str]) {
+        assert!(
+            self.allows_registering,
+            "Cannot register tags after registry has been frozen"
+        );
+
+        let patterns: Vec<BannerPatternRef> = pattern_keys
+            .iter()
+            .filter_map(|key| self.by_key(&Identifier::vanilla_static(key)))
+            .collect();
+
+        self.tags.insert(tag, patterns);
+    }
+
+    /// Checks if a banner pattern is in a given tag.
+    #[must_use]
+    pub fn is_in_tag(&self, pattern: BannerPatternRef, tag: &Identifier) -> bool {
+        self.tags
+            .get(tag)
+            .is_some_and(|patterns| patterns.iter().any(|&p| ptr::eq(p, pattern)))
+    }
+
+    /// Iterates over all banner patterns in a tag, in the tag's order.
+    pub fn iter_tag(&self, tag: &Identifier) -> impl Iterator<Item = BannerPatternRef> + '_ {
+        self.tags
+            .get(tag)
+            .map(|p| p.iter().copied())
+            .into_iter()
+            .flatten()
+    }
+
+    /// Gets all tag keys.
+    pub fn tag_keys(&self) -> impl Iterator<Item = &Identifier> + '_ {
+        self.tags.keys()
+    }
 }
 
 impl Default for BannerPatternRegistry {
@@ -4,9 +4,10 @@ use crate::{
     REGISTRY,
     blocks::{
         self, BlockRef,
-        properties::{Direction, Property},
+        properties::{BlockStateProperties, Direction, Property},
         shapes::SupportType,
     },
+    vanilla_blocks,
 };
 
 pub trait BlockStateExt {
@@ -19,6 +20,16 @@ pub trait BlockStateExt {
     #[must_use]
     fn set_value<T, P: Property<T>>(&self, property: &P, value: T) -> BlockStateId;
     fn get_property_str(&self, name: &str) -> Option<String>;
+    /// Sets the property called `name` to `value`, both as in the block
+    /// state's string form. Returns `None` if the block has no such property
+    /// or value.
+    #[must_use]
+    fn with_property(&self, name: &str, value: &str) -> Option<BlockStateId>;
+    /// Returns what is left when this block is removed: water if it is
+    /// waterlogged, air otherwise.
+    ///
+    /// Vanilla: `FluidState.createLegacyBlock`
+    fn fluid_block_state(&self) -> BlockStateId;
     fn get_collision_shape(&self) -> &'static [blocks::shapes::AABB];
     fn get_outline_shape(&self) -> &'static [blocks::shapes::AABB];
     /// Checks if this block face is sturdy enough to support other blocks.
@@ -71,6 +82,22 @@ impl BlockStateExt for BlockStateId {
             .map(|(_, v)| v.to_string())
     }
 
+    fn with_property(&self, name: &str, value: &str) -> Option<BlockStateId> {
+        REGISTRY.blocks.set_property_str(*self, name, value)
+    }
+
+    fn fluid_block_state(&self) -> BlockStateId {
+        let block = if self
+            .try_get_value(&BlockStateProperties::WATERLOGGED)
+            .unwrap_or(false)
+        {
+            vanilla_blocks::WATER
+        } else {
+            vanilla_blocks::AIR
+        };
+        REGISTRY.blocks.get_default_state_id(block)
+    }
+
     fn get_collision_shape(&self) -> &'static [blocks::shapes::AABB] {
         REGISTRY.blocks.get_collision_shape(*self)
     }
@@ -372,39 +372,57 @@ impl BlockRegistry {
                 )
             });
 
-        // Get the base state ID for this block (O(1) lookup)
-        let block_id = self.state_to_block_id[id.0 as usize];
-        let base_state_id = self.block_to_base_state[block_id];
-
-        // Calculate the relative state index
-        let relative_index = id.0 - base_state_id;
-
-        // Decode all property indices from the relative state index.
-        // Properties are decoded in reverse order (last property = inner loop).
-        let mut index = relative_index;
-        let mut property_indices = vec![0usize; block.properties.len()];
+        let new_value_index = property.get_internal_index(&value);
+        self.with_property_index(id, block, property_index, new_value_index)
+    }
 
-        for (i, prop) in block.properties.iter().enumerate().rev() {
-            let count = prop.get_possible_values().len() as u16;
-            property_indices[i] = (index % count) as usize;
-            index /= count;
-        }
+    /// Gets the state ID of the same block with the property called `name` set
+    /// to `value`, given as in the block state's string form.
+    ///
+    /// Returns `None` if the block has no such property or value.
+    #[must_use]
+    pub fn set_property_str(
+        &self,
+        id: BlockStateId,
+        name: &str,
+        value: &str,
+    ) -> Option<BlockStateId> {
+        let block = self.by_state_id(id)?;
+        let property_index = block
+            .properties
+            .iter()
+            .position(|prop| prop.get_name() == name)?;
+        let new_value_index = block.properties[property_index]
+            .get_possible_values()
+            .iter()
+            .position(|possible| *possible == value)?;
+        Some(self.with_property_index(id, block, property_index, new_value_index))
+    }
 
-        // Update the specific property's index
-        let new_value_index = property.get_internal_index(&value);
-        property_indices[property_index] = new_value_index;
+    /// Moves `id` to another value of a single property of `block`.
+    ///
+    /// The last property is the inner loop of the state layout, so each
+    /// property steps through the states by the product of the value counts of
+    /// the properties after it. Only that one property's index has to change.
+    fn with_property_index(
+        &self,
+        id: BlockStateId,
+        block: BlockRef,
+        property_index: usize,
+        value_index: usize,
+    ) -> BlockStateId {
+        let stride: u16 = block.properties[property_index + 1..]
+            .iter()
+            .map(|prop| prop.get_possible_values().len() as u16)
+            .product();
+        let count = block.properties[property_index].get_possible_values().len() as u16;
 
-        // Re-encode the property indices back to a state ID.
-        // Properties are processed in reverse order (last property = inner loop).
-        let mut new_relative_index = 0u16;
-        let mut multiplier = 1u16;
-        for (i, prop) in block.properties.iter().enumerate().rev() {
-            let count = prop.get_possible_values().len() as u16;
-            new_relative_index += property_indices[i] as u16 * multiplier;
-            multiplier *= count;
-        }
+        // Get the base state ID for this block (O(1) lookup)
+        let block_id = self.state_to_block_id[id.0 as usize];
+        let relative_index = id.0 - self.block_to_base_state[block_id];
+        let current_index = relative_index / stride % count;
 
-        BlockStateId(base_state_id + new_relative_index)
+        BlockStateId(id.0 - current_index * stride + value_index as u16 * stride)
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (usize, BlockRef)> + '_ {
@@ -756,6 +774,36 @@ mod tests {
         assert!(result.is_none(), "Should return None for invalid value");
     }
 
+    #[test]
+    fn test_set_property_str_composes() {
+        let registry = create_test_registry();
+        let key = Identifier::vanilla_static("oak_stairs");
+        let stairs = registry.by_key(&key).expect("oak_stairs should exist");
+
+        let state = registry.get_default_state_id(stairs);
+        let state = registry
+            .set_property_str(state, "waterlogged", "true")
+            .and_then(|state| registry.set_property_str(state, "facing", "east"))
+            .and_then(|state| registry.set_property_str(state, "half", "top"))
+            .expect("Stairs should have these properties");
+
+        let expected = registry
+            .state_id_from_properties(
+                &key,
+                &[
+                    ("facing", "east"),
+                    ("half", "top"),
+                    ("shape", "straight"),
+                    ("waterlogged", "true"),
+                ],
+            )
+            .expect("Should find state");
+        assert_eq!(state, expected);
+
+        assert!(registry.set_property_str(state, "power", "1").is_none());
+        assert!(registry.set_property_str(state, "half", "middle").is_none());
+    }
+
     #[test]
     fn test_stone_no_properties() {
         let registry = create_test_registry();
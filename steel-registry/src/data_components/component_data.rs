@@ -3,7 +3,10 @@
 //! This module provides the core types for storing component values in an ABI-stable way.
 //! Vanilla components get dedicated enum variants for zero-cost access, while plugin
 //! components use the `Other` variant with opaque bytes.
-use super::components::{Equippable, Tool};
+use super::components::{
+    ArmorTrim, BannerPatternLayers, Equippable, ItemEnchantments, MapPostProcessing,
+    PotionContents, ProvidesBannerPatterns, ProvidesTrimMaterial, Tool,
+};
 use text_components::TextComponent;
 
 /// Discriminant for [`ComponentData`] variants.
@@ -18,6 +21,13 @@ pub enum ComponentDataDiscriminant {
     Float,
     Tool,
     Equippable,
+    PotionContents,
+    ItemEnchantments,
+    BannerPatternLayers,
+    ProvidesBannerPatterns,
+    MapPostProcessing,
+    ArmorTrim,
+    ProvidesTrimMaterial,
     TextComponent,
     Todo,
     Other,
@@ -69,6 +79,20 @@ pub enum ComponentData {
     Tool(Tool),
     /// minecraft:equippable
     Equippable(Equippable),
+    /// minecraft:potion_contents
+    PotionContents(PotionContents),
+    /// minecraft:enchantments and minecraft:stored_enchantments
+    ItemEnchantments(ItemEnchantments),
+    /// minecraft:banner_patterns
+    BannerPatternLayers(BannerPatternLayers),
+    /// minecraft:provides_banner_patterns
+    ProvidesBannerPatterns(ProvidesBannerPatterns),
+    /// minecraft:map_post_processing
+    MapPostProcessing(MapPostProcessing),
+    /// minecraft:trim
+    ArmorTrim(ArmorTrim),
+    /// minecraft:provides_trim_material
+    ProvidesTrimMaterial(ProvidesTrimMaterial),
     /// TextComponent component (e.g., CustomName, ItemName)
     TextComponent(Box<TextComponent>),
 
@@ -109,6 +133,13 @@ impl ComponentData {
             Self::Float(_) => ComponentDataDiscriminant::Float,
             Self::Tool(_) => ComponentDataDiscriminant::Tool,
             Self::Equippable(_) => ComponentDataDiscriminant::Equippable,
+            Self::PotionContents(_) => ComponentDataDiscriminant::PotionContents,
+            Self::ItemEnchantments(_) => ComponentDataDiscriminant::ItemEnchantments,
+            Self::BannerPatternLayers(_) => ComponentDataDiscriminant::BannerPatternLayers,
+            Self::ProvidesBannerPatterns(_) => ComponentDataDiscriminant::ProvidesBannerPatterns,
+            Self::MapPostProcessing(_) => ComponentDataDiscriminant::MapPostProcessing,
+            Self::ArmorTrim(_) => ComponentDataDiscriminant::ArmorTrim,
+            Self::ProvidesTrimMaterial(_) => ComponentDataDiscriminant::ProvidesTrimMaterial,
             Self::TextComponent(_) => ComponentDataDiscriminant::TextComponent,
             Self::Todo => ComponentDataDiscriminant::Todo,
             Self::Other(_) => ComponentDataDiscriminant::Other,
@@ -134,6 +165,13 @@ impl ComponentData {
             // Complex types
             Self::Tool(v) => v.hash_component(&mut hasher),
             Self::Equippable(v) => v.hash_component(&mut hasher),
+            Self::PotionContents(v) => v.hash_component(&mut hasher),
+            Self::ItemEnchantments(v) => v.hash_component(&mut hasher),
+            Self::BannerPatternLayers(v) => v.hash_component(&mut hasher),
+            Self::ProvidesBannerPatterns(v) => v.hash_component(&mut hasher),
+            Self::MapPostProcessing(v) => v.hash_component(&mut hasher),
+            Self::ArmorTrim(v) => v.hash_component(&mut hasher),
+            Self::ProvidesTrimMaterial(v) => v.hash_component(&mut hasher),
             Self::TextComponent(v) => v.hash_component(&mut hasher),
 
             // Stub/plugin types - hash as empty map for now
@@ -305,6 +343,146 @@ impl Component for Equippable {
     }
 }
 
+impl Component for PotionContents {
+    fn into_data(self) -> ComponentData {
+        ComponentData::PotionContents(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::PotionContents(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::PotionContents(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for ItemEnchantments {
+    fn into_data(self) -> ComponentData {
+        ComponentData::ItemEnchantments(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::ItemEnchantments(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::ItemEnchantments(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for BannerPatternLayers {
+    fn into_data(self) -> ComponentData {
+        ComponentData::BannerPatternLayers(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::BannerPatternLayers(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::BannerPatternLayers(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for ProvidesBannerPatterns {
+    fn into_data(self) -> ComponentData {
+        ComponentData::ProvidesBannerPatterns(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::ProvidesBannerPatterns(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::ProvidesBannerPatterns(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for MapPostProcessing {
+    fn into_data(self) -> ComponentData {
+        ComponentData::MapPostProcessing(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::MapPostProcessing(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::MapPostProcessing(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for ArmorTrim {
+    fn into_data(self) -> ComponentData {
+        ComponentData::ArmorTrim(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::ArmorTrim(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::ArmorTrim(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for ProvidesTrimMaterial {
+    fn into_data(self) -> ComponentData {
+        ComponentData::ProvidesTrimMaterial(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::ProvidesTrimMaterial(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::ProvidesTrimMaterial(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
 impl Component for TextComponent {
     fn into_data(self) -> ComponentData {
         ComponentData::TextComponent(Box::new(self))
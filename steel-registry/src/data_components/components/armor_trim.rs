@@ -0,0 +1,160 @@
+//! Armor trim component, the pattern and material a smithing table put on a
+//! piece of armor, and the component naming the trim material an item is.
+
+use std::io::{Cursor, Error, Result, Write};
+
+use steel_utils::{
+    Identifier,
+    codec::VarInt,
+    hash::{ComponentHasher, HashComponent, HashEntry, sort_map_entries},
+    serial::{ReadFrom, WriteTo},
+};
+
+use crate::REGISTRY;
+
+/// The armor trim component data.
+///
+/// Matches Java's `ArmorTrim`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArmorTrim {
+    /// The trim material, such as `minecraft:redstone`.
+    pub material: Identifier,
+    /// The trim pattern, such as `minecraft:coast`.
+    pub pattern: Identifier,
+}
+
+impl WriteTo for ArmorTrim {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        let material = REGISTRY
+            .trim_materials
+            .by_key(&self.material)
+            .map(|material| *REGISTRY.trim_materials.get_id(material))
+            .ok_or_else(|| Error::other(format!("Unknown trim material {}", self.material)))?;
+        let pattern = REGISTRY
+            .trim_patterns
+            .by_key(&self.pattern)
+            .map(|pattern| *REGISTRY.trim_patterns.get_id(pattern))
+            .ok_or_else(|| Error::other(format!("Unknown trim pattern {}", self.pattern)))?;
+        // Registry holders are sent as id + 1, 0 marks an inline value
+        VarInt(material as i32 + 1).write(writer)?;
+        VarInt(pattern as i32 + 1).write(writer)
+    }
+}
+
+impl ReadFrom for ArmorTrim {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+        let material = VarInt::read(data)?.0;
+        let pattern = VarInt::read(data)?.0;
+        if material == 0 || pattern == 0 {
+            return Err(Error::other(
+                "Inline trim materials and patterns are not supported",
+            ));
+        }
+        let material = REGISTRY
+            .trim_materials
+            .by_id((material - 1) as usize)
+            .map(|material| material.key.clone())
+            .ok_or_else(|| Error::other(format!("Unknown trim material id {}", material - 1)))?;
+        let pattern = REGISTRY
+            .trim_patterns
+            .by_id((pattern - 1) as usize)
+            .map(|pattern| pattern.key.clone())
+            .ok_or_else(|| Error::other(format!("Unknown trim pattern id {}", pattern - 1)))?;
+        Ok(Self { material, pattern })
+    }
+}
+
+impl HashComponent for ArmorTrim {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        // Hashed as a {material, pattern} map, like the vanilla codec
+        let mut entries = Vec::with_capacity(2);
+        for (key, value) in [("material", &self.material), ("pattern", &self.pattern)] {
+            let mut key_hasher = ComponentHasher::new();
+            key_hasher.put_string(key);
+            let mut value_hasher = ComponentHasher::new();
+            value_hasher.put_string(&value.to_string());
+            entries.push(HashEntry::new(key_hasher, value_hasher));
+        }
+        sort_map_entries(&mut entries);
+
+        hasher.start_map();
+        for entry in entries {
+            hasher.put_raw_bytes(&entry.key_bytes);
+            hasher.put_raw_bytes(&entry.value_bytes);
+        }
+        hasher.end_map();
+    }
+}
+
+impl simdnbt::ToNbtTag for ArmorTrim {
+    fn to_nbt_tag(self) -> simdnbt::owned::NbtTag {
+        let mut compound = simdnbt::owned::NbtCompound::new();
+        compound.insert("material", self.material.to_string());
+        compound.insert("pattern", self.pattern.to_string());
+        simdnbt::owned::NbtTag::Compound(compound)
+    }
+}
+
+impl simdnbt::FromNbtTag for ArmorTrim {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        let compound = tag.compound()?;
+        Some(Self {
+            material: compound.string("material")?.to_str().parse().ok()?,
+            pattern: compound.string("pattern")?.to_str().parse().ok()?,
+        })
+    }
+}
+
+/// The provides trim material component data: the trim material an item
+/// counts as in a smithing table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvidesTrimMaterial(pub Identifier);
+
+impl WriteTo for ProvidesTrimMaterial {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        // An either of a registry holder or a bare key, the holder when the
+        // material is known
+        if let Some(material) = REGISTRY.trim_materials.by_key(&self.0) {
+            true.write(writer)?;
+            VarInt(*REGISTRY.trim_materials.get_id(material) as i32 + 1).write(writer)
+        } else {
+            false.write(writer)?;
+            self.0.write(writer)
+        }
+    }
+}
+
+impl ReadFrom for ProvidesTrimMaterial {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+        if !bool::read(data)? {
+            return Ok(Self(Identifier::read(data)?));
+        }
+        let id = VarInt::read(data)?.0;
+        if id == 0 {
+            return Err(Error::other("Inline trim materials are not supported"));
+        }
+        REGISTRY
+            .trim_materials
+            .by_id((id - 1) as usize)
+            .map(|material| Self(material.key.clone()))
+            .ok_or_else(|| Error::other(format!("Unknown trim material id {}", id - 1)))
+    }
+}
+
+impl HashComponent for ProvidesTrimMaterial {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        hasher.put_string(&self.0.to_string());
+    }
+}
+
+impl simdnbt::ToNbtTag for ProvidesTrimMaterial {
+    fn to_nbt_tag(self) -> simdnbt::owned::NbtTag {
+        simdnbt::owned::NbtTag::String(self.0.to_string().into())
+    }
+}
+
+impl simdnbt::FromNbtTag for ProvidesTrimMaterial {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        Some(Self(tag.string()?.to_str().parse().ok()?))
+    }
+}
@@ -0,0 +1,210 @@
+//! Banner patterns component, the pattern layers drawn over a banner's base
+//! color, and the component naming the patterns a banner pattern item unlocks
+//! in the loom.
+
+use std::io::{Cursor, Error, Result, Write};
+
+use steel_utils::{
+    Identifier,
+    codec::VarInt,
+    hash::{ComponentHasher, HashComponent, HashEntry, sort_map_entries},
+    serial::{ReadFrom, WriteTo},
+};
+
+use crate::REGISTRY;
+use crate::loot_table::DyeColor;
+
+/// The most layers a loom can put on a banner.
+pub const MAX_BANNER_LAYERS: usize = 6;
+
+/// A single pattern drawn on a banner in one color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BannerPatternLayer {
+    /// The pattern, such as `minecraft:creeper`.
+    pub pattern: Identifier,
+    /// The color the pattern is drawn in.
+    pub color: DyeColor,
+}
+
+/// The banner patterns component data: the layers of a banner, bottom first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BannerPatternLayers {
+    /// The layers, drawn in order.
+    pub layers: Vec<BannerPatternLayer>,
+}
+
+impl BannerPatternLayers {
+    /// Returns these layers with one more on top.
+    #[must_use]
+    pub fn with_layer(&self, pattern: Identifier, color: DyeColor) -> Self {
+        let mut layers = self.layers.clone();
+        layers.push(BannerPatternLayer { pattern, color });
+        Self { layers }
+    }
+}
+
+impl WriteTo for BannerPatternLayers {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        VarInt(self.layers.len() as i32).write(writer)?;
+        for layer in &self.layers {
+            let id = REGISTRY
+                .banner_patterns
+                .by_key(&layer.pattern)
+                .map(|pattern| *REGISTRY.banner_patterns.get_id(pattern))
+                .ok_or_else(|| Error::other(format!("Unknown banner pattern {}", layer.pattern)))?;
+            // Registry holders are sent as id + 1, 0 marks an inline pattern
+            VarInt(id as i32 + 1).write(writer)?;
+            VarInt(layer.color.id()).write(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl ReadFrom for BannerPatternLayers {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+        let count = VarInt::read(data)?.0;
+        let mut layers = Vec::new();
+        for _ in 0..count {
+            let id = VarInt::read(data)?.0;
+            if id == 0 {
+                return Err(Error::other("Inline banner patterns are not supported"));
+            }
+            let pattern = REGISTRY
+                .banner_patterns
+                .by_id((id - 1) as usize)
+                .map(|pattern| pattern.key.clone())
+                .ok_or_else(|| Error::other(format!("Unknown banner pattern id {}", id - 1)))?;
+            let color_id = VarInt::read(data)?.0;
+            let color = DyeColor::by_id(color_id)
+                .ok_or_else(|| Error::other(format!("Unknown dye color id {color_id}")))?;
+            layers.push(BannerPatternLayer { pattern, color });
+        }
+        Ok(Self { layers })
+    }
+}
+
+impl HashComponent for BannerPatternLayers {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        // Hashed as a list of {pattern, color} maps, like the vanilla codec
+        hasher.start_list();
+        for layer in &self.layers {
+            let mut entries = Vec::with_capacity(2);
+
+            let mut key_hasher = ComponentHasher::new();
+            key_hasher.put_string("pattern");
+            let mut value_hasher = ComponentHasher::new();
+            value_hasher.put_string(&layer.pattern.to_string());
+            entries.push(HashEntry::new(key_hasher, value_hasher));
+
+            let mut key_hasher = ComponentHasher::new();
+            key_hasher.put_string("color");
+            let mut value_hasher = ComponentHasher::new();
+            value_hasher.put_string(layer.color.name());
+            entries.push(HashEntry::new(key_hasher, value_hasher));
+
+            sort_map_entries(&mut entries);
+
+            let mut layer_hasher = ComponentHasher::new();
+            layer_hasher.start_map();
+            for entry in entries {
+                layer_hasher.put_raw_bytes(&entry.key_bytes);
+                layer_hasher.put_raw_bytes(&entry.value_bytes);
+            }
+            layer_hasher.end_map();
+            hasher.put_raw_bytes(&layer_hasher.finish().to_le_bytes());
+        }
+        hasher.end_list();
+    }
+}
+
+impl simdnbt::ToNbtTag for BannerPatternLayers {
+    fn to_nbt_tag(self) -> simdnbt::owned::NbtTag {
+        use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+        let layers = self
+            .layers
+            .into_iter()
+            .map(|layer| {
+                let mut compound = NbtCompound::new();
+                compound.insert("pattern", layer.pattern.to_string());
+                compound.insert("color", layer.color.name());
+                compound
+            })
+            .collect();
+        NbtTag::List(NbtList::Compound(layers))
+    }
+}
+
+impl simdnbt::FromNbtTag for BannerPatternLayers {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        let list = tag.list()?;
+        let Some(compounds) = list.compounds() else {
+            // An empty list has no element type
+            return Some(Self::default());
+        };
+        let layers = compounds
+            .into_iter()
+            .map(|compound| {
+                let pattern = compound.string("pattern")?.to_str().parse().ok()?;
+                let color = DyeColor::by_name(&compound.string("color")?.to_str())?;
+                Some(BannerPatternLayer { pattern, color })
+            })
+            .collect::<Option<_>>()?;
+        Some(Self { layers })
+    }
+}
+
+/// The provides banner patterns component data: the banner pattern tag a
+/// pattern item unlocks in the loom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvidesBannerPatterns(pub Identifier);
+
+impl WriteTo for ProvidesBannerPatterns {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        self.0.write(writer)
+    }
+}
+
+impl ReadFrom for ProvidesBannerPatterns {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+        Ok(Self(Identifier::read(data)?))
+    }
+}
+
+impl HashComponent for ProvidesBannerPatterns {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        // Tag keys are hashed with their '#' prefix
+        hasher.put_string(&format!("#{}", self.0));
+    }
+}
+
+impl simdnbt::ToNbtTag for ProvidesBannerPatterns {
+    fn to_nbt_tag(self) -> simdnbt::owned::NbtTag {
+        simdnbt::owned::NbtTag::String(format!("#{}", self.0).into())
+    }
+}
+
+impl simdnbt::FromNbtTag for ProvidesBannerPatterns {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        let value = tag.string()?.to_str();
+        let tag = value.strip_prefix('#')?;
+        Some(Self(tag.parse().ok()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adding_a_layer_keeps_the_ones_below() {
+        let base = BannerPatternLayers::default()
+            .with_layer(Identifier::vanilla_static("creeper"), DyeColor::Green);
+        let layers = base.with_layer(Identifier::vanilla_static("border"), DyeColor::Black);
+
+        assert_eq!(base.layers.len(), 1);
+        assert_eq!(layers.layers.len(), 2);
+        assert_eq!(layers.layers[0], base.layers[0]);
+        assert_eq!(layers.layers[1].color, DyeColor::Black);
+    }
+}
@@ -0,0 +1,188 @@
+//! Enchantments component, used both for the enchantments on an item and the
+//! enchantments stored in an enchanted book.
+
+use std::io::{Cursor, Error, Result, Write};
+
+use steel_utils::{
+    Identifier,
+    codec::VarInt,
+    hash::{ComponentHasher, HashComponent, HashEntry, sort_map_entries},
+    serial::{ReadFrom, WriteTo},
+};
+
+use crate::REGISTRY;
+
+/// The highest level an enchantment can be stored with.
+pub const MAX_ENCHANTMENT_LEVEL: i32 = 255;
+
+/// The enchantments component data: enchantment levels by enchantment key,
+/// in the order they were added.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ItemEnchantments {
+    enchantments: Vec<(Identifier, i32)>,
+}
+
+impl ItemEnchantments {
+    /// Returns the level of `enchantment`, or 0 if it is not present.
+    #[must_use]
+    pub fn get_level(&self, enchantment: &Identifier) -> i32 {
+        self.enchantments
+            .iter()
+            .find(|(key, _)| key == enchantment)
+            .map_or(0, |(_, level)| *level)
+    }
+
+    /// Sets the level of `enchantment`, removing it if `level` is 0 or less.
+    pub fn set(&mut self, enchantment: Identifier, level: i32) {
+        if level <= 0 {
+            self.enchantments.retain(|(key, _)| *key != enchantment);
+            return;
+        }
+        let level = level.min(MAX_ENCHANTMENT_LEVEL);
+        match self
+            .enchantments
+            .iter_mut()
+            .find(|(key, _)| *key == enchantment)
+        {
+            Some((_, existing)) => *existing = level,
+            None => self.enchantments.push((enchantment, level)),
+        }
+    }
+
+    /// Raises the level of `enchantment` to `level` if it is lower.
+    ///
+    /// Based on Java's `ItemEnchantments.Mutable::upgrade`.
+    pub fn upgrade(&mut self, enchantment: Identifier, level: i32) {
+        if level > self.get_level(&enchantment) {
+            self.set(enchantment, level);
+        }
+    }
+
+    /// Keeps only the enchantments `keep` returns true for.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Identifier, i32) -> bool) {
+        self.enchantments.retain(|(key, level)| keep(key, *level));
+    }
+
+    /// Iterates over the enchantments and their levels.
+    pub fn iter(&self) -> impl Iterator<Item = (&Identifier, i32)> {
+        self.enchantments.iter().map(|(key, level)| (key, *level))
+    }
+
+    /// Returns the number of enchantments.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.enchantments.len()
+    }
+
+    /// Returns true if there are no enchantments.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.enchantments.is_empty()
+    }
+}
+
+impl WriteTo for ItemEnchantments {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        VarInt(self.enchantments.len() as i32).write(writer)?;
+        for (key, level) in &self.enchantments {
+            let id = REGISTRY
+                .enchantments
+                .by_key(key)
+                .map(|enchantment| *REGISTRY.enchantments.get_id(enchantment))
+                .ok_or_else(|| Error::other(format!("Unknown enchantment {key}")))?;
+            VarInt(id as i32).write(writer)?;
+            VarInt(*level).write(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl ReadFrom for ItemEnchantments {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+        let count = VarInt::read(data)?.0;
+        let mut enchantments = Self::default();
+        for _ in 0..count {
+            let id = VarInt::read(data)?.0;
+            let key = REGISTRY
+                .enchantments
+                .by_id(id as usize)
+                .map(|enchantment| enchantment.key.clone())
+                .ok_or_else(|| Error::other(format!("Unknown enchantment id {id}")))?;
+            let level = VarInt::read(data)?.0;
+            enchantments.set(key, level);
+        }
+        Ok(enchantments)
+    }
+}
+
+impl HashComponent for ItemEnchantments {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        // Hashed as a map of enchantment key to level, like the vanilla codec
+        let mut entries: Vec<HashEntry> = self
+            .enchantments
+            .iter()
+            .map(|(key, level)| {
+                let mut key_hasher = ComponentHasher::new();
+                key_hasher.put_string(&key.to_string());
+                let mut value_hasher = ComponentHasher::new();
+                value_hasher.put_int(*level);
+                HashEntry::new(key_hasher, value_hasher)
+            })
+            .collect();
+
+        sort_map_entries(&mut entries);
+
+        hasher.start_map();
+        for entry in entries {
+            hasher.put_raw_bytes(&entry.key_bytes);
+            hasher.put_raw_bytes(&entry.value_bytes);
+        }
+        hasher.end_map();
+    }
+}
+
+impl simdnbt::ToNbtTag for ItemEnchantments {
+    fn to_nbt_tag(self) -> simdnbt::owned::NbtTag {
+        use simdnbt::owned::{NbtCompound, NbtTag};
+
+        let mut compound = NbtCompound::new();
+        for (key, level) in self.enchantments {
+            compound.insert(key.to_string(), level);
+        }
+        NbtTag::Compound(compound)
+    }
+}
+
+impl simdnbt::FromNbtTag for ItemEnchantments {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        let compound = tag.compound()?;
+        let mut enchantments = Self::default();
+        for (key, value) in compound.iter() {
+            let key = key.to_str().parse().ok()?;
+            enchantments.set(key, value.int()?);
+        }
+        Some(enchantments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn setting_a_level_replaces_or_removes_it() {
+        let sharpness = Identifier::vanilla_static("sharpness");
+        let mut enchantments = ItemEnchantments::default();
+
+        enchantments.set(sharpness.clone(), 3);
+        enchantments.upgrade(sharpness.clone(), 2);
+        assert_eq!(enchantments.get_level(&sharpness), 3);
+
+        enchantments.set(sharpness.clone(), 300);
+        assert_eq!(enchantments.get_level(&sharpness), MAX_ENCHANTMENT_LEVEL);
+        assert_eq!(enchantments.len(), 1);
+
+        enchantments.set(sharpness.clone(), 0);
+        assert!(enchantments.is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+//! Map post processing component, the change a cartography table makes to a
+//! map once it is taken out of the result slot.
+
+use std::io::{Cursor, Error, Result, Write};
+
+use steel_utils::{
+    codec::VarInt,
+    hash::{ComponentHasher, HashComponent},
+    serial::{ReadFrom, WriteTo},
+};
+
+/// What happens to a map taken out of a cartography table.
+///
+/// Matches Java's `MapPostProcessing`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapPostProcessing {
+    /// The map is copied into a locked map that no longer updates.
+    Lock,
+    /// The map is copied into a map one zoom level further out.
+    Scale,
+}
+
+impl MapPostProcessing {
+    /// Returns the network id.
+    #[must_use]
+    pub const fn id(self) -> i32 {
+        match self {
+            Self::Lock => 0,
+            Self::Scale => 1,
+        }
+    }
+
+    /// Gets the post processing by its network id.
+    #[must_use]
+    pub const fn by_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Lock),
+            1 => Some(Self::Scale),
+            _ => None,
+        }
+    }
+}
+
+impl WriteTo for MapPostProcessing {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        VarInt(self.id()).write(writer)
+    }
+}
+
+impl ReadFrom for MapPostProcessing {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+        let id = VarInt::read(data)?.0;
+        Self::by_id(id).ok_or_else(|| Error::other(format!("Unknown map post processing {id}")))
+    }
+}
+
+impl HashComponent for MapPostProcessing {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        hasher.put_int(self.id());
+    }
+}
+
+// Vanilla never saves this component, the map is processed as soon as it
+// leaves the result slot. The id is written so a stray one still loads.
+impl simdnbt::ToNbtTag for MapPostProcessing {
+    fn to_nbt_tag(self) -> simdnbt::owned::NbtTag {
+        simdnbt::owned::NbtTag::Int(self.id())
+    }
+}
+
+impl simdnbt::FromNbtTag for MapPostProcessing {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        Self::by_id(tag.int()?)
+    }
+}
@@ -1,7 +1,19 @@
 //! Individual component type definitions.
 
+mod armor_trim;
+mod banner_pattern_layers;
 mod equippable;
+mod item_enchantments;
+mod map_post_processing;
+mod potion_contents;
 mod tool;
 
+pub use armor_trim::{ArmorTrim, ProvidesTrimMaterial};
+pub use banner_pattern_layers::{
+    BannerPatternLayer, BannerPatternLayers, MAX_BANNER_LAYERS, ProvidesBannerPatterns,
+};
 pub use equippable::{Equippable, EquippableSlot};
+pub use item_enchantments::{ItemEnchantments, MAX_ENCHANTMENT_LEVEL};
+pub use map_post_processing::MapPostProcessing;
+pub use potion_contents::PotionContents;
 pub use tool::{Tool, ToolRule};
@@ -0,0 +1,200 @@
+//! Potion contents component for potions, splash potions and tipped arrows.
+
+use std::io::{Cursor, Error, Result, Write};
+
+use steel_utils::{
+    Identifier,
+    codec::VarInt,
+    hash::{ComponentHasher, HashComponent, HashEntry, sort_map_entries},
+    serial::{PrefixedRead, PrefixedWrite, ReadFrom, WriteTo},
+};
+
+use crate::REGISTRY;
+
+/// The potion contents component data.
+///
+/// Custom effects are not supported yet, so only the base potion, the color
+/// override and the custom name are kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PotionContents {
+    /// The base potion, such as `minecraft:swiftness`.
+    pub potion: Option<Identifier>,
+    /// An RGB color overriding the color computed from the effects.
+    pub custom_color: Option<i32>,
+    /// Overrides the translation key suffix used for the item name.
+    pub custom_name: Option<String>,
+}
+
+impl PotionContents {
+    /// Creates potion contents holding just the given base potion.
+    #[must_use]
+    pub const fn new(potion: Identifier) -> Self {
+        Self {
+            potion: Some(potion),
+            custom_color: None,
+            custom_name: None,
+        }
+    }
+
+    /// Returns true if this holds the given base potion.
+    #[must_use]
+    pub fn is(&self, potion: &Identifier) -> bool {
+        self.potion.as_ref() == Some(potion)
+    }
+}
+
+impl WriteTo for PotionContents {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        let potion_id = match &self.potion {
+            Some(key) => Some(
+                REGISTRY
+                    .potions
+                    .by_key(key)
+                    .map(|potion| *REGISTRY.potions.get_id(potion))
+                    .ok_or_else(|| Error::other(format!("Unknown potion {key}")))?,
+            ),
+            None => None,
+        };
+        potion_id.map(|id| VarInt(id as i32)).write(writer)?;
+        self.custom_color.write(writer)?;
+        // Custom effects
+        VarInt(0).write(writer)?;
+        match &self.custom_name {
+            Some(name) => {
+                true.write(writer)?;
+                name.write_prefixed::<VarInt>(writer)
+            }
+            None => false.write(writer),
+        }
+    }
+}
+
+impl ReadFrom for PotionContents {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+        let potion = match Option::<VarInt>::read(data)? {
+            Some(id) => Some(
+                REGISTRY
+                    .potions
+                    .by_id(id.0 as usize)
+                    .map(|potion| potion.key.clone())
+                    .ok_or_else(|| Error::other(format!("Unknown potion id {}", id.0)))?,
+            ),
+            None => None,
+        };
+        let custom_color = Option::<i32>::read(data)?;
+        // TODO: Read custom effects once mob effect instances are implemented
+        let custom_effects = VarInt::read(data)?;
+        if custom_effects.0 != 0 {
+            return Err(Error::other("Custom potion effects are not supported"));
+        }
+        let custom_name = Option::<String>::read_prefixed::<VarInt>(data)?;
+
+        Ok(Self {
+            potion,
+            custom_color,
+            custom_name,
+        })
+    }
+}
+
+impl HashComponent for PotionContents {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        // Hashed as a map of the fields that are present, like the vanilla codec
+        let mut entries = Vec::new();
+
+        if let Some(potion) = &self.potion {
+            let mut key_hasher = ComponentHasher::new();
+            key_hasher.put_string("potion");
+            let mut value_hasher = ComponentHasher::new();
+            value_hasher.put_string(&potion.to_string());
+            entries.push(HashEntry::new(key_hasher, value_hasher));
+        }
+
+        if let Some(color) = self.custom_color {
+            let mut key_hasher = ComponentHasher::new();
+            key_hasher.put_string("custom_color");
+            let mut value_hasher = ComponentHasher::new();
+            value_hasher.put_int(color);
+            entries.push(HashEntry::new(key_hasher, value_hasher));
+        }
+
+        if let Some(name) = &self.custom_name {
+            let mut key_hasher = ComponentHasher::new();
+            key_hasher.put_string("custom_name");
+            let mut value_hasher = ComponentHasher::new();
+            value_hasher.put_string(name);
+            entries.push(HashEntry::new(key_hasher, value_hasher));
+        }
+
+        sort_map_entries(&mut entries);
+
+        hasher.start_map();
+        for entry in entries {
+            hasher.put_raw_bytes(&entry.key_bytes);
+            hasher.put_raw_bytes(&entry.value_bytes);
+        }
+        hasher.end_map();
+    }
+}
+
+impl simdnbt::ToNbtTag for PotionContents {
+    fn to_nbt_tag(self) -> simdnbt::owned::NbtTag {
+        use simdnbt::owned::{NbtCompound, NbtTag};
+
+        let mut compound = NbtCompound::new();
+        if let Some(potion) = self.potion {
+            compound.insert("potion", potion.to_string());
+        }
+        if let Some(color) = self.custom_color {
+            compound.insert("custom_color", color);
+        }
+        if let Some(name) = self.custom_name {
+            compound.insert("custom_name", name);
+        }
+        NbtTag::Compound(compound)
+    }
+}
+
+impl simdnbt::FromNbtTag for PotionContents {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        // The component may also be stored as just the potion id
+        if let Some(potion) = tag.string() {
+            return Some(Self::new(potion.to_str().parse().ok()?));
+        }
+
+        let compound = tag.compound()?;
+        let potion = compound
+            .get("potion")
+            .and_then(|t| t.string())
+            .and_then(|s| s.to_str().parse().ok());
+        let custom_color = compound.get("custom_color").and_then(|t| t.int());
+        let custom_name = compound
+            .get("custom_name")
+            .and_then(|t| t.string())
+            .map(|s| s.to_str().into_owned());
+
+        Some(Self {
+            potion,
+            custom_color,
+            custom_name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_contents_hash_as_empty_map() {
+        let mut expected = ComponentHasher::new();
+        expected.start_map();
+        expected.end_map();
+
+        assert_eq!(PotionContents::default().compute_hash(), expected.finish());
+        assert_ne!(
+            PotionContents::new(Identifier::vanilla_static("swiftness")).compute_hash(),
+            PotionContents::default().compute_hash()
+        );
+    }
+}
@@ -27,6 +27,7 @@ use steel_utils::{
 use crate::RegistryExt;
 
 use super::component_data::{Component, ComponentData, ComponentDataDiscriminant};
+use super::components::ItemEnchantments;
 use super::vanilla_components::{
     ATTRIBUTE_MODIFIERS, BREAK_SOUND, ENCHANTMENTS, LORE, MAX_STACK_SIZE, RARITY, REPAIR_COST,
     TOOLTIP_DISPLAY,
@@ -413,7 +414,10 @@ impl DataComponentMap {
         let mut map = FxHashMap::default();
         map.insert(MAX_STACK_SIZE.key.clone(), ComponentData::I32(64));
         map.insert(LORE.key.clone(), ComponentData::Todo);
-        map.insert(ENCHANTMENTS.key.clone(), ComponentData::Todo);
+        map.insert(
+            ENCHANTMENTS.key.clone(),
+            ComponentData::ItemEnchantments(ItemEnchantments::default()),
+        );
         map.insert(REPAIR_COST.key.clone(), ComponentData::I32(0));
         map.insert(ATTRIBUTE_MODIFIERS.key.clone(), ComponentData::Todo);
         map.insert(RARITY.key.clone(), ComponentData::Todo);
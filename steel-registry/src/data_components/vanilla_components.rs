@@ -10,7 +10,11 @@ use super::registry::DataComponentRegistry;
 pub use super::registry::DataComponentType;
 
 // Re-export component types for convenience
-pub use super::components::{Equippable, EquippableSlot, Tool, ToolRule};
+pub use super::components::{
+    ArmorTrim, BannerPatternLayer, BannerPatternLayers, Equippable, EquippableSlot,
+    ItemEnchantments, MapPostProcessing, PotionContents, ProvidesBannerPatterns,
+    ProvidesTrimMaterial, Tool, ToolRule,
+};
 
 // ==================== Fully Implemented Components ====================
 
@@ -53,9 +57,43 @@ pub const INTANGIBLE_PROJECTILE: DataComponentType<()> =
 pub const ENCHANTMENT_GLINT_OVERRIDE: DataComponentType<bool> =
     DataComponentType::new(Identifier::vanilla_static("enchantment_glint_override"));
 
+pub const ENCHANTMENTS: DataComponentType<ItemEnchantments> =
+    DataComponentType::new(Identifier::vanilla_static("enchantments"));
+
+pub const STORED_ENCHANTMENTS: DataComponentType<ItemEnchantments> =
+    DataComponentType::new(Identifier::vanilla_static("stored_enchantments"));
+
+/// How good the enchantments rolled by an enchanting table are.
+///
+/// Only ever set by the item prototype, so it is kept as a plain level
+/// instead of the vanilla `{value}` record.
+pub const ENCHANTABLE: DataComponentType<i32> =
+    DataComponentType::new(Identifier::vanilla_static("enchantable"));
+
+pub const POTION_CONTENTS: DataComponentType<PotionContents> =
+    DataComponentType::new(Identifier::vanilla_static("potion_contents"));
+
 pub const POTION_DURATION_SCALE: DataComponentType<f32> =
     DataComponentType::new(Identifier::vanilla_static("potion_duration_scale"));
 
+pub const PROVIDES_BANNER_PATTERNS: DataComponentType<ProvidesBannerPatterns> =
+    DataComponentType::new(Identifier::vanilla_static("provides_banner_patterns"));
+
+pub const BANNER_PATTERNS: DataComponentType<BannerPatternLayers> =
+    DataComponentType::new(Identifier::vanilla_static("banner_patterns"));
+
+pub const MAP_ID: DataComponentType<i32> =
+    DataComponentType::new(Identifier::vanilla_static("map_id"));
+
+pub const MAP_POST_PROCESSING: DataComponentType<MapPostProcessing> =
+    DataComponentType::new(Identifier::vanilla_static("map_post_processing"));
+
+pub const TRIM: DataComponentType<ArmorTrim> =
+    DataComponentType::new(Identifier::vanilla_static("trim"));
+
+pub const PROVIDES_TRIM_MATERIAL: DataComponentType<ProvidesTrimMaterial> =
+    DataComponentType::new(Identifier::vanilla_static("provides_trim_material"));
+
 // ==================== Stub Component Keys ====================
 // These components are registered but use placeholder serialization.
 // They use the Todo ComponentData variant.
@@ -80,9 +118,6 @@ pub const LORE: DataComponentType<()> = DataComponentType::new(Identifier::vanil
 pub const RARITY: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("rarity"));
 
-pub const ENCHANTMENTS: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("enchantments"));
-
 pub const CAN_PLACE_ON: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("can_place_on"));
 
@@ -124,9 +159,6 @@ pub const WEAPON: DataComponentType<()> =
 pub const ATTACK_RANGE: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("attack_range"));
 
-pub const ENCHANTABLE: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("enchantable"));
-
 pub const REPAIRABLE: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("repairable"));
 
@@ -148,33 +180,21 @@ pub const SWING_ANIMATION: DataComponentType<()> =
 pub const ADDITIONAL_TRADE_COST: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("additional_trade_cost"));
 
-pub const STORED_ENCHANTMENTS: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("stored_enchantments"));
-
 pub const DYED_COLOR: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("dyed_color"));
 
 pub const MAP_COLOR: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("map_color"));
 
-pub const MAP_ID: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("map_id"));
-
 pub const MAP_DECORATIONS: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("map_decorations"));
 
-pub const MAP_POST_PROCESSING: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("map_post_processing"));
-
 pub const CHARGED_PROJECTILES: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("charged_projectiles"));
 
 pub const BUNDLE_CONTENTS: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("bundle_contents"));
 
-pub const POTION_CONTENTS: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("potion_contents"));
-
 pub const SUSPICIOUS_STEW_EFFECTS: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("suspicious_stew_effects"));
 
@@ -184,8 +204,6 @@ pub const WRITABLE_BOOK_CONTENT: DataComponentType<()> =
 pub const WRITTEN_BOOK_CONTENT: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("written_book_content"));
 
-pub const TRIM: DataComponentType<()> = DataComponentType::new(Identifier::vanilla_static("trim"));
-
 pub const DEBUG_STICK_STATE: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("debug_stick_state"));
 
@@ -201,18 +219,12 @@ pub const BLOCK_ENTITY_DATA: DataComponentType<()> =
 pub const INSTRUMENT: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("instrument"));
 
-pub const PROVIDES_TRIM_MATERIAL: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("provides_trim_material"));
-
 pub const OMINOUS_BOTTLE_AMPLIFIER: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("ominous_bottle_amplifier"));
 
 pub const JUKEBOX_PLAYABLE: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("jukebox_playable"));
 
-pub const PROVIDES_BANNER_PATTERNS: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("provides_banner_patterns"));
-
 pub const RECIPES: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("recipes"));
 
@@ -228,9 +240,6 @@ pub const FIREWORKS: DataComponentType<()> =
 pub const PROFILE: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("profile"));
 
-pub const BANNER_PATTERNS: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("banner_patterns"));
-
 pub const BASE_COLOR: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("base_color"));
 
@@ -435,7 +444,7 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 12: rarity
     register_stub!(registry, RARITY.key.clone());
     // 13: enchantments
-    register_stub!(registry, ENCHANTMENTS.key.clone());
+    registry.register(ENCHANTMENTS, ComponentDataDiscriminant::ItemEnchantments);
     // 14: can_place_on
     register_stub!(registry, CAN_PLACE_ON.key.clone());
     // 15: can_break
@@ -476,7 +485,12 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 30: attack_range
     register_stub!(registry, ATTACK_RANGE.key.clone());
     // 31: enchantable
-    register_stub!(registry, ENCHANTABLE.key.clone());
+    registry.register_custom_network(
+        ENCHANTABLE,
+        ComponentDataDiscriminant::I32,
+        varint_reader,
+        varint_writer,
+    );
     // 32: equippable
     registry.register(EQUIPPABLE, ComponentDataDiscriminant::Equippable);
     // 33: repairable
@@ -498,23 +512,34 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 41: additional_trade_cost
     register_stub!(registry, ADDITIONAL_TRADE_COST.key.clone());
     // 42: stored_enchantments
-    register_stub!(registry, STORED_ENCHANTMENTS.key.clone());
+    registry.register(
+        STORED_ENCHANTMENTS,
+        ComponentDataDiscriminant::ItemEnchantments,
+    );
     // 43: dyed_color
     register_stub!(registry, DYED_COLOR.key.clone());
     // 44: map_color
     register_stub!(registry, MAP_COLOR.key.clone());
     // 45: map_id
-    register_stub!(registry, MAP_ID.key.clone());
+    registry.register_custom_network(
+        MAP_ID,
+        ComponentDataDiscriminant::I32,
+        varint_reader,
+        varint_writer,
+    );
     // 46: map_decorations
     register_stub!(registry, MAP_DECORATIONS.key.clone());
     // 47: map_post_processing
-    register_stub!(registry, MAP_POST_PROCESSING.key.clone());
+    registry.register(
+        MAP_POST_PROCESSING,
+        ComponentDataDiscriminant::MapPostProcessing,
+    );
     // 48: charged_projectiles
     register_stub!(registry, CHARGED_PROJECTILES.key.clone());
     // 49: bundle_contents
     register_stub!(registry, BUNDLE_CONTENTS.key.clone());
     // 50: potion_contents
-    register_stub!(registry, POTION_CONTENTS.key.clone());
+    registry.register(POTION_CONTENTS, ComponentDataDiscriminant::PotionContents);
     // 51: potion_duration_scale
     registry.register(POTION_DURATION_SCALE, ComponentDataDiscriminant::Float);
     // 52: suspicious_stew_effects
@@ -524,7 +549,7 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 54: written_book_content
     register_stub!(registry, WRITTEN_BOOK_CONTENT.key.clone());
     // 55: trim
-    register_stub!(registry, TRIM.key.clone());
+    registry.register(TRIM, ComponentDataDiscriminant::ArmorTrim);
     // 56: debug_stick_state
     register_stub!(registry, DEBUG_STICK_STATE.key.clone());
     // 57: entity_data
@@ -536,13 +561,19 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 60: instrument
     register_stub!(registry, INSTRUMENT.key.clone());
     // 61: provides_trim_material
-    register_stub!(registry, PROVIDES_TRIM_MATERIAL.key.clone());
+    registry.register(
+        PROVIDES_TRIM_MATERIAL,
+        ComponentDataDiscriminant::ProvidesTrimMaterial,
+    );
     // 62: ominous_bottle_amplifier
     register_stub!(registry, OMINOUS_BOTTLE_AMPLIFIER.key.clone());
     // 63: jukebox_playable
     register_stub!(registry, JUKEBOX_PLAYABLE.key.clone());
     // 64: provides_banner_patterns
-    register_stub!(registry, PROVIDES_BANNER_PATTERNS.key.clone());
+    registry.register(
+        PROVIDES_BANNER_PATTERNS,
+        ComponentDataDiscriminant::ProvidesBannerPatterns,
+    );
     // 65: recipes
     register_stub!(registry, RECIPES.key.clone());
     // 66: lodestone_tracker
@@ -556,7 +587,10 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 70: note_block_sound
     register_stub!(registry, NOTE_BLOCK_SOUND.key.clone());
     // 71: banner_patterns
-    register_stub!(registry, BANNER_PATTERNS.key.clone());
+    registry.register(
+        BANNER_PATTERNS,
+        ComponentDataDiscriminant::BannerPatternLayers,
+    );
     // 72: base_color
     register_stub!(registry, BASE_COLOR.key.clone());
     // 73: pot_decorations
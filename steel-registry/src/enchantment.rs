@@ -0,0 +1,342 @@
+use rustc_hash::FxHashMap;
+use steel_utils::Identifier;
+use steel_utils::random::Random;
+
+use crate::item_stack::ItemStack;
+use crate::vanilla_items::ITEMS;
+use crate::{REGISTRY, RegistryExt};
+
+/// The most bookshelves an enchanting table counts.
+pub const MAX_BOOKSHELVES: i32 = 15;
+
+/// A level cost that grows linearly with the enchantment level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnchantmentCost {
+    pub base: i32,
+    pub per_level_above_first: i32,
+}
+
+impl EnchantmentCost {
+    /// Returns the cost at `level`.
+    #[must_use]
+    pub const fn calculate(&self, level: i32) -> i32 {
+        self.base + self.per_level_above_first * (level - 1)
+    }
+}
+
+/// Represents an enchantment definition from a data pack JSON file.
+///
+/// Only the parts used to pick and combine enchantments are kept; the
+/// effects are not implemented yet.
+#[derive(Debug)]
+pub struct Enchantment {
+    pub key: Identifier,
+    pub max_level: i32,
+    /// The lowest enchanting power that rolls each level.
+    pub min_cost: EnchantmentCost,
+    /// The highest enchanting power that rolls each level.
+    pub max_cost: EnchantmentCost,
+    pub weight: i32,
+    pub anvil_cost: i32,
+    /// The item tag of the items this enchantment can be applied to.
+    pub supported_items: Identifier,
+    /// The item tag of the items the enchanting table offers this
+    /// enchantment for, or `None` for all supported items.
+    pub primary_items: Option<Identifier>,
+    /// The enchantments this one cannot be combined with.
+    pub exclusive_set: &'static [Identifier],
+    /// Whether the enchanting table can offer this enchantment.
+    pub in_enchanting_table: bool,
+    /// Whether this enchantment is a curse, which grindstones cannot remove.
+    pub is_curse: bool,
+}
+
+impl Enchantment {
+    /// Returns the lowest enchanting power that rolls `level`.
+    #[must_use]
+    pub const fn min_cost(&self, level: i32) -> i32 {
+        self.min_cost.calculate(level)
+    }
+
+    /// Returns the highest enchanting power that rolls `level`.
+    #[must_use]
+    pub const fn max_cost(&self, level: i32) -> i32 {
+        self.max_cost.calculate(level)
+    }
+
+    /// Returns true if this enchantment can be applied to `stack`.
+    #[must_use]
+    pub fn is_supported_item(&self, stack: &ItemStack) -> bool {
+        REGISTRY.items.is_in_tag(stack.item, &self.supported_items)
+    }
+
+    /// Returns true if the enchanting table can offer this enchantment for
+    /// `stack`.
+    #[must_use]
+    pub fn is_primary_item(&self, stack: &ItemStack) -> bool {
+        self.is_supported_item(stack)
+            && self
+                .primary_items
+                .as_ref()
+                .is_none_or(|tag| REGISTRY.items.is_in_tag(stack.item, tag))
+    }
+
+    /// Returns true if two different enchantments can be on the same item.
+    #[must_use]
+    pub fn are_compatible(a: &Self, b: &Self) -> bool {
+        a.key != b.key && !a.exclusive_set.contains(&b.key) && !b.exclusive_set.contains(&a.key)
+    }
+}
+
+pub type EnchantmentRef = &'static Enchantment;
+
+/// An enchantment at a specific level.
+#[derive(Debug, Clone, Copy)]
+pub struct EnchantmentInstance {
+    pub enchantment: EnchantmentRef,
+    pub level: i32,
+}
+
+/// Returns the level cost of one of the enchanting table's three options.
+///
+/// Based on Java's `EnchantmentHelper.getEnchantmentCost`.
+pub fn get_enchantment_cost<R: Random>(
+    random: &mut R,
+    slot: i32,
+    bookshelves: i32,
+    enchantability: i32,
+) -> i32 {
+    if enchantability <= 0 {
+        return 0;
+    }
+    let bookshelves = bookshelves.min(MAX_BOOKSHELVES);
+    let selected = random.next_i32_bounded(8)
+        + 1
+        + (bookshelves >> 1)
+        + random.next_i32_bounded(bookshelves + 1);
+    match slot {
+        0 => (selected / 3).max(1),
+        1 => selected * 2 / 3 + 1,
+        _ => selected.max(bookshelves * 2),
+    }
+}
+
+/// Rolls the enchantments an enchanting table applies to `stack` for `level`
+/// levels, out of `candidates`.
+///
+/// Based on Java's `EnchantmentHelper.selectEnchantment`.
+pub fn select_enchantment<R: Random>(
+    random: &mut R,
+    stack: &ItemStack,
+    level: i32,
+    candidates: &[EnchantmentRef],
+) -> Vec<EnchantmentInstance> {
+    let mut results = Vec::new();
+    let enchantability = stack.get_enchantability();
+    if enchantability <= 0 {
+        return results;
+    }
+
+    let mut level = level
+        + 1
+        + random.next_i32_bounded(enchantability / 4 + 1)
+        + random.next_i32_bounded(enchantability / 4 + 1);
+    let spread = (random.next_f32() + random.next_f32() - 1.0) * 0.15;
+    level = ((level as f32 + level as f32 * spread + 0.5).floor() as i32).max(1);
+
+    let mut available = get_available_enchantment_results(level, stack, candidates);
+    if let Some(first) = weighted_random(random, &available) {
+        results.push(first);
+        while random.next_i32_bounded(50) <= level {
+            if let Some(last) = results.last() {
+                filter_compatible_enchantments(&mut available, last.enchantment);
+            }
+            if available.is_empty() {
+                break;
+            }
+            if let Some(next) = weighted_random(random, &available) {
+                results.push(next);
+            }
+            level /= 2;
+        }
+    }
+    results
+}
+
+/// Returns the highest level of every candidate the enchanting table could
+/// roll for `stack` with enchanting power `level`.
+///
+/// Based on Java's `EnchantmentHelper.getAvailableEnchantmentResults`.
+#[must_use]
+pub fn get_available_enchantment_results(
+    level: i32,
+    stack: &ItemStack,
+    candidates: &[EnchantmentRef],
+) -> Vec<EnchantmentInstance> {
+    let is_book = stack.is(&ITEMS.book);
+    candidates
+        .iter()
+        .filter(|enchantment| is_book || enchantment.is_primary_item(stack))
+        .filter_map(|&enchantment| {
+            (1..=enchantment.max_level)
+                .rev()
+                .find(|&enchantment_level| {
+                    level >= enchantment.min_cost(enchantment_level)
+                        && level <= enchantment.max_cost(enchantment_level)
+                })
+                .map(|enchantment_level| EnchantmentInstance {
+                    enchantment,
+                    level: enchantment_level,
+                })
+        })
+        .collect()
+}
+
+/// Removes the enchantments that cannot be combined with `picked`.
+///
+/// Based on Java's `EnchantmentHelper.filterCompatibleEnchantments`.
+pub fn filter_compatible_enchantments(
+    available: &mut Vec<EnchantmentInstance>,
+    picked: EnchantmentRef,
+) {
+    available.retain(|instance| Enchantment::are_compatible(picked, instance.enchantment));
+}
+
+/// Picks one of `instances` with a chance proportional to its weight.
+///
+/// Based on Java's `WeightedRandom.getRandomItem`.
+fn weighted_random<R: Random>(
+    random: &mut R,
+    instances: &[EnchantmentInstance],
+) -> Option<EnchantmentInstance> {
+    let total: i32 = instances
+        .iter()
+        .map(|instance| instance.enchantment.weight)
+        .sum();
+    if total <= 0 {
+        return None;
+    }
+    let mut selected = random.next_i32_bounded(total);
+    instances
+        .iter()
+        .find(|instance| {
+            selected -= instance.enchantment.weight;
+            selected < 0
+        })
+        .copied()
+}
+
+pub struct EnchantmentRegistry {
+    enchantments_by_id: Vec<EnchantmentRef>,
+    enchantments_by_key: FxHashMap<Identifier, usize>,
+    allows_registering: bool,
+}
+
+impl EnchantmentRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            enchantments_by_id: Vec::new(),
+            enchantments_by_key: FxHashMap::default(),
+            allows_registering: true,
+        }
+    }
+
+    pub fn register(&mut self, enchantment: EnchantmentRef) -> usize {
+        assert!(
+            self.allows_registering,
+            "Cannot register enchantments after the registry has been frozen"
+        );
+
+        let id = self.enchantments_by_id.len();
+        self.enchantments_by_key.insert(enchantment.key.clone(), id);
+        self.enchantments_by_id.push(enchantment);
+        id
+    }
+
+    #[must_use]
+    pub fn by_id(&self, id: usize) -> Option<EnchantmentRef> {
+        self.enchantments_by_id.get(id).copied()
+    }
+
+    #[must_use]
+    pub fn get_id(&self, enchantment: EnchantmentRef) -> &usize {
+        self.enchantments_by_key
+            .get(&enchantment.key)
+            .expect("Enchantment not found")
+    }
+
+    #[must_use]
+    pub fn by_key(&self, key: &Identifier) -> Option<EnchantmentRef> {
+        self.enchantments_by_key
+            .get(key)
+            .and_then(|id| self.by_id(*id))
+    }
+
+    /// Returns the enchantments the enchanting table can offer.
+    #[must_use]
+    pub fn in_enchanting_table(&self) -> Vec<EnchantmentRef> {
+        self.enchantments_by_id
+            .iter()
+            .copied()
+            .filter(|enchantment| enchantment.in_enchanting_table)
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, EnchantmentRef)> + '_ {
+        self.enchantments_by_id
+            .iter()
+            .enumerate()
+            .map(|(id, &enchantment)| (id, enchantment))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.enchantments_by_id.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.enchantments_by_id.is_empty()
+    }
+}
+
+impl RegistryExt for EnchantmentRegistry {
+    fn freeze(&mut self) {
+        self.allows_registering = false;
+    }
+}
+
+impl Default for EnchantmentRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use steel_utils::random::legacy_random::LegacyRandom;
+
+    use super::*;
+    use crate::vanilla_enchantments::{LOOTING, SHARPNESS, SMITE};
+
+    #[test]
+    fn exclusive_enchantments_are_incompatible() {
+        assert!(!Enchantment::are_compatible(SHARPNESS, SMITE));
+        assert!(!Enchantment::are_compatible(SHARPNESS, SHARPNESS));
+        assert!(Enchantment::are_compatible(SHARPNESS, LOOTING));
+        assert_eq!(SHARPNESS.min_cost(5), 45);
+        assert!(!SHARPNESS.is_curse);
+    }
+
+    #[test]
+    fn costs_grow_from_the_top_slot_down() {
+        for seed in 0..64 {
+            let mut random = LegacyRandom::from_seed(seed);
+            let top = get_enchantment_cost(&mut random, 0, 15, 10);
+            let bottom = get_enchantment_cost(&mut random, 2, 15, 10);
+            assert!(top >= 1);
+            assert_eq!(bottom, 30);
+        }
+    }
+}
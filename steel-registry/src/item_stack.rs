@@ -14,8 +14,9 @@ use crate::{
         Component, ComponentData, ComponentPatchEntry, DataComponentMap, DataComponentPatch,
         DataComponentType,
         vanilla_components::{
-            DAMAGE, EQUIPPABLE, Equippable, EquippableSlot, MAX_DAMAGE, MAX_STACK_SIZE, TOOL, Tool,
-            UNBREAKABLE,
+            DAMAGE, ENCHANTABLE, ENCHANTMENTS, EQUIPPABLE, Equippable, EquippableSlot,
+            ItemEnchantments, MAX_DAMAGE, MAX_STACK_SIZE, POTION_CONTENTS, PotionContents,
+            STORED_ENCHANTMENTS, TOOL, Tool, UNBREAKABLE,
         },
     },
     items::ItemRef,
@@ -156,6 +157,12 @@ impl ItemStack {
         self.is_damageable_item() && self.get_damage_value() >= self.get_max_damage()
     }
 
+    /// Returns true if taking one more point of damage would break the item.
+    #[must_use]
+    pub fn next_damage_will_break(&self) -> bool {
+        self.is_damageable_item() && self.get_damage_value() >= self.get_max_damage() - 1
+    }
+
     /// Damages the item and breaks it if durability reaches zero.
     ///
     /// Returns `true` if the item broke and should be removed/replaced.
@@ -268,6 +275,12 @@ impl ItemStack {
         self.get_equippable_slot() == Some(slot)
     }
 
+    /// Returns the base potion of this item, if it holds one.
+    #[must_use]
+    pub fn get_potion(&self) -> Option<&Identifier> {
+        self.get(POTION_CONTENTS)?.potion.as_ref()
+    }
+
     /// Gets the raw component data by key.
     #[must_use]
     pub fn get_effective_value_raw(&self, key: &Identifier) -> Option<&ComponentData> {
@@ -359,19 +372,91 @@ impl ItemStack {
     /// Gets the level of an enchantment on this item by identifier.
     /// Returns 0 if the enchantment is not present.
     #[must_use]
-    pub fn get_enchantment_level(&self, _enchantment: &Identifier) -> i32 {
-        // TODO: Implement proper enchantment lookup once ENCHANTMENTS component is implemented
-        // For now, return 0 (no enchantment)
-        0
+    pub fn get_enchantment_level(&self, enchantment: &Identifier) -> i32 {
+        self.get(ENCHANTMENTS)
+            .map_or(0, |enchantments| enchantments.get_level(enchantment))
     }
 
     /// Gets the level of an enchantment on this item by name (e.g., "silk_touch", "fortune").
     /// Returns 0 if the enchantment is not present.
     #[must_use]
-    pub fn get_enchantment_level_by_name(&self, _name: &str) -> i32 {
-        // TODO: Implement proper enchantment lookup once ENCHANTMENTS component is implemented
-        // For now, return 0 (no enchantment)
-        0
+    pub fn get_enchantment_level_by_name(&self, name: &str) -> i32 {
+        self.get(ENCHANTMENTS).map_or(0, |enchantments| {
+            enchantments
+                .iter()
+                .find(|(key, _)| key.namespace == Identifier::VANILLA_NAMESPACE && key.path == name)
+                .map_or(0, |(_, level)| level)
+        })
+    }
+
+    /// Returns how good the enchantments an enchanting table rolls for this
+    /// item are, or 0 if it cannot be enchanted there.
+    #[must_use]
+    pub fn get_enchantability(&self) -> i32 {
+        self.get_or_default(ENCHANTABLE, 0)
+    }
+
+    /// Returns true if this item can go through the enchanting table: it has
+    /// an enchantability and no enchantments yet.
+    ///
+    /// Based on Java's `ItemStack::isEnchantable`.
+    #[must_use]
+    pub fn is_enchantable(&self) -> bool {
+        self.get_enchantability() > 0
+            && self
+                .get(ENCHANTMENTS)
+                .is_none_or(ItemEnchantments::is_empty)
+    }
+
+    /// Adds `enchantment` at `level`, keeping a higher level already present.
+    /// Enchanted books store the enchantment instead.
+    ///
+    /// Based on Java's `ItemStack::enchant`.
+    pub fn enchant(&mut self, enchantment: Identifier, level: i32) {
+        self.update_enchantments(|enchantments| enchantments.upgrade(enchantment, level));
+    }
+
+    /// Returns the component holding the enchantments that count when
+    /// combining this item: the stored ones for enchanted books.
+    fn enchantments_component(&self) -> DataComponentType<ItemEnchantments> {
+        if self.is(&ITEMS.enchanted_book) {
+            STORED_ENCHANTMENTS
+        } else {
+            ENCHANTMENTS
+        }
+    }
+
+    /// Returns the enchantments that count when combining this item in an
+    /// anvil or grindstone.
+    ///
+    /// Based on Java's `EnchantmentHelper::getEnchantmentsForCrafting`.
+    #[must_use]
+    pub fn get_enchantments_for_crafting(&self) -> ItemEnchantments {
+        self.get(self.enchantments_component())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Modifies the enchantments that count when combining this item.
+    ///
+    /// Based on Java's `EnchantmentHelper::updateEnchantments`.
+    pub fn update_enchantments(&mut self, f: impl FnOnce(&mut ItemEnchantments)) {
+        let component = self.enchantments_component();
+        let mut enchantments = self.get_enchantments_for_crafting();
+        f(&mut enchantments);
+        self.set(component, enchantments);
+    }
+
+    /// Returns true if the item has any enchantments, applied or stored.
+    ///
+    /// Based on Java's `EnchantmentHelper::hasAnyEnchantments`.
+    #[must_use]
+    pub fn has_any_enchantments(&self) -> bool {
+        self.get(ENCHANTMENTS)
+            .is_some_and(|enchantments| !enchantments.is_empty())
+            || self
+                .get(STORED_ENCHANTMENTS)
+                .is_some_and(|enchantments| !enchantments.is_empty())
     }
 
     /// Sets the damage/durability as a fraction (0.0 = broken, 1.0 = full).
@@ -486,9 +571,8 @@ impl ItemStack {
     }
 
     /// Sets the potion type for this item.
-    pub fn set_potion(&mut self, _id: &Identifier) {
-        // TODO: Implement potion type setting
-        // Set the POTION_CONTENTS component with the potion ID
+    pub fn set_potion(&mut self, id: &Identifier) {
+        self.set(POTION_CONTENTS, PotionContents::new(id.clone()));
     }
 
     /// Sets the suspicious stew effects for this item.
@@ -8,6 +8,7 @@
 #![allow(internal_features)]
 
 use crate::{
+    advancement::AdvancementRegistry,
     banner_pattern::BannerPatternRegistry,
     biome::BiomeRegistry,
     block_entity_type::BlockEntityTypeRegistry,
@@ -20,6 +21,7 @@ use crate::{
     data_components::{DataComponentRegistry, vanilla_components},
     dialog::DialogRegistry,
     dimension_type::DimensionTypeRegistry,
+    enchantment::EnchantmentRegistry,
     entity_data::{EntityDataSerializerRegistry, register_vanilla_entity_data_serializers},
     entity_types::EntityTypeRegistry,
     fluid::FluidRegistry,
@@ -32,7 +34,10 @@ use crate::{
     menu_type::MenuTypeRegistry,
     painting_variant::PaintingVariantRegistry,
     pig_variant::PigVariantRegistry,
-    recipe::RecipeRegistry,
+    potion::PotionRegistry,
+    recipe::{BrewingRecipeRegistry, FuelRegistry, RecipeRegistry},
+    structure_set::StructureSetRegistry,
+    template_pool::TemplatePoolRegistry,
     timeline::TimelineRegistry,
     trim_material::TrimMaterialRegistry,
     trim_pattern::TrimPatternRegistry,
@@ -43,6 +48,7 @@ use crate::{
 use std::{fmt::Debug, ops::Deref, sync::OnceLock};
 use steel_utils::Identifier;
 
+pub mod advancement;
 pub mod banner_pattern;
 pub mod biome;
 pub mod block_entity_type;
@@ -55,6 +61,7 @@ pub mod damage_type;
 pub mod data_components;
 pub mod dialog;
 pub mod dimension_type;
+pub mod enchantment;
 pub mod entity_data;
 pub mod entity_types;
 pub mod fluid;
@@ -68,7 +75,10 @@ pub mod loot_table;
 pub mod menu_type;
 pub mod painting_variant;
 pub mod pig_variant;
+pub mod potion;
 pub mod recipe;
+pub mod structure_set;
+pub mod template_pool;
 pub mod timeline;
 pub mod trim_material;
 pub mod trim_pattern;
@@ -76,6 +86,26 @@ pub mod wolf_sound_variant;
 pub mod wolf_variant;
 pub mod zombie_nautilus_variant;
 
+#[allow(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_advancements.rs"]
+pub mod vanilla_advancements;
+
+#[allow(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_structure_sets.rs"]
+pub mod vanilla_structure_sets;
+
+#[allow(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_template_pools.rs"]
+pub mod vanilla_template_pools;
+
+#[allow(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_structure_templates.rs"]
+pub mod vanilla_structure_templates;
+
 #[allow(warnings)]
 #[rustfmt::skip]
 #[path = "generated/vanilla_blocks.rs"]
@@ -91,6 +121,11 @@ pub mod vanilla_block_tags;
 #[path = "generated/vanilla_banner_patterns.rs"]
 pub mod vanilla_banner_patterns;
 
+#[allow(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_banner_pattern_tags.rs"]
+pub mod vanilla_banner_pattern_tags;
+
 #[allow(warnings)]
 #[rustfmt::skip]
 #[path = "generated/vanilla_items.rs"]
@@ -160,6 +195,11 @@ pub mod vanilla_chicken_variants;
 #[path = "generated/vanilla_painting_variants.rs"]
 pub mod vanilla_painting_variants;
 
+#[allow(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_painting_variant_tags.rs"]
+pub mod vanilla_painting_variant_tags;
+
 #[allow(warnings)]
 #[rustfmt::skip]
 #[path = "generated/vanilla_dimension_types.rs"]
@@ -214,6 +254,16 @@ pub mod vanilla_timeline_tags;
 #[path = "generated/vanilla_recipes.rs"]
 pub mod vanilla_recipes;
 
+#[allow(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_potions.rs"]
+pub mod vanilla_potions;
+
+#[allow(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_enchantments.rs"]
+pub mod vanilla_enchantments;
+
 #[allow(warnings)]
 #[rustfmt::skip]
 #[path = "generated/vanilla_entities.rs"]
@@ -316,8 +366,7 @@ pub const PAINTING_VARIANT_REGISTRY: Identifier = Identifier::vanilla_static("pa
 pub const DIMENSION_TYPE_REGISTRY: Identifier = Identifier::vanilla_static("dimension_type");
 pub const DAMAGE_TYPE_REGISTRY: Identifier = Identifier::vanilla_static("damage_type");
 pub const BANNER_PATTERN_REGISTRY: Identifier = Identifier::vanilla_static("banner_pattern");
-//TODO: Add enchantments
-//pub const ENCHANTMENT_REGISTRY: Identifier = Identifier::vanilla_static("enchantment");
+pub const ENCHANTMENT_REGISTRY: Identifier = Identifier::vanilla_static("enchantment");
 pub const JUKEBOX_SONG_REGISTRY: Identifier = Identifier::vanilla_static("jukebox_song");
 pub const INSTRUMENT_REGISTRY: Identifier = Identifier::vanilla_static("instrument");
 pub const DIALOG_REGISTRY: Identifier = Identifier::vanilla_static("dialog");
@@ -356,11 +405,18 @@ pub struct Registry {
     pub zombie_nautilus_variants: ZombieNautilusVariantRegistry,
     pub timelines: TimelineRegistry,
     pub recipes: RecipeRegistry,
+    pub fuels: FuelRegistry,
+    pub potions: PotionRegistry,
+    pub brewing: BrewingRecipeRegistry,
+    pub enchantments: EnchantmentRegistry,
     pub entity_types: EntityTypeRegistry,
     pub loot_tables: LootTableRegistry,
     pub block_entity_types: BlockEntityTypeRegistry,
     pub game_rules: GameRuleRegistry,
     pub fluids: FluidRegistry,
+    pub advancements: AdvancementRegistry,
+    pub structure_sets: StructureSetRegistry,
+    pub template_pools: TemplatePoolRegistry,
 }
 
 impl Debug for Registry {
@@ -400,9 +456,13 @@ impl Registry {
         vanilla_cow_variants::register_cow_variants(&mut registry.cow_variants);
         vanilla_chicken_variants::register_chicken_variants(&mut registry.chicken_variants);
         vanilla_painting_variants::register_painting_variants(&mut registry.painting_variants);
+        vanilla_painting_variant_tags::register_painting_variant_tags(
+            &mut registry.painting_variants,
+        );
         vanilla_dimension_types::register_dimension_types(&mut registry.dimension_types);
         vanilla_damage_types::register_damage_types(&mut registry.damage_types);
         vanilla_banner_patterns::register_banner_patterns(&mut registry.banner_patterns);
+        vanilla_banner_pattern_tags::register_banner_pattern_tags(&mut registry.banner_patterns);
         vanilla_jukebox_songs::register_jukebox_songs(&mut registry.jukebox_songs);
         vanilla_instruments::register_instruments(&mut registry.instruments);
         vanilla_dialogs::register_dialogs(&mut registry.dialogs);
@@ -414,6 +474,10 @@ impl Registry {
         vanilla_timelines::register_timelines(&mut registry.timelines);
         vanilla_timeline_tags::register_timeline_tags(&mut registry.timelines);
         vanilla_recipes::register_recipes(&mut registry.recipes);
+        recipe::register_vanilla_fuels(&mut registry.fuels);
+        vanilla_potions::register_potions(&mut registry.potions);
+        recipe::register_vanilla_brewing(&mut registry.brewing);
+        vanilla_enchantments::register_enchantments(&mut registry.enchantments);
         vanilla_entities::register_entity_types(&mut registry.entity_types);
         vanilla_loot_tables::register_loot_tables(&mut registry.loot_tables);
         vanilla_block_entity_types::register_block_entity_types(&mut registry.block_entity_types);
@@ -422,6 +486,10 @@ impl Registry {
         vanilla_fluids::register_fluids(&mut registry.fluids);
         vanilla_fluid_tags::register_fluid_tags(&mut registry.fluids);
 
+        vanilla_advancements::register_advancements(&mut registry.advancements);
+        vanilla_structure_sets::register_structure_sets(&mut registry.structure_sets);
+        vanilla_template_pools::register_template_pools(&mut registry.template_pools);
+
         registry
     }
 
@@ -452,11 +520,18 @@ impl Registry {
         self.zombie_nautilus_variants.freeze();
         self.timelines.freeze();
         self.recipes.freeze();
+        self.fuels.freeze();
+        self.potions.freeze();
+        self.brewing.freeze();
+        self.enchantments.freeze();
         self.entity_types.freeze();
         self.loot_tables.freeze();
         self.block_entity_types.freeze();
         self.game_rules.freeze();
         self.fluids.freeze();
+        self.advancements.freeze();
+        self.structure_sets.freeze();
+        self.template_pools.freeze();
     }
 
     #[must_use]
@@ -488,11 +563,18 @@ impl Registry {
             zombie_nautilus_variants: ZombieNautilusVariantRegistry::new(),
             timelines: TimelineRegistry::new(),
             recipes: RecipeRegistry::new(),
+            fuels: FuelRegistry::new(),
+            potions: PotionRegistry::new(),
+            brewing: BrewingRecipeRegistry::new(),
+            enchantments: EnchantmentRegistry::new(),
             entity_types: EntityTypeRegistry::new(),
             loot_tables: LootTableRegistry::new(),
             block_entity_types: BlockEntityTypeRegistry::new(),
             game_rules: GameRuleRegistry::new(),
             fluids: FluidRegistry::new(),
+            advancements: AdvancementRegistry::new(),
+            structure_sets: StructureSetRegistry::new(),
+            template_pools: TemplatePoolRegistry::new(),
         }
     }
 }
@@ -58,6 +58,71 @@ pub enum DyeColor {
     Black,
 }
 
+impl DyeColor {
+    /// Every dye color, in network id order.
+    pub const ALL: [Self; 16] = [
+        Self::White,
+        Self::Orange,
+        Self::Magenta,
+        Self::LightBlue,
+        Self::Yellow,
+        Self::Lime,
+        Self::Pink,
+        Self::Gray,
+        Self::LightGray,
+        Self::Cyan,
+        Self::Purple,
+        Self::Blue,
+        Self::Brown,
+        Self::Green,
+        Self::Red,
+        Self::Black,
+    ];
+
+    /// Returns the network id of this color.
+    #[must_use]
+    pub const fn id(self) -> i32 {
+        self as i32
+    }
+
+    /// Returns the color with the given network id.
+    #[must_use]
+    pub fn by_id(id: i32) -> Option<Self> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|id| Self::ALL.get(id).copied())
+    }
+
+    /// Returns the serialized name of this color, like `light_blue`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::White => "white",
+            Self::Orange => "orange",
+            Self::Magenta => "magenta",
+            Self::LightBlue => "light_blue",
+            Self::Yellow => "yellow",
+            Self::Lime => "lime",
+            Self::Pink => "pink",
+            Self::Gray => "gray",
+            Self::LightGray => "light_gray",
+            Self::Cyan => "cyan",
+            Self::Purple => "purple",
+            Self::Blue => "blue",
+            Self::Brown => "brown",
+            Self::Green => "green",
+            Self::Red => "red",
+            Self::Black => "black",
+        }
+    }
+
+    /// Returns the color with the given serialized name.
+    #[must_use]
+    pub fn by_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|color| color.name() == name)
+    }
+}
+
 // =============================================================================
 // Loot table types
 // =============================================================================
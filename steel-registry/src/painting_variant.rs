@@ -1,3 +1,5 @@
+use std::ptr;
+
 use rustc_hash::FxHashMap;
 use steel_utils::Identifier;
 use text_components::TextComponent;
@@ -20,6 +22,7 @@ pub type PaintingVariantRef = &'static PaintingVariant;
 pub struct PaintingVariantRegistry {
     painting_variants_by_id: Vec<PaintingVariantRef>,
     painting_variants_by_key: FxHashMap<Identifier, usize>,
+    tags: FxHashMap<Identifier, Vec<PaintingVariantRef>>,
     allows_registering: bool,
 }
 
@@ -29,6 +32,7 @@ impl PaintingVariantRegistry {
         Self {
             painting_variants_by_id: Vec::new(),
             painting_variants_by_key: FxHashMap::default(),
+            tags: FxHashMap::default(),
             allows_registering: true,
         }
     }
@@ -81,6 +85,44 @@ impl PaintingVariantRegistry {
     pub fn is_empty(&self) -> bool {
         self.painting_variants_by_id.is_empty()
     }
+
+    /// Registers a tag with a list of painting variant keys.
+    /// Painting variant keys that don't exist in the registry are silently skipped.
+    pub fn register_tag(&mut self, tag: Identifier, variant_keys: &[&'static str]) {
+        assert!(
+            self.allows_registering,
+            "Cannot register tags after registry has been frozen"
+        );
+
+        let variants: Vec<PaintingVariantRef> = variant_keys
+            .iter()
+            .filter_map(|key| self.by_key(&Identifier::vanilla_static(key)))
+            .collect();
+
+        self.tags.insert(tag, variants);
+    }
+
+    /// Checks if a painting variant is in a given tag.
+    #[must_use]
+    pub fn is_in_tag(&self, variant: PaintingVariantRef, tag: &Identifier) -> bool {
+        self.tags
+            .get(tag)
+            .is_some_and(|variants| variants.iter().any(|&v| ptr::eq(v, variant)))
+    }
+
+    /// Iterates over all painting variants in a tag.
+    pub fn iter_tag(&self, tag: &Identifier) -> impl Iterator<Item = PaintingVariantRef> + '_ {
+        self.tags
+            .get(tag)
+            .map(|v| v.iter().copied())
+            .into_iter()
+            .flatten()
+    }
+
+    /// Gets all tag keys.
+    pub fn tag_keys(&self) -> impl Iterator<Item = &Identifier> + '_ {
+        self.tags.keys()
+    }
 }
 
 impl RegistryExt for PaintingVariantRegistry {
@@ -0,0 +1,98 @@
+use rustc_hash::FxHashMap;
+use steel_utils::Identifier;
+
+use crate::RegistryExt;
+
+/// A status effect applied by drinking a potion.
+#[derive(Debug)]
+pub struct PotionEffect {
+    pub effect: Identifier,
+    /// Duration in ticks.
+    pub duration: i32,
+    pub amplifier: i32,
+}
+
+/// Represents a potion type, such as `minecraft:swiftness` or
+/// `minecraft:long_swiftness`.
+#[derive(Debug)]
+pub struct Potion {
+    pub key: Identifier,
+    pub effects: &'static [PotionEffect],
+}
+
+pub type PotionRef = &'static Potion;
+
+pub struct PotionRegistry {
+    potions_by_id: Vec<PotionRef>,
+    potions_by_key: FxHashMap<Identifier, usize>,
+    allows_registering: bool,
+}
+
+impl PotionRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            potions_by_id: Vec::new(),
+            potions_by_key: FxHashMap::default(),
+            allows_registering: true,
+        }
+    }
+
+    pub fn register(&mut self, potion: PotionRef) -> usize {
+        assert!(
+            self.allows_registering,
+            "Cannot register potions after the registry has been frozen"
+        );
+
+        let id = self.potions_by_id.len();
+        self.potions_by_key.insert(potion.key.clone(), id);
+        self.potions_by_id.push(potion);
+        id
+    }
+
+    #[must_use]
+    pub fn by_id(&self, id: usize) -> Option<PotionRef> {
+        self.potions_by_id.get(id).copied()
+    }
+
+    #[must_use]
+    pub fn get_id(&self, potion: PotionRef) -> &usize {
+        self.potions_by_key
+            .get(&potion.key)
+            .expect("Potion not found")
+    }
+
+    #[must_use]
+    pub fn by_key(&self, key: &Identifier) -> Option<PotionRef> {
+        self.potions_by_key.get(key).and_then(|id| self.by_id(*id))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, PotionRef)> + '_ {
+        self.potions_by_id
+            .iter()
+            .enumerate()
+            .map(|(id, &potion)| (id, potion))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.potions_by_id.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.potions_by_id.is_empty()
+    }
+}
+
+impl RegistryExt for PotionRegistry {
+    fn freeze(&mut self) {
+        self.allows_registering = false;
+    }
+}
+
+impl Default for PotionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
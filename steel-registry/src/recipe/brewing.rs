@@ -0,0 +1,266 @@
+//! Brewing stand mixes.
+
+use crate::{
+    item_stack::ItemStack, items::ItemRef, potion::PotionRef, vanilla_items::ITEMS, vanilla_potions,
+};
+
+use super::ingredient::Ingredient;
+
+/// A brewing mix turning `from` into `to` when brewed with `ingredient`.
+#[derive(Debug)]
+pub struct BrewingMix<T> {
+    pub from: T,
+    pub ingredient: Ingredient,
+    pub to: T,
+}
+
+/// The mixes a brewing stand can brew.
+///
+/// Matches Java's `PotionBrewing`. Container mixes change the bottle (e.g.
+/// potion to splash potion) and keep the potion, potion mixes change the
+/// potion and keep the bottle.
+pub struct BrewingRecipeRegistry {
+    /// Items that can hold potions and be brewed.
+    containers: Vec<ItemRef>,
+    container_mixes: Vec<BrewingMix<ItemRef>>,
+    potion_mixes: Vec<BrewingMix<PotionRef>>,
+    /// Whether registration is still allowed.
+    allows_registering: bool,
+}
+
+impl Default for BrewingRecipeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BrewingRecipeRegistry {
+    /// Creates a new empty brewing recipe registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            containers: Vec::new(),
+            container_mixes: Vec::new(),
+            potion_mixes: Vec::new(),
+            allows_registering: true,
+        }
+    }
+
+    /// Registers an item that potions can be brewed in.
+    pub fn add_container(&mut self, item: ItemRef) {
+        assert!(
+            self.allows_registering,
+            "Cannot register brewing mixes after the registry has been frozen"
+        );
+        self.containers.push(item);
+    }
+
+    /// Registers a mix turning `from` bottles into `to` bottles.
+    pub fn add_container_mix(&mut self, from: ItemRef, ingredient: ItemRef, to: ItemRef) {
+        assert!(
+            self.allows_registering,
+            "Cannot register brewing mixes after the registry has been frozen"
+        );
+        self.container_mixes.push(BrewingMix {
+            from,
+            ingredient: Ingredient::Item(ingredient),
+            to,
+        });
+    }
+
+    /// Registers a mix turning the `from` potion into the `to` potion.
+    pub fn add_mix(&mut self, from: PotionRef, ingredient: ItemRef, to: PotionRef) {
+        assert!(
+            self.allows_registering,
+            "Cannot register brewing mixes after the registry has been frozen"
+        );
+        self.potion_mixes.push(BrewingMix {
+            from,
+            ingredient: Ingredient::Item(ingredient),
+            to,
+        });
+    }
+
+    /// Registers the mixes brewing `potion` from awkward potions. Brewing the
+    /// ingredient into water gives a mundane potion instead.
+    pub fn add_start_mix(&mut self, ingredient: ItemRef, potion: PotionRef) {
+        self.add_mix(vanilla_potions::WATER, ingredient, vanilla_potions::MUNDANE);
+        self.add_mix(vanilla_potions::AWKWARD, ingredient, potion);
+    }
+
+    /// Freezes the registry, preventing further registrations.
+    pub fn freeze(&mut self) {
+        self.allows_registering = false;
+    }
+
+    /// Returns true if `stack` can be put in a bottle slot.
+    #[must_use]
+    pub fn is_container(&self, stack: &ItemStack) -> bool {
+        self.containers.iter().any(|&item| stack.is(item))
+    }
+
+    /// Returns true if `stack` can be put in the ingredient slot.
+    #[must_use]
+    pub fn is_ingredient(&self, stack: &ItemStack) -> bool {
+        self.container_mixes
+            .iter()
+            .any(|mix| mix.ingredient.test(stack))
+            || self
+                .potion_mixes
+                .iter()
+                .any(|mix| mix.ingredient.test(stack))
+    }
+
+    /// Returns true if brewing `ingredient` changes the `input` bottle.
+    #[must_use]
+    pub fn has_mix(&self, input: &ItemStack, ingredient: &ItemStack) -> bool {
+        if !self.is_container(input) {
+            return false;
+        }
+        if self
+            .container_mixes
+            .iter()
+            .any(|mix| input.is(mix.from) && mix.ingredient.test(ingredient))
+        {
+            return true;
+        }
+        let Some(potion) = input.get_potion() else {
+            return false;
+        };
+        self.potion_mixes
+            .iter()
+            .any(|mix| mix.from.key == *potion && mix.ingredient.test(ingredient))
+    }
+
+    /// Returns what brewing `ingredient` turns the `input` bottle into. Inputs
+    /// without a mix are returned unchanged.
+    #[must_use]
+    pub fn mix(&self, ingredient: &ItemStack, input: &ItemStack) -> ItemStack {
+        let Some(potion) = input.get_potion() else {
+            return input.clone();
+        };
+
+        for mix in &self.container_mixes {
+            if input.is(mix.from) && mix.ingredient.test(ingredient) {
+                let mut result = ItemStack::new(mix.to);
+                result.set_potion(potion);
+                return result;
+            }
+        }
+
+        for mix in &self.potion_mixes {
+            if mix.from.key == *potion && mix.ingredient.test(ingredient) {
+                let mut result = ItemStack::new(input.item);
+                result.set_potion(&mix.to.key);
+                return result;
+            }
+        }
+
+        input.clone()
+    }
+}
+
+/// Registers the vanilla brewing mixes.
+///
+/// Matches Java's `PotionBrewing.addVanillaMixes`.
+pub fn register_vanilla_brewing(registry: &mut BrewingRecipeRegistry) {
+    use vanilla_potions::{
+        AWKWARD, FIRE_RESISTANCE, HARMING, HEALING, INFESTED, INVISIBILITY, LEAPING,
+        LONG_FIRE_RESISTANCE, LONG_INVISIBILITY, LONG_LEAPING, LONG_NIGHT_VISION, LONG_POISON,
+        LONG_REGENERATION, LONG_SLOW_FALLING, LONG_SLOWNESS, LONG_STRENGTH, LONG_SWIFTNESS,
+        LONG_TURTLE_MASTER, LONG_WATER_BREATHING, LONG_WEAKNESS, MUNDANE, NIGHT_VISION, OOZING,
+        POISON, REGENERATION, SLOW_FALLING, SLOWNESS, STRENGTH, STRONG_HARMING, STRONG_HEALING,
+        STRONG_LEAPING, STRONG_POISON, STRONG_REGENERATION, STRONG_SLOWNESS, STRONG_STRENGTH,
+        STRONG_SWIFTNESS, STRONG_TURTLE_MASTER, SWIFTNESS, THICK, TURTLE_MASTER, WATER,
+        WATER_BREATHING, WEAKNESS, WEAVING, WIND_CHARGED,
+    };
+
+    registry.add_container(&ITEMS.potion);
+    registry.add_container(&ITEMS.splash_potion);
+    registry.add_container(&ITEMS.lingering_potion);
+    registry.add_container_mix(&ITEMS.potion, &ITEMS.gunpowder, &ITEMS.splash_potion);
+    registry.add_container_mix(
+        &ITEMS.splash_potion,
+        &ITEMS.dragon_breath,
+        &ITEMS.lingering_potion,
+    );
+
+    registry.add_mix(WATER, &ITEMS.glowstone_dust, THICK);
+    registry.add_mix(WATER, &ITEMS.redstone, MUNDANE);
+    registry.add_mix(WATER, &ITEMS.nether_wart, AWKWARD);
+    registry.add_start_mix(&ITEMS.breeze_rod, WIND_CHARGED);
+    registry.add_start_mix(&ITEMS.slime_block, OOZING);
+    registry.add_start_mix(&ITEMS.stone, INFESTED);
+    registry.add_start_mix(&ITEMS.cobweb, WEAVING);
+    registry.add_mix(AWKWARD, &ITEMS.golden_carrot, NIGHT_VISION);
+    registry.add_mix(NIGHT_VISION, &ITEMS.redstone, LONG_NIGHT_VISION);
+    registry.add_mix(NIGHT_VISION, &ITEMS.fermented_spider_eye, INVISIBILITY);
+    registry.add_mix(
+        LONG_NIGHT_VISION,
+        &ITEMS.fermented_spider_eye,
+        LONG_INVISIBILITY,
+    );
+    registry.add_mix(INVISIBILITY, &ITEMS.redstone, LONG_INVISIBILITY);
+    registry.add_start_mix(&ITEMS.magma_cream, FIRE_RESISTANCE);
+    registry.add_mix(FIRE_RESISTANCE, &ITEMS.redstone, LONG_FIRE_RESISTANCE);
+    registry.add_start_mix(&ITEMS.rabbit_foot, LEAPING);
+    registry.add_mix(LEAPING, &ITEMS.redstone, LONG_LEAPING);
+    registry.add_mix(LEAPING, &ITEMS.glowstone_dust, STRONG_LEAPING);
+    registry.add_mix(LEAPING, &ITEMS.fermented_spider_eye, SLOWNESS);
+    registry.add_mix(LONG_LEAPING, &ITEMS.fermented_spider_eye, LONG_SLOWNESS);
+    registry.add_mix(SLOWNESS, &ITEMS.redstone, LONG_SLOWNESS);
+    registry.add_mix(SLOWNESS, &ITEMS.glowstone_dust, STRONG_SLOWNESS);
+    registry.add_mix(AWKWARD, &ITEMS.turtle_helmet, TURTLE_MASTER);
+    registry.add_mix(TURTLE_MASTER, &ITEMS.redstone, LONG_TURTLE_MASTER);
+    registry.add_mix(TURTLE_MASTER, &ITEMS.glowstone_dust, STRONG_TURTLE_MASTER);
+    registry.add_mix(SWIFTNESS, &ITEMS.fermented_spider_eye, SLOWNESS);
+    registry.add_mix(LONG_SWIFTNESS, &ITEMS.fermented_spider_eye, LONG_SLOWNESS);
+    registry.add_start_mix(&ITEMS.sugar, SWIFTNESS);
+    registry.add_mix(SWIFTNESS, &ITEMS.redstone, LONG_SWIFTNESS);
+    registry.add_mix(SWIFTNESS, &ITEMS.glowstone_dust, STRONG_SWIFTNESS);
+    registry.add_mix(AWKWARD, &ITEMS.pufferfish, WATER_BREATHING);
+    registry.add_mix(WATER_BREATHING, &ITEMS.redstone, LONG_WATER_BREATHING);
+    registry.add_start_mix(&ITEMS.glistering_melon_slice, HEALING);
+    registry.add_mix(HEALING, &ITEMS.glowstone_dust, STRONG_HEALING);
+    registry.add_mix(HEALING, &ITEMS.fermented_spider_eye, HARMING);
+    registry.add_mix(STRONG_HEALING, &ITEMS.fermented_spider_eye, STRONG_HARMING);
+    registry.add_mix(HARMING, &ITEMS.glowstone_dust, STRONG_HARMING);
+    registry.add_mix(POISON, &ITEMS.fermented_spider_eye, HARMING);
+    registry.add_mix(LONG_POISON, &ITEMS.fermented_spider_eye, HARMING);
+    registry.add_mix(STRONG_POISON, &ITEMS.fermented_spider_eye, STRONG_HARMING);
+    registry.add_start_mix(&ITEMS.spider_eye, POISON);
+    registry.add_mix(POISON, &ITEMS.redstone, LONG_POISON);
+    registry.add_mix(POISON, &ITEMS.glowstone_dust, STRONG_POISON);
+    registry.add_start_mix(&ITEMS.ghast_tear, REGENERATION);
+    registry.add_mix(REGENERATION, &ITEMS.redstone, LONG_REGENERATION);
+    registry.add_mix(REGENERATION, &ITEMS.glowstone_dust, STRONG_REGENERATION);
+    registry.add_start_mix(&ITEMS.blaze_powder, STRENGTH);
+    registry.add_mix(STRENGTH, &ITEMS.redstone, LONG_STRENGTH);
+    registry.add_mix(STRENGTH, &ITEMS.glowstone_dust, STRONG_STRENGTH);
+    registry.add_mix(WATER, &ITEMS.fermented_spider_eye, WEAKNESS);
+    registry.add_mix(WEAKNESS, &ITEMS.redstone, LONG_WEAKNESS);
+    registry.add_mix(AWKWARD, &ITEMS.phantom_membrane, SLOW_FALLING);
+    registry.add_mix(SLOW_FALLING, &ITEMS.redstone, LONG_SLOW_FALLING);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gunpowder_turns_potions_into_splash_potions() {
+        let mut registry = BrewingRecipeRegistry::new();
+        register_vanilla_brewing(&mut registry);
+
+        let mut input = ItemStack::new(&ITEMS.potion);
+        input.set_potion(&vanilla_potions::SWIFTNESS.key);
+        let gunpowder = ItemStack::new(&ITEMS.gunpowder);
+
+        assert!(registry.is_ingredient(&gunpowder));
+        assert!(registry.has_mix(&input, &gunpowder));
+
+        let result = registry.mix(&gunpowder, &input);
+        assert!(result.is(&ITEMS.splash_potion));
+        assert_eq!(result.get_potion(), Some(&vanilla_potions::SWIFTNESS.key));
+    }
+}
@@ -0,0 +1,167 @@
+//! Fuel burn times for furnace-like blocks.
+
+use steel_utils::Identifier;
+
+use crate::{item_stack::ItemStack, vanilla_items::ITEMS};
+
+use super::ingredient::Ingredient;
+
+/// Maps fuel items to how many ticks they burn for.
+///
+/// Matches Java's `FuelValues`. Later entries take precedence over earlier
+/// ones, like re-adding an item to the Java builder.
+pub struct FuelRegistry {
+    /// Fuels and their burn times, in registration order.
+    fuels: Vec<(Ingredient, i32)>,
+    /// Items that never burn, even if an entry above matches them.
+    excluded: Vec<Ingredient>,
+    /// Whether registration is still allowed.
+    allows_registering: bool,
+}
+
+impl Default for FuelRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FuelRegistry {
+    /// Creates a new empty fuel registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            fuels: Vec::new(),
+            excluded: Vec::new(),
+            allows_registering: true,
+        }
+    }
+
+    /// Registers the items matching `fuel` to burn for `ticks`.
+    pub fn register(&mut self, fuel: Ingredient, ticks: i32) {
+        assert!(
+            self.allows_registering,
+            "Cannot register fuels after the registry has been frozen"
+        );
+        self.fuels.push((fuel, ticks));
+    }
+
+    /// Stops the items matching `ingredient` from being used as fuel.
+    pub fn exclude(&mut self, ingredient: Ingredient) {
+        assert!(
+            self.allows_registering,
+            "Cannot register fuels after the registry has been frozen"
+        );
+        self.excluded.push(ingredient);
+    }
+
+    /// Freezes the registry, preventing further registrations.
+    pub fn freeze(&mut self) {
+        self.allows_registering = false;
+    }
+
+    /// Returns how many ticks one item of `stack` burns for, or 0 if it isn't
+    /// fuel.
+    #[must_use]
+    pub fn burn_duration(&self, stack: &ItemStack) -> i32 {
+        if stack.is_empty() || self.excluded.iter().any(|excluded| excluded.test(stack)) {
+            return 0;
+        }
+        self.fuels
+            .iter()
+            .rev()
+            .find(|(fuel, _)| fuel.test(stack))
+            .map_or(0, |(_, ticks)| *ticks)
+    }
+
+    /// Returns true if `stack` can be burnt as fuel.
+    #[must_use]
+    pub fn is_fuel(&self, stack: &ItemStack) -> bool {
+        self.burn_duration(stack) > 0
+    }
+}
+
+/// Registers the vanilla fuels.
+///
+/// Matches Java's `FuelValues.vanillaBurnTimes` with the default base smelt
+/// time of 200 ticks.
+pub fn register_vanilla_fuels(registry: &mut FuelRegistry) {
+    let tag = |name: &'static str| Ingredient::Tag(Identifier::vanilla_static(name));
+
+    registry.register(Ingredient::Item(&ITEMS.lava_bucket), 20000);
+    registry.register(Ingredient::Item(&ITEMS.coal_block), 16000);
+    registry.register(Ingredient::Item(&ITEMS.blaze_rod), 2400);
+    registry.register(Ingredient::Item(&ITEMS.coal), 1600);
+    registry.register(Ingredient::Item(&ITEMS.charcoal), 1600);
+    registry.register(tag("logs"), 300);
+    registry.register(tag("bamboo_blocks"), 300);
+    registry.register(tag("planks"), 300);
+    registry.register(Ingredient::Item(&ITEMS.bamboo_mosaic), 300);
+    registry.register(tag("wooden_stairs"), 300);
+    registry.register(Ingredient::Item(&ITEMS.bamboo_mosaic_stairs), 300);
+    registry.register(tag("wooden_slabs"), 150);
+    registry.register(Ingredient::Item(&ITEMS.bamboo_mosaic_slab), 150);
+    registry.register(tag("wooden_trapdoors"), 300);
+    registry.register(tag("wooden_pressure_plates"), 300);
+    registry.register(tag("wooden_fences"), 300);
+    registry.register(tag("fence_gates"), 300);
+    registry.register(Ingredient::Item(&ITEMS.note_block), 300);
+    registry.register(Ingredient::Item(&ITEMS.bookshelf), 300);
+    registry.register(tag("wooden_shelves"), 300);
+    registry.register(Ingredient::Item(&ITEMS.chiseled_bookshelf), 300);
+    registry.register(Ingredient::Item(&ITEMS.lectern), 300);
+    registry.register(Ingredient::Item(&ITEMS.jukebox), 300);
+    registry.register(Ingredient::Item(&ITEMS.chest), 300);
+    registry.register(Ingredient::Item(&ITEMS.trapped_chest), 300);
+    registry.register(Ingredient::Item(&ITEMS.crafting_table), 300);
+    registry.register(Ingredient::Item(&ITEMS.daylight_detector), 300);
+    registry.register(tag("banners"), 300);
+    registry.register(Ingredient::Item(&ITEMS.bow), 300);
+    registry.register(Ingredient::Item(&ITEMS.fishing_rod), 300);
+    registry.register(Ingredient::Item(&ITEMS.ladder), 300);
+    registry.register(tag("signs"), 200);
+    registry.register(tag("hanging_signs"), 800);
+    registry.register(Ingredient::Item(&ITEMS.wooden_shovel), 200);
+    registry.register(Ingredient::Item(&ITEMS.wooden_sword), 200);
+    registry.register(Ingredient::Item(&ITEMS.wooden_spear), 200);
+    registry.register(Ingredient::Item(&ITEMS.wooden_hoe), 200);
+    registry.register(Ingredient::Item(&ITEMS.wooden_axe), 200);
+    registry.register(Ingredient::Item(&ITEMS.wooden_pickaxe), 200);
+    registry.register(tag("wooden_doors"), 200);
+    registry.register(tag("boats"), 1200);
+    registry.register(tag("wool"), 100);
+    registry.register(tag("wooden_buttons"), 100);
+    registry.register(Ingredient::Item(&ITEMS.stick), 100);
+    registry.register(tag("saplings"), 100);
+    registry.register(Ingredient::Item(&ITEMS.bowl), 100);
+    registry.register(tag("wool_carpets"), 67);
+    registry.register(Ingredient::Item(&ITEMS.dried_kelp_block), 4001);
+    registry.register(Ingredient::Item(&ITEMS.crossbow), 300);
+    registry.register(Ingredient::Item(&ITEMS.bamboo), 50);
+    registry.register(Ingredient::Item(&ITEMS.dead_bush), 100);
+    registry.register(Ingredient::Item(&ITEMS.short_dry_grass), 100);
+    registry.register(Ingredient::Item(&ITEMS.tall_dry_grass), 100);
+    registry.register(Ingredient::Item(&ITEMS.scaffolding), 50);
+    registry.register(Ingredient::Item(&ITEMS.azalea), 100);
+    registry.register(Ingredient::Item(&ITEMS.flowering_azalea), 100);
+    registry.register(Ingredient::Item(&ITEMS.mangrove_roots), 300);
+    registry.register(Ingredient::Item(&ITEMS.leaf_litter), 100);
+    registry.exclude(tag("non_flammable_wood"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_fuels_take_precedence() {
+        let mut registry = FuelRegistry::new();
+        registry.register(Ingredient::Item(&ITEMS.coal), 1600);
+        registry.register(Ingredient::Item(&ITEMS.stick), 100);
+        registry.register(Ingredient::Item(&ITEMS.stick), 50);
+
+        assert_eq!(registry.burn_duration(&ItemStack::new(&ITEMS.coal)), 1600);
+        assert_eq!(registry.burn_duration(&ItemStack::new(&ITEMS.stick)), 50);
+        assert!(!registry.is_fuel(&ItemStack::new(&ITEMS.stone)));
+        assert!(!registry.is_fuel(&ItemStack::empty()));
+    }
+}
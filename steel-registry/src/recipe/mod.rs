@@ -1,15 +1,29 @@
 //! Recipe system for crafting and other recipe types.
 //!
 //! This module provides the data structures and matching logic for Minecraft recipes.
-//! Currently supports crafting recipes (shaped and shapeless).
+//! Currently supports crafting recipes (shaped and shapeless), cooking recipes,
+//! stonecutter recipes, smithing recipes, fuels and brewing mixes.
 
+mod brewing;
 mod crafting;
+mod fuel;
 mod ingredient;
 mod registry;
+mod smelting;
+mod smithing;
+mod stonecutting;
 
+pub use brewing::{BrewingMix, BrewingRecipeRegistry, register_vanilla_brewing};
 pub use crafting::{
     CraftingCategory, CraftingInput, CraftingRecipe, PositionedCraftingInput, RecipeResult,
     ShapedRecipe, ShapelessRecipe,
 };
+pub use fuel::{FuelRegistry, register_vanilla_fuels};
 pub use ingredient::Ingredient;
 pub use registry::RecipeRegistry;
+pub use smelting::{CookingKind, SmeltingRecipe, SmeltingRecipeRegistry};
+pub use smithing::{
+    SmithingInput, SmithingRecipe, SmithingRecipeRegistry, SmithingTransformRecipe,
+    SmithingTrimRecipe,
+};
+pub use stonecutting::{StonecutterRecipeRegistry, StonecuttingRecipe};
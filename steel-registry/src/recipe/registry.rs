@@ -3,6 +3,11 @@
 use steel_utils::Identifier;
 
 use super::crafting::{CraftingInput, CraftingRecipe, ShapedRecipe, ShapelessRecipe};
+use super::smelting::{CookingKind, SmeltingRecipe, SmeltingRecipeRegistry};
+use super::smithing::{
+    SmithingRecipe, SmithingRecipeRegistry, SmithingTransformRecipe, SmithingTrimRecipe,
+};
+use super::stonecutting::{StonecutterRecipeRegistry, StonecuttingRecipe};
 
 /// Registry for all recipes.
 pub struct RecipeRegistry {
@@ -10,6 +15,16 @@ pub struct RecipeRegistry {
     shaped_recipes: Vec<&'static ShapedRecipe>,
     /// All shapeless crafting recipes.
     shapeless_recipes: Vec<&'static ShapelessRecipe>,
+    /// Furnace recipes.
+    smelting: SmeltingRecipeRegistry,
+    /// Blast furnace recipes.
+    blasting: SmeltingRecipeRegistry,
+    /// Smoker recipes.
+    smoking: SmeltingRecipeRegistry,
+    /// Stonecutter recipes.
+    stonecutting: StonecutterRecipeRegistry,
+    /// Smithing table recipes.
+    smithing: SmithingRecipeRegistry,
     /// Whether registration is still allowed.
     allows_registering: bool,
 }
@@ -27,6 +42,11 @@ impl RecipeRegistry {
         Self {
             shaped_recipes: Vec::new(),
             shapeless_recipes: Vec::new(),
+            smelting: SmeltingRecipeRegistry::new(),
+            blasting: SmeltingRecipeRegistry::new(),
+            smoking: SmeltingRecipeRegistry::new(),
+            stonecutting: StonecutterRecipeRegistry::new(),
+            smithing: SmithingRecipeRegistry::new(),
             allows_registering: true,
         }
     }
@@ -49,6 +69,68 @@ impl RecipeRegistry {
         self.shapeless_recipes.push(recipe);
     }
 
+    /// Registers a cooking recipe for the blocks of `kind`.
+    pub fn register_cooking(&mut self, kind: CookingKind, recipe: &'static SmeltingRecipe) {
+        assert!(
+            self.allows_registering,
+            "Cannot register recipes after the registry has been frozen"
+        );
+        match kind {
+            CookingKind::Smelting => self.smelting.register(recipe),
+            CookingKind::Blasting => self.blasting.register(recipe),
+            CookingKind::Smoking => self.smoking.register(recipe),
+        }
+    }
+
+    /// Returns the cooking recipes for the blocks of `kind`.
+    #[must_use]
+    pub fn cooking(&self, kind: CookingKind) -> &SmeltingRecipeRegistry {
+        match kind {
+            CookingKind::Smelting => &self.smelting,
+            CookingKind::Blasting => &self.blasting,
+            CookingKind::Smoking => &self.smoking,
+        }
+    }
+
+    /// Registers a stonecutter recipe.
+    pub fn register_stonecutting(&mut self, recipe: &'static StonecuttingRecipe) {
+        assert!(
+            self.allows_registering,
+            "Cannot register recipes after the registry has been frozen"
+        );
+        self.stonecutting.register(recipe);
+    }
+
+    /// Returns the stonecutter recipes.
+    #[must_use]
+    pub fn stonecutting(&self) -> &StonecutterRecipeRegistry {
+        &self.stonecutting
+    }
+
+    /// Registers a smithing transform recipe.
+    pub fn register_smithing_transform(&mut self, recipe: &'static SmithingTransformRecipe) {
+        assert!(
+            self.allows_registering,
+            "Cannot register recipes after the registry has been frozen"
+        );
+        self.smithing.register(SmithingRecipe::Transform(recipe));
+    }
+
+    /// Registers a smithing trim recipe.
+    pub fn register_smithing_trim(&mut self, recipe: &'static SmithingTrimRecipe) {
+        assert!(
+            self.allows_registering,
+            "Cannot register recipes after the registry has been frozen"
+        );
+        self.smithing.register(SmithingRecipe::Trim(recipe));
+    }
+
+    /// Returns the smithing table recipes.
+    #[must_use]
+    pub fn smithing(&self) -> &SmithingRecipeRegistry {
+        &self.smithing
+    }
+
     /// Freezes the registry, preventing further registrations.
     pub fn freeze(&mut self) {
         self.allows_registering = false;
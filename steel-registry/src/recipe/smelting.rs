@@ -0,0 +1,103 @@
+//! Cooking recipes for furnaces, blast furnaces and smokers.
+
+use steel_utils::Identifier;
+
+use crate::item_stack::ItemStack;
+
+use super::crafting::RecipeResult;
+use super::ingredient::Ingredient;
+
+/// The recipe type a cooking block uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CookingKind {
+    /// Furnace recipes (`minecraft:smelting`).
+    Smelting,
+    /// Blast furnace recipes (`minecraft:blasting`), ores and metal gear
+    /// cooked twice as fast.
+    Blasting,
+    /// Smoker recipes (`minecraft:smoking`), food cooked twice as fast.
+    Smoking,
+}
+
+/// A recipe that cooks a single item in a furnace-like block.
+///
+/// Matches Java's `AbstractCookingRecipe`.
+#[derive(Debug)]
+pub struct SmeltingRecipe {
+    pub id: Identifier,
+    pub ingredient: Ingredient,
+    pub result: RecipeResult,
+    /// Experience awarded when the result is taken out.
+    pub experience: f32,
+    /// How many ticks the item takes to cook.
+    pub cooking_time: i32,
+}
+
+impl SmeltingRecipe {
+    /// Tests if the item in the input slot matches this recipe.
+    #[must_use]
+    pub fn matches(&self, input: &ItemStack) -> bool {
+        self.ingredient.test(input)
+    }
+}
+
+/// The cooking recipes of one recipe type.
+pub struct SmeltingRecipeRegistry {
+    recipes: Vec<&'static SmeltingRecipe>,
+}
+
+impl Default for SmeltingRecipeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SmeltingRecipeRegistry {
+    /// Creates a new empty cooking recipe registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            recipes: Vec::new(),
+        }
+    }
+
+    /// Registers a cooking recipe.
+    pub fn register(&mut self, recipe: &'static SmeltingRecipe) {
+        self.recipes.push(recipe);
+    }
+
+    /// Finds the recipe that cooks the given input.
+    #[must_use]
+    pub fn find_recipe(&self, input: &ItemStack) -> Option<&'static SmeltingRecipe> {
+        if input.is_empty() {
+            return None;
+        }
+        self.recipes
+            .iter()
+            .find(|recipe| recipe.matches(input))
+            .copied()
+    }
+
+    /// Gets a recipe by its identifier.
+    #[must_use]
+    pub fn get(&self, id: &Identifier) -> Option<&'static SmeltingRecipe> {
+        self.recipes.iter().find(|r| &r.id == id).copied()
+    }
+
+    /// Returns the number of recipes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.recipes.len()
+    }
+
+    /// Returns true if there are no recipes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.recipes.is_empty()
+    }
+
+    /// Iterates over all recipes.
+    pub fn iter(&self) -> impl Iterator<Item = &'static SmeltingRecipe> + '_ {
+        self.recipes.iter().copied()
+    }
+}
@@ -0,0 +1,291 @@
+//! Smithing table recipes.
+
+use steel_utils::Identifier;
+
+use crate::REGISTRY;
+use crate::data_components::vanilla_components::{ArmorTrim, PROVIDES_TRIM_MATERIAL, TRIM};
+use crate::item_stack::ItemStack;
+
+use super::crafting::RecipeResult;
+use super::ingredient::Ingredient;
+
+/// A recipe that turns the base item into another item, keeping its
+/// components, such as a diamond sword into a netherite sword.
+///
+/// Matches Java's `SmithingTransformRecipe`.
+#[derive(Debug)]
+pub struct SmithingTransformRecipe {
+    pub id: Identifier,
+    /// The template, or `None` if the template slot must be empty.
+    pub template: Option<Ingredient>,
+    pub base: Ingredient,
+    /// The addition, or `None` if the addition slot must be empty.
+    pub addition: Option<Ingredient>,
+    pub result: RecipeResult,
+}
+
+/// A recipe that puts a trim on a piece of armor. The trim material comes
+/// from the addition's `minecraft:provides_trim_material` component.
+///
+/// Matches Java's `SmithingTrimRecipe`.
+#[derive(Debug)]
+pub struct SmithingTrimRecipe {
+    pub id: Identifier,
+    pub template: Ingredient,
+    pub base: Ingredient,
+    pub addition: Ingredient,
+    /// The trim pattern the template applies, such as `minecraft:coast`.
+    pub pattern: Identifier,
+}
+
+/// The items in a smithing table's input slots.
+///
+/// Matches Java's `SmithingRecipeInput`.
+#[derive(Debug, Clone, Copy)]
+pub struct SmithingInput<'a> {
+    pub template: &'a ItemStack,
+    pub base: &'a ItemStack,
+    pub addition: &'a ItemStack,
+}
+
+/// Tests an optional ingredient, where `None` only takes an empty slot.
+///
+/// Based on Java's `Ingredient::testOptionalIngredient`.
+fn test_optional(ingredient: Option<&Ingredient>, stack: &ItemStack) -> bool {
+    ingredient.map_or_else(|| stack.is_empty(), |ingredient| ingredient.test(stack))
+}
+
+/// Either kind of smithing recipe.
+#[derive(Debug, Clone, Copy)]
+pub enum SmithingRecipe {
+    Transform(&'static SmithingTransformRecipe),
+    Trim(&'static SmithingTrimRecipe),
+}
+
+impl SmithingRecipe {
+    /// Returns the recipe identifier.
+    #[must_use]
+    pub fn id(&self) -> &Identifier {
+        match self {
+            Self::Transform(recipe) => &recipe.id,
+            Self::Trim(recipe) => &recipe.id,
+        }
+    }
+
+    /// Returns the template ingredient, if the recipe takes one.
+    #[must_use]
+    pub fn template(&self) -> Option<&Ingredient> {
+        match self {
+            Self::Transform(recipe) => recipe.template.as_ref(),
+            Self::Trim(recipe) => Some(&recipe.template),
+        }
+    }
+
+    /// Returns the base ingredient.
+    #[must_use]
+    pub fn base(&self) -> &Ingredient {
+        match self {
+            Self::Transform(recipe) => &recipe.base,
+            Self::Trim(recipe) => &recipe.base,
+        }
+    }
+
+    /// Returns the addition ingredient, if the recipe takes one.
+    #[must_use]
+    pub fn addition(&self) -> Option<&Ingredient> {
+        match self {
+            Self::Transform(recipe) => recipe.addition.as_ref(),
+            Self::Trim(recipe) => Some(&recipe.addition),
+        }
+    }
+
+    /// Tests if the items in the input slots match this recipe.
+    ///
+    /// Based on Java's `SmithingRecipe::matches`.
+    #[must_use]
+    pub fn matches(&self, input: SmithingInput<'_>) -> bool {
+        test_optional(self.template(), input.template)
+            && self.base().test(input.base)
+            && test_optional(self.addition(), input.addition)
+    }
+
+    /// Creates the result for the given input. May be empty, for example
+    /// when the armor already has the same trim.
+    ///
+    /// Based on Java's `SmithingTransformRecipe::assemble` and
+    /// `SmithingTrimRecipe::applyTrim`.
+    #[must_use]
+    pub fn assemble(&self, input: SmithingInput<'_>) -> ItemStack {
+        match self {
+            // Vanilla keeps the base's components as they are, so the damage
+            // carries over as a value rather than as a share of durability
+            Self::Transform(recipe) => ItemStack::with_count_and_patch(
+                recipe.result.item,
+                recipe.result.count,
+                input.base.patch().clone(),
+            ),
+            Self::Trim(recipe) => {
+                let Some(material) = input.addition.get(PROVIDES_TRIM_MATERIAL) else {
+                    return ItemStack::empty();
+                };
+                if REGISTRY.trim_materials.by_key(&material.0).is_none() {
+                    return ItemStack::empty();
+                }
+                let trim = ArmorTrim {
+                    material: material.0.clone(),
+                    pattern: recipe.pattern.clone(),
+                };
+                if input.base.get(TRIM) == Some(&trim) {
+                    return ItemStack::empty();
+                }
+                let mut result = input.base.clone();
+                result.set_count(1);
+                result.set(TRIM, trim);
+                result
+            }
+        }
+    }
+}
+
+/// The smithing table recipes.
+pub struct SmithingRecipeRegistry {
+    recipes: Vec<SmithingRecipe>,
+}
+
+impl Default for SmithingRecipeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SmithingRecipeRegistry {
+    /// Creates a new empty smithing recipe registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            recipes: Vec::new(),
+        }
+    }
+
+    /// Registers a smithing recipe.
+    pub fn register(&mut self, recipe: SmithingRecipe) {
+        self.recipes.push(recipe);
+    }
+
+    /// Finds the first recipe matching the items in the input slots.
+    #[must_use]
+    pub fn find(&self, input: SmithingInput<'_>) -> Option<SmithingRecipe> {
+        self.recipes
+            .iter()
+            .find(|recipe| recipe.matches(input))
+            .copied()
+    }
+
+    /// Returns true if the item goes in the template slot of any recipe.
+    ///
+    /// Vanilla: the `smithing_template` recipe property set.
+    #[must_use]
+    pub fn is_template(&self, stack: &ItemStack) -> bool {
+        self.recipes
+            .iter()
+            .any(|recipe| recipe.template().is_some_and(|t| t.test(stack)))
+    }
+
+    /// Returns true if the item goes in the base slot of any recipe.
+    ///
+    /// Vanilla: the `smithing_base` recipe property set.
+    #[must_use]
+    pub fn is_base(&self, stack: &ItemStack) -> bool {
+        self.recipes.iter().any(|recipe| recipe.base().test(stack))
+    }
+
+    /// Returns true if the item goes in the addition slot of any recipe.
+    ///
+    /// Vanilla: the `smithing_addition` recipe property set.
+    #[must_use]
+    pub fn is_addition(&self, stack: &ItemStack) -> bool {
+        self.recipes
+            .iter()
+            .any(|recipe| recipe.addition().is_some_and(|a| a.test(stack)))
+    }
+
+    /// Returns the trim pattern a template item applies, if it is an armor
+    /// trim template.
+    #[must_use]
+    pub fn trim_pattern_for(&self, template: &ItemStack) -> Option<&'static Identifier> {
+        self.recipes
+            .iter()
+            .copied()
+            .find_map(|recipe| match recipe {
+                SmithingRecipe::Trim(recipe) if recipe.template.test(template) => {
+                    Some(&recipe.pattern)
+                }
+                _ => None,
+            })
+    }
+
+    /// Gets a recipe by its identifier.
+    #[must_use]
+    pub fn get(&self, id: &Identifier) -> Option<SmithingRecipe> {
+        self.recipes.iter().find(|r| r.id() == id).copied()
+    }
+
+    /// Returns the number of recipes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.recipes.len()
+    }
+
+    /// Returns true if there are no recipes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.recipes.is_empty()
+    }
+
+    /// Iterates over all recipes.
+    pub fn iter(&self) -> impl Iterator<Item = SmithingRecipe> + '_ {
+        self.recipes.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_components::vanilla_components::DAMAGE;
+    use crate::vanilla_items::ITEMS;
+
+    #[test]
+    fn upgrading_keeps_the_components_of_the_base() {
+        let recipe = SmithingRecipe::Transform(Box::leak(Box::new(SmithingTransformRecipe {
+            id: Identifier::vanilla_static("netherite_sword_smithing"),
+            template: Some(Ingredient::Item(&ITEMS.netherite_upgrade_smithing_template)),
+            base: Ingredient::Item(&ITEMS.diamond_sword),
+            addition: Some(Ingredient::Item(&ITEMS.netherite_ingot)),
+            result: RecipeResult {
+                item: &ITEMS.netherite_sword,
+                count: 1,
+            },
+        })));
+
+        let template = ItemStack::new(&ITEMS.netherite_upgrade_smithing_template);
+        let mut base = ItemStack::new(&ITEMS.diamond_sword);
+        base.set(DAMAGE, 100);
+        let addition = ItemStack::new(&ITEMS.netherite_ingot);
+        let input = SmithingInput {
+            template: &template,
+            base: &base,
+            addition: &addition,
+        };
+
+        assert!(recipe.matches(input));
+        let result = recipe.assemble(input);
+        assert!(result.is(&ITEMS.netherite_sword));
+        assert_eq!(result.get(DAMAGE), Some(&100));
+
+        let empty = ItemStack::empty();
+        assert!(!recipe.matches(SmithingInput {
+            template: &empty,
+            ..input
+        }));
+    }
+}
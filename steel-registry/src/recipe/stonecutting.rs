@@ -0,0 +1,134 @@
+//! Stonecutter recipes.
+
+use steel_utils::Identifier;
+
+use crate::item_stack::ItemStack;
+
+use super::crafting::RecipeResult;
+use super::ingredient::Ingredient;
+
+/// A recipe that cuts a single item into another in a stonecutter.
+///
+/// Matches Java's `StonecutterRecipe`.
+#[derive(Debug)]
+pub struct StonecuttingRecipe {
+    pub id: Identifier,
+    pub ingredient: Ingredient,
+    pub result: RecipeResult,
+}
+
+impl StonecuttingRecipe {
+    /// Tests if the item in the input slot matches this recipe.
+    #[must_use]
+    pub fn matches(&self, input: &ItemStack) -> bool {
+        self.ingredient.test(input)
+    }
+}
+
+/// The stonecutter recipes, in the order they are sent to the client.
+///
+/// The client lists the options for an input in this same order and refers
+/// to them by index, so the order must not change once players have joined.
+pub struct StonecutterRecipeRegistry {
+    recipes: Vec<&'static StonecuttingRecipe>,
+}
+
+impl Default for StonecutterRecipeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StonecutterRecipeRegistry {
+    /// Creates a new empty stonecutter recipe registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            recipes: Vec::new(),
+        }
+    }
+
+    /// Registers a stonecutter recipe.
+    pub fn register(&mut self, recipe: &'static StonecuttingRecipe) {
+        self.recipes.push(recipe);
+    }
+
+    /// Returns the recipes that take the given input, in order.
+    ///
+    /// Based on Java's `SelectableRecipe.SingleInputSet::selectByInput`.
+    #[must_use]
+    pub fn recipes_for(&self, input: &ItemStack) -> Vec<&'static StonecuttingRecipe> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+        self.recipes
+            .iter()
+            .filter(|recipe| recipe.matches(input))
+            .copied()
+            .collect()
+    }
+
+    /// Returns true if any recipe takes the given input.
+    #[must_use]
+    pub fn accepts_input(&self, input: &ItemStack) -> bool {
+        !input.is_empty() && self.recipes.iter().any(|recipe| recipe.matches(input))
+    }
+
+    /// Gets a recipe by its identifier.
+    #[must_use]
+    pub fn get(&self, id: &Identifier) -> Option<&'static StonecuttingRecipe> {
+        self.recipes.iter().find(|r| &r.id == id).copied()
+    }
+
+    /// Returns the number of recipes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.recipes.len()
+    }
+
+    /// Returns true if there are no recipes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.recipes.is_empty()
+    }
+
+    /// Iterates over all recipes.
+    pub fn iter(&self) -> impl Iterator<Item = &'static StonecuttingRecipe> + '_ {
+        self.recipes.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::ItemRef;
+    use crate::vanilla_items::ITEMS;
+
+    fn recipe(input: ItemRef, result: ItemRef) -> &'static StonecuttingRecipe {
+        Box::leak(Box::new(StonecuttingRecipe {
+            id: result.key.clone(),
+            ingredient: Ingredient::Item(input),
+            result: RecipeResult {
+                item: result,
+                count: 1,
+            },
+        }))
+    }
+
+    #[test]
+    fn recipes_for_an_input_keep_their_order() {
+        let mut registry = StonecutterRecipeRegistry::new();
+        registry.register(recipe(&ITEMS.stone, &ITEMS.stone_bricks));
+        registry.register(recipe(&ITEMS.andesite, &ITEMS.andesite_slab));
+        registry.register(recipe(&ITEMS.stone, &ITEMS.stone_slab));
+
+        let options = registry.recipes_for(&ItemStack::new(&ITEMS.stone));
+        let results: Vec<_> = options.iter().map(|r| r.result.item.key.clone()).collect();
+        assert_eq!(
+            results,
+            [ITEMS.stone_bricks.key.clone(), ITEMS.stone_slab.key.clone()]
+        );
+        assert!(!registry.accepts_input(&ItemStack::new(&ITEMS.dirt)));
+        assert!(registry.recipes_for(&ItemStack::empty()).is_empty());
+    }
+}
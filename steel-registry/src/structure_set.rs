@@ -0,0 +1,173 @@
+use rustc_hash::FxHashMap;
+use steel_utils::Identifier;
+
+use crate::RegistryExt;
+
+/// How the offset inside each spacing cell of a random spread placement is picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RandomSpreadType {
+    Linear,
+    /// Averages two rolls, which biases structures towards the middle of the cell.
+    Triangular,
+}
+
+/// The algorithm used to thin out structures with a frequency below 1.
+///
+/// The legacy methods keep the seeds of structures that used them before 1.18.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrequencyReductionMethod {
+    Default,
+    LegacyType1,
+    LegacyType2,
+    LegacyType3,
+}
+
+/// Keeps structures of a set away from structures of another set.
+#[derive(Debug)]
+pub struct ExclusionZone {
+    pub other_set: Identifier,
+    /// Radius in chunks around which no structure of `other_set` may be placed.
+    pub chunk_count: i32,
+}
+
+#[derive(Debug)]
+pub enum StructurePlacementType {
+    /// One structure per `spacing`×`spacing` chunk cell, at least `separation` chunks apart.
+    RandomSpread {
+        spacing: i32,
+        separation: i32,
+        spread_type: RandomSpreadType,
+    },
+    /// A fixed number of structures placed in rings around the origin, like strongholds.
+    ConcentricRings {
+        distance: i32,
+        spread: i32,
+        count: i32,
+        /// Biome tag the structures are moved towards.
+        preferred_biomes: Option<Identifier>,
+    },
+}
+
+/// Decides which chunks the structures of a set may start in.
+#[derive(Debug)]
+pub struct StructurePlacement {
+    /// Offset from the corner of the start chunk reported by `/locate`.
+    pub locate_offset: [i32; 3],
+    pub frequency_reduction_method: FrequencyReductionMethod,
+    pub frequency: f32,
+    pub salt: i32,
+    pub exclusion_zone: Option<ExclusionZone>,
+    pub placement_type: StructurePlacementType,
+}
+
+/// A structure that can be picked when a structure set is placed.
+#[derive(Debug)]
+pub struct StructureSelectionEntry {
+    pub structure: Identifier,
+    pub weight: i32,
+}
+
+/// Represents a structure set definition from a data pack JSON file.
+#[derive(Debug)]
+pub struct StructureSet {
+    pub key: Identifier,
+    pub structures: &'static [StructureSelectionEntry],
+    pub placement: StructurePlacement,
+}
+
+impl StructureSet {
+    /// Returns true if the given structure is part of this set.
+    pub fn contains(&self, structure: &Identifier) -> bool {
+        self.structures
+            .iter()
+            .any(|entry| entry.structure == *structure)
+    }
+}
+
+pub type StructureSetRef = &'static StructureSet;
+
+pub struct StructureSetRegistry {
+    structure_sets_by_id: Vec<StructureSetRef>,
+    structure_sets_by_key: FxHashMap<Identifier, usize>,
+    allows_registering: bool,
+}
+
+impl StructureSetRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            structure_sets_by_id: Vec::new(),
+            structure_sets_by_key: FxHashMap::default(),
+            allows_registering: true,
+        }
+    }
+
+    pub fn register(&mut self, structure_set: StructureSetRef) -> usize {
+        assert!(
+            self.allows_registering,
+            "Cannot register structure sets after the registry has been frozen"
+        );
+
+        let id = self.structure_sets_by_id.len();
+        self.structure_sets_by_key
+            .insert(structure_set.key.clone(), id);
+        self.structure_sets_by_id.push(structure_set);
+        id
+    }
+
+    #[must_use]
+    pub fn by_id(&self, id: usize) -> Option<StructureSetRef> {
+        self.structure_sets_by_id.get(id).copied()
+    }
+
+    #[must_use]
+    pub fn get_id(&self, structure_set: StructureSetRef) -> &usize {
+        self.structure_sets_by_key
+            .get(&structure_set.key)
+            .expect("Structure set not found")
+    }
+
+    #[must_use]
+    pub fn by_key(&self, key: &Identifier) -> Option<StructureSetRef> {
+        self.structure_sets_by_key
+            .get(key)
+            .and_then(|id| self.by_id(*id))
+    }
+
+    /// Returns every structure set the given structure can be placed by.
+    pub fn containing(&self, structure: &Identifier) -> impl Iterator<Item = StructureSetRef> + '_ {
+        self.structure_sets_by_id
+            .iter()
+            .copied()
+            .filter(move |set| set.contains(structure))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, StructureSetRef)> + '_ {
+        self.structure_sets_by_id
+            .iter()
+            .enumerate()
+            .map(|(id, &set)| (id, set))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.structure_sets_by_id.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.structure_sets_by_id.is_empty()
+    }
+}
+
+impl RegistryExt for StructureSetRegistry {
+    fn freeze(&mut self) {
+        self.allows_registering = false;
+    }
+}
+
+impl Default for StructureSetRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
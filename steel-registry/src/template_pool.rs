@@ -0,0 +1,161 @@
+use std::iter;
+
+use rustc_hash::FxHashMap;
+use steel_utils::Identifier;
+
+use crate::RegistryExt;
+
+/// How a pool element is fitted to the terrain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureProjection {
+    /// Placed as-is at the height the jigsaw assembly picked.
+    Rigid,
+    /// Every column is moved to follow the terrain height, used for paths.
+    TerrainMatching,
+}
+
+/// A piece that can be picked from a template pool.
+#[derive(Debug)]
+pub enum StructurePoolElement {
+    /// A single structure template.
+    Single {
+        location: Identifier,
+        /// Processor list applied when placing. Inline processor lists aren't kept.
+        processors: Option<Identifier>,
+        projection: StructureProjection,
+        /// Legacy elements skip air blocks of the template instead of placing them.
+        legacy: bool,
+    },
+    /// Several elements placed on top of each other.
+    List {
+        elements: &'static [StructurePoolElement],
+        projection: StructureProjection,
+    },
+    /// A placed feature, such as a tree.
+    Feature {
+        feature: Identifier,
+        projection: StructureProjection,
+    },
+    /// Places nothing and stops the jigsaw from trying other elements.
+    Empty,
+}
+
+impl StructurePoolElement {
+    #[must_use]
+    pub fn projection(&self) -> StructureProjection {
+        match self {
+            Self::Single { projection, .. }
+            | Self::List { projection, .. }
+            | Self::Feature { projection, .. } => *projection,
+            Self::Empty => StructureProjection::Rigid,
+        }
+    }
+}
+
+/// Represents a template pool definition from a data pack JSON file.
+#[derive(Debug)]
+pub struct StructureTemplatePool {
+    pub key: Identifier,
+    /// Pool used instead when the jigsaw assembly reached its maximum depth.
+    pub fallback: Identifier,
+    /// Every element with its weight.
+    pub elements: &'static [(StructurePoolElement, i32)],
+}
+
+impl StructureTemplatePool {
+    /// Returns every element repeated by its weight, like vanilla's `rawTemplates`.
+    pub fn templates(&self) -> impl Iterator<Item = &'static StructurePoolElement> + '_ {
+        self.elements
+            .iter()
+            .flat_map(|(element, weight)| iter::repeat_n(element, (*weight).max(0) as usize))
+    }
+
+    /// Returns the number of entries in `templates`.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.elements
+            .iter()
+            .map(|(_, weight)| (*weight).max(0) as usize)
+            .sum()
+    }
+}
+
+pub type StructureTemplatePoolRef = &'static StructureTemplatePool;
+
+pub struct TemplatePoolRegistry {
+    template_pools_by_id: Vec<StructureTemplatePoolRef>,
+    template_pools_by_key: FxHashMap<Identifier, usize>,
+    allows_registering: bool,
+}
+
+impl TemplatePoolRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            template_pools_by_id: Vec::new(),
+            template_pools_by_key: FxHashMap::default(),
+            allows_registering: true,
+        }
+    }
+
+    pub fn register(&mut self, template_pool: StructureTemplatePoolRef) -> usize {
+        assert!(
+            self.allows_registering,
+            "Cannot register template pools after the registry has been frozen"
+        );
+
+        let id = self.template_pools_by_id.len();
+        self.template_pools_by_key
+            .insert(template_pool.key.clone(), id);
+        self.template_pools_by_id.push(template_pool);
+        id
+    }
+
+    #[must_use]
+    pub fn by_id(&self, id: usize) -> Option<StructureTemplatePoolRef> {
+        self.template_pools_by_id.get(id).copied()
+    }
+
+    #[must_use]
+    pub fn get_id(&self, template_pool: StructureTemplatePoolRef) -> &usize {
+        self.template_pools_by_key
+            .get(&template_pool.key)
+            .expect("Template pool not found")
+    }
+
+    #[must_use]
+    pub fn by_key(&self, key: &Identifier) -> Option<StructureTemplatePoolRef> {
+        self.template_pools_by_key
+            .get(key)
+            .and_then(|id| self.by_id(*id))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, StructureTemplatePoolRef)> + '_ {
+        self.template_pools_by_id
+            .iter()
+            .enumerate()
+            .map(|(id, &pool)| (id, pool))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.template_pools_by_id.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.template_pools_by_id.is_empty()
+    }
+}
+
+impl RegistryExt for TemplatePoolRegistry {
+    fn freeze(&mut self) {
+        self.allows_registering = false;
+    }
+}
+
+impl Default for TemplatePoolRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,51 @@
+#![allow(missing_docs)]
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use steel_utils::noise::ImprovedNoise;
+use steel_utils::random::{Random, legacy_random::LegacyRandom};
+
+const SAMPLES: usize = 128;
+
+fn positions() -> Vec<[f64; 3]> {
+    let mut rng = LegacyRandom::from_seed(0);
+    (0..SAMPLES)
+        .map(|_| {
+            [
+                rng.next_f64() * 1024.0,
+                rng.next_f64() * 384.0 - 64.0,
+                rng.next_f64() * 1024.0,
+            ]
+        })
+        .collect()
+}
+
+fn bench_noise(c: &mut Criterion) {
+    let noise = ImprovedNoise::new(&mut LegacyRandom::from_seed(12345));
+    let positions = positions();
+    let mut group = c.benchmark_group("improved_noise 128 samples");
+    group.throughput(Throughput::Elements(SAMPLES as u64));
+
+    group.bench_function("noise", |b| {
+        b.iter(|| {
+            for &[x, y, z] in &positions {
+                black_box(noise.noise(black_box(x), black_box(y), black_box(z)));
+            }
+        });
+    });
+
+    group.bench_function("noise_4", |b| {
+        b.iter(|| {
+            for chunk in positions.chunks_exact(4) {
+                let x = [chunk[0][0], chunk[1][0], chunk[2][0], chunk[3][0]];
+                let y = [chunk[0][1], chunk[1][1], chunk[2][1], chunk[3][1]];
+                let z = [chunk[0][2], chunk[1][2], chunk[2][2], chunk[3][2]];
+                black_box(noise.noise_4(black_box(x), black_box(y), black_box(z)));
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_noise);
+criterion_main!(benches);
@@ -0,0 +1,131 @@
+use std::io::{Cursor, Error, Write};
+
+use crate::codec::VarInt;
+use crate::math::Vector3;
+use crate::serial::{ReadFrom, WriteTo};
+
+/// The largest value a component is quantized to.
+const MAX_QUANTIZED: f64 = 32766.0;
+/// Vectors with no component larger than this are sent as zero.
+const ABS_MIN: f64 = 3.051_944_088_384_301e-5;
+/// Components are clamped to this before they are quantized.
+const ABS_MAX: f64 = 1.717_986_918_3e10;
+
+/// A velocity packed into as few bytes as its size allows: a single byte for
+/// no movement, and otherwise three 15 bit components scaled by the largest
+/// one.
+///
+/// Vanilla: `LpVec3`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LpVec3(pub Vector3<f64>);
+
+fn sanitize(value: f64) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(-ABS_MAX, ABS_MAX)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn pack(value: f64) -> u64 {
+    ((value * 0.5 + 0.5) * MAX_QUANTIZED).round() as u64
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn unpack(value: u64) -> f64 {
+    ((value & 0x7FFF) as f64).min(MAX_QUANTIZED) * 2.0 / MAX_QUANTIZED - 1.0
+}
+
+#[allow(missing_docs)]
+impl WriteTo for LpVec3 {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn write(&self, writer: &mut impl Write) -> Result<(), Error> {
+        let x = sanitize(self.0.x);
+        let y = sanitize(self.0.y);
+        let z = sanitize(self.0.z);
+        let max = x.abs().max(y.abs()).max(z.abs());
+        if max < ABS_MIN {
+            return 0_u8.write(writer);
+        }
+
+        let scale = max.ceil() as u64;
+        let extended = scale & 3 != scale;
+        let markers = if extended { scale & 3 | 4 } else { scale };
+        let scale_f = scale as f64;
+        let packed =
+            markers | pack(x / scale_f) << 3 | pack(y / scale_f) << 18 | pack(z / scale_f) << 33;
+        (packed as u8).write(writer)?;
+        ((packed >> 8) as u8).write(writer)?;
+        ((packed >> 16) as u32).write(writer)?;
+        if extended {
+            VarInt((scale >> 2) as i32).write(writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(missing_docs)]
+impl ReadFrom for LpVec3 {
+    #[allow(clippy::cast_sign_loss, clippy::cast_precision_loss)]
+    fn read(read: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let low = u64::from(u8::read(read)?);
+        if low == 0 {
+            return Ok(Self(Vector3::new(0.0, 0.0, 0.0)));
+        }
+        let middle = u64::from(u8::read(read)?);
+        let high = u64::from(u32::read(read)?);
+        let packed = high << 16 | middle << 8 | low;
+        let mut scale = low & 3;
+        if low & 4 == 4 {
+            scale |= u64::from(VarInt::read(read)?.0 as u32) << 2;
+        }
+        let scale = scale as f64;
+        Ok(Self(Vector3::new(
+            unpack(packed >> 3) * scale,
+            unpack(packed >> 18) * scale,
+            unpack(packed >> 33) * scale,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(vector: Vector3<f64>) -> (Vector3<f64>, usize) {
+        let mut bytes = Vec::new();
+        LpVec3(vector).write(&mut bytes).expect("write to vec");
+        let len = bytes.len();
+        let read = LpVec3::read(&mut Cursor::new(bytes.as_slice())).expect("read back");
+        (read.0, len)
+    }
+
+    #[test]
+    fn zero_is_a_single_byte() {
+        assert_eq!(round_trip(Vector3::new(0.0, 0.0, 0.0)).1, 1);
+        assert_eq!(round_trip(Vector3::new(1e-6, 0.0, -1e-6)).1, 1);
+    }
+
+    #[test]
+    fn velocities_survive_the_round_trip() {
+        for (vector, len) in [
+            (Vector3::new(0.5, -0.25, 0.125), 6),
+            (Vector3::new(-2.5, 1.0, 3.0), 6),
+            (Vector3::new(10.0, -7.5, 0.0), 7),
+        ] {
+            let (read, written) = round_trip(vector);
+            assert_eq!(written, len, "{vector:?}");
+            let scale = vector
+                .x
+                .abs()
+                .max(vector.y.abs())
+                .max(vector.z.abs())
+                .ceil();
+            let tolerance = scale / MAX_QUANTIZED * 2.0;
+            assert!((read.x - vector.x).abs() <= tolerance, "{read:?}");
+            assert!((read.y - vector.y).abs() <= tolerance, "{read:?}");
+            assert!((read.z - vector.z).abs() <= tolerance, "{read:?}");
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! This module contains various codecs for reading and writing data.
 /// A module for a bit set.
 pub mod bit_set;
+/// A module for a velocity packed into few bytes.
+pub mod lp_vec3;
 /// A module for an Or type that can be one of two types.
 pub mod or;
 /// A module for a variable-length integer.
@@ -11,6 +13,7 @@ pub mod var_long;
 pub mod var_uint;
 
 pub use bit_set::BitSet;
+pub use lp_vec3::LpVec3;
 pub use or::Or;
 pub use var_int::VarInt;
 pub use var_long::VarLong;
@@ -9,6 +9,7 @@
     derive_const,
     core_intrinsics
 )]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 #![allow(internal_features)]
 
 pub mod codec;
@@ -18,6 +19,7 @@ pub mod hash;
 /// A module for custom locks.
 pub mod locks;
 pub mod math;
+pub mod noise;
 pub mod random;
 pub mod serial;
 pub mod text;
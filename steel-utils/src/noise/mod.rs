@@ -0,0 +1,7 @@
+//! Noise functions used by world generation.
+
+pub mod improved_noise;
+pub mod simplex_noise;
+
+pub use improved_noise::ImprovedNoise;
+pub use simplex_noise::SimplexNoise;
@@ -0,0 +1,107 @@
+//! Simplex noise, matching vanilla's `SimplexNoise`.
+
+use crate::noise::improved_noise::GRADIENT;
+use crate::random::Random;
+
+/// Skews a 2D position onto the simplex grid, `(sqrt(3) - 1) / 2`.
+const F2: f64 = 0.366_025_403_784_438_6;
+/// Unskews a simplex grid position, `(3 - sqrt(3)) / 6`.
+const G2: f64 = 0.211_324_865_405_187_13;
+
+/// 2D simplex noise, used by the End islands.
+#[derive(Debug, Clone)]
+pub struct SimplexNoise {
+    /// Offset added to sampled x coordinates by vanilla's 3D sampling.
+    pub xo: f64,
+    /// Offset added to sampled y coordinates by vanilla's 3D sampling.
+    pub yo: f64,
+    /// Offset added to sampled z coordinates by vanilla's 3D sampling.
+    pub zo: f64,
+    p: [u8; 256],
+}
+
+impl SimplexNoise {
+    /// Creates a noise with offsets and a permutation taken from the random.
+    pub fn new<R: Random>(random: &mut R) -> Self {
+        let xo = random.next_f64() * 256.0;
+        let yo = random.next_f64() * 256.0;
+        let zo = random.next_f64() * 256.0;
+
+        let mut p = [0u8; 256];
+        for (i, value) in p.iter_mut().enumerate() {
+            *value = i as u8;
+        }
+        for i in 0..256 {
+            let j = random.next_i32_bounded(256 - i as i32) as usize;
+            p.swap(i, i + j);
+        }
+
+        Self { xo, yo, zo, p }
+    }
+
+    /// Samples the noise at a 2D position. The offsets aren't applied, like vanilla.
+    #[must_use]
+    pub fn get_value(&self, x: f64, y: f64) -> f64 {
+        let s = (x + y) * F2;
+        let i = (x + s).floor() as i32;
+        let j = (y + s).floor() as i32;
+        let t = f64::from(i + j) * G2;
+        let x0 = x - (f64::from(i) - t);
+        let y0 = y - (f64::from(j) - t);
+
+        // Which of the two triangles of the skewed cell the position is in
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let x1 = x0 - f64::from(i1) + G2;
+        let y1 = y0 - f64::from(j1) + G2;
+        let x2 = x0 - 1.0 + 2.0 * G2;
+        let y2 = y0 - 1.0 + 2.0 * G2;
+
+        let ii = i & 0xFF;
+        let jj = j & 0xFF;
+        let gi0 = self.perm(ii + self.perm(jj)) % 12;
+        let gi1 = self.perm(ii + i1 + self.perm(jj + j1)) % 12;
+        let gi2 = self.perm(ii + 1 + self.perm(jj + 1)) % 12;
+
+        let n0 = Self::corner_noise(gi0, x0, y0);
+        let n1 = Self::corner_noise(gi1, x1, y1);
+        let n2 = Self::corner_noise(gi2, x2, y2);
+        70.0 * (n0 + n1 + n2)
+    }
+
+    fn perm(&self, index: i32) -> i32 {
+        i32::from(self.p[(index & 0xFF) as usize])
+    }
+
+    /// The contribution of one simplex corner, vanilla's `getCornerNoise3D` with `z = 0`.
+    fn corner_noise(index: i32, x: f64, y: f64) -> f64 {
+        let t = 0.5 - x * x - y * y;
+        if t < 0.0 {
+            return 0.0;
+        }
+        let gradient = GRADIENT[index as usize];
+        let t = t * t;
+        t * t * (gradient[0] * x + gradient[1] * y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::legacy_random::LegacyRandom;
+
+    #[test]
+    fn matches_vanilla() {
+        let noise = SimplexNoise::new(&mut LegacyRandom::from_seed(42));
+        // Values from vanilla's SimplexNoise seeded with `new LegacyRandomSource(42)`
+        let expected = [
+            ((0.0, 0.0), 0.0),
+            ((12.5, -3.25), 0.844_398_729_513_583_1),
+            ((-700.0, 1234.0), 0.373_691_526_906_048_34),
+            ((65.0, 65.0), -0.865_463_001_428_991_8),
+        ];
+        for ((x, y), value) in expected {
+            assert_eq!(noise.get_value(x, y).to_bits(), value.to_bits());
+        }
+    }
+}
@@ -53,8 +53,9 @@ pub trait Random {
     fn next_positional(&mut self) -> RandomSplitter;
 
     fn consume_count(&mut self, count: i32) {
+        // Vanilla's RandomSource.consumeCount skips with nextInt
         for _ in 0..count {
-            self.next_i64();
+            self.next_i32();
         }
     }
 }
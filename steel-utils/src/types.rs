@@ -459,6 +459,44 @@ impl From<GameType> for f32 {
     }
 }
 
+/// The difficulty of the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(missing_docs)]
+pub enum Difficulty {
+    Peaceful = 0,
+    Easy = 1,
+    #[default]
+    Normal = 2,
+    Hard = 3,
+}
+
+impl Difficulty {
+    /// Returns the name of the difficulty.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Peaceful => "peaceful",
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    /// Returns the lowest health starvation can bring a player down to.
+    ///
+    /// Matches the checks in vanilla's `FoodData.tick`: easy stops at half the
+    /// maximum health, normal at half a heart and hard lets starvation kill.
+    #[must_use]
+    pub const fn starvation_health_floor(&self) -> f32 {
+        match self {
+            Difficulty::Peaceful | Difficulty::Easy => 10.0,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.0,
+        }
+    }
+}
+
 /// An identifier used by Minecraft.
 #[derive(Clone, PartialEq, Eq, Hash, Default)]
 pub struct Identifier {
@@ -2,13 +2,16 @@
 //!
 //! This module handles loading the server configuration from disk.
 //! The `ServerConfig` struct is defined in `steel-core`, this module
-//! just handles the file I/O and initialization.
+//! just handles the file I/O, initialization and reloading.
 
 use base64::{Engine, prelude::BASE64_STANDARD};
-use std::{fs, path::Path, sync::LazyLock};
+use std::{fs, path::Path};
 
 // Re-export types from steel-core for convenience
-pub use steel_core::config::{ConfigLabel, ConfigLink, ServerConfig, ServerConfigRef, ServerLinks};
+pub use steel_core::config::{
+    ConfigLabel, ConfigLink, FileLogConfig, LogFormat, ResourcePackConfig, STEEL_CONFIG,
+    ServerConfig, ServerConfigRef, ServerLinks,
+};
 
 #[cfg(feature = "stand-alone")]
 const DEFAULT_FAVICON: &[u8] = include_bytes!("../../package-content/favicon.png");
@@ -19,11 +22,8 @@ const DEFAULT_CONFIG: &str = include_str!("../../package-content/steel_config.js
 /// The Minecraft version this server supports.
 pub const MC_VERSION: &str = "1.21.11";
 
-/// The server configuration.
-///
-/// This is loaded from `config/steel_config.json5` or created if it doesn't exist.
-pub static STEEL_CONFIG: LazyLock<ServerConfig> =
-    LazyLock::new(|| load_or_create(Path::new("config/steel_config.json5")));
+/// Where the server configuration is loaded from. Created if it doesn't exist.
+const CONFIG_PATH: &str = "config/steel_config.json5";
 
 /// Loads the server configuration from the given path, or creates it if it doesn't exist.
 ///
@@ -72,6 +72,9 @@ fn validate(config: &ServerConfig) -> Result<(), &'static str> {
     if config.simulation_distance > config.view_distance {
         return Err("Simulation distance must be less than or equal to view distance");
     }
+    if config.spawn_chunk_radius > 32 {
+        return Err("spawn_chunk_radius must be less than or equal to 32");
+    }
     if let Some(compression) = config.compression {
         if compression.threshold.get() < 256 {
             return Err("Compression threshold must be greater than or equal to 256");
@@ -80,6 +83,45 @@ fn validate(config: &ServerConfig) -> Result<(), &'static str> {
             return Err("Compression level must be between 1 and 9");
         }
     }
+    if config.online_mode && !config.encryption {
+        return Err("encryption must be true when online_mode is enabled");
+    }
+    if config.profile_key_cache_ttl == 0 {
+        return Err("profile_key_cache_ttl must be greater than 0");
+    }
+    if config.skin_cache_ttl == 0 {
+        return Err("skin_cache_ttl must be greater than 0");
+    }
+    if config.chunk_generation_threads == Some(0) {
+        return Err("chunk_generation_threads must be greater than 0");
+    }
+    if let Some(server_links) = &config.server_links
+        && server_links.enable
+        && !server_links
+            .links
+            .iter()
+            .all(|link| link.url.starts_with("https://") || link.url.starts_with("http://"))
+    {
+        // The client refuses to open links with any other scheme
+        return Err("server_links urls must start with https:// or http://");
+    }
+    if let Some(resource_pack) = &config.resource_pack
+        && (resource_pack.sha1_hash.len() != 40
+            || !resource_pack
+                .sha1_hash
+                .bytes()
+                .all(|byte| byte.is_ascii_hexdigit()))
+    {
+        return Err("resource_pack sha1_hash must be exactly 40 hex characters");
+    }
+    if let Some(log_file) = &config.log_file {
+        if log_file.max_file_size_mb == 0 {
+            return Err("log_file max_file_size_mb must be greater than 0");
+        }
+        if log_file.max_files == 0 {
+            return Err("log_file max_files must be greater than 0");
+        }
+    }
     if config.enforce_secure_chat {
         if !config.online_mode {
             return Err("online_mode must be true when enforce_secure_chat is enabled");
@@ -129,10 +171,72 @@ pub fn load_favicon(config: &ServerConfig) -> Option<String> {
     None
 }
 
-/// Initializes the steel-core config reference.
+/// Loads the config and initializes the steel-core config reference.
 ///
-/// This must be called before any steel-core code accesses `STEEL_CONFIG`.
+/// This must be called before anything accesses `STEEL_CONFIG`.
 pub fn init_steel_core_config() {
-    // Force config to load, then initialize steel-core's reference
-    ServerConfigRef::init(&STEEL_CONFIG);
+    let config = load_or_create(Path::new(CONFIG_PATH));
+    ServerConfigRef::init(config, reload);
+}
+
+/// Reads the config file again and swaps it in for `STEEL_CONFIG`.
+///
+/// Options that are only read at startup keep their current value, with a
+/// warning if the file changed them.
+///
+/// # Errors
+/// Returns an error if the config can't be read, parsed or validated.
+/// The current config stays in use in that case.
+pub fn reload() -> Result<(), String> {
+    let config_str =
+        fs::read_to_string(CONFIG_PATH).map_err(|err| format!("Failed to read config: {err}"))?;
+    let mut config: ServerConfig = serde_json5::from_str(config_str.as_str())
+        .map_err(|err| format!("Failed to parse config: {err}"))?;
+    config.mc_version = MC_VERSION;
+
+    keep_restart_only_options(&STEEL_CONFIG.load(), &mut config);
+    validate(&config)?;
+
+    ServerConfigRef::replace(config);
+    log::info!("Reloaded config");
+    Ok(())
+}
+
+/// Restores the options that only take effect on restart.
+///
+/// Compression is one of them since players that are already connected keep
+/// the threshold they joined with, and world packets are encoded for all of them.
+fn keep_restart_only_options(current: &ServerConfig, new: &mut ServerConfig) {
+    fn keep<T: PartialEq + Clone>(name: &str, current: &T, new: &mut T) {
+        if new != current {
+            log::warn!("{name} can't change while the server is running, restart to apply it");
+            new.clone_from(current);
+        }
+    }
+
+    macro_rules! keep {
+        ($($option:ident),* $(,)?) => {
+            $(keep(stringify!($option), &current.$option, &mut new.$option);)*
+        };
+    }
+
+    keep!(
+        server_port,
+        seed,
+        online_mode,
+        encryption,
+        compression,
+        spawn_chunk_radius,
+        metrics_port,
+        rcon_password,
+        rcon_port,
+        enable_query,
+        profile_key_cache_ttl,
+        generator_type,
+        generator_settings,
+        generate_structures,
+        chunk_generation_threads,
+        log_format,
+        log_file,
+    );
 }
@@ -5,15 +5,34 @@
 use std::{
     net::{Ipv4Addr, SocketAddrV4},
     sync::Arc,
+    time::Duration,
 };
 
+use query::QueryServer;
+use rcon::RconServer;
 use steel_core::server::Server;
+use steel_crypto::mojang_api;
 use steel_login::JavaTcpClient;
-use tokio::{net::TcpListener, runtime::Runtime, select};
+use tokio::{
+    net::TcpListener,
+    runtime::Runtime,
+    select,
+    time::{sleep, timeout},
+};
 use tokio_util::{sync::CancellationToken, task::TaskTracker};
 
 /// Server configuration module.
 pub mod config;
+/// Log output formatting.
+pub mod logger;
+/// Prometheus metrics endpoint.
+pub mod metrics;
+/// Chunk pre-generation around spawn (`--pregen <radius>`).
+pub mod pregen;
+/// GameSpy4 query server.
+pub mod query;
+/// Remote console (RCON) server.
+pub mod rcon;
 /// Spawn chunk generation with optional terminal progress display.
 pub mod spawn_progress;
 
@@ -34,21 +53,20 @@ pub struct SteelServer {
 impl SteelServer {
     /// Creates a new Steel server.
     ///
+    /// The config must already be loaded with [`config::init_steel_core_config`].
+    ///
     /// # Panics
     /// This function will panic if the TCP listener fails to bind to the server address.
     pub async fn new(chunk_runtime: Arc<Runtime>) -> Self {
         log::info!("Starting Steel Server");
 
-        // Initialize steel-core's config reference before any steel-core code runs
-        config::init_steel_core_config();
-
         let cancel_token = CancellationToken::new();
         let server = Server::new(chunk_runtime, cancel_token.clone()).await;
 
         Self {
             tcp_listener: TcpListener::bind(SocketAddrV4::new(
                 Ipv4Addr::UNSPECIFIED,
-                STEEL_CONFIG.server_port,
+                STEEL_CONFIG.load().server_port,
             ))
             .await
             .expect("Failed to bind to server address"),
@@ -68,6 +86,31 @@ impl SteelServer {
             server.run(token).await;
         });
 
+        if STEEL_CONFIG.load().online_mode {
+            // Chat sessions can't be validated without the keys, so players
+            // are only let in once the first fetch is done
+            let delay = fetch_profile_keys().await;
+            task_tracker.spawn(refresh_profile_keys(delay, self.cancel_token.clone()));
+        }
+
+        if let Some(port) = STEEL_CONFIG.load().metrics_port {
+            task_tracker.spawn(metrics::serve(
+                self.server.clone(),
+                port,
+                self.cancel_token.clone(),
+            ));
+        }
+
+        if let Some(password) = &STEEL_CONFIG.load().rcon_password {
+            let rcon = Arc::new(RconServer::new(self.server.clone(), password.clone()));
+            task_tracker.spawn(rcon.run(STEEL_CONFIG.load().rcon_port, self.cancel_token.clone()));
+        }
+
+        if STEEL_CONFIG.load().enable_query {
+            let query = QueryServer::new(self.server.clone());
+            task_tracker.spawn(query.run(self.cancel_token.clone()));
+        }
+
         loop {
             select! {
                 () = self.cancel_token.cancelled() => {
@@ -95,3 +138,44 @@ impl SteelServer {
         let _ = server_handle.await;
     }
 }
+
+/// How soon to retry after failing to fetch Mojang's profile keys.
+const PROFILE_KEY_RETRY: Duration = Duration::from_secs(60);
+/// How long startup waits for Mojang's profile keys before letting players in
+/// without them.
+const PROFILE_KEY_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Fetches Mojang's profile keys once, returning how long to wait until the
+/// next fetch.
+async fn fetch_profile_keys() -> Duration {
+    let ttl = Duration::from_secs(STEEL_CONFIG.load().profile_key_cache_ttl);
+    match timeout(
+        PROFILE_KEY_FETCH_TIMEOUT,
+        mojang_api::refresh_profile_keys(),
+    )
+    .await
+    {
+        Ok(Ok(())) => ttl,
+        Ok(Err(err)) => {
+            log::warn!("Failed to fetch Mojang profile keys: {err}");
+            ttl.min(PROFILE_KEY_RETRY)
+        }
+        Err(_) => {
+            log::warn!("Timed out fetching Mojang profile keys");
+            ttl.min(PROFILE_KEY_RETRY)
+        }
+    }
+}
+
+/// Keeps Mojang's profile keys cached, refetching them every
+/// `profile_key_cache_ttl` seconds after waiting `delay` for the first time.
+async fn refresh_profile_keys(mut delay: Duration, cancel_token: CancellationToken) {
+    loop {
+        select! {
+            () = cancel_token.cancelled() => break,
+            () = sleep(delay) => {}
+        }
+
+        delay = fetch_profile_keys().await;
+    }
+}
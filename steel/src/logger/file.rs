@@ -0,0 +1,183 @@
+//! Writing logs to a file that is rotated by size and by day.
+//!
+//! The current log is written to the configured path, e.g. `logs/steel.log`.
+//! When it would grow past `max_file_size_mb`, or the UTC date changes, it is
+//! renamed to `steel.log.1`, older logs shift up to `.2`, `.3` and so on, and
+//! logs past `max_files` are deleted.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::json;
+use steel_utils::locks::SyncMutex;
+use tracing_subscriber::fmt::{
+    format::Writer,
+    time::{self, FormatTime},
+};
+
+use crate::config::{FileLogConfig, LogFormat};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A log file that rotates itself as it is written to.
+///
+/// Every write is one formatted log line, so rotation never splits a line.
+pub struct LogFileWriter {
+    path: PathBuf,
+    max_size: u64,
+    max_files: usize,
+    format: LogFormat,
+    state: SyncMutex<FileState>,
+}
+
+struct FileState {
+    file: File,
+    size: u64,
+    day: u64,
+}
+
+impl LogFileWriter {
+    /// Opens the log file, appending to it if it already exists.
+    ///
+    /// # Errors
+    /// Returns an error if the log directory or file cannot be created.
+    pub fn open(config: &FileLogConfig, format: LogFormat) -> io::Result<Self> {
+        let path = PathBuf::from(&config.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = open_append(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            max_size: config.max_file_size_mb * 1024 * 1024,
+            max_files: config.max_files,
+            format,
+            state: SyncMutex::new(FileState {
+                file,
+                size,
+                day: current_day(),
+            }),
+        })
+    }
+
+    /// Closes the current file and shifts it into the rotated logs, then opens a new one.
+    fn rotate(&self, state: &mut FileState) -> io::Result<()> {
+        let line = self.rotation_line();
+        state.file.write_all(line.as_bytes())?;
+        state.file.flush()?;
+
+        let rotated = |index: usize| {
+            let mut name = self.path.clone().into_os_string();
+            name.push(format!(".{index}"));
+            PathBuf::from(name)
+        };
+        let oldest = rotated(self.max_files);
+        if oldest.exists() {
+            fs::remove_file(oldest)?;
+        }
+        for index in (1..self.max_files).rev() {
+            let from = rotated(index);
+            if from.exists() {
+                fs::rename(from, rotated(index + 1))?;
+            }
+        }
+        // The lock is held, so no line is written between the rename and the new file opening
+        fs::rename(&self.path, rotated(1))?;
+        state.file = open_append(&self.path)?;
+        state.file.write_all(line.as_bytes())?;
+        state.size = line.len() as u64;
+        Ok(())
+    }
+
+    /// The line noting a rotation, in the same format as the other log lines.
+    fn rotation_line(&self) -> String {
+        let mut timestamp = String::new();
+        // Formatting into a String can't fail
+        let _ = time::SystemTime.format_time(&mut Writer::new(&mut timestamp));
+        let target = module_path!();
+        match self.format {
+            LogFormat::Text => format!("{timestamp}  INFO {target}: Rotating log file\n"),
+            LogFormat::Json => format!(
+                "{}\n",
+                json!({
+                    "timestamp": timestamp,
+                    "level": "INFO",
+                    "module": target,
+                    "message": "Rotating log file",
+                })
+            ),
+        }
+    }
+}
+
+impl Write for &LogFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.state.lock();
+        let day = current_day();
+        if state.day != day || state.size + buf.len() as u64 > self.max_size {
+            if state.size > 0 {
+                self.rotate(&mut state)?;
+            }
+            state.day = day;
+        }
+        state.file.write_all(buf)?;
+        state.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.state.lock().file.flush()
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Days since the unix epoch, which change at UTC midnight.
+fn current_day() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / SECONDS_PER_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    #[test]
+    fn rotates_when_the_file_is_full() {
+        let dir = env::temp_dir().join(format!("steel-log-test-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let config = FileLogConfig {
+            path: dir.join("steel.log").to_string_lossy().into_owned(),
+            max_file_size_mb: 1,
+            max_files: 2,
+        };
+        let writer = LogFileWriter::open(&config, LogFormat::Text).expect("log file should open");
+
+        let mut line = vec![b'a'; 1023];
+        line.push(b'\n');
+        for _ in 0..(4 * 1024) {
+            (&writer).write_all(&line).expect("write should succeed");
+        }
+
+        assert!(dir.join("steel.log").exists());
+        assert!(dir.join("steel.log.1").exists());
+        assert!(dir.join("steel.log.2").exists());
+        assert!(!dir.join("steel.log.3").exists());
+        let rotated = fs::read_to_string(dir.join("steel.log.1")).expect("rotated log");
+        assert!(rotated.len() as u64 <= 1024 * 1024 + 128);
+        assert!(rotated.ends_with("Rotating log file\n"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,227 @@
+//! Log output formatting.
+//!
+//! Logs are written as colored text by default. With `log_format: "json"` in
+//! the config, each event is written as a single line JSON object instead:
+//! `{"timestamp":"...","level":"INFO","module":"steel_core::player","message":"..."}`.
+//! Logs can also be written to a rotated file, see [`file`].
+
+use std::{
+    fmt::{self, Debug},
+    sync::Arc,
+};
+
+use serde_json::{Map, Value};
+use tracing::{
+    Event, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::{
+    Layer,
+    fmt::{
+        self as subscriber_fmt, FmtContext, FormatEvent, FormatFields, MakeWriter,
+        format::Writer,
+        time::{FormatTime, SystemTime},
+    },
+    registry::LookupSpan,
+};
+
+use crate::config::{LogFormat, STEEL_CONFIG};
+use file::LogFileWriter;
+
+/// Log files rotated by size and by day.
+pub mod file;
+
+/// Creates the layer that writes log lines to `writer` in the configured format.
+pub fn fmt_layer<S, W>(writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    layer(writer, true)
+}
+
+/// Creates the layer that writes log lines to the configured log file, if there is one.
+///
+/// # Panics
+/// Panics if the log file cannot be opened.
+#[must_use]
+pub fn file_layer<S>() -> Option<Box<dyn Layer<S> + Send + Sync>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let config = STEEL_CONFIG.load();
+    let file_config = config.log_file.as_ref()?;
+    let writer =
+        LogFileWriter::open(file_config, config.log_format).expect("Failed to open log file");
+    Some(layer(Arc::new(writer), false))
+}
+
+fn layer<S, W>(writer: W, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match STEEL_CONFIG.load().log_format {
+        LogFormat::Text => subscriber_fmt::layer()
+            .with_timer(subscriber_fmt::time::uptime())
+            .with_ansi(ansi)
+            .with_writer(writer)
+            .boxed(),
+        LogFormat::Json => subscriber_fmt::layer()
+            .event_format(JsonFormat)
+            .with_ansi(false)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+/// Formats each event as a single line JSON object.
+pub struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        _ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        // Events from the `log` crate carry their real target in `log.*` fields
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+
+        let mut timestamp = String::new();
+        SystemTime.format_time(&mut Writer::new(&mut timestamp))?;
+
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+
+        let mut entry = Map::new();
+        entry.insert("timestamp".to_owned(), Value::String(timestamp));
+        entry.insert(
+            "level".to_owned(),
+            Value::String(metadata.level().to_string()),
+        );
+        entry.insert(
+            "module".to_owned(),
+            Value::String(metadata.target().to_owned()),
+        );
+        entry.insert("message".to_owned(), Value::String(visitor.message));
+        if !visitor.fields.is_empty() {
+            entry.insert("fields".to_owned(), Value::Object(visitor.fields));
+        }
+
+        writeln!(writer, "{}", Value::Object(entry))
+    }
+}
+
+/// Collects the message and the other fields of an event.
+#[derive(Default)]
+struct JsonVisitor {
+    message: String,
+    fields: Map<String, Value>,
+}
+
+impl JsonVisitor {
+    fn record(&mut self, field: &Field, value: Value) {
+        match field.name() {
+            "message" => {
+                self.message = match value {
+                    Value::String(message) => message,
+                    other => other.to_string(),
+                };
+            }
+            name if name.starts_with("log.") => {}
+            name => {
+                self.fields.insert(name.to_owned(), value);
+            }
+        }
+    }
+}
+
+impl Visit for JsonVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.record(field, Value::String(format!("{value:?}")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    use tracing::subscriber;
+
+    use super::*;
+
+    /// Writes into a shared buffer so the test can read the output back.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().expect("buffer lock").extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writes_one_json_object_per_event() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = subscriber_fmt::fmt()
+            .event_format(JsonFormat)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        subscriber::with_default(subscriber, || {
+            for i in 0..100 {
+                tracing::info!(chunk = i, "generated chunk {i}");
+            }
+        });
+
+        let output = String::from_utf8(buffer.0.lock().expect("buffer lock").clone())
+            .expect("output should be utf-8");
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 100);
+        for (i, line) in lines.into_iter().enumerate() {
+            let entry: Value = serde_json::from_str(line).expect("line should be json");
+            assert!(entry["timestamp"].is_string());
+            assert_eq!(entry["level"], "INFO");
+            assert_eq!(entry["module"], module_path!());
+            assert_eq!(entry["message"], format!("generated chunk {i}"));
+            assert_eq!(entry["fields"]["chunk"], i);
+        }
+    }
+}
@@ -1,11 +1,15 @@
 //! Main entry point for the Steel Minecraft server.
 
-use std::sync::Arc;
+use std::{env, io, process, sync::Arc};
 
 use steel::SteelServer;
+use steel::config;
+use steel::logger::{file_layer, fmt_layer};
+use steel::pregen::{PREGEN_USAGE, parse_pregen_radius, pregenerate};
 #[cfg(feature = "spawn_chunk_display")]
 use steel::spawn_progress::SwitchableWriter;
 use steel::spawn_progress::generate_spawn_chunks;
+use steel_core::server::Server;
 use steel_utils::text::DisplayResolutor;
 use text_components::fmt::set_display_resolutor;
 use tokio::{
@@ -13,7 +17,7 @@ use tokio::{
     signal,
 };
 use tokio_util::task::TaskTracker;
-use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
 
 fn default_env_filter() -> EnvFilter {
     EnvFilter::builder()
@@ -73,18 +77,16 @@ fn init_tracing() {
                 OpenTelemetryLayer::new(tracer)
                     .with_filter(EnvFilter::new("trace,h2=off,hyper=off,tonic=off,tower=off")),
             )
-            .with(
-                fmt::layer()
-                    .with_timer(fmt::time::uptime())
-                    .with_filter(default_env_filter()),
-            )
+            .with(fmt_layer(io::stdout).with_filter(default_env_filter()))
+            .with(file_layer().with_filter(default_env_filter()))
             .init();
     }
 
     #[cfg(not(feature = "jaeger"))]
     {
         tracing_subscriber::registry()
-            .with(fmt::layer().with_timer(fmt::time::uptime()))
+            .with(fmt_layer(io::stdout))
+            .with(file_layer())
             .with(default_env_filter())
             .init();
     }
@@ -108,23 +110,16 @@ fn init_tracing() -> SwitchableWriter {
                 OpenTelemetryLayer::new(tracer)
                     .with_filter(EnvFilter::new("trace,h2=off,hyper=off,tonic=off,tower=off")),
             )
-            .with(
-                fmt::layer()
-                    .with_timer(fmt::time::uptime())
-                    .with_writer(writer.clone())
-                    .with_filter(default_env_filter()),
-            )
+            .with(fmt_layer(writer.clone()).with_filter(default_env_filter()))
+            .with(file_layer().with_filter(default_env_filter()))
             .init();
     }
 
     #[cfg(not(feature = "jaeger"))]
     {
         tracing_subscriber::registry()
-            .with(
-                fmt::layer()
-                    .with_timer(fmt::time::uptime())
-                    .with_writer(writer.clone()),
-            )
+            .with(fmt_layer(writer.clone()))
+            .with(file_layer())
             .with(default_env_filter())
             .init();
     }
@@ -155,31 +150,43 @@ fn main() {
     #[cfg(feature = "dhat-heap")]
     let _profiler = dhat::Profiler::new_heap();
 
+    let pregen_radius = match parse_pregen_radius(env::args().skip(1)) {
+        Ok(radius) => radius,
+        Err(err) => {
+            eprintln!("{err}\n{PREGEN_USAGE}");
+            process::exit(2);
+        }
+    };
+
     let chunk_runtime = Arc::new(Builder::new_multi_thread().enable_all().build().unwrap());
 
     let main_runtime = Builder::new_multi_thread().enable_all().build().unwrap();
 
-    main_runtime.block_on(main_async(chunk_runtime.clone()));
+    main_runtime.block_on(main_async(chunk_runtime.clone(), pregen_radius));
 
     drop(main_runtime);
     drop(chunk_runtime);
 }
 
-async fn main_async(chunk_runtime: Arc<Runtime>) {
+async fn main_async(chunk_runtime: Arc<Runtime>, pregen_radius: Option<i32>) {
+    // Logging reads its format from the config, so load it first
+    config::init_steel_core_config();
+
     #[cfg(feature = "spawn_chunk_display")]
     {
         let writer = init_tracing();
-        run_server(chunk_runtime, &writer).await;
+        run_server(chunk_runtime, pregen_radius, &writer).await;
     }
     #[cfg(not(feature = "spawn_chunk_display"))]
     {
         init_tracing();
-        run_server(chunk_runtime).await;
+        run_server(chunk_runtime, pregen_radius).await;
     }
 }
 
 async fn run_server(
     chunk_runtime: Arc<Runtime>,
+    pregen_radius: Option<i32>,
     #[cfg(feature = "spawn_chunk_display")] writer: &SwitchableWriter,
 ) {
     set_display_resolutor(&DisplayResolutor);
@@ -214,10 +221,16 @@ async fn run_server(
 
     let mut steel = SteelServer::new(chunk_runtime.clone()).await;
 
+    if let Some(radius) = pregen_radius {
+        pregenerate(steel.server.overworld(), radius).await;
+        save_worlds(&steel.server).await;
+        return;
+    }
+
     #[cfg(feature = "spawn_chunk_display")]
-    generate_spawn_chunks(&steel.server, writer).await;
+    generate_spawn_chunks(steel.server.overworld(), writer).await;
     #[cfg(not(feature = "spawn_chunk_display"))]
-    generate_spawn_chunks(&steel.server).await;
+    generate_spawn_chunks(steel.server.overworld()).await;
 
     let server = steel.server.clone();
     let cancel_token = steel.cancel_token.clone();
@@ -229,6 +242,25 @@ async fn run_server(
         }
     });
 
+    #[cfg(unix)]
+    tokio::spawn(async {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(err) => {
+                log::warn!("Failed to listen for SIGHUP, config reloading is disabled: {err}");
+                return;
+            }
+        };
+        while hangup.recv().await.is_some() {
+            log::info!("SIGHUP received, reloading config");
+            if let Err(err) = config::reload() {
+                log::error!("{err}");
+            }
+        }
+    });
+
     let task_tracker = TaskTracker::new();
 
     steel.start(task_tracker.clone()).await;
@@ -238,6 +270,13 @@ async fn run_server(
     task_tracker.close();
     task_tracker.wait().await;
 
+    save_worlds(&server).await;
+
+    log::info!("Server stopped");
+}
+
+/// Waits for outstanding chunk tasks and saves every world to disk.
+async fn save_worlds(server: &Server) {
     for world in &server.worlds {
         world.chunk_map.task_tracker.close();
         world.chunk_map.task_tracker.wait().await;
@@ -250,6 +289,4 @@ async fn run_server(
         world.cleanup(&mut total_saved).await;
     }
     log::info!("Saved {total_saved} chunks");
-
-    log::info!("Server stopped");
 }
@@ -0,0 +1,171 @@
+//! Prometheus metrics endpoint.
+//!
+//! Serves `/metrics` in the Prometheus text exposition format on `metrics_port`.
+//! The endpoint only needs to answer simple scrapes, so it speaks just enough
+//! HTTP/1.1 to do that instead of pulling in a full web framework.
+
+use std::fmt::{Display, Write as _};
+use std::io;
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::sync::Arc;
+use std::time::Duration;
+
+use steel_core::server::Server;
+use steel_core::server::metrics::{CHUNK_GEN_BUCKETS, METRICS};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::select;
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+
+/// Largest request head we bother reading.
+const MAX_REQUEST_SIZE: usize = 4096;
+
+/// How long a client gets to send its request before the connection is
+/// dropped, so idle connections don't pile up.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Runs the metrics HTTP server until `cancel_token` is cancelled.
+pub async fn serve(server: Arc<Server>, port: u16, cancel_token: CancellationToken) {
+    let listener = match TcpListener::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, port)).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            log::error!("Failed to bind metrics endpoint on port {port}: {err}");
+            return;
+        }
+    };
+    log::info!("Metrics endpoint listening on port {port}");
+
+    loop {
+        select! {
+            () = cancel_token.cancelled() => break,
+            accept_result = listener.accept() => {
+                let Ok((stream, _)) = accept_result else {
+                    continue;
+                };
+                let server = server.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = handle_connection(stream, &server).await {
+                        log::debug!("Metrics request failed: {err}");
+                    }
+                });
+            }
+        }
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, server: &Server) -> io::Result<()> {
+    let mut buf = vec![0; MAX_REQUEST_SIZE];
+    let read = timeout(READ_TIMEOUT, stream.read(&mut buf))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "request read timed out"))??;
+    let request = String::from_utf8_lossy(&buf[..read]);
+
+    let mut request_line = request.lines().next().unwrap_or_default().split(' ');
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+
+    let response = if method == "GET" && path == "/metrics" {
+        let body = render(server);
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    } else {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_owned()
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Renders all metrics in the Prometheus text format.
+fn render(server: &Server) -> String {
+    let players = server.get_players().len();
+    let tps = server.tick_rate_manager.read().get_tps();
+    let report = server.profiler.lock().report();
+    let chunks_loaded: usize = server
+        .worlds
+        .iter()
+        .map(|world| world.chunk_map.chunks.len())
+        .sum();
+    let entities_loaded: usize = server.worlds.iter().map(|world| world.entities.len()).sum();
+
+    let mut out = String::new();
+    gauge(
+        &mut out,
+        "steel_players_online",
+        "Players currently online.",
+        players,
+    );
+    gauge(&mut out, "steel_tps", "Ticks per second.", tps);
+    gauge(
+        &mut out,
+        "steel_mspt_avg",
+        "Average milliseconds per tick over the last 100 ticks.",
+        report.mspt_avg,
+    );
+    gauge(
+        &mut out,
+        "steel_mspt_max",
+        "Slowest tick in milliseconds over the last 100 ticks.",
+        report.mspt_max,
+    );
+    gauge(
+        &mut out,
+        "steel_chunks_loaded",
+        "Chunks currently loaded.",
+        chunks_loaded,
+    );
+    // Players count as entities, like in vanilla's entity count
+    gauge(
+        &mut out,
+        "steel_entities_loaded",
+        "Entities currently loaded.",
+        players + entities_loaded,
+    );
+
+    counter(
+        &mut out,
+        "steel_packets_received_total",
+        "Packets received from clients.",
+        METRICS.packets_received(),
+    );
+    counter(
+        &mut out,
+        "steel_packets_sent_total",
+        "Packets sent to clients.",
+        METRICS.packets_sent(),
+    );
+    counter(
+        &mut out,
+        "steel_chunk_generations_total",
+        "Chunks generated.",
+        METRICS.chunk_generations(),
+    );
+
+    let histogram = METRICS.chunk_gen_histogram();
+    let name = "steel_chunk_gen_duration_seconds";
+    let _ = writeln!(out, "# HELP {name} Time taken to generate a chunk.");
+    let _ = writeln!(out, "# TYPE {name} histogram");
+    for (bound, count) in CHUNK_GEN_BUCKETS.iter().zip(histogram.buckets) {
+        let _ = writeln!(out, "{name}_bucket{{le=\"{bound}\"}} {count}");
+    }
+    let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {}", histogram.count);
+    let _ = writeln!(out, "{name}_sum {}", histogram.sum);
+    let _ = writeln!(out, "{name}_count {}", histogram.count);
+
+    out
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: impl Display) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} gauge");
+    let _ = writeln!(out, "{name} {value}");
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: u64) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} counter");
+    let _ = writeln!(out, "{name} {value}");
+}
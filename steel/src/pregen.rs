@@ -0,0 +1,185 @@
+//! Chunk pre-generation around the world spawn.
+//!
+//! Started with `--pregen <radius>`. Chunks are generated tile by tile through the
+//! regular ticket pipeline, so the output is identical to what players would
+//! trigger by exploring. Every chunk is flushed to region files once done.
+
+use std::time::{Duration, Instant};
+
+use steel_core::chunk::chunk_access::ChunkStatus;
+use steel_core::chunk::chunk_ticket_manager::MAX_VIEW_DISTANCE;
+use steel_core::world::World;
+use steel_utils::{ChunkPos, SectionPos};
+use thiserror::Error;
+use tokio::time::sleep;
+
+/// Radius of a single generation tile. Each tile is one ticket, so this bounds
+/// how many chunks are kept loaded at once (17×17 plus dependencies).
+const TILE_RADIUS: i32 = 8;
+
+/// Side length of a tile in chunks.
+const TILE_SIZE: i32 = TILE_RADIUS * 2 + 1;
+
+/// How often progress is printed.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How to use the `--pregen` flag, printed when it is given wrong.
+pub const PREGEN_USAGE: &str = "Usage: steel --pregen <radius>";
+
+/// Errors in the arguments of the `--pregen` flag.
+#[derive(Debug, Error)]
+pub enum PregenArgsError {
+    /// The flag is the last argument.
+    #[error("--pregen expects a chunk radius")]
+    MissingRadius,
+    /// The radius isn't a non-negative number.
+    #[error("--pregen expects a non-negative chunk radius, got '{0}'")]
+    InvalidRadius(String),
+}
+
+/// Parses the `--pregen <radius>` flag from the command line arguments.
+///
+/// Returns `Ok(None)` if the flag isn't present.
+///
+/// # Errors
+/// Returns an error if the flag is present without a valid non-negative radius.
+pub fn parse_pregen_radius(
+    mut args: impl Iterator<Item = String>,
+) -> Result<Option<i32>, PregenArgsError> {
+    if !args.any(|arg| arg == "--pregen") {
+        return Ok(None);
+    }
+    let radius = args.next().ok_or(PregenArgsError::MissingRadius)?;
+    match radius.parse::<i32>() {
+        Ok(parsed) if parsed >= 0 => Ok(Some(parsed)),
+        _ => Err(PregenArgsError::InvalidRadius(radius)),
+    }
+}
+
+/// Generates every chunk of `world` within a square `radius` (in chunks)
+/// around its spawn, then saves them to disk.
+pub async fn pregenerate(world: &World, radius: i32) {
+    let spawn_pos = world.level_data.read().data().spawn_pos();
+    let center = ChunkPos::new(
+        SectionPos::block_to_section_coord(spawn_pos.0.x),
+        SectionPos::block_to_section_coord(spawn_pos.0.z),
+    );
+
+    let side = (radius * 2 + 1) as usize;
+    let total = side * side;
+    log::info!(
+        "Pre-generating {total} chunks of {} around chunk ({}, {})",
+        world.dimension.key,
+        center.0.x,
+        center.0.y,
+    );
+
+    let start = Instant::now();
+    let mut last_report = Instant::now();
+    let mut completed = 0;
+    let mut tick_count: u64 = 1;
+
+    let tile_level = MAX_VIEW_DISTANCE - TILE_RADIUS as u8;
+
+    let mut tile_z = -radius;
+    while tile_z <= radius {
+        let mut tile_x = -radius;
+        while tile_x <= radius {
+            let min = ChunkPos::new(center.0.x + tile_x, center.0.y + tile_z);
+            let max = ChunkPos::new(
+                (min.0.x + TILE_SIZE - 1).min(center.0.x + radius),
+                (min.0.y + TILE_SIZE - 1).min(center.0.y + radius),
+            );
+            let tile_center = ChunkPos::new(min.0.x + TILE_RADIUS, min.0.y + TILE_RADIUS);
+            let tile_total = ((max.0.x - min.0.x + 1) * (max.0.y - min.0.y + 1)) as usize;
+
+            world
+                .chunk_map
+                .chunk_tickets
+                .lock()
+                .add_ticket(tile_center, tile_level);
+
+            loop {
+                world.chunk_map.tick_b(tick_count, 0, false);
+                tick_count += 1;
+
+                let tile_completed = count_full_chunks(world, min, max);
+                if last_report.elapsed() >= PROGRESS_INTERVAL {
+                    println!("({} / {total})", completed + tile_completed);
+                    last_report = Instant::now();
+                }
+
+                if tile_completed == tile_total {
+                    completed += tile_total;
+                    break;
+                }
+
+                sleep(Duration::from_millis(10)).await;
+            }
+
+            // Dropping the ticket lets the chunk map unload and save the tile
+            world
+                .chunk_map
+                .chunk_tickets
+                .lock()
+                .remove_ticket(tile_center, tile_level);
+
+            tile_x += TILE_SIZE;
+        }
+        tile_z += TILE_SIZE;
+    }
+
+    // Pump once more so the last tile gets queued for unloading
+    world.chunk_map.tick_b(tick_count, 0, false);
+
+    println!("({completed} / {total})");
+    log::info!(
+        "Pre-generated {completed} chunks in {:.2}s",
+        start.elapsed().as_secs_f64()
+    );
+}
+
+/// Counts the chunks between `min` and `max` (inclusive) that reached Full status.
+fn count_full_chunks(world: &World, min: ChunkPos, max: ChunkPos) -> usize {
+    let mut completed = 0;
+    for z in min.0.y..=max.0.y {
+        for x in min.0.x..=max.0.x {
+            let status = world
+                .chunk_map
+                .chunks
+                .read_sync(&ChunkPos::new(x, z), |_, holder| holder.persisted_status())
+                .flatten();
+            if status == Some(ChunkStatus::Full) {
+                completed += 1;
+            }
+        }
+    }
+    completed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<i32>, PregenArgsError> {
+        parse_pregen_radius(args.iter().map(|arg| (*arg).to_owned()))
+    }
+
+    #[test]
+    fn bad_radius_is_an_error() {
+        assert_eq!(parse(&[]).ok(), Some(None));
+        assert_eq!(parse(&["--pregen", "12"]).ok(), Some(Some(12)));
+        assert!(matches!(
+            parse(&["--pregen"]),
+            Err(PregenArgsError::MissingRadius)
+        ));
+        assert!(matches!(
+            parse(&["--pregen", "-3"]),
+            Err(PregenArgsError::InvalidRadius(radius)) if radius == "-3"
+        ));
+        assert!(matches!(
+            parse(&["--pregen", "far"]),
+            Err(PregenArgsError::InvalidRadius(_))
+        ));
+    }
+}
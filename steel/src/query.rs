@@ -0,0 +1,260 @@
+//! GameSpy4 (GS4) query server.
+//!
+//! Answers the UDP query protocol that server lists and monitoring tools use
+//! to read the MOTD, player count and player names without joining.
+//! Listens on `server_port + 1` when `enable_query` is set.
+
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rustc_hash::FxHashMap;
+use steel_core::server::Server;
+use tokio::net::UdpSocket;
+use tokio::select;
+use tokio_util::sync::CancellationToken;
+
+use crate::{MC_VERSION, STEEL_CONFIG};
+
+const MAGIC: [u8; 2] = [0xFE, 0xFD];
+const TYPE_HANDSHAKE: u8 = 9;
+const TYPE_STAT: u8 = 0;
+
+/// Session ids only use the low nibble of each byte.
+const SESSION_ID_MASK: i32 = 0x0F0F_0F0F;
+
+/// How long a challenge token stays valid.
+const CHALLENGE_LIFETIME: Duration = Duration::from_secs(30);
+/// How many challenge tokens are kept at once. Handshakes beyond this evict
+/// the oldest token, so spoofed handshakes can't grow the map without bound.
+const MAX_CHALLENGES: usize = 4096;
+
+/// Fixed padding that starts the key/value section of a full stat response.
+const FULL_STAT_HEADER: &[u8] = b"splitnum\0\x80\0";
+/// Padding that starts the player section of a full stat response.
+const PLAYER_SECTION_HEADER: &[u8] = b"\x01player_\0\0";
+
+/// Answers GS4 query requests.
+pub struct QueryServer {
+    server: Arc<Server>,
+    /// Challenge tokens handed out per client address.
+    challenges: Challenges,
+}
+
+impl QueryServer {
+    /// Creates a new query server.
+    #[must_use]
+    pub fn new(server: Arc<Server>) -> Self {
+        Self {
+            server,
+            challenges: Challenges::default(),
+        }
+    }
+
+    /// Binds to `server_port + 1` and answers requests until `cancel_token` is cancelled.
+    pub async fn run(mut self, cancel_token: CancellationToken) {
+        let port = STEEL_CONFIG.load().server_port.wrapping_add(1);
+        let socket = match UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, port)).await {
+            Ok(socket) => socket,
+            Err(err) => {
+                log::error!("Failed to bind query on port {port}: {err}");
+                return;
+            }
+        };
+        log::info!("Query listening on port {port}");
+
+        let mut buf = [0; 1460];
+        loop {
+            select! {
+                () = cancel_token.cancelled() => break,
+                result = socket.recv_from(&mut buf) => {
+                    let Ok((len, address)) = result else {
+                        continue;
+                    };
+                    if let Some(response) = self.handle_packet(&buf[..len], address, &socket)
+                        && let Err(err) = socket.send_to(&response, address).await
+                    {
+                        log::debug!("Failed to answer query from {address}: {err}");
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_packet(
+        &mut self,
+        packet: &[u8],
+        address: SocketAddr,
+        socket: &UdpSocket,
+    ) -> Option<Vec<u8>> {
+        if packet.len() < 7 || packet[..2] != MAGIC {
+            return None;
+        }
+
+        let kind = packet[2];
+        let session_id = i32::from_be_bytes(packet[3..7].try_into().ok()?) & SESSION_ID_MASK;
+
+        let mut response = vec![kind];
+        response.extend_from_slice(&session_id.to_be_bytes());
+
+        match kind {
+            TYPE_HANDSHAKE => {
+                let token = rand::random::<i32>() & 0x7FFF_FFFF;
+                self.challenges.issue(address, token, Instant::now());
+                push_str(&mut response, &token.to_string());
+            }
+            TYPE_STAT if packet.len() >= 11 => {
+                let token = i32::from_be_bytes(packet[7..11].try_into().ok()?);
+                if !self.challenges.is_valid(address, token, Instant::now()) {
+                    return None;
+                }
+                let host_ip = socket.local_addr().map_or_else(
+                    |_| Ipv4Addr::UNSPECIFIED.to_string(),
+                    |a| a.ip().to_string(),
+                );
+
+                // Full stat requests carry 4 bytes of padding after the token
+                if packet.len() >= 15 {
+                    self.write_full_stat(&mut response, &host_ip);
+                } else {
+                    self.write_basic_stat(&mut response, &host_ip);
+                }
+            }
+            _ => return None,
+        }
+
+        Some(response)
+    }
+
+    fn player_count(&self) -> usize {
+        self.server
+            .worlds
+            .iter()
+            .map(|world| world.players.len())
+            .sum()
+    }
+
+    fn write_basic_stat(&self, out: &mut Vec<u8>, host_ip: &str) {
+        let config = STEEL_CONFIG.load();
+        push_str(out, &config.motd);
+        push_str(out, "SMP");
+        push_str(out, "world");
+        push_str(out, &self.player_count().to_string());
+        push_str(out, &config.max_players.to_string());
+        out.extend_from_slice(&config.server_port.to_le_bytes());
+        push_str(out, host_ip);
+    }
+
+    fn write_full_stat(&self, out: &mut Vec<u8>, host_ip: &str) {
+        out.extend_from_slice(FULL_STAT_HEADER);
+
+        let config = STEEL_CONFIG.load();
+        let num_players = self.player_count().to_string();
+        let max_players = config.max_players.to_string();
+        let host_port = config.server_port.to_string();
+        for (key, value) in [
+            ("hostname", config.motd.as_str()),
+            ("gametype", "SMP"),
+            ("game_id", "MINECRAFT"),
+            ("version", MC_VERSION),
+            ("plugins", ""),
+            ("map", "world"),
+            ("numplayers", num_players.as_str()),
+            ("maxplayers", max_players.as_str()),
+            ("hostport", host_port.as_str()),
+            ("hostip", host_ip),
+        ] {
+            push_str(out, key);
+            push_str(out, value);
+        }
+        out.push(0);
+
+        out.extend_from_slice(PLAYER_SECTION_HEADER);
+        for player in self.server.get_players() {
+            push_str(out, &player.gameprofile.name);
+        }
+        out.push(0);
+    }
+}
+
+/// The challenge tokens handed out per client address, with when they were
+/// issued.
+#[derive(Default)]
+struct Challenges {
+    tokens: FxHashMap<SocketAddr, (i32, Instant)>,
+}
+
+impl Challenges {
+    /// Hands `token` out to `address`, replacing its previous one.
+    fn issue(&mut self, address: SocketAddr, token: i32, now: Instant) {
+        if self.tokens.len() >= MAX_CHALLENGES && !self.tokens.contains_key(&address) {
+            self.prune(now);
+            if self.tokens.len() >= MAX_CHALLENGES
+                && let Some(oldest) = self
+                    .tokens
+                    .iter()
+                    .min_by_key(|(_, (_, issued))| *issued)
+                    .map(|(address, _)| *address)
+            {
+                self.tokens.remove(&oldest);
+            }
+        }
+        self.tokens.insert(address, (token, now));
+    }
+
+    /// Whether `token` is the unexpired token handed out to `address`.
+    fn is_valid(&mut self, address: SocketAddr, token: i32, now: Instant) -> bool {
+        self.prune(now);
+        self.tokens
+            .get(&address)
+            .is_some_and(|(expected, _)| *expected == token)
+    }
+
+    /// Drops the expired tokens.
+    fn prune(&mut self, now: Instant) {
+        self.tokens
+            .retain(|_, (_, issued)| now.duration_since(*issued) < CHALLENGE_LIFETIME);
+    }
+}
+
+/// Writes a null terminated string.
+fn push_str(out: &mut Vec<u8>, value: &str) {
+    out.extend_from_slice(value.as_bytes());
+    out.push(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(port: u16) -> SocketAddr {
+        SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, port))
+    }
+
+    #[test]
+    fn challenges_expire() {
+        let mut challenges = Challenges::default();
+        let issued = Instant::now();
+        challenges.issue(address(1), 7, issued);
+
+        assert!(challenges.is_valid(address(1), 7, issued));
+        assert!(!challenges.is_valid(address(1), 8, issued));
+        assert!(!challenges.is_valid(address(1), 7, issued + CHALLENGE_LIFETIME));
+        assert!(challenges.tokens.is_empty());
+    }
+
+    #[test]
+    fn challenges_are_capped() {
+        let mut challenges = Challenges::default();
+        let start = Instant::now();
+        let newest = u16::try_from(MAX_CHALLENGES).expect("the cap fits in a port");
+        for port in 0..=newest {
+            challenges.issue(address(port), 1, start + Duration::from_millis(port.into()));
+        }
+
+        assert_eq!(challenges.tokens.len(), MAX_CHALLENGES);
+        // The oldest token made room for the newest
+        assert!(!challenges.tokens.contains_key(&address(0)));
+        assert!(challenges.tokens.contains_key(&address(newest)));
+    }
+}
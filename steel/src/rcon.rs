@@ -0,0 +1,238 @@
+//! Remote console (RCON) server.
+//!
+//! Implements the Source RCON protocol used by vanilla: a client authenticates
+//! with `SERVERDATA_AUTH` and can then run commands with `SERVERDATA_EXECCOMMAND`.
+//! Command output is returned as one or more `SERVERDATA_RESPONSE_VALUE` packets.
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rustc_hash::FxHashMap;
+use steel_core::command::sender::CommandSender;
+use steel_core::server::Server;
+use steel_utils::locks::SyncMutex;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::select;
+use tokio_util::sync::CancellationToken;
+
+const SERVERDATA_AUTH: i32 = 3;
+const SERVERDATA_AUTH_RESPONSE: i32 = 2;
+const SERVERDATA_EXECCOMMAND: i32 = 2;
+const SERVERDATA_RESPONSE_VALUE: i32 = 0;
+
+/// Request id sent back when authentication fails.
+const AUTH_FAILED_ID: i32 = -1;
+
+/// Largest packet a client may send (matches vanilla).
+const MAX_INCOMING_PACKET: i32 = 1460;
+/// Largest body sent in a single response packet; longer output is split.
+const MAX_RESPONSE_BODY: usize = 4096;
+
+/// Failed logins allowed from one address before it is locked out.
+const MAX_FAILED_AUTH: u32 = 3;
+/// How long an address stays locked out after too many failed logins.
+const AUTH_LOCKOUT: Duration = Duration::from_secs(60);
+
+struct RconPacket {
+    id: i32,
+    kind: i32,
+    body: String,
+}
+
+/// Accepts RCON connections and executes their commands on the server.
+pub struct RconServer {
+    server: Arc<Server>,
+    password: String,
+    /// Failed login count and time of the last failure, per address.
+    failed_auth: SyncMutex<FxHashMap<IpAddr, (u32, Instant)>>,
+}
+
+impl RconServer {
+    /// Creates a new RCON server that authenticates clients with `password`.
+    #[must_use]
+    pub fn new(server: Arc<Server>, password: String) -> Self {
+        Self {
+            server,
+            password,
+            failed_auth: SyncMutex::new(FxHashMap::default()),
+        }
+    }
+
+    /// Listens on `port` until `cancel_token` is cancelled.
+    pub async fn run(self: Arc<Self>, port: u16, cancel_token: CancellationToken) {
+        let listener = match TcpListener::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, port)).await
+        {
+            Ok(listener) => listener,
+            Err(err) => {
+                log::error!("Failed to bind RCON on port {port}: {err}");
+                return;
+            }
+        };
+        log::info!("RCON listening on port {port}");
+
+        loop {
+            select! {
+                () = cancel_token.cancelled() => break,
+                accept_result = listener.accept() => {
+                    let Ok((stream, address)) = accept_result else {
+                        continue;
+                    };
+                    let rcon = self.clone();
+                    let cancel_token = cancel_token.clone();
+                    tokio::spawn(async move {
+                        select! {
+                            () = cancel_token.cancelled() => {}
+                            result = rcon.handle_client(stream, address) => {
+                                if let Err(err) = result {
+                                    log::debug!("RCON client {address} disconnected: {err}");
+                                }
+                            }
+                        }
+                    });
+                }
+            }
+        }
+    }
+
+    async fn handle_client(&self, stream: TcpStream, address: SocketAddr) -> io::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+        let mut authenticated = false;
+
+        loop {
+            let packet = read_packet(&mut reader).await?;
+
+            match packet.kind {
+                SERVERDATA_AUTH => {
+                    if self.is_locked_out(address.ip()) {
+                        write_packet(&mut writer, AUTH_FAILED_ID, SERVERDATA_AUTH_RESPONSE, "")
+                            .await?;
+                        return Ok(());
+                    }
+
+                    if packet.body == self.password {
+                        authenticated = true;
+                        self.failed_auth.lock().remove(&address.ip());
+                        log::info!("RCON client {address} authenticated");
+                        write_packet(&mut writer, packet.id, SERVERDATA_AUTH_RESPONSE, "").await?;
+                    } else {
+                        authenticated = false;
+                        self.record_failed_auth(address.ip());
+                        log::warn!("RCON client {address} failed to authenticate");
+                        write_packet(&mut writer, AUTH_FAILED_ID, SERVERDATA_AUTH_RESPONSE, "")
+                            .await?;
+                    }
+                }
+                SERVERDATA_EXECCOMMAND if authenticated => {
+                    let output = self.execute(packet.body);
+                    write_response(&mut writer, packet.id, &output).await?;
+                }
+                // Clients send an empty response packet after a command and wait for it to be
+                // echoed back, which tells them a fragmented response is complete.
+                SERVERDATA_RESPONSE_VALUE if authenticated => {
+                    write_packet(&mut writer, packet.id, SERVERDATA_RESPONSE_VALUE, "").await?;
+                }
+                _ => {
+                    write_packet(&mut writer, AUTH_FAILED_ID, SERVERDATA_AUTH_RESPONSE, "").await?;
+                }
+            }
+        }
+    }
+
+    /// Runs a command and returns everything it sent back to the sender.
+    fn execute(&self, command: String) -> String {
+        log::info!("RCON issued command: /{command}");
+        let output = Arc::new(SyncMutex::new(String::new()));
+        self.server.command_dispatcher.read().handle_command(
+            CommandSender::Rcon(output.clone()),
+            command,
+            &self.server,
+        );
+        output.lock().clone()
+    }
+
+    fn is_locked_out(&self, ip: IpAddr) -> bool {
+        let mut failed_auth = self.failed_auth.lock();
+        match failed_auth.get(&ip).copied() {
+            Some((_, last_failure)) if last_failure.elapsed() >= AUTH_LOCKOUT => {
+                failed_auth.remove(&ip);
+                false
+            }
+            Some((attempts, _)) => attempts >= MAX_FAILED_AUTH,
+            None => false,
+        }
+    }
+
+    fn record_failed_auth(&self, ip: IpAddr) {
+        let mut failed_auth = self.failed_auth.lock();
+        let entry = failed_auth.entry(ip).or_insert((0, Instant::now()));
+        entry.0 += 1;
+        entry.1 = Instant::now();
+    }
+}
+
+async fn read_packet(reader: &mut (impl AsyncReadExt + Unpin)) -> io::Result<RconPacket> {
+    let length = reader.read_i32_le().await?;
+    if !(10..=MAX_INCOMING_PACKET).contains(&length) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid RCON packet length {length}"),
+        ));
+    }
+
+    let id = reader.read_i32_le().await?;
+    let kind = reader.read_i32_le().await?;
+
+    // Body is null terminated and followed by an empty null terminated string
+    let mut body = vec![0; length as usize - 8];
+    reader.read_exact(&mut body).await?;
+    let end = body.iter().position(|&b| b == 0).unwrap_or(body.len());
+    body.truncate(end);
+
+    Ok(RconPacket {
+        id,
+        kind,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+async fn write_packet(
+    writer: &mut (impl AsyncWriteExt + Unpin),
+    id: i32,
+    kind: i32,
+    body: &str,
+) -> io::Result<()> {
+    let body = body.as_bytes();
+    let mut buf = Vec::with_capacity(body.len() + 14);
+    buf.extend_from_slice(&(body.len() as i32 + 10).to_le_bytes());
+    buf.extend_from_slice(&id.to_le_bytes());
+    buf.extend_from_slice(&kind.to_le_bytes());
+    buf.extend_from_slice(body);
+    buf.extend_from_slice(&[0, 0]);
+    writer.write_all(&buf).await
+}
+
+/// Writes command output, splitting it over several packets when it exceeds
+/// [`MAX_RESPONSE_BODY`] bytes.
+async fn write_response(
+    writer: &mut (impl AsyncWriteExt + Unpin),
+    id: i32,
+    output: &str,
+) -> io::Result<()> {
+    let mut remaining = output;
+    loop {
+        let mut split = remaining.len().min(MAX_RESPONSE_BODY);
+        while !remaining.is_char_boundary(split) {
+            split -= 1;
+        }
+        let (chunk, rest) = remaining.split_at(split);
+        write_packet(writer, id, SERVERDATA_RESPONSE_VALUE, chunk).await?;
+        if rest.is_empty() {
+            return Ok(());
+        }
+        remaining = rest;
+    }
+}
@@ -5,7 +5,6 @@
 //! enabled, a colored ANSI grid shows real-time progress including the
 //! surrounding dependency rings.
 
-use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use steel_core::chunk::chunk_pyramid::GENERATION_PYRAMID;
@@ -13,7 +12,6 @@ use tokio::time::sleep;
 
 use steel_core::chunk::chunk_access::ChunkStatus;
 use steel_core::chunk::chunk_ticket_manager::MAX_VIEW_DISTANCE;
-use steel_core::server::Server;
 use steel_core::world::World;
 use steel_utils::{ChunkPos, SectionPos};
 
@@ -47,18 +45,17 @@ pub const DISPLAY_DIAMETER: usize = (DISPLAY_RADIUS * 2 + 1) as usize;
 /// Number of chunks that must reach Full status (7×7).
 const TOTAL_SPAWN_CHUNKS: usize = ((SPAWN_RADIUS * 2 + 1) * (SPAWN_RADIUS * 2 + 1)) as usize;
 
-/// Generates spawn chunks, optionally displaying progress in the terminal.
+/// Generates the spawn chunks of `world`, optionally displaying progress in
+/// the terminal. Like vanilla, only the overworld is prepared at startup.
 ///
 /// Adds a ticket at the world spawn position so that a 7×7 area of chunks
 /// reaches `Full` status. The generation system is pumped in a loop until
 /// completion. With the `spawn_chunk_display` feature, progress is shown as
 /// a colored terminal grid that includes the surrounding dependency chunks.
 pub async fn generate_spawn_chunks(
-    server: &Arc<Server>,
+    world: &World,
     #[cfg(feature = "spawn_chunk_display")] writer: &SwitchableWriter,
 ) {
-    let world = &server.worlds[0];
-
     let spawn_pos = world.level_data.read().data().spawn_pos();
     let center_chunk = ChunkPos::new(
         SectionPos::block_to_section_coord(spawn_pos.0.x),
@@ -66,7 +63,8 @@ pub async fn generate_spawn_chunks(
     );
 
     log::info!(
-        "Preparing spawn area: {TOTAL_SPAWN_CHUNKS} chunks around chunk ({}, {})",
+        "Preparing spawn area of {}: {TOTAL_SPAWN_CHUNKS} chunks around chunk ({}, {})",
+        world.dimension.key,
         center_chunk.0.x,
         center_chunk.0.y,
     );
@@ -98,7 +96,8 @@ pub async fn generate_spawn_chunks(
     #[cfg(feature = "slow_chunk_gen")]
     SLOW_CHUNK_GEN.store(false, Ordering::Relaxed);
 
-    // Remove the ticket now that generation is complete (spawn chunks no longer stay loaded)
+    // Remove the ticket now that generation is complete. The spawn chunks stay
+    // loaded through `World::spawn_chunks` when `spawn_chunk_radius` isn't 0.
     {
         let mut tickets = world.chunk_map.chunk_tickets.lock();
         tickets.remove_ticket(center_chunk, ticket_level);
@@ -119,9 +118,12 @@ async fn generate_with_display(
 ) -> Duration {
     use std::io::{self, IsTerminal};
 
+    use crate::config::{LogFormat, STEEL_CONFIG};
     use crate::spawn_progress::{DISPLAY_DIAMETER, DISPLAY_RADIUS};
 
-    let use_display = io::stderr().is_terminal();
+    // JSON logs go to a file or pipe, so don't draw the grid over them
+    let use_display =
+        io::stderr().is_terminal() && STEEL_CONFIG.load().log_format == LogFormat::Text;
     if use_display {
         writer.activate();
     }